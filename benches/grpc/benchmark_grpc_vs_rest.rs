@@ -319,6 +319,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         storage_type: None,
         graph: None, // Graph disabled for benchmarks
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
     rest_store
         .create_collection(rest_collection, rest_config)
@@ -341,6 +348,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         storage_type: None,
         graph: None, // Graph disabled for benchmarks
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
     grpc_store
         .create_collection(grpc_collection, grpc_config)
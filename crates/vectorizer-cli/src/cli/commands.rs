@@ -386,6 +386,13 @@ pub async fn handle_collection_command(
                 sharding: None,
                 graph: None,
                 encryption: None,
+                id_policy: None,
+                quota: None,
+                scoring: None,
+                tenant_partition: None,
+                dedup: None,
+                chunking: None,
+                full_text: None,
             };
 
             store.create_collection(&name, config)?;
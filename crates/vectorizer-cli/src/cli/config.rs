@@ -267,6 +267,7 @@ impl ConfigManager {
                 enabled: config_file.auth.enabled,
                 cookies: vectorizer::auth::CookieConfig::default(),
                 dev_mode_skip_loopback: false,
+                oidc: None,
             },
             database: super::DatabaseConfig {
                 persistence_path: PathBuf::from(config_file.database.persistence_path),
@@ -57,10 +57,15 @@ impl VectorizerError {
             VectorizerError::AuthorizationError(_) => ErrorKind::Forbidden,
 
             // Conflict
-            VectorizerError::CollectionAlreadyExists(_) => ErrorKind::Conflict,
+            VectorizerError::CollectionAlreadyExists(_)
+            | VectorizerError::QuotaExceeded { .. }
+            | VectorizerError::VersionConflict { .. }
+            | VectorizerError::JobCancelled(_) => ErrorKind::Conflict,
 
-            // Rate limit
-            VectorizerError::RateLimitExceeded { .. } => ErrorKind::TooManyRequests,
+            // Rate limit, and tenant quotas, which HiveHub clients have
+            // always seen as 429
+            VectorizerError::RateLimitExceeded { .. }
+            | VectorizerError::TenantQuotaExceeded { .. } => ErrorKind::TooManyRequests,
 
             // Bad request — invalid input, dimension, config, encryption, or encoding failures
             // that originate from the caller's payload.
@@ -80,8 +85,17 @@ impl VectorizerError {
             | VectorizerError::JsonError(_)
             | VectorizerError::YamlError(_)
             | VectorizerError::TransmutationError(_)
+            | VectorizerError::OcrError(_)
+            | VectorizerError::TranscriptionError(_)
+            | VectorizerError::PluginRejected { .. }
+            | VectorizerError::InvalidVectorId { .. }
             | VectorizerError::UmicpError(_) => ErrorKind::BadRequest,
 
+            // Unavailable
+            VectorizerError::TaskPaused(_) | VectorizerError::WritesFenced(_) => {
+                ErrorKind::Unavailable
+            }
+
             // Internal — everything else (I/O, persistence, indexing, ML, catch-all).
             VectorizerError::IoError(_)
             | VectorizerError::Io(_)
@@ -136,8 +150,19 @@ impl VectorizerError {
             VectorizerError::Other(_) => "other_error",
             VectorizerError::UmicpError(_) => "umicp_error",
             VectorizerError::TransmutationError(_) => "transmutation_error",
+            VectorizerError::OcrError(_) => "ocr_error",
+            VectorizerError::TranscriptionError(_) => "transcription_error",
             VectorizerError::Storage(_) => "storage_error",
             VectorizerError::Unimplemented(_) => "unimplemented",
+            VectorizerError::PluginRejected { .. } => "plugin_rejected",
+            VectorizerError::TaskPaused(_) => "task_paused",
+            VectorizerError::InvalidVectorId { .. } => "invalid_vector_id",
+            VectorizerError::QuotaExceeded { .. } => "quota_exceeded",
+            // The error type tenant quota refusals have always had
+            VectorizerError::TenantQuotaExceeded { .. } => "QUOTA_EXCEEDED",
+            VectorizerError::VersionConflict { .. } => "version_conflict",
+            VectorizerError::WritesFenced(_) => "writes_fenced",
+            VectorizerError::JobCancelled(_) => "job_cancelled",
             #[cfg(feature = "candle-models")]
             VectorizerError::CandleError(_) => "candle_error",
         }
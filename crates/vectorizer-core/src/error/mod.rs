@@ -179,6 +179,14 @@ pub enum VectorizerError {
     #[error("Transmutation error: {0}")]
     TransmutationError(String),
 
+    /// OCR text extraction error
+    #[error("OCR error: {0}")]
+    OcrError(String),
+
+    /// Audio transcription error
+    #[error("Transcription error: {0}")]
+    TranscriptionError(String),
+
     /// Storage error (for .vecdb operations)
     #[error("Storage error: {0}")]
     Storage(String),
@@ -202,6 +210,73 @@ pub enum VectorizerError {
     /// Operation not implemented by the remote peer (cluster RPC compat).
     #[error("Unimplemented: {0}")]
     Unimplemented(String),
+
+    /// A write-path plugin refused the vector.
+    #[error("Write rejected by plugin '{plugin}': {reason}")]
+    PluginRejected {
+        /// Name of the plugin that refused the write.
+        plugin: String,
+        /// Reason the plugin gave.
+        reason: String,
+    },
+
+    /// The background task that would run the operation is paused.
+    #[error("Background task '{0}' is paused")]
+    TaskPaused(String),
+
+    /// A vector ID breaks the collection's ID policy.
+    #[error("Invalid vector ID '{id}': {reason}")]
+    InvalidVectorId {
+        /// The rejected ID.
+        id: String,
+        /// Which rule it breaks.
+        reason: String,
+    },
+
+    /// An insert would take the collection past its quota.
+    #[error("Collection '{collection}' is at its quota of {max_vectors} vectors")]
+    QuotaExceeded {
+        /// Collection that is full.
+        collection: String,
+        /// The collection's `quota.max_vectors`.
+        max_vectors: usize,
+    },
+
+    /// A write would take a tenant past one of its quotas.
+    #[error(
+        "Tenant '{tenant_id}' {quota} quota exceeded: {used} used, {requested} requested, limit {limit}"
+    )]
+    TenantQuotaExceeded {
+        /// Tenant that is full.
+        tenant_id: String,
+        /// Which quota (`vector_count`, `storage` or `collection_count`).
+        quota: String,
+        /// The tenant's limit for that quota.
+        limit: u64,
+        /// Current usage.
+        used: u64,
+        /// What the rejected write needed.
+        requested: u64,
+    },
+
+    /// A compare-and-swap write found the key at another version.
+    #[error("Version conflict on '{key}': expected version {expected}, found {actual}")]
+    VersionConflict {
+        /// Key the write was conditioned on.
+        key: String,
+        /// Version the caller expected (0 = absent).
+        expected: u64,
+        /// Version actually stored (0 = absent).
+        actual: u64,
+    },
+
+    /// Writes are held while a cluster snapshot takes its consistent cut.
+    #[error("Writes are paused for cluster snapshot '{0}'")]
+    WritesFenced(String),
+
+    /// A job was cancelled through `DELETE /jobs/{id}` before it finished.
+    #[error("Job '{0}' was cancelled")]
+    JobCancelled(String),
 }
 
 /// Result type alias for Vectorizer operations
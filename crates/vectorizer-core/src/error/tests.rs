@@ -167,6 +167,73 @@ fn rate_limit_is_429() {
     );
 }
 
+#[test]
+fn paused_task_is_503() {
+    let err = VectorizerError::TaskPaused("reindex".into());
+    assert_eq!(err.kind(), ErrorKind::Unavailable);
+    assert_eq!(err.code(), "task_paused");
+}
+
+#[test]
+fn invalid_vector_id_is_400() {
+    let err = VectorizerError::InvalidVectorId {
+        id: "a b".into(),
+        reason: "contains ' '".into(),
+    };
+    assert_eq!(err.kind(), ErrorKind::BadRequest);
+    assert_eq!(err.code(), "invalid_vector_id");
+}
+
+#[test]
+fn quota_exceeded_is_409() {
+    let err = VectorizerError::QuotaExceeded {
+        collection: "memory".into(),
+        max_vectors: 1000,
+    };
+    assert_eq!(err.kind(), ErrorKind::Conflict);
+    assert_eq!(err.code(), "quota_exceeded");
+}
+
+#[test]
+fn tenant_quota_exceeded_is_429() {
+    let err = |quota: &str| VectorizerError::TenantQuotaExceeded {
+        tenant_id: "acme".into(),
+        quota: quota.into(),
+        limit: 10,
+        used: 10,
+        requested: 1,
+    };
+    for quota in ["collection_count", "vector_count", "storage"] {
+        assert_eq!(err(quota).kind(), ErrorKind::TooManyRequests);
+        assert_eq!(err(quota).code(), "QUOTA_EXCEEDED");
+    }
+}
+
+#[test]
+fn version_conflict_is_409() {
+    let err = VectorizerError::VersionConflict {
+        key: "cursor".into(),
+        expected: 3,
+        actual: 5,
+    };
+    assert_eq!(err.kind(), ErrorKind::Conflict);
+    assert_eq!(err.code(), "version_conflict");
+}
+
+#[test]
+fn fenced_writes_are_503() {
+    let err = VectorizerError::WritesFenced("snap-1".into());
+    assert_eq!(err.kind(), ErrorKind::Unavailable);
+    assert_eq!(err.code(), "writes_fenced");
+}
+
+#[test]
+fn cancelled_job_is_409() {
+    let err = VectorizerError::JobCancelled("4f0c".into());
+    assert_eq!(err.kind(), ErrorKind::Conflict);
+    assert_eq!(err.code(), "job_cancelled");
+}
+
 #[test]
 fn storage_and_internal_are_500() {
     assert_eq!(
@@ -19,7 +19,8 @@
 
 /// Abstraction over metrics emission for the four call sites cataloged
 /// in the 2026-07-11 improvement analysis (§1.1): the TTL reaper, the
-/// query cache, HiveHub quota checks, and per-API-key usage tracking.
+/// query cache, HiveHub quota checks, and per-API-key usage tracking —
+/// plus the background index optimizer and backup scheduler added since.
 ///
 /// Implementations MUST be cheap to call on the hot path: no I/O, and
 /// no locking beyond whatever the underlying metrics backend already
@@ -41,11 +42,33 @@ pub trait MetricsSink: Send + Sync + std::fmt::Debug {
     /// `collection`.
     fn ttl_vectors_expired(&self, _collection: &str, _count: f64) {}
 
+    /// Record the stale-node share of `collection`'s HNSW graph, as
+    /// measured by an index optimizer sweep.
+    fn index_fragmentation(&self, _collection: &str, _ratio: f64) {}
+
+    /// Record one HNSW compaction of `collection`: whether it succeeded,
+    /// how long it took, and how many stale nodes it reclaimed.
+    fn index_compaction(&self, _collection: &str, _ok: bool, _seconds: f64, _reclaimed_nodes: f64) {
+    }
+
+    /// Record one run of the scheduled backup job `job`: whether it
+    /// succeeded, how long it took, and how many old snapshots its
+    /// retention policy pruned.
+    fn scheduled_backup(&self, _job: &str, _ok: bool, _seconds: f64, _pruned: f64) {}
+
     /// Record a cache lookup outcome for `cache_type` (e.g. `"query"`).
     /// `hit` is `true` for a cache hit, `false` for a miss (including
     /// an expired entry treated as a miss).
     fn cache_request(&self, _cache_type: &str, _hit: bool) {}
 
+    /// Record a query cache lookup for a search on `collection`, so the
+    /// hit rate can be followed per collection.
+    fn query_cache_request(&self, _collection: &str, _hit: bool) {}
+
+    /// Record `count` query cache entries of `collection` removed for
+    /// `reason` (`"invalidated"`, `"evicted"` or `"expired"`).
+    fn query_cache_removal(&self, _collection: &str, _reason: &str, _count: f64) {}
+
     /// Record a HiveHub quota check outcome for `tenant_id`/`quota_type`.
     /// `allowed` is `false` when the quota was exceeded.
     fn hub_quota_check(&self, _tenant_id: &str, _quota_type: &str, _allowed: bool) {}
@@ -82,7 +105,11 @@ mod tests {
         sink.ttl_reaper_lag_seconds("test", 1.0);
         sink.ttl_reaper_scan_completed("test");
         sink.ttl_vectors_expired("test", 3.0);
+        sink.index_fragmentation("test", 0.25);
+        sink.index_compaction("test", true, 1.5, 100.0);
+        sink.scheduled_backup("nightly", false, 2.0, 3.0);
         sink.cache_request("query", true);
+        sink.query_cache_request("test", false);
         sink.hub_quota_check("tenant", "storage", false);
         sink.hub_quota_usage("tenant", "storage", 42.0);
         sink.hub_quota_check_latency(0.01);
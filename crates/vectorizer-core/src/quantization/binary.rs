@@ -11,6 +11,30 @@ use serde::{Deserialize, Serialize};
 use crate::quantization::traits::*;
 use crate::quantization::{QuantizationError, QuantizationResult, QuantizationType};
 
+/// Number of differing bits between two packed binary codes.
+///
+/// Compares the common prefix when lengths differ. Works on 8-byte words so
+/// the compiler can lower `count_ones` to `popcnt` on the hot pre-search path.
+pub fn hamming_distance(a: &[u8], b: &[u8]) -> u32 {
+    let len = a.len().min(b.len());
+    let (a, b) = (&a[..len], &b[..len]);
+
+    let mut distance = 0u32;
+    let mut a_words = a.chunks_exact(8);
+    let mut b_words = b.chunks_exact(8);
+    for (wa, wb) in (&mut a_words).zip(&mut b_words) {
+        let mut xa = [0u8; 8];
+        let mut xb = [0u8; 8];
+        xa.copy_from_slice(wa);
+        xb.copy_from_slice(wb);
+        distance += (u64::from_le_bytes(xa) ^ u64::from_le_bytes(xb)).count_ones();
+    }
+    for (x, y) in a_words.remainder().iter().zip(b_words.remainder()) {
+        distance += (x ^ y).count_ones();
+    }
+    distance
+}
+
 /// Binary Quantization implementation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BinaryQuantization {
@@ -236,18 +260,11 @@ impl QuantizedSearch for BinaryQuantization {
         // Quantize query
         let quantized_query = self.quantize_vector(query)?;
 
-        // Calculate Hamming distance (XOR and count bits)
-        let mut hamming_distance = 0;
-        let min_len = quantized_query.len().min(quantized_vector.len());
-
-        for i in 0..min_len {
-            let xor = quantized_query[i] ^ quantized_vector[i];
-            hamming_distance += xor.count_ones() as usize;
-        }
+        let hamming = hamming_distance(&quantized_query, quantized_vector);
 
         // Convert Hamming distance to similarity (normalized)
         let max_distance = query.len();
-        let similarity = 1.0 - (hamming_distance as f32 / max_distance as f32);
+        let similarity = 1.0 - (hamming as f32 / max_distance as f32);
 
         Ok(similarity)
     }
@@ -257,17 +274,12 @@ impl QuantizedSearch for BinaryQuantization {
         quantized_a: &[u8],
         quantized_b: &[u8],
     ) -> QuantizationResult<f32> {
-        let mut hamming_distance = 0;
         let min_len = quantized_a.len().min(quantized_b.len());
-
-        for i in 0..min_len {
-            let xor = quantized_a[i] ^ quantized_b[i];
-            hamming_distance += xor.count_ones() as usize;
-        }
+        let hamming = hamming_distance(quantized_a, quantized_b);
 
         // Estimate dimension from byte count (assuming 8 bits per byte)
         let estimated_dim = min_len * 8;
-        let similarity = 1.0 - (hamming_distance as f32 / estimated_dim as f32);
+        let similarity = 1.0 - (hamming as f32 / estimated_dim as f32);
 
         Ok(similarity)
     }
@@ -376,6 +388,20 @@ mod tests {
         assert!(similarity <= 1.0);
     }
 
+    #[test]
+    fn test_hamming_distance_word_and_tail() {
+        // 10 bytes: one full 8-byte word plus a 2-byte remainder.
+        let a = [0u8; 10];
+        let mut b = [0u8; 10];
+        b[0] = 0b1010_0000;
+        b[7] = 0xFF;
+        b[9] = 0b0000_0011;
+        assert_eq!(hamming_distance(&a, &b), 2 + 8 + 2);
+        assert_eq!(hamming_distance(&b, &b), 0);
+        // Mismatched lengths compare the common prefix only.
+        assert_eq!(hamming_distance(&a[..1], &b), 2);
+    }
+
     #[test]
     fn test_binary_quantization_trait_implementation() {
         let mut quantizer = BinaryQuantization::new();
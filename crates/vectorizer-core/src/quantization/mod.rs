@@ -237,7 +237,7 @@ mod tests {
 }
 
 // Re-export main types
-pub use binary::BinaryQuantization;
+pub use binary::{BinaryQuantization, hamming_distance};
 pub use hnsw_integration::{HnswQuantizationConfig, QuantizedHnswIndex};
 pub use product::ProductQuantization;
 pub use scalar::ScalarQuantization;
@@ -18,11 +18,16 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // the build unambiguous.
     println!("cargo:rerun-if-changed=proto/vectorizer.proto");
     println!("cargo:rerun-if-changed=proto/cluster.proto");
+    // Encoded descriptors for the server's gRPC reflection service go
+    // to OUT_DIR: they are build artifacts, not checked-in sources.
+    let descriptor_dir = std::path::PathBuf::from(std::env::var("OUT_DIR")?);
+
     std::fs::create_dir_all("src/grpc_gen")?;
     tonic_prost_build::configure()
         .build_server(true)
         .build_client(true) // Enable client generation for tests + the Rust SDK
         .out_dir("src/grpc_gen")
+        .file_descriptor_set_path(descriptor_dir.join("vectorizer_descriptor.bin"))
         .compile_protos(
             &["proto/vectorizer.proto", "proto/cluster.proto"],
             &["proto"],
@@ -35,6 +40,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .build_server(true)
         .build_client(true)
         .out_dir("src/grpc_gen/qdrant")
+        .file_descriptor_set_path(descriptor_dir.join("qdrant_descriptor.bin"))
         .compile_protos(
             &[
                 "proto/qdrant/collections_service.proto",
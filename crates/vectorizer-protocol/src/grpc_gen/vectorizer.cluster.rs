@@ -449,6 +449,34 @@ pub struct RaftSnapshotResponse {
     #[prost(bytes = "vec", tag = "1")]
     pub data: ::prost::alloc::vec::Vec<u8>,
 }
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct SnapshotPhaseRequest {
+    /// Cluster snapshot this phase belongs to
+    #[prost(string, tag = "1")]
+    pub snapshot_id: ::prost::alloc::string::String,
+    #[prost(enumeration = "SnapshotPhase", tag = "2")]
+    pub phase: i32,
+    /// How long the write fence may stay up before it lifts by itself (prepare)
+    #[prost(uint64, tag = "3")]
+    pub fence_timeout_ms: u64,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct SnapshotPhaseResponse {
+    #[prost(bool, tag = "1")]
+    pub success: bool,
+    #[prost(string, tag = "2")]
+    pub message: ::prost::alloc::string::String,
+    /// Node that ran the phase
+    #[prost(string, tag = "3")]
+    pub node_id: ::prost::alloc::string::String,
+    /// Collections in the node's part (commit, restore)
+    #[prost(string, repeated, tag = "4")]
+    pub collections: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(uint64, tag = "5")]
+    pub vector_count: u64,
+    #[prost(uint64, tag = "6")]
+    pub size_bytes: u64,
+}
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
 pub enum NodeStatus {
@@ -539,6 +567,42 @@ impl QuotaType {
         }
     }
 }
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum SnapshotPhase {
+    /// Raise the write fence and wait for in-flight writes to drain
+    Prepare = 0,
+    /// Write the node's part of the snapshot and lower the fence
+    Commit = 1,
+    /// Lower the fence without writing anything
+    Abort = 2,
+    /// Replace local data with the node's part and lower the fence
+    Restore = 3,
+}
+impl SnapshotPhase {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Prepare => "SNAPSHOT_PHASE_PREPARE",
+            Self::Commit => "SNAPSHOT_PHASE_COMMIT",
+            Self::Abort => "SNAPSHOT_PHASE_ABORT",
+            Self::Restore => "SNAPSHOT_PHASE_RESTORE",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "SNAPSHOT_PHASE_PREPARE" => Some(Self::Prepare),
+            "SNAPSHOT_PHASE_COMMIT" => Some(Self::Commit),
+            "SNAPSHOT_PHASE_ABORT" => Some(Self::Abort),
+            "SNAPSHOT_PHASE_RESTORE" => Some(Self::Restore),
+            _ => None,
+        }
+    }
+}
 /// Generated client implementations.
 pub mod cluster_service_client {
     #![allow(
@@ -1091,6 +1155,33 @@ pub mod cluster_service_client {
                 );
             self.inner.unary(req, path, codec).await
         }
+        /// Coordinated cluster snapshot: one phase of the two-phase cut
+        pub async fn snapshot_phase(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SnapshotPhaseRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SnapshotPhaseResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/vectorizer.cluster.ClusterService/SnapshotPhase",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("vectorizer.cluster.ClusterService", "SnapshotPhase"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -1226,6 +1317,14 @@ pub mod cluster_service_server {
             tonic::Response<super::RaftSnapshotResponse>,
             tonic::Status,
         >;
+        /// Coordinated cluster snapshot: one phase of the two-phase cut
+        async fn snapshot_phase(
+            &self,
+            request: tonic::Request<super::SnapshotPhaseRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SnapshotPhaseResponse>,
+            tonic::Status,
+        >;
     }
     /// Cluster service for inter-server communication
     #[derive(Debug)]
@@ -2050,6 +2149,51 @@ pub mod cluster_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/vectorizer.cluster.ClusterService/SnapshotPhase" => {
+                    #[allow(non_camel_case_types)]
+                    struct SnapshotPhaseSvc<T: ClusterService>(pub Arc<T>);
+                    impl<
+                        T: ClusterService,
+                    > tonic::server::UnaryService<super::SnapshotPhaseRequest>
+                    for SnapshotPhaseSvc<T> {
+                        type Response = super::SnapshotPhaseResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SnapshotPhaseRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as ClusterService>::snapshot_phase(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SnapshotPhaseSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         let mut response = http::Response::new(
@@ -44,4 +44,14 @@ pub mod grpc_gen {
     pub mod qdrant_proto {
         include!("grpc_gen/qdrant/qdrant.rs");
     }
+
+    /// Encoded `FileDescriptorSet` of `vectorizer` and `cluster`, for
+    /// gRPC server reflection.
+    pub const FILE_DESCRIPTOR_SET: &[u8] =
+        include_bytes!(concat!(env!("OUT_DIR"), "/vectorizer_descriptor.bin"));
+
+    /// Encoded `FileDescriptorSet` of `qdrant_proto`, for gRPC server
+    /// reflection.
+    pub const QDRANT_FILE_DESCRIPTOR_SET: &[u8] =
+        include_bytes!(concat!(env!("OUT_DIR"), "/qdrant_descriptor.bin"));
 }
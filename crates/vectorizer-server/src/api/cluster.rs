@@ -15,7 +15,10 @@ use axum::response::Json;
 use axum::routing::{delete, get, post};
 use serde::{Deserialize, Serialize};
 use tracing::{debug, info, warn};
-use vectorizer::cluster::{ClusterManager, NodeId};
+use vectorizer::cluster::{
+    ClusterClientPool, ClusterManager, ClusterSnapshotCoordinator, ClusterSnapshotManifest, NodeId,
+    NodeRestoreResult,
+};
 use vectorizer::db::VectorStore;
 use vectorizer_core::error::VectorizerError;
 
@@ -26,6 +29,8 @@ pub struct ClusterApiState {
     pub cluster_manager: Arc<ClusterManager>,
     /// Vector store
     pub store: Arc<VectorStore>,
+    /// Clients for the other nodes (cluster snapshots)
+    pub client_pool: Arc<ClusterClientPool>,
 }
 
 /// Response for cluster node information
@@ -76,6 +81,47 @@ pub struct NodeShardCount {
     pub shard_count: usize,
 }
 
+/// Consistent-hash routing table for shard-aware clients
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RoutingTableResponse {
+    /// Router epoch; bumps on every shard reassignment.
+    pub epoch: u64,
+    /// Hash applied to vector ids before the ring lookup.
+    pub hash: String,
+    /// Ring points in ascending hash order.
+    pub ring: Vec<RingPointResponse>,
+    pub shards: Vec<ShardOwnerResponse>,
+    pub nodes: Vec<RoutingNodeResponse>,
+}
+
+/// One virtual node on the hash ring
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RingPointResponse {
+    pub hash: u64,
+    pub shard_id: u32,
+}
+
+/// Current owner of a shard
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ShardOwnerResponse {
+    pub shard_id: u32,
+    pub node_id: String,
+    pub epoch: Option<u64>,
+}
+
+/// Node a client may route to
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RoutingNodeResponse {
+    pub id: String,
+    pub address: String,
+    pub grpc_port: u16,
+    pub status: String,
+    /// Whether this is the node that served the table. Its `address`
+    /// may be a placeholder; clients should reach it through the URL
+    /// they fetched the table from.
+    pub local: bool,
+}
+
 /// Request to add a node
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AddNodeRequest {
@@ -98,6 +144,21 @@ pub struct RebalanceResponse {
     pub shards_moved: Option<usize>,
 }
 
+/// Response for listing cluster snapshots
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListClusterSnapshotsResponse {
+    pub snapshots: Vec<ClusterSnapshotManifest>,
+}
+
+/// Response for restoring a cluster snapshot
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RestoreClusterSnapshotResponse {
+    pub snapshot_id: String,
+    /// Whether every node restored its part
+    pub success: bool,
+    pub nodes: Vec<NodeRestoreResult>,
+}
+
 /// Create cluster API router
 pub fn create_cluster_router() -> Router<ClusterApiState> {
     Router::new()
@@ -109,9 +170,20 @@ pub fn create_cluster_router() -> Router<ClusterApiState> {
             "/api/v1/cluster/shard-distribution",
             get(get_shard_distribution),
         )
+        .route("/api/v1/cluster/routing", get(get_routing_table))
         .route("/api/v1/cluster/rebalance", post(trigger_rebalance))
         .route("/api/v1/cluster/leader", get(get_cluster_leader))
         .route("/api/v1/cluster/role", get(get_cluster_role))
+        .route("/api/v1/cluster/snapshots", post(create_cluster_snapshot))
+        .route("/api/v1/cluster/snapshots", get(list_cluster_snapshots))
+        .route(
+            "/api/v1/cluster/snapshots/{snapshot_id}",
+            get(get_cluster_snapshot),
+        )
+        .route(
+            "/api/v1/cluster/snapshots/{snapshot_id}/restore",
+            post(restore_cluster_snapshot),
+        )
 }
 
 /// List all cluster nodes
@@ -278,6 +350,64 @@ async fn get_shard_distribution(
     }))
 }
 
+/// GET /api/v1/cluster/routing
+///
+/// The hash ring and shard owners, so clients can send single-vector
+/// requests straight to the node owning the vector instead of paying
+/// the proxy hop through whichever node they are connected to. A vector
+/// belongs to the shard at the first ring point at or after the xxh3-64
+/// hash of its id, wrapping around to the first point.
+async fn get_routing_table(State(state): State<ClusterApiState>) -> Json<RoutingTableResponse> {
+    debug!("REST: Get cluster routing table");
+
+    let shard_router = state.cluster_manager.shard_router();
+    let local_node_id = state.cluster_manager.local_node_id().clone();
+
+    let ring = shard_router
+        .ring_points()
+        .into_iter()
+        .map(|(hash, shard_id)| RingPointResponse {
+            hash,
+            shard_id: shard_id.as_u32(),
+        })
+        .collect();
+
+    let mut shards: Vec<ShardOwnerResponse> = shard_router
+        .get_all_shards()
+        .into_iter()
+        .filter_map(|shard_id| {
+            let node_id = shard_router.get_node_for_shard(&shard_id)?;
+            Some(ShardOwnerResponse {
+                shard_id: shard_id.as_u32(),
+                node_id: node_id.as_str().to_string(),
+                epoch: shard_router.get_shard_epoch(&shard_id),
+            })
+        })
+        .collect();
+    shards.sort_by_key(|s| s.shard_id);
+
+    let nodes = state
+        .cluster_manager
+        .get_nodes()
+        .into_iter()
+        .map(|node| RoutingNodeResponse {
+            local: node.id == local_node_id,
+            id: node.id.as_str().to_string(),
+            address: node.address.clone(),
+            grpc_port: node.grpc_port,
+            status: format!("{:?}", node.status).to_lowercase(),
+        })
+        .collect();
+
+    Json(RoutingTableResponse {
+        epoch: shard_router.current_epoch(),
+        hash: "xxh3_64".to_string(),
+        ring,
+        shards,
+        nodes,
+    })
+}
+
 /// Trigger shard rebalancing
 async fn trigger_rebalance(
     State(state): State<ClusterApiState>,
@@ -415,6 +545,104 @@ pub async fn get_cluster_role(State(_state): State<ClusterApiState>) -> Json<ser
     }))
 }
 
+/// Coordinator for cluster snapshots taken from this node
+fn snapshot_coordinator(state: &ClusterApiState) -> ClusterSnapshotCoordinator {
+    ClusterSnapshotCoordinator::new(
+        state.store.clone(),
+        state.cluster_manager.clone(),
+        state.client_pool.clone(),
+    )
+}
+
+fn snapshot_error(e: VectorizerError) -> (StatusCode, Json<serde_json::Value>) {
+    (
+        e.kind().http_status(),
+        Json(serde_json::json!({
+            "error": e.to_string(),
+            "error_type": e.code(),
+        })),
+    )
+}
+
+/// POST /api/v1/cluster/snapshots
+///
+/// Take a snapshot of every node at one consistent cut. Writes are
+/// refused across the cluster (503 `writes_fenced`) while it is taken.
+async fn create_cluster_snapshot(
+    State(state): State<ClusterApiState>,
+) -> Result<Json<ClusterSnapshotManifest>, (StatusCode, Json<serde_json::Value>)> {
+    info!("REST: Create cluster snapshot");
+
+    snapshot_coordinator(&state)
+        .create()
+        .await
+        .map(Json)
+        .map_err(snapshot_error)
+}
+
+/// GET /api/v1/cluster/snapshots
+async fn list_cluster_snapshots(
+    State(state): State<ClusterApiState>,
+) -> Result<Json<ListClusterSnapshotsResponse>, (StatusCode, Json<serde_json::Value>)> {
+    debug!("REST: List cluster snapshots");
+
+    let snapshots = snapshot_coordinator(&state)
+        .list()
+        .map_err(snapshot_error)?;
+    Ok(Json(ListClusterSnapshotsResponse { snapshots }))
+}
+
+/// GET /api/v1/cluster/snapshots/{snapshot_id}
+async fn get_cluster_snapshot(
+    State(state): State<ClusterApiState>,
+    Path(snapshot_id): Path<String>,
+) -> Result<Json<ClusterSnapshotManifest>, (StatusCode, Json<serde_json::Value>)> {
+    debug!("REST: Get cluster snapshot {}", snapshot_id);
+
+    snapshot_coordinator(&state)
+        .get(&snapshot_id)
+        .map(Json)
+        .map_err(snapshot_error)
+}
+
+/// POST /api/v1/cluster/snapshots/{snapshot_id}/restore
+///
+/// Restore every node from a cluster snapshot. Refused (400) when a
+/// node of the snapshot is gone or a shard has moved since; a node that
+/// fails to restore its part makes the response a 500 listing it.
+async fn restore_cluster_snapshot(
+    State(state): State<ClusterApiState>,
+    Path(snapshot_id): Path<String>,
+) -> Result<(StatusCode, Json<RestoreClusterSnapshotResponse>), (StatusCode, Json<serde_json::Value>)>
+{
+    info!("REST: Restore cluster snapshot {}", snapshot_id);
+
+    let nodes = snapshot_coordinator(&state)
+        .restore(&snapshot_id)
+        .await
+        .map_err(snapshot_error)?;
+    let success = nodes.iter().all(|n| n.success);
+    if !success {
+        warn!(
+            "Cluster snapshot {} restored only partially: {:?}",
+            snapshot_id, nodes
+        );
+    }
+    let status = if success {
+        StatusCode::OK
+    } else {
+        StatusCode::INTERNAL_SERVER_ERROR
+    };
+    Ok((
+        status,
+        Json(RestoreClusterSnapshotResponse {
+            snapshot_id,
+            success,
+            nodes,
+        }),
+    ))
+}
+
 /// Get current shard distribution across nodes
 fn get_current_shard_distribution(
     shard_router: &std::sync::Arc<vectorizer::cluster::DistributedShardRouter>,
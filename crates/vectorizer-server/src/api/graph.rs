@@ -6,6 +6,7 @@
 //! - Finding related nodes
 //! - Finding paths between nodes
 //! - Creating and deleting edges
+//! - Extracting entities from a collection's chunks and listing them
 
 // Internal data-layout file: public fields are self-documenting; the
 // blanket allow keeps `cargo doc -W missing-docs` clean without padding
@@ -81,6 +82,8 @@ pub fn create_graph_router() -> Router<GraphApiState> {
             "/graph/discover/{collection}/status",
             get(get_discovery_status),
         )
+        .route("/graph/extract/{collection}", post(extract_entities))
+        .route("/graph/entities/{collection}", get(list_entities))
         // Enable graph for a collection
         .route("/graph/enable/{collection}", post(enable_graph))
         .route("/graph/status/{collection}", get(graph_status))
@@ -196,6 +199,38 @@ pub struct DiscoveryStatusResponse {
     pub progress_percentage: f64,
 }
 
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ExtractEntitiesRequest {
+    /// Recognizers to run instead of the collection's
+    pub recognizers: Option<Vec<String>>,
+    /// Entity labels to keep instead of the collection's
+    pub labels: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExtractEntitiesResponse {
+    pub success: bool,
+    pub entities: usize,
+    pub mentions: usize,
+    pub relations: usize,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EntityInfo {
+    pub id: String,
+    pub name: String,
+    pub label: String,
+    /// Chunks mentioning the entity
+    pub mentions: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListEntitiesResponse {
+    pub entities: Vec<EntityInfo>,
+    pub count: usize,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct EnableGraphResponse {
     pub success: bool,
@@ -620,6 +655,8 @@ pub async fn list_edges(
             "REFERENCES" => Some(RelationshipType::References),
             "CONTAINS" => Some(RelationshipType::Contains),
             "DERIVED_FROM" => Some(RelationshipType::DerivedFrom),
+            "MENTIONS" => Some(RelationshipType::Mentions),
+            "RELATED_TO" => Some(RelationshipType::RelatedTo),
             _ => None,
         });
 
@@ -661,6 +698,8 @@ pub async fn list_edges(
                     RelationshipType::References => "REFERENCES".to_string(),
                     RelationshipType::Contains => "CONTAINS".to_string(),
                     RelationshipType::DerivedFrom => "DERIVED_FROM".to_string(),
+                    RelationshipType::Mentions => "MENTIONS".to_string(),
+                    RelationshipType::RelatedTo => "RELATED_TO".to_string(),
                 },
                 weight: edge.weight,
                 metadata: edge.metadata.clone(),
@@ -974,6 +1013,157 @@ pub async fn get_discovery_status(
     }))
 }
 
+/// POST /graph/extract/{collection}
+/// Extract entities from every vector already in a collection
+pub async fn extract_entities(
+    State(state): State<GraphApiState>,
+    Path(collection_name): Path<String>,
+    request: Option<Json<ExtractEntitiesRequest>>,
+) -> Result<Json<ExtractEntitiesResponse>, (StatusCode, Json<serde_json::Value>)> {
+    debug!("POST /graph/extract/{}", collection_name);
+    let request = request.map(|Json(r)| r).unwrap_or_default();
+
+    let collection = state.store.get_collection(&collection_name).map_err(|e| {
+        error!("Collection '{}' not found: {}", collection_name, e);
+        (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({
+                "error": format!("Collection '{}' not found", collection_name)
+            })),
+        )
+    })?;
+
+    let cpu_collection = match &*collection {
+        CollectionType::Cpu(c) if c.get_graph().is_some() => c,
+        _ => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({
+                    "error": format!("Graph not enabled for collection '{}'", collection_name)
+                })),
+            ));
+        }
+    };
+
+    // The collection's settings, with the request's overrides
+    let mut config = collection
+        .config()
+        .graph
+        .as_ref()
+        .map(|g| g.entity_extraction.clone())
+        .unwrap_or_default();
+    config.enabled = true;
+    if let Some(recognizers) = request.recognizers {
+        if let Some(unknown) = recognizers
+            .iter()
+            .find(|name| vectorizer::db::graph_entity_extraction::entity_recognizer(name).is_none())
+        {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({
+                    "error": format!("Entity recognizer '{}' is not registered", unknown)
+                })),
+            ));
+        }
+        config.recognizers = recognizers;
+    }
+    if let Some(labels) = request.labels {
+        config.labels = labels;
+    }
+
+    let stats = cpu_collection.extract_entities(&config).map_err(|e| {
+        error!("Failed to extract entities: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({
+                "error": format!("Failed to extract entities: {}", e)
+            })),
+        )
+    })?;
+
+    Ok(Json(ExtractEntitiesResponse {
+        success: true,
+        entities: stats.entities,
+        mentions: stats.mentions,
+        relations: stats.relations,
+        message: format!(
+            "Linked {} entity mentions and {} relations",
+            stats.mentions, stats.relations
+        ),
+    }))
+}
+
+/// GET /graph/entities/{collection}
+/// List extracted entities, most mentioned first
+pub async fn list_entities(
+    State(state): State<GraphApiState>,
+    Path(collection_name): Path<String>,
+    axum::extract::Query(params): axum::extract::Query<std::collections::HashMap<String, String>>,
+) -> Result<Json<ListEntitiesResponse>, (StatusCode, Json<serde_json::Value>)> {
+    debug!("GET /graph/entities/{}", collection_name);
+
+    let collection = state.store.get_collection(&collection_name).map_err(|e| {
+        error!("Collection '{}' not found: {}", collection_name, e);
+        (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({
+                "error": format!("Collection '{}' not found", collection_name)
+            })),
+        )
+    })?;
+
+    let graph = get_collection_graph_from_type(&collection).ok_or_else(|| {
+        error!("Graph not enabled for collection '{}'", collection_name);
+        (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({
+                "error": format!("Graph not enabled for collection '{}'", collection_name)
+            })),
+        )
+    })?;
+
+    let label_filter = params.get("label").map(|l| l.to_uppercase());
+    let limit = params.get("limit").and_then(|s| s.parse::<usize>().ok());
+
+    let mut mentions: HashMap<String, usize> = HashMap::new();
+    for edge in graph.get_all_edges() {
+        if edge.relationship_type == RelationshipType::Mentions {
+            *mentions.entry(edge.target).or_default() += 1;
+        }
+    }
+
+    let metadata_str = |node: &Node, key: &str| {
+        node.metadata
+            .get(key)
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string()
+    };
+    let mut entities: Vec<EntityInfo> = graph
+        .get_all_nodes()
+        .into_iter()
+        .filter(|node| node.node_type == vectorizer::db::graph_entity_extraction::ENTITY_NODE_TYPE)
+        .map(|node| EntityInfo {
+            name: metadata_str(&node, "name"),
+            label: metadata_str(&node, "label"),
+            mentions: mentions.get(&node.id).copied().unwrap_or(0),
+            id: node.id,
+        })
+        .filter(|entity| {
+            label_filter
+                .as_ref()
+                .is_none_or(|label| entity.label == *label)
+        })
+        .collect();
+    entities.sort_by(|a, b| b.mentions.cmp(&a.mentions).then_with(|| a.id.cmp(&b.id)));
+    if let Some(limit) = limit {
+        entities.truncate(limit);
+    }
+
+    let count = entities.len();
+    Ok(Json(ListEntitiesResponse { entities, count }))
+}
+
 /// Parse relationship type from string
 fn parse_relationship_type(s: &str) -> Option<RelationshipType> {
     match s.to_uppercase().as_str() {
@@ -981,6 +1171,8 @@ fn parse_relationship_type(s: &str) -> Option<RelationshipType> {
         "REFERENCES" => Some(RelationshipType::References),
         "CONTAINS" => Some(RelationshipType::Contains),
         "DERIVED_FROM" | "DERIVEDFROM" => Some(RelationshipType::DerivedFrom),
+        "MENTIONS" => Some(RelationshipType::Mentions),
+        "RELATED_TO" | "RELATEDTO" => Some(RelationshipType::RelatedTo),
         _ => None,
     }
 }
@@ -2,7 +2,8 @@
 //!
 //! This module provides a full GraphQL API with:
 //! - Query operations (collections, vectors, search, graph)
-//! - Mutation operations (create, update, delete)
+//! - Mutation operations (create, update, delete, batch upsert/update/delete)
+//! - Subscriptions over WebSocket at `/graphql/ws` (indexing progress)
 //! - GraphQL Playground for interactive exploration
 //!
 //! # Example
@@ -28,5 +29,6 @@ mod tests;
 
 pub use schema::{
     VectorizerSchema, create_schema, create_schema_with_auto_save, create_schema_with_hub,
+    create_schema_with_progress,
 };
 pub use types::*;
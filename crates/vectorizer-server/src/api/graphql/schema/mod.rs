@@ -1,23 +1,25 @@
 //! GraphQL Schema and Resolvers for Vectorizer
 //!
-//! This module defines the GraphQL schema including Query and Mutation types.
+//! This module defines the GraphQL schema including Query, Mutation and
+//! Subscription types.
 
 use std::sync::Arc;
 
-use async_graphql::{Context, EmptySubscription, Object, Schema};
+use async_graphql::{Context, Object, Schema};
 use tracing::{error, info, warn};
 use vectorizer::config::FileUploadConfig;
-use vectorizer::db::VectorStore;
 use vectorizer::db::auto_save::AutoSaveManager;
 use vectorizer::db::graph::{Edge, Node, RelationshipType};
+use vectorizer::db::{StartupProgress, VectorStore};
 use vectorizer::embedding::EmbeddingManager;
 use vectorizer::file_loader::chunker::Chunker;
 use vectorizer::file_loader::config::LoaderConfig;
 use vectorizer::hub::auth::TenantContext;
-use vectorizer::hub::quota::QuotaManager;
+use vectorizer::hub::quota::{QuotaManager, TenantUsage, WriteAdmission};
 use vectorizer::models::{
     CollectionConfig, DistanceMetric, HnswConfig, Payload, QuantizationConfig, Vector,
 };
+use vectorizer::security::PayloadView;
 
 use super::types::*;
 
@@ -32,10 +34,12 @@ pub struct GraphQLContext {
     pub quota_manager: Option<Arc<QuotaManager>>,
     /// Optional auto-save manager for persistence
     pub auto_save_manager: Option<Arc<AutoSaveManager>>,
+    /// Startup load progress behind the `indexingProgress` subscription
+    pub startup_progress: Arc<StartupProgress>,
 }
 
 /// The GraphQL schema type
-pub type VectorizerSchema = Schema<QueryRoot, MutationRoot, EmptySubscription>;
+pub type VectorizerSchema = Schema<QueryRoot, MutationRoot, SubscriptionRoot>;
 
 /// Create the GraphQL schema with the given context
 ///
@@ -54,9 +58,10 @@ pub fn create_schema(
         tenant_context: None,
         quota_manager: None,
         auto_save_manager: None,
+        startup_progress: Arc::new(StartupProgress::completed()),
     };
 
-    Schema::build(QueryRoot, MutationRoot, EmptySubscription)
+    Schema::build(QueryRoot, MutationRoot, SubscriptionRoot)
         .data(ctx)
         // Limit query depth to prevent deeply nested queries
         .limit_depth(10)
@@ -79,9 +84,10 @@ pub fn create_schema_with_auto_save(
         tenant_context: None,
         quota_manager: None,
         auto_save_manager: Some(auto_save_manager),
+        startup_progress: Arc::new(StartupProgress::completed()),
     };
 
-    Schema::build(QueryRoot, MutationRoot, EmptySubscription)
+    Schema::build(QueryRoot, MutationRoot, SubscriptionRoot)
         .data(ctx)
         .limit_depth(10)
         .limit_complexity(1000)
@@ -102,9 +108,37 @@ pub fn create_schema_with_hub(
         tenant_context: None, // Set per-request in handler
         quota_manager: Some(quota_manager),
         auto_save_manager: None,
+        startup_progress: Arc::new(StartupProgress::completed()),
     };
 
-    Schema::build(QueryRoot, MutationRoot, EmptySubscription)
+    Schema::build(QueryRoot, MutationRoot, SubscriptionRoot)
+        .data(ctx)
+        .limit_depth(10)
+        .limit_complexity(1000)
+        .finish()
+}
+
+/// Create the GraphQL schema the server mounts: auto-save when the server
+/// persists, and the server's startup progress behind the
+/// `indexingProgress` subscription
+pub fn create_schema_with_progress(
+    store: Arc<VectorStore>,
+    embedding_manager: Arc<EmbeddingManager>,
+    start_time: std::time::Instant,
+    auto_save_manager: Option<Arc<AutoSaveManager>>,
+    startup_progress: Arc<StartupProgress>,
+) -> VectorizerSchema {
+    let ctx = GraphQLContext {
+        store,
+        embedding_manager,
+        start_time,
+        tenant_context: None,
+        quota_manager: None,
+        auto_save_manager,
+        startup_progress,
+    };
+
+    Schema::build(QueryRoot, MutationRoot, SubscriptionRoot)
         .data(ctx)
         .limit_depth(10)
         .limit_complexity(1000)
@@ -151,20 +185,50 @@ fn check_collection_ownership(
     Ok(())
 }
 
+/// The caller's payload redaction view. The HTTP and WebSocket handlers
+/// attach one to every request; schemas executed directly see raw
+/// payloads.
+fn payload_view(ctx: &Context<'_>) -> PayloadView {
+    ctx.data_opt::<PayloadView>().cloned().unwrap_or_default()
+}
+
+/// Refuse a write that would take the tenant past one of its quotas, in
+/// multi-tenant mode. `requested` is what the write adds; hold the
+/// returned admission until it is written.
+async fn enforce_tenant_quota(
+    gql_ctx: &GraphQLContext,
+    tenant_ctx: Option<&TenantContext>,
+    requested: TenantUsage,
+) -> async_graphql::Result<WriteAdmission> {
+    let (Some(tenant), Some(quota_mgr)) = (tenant_ctx, &gql_ctx.quota_manager) else {
+        return Ok(WriteAdmission::default());
+    };
+    quota_mgr
+        .admit_write(&gql_ctx.store, &tenant.tenant_id, requested)
+        .await
+        .map_err(|e| {
+            warn!("GraphQL: write denied for tenant {}: {e}", tenant.tenant_id);
+            async_graphql::Error::new(e.to_string())
+        })
+}
+
 // ============================================================================
-// Sub-modules — QueryRoot / MutationRoot (phase4_split-graphql-schema).
+// Sub-modules — QueryRoot / MutationRoot (phase4_split-graphql-schema),
+// and SubscriptionRoot.
 // The `#[Object] impl` blocks are too large to keep next to the schema
 // builders. Helpers below (`check_collection_ownership`,
-// `load_file_upload_config`, `base64_decode`, `is_binary_content`,
+// `enforce_tenant_quota`, `load_file_upload_config`, `base64_decode`, `is_binary_content`,
 // `get_language_from_extension`) are shared by both roots and stay here;
 // the sub-files import them via `use super::...`.
 // ============================================================================
 
 pub mod mutation;
 pub mod query;
+pub mod subscription;
 
 pub use mutation::MutationRoot;
 pub use query::QueryRoot;
+pub use subscription::SubscriptionRoot;
 
 // =============================================================================
 // HELPER FUNCTIONS
@@ -11,14 +11,16 @@ use vectorizer::db::graph::{Edge, Node, RelationshipType};
 use vectorizer::file_loader::chunker::Chunker;
 use vectorizer::file_loader::config::LoaderConfig;
 use vectorizer::hub::auth::TenantContext;
+use vectorizer::hub::quota::TenantUsage;
 use vectorizer::models::{
     CollectionConfig, DistanceMetric, HnswConfig, Payload, QuantizationConfig, Vector,
 };
 
 use super::super::types::*;
 use super::{
-    GraphQLContext, base64_decode, check_collection_ownership, get_language_from_extension,
-    is_binary_content, load_file_upload_config, tenant_collection_name,
+    GraphQLContext, base64_decode, check_collection_ownership, enforce_tenant_quota,
+    get_language_from_extension, is_binary_content, load_file_upload_config,
+    tenant_collection_name,
 };
 
 pub struct MutationRoot;
@@ -34,29 +36,16 @@ impl MutationRoot {
         let gql_ctx = ctx.data::<GraphQLContext>()?;
         let tenant_ctx = ctx.data_opt::<TenantContext>();
 
-        // In multi-tenant mode, enforce quota check
-        if let (Some(tenant), Some(quota_mgr)) = (tenant_ctx, &gql_ctx.quota_manager) {
-            // Check collection count quota
-            match quota_mgr
-                .check_quota(
-                    &tenant.tenant_id,
-                    vectorizer::hub::quota::QuotaType::CollectionCount,
-                    1,
-                )
-                .await
-            {
-                Ok(true) => {} // Quota OK
-                Ok(false) => {
-                    return Err(async_graphql::Error::new(
-                        "Collection limit exceeded for your plan",
-                    ));
-                }
-                Err(e) => {
-                    error!("GraphQL: Quota check failed: {e}");
-                    // Continue anyway if quota check fails
-                }
-            }
-        }
+        // In multi-tenant mode, enforce the tenant's collection quota
+        let _tenant_admission = enforce_tenant_quota(
+            gql_ctx,
+            tenant_ctx,
+            TenantUsage {
+                collections: 1,
+                ..TenantUsage::default()
+            },
+        )
+        .await?;
 
         // Build collection config
         let mut config = CollectionConfig {
@@ -186,26 +175,16 @@ impl MutationRoot {
         check_collection_ownership(&gql_ctx.store, &collection, tenant_ctx)?;
 
         // Check quota in multi-tenant mode
-        if let (Some(tenant), Some(quota_mgr)) = (tenant_ctx, &gql_ctx.quota_manager) {
-            match quota_mgr
-                .check_quota(
-                    &tenant.tenant_id,
-                    vectorizer::hub::quota::QuotaType::VectorCount,
-                    1,
-                )
-                .await
-            {
-                Ok(true) => {} // Quota OK
-                Ok(false) => {
-                    return Err(async_graphql::Error::new(
-                        "Vector count limit exceeded for your plan",
-                    ));
-                }
-                Err(e) => {
-                    error!("GraphQL: Quota check failed: {e}");
-                }
-            }
-        }
+        let _tenant_admission = enforce_tenant_quota(
+            gql_ctx,
+            tenant_ctx,
+            TenantUsage {
+                vectors: 1,
+                bytes: std::mem::size_of_val(input.data.as_slice()) as u64,
+                collections: 0,
+            },
+        )
+        .await?;
 
         let payload = if let Some(payload_json) = input.payload {
             if let Some(ref key) = input.public_key {
@@ -258,26 +237,20 @@ impl MutationRoot {
         check_collection_ownership(&gql_ctx.store, &input.collection, tenant_ctx)?;
 
         // Check quota in multi-tenant mode
-        if let (Some(tenant), Some(quota_mgr)) = (tenant_ctx, &gql_ctx.quota_manager) {
-            match quota_mgr
-                .check_quota(
-                    &tenant.tenant_id,
-                    vectorizer::hub::quota::QuotaType::VectorCount,
-                    1,
-                )
-                .await
-            {
-                Ok(true) => {} // Quota OK
-                Ok(false) => {
-                    return Err(async_graphql::Error::new(
-                        "Vector count limit exceeded for your plan",
-                    ));
-                }
-                Err(e) => {
-                    error!("GraphQL: Quota check failed: {e}");
-                }
-            }
-        }
+        let _tenant_admission = enforce_tenant_quota(
+            gql_ctx,
+            tenant_ctx,
+            TenantUsage {
+                vectors: input.vectors.len() as u64,
+                bytes: input
+                    .vectors
+                    .iter()
+                    .map(|v| std::mem::size_of_val(v.data.as_slice()) as u64)
+                    .sum(),
+                collections: 0,
+            },
+        )
+        .await?;
 
         let request_public_key = input.public_key.clone();
         let vectors: Result<Vec<Vector>, async_graphql::Error> = input
@@ -414,6 +387,124 @@ impl MutationRoot {
         Ok(MutationResult::ok_with_message("Payload updated"))
     }
 
+    /// Delete vectors by ID in batch (with tenant ownership check).
+    /// IDs that do not exist are skipped and listed in the message.
+    async fn delete_vectors(
+        &self,
+        ctx: &Context<'_>,
+        collection: String,
+        ids: Vec<String>,
+    ) -> async_graphql::Result<MutationResult> {
+        let gql_ctx = ctx.data::<GraphQLContext>()?;
+        let tenant_ctx = ctx.data_opt::<TenantContext>();
+
+        // Verify ownership
+        check_collection_ownership(&gql_ctx.store, &collection, tenant_ctx)?;
+
+        let mut deleted = 0;
+        let mut missing = Vec::new();
+        for id in ids {
+            match gql_ctx.store.delete(&collection, &id) {
+                Ok(_) => deleted += 1,
+                Err(_) => missing.push(id),
+            }
+        }
+
+        // Mark changes for auto-save
+        if deleted > 0
+            && let Some(ref auto_save) = gql_ctx.auto_save_manager
+        {
+            auto_save.mark_changed();
+        }
+
+        info!("GraphQL: Deleted {deleted} vectors from '{collection}'");
+        let mut result = MutationResult::ok_with_count(deleted);
+        if !missing.is_empty() {
+            result.message = Some(format!("Not found: {}", missing.join(", ")));
+        }
+        Ok(result)
+    }
+
+    /// Update vectors in batch (with tenant ownership check). Each input
+    /// replaces the vector's data, its payload, or both; every ID must
+    /// exist, and nothing is written when one does not.
+    async fn update_vectors(
+        &self,
+        ctx: &Context<'_>,
+        input: UpdateVectorsInput,
+    ) -> async_graphql::Result<MutationResult> {
+        let gql_ctx = ctx.data::<GraphQLContext>()?;
+        let tenant_ctx = ctx.data_opt::<TenantContext>();
+
+        // Verify ownership
+        check_collection_ownership(&gql_ctx.store, &input.collection, tenant_ctx)?;
+
+        // Build every updated vector before writing any of them
+        let mut updated = Vec::with_capacity(input.vectors.len());
+        for v_input in input.vectors {
+            if v_input.data.is_none() && v_input.payload.is_none() {
+                return Err(async_graphql::Error::new(format!(
+                    "Vector '{}': nothing to update, set data or payload",
+                    v_input.id
+                )));
+            }
+            let existing = gql_ctx
+                .store
+                .get_vector(&input.collection, &v_input.id)
+                .map_err(|e| {
+                    async_graphql::Error::new(format!("Vector '{}' not found: {e}", v_input.id))
+                })?;
+
+            let payload = match v_input.payload {
+                Some(payload_json) => {
+                    // Use vector-level public_key if present, otherwise request-level
+                    match v_input.public_key.as_ref().or(input.public_key.as_ref()) {
+                        Some(key) => {
+                            let encrypted =
+                                vectorizer::security::payload_encryption::encrypt_payload(
+                                    &payload_json.0,
+                                    key,
+                                )
+                                .map_err(|e| {
+                                    async_graphql::Error::new(format!(
+                                        "Failed to encrypt payload: {e}"
+                                    ))
+                                })?;
+                            Some(Payload::from_encrypted(encrypted))
+                        }
+                        None => Some(Payload::new(payload_json.0)),
+                    }
+                }
+                None => existing.payload,
+            };
+
+            updated.push(Vector {
+                data: v_input.data.unwrap_or(existing.data),
+                payload,
+                ..existing
+            });
+        }
+
+        let count = updated.len() as i32;
+        for vector in updated {
+            let id = vector.id.clone();
+            gql_ctx
+                .store
+                .update(&input.collection, vector)
+                .map_err(|e| {
+                    async_graphql::Error::new(format!("Failed to update vector '{id}': {e}"))
+                })?;
+        }
+
+        // Mark changes for auto-save
+        if let Some(ref auto_save) = gql_ctx.auto_save_manager {
+            auto_save.mark_changed();
+        }
+
+        info!("GraphQL: Updated {count} vectors in '{}'", input.collection);
+        Ok(MutationResult::ok_with_count(count))
+    }
+
     // =========================================================================
     // GRAPH MUTATIONS
     // =========================================================================
@@ -789,6 +880,13 @@ impl MutationRoot {
                 sharding: None,
                 graph: None,
                 encryption: None,
+                id_policy: None,
+                quota: None,
+                scoring: None,
+                tenant_partition: None,
+                dedup: None,
+                chunking: None,
+                full_text: None,
             };
 
             if let Err(e) = gql_ctx
@@ -804,16 +902,25 @@ impl MutationRoot {
             info!("GraphQL: Created new collection: {}", collection_name);
         }
 
-        // Create chunks
+        // Create chunks, with the collection's chunking settings filling in
+        // what the input leaves out
+        let chunking = gql_ctx
+            .store
+            .get_collection(&collection_name)
+            .ok()
+            .and_then(|c| c.config().chunking.clone())
+            .unwrap_or_default();
         let loader_config = LoaderConfig {
             max_chunk_size: input
                 .chunk_size
-                .unwrap_or(upload_config.default_chunk_size as i32)
-                as usize,
+                .map(|size| size as usize)
+                .or(chunking.max_chunk_size)
+                .unwrap_or(upload_config.default_chunk_size),
             chunk_overlap: input
                 .chunk_overlap
-                .unwrap_or(upload_config.default_chunk_overlap as i32)
-                as usize,
+                .map(|overlap| overlap as usize)
+                .or(chunking.chunk_overlap)
+                .unwrap_or(upload_config.default_chunk_overlap),
             include_patterns: vec![],
             exclude_patterns: vec![],
             embedding_dimension: 512,
@@ -822,7 +929,7 @@ impl MutationRoot {
             max_file_size: upload_config.max_file_size,
         };
 
-        let chunker = Chunker::new(loader_config);
+        let chunker = Chunker::with_strategy(loader_config, chunking.strategy);
         let file_path = std::path::PathBuf::from(&input.filename);
 
         let chunks = match chunker.chunk_text(&content, &file_path) {
@@ -850,6 +957,23 @@ impl MutationRoot {
             ));
         }
 
+        // Check quota in multi-tenant mode
+        let dimension = gql_ctx
+            .store
+            .get_collection(&collection_name)
+            .map(|c| c.config().dimension)
+            .unwrap_or(0);
+        let _tenant_admission = enforce_tenant_quota(
+            gql_ctx,
+            tenant_ctx,
+            TenantUsage {
+                vectors: chunks.len() as u64,
+                bytes: (chunks.len() * dimension * std::mem::size_of::<f32>()) as u64,
+                collections: 0,
+            },
+        )
+        .await?;
+
         // Create embeddings and store vectors
         let mut vectors_created = 0i32;
 
@@ -905,7 +1029,7 @@ impl MutationRoot {
             payload.normalize();
 
             let vector = Vector {
-                id: uuid::Uuid::new_v4().to_string(),
+                id: gql_ctx.store.new_vector_id(&collection_name),
                 data: embedding,
                 sparse: None,
                 payload: Some(payload),
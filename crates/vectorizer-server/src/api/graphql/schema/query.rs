@@ -10,7 +10,7 @@ use tracing::{info, warn};
 use vectorizer::hub::auth::TenantContext;
 
 use super::super::types::*;
-use super::{GraphQLContext, check_collection_ownership, load_file_upload_config};
+use super::{GraphQLContext, check_collection_ownership, load_file_upload_config, payload_view};
 
 pub struct QueryRoot;
 
@@ -86,7 +86,10 @@ impl QueryRoot {
         check_collection_ownership(&gql_ctx.store, &collection, tenant_ctx)?;
 
         match gql_ctx.store.get_vector(&collection, &id) {
-            Ok(v) => Ok(Some(v.into())),
+            Ok(mut v) => {
+                payload_view(ctx).redact(&collection, &mut v.payload);
+                Ok(Some(v.into()))
+            }
             Err(_) => Ok(None), // Vector not found
         }
     }
@@ -111,6 +114,7 @@ impl QueryRoot {
 
         let all_vectors = collection_ref.get_all_vectors();
         let total_count = all_vectors.len() as i32;
+        let view = payload_view(ctx);
 
         // Apply cursor-based pagination
         let offset = input
@@ -124,7 +128,10 @@ impl QueryRoot {
             .into_iter()
             .skip(offset)
             .take(limit)
-            .map(|v| v.into())
+            .map(|mut v| {
+                view.redact(&input.collection, &mut v.payload);
+                v.into()
+            })
             .collect();
 
         let has_next_page = offset + items.len() < total_count as usize;
@@ -154,10 +161,11 @@ impl QueryRoot {
         // Verify ownership
         check_collection_ownership(&gql_ctx.store, &input.collection, tenant_ctx)?;
 
-        let results = gql_ctx
+        let mut results = gql_ctx
             .store
             .search(&input.collection, &input.vector, input.limit as usize)
             .map_err(|e| async_graphql::Error::new(format!("Search failed: {e}")))?;
+        payload_view(ctx).redact_results(&input.collection, &mut results);
 
         // Apply score threshold filter if specified
         let filtered: Vec<GqlSearchResult> = results
@@ -0,0 +1,144 @@
+//! `SubscriptionRoot` — pushed over the GraphQL WebSocket at
+//! `/graphql/ws`, so clients such as the dashboard do not have to poll
+//! the REST progress endpoints.
+
+use std::collections::{BTreeMap, HashSet};
+use std::time::Duration;
+
+use async_graphql::{Context, Subscription};
+use futures::Stream;
+use tokio::time::MissedTickBehavior;
+use vectorizer::db::{StartupProgress, VectorStore};
+use vectorizer::hub::auth::TenantContext;
+
+use super::super::types::*;
+use super::GraphQLContext;
+
+/// Bounds on the `intervalMs` a subscriber may ask for
+const MIN_INTERVAL_MS: i32 = 100;
+const MAX_INTERVAL_MS: i32 = 60_000;
+
+pub struct SubscriptionRoot;
+
+#[Subscription]
+impl SubscriptionRoot {
+    /// Indexing progress (filtered by tenant in multi-tenant mode), sent
+    /// on subscribe and then whenever it changes. Changes are looked for
+    /// every `intervalMs` milliseconds (default 1000, 100 to 60000).
+    async fn indexing_progress(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 1000)] interval_ms: i32,
+    ) -> async_graphql::Result<impl Stream<Item = GqlIndexingProgress>> {
+        let gql_ctx = ctx.data::<GraphQLContext>()?;
+        let owner = match ctx.data_opt::<TenantContext>() {
+            Some(tenant) => Some(
+                uuid::Uuid::parse_str(&tenant.tenant_id)
+                    .map_err(|e| async_graphql::Error::new(format!("Invalid tenant ID: {e}")))?,
+            ),
+            None => None,
+        };
+        let store = gql_ctx.store.clone();
+        let progress = gql_ctx.startup_progress.clone();
+
+        let period = interval_ms.clamp(MIN_INTERVAL_MS, MAX_INTERVAL_MS) as u64;
+        let mut ticks = tokio::time::interval(Duration::from_millis(period));
+        ticks.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+        Ok(futures::stream::unfold(
+            (ticks, None::<GqlIndexingProgress>),
+            move |(mut ticks, last)| {
+                let store = store.clone();
+                let progress = progress.clone();
+                async move {
+                    loop {
+                        ticks.tick().await;
+                        let snapshot = indexing_progress(&store, &progress, owner.as_ref());
+                        if last.as_ref() != Some(&snapshot) {
+                            return Some((snapshot.clone(), (ticks, Some(snapshot))));
+                        }
+                    }
+                }
+            },
+        ))
+    }
+}
+
+/// Indexing progress of the collections `owner` can see (all of them
+/// without an owner): the startup load's view of each collection it has
+/// seen, and collections created since then as indexed.
+fn indexing_progress(
+    store: &VectorStore,
+    progress: &StartupProgress,
+    owner: Option<&uuid::Uuid>,
+) -> GqlIndexingProgress {
+    let report = progress.report();
+    let visible: HashSet<String> = match owner {
+        Some(owner) => store.list_collections_for_owner(owner),
+        None => store.list_collections(),
+    }
+    .into_iter()
+    .collect();
+
+    let mut collections: BTreeMap<String, GqlCollectionProgress> = report
+        .collections
+        .into_iter()
+        .filter(|(name, _)| owner.is_none() || visible.contains(name))
+        .map(|(name, entry)| {
+            let collection = GqlCollectionProgress {
+                name: name.clone(),
+                state: entry.state.into(),
+                vectors: entry.vectors.map(|v| v as i64),
+                error: entry.error,
+                elapsed_ms: entry.elapsed_ms.map(|ms| ms as i64),
+            };
+            (name, collection)
+        })
+        .collect();
+    for name in visible {
+        if collections.contains_key(&name) {
+            continue;
+        }
+        let vectors = store
+            .get_collection_metadata(&name)
+            .ok()
+            .map(|meta| meta.vector_count as i64);
+        collections.insert(
+            name.clone(),
+            GqlCollectionProgress {
+                name,
+                state: GqlIndexingState::Indexed,
+                vectors,
+                error: None,
+                elapsed_ms: None,
+            },
+        );
+    }
+
+    let mut snapshot = GqlIndexingProgress {
+        phase: report.phase.into(),
+        complete: report.complete,
+        error: report.error,
+        pending_collections: 0,
+        loading_collections: 0,
+        indexed_collections: 0,
+        failed_collections: 0,
+        deferred_collections: 0,
+        vectors_indexed: 0,
+        collections: Vec::with_capacity(collections.len()),
+    };
+    for collection in collections.into_values() {
+        match collection.state {
+            GqlIndexingState::Pending => snapshot.pending_collections += 1,
+            GqlIndexingState::Loading => snapshot.loading_collections += 1,
+            GqlIndexingState::Indexed => {
+                snapshot.indexed_collections += 1;
+                snapshot.vectors_indexed += collection.vectors.unwrap_or(0);
+            }
+            GqlIndexingState::Failed => snapshot.failed_collections += 1,
+            GqlIndexingState::Deferred => snapshot.deferred_collections += 1,
+        }
+        snapshot.collections.push(collection);
+    }
+    snapshot
+}
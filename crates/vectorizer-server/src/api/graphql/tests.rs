@@ -821,6 +821,152 @@ mod schema_tests {
             "Deep query should work within limit"
         );
     }
+
+    #[tokio::test]
+    async fn test_batch_vector_mutations() {
+        let (schema, _temp_dir) = create_test_schema();
+
+        schema
+            .execute(
+                r#"mutation { createCollection(input: { name: "batch", dimension: 3 }) { name } }"#,
+            )
+            .await;
+
+        let result = schema
+            .execute(
+                r#"
+                mutation {
+                    upsertVectors(input: {
+                        collection: "batch"
+                        vectors: [
+                            { id: "a", data: [1.0, 0.0, 0.0], payload: { tag: "x" } }
+                            { id: "b", data: [0.0, 1.0, 0.0] }
+                            { id: "c", data: [0.0, 0.0, 1.0] }
+                        ]
+                    }) { success affectedCount }
+                }
+            "#,
+            )
+            .await;
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        let data = result.data.into_json().unwrap();
+        assert_eq!(data["upsertVectors"]["affectedCount"], 3);
+
+        let result = schema
+            .execute(
+                r#"
+                mutation {
+                    updateVectors(input: {
+                        collection: "batch"
+                        vectors: [
+                            { id: "a", payload: { tag: "y" } }
+                            { id: "b", data: [0.0, 0.6, 0.8] }
+                        ]
+                    }) { success affectedCount }
+                }
+            "#,
+            )
+            .await;
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        let data = result.data.into_json().unwrap();
+        assert_eq!(data["updateVectors"]["affectedCount"], 2);
+
+        let result = schema
+            .execute(r#"{ vector(collection: "batch", id: "a") { data payload } }"#)
+            .await;
+        let data = result.data.into_json().unwrap();
+        assert_eq!(data["vector"]["payload"]["tag"], "y");
+        assert_eq!(data["vector"]["data"][0], 1.0);
+
+        // An unknown ID fails the whole batch before anything is written
+        let result = schema
+            .execute(
+                r#"
+                mutation {
+                    updateVectors(input: {
+                        collection: "batch"
+                        vectors: [
+                            { id: "c", payload: { tag: "z" } }
+                            { id: "missing", payload: { tag: "z" } }
+                        ]
+                    }) { success }
+                }
+            "#,
+            )
+            .await;
+        assert!(!result.errors.is_empty());
+        let result = schema
+            .execute(r#"{ vector(collection: "batch", id: "c") { payload } }"#)
+            .await;
+        let data = result.data.into_json().unwrap();
+        assert!(data["vector"]["payload"].is_null());
+
+        let result = schema
+            .execute(
+                r#"
+                mutation {
+                    deleteVectors(collection: "batch", ids: ["a", "c", "missing"]) {
+                        success affectedCount message
+                    }
+                }
+            "#,
+            )
+            .await;
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        let data = result.data.into_json().unwrap();
+        assert_eq!(data["deleteVectors"]["affectedCount"], 2);
+        assert_eq!(data["deleteVectors"]["message"], "Not found: missing");
+
+        let result = schema
+            .execute(r#"{ collection(name: "batch") { vectorCount } }"#)
+            .await;
+        let data = result.data.into_json().unwrap();
+        assert_eq!(data["collection"]["vectorCount"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_indexing_progress_subscription() {
+        use futures::StreamExt;
+
+        let (schema, _temp_dir) = create_test_schema();
+        schema
+            .execute(r#"mutation { createCollection(input: { name: "progress", dimension: 3 }) { name } }"#)
+            .await;
+
+        let mut stream = schema.execute_stream(
+            r#"
+            subscription {
+                indexingProgress(intervalMs: 100) {
+                    phase
+                    complete
+                    indexedCollections
+                    collections { name state vectors }
+                }
+            }
+        "#,
+        );
+
+        let first = stream.next().await.unwrap();
+        assert!(first.errors.is_empty(), "{:?}", first.errors);
+        let data = first.data.into_json().unwrap();
+        let progress = &data["indexingProgress"];
+        assert_eq!(progress["phase"], "COMPLETE");
+        assert_eq!(progress["complete"], true);
+        assert_eq!(progress["indexedCollections"], 1);
+        assert_eq!(progress["collections"][0]["name"], "progress");
+        assert_eq!(progress["collections"][0]["state"], "INDEXED");
+        assert_eq!(progress["collections"][0]["vectors"], 0);
+
+        // The next event is sent once something changes
+        schema
+            .execute(
+                r#"mutation { upsertVector(collection: "progress", input: { id: "v", data: [1.0, 0.0, 0.0] }) { id } }"#,
+            )
+            .await;
+        let next = stream.next().await.unwrap();
+        let data = next.data.into_json().unwrap();
+        assert_eq!(data["indexingProgress"]["collections"][0]["vectors"], 1);
+    }
 }
 
 #[cfg(test)]
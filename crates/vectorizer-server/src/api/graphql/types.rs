@@ -300,6 +300,10 @@ pub enum GqlRelationshipType {
     Contains,
     /// Document is derived from another document
     DerivedFrom,
+    /// Document mentions an extracted entity
+    Mentions,
+    /// Extracted entities are related
+    RelatedTo,
 }
 
 impl From<vectorizer::db::graph::RelationshipType> for GqlRelationshipType {
@@ -311,6 +315,8 @@ impl From<vectorizer::db::graph::RelationshipType> for GqlRelationshipType {
             vectorizer::db::graph::RelationshipType::DerivedFrom => {
                 GqlRelationshipType::DerivedFrom
             }
+            vectorizer::db::graph::RelationshipType::Mentions => GqlRelationshipType::Mentions,
+            vectorizer::db::graph::RelationshipType::RelatedTo => GqlRelationshipType::RelatedTo,
         }
     }
 }
@@ -324,6 +330,8 @@ impl From<GqlRelationshipType> for vectorizer::db::graph::RelationshipType {
             GqlRelationshipType::DerivedFrom => {
                 vectorizer::db::graph::RelationshipType::DerivedFrom
             }
+            GqlRelationshipType::Mentions => vectorizer::db::graph::RelationshipType::Mentions,
+            GqlRelationshipType::RelatedTo => vectorizer::db::graph::RelationshipType::RelatedTo,
         }
     }
 }
@@ -532,6 +540,35 @@ pub struct UpsertVectorsInput {
     pub public_key: Option<String>,
 }
 
+/// Input for updating one vector; `data`, `payload` or both replace the
+/// stored values
+#[derive(InputObject, Clone, Debug)]
+pub struct UpdateVectorInput {
+    /// Vector unique identifier
+    pub id: String,
+    /// New vector data
+    #[graphql(default)]
+    pub data: Option<Vec<f32>>,
+    /// New payload as JSON
+    #[graphql(default)]
+    pub payload: Option<async_graphql::Json<JsonValue>>,
+    /// Optional ECC public key for payload encryption (PEM/hex/base64 format)
+    #[graphql(default, name = "publicKey")]
+    pub public_key: Option<String>,
+}
+
+/// Input for batch updating vectors
+#[derive(InputObject, Clone, Debug)]
+pub struct UpdateVectorsInput {
+    /// Collection name
+    pub collection: String,
+    /// Vectors to update
+    pub vectors: Vec<UpdateVectorInput>,
+    /// Optional ECC public key for payload encryption (applies to all vectors unless overridden)
+    #[graphql(default, name = "publicKey")]
+    pub public_key: Option<String>,
+}
+
 /// Input for semantic search
 #[derive(InputObject, Clone, Debug)]
 pub struct SearchInput {
@@ -849,3 +886,98 @@ pub struct GqlFileUploadConfig {
     /// List of allowed file extensions
     pub allowed_extensions: Vec<String>,
 }
+
+// =============================================================================
+// INDEXING PROGRESS TYPES
+// =============================================================================
+
+/// Step the startup load is on
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum GqlStartupPhase {
+    /// The load has not started
+    Starting,
+    /// Restoring collections from the data directory
+    LoadingPersisted,
+    /// Indexing workspace collections that were not persisted
+    IndexingWorkspace,
+    /// Every collection is indexed or failed
+    Complete,
+}
+
+impl From<vectorizer::db::StartupPhase> for GqlStartupPhase {
+    fn from(phase: vectorizer::db::StartupPhase) -> Self {
+        match phase {
+            vectorizer::db::StartupPhase::Starting => GqlStartupPhase::Starting,
+            vectorizer::db::StartupPhase::LoadingPersisted => GqlStartupPhase::LoadingPersisted,
+            vectorizer::db::StartupPhase::IndexingWorkspace => GqlStartupPhase::IndexingWorkspace,
+            vectorizer::db::StartupPhase::Complete => GqlStartupPhase::Complete,
+        }
+    }
+}
+
+/// Where a collection is in indexing
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum GqlIndexingState {
+    /// Known but not picked up yet
+    Pending,
+    /// Being restored or indexed
+    Loading,
+    /// Serving queries
+    Indexed,
+    /// Could not be loaded
+    Failed,
+    /// Left on disk until first access
+    Deferred,
+}
+
+impl From<vectorizer::db::CollectionLoadState> for GqlIndexingState {
+    fn from(state: vectorizer::db::CollectionLoadState) -> Self {
+        match state {
+            vectorizer::db::CollectionLoadState::Pending => GqlIndexingState::Pending,
+            vectorizer::db::CollectionLoadState::Loading => GqlIndexingState::Loading,
+            vectorizer::db::CollectionLoadState::Indexed => GqlIndexingState::Indexed,
+            vectorizer::db::CollectionLoadState::Failed => GqlIndexingState::Failed,
+            vectorizer::db::CollectionLoadState::Deferred => GqlIndexingState::Deferred,
+        }
+    }
+}
+
+/// Indexing progress of one collection
+#[derive(SimpleObject, Clone, Debug, PartialEq)]
+pub struct GqlCollectionProgress {
+    /// Collection name
+    pub name: String,
+    /// Current state
+    pub state: GqlIndexingState,
+    /// Vectors in the collection, when known
+    pub vectors: Option<i64>,
+    /// Why the load failed
+    pub error: Option<String>,
+    /// Time spent loading, once indexed or failed
+    pub elapsed_ms: Option<i64>,
+}
+
+/// Indexing progress across collections
+#[derive(SimpleObject, Clone, Debug, PartialEq)]
+pub struct GqlIndexingProgress {
+    /// Step the startup load is on
+    pub phase: GqlStartupPhase,
+    /// Whether the startup load has finished
+    pub complete: bool,
+    /// Why the startup load stopped early
+    pub error: Option<String>,
+    /// Collections not picked up yet
+    pub pending_collections: i32,
+    /// Collections being restored or indexed
+    pub loading_collections: i32,
+    /// Collections serving queries
+    pub indexed_collections: i32,
+    /// Collections that could not be loaded
+    pub failed_collections: i32,
+    /// Collections left on disk until first access
+    pub deferred_collections: i32,
+    /// Vectors in indexed collections
+    pub vectors_indexed: i64,
+    /// Every collection, by name
+    pub collections: Vec<GqlCollectionProgress>,
+}
@@ -5,7 +5,7 @@
 
 #![allow(clippy::uninlined_format_args)]
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use tracing::{error, info, warn};
 use vectorizer::config::VectorizerConfig;
 use vectorizer_server::server::{RootUserConfig, VectorizerServer};
@@ -65,6 +65,30 @@ struct Cli {
     /// the original ephemeral-writable-layer trap this addresses.
     #[arg(long, env = "VECTORIZER_DATA_DIR")]
     data_dir: Option<String>,
+
+    /// Run a throwaway conformance server instead: in-memory store, no
+    /// auth, config.yml ignored, plus the SDK parity suite at
+    /// `GET /conformance/suite` and `POST /conformance/reset`. State
+    /// lives in a temporary directory unless `--data-dir` is given.
+    /// Listens on 127.0.0.1:15002 unless `--host` / `--port` say
+    /// otherwise.
+    #[arg(long)]
+    conformance: bool,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run only the MCP server, without the HTTP/gRPC listeners
+    Mcp {
+        /// Speak MCP over stdin/stdout against the local data directory
+        /// (`vectorizer.vecdb`), for editors that spawn MCP servers as
+        /// subprocesses. Changes are saved when stdin closes.
+        #[arg(long)]
+        stdio: bool,
+    },
 }
 
 /// Load configuration from config.yml, creating with defaults if not exists
@@ -293,7 +317,17 @@ async fn main() -> anyhow::Result<()> {
     // store, snapshots, fastembed cache — resolves to the same path.
     // Without this, only `vectorizer.rs` would see the flag and the
     // rest of the engine would silently fall back to the XDG default.
-    if let Some(ref dir) = cli.data_dir {
+    // Conformance runs never touch the real data directory
+    let conformance_dir = match (cli.conformance, &cli.data_dir) {
+        (true, None) => Some(tempfile::tempdir()?),
+        _ => None,
+    };
+    let data_dir = cli
+        .data_dir
+        .as_ref()
+        .map(std::path::PathBuf::from)
+        .or_else(|| conformance_dir.as_ref().map(|d| d.path().to_path_buf()));
+    if let Some(ref dir) = data_dir {
         // SAFETY: this runs once, before any worker threads are
         // spawned (Tokio runtime hasn't started yet), so the Rust
         // 2024 cross-thread-env-mutation guard is satisfied.
@@ -307,6 +341,19 @@ async fn main() -> anyhow::Result<()> {
         }
     }
 
+    if cli.conformance {
+        let host = cli.host.unwrap_or_else(|| "127.0.0.1".to_string());
+        let port = cli.port.unwrap_or(15002);
+        return vectorizer_server::server::conformance::serve(&host, port).await;
+    }
+
+    if let Some(Command::Mcp { stdio }) = cli.command {
+        if !stdio {
+            anyhow::bail!("`vectorizer mcp` needs --stdio; the HTTP server serves MCP at /mcp");
+        }
+        return vectorizer_server::server::serve_mcp_stdio(&cli.config).await;
+    }
+
     // Load configuration from config.yml first
     let config = load_config(&cli.config);
 
@@ -354,7 +401,12 @@ async fn main() -> anyhow::Result<()> {
     let server = VectorizerServer::new_with_root_config(root_config).await?;
 
     // Start the server (this will block)
-    if let Err(e) = server.start(&host, port).await {
+    let result = server.start(&host, port).await;
+
+    // Flush spans still queued for the OTLP collector
+    vectorizer::monitoring::telemetry::shutdown();
+
+    if let Err(e) = result {
         error!("❌ Server failed: {}", e);
         std::process::exit(1);
     }
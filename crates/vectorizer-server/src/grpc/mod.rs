@@ -9,6 +9,9 @@
 // for the orphan rule.
 pub mod server;
 
+// API-key collection namespace checks shared by both service families.
+pub mod namespace;
+
 // Generated proto modules now live in the `vectorizer-protocol` crate
 // (phase4_split-vectorizer-workspace, sub-phase 2). Re-exported here so
 // existing consumers (`use vectorizer::grpc::vectorizer::*` etc.) keep
@@ -0,0 +1,94 @@
+//! API-key collection namespaces on the gRPC services.
+//!
+//! The server's namespace layer resolves the API key a call presents in
+//! its `authorization` or `x-api-key` metadata and, when the key is
+//! confined to a namespace, attaches it to the request. The handlers
+//! here refuse collections outside it and filter listings to the
+//! visible set. The same layer attaches the caller's payload redaction
+//! view, which handlers apply to every payload they return.
+
+use tonic::{Request, Status};
+use vectorizer::auth::CollectionNamespace;
+use vectorizer::security::PayloadView;
+
+/// The namespace the caller's API key is confined to, if any.
+pub fn request_namespace<T>(request: &Request<T>) -> Option<CollectionNamespace> {
+    request.extensions().get::<CollectionNamespace>().cloned()
+}
+
+/// How payloads are redacted for the caller. Without a view (redaction
+/// disabled) payloads are returned as stored.
+pub fn payload_view<T>(request: &Request<T>) -> PayloadView {
+    request
+        .extensions()
+        .get::<PayloadView>()
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Refuse `collection` when it is outside `namespace`.
+pub fn check_collection(
+    namespace: Option<&CollectionNamespace>,
+    collection: &str,
+) -> Result<(), Status> {
+    match namespace {
+        Some(namespace) => namespace
+            .check(collection)
+            .map_err(|e| Status::permission_denied(e.to_string())),
+        None => Ok(()),
+    }
+}
+
+/// Refuse calls that span every collection (full-storage snapshots)
+/// from a caller confined to a namespace.
+pub fn refuse_namespaced<T>(request: &Request<T>) -> Result<(), Status> {
+    match request_namespace(request) {
+        Some(namespace) => Err(Status::permission_denied(format!(
+            "not available to API keys confined to namespace '{}'",
+            namespace
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Unwrap a request that names one collection, refusing it when the
+/// collection is outside the caller's namespace.
+pub fn namespaced<T>(
+    request: Request<T>,
+    collection: impl FnOnce(&T) -> &str,
+) -> Result<T, Status> {
+    let namespace = request_namespace(&request);
+    let inner = request.into_inner();
+    check_collection(namespace.as_ref(), collection(&inner))?;
+    Ok(inner)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requests_outside_the_namespace_are_refused() {
+        let mut request = Request::new("team-b/docs".to_string());
+        request
+            .extensions_mut()
+            .insert(CollectionNamespace::parse("team-a/*").unwrap());
+        let status = namespaced(request, |name| name.as_str()).unwrap_err();
+        assert_eq!(status.code(), tonic::Code::PermissionDenied);
+
+        let mut request = Request::new("team-a/docs".to_string());
+        request
+            .extensions_mut()
+            .insert(CollectionNamespace::parse("team-a/*").unwrap());
+        assert!(refuse_namespaced(&request).is_err());
+        assert_eq!(
+            namespaced(request, |name| name.as_str()).unwrap(),
+            "team-a/docs"
+        );
+
+        let request = Request::new("anything".to_string());
+        assert!(refuse_namespaced(&request).is_ok());
+        assert!(namespaced(request, |name| name.as_str()).is_ok());
+    }
+}
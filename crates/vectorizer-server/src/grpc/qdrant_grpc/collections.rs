@@ -8,6 +8,7 @@ use tonic::{Request, Response, Status};
 use tracing::{error, info};
 
 use super::QdrantGrpcService;
+use crate::grpc::namespace::{check_collection, namespaced, request_namespace};
 use crate::grpc::qdrant_proto::collections_server::Collections;
 use crate::grpc::qdrant_proto::*;
 
@@ -22,7 +23,7 @@ impl Collections for QdrantGrpcService {
         request: Request<GetCollectionInfoRequest>,
     ) -> Result<Response<GetCollectionInfoResponse>, Status> {
         let start = Instant::now();
-        let req = request.into_inner();
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         info!(collection = %req.collection_name, "Qdrant gRPC: Get collection info");
 
         let collection = self
@@ -97,15 +98,17 @@ impl Collections for QdrantGrpcService {
 
     async fn list(
         &self,
-        _request: Request<ListCollectionsRequest>,
+        request: Request<ListCollectionsRequest>,
     ) -> Result<Response<ListCollectionsResponse>, Status> {
         let start = Instant::now();
         info!("Qdrant gRPC: List collections");
 
+        let namespace = request_namespace(&request);
         let collections: Vec<CollectionDescription> = self
             .store
             .list_collections()
             .into_iter()
+            .filter(|name| namespace.as_ref().is_none_or(|ns| ns.contains(name)))
             .map(|name| CollectionDescription { name })
             .collect();
 
@@ -122,7 +125,7 @@ impl Collections for QdrantGrpcService {
         request: Request<CreateCollection>,
     ) -> Result<Response<CollectionOperationResponse>, Status> {
         let start = Instant::now();
-        let req = request.into_inner();
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         info!(collection = %req.collection_name, "Qdrant gRPC: Create collection");
 
         // Extract dimension from vectors config
@@ -188,7 +191,7 @@ impl Collections for QdrantGrpcService {
         request: Request<UpdateCollection>,
     ) -> Result<Response<CollectionOperationResponse>, Status> {
         let start = Instant::now();
-        let req = request.into_inner();
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         info!(collection = %req.collection_name, "Qdrant gRPC: Update collection");
 
         // Verify collection exists
@@ -208,7 +211,7 @@ impl Collections for QdrantGrpcService {
         request: Request<DeleteCollection>,
     ) -> Result<Response<CollectionOperationResponse>, Status> {
         let start = Instant::now();
-        let req = request.into_inner();
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         info!(collection = %req.collection_name, "Qdrant gRPC: Delete collection");
 
         self.store
@@ -226,9 +229,27 @@ impl Collections for QdrantGrpcService {
         request: Request<ChangeAliases>,
     ) -> Result<Response<CollectionOperationResponse>, Status> {
         let start = Instant::now();
+        let namespace = request_namespace(&request);
         let req = request.into_inner();
         info!("Qdrant gRPC: Update aliases");
 
+        // A namespaced key may only alias inside its namespace, or an
+        // alias would reach a collection outside it
+        for action in req.actions.iter().filter_map(|a| a.action.as_ref()) {
+            let names = match action {
+                alias_operations::Action::CreateAlias(create) => {
+                    vec![&create.alias_name, &create.collection_name]
+                }
+                alias_operations::Action::DeleteAlias(delete) => vec![&delete.alias_name],
+                alias_operations::Action::RenameAlias(rename) => {
+                    vec![&rename.old_alias_name, &rename.new_alias_name]
+                }
+            };
+            for name in names {
+                check_collection(namespace.as_ref(), name)?;
+            }
+        }
+
         for action in req.actions {
             if let Some(action_type) = action.action {
                 match action_type {
@@ -267,7 +288,7 @@ impl Collections for QdrantGrpcService {
         request: Request<ListCollectionAliasesRequest>,
     ) -> Result<Response<ListAliasesResponse>, Status> {
         let start = Instant::now();
-        let req = request.into_inner();
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         info!(collection = %req.collection_name, "Qdrant gRPC: List collection aliases");
 
         let aliases: Vec<AliasDescription> = self
@@ -289,15 +310,17 @@ impl Collections for QdrantGrpcService {
 
     async fn list_aliases(
         &self,
-        _request: Request<ListAliasesRequest>,
+        request: Request<ListAliasesRequest>,
     ) -> Result<Response<ListAliasesResponse>, Status> {
         let start = Instant::now();
         info!("Qdrant gRPC: List all aliases");
 
+        let namespace = request_namespace(&request);
         let aliases: Vec<AliasDescription> = self
             .store
             .list_aliases()
             .into_iter()
+            .filter(|(_, collection)| namespace.as_ref().is_none_or(|ns| ns.contains(collection)))
             .map(|(alias, collection)| AliasDescription {
                 alias_name: alias,
                 collection_name: collection,
@@ -314,7 +337,7 @@ impl Collections for QdrantGrpcService {
         &self,
         request: Request<CollectionClusterInfoRequest>,
     ) -> Result<Response<CollectionClusterInfoResponse>, Status> {
-        let req = request.into_inner();
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         info!(collection = %req.collection_name, "Qdrant gRPC: Get collection cluster info");
 
         let _collection = self
@@ -342,7 +365,7 @@ impl Collections for QdrantGrpcService {
         request: Request<CollectionExistsRequest>,
     ) -> Result<Response<CollectionExistsResponse>, Status> {
         let start = Instant::now();
-        let req = request.into_inner();
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
 
         let exists = self.store.get_collection(&req.collection_name).is_ok();
 
@@ -356,7 +379,7 @@ impl Collections for QdrantGrpcService {
         &self,
         request: Request<UpdateCollectionClusterSetupRequest>,
     ) -> Result<Response<UpdateCollectionClusterSetupResponse>, Status> {
-        let req = request.into_inner();
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         info!(collection = %req.collection_name, "Qdrant gRPC: Update collection cluster setup");
 
         let _collection = self
@@ -373,7 +396,7 @@ impl Collections for QdrantGrpcService {
         &self,
         request: Request<CreateShardKeyRequest>,
     ) -> Result<Response<CreateShardKeyResponse>, Status> {
-        let req = request.into_inner();
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         info!(collection = %req.collection_name, "Qdrant gRPC: Create shard key");
 
         let _collection = self
@@ -388,7 +411,7 @@ impl Collections for QdrantGrpcService {
         &self,
         request: Request<DeleteShardKeyRequest>,
     ) -> Result<Response<DeleteShardKeyResponse>, Status> {
-        let req = request.into_inner();
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         info!(collection = %req.collection_name, "Qdrant gRPC: Delete shard key");
 
         let _collection = self
@@ -0,0 +1,617 @@
+//! Qdrant gRPC `Filter` translation and evaluation.
+//!
+//! A request's filter is translated once into a [`PointFilter`] and then
+//! checked against every candidate point. Payload field conditions
+//! (`match`, `range`, geo, `values_count`) become the same
+//! [`QdrantCondition`]s the REST API evaluates through
+//! [`FilterProcessor`]; the conditions that look at more than one payload
+//! value or at the point itself (`has_id`, `has_vector`, `is_empty`,
+//! `is_null`, `datetime_range`, `nested`, `min_should`) are evaluated
+//! here. A condition Vectorizer cannot evaluate is refused with
+//! `invalid_argument`: dropping it would silently widen the result set.
+
+use std::collections::HashSet;
+
+use ::vectorizer::models::Payload;
+use ::vectorizer::models::qdrant::filter::{
+    QdrantCondition, QdrantFilter, QdrantGeoBoundingBox, QdrantGeoPoint, QdrantGeoRadius,
+    QdrantMatchValue, QdrantRange, QdrantTextMatch, QdrantTextMatchType, QdrantValuesCount,
+};
+use ::vectorizer::models::qdrant::filter_processor::FilterProcessor;
+use serde_json::Value;
+use tonic::Status;
+
+use crate::grpc::qdrant_proto::r#match::MatchValue;
+use crate::grpc::qdrant_proto::*;
+
+/// A translated Qdrant gRPC filter.
+#[derive(Debug, Default)]
+pub(super) struct PointFilter {
+    must: Vec<PointCondition>,
+    should: Vec<PointCondition>,
+    must_not: Vec<PointCondition>,
+    /// `min_should`: at least `.1` of `.0` must hold
+    min_should: Option<(Vec<PointCondition>, usize)>,
+}
+
+#[derive(Debug)]
+enum PointCondition {
+    /// A payload condition, wrapped as a one-condition `must` filter
+    Payload(QdrantFilter),
+    Datetime {
+        key: String,
+        range: DatetimeBounds,
+    },
+    IsEmpty(String),
+    IsNull(String),
+    HasId(HashSet<String>),
+    /// Points carry a single unnamed dense vector, so `has_vector` is
+    /// decided by the name alone
+    HasVector(bool),
+    /// Holds when any object under `key` matches `filter`
+    Nested {
+        key: String,
+        filter: PointFilter,
+    },
+    Filter(PointFilter),
+}
+
+/// Datetime range bounds, in nanoseconds since the Unix epoch.
+#[derive(Debug)]
+struct DatetimeBounds {
+    lt: Option<i128>,
+    gt: Option<i128>,
+    gte: Option<i128>,
+    lte: Option<i128>,
+}
+
+impl PointFilter {
+    /// Translate a gRPC filter, refusing conditions that cannot be
+    /// evaluated.
+    pub(super) fn from_grpc(filter: &Filter) -> Result<Self, Status> {
+        let min_should = match &filter.min_should {
+            Some(min_should) => Some((
+                convert_conditions(&min_should.conditions)?,
+                usize::try_from(min_should.min_count).unwrap_or(usize::MAX),
+            )),
+            None => None,
+        };
+        Ok(Self {
+            must: convert_conditions(&filter.must)?,
+            should: convert_conditions(&filter.should)?,
+            must_not: convert_conditions(&filter.must_not)?,
+            min_should,
+        })
+    }
+
+    /// Whether the point `id` with `payload` passes the filter.
+    pub(super) fn matches(&self, id: &str, payload: Option<&Payload>) -> bool {
+        match payload {
+            Some(payload) => self.matches_payload(id, payload),
+            None => self.matches_payload(id, &Payload::new(Value::Object(Default::default()))),
+        }
+    }
+
+    fn matches_payload(&self, id: &str, payload: &Payload) -> bool {
+        let holds = |condition: &PointCondition| condition.matches(id, payload);
+        self.must.iter().all(holds)
+            && !self.must_not.iter().any(holds)
+            && (self.should.is_empty() || self.should.iter().any(holds))
+            && self
+                .min_should
+                .as_ref()
+                .is_none_or(|(conditions, min_count)| {
+                    conditions.iter().filter(|&c| holds(c)).count() >= *min_count
+                })
+    }
+}
+
+impl PointCondition {
+    fn matches(&self, id: &str, payload: &Payload) -> bool {
+        match self {
+            Self::Payload(filter) => FilterProcessor::apply_filter(filter, payload),
+            Self::Datetime { key, range } => {
+                field_value(payload, key).is_some_and(|value| range.contains(value))
+            }
+            Self::IsEmpty(key) => match field_value(payload, key) {
+                None | Some(Value::Null) => true,
+                Some(Value::Array(items)) => items.iter().all(Value::is_null),
+                Some(_) => false,
+            },
+            Self::IsNull(key) => matches!(field_value(payload, key), Some(Value::Null)),
+            Self::HasId(ids) => ids.contains(id),
+            Self::HasVector(present) => *present,
+            Self::Nested { key, filter } => match field_value(payload, key) {
+                Some(Value::Array(items)) => items
+                    .iter()
+                    .any(|item| filter.matches_payload(id, &Payload::new(item.clone()))),
+                Some(item @ Value::Object(_)) => {
+                    filter.matches_payload(id, &Payload::new(item.clone()))
+                }
+                _ => false,
+            },
+            Self::Filter(filter) => filter.matches_payload(id, payload),
+        }
+    }
+}
+
+impl DatetimeBounds {
+    /// Whether `value` (an RFC 3339 string, or an array of them) falls
+    /// in the range.
+    fn contains(&self, value: &Value) -> bool {
+        let at = match value {
+            Value::String(s) => match chrono::DateTime::parse_from_rfc3339(s) {
+                Ok(at) => {
+                    i128::from(at.timestamp()) * 1_000_000_000
+                        + i128::from(at.timestamp_subsec_nanos())
+                }
+                Err(_) => return false,
+            },
+            Value::Array(items) => return items.iter().any(|item| self.contains(item)),
+            _ => return false,
+        };
+        self.lt.is_none_or(|lt| at < lt)
+            && self.gt.is_none_or(|gt| at > gt)
+            && self.gte.is_none_or(|gte| at >= gte)
+            && self.lte.is_none_or(|lte| at <= lte)
+    }
+}
+
+/// The string form a point id is stored under.
+pub(super) fn point_id_string(id: &PointId) -> Option<String> {
+    match id.point_id_options.as_ref()? {
+        point_id::PointIdOptions::Num(n) => Some(n.to_string()),
+        point_id::PointIdOptions::Uuid(u) => Some(u.clone()),
+    }
+}
+
+fn convert_conditions(conditions: &[Condition]) -> Result<Vec<PointCondition>, Status> {
+    conditions.iter().map(convert_condition).collect()
+}
+
+fn convert_condition(condition: &Condition) -> Result<PointCondition, Status> {
+    use condition::ConditionOneOf;
+
+    match &condition.condition_one_of {
+        Some(ConditionOneOf::Field(field)) => convert_field_condition(field),
+        Some(ConditionOneOf::IsEmpty(is_empty)) => {
+            Ok(PointCondition::IsEmpty(is_empty.key.clone()))
+        }
+        Some(ConditionOneOf::IsNull(is_null)) => Ok(PointCondition::IsNull(is_null.key.clone())),
+        Some(ConditionOneOf::HasId(has_id)) => Ok(PointCondition::HasId(
+            has_id.has_id.iter().filter_map(point_id_string).collect(),
+        )),
+        Some(ConditionOneOf::HasVector(has_vector)) => {
+            Ok(PointCondition::HasVector(has_vector.has_vector.is_empty()))
+        }
+        Some(ConditionOneOf::Nested(nested)) => Ok(PointCondition::Nested {
+            key: nested.key.clone(),
+            filter: match &nested.filter {
+                Some(filter) => PointFilter::from_grpc(filter)?,
+                None => PointFilter::default(),
+            },
+        }),
+        Some(ConditionOneOf::Filter(filter)) => {
+            Ok(PointCondition::Filter(PointFilter::from_grpc(filter)?))
+        }
+        None => Err(Status::invalid_argument("Filter condition is empty")),
+    }
+}
+
+/// Translate a field condition; every clause it sets must hold.
+fn convert_field_condition(field: &FieldCondition) -> Result<PointCondition, Status> {
+    let key = &field.key;
+    let payload = |condition: QdrantCondition| {
+        PointCondition::Payload(QdrantFilter {
+            must: Some(vec![condition]),
+            should: None,
+            must_not: None,
+        })
+    };
+    let mut clauses = Vec::new();
+
+    if let Some(match_value) = field.r#match.as_ref().and_then(|m| m.match_value.as_ref()) {
+        clauses.push(convert_match(key, match_value));
+    }
+    if let Some(r) = &field.range {
+        clauses.push(payload(QdrantCondition::Range {
+            key: key.clone(),
+            range: QdrantRange {
+                gt: r.gt,
+                gte: r.gte,
+                lt: r.lt,
+                lte: r.lte,
+            },
+        }));
+    }
+    if let Some(bbox) = &field.geo_bounding_box {
+        let (Some(top_left), Some(bottom_right)) = (&bbox.top_left, &bbox.bottom_right) else {
+            return Err(Status::invalid_argument(format!(
+                "geo_bounding_box on '{key}' needs top_left and bottom_right"
+            )));
+        };
+        clauses.push(payload(QdrantCondition::GeoBoundingBox {
+            key: key.clone(),
+            geo_bounding_box: QdrantGeoBoundingBox {
+                top_right: QdrantGeoPoint::new(top_left.lat, bottom_right.lon),
+                bottom_left: QdrantGeoPoint::new(bottom_right.lat, top_left.lon),
+            },
+        }));
+    }
+    if let Some(radius) = &field.geo_radius {
+        let Some(center) = &radius.center else {
+            return Err(Status::invalid_argument(format!(
+                "geo_radius on '{key}' needs a center"
+            )));
+        };
+        clauses.push(payload(QdrantCondition::GeoRadius {
+            key: key.clone(),
+            geo_radius: QdrantGeoRadius {
+                center: QdrantGeoPoint::new(center.lat, center.lon),
+                radius: f64::from(radius.radius),
+            },
+        }));
+    }
+    if let Some(count) = &field.values_count {
+        let bound = |v: Option<u64>| v.map(|v| u32::try_from(v).unwrap_or(u32::MAX));
+        clauses.push(payload(QdrantCondition::ValuesCount {
+            key: key.clone(),
+            values_count: QdrantValuesCount {
+                gt: bound(count.gt),
+                gte: bound(count.gte),
+                lt: bound(count.lt),
+                lte: bound(count.lte),
+            },
+        }));
+    }
+    if field.geo_polygon.is_some() {
+        return Err(Status::invalid_argument(format!(
+            "geo_polygon filters are not supported (field '{key}')"
+        )));
+    }
+    if let Some(r) = &field.datetime_range {
+        let nanos = |t: &Option<prost_types::Timestamp>| {
+            t.as_ref()
+                .map(|t| i128::from(t.seconds) * 1_000_000_000 + i128::from(t.nanos))
+        };
+        clauses.push(PointCondition::Datetime {
+            key: key.clone(),
+            range: DatetimeBounds {
+                lt: nanos(&r.lt),
+                gt: nanos(&r.gt),
+                gte: nanos(&r.gte),
+                lte: nanos(&r.lte),
+            },
+        });
+    }
+    if let Some(is_empty) = field.is_empty {
+        clauses.push(negated_unless(
+            is_empty,
+            PointCondition::IsEmpty(key.clone()),
+        ));
+    }
+    if let Some(is_null) = field.is_null {
+        clauses.push(negated_unless(is_null, PointCondition::IsNull(key.clone())));
+    }
+
+    match clauses.len() {
+        0 => Err(Status::invalid_argument(format!(
+            "Field condition on '{key}' sets no condition"
+        ))),
+        1 => Ok(clauses.remove(0)),
+        _ => Ok(PointCondition::Filter(PointFilter {
+            must: clauses,
+            ..Default::default()
+        })),
+    }
+}
+
+/// Translate a `match` clause. The multi-value forms become `should`
+/// (any of) and `must_not` (except) sub-filters.
+fn convert_match(key: &str, match_value: &MatchValue) -> PointCondition {
+    let condition = |match_value: QdrantMatchValue| {
+        PointCondition::Payload(QdrantFilter {
+            must: Some(vec![QdrantCondition::Match {
+                key: key.to_string(),
+                match_value,
+            }]),
+            should: None,
+            must_not: None,
+        })
+    };
+    let contains = |text: &str| {
+        condition(QdrantMatchValue::Text(QdrantTextMatch {
+            text: text.to_string(),
+            match_type: QdrantTextMatchType::Contains,
+        }))
+    };
+    let any_of = |conditions: Vec<PointCondition>| {
+        PointCondition::Filter(PointFilter {
+            should: conditions,
+            ..Default::default()
+        })
+    };
+    let none_of = |conditions: Vec<PointCondition>| {
+        PointCondition::Filter(PointFilter {
+            must_not: conditions,
+            ..Default::default()
+        })
+    };
+
+    match match_value {
+        MatchValue::Keyword(s) => condition(QdrantMatchValue::String(s.clone())),
+        MatchValue::Integer(i) => condition(QdrantMatchValue::Integer(*i)),
+        MatchValue::Boolean(b) => condition(QdrantMatchValue::Bool(*b)),
+        // Without a full-text index, text and phrase match as substrings
+        MatchValue::Text(t) | MatchValue::Phrase(t) => contains(t),
+        MatchValue::TextAny(t) => any_of(t.split_whitespace().map(contains).collect()),
+        MatchValue::Keywords(kw) => any_of(
+            kw.strings
+                .iter()
+                .map(|s| condition(QdrantMatchValue::String(s.clone())))
+                .collect(),
+        ),
+        MatchValue::Integers(ints) => any_of(
+            ints.integers
+                .iter()
+                .map(|i| condition(QdrantMatchValue::Integer(*i)))
+                .collect(),
+        ),
+        MatchValue::ExceptKeywords(kw) => none_of(
+            kw.strings
+                .iter()
+                .map(|s| condition(QdrantMatchValue::String(s.clone())))
+                .collect(),
+        ),
+        MatchValue::ExceptIntegers(ints) => none_of(
+            ints.integers
+                .iter()
+                .map(|i| condition(QdrantMatchValue::Integer(*i)))
+                .collect(),
+        ),
+    }
+}
+
+/// `condition` when `expected`, otherwise its negation.
+fn negated_unless(expected: bool, condition: PointCondition) -> PointCondition {
+    if expected {
+        condition
+    } else {
+        PointCondition::Filter(PointFilter {
+            must_not: vec![condition],
+            ..Default::default()
+        })
+    }
+}
+
+/// The payload value at a dot-separated `key`.
+fn field_value<'a>(payload: &'a Payload, key: &str) -> Option<&'a Value> {
+    key.split('.')
+        .try_fold(&payload.data, |value, part| value.as_object()?.get(part))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn field(key: &str, field: FieldCondition) -> Condition {
+        Condition {
+            condition_one_of: Some(condition::ConditionOneOf::Field(FieldCondition {
+                key: key.to_string(),
+                ..field
+            })),
+        }
+    }
+
+    fn keyword(key: &str, value: &str) -> Condition {
+        field(
+            key,
+            FieldCondition {
+                r#match: Some(Match {
+                    match_value: Some(MatchValue::Keyword(value.to_string())),
+                }),
+                ..Default::default()
+            },
+        )
+    }
+
+    fn range(key: &str, range: Range) -> Condition {
+        field(
+            key,
+            FieldCondition {
+                range: Some(range),
+                ..Default::default()
+            },
+        )
+    }
+
+    fn payload(data: Value) -> Payload {
+        Payload::new(data)
+    }
+
+    #[test]
+    fn must_should_and_must_not_combine() {
+        let filter = PointFilter::from_grpc(&Filter {
+            must: vec![range(
+                "price",
+                Range {
+                    gte: Some(10.0),
+                    lt: Some(100.0),
+                    ..Default::default()
+                },
+            )],
+            should: vec![keyword("color", "red"), keyword("color", "blue")],
+            must_not: vec![keyword("status", "sold")],
+            min_should: None,
+        })
+        .unwrap();
+
+        let red = payload(json!({"price": 50, "color": "red", "status": "new"}));
+        let green = payload(json!({"price": 50, "color": "green"}));
+        let sold = payload(json!({"price": 50, "color": "blue", "status": "sold"}));
+        let cheap = payload(json!({"price": 5, "color": "red"}));
+        assert!(filter.matches("1", Some(&red)));
+        assert!(!filter.matches("2", Some(&green)));
+        assert!(!filter.matches("3", Some(&sold)));
+        assert!(!filter.matches("4", Some(&cheap)));
+        assert!(!filter.matches("5", None));
+    }
+
+    #[test]
+    fn multi_value_matches_are_any_of_and_except() {
+        let any_of = PointFilter::from_grpc(&Filter {
+            must: vec![field(
+                "tags",
+                FieldCondition {
+                    r#match: Some(Match {
+                        match_value: Some(MatchValue::Keywords(RepeatedStrings {
+                            strings: vec!["rust".into(), "go".into()],
+                        })),
+                    }),
+                    ..Default::default()
+                },
+            )],
+            ..Default::default()
+        })
+        .unwrap();
+        let except = PointFilter::from_grpc(&Filter {
+            must: vec![field(
+                "year",
+                FieldCondition {
+                    r#match: Some(Match {
+                        match_value: Some(MatchValue::ExceptIntegers(RepeatedIntegers {
+                            integers: vec![2020, 2021],
+                        })),
+                    }),
+                    ..Default::default()
+                },
+            )],
+            ..Default::default()
+        })
+        .unwrap();
+
+        let rust = payload(json!({"tags": ["python", "go"], "year": 2024}));
+        let java = payload(json!({"tags": ["java"], "year": 2021}));
+        assert!(any_of.matches("1", Some(&rust)));
+        assert!(!any_of.matches("2", Some(&java)));
+        assert!(except.matches("1", Some(&rust)));
+        assert!(!except.matches("2", Some(&java)));
+    }
+
+    #[test]
+    fn point_level_conditions() {
+        let filter = PointFilter::from_grpc(&Filter {
+            must: vec![
+                Condition {
+                    condition_one_of: Some(condition::ConditionOneOf::HasId(HasIdCondition {
+                        has_id: vec![
+                            PointId {
+                                point_id_options: Some(point_id::PointIdOptions::Num(7)),
+                            },
+                            PointId {
+                                point_id_options: Some(point_id::PointIdOptions::Uuid(
+                                    "a".to_string(),
+                                )),
+                            },
+                        ],
+                    })),
+                },
+                Condition {
+                    condition_one_of: Some(condition::ConditionOneOf::IsEmpty(IsEmptyCondition {
+                        key: "notes".to_string(),
+                    })),
+                },
+            ],
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert!(filter.matches("7", Some(&payload(json!({"notes": []})))));
+        assert!(filter.matches("a", None));
+        assert!(!filter.matches("8", None));
+        assert!(!filter.matches("7", Some(&payload(json!({"notes": ["x"]})))));
+    }
+
+    #[test]
+    fn nested_and_datetime_conditions() {
+        let nested = PointFilter::from_grpc(&Filter {
+            must: vec![Condition {
+                condition_one_of: Some(condition::ConditionOneOf::Nested(NestedCondition {
+                    key: "reviews".to_string(),
+                    filter: Some(Filter {
+                        must: vec![
+                            keyword("author", "ana"),
+                            range(
+                                "stars",
+                                Range {
+                                    gte: Some(4.0),
+                                    ..Default::default()
+                                },
+                            ),
+                        ],
+                        ..Default::default()
+                    }),
+                })),
+            }],
+            ..Default::default()
+        })
+        .unwrap();
+        // The author and the stars must come from the same review
+        let split = payload(json!({"reviews": [
+            {"author": "ana", "stars": 2},
+            {"author": "bo", "stars": 5}
+        ]}));
+        let same = payload(json!({"reviews": [{"author": "ana", "stars": 5}]}));
+        assert!(!nested.matches("1", Some(&split)));
+        assert!(nested.matches("2", Some(&same)));
+
+        let since_2024 = PointFilter::from_grpc(&Filter {
+            must: vec![field(
+                "created_at",
+                FieldCondition {
+                    datetime_range: Some(DatetimeRange {
+                        gte: Some(prost_types::Timestamp {
+                            seconds: 1_704_067_200,
+                            nanos: 0,
+                        }),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+            )],
+            ..Default::default()
+        })
+        .unwrap();
+        let new = payload(json!({"created_at": "2024-03-01T12:00:00Z"}));
+        let old = payload(json!({"created_at": "2023-12-31T23:59:59Z"}));
+        assert!(since_2024.matches("1", Some(&new)));
+        assert!(!since_2024.matches("2", Some(&old)));
+    }
+
+    #[test]
+    fn unsupported_and_empty_conditions_are_refused() {
+        let polygon = Filter {
+            must: vec![field(
+                "area",
+                FieldCondition {
+                    geo_polygon: Some(GeoPolygon::default()),
+                    ..Default::default()
+                },
+            )],
+            ..Default::default()
+        };
+        let status = PointFilter::from_grpc(&polygon).unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+        let empty = Filter {
+            must: vec![Condition {
+                condition_one_of: None,
+            }],
+            ..Default::default()
+        };
+        assert!(PointFilter::from_grpc(&empty).is_err());
+    }
+}
@@ -12,17 +12,12 @@
 use std::sync::Arc;
 use std::time::Instant;
 
-use ::vectorizer::models::qdrant::filter::{
-    QdrantCondition, QdrantFilter, QdrantMatchValue, QdrantRange,
-};
-use ::vectorizer::models::qdrant::filter_processor::FilterProcessor;
-use ::vectorizer::models::{Payload, Vector};
+use ::vectorizer::models::Vector;
 use tonic::{Request, Response, Status};
-use tracing::{debug, error, info};
+use tracing::{debug, error, field, info, info_span};
 use vectorizer::VectorStore;
 
 use crate::grpc::qdrant_proto::collections_server::Collections;
-use crate::grpc::qdrant_proto::r#match::MatchValue;
 use crate::grpc::qdrant_proto::points_server::Points;
 use crate::grpc::qdrant_proto::snapshots_server::Snapshots;
 use crate::grpc::qdrant_proto::*;
@@ -37,6 +32,9 @@ pub struct QdrantGrpcService {
     /// collection's in-flight depth past `upsert_queue_hard_limit` is
     /// refused with `Status::resource_exhausted`.
     pub(super) upsert_queue: Arc<vectorizer::db::UpsertQueue>,
+    /// HiveHub integration; upserts are admitted against the tenant
+    /// quotas of the collection's owner when present.
+    pub(super) hub_manager: Option<Arc<vectorizer::hub::HubManager>>,
 }
 
 impl QdrantGrpcService {
@@ -45,6 +43,7 @@ impl QdrantGrpcService {
             store,
             snapshot_manager: None,
             upsert_queue,
+            hub_manager: None,
         }
     }
 
@@ -57,201 +56,49 @@ impl QdrantGrpcService {
             store,
             snapshot_manager: Some(snapshot_manager),
             upsert_queue,
+            hub_manager: None,
         }
     }
-}
-
-// ============================================================================
-// Filter Conversion Functions
-// ============================================================================
-
-/// Convert gRPC Filter to internal QdrantFilter
-fn convert_grpc_filter(filter: &Filter) -> QdrantFilter {
-    let must = if filter.must.is_empty() {
-        None
-    } else {
-        Some(
-            filter
-                .must
-                .iter()
-                .filter_map(convert_grpc_condition)
-                .collect(),
-        )
-    };
-
-    let must_not = if filter.must_not.is_empty() {
-        None
-    } else {
-        Some(
-            filter
-                .must_not
-                .iter()
-                .filter_map(convert_grpc_condition)
-                .collect(),
-        )
-    };
-
-    let should = if filter.should.is_empty() {
-        None
-    } else {
-        Some(
-            filter
-                .should
-                .iter()
-                .filter_map(convert_grpc_condition)
-                .collect(),
-        )
-    };
 
-    QdrantFilter {
-        must,
-        must_not,
-        should,
-    }
-}
-
-/// Convert gRPC Condition to internal QdrantCondition
-fn convert_grpc_condition(condition: &Condition) -> Option<QdrantCondition> {
-    use condition::ConditionOneOf;
-
-    match &condition.condition_one_of {
-        Some(ConditionOneOf::Field(field)) => convert_field_condition(field),
-        Some(ConditionOneOf::Filter(nested_filter)) => Some(QdrantCondition::Nested {
-            filter: Box::new(convert_grpc_filter(nested_filter)),
-        }),
-        Some(ConditionOneOf::IsEmpty(is_empty)) => {
-            // IsEmpty checks if field is empty/doesn't exist
-            Some(QdrantCondition::Match {
-                key: is_empty.key.clone(),
-                match_value: QdrantMatchValue::Any,
-            })
-        }
-        Some(ConditionOneOf::IsNull(is_null)) => {
-            // IsNull checks if field is null
-            Some(QdrantCondition::Match {
-                key: is_null.key.clone(),
-                match_value: QdrantMatchValue::Any,
-            })
-        }
-        Some(ConditionOneOf::HasId(_has_id)) => {
-            // HasId is handled separately in the calling code
-            None
-        }
-        Some(ConditionOneOf::Nested(nested)) => {
-            nested.filter.as_ref().map(|f| QdrantCondition::Nested {
-                filter: Box::new(convert_grpc_filter(f)),
-            })
-        }
-        Some(ConditionOneOf::HasVector(_)) => {
-            // HasVector condition - not applicable for filter-based operations
-            None
-        }
-        None => None,
+    /// Enforce the tenant quotas of `hub_manager` on upserts.
+    pub fn with_hub_manager(
+        mut self,
+        hub_manager: Option<Arc<vectorizer::hub::HubManager>>,
+    ) -> Self {
+        self.hub_manager = hub_manager;
+        self
     }
 }
 
-/// Convert gRPC FieldCondition to internal QdrantCondition
-fn convert_field_condition(field: &FieldCondition) -> Option<QdrantCondition> {
-    let key = field.key.clone();
-
-    // Check match field first
-    if let Some(m) = &field.r#match {
-        if let Some(match_value) = &m.match_value {
-            return match match_value {
-                MatchValue::Keyword(s) => Some(QdrantCondition::Match {
-                    key,
-                    match_value: QdrantMatchValue::String(s.clone()),
-                }),
-                MatchValue::Integer(i) => Some(QdrantCondition::Match {
-                    key,
-                    match_value: QdrantMatchValue::Integer(*i),
-                }),
-                MatchValue::Boolean(b) => Some(QdrantCondition::Match {
-                    key,
-                    match_value: QdrantMatchValue::Bool(*b),
-                }),
-                MatchValue::Text(t) => Some(QdrantCondition::Match {
-                    key,
-                    match_value: QdrantMatchValue::String(t.clone()),
-                }),
-                MatchValue::Keywords(kw) => {
-                    // Match any of the keywords
-                    kw.strings.first().map(|first| QdrantCondition::Match {
-                        key,
-                        match_value: QdrantMatchValue::String(first.clone()),
-                    })
-                }
-                MatchValue::Integers(ints) => {
-                    ints.integers.first().map(|first| QdrantCondition::Match {
-                        key,
-                        match_value: QdrantMatchValue::Integer(*first),
-                    })
-                }
-                MatchValue::ExceptIntegers(_) => None,
-                MatchValue::ExceptKeywords(_) => None,
-                MatchValue::Phrase(p) => Some(QdrantCondition::Match {
-                    key,
-                    match_value: QdrantMatchValue::String(p.clone()),
-                }),
-                MatchValue::TextAny(t) => Some(QdrantCondition::Match {
-                    key,
-                    match_value: QdrantMatchValue::String(t.clone()),
-                }),
-            };
-        }
-    }
-
-    // Check range field
-    if let Some(r) = &field.range {
-        return Some(QdrantCondition::Range {
-            key,
-            range: QdrantRange {
-                lt: r.lt,
-                lte: r.lte,
-                gt: r.gt,
-                gte: r.gte,
-            },
-        });
-    }
-
-    // Geo conditions not yet fully supported
-    if field.geo_bounding_box.is_some() || field.geo_radius.is_some() || field.geo_polygon.is_some()
-    {
-        return None;
-    }
-
-    // Values count not yet fully supported
-    if field.values_count.is_some() {
-        return None;
-    }
-
-    // Datetime range not yet fully supported
-    if field.datetime_range.is_some() {
-        return None;
-    }
+// ============================================================================
+// Filtering
+// ============================================================================
 
-    None
-}
+/// Candidates a filtered search first asks the index for, per result
+const FILTER_OVERSAMPLE: usize = 4;
 
 /// Get vector IDs that match a filter from collection
 fn get_matching_vector_ids(
     collection: &vectorizer::db::vector_store::CollectionType,
-    filter: &QdrantFilter,
+    filter: &PointFilter,
 ) -> Vec<String> {
     let all_vectors = collection.get_all_vectors();
     let total_count = all_vectors.len();
+    let span = info_span!(
+        "filter.evaluate",
+        candidates = total_count,
+        matched = field::Empty
+    )
+    .entered();
     let mut matching_ids = Vec::new();
 
     for vector in all_vectors {
-        let payload = vector.payload.as_ref().cloned().unwrap_or_else(|| Payload {
-            data: serde_json::json!({}),
-        });
-
-        if FilterProcessor::apply_filter(filter, &payload) {
+        if filter.matches(&vector.id, vector.payload.as_ref()) {
             matching_ids.push(vector.id.clone());
         }
     }
 
+    span.record("matched", matching_ids.len());
     debug!(
         "Filter matched {} vectors out of {}",
         matching_ids.len(),
@@ -260,6 +107,50 @@ fn get_matching_vector_ids(
     matching_ids
 }
 
+/// Search `collection` for the `limit` best points after `offset` that
+/// reach `score_threshold` and pass `filter`.
+///
+/// A filtered search oversamples the index and, while too few candidates
+/// pass, doubles the candidate count up to the whole collection, so a
+/// selective filter still fills the page instead of post-filtering a
+/// fixed top-k down to nothing.
+fn search_points(
+    collection: &vectorizer::db::vector_store::CollectionType,
+    vector: &[f32],
+    limit: usize,
+    offset: usize,
+    score_threshold: Option<f32>,
+    filter: Option<&PointFilter>,
+) -> Result<Vec<vectorizer::models::SearchResult>, Status> {
+    let wanted = limit.saturating_add(offset);
+    if wanted == 0 {
+        return Ok(Vec::new());
+    }
+    let total = collection.vector_count();
+    let mut candidates = match filter {
+        Some(_) => wanted
+            .saturating_mul(FILTER_OVERSAMPLE)
+            .min(total.max(wanted)),
+        None => wanted,
+    };
+
+    loop {
+        let results = collection
+            .search(vector, candidates)
+            .map_err(|e| Status::internal(format!("Search failed: {}", e)))?;
+        let exhausted = results.len() < candidates || candidates >= total;
+        let hits: Vec<_> = results
+            .into_iter()
+            .filter(|r| score_threshold.is_none_or(|threshold| r.score >= threshold))
+            .filter(|r| filter.is_none_or(|f| f.matches(&r.id, r.payload.as_ref())))
+            .collect();
+        if hits.len() >= wanted || exhausted {
+            return Ok(hits.into_iter().skip(offset).take(limit).collect());
+        }
+        candidates = candidates.saturating_mul(2).min(total);
+    }
+}
+
 // ============================================================================
 // Sub-modules — one per gRPC trait (phase3_split-qdrant-grpc).
 // The trait impls live in collections.rs / points.rs / snapshots.rs; this
@@ -268,9 +159,12 @@ fn get_matching_vector_ids(
 // ============================================================================
 
 pub mod collections;
+mod filter;
 pub mod points;
 pub mod snapshots;
 
+use filter::{PointFilter, point_id_string};
+
 // ============================================================================
 // Helper Functions
 // ============================================================================
@@ -13,9 +13,10 @@ use tonic::{Request, Response, Status};
 use tracing::{debug, error, info};
 
 use super::{
-    QdrantGrpcService, convert_grpc_filter, convert_json_to_payload, convert_payload_to_json,
-    get_matching_vector_ids,
+    PointFilter, QdrantGrpcService, convert_json_to_payload, convert_payload_to_json,
+    get_matching_vector_ids, point_id_string, search_points,
 };
+use crate::grpc::namespace::{namespaced, payload_view};
 use crate::grpc::qdrant_proto::r#match::MatchValue;
 use crate::grpc::qdrant_proto::points_server::Points;
 use crate::grpc::qdrant_proto::*;
@@ -31,7 +32,7 @@ impl Points for QdrantGrpcService {
         request: Request<UpsertPoints>,
     ) -> Result<Response<PointsOperationResponse>, Status> {
         let start = Instant::now();
-        let req = request.into_inner();
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         info!(collection = %req.collection_name, "Qdrant gRPC: Upsert points");
 
         // Issue #263: per-collection admission. Held until the end of
@@ -53,6 +54,13 @@ impl Points for QdrantGrpcService {
                 return Err(status);
             }
         };
+        let _tenant_admission = match &self.hub_manager {
+            Some(hub_manager) => hub_manager
+                .admit_insert(&self.store, None, &req.collection_name, req.points.len())
+                .await
+                .map_err(Status::from)?,
+            None => ::vectorizer::hub::WriteAdmission::default(),
+        };
 
         let mut collection = self
             .store
@@ -96,6 +104,9 @@ impl Points for QdrantGrpcService {
             }
         }
 
+        drop(collection);
+        self.store.notify_mutation(&req.collection_name);
+
         Ok(Response::new(PointsOperationResponse {
             result: Some(UpdateResult {
                 operation_id: Some(0),
@@ -111,7 +122,7 @@ impl Points for QdrantGrpcService {
         request: Request<DeletePoints>,
     ) -> Result<Response<PointsOperationResponse>, Status> {
         let start = Instant::now();
-        let req = request.into_inner();
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         info!(collection = %req.collection_name, "Qdrant gRPC: Delete points");
 
         let mut collection = self
@@ -132,7 +143,7 @@ impl Points for QdrantGrpcService {
                     }
                 }
                 Some(points_selector::PointsSelectorOneOf::Filter(filter)) => {
-                    let internal_filter = convert_grpc_filter(&filter);
+                    let internal_filter = PointFilter::from_grpc(&filter)?;
                     let matching_ids = get_matching_vector_ids(&*collection, &internal_filter);
                     info!(
                         "Filter-based deletion: {} vectors matched",
@@ -146,6 +157,9 @@ impl Points for QdrantGrpcService {
             }
         }
 
+        drop(collection);
+        self.store.notify_mutation(&req.collection_name);
+
         Ok(Response::new(PointsOperationResponse {
             result: Some(UpdateResult {
                 operation_id: Some(0),
@@ -158,7 +172,8 @@ impl Points for QdrantGrpcService {
 
     async fn get(&self, request: Request<GetPoints>) -> Result<Response<GetResponse>, Status> {
         let start = Instant::now();
-        let req = request.into_inner();
+        let view = payload_view(&request);
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         info!(collection = %req.collection_name, "Qdrant gRPC: Get points");
 
         let collection = self
@@ -184,7 +199,8 @@ impl Points for QdrantGrpcService {
                 None => continue,
             };
 
-            if let Ok(vec) = collection.get_vector(&id) {
+            if let Ok(mut vec) = collection.get_vector(&id) {
+                view.redact(&req.collection_name, &mut vec.payload);
                 let empty_json = serde_json::Value::Object(serde_json::Map::new());
                 let payload_json = vec.payload.as_ref().map(|p| &p.data).unwrap_or(&empty_json);
 
@@ -231,7 +247,7 @@ impl Points for QdrantGrpcService {
         request: Request<UpdatePointVectors>,
     ) -> Result<Response<PointsOperationResponse>, Status> {
         let start = Instant::now();
-        let req = request.into_inner();
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         info!(collection = %req.collection_name, "Qdrant gRPC: Update vectors");
 
         let mut collection = self
@@ -270,6 +286,9 @@ impl Points for QdrantGrpcService {
             let _ = collection.update_vector(vec);
         }
 
+        drop(collection);
+        self.store.notify_mutation(&req.collection_name);
+
         Ok(Response::new(PointsOperationResponse {
             result: Some(UpdateResult {
                 operation_id: Some(0),
@@ -285,7 +304,7 @@ impl Points for QdrantGrpcService {
         request: Request<DeletePointVectors>,
     ) -> Result<Response<PointsOperationResponse>, Status> {
         let start = Instant::now();
-        let req = request.into_inner();
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         info!(collection = %req.collection_name, "Qdrant gRPC: Delete vectors");
 
         Ok(Response::new(PointsOperationResponse {
@@ -303,7 +322,7 @@ impl Points for QdrantGrpcService {
         request: Request<SetPayloadPoints>,
     ) -> Result<Response<PointsOperationResponse>, Status> {
         let start = Instant::now();
-        let req = request.into_inner();
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         info!(collection = %req.collection_name, "Qdrant gRPC: Set payload");
 
         let mut collection = self
@@ -348,7 +367,7 @@ impl Points for QdrantGrpcService {
                     }
                 }
                 Some(points_selector::PointsSelectorOneOf::Filter(filter)) => {
-                    let internal_filter = convert_grpc_filter(&filter);
+                    let internal_filter = PointFilter::from_grpc(&filter)?;
                     let matching_ids = get_matching_vector_ids(&*collection, &internal_filter);
                     info!(
                         "Filter-based payload update: {} vectors matched",
@@ -383,6 +402,9 @@ impl Points for QdrantGrpcService {
             }
         }
 
+        drop(collection);
+        self.store.notify_mutation(&req.collection_name);
+
         Ok(Response::new(PointsOperationResponse {
             result: Some(UpdateResult {
                 operation_id: Some(0),
@@ -398,7 +420,7 @@ impl Points for QdrantGrpcService {
         request: Request<SetPayloadPoints>,
     ) -> Result<Response<PointsOperationResponse>, Status> {
         let start = Instant::now();
-        let req = request.into_inner();
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         info!(collection = %req.collection_name, "Qdrant gRPC: Overwrite payload");
 
         let mut collection = self
@@ -431,7 +453,7 @@ impl Points for QdrantGrpcService {
                     }
                 }
                 Some(points_selector::PointsSelectorOneOf::Filter(filter)) => {
-                    let internal_filter = convert_grpc_filter(&filter);
+                    let internal_filter = PointFilter::from_grpc(&filter)?;
                     let matching_ids = get_matching_vector_ids(&*collection, &internal_filter);
                     info!(
                         "Filter-based payload overwrite: {} vectors matched",
@@ -454,6 +476,9 @@ impl Points for QdrantGrpcService {
             }
         }
 
+        drop(collection);
+        self.store.notify_mutation(&req.collection_name);
+
         Ok(Response::new(PointsOperationResponse {
             result: Some(UpdateResult {
                 operation_id: Some(0),
@@ -469,7 +494,7 @@ impl Points for QdrantGrpcService {
         request: Request<DeletePayloadPoints>,
     ) -> Result<Response<PointsOperationResponse>, Status> {
         let start = Instant::now();
-        let req = request.into_inner();
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         info!(collection = %req.collection_name, "Qdrant gRPC: Delete payload keys");
 
         let mut collection = self
@@ -509,7 +534,7 @@ impl Points for QdrantGrpcService {
                     }
                 }
                 Some(points_selector::PointsSelectorOneOf::Filter(filter)) => {
-                    let internal_filter = convert_grpc_filter(&filter);
+                    let internal_filter = PointFilter::from_grpc(&filter)?;
                     let matching_ids = get_matching_vector_ids(&*collection, &internal_filter);
                     info!(
                         "Filter-based payload key deletion: {} vectors matched",
@@ -541,6 +566,9 @@ impl Points for QdrantGrpcService {
             }
         }
 
+        drop(collection);
+        self.store.notify_mutation(&req.collection_name);
+
         Ok(Response::new(PointsOperationResponse {
             result: Some(UpdateResult {
                 operation_id: Some(0),
@@ -556,7 +584,7 @@ impl Points for QdrantGrpcService {
         request: Request<ClearPayloadPoints>,
     ) -> Result<Response<PointsOperationResponse>, Status> {
         let start = Instant::now();
-        let req = request.into_inner();
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         info!(collection = %req.collection_name, "Qdrant gRPC: Clear payload");
 
         let mut collection = self
@@ -581,7 +609,7 @@ impl Points for QdrantGrpcService {
                     }
                 }
                 Some(points_selector::PointsSelectorOneOf::Filter(filter)) => {
-                    let internal_filter = convert_grpc_filter(&filter);
+                    let internal_filter = PointFilter::from_grpc(&filter)?;
                     let matching_ids = get_matching_vector_ids(&*collection, &internal_filter);
                     info!(
                         "Filter-based payload clear: {} vectors matched",
@@ -598,6 +626,9 @@ impl Points for QdrantGrpcService {
             }
         }
 
+        drop(collection);
+        self.store.notify_mutation(&req.collection_name);
+
         Ok(Response::new(PointsOperationResponse {
             result: Some(UpdateResult {
                 operation_id: Some(0),
@@ -613,7 +644,7 @@ impl Points for QdrantGrpcService {
         request: Request<CreateFieldIndexCollection>,
     ) -> Result<Response<PointsOperationResponse>, Status> {
         let start = Instant::now();
-        let req = request.into_inner();
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         info!(collection = %req.collection_name, field = %req.field_name, "Qdrant gRPC: Create field index");
 
         let _collection = self
@@ -636,7 +667,7 @@ impl Points for QdrantGrpcService {
         request: Request<DeleteFieldIndexCollection>,
     ) -> Result<Response<PointsOperationResponse>, Status> {
         let start = Instant::now();
-        let req = request.into_inner();
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         info!(collection = %req.collection_name, field = %req.field_name, "Qdrant gRPC: Delete field index");
 
         let _collection = self
@@ -659,7 +690,8 @@ impl Points for QdrantGrpcService {
         request: Request<SearchPoints>,
     ) -> Result<Response<SearchResponse>, Status> {
         let start = Instant::now();
-        let req = request.into_inner();
+        let view = payload_view(&request);
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         info!(collection = %req.collection_name, "Qdrant gRPC: Search points");
 
         let collection = self
@@ -672,10 +704,21 @@ impl Points for QdrantGrpcService {
             .with_payload
             .map(|w| w.selector_options.is_some())
             .unwrap_or(true);
-
-        let results = collection
-            .search(&req.vector, limit)
-            .map_err(|e| Status::internal(format!("Search failed: {}", e)))?;
+        let filter = req
+            .filter
+            .as_ref()
+            .map(PointFilter::from_grpc)
+            .transpose()?;
+
+        let mut results = search_points(
+            &collection,
+            &req.vector,
+            limit,
+            req.offset.unwrap_or(0) as usize,
+            req.score_threshold,
+            filter.as_ref(),
+        )?;
+        view.redact_results(&req.collection_name, &mut results);
 
         let scored_points: Vec<ScoredPoint> = results
             .into_iter()
@@ -711,7 +754,8 @@ impl Points for QdrantGrpcService {
         request: Request<SearchBatchPoints>,
     ) -> Result<Response<SearchBatchResponse>, Status> {
         let start = Instant::now();
-        let req = request.into_inner();
+        let view = payload_view(&request);
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         info!(collection = %req.collection_name, "Qdrant gRPC: Search batch");
 
         let collection = self
@@ -728,9 +772,21 @@ impl Points for QdrantGrpcService {
                 .map(|w| w.selector_options.is_some())
                 .unwrap_or(true);
 
-            let results = collection
-                .search(&search_req.vector, limit)
-                .map_err(|e| Status::internal(format!("Search failed: {}", e)))?;
+            let filter = search_req
+                .filter
+                .as_ref()
+                .map(PointFilter::from_grpc)
+                .transpose()?;
+
+            let mut results = search_points(
+                &collection,
+                &search_req.vector,
+                limit,
+                search_req.offset.unwrap_or(0) as usize,
+                search_req.score_threshold,
+                filter.as_ref(),
+            )?;
+            view.redact_results(&req.collection_name, &mut results);
 
             let scored_points: Vec<ScoredPoint> = results
                 .into_iter()
@@ -771,7 +827,8 @@ impl Points for QdrantGrpcService {
         request: Request<SearchPointGroups>,
     ) -> Result<Response<SearchGroupsResponse>, Status> {
         let start = Instant::now();
-        let req = request.into_inner();
+        let view = payload_view(&request);
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         info!(collection = %req.collection_name, "Qdrant gRPC: Search groups");
 
         let collection = self
@@ -783,9 +840,11 @@ impl Points for QdrantGrpcService {
         let group_by = req.group_by;
         let group_size = req.group_size as usize;
 
-        let results = collection
+        let mut results = collection
             .search(&req.vector, limit * group_size)
             .map_err(|e| Status::internal(format!("Search failed: {}", e)))?;
+        // Before grouping, so group keys never expose a redacted field
+        view.redact_results(&req.collection_name, &mut results);
 
         let mut groups: std::collections::HashMap<String, Vec<ScoredPoint>> =
             std::collections::HashMap::new();
@@ -845,7 +904,8 @@ impl Points for QdrantGrpcService {
         request: Request<ScrollPoints>,
     ) -> Result<Response<ScrollResponse>, Status> {
         let start = Instant::now();
-        let req = request.into_inner();
+        let view = payload_view(&request);
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         info!(collection = %req.collection_name, "Qdrant gRPC: Scroll points");
 
         let collection = self
@@ -863,12 +923,38 @@ impl Points for QdrantGrpcService {
             .map(|w| w.selector_options.is_some())
             .unwrap_or(false);
 
-        let all_vectors = collection.get_all_vectors();
+        let filter = req
+            .filter
+            .as_ref()
+            .map(PointFilter::from_grpc)
+            .transpose()?;
+        let matching: Vec<_> = collection
+            .get_all_vectors()
+            .into_iter()
+            .filter(|v| {
+                filter
+                    .as_ref()
+                    .is_none_or(|f| f.matches(&v.id, v.payload.as_ref()))
+            })
+            .collect();
 
-        let points: Vec<RetrievedPoint> = all_vectors
+        // `offset` is the first point of the page; an unknown one starts over
+        let start_index = req
+            .offset
+            .as_ref()
+            .and_then(point_id_string)
+            .and_then(|offset| matching.iter().position(|v| v.id == offset))
+            .unwrap_or(0);
+        let next_page_offset = matching.get(start_index + limit).map(|v| PointId {
+            point_id_options: Some(point_id::PointIdOptions::Uuid(v.id.clone())),
+        });
+
+        let points: Vec<RetrievedPoint> = matching
             .into_iter()
+            .skip(start_index)
             .take(limit)
-            .map(|v| {
+            .map(|mut v| {
+                view.redact(&req.collection_name, &mut v.payload);
                 let payload_map = v
                     .payload
                     .as_ref()
@@ -906,12 +992,6 @@ impl Points for QdrantGrpcService {
             })
             .collect();
 
-        let next_page_offset = if points.len() >= limit {
-            points.last().and_then(|p| p.id.clone())
-        } else {
-            None
-        };
-
         Ok(Response::new(ScrollResponse {
             result: points,
             next_page_offset,
@@ -925,7 +1005,8 @@ impl Points for QdrantGrpcService {
         request: Request<RecommendPoints>,
     ) -> Result<Response<RecommendResponse>, Status> {
         let start = Instant::now();
-        let req = request.into_inner();
+        let view = payload_view(&request);
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         info!(collection = %req.collection_name, "Qdrant gRPC: Recommend points");
 
         let collection = self
@@ -963,9 +1044,21 @@ impl Points for QdrantGrpcService {
             *v /= positive_vectors.len() as f32;
         }
 
-        let results = collection
-            .search(&avg_vector, limit)
-            .map_err(|e| Status::internal(format!("Search failed: {}", e)))?;
+        // Over-fetch by the positives, which are excluded from the results
+        let filter = req
+            .filter
+            .as_ref()
+            .map(PointFilter::from_grpc)
+            .transpose()?;
+        let mut results = search_points(
+            &collection,
+            &avg_vector,
+            limit + req.positive.len(),
+            req.offset.unwrap_or(0) as usize,
+            req.score_threshold,
+            filter.as_ref(),
+        )?;
+        view.redact_results(&req.collection_name, &mut results);
 
         let positive_ids: std::collections::HashSet<String> = req
             .positive
@@ -980,6 +1073,7 @@ impl Points for QdrantGrpcService {
         let scored_points: Vec<ScoredPoint> = results
             .into_iter()
             .filter(|r| !positive_ids.contains(&r.id))
+            .take(limit)
             .map(|r| ScoredPoint {
                 id: Some(PointId {
                     point_id_options: Some(point_id::PointIdOptions::Uuid(r.id.clone())),
@@ -1009,7 +1103,7 @@ impl Points for QdrantGrpcService {
         request: Request<RecommendBatchPoints>,
     ) -> Result<Response<RecommendBatchResponse>, Status> {
         let start = Instant::now();
-        let req = request.into_inner();
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         info!(collection = %req.collection_name, "Qdrant gRPC: Recommend batch");
 
         let mut batch_results = vec![];
@@ -1029,7 +1123,7 @@ impl Points for QdrantGrpcService {
         request: Request<RecommendPointGroups>,
     ) -> Result<Response<RecommendGroupsResponse>, Status> {
         let start = Instant::now();
-        let req = request.into_inner();
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         info!(collection = %req.collection_name, "Qdrant gRPC: Recommend groups");
 
         Ok(Response::new(RecommendGroupsResponse {
@@ -1044,7 +1138,7 @@ impl Points for QdrantGrpcService {
         request: Request<DiscoverPoints>,
     ) -> Result<Response<DiscoverResponse>, Status> {
         let start = Instant::now();
-        let req = request.into_inner();
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         info!(collection = %req.collection_name, "Qdrant gRPC: Discover points");
 
         Ok(Response::new(DiscoverResponse {
@@ -1059,7 +1153,7 @@ impl Points for QdrantGrpcService {
         request: Request<DiscoverBatchPoints>,
     ) -> Result<Response<DiscoverBatchResponse>, Status> {
         let start = Instant::now();
-        let req = request.into_inner();
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         info!(collection = %req.collection_name, "Qdrant gRPC: Discover batch");
 
         Ok(Response::new(DiscoverBatchResponse {
@@ -1074,7 +1168,7 @@ impl Points for QdrantGrpcService {
         request: Request<CountPoints>,
     ) -> Result<Response<CountResponse>, Status> {
         let start = Instant::now();
-        let req = request.into_inner();
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         info!(collection = %req.collection_name, "Qdrant gRPC: Count points");
 
         let collection = self
@@ -1082,7 +1176,13 @@ impl Points for QdrantGrpcService {
             .get_collection(&req.collection_name)
             .map_err(|e| Status::not_found(format!("Collection not found: {}", e)))?;
 
-        let count = collection.vector_count() as u64;
+        let count = match &req.filter {
+            Some(filter) => {
+                let filter = PointFilter::from_grpc(filter)?;
+                get_matching_vector_ids(&collection, &filter).len() as u64
+            }
+            None => collection.vector_count() as u64,
+        };
 
         Ok(Response::new(CountResponse {
             result: Some(CountResult { count }),
@@ -1096,7 +1196,7 @@ impl Points for QdrantGrpcService {
         request: Request<UpdateBatchPoints>,
     ) -> Result<Response<UpdateBatchResponse>, Status> {
         let start = Instant::now();
-        let req = request.into_inner();
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         info!(collection = %req.collection_name, "Qdrant gRPC: Update batch");
 
         let mut statuses = vec![];
@@ -1119,7 +1219,8 @@ impl Points for QdrantGrpcService {
         request: Request<QueryPoints>,
     ) -> Result<Response<QueryResponse>, Status> {
         let start = Instant::now();
-        let req = request.into_inner();
+        let view = payload_view(&request);
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         info!(collection = %req.collection_name, "Qdrant gRPC: Query points");
 
         let collection = self
@@ -1139,9 +1240,20 @@ impl Points for QdrantGrpcService {
             });
 
         let scored_points = if let Some(vector) = query_vector {
-            let results = collection
-                .search(&vector, limit)
-                .map_err(|e| Status::internal(format!("Search failed: {}", e)))?;
+            let filter = req
+                .filter
+                .as_ref()
+                .map(PointFilter::from_grpc)
+                .transpose()?;
+            let mut results = search_points(
+                &collection,
+                &vector,
+                limit,
+                req.offset.unwrap_or(0) as usize,
+                req.score_threshold,
+                filter.as_ref(),
+            )?;
+            view.redact_results(&req.collection_name, &mut results);
 
             results
                 .into_iter()
@@ -1177,7 +1289,7 @@ impl Points for QdrantGrpcService {
         request: Request<QueryBatchPoints>,
     ) -> Result<Response<QueryBatchResponse>, Status> {
         let start = Instant::now();
-        let req = request.into_inner();
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         info!(collection = %req.collection_name, "Qdrant gRPC: Query batch");
 
         let mut batch_results = vec![];
@@ -1197,7 +1309,7 @@ impl Points for QdrantGrpcService {
         request: Request<QueryPointGroups>,
     ) -> Result<Response<QueryGroupsResponse>, Status> {
         let start = Instant::now();
-        let req = request.into_inner();
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         info!(collection = %req.collection_name, "Qdrant gRPC: Query groups");
 
         Ok(Response::new(QueryGroupsResponse {
@@ -1212,7 +1324,7 @@ impl Points for QdrantGrpcService {
         request: Request<FacetCounts>,
     ) -> Result<Response<FacetResponse>, Status> {
         let start = Instant::now();
-        let req = request.into_inner();
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         info!(collection = %req.collection_name, "Qdrant gRPC: Facet counts");
 
         Ok(Response::new(FacetResponse {
@@ -1227,7 +1339,7 @@ impl Points for QdrantGrpcService {
         request: Request<SearchMatrixPoints>,
     ) -> Result<Response<SearchMatrixPairsResponse>, Status> {
         let start = Instant::now();
-        let req = request.into_inner();
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         info!(collection = %req.collection_name, "Qdrant gRPC: Search matrix pairs");
 
         Ok(Response::new(SearchMatrixPairsResponse {
@@ -1242,7 +1354,7 @@ impl Points for QdrantGrpcService {
         request: Request<SearchMatrixPoints>,
     ) -> Result<Response<SearchMatrixOffsetsResponse>, Status> {
         let start = Instant::now();
-        let req = request.into_inner();
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         info!(collection = %req.collection_name, "Qdrant gRPC: Search matrix offsets");
 
         Ok(Response::new(SearchMatrixOffsetsResponse {
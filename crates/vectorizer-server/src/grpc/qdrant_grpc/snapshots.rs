@@ -5,9 +5,10 @@
 use std::time::Instant;
 
 use tonic::{Request, Response, Status};
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 use super::QdrantGrpcService;
+use crate::grpc::namespace::{namespaced, refuse_namespaced};
 use crate::grpc::qdrant_proto::snapshots_server::Snapshots;
 use crate::grpc::qdrant_proto::*;
 
@@ -22,7 +23,7 @@ impl Snapshots for QdrantGrpcService {
         request: Request<CreateSnapshotRequest>,
     ) -> Result<Response<CreateSnapshotResponse>, Status> {
         let start = Instant::now();
-        let req = request.into_inner();
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         info!(collection = %req.collection_name, "Qdrant gRPC: Create snapshot");
 
         let snapshot_manager = self
@@ -33,6 +34,12 @@ impl Snapshots for QdrantGrpcService {
         let snapshot = snapshot_manager
             .create_snapshot()
             .map_err(|e| Status::internal(format!("Failed to create snapshot: {}", e)))?;
+        if let Err(e) = snapshot_manager.upload_snapshot(&snapshot).await {
+            warn!(
+                "Failed to copy snapshot {} to object store: {}",
+                snapshot.id, e
+            );
+        }
 
         Ok(Response::new(CreateSnapshotResponse {
             snapshot_description: Some(SnapshotDescription {
@@ -53,7 +60,7 @@ impl Snapshots for QdrantGrpcService {
         request: Request<ListSnapshotsRequest>,
     ) -> Result<Response<ListSnapshotsResponse>, Status> {
         let start = Instant::now();
-        let req = request.into_inner();
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         info!(collection = %req.collection_name, "Qdrant gRPC: List snapshots");
 
         let snapshot_manager = self
@@ -89,7 +96,7 @@ impl Snapshots for QdrantGrpcService {
         request: Request<DeleteSnapshotRequest>,
     ) -> Result<Response<DeleteSnapshotResponse>, Status> {
         let start = Instant::now();
-        let req = request.into_inner();
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         info!(collection = %req.collection_name, snapshot = %req.snapshot_name, "Qdrant gRPC: Delete snapshot");
 
         let snapshot_manager = self
@@ -108,10 +115,11 @@ impl Snapshots for QdrantGrpcService {
 
     async fn create_full(
         &self,
-        _request: Request<CreateFullSnapshotRequest>,
+        request: Request<CreateFullSnapshotRequest>,
     ) -> Result<Response<CreateSnapshotResponse>, Status> {
         let start = Instant::now();
         info!("Qdrant gRPC: Create full snapshot");
+        refuse_namespaced(&request)?;
 
         let snapshot_manager = self
             .snapshot_manager
@@ -121,6 +129,12 @@ impl Snapshots for QdrantGrpcService {
         let snapshot = snapshot_manager
             .create_snapshot()
             .map_err(|e| Status::internal(format!("Failed to create snapshot: {}", e)))?;
+        if let Err(e) = snapshot_manager.upload_snapshot(&snapshot).await {
+            warn!(
+                "Failed to copy snapshot {} to object store: {}",
+                snapshot.id, e
+            );
+        }
 
         Ok(Response::new(CreateSnapshotResponse {
             snapshot_description: Some(SnapshotDescription {
@@ -138,10 +152,11 @@ impl Snapshots for QdrantGrpcService {
 
     async fn list_full(
         &self,
-        _request: Request<ListFullSnapshotsRequest>,
+        request: Request<ListFullSnapshotsRequest>,
     ) -> Result<Response<ListSnapshotsResponse>, Status> {
         let start = Instant::now();
         info!("Qdrant gRPC: List full snapshots");
+        refuse_namespaced(&request)?;
 
         let snapshot_manager = self
             .snapshot_manager
@@ -176,6 +191,7 @@ impl Snapshots for QdrantGrpcService {
         request: Request<DeleteFullSnapshotRequest>,
     ) -> Result<Response<DeleteSnapshotResponse>, Status> {
         let start = Instant::now();
+        refuse_namespaced(&request)?;
         let req = request.into_inner();
         info!(snapshot = %req.snapshot_name, "Qdrant gRPC: Delete full snapshot");
 
@@ -8,12 +8,14 @@ use std::time::Instant;
 use ::vectorizer::db::hybrid_search::HybridScoringAlgorithm;
 use ::vectorizer::db::{HybridSearchConfig, VectorStore};
 use ::vectorizer::grpc_conversions::*;
+use ::vectorizer::hub::{HubManager, WriteAdmission};
 use ::vectorizer::models::{CollectionConfig, Payload, QuantizationConfig, SparseVector, Vector};
 use once_cell::sync::Lazy;
 use tonic::{Request, Response, Status};
 use tracing::{debug, error, info};
 use vectorizer_core::error::VectorizerError;
 
+use super::namespace::{check_collection, namespaced, payload_view, request_namespace};
 use super::vectorizer as proto;
 use super::vectorizer::vectorizer_service_server::VectorizerService;
 
@@ -57,6 +59,9 @@ pub struct VectorizerGrpcService {
     /// in-flight depth past `upsert_queue_hard_limit` is refused with
     /// `Status::resource_exhausted` and a retry hint.
     upsert_queue: Arc<vectorizer::db::UpsertQueue>,
+    /// HiveHub integration; inserts are admitted against the tenant
+    /// quotas of the collection's owner when present.
+    hub_manager: Option<Arc<HubManager>>,
 }
 
 impl VectorizerGrpcService {
@@ -67,9 +72,34 @@ impl VectorizerGrpcService {
         Self {
             store,
             upsert_queue,
+            hub_manager: None,
         }
     }
 
+    /// Enforce the tenant quotas of `hub_manager` on inserts.
+    pub fn with_hub_manager(mut self, hub_manager: Option<Arc<HubManager>>) -> Self {
+        self.hub_manager = hub_manager;
+        self
+    }
+
+    /// Admit inserting `vectors` vectors into `collection` against the
+    /// owning tenant's quotas; see
+    /// [`HubManager::admit_insert`]. Hold the admission until the
+    /// vectors are inserted.
+    async fn admit_insert(
+        &self,
+        collection: &str,
+        vectors: usize,
+    ) -> Result<WriteAdmission, Status> {
+        let Some(hub_manager) = &self.hub_manager else {
+            return Ok(WriteAdmission::default());
+        };
+        hub_manager
+            .admit_insert(&self.store, None, collection, vectors)
+            .await
+            .map_err(Status::from)
+    }
+
     /// Translate an [`AdmissionError`] into a gRPC `RESOURCE_EXHAUSTED`
     /// status carrying a `retry-after` metadata entry. The same
     /// well-formed status is used by the streaming and unary upsert
@@ -100,11 +130,14 @@ impl VectorizerGrpcService {
 impl VectorizerService for VectorizerGrpcService {
     async fn list_collections(
         &self,
-        _request: Request<proto::ListCollectionsRequest>,
+        request: Request<proto::ListCollectionsRequest>,
     ) -> Result<Response<proto::ListCollectionsResponse>, Status> {
         debug!("gRPC: ListCollections request");
 
-        let collections = self.store.list_collections();
+        let mut collections = self.store.list_collections();
+        if let Some(namespace) = request_namespace(&request) {
+            collections.retain(|name| namespace.contains(name));
+        }
 
         Ok(Response::new(proto::ListCollectionsResponse {
             collection_names: collections,
@@ -115,7 +148,7 @@ impl VectorizerService for VectorizerGrpcService {
         &self,
         request: Request<proto::CreateCollectionRequest>,
     ) -> Result<Response<proto::CreateCollectionResponse>, Status> {
-        let req = request.into_inner();
+        let req = namespaced(request, |r| r.name.as_str())?;
         debug!("gRPC: CreateCollection request for '{}'", req.name);
 
         let config: vectorizer::models::CollectionConfig = req
@@ -144,7 +177,7 @@ impl VectorizerService for VectorizerGrpcService {
         &self,
         request: Request<proto::GetCollectionInfoRequest>,
     ) -> Result<Response<proto::GetCollectionInfoResponse>, Status> {
-        let req = request.into_inner();
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         debug!(
             "gRPC: GetCollectionInfo request for '{}'",
             req.collection_name
@@ -200,7 +233,7 @@ impl VectorizerService for VectorizerGrpcService {
         &self,
         request: Request<proto::DeleteCollectionRequest>,
     ) -> Result<Response<proto::DeleteCollectionResponse>, Status> {
-        let req = request.into_inner();
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         debug!(
             "gRPC: DeleteCollection request for '{}'",
             req.collection_name
@@ -225,7 +258,7 @@ impl VectorizerService for VectorizerGrpcService {
         &self,
         request: Request<proto::InsertVectorRequest>,
     ) -> Result<Response<proto::InsertVectorResponse>, Status> {
-        let req = request.into_inner();
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         debug!(
             "gRPC: InsertVector request for collection '{}', vector '{}'",
             req.collection_name, req.vector_id
@@ -236,6 +269,7 @@ impl VectorizerService for VectorizerGrpcService {
             Ok((ticket, _status)) => ticket,
             Err(e) => return Err(Self::queue_full_status(&req.collection_name, e)),
         };
+        let _tenant_admission = self.admit_insert(&req.collection_name, 1).await?;
 
         let vector: Vector = (&req)
             .try_into()
@@ -262,6 +296,7 @@ impl VectorizerService for VectorizerGrpcService {
     ) -> Result<Response<proto::InsertVectorsResponse>, Status> {
         debug!("gRPC: InsertVectors streaming request");
 
+        let namespace = request_namespace(&request);
         let mut stream = request.into_inner();
         let mut inserted_count = 0u32;
         let mut failed_count = 0u32;
@@ -271,6 +306,7 @@ impl VectorizerService for VectorizerGrpcService {
 
         while let Some(req) = stream.message().await? {
             if current_collection.is_none() {
+                check_collection(namespace.as_ref(), &req.collection_name)?;
                 current_collection = Some(req.collection_name.clone());
             }
 
@@ -299,6 +335,7 @@ impl VectorizerService for VectorizerGrpcService {
                 Ok((ticket, _status)) => ticket,
                 Err(e) => return Err(Self::queue_full_status(&collection_name, e)),
             };
+            let _tenant_admission = self.admit_insert(&collection_name, batch.len()).await?;
 
             let batch_len = batch.len() as u32;
             match self.store.insert(&collection_name, batch) {
@@ -323,16 +360,18 @@ impl VectorizerService for VectorizerGrpcService {
         &self,
         request: Request<proto::GetVectorRequest>,
     ) -> Result<Response<proto::GetVectorResponse>, Status> {
-        let req = request.into_inner();
+        let view = payload_view(&request);
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         debug!(
             "gRPC: GetVector request for collection '{}', vector '{}'",
             req.collection_name, req.vector_id
         );
 
-        let vector = self
+        let mut vector = self
             .store
             .get_vector(&req.collection_name, &req.vector_id)
             .map_err(|e| Status::not_found(e.to_string()))?;
+        view.redact(&req.collection_name, &mut vector.payload);
 
         use std::collections::HashMap;
         let payload: HashMap<String, String> = vector
@@ -363,7 +402,7 @@ impl VectorizerService for VectorizerGrpcService {
         &self,
         request: Request<proto::UpdateVectorRequest>,
     ) -> Result<Response<proto::UpdateVectorResponse>, Status> {
-        let req = request.into_inner();
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         debug!(
             "gRPC: UpdateVector request for collection '{}', vector '{}'",
             req.collection_name, req.vector_id
@@ -407,7 +446,7 @@ impl VectorizerService for VectorizerGrpcService {
         &self,
         request: Request<proto::DeleteVectorRequest>,
     ) -> Result<Response<proto::DeleteVectorResponse>, Status> {
-        let req = request.into_inner();
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         debug!(
             "gRPC: DeleteVector request for collection '{}', vector '{}'",
             req.collection_name, req.vector_id
@@ -432,16 +471,18 @@ impl VectorizerService for VectorizerGrpcService {
         &self,
         request: Request<proto::SearchRequest>,
     ) -> Result<Response<proto::SearchResponse>, Status> {
-        let req = request.into_inner();
+        let view = payload_view(&request);
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         debug!(
             "gRPC: Search request for collection '{}', limit={}",
             req.collection_name, req.limit
         );
 
-        let results = self
+        let mut results = self
             .store
             .search(&req.collection_name, &req.query_vector, req.limit as usize)
             .map_err(|e| Status::internal(e.to_string()))?;
+        view.redact_results(&req.collection_name, &mut results);
 
         let proto_results: Vec<proto::SearchResult> = results.iter().map(|r| r.into()).collect();
 
@@ -454,7 +495,8 @@ impl VectorizerService for VectorizerGrpcService {
         &self,
         request: Request<proto::BatchSearchRequest>,
     ) -> Result<Response<proto::BatchSearchResponse>, Status> {
-        let req = request.into_inner();
+        let view = payload_view(&request);
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         debug!(
             "gRPC: BatchSearch request for collection '{}', {} queries",
             req.collection_name,
@@ -469,7 +511,8 @@ impl VectorizerService for VectorizerGrpcService {
                 &query.query_vector,
                 query.limit as usize,
             ) {
-                Ok(results) => {
+                Ok(mut results) => {
+                    view.redact_results(&req.collection_name, &mut results);
                     let proto_results: Vec<proto::SearchResult> =
                         results.iter().map(|r| r.into()).collect();
                     batch_results.push(proto::SearchResponse {
@@ -492,7 +535,8 @@ impl VectorizerService for VectorizerGrpcService {
         &self,
         request: Request<proto::HybridSearchRequest>,
     ) -> Result<Response<proto::HybridSearchResponse>, Status> {
-        let req = request.into_inner();
+        let view = payload_view(&request);
+        let req = namespaced(request, |r| r.collection_name.as_str())?;
         debug!(
             "gRPC: HybridSearch request for collection '{}'",
             req.collection_name
@@ -525,7 +569,7 @@ impl VectorizerService for VectorizerGrpcService {
             algorithm: HybridScoringAlgorithm::ReciprocalRankFusion,
         });
 
-        let results = self
+        let mut results = self
             .store
             .hybrid_search(
                 &req.collection_name,
@@ -534,6 +578,7 @@ impl VectorizerService for VectorizerGrpcService {
                 config,
             )
             .map_err(|e| Status::internal(e.to_string()))?;
+        view.redact_results(&req.collection_name, &mut results);
 
         let proto_results: Vec<proto::HybridSearchResult> = results
             .iter()
@@ -12,9 +12,10 @@ use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use chrono::{DateTime, Local};
 use tracing::{error, info, warn};
-use tracing_subscriber::EnvFilter;
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Layer, Registry};
+use vectorizer::monitoring::telemetry;
 
 /// Initialize the centralized logging system
 pub fn init_logging(service_name: &str) -> Result<(), Box<dyn std::error::Error>> {
@@ -52,34 +53,44 @@ pub fn init_logging_with_level(
         .append(true)
         .open(&log_path)?;
 
-    // Initialize tracing with both console and file output
+    // Export spans over OTLP when an endpoint is configured. Built before
+    // the subscriber so a failure can only be reported once it exists.
+    let (otel_layer, otel_error) = match telemetry::try_init::<Registry>(service_name, None) {
+        Ok(layer) => (layer, None),
+        Err(e) => (None, Some(e)),
+    };
+
+    // Initialize tracing with both console and file output. The log filter
+    // applies to those two outputs only; the OpenTelemetry layer carries
+    // its own, so request spans are exported at any log level.
     let result = tracing_subscriber::registry()
-        .with(
-            EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| format!("{}={}", service_name, default_level).into()),
-        )
+        .with(otel_layer)
         .with(
             tracing_subscriber::fmt::layer()
                 .with_writer(std::io::stderr)
                 .with_target(false)
                 .with_thread_ids(true)
-                .with_thread_names(true),
-        )
-        .with(
-            tracing_subscriber::fmt::layer()
-                // SAFE: `try_clone` only fails on EBADF / file descriptor
-                // exhaustion, both of which are unrecoverable for the
-                // logging subsystem. Panic surfaces the failure at startup
-                // rather than silently dropping log lines.
-                .with_writer({
-                    #[allow(clippy::expect_used)]
-                    move || log_file.try_clone().expect("Failed to clone log file")
-                })
-                .with_target(true)
-                .with_thread_ids(true)
                 .with_thread_names(true)
-                .with_file(true)
-                .with_line_number(true),
+                .and_then(
+                    tracing_subscriber::fmt::layer()
+                        // SAFE: `try_clone` only fails on EBADF / file descriptor
+                        // exhaustion, both of which are unrecoverable for the
+                        // logging subsystem. Panic surfaces the failure at startup
+                        // rather than silently dropping log lines.
+                        .with_writer({
+                            #[allow(clippy::expect_used)]
+                            move || log_file.try_clone().expect("Failed to clone log file")
+                        })
+                        .with_target(true)
+                        .with_thread_ids(true)
+                        .with_thread_names(true)
+                        .with_file(true)
+                        .with_line_number(true),
+                )
+                .with_filter(
+                    EnvFilter::try_from_default_env()
+                        .unwrap_or_else(|_| format!("{}={}", service_name, default_level).into()),
+                ),
         )
         .try_init();
 
@@ -89,6 +100,15 @@ pub fn init_logging_with_level(
         return Err(format!("Failed to initialize tracing: {}", e).into());
     }
 
+    match otel_error {
+        Some(e) => warn!("OpenTelemetry tracing not available: {}", e),
+        None if telemetry::is_enabled() => info!(
+            "OpenTelemetry spans exported to {}",
+            telemetry::otlp_endpoint_from_env().unwrap_or_default()
+        ),
+        None => {}
+    }
+
     // Only log initialization message if verbose
     if default_level == "debug" || default_level == "info" {
         info!(
@@ -23,6 +23,8 @@ use std::sync::Arc;
 use parking_lot::RwLock;
 use tracing::debug;
 use vectorizer::auth::roles::Role;
+use vectorizer::hub::WriteAdmission;
+use vectorizer::security::{ActorKind, AuditActor, AuditProtocol, MutationRecord};
 use vectorizer_protocol::rpc_wire::types::{Request, Response, VectorizerValue};
 
 use super::server::RpcState;
@@ -58,6 +60,26 @@ fn vectorizer_err_ctx(id: u32, context: &str, err: &vectorizer::VectorizerError)
     Response::err(id, format!("[{}] {}: {}", err.code(), context, err))
 }
 
+/// Admit inserting `vectors` vectors into `collection` against the
+/// tenant quotas of its owner; see
+/// [`vectorizer::hub::HubManager::admit_insert`]. Hold the admission
+/// until the vectors are inserted.
+async fn admit_insert(
+    state: &RpcState,
+    id: u32,
+    context: &str,
+    collection: &str,
+    vectors: usize,
+) -> Result<WriteAdmission, Response> {
+    let Some(hub_manager) = &state.hub_manager else {
+        return Ok(WriteAdmission::default());
+    };
+    hub_manager
+        .admit_insert(&state.store, None, collection, vectors)
+        .await
+        .map_err(|e| vectorizer_err_ctx(id, context, &e))
+}
+
 // ── Value conversion helpers ─────────────────────────────────────────────────
 
 /// Convert a `serde_json::Value` into a `VectorizerValue`.
@@ -170,6 +192,36 @@ pub struct ConnectionAuth {
 
 const PROTOCOL_VERSION: i64 = 1;
 
+/// Commands that change data or configuration, recorded in the audit
+/// log when `audit_log` is enabled.
+const MUTATING_COMMANDS: &[&str] = &[
+    "collections.create",
+    "collections.delete",
+    "collections.cleanup_empty",
+    "vectors.insert",
+    "vectors.insert_text",
+    "vectors.update",
+    "vectors.delete",
+    "vectors.batch_insert",
+    "vectors.batch_insert_texts",
+    "vectors.batch_update",
+    "vectors.batch_delete",
+    "vectors.move",
+    "vectors.copy",
+    "vectors.delete_by_filter",
+    "vectors.bulk_update_metadata",
+    "vectors.set_expiry",
+    "graph.create_edge",
+    "graph.delete_edge",
+    "graph.discover_edges",
+    "graph.discover_edges_for_node",
+    "admin.config_update",
+    "admin.backups_create",
+    "admin.backups_restore",
+    "admin.workspace_add",
+    "admin.workspace_remove",
+];
+
 /// Dispatch one [`Request`] against the live capability set. Always
 /// returns a [`Response`] — a transport-level error becomes
 /// `Response::err`, never a `Result::Err` from this fn (the caller
@@ -178,6 +230,59 @@ pub async fn dispatch(
     state: &Arc<RpcState>,
     auth: &Arc<RwLock<ConnectionAuth>>,
     req: Request,
+) -> Response {
+    let Some(audit_log) = state
+        .audit_log
+        .as_ref()
+        .filter(|_| MUTATING_COMMANDS.contains(&req.command.as_str()))
+    else {
+        return dispatch_command(state, auth, req).await;
+    };
+
+    let mut record = audit_record(state, &auth.read(), &req);
+    let response = dispatch_command(state, auth, req).await;
+    record.success = Some(response.result.is_ok());
+    audit_log.record(record);
+    response
+}
+
+/// Audit record of a mutating command: the collection is its first
+/// argument, the IDs its second (one ID, a list, or a map of named
+/// lists) for `vectors.*` commands.
+fn audit_record(state: &RpcState, auth: &ConnectionAuth, req: &Request) -> MutationRecord {
+    let kind = match (&state.auth, &auth.principal) {
+        (Some(_), Some(name)) if name.starts_with("api_key_") => ActorKind::ApiKey,
+        (Some(_), Some(_)) => ActorKind::Jwt,
+        _ => ActorKind::Anonymous,
+    };
+    let actor = AuditActor {
+        kind,
+        id: None,
+        name: auth.principal.clone(),
+    };
+    let mut record = MutationRecord::new(AuditProtocol::Rpc, actor, req.command.clone());
+    record.collection = req
+        .args
+        .first()
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+    if req.command.starts_with("vectors.") {
+        record.ids_count = match req.args.get(1) {
+            Some(VectorizerValue::Str(_)) => Some(1),
+            Some(VectorizerValue::Array(items)) => Some(items.len()),
+            Some(map @ VectorizerValue::Map(_)) => {
+                vectorizer::security::count_ids(&value_to_json(map))
+            }
+            _ => None,
+        };
+    }
+    record
+}
+
+async fn dispatch_command(
+    state: &Arc<RpcState>,
+    auth: &Arc<RwLock<ConnectionAuth>>,
+    req: Request,
 ) -> Response {
     let Request { id, command, args } = req;
 
@@ -210,21 +315,21 @@ pub async fn dispatch(
                 "collections.force_save" => handle_collections_force_save(state, id, &args).await,
                 // ── Vectors ──────────────────────────────────────────────
                 "vectors.get" => handle_vector_get(state, id, &args),
-                "vectors.insert" => handle_vectors_insert(state, id, &args),
+                "vectors.insert" => handle_vectors_insert(state, id, &args).await,
                 "vectors.insert_text" => handle_vectors_insert_text(state, id, &args).await,
                 "vectors.update" => handle_vectors_update(state, id, &args),
                 "vectors.delete" => handle_vectors_delete(state, id, &args),
                 "vectors.list" => handle_vectors_list(state, id, &args),
                 "vectors.embed" => handle_vectors_embed(state, id, &args),
-                "vectors.batch_insert" => handle_vectors_batch_insert(state, id, &args),
+                "vectors.batch_insert" => handle_vectors_batch_insert(state, id, &args).await,
                 "vectors.batch_insert_texts" => {
                     handle_vectors_batch_insert_texts(state, id, &args).await
                 }
                 "vectors.batch_search" => handle_vectors_batch_search(state, id, &args).await,
                 "vectors.batch_update" => handle_vectors_batch_update(state, id, &args),
                 "vectors.batch_delete" => handle_vectors_batch_delete(state, id, &args),
-                "vectors.move" => handle_vectors_move(state, id, &args),
-                "vectors.copy" => handle_vectors_copy(state, id, &args),
+                "vectors.move" => handle_vectors_move(state, id, &args).await,
+                "vectors.copy" => handle_vectors_copy(state, id, &args).await,
                 "vectors.delete_by_filter" => handle_vectors_delete_by_filter(state, id, &args),
                 "vectors.bulk_update_metadata" => {
                     handle_vectors_bulk_update_metadata(state, id, &args)
@@ -635,6 +740,13 @@ fn handle_collections_create(
         sharding: None,
         graph: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
     match state.store.create_collection(name, config) {
         Ok(()) => {
@@ -765,7 +877,11 @@ async fn handle_collections_force_save(
 
 // ── Vector operations ────────────────────────────────────────────────────────
 
-fn handle_vectors_insert(state: &Arc<RpcState>, id: u32, args: &[VectorizerValue]) -> Response {
+async fn handle_vectors_insert(
+    state: &Arc<RpcState>,
+    id: u32,
+    args: &[VectorizerValue],
+) -> Response {
     let collection = match args.first().and_then(|v| v.as_str()) {
         Some(c) => c,
         None => {
@@ -777,7 +893,7 @@ fn handle_vectors_insert(state: &Arc<RpcState>, id: u32, args: &[VectorizerValue
     };
     let vector_id = match args.get(1).and_then(|v| v.as_str()) {
         Some(v) => v.to_string(),
-        None => uuid::Uuid::new_v4().to_string(),
+        None => state.store.new_vector_id(collection),
     };
     let data: Vec<f32> = match args.get(2).and_then(|v| v.as_array()) {
         Some(arr) => arr
@@ -800,6 +916,10 @@ fn handle_vectors_insert(state: &Arc<RpcState>, id: u32, args: &[VectorizerValue
         payload,
         document_id: None,
     };
+    let _tenant_admission = match admit_insert(state, id, "vectors.insert", collection, 1).await {
+        Ok(admission) => admission,
+        Err(response) => return response,
+    };
     match state.store.insert(collection, vec![vector]) {
         Ok(()) => {
             let map = vec![
@@ -843,7 +963,7 @@ async fn handle_vectors_insert_text(
     };
     let payload_json = args.get(3).map(value_to_json);
     let payload = payload_json.map(vectorizer::models::Payload::new);
-    let vector_id = client_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let vector_id = client_id.unwrap_or_else(|| state.store.new_vector_id(collection));
     let vector = vectorizer::models::Vector {
         id: vector_id.clone(),
         data: embedding,
@@ -861,6 +981,11 @@ async fn handle_vectors_insert_text(
             );
         }
     }
+    let _tenant_admission =
+        match admit_insert(state, id, "vectors.insert_text", collection, 1).await {
+            Ok(admission) => admission,
+            Err(response) => return response,
+        };
     match state.store.insert(collection, vec![vector]) {
         Ok(()) => {
             let map = vec![
@@ -1038,7 +1163,7 @@ fn handle_vectors_embed(state: &Arc<RpcState>, id: u32, args: &[VectorizerValue]
     }
 }
 
-fn handle_vectors_batch_insert(
+async fn handle_vectors_batch_insert(
     state: &Arc<RpcState>,
     id: u32,
     args: &[VectorizerValue],
@@ -1056,6 +1181,11 @@ fn handle_vectors_batch_insert(
         Some(arr) => arr,
         None => return Response::err(id, "vectors.batch_insert: Array(items) missing"),
     };
+    let _tenant_admission =
+        match admit_insert(state, id, "vectors.batch_insert", collection, items.len()).await {
+            Ok(admission) => admission,
+            Err(response) => return response,
+        };
     let mut inserted = 0usize;
     let mut failed = 0usize;
     let mut results: Vec<VectorizerValue> = Vec::with_capacity(items.len());
@@ -1064,7 +1194,7 @@ fn handle_vectors_batch_insert(
             .map_get("id")
             .and_then(|v| v.as_str())
             .map(str::to_owned)
-            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+            .unwrap_or_else(|| state.store.new_vector_id(collection));
         let data: Vec<f32> = match item.map_get("data").and_then(|v| v.as_array()) {
             Some(arr) => arr
                 .iter()
@@ -1181,6 +1311,18 @@ async fn handle_vectors_batch_insert_texts(
             );
         }
     }
+    let _tenant_admission = match admit_insert(
+        state,
+        id,
+        "vectors.batch_insert_texts",
+        collection,
+        items.len(),
+    )
+    .await
+    {
+        Ok(admission) => admission,
+        Err(response) => return response,
+    };
     let mut inserted = 0usize;
     let mut failed = 0usize;
     let mut results: Vec<VectorizerValue> = Vec::with_capacity(items.len());
@@ -1210,7 +1352,7 @@ async fn handle_vectors_batch_insert_texts(
             .map_get("id")
             .and_then(|v| v.as_str())
             .map(str::to_owned)
-            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+            .unwrap_or_else(|| state.store.new_vector_id(collection));
         let embedding = match state.embedding_manager.embed(&text) {
             Ok(e) => e,
             Err(e) => {
@@ -1663,7 +1805,7 @@ fn handle_vectors_batch_delete(
     Response::ok(id, VectorizerValue::Map(map))
 }
 
-fn handle_vectors_move(state: &Arc<RpcState>, id: u32, args: &[VectorizerValue]) -> Response {
+async fn handle_vectors_move(state: &Arc<RpcState>, id: u32, args: &[VectorizerValue]) -> Response {
     let src = match args.first().and_then(|v| v.as_str()) {
         Some(c) => c,
         None => {
@@ -1684,6 +1826,23 @@ fn handle_vectors_move(state: &Arc<RpcState>, id: u32, args: &[VectorizerValue])
         Some(arr) => arr.to_vec(),
         None => return Response::err(id, "vectors.move: Array(ids) missing"),
     };
+    // A move between two collections of one owner leaves its usage as
+    // it was
+    let owner = |name: &str| {
+        state
+            .store
+            .get_collection(name)
+            .ok()
+            .and_then(|c| c.owner_id())
+    };
+    let _tenant_admission = if owner(src) == owner(dst) {
+        WriteAdmission::default()
+    } else {
+        match admit_insert(state, id, "vectors.move", dst, ids.len()).await {
+            Ok(admission) => admission,
+            Err(response) => return response,
+        }
+    };
     let mut moved = 0usize;
     let mut failed = 0usize;
     for entry in &ids {
@@ -1732,7 +1891,7 @@ fn handle_vectors_move(state: &Arc<RpcState>, id: u32, args: &[VectorizerValue])
     Response::ok(id, VectorizerValue::Map(map))
 }
 
-fn handle_vectors_copy(state: &Arc<RpcState>, id: u32, args: &[VectorizerValue]) -> Response {
+async fn handle_vectors_copy(state: &Arc<RpcState>, id: u32, args: &[VectorizerValue]) -> Response {
     let src = match args.first().and_then(|v| v.as_str()) {
         Some(c) => c,
         None => {
@@ -1753,6 +1912,10 @@ fn handle_vectors_copy(state: &Arc<RpcState>, id: u32, args: &[VectorizerValue])
         Some(arr) => arr.to_vec(),
         None => return Response::err(id, "vectors.copy: Array(ids) missing"),
     };
+    let _tenant_admission = match admit_insert(state, id, "vectors.copy", dst, ids.len()).await {
+        Ok(admission) => admission,
+        Err(response) => return response,
+    };
     let mut copied = 0usize;
     let mut failed = 0usize;
     for entry in &ids {
@@ -1918,18 +2081,17 @@ fn handle_vectors_bulk_update_metadata(
         })
         .collect();
     let matched = matching.len();
-    let mut updated = 0usize;
-    for mut vector in matching {
-        let new_data = if let Some(existing) = vector.payload.as_ref() {
-            json_merge_patch(existing.data.clone(), patch_json.clone())
-        } else {
-            patch_json.clone()
-        };
-        vector.payload = Some(vectorizer::models::Payload { data: new_data });
-        if state.store.update(collection, vector).is_ok() {
-            updated += 1;
-        }
-    }
+    let updated = matching
+        .iter()
+        .filter(|vector| {
+            state
+                .store
+                .patch_payload(collection, &vector.id, &patch_json, |_| {
+                    Ok::<_, vectorizer::error::VectorizerError>(())
+                })
+                .is_ok()
+        })
+        .count();
     let map = vec![
         (
             VectorizerValue::Str("scanned".into()),
@@ -1947,24 +2109,6 @@ fn handle_vectors_bulk_update_metadata(
     Response::ok(id, VectorizerValue::Map(map))
 }
 
-/// JSON-merge-patch (RFC 7396) — reused from rest_handlers/vectors.rs logic.
-fn json_merge_patch(mut target: serde_json::Value, patch: serde_json::Value) -> serde_json::Value {
-    match (target.as_object_mut(), patch) {
-        (Some(t), serde_json::Value::Object(p)) => {
-            for (k, v) in p {
-                if v.is_null() {
-                    t.remove(&k);
-                } else {
-                    let existing = t.remove(&k).unwrap_or(serde_json::Value::Null);
-                    t.insert(k, json_merge_patch(existing, v));
-                }
-            }
-            serde_json::Value::Object(t.clone())
-        }
-        (_, patch) => patch,
-    }
-}
-
 fn handle_vectors_set_expiry(state: &Arc<RpcState>, id: u32, args: &[VectorizerValue]) -> Response {
     let collection = match args.first().and_then(|v| v.as_str()) {
         Some(c) => c,
@@ -2033,6 +2177,7 @@ async fn handle_search_intelligent(
     id: u32,
     args: &[VectorizerValue],
 ) -> Response {
+    use vectorizer::intelligent_search::graph_expansion::GraphExpansion;
     use vectorizer::intelligent_search::rest_api::{IntelligentSearchRequest, RESTAPIHandler};
 
     let payload = args
@@ -2056,6 +2201,22 @@ async fn handle_search_intelligent(
         .and_then(|m| m.as_u64())
         .map(|m| m as usize);
     let domain_expansion = payload.get("domain_expansion").and_then(|d| d.as_bool());
+    let translate_to = payload
+        .get("translate_to")
+        .and_then(|t| t.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str().map(str::to_owned))
+                .collect::<Vec<_>>()
+        });
+    let graph_expansion = match payload
+        .get("graph_expansion")
+        .map(GraphExpansion::from_request)
+        .transpose()
+    {
+        Ok(options) => options.flatten(),
+        Err(e) => return Response::err(id, format!("search.intelligent: graph_expansion: {e}")),
+    };
     let handler = RESTAPIHandler::new_with_store(state.store.clone());
     let request = IntelligentSearchRequest {
         query,
@@ -2065,6 +2226,8 @@ async fn handle_search_intelligent(
         technical_focus: None,
         mmr_enabled: None,
         mmr_lambda: None,
+        translate_to,
+        graph_expansion,
     };
     match handler.handle_intelligent_search(request).await {
         Ok(resp) => {
@@ -2179,7 +2342,7 @@ fn handle_search_hybrid(state: &Arc<RpcState>, id: u32, args: &[VectorizerValue]
         Some(q) => q,
         None => return Response::err(id, "search.hybrid: missing query in request map"),
     };
-    let alpha = req.get("alpha").and_then(|v| v.as_f64()).unwrap_or(0.7) as f32;
+    let alpha = req.get("alpha").and_then(|v| v.as_f64()).map(|a| a as f32);
     let dense_k = req.get("dense_k").and_then(|v| v.as_u64()).unwrap_or(20) as usize;
     let sparse_k = req.get("sparse_k").and_then(|v| v.as_u64()).unwrap_or(20) as usize;
     let final_k = req
@@ -2187,15 +2350,7 @@ fn handle_search_hybrid(state: &Arc<RpcState>, id: u32, args: &[VectorizerValue]
         .or_else(|| req.get("limit"))
         .and_then(|v| v.as_u64())
         .unwrap_or(10) as usize;
-    let algorithm_str = req
-        .get("algorithm")
-        .and_then(|v| v.as_str())
-        .unwrap_or("rrf");
-    let algorithm = match algorithm_str {
-        "weighted" => HybridScoringAlgorithm::WeightedCombination,
-        "alpha" => HybridScoringAlgorithm::AlphaBlending,
-        _ => HybridScoringAlgorithm::ReciprocalRankFusion,
-    };
+    let algorithm_str = req.get("algorithm").and_then(|v| v.as_str());
     let dense = match state.embedding_manager.embed(query) {
         Ok(e) => e,
         Err(e) => return Response::err(id, format!("search.hybrid: embed failed: {}", e)),
@@ -2204,6 +2359,22 @@ fn handle_search_hybrid(state: &Arc<RpcState>, id: u32, args: &[VectorizerValue]
         Ok(c) => c,
         Err(e) => return Response::err(id, format!("search.hybrid: {}", e)),
     };
+    // Weights tuned from relevance judgements stand in for the defaults
+    // when neither alpha nor algorithm is given
+    let tuned = if alpha.is_none() && algorithm_str.is_none() {
+        coll.tuned_hybrid_weights()
+    } else {
+        None
+    };
+    let alpha = alpha.or(tuned.as_ref().map(|w| w.alpha)).unwrap_or(0.7);
+    let algorithm = match algorithm_str {
+        Some("weighted") => HybridScoringAlgorithm::WeightedCombination,
+        Some("alpha") => HybridScoringAlgorithm::AlphaBlending,
+        Some(_) => HybridScoringAlgorithm::ReciprocalRankFusion,
+        None => tuned.map_or(HybridScoringAlgorithm::ReciprocalRankFusion, |w| {
+            w.algorithm
+        }),
+    };
     let config = HybridSearchConfig {
         alpha,
         dense_k,
@@ -2370,6 +2541,34 @@ async fn handle_search_multi_collection(
             .collect::<Vec<_>>(),
         None => return Response::err(id, "search.multi_collection: missing collections field"),
     };
+    let score_normalization = match payload.get("score_normalization") {
+        None => None,
+        Some(value) => match value
+            .as_str()
+            .and_then(vectorizer::search::ScoreNormalization::parse)
+        {
+            Some(normalization) => Some(normalization),
+            None => {
+                return Response::err(
+                    id,
+                    "search.multi_collection: score_normalization must be one of: none, min_max, z_score, rrf",
+                );
+            }
+        },
+    };
+    let collection_weights = match payload
+        .get("collection_weights")
+        .map(|w| serde_json::from_value(w.clone()))
+        .transpose()
+    {
+        Ok(weights) => weights,
+        Err(e) => {
+            return Response::err(
+                id,
+                format!("search.multi_collection: collection_weights: {e}"),
+            );
+        }
+    };
     let handler = RESTAPIHandler::new_with_store(state.store.clone());
     let request = MultiCollectionSearchRequest {
         query,
@@ -2385,6 +2584,8 @@ async fn handle_search_multi_collection(
         cross_collection_reranking: payload
             .get("cross_collection_reranking")
             .and_then(|v| v.as_bool()),
+        score_normalization,
+        collection_weights,
     };
     match handler.handle_multi_collection_search(request).await {
         Ok(resp) => {
@@ -3659,6 +3860,8 @@ fn parse_relationship_type(s: &str) -> Option<vectorizer::db::graph::Relationshi
         "REFERENCES" => Some(RelationshipType::References),
         "CONTAINS" => Some(RelationshipType::Contains),
         "DERIVED_FROM" | "DERIVEDFROM" => Some(RelationshipType::DerivedFrom),
+        "MENTIONS" => Some(RelationshipType::Mentions),
+        "RELATED_TO" | "RELATEDTO" => Some(RelationshipType::RelatedTo),
         _ => None,
     }
 }
@@ -5397,6 +5600,8 @@ mod tests {
             slow_query_ring: vectorizer::cache::SlowQueryRing::new(
                 vectorizer::cache::slow_query::SlowQueryConfig::default(),
             ),
+            audit_log: None,
+            hub_manager: None,
         })
     }
 
@@ -5491,4 +5696,63 @@ mod tests {
             "got: {err}"
         );
     }
+
+    #[tokio::test]
+    async fn mutating_commands_are_audited() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("mutations.jsonl");
+        let store = Arc::new(vectorizer::db::VectorStore::new());
+        let config = vectorizer::config::AuditLogConfig {
+            enabled: true,
+            path: Some(path.clone()),
+            ..Default::default()
+        };
+        let audit_log = vectorizer::security::MutationAuditLog::from_config(&config, store)
+            .unwrap()
+            .map(Arc::new);
+        let mut state = (*fake_state()).clone();
+        state.audit_log = audit_log;
+        let state = Arc::new(state);
+        let auth = Arc::new(RwLock::new(ConnectionAuth {
+            authenticated: true,
+            admin: true,
+            principal: Some("local".into()),
+        }));
+
+        for (id, command) in [(1, "collections.list"), (2, "vectors.delete")] {
+            let _ = dispatch(
+                &state,
+                &auth,
+                Request {
+                    id,
+                    command: command.into(),
+                    args: vec![
+                        VectorizerValue::Str("missing".into()),
+                        VectorizerValue::Str("v1".into()),
+                    ],
+                },
+            )
+            .await;
+        }
+
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        let contents = loop {
+            let contents = std::fs::read_to_string(&path).unwrap_or_default();
+            if !contents.is_empty() || std::time::Instant::now() > deadline {
+                break contents;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        };
+        let lines: Vec<serde_json::Value> = contents
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 1, "only the mutation is audited: {contents}");
+        assert_eq!(lines[0]["protocol"], "rpc");
+        assert_eq!(lines[0]["operation"], "vectors.delete");
+        assert_eq!(lines[0]["collection"], "missing");
+        assert_eq!(lines[0]["ids_count"], 1);
+        assert_eq!(lines[0]["success"], false);
+        assert_eq!(lines[0]["actor"]["name"], "local");
+    }
 }
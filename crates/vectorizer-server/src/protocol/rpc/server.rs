@@ -52,6 +52,11 @@ pub struct RpcState {
     pub cluster_manager: Option<Arc<vectorizer::cluster::ClusterManager>>,
     /// Slow-query ring buffer for `admin.slow_queries_*`.
     pub slow_query_ring: SlowQueryRing,
+    /// Audit trail of mutating commands (`audit_log`).
+    pub audit_log: Option<Arc<vectorizer::security::MutationAuditLog>>,
+    /// HiveHub integration; inserts are admitted against the tenant
+    /// quotas of the collection's owner when present.
+    pub hub_manager: Option<Arc<vectorizer::hub::HubManager>>,
 }
 
 /// Spawn the RPC TCP listener on `addr`. Returns immediately; the
@@ -56,6 +56,7 @@ pub async fn create_user(
             }),
         ));
     }
+    state.ensure_local_login()?;
 
     // Validate username
     if request.username.is_empty() || request.username.len() < 3 {
@@ -340,6 +341,7 @@ pub async fn change_password(
             }),
         ));
     }
+    state.ensure_local_login()?;
 
     // Validate new password complexity
     let password_validation = vectorizer::auth::validate_password(&request.new_password);
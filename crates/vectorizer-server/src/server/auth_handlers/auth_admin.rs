@@ -29,7 +29,9 @@ use tracing::info;
 use vectorizer::auth::audit::AuditQuery;
 use vectorizer::auth::middleware::AuthState;
 use vectorizer::auth::roles::Role;
-use vectorizer::auth::{AuditEntry, Permission, TokenIntrospection, TokenScope};
+use vectorizer::auth::{
+    AuditEntry, CollectionNamespace, Permission, TokenIntrospection, TokenScope,
+};
 use vectorizer::monitoring::api_key_usage::UsageBucket;
 
 use super::state::AuthHandlerState;
@@ -49,6 +51,11 @@ pub struct CreateScopedApiKeyRequest {
     /// Per-collection scopes. Empty = default-deny (no implicit access).
     #[serde(default)]
     pub scopes: Vec<ScopeDto>,
+    /// Collection-name namespace (`team-a/*`) the key is confined to.
+    /// A caller that is itself namespaced can only hand out its own
+    /// namespace or a narrower one, and gets its own by default.
+    #[serde(default)]
+    pub namespace: Option<String>,
 }
 
 /// DTO for a single collection scope in the request body.
@@ -67,6 +74,7 @@ pub struct CreateScopedApiKeyResponse {
     pub name: String,
     pub permissions: Vec<String>,
     pub scopes: Vec<ScopeDto>,
+    pub namespace: Option<String>,
     pub expires_at: Option<u64>,
     pub warning: String,
 }
@@ -126,6 +134,7 @@ pub struct ApiKeyView {
     pub user_id: String,
     pub permissions: Vec<String>,
     pub scopes: Vec<ScopeDto>,
+    pub namespace: Option<String>,
     pub created_at: u64,
     pub last_used: Option<u64>,
     pub expires_at: Option<u64>,
@@ -245,6 +254,7 @@ fn into_view(key: vectorizer::auth::ApiKey) -> ApiKeyView {
                 permissions: s.permissions.clone(),
             })
             .collect(),
+        namespace: key.namespace.map(String::from),
         created_at: key.created_at,
         last_used: key.last_used,
         expires_at: key.expires_at,
@@ -261,6 +271,9 @@ fn into_view(key: vectorizer::auth::ApiKey) -> ApiKeyView {
 /// collection-scoped and will be denied on collections not listed.
 /// When absent (or empty) the key is default-deny for scope-aware routes
 /// but retains its global permissions for role-based routes.
+///
+/// An optional `namespace` (`team-a/*`) confines every call made with
+/// the key to collections whose names start with the prefix.
 pub async fn create_scoped_api_key(
     State(state): State<AuthHandlerState>,
     Extension(auth_state): Extension<AuthState>,
@@ -286,6 +299,37 @@ pub async fn create_scoped_api_key(
         })
         .collect();
 
+    let requested = request
+        .namespace
+        .as_deref()
+        .map(CollectionNamespace::parse)
+        .transpose()
+        .map_err(|e| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(AuthErrorResponse {
+                    error: "invalid_namespace".to_string(),
+                    message: e.to_string(),
+                }),
+            )
+        })?;
+    let namespace = match (&auth_state.user_claims.namespace, requested) {
+        (Some(own), Some(requested)) if !own.covers(&requested) => {
+            return Err((
+                StatusCode::FORBIDDEN,
+                Json(AuthErrorResponse {
+                    error: "forbidden".to_string(),
+                    message: format!(
+                        "namespace '{}' is outside the caller's namespace '{}'",
+                        requested, own
+                    ),
+                }),
+            ));
+        }
+        (Some(own), None) => Some(own.clone()),
+        (_, requested) => requested,
+    };
+
     let expires_at = request
         .expires_in
         .map(|secs| chrono::Utc::now().timestamp() as u64 + secs);
@@ -301,6 +345,14 @@ pub async fn create_scoped_api_key(
         )
         .await
         .map_err(|e| internal_err(&format!("Failed to create API key: {}", e)))?;
+    let key_info = match namespace {
+        Some(namespace) => state
+            .auth_manager
+            .set_api_key_namespace(&key_info.id, Some(namespace))
+            .await
+            .map_err(|e| internal_err(&format!("Failed to create API key: {}", e)))?,
+        None => key_info,
+    };
 
     state.audit_logger.record(
         &auth_state.user_claims.username,
@@ -326,6 +378,7 @@ pub async fn create_scoped_api_key(
                 permissions: s.permissions.clone(),
             })
             .collect(),
+        namespace: key_info.namespace.map(String::from),
         expires_at: key_info.expires_at,
         warning: "Save this API key now! It will not be shown again.".to_string(),
     }))
@@ -145,6 +145,19 @@ pub async fn refresh_token(
 
     let current_token = extract_jwt(request.headers());
 
+    // Identity-provider tokens are not ours to re-sign
+    if let Some(ref token) = current_token
+        && state.auth_manager.is_provider_token(token)
+    {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(AuthErrorResponse {
+                error: "provider_token".to_string(),
+                message: "Tokens from the identity provider are refreshed through it".to_string(),
+            }),
+        ));
+    }
+
     // Check if the current token is blacklisted
     if let Some(ref token) = current_token {
         if state.is_token_blacklisted(token).await {
@@ -300,6 +313,7 @@ pub async fn create_api_key(
         expires_at: key_info.expires_at,
         active: key_info.active,
         usage_count: key_info.usage_count,
+        namespace: key_info.namespace.clone(),
     };
     if let Err(e) = state.persistence.save_api_key(persisted_key) {
         error!("Failed to persist API key to disk: {}", e);
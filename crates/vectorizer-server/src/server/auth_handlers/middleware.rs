@@ -316,6 +316,7 @@ fn anonymous_auth_state() -> AuthState {
             iat: 0,
             exp: 0,
             scopes: vec![],
+            namespace: None,
         },
         authenticated: false,
     }
@@ -348,6 +349,7 @@ pub fn local_dev_admin_state() -> AuthState {
             iat: 0,
             exp: 0,
             scopes: vec![],
+            namespace: None,
         },
         authenticated: true,
     }
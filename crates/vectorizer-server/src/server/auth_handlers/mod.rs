@@ -8,7 +8,7 @@
 //! - [`state`] — `AuthHandlerState`, `UserRecord`, rate-limit bookkeeping,
 //!   persistence bootstrap, and the one-time first-run credentials helper
 //! - [`public`] — unauthenticated endpoints (`/auth/login`,
-//!   `/auth/validate-password`)
+//!   `/auth/validate-password`, `/.well-known/oauth-protected-resource`)
 //! - [`authenticated`] — logged-in endpoints (`/auth/me`, `/auth/logout`,
 //!   `/auth/refresh`, `/auth/keys…`)
 //! - [`admin`] — admin-gated user management endpoints
@@ -51,7 +51,7 @@ pub use middleware::{
     auth_middleware, require_admin_for_rest, require_admin_from_headers, require_admin_middleware,
     require_auth_middleware,
 };
-pub use public::{login, validate_password_endpoint};
+pub use public::{login, protected_resource_metadata, validate_password_endpoint};
 // Internal items that tests reach for via `use super::*`. The outer world
 // doesn't see these — the visibility is scoped to the crate.
 pub(crate) use state::persist_first_run_credentials;
@@ -6,6 +6,8 @@
 //! - `POST /auth/validate-password` is a pure strength/complexity check.
 //!   It does NOT persist anything and is safe to expose to unauthenticated
 //!   clients (e.g. sign-up forms showing live strength feedback).
+//! - `GET /.well-known/oauth-protected-resource` advertises the OIDC
+//!   issuer (RFC 9728) when `auth.oidc` is configured; 404 otherwise.
 
 use axum::extract::State;
 use axum::http::{HeaderMap, StatusCode};
@@ -58,6 +60,7 @@ pub async fn login(
     Json(request): Json<LoginRequest>,
 ) -> Result<Response, (StatusCode, Json<AuthErrorResponse>)> {
     debug!("Login attempt for user: {}", request.username);
+    state.ensure_local_login()?;
 
     // Check rate limiting first
     if let Err((attempts, remaining_secs)) = state.check_login_rate_limit(&request.username).await {
@@ -167,3 +170,16 @@ pub async fn login(
 
     Ok((headers, body).into_response())
 }
+
+/// Protected resource metadata - GET /.well-known/oauth-protected-resource
+///
+/// Tells clients (and the dashboard's SSO button) which issuer's tokens
+/// this server accepts. The `resource` identifier is the configured
+/// audience.
+pub async fn protected_resource_metadata(
+    State(state): State<AuthHandlerState>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let oidc = state.auth_manager.oidc().ok_or(StatusCode::NOT_FOUND)?;
+    let resource = oidc.config().audience.clone();
+    Ok(Json(oidc.protected_resource_metadata(&resource)))
+}
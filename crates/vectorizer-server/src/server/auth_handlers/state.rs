@@ -12,6 +12,8 @@ use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+use axum::http::StatusCode;
+use axum::response::Json;
 use tracing::{debug, error, info, warn};
 use vectorizer::auth::AuthManager;
 use vectorizer::auth::audit::AuditLogger;
@@ -19,6 +21,8 @@ use vectorizer::auth::persistence::{AuthPersistence, PersistedUser};
 use vectorizer::auth::roles::Role;
 use vectorizer::monitoring::api_key_usage::ApiKeyUsageRecorder;
 
+use super::types::AuthErrorResponse;
+
 /// Persist first-run root credentials to a file with the most restrictive
 /// permissions the host supports (0o600 on POSIX). Returns the path written.
 ///
@@ -215,6 +219,7 @@ impl AuthHandlerState {
                 grace_until: None,
                 rotated_to: None,
                 usage_count: persisted_key.usage_count,
+                namespace: persisted_key.namespace,
             };
             if let Err(e) = auth_manager.register_api_key(api_key).await {
                 warn!("Failed to register API key from disk: {}", e);
@@ -226,8 +231,9 @@ impl AuthHandlerState {
             .values()
             .any(|u| u.roles.contains(&Role::Admin));
 
-        // Create root admin if no admin users exist
-        if !has_admin {
+        // Create root admin if no admin users exist. Not when local login
+        // is off: admins then come from the identity provider's roles.
+        if !has_admin && auth_manager.local_login_enabled() {
             let username = root_user.unwrap_or_else(|| "root".to_string());
             let (password, was_generated) = match root_password {
                 Some(pwd) => (pwd, false),
@@ -343,6 +349,22 @@ impl AuthHandlerState {
         password
     }
 
+    /// Refuse with 403 `local_login_disabled` when `auth.oidc.local_login`
+    /// is off, so no password is accepted or stored locally.
+    pub fn ensure_local_login(&self) -> Result<(), (StatusCode, Json<AuthErrorResponse>)> {
+        if self.auth_manager.local_login_enabled() {
+            return Ok(());
+        }
+        Err((
+            StatusCode::FORBIDDEN,
+            Json(AuthErrorResponse {
+                error: "local_login_disabled".to_string(),
+                message: "Local users are disabled; sign in through the identity provider"
+                    .to_string(),
+            }),
+        ))
+    }
+
     /// Check if a token is blacklisted (logged out)
     pub async fn is_token_blacklisted(&self, token: &str) -> bool {
         self.token_blacklist.read().await.contains(token)
@@ -10,10 +10,10 @@
 //! one-sided (`RestOnly` / `McpOnly`).
 //!
 //! What is **not** in this registry: auth/session endpoints (`/auth/*`),
-//! admin / setup / backup / workspace lifecycle, the Qdrant
-//! compatibility surface (`/qdrant/*`), `/replication/*`, `/metrics`,
-//! `/dashboard`, `/graphql`, multipart upload, and the UMICP protocol
-//! adapter. Those are documented as transport-specific by design — see
+//! admin / setup / backup / workspace lifecycle (only the read-only
+//! `workspace.list` is mirrored), the Qdrant compatibility surface
+//! (`/qdrant/*`), `/replication/*`, `/metrics`, `/dashboard`,
+//! `/graphql`, multipart upload, and the UMICP protocol adapter. Those are documented as transport-specific by design — see
 //! `docs/architecture/capabilities.md` for the rationale.
 //!
 //! The registry is consumed by:
@@ -246,6 +246,15 @@ pub fn inventory() -> Vec<Capability> {
             auth: AuthBucket::User,
             transport: Transport::RestOnly,
         },
+        Capability {
+            id: "search.by_text_batch",
+            summary: "Run many text queries against one collection with a single embedding pass.",
+            mcp_tool_name: None,
+            mcp_input_schema: None,
+            rest: Some(("POST", "/collections/{name}/search/text/batch")),
+            auth: AuthBucket::User,
+            transport: Transport::RestOnly,
+        },
         Capability {
             id: "search.by_file",
             summary: "Search a specific collection using an uploaded file as the query.",
@@ -255,6 +264,123 @@ pub fn inventory() -> Vec<Capability> {
             auth: AuthBucket::User,
             transport: Transport::RestOnly,
         },
+        Capability {
+            id: "search.full_text",
+            summary: "Lexical BM25 search over a collection's payload text fields with highlighted snippets.",
+            mcp_tool_name: None,
+            mcp_input_schema: None,
+            rest: Some(("POST", "/collections/{name}/search/full_text")),
+            auth: AuthBucket::User,
+            transport: Transport::RestOnly,
+        },
+        Capability {
+            id: "vector.similarity_matrix",
+            summary: "Pairwise similarity/distance matrix for a bounded set of vector IDs.",
+            mcp_tool_name: None,
+            mcp_input_schema: None,
+            rest: Some(("POST", "/collections/{name}/similarity_matrix")),
+            auth: AuthBucket::User,
+            transport: Transport::RestOnly,
+        },
+        Capability {
+            id: "vector.get_many",
+            summary: "Fetch many vectors by id in one request, with or without their data and payloads.",
+            mcp_tool_name: None,
+            mcp_input_schema: None,
+            rest: Some(("POST", "/collections/{name}/vectors/get")),
+            auth: AuthBucket::User,
+            transport: Transport::RestOnly,
+        },
+        Capability {
+            id: "vector.scroll",
+            summary: "Page through the vectors matching a filter in id order, following a cursor.",
+            mcp_tool_name: None,
+            mcp_input_schema: None,
+            rest: Some(("POST", "/collections/{name}/scroll")),
+            auth: AuthBucket::User,
+            transport: Transport::RestOnly,
+        },
+        Capability {
+            id: "vector.count",
+            summary: "Count the vectors matching a filter, exactly or estimated from a sample.",
+            mcp_tool_name: None,
+            mcp_input_schema: None,
+            rest: Some(("POST", "/collections/{name}/count")),
+            auth: AuthBucket::User,
+            transport: Transport::RestOnly,
+        },
+        Capability {
+            id: "vector.sample",
+            summary: "Random sample of a collection's vectors with payload previews and their nearest neighbors.",
+            mcp_tool_name: None,
+            mcp_input_schema: None,
+            rest: Some(("GET", "/collections/{name}/sample")),
+            auth: AuthBucket::User,
+            transport: Transport::RestOnly,
+        },
+        Capability {
+            id: "vector.neighbors",
+            summary: "Nearest neighbors of a stored vector with scores, distances and payload previews.",
+            mcp_tool_name: None,
+            mcp_input_schema: None,
+            rest: Some(("GET", "/collections/{name}/vectors/{id}/neighbors")),
+            auth: AuthBucket::User,
+            transport: Transport::RestOnly,
+        },
+        Capability {
+            id: "vector.projection",
+            summary: "2D projection (PCA, or UMAP when built with the feature) of a sample of a collection for scatter plots.",
+            mcp_tool_name: None,
+            mcp_input_schema: None,
+            rest: Some(("POST", "/collections/{name}/projection")),
+            auth: AuthBucket::User,
+            transport: Transport::RestOnly,
+        },
+        Capability {
+            id: "vector.duplicates",
+            summary: "Near-duplicate report: groups of vectors scoring above a similarity threshold, with payload previews, run as a job.",
+            mcp_tool_name: None,
+            mcp_input_schema: None,
+            rest: Some(("POST", "/collections/{name}/duplicates")),
+            auth: AuthBucket::User,
+            transport: Transport::RestOnly,
+        },
+        Capability {
+            id: "vector.cluster",
+            summary: "Cluster a collection with mini-batch k-means (or HDBSCAN when built with the feature), writing cluster ids into payloads, run as a job.",
+            mcp_tool_name: None,
+            mcp_input_schema: None,
+            rest: Some(("POST", "/collections/{name}/cluster")),
+            auth: AuthBucket::User,
+            transport: Transport::RestOnly,
+        },
+        Capability {
+            id: "vector.aggregate",
+            summary: "Mean or medoid vector of the vectors matching a filter, optionally searched with in the same call.",
+            mcp_tool_name: None,
+            mcp_input_schema: None,
+            rest: Some(("POST", "/collections/{name}/aggregate")),
+            auth: AuthBucket::User,
+            transport: Transport::RestOnly,
+        },
+        Capability {
+            id: "vector.facets",
+            summary: "Value counts of payload keys over the vectors matching a filter or a query's candidates, for filter UIs.",
+            mcp_tool_name: None,
+            mcp_input_schema: None,
+            rest: Some(("POST", "/collections/{name}/facets")),
+            auth: AuthBucket::User,
+            transport: Transport::RestOnly,
+        },
+        Capability {
+            id: "search.federation_sources",
+            summary: "List the remote Vectorizer instances multi-collection search can federate to.",
+            mcp_tool_name: None,
+            mcp_input_schema: None,
+            rest: Some(("GET", "/federation/sources")),
+            auth: AuthBucket::User,
+            transport: Transport::RestOnly,
+        },
         // -----------------------------------------------------------------
         // Discovery
         // -----------------------------------------------------------------
@@ -324,6 +450,36 @@ pub fn inventory() -> Vec<Capability> {
             auth: AuthBucket::User,
             transport: Transport::Both,
         },
+        Capability {
+            id: "file.get_summary",
+            summary: "Generate an extractive and/or structural summary of an indexed file.",
+            mcp_tool_name: Some("get_file_summary"),
+            mcp_input_schema: Some(schema_get_file_summary),
+            rest: Some(("POST", "/file/summary")),
+            auth: AuthBucket::User,
+            transport: Transport::Both,
+        },
+        Capability {
+            id: "file.search_by_type",
+            summary: "Semantic search restricted to files with the given extensions.",
+            mcp_tool_name: Some("search_by_file_type"),
+            mcp_input_schema: Some(schema_search_by_file_type),
+            rest: Some(("POST", "/file/search_by_type")),
+            auth: AuthBucket::User,
+            transport: Transport::Both,
+        },
+        // -----------------------------------------------------------------
+        // Workspace (read-only; add/remove/config stay admin REST)
+        // -----------------------------------------------------------------
+        Capability {
+            id: "workspace.list",
+            summary: "List the registered workspaces with their path, collection and indexing status.",
+            mcp_tool_name: Some("list_workspaces"),
+            mcp_input_schema: Some(schema_empty_object),
+            rest: Some(("GET", "/workspace/list")),
+            auth: AuthBucket::User,
+            transport: Transport::Both,
+        },
         // -----------------------------------------------------------------
         // Graph
         // -----------------------------------------------------------------
@@ -437,6 +593,24 @@ pub fn inventory() -> Vec<Capability> {
         // -----------------------------------------------------------------
         // Collection maintenance
         // -----------------------------------------------------------------
+        Capability {
+            id: "collection.list_templates",
+            summary: "List the collection templates declared in config.yml (usable via `template` on create).",
+            mcp_tool_name: None,
+            mcp_input_schema: None,
+            rest: Some(("GET", "/collections/templates")),
+            auth: AuthBucket::User,
+            transport: Transport::RestOnly,
+        },
+        Capability {
+            id: "collection.set_cold",
+            summary: "Move a collection's HNSW index to disk (served via mmap, kept across restarts) or back into memory.",
+            mcp_tool_name: None,
+            mcp_input_schema: None,
+            rest: Some(("POST", "/collections/{name}/cold")),
+            auth: AuthBucket::User,
+            transport: Transport::RestOnly,
+        },
         Capability {
             id: "collection.list_empty",
             summary: "List all collections that have zero vectors. Useful for identifying collections that can be cleaned up.",
@@ -642,6 +816,15 @@ pub fn inventory() -> Vec<Capability> {
             auth: AuthBucket::Public,
             transport: Transport::RestOnly,
         },
+        Capability {
+            id: "vector.insert_image",
+            summary: "Insert an image (base64 JSON or multipart) into a collection, embedded by its multimodal provider. REST-only because MCP tool calls carry no binary payloads.",
+            mcp_tool_name: None,
+            mcp_input_schema: None,
+            rest: Some(("POST", "/insert_image")),
+            auth: AuthBucket::User,
+            transport: Transport::RestOnly,
+        },
     ]
 }
 
@@ -803,6 +986,16 @@ fn schema_multi_collection_search() -> Value {
                 "description": "Total maximum results",
                 "default": 20
             },
+            "score_normalization": {
+                "type": "string",
+                "enum": ["none", "min_max", "z_score", "rrf"],
+                "description": "Rescale each collection's scores before merging, so collections with different metrics or models compete fairly (rrf = reciprocal rank fusion). Raw scores when omitted"
+            },
+            "collection_weights": {
+                "type": "object",
+                "additionalProperties": { "type": "number" },
+                "description": "Score multiplier per collection name (default 1.0)"
+            },
             "similarity_threshold": {
                 "type": "number",
                 "description": "Minimum similarity score 0.0-1.0",
@@ -833,6 +1026,37 @@ fn schema_search_intelligent() -> Value {
                 "description": "Enable domain-specific query expansion",
                 "default": true
             },
+            "translate_to": {
+                "type": "array",
+                "items": { "type": "string" },
+                "description": "Language codes (e.g. [\"pt\"]) to also search the query translated into, for documents in other languages"
+            },
+            "graph_expansion": {
+                "type": "object",
+                "description": "Expand the top hits through the collection graph before reranking (GraphRAG)",
+                "properties": {
+                    "hops": {
+                        "type": "integer",
+                        "description": "Hops to walk from each hit (1-3)",
+                        "default": 1
+                    },
+                    "edge_types": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Edge types to follow: SIMILAR_TO, REFERENCES, CONTAINS, DERIVED_FROM, MENTIONS, RELATED_TO, SAME_FILE (default: all)"
+                    },
+                    "max_neighbors": {
+                        "type": "integer",
+                        "description": "Most neighbors added per hit",
+                        "default": 5
+                    },
+                    "score_decay": {
+                        "type": "number",
+                        "description": "Score multiplier per hop",
+                        "default": 0.85
+                    }
+                }
+            },
             "similarity_threshold": {
                 "type": "number",
                 "description": "Minimum similarity score 0.0-1.0",
@@ -918,7 +1142,7 @@ fn schema_search_hybrid() -> Value {
             },
             "alpha": {
                 "type": "number",
-                "description": "Weight for dense search (0.0 = pure sparse, 1.0 = pure dense)",
+                "description": "Weight for dense search (0.0 = pure sparse, 1.0 = pure dense). Without alpha and algorithm, the collection's tuned hybrid weights are used if it has any",
                 "default": 0.7,
                 "minimum": 0.0,
                 "maximum": 1.0
@@ -1133,6 +1357,56 @@ fn schema_get_related_files() -> Value {
     })
 }
 
+fn schema_get_file_summary() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "collection": { "type": "string", "description": "Collection name" },
+            "file_path": { "type": "string", "description": "File path" },
+            "summary_type": {
+                "type": "string",
+                "description": "Kind of summary to generate",
+                "enum": ["extractive", "structural", "both"],
+                "default": "both"
+            },
+            "max_sentences": {
+                "type": "integer",
+                "description": "Maximum sentences in the extractive summary",
+                "default": 5,
+                "minimum": 1,
+                "maximum": 20
+            }
+        },
+        "required": ["collection", "file_path"]
+    })
+}
+
+fn schema_search_by_file_type() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "collection": { "type": "string", "description": "Collection name" },
+            "query": { "type": "string", "description": "Search query" },
+            "file_types": {
+                "type": "array",
+                "items": { "type": "string" },
+                "description": "File extensions to include (e.g. [\"rs\", \"md\"])"
+            },
+            "limit": {
+                "type": "integer",
+                "description": "Maximum number of results",
+                "default": 10
+            },
+            "return_full_files": {
+                "type": "boolean",
+                "description": "Return whole files instead of matching chunks",
+                "default": false
+            }
+        },
+        "required": ["collection", "query", "file_types"]
+    })
+}
+
 fn schema_graph_list_nodes() -> Value {
     json!({
         "type": "object",
@@ -1167,8 +1441,8 @@ fn schema_graph_find_related() -> Value {
             },
             "relationship_type": {
                 "type": "string",
-                "description": "Filter by relationship type (SIMILAR_TO, REFERENCES, CONTAINS, DERIVED_FROM)",
-                "enum": ["SIMILAR_TO", "REFERENCES", "CONTAINS", "DERIVED_FROM"]
+                "description": "Filter by relationship type (SIMILAR_TO, REFERENCES, CONTAINS, DERIVED_FROM, MENTIONS, RELATED_TO)",
+                "enum": ["SIMILAR_TO", "REFERENCES", "CONTAINS", "DERIVED_FROM", "MENTIONS", "RELATED_TO"]
             }
         },
         "required": ["collection", "node_id"]
@@ -1197,7 +1471,7 @@ fn schema_graph_create_edge() -> Value {
             "relationship_type": {
                 "type": "string",
                 "description": "Type of relationship",
-                "enum": ["SIMILAR_TO", "REFERENCES", "CONTAINS", "DERIVED_FROM"]
+                "enum": ["SIMILAR_TO", "REFERENCES", "CONTAINS", "DERIVED_FROM", "MENTIONS", "RELATED_TO"]
             },
             "weight": {
                 "type": "number",
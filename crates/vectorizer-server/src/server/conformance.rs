@@ -0,0 +1,261 @@
+//! Conformance mode (`vectorizer --conformance`): a throwaway server for
+//! checking SDKs against the REST API.
+//!
+//! The suite (`conformance/suite.json`, embedded at build time) lists
+//! canonical requests with the responses the server gives and the SDK
+//! method that covers each one, per language. In conformance mode the
+//! server runs on an in-memory store with a BM25 embedder fitted on a
+//! fixed corpus and no auth, and additionally serves:
+//!
+//! - `GET /conformance/suite` — the suite of this server version
+//! - `POST /conformance/reset` — drop every collection, so each run
+//!   starts from an empty server
+//!
+//! An SDK runner fetches the suite, calls the method each case names
+//! with the case's `params` and fails on a missing method or an outcome
+//! that differs from `expect`. [`check_response`] is the reference
+//! matcher for the raw responses.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use axum::Router;
+use axum::extract::State;
+use axum::http::header;
+use axum::response::{IntoResponse, Json};
+use axum::routing::{get, post};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use tracing::{info, warn};
+use vectorizer::VectorStore;
+use vectorizer::embedding::{Bm25Embedding, EmbeddingManager};
+
+use crate::server::VectorizerServer;
+
+/// The suite shipped with this server version.
+pub const SUITE_JSON: &str = include_str!("../../conformance/suite.json");
+
+/// Expected value that matches anything present.
+const ANY: &str = "$any";
+
+/// Tolerance for numbers, which go through `f32` in vector data.
+const NUMBER_TOLERANCE: f64 = 1e-6;
+
+/// Dimension of the conformance embedder. Collections must match their
+/// provider's dimension, so the suite's collections use this one and
+/// its vectors stay short enough to read.
+pub const EMBEDDING_DIMENSION: usize = 4;
+
+/// Corpus the BM25 embedder is fitted on, so text embeddings are the
+/// same on every run.
+const BM25_CORPUS: &[&str] = &[
+    "the quick brown fox jumps over the lazy dog",
+    "vector databases store high dimensional embeddings",
+    "semantic search finds documents by meaning not keywords",
+    "machine learning models transform text into numeric vectors",
+];
+
+/// The conformance suite.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConformanceSuite {
+    /// Bumped when cases change incompatibly
+    pub version: u32,
+    pub description: String,
+    /// How `expect.body` is compared, for runner authors
+    #[serde(default)]
+    pub matching: String,
+    /// Cases, in the order they must run
+    pub cases: Vec<ConformanceCase>,
+}
+
+/// One canonical request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConformanceCase {
+    pub id: String,
+    pub description: String,
+    pub request: CaseRequest,
+    pub expect: CaseExpectation,
+    /// Arguments of the SDK call, named after the request fields
+    #[serde(default)]
+    pub params: Value,
+    /// SDK method covering the case, by language
+    #[serde(default)]
+    pub sdk: BTreeMap<String, String>,
+}
+
+/// The raw REST request of a case.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaseRequest {
+    pub method: String,
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<Value>,
+}
+
+/// The response a case expects.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaseExpectation {
+    pub status: u16,
+    /// Subset of the response body, see [`check_response`]
+    #[serde(default)]
+    pub body: Value,
+}
+
+/// Parse the embedded suite.
+pub fn suite() -> serde_json::Result<ConformanceSuite> {
+    serde_json::from_str(SUITE_JSON)
+}
+
+/// Check a response against a case's expectation. `expect.body` is a
+/// subset of `body`: objects may carry extra fields, arrays must have
+/// the same length and match element-wise, numbers compare by value and
+/// `"$any"` matches anything present. The error names the first
+/// mismatch.
+pub fn check_response(expect: &CaseExpectation, status: u16, body: &Value) -> Result<(), String> {
+    if status != expect.status {
+        return Err(format!("status {status}, expected {}", expect.status));
+    }
+    match_subset(&expect.body, body, "$")
+}
+
+fn match_subset(expected: &Value, actual: &Value, at: &str) -> Result<(), String> {
+    match (expected, actual) {
+        (Value::String(s), _) if s == ANY => Ok(()),
+        (Value::Object(fields), Value::Object(actual_fields)) => {
+            for (key, value) in fields {
+                let path = format!("{at}.{key}");
+                let found = actual_fields
+                    .get(key)
+                    .ok_or_else(|| format!("{path}: missing"))?;
+                match_subset(value, found, &path)?;
+            }
+            Ok(())
+        }
+        (Value::Array(items), Value::Array(actual_items)) => {
+            if items.len() != actual_items.len() {
+                return Err(format!(
+                    "{at}: {} items, expected {}",
+                    actual_items.len(),
+                    items.len()
+                ));
+            }
+            items
+                .iter()
+                .zip(actual_items)
+                .enumerate()
+                .try_for_each(|(i, (item, found))| match_subset(item, found, &format!("{at}[{i}]")))
+        }
+        (Value::Number(e), Value::Number(a))
+            if e.as_f64()
+                .zip(a.as_f64())
+                .is_some_and(|(e, a)| (e - a).abs() <= NUMBER_TOLERANCE) =>
+        {
+            Ok(())
+        }
+        _ if expected == actual => Ok(()),
+        _ => Err(format!("{at}: got {actual}, expected {expected}")),
+    }
+}
+
+/// Routes only conformance mode serves.
+pub fn conformance_routes(server: VectorizerServer) -> Router {
+    Router::new()
+        .route("/conformance/suite", get(get_suite))
+        .route("/conformance/reset", post(reset))
+        .with_state(server)
+}
+
+/// GET /conformance/suite
+async fn get_suite() -> impl IntoResponse {
+    ([(header::CONTENT_TYPE, "application/json")], SUITE_JSON)
+}
+
+/// POST /conformance/reset
+async fn reset(State(state): State<VectorizerServer>) -> Json<Value> {
+    let names = state.store.list_collections();
+    for name in &names {
+        if let Err(e) = state.store.delete_collection(name) {
+            warn!("Conformance reset could not delete '{}': {}", name, e);
+        }
+        state.query_cache.invalidate_collection(name);
+    }
+    Json(json!({ "deleted": names.len() }))
+}
+
+/// The embedder conformance mode serves: BM25 with
+/// [`EMBEDDING_DIMENSION`] dimensions fitted on a fixed corpus, as the
+/// default provider.
+pub fn embedding_manager() -> anyhow::Result<EmbeddingManager> {
+    let mut bm25 = Bm25Embedding::new(EMBEDDING_DIMENSION);
+    bm25.build_vocabulary(
+        &BM25_CORPUS
+            .iter()
+            .map(|s| (*s).to_string())
+            .collect::<Vec<_>>(),
+    );
+    let mut embedding_manager = EmbeddingManager::new();
+    embedding_manager.register_provider("bm25".to_string(), Box::new(bm25));
+    embedding_manager.set_default_provider("bm25")?;
+    Ok(embedding_manager)
+}
+
+/// Run the conformance server on `host:port` until Ctrl+C.
+pub async fn serve(host: &str, port: u16) -> anyhow::Result<()> {
+    let server = VectorizerServer::new_for_test_harness(
+        Arc::new(VectorStore::new_cpu_only()),
+        Arc::new(embedding_manager()?),
+    );
+    let app = server
+        .build_router(false)
+        .await
+        .merge(conformance_routes(server.clone()));
+
+    let listener = tokio::net::TcpListener::bind(format!("{host}:{port}")).await?;
+    info!(
+        "🧪 Conformance server listening on {}:{} (suite at /conformance/suite)",
+        host, port
+    );
+    axum::serve(listener, app)
+        .with_graceful_shutdown(async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn embedded_suite_parses_with_unique_ids() {
+        let suite = suite().expect("suite.json parses");
+        assert!(!suite.cases.is_empty());
+        let mut ids: Vec<_> = suite.cases.iter().map(|c| c.id.as_str()).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        assert_eq!(ids.len(), suite.cases.len(), "duplicate case id");
+        assert!(suite.cases.iter().all(|c| c.sdk.contains_key("rust")));
+    }
+
+    #[test]
+    fn matcher_treats_expectations_as_subsets() {
+        let expect = CaseExpectation {
+            status: 200,
+            body: json!({"id": "v1", "vector": [1.0, 0], "score": "$any"}),
+        };
+        let body = json!({"id": "v1", "vector": [1, 0.0], "score": 0.5, "extra": true});
+        assert!(check_response(&expect, 200, &body).is_ok());
+        assert!(check_response(&expect, 404, &body).is_err());
+
+        let short = json!({"id": "v1", "vector": [1.0], "score": 0.5});
+        let err = check_response(&expect, 200, &short).unwrap_err();
+        assert!(err.starts_with("$.vector"), "{err}");
+        let missing = json!({"id": "v1", "vector": [1.0, 0.0]});
+        assert_eq!(
+            check_response(&expect, 200, &missing).unwrap_err(),
+            "$.score: missing"
+        );
+    }
+}
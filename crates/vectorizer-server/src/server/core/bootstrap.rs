@@ -25,7 +25,7 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, error, info, warn};
 use vectorizer::VectorStore;
-use vectorizer::embedding::{EmbeddingManager, EmbeddingProvider};
+use vectorizer::embedding::{EmbeddingCache, EmbeddingManager, EmbeddingProvider};
 use vectorizer::file_watcher::MetricsCollector;
 
 use super::workspace_loader::{load_file_watcher_config, load_workspace_collections};
@@ -39,6 +39,8 @@ use crate::server::{AuthHandlerState, RootUserConfig, VectorizerServer};
 ///   - `"bm25"` (default) — handled by the caller via `Bm25Embedding::new(dim)`
 ///   - `"fastembed:<model-id>"` — resolved via
 ///     `vectorizer::embedding::providers::fastembed::parse_model_id`
+///   - `"clip"` — CLIP ViT-B/32 image + text encoders (requires the
+///     `onnx-models` feature)
 ///
 /// An unknown prefix / unresolvable fastembed id returns `Err` so boot
 /// fails fast instead of silently falling back to BM25.
@@ -59,6 +61,73 @@ fn resolve_embedding_model_name(config_path: &str) -> anyhow::Result<String> {
     Ok(model)
 }
 
+/// Parse `embedding.providers` from `config.yml`: models to register next
+/// to the default one, in the same forms as `embedding.model`, so
+/// collections can pick them as `embedding_provider`:
+///
+/// ```yaml
+/// embedding:
+///   model: fastembed:all-MiniLM-L6-v2
+///   providers: [clip]
+/// ```
+pub(super) fn resolve_extra_embedding_models(config_path: &str) -> Vec<String> {
+    let Ok(content) = std::fs::read_to_string(config_path) else {
+        return Vec::new();
+    };
+    let Ok(value) = serde_yaml::from_str::<serde_yaml::Value>(&content) else {
+        return Vec::new();
+    };
+    value
+        .get("embedding")
+        .and_then(|e| e.get("providers"))
+        .and_then(|p| p.as_sequence())
+        .map(|models| {
+            models
+                .iter()
+                .filter_map(|m| m.as_str())
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Parse `embedding.model_aliases` from `config.yml`: model names the
+/// OpenAI-compatible `POST /v1/embeddings` accepts for a registered
+/// provider, so clients keep sending the model name they were written for:
+///
+/// ```yaml
+/// embedding:
+///   model: fastembed:all-MiniLM-L6-v2
+///   model_aliases:
+///     text-embedding-3-small: fastembed:all-MiniLM-L6-v2
+/// ```
+pub(super) fn resolve_embedding_model_aliases(config_path: &str) -> Vec<(String, String)> {
+    let Ok(content) = std::fs::read_to_string(config_path) else {
+        return Vec::new();
+    };
+    let Ok(value) = serde_yaml::from_str::<serde_yaml::Value>(&content) else {
+        return Vec::new();
+    };
+    value
+        .get("embedding")
+        .and_then(|e| e.get("model_aliases"))
+        .and_then(|a| a.as_mapping())
+        .map(|aliases| {
+            aliases
+                .iter()
+                .filter_map(|(alias, provider)| {
+                    Some((
+                        alias.as_str()?.trim().to_string(),
+                        provider.as_str()?.trim().to_string(),
+                    ))
+                })
+                .filter(|(alias, provider)| !alias.is_empty() && !provider.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 /// Build the default embedding provider for a fresh `EmbeddingManager`
 /// according to `config.embedding.model`. Returns `(name, dimension,
 /// boxed_provider)`.
@@ -69,11 +138,20 @@ fn resolve_embedding_model_name(config_path: &str) -> anyhow::Result<String> {
 /// three must point at the same provider shape so text indexed by the
 /// file watcher lands in the same embedding space as text indexed via
 /// `POST /insert`.
-fn build_default_provider(
+pub(super) fn build_default_provider(
     config_path: &str,
 ) -> anyhow::Result<(String, usize, Box<dyn EmbeddingProvider>)> {
     let model = resolve_embedding_model_name(config_path)?;
+    build_embedding_provider(&model)
+}
 
+/// Build the provider for an `embedding.model` value (see
+/// [`resolve_embedding_model_name`] for the recognized forms). Returns
+/// `(name, dimension, boxed_provider)`. Also used by the provider reload
+/// endpoint to load a new model version at runtime.
+pub(crate) fn build_embedding_provider(
+    model: &str,
+) -> anyhow::Result<(String, usize, Box<dyn EmbeddingProvider>)> {
     if let Some(fastembed_id) = model.strip_prefix("fastembed:") {
         let cache_dir = vectorizer_core::paths::data_dir().join("fastembed");
         let provider = vectorizer::embedding::providers::try_build_fastembed_provider(
@@ -92,6 +170,19 @@ fn build_default_provider(
         return Ok((name, dim, provider));
     }
 
+    if model == "clip" {
+        let cache_dir = vectorizer_core::paths::data_dir().join("clip");
+        let provider = vectorizer::embedding::providers::try_build_clip_provider(cache_dir.clone())
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+        let dim = provider.dimension();
+        info!(
+            "🧠 Embedding provider: clip (image + text, dim={}, cache_dir={})",
+            dim,
+            cache_dir.display()
+        );
+        return Ok(("clip".to_string(), dim, provider));
+    }
+
     if model == "bm25" {
         info!("🧠 Embedding provider: bm25 (default, dim=512)");
         return Ok((
@@ -104,11 +195,66 @@ fn build_default_provider(
     Err(anyhow::anyhow!(
         "Unknown embedding model '{}'. Supported prefixes: \"bm25\" (default), \
          \"fastembed:<model-id>\" (requires the fastembed Cargo feature at \
-         compile time). See docs/specs/EMBEDDING.md for the full matrix.",
+         compile time), \"clip\" (requires the onnx-models Cargo feature). \
+         See docs/specs/EMBEDDING.md for the full matrix.",
         model
     ))
 }
 
+/// Open the persisted embedding cache configured under
+/// `embedding.cache` in `config.yml`:
+///
+/// ```yaml
+/// embedding:
+///   cache:
+///     enabled: true
+///     dir: ./data/embedding_cache   # default: <data_dir>/embedding_cache
+///     max_size_mb: 2048
+/// ```
+///
+/// `None` when it is disabled (the default) or cannot be opened; the
+/// cache only saves work, so a broken one is logged and skipped.
+pub(super) fn open_embedding_cache(config_path: &str) -> Option<Arc<EmbeddingCache>> {
+    let content = std::fs::read_to_string(config_path).ok()?;
+    let value = serde_yaml::from_str::<serde_yaml::Value>(&content).ok()?;
+    let section = value.get("embedding")?.get("cache")?;
+    if !section
+        .get("enabled")
+        .and_then(|e| e.as_bool())
+        .unwrap_or(false)
+    {
+        return None;
+    }
+    let mut config = vectorizer::embedding::CacheConfig {
+        cache_dir: VectorStore::get_data_dir().join("embedding_cache"),
+        ..Default::default()
+    };
+    if let Some(dir) = section.get("dir").and_then(|d| d.as_str()) {
+        config.cache_dir = std::path::PathBuf::from(dir);
+    }
+    if let Some(max_size_mb) = section.get("max_size_mb").and_then(|m| m.as_u64()) {
+        config.max_size = (max_size_mb as usize).saturating_mul(1024 * 1024);
+    }
+    match EmbeddingCache::new(config.clone()) {
+        Ok(cache) => {
+            info!(
+                "💾 Embedding cache: {} ({} embeddings)",
+                config.cache_dir.display(),
+                cache.len()
+            );
+            Some(Arc::new(cache))
+        }
+        Err(e) => {
+            warn!(
+                "⚠️ Embedding cache at {} disabled: {}",
+                config.cache_dir.display(),
+                e
+            );
+            None
+        }
+    }
+}
+
 /// Register every embedding provider the binary was built with on
 /// `manager`, then set `default_name` as the default. Always registers
 /// `bm25` so the sparse lane is available for hybrid retrieval.
@@ -120,12 +266,28 @@ fn build_default_provider(
 /// with the fastembed feature. Registering every available provider
 /// (the default plus `bm25` as the always-on sparse fallback) makes
 /// the contract change actionable.
-fn register_all_providers(
+///
+/// `extra_models` (from `embedding.providers`, see
+/// [`resolve_extra_embedding_models`]) are built and registered too;
+/// one that cannot be built fails boot like the default would.
+///
+/// Every manager shares `cache`, if one is configured (see
+/// [`open_embedding_cache`]).
+pub(super) fn register_all_providers(
     manager: &mut EmbeddingManager,
     default_name: String,
     default_provider: Box<dyn EmbeddingProvider>,
+    extra_models: &[String],
+    cache: Option<&Arc<EmbeddingCache>>,
 ) -> anyhow::Result<()> {
     manager.register_provider(default_name.clone(), default_provider);
+    for model in extra_models {
+        if manager.has_provider(model) {
+            continue;
+        }
+        let (name, _dim, provider) = build_embedding_provider(model)?;
+        manager.register_provider(name, provider);
+    }
     // `bm25` is always-on so `POST /collections {embedding_provider:
     // "bm25"}` works on every build, even one configured to default to
     // fastembed. Skip re-registration if the default was already bm25.
@@ -137,6 +299,9 @@ fn register_all_providers(
         );
     }
     manager.set_default_provider(&default_name)?;
+    if let Some(cache) = cache {
+        manager.set_cache(cache.clone());
+    }
     Ok(())
 }
 
@@ -282,10 +447,29 @@ impl VectorizerServer {
             warn!("Failed to initialize monitoring system: {}", e);
         }
 
-        // Try to initialize OpenTelemetry (optional, graceful degradation)
-        if let Err(e) = vectorizer::monitoring::telemetry::try_init("vectorizer", None) {
-            warn!("OpenTelemetry not available: {}", e);
-        }
+        // Cross-lingual query translation (`intelligent_search.translation`)
+        let query_translation = std::fs::read_to_string(&config_path)
+            .ok()
+            .and_then(|content| serde_yaml::from_str::<serde_yaml::Value>(&content).ok())
+            .and_then(|config| {
+                config
+                    .get("intelligent_search")?
+                    .get("translation")
+                    .cloned()
+            })
+            .and_then(|translation| {
+                serde_yaml::from_value::<
+                    vectorizer::intelligent_search::translation::QueryTranslationConfig,
+                >(translation)
+                .ok()
+            })
+            .unwrap_or_default();
+        vectorizer::intelligent_search::translation::init_global_query_translation(
+            query_translation,
+        );
+
+        // OpenTelemetry span export, when configured, is set up with the
+        // log subscriber in `logging::init_logging_with_level`.
 
         // Initialize VectorStore with auto-save enabled
         let vector_store = VectorStore::new_auto();
@@ -317,13 +501,21 @@ impl VectorizerServer {
         }
 
         info!("🔍 PRE_INIT: Creating embedding manager...");
+        let embedding_cache = open_embedding_cache(&config_path);
+        let extra_embedding_models = resolve_extra_embedding_models(&config_path);
         let mut embedding_manager = EmbeddingManager::new();
         let (provider_name, _provider_dim, provider) = build_default_provider(&config_path)?;
         info!(
             "🔍 PRE_INIT: Registering '{}' provider (dim {}) as default",
             provider_name, _provider_dim
         );
-        register_all_providers(&mut embedding_manager, provider_name.clone(), provider)?;
+        register_all_providers(
+            &mut embedding_manager,
+            provider_name.clone(),
+            provider,
+            &extra_embedding_models,
+            embedding_cache.as_ref(),
+        )?;
         info!(
             "✅ PRE_INIT: Embedding manager configured (providers: {:?}, default: {})",
             embedding_manager.list_providers(),
@@ -343,10 +535,14 @@ impl VectorizerServer {
         let mut embedding_manager_for_watcher = EmbeddingManager::new();
         let (watcher_provider_name, _watcher_dim, watcher_provider) =
             build_default_provider(&config_path)?;
+        // The watcher only embeds text with the default provider, so it
+        // skips the extra `embedding.providers` models
         register_all_providers(
             &mut embedding_manager_for_watcher,
             watcher_provider_name.clone(),
             watcher_provider,
+            &[],
+            embedding_cache.as_ref(),
         )?;
         info!(
             "✅ STEP 2: File watcher embedding manager initialized with default '{}'",
@@ -385,6 +581,25 @@ impl VectorizerServer {
             })
             .unwrap_or(false); // Default to disabled if not found
 
+        // OCR of watched images (`file_watcher.ocr`)
+        let watcher_ocr = std::fs::read_to_string(&config_path)
+            .ok()
+            .and_then(|content| serde_yaml::from_str::<serde_yaml::Value>(&content).ok())
+            .and_then(|config| config.get("file_watcher")?.get("ocr").cloned())
+            .and_then(|ocr| serde_yaml::from_value::<vectorizer::ocr::OcrConfig>(ocr).ok());
+
+        // Transcription of watched audio (`file_watcher.transcription`)
+        let watcher_transcription = std::fs::read_to_string(&config_path)
+            .ok()
+            .and_then(|content| serde_yaml::from_str::<serde_yaml::Value>(&content).ok())
+            .and_then(|config| config.get("file_watcher")?.get("transcription").cloned())
+            .and_then(|transcription| {
+                serde_yaml::from_value::<vectorizer::transcription::TranscriptionConfig>(
+                    transcription,
+                )
+                .ok()
+            });
+
         // Disable file watcher if cluster mode is enabled and requires it
         let file_watcher_enabled = if cluster_config_for_watcher.enabled
             && cluster_config_for_watcher.memory.disable_file_watcher
@@ -415,10 +630,16 @@ impl VectorizerServer {
                 info!("🔍 STEP 5: Creating FileWatcherSystem instance...");
 
                 // Load file watcher configuration from workspace
-                let watcher_config = load_file_watcher_config().await.unwrap_or_else(|e| {
+                let mut watcher_config = load_file_watcher_config().await.unwrap_or_else(|e| {
                     warn!("Failed to load file watcher config: {}, using defaults", e);
                     vectorizer::file_watcher::FileWatcherConfig::default()
                 });
+                if let Some(ocr) = watcher_ocr {
+                    watcher_config.ocr = ocr;
+                }
+                if let Some(transcription) = watcher_transcription {
+                    watcher_config.transcription = transcription;
+                }
 
                 let mut watcher_system = vectorizer::file_watcher::FileWatcherSystem::new(
                     watcher_config,
@@ -477,6 +698,21 @@ impl VectorizerServer {
             None
         };
 
+        // Lazy loading must be in place before the background load, which
+        // then leaves `.vecdb` collections on disk until first accessed
+        if loaded_config.lazy_loading.enabled {
+            let residency = vectorizer::db::Residency::from_config(&loaded_config.lazy_loading)?;
+            let sweep_interval = residency.sweep_interval();
+            info!(
+                "💤 Lazy collection loading: at most {} resident collections, {} MB budget (0 = unlimited)",
+                loaded_config.lazy_loading.max_resident_collections,
+                loaded_config.lazy_loading.memory_budget_mb
+            );
+            store_arc.set_residency(residency);
+            // Lives for the whole process, like the read-through evictor.
+            vectorizer::db::ResidencyEvictor::spawn(store_arc.clone(), sweep_interval);
+        }
+
         // Create cancellation token for background task
         let (cancel_tx, mut cancel_rx) = tokio::sync::watch::channel(false);
 
@@ -493,7 +729,14 @@ impl VectorizerServer {
                 })
                 .map(|cfg| vectorizer::db::BackpressureGuard::from_config(&cfg.backpressure));
 
+        let readiness = Arc::new(crate::server::readiness::Readiness::new(
+            loaded_config.health.readiness.clone(),
+        ));
+        let startup_progress = Arc::new(vectorizer::db::StartupProgress::new());
+
         // Start background collection loading and workspace indexing
+        let readiness_for_loading = readiness.clone();
+        let progress_for_loading = startup_progress.clone();
         let store_for_loading = store_arc.clone();
         let embedding_manager_for_loading = Arc::new(embedding_manager);
         let backpressure_for_loading = backpressure_guard.clone();
@@ -506,6 +749,7 @@ impl VectorizerServer {
             // Check for cancellation before starting
             if *cancel_rx.borrow() {
                 info!("Background task cancelled before start");
+                progress_for_loading.finish(Some("startup load cancelled".to_string()));
                 return;
             }
 
@@ -534,11 +778,15 @@ impl VectorizerServer {
             };
 
             // Load all persisted collections in background
+            let mut load_error = None;
             let persisted_count = if should_auto_load {
                 info!(
                     "🔍 COLLECTION_LOAD_STEP_1: Auto-load ENABLED - loading all persisted collections..."
                 );
-                match store_for_loading.load_all_persisted_collections() {
+                progress_for_loading.set_phase(vectorizer::db::StartupPhase::LoadingPersisted);
+                match store_for_loading
+                    .load_all_persisted_collections_with_progress(&progress_for_loading)
+                {
                     Ok(count) => {
                         if count > 0 {
                             info!(
@@ -708,6 +956,7 @@ impl VectorizerServer {
                             "⚠️  Failed to load persisted collections in background: {}",
                             e
                         );
+                        load_error = Some(e.to_string());
                         0
                     }
                 }
@@ -721,17 +970,20 @@ impl VectorizerServer {
             // Check for cancellation before workspace loading
             if *cancel_rx.borrow() {
                 info!("Background task cancelled before workspace loading");
+                progress_for_loading.finish(Some("startup load cancelled".to_string()));
                 return;
             }
 
             // Check for workspace configuration and reindex if needed
             // (data_dir and vecdb_path already declared above)
 
+            progress_for_loading.set_phase(vectorizer::db::StartupPhase::IndexingWorkspace);
             match load_workspace_collections(
                 &store_for_loading,
                 &embedding_manager_for_loading,
                 cancel_rx.clone(),
                 backpressure_for_loading.clone(),
+                &progress_for_loading,
             )
             .await
             {
@@ -830,9 +1082,14 @@ impl VectorizerServer {
                 }
                 Err(e) => {
                     warn!("⚠️  Failed to process workspace: {}", e);
+                    load_error.get_or_insert(e.to_string());
                 }
             }
 
+            progress_for_loading.finish(load_error);
+            readiness_for_loading.mark_collections_loaded();
+            info!("✅ Startup collection loading finished - /health/ready can report ready");
+
             // NOW enable auto-save after all collections are loaded
             info!("🔄 Enabling auto-save after successful initialization");
             store_for_loading.enable_auto_save();
@@ -850,7 +1107,15 @@ impl VectorizerServer {
             &mut final_embedding_manager,
             final_provider_name.clone(),
             final_provider,
+            &extra_embedding_models,
+            embedding_cache.as_ref(),
         )?;
+        for (alias, provider) in resolve_embedding_model_aliases(&config_path) {
+            match final_embedding_manager.register_model_alias(alias.clone(), &provider) {
+                Ok(()) => info!("🧠 Model alias '{}' → provider '{}'", alias, provider),
+                Err(e) => warn!("⚠️  Ignoring model alias '{}': {}", alias, e),
+            }
+        }
 
         // Restore the persisted vocabulary into the query-time provider
         // (phase37). Without this every text query after a restart is
@@ -865,6 +1130,7 @@ impl VectorizerServer {
                 .restore_vocabulary_from_disk(&final_provider_name, &data_dir)
             {
                 Ok(report) => {
+                    readiness.set_vocabulary_loaded(true);
                     if let Some(source) = &report.restored_from {
                         info!(
                             "✅ Restored '{}' vocabulary from tokenizer snapshot of '{}'",
@@ -898,10 +1164,55 @@ impl VectorizerServer {
             }
         }
 
+        // Write-ahead log (`storage.wal`): vector writes are journaled so
+        // a collection can be restored to any time in the retention
+        // window. Older history is pruned hourly.
+        if loaded_config.storage.wal.enabled {
+            store_arc
+                .enable_wal(VectorStore::get_data_dir(), None)
+                .await?;
+            let retention_hours = loaded_config.storage.wal.retention_hours;
+            if let Some(retention) = i64::try_from(retention_hours)
+                .ok()
+                .and_then(chrono::Duration::try_hours)
+            {
+                let wal_store = store_arc.clone();
+                tokio::spawn(async move {
+                    let mut interval = tokio::time::interval(std::time::Duration::from_secs(3600));
+                    loop {
+                        interval.tick().await;
+                        let Some(cutoff) = chrono::Utc::now().checked_sub_signed(retention) else {
+                            continue;
+                        };
+                        if let Err(e) = wal_store.prune_wal(cutoff).await {
+                            warn!("⚠️  Failed to prune the WAL: {}", e);
+                        }
+                    }
+                });
+            }
+            info!(
+                "✅ WAL enabled ({}h of history for point-in-time restore)",
+                retention_hours
+            );
+        }
+
+        // Off-node copies of snapshots (`storage.snapshots.object_store`)
+        let snapshot_store = match &loaded_config.storage.snapshots.object_store {
+            Some(config) => {
+                let store = Arc::new(vectorizer::storage::ObjectStore::new(config.clone())?);
+                info!("☁️  Snapshots are copied to {}", store.describe());
+                Some(store)
+            }
+            None => None,
+        };
+
         // Initialize AutoSaveManager (5min save + 1h snapshot intervals)
         info!("🔄 Initializing AutoSaveManager...");
-        let auto_save_manager =
-            Arc::new(vectorizer::db::AutoSaveManager::new(store_arc.clone(), 1));
+        let mut auto_save_manager = vectorizer::db::AutoSaveManager::new(store_arc.clone(), 1);
+        if let Some(store) = &snapshot_store {
+            auto_save_manager = auto_save_manager.with_object_store(store.clone());
+        }
+        let auto_save_manager = Arc::new(auto_save_manager);
 
         // Clean up old snapshots on server startup
         info!("🧹 Cleaning up old snapshots on server startup...");
@@ -932,12 +1243,31 @@ impl VectorizerServer {
 
         // Initialize query cache
         info!("💾 Initializing query cache...");
-        let cache_config = vectorizer::cache::query_cache::QueryCacheConfig::default();
+        let tuning = &loaded_config.tuning;
+        tuning.validate().map_err(|e| anyhow::anyhow!(e))?;
+        let cache_config = vectorizer::cache::query_cache::QueryCacheConfig {
+            max_size: tuning.query_cache.max_size,
+            ttl_seconds: tuning.query_cache.ttl_seconds,
+            ..Default::default()
+        };
         let max_size = cache_config.max_size;
         let ttl_seconds = cache_config.ttl_seconds;
-        let query_cache = Arc::new(vectorizer::cache::query_cache::QueryCache::new(
-            cache_config,
-        ));
+        vectorizer::db::set_default_ef_search(tuning.ef_search);
+        if let Some(ef_search) = tuning.ef_search {
+            info!("🎛️ HNSW search beam pinned to ef_search={}", ef_search);
+        }
+        let query_cache = Arc::new(
+            vectorizer::cache::query_cache::QueryCache::new_with_metrics(
+                cache_config,
+                Arc::new(vectorizer::monitoring::PrometheusMetricsSink::new()),
+            ),
+        );
+        // Every write path (REST, gRPC, MCP, replication, WAL replay)
+        // goes through the store, so invalidation is hooked there
+        let invalidated_cache = query_cache.clone();
+        store_arc.set_mutation_listener(Arc::new(move |collection: &str| {
+            invalidated_cache.invalidate_collection(collection);
+        }));
         info!(
             "✅ Query cache initialized (max_size: {}, ttl: {}s)",
             max_size, ttl_seconds
@@ -1448,6 +1778,180 @@ impl VectorizerServer {
 
         info!("📦 API max request size: {}MB", max_request_size_mb);
 
+        // Optional HMAC request signing. Enabled-without-keys is a hard
+        // boot error: it would otherwise reject every request.
+        let request_verifier = vectorizer::auth::request_signing::RequestVerifier::from_config(
+            &loaded_config.api.request_signing,
+        )?
+        .map(Arc::new);
+        let payload_redactor = vectorizer::security::PayloadRedactor::from_config(
+            &loaded_config.api.payload_redaction,
+        )?
+        .map(Arc::new);
+        let tls = if loaded_config.tls.enabled {
+            let tls_config = vectorizer::security::tls::TlsConfig::from(&loaded_config.tls);
+            Some(Arc::new(vectorizer::security::tls::ReloadableTls::new(
+                tls_config,
+            )?))
+        } else {
+            None
+        };
+        let audit_log = vectorizer::security::MutationAuditLog::from_config(
+            &loaded_config.audit_log,
+            store_arc.clone(),
+        )?
+        .map(Arc::new);
+        let federation = Arc::new(vectorizer::search::FederationRegistry::from_config(
+            &loaded_config.federation,
+        )?);
+        for (name, template) in &loaded_config.collection_templates {
+            template.validate(name).map_err(|e| anyhow::anyhow!(e))?;
+        }
+        let collection_templates = Arc::new(loaded_config.collection_templates.clone());
+        if !collection_templates.is_empty() {
+            info!(
+                "🧩 Collection templates: {}",
+                collection_templates
+                    .keys()
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+        let search_templates = Arc::new(vectorizer::search::SearchTemplateStore::open_in(
+            &VectorStore::get_data_dir(),
+        )?);
+        let search_template_count = search_templates.list().len();
+        if search_template_count > 0 {
+            info!("🔎 Search templates: {}", search_template_count);
+        }
+        let experiments = Arc::new(vectorizer::search::ExperimentStore::open_in(
+            &VectorStore::get_data_dir(),
+        )?);
+        let experiment_count = experiments.list().len();
+        if experiment_count > 0 {
+            info!("🧪 Experiments: {}", experiment_count);
+        }
+
+        // Query analytics, saved to query_analytics.json every
+        // `flush_interval_secs`. An unreadable file keeps the analytics in
+        // memory (and the file untouched) rather than failing startup.
+        let query_analytics = if loaded_config.query_analytics.enabled {
+            let analytics_config = &loaded_config.query_analytics;
+            let analytics = match vectorizer::monitoring::QueryAnalytics::open_in(
+                &VectorStore::get_data_dir(),
+                analytics_config.capacity,
+            ) {
+                Ok(analytics) => analytics,
+                Err(e) => {
+                    warn!("Query analytics not restored, starting empty: {}", e);
+                    vectorizer::monitoring::QueryAnalytics::new(analytics_config.capacity)
+                }
+            };
+            let analytics = Arc::new(analytics);
+            analytics
+                .clone()
+                .spawn_flush(std::time::Duration::from_secs(
+                    analytics_config.flush_interval_secs.max(1),
+                ));
+            info!(
+                "📈 Query analytics enabled ({} of {} queries restored)",
+                analytics.len(),
+                analytics.capacity()
+            );
+            Some(analytics)
+        } else {
+            None
+        };
+
+        if request_verifier.is_some() {
+            info!(
+                "🔏 Request signing enabled ({} key(s), ±{}s clock skew)",
+                loaded_config.api.request_signing.keys.len(),
+                loaded_config.api.request_signing.max_clock_skew_secs
+            );
+        }
+        if let Some(tls) = &tls {
+            info!(
+                "🔒 TLS enabled for HTTP and gRPC (client certificates {})",
+                if tls.mtls_enabled() {
+                    "required"
+                } else {
+                    "not requested"
+                }
+            );
+        }
+        if payload_redactor.is_some() {
+            info!(
+                "🙈 Payload redaction enabled ({} rule(s), exempt roles: {:?})",
+                loaded_config.api.payload_redaction.rules.len(),
+                loaded_config.api.payload_redaction.exempt_roles
+            );
+        }
+        if let Some(log) = &audit_log {
+            info!(
+                "📜 Mutation audit log enabled ({}{})",
+                log.path().display(),
+                log.collection()
+                    .map(|c| format!(", collection '{}'", c))
+                    .unwrap_or_default()
+            );
+        }
+        if !loaded_config.federation.sources.is_empty() {
+            info!(
+                "🌐 Query federation: {} remote source(s), default timeout {}ms",
+                loaded_config.federation.sources.len(),
+                loaded_config.federation.default_timeout_ms
+            );
+        }
+        if !loaded_config.plugins.write.is_empty() {
+            let chain = vectorizer::plugins::WritePluginChain::from_config(&loaded_config.plugins)?;
+            info!(
+                "🧩 Write plugins: {}",
+                loaded_config
+                    .plugins
+                    .write
+                    .iter()
+                    .map(|p| p.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+            store_arc.set_write_plugins(chain);
+        }
+        if !loaded_config.read_through.collections.is_empty() {
+            let read_through =
+                vectorizer::db::ReadThrough::from_config(&loaded_config.read_through)?;
+            let sweep_interval = read_through.sweep_interval();
+            info!(
+                "🧊 Read-through collections: {} (idle eviction after {}s)",
+                read_through.len(),
+                loaded_config.read_through.idle_eviction_secs
+            );
+            store_arc.set_read_through(read_through);
+            // Lives for the whole process, like the index optimizer.
+            vectorizer::db::ReadThroughEvictor::spawn(store_arc.clone(), sweep_interval);
+        }
+        if loaded_config.change_feed.enabled {
+            let feed = Arc::new(vectorizer::db::ChangeFeed::new(
+                loaded_config.change_feed.buffer_size,
+            ));
+            // Sinks hold a receiver each and run until the feed is dropped
+            vectorizer::db::change_feed::spawn_sinks(&feed, &loaded_config.change_feed.sinks);
+            info!(
+                "📣 Change feed enabled (buffer {} events, {} sink(s))",
+                loaded_config.change_feed.buffer_size,
+                loaded_config.change_feed.sinks.len()
+            );
+            store_arc.set_change_feed(feed);
+        }
+        if loaded_config.summarization.enabled && loaded_config.summarization.auto_summarize {
+            info!(
+                "📝 Summary collections enabled (<collection>_summaries, method '{}')",
+                loaded_config.summarization.default_method
+            );
+            store_arc.set_summary_collections(loaded_config.summarization.clone());
+        }
+
         // Initialize auth handler state if auth is enabled
         let auth_handler_state = {
             // Sourced from the single `loaded_config` read at the top
@@ -1500,6 +2004,13 @@ impl VectorizerServer {
                 match vectorizer::auth::AuthManager::new(auth_config) {
                     Ok(auth_manager) => {
                         let auth_manager_arc = Arc::new(auth_manager);
+                        if let Some(oidc) = auth_manager_arc.oidc() {
+                            info!(
+                                "🔐 OIDC enabled: accepting tokens from {}",
+                                oidc.config().issuer
+                            );
+                            oidc.clone().spawn_refresh();
+                        }
                         // Create auth handler state with root user configuration
                         let handler_state = AuthHandlerState::new_with_root_user(
                             auth_manager_arc,
@@ -1558,7 +2069,10 @@ impl VectorizerServer {
         // Initialize user backup manager if hub integration is enabled
         let backup_manager = if hub_manager.is_some() {
             info!("📦 Initializing HiveHub backup manager...");
-            let backup_config = vectorizer::hub::BackupConfig::default();
+            let backup_config = vectorizer::hub::BackupConfig {
+                object_store: loaded_config.hub.backup_store.clone(),
+                ..Default::default()
+            };
             match vectorizer::hub::UserBackupManager::new(backup_config, store_arc.clone()) {
                 Ok(manager) => {
                     info!("✅ HiveHub backup manager initialized");
@@ -1679,6 +2193,51 @@ impl VectorizerServer {
         }
         info!("📊 Log tailer publisher started (phase30 §2)");
 
+        // Reindexes and hybrid weight fits run on request rather than
+        // from a loop; register the tasks up front so they are listed (and
+        // can be paused) before the first one.
+        vectorizer::background_tasks::TASKS.register(vectorizer::background_tasks::REINDEX);
+        vectorizer::background_tasks::TASKS.register(vectorizer::background_tasks::HYBRID_TUNING);
+
+        // GPU brute-force scoring for exact searches and binary-quantized
+        // rescoring. Stays on the CPU unless `gpu.rescoring.enabled` is set
+        // and a GPU backend is found.
+        #[cfg(feature = "hive-gpu")]
+        vectorizer::gpu_adapter::GpuRescorer::install(&loaded_config.gpu);
+
+        // Background HNSW compaction: rebuilds graphs that deletes and
+        // updates have left fragmented. The task lives for the whole
+        // process, so the handle is not kept.
+        if loaded_config.index_optimizer.enabled {
+            vectorizer::db::IndexOptimizer::spawn(
+                store_arc.clone(),
+                loaded_config.index_optimizer.clone(),
+                Arc::new(vectorizer::monitoring::PrometheusMetricsSink::new()),
+            );
+        }
+
+        // Cron-scheduled backups (`backup_schedule.jobs`). A bad cron
+        // expression or job name fails startup rather than silently
+        // never backing up.
+        let backup_scheduler = if loaded_config.backup_schedule.jobs.is_empty() {
+            None
+        } else {
+            let scheduler = vectorizer::db::BackupScheduler::from_config(
+                store_arc.clone(),
+                &loaded_config.backup_schedule,
+                &VectorStore::get_data_dir(),
+                Arc::new(vectorizer::monitoring::PrometheusMetricsSink::new()),
+            )?
+            .with_auto_save(auto_save_manager.clone());
+            let scheduler = Arc::new(scheduler);
+            scheduler.clone().spawn();
+            info!(
+                "✅ Backup scheduler started ({} jobs)",
+                loaded_config.backup_schedule.jobs.len()
+            );
+            Some(scheduler)
+        };
+
         // VectorizerRPC binary listener — opt-in via `rpc.enabled` in
         // config.yml. The listener spawns its own background tasks per
         // accepted connection; nothing else in `Self` needs to retain a
@@ -1723,6 +2282,8 @@ impl VectorizerServer {
                         slow_query_ring: vectorizer::cache::slow_query::SlowQueryRing::new(
                             vectorizer::cache::slow_query::SlowQueryConfig::default(),
                         ),
+                        audit_log: audit_log.clone(),
+                        hub_manager: hub_manager.clone(),
                     };
                     if let Err(e) = crate::protocol::rpc::spawn_rpc_listener(rpc_state, addr).await
                     {
@@ -1745,6 +2306,7 @@ impl VectorizerServer {
         Ok(Self {
             store: store_arc,
             embedding_manager: embedding_manager_arc,
+            file_watcher_embedding_manager: Some(embedding_manager_for_watcher_arc),
             start_time: std::time::Instant::now(),
             file_watcher_system: watcher_system_for_server,
             metrics_collector: Arc::new(MetricsCollector::new()),
@@ -1767,16 +2329,30 @@ impl VectorizerServer {
             cluster_manager,
             cluster_client_pool,
             max_request_size_mb,
+            collection_templates,
+            search_templates,
+            experiments,
+            query_analytics,
+            request_verifier,
+            tls,
+            payload_redactor,
+            audit_log,
+            federation,
             snapshot_manager: {
                 let data_dir = VectorStore::get_data_dir();
                 let snapshots_dir = data_dir.join("snapshots");
-                Some(Arc::new(vectorizer::storage::SnapshotManager::new(
+                let mut manager = vectorizer::storage::SnapshotManager::new(
                     &data_dir,
                     &snapshots_dir,
                     10,  // max_snapshots: keep up to 10 snapshots
                     168, // retention_hours: 7 days
-                )))
+                );
+                if let Some(store) = snapshot_store {
+                    manager = manager.with_object_store(store);
+                }
+                Some(Arc::new(manager))
             },
+            backup_scheduler,
             auth_handler_state,
             hub_manager,
             backup_manager,
@@ -1814,6 +2390,10 @@ impl VectorizerServer {
             runtime_sampler,
             // phase29 + phase30: dashboard broadcast bus sender.
             dashboard_tx,
+            drain: Arc::new(crate::server::drain::DrainState::default()),
+            config_path: Some(std::path::PathBuf::from(&config_path)),
+            readiness,
+            startup_progress,
         })
     }
 
@@ -1830,6 +2410,8 @@ impl VectorizerServer {
     /// a BM25 provider fitted via `build_vocabulary`); every handler
     /// invoked through the resulting router runs the exact same code
     /// path production traffic does, backed by real in-memory state.
+    /// Conformance mode (`vectorizer --conformance`, see
+    /// `server::conformance`) serves the same setup over TCP.
     ///
     /// Exists specifically because several `VectorizerServer` fields
     /// are `pub(super)` (background task handles) and therefore not
@@ -1845,19 +2427,28 @@ impl VectorizerServer {
         runtime_sampler.set_broadcast(dashboard_tx.clone());
 
         let backpressure_config = vectorizer::config::BackpressureConfig::default();
+        let query_cache = Arc::new(
+            vectorizer::cache::query_cache::QueryCache::new_with_metrics(
+                vectorizer::cache::query_cache::QueryCacheConfig::default(),
+                Arc::new(vectorizer::monitoring::PrometheusMetricsSink::new()),
+            ),
+        );
+        let invalidated_cache = query_cache.clone();
+        store.set_mutation_listener(Arc::new(move |collection: &str| {
+            invalidated_cache.invalidate_collection(collection);
+        }));
 
         Self {
             store,
             embedding_manager,
+            file_watcher_embedding_manager: None,
             start_time: std::time::Instant::now(),
             file_watcher_system: Arc::new(tokio::sync::Mutex::new(None)),
             metrics_collector: Arc::new(MetricsCollector::new()),
             auto_save_manager: None,
             master_node: None,
             replica_node: None,
-            query_cache: Arc::new(vectorizer::cache::query_cache::QueryCache::new(
-                vectorizer::cache::query_cache::QueryCacheConfig::default(),
-            )),
+            query_cache,
             slow_query_ring: vectorizer::cache::slow_query::SlowQueryRing::new(
                 vectorizer::cache::slow_query::SlowQueryConfig::default(),
             ),
@@ -1870,7 +2461,17 @@ impl VectorizerServer {
             cluster_manager: None,
             cluster_client_pool: None,
             max_request_size_mb: 100,
+            collection_templates: Arc::new(std::collections::BTreeMap::new()),
+            search_templates: Arc::new(vectorizer::search::SearchTemplateStore::default()),
+            experiments: Arc::new(vectorizer::search::ExperimentStore::default()),
+            query_analytics: None,
+            request_verifier: None,
+            tls: None,
+            payload_redactor: None,
+            audit_log: None,
+            federation: Arc::new(vectorizer::search::FederationRegistry::default()),
             snapshot_manager: None,
+            backup_scheduler: None,
             auth_handler_state: None,
             hub_manager: None,
             backup_manager: None,
@@ -1885,6 +2486,12 @@ impl VectorizerServer {
             )),
             runtime_sampler: Arc::new(runtime_sampler),
             dashboard_tx,
+            drain: Arc::new(crate::server::drain::DrainState::default()),
+            config_path: None,
+            readiness: Arc::new(crate::server::readiness::Readiness::loaded(
+                vectorizer::config::ReadinessConfig::default(),
+            )),
+            startup_progress: Arc::new(vectorizer::db::StartupProgress::completed()),
         }
     }
 }
@@ -1,13 +1,28 @@
 //! gRPC server bootstrap. Runs on `port + 1` alongside the HTTP server
 //! and serves the native Vectorizer gRPC API plus the Qdrant-compatible
-//! services (Collections / Points / Snapshots) and the cluster service.
+//! services (Collections / Points / Snapshots) and the cluster service,
+//! along with server reflection (`grpc.reflection.v1` / `v1alpha`) and
+//! the standard `grpc.health.v1.Health` service.
 
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::Arc;
+use std::task::{Context, Poll};
 
-use tracing::info;
+use tonic_health::ServingStatus;
+use tonic_health::server::HealthReporter;
+use tracing::{info, warn};
 use vectorizer::VectorStore;
+use vectorizer::auth::AuthManager;
+use vectorizer::security::tls::ReloadableTls;
+use vectorizer::security::{
+    AuditActor, AuditProtocol, MutationAuditLog, MutationRecord, PayloadRedactor, PayloadView,
+};
+use vectorizer_protocol::grpc_gen::{FILE_DESCRIPTOR_SET, QDRANT_FILE_DESCRIPTOR_SET};
 
+use super::tls::TlsListener;
 use crate::server::VectorizerServer;
+use crate::server::drain::DrainState;
 
 impl VectorizerServer {
     /// Start gRPC server
@@ -19,19 +34,44 @@ impl VectorizerServer {
         snapshot_manager: Option<Arc<vectorizer::storage::SnapshotManager>>,
         raft_manager: Option<Arc<vectorizer::cluster::raft_node::RaftManager>>,
         upsert_queue: Arc<vectorizer::db::UpsertQueue>,
+        audit_log: Option<Arc<MutationAuditLog>>,
+        auth_manager: Option<Arc<AuthManager>>,
+        payload_redactor: Option<Arc<PayloadRedactor>>,
+        tls: Option<Arc<ReloadableTls>>,
+        drain: Arc<DrainState>,
+        hub_manager: Option<Arc<vectorizer::hub::HubManager>>,
     ) -> anyhow::Result<()> {
         use tonic::transport::Server;
 
         use crate::grpc::VectorizerGrpcService;
         use crate::grpc::vectorizer::vectorizer_service_server::VectorizerServiceServer;
 
-        let addr = format!("{}:{}", host, port).parse()?;
-        let service = VectorizerGrpcService::new(store.clone(), upsert_queue.clone());
+        let addr: std::net::SocketAddr = format!("{}:{}", host, port).parse()?;
+        let service = VectorizerGrpcService::new(store.clone(), upsert_queue.clone())
+            .with_hub_manager(hub_manager.clone());
 
         info!("🚀 Starting gRPC server on {}", addr);
 
-        let mut server_builder =
-            Server::builder().add_service(VectorizerServiceServer::new(service));
+        let (health_reporter, health_service) = tonic_health::server::health_reporter();
+        health_reporter
+            .set_serving::<VectorizerServiceServer<VectorizerGrpcService>>()
+            .await;
+
+        let reflection_v1 = reflection_builder().build_v1()?;
+        // grpcurl and older tooling still ask the pre-release v1alpha API
+        let reflection_v1alpha = reflection_builder().build_v1alpha()?;
+
+        let mut server_builder = Server::builder()
+            .layer(MutationAuditLayer(audit_log))
+            .layer(NamespaceLayer {
+                auth_manager,
+                payload_redactor,
+            })
+            .layer(DrainLayer(drain.clone()))
+            .add_service(health_service)
+            .add_service(reflection_v1)
+            .add_service(reflection_v1alpha)
+            .add_service(VectorizerServiceServer::new(service));
 
         // Add ClusterService if cluster is enabled
         if let Some(cluster_mgr) = cluster_manager {
@@ -42,6 +82,9 @@ impl VectorizerServer {
             info!("🔗 Adding Cluster gRPC service");
             let cluster_service =
                 ClusterGrpcService::new(store.clone(), cluster_mgr, raft_manager.clone());
+            health_reporter
+                .set_serving::<ClusterServiceServer<ClusterGrpcService>>()
+                .await;
             server_builder = server_builder.add_service(ClusterServiceServer::new(cluster_service));
         }
 
@@ -57,7 +100,18 @@ impl VectorizerServer {
                 QdrantGrpcService::with_snapshot_manager(store.clone(), sm, upsert_queue.clone())
             } else {
                 QdrantGrpcService::new(store.clone(), upsert_queue.clone())
-            };
+            }
+            .with_hub_manager(hub_manager);
+
+            health_reporter
+                .set_serving::<CollectionsServer<QdrantGrpcService>>()
+                .await;
+            health_reporter
+                .set_serving::<PointsServer<QdrantGrpcService>>()
+                .await;
+            health_reporter
+                .set_serving::<SnapshotsServer<QdrantGrpcService>>()
+                .await;
 
             // Add all Qdrant services using the same service instance (it implements all traits)
             server_builder = server_builder
@@ -66,8 +120,330 @@ impl VectorizerServer {
                 .add_service(SnapshotsServer::new(qdrant_service));
         }
 
-        server_builder.serve(addr).await?;
+        let serve = async {
+            match tls {
+                Some(tls) => {
+                    info!("🔒 gRPC server terminating TLS on {}", addr);
+                    let listener = tokio::net::TcpListener::bind(addr).await?;
+                    server_builder
+                        .serve_with_incoming(TlsListener::new(listener, tls)?.into_stream())
+                        .await?;
+                }
+                None => server_builder.serve(addr).await?,
+            }
+            anyhow::Ok(())
+        };
+
+        tokio::select! {
+            result = serve => result,
+            () = report_drain_health(health_reporter, drain) => Ok(()),
+        }
+    }
+}
+
+/// Reflection over every service this server can expose.
+fn reflection_builder() -> tonic_reflection::server::Builder<'static> {
+    tonic_reflection::server::Builder::configure()
+        .register_encoded_file_descriptor_set(FILE_DESCRIPTOR_SET)
+        .register_encoded_file_descriptor_set(QDRANT_FILE_DESCRIPTOR_SET)
+        .register_encoded_file_descriptor_set(tonic_health::pb::FILE_DESCRIPTOR_SET)
+}
+
+/// How often the overall health status follows the drain state.
+const HEALTH_DRAIN_POLL: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Reports the server as a whole (the `""` service) `NOT_SERVING` while
+/// it drains, like `GET /health` answering `503`, so load balancers
+/// probing `grpc.health.v1.Health` stop routing to it. Runs until the
+/// gRPC server stops.
+async fn report_drain_health(reporter: HealthReporter, drain: Arc<DrainState>) {
+    let mut draining = false;
+    let mut ticker = tokio::time::interval(HEALTH_DRAIN_POLL);
+    loop {
+        ticker.tick().await;
+        if drain.is_draining() != draining {
+            draining = !draining;
+            let status = if draining {
+                warn!("gRPC health: NOT_SERVING while the server drains");
+                ServingStatus::NotServing
+            } else {
+                info!("gRPC health: SERVING again");
+                ServingStatus::Serving
+            };
+            reporter.set_service_status("", status).await;
+        }
+    }
+}
+
+/// RPC name prefixes of the methods that change data or configuration,
+/// native and Qdrant-compatible alike.
+const MUTATING_RPC_PREFIXES: &[&str] = &[
+    "Create",
+    "Update",
+    "Delete",
+    "Insert",
+    "Upsert",
+    "Set",
+    "Overwrite",
+    "Clear",
+];
+
+/// Whether a gRPC path (`/package.Service/Method`) is a mutating call.
+/// Cluster-internal calls are not audited: they replay mutations that
+/// were audited on the node that received them.
+fn is_mutating_grpc_method(path: &str) -> bool {
+    let Some((service, method)) = path.trim_start_matches('/').split_once('/') else {
+        return false;
+    };
+    !service.ends_with("ClusterService")
+        && MUTATING_RPC_PREFIXES
+            .iter()
+            .any(|prefix| method.starts_with(prefix))
+}
+
+/// Records mutating gRPC calls in the audit log (`audit_log`). Always
+/// installed so the server type does not depend on the config; a no-op
+/// without a log.
+///
+/// gRPC has no authentication, so callers are recorded as anonymous with
+/// their peer address. Request messages are not decoded here, so the
+/// collection and ID count are left empty, and the record is written
+/// when the call starts, before its outcome is known.
+#[derive(Clone)]
+struct MutationAuditLayer(Option<Arc<MutationAuditLog>>);
+
+impl<S> tower::Layer<S> for MutationAuditLayer {
+    type Service = MutationAuditService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        MutationAuditService {
+            inner,
+            audit_log: self.0.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+struct MutationAuditService<S> {
+    inner: S,
+    audit_log: Option<Arc<MutationAuditLog>>,
+}
+
+impl<S, B> tower::Service<axum::http::Request<B>> for MutationAuditService<S>
+where
+    S: tower::Service<axum::http::Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: axum::http::Request<B>) -> Self::Future {
+        if let Some(audit_log) = &self.audit_log
+            && is_mutating_grpc_method(req.uri().path())
+        {
+            let mut record = MutationRecord::new(
+                AuditProtocol::Grpc,
+                AuditActor::anonymous(),
+                req.uri().path(),
+            );
+            record.client_ip = grpc_remote_addr(req.extensions()).map(|addr| addr.ip().to_string());
+            audit_log.record(record);
+        }
+        self.inner.call(req)
+    }
+}
+
+/// Peer address of a gRPC call, over plain TCP or TLS.
+fn grpc_remote_addr(extensions: &axum::http::Extensions) -> Option<std::net::SocketAddr> {
+    use tonic::transport::server::{TcpConnectInfo, TlsConnectInfo};
+
+    match extensions.get::<TcpConnectInfo>() {
+        Some(info) => info.remote_addr(),
+        None => extensions
+            .get::<TlsConnectInfo<TcpConnectInfo>>()
+            .and_then(|info| info.get_ref().remote_addr()),
+    }
+}
+
+/// Refuses mutating calls with `UNAVAILABLE` while the server drains
+/// (`POST /admin/drain`) or is read-only and counts the admitted ones as in flight.
+#[derive(Clone)]
+struct DrainLayer(Arc<DrainState>);
+
+impl<S> tower::Layer<S> for DrainLayer {
+    type Service = DrainService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        DrainService {
+            inner,
+            drain: self.0.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+struct DrainService<S> {
+    inner: S,
+    drain: Arc<DrainState>,
+}
+
+impl<S, B, ResBody> tower::Service<axum::http::Request<B>> for DrainService<S>
+where
+    S: tower::Service<axum::http::Request<B>, Response = axum::http::Response<ResBody>>,
+    S::Future: Send + 'static,
+    S::Error: Send + 'static,
+    ResBody: Default + Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: axum::http::Request<B>) -> Self::Future {
+        if !is_mutating_grpc_method(req.uri().path()) {
+            return Box::pin(self.inner.call(req));
+        }
+        let write = match self.drain.try_enter() {
+            Ok(write) => write,
+            Err(refusal) => {
+                let refused = tonic::Status::unavailable(refusal.message());
+                return Box::pin(std::future::ready(Ok(refused.into_http())));
+            }
+        };
+        let call = self.inner.call(req);
+        Box::pin(async move {
+            let response = call.await;
+            drop(write);
+            response
+        })
+    }
+}
+
+/// The API key a gRPC call presents: `x-api-key` metadata, or
+/// `authorization` with or without a `Bearer ` prefix.
+fn presented_api_key(headers: &axum::http::HeaderMap) -> Option<String> {
+    let value = headers
+        .get("x-api-key")
+        .or_else(|| headers.get(axum::http::header::AUTHORIZATION))?
+        .to_str()
+        .ok()?;
+    Some(value.strip_prefix("Bearer ").unwrap_or(value).to_string())
+}
+
+/// Attaches the collection namespace of the API key a call presents to
+/// the request, where [`crate::grpc::namespace`] enforces it. Calls
+/// without a key, or with one that does not validate, pass through
+/// unchanged: gRPC has no authentication of its own.
+///
+/// With `api.payload_redaction` enabled it also attaches the caller's
+/// [`PayloadView`], built from the key's roles; calls without a valid
+/// key hold no roles and are redacted.
+#[derive(Clone)]
+struct NamespaceLayer {
+    auth_manager: Option<Arc<AuthManager>>,
+    payload_redactor: Option<Arc<PayloadRedactor>>,
+}
+
+impl<S> tower::Layer<S> for NamespaceLayer {
+    type Service = NamespaceService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        NamespaceService {
+            inner,
+            auth_manager: self.auth_manager.clone(),
+            payload_redactor: self.payload_redactor.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+struct NamespaceService<S> {
+    inner: S,
+    auth_manager: Option<Arc<AuthManager>>,
+    payload_redactor: Option<Arc<PayloadRedactor>>,
+}
+
+impl<S, B> tower::Service<axum::http::Request<B>> for NamespaceService<S>
+where
+    S: tower::Service<axum::http::Request<B>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    B: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: axum::http::Request<B>) -> Self::Future {
+        // Keep the service that was polled ready; leave a fresh clone
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let auth_manager = self.auth_manager.clone();
+        let payload_redactor = self.payload_redactor.clone();
+        Box::pin(async move {
+            let claims = match (auth_manager, presented_api_key(req.headers())) {
+                (Some(auth_manager), Some(key)) => auth_manager.validate_api_key(&key).await.ok(),
+                _ => None,
+            };
+            if payload_redactor.is_some() {
+                let roles = claims.as_ref().map(|c| c.roles.clone()).unwrap_or_default();
+                req.extensions_mut()
+                    .insert(PayloadView::new(payload_redactor, roles));
+            }
+            if let Some(namespace) = claims.and_then(|c| c.namespace) {
+                req.extensions_mut().insert(namespace);
+            }
+            inner.call(req).await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mutating_grpc_methods_are_recognised() {
+        assert!(is_mutating_grpc_method(
+            "/vectorizer.VectorizerService/InsertVectors"
+        ));
+        assert!(is_mutating_grpc_method("/qdrant.Points/Upsert"));
+        assert!(is_mutating_grpc_method("/qdrant.Points/ClearPayload"));
+        assert!(!is_mutating_grpc_method("/qdrant.Points/Search"));
+        assert!(!is_mutating_grpc_method(
+            "/vectorizer.VectorizerService/GetVector"
+        ));
+        assert!(!is_mutating_grpc_method(
+            "/vectorizer.cluster.ClusterService/RemoteInsertVector"
+        ));
+    }
+
+    #[test]
+    fn reflection_decodes_every_descriptor_set() {
+        assert!(reflection_builder().build_v1().is_ok());
+        assert!(reflection_builder().build_v1alpha().is_ok());
+    }
 
-        Ok(())
+    #[test]
+    fn api_key_is_read_from_metadata() {
+        let mut headers = axum::http::HeaderMap::new();
+        assert_eq!(presented_api_key(&headers), None);
+        headers.insert(
+            "authorization",
+            axum::http::HeaderValue::from_static("Bearer abc"),
+        );
+        assert_eq!(presented_api_key(&headers).as_deref(), Some("abc"));
+        headers.insert("x-api-key", axum::http::HeaderValue::from_static("xyz"));
+        assert_eq!(presented_api_key(&headers).as_deref(), Some("xyz"));
     }
 }
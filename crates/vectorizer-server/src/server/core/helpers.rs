@@ -3,8 +3,21 @@
 //! - [`extract_auth_credentials`] / [`check_mcp_auth_with_credentials`]
 //!   — header parsing + validation used by the global auth middleware
 //!   and (historically) the MCP entrypoint.
+//! - [`identify_mcp_caller`] — optional caller identity for the MCP
+//!   transports.
 //! - [`security_headers_middleware`] — adds standard security headers
 //!   (CSP, X-Frame-Options, etc.) to every response.
+//! - [`request_signing_middleware`] — verifies HMAC request signatures
+//!   when `api.request_signing` is enabled.
+//! - [`payload_redaction_middleware`] / [`caller_payload_view`] — redact
+//!   payload fields in responses when `api.payload_redaction` is enabled.
+//! - [`mutation_audit_middleware`] / [`audit_actor`] — record mutating
+//!   REST calls in the audit log when `audit_log` is enabled.
+//! - [`collection_namespace_middleware`] / [`check_caller_namespace`] —
+//!   refuse calls that name, or resolve to, a collection outside the
+//!   caller's API-key namespace.
+//! - [`drain_middleware`] — refuse writes while the server drains or is
+//!   read-only.
 //! - [`get_file_watcher_metrics`] — the `/metrics` REST handler that
 //!   exposes File Watcher metrics to the dashboard.
 
@@ -12,9 +25,19 @@ use std::sync::Arc;
 
 use axum::extract::State;
 use axum::response::Json;
+use vectorizer::auth::collections_named_in;
+use vectorizer::auth::middleware::AuthState;
+use vectorizer::auth::request_signing::{self, RequestVerifier, SignatureHeaders};
 use vectorizer::file_watcher::FileWatcherMetrics;
+use vectorizer::hub::middleware::RequestTenantContext;
+use vectorizer::security::payload_redaction::collection_from_path;
+use vectorizer::security::{
+    AuditActor, AuditProtocol, MutationAuditLog, MutationRecord, PayloadRedactor, PayloadView,
+};
 
 use crate::server::ServerState;
+use crate::server::drain::DrainState;
+use crate::server::error_middleware::{ErrorResponse, create_error_response};
 
 /// Extract auth credentials from request headers (sync part)
 /// Returns (Option<jwt_token>, Option<api_key>)
@@ -48,6 +71,15 @@ pub(super) fn extract_auth_credentials(
         }
     }
 
+    // Pinecone clients send their key as `Api-Key`
+    if api_key.is_none() {
+        if let Some(api_key_header) = req.headers().get("Api-Key") {
+            if let Ok(key) = api_key_header.to_str() {
+                api_key = Some(key.to_string());
+            }
+        }
+    }
+
     // Check for API key in query parameters (if no API key found yet)
     if api_key.is_none() {
         if let Some(query) = req.uri().query() {
@@ -63,6 +95,29 @@ pub(super) fn extract_auth_credentials(
     (jwt_token, api_key)
 }
 
+/// Identity of an MCP caller presenting the credentials found by
+/// [`extract_auth_credentials`]. MCP stays open, so
+/// missing or invalid credentials just leave the caller anonymous; a
+/// caller that is identified gets its API-key namespace applied to tool
+/// calls.
+pub(super) async fn identify_mcp_caller(
+    (jwt_token, api_key): (Option<String>, Option<String>),
+    auth_manager: &vectorizer::auth::AuthManager,
+) -> Option<AuthState> {
+    let claims = match jwt_token {
+        Some(token) => auth_manager.validate_jwt(&token).ok(),
+        None => None,
+    };
+    let claims = match (claims, api_key) {
+        (None, Some(key)) => auth_manager.validate_api_key(&key).await.ok(),
+        (claims, _) => claims,
+    };
+    claims.map(|user_claims| AuthState {
+        user_claims,
+        authenticated: true,
+    })
+}
+
 /// Check authentication for MCP/UMICP requests in production mode
 /// Returns true if authentication is valid, false otherwise.
 ///
@@ -161,6 +216,680 @@ pub(super) async fn security_headers_middleware(
     response
 }
 
+/// Verify the HMAC signature headers on every non-exempt request.
+///
+/// The body is buffered (up to `max_body_bytes`) so its hash can be checked,
+/// then handed to the inner router unchanged. Rejections are 401 with the
+/// usual error JSON plus `X-Vectorizer-Server-Time`, which lets clients
+/// correct their clock offset and retry.
+pub(super) async fn request_signing_middleware(
+    State((verifier, max_body_bytes)): State<(Arc<RequestVerifier>, usize)>,
+    req: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> axum::response::Response {
+    use axum::response::IntoResponse;
+
+    if req.method() == axum::http::Method::OPTIONS || verifier.is_exempt(req.uri().path()) {
+        return next.run(req).await;
+    }
+
+    let (parts, body) = req.into_parts();
+    let bytes = match axum::body::to_bytes(body, max_body_bytes).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            return create_error_response(
+                "payload_too_large",
+                &format!("Failed to read request body: {}", e),
+                axum::http::StatusCode::PAYLOAD_TOO_LARGE,
+            )
+            .into_response();
+        }
+    };
+
+    let header = |name: &str| parts.headers.get(name).and_then(|v| v.to_str().ok());
+    let path_and_query = parts
+        .uri
+        .path_and_query()
+        .map(|pq| pq.as_str())
+        .unwrap_or("/");
+    let now = request_signing::unix_now();
+
+    if let Err(e) = verifier.verify(
+        parts.method.as_str(),
+        path_and_query,
+        SignatureHeaders {
+            key_id: header(request_signing::HEADER_KEY_ID),
+            timestamp: header(request_signing::HEADER_TIMESTAMP),
+            signature: header(request_signing::HEADER_SIGNATURE),
+        },
+        &bytes,
+        now,
+    ) {
+        tracing::warn!(
+            "Rejected unsigned/invalid request {} {}: {}",
+            parts.method,
+            parts.uri.path(),
+            e
+        );
+        let mut response = create_error_response(
+            e.code(),
+            &e.to_string(),
+            axum::http::StatusCode::UNAUTHORIZED,
+        )
+        .into_response();
+        response.headers_mut().insert(
+            request_signing::HEADER_SERVER_TIME,
+            axum::http::HeaderValue::from(now),
+        );
+        return response;
+    }
+
+    next.run(axum::extract::Request::from_parts(
+        parts,
+        axum::body::Body::from(bytes),
+    ))
+    .await
+}
+
+/// The redaction policy as it applies to the caller of a request.
+///
+/// Roles come from the `AuthState` the auth layers attach; a caller
+/// without one (auth disabled, or not authenticated) has no roles and is
+/// redacted.
+pub(crate) fn caller_payload_view(
+    redactor: Option<Arc<PayloadRedactor>>,
+    auth: Option<&AuthState>,
+) -> PayloadView {
+    let roles = auth
+        .filter(|auth| auth.authenticated)
+        .map(|auth| auth.user_claims.roles.clone())
+        .unwrap_or_default();
+    PayloadView::new(redactor, roles)
+}
+
+/// Redact payload fields in JSON responses for callers that are not in
+/// `api.payload_redaction.exempt_roles`.
+///
+/// Must sit inside the auth layers so the `AuthState` extension is
+/// present. Responses that are not JSON, or that the policy leaves
+/// unchanged, are passed through byte-for-byte. `/graphql` is skipped:
+/// its resolvers redact with the collection each payload came from.
+pub(super) async fn payload_redaction_middleware(
+    State((redactor, max_body_bytes)): State<(Arc<PayloadRedactor>, usize)>,
+    req: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> axum::response::Response {
+    let view = caller_payload_view(Some(redactor), req.extensions().get::<AuthState>());
+    if view.is_passthrough() || req.uri().path() == "/graphql" {
+        return next.run(req).await;
+    }
+    let path_collection = collection_from_path(req.uri().path()).map(str::to_string);
+
+    let response = next.run(req).await;
+    let is_json = response
+        .headers()
+        .get(axum::http::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|ct| ct.starts_with("application/json"));
+    if !is_json {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match axum::body::to_bytes(body, max_body_bytes).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            // Never leak an unredacted body we could not inspect.
+            tracing::warn!("Payload redaction could not read response body: {}", e);
+            return axum::response::IntoResponse::into_response(create_error_response(
+                "redaction_failed",
+                "Response too large to redact",
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+            ));
+        }
+    };
+
+    let Ok(mut value) = serde_json::from_slice::<serde_json::Value>(&bytes) else {
+        return axum::response::Response::from_parts(parts, axum::body::Body::from(bytes));
+    };
+    if !view.redact_response(path_collection.as_deref(), &mut value) {
+        return axum::response::Response::from_parts(parts, axum::body::Body::from(bytes));
+    }
+
+    parts.headers.remove(axum::http::header::CONTENT_LENGTH);
+    let body = serde_json::to_vec(&value).unwrap_or_default();
+    axum::response::Response::from_parts(parts, axum::body::Body::from(body))
+}
+
+/// Who made a request, from the extensions the hub and auth middleware
+/// attach: the hub tenant when there is one, else the authenticated
+/// JWT / API key user, else anonymous.
+pub(super) fn audit_actor(extensions: &axum::http::Extensions) -> AuditActor {
+    if let Some(RequestTenantContext(tenant)) = extensions.get::<RequestTenantContext>() {
+        return AuditActor::from_tenant(tenant);
+    }
+    extensions
+        .get::<AuthState>()
+        .filter(|auth| auth.authenticated)
+        .map(|auth| AuditActor::from_claims(&auth.user_claims))
+        .unwrap_or_else(AuditActor::anonymous)
+}
+
+/// POST routes that only read: searches, retrievals, exports and the
+/// discovery pipeline. Matched against the route template, never the
+/// raw path, so a collection's name cannot change how a call counts.
+const READ_ONLY_POST_ROUTES: &[&str] = &[
+    "/vector",
+    "/embed",
+    "/v1/embeddings",
+    "/setup/browse",
+    "/hub/validate-key",
+    "/search",
+    "/retrieve",
+    "/batch_search",
+    "/intelligent_search",
+    "/multi_collection_search",
+    "/semantic_search",
+    "/contextual_search",
+    "/search/templates/{name}/run",
+    "/collections/{name}/search",
+    "/collections/{name}/search/text",
+    "/collections/{name}/search/text/batch",
+    "/collections/{name}/search/file",
+    "/collections/{name}/search/full_text",
+    "/collections/{name}/hybrid_search",
+    "/collections/{name}/explain",
+    "/collections/{name}/export",
+    "/collections/{name}/projection",
+    "/collections/{name}/duplicates",
+    "/collections/{name}/aggregate",
+    "/collections/{name}/facets",
+    "/collections/{name}/count",
+    "/collections/{name}/scroll",
+    "/collections/{name}/similarity_matrix",
+    "/collections/{name}/vectors/get",
+    "/discover",
+    "/discovery/filter_collections",
+    "/discovery/score_collections",
+    "/discovery/expand_queries",
+    "/discovery/broad_discovery",
+    "/discovery/semantic_focus",
+    "/discovery/promote_readme",
+    "/discovery/compress_evidence",
+    "/discovery/build_answer_plan",
+    "/discovery/render_llm_prompt",
+    "/file/content",
+    "/file/list",
+    "/file/summary",
+    "/file/chunks",
+    "/file/outline",
+    "/file/related",
+    "/file/search_by_type",
+    "/qdrant/collections/{name}/points",
+    "/qdrant/collections/{name}/points/scroll",
+    "/qdrant/collections/{name}/points/count",
+    "/qdrant/collections/{name}/points/search",
+    "/qdrant/collections/{name}/points/search/batch",
+    "/qdrant/collections/{name}/points/search/groups",
+    "/qdrant/collections/{name}/points/search/matrix/pairs",
+    "/qdrant/collections/{name}/points/search/matrix/offsets",
+    "/qdrant/collections/{name}/points/recommend",
+    "/qdrant/collections/{name}/points/recommend/batch",
+    "/qdrant/collections/{name}/points/query",
+    "/qdrant/collections/{name}/points/query/batch",
+    "/qdrant/collections/{name}/points/query/groups",
+    "/pinecone/collections/{name}/query",
+    "/pinecone/collections/{name}/describe_index_stats",
+];
+
+/// The route template a request matched, or its raw path when it
+/// matched none (an unknown route still counts as a possible write).
+fn matched_route(req: &axum::extract::Request) -> &str {
+    req.extensions()
+        .get::<axum::extract::MatchedPath>()
+        .map_or(req.uri().path(), |p| p.as_str())
+}
+
+/// Whether a REST call to `route` (see [`matched_route`]) can change
+/// data or configuration. GraphQL is decided from the request body (see
+/// [`is_graphql_mutation`]).
+fn is_mutating_rest_call(method: &axum::http::Method, route: &str) -> bool {
+    if !crate::server::VectorizerServer::is_write_request(method) {
+        return false;
+    }
+    if route.starts_with("/mcp") || route == "/message" {
+        // Audited per tool call by the MCP service
+        return false;
+    }
+    !(method == axum::http::Method::POST && READ_ONLY_POST_ROUTES.contains(&route))
+}
+
+/// Whether a GraphQL request body is a mutation.
+fn is_graphql_mutation(body: &serde_json::Value) -> bool {
+    body.get("query")
+        .and_then(|q| q.as_str())
+        .is_some_and(|q| q.trim_start().starts_with("mutation"))
+}
+
+/// Record mutating REST calls in the audit log (`audit_log`).
+///
+/// Must sit inside the auth and hub layers so the caller's `AuthState`
+/// and tenant are present. JSON bodies are read (up to the request size
+/// limit) to find the target collection and the number of IDs; the
+/// record is written once the response status is known.
+pub(super) async fn mutation_audit_middleware(
+    State((audit_log, max_body_bytes)): State<(Arc<MutationAuditLog>, usize)>,
+    req: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> axum::response::Response {
+    let path = req.uri().path().to_string();
+    let route = matched_route(&req).to_string();
+    let is_graphql = path.ends_with("/graphql");
+    if !is_graphql && !is_mutating_rest_call(req.method(), &route) {
+        return next.run(req).await;
+    }
+    if is_graphql && req.method() != axum::http::Method::POST {
+        return next.run(req).await;
+    }
+
+    let operation = format!("{} {}", req.method(), route);
+    let mut record = MutationRecord::new(
+        AuditProtocol::Rest,
+        audit_actor(req.extensions()),
+        operation,
+    );
+    record.collection = collection_from_path(&path).map(str::to_string);
+    record.client_ip = ["x-forwarded-for", "x-real-ip"].iter().find_map(|h| {
+        req.headers()
+            .get(*h)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.split(',').next())
+            .map(|v| v.trim().to_string())
+    });
+
+    let is_json = req
+        .headers()
+        .get(axum::http::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|ct| ct.starts_with("application/json"));
+    let (req, body) = if is_json {
+        let (parts, body) = req.into_parts();
+        let bytes = match axum::body::to_bytes(body, max_body_bytes).await {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                tracing::warn!("Mutation audit could not read request body: {}", e);
+                return axum::response::IntoResponse::into_response(create_error_response(
+                    "payload_too_large",
+                    "Request body too large",
+                    axum::http::StatusCode::PAYLOAD_TOO_LARGE,
+                ));
+            }
+        };
+        let body = serde_json::from_slice::<serde_json::Value>(&bytes).ok();
+        let req = axum::extract::Request::from_parts(parts, axum::body::Body::from(bytes));
+        (req, body)
+    } else {
+        (req, None)
+    };
+    if is_graphql && !body.as_ref().is_some_and(is_graphql_mutation) {
+        return next.run(req).await;
+    }
+    if let Some(body) = &body {
+        record = record.with_request_body(body);
+    }
+    if record.ids_count.is_none() && record.operation.contains("/vectors/{id}") {
+        // `/collections/{name}/vectors/{id}` and its sub-resources
+        record.ids_count = Some(1);
+    }
+
+    let response = next.run(req).await;
+    let status = response.status();
+    record.status = Some(status.as_u16());
+    record.success = Some(status.is_success());
+    record.correlation_id = response
+        .headers()
+        .get("x-correlation-id")
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    audit_log.record(record);
+    response
+}
+
+/// The collection a route names in its path: the parameter right after a
+/// `collections` segment of the matched route, percent-decoded.
+async fn path_collection(parts: &mut axum::http::request::Parts) -> Option<String> {
+    use axum::extract::FromRequestParts;
+
+    let route = parts.extensions.get::<axum::extract::MatchedPath>()?;
+    let param = collection_from_path(route.as_str())?
+        .strip_prefix('{')?
+        .strip_suffix('}')?
+        .to_string();
+    let params = axum::extract::RawPathParams::from_request_parts(parts, &())
+        .await
+        .ok()?;
+    params
+        .iter()
+        .find(|(key, _)| *key == param)
+        .map(|(_, value)| value.to_string())
+}
+
+/// Whether a request carries a JSON body by the rule axum's `Json`
+/// extractor accepts it with: `application/json` or any
+/// `application/*+json`, parameters and case aside.
+fn is_json_content_type(headers: &axum::http::HeaderMap) -> bool {
+    let Some(content_type) = headers
+        .get(axum::http::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+    else {
+        return false;
+    };
+    let essence = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    match essence.split_once('/') {
+        Some(("application", subtype)) => subtype == "json" || subtype.ends_with("+json"),
+        _ => false,
+    }
+}
+
+/// The collections a REST call names: the route's `collections/{name}`
+/// segment, a `collection` query parameter and the JSON body. The body
+/// is read (up to `max_body_bytes`) and handed back with the request; one
+/// too large to read is answered with 413. `check` names the caller in
+/// the log.
+async fn named_collections(
+    req: axum::extract::Request,
+    max_body_bytes: usize,
+    check: &str,
+) -> Result<(axum::extract::Request, Vec<String>), axum::response::Response> {
+    let (mut parts, body) = req.into_parts();
+    let mut named: Vec<String> = path_collection(&mut parts).await.into_iter().collect();
+    if let Ok(axum::extract::Query(query)) =
+        axum::extract::Query::<std::collections::HashMap<String, String>>::try_from_uri(&parts.uri)
+        && let Some(collection) = query.get("collection")
+    {
+        named.push(collection.clone());
+    }
+
+    let body = if is_json_content_type(&parts.headers) {
+        let bytes = match axum::body::to_bytes(body, max_body_bytes).await {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                tracing::warn!("{} could not read request body: {}", check, e);
+                return Err(axum::response::IntoResponse::into_response(
+                    create_error_response(
+                        "payload_too_large",
+                        "Request body too large",
+                        axum::http::StatusCode::PAYLOAD_TOO_LARGE,
+                    ),
+                ));
+            }
+        };
+        if let Ok(value) = serde_json::from_slice::<serde_json::Value>(&bytes) {
+            // `POST /collections` and its Qdrant twin name the new
+            // collection in `name`
+            let creates = parts.method == axum::http::Method::POST
+                && parts.uri.path().ends_with("/collections");
+            named.extend(
+                collections_named_in(&value, creates)
+                    .into_iter()
+                    .map(str::to_string),
+            );
+            // `POST /collections/{name}/clone` creates `target`
+            if parts.uri.path().ends_with("/clone")
+                && let Some(target) = value.get("target").and_then(serde_json::Value::as_str)
+            {
+                named.push(target.to_string());
+            }
+        }
+        axum::body::Body::from(bytes)
+    } else {
+        body
+    };
+    Ok((axum::extract::Request::from_parts(parts, body), named))
+}
+
+/// Refuse REST calls that name a collection outside the caller's API-key
+/// namespace (`namespace` on `POST /auth/keys`) with 403.
+///
+/// Must sit inside the auth layers so the `AuthState` extension is
+/// present; callers without a namespace pass straight through. The
+/// collections are found by [`named_collections`]. Collection listings
+/// are filtered by their handlers.
+pub(super) async fn collection_namespace_middleware(
+    State(max_body_bytes): State<usize>,
+    req: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> axum::response::Response {
+    let Some(namespace) = req
+        .extensions()
+        .get::<AuthState>()
+        .filter(|auth| auth.authenticated)
+        .and_then(|auth| auth.user_claims.namespace.clone())
+    else {
+        return next.run(req).await;
+    };
+
+    let (req, named) = match named_collections(req, max_body_bytes, "Namespace check").await {
+        Ok(found) => found,
+        Err(response) => return response,
+    };
+    if let Some(outside) = named.iter().find(|name| !namespace.contains(name)) {
+        return axum::response::IntoResponse::into_response(create_error_response(
+            "forbidden",
+            &format!(
+                "Collection '{}' is outside this API key's namespace '{}'",
+                outside, namespace
+            ),
+            axum::http::StatusCode::FORBIDDEN,
+        ));
+    }
+    next.run(req).await
+}
+
+/// Whether the caller's API-key namespace covers `collection`. Callers
+/// without a namespace reach every collection.
+pub(crate) fn caller_can_access(auth: Option<&AuthState>, collection: &str) -> bool {
+    auth.filter(|auth| auth.authenticated)
+        .is_none_or(|auth| auth.user_claims.can_access_collection(collection))
+}
+
+/// The collections a caller confined to an API-key namespace may reach,
+/// for calls that cover every collection when the request names none;
+/// `None` for callers without a namespace.
+pub(crate) fn caller_visible_collections(
+    store: &vectorizer::VectorStore,
+    auth: Option<&AuthState>,
+) -> Option<Vec<String>> {
+    auth.filter(|auth| auth.authenticated)?
+        .user_claims
+        .namespace
+        .as_ref()?;
+    let mut collections = store.list_collections();
+    collections.retain(|name| caller_can_access(auth, name));
+    Some(collections)
+}
+
+/// Refuse, with 403, a collection outside the caller's API-key
+/// namespace.
+///
+/// [`collection_namespace_middleware`] only sees the collections a
+/// request names. Handlers call this where the server resolves a
+/// collection the request does not name itself: a stored search
+/// template's collections, an experiment variant's collection or a
+/// Qdrant `with_lookup` target.
+pub(crate) fn check_caller_namespace(
+    auth: Option<&AuthState>,
+    collection: &str,
+) -> Result<(), ErrorResponse> {
+    if caller_can_access(auth, collection) {
+        return Ok(());
+    }
+    Err(create_error_response(
+        "forbidden",
+        &format!(
+            "Collection '{}' is outside this API key's namespace",
+            collection
+        ),
+        axum::http::StatusCode::FORBIDDEN,
+    ))
+}
+
+/// Refuse, with 403, calls whose collections cannot be checked against
+/// an API-key namespace (GraphQL documents name them anywhere in the
+/// query) from a caller confined to one.
+pub(crate) fn refuse_namespaced(auth: Option<&AuthState>) -> Result<(), ErrorResponse> {
+    let Some(namespace) = auth
+        .filter(|auth| auth.authenticated)
+        .and_then(|auth| auth.user_claims.namespace.as_ref())
+    else {
+        return Ok(());
+    };
+    Err(create_error_response(
+        "forbidden",
+        &format!(
+            "Not available to API keys confined to namespace '{}'",
+            namespace
+        ),
+        axum::http::StatusCode::FORBIDDEN,
+    ))
+}
+
+/// Routes that confine a hub tenant to its slice of a tenant-partitioned
+/// collection (see `rest_handlers::tenant_partition`), by method and
+/// matched path.
+const TENANT_PARTITION_ROUTES: &[(&str, &str)] = &[
+    ("GET", "/collections"),
+    ("GET", "/collections/{name}"),
+    ("POST", "/insert"),
+    ("POST", "/insert_texts"),
+    ("POST", "/batch_insert"),
+    ("POST", "/insert_vectors"),
+    ("POST", "/insert_image"),
+    ("POST", "/search"),
+    ("POST", "/collections/{name}/search"),
+    ("POST", "/collections/{name}/search/text"),
+    ("POST", "/retrieve"),
+    ("GET", "/collections/{name}/vectors/{id}"),
+    ("POST", "/collections/{name}/vectors/get"),
+    ("POST", "/collections/{name}/scroll"),
+    ("DELETE", "/collections/{name}/vectors/{id}"),
+    ("PATCH", "/collections/{name}/vectors/{id}/payload"),
+    ("GET", "/collections/{name}/vectors/{id}/neighbors"),
+    ("GET", "/collections/{name}/sample"),
+    ("POST", "/collections/{name}/projection"),
+    ("POST", "/collections/{name}/duplicates"),
+    ("POST", "/collections/{name}/cluster"),
+    ("POST", "/collections/{name}/aggregate"),
+    ("POST", "/collections/{name}/facets"),
+    ("POST", "/collections/{name}/count"),
+    ("POST", "/search/templates/{name}/run"),
+];
+
+/// Refuse a hub tenant's calls that name a tenant-partitioned collection
+/// on routes that do not enforce the partition, with 403
+/// `tenant_partition_unsupported`, so no route reads or writes another
+/// tenant's vectors.
+///
+/// Must sit inside the hub layer so the tenant is known; calls without a
+/// tenant pass straight through.
+pub(super) async fn tenant_partition_middleware(
+    State((store, max_body_bytes)): State<(Arc<vectorizer::VectorStore>, usize)>,
+    req: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> axum::response::Response {
+    if req.extensions().get::<RequestTenantContext>().is_none() {
+        return next.run(req).await;
+    }
+    let route = format!(
+        "{} {}",
+        req.method(),
+        req.extensions()
+            .get::<axum::extract::MatchedPath>()
+            .map_or(req.uri().path(), |p| p.as_str())
+    );
+    if TENANT_PARTITION_ROUTES
+        .iter()
+        .any(|(method, path)| route == format!("{method} {path}"))
+    {
+        return next.run(req).await;
+    }
+
+    let (req, named) = match named_collections(req, max_body_bytes, "Partition check").await {
+        Ok(found) => found,
+        Err(response) => return response,
+    };
+    let partitioned = named.iter().find(|name| {
+        store
+            .get_collection(name)
+            .is_ok_and(|c| c.config().tenant_partition.is_some())
+    });
+    if let Some(name) = partitioned {
+        return axum::response::IntoResponse::into_response(create_error_response(
+            "tenant_partition_unsupported",
+            &format!(
+                "Collection '{}' is partitioned by tenant and {} does not enforce the partition",
+                name, route
+            ),
+            axum::http::StatusCode::FORBIDDEN,
+        ));
+    }
+    next.run(req).await
+}
+
+/// Refuse mutating REST calls with 503 while the server drains
+/// (`POST /admin/drain`) or is read-only (`PUT /admin/read_only`) and
+/// count the admitted ones as in flight so the drain can wait for them.
+///
+/// GraphQL bodies are only read once writes are refused, to refuse
+/// mutations; GraphQL mutations admitted earlier are not counted.
+pub(super) async fn drain_middleware(
+    State((drain, max_body_bytes)): State<(Arc<DrainState>, usize)>,
+    req: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> axum::response::Response {
+    let path = req.uri().path();
+    if path.ends_with("/graphql") && req.method() == axum::http::Method::POST {
+        let Some(refusal) = drain.refusal() else {
+            return next.run(req).await;
+        };
+        let (parts, body) = req.into_parts();
+        let Ok(bytes) = axum::body::to_bytes(body, max_body_bytes).await else {
+            return axum::response::IntoResponse::into_response(create_error_response(
+                "payload_too_large",
+                "Request body too large",
+                axum::http::StatusCode::PAYLOAD_TOO_LARGE,
+            ));
+        };
+        if serde_json::from_slice::<serde_json::Value>(&bytes)
+            .is_ok_and(|body| is_graphql_mutation(&body))
+        {
+            return axum::response::IntoResponse::into_response(ErrorResponse::from(refusal));
+        }
+        return next
+            .run(axum::extract::Request::from_parts(
+                parts,
+                axum::body::Body::from(bytes),
+            ))
+            .await;
+    }
+
+    if !is_mutating_rest_call(req.method(), matched_route(&req)) {
+        return next.run(req).await;
+    }
+    let _write = match drain.try_enter() {
+        Ok(write) => write,
+        Err(refusal) => {
+            return axum::response::IntoResponse::into_response(ErrorResponse::from(refusal));
+        }
+    };
+    next.run(req).await
+}
+
 /// Get File Watcher metrics endpoint
 pub async fn get_file_watcher_metrics(
     State(state): State<Arc<ServerState>>,
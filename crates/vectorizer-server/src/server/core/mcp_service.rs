@@ -1,14 +1,134 @@
-//! MCP `ServerHandler` implementation used by the StreamableHTTP
-//! transport. Constructed per-session from
-//! [`super::routing::VectorizerServer::create_mcp_router`] and bridges
+//! MCP `ServerHandler` implementation used by the StreamableHTTP and
+//! legacy SSE transports and `vectorizer mcp --stdio`. Constructed
+//! per-session from
+//! [`super::routing::VectorizerServer::create_mcp_router`] (once for the
+//! process in [`super::mcp_stdio`]) and bridges
 //! MCP tool calls into the existing [`crate::server::mcp::handlers`]
-//! dispatch table.
+//! dispatch table, resource reads / subscriptions into
+//! [`crate::server::mcp::resources`] and prompt rendering into
+//! [`crate::server::mcp::prompts`].
 
 use std::sync::Arc;
 
 use vectorizer::VectorStore;
+use vectorizer::auth::middleware::AuthState;
+use vectorizer::auth::{CollectionNamespace, collections_named_in};
 use vectorizer::db::UpsertQueue;
 use vectorizer::embedding::EmbeddingManager;
+use vectorizer::security::{
+    AuditProtocol, MutationAuditLog, MutationRecord, PayloadRedactor, PayloadView,
+};
+
+use crate::server::drain::DrainState;
+use crate::server::mcp::prompts;
+use crate::server::mcp::resources::{self, ResourceSubscriptions};
+
+/// MCP tools that change data, recorded in the audit log and refused
+/// while the server drains or is read-only.
+const MUTATING_TOOLS: &[&str] = &[
+    "create_collection",
+    "delete_collection",
+    "cleanup_empty_collections",
+    "insert_text",
+    "update_vector",
+    "delete_vector",
+    "batch_insert_texts",
+    "batch_update",
+    "batch_delete",
+    "graph_create_edge",
+    "graph_delete_edge",
+    "graph_discover_edges",
+];
+
+/// MCP tools whose `name` argument is a collection name.
+const COLLECTION_NAME_TOOLS: &[&str] = &[
+    "create_collection",
+    "get_collection_info",
+    "delete_collection",
+];
+
+/// MCP tools whose results carry stored payloads as `"payload"`
+/// objects; they are redacted in place for callers under
+/// `api.payload_redaction`.
+const PAYLOAD_TOOLS: &[&str] = &["search", "get_vector", "search_hybrid", "batch_search"];
+
+/// MCP tools that flatten payload fields into result shapes of their own
+/// (passage text, metadata maps, file listings and contents), where
+/// field rules cannot be applied. They are refused to callers whose
+/// payloads are redacted, as are prompts, which inline passage text.
+const PAYLOAD_FLATTENING_TOOLS: &[&str] = &[
+    "search_intelligent",
+    "search_semantic",
+    "search_extra",
+    "multi_collection_search",
+    "contextual_search",
+    "get_file_content",
+    "list_files",
+    "get_file_chunks",
+    "get_project_outline",
+    "get_related_files",
+    "get_file_summary",
+    "search_by_file_type",
+    "discover",
+    "broad_discovery",
+    "semantic_focus",
+];
+
+/// Refusal for a tool or prompt that would expose unredacted payloads.
+fn redaction_refusal(what: &str) -> rmcp::model::ErrorData {
+    rmcp::model::ErrorData::invalid_request(
+        format!(
+            "'{}' is not available to callers whose payloads are redacted",
+            what
+        ),
+        None,
+    )
+}
+
+/// Redact the `"payload"` objects in the JSON text blocks of a tool
+/// result, resolving collections like the HTTP layer does.
+fn redact_tool_result(
+    view: &PayloadView,
+    collection: Option<&str>,
+    mut result: rmcp::model::CallToolResult,
+) -> rmcp::model::CallToolResult {
+    for block in &mut result.content {
+        let Some(mut value) = block
+            .as_text()
+            .and_then(|text| serde_json::from_str::<serde_json::Value>(&text.text).ok())
+        else {
+            continue;
+        };
+        if view.redact_response(collection, &mut value) {
+            *block = rmcp::model::ContentBlock::text(value.to_string());
+        }
+    }
+    result
+}
+
+/// `list_collections` for a caller confined to `namespace`.
+fn namespaced_collection_list(
+    store: &VectorStore,
+    namespace: &CollectionNamespace,
+) -> rmcp::model::CallToolResult {
+    let mut collections = store.list_collections();
+    collections.retain(|name| namespace.contains(name));
+    let response = serde_json::json!({
+        "collections": collections,
+        "total": collections.len()
+    });
+    rmcp::model::CallToolResult::success(vec![rmcp::model::ContentBlock::text(
+        response.to_string(),
+    )])
+}
+
+/// Collection namespace of an authenticated caller confined to one.
+fn caller_namespace(extensions: Option<&axum::http::Extensions>) -> Option<CollectionNamespace> {
+    extensions
+        .and_then(|extensions| extensions.get::<AuthState>())
+        .filter(|auth| auth.authenticated)
+        .and_then(|auth| auth.user_claims.namespace.clone())
+}
 
 /// MCP Service implementation
 #[derive(Clone)]
@@ -21,6 +141,49 @@ pub(super) struct VectorizerMcpService {
     /// collection's in-flight depth past the configured hard limit
     /// returns a structured error.
     pub(super) upsert_queue: Arc<UpsertQueue>,
+    /// HiveHub integration; insert tools are admitted against the
+    /// tenant quotas of the collection's owner when present
+    pub(super) hub_manager: Option<Arc<vectorizer::hub::HubManager>>,
+    /// Audit trail of mutating tool calls (`audit_log`)
+    pub(super) audit_log: Option<Arc<MutationAuditLog>>,
+    /// Drain mode; mutating tools are refused while it is on
+    pub(super) drain: Arc<DrainState>,
+    /// `api.payload_redaction`, applied per caller to tool results
+    pub(super) payload_redactor: Option<Arc<PayloadRedactor>>,
+    /// This session's resource subscriptions
+    pub(super) resource_subscriptions: Arc<ResourceSubscriptions>,
+    /// HTTP extensions of the request that opened the session, for
+    /// transports whose messages carry no HTTP request of their own
+    /// (legacy SSE)
+    pub(super) session_extensions: Option<axum::http::Extensions>,
+}
+
+impl VectorizerMcpService {
+    /// HTTP extensions identifying the caller of a request. The
+    /// StreamableHTTP transport hands over each request's HTTP parts;
+    /// other transports fall back to the session's.
+    fn request_extensions<'a>(
+        &'a self,
+        context: &'a rmcp::service::RequestContext<rmcp::RoleServer>,
+    ) -> Option<&'a axum::http::Extensions> {
+        context
+            .extensions
+            .get::<axum::http::request::Parts>()
+            .map(|parts| &parts.extensions)
+            .or(self.session_extensions.as_ref())
+    }
+
+    /// How payloads are redacted for the caller of a request.
+    fn payload_view(
+        &self,
+        context: &rmcp::service::RequestContext<rmcp::RoleServer>,
+    ) -> PayloadView {
+        super::helpers::caller_payload_view(
+            self.payload_redactor.clone(),
+            self.request_extensions(context)
+                .and_then(|extensions| extensions.get::<AuthState>()),
+        )
+    }
 }
 
 impl rmcp::ServerHandler for VectorizerMcpService {
@@ -31,7 +194,14 @@ impl rmcp::ServerHandler for VectorizerMcpService {
         // `#[non_exhaustive]`, so struct-literal syntax is no longer
         // legal — build them through the `Implementation::new` +
         // `InitializeResult::new` builder chains instead.
-        ServerInfo::new(ServerCapabilities::builder().enable_tools().build())
+        ServerInfo::new(
+            ServerCapabilities::builder()
+                .enable_tools()
+                .enable_resources()
+                .enable_resources_subscribe()
+                .enable_prompts()
+                .build(),
+        )
             .with_protocol_version(ProtocolVersion::default())
             .with_server_info(
                 Implementation::new("vectorizer-server", env!("CARGO_PKG_VERSION"))
@@ -61,20 +231,93 @@ impl rmcp::ServerHandler for VectorizerMcpService {
     fn call_tool(
         &self,
         request: rmcp::model::CallToolRequestParams,
-        _context: rmcp::service::RequestContext<rmcp::RoleServer>,
+        context: rmcp::service::RequestContext<rmcp::RoleServer>,
     ) -> impl std::future::Future<
         Output = Result<rmcp::model::CallToolResult, rmcp::model::ErrorData>,
     > + Send
     + '_ {
         async move {
-            crate::server::mcp::handlers::handle_mcp_tool(
+            let namespace = caller_namespace(self.request_extensions(&context));
+            if let Some(namespace) = &namespace {
+                if request.name == "list_collections" {
+                    return Ok(namespaced_collection_list(&self.store, namespace));
+                }
+                if let Some(args) = &request.arguments {
+                    let args = serde_json::Value::Object(args.clone());
+                    let names_collection = COLLECTION_NAME_TOOLS.contains(&request.name.as_ref());
+                    if let Err(e) = collections_named_in(&args, names_collection)
+                        .into_iter()
+                        .try_for_each(|collection| namespace.check(collection))
+                    {
+                        return Err(crate::server::mcp::handlers::to_mcp_error(e));
+                    }
+                }
+            }
+
+            let view = self.payload_view(&context);
+            if !view.is_passthrough() && PAYLOAD_FLATTENING_TOOLS.contains(&request.name.as_ref()) {
+                return Err(redaction_refusal(&request.name));
+            }
+            let collection = request
+                .arguments
+                .as_ref()
+                .and_then(|args| args.get("collection"))
+                .and_then(|v| v.as_str())
+                .map(str::to_string);
+
+            let _write = if MUTATING_TOOLS.contains(&request.name.as_ref()) {
+                match self.drain.try_enter() {
+                    Ok(write) => Some(write),
+                    Err(refusal) => {
+                        return Err(rmcp::model::ErrorData::internal_error(
+                            format!("{}: {}", refusal.code(), refusal.message()),
+                            Some(serde_json::json!({ "code": refusal.code() })),
+                        ));
+                    }
+                }
+            } else {
+                None
+            };
+
+            let audit = self
+                .audit_log
+                .as_ref()
+                .filter(|_| MUTATING_TOOLS.contains(&request.name.as_ref()))
+                .map(|log| {
+                    let actor = self
+                        .request_extensions(&context)
+                        .map(super::helpers::audit_actor)
+                        .unwrap_or_else(vectorizer::security::AuditActor::anonymous);
+                    let record =
+                        MutationRecord::new(AuditProtocol::Mcp, actor, request.name.to_string());
+                    let record = match &request.arguments {
+                        Some(args) => {
+                            record.with_request_body(&serde_json::Value::Object(args.clone()))
+                        }
+                        None => record,
+                    };
+                    (log, record)
+                });
+
+            let redact = !view.is_passthrough() && PAYLOAD_TOOLS.contains(&request.name.as_ref());
+            let result = crate::server::mcp::handlers::handle_mcp_tool(
                 request,
                 self.store.clone(),
                 self.embedding_manager.clone(),
                 self.cluster_manager.clone(),
                 self.upsert_queue.clone(),
+                self.hub_manager.clone(),
             )
-            .await
+            .await;
+
+            if let Some((log, mut record)) = audit {
+                record.success = Some(result.as_ref().is_ok_and(|r| r.is_error != Some(true)));
+                log.record(record);
+            }
+            if redact {
+                return result.map(|r| redact_tool_result(&view, collection.as_deref(), r));
+            }
+            result
         }
     }
 
@@ -88,7 +331,131 @@ impl rmcp::ServerHandler for VectorizerMcpService {
     + '_ {
         async move {
             use rmcp::model::ListResourcesResult;
-            Ok(ListResourcesResult::with_all_items(vec![]))
+            Ok(ListResourcesResult::with_all_items(
+                resources::list_resources(),
+            ))
         }
     }
+
+    fn read_resource(
+        &self,
+        request: rmcp::model::ReadResourceRequestParams,
+        context: rmcp::service::RequestContext<rmcp::RoleServer>,
+    ) -> impl std::future::Future<
+        Output = Result<rmcp::model::ReadResourceResult, rmcp::model::ErrorData>,
+    > + Send
+    + '_ {
+        async move {
+            let namespace = caller_namespace(self.request_extensions(&context));
+            resources::read_resource(&self.store, &request.uri, namespace.as_ref())
+        }
+    }
+
+    fn subscribe(
+        &self,
+        request: rmcp::model::SubscribeRequestParams,
+        context: rmcp::service::RequestContext<rmcp::RoleServer>,
+    ) -> impl std::future::Future<Output = Result<(), rmcp::model::ErrorData>> + Send + '_ {
+        async move {
+            self.resource_subscriptions.subscribe(
+                &request.uri,
+                self.store.clone(),
+                caller_namespace(self.request_extensions(&context)),
+                context.peer.clone(),
+            )
+        }
+    }
+
+    fn list_prompts(
+        &self,
+        _request: Option<rmcp::model::PaginatedRequestParam>,
+        _context: rmcp::service::RequestContext<rmcp::RoleServer>,
+    ) -> impl std::future::Future<
+        Output = Result<rmcp::model::ListPromptsResult, rmcp::model::ErrorData>,
+    > + Send
+    + '_ {
+        async move {
+            use rmcp::model::ListPromptsResult;
+            Ok(ListPromptsResult::with_all_items(prompts::list_prompts()))
+        }
+    }
+
+    fn get_prompt(
+        &self,
+        request: rmcp::model::GetPromptRequestParams,
+        context: rmcp::service::RequestContext<rmcp::RoleServer>,
+    ) -> impl std::future::Future<
+        Output = Result<rmcp::model::GetPromptResult, rmcp::model::ErrorData>,
+    > + Send
+    + '_ {
+        async move {
+            if !self.payload_view(&context).is_passthrough() {
+                return Err(redaction_refusal(&request.name));
+            }
+            let namespace = caller_namespace(self.request_extensions(&context));
+            prompts::get_prompt(
+                self.store.clone(),
+                &request.name,
+                request.arguments.as_ref(),
+                namespace.as_ref(),
+            )
+            .await
+        }
+    }
+
+    fn unsubscribe(
+        &self,
+        request: rmcp::model::UnsubscribeRequestParams,
+        _context: rmcp::service::RequestContext<rmcp::RoleServer>,
+    ) -> impl std::future::Future<Output = Result<(), rmcp::model::ErrorData>> + Send + '_ {
+        async move {
+            self.resource_subscriptions.unsubscribe(&request.uri);
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use vectorizer::config::sections::redaction::{
+        PayloadRedactionConfig, RedactionAction, RedactionRule,
+    };
+
+    use super::*;
+
+    #[test]
+    fn tool_results_redact_payloads_of_the_named_collection() {
+        let redactor = PayloadRedactor::from_config(&PayloadRedactionConfig {
+            enabled: true,
+            rules: vec![RedactionRule {
+                field: "email".to_string(),
+                action: RedactionAction::Drop,
+                collections: vec!["customers".to_string()],
+                roles: Vec::new(),
+            }],
+            ..Default::default()
+        })
+        .unwrap()
+        .map(Arc::new);
+        let view = PayloadView::new(redactor, Vec::new());
+        let body = serde_json::json!({
+            "results": [{"id": "p1", "payload": {"email": "a@b.c", "title": "t"}}],
+        });
+        let result = rmcp::model::CallToolResult::success(vec![rmcp::model::ContentBlock::text(
+            body.to_string(),
+        )]);
+
+        let redacted = redact_tool_result(&view, Some("customers"), result.clone());
+        let text = &redacted.content[0].as_text().unwrap().text;
+        let value: serde_json::Value = serde_json::from_str(text).unwrap();
+        assert!(value["results"][0]["payload"].get("email").is_none());
+        assert_eq!(value["results"][0]["payload"]["title"], "t");
+
+        let untouched = redact_tool_result(&view, Some("orders"), result);
+        assert_eq!(
+            untouched.content[0].as_text().unwrap().text,
+            body.to_string()
+        );
+    }
 }
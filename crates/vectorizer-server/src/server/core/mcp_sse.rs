@@ -0,0 +1,196 @@
+//! Legacy HTTP+SSE MCP transport (`GET /sse` + `POST /message`).
+//!
+//! Older MCP clients — and the published SDKs, whose `DEFAULT_MCP_URL`
+//! points at `/sse` — speak the pre-StreamableHTTP transport: the client
+//! opens an event stream, the first `endpoint` event names the URL to
+//! POST JSON-RPC messages to, and every server message comes back on the
+//! stream as a `message` event.
+//!
+//! Each stream is one session running its own [`VectorizerMcpService`].
+//! The service is served over an in-memory duplex pipe with the same
+//! newline-delimited JSON framing the stdio transport uses: POSTed
+//! messages are written into the pipe and every line the service writes
+//! back becomes an SSE event. The session ends when the client drops the
+//! stream; POSTs for an unknown session get `404`.
+
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::Router;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::routing::{get, post};
+use dashmap::DashMap;
+use futures::{Stream, StreamExt};
+use rmcp::ServiceExt;
+use serde::Deserialize;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::sync::mpsc;
+use tracing::{debug, warn};
+use vectorizer::auth::AuthManager;
+
+use super::helpers::{extract_auth_credentials, identify_mcp_caller};
+use super::mcp_service::VectorizerMcpService;
+use crate::server::error_middleware::{ErrorResponse, create_error_response};
+
+/// Capacity of a session's in-memory pipe, per direction.
+const SESSION_PIPE_BYTES: usize = 256 * 1024;
+
+/// Client messages a session buffers before `POST /message` waits.
+const SESSION_QUEUE_DEPTH: usize = 64;
+
+/// Open sessions, by id, to the queue feeding the session's service.
+type Sessions = DashMap<String, mpsc::Sender<String>>;
+
+#[derive(Clone)]
+struct SseState {
+    /// Service every session starts from
+    service: VectorizerMcpService,
+    auth_manager: Option<Arc<AuthManager>>,
+    sessions: Arc<Sessions>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MessageQuery {
+    #[serde(rename = "sessionId")]
+    session_id: String,
+}
+
+/// Removes its session when the event stream is dropped, which closes
+/// the session's pipe and stops its service.
+struct SessionGuard {
+    id: String,
+    sessions: Arc<Sessions>,
+}
+
+impl Drop for SessionGuard {
+    fn drop(&mut self) {
+        self.sessions.remove(&self.id);
+        debug!("MCP SSE session {} closed", self.id);
+    }
+}
+
+/// `GET /sse` and `POST /message`, serving sessions of `service`.
+pub(super) fn sse_router(
+    service: VectorizerMcpService,
+    auth_manager: Option<Arc<AuthManager>>,
+) -> Router {
+    Router::new()
+        .route("/sse", get(open_session))
+        .route("/message", post(post_message))
+        .with_state(SseState {
+            service,
+            auth_manager,
+            sessions: Arc::new(DashMap::new()),
+        })
+}
+
+/// GET /sse
+async fn open_session(
+    State(state): State<SseState>,
+    req: axum::extract::Request,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    // The session's messages carry no HTTP request of their own, so the
+    // caller is identified once, here
+    let mut extensions = req.extensions().clone();
+    if let Some(auth_manager) = &state.auth_manager
+        && let Some(auth) = identify_mcp_caller(extract_auth_credentials(&req), auth_manager).await
+    {
+        extensions.insert(auth);
+    }
+    let service = VectorizerMcpService {
+        resource_subscriptions: Default::default(),
+        session_extensions: Some(extensions),
+        ..state.service.clone()
+    };
+
+    let id = uuid::Uuid::new_v4().to_string();
+    let (client_pipe, server_pipe) = tokio::io::duplex(SESSION_PIPE_BYTES);
+    let session_id = id.clone();
+    tokio::spawn(async move {
+        match service.serve(server_pipe).await {
+            Ok(running) => {
+                let _ = running.waiting().await;
+            }
+            Err(e) => debug!(
+                "MCP SSE session {} ended during initialize: {}",
+                session_id, e
+            ),
+        }
+    });
+
+    let (client_read, mut client_write) = tokio::io::split(client_pipe);
+    let (sender, mut receiver) = mpsc::channel::<String>(SESSION_QUEUE_DEPTH);
+    tokio::spawn(async move {
+        while let Some(message) = receiver.recv().await {
+            if client_write.write_all(message.as_bytes()).await.is_err()
+                || client_write.write_all(b"\n").await.is_err()
+            {
+                break;
+            }
+        }
+        let _ = client_write.shutdown().await;
+    });
+    state.sessions.insert(id.clone(), sender);
+    debug!("MCP SSE session {} opened", id);
+
+    let endpoint = Event::default()
+        .event("endpoint")
+        .data(format!("/message?sessionId={id}"));
+    let guard = SessionGuard {
+        id,
+        sessions: state.sessions.clone(),
+    };
+    let lines = BufReader::new(client_read).lines();
+    let messages = futures::stream::unfold((lines, guard), |(mut lines, guard)| async move {
+        match lines.next_line().await {
+            Ok(Some(line)) => Some((
+                Ok(Event::default().event("message").data(line)),
+                (lines, guard),
+            )),
+            Ok(None) => None,
+            Err(e) => {
+                warn!("MCP SSE session {} failed: {}", guard.id, e);
+                None
+            }
+        }
+    });
+
+    let events = futures::stream::once(async move { Ok(endpoint) }).chain(messages);
+    Sse::new(events).keep_alive(KeepAlive::new().interval(Duration::from_secs(15)))
+}
+
+/// POST /message?sessionId=...
+async fn post_message(
+    State(state): State<SseState>,
+    Query(query): Query<MessageQuery>,
+    body: String,
+) -> Result<StatusCode, ErrorResponse> {
+    let message: serde_json::Value = serde_json::from_str(&body).map_err(|e| {
+        create_error_response(
+            "invalid_json",
+            &format!("Invalid JSON-RPC message: {e}"),
+            StatusCode::BAD_REQUEST,
+        )
+    })?;
+    let session_gone = || {
+        create_error_response(
+            "session_not_found",
+            &format!("No MCP session '{}'", query.session_id),
+            StatusCode::NOT_FOUND,
+        )
+    };
+    let sender = state
+        .sessions
+        .get(&query.session_id)
+        .map(|entry| entry.value().clone())
+        .ok_or_else(session_gone)?;
+    // Re-serialized so the message is a single line in the pipe
+    sender
+        .send(message.to_string())
+        .await
+        .map_err(|_| session_gone())?;
+    Ok(StatusCode::ACCEPTED)
+}
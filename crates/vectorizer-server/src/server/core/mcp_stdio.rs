@@ -0,0 +1,91 @@
+//! MCP over stdin/stdout (`vectorizer mcp --stdio`).
+//!
+//! Serves the same [`VectorizerMcpService`] the `/mcp` route does, but
+//! over the rmcp stdio transport and against the local data directory,
+//! so editors that spawn MCP servers as subprocesses can use Vectorizer
+//! without an HTTP server running. No HTTP, gRPC, file watcher, cluster
+//! or auth is started: the session is the parent process, and every
+//! caller is unrestricted.
+//!
+//! stdout carries the protocol, so logging must stay on stderr / the log
+//! file (which is what `logging::init_logging_with_level` does).
+//! Collections are loaded from `vectorizer.vecdb` on start and written
+//! back when the client closes stdin.
+
+use std::sync::Arc;
+
+use rmcp::ServiceExt;
+use tracing::{info, warn};
+use vectorizer::VectorStore;
+use vectorizer::embedding::EmbeddingManager;
+
+use super::bootstrap::{
+    build_default_provider, open_embedding_cache, register_all_providers,
+    resolve_extra_embedding_models,
+};
+use super::mcp_service::VectorizerMcpService;
+
+/// Run the MCP server over stdio until the client closes stdin.
+pub async fn serve_mcp_stdio(config_path: &str) -> anyhow::Result<()> {
+    let config = std::fs::read_to_string(config_path)
+        .ok()
+        .and_then(|content| {
+            serde_yaml::from_str::<vectorizer::config::VectorizerConfig>(&content).ok()
+        })
+        .unwrap_or_default();
+
+    let store = Arc::new(VectorStore::new_auto());
+    let vecdb_path = VectorStore::get_data_dir().join("vectorizer.vecdb");
+    if vecdb_path.exists() {
+        let count = store.load_all_persisted_collections()?;
+        info!(
+            "📦 MCP stdio: loaded {} collections from {}",
+            count,
+            vecdb_path.display()
+        );
+    } else {
+        info!(
+            "📦 MCP stdio: no {} yet, starting empty",
+            vecdb_path.display()
+        );
+    }
+
+    let mut embedding_manager = EmbeddingManager::new();
+    let (provider_name, _dim, provider) = build_default_provider(config_path)?;
+    register_all_providers(
+        &mut embedding_manager,
+        provider_name,
+        provider,
+        &resolve_extra_embedding_models(config_path),
+        open_embedding_cache(config_path).as_ref(),
+    )?;
+
+    let service = VectorizerMcpService {
+        store: store.clone(),
+        embedding_manager: Arc::new(embedding_manager),
+        cluster_manager: None,
+        upsert_queue: Arc::new(vectorizer::db::UpsertQueue::from_config(
+            &config.backpressure,
+        )),
+        hub_manager: None,
+        audit_log: None,
+        drain: Arc::new(crate::server::drain::DrainState::default()),
+        // Local operator over stdio, like a caller in an exempt role
+        payload_redactor: None,
+        resource_subscriptions: Default::default(),
+        session_extensions: None,
+    };
+
+    info!("🔌 MCP stdio transport ready");
+    let running = service.serve(rmcp::transport::stdio()).await?;
+    let reason = running.waiting().await?;
+    info!("🔌 MCP stdio session ended: {:?}", reason);
+
+    // Tool calls only change memory; flush them into `vectorizer.vecdb`
+    let auto_save = vectorizer::db::AutoSaveManager::new(store, 1);
+    if let Err(e) = auto_save.force_save().await {
+        warn!("⚠️  MCP stdio: could not save collections: {}", e);
+        return Err(e.into());
+    }
+    Ok(())
+}
@@ -12,8 +12,14 @@
 //!                          `create_mcp_router`: route composition,
 //!                          middleware layering, graceful shutdown
 //! - [`grpc`]             — `VectorizerServer::start_grpc_server`
+//! - [`tls`]              — TLS listener shared by HTTP and gRPC, and
+//!                          certificate reload on `SIGHUP`
 //! - [`mcp_service`]      — the rmcp `ServerHandler` implementation
 //!                          (`VectorizerMcpService`)
+//! - [`mcp_sse`]          — legacy HTTP+SSE MCP transport (`/sse` +
+//!                          `/message`) for older clients and the SDKs
+//! - [`mcp_stdio`]        — `vectorizer mcp --stdio`: the same service
+//!                          over stdin/stdout against the local data dir
 //! - [`helpers`]          — shared request-parsing + response helpers
 //!                          (auth extractor, security headers, file-watcher
 //!                          metrics endpoint)
@@ -29,10 +35,19 @@ mod bootstrap;
 mod grpc;
 pub(super) mod helpers;
 mod mcp_service;
+mod mcp_sse;
+mod mcp_stdio;
 mod routing;
+mod tls;
 mod workspace_loader;
 
 // Re-export the one public handler so `src/server/mod.rs` can keep the
 // existing `/metrics` route referencing `get_file_watcher_metrics`
 // without knowing it moved.
+pub(crate) use bootstrap::build_embedding_provider;
+pub(crate) use helpers::{
+    caller_can_access, caller_payload_view, caller_visible_collections, check_caller_namespace,
+    refuse_namespaced,
+};
 pub use helpers::get_file_watcher_metrics;
+pub use mcp_stdio::serve_mcp_stdio;
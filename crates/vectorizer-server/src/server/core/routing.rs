@@ -13,7 +13,10 @@ use tower_http::cors::CorsLayer;
 use tracing::{error, info, warn};
 
 use super::helpers::{
-    extract_auth_credentials, get_file_watcher_metrics, security_headers_middleware,
+    collection_namespace_middleware, drain_middleware, extract_auth_credentials,
+    get_file_watcher_metrics, identify_mcp_caller, mutation_audit_middleware,
+    payload_redaction_middleware, request_signing_middleware, security_headers_middleware,
+    tenant_partition_middleware,
 };
 use super::mcp_service::VectorizerMcpService;
 use crate::server::{
@@ -21,6 +24,10 @@ use crate::server::{
     hub_handlers, qdrant, replication_handlers, rest_handlers, setup_handlers,
 };
 
+/// How long shutdown waits for running gRPC/MCP writes before it aborts
+/// the remaining tasks.
+const SHUTDOWN_DRAIN_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
 impl VectorizerServer {
     /// Start the server.
     ///
@@ -126,6 +133,15 @@ impl VectorizerServer {
         let grpc_snapshot_manager = self.snapshot_manager.clone();
         let grpc_raft_manager = self.raft_manager.clone();
         let grpc_upsert_queue = self.upsert_queue.clone();
+        let grpc_audit_log = self.audit_log.clone();
+        let grpc_auth_manager = self
+            .auth_handler_state
+            .as_ref()
+            .map(|state| state.auth_manager.clone());
+        let grpc_payload_redactor = self.payload_redactor.clone();
+        let grpc_tls = self.tls.clone();
+        let grpc_drain = self.drain.clone();
+        let grpc_hub_manager = self.hub_manager.clone();
         let grpc_handle = tokio::spawn(async move {
             if let Err(e) = Self::start_grpc_server(
                 &grpc_host,
@@ -135,6 +151,12 @@ impl VectorizerServer {
                 grpc_snapshot_manager,
                 grpc_raft_manager,
                 grpc_upsert_queue,
+                grpc_audit_log,
+                grpc_auth_manager,
+                grpc_payload_redactor,
+                grpc_tls,
+                grpc_drain,
+                grpc_hub_manager,
             )
             .await
             {
@@ -146,23 +168,27 @@ impl VectorizerServer {
         info!("✅ gRPC server task spawned");
 
         let app = self.build_router(is_production_bind).await;
+        let scheme = if self.tls.is_some() { "https" } else { "http" };
         info!("🌐 Vectorizer Server available at:");
-        info!("   📡 MCP StreamableHTTP: http://{}:{}/mcp", host, port);
-        info!("   🔌 REST API: http://{}:{}", host, port);
-        info!("   🔗 UMICP: http://{}:{}/umicp", host, port);
         info!(
-            "   🔍 UMICP Discovery (v0.2.1): http://{}:{}/umicp/discover",
-            host, port
+            "   📡 MCP StreamableHTTP: {}://{}:{}/mcp",
+            scheme, host, port
         );
-        info!("   🎯 Qdrant API: http://{}:{}/qdrant", host, port);
-        info!("   📊 GraphQL API: http://{}:{}/graphql", host, port);
+        info!("   🔌 REST API: {}://{}:{}", scheme, host, port);
+        info!("   🔗 UMICP: {}://{}:{}/umicp", scheme, host, port);
         info!(
-            "   🎮 GraphQL Playground: http://{}:{}/graphiql",
-            host, port
+            "   🔍 UMICP Discovery (v0.2.1): {}://{}:{}/umicp/discover",
+            scheme, host, port
         );
-        info!("   📊 Dashboard: http://{}:{}/dashboard/", host, port);
+        info!("   🎯 Qdrant API: {}://{}:{}/qdrant", scheme, host, port);
+        info!("   📊 GraphQL API: {}://{}:{}/graphql", scheme, host, port);
+        info!(
+            "   🎮 GraphQL Playground: {}://{}:{}/graphiql",
+            scheme, host, port
+        );
+        info!("   📊 Dashboard: {}://{}:{}/dashboard/", scheme, host, port);
         if self.auth_handler_state.is_some() {
-            info!("   🔐 Auth API: http://{}:{}/auth", host, port);
+            info!("   🔐 Auth API: {}://{}:{}/auth", scheme, host, port);
         }
         if self.hub_manager.is_some() {
             info!("   🌐 HiveHub: Cluster mode enabled (internal service access)");
@@ -216,11 +242,30 @@ impl VectorizerServer {
             let _ = shutdown_tx.send(());
         });
 
-        // Serve the application with graceful shutdown
-        let server_handle = axum::serve(listener, app).with_graceful_shutdown(async {
+        // Serve the application with graceful shutdown, over TLS when
+        // `tls` is enabled
+        let graceful = async {
             shutdown_rx.await.ok();
             info!("🛑 Graceful shutdown signal received, stopping HTTP server...");
-        });
+        };
+        let server_handle: std::pin::Pin<
+            Box<dyn std::future::Future<Output = std::io::Result<()>> + Send>,
+        > = match self.tls.clone() {
+            Some(tls) => {
+                super::tls::spawn_reload_on_sighup(tls.clone());
+                let listener = super::tls::TlsListener::new(listener, tls)?;
+                Box::pin(async move {
+                    axum::serve(listener, app)
+                        .with_graceful_shutdown(graceful)
+                        .await
+                })
+            }
+            None => Box::pin(async move {
+                axum::serve(listener, app)
+                    .with_graceful_shutdown(graceful)
+                    .await
+            }),
+        };
 
         // Spawn server task
         let server_task = tokio::spawn(async move {
@@ -248,6 +293,17 @@ impl VectorizerServer {
             }
         }
 
+        // HTTP requests have finished; refuse new gRPC/MCP writes and
+        // let the running ones complete before the tasks are aborted
+        self.drain.begin();
+        if !self.drain.wait_idle(SHUTDOWN_DRAIN_TIMEOUT).await {
+            warn!(
+                "⚠️ {} write(s) still running after {:?}, stopping anyway",
+                self.drain.in_flight(),
+                SHUTDOWN_DRAIN_TIMEOUT
+            );
+        }
+
         // Now shutdown all background tasks AFTER HTTP server has stopped
         info!("🛑 Stopping all background tasks...");
 
@@ -307,6 +363,27 @@ impl VectorizerServer {
             }
         }
 
+        // Persist the embedding cache index so the next boot reuses it
+        if let Some(cache) = self.embedding_manager.cache() {
+            match cache.flush() {
+                Ok(()) => info!("✅ Embedding cache flushed ({} embeddings)", cache.len()),
+                Err(e) => warn!("⚠️ Embedding cache flush failed: {}", e),
+            }
+        }
+
+        // Save the queries recorded since the last analytics flush
+        if let Some(analytics) = &self.query_analytics {
+            match analytics.flush() {
+                Ok(_) => info!("✅ Query analytics saved ({} queries)", analytics.len()),
+                Err(e) => warn!("⚠️ Query analytics save failed: {}", e),
+            }
+        }
+
+        // Save the experiment counters recorded since the last change
+        if let Err(e) = self.experiments.flush() {
+            warn!("⚠️ Experiment counters save failed: {}", e);
+        }
+
         // Auto save task (non-blocking) - abort AFTER force_save
         if let Ok(mut auto_task) = self.auto_save_task.try_lock() {
             if let Some(handle) = auto_task.take() {
@@ -384,6 +461,8 @@ impl VectorizerServer {
         // anonymous HTTP before they can present credentials.
         let public_routes = Router::new()
             .route("/health", get(rest_handlers::health_check))
+            .route("/health/live", get(rest_handlers::health_live))
+            .route("/health/ready", get(rest_handlers::health_ready))
             .route(
                 "/prometheus/metrics",
                 get(rest_handlers::get_prometheus_metrics),
@@ -404,6 +483,10 @@ impl VectorizerServer {
                 "/indexing/progress",
                 get(rest_handlers::get_indexing_progress),
             )
+            .route(
+                "/startup/progress",
+                get(rest_handlers::get_startup_progress),
+            )
             // GUI-specific endpoints
             .route("/status", get(rest_handlers::get_status))
             .route("/logs", get(rest_handlers::get_logs))
@@ -493,17 +576,26 @@ impl VectorizerServer {
                 "/collections/{name}",
                 delete(rest_handlers::delete_collection),
             )
+            .route(
+                "/collections/{name}",
+                axum::routing::patch(rest_handlers::patch_collection),
+            )
             // Collection cleanup (file watcher bug fix)
             .route(
                 "/collections/empty",
                 get(rest_handlers::list_empty_collections),
             )
+            .route(
+                "/collections/templates",
+                get(rest_handlers::list_collection_templates),
+            )
             .route(
                 "/collections/cleanup",
                 delete(rest_handlers::cleanup_empty_collections),
             )
             // Vector operations - single
             .route("/search", post(rest_handlers::search_vectors))
+            .route("/retrieve", post(rest_handlers::retrieve_documents))
             .route(
                 "/collections/{name}/search",
                 post(rest_handlers::search_vectors_by_collection),
@@ -512,18 +604,35 @@ impl VectorizerServer {
                 "/collections/{name}/search/text",
                 post(rest_handlers::search_vectors_by_text),
             )
+            .route(
+                "/collections/{name}/search/text/batch",
+                post(rest_handlers::search_text_batch),
+            )
             .route(
                 "/collections/{name}/search/file",
                 post(rest_handlers::search_by_file),
             )
+            .route(
+                "/collections/{name}/search/full_text",
+                post(rest_handlers::full_text_search),
+            )
             .route(
                 "/collections/{name}/hybrid_search",
                 post(rest_handlers::hybrid_search_vectors),
             )
+            // Live search subscriptions (needs change_feed.enabled)
+            .route(
+                "/ws/subscriptions",
+                get(crate::server::ws::subscriptions_ws_handler),
+            )
             .route("/insert", post(rest_handlers::insert_text))
+            .route("/insert_image", post(rest_handlers::insert_image))
             .route("/update", post(rest_handlers::update_vector))
             .route("/delete", post(rest_handlers::delete_vector))
             .route("/embed", post(rest_handlers::embed_text))
+            // OpenAI-compatible embeddings API
+            .route("/v1/embeddings", post(rest_handlers::create_embeddings))
+            .route("/v1/models", get(rest_handlers::list_models))
             .route("/vector", post(rest_handlers::get_vector))
             .route(
                 "/collections/{name}/vectors",
@@ -537,12 +646,48 @@ impl VectorizerServer {
                 "/collections/{name}/vectors/{id}",
                 delete(rest_handlers::delete_vector),
             )
+            .route(
+                "/collections/{name}/vectors/{id}/neighbors",
+                get(rest_handlers::vector_neighbors),
+            )
+            .route(
+                "/collections/{name}/sample",
+                get(rest_handlers::sample_collection),
+            )
+            .route(
+                "/collections/{name}/projection",
+                post(rest_handlers::project_collection),
+            )
+            .route(
+                "/collections/{name}/duplicates",
+                post(rest_handlers::find_collection_duplicates),
+            )
+            .route(
+                "/collections/{name}/cluster",
+                post(rest_handlers::cluster_collection),
+            )
+            .route(
+                "/collections/{name}/aggregate",
+                post(rest_handlers::aggregate_vectors),
+            )
+            .route(
+                "/collections/{name}/facets",
+                post(rest_handlers::collection_facets),
+            )
             // Issue #265: cross-collection move (tier demotion).
             // Insert-before-delete invariant — see handler doc.
             .route(
                 "/collections/{name}/vectors/move",
                 post(rest_handlers::move_vectors),
             )
+            .route(
+                "/collections/{name}/vectors/get",
+                post(rest_handlers::get_vectors),
+            )
+            .route(
+                "/collections/{name}/scroll",
+                post(rest_handlers::scroll_vectors),
+            )
             // Phase13: tier-control primitives
             .route(
                 "/collections/{name}/vectors/delete_by_filter",
@@ -556,10 +701,22 @@ impl VectorizerServer {
                 "/collections/{name}/vectors/copy",
                 post(rest_handlers::copy_vectors),
             )
+            .route(
+                "/collections/{name}/vectors/{id}/payload",
+                axum::routing::patch(rest_handlers::patch_vector_payload),
+            )
             .route(
                 "/collections/{name}/vectors/{id}/expiry",
                 axum::routing::patch(rest_handlers::set_vector_expiry),
             )
+            .route(
+                "/collections/{name}/similarity_matrix",
+                post(rest_handlers::similarity_matrix),
+            )
+            .route(
+                "/collections/{name}/count",
+                post(rest_handlers::count_vectors),
+            )
             .route(
                 "/collections/{name}/reencode",
                 post(rest_handlers::reencode_collection),
@@ -568,6 +725,16 @@ impl VectorizerServer {
                 "/collections/{name}/ttl",
                 post(rest_handlers::set_collection_ttl),
             )
+            .route(
+                "/collections/{name}/clone",
+                post(rest_handlers::clone_collection),
+            )
+            .route("/jobs", get(rest_handlers::list_jobs))
+            .route(
+                "/jobs/{id}",
+                get(rest_handlers::get_job).delete(rest_handlers::cancel_job),
+            )
+            .route("/jobs/{id}/result", get(rest_handlers::download_job_result))
             // phase14: schema evolution + observability
             .route(
                 "/collections/{name}/rename",
@@ -577,6 +744,50 @@ impl VectorizerServer {
                 "/collections/{name}/reindex",
                 post(rest_handlers::reindex_collection),
             )
+            .route(
+                "/collections/{name}/cold",
+                post(rest_handlers::set_collection_cold),
+            )
+            .route(
+                "/collections/{name}/recall_profile",
+                post(rest_handlers::measure_recall_profile),
+            )
+            .route(
+                "/collections/{name}/benchmark",
+                post(rest_handlers::run_recall_benchmark),
+            )
+            .route(
+                "/collections/{name}/feedback",
+                post(rest_handlers::record_feedback)
+                    .get(rest_handlers::get_feedback)
+                    .delete(rest_handlers::clear_feedback),
+            )
+            .route(
+                "/collections/{name}/hybrid_weights",
+                get(rest_handlers::get_hybrid_weights).delete(rest_handlers::clear_hybrid_weights),
+            )
+            .route(
+                "/collections/{name}/hybrid_weights/judgments",
+                post(rest_handlers::record_hybrid_judgments),
+            )
+            .route(
+                "/collections/{name}/hybrid_weights/tune",
+                post(rest_handlers::tune_hybrid_weights),
+            )
+            .route(
+                "/collections/{name}/kv",
+                get(rest_handlers::list_kv).post(rest_handlers::kv_transaction),
+            )
+            .route(
+                "/collections/{name}/kv/{*key}",
+                get(rest_handlers::get_kv)
+                    .put(rest_handlers::put_kv)
+                    .delete(rest_handlers::delete_kv),
+            )
+            .route(
+                "/collections/{name}/documents",
+                post(rest_handlers::ingest_document).delete(rest_handlers::delete_document),
+            )
             .route(
                 "/collections/{name}/snapshot",
                 post(rest_handlers::create_native_snapshot),
@@ -589,6 +800,10 @@ impl VectorizerServer {
                 "/collections/{name}/snapshots/{id}/restore",
                 post(rest_handlers::restore_native_snapshot),
             )
+            .route(
+                "/collections/{name}/restore",
+                post(rest_handlers::restore_collection_to_time),
+            )
             .route(
                 "/collections/{name}/explain",
                 post(rest_handlers::explain_search),
@@ -598,6 +813,40 @@ impl VectorizerServer {
                 "/slow_queries/config",
                 post(rest_handlers::set_slow_query_config),
             )
+            .route(
+                "/analytics/queries",
+                get(rest_handlers::get_query_analytics),
+            )
+            .route(
+                "/analytics/queries/clicks",
+                post(rest_handlers::record_query_clicks),
+            )
+            .route("/experiments", get(rest_handlers::list_experiments))
+            .route("/experiments/{name}", get(rest_handlers::get_experiment))
+            .route(
+                "/experiments/{name}/clicks",
+                post(rest_handlers::record_experiment_click),
+            )
+            .route(
+                "/experiments/{name}/evaluate",
+                post(rest_handlers::evaluate_experiment),
+            )
+            .route(
+                "/federation/sources",
+                get(rest_handlers::list_federated_sources),
+            )
+            .route(
+                "/search/templates",
+                get(rest_handlers::list_search_templates),
+            )
+            .route(
+                "/search/templates/{name}",
+                get(rest_handlers::get_search_template),
+            )
+            .route(
+                "/search/templates/{name}/run",
+                post(rest_handlers::run_search_template),
+            )
             // Vector operations - batch
             .route("/batch_insert", post(rest_handlers::batch_insert_texts))
             .route("/insert_texts", post(rest_handlers::insert_texts))
@@ -843,6 +1092,10 @@ impl VectorizerServer {
                 "/qdrant/collections/{name}/shards/delete",
                 post(qdrant::sharding_handlers::delete_shard_key),
             )
+            .route(
+                "/qdrant/collections/{name}/cluster",
+                get(qdrant::sharding_handlers::get_collection_cluster_info),
+            )
             // Cluster API endpoints
             .route(
                 "/qdrant/cluster",
@@ -868,6 +1121,28 @@ impl VectorizerServer {
                 "/qdrant/cluster/metadata/keys/{key}",
                 put(qdrant::cluster_handlers::update_metadata_key),
             )
+            // Pinecone-compatible data plane; a Pinecone index is a collection
+            .route(
+                "/pinecone/collections/{name}/vectors/upsert",
+                post(rest_handlers::pinecone_upsert),
+            )
+            .route(
+                "/pinecone/collections/{name}/query",
+                post(rest_handlers::pinecone_query),
+            )
+            .route(
+                "/pinecone/collections/{name}/vectors/fetch",
+                get(rest_handlers::pinecone_fetch),
+            )
+            .route(
+                "/pinecone/collections/{name}/vectors/delete",
+                post(rest_handlers::pinecone_delete),
+            )
+            .route(
+                "/pinecone/collections/{name}/describe_index_stats",
+                get(rest_handlers::pinecone_describe_index_stats)
+                    .post(rest_handlers::pinecone_describe_index_stats),
+            )
             // Dashboard - serve embedded static files (production build)
             // Dashboard routes moved to `public_routes` so the SPA shell
             // stays reachable over anonymous HTTP when auth enforcement is
@@ -910,13 +1185,14 @@ impl VectorizerServer {
             .with_state(self.clone());
 
         // Add cluster routes if cluster is enabled
-        let rest_routes = if let (Some(cluster_mgr), Some(_client_pool)) = (
+        let rest_routes = if let (Some(cluster_mgr), Some(client_pool)) = (
             self.cluster_manager.as_ref(),
             self.cluster_client_pool.as_ref(),
         ) {
             let cluster_state = crate::api::cluster::ClusterApiState {
                 cluster_manager: cluster_mgr.clone(),
                 store: self.store.clone(),
+                client_pool: client_pool.clone(),
             };
             let cluster_router =
                 crate::api::cluster::create_cluster_router().with_state(cluster_state);
@@ -925,36 +1201,98 @@ impl VectorizerServer {
             rest_routes
         };
 
+        // Add bulk import (JSONL, or Parquet with the feature) and Parquet export routes
+        let rest_routes = rest_routes.merge(
+            Router::new()
+                .route(
+                    "/collections/{name}/import",
+                    post(rest_handlers::import_collection),
+                )
+                .with_state(self.clone()),
+        );
+        #[cfg(feature = "parquet")]
+        let rest_routes = rest_routes.merge(
+            Router::new()
+                .route(
+                    "/collections/{name}/export",
+                    post(rest_handlers::export_collection),
+                )
+                .with_state(self.clone()),
+        );
+
         // Add graph routes
         let graph_state = crate::api::graph::GraphApiState::new(self.store.clone());
         let graph_router = crate::api::graph::create_graph_router().with_state(graph_state);
         let rest_routes = rest_routes.merge(graph_router);
 
         // Add GraphQL routes
-        let graphql_schema = if let Some(ref auto_save) = self.auto_save_manager {
-            crate::api::graphql::create_schema_with_auto_save(
-                self.store.clone(),
-                self.embedding_manager.clone(),
-                self.start_time,
-                auto_save.clone(),
-            )
-        } else {
-            crate::api::graphql::create_schema(
-                self.store.clone(),
-                self.embedding_manager.clone(),
-                self.start_time,
-            )
-        };
+        let graphql_schema = crate::api::graphql::create_schema_with_progress(
+            self.store.clone(),
+            self.embedding_manager.clone(),
+            self.start_time,
+            self.auto_save_manager.clone(),
+            self.startup_progress.clone(),
+        );
         let graphql_state = graphql_handlers::GraphQLState {
             schema: graphql_schema,
+            payload_redactor: self.payload_redactor.clone(),
         };
         let graphql_router = Router::new()
             .route("/graphql", post(graphql_handlers::graphql_handler))
             .route("/graphql", get(graphql_handlers::graphql_playground))
             .route("/graphiql", get(graphql_handlers::graphql_playground))
+            .route("/graphql/ws", get(graphql_handlers::graphql_ws_handler))
             .with_state(graphql_state);
         let rest_routes = rest_routes.merge(graphql_router);
-        info!("📊 GraphQL API available at /graphql (playground at /graphiql)");
+        info!(
+            "📊 GraphQL API available at /graphql (playground at /graphiql, subscriptions at /graphql/ws)"
+        );
+
+        // Redact payload fields in read responses (`api.payload_redaction`).
+        // Layered before the auth gates below so it runs inside them and
+        // sees the caller's `AuthState`.
+        let rest_routes = if let Some(ref redactor) = self.payload_redactor {
+            rest_routes.layer(axum::middleware::from_fn_with_state(
+                (redactor.clone(), self.max_request_size_mb * 1024 * 1024),
+                payload_redaction_middleware,
+            ))
+        } else {
+            rest_routes
+        };
+
+        // Record mutating calls in the audit log (`audit_log`). Inside the
+        // auth and hub layers, like redaction, so the caller is known.
+        let rest_routes = if let Some(ref audit_log) = self.audit_log {
+            rest_routes.layer(axum::middleware::from_fn_with_state(
+                (audit_log.clone(), self.max_request_size_mb * 1024 * 1024),
+                mutation_audit_middleware,
+            ))
+        } else {
+            rest_routes
+        };
+
+        // Refuse calls naming collections outside the caller's API-key
+        // namespace. Inside the auth layers, like the two above; a no-op
+        // for callers without a namespace.
+        let rest_routes = rest_routes.layer(axum::middleware::from_fn_with_state(
+            self.max_request_size_mb * 1024 * 1024,
+            collection_namespace_middleware,
+        ));
+
+        // Refuse hub tenants' calls to tenant-partitioned collections on
+        // routes that do not enforce the partition. Inside the hub layer
+        // so the tenant is known; a no-op for callers without one.
+        let rest_routes = rest_routes.layer(axum::middleware::from_fn_with_state(
+            (self.store.clone(), self.max_request_size_mb * 1024 * 1024),
+            tenant_partition_middleware,
+        ));
+
+        // Refuse writes while draining (`POST /admin/drain`) and count
+        // the admitted ones so the drain can wait for them.
+        let rest_routes = rest_routes.layer(axum::middleware::from_fn_with_state(
+            (self.drain.clone(), self.max_request_size_mb * 1024 * 1024),
+            drain_middleware,
+        ));
 
         // Add auth routes and apply auth middleware if auth is enabled
         let rest_routes = if let Some(auth_state) = self.auth_handler_state.clone() {
@@ -967,6 +1305,10 @@ impl VectorizerServer {
                     "/auth/validate-password",
                     post(auth_handlers::validate_password_endpoint),
                 )
+                .route(
+                    "/.well-known/oauth-protected-resource",
+                    get(auth_handlers::protected_resource_metadata),
+                )
                 .with_state(auth_state.clone());
 
             // Protected + admin-gated auth routes. Every handler already takes
@@ -1023,13 +1365,18 @@ impl VectorizerServer {
             // families.
 
             info!(
-                "🔐 Auth buckets — public: /health, /prometheus/metrics, /auth/login, \
-                 /auth/validate-password. Authenticated (any logged-in user): /auth/me, \
+                "🔐 Auth buckets — public: /health, /health/live, /health/ready, \
+                 /prometheus/metrics, /auth/login, \
+                 /auth/validate-password, /.well-known/oauth-protected-resource. Authenticated (any logged-in user): /auth/me, \
                  /auth/logout, /auth/refresh, /auth/keys/*, all data-access routes. \
                  Admin (role=admin enforced inside handler): /auth/users*, \
                  /workspace/add, /workspace/remove, POST /workspace/config, \
-                 /setup/apply, /setup/browse, POST /config, /admin/restart, \
-                 /backups/create, /backups/restore."
+                 /setup/apply, /setup/browse, POST /config, /admin/config, /admin/restart, \
+                 /admin/drain, /admin/read_only, \
+                 /admin/embedding/providers/*/reload, /admin/federation/sources*, \
+                 /admin/search/templates/*, /admin/experiments/*, \
+                 /admin/tasks*, /admin/read_through, /admin/lazy_loading, /admin/backups/schedule*, \
+                 /changes/stream, /ws/changes, /backups/create, /backups/restore."
             );
 
             // Gate POST/PUT/DELETE/PATCH on `/auth/*` behind the CSRF
@@ -1093,6 +1440,7 @@ impl VectorizerServer {
             store: self.store.clone(),
             embedding_manager: self.embedding_manager.clone(),
             upsert_queue: self.upsert_queue.clone(),
+            hub_manager: self.hub_manager.clone(),
         };
 
         // Create UMICP routes (needs custom state)
@@ -1129,6 +1477,10 @@ impl VectorizerServer {
                 "/ws/dashboard",
                 get(crate::server::ws::dashboard_ws_handler),
             )
+            // Change feed of vector inserts, updates and deletes across
+            // every collection, so admin-only like the dashboard socket.
+            .route("/changes/stream", get(rest_handlers::stream_changes))
+            .route("/ws/changes", get(crate::server::ws::changes_ws_handler))
             .route("/workspace/add", post(rest_handlers::add_workspace))
             .route("/workspace/remove", post(rest_handlers::remove_workspace))
             .route(
@@ -1138,7 +1490,67 @@ impl VectorizerServer {
             .route("/setup/apply", post(setup_handlers::apply_setup_config))
             .route("/setup/browse", post(setup_handlers::browse_directory))
             .route("/config", post(rest_handlers::update_config))
+            .route(
+                "/admin/config",
+                get(rest_handlers::get_runtime_config).patch(rest_handlers::patch_runtime_config),
+            )
             .route("/admin/restart", post(rest_handlers::restart_server))
+            .route(
+                "/admin/drain",
+                get(rest_handlers::get_drain_status)
+                    .post(rest_handlers::drain_server)
+                    .delete(rest_handlers::resume_server),
+            )
+            .route(
+                "/admin/read_only",
+                get(rest_handlers::get_read_only_status).put(rest_handlers::set_read_only),
+            )
+            .route(
+                "/admin/embedding/providers/{name}/reload",
+                post(rest_handlers::reload_embedding_provider),
+            )
+            .route(
+                "/admin/federation/sources",
+                post(rest_handlers::register_federated_source),
+            )
+            .route(
+                "/admin/federation/sources/{name}",
+                delete(rest_handlers::remove_federated_source),
+            )
+            .route(
+                "/admin/search/templates/{name}",
+                put(rest_handlers::put_search_template)
+                    .delete(rest_handlers::delete_search_template),
+            )
+            .route(
+                "/admin/experiments/{name}",
+                put(rest_handlers::put_experiment).delete(rest_handlers::delete_experiment),
+            )
+            .route("/admin/tasks", get(rest_handlers::list_background_tasks))
+            .route(
+                "/admin/tasks/{name}/pause",
+                post(rest_handlers::pause_background_task),
+            )
+            .route(
+                "/admin/tasks/{name}/resume",
+                post(rest_handlers::resume_background_task),
+            )
+            .route(
+                "/admin/read_through",
+                get(rest_handlers::list_read_through_collections),
+            )
+            .route(
+                "/admin/lazy_loading",
+                get(rest_handlers::get_lazy_loading_status),
+            )
+            .route(
+                "/admin/backups/schedule",
+                get(rest_handlers::get_backup_schedule),
+            )
+            .route(
+                "/admin/backups/schedule/{job}/run",
+                post(rest_handlers::run_backup_job),
+            )
             .route("/backups/create", post(rest_handlers::create_backup))
             .route("/backups/restore", post(rest_handlers::restore_backup))
             .with_state(self.clone());
@@ -1203,12 +1615,16 @@ impl VectorizerServer {
                     // Public routes - no auth required
                     // NOTE: /mcp added to bypass auth for MCP access
                     if path == "/health"
+                        || path.starts_with("/health/")
                         || path == "/prometheus/metrics"
                         || path == "/auth/login"
                         || path == "/auth/validate-password"
+                        || path == "/.well-known/oauth-protected-resource"
                         || path == "/umicp/health"
                         || path == "/umicp/discover"
                         || path == "/mcp"
+                        || path == "/sse"
+                        || path == "/message"
                         || path.starts_with("/dashboard")
                         || path.starts_with("/setup")
                     {
@@ -1236,15 +1652,17 @@ impl VectorizerServer {
                         let mut user_claims = None;
 
                         // Try JWT first
-                        if let Some(token) = jwt_token {
-                            if let Ok(claims) = auth_manager.validate_jwt(&token) {
+                        if let Some(token) = &jwt_token {
+                            if let Ok(claims) = auth_manager.validate_jwt(token) {
                                 user_claims = Some(claims);
                             }
                         }
 
-                        // Try API key if JWT failed
+                        // Try API key if JWT failed. A bearer token that is
+                        // not a JWT is tried as an API key, which is how
+                        // OpenAI clients send theirs.
                         if user_claims.is_none() {
-                            if let Some(key) = api_key {
+                            if let Some(key) = api_key.or(jwt_token) {
                                 if let Ok(claims) = auth_manager.validate_api_key(&key).await {
                                     user_claims = Some(claims);
                                 }
@@ -1302,6 +1720,18 @@ impl VectorizerServer {
                 .layer(axum::middleware::from_fn(security_headers_middleware))
         };
 
+        // Verify HMAC request signatures when `api.request_signing` is
+        // enabled. Layered outside auth so tampered or stale requests are
+        // rejected before any credential lookup.
+        let app = if let Some(ref verifier) = self.request_verifier {
+            app.layer(axum::middleware::from_fn_with_state(
+                (verifier.clone(), self.max_request_size_mb * 1024 * 1024),
+                request_signing_middleware,
+            ))
+        } else {
+            app
+        };
+
         // Apply write-redirect middleware if this node is a replica
         // Replicas redirect POST/PUT/DELETE/PATCH to the leader with HTTP 307
         let app = if let Some(ref ha) = self.ha_manager {
@@ -1352,7 +1782,15 @@ impl VectorizerServer {
             app
         };
 
-        app
+        // Outermost: the request span wraps every other middleware, so
+        // auth, redirects and handlers all nest under it.
+        if vectorizer::monitoring::telemetry::is_enabled() {
+            app.layer(axum::middleware::from_fn(
+                vectorizer::monitoring::telemetry::trace_middleware,
+            ))
+        } else {
+            app
+        }
     }
 
     /// Create MCP router with StreamableHTTP transport (rmcp 0.8.1).
@@ -1368,27 +1806,35 @@ impl VectorizerServer {
     async fn create_mcp_router(
         &self,
         _is_production: bool,
-        _auth_state: Option<auth_handlers::AuthHandlerState>,
+        auth_state: Option<auth_handlers::AuthHandlerState>,
     ) -> Router {
         use hyper::service::Service;
         use hyper_util::service::TowerToHyperService;
         use rmcp::transport::streamable_http_server::StreamableHttpService;
         use rmcp::transport::streamable_http_server::session::local::LocalSessionManager;
 
-        // Create MCP service handler
-        let store = self.store.clone();
-        let embedding_manager = self.embedding_manager.clone();
-        let cluster_manager = self.cluster_manager.clone();
-        let upsert_queue = self.upsert_queue.clone();
+        // Create MCP service handler; each session starts from this one
+        let mcp_service = VectorizerMcpService {
+            store: self.store.clone(),
+            embedding_manager: self.embedding_manager.clone(),
+            cluster_manager: self.cluster_manager.clone(),
+            upsert_queue: self.upsert_queue.clone(),
+            hub_manager: self.hub_manager.clone(),
+            audit_log: self.audit_log.clone(),
+            drain: self.drain.clone(),
+            payload_redactor: self.payload_redactor.clone(),
+            resource_subscriptions: Default::default(),
+            session_extensions: None,
+        };
+        let auth_manager = auth_state.map(|state| state.auth_manager.clone());
+        let sse_router = super::mcp_sse::sse_router(mcp_service.clone(), auth_manager.clone());
 
         // Create StreamableHTTP service
         let streamable_service = StreamableHttpService::new(
             move || {
                 Ok(VectorizerMcpService {
-                    store: store.clone(),
-                    embedding_manager: embedding_manager.clone(),
-                    cluster_manager: cluster_manager.clone(),
-                    upsert_queue: upsert_queue.clone(),
+                    resource_subscriptions: Default::default(),
+                    ..mcp_service.clone()
                 })
             },
             LocalSessionManager::default().into(),
@@ -1398,11 +1844,23 @@ impl VectorizerServer {
         // Convert to axum service and create router
         let hyper_service = TowerToHyperService::new(streamable_service);
 
-        Router::new().route(
+        // `/mcp` (StreamableHTTP) next to the legacy SSE transport's
+        // `/sse` + `/message`
+        sse_router.route(
             "/mcp",
-            axum::routing::any(move |req: axum::extract::Request| {
+            axum::routing::any(move |mut req: axum::extract::Request| {
                 let mut service = hyper_service.clone();
+                let auth_manager = auth_manager.clone();
                 async move {
+                    // MCP stays open, but a caller presenting credentials is
+                    // identified so its API-key namespace applies to tool calls
+                    let credentials = extract_auth_credentials(&req);
+                    if let Some(auth_manager) = auth_manager
+                        && let Some(auth) = identify_mcp_caller(credentials, &auth_manager).await
+                    {
+                        req.extensions_mut().insert(auth);
+                    }
+
                     // Forward request to hyper service
                     match service.call(req).await {
                         Ok(response) => {
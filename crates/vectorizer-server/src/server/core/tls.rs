@@ -0,0 +1,136 @@
+//! TLS termination for the HTTP and gRPC listeners (`tls:` in config.yml).
+//!
+//! Handshakes run on their own tasks so a slow or stalled client cannot
+//! hold up the accept loop; finished streams reach axum through
+//! [`TlsListener`] and tonic through [`TlsListener::into_stream`]. Every
+//! handshake takes the current [`ReloadableTls`] config, so
+//! [`spawn_reload_on_sighup`] swaps certificates without dropping
+//! established connections.
+
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio_rustls::TlsAcceptor;
+use tokio_rustls::server::TlsStream;
+use tokio_stream::Stream;
+use tokio_stream::StreamExt;
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::{debug, error, info, warn};
+use vectorizer::security::tls::ReloadableTls;
+
+/// Completed handshakes waiting to be picked up by the server.
+const ACCEPT_BACKLOG: usize = 128;
+
+/// Time a client gets to finish the TLS handshake.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A TCP listener that hands out TLS streams.
+pub(super) struct TlsListener {
+    local_addr: SocketAddr,
+    accepted: mpsc::Receiver<(TlsStream<TcpStream>, SocketAddr)>,
+}
+
+impl TlsListener {
+    /// Start accepting on `listener`, terminating TLS with `tls`.
+    pub(super) fn new(listener: TcpListener, tls: Arc<ReloadableTls>) -> io::Result<Self> {
+        let local_addr = listener.local_addr()?;
+        let (tx, accepted) = mpsc::channel(ACCEPT_BACKLOG);
+        tokio::spawn(accept_loop(listener, tls, tx));
+        Ok(Self {
+            local_addr,
+            accepted,
+        })
+    }
+
+    /// Accepted streams as tonic's `serve_with_incoming` expects them.
+    pub(super) fn into_stream(self) -> impl Stream<Item = io::Result<TlsStream<TcpStream>>> {
+        ReceiverStream::new(self.accepted).map(|(stream, _)| Ok(stream))
+    }
+}
+
+impl axum::serve::Listener for TlsListener {
+    type Io = TlsStream<TcpStream>;
+    type Addr = SocketAddr;
+
+    async fn accept(&mut self) -> (Self::Io, Self::Addr) {
+        match self.accepted.recv().await {
+            Some(connection) => connection,
+            // The accept loop only stops once this listener is dropped
+            None => std::future::pending().await,
+        }
+    }
+
+    fn local_addr(&self) -> io::Result<Self::Addr> {
+        Ok(self.local_addr)
+    }
+}
+
+async fn accept_loop(
+    listener: TcpListener,
+    tls: Arc<ReloadableTls>,
+    tx: mpsc::Sender<(TlsStream<TcpStream>, SocketAddr)>,
+) {
+    loop {
+        let (tcp, peer) = match listener.accept().await {
+            Ok(connection) => connection,
+            Err(e) => {
+                // Usually fd exhaustion; back off instead of spinning
+                warn!("⚠️ TLS listener accept failed: {}", e);
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                continue;
+            }
+        };
+        if tx.is_closed() {
+            break;
+        }
+
+        let acceptor = TlsAcceptor::from(tls.server_config());
+        let tx = tx.clone();
+        tokio::spawn(async move {
+            match tokio::time::timeout(HANDSHAKE_TIMEOUT, acceptor.accept(tcp)).await {
+                Ok(Ok(stream)) => {
+                    let _ = tx.send((stream, peer)).await;
+                }
+                Ok(Err(e)) => debug!("TLS handshake with {} failed: {}", peer, e),
+                Err(_) => debug!("TLS handshake with {} timed out", peer),
+            }
+        });
+    }
+}
+
+/// Reload the certificates on `SIGHUP`. A failed reload keeps serving
+/// the current ones.
+pub(super) fn spawn_reload_on_sighup(tls: Arc<ReloadableTls>) {
+    #[cfg(unix)]
+    tokio::spawn(async move {
+        use tokio::signal::unix::{SignalKind, signal};
+
+        let mut hangup = match signal(SignalKind::hangup()) {
+            Ok(hangup) => hangup,
+            Err(e) => {
+                warn!(
+                    "⚠️ Cannot install SIGHUP handler, TLS reload disabled: {}",
+                    e
+                );
+                return;
+            }
+        };
+        while hangup.recv().await.is_some() {
+            info!("🔁 Received SIGHUP, reloading TLS certificates");
+            if let Err(e) = tls.reload() {
+                error!(
+                    "❌ TLS reload failed, keeping current certificates: {:#}",
+                    e
+                );
+            }
+        }
+    });
+
+    // No SIGHUP outside Unix; certificates load once at startup
+    #[cfg(not(unix))]
+    drop(tls);
+}
@@ -16,7 +16,7 @@ use std::sync::Arc;
 
 use tracing::{debug, info, warn};
 use vectorizer::VectorStore;
-use vectorizer::db::BackpressureGuard;
+use vectorizer::db::{BackpressureGuard, LoadSource, StartupProgress};
 use vectorizer::embedding::EmbeddingManager;
 
 /// Load file watcher configuration from workspace.yml
@@ -44,14 +44,17 @@ pub(super) async fn load_file_watcher_config()
 ///
 /// Returns the number of collections indexed / loaded. Collections
 /// that already exist in memory are skipped; collections present in
-/// the `.vecdb` archive are force-loaded with their HNSW index;
+/// the `.vecdb` archive are force-loaded with their HNSW index, unless
+/// lazy loading leaves them on disk;
 /// everything else is indexed from project files via
-/// [`vectorizer::file_loader::FileLoader`].
+/// [`vectorizer::file_loader::FileLoader`]. Each collection's state is
+/// recorded in `progress`.
 pub(super) async fn load_workspace_collections(
     store: &Arc<VectorStore>,
     embedding_manager: &Arc<EmbeddingManager>,
     mut cancel_rx: tokio::sync::watch::Receiver<bool>,
     backpressure: Option<BackpressureGuard>,
+    progress: &StartupProgress,
 ) -> anyhow::Result<usize> {
     use std::path::Path;
 
@@ -85,6 +88,17 @@ pub(super) async fn load_workspace_collections(
         workspace_manager.config().projects.len()
     );
 
+    for project in workspace_manager.enabled_projects() {
+        for collection in &project.collections {
+            // Collections lazy loading left on disk stay deferred
+            if !store.has_collection_in_memory(&collection.name)
+                && !store.is_evicted(&collection.name)
+            {
+                progress.pending(&collection.name, LoadSource::Workspace, None);
+            }
+        }
+    }
+
     let mut indexed_count = 0;
 
     // Check if using .vecdb format - if so, verify collections in archive first
@@ -142,8 +156,18 @@ pub(super) async fn load_workspace_collections(
 
             // Check if collection already exists in .vecdb archive
             if using_vecdb && existing_in_vecdb.contains(&collection.name) {
+                // With lazy loading the collection stays in .vecdb until
+                // first accessed
+                if store.is_evicted(&collection.name) {
+                    info!(
+                        "💤 Collection '{}' left in .vecdb until first access",
+                        collection.name
+                    );
+                    continue;
+                }
                 // Collection exists in .vecdb - FORCE LOAD it into memory (same as when no cache)
                 if !store.has_collection_in_memory(&collection.name) {
+                    progress.loading(&collection.name, LoadSource::Workspace);
                     info!(
                         "📥 FORCE LOADING collection '{}' from .vecdb into memory...",
                         collection.name
@@ -268,6 +292,7 @@ pub(super) async fn load_workspace_collections(
                                                     indexed_count += 1;
                                                 }
                                             }
+                                            report_vecdb_load(store, progress, &collection.name);
                                             continue;
                                         }
 
@@ -338,6 +363,7 @@ pub(super) async fn load_workspace_collections(
                             );
                         }
                     }
+                    report_vecdb_load(store, progress, &collection.name);
                 } else {
                     info!(
                         "✅ Collection '{}' already in memory, skipping",
@@ -361,9 +387,11 @@ pub(super) async fn load_workspace_collections(
                 Ok(path) => path,
                 Err(e) => {
                     warn!("Failed to get project path for '{}': {}", project.name, e);
+                    progress.failed(&collection.name, LoadSource::Workspace, e.to_string());
                     continue;
                 }
             };
+            progress.loading(&collection.name, LoadSource::Workspace);
 
             // Use FileLoader to index files
             let mut loader_config = LoaderConfig {
@@ -389,6 +417,12 @@ pub(super) async fn load_workspace_collections(
 
             let mut loader =
                 FileLoader::with_embedding_manager(loader_config, coll_embedding_manager);
+            if let Some(strategy) = collection.processing.chunking {
+                loader = loader.with_chunking(strategy);
+            }
+            if collection.processing.language_detection {
+                loader = loader.with_language_detection();
+            }
             // Issue #263: gate the BM25 vocab-build behind a shared
             // semaphore so concurrent collection loads can't saturate
             // the host. `backpressure` is None when bootstrap couldn't
@@ -402,6 +436,11 @@ pub(super) async fn load_workspace_collections(
                 .await
             {
                 Ok(file_count) => {
+                    let vectors = store
+                        .get_collection(&collection.name)
+                        .map(|c| c.vector_count())
+                        .unwrap_or(0);
+                    progress.indexed(&collection.name, LoadSource::Workspace, vectors);
                     if file_count > 0 {
                         info!(
                             "Indexed {} vectors for collection '{}'",
@@ -417,6 +456,7 @@ pub(super) async fn load_workspace_collections(
                 }
                 Err(e) => {
                     warn!("Failed to index collection '{}': {}", collection.name, e);
+                    progress.failed(&collection.name, LoadSource::Workspace, e.to_string());
                 }
             }
         }
@@ -429,3 +469,22 @@ pub(super) async fn load_workspace_collections(
 
     Ok(indexed_count)
 }
+
+/// Record the outcome of force-loading a workspace collection from the
+/// `.vecdb` archive: indexed if it made it into memory, failed otherwise.
+/// The reason for a failure is in the warnings logged above.
+fn report_vecdb_load(store: &VectorStore, progress: &StartupProgress, name: &str) {
+    if store.has_collection_in_memory(name) {
+        let vectors = store
+            .get_collection(name)
+            .map(|c| c.vector_count())
+            .unwrap_or(0);
+        progress.indexed(name, LoadSource::Workspace, vectors);
+    } else {
+        progress.failed(
+            name,
+            LoadSource::Workspace,
+            "could not be loaded from .vecdb",
+        );
+    }
+}
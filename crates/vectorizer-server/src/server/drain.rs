@@ -0,0 +1,199 @@
+//! Drain mode for rolling restarts (`POST /admin/drain`).
+//!
+//! While the server drains, new writes are refused on every transport
+//! (503 over REST, `UNAVAILABLE` over gRPC, an error result over MCP)
+//! and `/health` reports the node as not ready so load balancers move
+//! traffic elsewhere. Reads keep being served. Each admitted write holds
+//! a [`WriteGuard`]; [`DrainState::wait_idle`] resolves once the last
+//! one is dropped, after which the caller can save and stop safely.
+//!
+//! Read-only mode (`PUT /admin/read_only`) goes through the same gate
+//! for maintenance windows — migrations, compaction, storage failover:
+//! writes are refused the same way, but the node stays ready and keeps
+//! serving searches until the mode is switched off again.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::Duration;
+
+use parking_lot::RwLock;
+use tokio::sync::Notify;
+
+/// Drain and read-only flags plus the number of writes currently
+/// running.
+#[derive(Debug, Default)]
+pub struct DrainState {
+    draining: AtomicBool,
+    read_only: AtomicBool,
+    read_only_reason: RwLock<Option<String>>,
+    in_flight: AtomicUsize,
+    idle: Notify,
+}
+
+/// Why a write was refused. Draining wins when both modes are on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WriteRefusal {
+    /// The server drains ahead of a restart.
+    Draining,
+    /// The server is in read-only mode for maintenance.
+    ReadOnly,
+}
+
+impl WriteRefusal {
+    /// Machine-readable error code.
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::Draining => "draining",
+            Self::ReadOnly => "read_only",
+        }
+    }
+
+    /// Message returned to clients.
+    pub fn message(self) -> &'static str {
+        match self {
+            Self::Draining => "server is draining for a restart and accepts no new writes",
+            Self::ReadOnly => "server is in read-only mode for maintenance and accepts no writes",
+        }
+    }
+}
+
+impl DrainState {
+    /// Whether new writes are being refused.
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::Acquire)
+    }
+
+    /// Start refusing new writes. Returns `false` if already draining.
+    pub fn begin(&self) -> bool {
+        !self.draining.swap(true, Ordering::AcqRel)
+    }
+
+    /// Accept writes again. Returns `false` if the server was not draining.
+    pub fn resume(&self) -> bool {
+        self.draining.swap(false, Ordering::AcqRel)
+    }
+
+    /// Whether read-only mode is on.
+    pub fn is_read_only(&self) -> bool {
+        self.read_only.load(Ordering::Acquire)
+    }
+
+    /// Why read-only mode was switched on, if a reason was given.
+    pub fn read_only_reason(&self) -> Option<String> {
+        self.read_only_reason.read().clone()
+    }
+
+    /// Switch read-only mode on (with an optional reason) or off.
+    /// Returns the previous setting.
+    pub fn set_read_only(&self, enabled: bool, reason: Option<String>) -> bool {
+        let mut current_reason = self.read_only_reason.write();
+        *current_reason = if enabled { reason } else { None };
+        self.read_only.swap(enabled, Ordering::AcqRel)
+    }
+
+    /// Why new writes are refused right now, if they are.
+    pub fn refusal(&self) -> Option<WriteRefusal> {
+        if self.is_draining() {
+            Some(WriteRefusal::Draining)
+        } else if self.is_read_only() {
+            Some(WriteRefusal::ReadOnly)
+        } else {
+            None
+        }
+    }
+
+    /// Writes admitted and not yet finished.
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::Acquire)
+    }
+
+    /// Admit one write, or say why it is refused while draining or
+    /// read-only. Hold the guard until the write has finished.
+    pub fn try_enter(self: &Arc<Self>) -> Result<WriteGuard, WriteRefusal> {
+        // Count first so a drain that starts between the check and the
+        // increment still waits for this write
+        self.in_flight.fetch_add(1, Ordering::AcqRel);
+        let guard = WriteGuard(Arc::clone(self));
+        match self.refusal() {
+            Some(refusal) => Err(refusal),
+            None => Ok(guard),
+        }
+    }
+
+    /// Wait until no write is in flight. Returns `false` if `timeout`
+    /// passed first.
+    pub async fn wait_idle(&self, timeout: Duration) -> bool {
+        tokio::time::timeout(timeout, async {
+            loop {
+                let notified = self.idle.notified();
+                tokio::pin!(notified);
+                notified.as_mut().enable();
+                if self.in_flight() == 0 {
+                    return;
+                }
+                notified.await;
+            }
+        })
+        .await
+        .is_ok()
+    }
+}
+
+/// An admitted write. Dropping it wakes [`DrainState::wait_idle`] when
+/// it was the last one.
+#[derive(Debug)]
+pub struct WriteGuard(Arc<DrainState>);
+
+impl Drop for WriteGuard {
+    fn drop(&mut self) {
+        if self.0.in_flight.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.0.idle.notify_waiters();
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn drain_refuses_new_writes_and_waits_for_running_ones() {
+        let drain = Arc::new(DrainState::default());
+        let running = drain.try_enter().expect("admitted before drain");
+
+        assert!(drain.begin());
+        assert!(!drain.begin());
+        assert_eq!(drain.try_enter().err(), Some(WriteRefusal::Draining));
+        assert_eq!(drain.in_flight(), 1);
+        assert!(!drain.wait_idle(Duration::from_millis(20)).await);
+
+        let waiter = {
+            let drain = Arc::clone(&drain);
+            tokio::spawn(async move { drain.wait_idle(Duration::from_secs(5)).await })
+        };
+        drop(running);
+        assert!(waiter.await.expect("waiter task"));
+
+        assert!(drain.resume());
+        assert!(drain.try_enter().is_ok());
+    }
+
+    #[test]
+    fn read_only_refuses_writes_until_switched_off() {
+        let drain = Arc::new(DrainState::default());
+
+        assert!(!drain.set_read_only(true, Some("reindex".into())));
+        assert_eq!(drain.try_enter().err(), Some(WriteRefusal::ReadOnly));
+        assert_eq!(drain.read_only_reason().as_deref(), Some("reindex"));
+        assert_eq!(drain.in_flight(), 0);
+
+        drain.begin();
+        assert_eq!(drain.refusal(), Some(WriteRefusal::Draining));
+        drain.resume();
+
+        assert!(drain.set_read_only(false, None));
+        assert_eq!(drain.read_only_reason(), None);
+        assert!(drain.try_enter().is_ok());
+    }
+}
@@ -8,6 +8,8 @@ use serde_json::{Value, json};
 use tracing::error;
 use vectorizer_core::error::VectorizerError;
 
+use crate::server::drain::WriteRefusal;
+
 /// Standard error response format
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ErrorResponse {
@@ -85,6 +87,28 @@ impl From<VectorizerError> for ErrorResponse {
                 "limit_type": limit_type,
                 "limit": limit
             })),
+            VectorizerError::TenantQuotaExceeded {
+                tenant_id,
+                quota,
+                limit,
+                used,
+                requested,
+            } => Some(json!({
+                "tenant_id": tenant_id,
+                "quota": quota,
+                "limit": limit,
+                "used": used,
+                "requested": requested,
+            })),
+            VectorizerError::VersionConflict {
+                key,
+                expected,
+                actual,
+            } => Some(json!({
+                "key": key,
+                "expected_version": expected,
+                "actual_version": actual,
+            })),
             VectorizerError::CollectionNotFound(name) => Some(json!({
                 "collection_name": name
             })),
@@ -255,6 +279,46 @@ pub fn create_queue_full_error(
     .with_retry_after(retry_after_seconds)
 }
 
+/// Build a 503 Service Unavailable for a write refused while the server
+/// drains (`POST /admin/drain`). Clients should retry against another
+/// node; `Retry-After` covers clients talking to this one directly.
+pub fn create_draining_error() -> ErrorResponse {
+    ErrorResponse::new(
+        "draining".to_string(),
+        WriteRefusal::Draining.message().to_string(),
+        StatusCode::SERVICE_UNAVAILABLE,
+    )
+    .with_retry_after(DRAINING_RETRY_AFTER_SECONDS)
+}
+
+/// `Retry-After` sent with [`create_draining_error`].
+const DRAINING_RETRY_AFTER_SECONDS: u32 = 5;
+
+/// Build a 503 Service Unavailable for a write refused while the server
+/// is in read-only mode (`PUT /admin/read_only`). Searches still work.
+pub fn create_read_only_error() -> ErrorResponse {
+    let refusal = WriteRefusal::ReadOnly;
+    ErrorResponse::new(
+        refusal.code().to_string(),
+        refusal.message().to_string(),
+        StatusCode::SERVICE_UNAVAILABLE,
+    )
+    .with_retry_after(READ_ONLY_RETRY_AFTER_SECONDS)
+}
+
+/// `Retry-After` sent with [`create_read_only_error`]; maintenance
+/// windows outlast a drain.
+const READ_ONLY_RETRY_AFTER_SECONDS: u32 = 30;
+
+impl From<WriteRefusal> for ErrorResponse {
+    fn from(refusal: WriteRefusal) -> Self {
+        match refusal {
+            WriteRefusal::Draining => create_draining_error(),
+            WriteRefusal::ReadOnly => create_read_only_error(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
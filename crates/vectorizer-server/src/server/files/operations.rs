@@ -4,7 +4,7 @@ use rmcp::model::{CallToolRequestParams, CallToolResult, ContentBlock, ErrorData
 use serde_json::json;
 use vectorizer::VectorStore;
 use vectorizer::embedding::EmbeddingManager;
-use vectorizer::file_operations::FileOperations;
+use vectorizer::file_operations::{FileOperations, SummaryType};
 
 pub async fn handle_get_file_chunks_ordered(
     request: CallToolRequestParams,
@@ -98,6 +98,57 @@ pub async fn handle_get_project_outline(
     )]))
 }
 
+pub async fn handle_get_file_summary(
+    request: CallToolRequestParams,
+    store: Arc<VectorStore>,
+) -> Result<CallToolResult, ErrorData> {
+    let args = request
+        .arguments
+        .as_ref()
+        .ok_or_else(|| ErrorData::invalid_params("Missing arguments", None))?;
+
+    let collection = args
+        .get("collection")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| ErrorData::invalid_params("Missing collection", None))?;
+
+    let file_path = args
+        .get("file_path")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| ErrorData::invalid_params("Missing file_path", None))?;
+
+    let summary_type = match args.get("summary_type").and_then(|v| v.as_str()) {
+        None | Some("both") => SummaryType::Both,
+        Some("extractive") => SummaryType::Extractive,
+        Some("structural") => SummaryType::Structural,
+        Some(other) => {
+            return Err(ErrorData::invalid_params(
+                format!("Invalid summary_type '{}'", other),
+                None,
+            ));
+        }
+    };
+
+    let max_sentences = args
+        .get("max_sentences")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(5) as usize;
+
+    // Call actual implementation
+    let file_ops = FileOperations::with_store(store);
+    let result = file_ops
+        .get_file_summary(collection, file_path, summary_type, max_sentences)
+        .await
+        .map_err(|e| {
+            ErrorData::internal_error(format!("Failed to get file summary: {}", e), None)
+        })?;
+
+    let response = json!(result);
+    Ok(CallToolResult::success(vec![ContentBlock::text(
+        response.to_string(),
+    )]))
+}
+
 pub async fn handle_get_related_files(
     request: CallToolRequestParams,
     store: Arc<VectorStore>,
@@ -118,7 +169,12 @@ pub async fn handle_get_related_files(
         .and_then(|v| v.as_str())
         .ok_or_else(|| ErrorData::invalid_params("Missing file_path", None))?;
 
-    let limit = args.get("limit").and_then(|v| v.as_u64()).unwrap_or(5) as usize;
+    // The tool schema names this `max_results`; `limit` is still accepted
+    let limit = args
+        .get("max_results")
+        .or_else(|| args.get("limit"))
+        .and_then(|v| v.as_u64())
+        .unwrap_or(10) as usize;
 
     let similarity_threshold = args
         .get("similarity_threshold")
@@ -29,6 +29,7 @@ use crate::server::VectorizerServer;
 use crate::server::error_middleware::{
     ErrorResponse, create_bad_request_error, create_not_found_error,
 };
+use crate::server::rest_handlers::check_insert_quota;
 
 /// Request for file upload with metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -316,6 +317,13 @@ pub async fn upload_file(
             sharding: None,
             graph: None,
             encryption: None,
+            id_policy: None,
+            quota: None,
+            scoring: None,
+            tenant_partition: None,
+            dedup: None,
+            chunking: None,
+            full_text: None,
         };
 
         state
@@ -423,10 +431,21 @@ pub async fn upload_file(
         }
     }
 
-    // Create chunks using the file loader chunker
+    // Create chunks using the file loader chunker, with the collection's
+    // chunking settings filling in what the request leaves out
+    let chunking = state
+        .store
+        .get_collection(&collection_name)
+        .ok()
+        .and_then(|c| c.config().chunking.clone())
+        .unwrap_or_default();
     let loader_config = LoaderConfig {
-        max_chunk_size: chunk_size.unwrap_or(upload_config.default_chunk_size),
-        chunk_overlap: chunk_overlap.unwrap_or(upload_config.default_chunk_overlap),
+        max_chunk_size: chunk_size
+            .or(chunking.max_chunk_size)
+            .unwrap_or(upload_config.default_chunk_size),
+        chunk_overlap: chunk_overlap
+            .or(chunking.chunk_overlap)
+            .unwrap_or(upload_config.default_chunk_overlap),
         include_patterns: vec![],
         exclude_patterns: vec![],
         embedding_dimension: 512,
@@ -435,7 +454,7 @@ pub async fn upload_file(
         max_file_size: upload_config.max_file_size,
     };
 
-    let chunker = Chunker::new(loader_config);
+    let chunker = Chunker::with_strategy(loader_config, chunking.strategy);
 
     let chunks = chunker
         .chunk_text(&content_to_chunk, &file_path)
@@ -469,12 +488,20 @@ pub async fn upload_file(
         }));
     }
 
+    let _tenant_admission = check_insert_quota(
+        &state,
+        tenant_ctx.as_ref(),
+        &collection_name,
+        chunks_created,
+    )
+    .await?;
+
     // Create embeddings and store vectors
     let mut vectors_created = 0;
 
     for chunk in &chunks {
         // Create embedding using the embedding manager
-        let embedding = match state.embedding_manager.embed(&chunk.content) {
+        let embedding = match state.embed_for_collection(&collection_name, &chunk.content) {
             Ok(emb) => emb,
             Err(e) => {
                 warn!("Failed to embed chunk: {}", e);
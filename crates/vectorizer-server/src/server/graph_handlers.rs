@@ -590,6 +590,8 @@ fn parse_relationship_type(s: &str) -> Option<RelationshipType> {
         "REFERENCES" => Some(RelationshipType::References),
         "CONTAINS" => Some(RelationshipType::Contains),
         "DERIVED_FROM" | "DERIVEDFROM" => Some(RelationshipType::DerivedFrom),
+        "MENTIONS" => Some(RelationshipType::Mentions),
+        "RELATED_TO" | "RELATEDTO" => Some(RelationshipType::RelatedTo),
         _ => None,
     }
 }
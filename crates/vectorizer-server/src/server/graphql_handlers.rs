@@ -1,22 +1,30 @@
 //! GraphQL HTTP handlers for Axum integration
 //!
-//! This module provides HTTP handlers for the GraphQL API.
+//! This module provides HTTP and WebSocket handlers for the GraphQL API.
 
 use std::sync::Arc;
 
-use async_graphql::http::GraphiQLSource;
-use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
-use axum::extract::State;
+use async_graphql::Data;
+use async_graphql::http::{ALL_WEBSOCKET_PROTOCOLS, GraphiQLSource};
+use async_graphql_axum::{GraphQLProtocol, GraphQLRequest, GraphQLResponse, GraphQLWebSocket};
+use axum::Extension;
+use axum::extract::{State, WebSocketUpgrade};
 use axum::http::HeaderMap;
-use axum::response::{Html, IntoResponse};
+use axum::response::{Html, IntoResponse, Response};
+use vectorizer::auth::middleware::AuthState;
 use vectorizer::hub::auth::TenantContext;
+use vectorizer::security::PayloadRedactor;
 
 use crate::api::graphql::VectorizerSchema;
+use crate::server::core::{caller_payload_view, refuse_namespaced};
 
 /// GraphQL state containing the schema
 #[derive(Clone)]
 pub struct GraphQLState {
     pub schema: VectorizerSchema,
+    /// `api.payload_redaction`; resolvers apply it per caller through the
+    /// `PayloadView` each request carries
+    pub payload_redactor: Option<Arc<PayloadRedactor>>,
 }
 
 /// Handle GraphQL queries/mutations
@@ -24,22 +32,62 @@ pub struct GraphQLState {
 /// Supports multi-tenant authentication via headers:
 /// - x-hivehub-service: Service API key (bypasses user auth)
 /// - x-hivehub-user-id: User/tenant ID for context
+///
+/// API keys confined to a collection namespace are refused (403): the
+/// collections a document reads are not known until its resolvers run.
 pub async fn graphql_handler(
     State(state): State<GraphQLState>,
+    auth: Option<Extension<AuthState>>,
     headers: HeaderMap,
     req: GraphQLRequest,
-) -> GraphQLResponse {
+) -> Response {
+    if let Err(refusal) = refuse_namespaced(auth.as_deref()) {
+        return refusal.into_response();
+    }
     // Extract tenant context from headers (if present)
     let tenant_context = extract_tenant_context(&headers);
 
-    // Build request with tenant context
-    let mut graphql_req = req.into_inner();
+    // Build request with tenant context and the caller's redaction view
+    let mut graphql_req = req
+        .into_inner()
+        .data(caller_payload_view(state.payload_redactor, auth.as_deref()));
 
     if let Some(ctx) = tenant_context {
         graphql_req = graphql_req.data(ctx);
     }
 
-    state.schema.execute(graphql_req).await.into()
+    GraphQLResponse::from(state.schema.execute(graphql_req).await).into_response()
+}
+
+/// Serve GraphQL subscriptions over WebSocket (`graphql-transport-ws`
+/// and the legacy `graphql-ws` protocol)
+///
+/// The tenant context comes from the upgrade request's headers and the
+/// redaction view from its caller, and namespaced API keys are refused,
+/// as for [`graphql_handler`].
+pub async fn graphql_ws_handler(
+    State(state): State<GraphQLState>,
+    auth: Option<Extension<AuthState>>,
+    headers: HeaderMap,
+    protocol: GraphQLProtocol,
+    upgrade: WebSocketUpgrade,
+) -> Response {
+    if let Err(refusal) = refuse_namespaced(auth.as_deref()) {
+        return refusal.into_response();
+    }
+    let mut data = Data::default();
+    data.insert(caller_payload_view(state.payload_redactor, auth.as_deref()));
+    if let Some(ctx) = extract_tenant_context(&headers) {
+        data.insert(ctx);
+    }
+
+    upgrade
+        .protocols(ALL_WEBSOCKET_PROTOCOLS)
+        .on_upgrade(move |stream| {
+            GraphQLWebSocket::new(stream, state.schema, protocol)
+                .with_data(data)
+                .serve()
+        })
 }
 
 /// Extract tenant context from request headers
@@ -79,6 +127,7 @@ pub async fn graphql_playground() -> impl IntoResponse {
     Html(
         GraphiQLSource::build()
             .endpoint("/graphql")
+            .subscription_endpoint("/graphql/ws")
             .title("Vectorizer GraphQL Playground")
             .finish(),
     )
@@ -12,6 +12,8 @@ use vectorizer::discovery::{
 };
 use vectorizer::embedding::EmbeddingManager;
 use vectorizer::file_operations::{FileListFilter, FileOperations, SortBy, SummaryType};
+use vectorizer::hub::{HubManager, WriteAdmission};
+use vectorizer::intelligent_search::graph_expansion::GraphExpansion;
 use vectorizer::intelligent_search::mcp_tools::*;
 use vectorizer::models::SparseVector;
 use vectorizer::{VectorStore, VectorizerError};
@@ -31,12 +33,31 @@ use crate::server::graph_handlers::*;
 /// [`VectorizerError::code`] identifier travels in `data.code` so MCP
 /// clients get the same machine-readable signal REST's `error_type`
 /// field carries.
-fn to_mcp_error(err: VectorizerError) -> ErrorData {
+pub(crate) fn to_mcp_error(err: VectorizerError) -> ErrorData {
     let code = mapping::mcp_code(&err);
     let data = json!({ "code": err.code() });
     ErrorData::new(ErrorCode(code), err.to_string(), Some(data))
 }
 
+/// Admit inserting `vectors` vectors into `collection` against the
+/// tenant quotas of its owner; see
+/// [`vectorizer::hub::HubManager::admit_insert`]. Hold the admission
+/// until the vectors are inserted.
+async fn admit_insert(
+    hub_manager: Option<&HubManager>,
+    store: &VectorStore,
+    collection: &str,
+    vectors: usize,
+) -> Result<WriteAdmission, ErrorData> {
+    let Some(hub_manager) = hub_manager else {
+        return Ok(WriteAdmission::default());
+    };
+    hub_manager
+        .admit_insert(store, None, collection, vectors)
+        .await
+        .map_err(to_mcp_error)
+}
+
 /// Same idea as [`to_mcp_error`] but for
 /// [`vectorizer::file_operations::FileOperationError`] — a distinct
 /// error type from [`VectorizerError`] predating the centralized error
@@ -165,6 +186,7 @@ pub async fn handle_mcp_tool(
     embedding_manager: Arc<EmbeddingManager>,
     cluster_manager: Option<Arc<vectorizer::cluster::ClusterManager>>,
     upsert_queue: Arc<vectorizer::db::UpsertQueue>,
+    hub_manager: Option<Arc<HubManager>>,
 ) -> Result<CallToolResult, ErrorData> {
     match request.name.as_ref() {
         // Core Collection/Vector Operations
@@ -172,7 +194,9 @@ pub async fn handle_mcp_tool(
         "list_providers" => handle_list_providers(embedding_manager).await,
         "create_collection" => handle_create_collection(request, store).await,
         "get_collection_info" => handle_get_collection_info(request, store).await,
-        "insert_text" => handle_insert_text(request, store, embedding_manager, upsert_queue).await,
+        "insert_text" => {
+            handle_insert_text(request, store, embedding_manager, upsert_queue, hub_manager).await
+        }
         "get_vector" => handle_get_vector(request, store).await,
         "update_vector" => handle_update_vector(request, store, embedding_manager).await,
         "delete_vector" => handle_delete_vectors(request, store).await,
@@ -197,6 +221,13 @@ pub async fn handle_mcp_tool(
         "get_file_chunks" => handle_get_file_chunks_ordered(request, store).await,
         "get_project_outline" => handle_get_project_outline(request, store).await,
         "get_related_files" => handle_get_related_files(request, store, embedding_manager).await,
+        "get_file_summary" => handle_get_file_summary(request, store).await,
+        "search_by_file_type" => {
+            handle_search_by_file_type(request, store, embedding_manager).await
+        }
+
+        // Workspace
+        "list_workspaces" => handle_list_workspaces().await,
 
         // Graph Operations
         "graph_list_nodes" => handle_graph_list_nodes(request, store).await,
@@ -236,7 +267,8 @@ pub async fn handle_mcp_tool(
 
         // phase40 §2.2: batch operations mirroring REST /batch_* routes
         "batch_insert_texts" => {
-            handle_batch_insert_texts(request, store, embedding_manager, upsert_queue).await
+            handle_batch_insert_texts(request, store, embedding_manager, upsert_queue, hub_manager)
+                .await
         }
         "batch_search" => handle_batch_search(request, store, embedding_manager).await,
         "batch_update" => handle_batch_update(request, store).await,
@@ -397,6 +429,10 @@ async fn handle_create_collection(
                 Some(vectorizer::models::GraphConfig {
                     enabled: true,
                     auto_relationship: vectorizer::models::AutoRelationshipConfig::default(),
+                    entity_extraction: g
+                        .get("entity_extraction")
+                        .and_then(|e| serde_json::from_value(e.clone()).ok())
+                        .unwrap_or_default(),
                 })
             } else {
                 None
@@ -422,6 +458,13 @@ async fn handle_create_collection(
         graph: graph_config,
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     store
@@ -501,6 +544,7 @@ async fn handle_insert_text(
     store: Arc<VectorStore>,
     embedding_manager: Arc<EmbeddingManager>,
     upsert_queue: Arc<vectorizer::db::UpsertQueue>,
+    hub_manager: Option<Arc<HubManager>>,
 ) -> Result<CallToolResult, ErrorData> {
     let args = request
         .arguments
@@ -541,13 +585,16 @@ async fn handle_insert_text(
         }
     };
 
+    let _tenant_admission =
+        admit_insert(hub_manager.as_deref(), &store, collection_name, 1).await?;
+
     let metadata = args.get("metadata").cloned();
     let public_key = args.get("public_key").and_then(|v| v.as_str());
 
     // Generate embedding
     let embedding = embedding_manager.embed(text).map_err(to_mcp_error)?;
 
-    let vector_id = uuid::Uuid::new_v4().to_string();
+    let vector_id = store.new_vector_id(collection_name);
 
     let payload_json = if let Some(meta) = metadata {
         meta
@@ -758,6 +805,23 @@ async fn handle_intelligent_search(
         .and_then(|v| v.as_bool())
         .unwrap_or(true);
 
+    let translate_to = args
+        .get("translate_to")
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str())
+                .map(|s| s.to_string())
+                .collect::<Vec<_>>()
+        });
+
+    let graph_expansion = args
+        .get("graph_expansion")
+        .map(GraphExpansion::from_request)
+        .transpose()
+        .map_err(|e| ErrorData::invalid_params(format!("Invalid graph_expansion: {}", e), None))?
+        .flatten();
+
     let tool = IntelligentSearchTool {
         query: query.to_string(),
         collections,
@@ -766,6 +830,8 @@ async fn handle_intelligent_search(
         technical_focus: Some(true),
         mmr_enabled: Some(false), // Disabled for MCP
         mmr_lambda: Some(0.7),
+        translate_to,
+        graph_expansion,
     };
 
     // Create handler with collection-specific embedding managers
@@ -816,12 +882,36 @@ async fn handle_multi_collection_search(
         .and_then(|v| v.as_u64())
         .unwrap_or(20) as usize;
 
+    let score_normalization = args
+        .get("score_normalization")
+        .map(|v| {
+            v.as_str()
+                .and_then(vectorizer::search::ScoreNormalization::parse)
+                .ok_or_else(|| {
+                    ErrorData::invalid_params(
+                        "score_normalization must be one of: none, min_max, z_score, rrf",
+                        None,
+                    )
+                })
+        })
+        .transpose()?;
+
+    let collection_weights = args
+        .get("collection_weights")
+        .map(|v| serde_json::from_value(v.clone()))
+        .transpose()
+        .map_err(|e| {
+            ErrorData::invalid_params(format!("Invalid collection_weights: {}", e), None)
+        })?;
+
     let tool = MultiCollectionSearchTool {
         query: query.to_string(),
         collections,
         max_per_collection: Some(max_per_collection),
         max_total_results: Some(max_total_results),
         cross_collection_reranking: Some(false), // Disabled for MCP
+        score_normalization,
+        collection_weights,
     };
 
     let handler = MCPToolHandler::new(store.clone(), embedding_manager.clone());
@@ -1011,6 +1101,8 @@ async fn handle_search_extra(
                     technical_focus: Some(true),
                     mmr_enabled: Some(false),
                     mmr_lambda: Some(0.7),
+                    translate_to: None,
+                    graph_expansion: None,
                 };
                 let handler = MCPToolHandler::new_with_store(store.clone());
                 let response = handler.handle_intelligent_search(tool).await.map_err(|e| {
@@ -1268,17 +1360,24 @@ async fn handle_hybrid_search(
         None
     };
 
-    // Parse hybrid search configuration
-    let alpha = args.get("alpha").and_then(|v| v.as_f64()).unwrap_or(0.7) as f32;
-    let algorithm_str = args
-        .get("algorithm")
-        .and_then(|v| v.as_str())
-        .unwrap_or("rrf");
+    // Parse hybrid search configuration; weights tuned from relevance
+    // judgements stand in for the defaults when neither alpha nor
+    // algorithm is given
+    let alpha = args.get("alpha").and_then(|v| v.as_f64()).map(|a| a as f32);
+    let algorithm_str = args.get("algorithm").and_then(|v| v.as_str());
+    let tuned = if alpha.is_none() && algorithm_str.is_none() {
+        collection.tuned_hybrid_weights()
+    } else {
+        None
+    };
+    let alpha = alpha.or(tuned.as_ref().map(|w| w.alpha)).unwrap_or(0.7);
     let algorithm = match algorithm_str {
-        "rrf" => HybridScoringAlgorithm::ReciprocalRankFusion,
-        "weighted" => HybridScoringAlgorithm::WeightedCombination,
-        "alpha" => HybridScoringAlgorithm::AlphaBlending,
-        _ => HybridScoringAlgorithm::ReciprocalRankFusion,
+        Some("weighted") => HybridScoringAlgorithm::WeightedCombination,
+        Some("alpha") => HybridScoringAlgorithm::AlphaBlending,
+        Some(_) => HybridScoringAlgorithm::ReciprocalRankFusion,
+        None => tuned.map_or(HybridScoringAlgorithm::ReciprocalRankFusion, |w| {
+            w.algorithm
+        }),
     };
     let dense_k = args.get("dense_k").and_then(|v| v.as_u64()).unwrap_or(20) as usize;
     let sparse_k = args.get("sparse_k").and_then(|v| v.as_u64()).unwrap_or(20) as usize;
@@ -1306,7 +1405,7 @@ async fn handle_hybrid_search(
         "total": results.len(),
         "config": {
             "alpha": alpha,
-            "algorithm": algorithm_str,
+            "algorithm": algorithm,
             "dense_k": dense_k,
             "sparse_k": sparse_k,
             "final_k": final_k
@@ -1318,6 +1417,37 @@ async fn handle_hybrid_search(
     )]))
 }
 
+// =============================================
+// Workspace Handlers
+// =============================================
+
+async fn handle_list_workspaces() -> Result<CallToolResult, ErrorData> {
+    let workspace_manager = vectorizer::config::WorkspaceManager::new();
+    let workspaces: Vec<serde_json::Value> = workspace_manager
+        .list_workspaces()
+        .iter()
+        .map(|w| {
+            json!({
+                "id": w.id,
+                "path": w.path,
+                "collection_name": w.collection_name,
+                "active": w.active,
+                "file_count": w.file_count,
+                "created_at": w.created_at.to_rfc3339(),
+                "updated_at": w.updated_at.to_rfc3339(),
+                "last_indexed": w.last_indexed.map(|t| t.to_rfc3339()),
+                "exists": w.exists()
+            })
+        })
+        .collect();
+
+    let response = json!({ "workspaces": workspaces });
+
+    Ok(CallToolResult::success(vec![ContentBlock::text(
+        response.to_string(),
+    )]))
+}
+
 // =============================================
 // Collection Maintenance Handlers
 // =============================================
@@ -1635,6 +1765,7 @@ async fn handle_batch_insert_texts(
     store: Arc<VectorStore>,
     embedding_manager: Arc<EmbeddingManager>,
     upsert_queue: Arc<vectorizer::db::UpsertQueue>,
+    hub_manager: Option<Arc<HubManager>>,
 ) -> Result<CallToolResult, ErrorData> {
     let args = request
         .arguments
@@ -1683,6 +1814,9 @@ async fn handle_batch_insert_texts(
         }
     };
 
+    let _tenant_admission =
+        admit_insert(hub_manager.as_deref(), &store, collection_name, texts.len()).await?;
+
     let mut inserted: usize = 0;
     let mut failed: usize = 0;
     let mut results: Vec<serde_json::Value> = Vec::with_capacity(texts.len());
@@ -1719,7 +1853,7 @@ async fn handle_batch_insert_texts(
 
         let vector_id = client_id
             .clone()
-            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+            .unwrap_or_else(|| store.new_vector_id(collection_name));
         let payload_json = metadata.unwrap_or_else(|| json!({}));
 
         let payload = if let Some(key) = public_key {
@@ -1784,7 +1918,7 @@ async fn handle_batch_insert_texts(
 }
 
 /// Mirrors `POST /batch_search`
-/// (`rest_handlers::search::batch_search_vectors`): each entry may
+/// (`rest_handlers::batch_search_vectors`): each entry may
 /// carry a text `query` (embedded server-side) or a raw `vector`.
 /// `limit` is clamped to the same 100-result ceiling the `search`
 /// tool's schema declares. Per-query failures are captured without
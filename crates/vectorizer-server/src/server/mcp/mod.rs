@@ -4,6 +4,10 @@
 //!   vector-store or cluster operation (handle_mcp_tool)
 //! - [`tools`] — the catalog of MCP tools exposed to clients
 //!   (get_mcp_tools)
+//! - [`prompts`] — retrieval-augmented prompt templates that inline
+//!   intelligent-search evidence
+//! - [`resources`] — the `vectorizer://collections` and
+//!   `vectorizer://stats` resources and their subscriptions
 //! - [`connection_manager`] / [`performance`] — carried over from the
 //!   previous flat layout; kept behind `#[allow(dead_code)]` until the
 //!   next consumer wires them back in
@@ -22,4 +26,6 @@ pub mod connection_manager;
 pub mod handlers;
 #[allow(dead_code)]
 pub mod performance;
+pub mod prompts;
+pub mod resources;
 pub mod tools;
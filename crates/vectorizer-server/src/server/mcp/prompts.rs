@@ -0,0 +1,273 @@
+//! MCP prompts — retrieval-augmented prompt templates.
+//!
+//! Each prompt runs an intelligent search for its question or topic and
+//! inlines the hits as numbered evidence, so an MCP host gets a prompt
+//! that is ready to send to its model without calling any tool first.
+//!
+//! - `answer_from_collection` — answer a question from the evidence,
+//!   citing it as `[n]`
+//! - `summarize_topic` — summarize what the evidence says about a topic
+//!
+//! Prompt arguments are strings, per the MCP spec. `collection` may be
+//! left out to search every collection the caller can read.
+
+use std::sync::Arc;
+
+use rmcp::model::{ErrorData, GetPromptResult, Prompt, PromptArgument, PromptMessage, Role};
+use serde_json::{Map, Value};
+use vectorizer::VectorStore;
+use vectorizer::auth::CollectionNamespace;
+use vectorizer::intelligent_search::IntelligentSearchResult;
+use vectorizer::intelligent_search::mcp_tools::{IntelligentSearchTool, MCPToolHandler};
+
+use super::handlers::to_mcp_error;
+
+/// Prompt answering a question from retrieved evidence.
+pub const ANSWER_FROM_COLLECTION: &str = "answer_from_collection";
+
+/// Prompt summarizing retrieved evidence about a topic.
+pub const SUMMARIZE_TOPIC: &str = "summarize_topic";
+
+/// Evidence passages inlined when `max_results` is not given.
+pub const DEFAULT_EVIDENCE_RESULTS: usize = 5;
+
+/// Most evidence passages a prompt inlines.
+pub const MAX_EVIDENCE_RESULTS: usize = 20;
+
+/// Longest passage inlined, in characters; longer ones are cut.
+pub const MAX_EVIDENCE_CHARS: usize = 2_000;
+
+fn argument(name: &str, description: &str, required: bool) -> PromptArgument {
+    let mut argument = PromptArgument::new(name);
+    argument.description = Some(description.to_string());
+    argument.required = Some(required);
+    argument
+}
+
+/// The prompts every session can render.
+pub fn list_prompts() -> Vec<Prompt> {
+    let collection = || {
+        argument(
+            "collection",
+            "Collection to retrieve evidence from (all readable collections when omitted)",
+            false,
+        )
+    };
+    let max_results = || {
+        argument(
+            "max_results",
+            "Number of evidence passages to inline (default 5, at most 20)",
+            false,
+        )
+    };
+    vec![
+        Prompt::new(
+            ANSWER_FROM_COLLECTION,
+            Some("Answer a question from passages retrieved by intelligent search, citing them."),
+            Some(vec![
+                argument("question", "Question to answer", true),
+                collection(),
+                max_results(),
+            ]),
+        ),
+        Prompt::new(
+            SUMMARIZE_TOPIC,
+            Some("Summarize what the indexed passages retrieved for a topic say about it."),
+            Some(vec![
+                argument("topic", "Topic to summarize", true),
+                collection(),
+                max_results(),
+            ]),
+        ),
+    ]
+}
+
+/// Render prompt `name` with `arguments`, confining the search to
+/// `namespace` when the caller is confined to one.
+pub async fn get_prompt(
+    store: Arc<VectorStore>,
+    name: &str,
+    arguments: Option<&Map<String, Value>>,
+    namespace: Option<&CollectionNamespace>,
+) -> Result<GetPromptResult, ErrorData> {
+    let (query_arg, instructions) = match name {
+        ANSWER_FROM_COLLECTION => (
+            "question",
+            "Answer the question using only the context below. Cite the passages you use as [n]. \
+             If the context does not contain the answer, say so instead of guessing.",
+        ),
+        SUMMARIZE_TOPIC => (
+            "topic",
+            "Summarize what the context below says about the topic. Cite the passages you use \
+             as [n] and point out where they disagree.",
+        ),
+        _ => {
+            return Err(ErrorData::invalid_params(
+                format!("unknown prompt '{}'", name),
+                None,
+            ));
+        }
+    };
+    let text_arg = |key: &str| {
+        arguments
+            .and_then(|args| args.get(key))
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+    };
+
+    let query = text_arg(query_arg)
+        .ok_or_else(|| ErrorData::invalid_params(format!("Missing {}", query_arg), None))?;
+    let max_results = match text_arg("max_results") {
+        Some(raw) => raw
+            .parse::<usize>()
+            .ok()
+            .filter(|n| (1..=MAX_EVIDENCE_RESULTS).contains(n))
+            .ok_or_else(|| {
+                ErrorData::invalid_params(
+                    format!("max_results must be between 1 and {}", MAX_EVIDENCE_RESULTS),
+                    None,
+                )
+            })?,
+        None => DEFAULT_EVIDENCE_RESULTS,
+    };
+    let collections = match (text_arg("collection"), namespace) {
+        (Some(collection), Some(namespace)) => {
+            namespace.check(collection).map_err(to_mcp_error)?;
+            Some(vec![collection.to_string()])
+        }
+        (Some(collection), None) => Some(vec![collection.to_string()]),
+        (None, Some(namespace)) => {
+            let mut names = store.list_collections();
+            names.retain(|name| namespace.contains(name));
+            Some(names)
+        }
+        (None, None) => None,
+    };
+
+    let evidence = if collections.as_ref().is_some_and(|c| c.is_empty()) {
+        Vec::new()
+    } else {
+        let tool = IntelligentSearchTool {
+            query: query.to_string(),
+            collections,
+            max_results: Some(max_results),
+            domain_expansion: Some(true),
+            technical_focus: Some(true),
+            mmr_enabled: Some(true),
+            mmr_lambda: Some(0.7),
+            translate_to: None,
+            graph_expansion: None,
+        };
+        MCPToolHandler::new_with_store(store)
+            .handle_intelligent_search(tool)
+            .await
+            .map_err(|e| {
+                ErrorData::internal_error(format!("Intelligent search failed: {}", e), None)
+            })?
+            .results
+    };
+
+    let label = if query_arg == "question" {
+        "Question"
+    } else {
+        "Topic"
+    };
+    let text = render(instructions, label, query, &evidence);
+    let mut result = GetPromptResult::new(vec![PromptMessage::new_text(Role::User, text)]);
+    result.description = Some(format!(
+        "{} with {} retrieved passage(s)",
+        name,
+        evidence.len()
+    ));
+    Ok(result)
+}
+
+/// Where a passage came from: its file path when indexed from a file,
+/// its vector ID otherwise.
+fn source_of(result: &IntelligentSearchResult) -> &str {
+    result
+        .metadata
+        .get("file_path")
+        .and_then(|v| v.as_str())
+        .unwrap_or(&result.doc_id)
+}
+
+fn render(
+    instructions: &str,
+    label: &str,
+    query: &str,
+    evidence: &[IntelligentSearchResult],
+) -> String {
+    let mut text = format!("{}\n\n{}: {}\n\nContext:\n", instructions, label, query);
+    if evidence.is_empty() {
+        text.push_str("(no passages matched)\n");
+    }
+    for (i, result) in evidence.iter().enumerate() {
+        let content: String = result.content.chars().take(MAX_EVIDENCE_CHARS).collect();
+        text.push_str(&format!(
+            "\n[{}] {} / {} (score {:.3})\n{}\n",
+            i + 1,
+            result.collection,
+            source_of(result),
+            result.score,
+            content.trim()
+        ));
+    }
+    text
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn passage(doc_id: &str, content: &str, file_path: Option<&str>) -> IntelligentSearchResult {
+        let mut metadata = HashMap::new();
+        if let Some(path) = file_path {
+            metadata.insert("file_path".to_string(), Value::from(path));
+        }
+        IntelligentSearchResult {
+            content: content.to_string(),
+            score: 0.5,
+            collection: "docs".to_string(),
+            doc_id: doc_id.to_string(),
+            metadata,
+            score_breakdown: None,
+        }
+    }
+
+    #[test]
+    fn render_numbers_and_cites_the_evidence() {
+        let evidence = vec![
+            passage("v1", "Rust is fast.", Some("README.md")),
+            passage("v2", &"x".repeat(MAX_EVIDENCE_CHARS + 10), None),
+        ];
+        let text = render("Answer.", "Question", "why rust?", &evidence);
+
+        assert!(text.starts_with("Answer.\n\nQuestion: why rust?"));
+        assert!(text.contains("[1] docs / README.md (score 0.500)\nRust is fast."));
+        assert!(text.contains("[2] docs / v2"));
+        assert!(!text.contains(&"x".repeat(MAX_EVIDENCE_CHARS + 1)));
+    }
+
+    #[test]
+    fn render_says_when_nothing_matched() {
+        let text = render("Answer.", "Topic", "nothing", &[]);
+        assert!(text.contains("(no passages matched)"));
+    }
+
+    #[tokio::test]
+    async fn unknown_prompt_and_missing_question_are_rejected() {
+        let store = Arc::new(VectorStore::new());
+        assert!(get_prompt(store.clone(), "nope", None, None).await.is_err());
+        assert!(
+            get_prompt(store, ANSWER_FROM_COLLECTION, None, None)
+                .await
+                .is_err()
+        );
+        assert_eq!(list_prompts().len(), 2);
+    }
+}
@@ -0,0 +1,270 @@
+//! MCP resources — read-only JSON views of the store.
+//!
+//! - `vectorizer://collections` — every collection with its vector
+//!   count, dimension, metric and embedding provider
+//! - `vectorizer://stats` — collection and vector totals plus the
+//!   server version
+//!
+//! A client that subscribes to a resource gets `notifications/resources/updated`
+//! whenever it changes. The store has no event for collection
+//! create / delete / resize that covers every transport, so each session
+//! with subscriptions polls a fingerprint of the store every
+//! [`RESOURCE_POLL_INTERVAL`] and notifies on a difference. The watcher
+//! stops when the last subscription goes away or the session closes.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use rmcp::RoleServer;
+use rmcp::model::{
+    ErrorData, ReadResourceResult, Resource, ResourceContents, ResourceUpdatedNotificationParam,
+};
+use rmcp::service::Peer;
+use serde_json::{Value, json};
+use tokio::task::JoinHandle;
+use vectorizer::VectorStore;
+use vectorizer::auth::CollectionNamespace;
+
+/// URI of the collection list resource.
+pub const COLLECTIONS_URI: &str = "vectorizer://collections";
+
+/// URI of the store statistics resource.
+pub const STATS_URI: &str = "vectorizer://stats";
+
+/// How often a session with subscriptions checks the store for changes.
+pub const RESOURCE_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// The resources every session can read.
+pub fn list_resources() -> Vec<Resource> {
+    let resource = |uri: &str, name: &str, description: &str| {
+        Resource::new(uri, name)
+            .with_description(description)
+            .with_mime_type("application/json")
+    };
+    vec![
+        resource(
+            COLLECTIONS_URI,
+            "collections",
+            "Collections with their vector count, dimension, metric and embedding provider.",
+        ),
+        resource(
+            STATS_URI,
+            "stats",
+            "Collection and vector totals and the server version.",
+        ),
+    ]
+}
+
+/// Read `uri`, listing only the collections in `namespace` when the
+/// caller is confined to one.
+pub fn read_resource(
+    store: &VectorStore,
+    uri: &str,
+    namespace: Option<&CollectionNamespace>,
+) -> Result<ReadResourceResult, ErrorData> {
+    let body = match uri {
+        COLLECTIONS_URI => collections_json(store, namespace),
+        STATS_URI => stats_json(store, namespace),
+        _ => return Err(unknown_resource(uri)),
+    };
+    Ok(ReadResourceResult::new(vec![ResourceContents::text(
+        body.to_string(),
+        uri,
+    )]))
+}
+
+/// `resource_not_found` error for a URI this server does not expose.
+pub fn unknown_resource(uri: &str) -> ErrorData {
+    ErrorData::resource_not_found(
+        format!("unknown resource '{}'", uri),
+        Some(json!({ "uri": uri })),
+    )
+}
+
+/// Whether `uri` names one of [`list_resources`].
+pub fn is_known_resource(uri: &str) -> bool {
+    uri == COLLECTIONS_URI || uri == STATS_URI
+}
+
+fn visible_collections(
+    store: &VectorStore,
+    namespace: Option<&CollectionNamespace>,
+) -> Vec<String> {
+    let mut names = store.list_collections();
+    if let Some(namespace) = namespace {
+        names.retain(|name| namespace.contains(name));
+    }
+    names.sort();
+    names
+}
+
+fn collections_json(store: &VectorStore, namespace: Option<&CollectionNamespace>) -> Value {
+    let collections: Vec<Value> = visible_collections(store, namespace)
+        .into_iter()
+        .filter_map(|name| {
+            let collection = store.get_collection(&name).ok()?;
+            let config = collection.config();
+            Some(json!({
+                "name": name,
+                "vector_count": collection.vector_count(),
+                "dimension": config.dimension,
+                "metric": config.metric,
+                "embedding_provider": config.embedding_provider,
+            }))
+        })
+        .collect();
+    json!({
+        "collections": collections,
+        "total": collections.len(),
+    })
+}
+
+fn stats_json(store: &VectorStore, namespace: Option<&CollectionNamespace>) -> Value {
+    let counts = fingerprint(store, namespace);
+    json!({
+        "collections": counts.len(),
+        "total_vectors": counts.iter().map(|(_, count)| count).sum::<usize>(),
+        "version": env!("CARGO_PKG_VERSION"),
+    })
+}
+
+/// Name and vector count of every visible collection — enough to tell
+/// that either resource changed.
+fn fingerprint(
+    store: &VectorStore,
+    namespace: Option<&CollectionNamespace>,
+) -> Vec<(String, usize)> {
+    visible_collections(store, namespace)
+        .into_iter()
+        .filter_map(|name| {
+            let count = store.get_collection(&name).ok()?.vector_count();
+            Some((name, count))
+        })
+        .collect()
+}
+
+/// Resource subscriptions of one MCP session.
+#[derive(Default)]
+pub struct ResourceSubscriptions {
+    uris: Arc<Mutex<HashSet<String>>>,
+    watcher: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl ResourceSubscriptions {
+    /// Subscribe to `uri`, starting the session's watcher if it is not
+    /// running yet.
+    pub fn subscribe(
+        &self,
+        uri: &str,
+        store: Arc<VectorStore>,
+        namespace: Option<CollectionNamespace>,
+        peer: Peer<RoleServer>,
+    ) -> Result<(), ErrorData> {
+        if !is_known_resource(uri) {
+            return Err(unknown_resource(uri));
+        }
+        self.uris.lock().insert(uri.to_string());
+
+        let mut watcher = self.watcher.lock();
+        if watcher.as_ref().is_none_or(|task| task.is_finished()) {
+            *watcher = Some(tokio::spawn(watch(
+                self.uris.clone(),
+                store,
+                namespace,
+                peer,
+            )));
+        }
+        Ok(())
+    }
+
+    /// Drop the subscription to `uri`; the watcher stops with the last one.
+    pub fn unsubscribe(&self, uri: &str) {
+        let mut uris = self.uris.lock();
+        uris.remove(uri);
+        if uris.is_empty()
+            && let Some(task) = self.watcher.lock().take()
+        {
+            task.abort();
+        }
+    }
+}
+
+impl Drop for ResourceSubscriptions {
+    fn drop(&mut self) {
+        if let Some(task) = self.watcher.get_mut().take() {
+            task.abort();
+        }
+    }
+}
+
+/// Poll the store and notify `peer` of every subscribed URI when it
+/// changes. Returns when the session can no longer be reached.
+async fn watch(
+    uris: Arc<Mutex<HashSet<String>>>,
+    store: Arc<VectorStore>,
+    namespace: Option<CollectionNamespace>,
+    peer: Peer<RoleServer>,
+) {
+    let mut last = fingerprint(&store, namespace.as_ref());
+    let mut interval = tokio::time::interval(RESOURCE_POLL_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    loop {
+        interval.tick().await;
+        let current = fingerprint(&store, namespace.as_ref());
+        if current == last {
+            continue;
+        }
+        last = current;
+
+        let subscribed: Vec<String> = uris.lock().iter().cloned().collect();
+        for uri in subscribed {
+            let notification = ResourceUpdatedNotificationParam::new(uri);
+            if let Err(e) = peer.notify_resource_updated(notification).await {
+                tracing::debug!("MCP resource watcher stopping: {}", e);
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use vectorizer::models::CollectionConfig;
+
+    use super::*;
+
+    fn read_json(store: &VectorStore, uri: &str) -> Value {
+        let result = read_resource(store, uri, None).unwrap();
+        let ResourceContents::TextResourceContents { text, .. } = &result.contents[0] else {
+            panic!("expected text contents");
+        };
+        serde_json::from_str(text).unwrap()
+    }
+
+    #[test]
+    fn collections_and_stats_reflect_the_store() {
+        let store = VectorStore::new();
+        store
+            .create_collection("docs", CollectionConfig::default())
+            .unwrap();
+
+        let collections = read_json(&store, COLLECTIONS_URI);
+        assert_eq!(collections["total"], 1);
+        assert_eq!(collections["collections"][0]["name"], "docs");
+        assert_eq!(collections["collections"][0]["vector_count"], 0);
+
+        let stats = read_json(&store, STATS_URI);
+        assert_eq!(stats["collections"], 1);
+        assert_eq!(stats["total_vectors"], 0);
+    }
+
+    #[test]
+    fn unknown_uri_is_rejected() {
+        let store = VectorStore::new();
+        assert!(read_resource(&store, "vectorizer://nope", None).is_err());
+        assert_eq!(list_resources().len(), 2);
+    }
+}
@@ -260,6 +260,16 @@ pub fn get_mcp_tools() -> Vec<Tool> {
                         "description": "Total maximum results",
                         "default": 20
                     },
+                    "score_normalization": {
+                        "type": "string",
+                        "enum": ["none", "min_max", "z_score", "rrf"],
+                        "description": "Rescale each collection's scores before merging, so collections with different metrics or models compete fairly (rrf = reciprocal rank fusion). Raw scores when omitted"
+                    },
+                    "collection_weights": {
+                        "type": "object",
+                        "additionalProperties": {"type": "number"},
+                        "description": "Score multiplier per collection name (default 1.0)"
+                    },
                     "similarity_threshold": {
                         "type": "number",
                         "description": "Minimum similarity score 0.0-1.0",
@@ -334,6 +344,25 @@ pub fn get_mcp_tools() -> Vec<Tool> {
                         "description": "Enable domain-specific query expansion",
                         "default": true
                     },
+                    "translate_to": {
+                        "type": "array",
+                        "items": {"type": "string"},
+                        "description": "Language codes (e.g. [\"pt\"]) to also search the query translated into, for documents in other languages"
+                    },
+                    "graph_expansion": {
+                        "type": "object",
+                        "description": "Expand the top hits through the collection graph before reranking (GraphRAG)",
+                        "properties": {
+                            "hops": {"type": "integer", "description": "Hops to walk from each hit (1-3)", "default": 1},
+                            "edge_types": {
+                                "type": "array",
+                                "items": {"type": "string"},
+                                "description": "Edge types to follow: SIMILAR_TO, REFERENCES, CONTAINS, DERIVED_FROM, MENTIONS, RELATED_TO, SAME_FILE (default: all)"
+                            },
+                            "max_neighbors": {"type": "integer", "description": "Most neighbors added per hit", "default": 5},
+                            "score_decay": {"type": "number", "description": "Score multiplier per hop", "default": 0.85}
+                        }
+                    },
                     "similarity_threshold": {
                         "type": "number",
                         "description": "Minimum similarity score 0.0-1.0",
@@ -448,7 +477,7 @@ pub fn get_mcp_tools() -> Vec<Tool> {
                     },
                     "alpha": {
                         "type": "number",
-                        "description": "Weight for dense search (0.0 = pure sparse, 1.0 = pure dense)",
+                        "description": "Weight for dense search (0.0 = pure sparse, 1.0 = pure dense). Without alpha and algorithm, the collection's tuned hybrid weights are used if it has any",
                         "default": 0.7,
                         "minimum": 0.0,
                         "maximum": 1.0
@@ -548,7 +577,7 @@ pub fn get_mcp_tools() -> Vec<Tool> {
             ToolAnnotations::new().read_only(true).idempotent(true),
         ),
         // =============================================
-        // File Operations (5 tools)
+        // File Operations (7 tools)
         // =============================================
 
         // 15. Get File Content
@@ -726,6 +755,92 @@ pub fn get_mcp_tools() -> Vec<Tool> {
             }),
             ToolAnnotations::new().read_only(true).idempotent(true),
         ),
+        // 20. Get File Summary
+        mk_tool(
+            "get_file_summary",
+            "Get File Summary",
+            "Generate an extractive and/or structural summary of an indexed file.",
+            json!({
+                "type": "object",
+                "properties": {
+                    "collection": {
+                        "type": "string",
+                        "description": "Collection name"
+                    },
+                    "file_path": {
+                        "type": "string",
+                        "description": "File path"
+                    },
+                    "summary_type": {
+                        "type": "string",
+                        "description": "Kind of summary to generate",
+                        "enum": ["extractive", "structural", "both"],
+                        "default": "both"
+                    },
+                    "max_sentences": {
+                        "type": "integer",
+                        "description": "Maximum sentences in the extractive summary",
+                        "default": 5,
+                        "minimum": 1,
+                        "maximum": 20
+                    }
+                },
+                "required": ["collection", "file_path"]
+            }),
+            ToolAnnotations::new().read_only(true).idempotent(true),
+        ),
+        // 21. Search By File Type
+        mk_tool(
+            "search_by_file_type",
+            "Search By File Type",
+            "Semantic search restricted to files with the given extensions.",
+            json!({
+                "type": "object",
+                "properties": {
+                    "collection": {
+                        "type": "string",
+                        "description": "Collection name"
+                    },
+                    "query": {
+                        "type": "string",
+                        "description": "Search query"
+                    },
+                    "file_types": {
+                        "type": "array",
+                        "items": {"type": "string"},
+                        "description": "File extensions to include (e.g. [\"rs\", \"md\"])"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Maximum number of results",
+                        "default": 10
+                    },
+                    "return_full_files": {
+                        "type": "boolean",
+                        "description": "Return whole files instead of matching chunks",
+                        "default": false
+                    }
+                },
+                "required": ["collection", "query", "file_types"]
+            }),
+            ToolAnnotations::new().read_only(true).idempotent(true),
+        ),
+        // =============================================
+        // Workspace (1 tool)
+        // =============================================
+
+        // List Workspaces
+        mk_tool(
+            "list_workspaces",
+            "List Workspaces",
+            "List the registered workspaces with their path, collection and indexing status. Mirrors GET /workspace/list.",
+            json!({
+                "type": "object",
+                "properties": {},
+                "required": []
+            }),
+            ToolAnnotations::new().read_only(true).idempotent(true),
+        ),
         // =============================================
         // Graph Operations (6 tools)
         // =============================================
@@ -791,8 +906,8 @@ pub fn get_mcp_tools() -> Vec<Tool> {
                     },
                     "relationship_type": {
                         "type": "string",
-                        "description": "Filter by relationship type (SIMILAR_TO, REFERENCES, CONTAINS, DERIVED_FROM)",
-                        "enum": ["SIMILAR_TO", "REFERENCES", "CONTAINS", "DERIVED_FROM"]
+                        "description": "Filter by relationship type (SIMILAR_TO, REFERENCES, CONTAINS, DERIVED_FROM, MENTIONS, RELATED_TO)",
+                        "enum": ["SIMILAR_TO", "REFERENCES", "CONTAINS", "DERIVED_FROM", "MENTIONS", "RELATED_TO"]
                     }
                 },
                 "required": ["collection", "node_id"]
@@ -847,7 +962,7 @@ pub fn get_mcp_tools() -> Vec<Tool> {
                     "relationship_type": {
                         "type": "string",
                         "description": "Type of relationship",
-                        "enum": ["SIMILAR_TO", "REFERENCES", "CONTAINS", "DERIVED_FROM"]
+                        "enum": ["SIMILAR_TO", "REFERENCES", "CONTAINS", "DERIVED_FROM", "MENTIONS", "RELATED_TO"]
                     },
                     "weight": {
                         "type": "number",
@@ -15,7 +15,8 @@
 //! - [`files`]          — file-operation REST handlers + upload
 //! - [`graph_handlers`], [`graphql_handlers`], [`replication_handlers`],
 //!   [`discovery_handlers`], [`setup_handlers`], [`error_middleware`],
-//!   [`embedded_assets`] — each a single-concern file at this level
+//!   [`embedded_assets`], [`drain`] — each a single-concern file at this
+//!   level
 //!
 //! Downstream callers still see everything at its historic
 //! `crate::server::X` path thanks to the `pub use` aliases below.
@@ -35,8 +36,10 @@ use std::sync::Arc;
 
 mod auth_handlers;
 pub mod capabilities;
+pub mod conformance;
 mod core;
 mod discovery_handlers;
+pub mod drain;
 mod embedded_assets;
 pub mod error_middleware;
 pub mod files;
@@ -46,13 +49,14 @@ mod hub_handlers;
 pub mod mcp;
 pub mod metrics_middleware;
 mod qdrant;
+pub mod readiness;
 pub mod replication_handlers;
 pub mod rest_handlers;
 pub mod runtime_metrics;
 mod setup_handlers;
 pub mod ws;
 
-pub use core::get_file_watcher_metrics;
+pub use core::{get_file_watcher_metrics, serve_mcp_stdio};
 
 pub use auth_handlers::csrf::require_csrf_middleware;
 pub use auth_handlers::{
@@ -86,6 +90,9 @@ pub struct ServerState {
 pub struct VectorizerServer {
     pub store: Arc<VectorStore>,
     pub embedding_manager: Arc<EmbeddingManager>,
+    /// The file watcher's own embedding manager, kept so a provider
+    /// reload swaps the model there too (optional, absent in test harnesses)
+    pub file_watcher_embedding_manager: Option<Arc<tokio::sync::RwLock<EmbeddingManager>>>,
     pub start_time: std::time::Instant,
     pub file_watcher_system:
         Arc<tokio::sync::Mutex<Option<vectorizer::file_watcher::FileWatcherSystem>>>,
@@ -116,8 +123,35 @@ pub struct VectorizerServer {
     pub cluster_client_pool: Option<Arc<vectorizer::cluster::ClusterClientPool>>,
     /// Maximum request body size in MB (from config)
     pub max_request_size_mb: usize,
+    /// Collection templates from `collection_templates:` in config.yml
+    pub collection_templates:
+        Arc<std::collections::BTreeMap<String, vectorizer::config::CollectionTemplate>>,
+    /// HMAC request verifier (optional, only if `api.request_signing` is enabled)
+    pub request_verifier: Option<Arc<vectorizer::auth::request_signing::RequestVerifier>>,
+    /// TLS for the HTTP and gRPC listeners (optional, only if `tls` is enabled)
+    pub tls: Option<Arc<vectorizer::security::tls::ReloadableTls>>,
+    /// Payload redaction policy (optional, only if `api.payload_redaction` is enabled)
+    pub payload_redactor: Option<Arc<vectorizer::security::PayloadRedactor>>,
+    /// Audit trail of mutating calls (optional, only if `audit_log` is enabled)
+    pub audit_log: Option<Arc<vectorizer::security::MutationAuditLog>>,
+    /// Remote Vectorizer instances multi-collection search can fan out to
+    /// (`federation:` in config.yml, editable via `/federation/sources`)
+    pub federation: Arc<vectorizer::search::FederationRegistry>,
+    /// Named searches clients run via `/search/templates/{name}/run`,
+    /// saved to `search_templates.json` in the data directory
+    pub search_templates: Arc<vectorizer::search::SearchTemplateStore>,
+    /// A/B experiments splitting text searches of a collection between
+    /// two retrieval configurations, saved to `experiments.json` in the
+    /// data directory
+    pub experiments: Arc<vectorizer::search::ExperimentStore>,
+    /// Anonymized statistics of text, hybrid and intelligent searches
+    /// (optional, only if `query_analytics` is enabled)
+    pub query_analytics: Option<Arc<vectorizer::monitoring::QueryAnalytics>>,
     /// Snapshot manager (optional, for Qdrant snapshot API)
     pub snapshot_manager: Option<Arc<vectorizer::storage::SnapshotManager>>,
+    /// Cron-scheduled backups (optional, only if `backup_schedule.jobs`
+    /// is non-empty)
+    pub backup_scheduler: Option<Arc<vectorizer::db::BackupScheduler>>,
     /// Authentication handler state (optional, only if auth is enabled)
     pub auth_handler_state: Option<AuthHandlerState>,
     /// HiveHub manager (optional, only if hub integration is enabled)
@@ -148,6 +182,18 @@ pub struct VectorizerServer {
     /// are no live receivers, which is the normal idle state — every
     /// caller drops it on the floor.
     pub dashboard_tx: tokio::sync::broadcast::Sender<runtime_metrics::DashboardEvent>,
+    /// Drain mode (`POST /admin/drain`) and read-only mode
+    /// (`PUT /admin/read_only`): refuses new writes and tracks the
+    /// running ones so a rolling restart loses none of them.
+    pub drain: Arc<drain::DrainState>,
+    /// `config.yml` the server booted from. `PATCH /admin/config` writes
+    /// the settings it changes back here (absent in test harnesses).
+    pub config_path: Option<std::path::PathBuf>,
+    /// Startup milestones and the checks behind `/health/ready`.
+    pub readiness: Arc<readiness::Readiness>,
+    /// Per-collection state of the background startup load, served by
+    /// `GET /startup/progress`.
+    pub startup_progress: Arc<vectorizer::db::StartupProgress>,
 }
 
 /// Configuration for root user credentials.
@@ -180,6 +226,70 @@ impl VectorizerServer {
         )
     }
 
+    /// Embed `text` for `collection_name`: with the collection's
+    /// `embedding_provider` when that provider is registered and matches
+    /// the collection's dimension (so text search over a `clip` image
+    /// collection uses CLIP's text encoder), otherwise with the default
+    /// provider.
+    pub(crate) fn embed_for_collection(
+        &self,
+        collection_name: &str,
+        text: &str,
+    ) -> vectorizer::error::Result<Vec<f32>> {
+        let provider = self
+            .store
+            .get_collection(collection_name)
+            .ok()
+            .and_then(|c| {
+                let config = c.config();
+                let dimension = self
+                    .embedding_manager
+                    .get_provider_dimension(&config.embedding_provider)
+                    .ok()?;
+                (dimension == config.dimension).then(|| config.embedding_provider.clone())
+            });
+        match provider {
+            Some(provider) => self.embedding_manager.embed_with_provider(&provider, text),
+            None => self.embedding_manager.embed(text),
+        }
+    }
+
+    /// Commit a metadata change through the Raft log when HA mode is on.
+    ///
+    /// Returns `Ok(None)` when Raft is disabled so callers fall back to the
+    /// local-only path. A committed-but-rejected command (e.g. a racing
+    /// `CreateCollection` with a different config) maps to 409; a propose
+    /// failure (no leader, forwarding needed, quorum lost) maps to 503 so
+    /// clients retry instead of assuming the write landed.
+    pub(crate) async fn propose_cluster_command(
+        &self,
+        cmd: vectorizer::cluster::raft_node::ClusterCommand,
+    ) -> Result<
+        Option<vectorizer::cluster::raft_node::ClusterResponse>,
+        error_middleware::ErrorResponse,
+    > {
+        let Some(ref raft) = self.raft_manager else {
+            return Ok(None);
+        };
+        let summary = cmd.to_string();
+        match raft.propose(cmd).await {
+            Ok(resp) if resp.success => Ok(Some(resp)),
+            Ok(resp) => Err(error_middleware::create_error_response(
+                "cluster_conflict",
+                &resp.message,
+                axum::http::StatusCode::CONFLICT,
+            )),
+            Err(e) => {
+                tracing::warn!("Raft propose {} failed: {}", summary, e);
+                Err(error_middleware::create_error_response(
+                    "not_leader",
+                    &format!("cluster consensus unavailable: {}", e),
+                    axum::http::StatusCode::SERVICE_UNAVAILABLE,
+                ))
+            }
+        }
+    }
+
     /// Check if authentication should be required based on host binding.
     /// Returns true if host is 0.0.0.0 (production mode) and auth is not enabled.
     #[allow(dead_code)]
@@ -3,7 +3,8 @@
 use axum::extract::{Path, State};
 use axum::response::Json;
 use serde_json::json;
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
+use vectorizer::cluster::raft_node::ClusterCommand;
 use vectorizer::models::qdrant::{
     QdrantAliasDescription, QdrantAliasOperations, QdrantChangeAliasesOperation,
     QdrantCollectionsAliasesResponse,
@@ -21,6 +22,15 @@ fn record_alias_metric(operation: &str, status: &str) {
         .inc();
 }
 
+/// Current target of `alias` in the local store, if any.
+fn alias_target(state: &VectorizerServer, alias: &str) -> Option<String> {
+    state
+        .store
+        .list_aliases()
+        .into_iter()
+        .find_map(|(name, target)| (name == alias).then_some(target))
+}
+
 /// List all aliases and their target collections
 pub async fn list_aliases(
     State(state): State<VectorizerServer>,
@@ -81,6 +91,17 @@ pub async fn update_aliases(
                 let target = op.create_alias.collection_name;
                 match state.store.create_alias(&alias, &target) {
                     Ok(_) => {
+                        let cmd = ClusterCommand::CreateAlias {
+                            alias: alias.clone(),
+                            collection: target.clone(),
+                        };
+                        if let Err(err) = state.propose_cluster_command(cmd).await {
+                            record_alias_metric("create", "error");
+                            if let Err(e) = state.store.delete_alias(&alias) {
+                                warn!("Failed to roll back alias '{}': {}", alias, e);
+                            }
+                            return Err(err);
+                        }
                         record_alias_metric("create", "success");
                         info!("Alias '{}' -> '{}' created", alias, target);
                     }
@@ -96,8 +117,21 @@ pub async fn update_aliases(
             }
             QdrantAliasOperations::Delete(op) => {
                 let alias = op.delete_alias.alias_name;
+                let previous = alias_target(&state, &alias);
                 match state.store.delete_alias(&alias) {
                     Ok(_) => {
+                        let cmd = ClusterCommand::DeleteAlias {
+                            alias: alias.clone(),
+                        };
+                        if let Err(err) = state.propose_cluster_command(cmd).await {
+                            record_alias_metric("delete", "error");
+                            if let Some(target) = previous
+                                && let Err(e) = state.store.create_alias(&alias, &target)
+                            {
+                                warn!("Failed to roll back alias '{}': {}", alias, e);
+                            }
+                            return Err(err);
+                        }
                         record_alias_metric("delete", "success");
                         info!("Alias '{}' deleted", alias);
                     }
@@ -111,8 +145,36 @@ pub async fn update_aliases(
             QdrantAliasOperations::Rename(op) => {
                 let old_alias = op.rename_alias.old_alias_name;
                 let new_alias = op.rename_alias.new_alias_name;
+                let previous = alias_target(&state, &old_alias);
                 match state.store.rename_alias(&old_alias, &new_alias) {
                     Ok(_) => {
+                        // Replicated as delete + create so followers converge
+                        // even if they never saw the old alias.
+                        if let Some(target) = previous {
+                            let replicated = async {
+                                state
+                                    .propose_cluster_command(ClusterCommand::DeleteAlias {
+                                        alias: old_alias.clone(),
+                                    })
+                                    .await?;
+                                state
+                                    .propose_cluster_command(ClusterCommand::CreateAlias {
+                                        alias: new_alias.clone(),
+                                        collection: target,
+                                    })
+                                    .await
+                            };
+                            if let Err(err) = replicated.await {
+                                record_alias_metric("rename", "error");
+                                if let Err(e) = state.store.rename_alias(&new_alias, &old_alias) {
+                                    warn!(
+                                        "Failed to roll back alias rename '{}': {}",
+                                        new_alias, e
+                                    );
+                                }
+                                return Err(err);
+                            }
+                        }
                         record_alias_metric("rename", "success");
                         info!("Alias '{}' renamed to '{}'", old_alias, new_alias);
                     }
@@ -1,16 +1,27 @@
 //! Qdrant Cluster API handlers
 //!
 //! This module provides handlers for the Qdrant Cluster API endpoints.
-//! Vectorizer operates as a single-node server, so cluster operations are simulated
-//! for API compatibility.
+//!
+//! Cluster metadata (`/qdrant/cluster/metadata/keys*`) is committed through
+//! the Raft log when HA mode is enabled, so every node serves the same map.
+//! Without Raft the map lives in the local store's metadata table under
+//! [`METADATA_KEY_PREFIX`].
+//!
+//! Peers come from the Raft membership in HA mode and from
+//! `ClusterManager` in a plain cluster; a standalone server reports the
+//! cluster as disabled. Peer IDs come from
+//! [`NodeId::peer_id`](vectorizer::cluster::NodeId::peer_id).
 
 use std::collections::HashMap;
-use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Instant;
 
-use axum::extract::{Path, State};
+use axum::extract::{Path, Query, State};
 use axum::response::Json;
+use openraft::rt::WatchReceiver;
+use serde::Deserialize;
 use tracing::{info, warn};
+use vectorizer::cluster::NodeStatus;
+use vectorizer::cluster::raft_node::ClusterCommand;
 use vectorizer::models::qdrant::cluster::{
     QdrantClusterRecoverResponse, QdrantClusterStatus, QdrantClusterStatusResponse,
     QdrantGetMetadataKeyResponse, QdrantListMetadataKeysResponse, QdrantPeerInfo, QdrantPeerState,
@@ -19,46 +30,122 @@ use vectorizer::models::qdrant::cluster::{
 };
 
 use crate::server::VectorizerServer;
-use crate::server::error_middleware::ErrorResponse;
+use crate::server::error_middleware::{
+    ErrorResponse, create_bad_request_error, create_not_found_error, create_validation_error,
+};
+
+/// Peer ID reported by a standalone server.
+const STANDALONE_PEER_ID: u64 = 1;
+
+/// Peer ID of this node: the Raft node ID in HA mode, else derived from
+/// the cluster node ID.
+pub(super) fn local_peer_id(state: &VectorizerServer) -> u64 {
+    if let Some(ref raft) = state.raft_manager {
+        return raft.node_id;
+    }
+    state
+        .cluster_manager
+        .as_ref()
+        .map(|manager| manager.local_node_id().peer_id())
+        .unwrap_or(STANDALONE_PEER_ID)
+}
+
+/// Qdrant peer state for a cluster node status.
+fn peer_state(status: NodeStatus) -> QdrantPeerState {
+    match status {
+        NodeStatus::Active | NodeStatus::Leaving => QdrantPeerState::Active,
+        NodeStatus::Joining => QdrantPeerState::Restarting,
+        NodeStatus::Unavailable => QdrantPeerState::Dead,
+    }
+}
 
-// Static peer ID for this single-node instance
-static PEER_ID: AtomicU64 = AtomicU64::new(1);
+/// Namespace for cluster metadata in the local store when Raft is off,
+/// keeping it apart from other entries in the store's metadata table.
+const METADATA_KEY_PREFIX: &str = "qdrant.cluster.metadata.";
 
 /// Get cluster status
 /// GET /qdrant/cluster
 pub async fn get_cluster_status(
-    State(_state): State<VectorizerServer>,
+    State(state): State<VectorizerServer>,
 ) -> Result<Json<QdrantClusterStatusResponse>, ErrorResponse> {
     let start = Instant::now();
     info!("Qdrant Cluster API: Getting cluster status");
 
-    let peer_id = PEER_ID.load(Ordering::SeqCst);
+    if let Some(ref raft) = state.raft_manager {
+        let metrics = raft.raft().metrics().borrow_watched().clone();
+        let sm = raft.state().await;
 
-    // Build peer info for this single node
-    let mut peers = HashMap::new();
-    peers.insert(
-        peer_id.to_string(),
-        QdrantPeerInfo {
-            uri: "http://localhost:7777".to_string(),
-            state: Some(QdrantPeerState::Active),
-        },
-    );
+        let mut peers: HashMap<String, QdrantPeerInfo> = sm
+            .nodes
+            .iter()
+            .map(|(id, (address, port))| {
+                (
+                    id.to_string(),
+                    QdrantPeerInfo {
+                        uri: format!("http://{}:{}", address, port),
+                        state: Some(QdrantPeerState::Active),
+                    },
+                )
+            })
+            .collect();
+        peers
+            .entry(raft.node_id.to_string())
+            .or_insert_with(|| QdrantPeerInfo {
+                uri: "http://localhost:7777".to_string(),
+                state: Some(QdrantPeerState::Active),
+            });
+
+        let cluster_status = QdrantClusterStatus {
+            status: "enabled".to_string(),
+            peer_id: raft.node_id,
+            peers,
+            raft_info: Some(QdrantRaftInfo {
+                term: metrics.current_term,
+                commit: sm.last_applied_log.map(|id| id.index()).unwrap_or(0),
+                pending_operations: 0,
+                leader: metrics.current_leader,
+                role: Some(format!("{:?}", metrics.state)),
+                is_voter: true,
+            }),
+            consensus_thread_status: None,
+            message_send_failures: None,
+        };
+
+        let elapsed = start.elapsed().as_secs_f64();
+        return Ok(Json(QdrantClusterStatusResponse {
+            result: cluster_status,
+            status: "ok".to_string(),
+            time: elapsed,
+        }));
+    }
 
-    // Build Raft info (simulated for single-node)
-    let raft_info = QdrantRaftInfo {
-        term: 1,
-        commit: 0,
-        pending_operations: 0,
-        leader: Some(peer_id),
-        role: Some("Leader".to_string()),
-        is_voter: true,
+    let peer_id = local_peer_id(&state);
+    let (status, peers) = match state.cluster_manager {
+        Some(ref manager) if manager.is_enabled() => {
+            let peers = manager
+                .get_nodes()
+                .into_iter()
+                .map(|node| {
+                    (
+                        node.id.peer_id().to_string(),
+                        QdrantPeerInfo {
+                            uri: format!("http://{}:{}", node.address, node.grpc_port),
+                            state: Some(peer_state(node.status)),
+                        },
+                    )
+                })
+                .collect();
+            ("enabled", peers)
+        }
+        _ => ("disabled", HashMap::new()),
     };
 
+    // Without Raft there is no consensus state to report
     let cluster_status = QdrantClusterStatus {
-        status: "enabled".to_string(),
+        status: status.to_string(),
         peer_id,
         peers,
-        raft_info: Some(raft_info),
+        raft_info: None,
         consensus_thread_status: None,
         message_send_failures: None,
     };
@@ -66,6 +153,7 @@ pub async fn get_cluster_status(
     let elapsed = start.elapsed().as_secs_f64();
     info!(
         peer_id = peer_id,
+        peer_count = cluster_status.peers.len(),
         elapsed_ms = elapsed * 1000.0,
         "Qdrant Cluster API: Retrieved cluster status"
     );
@@ -101,34 +189,65 @@ pub async fn cluster_recover(
     }))
 }
 
+/// Query parameters for peer removal
+#[derive(Debug, Default, Deserialize)]
+pub struct RemovePeerQuery {
+    /// Remove the peer even if it still holds shards
+    #[serde(default)]
+    pub force: bool,
+}
+
 /// Remove peer from cluster
 /// DELETE /qdrant/cluster/peer/{peer_id}
 pub async fn remove_peer(
-    State(_state): State<VectorizerServer>,
+    State(state): State<VectorizerServer>,
     Path(peer_id): Path<u64>,
+    Query(query): Query<RemovePeerQuery>,
 ) -> Result<Json<QdrantRemovePeerResponse>, ErrorResponse> {
     let start = Instant::now();
-    info!(peer_id = peer_id, "Qdrant Cluster API: Removing peer");
-
-    let current_peer_id = PEER_ID.load(Ordering::SeqCst);
+    info!(
+        peer_id = peer_id,
+        force = query.force,
+        "Qdrant Cluster API: Removing peer"
+    );
 
-    if peer_id == current_peer_id {
-        warn!(
-            peer_id = peer_id,
-            "Cannot remove self from cluster on single-node instance"
-        );
-    } else {
+    let Some(ref manager) = state.cluster_manager else {
+        return Err(create_bad_request_error(
+            "Cluster mode is disabled; there are no peers to remove",
+        ));
+    };
+    if peer_id == local_peer_id(&state) {
+        return Err(create_bad_request_error(
+            "A peer cannot remove itself from the cluster",
+        ));
+    }
+    let node = manager
+        .get_nodes()
+        .into_iter()
+        .find(|node| node.id.peer_id() == peer_id)
+        .ok_or_else(|| create_not_found_error("peer", &peer_id.to_string()))?;
+    if !node.shards.is_empty() && !query.force {
+        return Err(create_bad_request_error(&format!(
+            "Peer {} still holds {} shard(s); move them first or pass force=true",
+            peer_id,
+            node.shards.len()
+        )));
+    }
+    if !node.shards.is_empty() {
         warn!(
             peer_id = peer_id,
-            "Peer not found in single-node cluster (no-op)"
+            shard_count = node.shards.len(),
+            "Force-removing peer that still holds shards"
         );
     }
+    manager.remove_node(&node.id);
 
     let elapsed = start.elapsed().as_secs_f64();
     info!(
         peer_id = peer_id,
+        node_id = %node.id,
         elapsed_ms = elapsed * 1000.0,
-        "Qdrant Cluster API: Remove peer completed (simulated)"
+        "Qdrant Cluster API: Removed peer"
     );
 
     Ok(Json(QdrantRemovePeerResponse {
@@ -141,13 +260,21 @@ pub async fn remove_peer(
 /// List metadata keys
 /// GET /qdrant/cluster/metadata/keys
 pub async fn list_metadata_keys(
-    State(_state): State<VectorizerServer>,
+    State(state): State<VectorizerServer>,
 ) -> Result<Json<QdrantListMetadataKeysResponse>, ErrorResponse> {
     let start = Instant::now();
     info!("Qdrant Cluster API: Listing metadata keys");
 
-    // Vectorizer doesn't maintain cluster metadata, return empty list
-    let keys: Vec<String> = vec![];
+    let mut keys: Vec<String> = match state.raft_manager {
+        Some(ref raft) => raft.state().await.metadata.into_keys().collect(),
+        None => state
+            .store
+            .list_metadata_keys()
+            .into_iter()
+            .filter_map(|k| k.strip_prefix(METADATA_KEY_PREFIX).map(str::to_string))
+            .collect(),
+    };
+    keys.sort();
 
     let elapsed = start.elapsed().as_secs_f64();
     info!(
@@ -166,13 +293,23 @@ pub async fn list_metadata_keys(
 /// Get metadata key value
 /// GET /qdrant/cluster/metadata/keys/{key}
 pub async fn get_metadata_key(
-    State(_state): State<VectorizerServer>,
+    State(state): State<VectorizerServer>,
     Path(key): Path<String>,
 ) -> Result<Json<QdrantGetMetadataKeyResponse>, ErrorResponse> {
     let start = Instant::now();
     info!(key = %key, "Qdrant Cluster API: Getting metadata key");
 
-    // Vectorizer doesn't maintain cluster metadata, return null
+    let raw = match state.raft_manager {
+        Some(ref raft) => raft.state().await.metadata.get(&key).cloned(),
+        None => state
+            .store
+            .get_metadata(&format!("{}{}", METADATA_KEY_PREFIX, key)),
+    };
+    // Values are stored as JSON text; a missing key reads as null like Qdrant.
+    let value = raw
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or(serde_json::Value::Null);
+
     let elapsed = start.elapsed().as_secs_f64();
     info!(
         key = %key,
@@ -181,7 +318,7 @@ pub async fn get_metadata_key(
     );
 
     Ok(Json(QdrantGetMetadataKeyResponse {
-        result: serde_json::Value::Null,
+        result: value,
         status: "ok".to_string(),
         time: elapsed,
     }))
@@ -190,7 +327,7 @@ pub async fn get_metadata_key(
 /// Update metadata key value
 /// PUT /qdrant/cluster/metadata/keys/{key}
 pub async fn update_metadata_key(
-    State(_state): State<VectorizerServer>,
+    State(state): State<VectorizerServer>,
     Path(key): Path<String>,
     Json(request): Json<QdrantUpdateMetadataKeyRequest>,
 ) -> Result<Json<QdrantUpdateMetadataKeyResponse>, ErrorResponse> {
@@ -201,17 +338,32 @@ pub async fn update_metadata_key(
         "Qdrant Cluster API: Updating metadata key"
     );
 
-    // Vectorizer doesn't maintain cluster metadata, acknowledge but don't persist
-    warn!(
-        key = %key,
-        "Cluster metadata update acknowledged but not persisted (single-node mode)"
-    );
+    if key.is_empty() {
+        return Err(create_validation_error(
+            "key",
+            "metadata key must not be empty",
+        ));
+    }
+
+    let encoded = request.value.to_string();
+    let committed = state
+        .propose_cluster_command(ClusterCommand::SetMetadata {
+            key: key.clone(),
+            value: encoded.clone(),
+        })
+        .await?;
+    if committed.is_none() {
+        state
+            .store
+            .set_metadata(&format!("{}{}", METADATA_KEY_PREFIX, key), encoded);
+    }
 
     let elapsed = start.elapsed().as_secs_f64();
     info!(
         key = %key,
+        replicated = committed.is_some(),
         elapsed_ms = elapsed * 1000.0,
-        "Qdrant Cluster API: Updated metadata key (simulated)"
+        "Qdrant Cluster API: Updated metadata key"
     );
 
     Ok(Json(QdrantUpdateMetadataKeyResponse {
@@ -2,11 +2,13 @@
 
 use std::collections::HashMap;
 
+use axum::Extension;
 use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
 use axum::response::Json;
 use serde_json::{Value, json};
 use tracing::{debug, error, info, warn};
+use vectorizer::auth::middleware::AuthState;
 use vectorizer::models::qdrant::{
     PointOperationStatus as QdrantOperationStatus, QdrantBinaryQuantization,
     QdrantBinaryQuantizationConfig, QdrantCollectionConfig, QdrantCollectionInfo,
@@ -169,10 +171,14 @@ fn convert_qdrant_optimizer_config(
 /// Get all collections
 pub async fn get_collections(
     State(state): State<VectorizerServer>,
+    auth_state: Option<Extension<AuthState>>,
 ) -> Result<Json<QdrantCollectionListResponse>, ErrorResponse> {
     debug!("Getting all collections");
 
-    let collections = state.store.list_collections();
+    let mut collections = state.store.list_collections();
+    if let Some(auth) = &auth_state {
+        collections.retain(|name| auth.user_claims.can_access_collection(name));
+    }
     let mut collection_infos = Vec::new();
 
     for collection_name in collections {
@@ -580,5 +586,12 @@ fn convert_from_qdrant_config(
         sharding: None,
         graph: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     })
 }
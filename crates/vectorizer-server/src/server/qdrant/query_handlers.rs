@@ -8,7 +8,7 @@ use std::collections::{HashMap, HashSet};
 use axum::extract::{Path, State};
 use axum::http::StatusCode;
 use axum::response::Json;
-use tracing::{debug, error, info, warn};
+use tracing::{debug, error, info, info_span, warn};
 use vectorizer::models::qdrant::point::{QdrantPointId, QdrantValue, QdrantVector};
 use vectorizer::models::qdrant::{
     FilterProcessor, QdrantBatchQueryRequest, QdrantBatchQueryResponse, QdrantComplexQuery,
@@ -390,6 +390,12 @@ pub async fn query_points(
     let include_payload = should_include_payload(&request.with_payload);
     let include_vector = should_include_vector(&request.with_vector);
 
+    let filter_span = info_span!(
+        "filter.evaluate",
+        candidates = search_results.len(),
+        filtered = request.filter.is_some()
+    )
+    .entered();
     let results: Vec<QdrantScoredPoint> = search_results
         .into_iter()
         .filter(|result| {
@@ -453,6 +459,7 @@ pub async fn query_points(
             }
         })
         .collect();
+    drop(filter_span);
 
     info!(
         collection = %collection_name,
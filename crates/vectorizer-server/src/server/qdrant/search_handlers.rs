@@ -7,13 +7,15 @@ use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
 use axum::response::Json;
 use serde_json::{Value, json};
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, info_span};
 use uuid::Uuid;
+use vectorizer::auth::middleware::AuthState;
 use vectorizer::hub::middleware::RequestTenantContext;
 use vectorizer::models::qdrant::point::{QdrantPointId, QdrantValue, QdrantVector};
 use vectorizer_core::error::VectorizerError;
 
 use crate::server::VectorizerServer;
+use crate::server::core::check_caller_namespace;
 use crate::server::error_middleware::{
     ErrorResponse, create_error_response, create_not_found_error,
 };
@@ -37,6 +39,35 @@ use vectorizer::models::qdrant::{
 };
 use vectorizer::models::{Payload, SearchResult, Vector};
 
+/// Run a dense search honouring Qdrant's `params.quantization` block.
+///
+/// `exact: true` scores every vector instead of walking the HNSW graph.
+/// `oversampling` and `rescore` only change behaviour on binary-quantized
+/// collections; `ignore: true` falls back to the regular search path.
+fn search_with_params(
+    collection: &vectorizer::db::CollectionType,
+    vector: &[f32],
+    limit: usize,
+    params: Option<&vectorizer::models::qdrant::QdrantSearchParams>,
+) -> vectorizer::error::Result<Vec<SearchResult>> {
+    if params.is_some_and(|p| p.exact.unwrap_or(false)) {
+        return collection.search_exact(vector, limit);
+    }
+    let quantization = params
+        .and_then(|p| p.quantization.as_ref())
+        .filter(|q| !q.ignore.unwrap_or(false));
+    match quantization {
+        Some(q) => collection.search_quantized(
+            vector,
+            limit,
+            q.oversampling
+                .unwrap_or(vectorizer::db::BINARY_DEFAULT_OVERSAMPLING),
+            q.rescore.unwrap_or(true),
+        ),
+        None => collection.search(vector, limit),
+    }
+}
+
 /// Convert serde_json::Value to QdrantValue
 fn json_value_to_qdrant_value(value: serde_json::Value) -> QdrantValue {
     match value {
@@ -177,17 +208,27 @@ pub async fn search_points(
     let offset = request.offset.unwrap_or(0) as usize;
 
     // Perform search
-    let search_results = collection
-        .search(&request.vector, limit + offset)
-        .map_err(|e| {
-            create_error_response(
-                &format!("{}", e),
-                "Search failed",
-                StatusCode::INTERNAL_SERVER_ERROR,
-            )
-        })?;
+    let search_results = search_with_params(
+        &collection,
+        &request.vector,
+        limit + offset,
+        request.params.as_ref(),
+    )
+    .map_err(|e| {
+        create_error_response(
+            &format!("{}", e),
+            "Search failed",
+            StatusCode::INTERNAL_SERVER_ERROR,
+        )
+    })?;
 
     // Apply offset, filters, and limit
+    let filter_span = info_span!(
+        "filter.evaluate",
+        candidates = search_results.len(),
+        filtered = request.filter.is_some()
+    )
+    .entered();
     let results: Vec<QdrantScoredPoint> = search_results
         .into_iter()
         .skip(offset)
@@ -246,6 +287,7 @@ pub async fn search_points(
             }
         })
         .collect();
+    drop(filter_span);
 
     info!(
         collection = %collection_name,
@@ -474,15 +516,19 @@ pub async fn batch_search_points(
         let offset = search_request.offset.unwrap_or(0) as usize;
 
         // Perform search
-        let search_results = collection
-            .search(&search_request.vector, limit + offset)
-            .map_err(|e| {
-                create_error_response(
-                    &format!("Search {}: {}", i, e),
-                    "Batch search failed",
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                )
-            })?;
+        let search_results = search_with_params(
+            &collection,
+            &search_request.vector,
+            limit + offset,
+            search_request.params.as_ref(),
+        )
+        .map_err(|e| {
+            create_error_response(
+                &format!("Search {}: {}", i, e),
+                "Batch search failed",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )
+        })?;
 
         // Apply offset, filters, and limit
         let batch_results: Vec<QdrantScoredPoint> = search_results
@@ -749,6 +795,7 @@ pub async fn search_points_groups(
     State(state): State<VectorizerServer>,
     Path(collection_name): Path<String>,
     tenant_ctx: Option<Extension<RequestTenantContext>>,
+    auth: Option<Extension<AuthState>>,
     Json(request): Json<QdrantSearchGroupsRequest>,
 ) -> Result<Json<QdrantSearchGroupsResponse>, ErrorResponse> {
     info!(
@@ -760,6 +807,16 @@ pub async fn search_points_groups(
         "Searching points with grouping"
     );
 
+    // `with_lookup` reads a second collection, named where the
+    // namespace middleware does not look
+    if let Some(lookup) = &request.with_lookup {
+        let lookup_collection = match lookup {
+            QdrantWithLookup::Collection(name) => name,
+            QdrantWithLookup::Config(config) => &config.collection,
+        };
+        check_caller_namespace(auth.as_deref(), lookup_collection)?;
+    }
+
     // Extract tenant ID for multi-tenant access control
     let tenant_id = extract_tenant_id(&tenant_ctx);
 
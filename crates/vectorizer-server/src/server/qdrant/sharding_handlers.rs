@@ -1,6 +1,12 @@
 //! Qdrant Sharding API handlers
 //!
 //! This module provides handlers for the Qdrant Sharding API endpoints.
+//!
+//! Shard listings and `GET /qdrant/collections/{name}/cluster` report the
+//! collection's real layout: one shard for plain collections, every
+//! shard with its point count for locally sharded ones, and for
+//! distributed collections the owning peer (from the cluster router) and
+//! its health (from `ClusterManager`), plus in-flight shard transfers.
 
 use std::time::Instant;
 
@@ -8,13 +14,17 @@ use axum::extract::{Path, State};
 use axum::http::StatusCode;
 use axum::response::Json;
 use tracing::{error, info, warn};
+use vectorizer::cluster::{NodeId, NodeStatus};
 use vectorizer::db::sharding::ShardId;
+use vectorizer::db::vector_store::CollectionType;
 use vectorizer::models::qdrant::sharding::{
-    QdrantCreateShardKeyRequest, QdrantCreateShardKeyResponse, QdrantDeleteShardKeyRequest,
-    QdrantDeleteShardKeyResponse, QdrantListShardKeysResponse, QdrantLocalShardInfo,
-    QdrantShardKeyInfo, QdrantShardKeyValue, QdrantShardKeysResult, QdrantShardState,
+    QdrantCollectionClusterInfo, QdrantCollectionClusterInfoResponse, QdrantCreateShardKeyRequest,
+    QdrantCreateShardKeyResponse, QdrantDeleteShardKeyRequest, QdrantDeleteShardKeyResponse,
+    QdrantListShardKeysResponse, QdrantLocalShardInfo, QdrantRemoteShardInfo, QdrantShardKeyInfo,
+    QdrantShardKeyValue, QdrantShardKeysResult, QdrantShardState, QdrantShardTransfer,
 };
 
+use super::cluster_handlers::local_peer_id;
 use crate::server::VectorizerServer;
 use crate::server::error_middleware::{
     ErrorResponse, create_error_response, create_not_found_error,
@@ -144,27 +154,23 @@ pub async fn list_shard_keys(
         .get_collection(&collection_name)
         .map_err(|_| create_not_found_error("collection", &collection_name))?;
 
-    // Get collection stats for shard info
-    let vector_count = collection.vector_count() as u64;
-
-    // Vectorizer uses automatic sharding - report the default shard
-    // In a full implementation, this would query the shard router
+    // Vectorizer shards automatically, so every shard sits under the
+    // default key
+    let (local_shards, remote_shards) = shard_layout(&state, &collection);
+    drop(collection);
+    let shard_count = local_shards.len() + remote_shards.len();
     let shard_info = QdrantShardKeyInfo {
         shard_key: QdrantShardKeyValue::String("_default".to_string()),
-        shards_number: 1,
+        shards_number: shard_count as u32,
         replication_factor: 1,
-        local_shards: vec![QdrantLocalShardInfo {
-            shard_id: 0,
-            points_count: vector_count,
-            state: QdrantShardState::Active,
-        }],
-        remote_shards: vec![],
+        local_shards,
+        remote_shards,
     };
 
     let elapsed = start.elapsed().as_secs_f64();
     info!(
         collection = %collection_name,
-        shard_count = 1,
+        shard_count = shard_count,
         elapsed_ms = elapsed * 1000.0,
         "Qdrant Sharding API: Listed shard keys"
     );
@@ -177,3 +183,133 @@ pub async fn list_shard_keys(
         time: elapsed,
     }))
 }
+
+/// Collection cluster info
+/// GET /qdrant/collections/{name}/cluster
+pub async fn get_collection_cluster_info(
+    State(state): State<VectorizerServer>,
+    Path(collection_name): Path<String>,
+) -> Result<Json<QdrantCollectionClusterInfoResponse>, ErrorResponse> {
+    let start = Instant::now();
+    info!(
+        collection = %collection_name,
+        "Qdrant Sharding API: Getting collection cluster info"
+    );
+
+    let collection = state
+        .store
+        .get_collection(&collection_name)
+        .map_err(|_| create_not_found_error("collection", &collection_name))?;
+    let (local_shards, remote_shards) = shard_layout(&state, &collection);
+    drop(collection);
+
+    let shard_transfers = vectorizer::cluster::shard_migrator::shard_transfers()
+        .into_iter()
+        .filter(|m| m.collection == collection_name)
+        .map(|m| QdrantShardTransfer {
+            shard_id: m.shard_id,
+            from: NodeId::new(m.from_node).peer_id(),
+            to: NodeId::new(m.to_node).peer_id(),
+            sync: None,
+        })
+        .collect();
+
+    let result = QdrantCollectionClusterInfo {
+        peer_id: local_peer_id(&state),
+        shard_count: (local_shards.len() + remote_shards.len()) as u32,
+        local_shards,
+        remote_shards,
+        shard_transfers,
+    };
+
+    let elapsed = start.elapsed().as_secs_f64();
+    info!(
+        collection = %collection_name,
+        shard_count = result.shard_count,
+        elapsed_ms = elapsed * 1000.0,
+        "Qdrant Sharding API: Retrieved collection cluster info"
+    );
+
+    Ok(Json(QdrantCollectionClusterInfoResponse {
+        result,
+        status: "ok".to_string(),
+        time: elapsed,
+    }))
+}
+
+/// Where each shard of `collection` lives: shards held on this node with
+/// their point counts, and shards owned by other peers.
+fn shard_layout(
+    state: &VectorizerServer,
+    collection: &CollectionType,
+) -> (Vec<QdrantLocalShardInfo>, Vec<QdrantRemoteShardInfo>) {
+    match collection {
+        CollectionType::Sharded(sharded) => {
+            let mut local: Vec<_> = sharded
+                .shard_counts()
+                .into_iter()
+                .map(|(shard_id, count)| QdrantLocalShardInfo {
+                    shard_id: shard_id.as_u32(),
+                    points_count: count as u64,
+                    state: QdrantShardState::Active,
+                })
+                .collect();
+            local.sort_by_key(|s| s.shard_id);
+            (local, Vec::new())
+        }
+        CollectionType::DistributedSharded(distributed) => {
+            let local_counts = distributed.local_shard_counts();
+            let mut local = Vec::new();
+            let mut remote = Vec::new();
+            for (shard_id, owner) in distributed.shard_owners() {
+                if let Some(count) = local_counts.get(&shard_id) {
+                    local.push(QdrantLocalShardInfo {
+                        shard_id: shard_id.as_u32(),
+                        points_count: *count as u64,
+                        state: QdrantShardState::Active,
+                    });
+                    continue;
+                }
+                // A shard nobody owns is unavailable
+                let Some(owner) = owner.map(NodeId::new) else {
+                    remote.push(QdrantRemoteShardInfo {
+                        shard_id: shard_id.as_u32(),
+                        peer_id: 0,
+                        state: QdrantShardState::Dead,
+                    });
+                    continue;
+                };
+                let status = state
+                    .cluster_manager
+                    .as_ref()
+                    .and_then(|cm| cm.get_node(&owner))
+                    .map(|node| node.status);
+                remote.push(QdrantRemoteShardInfo {
+                    shard_id: shard_id.as_u32(),
+                    peer_id: owner.peer_id(),
+                    state: shard_state(status),
+                });
+            }
+            (local, remote)
+        }
+        _ => (
+            vec![QdrantLocalShardInfo {
+                shard_id: 0,
+                points_count: collection.vector_count() as u64,
+                state: QdrantShardState::Active,
+            }],
+            Vec::new(),
+        ),
+    }
+}
+
+/// Qdrant shard state for a shard on a peer in `status` (`None`: the
+/// peer is unknown to this node).
+fn shard_state(status: Option<NodeStatus>) -> QdrantShardState {
+    match status {
+        Some(NodeStatus::Active) => QdrantShardState::Active,
+        Some(NodeStatus::Joining) => QdrantShardState::Initializing,
+        Some(NodeStatus::Leaving) => QdrantShardState::Partial,
+        Some(NodeStatus::Unavailable) | None => QdrantShardState::Dead,
+    }
+}
@@ -1,14 +1,19 @@
 //! Qdrant Snapshot API handlers
 //!
 //! This module provides handlers for the Qdrant Snapshots API endpoints.
+//! Uploads take either a Vectorizer snapshot or a collection snapshot
+//! written by Qdrant itself, which is imported into the collection (see
+//! `vectorizer::migration::qdrant::snapshot`).
 
+use std::collections::HashMap;
 use std::time::Instant;
 
 use axum::body::Bytes;
-use axum::extract::{Path, State};
+use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
 use axum::response::Json;
-use tracing::{error, info};
+use tracing::{error, info, warn};
+use vectorizer::migration::qdrant::{QdrantDataImporter, QdrantSnapshotReader};
 use vectorizer::models::qdrant::snapshot::{
     QdrantCreateSnapshotResponse, QdrantDeleteSnapshotResponse, QdrantListSnapshotsResponse,
     QdrantSnapshotDescription, QdrantUploadSnapshotResponse,
@@ -117,6 +122,12 @@ pub async fn create_collection_snapshot(
             StatusCode::INTERNAL_SERVER_ERROR,
         )
     })?;
+    if let Err(e) = snapshot_manager.upload_snapshot(&snapshot).await {
+        warn!(
+            "Failed to copy snapshot {} to object store: {}",
+            snapshot.id, e
+        );
+    }
 
     let elapsed = start.elapsed().as_secs_f64();
     info!(
@@ -275,6 +286,12 @@ pub async fn create_full_snapshot(
             StatusCode::INTERNAL_SERVER_ERROR,
         )
     })?;
+    if let Err(e) = snapshot_manager.upload_snapshot(&snapshot).await {
+        warn!(
+            "Failed to copy snapshot {} to object store: {}",
+            snapshot.id, e
+        );
+    }
 
     let elapsed = start.elapsed().as_secs_f64();
     info!(
@@ -321,9 +338,11 @@ pub async fn recover_collection_snapshot(
     })?;
 
     // The location could be a snapshot ID or a path
-    // For now, we treat it as a snapshot ID
+    // For now, we treat it as a snapshot ID; snapshots only held in the
+    // object store are downloaded first
     snapshot_manager
-        .restore_snapshot(&request.location)
+        .fetch_and_restore_snapshot(&request.location)
+        .await
         .map_err(|e| {
             error!("Failed to recover from snapshot: {}", e);
             create_error_response(
@@ -352,9 +371,14 @@ pub async fn recover_collection_snapshot(
 
 /// Upload a snapshot for a specific collection
 /// POST /qdrant/collections/{name}/snapshots/upload
+///
+/// A Qdrant collection snapshot is imported into the collection, which
+/// is created from the snapshot config when missing; `?vector=<name>`
+/// picks the vector of a collection with several named vectors.
 pub async fn upload_collection_snapshot(
     State(state): State<VectorizerServer>,
     Path(collection_name): Path<String>,
+    Query(params): Query<HashMap<String, String>>,
     body: Bytes,
 ) -> Result<Json<QdrantUploadSnapshotResponse>, ErrorResponse> {
     let start = Instant::now();
@@ -364,6 +388,17 @@ pub async fn upload_collection_snapshot(
         "Qdrant Snapshots API: Uploading collection snapshot"
     );
 
+    if QdrantSnapshotReader::is_snapshot(&body) {
+        return import_qdrant_snapshot(
+            &state,
+            collection_name,
+            params.get("vector").cloned(),
+            body,
+            start,
+        )
+        .await;
+    }
+
     // Verify collection exists
     state
         .store
@@ -418,3 +453,75 @@ pub async fn upload_collection_snapshot(
         time: elapsed,
     }))
 }
+
+/// Import a snapshot written by Qdrant into `collection_name`.
+async fn import_qdrant_snapshot(
+    state: &VectorizerServer,
+    collection_name: String,
+    vector_name: Option<String>,
+    body: Bytes,
+    start: Instant,
+) -> Result<Json<QdrantUploadSnapshotResponse>, ErrorResponse> {
+    let size = body.len() as u64;
+    let name = collection_name.clone();
+    let contents = tokio::task::spawn_blocking(move || {
+        QdrantSnapshotReader::read(&body, &name, vector_name.as_deref())
+    })
+    .await
+    .map_err(|e| {
+        create_error_response(
+            &format!("Snapshot import task failed: {}", e),
+            "Snapshot import failed",
+            StatusCode::INTERNAL_SERVER_ERROR,
+        )
+    })?
+    .map_err(ErrorResponse::from)?;
+    for warning in &contents.warnings {
+        warn!(collection = %collection_name, "Qdrant snapshot import: {}", warning);
+    }
+
+    let result = QdrantDataImporter::import_collection(&state.store, &contents.collection)
+        .await
+        .map_err(ErrorResponse::from)?;
+    if let Some(ref auto_save) = state.auto_save_manager {
+        auto_save.mark_changed();
+    }
+    state.query_cache.invalidate_collection(&collection_name);
+    if result.imported_count == 0 && result.error_count > 0 {
+        return Err(create_error_response(
+            "snapshot_import_failed",
+            &format!(
+                "No point of the Qdrant snapshot could be imported: {}",
+                result
+                    .errors
+                    .iter()
+                    .take(3)
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            ),
+            StatusCode::BAD_REQUEST,
+        ));
+    }
+
+    let elapsed = start.elapsed().as_secs_f64();
+    info!(
+        collection = %collection_name,
+        segments = contents.segments,
+        imported = result.imported_count,
+        errors = result.error_count,
+        elapsed_ms = elapsed * 1000.0,
+        "Qdrant Snapshots API: Imported Qdrant snapshot"
+    );
+
+    Ok(Json(QdrantUploadSnapshotResponse {
+        result: QdrantSnapshotDescription {
+            name: format!("qdrant_import_{}", collection_name),
+            creation_time: Some(chrono::Utc::now().to_rfc3339()),
+            size,
+            checksum: None,
+        },
+        status: "ok".to_string(),
+        time: elapsed,
+    }))
+}
@@ -2,11 +2,13 @@
 
 use std::collections::HashMap;
 
+use axum::Extension;
 use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
 use axum::response::Json;
 use serde_json::{Value, json};
 use tracing::{debug, error, info};
+use vectorizer::hub::middleware::RequestTenantContext;
 use vectorizer::models::qdrant::{
     PointCountResult as QdrantCountResult, PointOperationStatus as QdrantOperationStatus,
     PointScrollResult as QdrantScrollResult, QdrantCountPointsRequest, QdrantCountPointsResponse,
@@ -25,6 +27,7 @@ use crate::server::VectorizerServer;
 use crate::server::error_middleware::{
     ErrorResponse, create_error_response, create_not_found_error,
 };
+use crate::server::rest_handlers::check_insert_quota;
 
 /// Convert QdrantValue to serde_json::Value
 fn qdrant_value_to_json_value(value: QdrantValue) -> serde_json::Value {
@@ -77,6 +80,7 @@ fn json_value_to_qdrant_value(value: serde_json::Value) -> QdrantValue {
 pub async fn upsert_points(
     State(state): State<VectorizerServer>,
     Path(collection_name): Path<String>,
+    tenant_ctx: Option<Extension<RequestTenantContext>>,
     req: axum::extract::Request,
 ) -> Result<Json<QdrantPointOperationResult>, ErrorResponse> {
     // Read body with configurable limit
@@ -132,6 +136,15 @@ pub async fn upsert_points(
         collection_name
     );
 
+    // Held by the background insert until it lands
+    let tenant_admission = check_insert_quota(
+        &state,
+        tenant_ctx.as_ref(),
+        &collection_name,
+        request.points.len(),
+    )
+    .await?;
+
     // Validate collection exists
     let collection = state
         .store
@@ -220,6 +233,7 @@ pub async fn upsert_points(
             store_clone.insert(&collection_name_for_bg, vectors)
         })
         .await;
+        drop(tenant_admission);
 
         match insert_result {
             Ok(Ok(_)) => {
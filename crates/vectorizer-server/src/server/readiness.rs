@@ -0,0 +1,124 @@
+//! Readiness behind `/health/ready`.
+//!
+//! `/health/live` only says the process answers; readiness also waits
+//! for the startup collection load, the vocabulary restore, replication
+//! to catch up and the upsert queues to drain below a threshold, so a
+//! load balancer does not send queries to a node still filling in
+//! `/collections`. Which checks run comes from `health.readiness`.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde_json::{Value, json};
+use vectorizer::config::ReadinessConfig;
+
+use crate::server::VectorizerServer;
+
+/// Startup milestones plus the configured checks.
+#[derive(Debug)]
+pub struct Readiness {
+    config: ReadinessConfig,
+    collections_loaded: AtomicBool,
+    vocabulary_loaded: AtomicBool,
+}
+
+impl Readiness {
+    /// Readiness for a server that is still starting: collections and
+    /// vocabulary are not loaded yet.
+    pub fn new(config: ReadinessConfig) -> Self {
+        Self {
+            config,
+            collections_loaded: AtomicBool::new(false),
+            vocabulary_loaded: AtomicBool::new(false),
+        }
+    }
+
+    /// Readiness for a server with nothing to load at startup.
+    pub fn loaded(config: ReadinessConfig) -> Self {
+        let readiness = Self::new(config);
+        readiness.mark_collections_loaded();
+        readiness.set_vocabulary_loaded(true);
+        readiness
+    }
+
+    /// The background load of persisted and workspace collections has
+    /// finished.
+    pub fn mark_collections_loaded(&self) {
+        self.collections_loaded.store(true, Ordering::Release);
+    }
+
+    /// Record whether the persisted vocabulary was restored.
+    pub fn set_vocabulary_loaded(&self, loaded: bool) {
+        self.vocabulary_loaded.store(loaded, Ordering::Release);
+    }
+
+    /// Whether the startup collection load has finished.
+    pub fn collections_loaded(&self) -> bool {
+        self.collections_loaded.load(Ordering::Acquire)
+    }
+
+    /// Run the configured checks. Returns whether all of them pass and
+    /// the outcome of each; checks switched off are left out.
+    pub fn check(&self, state: &VectorizerServer) -> (bool, Value) {
+        let mut checks = serde_json::Map::new();
+
+        checks.insert(
+            "draining".to_string(),
+            json!({"ok": !state.drain.is_draining()}),
+        );
+        // Informational: a read-only node still serves searches
+        checks.insert(
+            "read_only".to_string(),
+            json!({
+                "ok": true,
+                "enabled": state.drain.is_read_only(),
+                "reason": state.drain.read_only_reason(),
+            }),
+        );
+        if self.config.collections_loaded {
+            checks.insert(
+                "collections_loaded".to_string(),
+                json!({"ok": self.collections_loaded()}),
+            );
+        }
+        if self.config.vocabulary_loaded {
+            checks.insert(
+                "vocabulary_loaded".to_string(),
+                json!({"ok": self.vocabulary_loaded.load(Ordering::Acquire)}),
+            );
+        }
+        if let Some(max_lag_ms) = self.config.max_replication_lag_ms
+            && let Some(replica) = &state.replica_node
+        {
+            let connected = replica.is_connected();
+            let lag_ms = replica.get_stats().lag_ms;
+            checks.insert(
+                "replication".to_string(),
+                json!({
+                    "ok": connected && lag_ms <= max_lag_ms,
+                    "connected": connected,
+                    "lag_ms": lag_ms,
+                    "max_lag_ms": max_lag_ms,
+                }),
+            );
+        }
+        if let Some(max_queued) = self.config.max_indexing_queue {
+            let queued: usize = state
+                .upsert_queue
+                .snapshot_depths()
+                .iter()
+                .map(|(_, depth)| depth)
+                .sum();
+            checks.insert(
+                "indexing_queue".to_string(),
+                json!({
+                    "ok": queued <= max_queued,
+                    "queued": queued,
+                    "max_queued": max_queued,
+                }),
+            );
+        }
+
+        let ready = checks.values().all(|check| check["ok"] == true);
+        (ready, Value::Object(checks))
+    }
+}
@@ -1,12 +1,17 @@
 //! Administrative REST handlers.
 //!
 //! Workspace management (add/remove/list + config get/update), server
-//! configuration (read/update `config.yml`), and a graceful restart
-//! endpoint. The write endpoints here go through
+//! configuration (read/update `config.yml`) and a graceful restart
+//! endpoint. Drain and read-only mode live in
+//! [`super::ops::admin_drain`], live search and cache tuning in
+//! [`super::ops::admin_config`] and model hot swap in
+//! [`super::ops::admin_embedding`]. The write endpoints here go through
 //! [`crate::server::auth_handlers::require_admin_for_rest`] so that in
 //! mixed auth / no-auth deployments they still enforce Role::Admin when
 //! an `AuthHandlerState` is configured.
 
+use std::time::Duration;
+
 use axum::extract::State;
 use axum::response::Json;
 use serde_json::{Value, json};
@@ -205,7 +210,6 @@ pub async fn restart_server(
     State(_state): State<VectorizerServer>,
 ) -> Result<Json<Value>, ErrorResponse> {
     use std::sync::atomic::{AtomicBool, Ordering};
-    use std::time::Duration;
 
     static RESTART_IN_PROGRESS: AtomicBool = AtomicBool::new(false);
 
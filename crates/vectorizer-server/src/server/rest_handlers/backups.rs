@@ -249,6 +249,13 @@ pub async fn restore_backup(
                 sharding: None,
                 graph: None,
                 encryption: None,
+                id_policy: None,
+                quota: None,
+                scoring: None,
+                tenant_partition: None,
+                dedup: None,
+                chunking: None,
+                full_text: None,
             };
 
             state
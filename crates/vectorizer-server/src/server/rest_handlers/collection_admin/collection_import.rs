@@ -0,0 +1,540 @@
+//! Bulk import into an existing collection.
+//!
+//! - `import_collection` — POST /collections/{name}/import
+//!
+//! The body is either a JSONL document stream (`?format=jsonl`, or a
+//! `application/x-ndjson` / `application/jsonl` body) or a Parquet file
+//! (`?format=parquet`, handled by `super::transfer`, feature `parquet`).
+//!
+//! JSONL lines are `{"id": "...", "text": "...", "payload": {...}}`;
+//! only `text` is required. The body is read chunk by chunk, lines are
+//! embedded server-side with the collection's embedding provider and
+//! inserted in batches of `?batch_size=` (default 256), so an import is
+//! not bounded by the request size limit — only a single line is. The
+//! response is an NDJSON stream of events:
+//!
+//! - `{"event": "progress", "offset", "imported", "failed", "errors"}`
+//!   after every committed batch
+//! - `{"event": "done", ...}` with the same fields once the body ends
+//! - `{"event": "error", "offset", "error_type", "error", ...}` with
+//!   the same counters when the import stops early
+//!
+//! `offset` counts the body lines handled so far, committed or failed.
+//! Re-sending the same body with `?offset=<offset>` skips those lines,
+//! so an interrupted import resumes after its last committed batch.
+//! Invalid lines do not stop the import; they are counted in `failed`
+//! and listed in `errors` with their 1-based line number.
+//!
+//! Every JSONL import is a job (`job_id` in each event): `GET /jobs/{id}`
+//! reports the lines handled so far as `processed`, and
+//! `DELETE /jobs/{id}` stops the import before its next batch.
+
+#![allow(missing_docs)]
+
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use axum::Extension;
+use axum::body::{Body, Bytes};
+use axum::extract::{Path, Query, State};
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use tokio::sync::mpsc;
+use tokio_stream::StreamExt;
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::{info, warn};
+use vectorizer::hub::middleware::RequestTenantContext;
+use vectorizer::jobs::{COLLECTION_IMPORT, JOBS, Job};
+use vectorizer::models::{Payload, Vector};
+
+use super::common::admit_upsert;
+use super::insert::{
+    check_insert_quota, mark_collection_dirty, record_insert_usage, validate_client_id,
+};
+use super::jobs::JobOwner;
+use crate::server::VectorizerServer;
+use crate::server::error_middleware::{
+    ErrorResponse, create_error_response, create_validation_error,
+};
+
+/// Media types that select JSONL when `?format=` is absent.
+const JSONL_CONTENT_TYPES: &[&str] = &[
+    "application/x-ndjson",
+    "application/jsonl",
+    "application/x-jsonlines",
+];
+
+/// Media type of the JSONL progress stream.
+const NDJSON_CONTENT_TYPE: &str = "application/x-ndjson";
+
+const DEFAULT_BATCH_SIZE: usize = 256;
+const MAX_BATCH_SIZE: usize = 4096;
+
+/// Line errors carried by one event; `failed` keeps the full count.
+const MAX_REPORTED_ERRORS: usize = 20;
+
+/// Events queued ahead of a slow client before the import waits.
+const QUEUED_EVENTS: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ImportFormat {
+    Jsonl,
+    Parquet,
+}
+
+impl ImportFormat {
+    /// `?format=` wins; otherwise a JSONL content type selects JSONL and
+    /// anything else is taken for a Parquet file.
+    fn resolve(
+        params: &HashMap<String, String>,
+        headers: &HeaderMap,
+    ) -> Result<Self, ErrorResponse> {
+        match params.get("format").map(String::as_str) {
+            Some("jsonl") => Ok(Self::Jsonl),
+            Some("parquet") => Ok(Self::Parquet),
+            Some(other) => Err(create_validation_error(
+                "format",
+                &format!("unsupported format '{other}', expected 'jsonl' or 'parquet'"),
+            )),
+            None => {
+                let content_type = headers
+                    .get(header::CONTENT_TYPE)
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.split(';').next())
+                    .map(str::trim)
+                    .unwrap_or_default();
+                if JSONL_CONTENT_TYPES
+                    .iter()
+                    .any(|t| t.eq_ignore_ascii_case(content_type))
+                {
+                    Ok(Self::Jsonl)
+                } else {
+                    Ok(Self::Parquet)
+                }
+            }
+        }
+    }
+}
+
+fn usize_param(
+    params: &HashMap<String, String>,
+    name: &str,
+    default: usize,
+) -> Result<usize, ErrorResponse> {
+    params.get(name).map_or(Ok(default), |raw| {
+        raw.parse()
+            .map_err(|_| create_validation_error(name, "expected a non-negative integer"))
+    })
+}
+
+/// POST /collections/{name}/import
+///
+/// Query: `format` (`jsonl` | `parquet`), and for JSONL `offset` (body
+/// lines to skip, default 0) and `batch_size` (1-4096, default 256).
+/// Unknown collections, bad parameters and a missing embedding provider
+/// are refused before the body is read; see the module docs for the
+/// JSONL event stream.
+pub async fn import_collection(
+    State(state): State<VectorizerServer>,
+    Path(collection_name): Path<String>,
+    tenant_ctx: Option<Extension<RequestTenantContext>>,
+    owner: JobOwner,
+    Query(params): Query<HashMap<String, String>>,
+    headers: HeaderMap,
+    body: Body,
+) -> Result<Response, ErrorResponse> {
+    match ImportFormat::resolve(&params, &headers)? {
+        ImportFormat::Jsonl => {
+            import_jsonl(state, collection_name, tenant_ctx, owner, &params, body).await
+        }
+        ImportFormat::Parquet => import_parquet(state, collection_name, tenant_ctx, body).await,
+    }
+}
+
+#[cfg(feature = "parquet")]
+async fn import_parquet(
+    state: VectorizerServer,
+    collection_name: String,
+    tenant_ctx: Option<Extension<RequestTenantContext>>,
+    body: Body,
+) -> Result<Response, ErrorResponse> {
+    let limit = state.max_request_size_mb * 1024 * 1024;
+    let body = axum::body::to_bytes(body, limit).await.map_err(|_| {
+        create_error_response(
+            "payload_too_large",
+            &format!(
+                "Parquet imports are limited to {}MB",
+                state.max_request_size_mb
+            ),
+            StatusCode::PAYLOAD_TOO_LARGE,
+        )
+    })?;
+    super::transfer::import_file(state, collection_name, tenant_ctx, body)
+        .await
+        .map(IntoResponse::into_response)
+}
+
+#[cfg(not(feature = "parquet"))]
+async fn import_parquet(
+    _state: VectorizerServer,
+    _collection_name: String,
+    _tenant_ctx: Option<Extension<RequestTenantContext>>,
+    _body: Body,
+) -> Result<Response, ErrorResponse> {
+    Err(create_validation_error(
+        "format",
+        "this server was built without Parquet support, use format=jsonl",
+    ))
+}
+
+async fn import_jsonl(
+    state: VectorizerServer,
+    collection_name: String,
+    tenant_ctx: Option<Extension<RequestTenantContext>>,
+    owner: JobOwner,
+    params: &HashMap<String, String>,
+    body: Body,
+) -> Result<Response, ErrorResponse> {
+    let skip = usize_param(params, "offset", 0)? as u64;
+    let batch_size = usize_param(params, "batch_size", DEFAULT_BATCH_SIZE)?;
+    if !(1..=MAX_BATCH_SIZE).contains(&batch_size) {
+        return Err(create_validation_error(
+            "batch_size",
+            &format!("must be between 1 and {MAX_BATCH_SIZE}"),
+        ));
+    }
+    let configured = state
+        .store
+        .get_collection(&collection_name)
+        .map(|c| c.config().embedding_provider.clone())
+        .map_err(ErrorResponse::from)?;
+    // The collection's own provider when this server has it, else the
+    // default one that text inserts use
+    let provider = if state.embedding_manager.has_provider(&configured) {
+        configured
+    } else {
+        state
+            .embedding_manager
+            .get_default_provider_name()
+            .map(str::to_string)
+            .ok_or_else(|| {
+                create_validation_error(
+                    "collection",
+                    "no embedding provider is available for server-side embedding",
+                )
+            })?
+    };
+
+    info!(
+        "Importing JSONL into '{}' (provider: {}, batch size: {}, offset: {})",
+        collection_name, provider, batch_size, skip
+    );
+    let (tx, rx) = mpsc::channel(QUEUED_EVENTS);
+    let import = JsonlImport {
+        max_line_bytes: state.max_request_size_mb * 1024 * 1024,
+        job: JOBS.start_owned(COLLECTION_IMPORT, &collection_name, owner.0),
+        state,
+        collection: collection_name,
+        provider,
+        tenant_ctx,
+        batch_size,
+        skip,
+        tx,
+        lines: 0,
+        offset: skip,
+        batch: Vec::new(),
+        imported: 0,
+        failed: 0,
+        errors: Vec::new(),
+    };
+    tokio::spawn(import.run(body));
+
+    Ok((
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, NDJSON_CONTENT_TYPE)],
+        Body::from_stream(ReceiverStream::new(rx)),
+    )
+        .into_response())
+}
+
+/// One JSONL line.
+#[derive(Debug, Deserialize)]
+struct ImportLine {
+    #[serde(default)]
+    id: Option<String>,
+    text: String,
+    #[serde(default)]
+    payload: Option<Value>,
+}
+
+/// A parsed line waiting for its batch.
+struct PendingDocument {
+    id: String,
+    text: String,
+    payload: Value,
+}
+
+/// State of one streaming JSONL import.
+struct JsonlImport {
+    state: VectorizerServer,
+    collection: String,
+    provider: String,
+    tenant_ctx: Option<Extension<RequestTenantContext>>,
+    batch_size: usize,
+    max_line_bytes: usize,
+    job: Arc<Job>,
+    /// Lines the client asked to skip (`?offset=`)
+    skip: u64,
+    tx: mpsc::Sender<Result<Bytes, Infallible>>,
+    /// Lines read from the body so far
+    lines: u64,
+    /// Lines handled up to the last committed batch
+    offset: u64,
+    batch: Vec<PendingDocument>,
+    imported: u64,
+    failed: u64,
+    /// Line errors since the last event
+    errors: Vec<Value>,
+}
+
+impl JsonlImport {
+    async fn run(mut self, body: Body) {
+        let outcome = self.read(body).await;
+        self.job.finish(&match &outcome {
+            Ok(true) => Ok(json!({
+                "offset": self.offset,
+                "imported": self.imported,
+                "failed": self.failed,
+            })),
+            Ok(false) => Err("the client disconnected".to_string()),
+            Err(e) => Err(e.message.clone()),
+        });
+        let event = match outcome {
+            Ok(true) => self.counters("done"),
+            // The client went away, nobody is left to tell
+            Ok(false) => return,
+            Err(e) => {
+                warn!(
+                    "JSONL import into '{}' stopped at line {}: {}",
+                    self.collection, self.offset, e.message
+                );
+                json!({
+                    "event": "error",
+                    "job_id": self.job.id(),
+                    "offset": self.offset,
+                    "imported": self.imported,
+                    "failed": self.failed,
+                    "error_type": e.error_type,
+                    "error": e.message,
+                })
+            }
+        };
+        self.emit(event).await;
+        info!(
+            "JSONL import into '{}': {} imported, {} failed",
+            self.collection, self.imported, self.failed
+        );
+    }
+
+    /// Read the body line by line. `Ok(false)` when the client stopped
+    /// listening.
+    async fn read(&mut self, body: Body) -> Result<bool, ErrorResponse> {
+        let mut stream = body.into_data_stream();
+        let mut pending: Vec<u8> = Vec::new();
+        loop {
+            let chunk = match stream.next().await {
+                Some(Ok(chunk)) => Some(chunk),
+                Some(Err(e)) => {
+                    return Err(create_error_response(
+                        "bad_request",
+                        &format!("Failed to read request body: {e}"),
+                        StatusCode::BAD_REQUEST,
+                    ));
+                }
+                None => None,
+            };
+            let Some(chunk) = chunk else {
+                if !pending.is_empty() {
+                    let line = std::mem::take(&mut pending);
+                    if !self.line(&line).await? {
+                        return Ok(false);
+                    }
+                }
+                if !self.batch.is_empty() {
+                    self.insert_batch().await?;
+                }
+                self.advance();
+                return Ok(true);
+            };
+            pending.extend_from_slice(&chunk);
+            while let Some(end) = pending.iter().position(|b| *b == b'\n') {
+                let line: Vec<u8> = pending.drain(..=end).collect();
+                if !self.line(&line[..end]).await? {
+                    return Ok(false);
+                }
+            }
+            if pending.len() > self.max_line_bytes {
+                return Err(create_error_response(
+                    "payload_too_large",
+                    &format!(
+                        "line {} exceeds the {}MB request size limit",
+                        self.lines + 1,
+                        self.state.max_request_size_mb
+                    ),
+                    StatusCode::PAYLOAD_TOO_LARGE,
+                ));
+            }
+        }
+    }
+
+    /// Take one line, committing the batch when it is full.
+    async fn line(&mut self, line: &[u8]) -> Result<bool, ErrorResponse> {
+        self.lines += 1;
+        if self.lines <= self.skip {
+            return Ok(true);
+        }
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        if line.iter().all(u8::is_ascii_whitespace) {
+            return Ok(true);
+        }
+        match self.parse(line) {
+            Ok(document) => self.batch.push(document),
+            Err(reason) => {
+                self.failed += 1;
+                if self.errors.len() < MAX_REPORTED_ERRORS {
+                    self.errors
+                        .push(json!({ "line": self.lines, "error": reason }));
+                }
+            }
+        }
+        if self.batch.len() >= self.batch_size {
+            return self.commit().await;
+        }
+        Ok(true)
+    }
+
+    fn parse(&self, line: &[u8]) -> Result<PendingDocument, String> {
+        let line: ImportLine =
+            serde_json::from_slice(line).map_err(|e| format!("invalid JSON line: {e}"))?;
+        if line.text.trim().is_empty() {
+            return Err("text must not be empty".to_string());
+        }
+        let id = match line.id {
+            Some(id) => {
+                validate_client_id(&id)?;
+                id
+            }
+            None => self.state.store.new_vector_id(&self.collection),
+        };
+        let mut payload = match line.payload {
+            None | Some(Value::Null) => json!({}),
+            Some(Value::Object(fields)) => Value::Object(fields),
+            Some(_) => return Err("payload must be a JSON object".to_string()),
+        };
+        if let Value::Object(fields) = &mut payload {
+            fields
+                .entry("content")
+                .or_insert_with(|| Value::String(line.text.clone()));
+        }
+        Ok(PendingDocument {
+            id,
+            text: line.text,
+            payload,
+        })
+    }
+
+    /// Embed and insert the full batch, then report progress.
+    async fn commit(&mut self) -> Result<bool, ErrorResponse> {
+        self.insert_batch().await?;
+        self.advance();
+        let event = self.counters("progress");
+        Ok(self.emit(event).await)
+    }
+
+    /// Mark the lines read so far as handled.
+    fn advance(&mut self) {
+        self.job.advance(self.lines.saturating_sub(self.offset));
+        self.offset = self.lines;
+    }
+
+    async fn insert_batch(&mut self) -> Result<(), ErrorResponse> {
+        self.job.check_cancelled().map_err(ErrorResponse::from)?;
+        // The drain middleware only covers the request itself, so each
+        // batch is admitted on its own
+        let _guard = self.state.drain.try_enter().map_err(ErrorResponse::from)?;
+        let _ticket = admit_upsert(&self.state.upsert_queue, &self.collection)?;
+        let _tenant_admission = check_insert_quota(
+            &self.state,
+            self.tenant_ctx.as_ref(),
+            &self.collection,
+            self.batch.len(),
+        )
+        .await?;
+
+        let texts: Vec<&str> = self.batch.iter().map(|d| d.text.as_str()).collect();
+        let embeddings = self
+            .state
+            .embedding_manager
+            .embed_batch_with_provider(&texts, &self.provider)
+            .map_err(|e| {
+                create_error_response(
+                    "embedding_failed",
+                    &format!("Failed to generate embeddings: {e}"),
+                    StatusCode::BAD_REQUEST,
+                )
+            })?;
+        let embedding_len = embeddings.first().map_or(0, Vec::len);
+        let mut ids = Vec::with_capacity(self.batch.len());
+        let vectors: Vec<Vector> = self
+            .batch
+            .drain(..)
+            .zip(embeddings)
+            .map(|(document, data)| {
+                ids.push(document.id.clone());
+                Vector {
+                    id: document.id,
+                    data,
+                    sparse: None,
+                    payload: Some(Payload::new(document.payload)),
+                    document_id: None,
+                }
+            })
+            .collect();
+        self.state
+            .store
+            .insert(&self.collection, vectors)
+            .map_err(ErrorResponse::from)?;
+
+        self.imported += ids.len() as u64;
+        record_insert_usage(
+            &self.state,
+            &self.collection,
+            embedding_len,
+            ids.len() as u64,
+        )
+        .await;
+        mark_collection_dirty(&self.state, &self.collection, &ids);
+        Ok(())
+    }
+
+    fn counters(&mut self, event: &str) -> Value {
+        json!({
+            "event": event,
+            "job_id": self.job.id(),
+            "offset": self.offset,
+            "imported": self.imported,
+            "failed": self.failed,
+            "errors": std::mem::take(&mut self.errors),
+        })
+    }
+
+    /// Send one event line; `false` once the client is gone.
+    async fn emit(&self, event: Value) -> bool {
+        let mut line = event.to_string();
+        line.push('\n');
+        self.tx.send(Ok(Bytes::from(line))).await.is_ok()
+    }
+}
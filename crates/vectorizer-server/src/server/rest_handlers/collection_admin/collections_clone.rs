@@ -0,0 +1,138 @@
+//! `clone_collection` — POST /collections/{name}/clone, a copy of a
+//! collection under a different config, run as a job.
+
+use axum::extract::{Path, State};
+use axum::response::Json;
+use serde_json::{Value, json};
+use tracing::info;
+
+use super::jobs::{JobOwner, run_job};
+use crate::server::VectorizerServer;
+use crate::server::error_middleware::ErrorResponse;
+
+/// POST /collections/{name}/clone
+///
+/// Body: `{"target": "…", "config": {…}}`
+///
+/// Copies every vector of the collection into a new `target` collection
+/// as a background job and returns its id; `GET /jobs/{id}` reports
+/// progress and, once done, how many vectors were copied.
+///
+/// `config` holds the fields of the target's config that differ from the
+/// source's (e.g. `{"quantization": {"type": "binary"}}` or
+/// `{"hnsw_config": {"m": 32, …}}`). Vectors are re-encoded for the
+/// target's quantization as they are inserted; when `dimension` or
+/// `embedding_provider` changes they are re-embedded from their payload
+/// `content` / `text` instead, and vectors without text are skipped.
+/// The source stays readable and writable throughout.
+pub async fn clone_collection(
+    State(state): State<VectorizerServer>,
+    Path(collection_name): Path<String>,
+    owner: JobOwner,
+    Json(payload): Json<Value>,
+) -> Result<Json<Value>, ErrorResponse> {
+    use crate::server::error_middleware::create_validation_error;
+
+    let target = payload
+        .get("target")
+        .and_then(|t| t.as_str())
+        .filter(|t| !t.is_empty())
+        .ok_or_else(|| create_validation_error("target", "missing or invalid target parameter"))?
+        .to_string();
+    if state.store.get_collection(&target).is_ok() {
+        return Err(ErrorResponse::from(
+            vectorizer_core::error::VectorizerError::CollectionAlreadyExists(target),
+        ));
+    }
+
+    let source_config = state
+        .store
+        .get_collection(&collection_name)
+        .map_err(ErrorResponse::from)?
+        .config()
+        .clone();
+    let config = match payload.get("config") {
+        None | Some(Value::Null) => source_config.clone(),
+        Some(Value::Object(changes)) => {
+            let mut merged = serde_json::to_value(&source_config)
+                .map_err(|e| create_validation_error("config", &e.to_string()))?;
+            if let Some(fields) = merged.as_object_mut() {
+                for (key, value) in changes {
+                    fields.insert(key.clone(), value.clone());
+                }
+            }
+            serde_json::from_value::<vectorizer::models::CollectionConfig>(merged)
+                .map_err(|e| create_validation_error("config", &e.to_string()))?
+        }
+        Some(_) => {
+            return Err(create_validation_error(
+                "config",
+                "config must be a JSON object",
+            ));
+        }
+    };
+
+    // Re-embedded vectors come from the target's provider, so it must
+    // exist and produce the target's dimension
+    let reembed = vectorizer::db::VectorStore::clone_needs_reembedding(&source_config, &config);
+    if reembed {
+        let provider = &config.embedding_provider;
+        if !state.embedding_manager.has_provider(provider) {
+            return Err(ErrorResponse::from(
+                vectorizer_core::error::VectorizerError::UnsupportedProvider {
+                    requested: provider.clone(),
+                    available: state.embedding_manager.list_providers(),
+                },
+            ));
+        }
+        if let Ok(provider_dim) = state.embedding_manager.get_provider_dimension(provider)
+            && provider_dim != config.dimension
+        {
+            return Err(ErrorResponse::from(
+                vectorizer_core::error::VectorizerError::ProviderDimensionMismatch {
+                    provider: provider.clone(),
+                    provider_dimension: provider_dim,
+                    requested_dimension: config.dimension,
+                },
+            ));
+        }
+    }
+
+    let server = state.clone();
+    let source = collection_name.clone();
+    let target_name = target.clone();
+    let (job_id, _) = run_job(
+        vectorizer::jobs::COLLECTION_CLONE,
+        &collection_name,
+        owner,
+        false,
+        move |job| {
+            let embed = |text: &str| server.embed_for_collection(&target_name, text);
+            let report = server.store.clone_collection(
+                &source,
+                &target_name,
+                Some(config),
+                Some(&embed),
+                job,
+            )?;
+            if let Some(ref auto_save) = server.auto_save_manager {
+                auto_save.mark_changed();
+            }
+            Ok(report)
+        },
+    )
+    .await?;
+
+    info!(
+        "Cloning collection '{}' into '{}' (job {})",
+        collection_name, target, job_id
+    );
+
+    Ok(Json(json!({
+        "job_id": job_id,
+        "source": collection_name,
+        "target": target,
+        "status": "started",
+        "reembed": reembed,
+    })))
+}
@@ -0,0 +1,430 @@
+//! Collection creation.
+//!
+//! - `create_collection`         — POST   /collections
+//! - `list_collection_templates` — GET    /collections/templates
+
+use axum::Extension;
+use axum::extract::State;
+use axum::response::Json;
+use serde_json::{Value, json};
+use tracing::{debug, info, warn};
+use vectorizer::cluster::raft_node::ClusterCommand;
+use vectorizer::hub::middleware::RequestTenantContext;
+use vectorizer::hub::{TenantUsage, WriteAdmission};
+
+use super::common::{collection_metrics_uuid, extract_tenant_id, publish_collections_snapshot};
+use crate::server::VectorizerServer;
+use crate::server::error_middleware::ErrorResponse;
+
+/// POST /collections — create a new collection
+pub async fn create_collection(
+    State(state): State<VectorizerServer>,
+    tenant_ctx: Option<Extension<RequestTenantContext>>,
+    Json(payload): Json<Value>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let name = payload
+        .get("name")
+        .and_then(|n| n.as_str())
+        .ok_or_else(|| {
+            crate::server::error_middleware::create_validation_error(
+                "name",
+                "missing or invalid name parameter",
+            )
+        })?;
+
+    // Optional collection template (`collection_templates:` in config.yml).
+    // Explicit request fields override the template; the template
+    // overrides server defaults.
+    let template = match payload.get("template").and_then(|t| t.as_str()) {
+        Some(template_name) => Some(
+            state
+                .collection_templates
+                .get(template_name)
+                .ok_or_else(|| {
+                    crate::server::error_middleware::create_validation_error(
+                        "template",
+                        &format!("unknown collection template '{}'", template_name),
+                    )
+                })?
+                .clone(),
+        ),
+        None => None,
+    };
+
+    let dimension = payload
+        .get("dimension")
+        .and_then(|d| d.as_u64())
+        .map(|d| d as usize)
+        .or_else(|| template.as_ref().and_then(|t| t.dimension))
+        .unwrap_or(512);
+    let template_metric = template
+        .as_ref()
+        .and_then(|t| t.metric)
+        .map(|m| m.to_string());
+    let metric = payload
+        .get("metric")
+        .and_then(|m| m.as_str())
+        .or(template_metric.as_deref())
+        .unwrap_or("cosine");
+
+    // phase33 (#306): honour the `embedding_provider` field instead of
+    // silently coercing every collection to bm25. When omitted, fall
+    // back to the server's configured default — never to a hardcoded
+    // provider name.
+    let requested_provider = payload
+        .get("embedding_provider")
+        .and_then(|p| p.as_str())
+        .map(|s| s.to_string())
+        .or_else(|| template.as_ref().and_then(|t| t.embedding_provider.clone()));
+    let resolved_provider = match requested_provider.clone() {
+        Some(name) => {
+            if !state.embedding_manager.has_provider(&name) {
+                return Err(ErrorResponse::from(
+                    vectorizer_core::error::VectorizerError::UnsupportedProvider {
+                        requested: name,
+                        available: state.embedding_manager.list_providers(),
+                    },
+                ));
+            }
+            name
+        }
+        None => state
+            .embedding_manager
+            .get_default_provider_name()
+            .unwrap_or("bm25")
+            .to_string(),
+    };
+    // Reject dimension mismatch against the provider's native dimension
+    // before persisting the collection — silent quantization to a
+    // different size is what caused the BM25-512 coercion downstream.
+    if let Ok(provider_dim) = state
+        .embedding_manager
+        .get_provider_dimension(&resolved_provider)
+    {
+        if provider_dim != dimension {
+            return Err(ErrorResponse::from(
+                vectorizer_core::error::VectorizerError::ProviderDimensionMismatch {
+                    provider: resolved_provider.clone(),
+                    provider_dimension: provider_dim,
+                    requested_dimension: dimension,
+                },
+            ));
+        }
+    }
+
+    // Extract tenant ID for multi-tenant mode
+    let tenant_id = extract_tenant_id(&tenant_ctx);
+
+    info!(
+        "Creating collection: {} with dimension {} and metric {} (tenant: {:?})",
+        name, dimension, metric, tenant_id
+    );
+
+    // Check tenant quotas if HiveHub is enabled, before anything is
+    // created. The admission is held until the collection exists.
+    let _tenant_admission = match state.hub_manager {
+        Some(ref hub_manager) => {
+            let tenant_id_str = tenant_id
+                .map(|id| id.to_string())
+                .unwrap_or_else(|| "default".to_string());
+            let requested = TenantUsage {
+                collections: 1,
+                ..TenantUsage::default()
+            };
+            hub_manager
+                .admit_write(&state.store, &tenant_id_str, requested)
+                .await
+                .map_err(|e| {
+                    warn!(
+                        "Collection creation denied for tenant {}: {}",
+                        tenant_id_str, e
+                    );
+                    ErrorResponse::from(e)
+                })?
+        }
+        None => WriteAdmission::default(),
+    };
+
+    // Parse graph configuration if provided
+    let graph_config = payload.get("graph").and_then(|g| {
+        if let Some(enabled) = g.get("enabled").and_then(|e| e.as_bool()) {
+            if enabled {
+                Some(vectorizer::models::GraphConfig {
+                    enabled: true,
+                    auto_relationship: vectorizer::models::AutoRelationshipConfig::default(),
+                    entity_extraction: g
+                        .get("entity_extraction")
+                        .and_then(|e| serde_json::from_value(e.clone()).ok())
+                        .unwrap_or_default(),
+                })
+            } else {
+                None
+            }
+        } else {
+            None
+        }
+    });
+
+    // Storage type: explicit `storage` field, then the template, then
+    // MMap when the cluster enforces it (`enforce_mmap_storage`), else
+    // Memory. An explicit "memory" is rejected when MMap is enforced.
+    let requested_storage = match payload.get("storage").and_then(|s| s.as_str()) {
+        Some(s) => Some(s.parse::<vectorizer::models::StorageType>().map_err(|e| {
+            crate::server::error_middleware::create_validation_error("storage", &e)
+        })?),
+        None => template.as_ref().and_then(|t| t.storage),
+    };
+    let cluster_config = state.cluster_manager.as_ref().map(|m| m.config());
+    let storage_type = match (requested_storage, cluster_config) {
+        (Some(storage), Some(cluster_config)) => {
+            let result = vectorizer::cluster::ClusterConfigValidator::new()
+                .validate_storage_type(cluster_config, &storage);
+            if result.has_errors() {
+                return Err(crate::server::error_middleware::create_validation_error(
+                    "storage",
+                    &result.error_message(),
+                ));
+            }
+            storage
+        }
+        (Some(storage), None) => storage,
+        (None, Some(cluster_config))
+            if cluster_config.enabled && cluster_config.memory.enforce_mmap_storage =>
+        {
+            info!(
+                "Cluster mode enforces MMap storage — using MMap for collection '{}'",
+                name
+            );
+            vectorizer::models::StorageType::Mmap
+        }
+        (None, _) => vectorizer::models::StorageType::Memory,
+    };
+
+    // Vector ID generator and rules, checked by the store on create.
+    let id_policy = payload
+        .get("id_policy")
+        .map(|p| serde_json::from_value::<vectorizer::models::IdPolicy>(p.clone()))
+        .transpose()
+        .map_err(|e| {
+            crate::server::error_middleware::create_validation_error("id_policy", &e.to_string())
+        })?;
+
+    // Size quota and its over-quota policy, checked by the store on create.
+    let quota = payload
+        .get("quota")
+        .map(|q| serde_json::from_value::<vectorizer::models::CollectionQuota>(q.clone()))
+        .transpose()
+        .map_err(|e| {
+            crate::server::error_middleware::create_validation_error("quota", &e.to_string())
+        })?;
+
+    // Custom scoring expression, parsed here so a malformed one is a 400.
+    let scoring = payload
+        .get("scoring")
+        .map(|s| serde_json::from_value::<vectorizer::models::ScoringConfig>(s.clone()))
+        .transpose()
+        .map_err(|e| {
+            crate::server::error_middleware::create_validation_error("scoring", &e.to_string())
+        })?;
+
+    // Tenant partition of a shared collection (`{}` keys on `tenant_id`).
+    let tenant_partition = payload
+        .get("tenant_partition")
+        .map(|p| serde_json::from_value::<vectorizer::models::TenantPartitionConfig>(p.clone()))
+        .transpose()
+        .map_err(|e| {
+            crate::server::error_middleware::create_validation_error(
+                "tenant_partition",
+                &e.to_string(),
+            )
+        })?;
+
+    // Deduplication of inserted vectors (`{}` skips repeated `content_hash`es).
+    let dedup = payload
+        .get("dedup")
+        .map(|d| serde_json::from_value::<vectorizer::models::DedupPolicy>(d.clone()))
+        .transpose()
+        .map_err(|e| {
+            crate::server::error_middleware::create_validation_error("dedup", &e.to_string())
+        })?;
+
+    // How uploads and text inserts are split (`{"strategy": "markdown"}`).
+    let chunking = payload
+        .get("chunking")
+        .map(|c| serde_json::from_value::<vectorizer::chunking::ChunkingConfig>(c.clone()))
+        .transpose()
+        .map_err(|e| {
+            crate::server::error_middleware::create_validation_error("chunking", &e.to_string())
+        })?;
+
+    // Full-text indexing of payload text (`{}` indexes `content` and `text`).
+    let full_text = payload
+        .get("full_text")
+        .map(|f| serde_json::from_value::<vectorizer::models::FullTextConfig>(f.clone()))
+        .transpose()
+        .map_err(|e| {
+            crate::server::error_middleware::create_validation_error("full_text", &e.to_string())
+        })?;
+
+    // Create collection configuration
+    let config = vectorizer::models::CollectionConfig {
+        dimension,
+        metric: match metric {
+            "cosine" => vectorizer::models::DistanceMetric::Cosine,
+            "euclidean" => vectorizer::models::DistanceMetric::Euclidean,
+            "dot" | "dot_product" | "dotproduct" => vectorizer::models::DistanceMetric::DotProduct,
+            _ => vectorizer::models::DistanceMetric::Cosine,
+        },
+        hnsw_config: template
+            .as_ref()
+            .map(|t| t.hnsw_config())
+            .unwrap_or_default(),
+        quantization: template
+            .as_ref()
+            .and_then(|t| t.quantization.clone())
+            .unwrap_or(vectorizer::models::QuantizationConfig::None),
+        compression: vectorizer::models::CompressionConfig::default(),
+        embedding_provider: resolved_provider,
+        normalization: None,
+        storage_type: Some(storage_type),
+        sharding: None,
+        graph: graph_config,
+        encryption: None,
+        id_policy,
+        quota,
+        scoring,
+        tenant_partition,
+        dedup,
+        chunking,
+        full_text,
+    };
+
+    // Canonical spelling, so "dot" and "dot_product" are the same
+    // definition in the Raft log.
+    let cluster_metric = config.metric.to_string();
+
+    // Actually create the collection in the store
+    // In multi-tenant mode, associate collection with the owner
+    if let Some(owner_id) = tenant_id {
+        state
+            .store
+            .create_collection_with_owner(name, config, owner_id)
+            .map_err(|e| ErrorResponse::from(e))?;
+    } else {
+        state
+            .store
+            .create_collection(name, config)
+            .map_err(|e| ErrorResponse::from(e))?;
+    }
+
+    // In HA mode the definition then goes through the Raft log: the leader
+    // orders concurrent creates, so a second create with a different
+    // dimension/metric is rejected (409) instead of both nodes diverging.
+    // The local create above already ran validation and quotas, so only a
+    // collection this node can hold is proposed; a failed proposal undoes
+    // it and takes whatever definition won instead.
+    if let Err(e) = state
+        .propose_cluster_command(ClusterCommand::CreateCollection {
+            name: name.to_string(),
+            dimension,
+            metric: cluster_metric,
+        })
+        .await
+    {
+        if let Err(undo) = state.store.delete_collection(name) {
+            warn!("Failed to undo local create of '{}': {}", name, undo);
+        }
+        if let Some(ref raft) = state.raft_manager
+            && let Some((dimension, metric)) = raft.state().await.collections.get(name).cloned()
+        {
+            vectorizer::cluster::raft_watcher::apply_store_command(
+                &state.store,
+                &ClusterCommand::CreateCollection {
+                    name: name.to_string(),
+                    dimension,
+                    metric,
+                },
+            );
+        }
+        return Err(e);
+    }
+
+    if let Some(ref template) = template {
+        let indexes = template.payload_index_configs();
+        if !indexes.is_empty() {
+            let collection = state
+                .store
+                .get_collection(name)
+                .map_err(|e| ErrorResponse::from(e))?;
+            for index in indexes {
+                collection.add_payload_index(index);
+            }
+        }
+    }
+
+    // Replicate collection creation to replicas.
+    // Check both static master_node and HA manager (Raft-managed master).
+    let active_master: Option<std::sync::Arc<vectorizer::replication::MasterNode>> = state
+        .master_node
+        .clone()
+        .or_else(|| state.ha_manager.as_ref().and_then(|ha| ha.master_node()));
+
+    if let Some(ref master) = active_master {
+        let op = vectorizer::replication::VectorOperation::CreateCollection {
+            name: name.to_string(),
+            config: vectorizer::replication::CollectionConfigData {
+                dimension,
+                metric: metric.to_string(),
+            },
+            owner_id: tenant_id.map(|id| id.to_string()),
+        };
+        master.replicate(op);
+        debug!("Replicated collection creation: {}", name);
+    }
+
+    // Mark changes for auto-save
+    if let Some(ref auto_save) = state.auto_save_manager {
+        auto_save.mark_changed();
+    }
+
+    // Record usage metrics if HiveHub is enabled
+    if let Some(ref hub_manager) = state.hub_manager {
+        let mut metrics = vectorizer::hub::UsageMetrics::new();
+        metrics.record_collection_create();
+        // Stable UUID derived from the collection name so subsequent calls
+        // aggregate under the same Hub usage row.
+        let collection_id = collection_metrics_uuid(&name);
+        if let Err(e) = hub_manager.record_usage(collection_id, metrics).await {
+            warn!("Failed to record collection creation usage: {}", e);
+        }
+    }
+
+    info!("Collection '{}' created successfully", name);
+    publish_collections_snapshot(&state);
+    Ok(Json(json!({
+        "message": format!("Collection '{}' created successfully", name),
+        "collection": name,
+        "dimension": dimension,
+        "metric": metric,
+        "storage": storage_type.as_str(),
+        "template": payload.get("template").and_then(|t| t.as_str()),
+    })))
+}
+
+/// GET /collections/templates — list the collection templates declared
+/// under `collection_templates:` in config.yml.
+pub async fn list_collection_templates(State(state): State<VectorizerServer>) -> Json<Value> {
+    let templates: Vec<Value> = state
+        .collection_templates
+        .iter()
+        .map(|(name, template)| {
+            let mut entry = json!(template);
+            entry["name"] = json!(name);
+            entry
+        })
+        .collect();
+    Json(json!({
+        "templates": templates,
+        "total": templates.len(),
+    }))
+}
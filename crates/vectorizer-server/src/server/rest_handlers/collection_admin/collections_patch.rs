@@ -0,0 +1,213 @@
+//! In-place changes to a collection's settings.
+//!
+//! - `patch_collection`    — PATCH  /collections/{name}
+//! - `set_collection_cold` — POST   /collections/{name}/cold
+
+use axum::extract::{Path, State};
+use axum::response::Json;
+use serde_json::{Value, json};
+use tracing::info;
+
+use crate::server::VectorizerServer;
+use crate::server::error_middleware::ErrorResponse;
+
+/// Fields fixed when a collection is created; `PATCH` rejects them.
+const IMMUTABLE_COLLECTION_FIELDS: &[&str] = &[
+    "name",
+    "dimension",
+    "metric",
+    "hnsw_config",
+    "storage",
+    "embedding_provider",
+    "normalization",
+    "encryption",
+    "sharding",
+    "tenant_partition",
+];
+
+/// PATCH /collections/{name} — change mutable settings in place.
+///
+/// Body (every field optional):
+/// `{"ef_search": 128, "quantization": "sq8", "compression": {...},
+/// "ttl_secs": 3600, "payload_indexes": [{"field_name": "lang",
+/// "index_type": "Keyword", "enabled": true}]}`.
+///
+/// - `ef_search` pins the search beam of the collection; `null` returns
+///   it to the server default.
+/// - `quantization` (`none`/`fp32`, `sq8`, `binary`, or the object form
+///   of the create request) re-encodes the stored vectors in place, like
+///   `POST /collections/{name}/reencode`.
+/// - `ttl_secs` behaves like `POST /collections/{name}/ttl`.
+/// - `payload_indexes` are added and backfilled over the stored vectors.
+///
+/// Dimension, metric, HNSW graph parameters and storage cannot change
+/// without rebuilding the collection and are rejected; use
+/// `POST /collections/{name}/reindex` for the graph. Settings are saved
+/// with the collection in `.vecdb`.
+pub async fn patch_collection(
+    State(state): State<VectorizerServer>,
+    Path(collection_name): Path<String>,
+    Json(payload): Json<Value>,
+) -> Result<Json<Value>, ErrorResponse> {
+    use crate::server::error_middleware::create_validation_error;
+    use vectorizer::config::MAX_EF_SEARCH;
+    use vectorizer::models::QuantizationConfig;
+
+    let fields = payload
+        .as_object()
+        .ok_or_else(|| create_validation_error("body", "body must be a JSON object"))?;
+    if let Some(field) = fields
+        .keys()
+        .find(|k| IMMUTABLE_COLLECTION_FIELDS.contains(&k.as_str()))
+    {
+        return Err(create_validation_error(
+            field,
+            &format!(
+                "{} cannot be changed on an existing collection; reindex or recreate it",
+                field
+            ),
+        ));
+    }
+
+    let mut patch = vectorizer::db::CollectionPatch::default();
+    if let Some(value) = fields.get("ef_search") {
+        patch.ef_search = Some(match value {
+            Value::Null => None,
+            value => Some(
+                value
+                    .as_u64()
+                    .filter(|ef| (1..=MAX_EF_SEARCH as u64).contains(ef))
+                    .ok_or_else(|| {
+                        create_validation_error(
+                            "ef_search",
+                            &format!("ef_search must be between 1 and {} or null", MAX_EF_SEARCH),
+                        )
+                    })? as usize,
+            ),
+        });
+    }
+    if let Some(value) = fields.get("quantization") {
+        patch.quantization = Some(match value.as_str() {
+            Some("none" | "fp32") => QuantizationConfig::None,
+            Some("sq8" | "SQ8" | "scalar") => QuantizationConfig::SQ { bits: 8 },
+            Some("binary") => QuantizationConfig::Binary,
+            Some(other) => {
+                return Err(create_validation_error(
+                    "quantization",
+                    &format!(
+                        "unsupported quantization '{}'; valid values: none, sq8, binary",
+                        other
+                    ),
+                ));
+            }
+            None => serde_json::from_value(value.clone())
+                .map_err(|e| create_validation_error("quantization", &e.to_string()))?,
+        });
+    }
+    if let Some(value) = fields.get("compression") {
+        patch.compression = Some(
+            serde_json::from_value(value.clone())
+                .map_err(|e| create_validation_error("compression", &e.to_string()))?,
+        );
+    }
+    if let Some(value) = fields.get("ttl_secs") {
+        patch.ttl_secs = Some(match value {
+            Value::Null => None,
+            value => Some(value.as_u64().ok_or_else(|| {
+                create_validation_error(
+                    "ttl_secs",
+                    "ttl_secs must be a non-negative integer or null",
+                )
+            })?),
+        });
+    }
+    if let Some(value) = fields.get("payload_indexes") {
+        patch.payload_indexes = serde_json::from_value(value.clone())
+            .map_err(|e| create_validation_error("payload_indexes", &e.to_string()))?;
+    }
+
+    // Re-encoding and index backfill walk every vector
+    let store = state.store.clone();
+    let name = collection_name.clone();
+    let changed = tokio::task::spawn_blocking(move || store.patch_collection(&name, patch))
+        .await
+        .map_err(|e| {
+            crate::server::error_middleware::create_bad_request_error(&format!(
+                "patch task error: {}",
+                e
+            ))
+        })?
+        .map_err(ErrorResponse::from)?;
+
+    if !changed.is_empty()
+        && let Some(ref auto_save) = state.auto_save_manager
+    {
+        auto_save.mark_changed();
+    }
+    info!("patch_collection '{}': {:?}", collection_name, changed);
+
+    let collection = state
+        .store
+        .get_collection(&collection_name)
+        .map_err(ErrorResponse::from)?;
+    let config = collection.config();
+    Ok(Json(json!({
+        "collection": collection_name,
+        "changed": changed,
+        "ef_search": collection.pinned_ef_search(),
+        "quantization": config.quantization,
+        "compression": config.compression,
+        "ttl_secs": collection.ttl_secs(),
+        "status": "ok",
+    })))
+}
+
+/// POST /collections/{name}/cold
+///
+/// Body: `{"cold": true}` (the default) or `{"cold": false}`
+///
+/// `true` writes the HNSW graph to `{data_dir}/hnsw/{name}.vzhnsw` and
+/// serves searches from the memory-mapped file, releasing the in-memory
+/// graph; the file is reused on restart instead of rebuilding the graph.
+/// `false` rebuilds the in-memory graph. Any write to a cold collection
+/// does the same implicitly.
+pub async fn set_collection_cold(
+    State(state): State<VectorizerServer>,
+    Path(collection_name): Path<String>,
+    Json(payload): Json<serde_json::Value>,
+) -> Result<Json<serde_json::Value>, ErrorResponse> {
+    let cold = match payload.get("cold") {
+        None => true,
+        Some(v) => v.as_bool().ok_or_else(|| {
+            crate::server::error_middleware::create_validation_error(
+                "cold",
+                "cold must be a boolean",
+            )
+        })?,
+    };
+
+    state
+        .store
+        .get_collection(&collection_name)
+        .map_err(ErrorResponse::from)?;
+
+    let store = state.store.clone();
+    let col_name = collection_name.clone();
+    tokio::task::spawn_blocking(move || store.set_collection_cold(&col_name, cold))
+        .await
+        .map_err(|e| {
+            crate::server::error_middleware::create_bad_request_error(&format!(
+                "cold index task error: {}",
+                e
+            ))
+        })?
+        .map_err(ErrorResponse::from)?;
+
+    info!("set_collection_cold '{}' -> {}", collection_name, cold);
+
+    Ok(Json(json!({
+        "collection": collection_name,
+        "cold": cold,
+        "status": "ok",
+    })))
+}
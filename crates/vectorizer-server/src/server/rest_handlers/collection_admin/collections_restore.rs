@@ -0,0 +1,65 @@
+//! `restore_collection_to_time` — POST /collections/{name}/restore,
+//! point-in-time restore from a snapshot plus the write-ahead log.
+
+use axum::extract::{Path, Query, State};
+use axum::response::Json;
+use serde_json::json;
+use tracing::info;
+
+use crate::server::VectorizerServer;
+use crate::server::error_middleware::ErrorResponse;
+
+/// POST /collections/{name}/restore?timestamp=...
+///
+/// Restores the collection to its state at `timestamp` (RFC 3339, or
+/// Unix seconds): the newest snapshot taken before it, rolled forward
+/// with the write-ahead log. Needs `storage.wal.enabled`.
+pub async fn restore_collection_to_time(
+    State(state): State<VectorizerServer>,
+    Path(collection_name): Path<String>,
+    Query(params): Query<std::collections::HashMap<String, String>>,
+) -> Result<Json<serde_json::Value>, ErrorResponse> {
+    let raw = params.get("timestamp").ok_or_else(|| {
+        crate::server::error_middleware::create_validation_error(
+            "timestamp",
+            "timestamp is required",
+        )
+    })?;
+    let at = chrono::DateTime::parse_from_rfc3339(raw)
+        .map(|t| t.with_timezone(&chrono::Utc))
+        .ok()
+        .or_else(|| {
+            raw.parse::<i64>()
+                .ok()
+                .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+        })
+        .ok_or_else(|| {
+            crate::server::error_middleware::create_validation_error(
+                "timestamp",
+                "timestamp must be RFC 3339 or Unix seconds",
+            )
+        })?;
+
+    let report = state
+        .store
+        .restore_collection_to_time(&collection_name, at)
+        .await
+        .map_err(ErrorResponse::from)?;
+
+    if let Some(ref auto_save) = state.auto_save_manager {
+        auto_save.mark_changed();
+    }
+
+    info!(
+        "restore_collection_to_time '{}' to {} from snapshot '{}' + {} WAL operations",
+        collection_name,
+        at.to_rfc3339(),
+        report.base_snapshot,
+        report.replayed_operations
+    );
+
+    Ok(Json(json!({
+        "status": "restored",
+        "restore": report,
+    })))
+}
@@ -0,0 +1,264 @@
+//! Metadata key-value store REST handlers.
+//!
+//! - `list_kv`        — GET    /collections/{name}/kv?prefix=...&limit=...
+//! - `kv_transaction` — POST   /collections/{name}/kv
+//! - `get_kv`         — GET    /collections/{name}/kv/{*key}
+//! - `put_kv`         — PUT    /collections/{name}/kv/{*key}
+//! - `delete_kv`      — DELETE /collections/{name}/kv/{*key}?if_version=...
+//!
+//! Ingestion services keep their bookkeeping (cursors, watermarks,
+//! schema versions) next to the vectors they write. Each tenant gets its
+//! own namespace; without a hub tenant every caller shares `default`.
+//! Writes can be made conditional on the version last read, and a
+//! transaction checks and applies several writes atomically; a failed
+//! condition answers 409 with the version found.
+
+#![allow(missing_docs)]
+
+use std::collections::HashMap;
+
+use axum::Extension;
+use axum::extract::{Path, Query, State};
+use axum::response::Json;
+use serde_json::{Value, json};
+use tracing::debug;
+use vectorizer::db::{KvCheck, KvEntry, KvOp};
+use vectorizer::hub::middleware::RequestTenantContext;
+
+use crate::server::VectorizerServer;
+use crate::server::error_middleware::{
+    ErrorResponse, create_not_found_error, create_validation_error,
+};
+
+/// Namespace of callers without a hub tenant.
+const DEFAULT_NAMESPACE: &str = "default";
+
+/// Keys listed by default.
+const DEFAULT_LIST_LIMIT: usize = 100;
+
+/// Most keys one list call returns.
+const MAX_LIST_LIMIT: usize = 1_000;
+
+fn namespace(tenant_ctx: &Option<Extension<RequestTenantContext>>) -> String {
+    tenant_ctx
+        .as_ref()
+        .map(|ctx| ctx.0.0.tenant_id.clone())
+        .unwrap_or_else(|| DEFAULT_NAMESPACE.to_string())
+}
+
+fn entry_json(key: &str, entry: &KvEntry) -> Value {
+    json!({
+        "key": key,
+        "value": entry.value,
+        "version": entry.version,
+        "updated_at": entry.updated_at,
+    })
+}
+
+fn parse_version(field: &str, value: Option<&Value>) -> Result<Option<u64>, ErrorResponse> {
+    match value {
+        None | Some(Value::Null) => Ok(None),
+        Some(v) => v
+            .as_u64()
+            .map(Some)
+            .ok_or_else(|| create_validation_error(field, "expected a non-negative integer")),
+    }
+}
+
+/// Run a transaction and persist it with the next save.
+fn commit(
+    state: &VectorizerServer,
+    collection_name: &str,
+    namespace: &str,
+    checks: &[KvCheck],
+    ops: Vec<KvOp>,
+) -> Result<Vec<vectorizer::db::KvWriteResult>, ErrorResponse> {
+    let results = state
+        .store
+        .kv_transaction(collection_name, namespace, checks, ops)
+        .map_err(ErrorResponse::from)?;
+    if let Some(ref auto_save) = state.auto_save_manager {
+        auto_save.mark_changed();
+    }
+    Ok(results)
+}
+
+/// Condition on `key` from an optional `if_version` (0 = must be absent).
+fn checks_for(key: &str, if_version: Option<u64>) -> Vec<KvCheck> {
+    if_version
+        .map(|version| KvCheck {
+            key: key.to_string(),
+            version,
+        })
+        .into_iter()
+        .collect()
+}
+
+/// GET /collections/{name}/kv?prefix=cursor/&limit=100
+///
+/// Keys of the caller's namespace in key order.
+pub async fn list_kv(
+    State(state): State<VectorizerServer>,
+    Path(collection_name): Path<String>,
+    tenant_ctx: Option<Extension<RequestTenantContext>>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let namespace = namespace(&tenant_ctx);
+    let prefix = params.get("prefix").map(String::as_str).unwrap_or("");
+    let limit = params
+        .get("limit")
+        .and_then(|v| v.parse::<usize>().ok())
+        .unwrap_or(DEFAULT_LIST_LIMIT)
+        .min(MAX_LIST_LIMIT);
+
+    let entries = state
+        .store
+        .kv_list(&collection_name, &namespace, prefix, limit)
+        .map_err(ErrorResponse::from)?;
+
+    Ok(Json(json!({
+        "collection": collection_name,
+        "namespace": namespace,
+        "entries": entries
+            .iter()
+            .map(|(key, entry)| entry_json(key, entry))
+            .collect::<Vec<_>>(),
+    })))
+}
+
+/// GET /collections/{name}/kv/{*key}
+pub async fn get_kv(
+    State(state): State<VectorizerServer>,
+    Path((collection_name, key)): Path<(String, String)>,
+    tenant_ctx: Option<Extension<RequestTenantContext>>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let namespace = namespace(&tenant_ctx);
+    let entry = state
+        .store
+        .kv_get(&collection_name, &namespace, &key)
+        .map_err(ErrorResponse::from)?
+        .ok_or_else(|| create_not_found_error("key", &key))?;
+    Ok(Json(entry_json(&key, &entry)))
+}
+
+/// PUT /collections/{name}/kv/{*key}
+///
+/// Body: `{"value": {"offset": 1200}, "if_version": 7}`. `if_version` is
+/// optional: the write only happens if the key is at that version, and
+/// `0` means it must not exist yet.
+pub async fn put_kv(
+    State(state): State<VectorizerServer>,
+    Path((collection_name, key)): Path<(String, String)>,
+    tenant_ctx: Option<Extension<RequestTenantContext>>,
+    Json(payload): Json<Value>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let namespace = namespace(&tenant_ctx);
+    let value = payload
+        .get("value")
+        .cloned()
+        .ok_or_else(|| create_validation_error("value", "missing value"))?;
+    let if_version = parse_version("if_version", payload.get("if_version"))?;
+
+    let results = commit(
+        &state,
+        &collection_name,
+        &namespace,
+        &checks_for(&key, if_version),
+        vec![KvOp::Put {
+            key: key.clone(),
+            value,
+        }],
+    )?;
+    debug!("KV put '{}' in '{}/{}'", key, collection_name, namespace);
+
+    Ok(Json(json!({
+        "key": key,
+        "version": results.first().and_then(|r| r.version),
+    })))
+}
+
+/// DELETE /collections/{name}/kv/{*key}?if_version=7
+pub async fn delete_kv(
+    State(state): State<VectorizerServer>,
+    Path((collection_name, key)): Path<(String, String)>,
+    tenant_ctx: Option<Extension<RequestTenantContext>>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let namespace = namespace(&tenant_ctx);
+    let if_version = params
+        .get("if_version")
+        .map(|v| {
+            v.parse::<u64>().map_err(|_| {
+                create_validation_error("if_version", "expected a non-negative integer")
+            })
+        })
+        .transpose()?;
+
+    let results = commit(
+        &state,
+        &collection_name,
+        &namespace,
+        &checks_for(&key, if_version),
+        vec![KvOp::Delete { key: key.clone() }],
+    )?;
+    let deleted = results.first().is_some_and(|r| r.changed);
+    if !deleted {
+        return Err(create_not_found_error("key", &key));
+    }
+
+    Ok(Json(json!({
+        "key": key,
+        "deleted": true,
+    })))
+}
+
+/// POST /collections/{name}/kv
+///
+/// Request shape:
+/// ```json
+/// {
+///   "checks": [{"key": "cursor", "version": 7}],       // optional, 0 = absent
+///   "ops": [
+///     {"op": "put", "key": "cursor", "value": 1300},
+///     {"op": "put", "key": "watermark", "value": "2026-10-16T00:00:00Z"},
+///     {"op": "delete", "key": "lease"}
+///   ]
+/// }
+/// ```
+///
+/// Either every check holds and every op is applied, or nothing is
+/// written (409 on a failed check).
+pub async fn kv_transaction(
+    State(state): State<VectorizerServer>,
+    Path(collection_name): Path<String>,
+    tenant_ctx: Option<Extension<RequestTenantContext>>,
+    Json(payload): Json<Value>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let namespace = namespace(&tenant_ctx);
+    let checks: Vec<KvCheck> = match payload.get("checks") {
+        None | Some(Value::Null) => Vec::new(),
+        Some(v) => serde_json::from_value(v.clone()).map_err(|e| {
+            create_validation_error("checks", &format!("expected [{{key, version}}]: {e}"))
+        })?,
+    };
+    let ops: Vec<KvOp> = payload
+        .get("ops")
+        .cloned()
+        .ok_or_else(|| create_validation_error("ops", "missing ops"))
+        .and_then(|v| {
+            serde_json::from_value(v).map_err(|e| {
+                create_validation_error(
+                    "ops",
+                    &format!("expected [{{op: put|delete, key, value}}]: {e}"),
+                )
+            })
+        })?;
+
+    let results = commit(&state, &collection_name, &namespace, &checks, ops)?;
+
+    Ok(Json(json!({
+        "collection": collection_name,
+        "namespace": namespace,
+        "results": results,
+    })))
+}
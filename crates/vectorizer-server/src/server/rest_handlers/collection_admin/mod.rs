@@ -0,0 +1,35 @@
+//! Collection lifecycle beyond the CRUD in [`super::collections`]:
+//! creation, in-place setting changes, clones, point-in-time restore,
+//! imports and exports, and the per-collection key-value store.
+//!
+//! - [`collection_import`]   — /collections/{name}/import streamed JSONL
+//!                             with server-side embedding (and Parquet)
+//! - [`collections_clone`]   — /collections/{name}/clone, run as a job
+//! - [`collections_create`]  — POST /collections and /collections/templates
+//! - [`collections_patch`]   — PATCH /collections/{name} and the cold toggle
+//! - [`collections_restore`] — /collections/{name}/restore point-in-time
+//!                             restore
+//! - [`kv`]                  — /collections/{name}/kv metadata key-value
+//!                             store
+//! - [`transfer`]            — /collections/{name}/export and Parquet
+//!                             imports (feature `parquet`)
+
+use super::{common, insert, jobs};
+
+mod collection_import;
+mod collections_clone;
+mod collections_create;
+mod collections_patch;
+mod collections_restore;
+mod kv;
+#[cfg(feature = "parquet")]
+mod transfer;
+
+pub use collection_import::import_collection;
+pub use collections_clone::clone_collection;
+pub use collections_create::{create_collection, list_collection_templates};
+pub use collections_patch::{patch_collection, set_collection_cold};
+pub use collections_restore::restore_collection_to_time;
+pub use kv::{delete_kv, get_kv, kv_transaction, list_kv, put_kv};
+#[cfg(feature = "parquet")]
+pub use transfer::export_collection;
@@ -0,0 +1,173 @@
+//! Parquet import / export REST handlers (feature `parquet`).
+//!
+//! - `export_collection` — POST /collections/{name}/export
+//! - `import_file`       — POST /collections/{name}/import?format=parquet,
+//!   dispatched by `super::collection_import::import_collection`
+//!
+//! Exports are Parquet files laid out as described in
+//! `vectorizer::migration::parquet`, streamed to the client while they
+//! are written. Imports take the file as the raw request body, so they
+//! are bounded by the request size limit.
+
+#![allow(missing_docs)]
+
+use std::io::{self, BufWriter, Write};
+
+use axum::Extension;
+use axum::body::{Body, Bytes};
+use axum::extract::{Path, State};
+use axum::http::{StatusCode, header};
+use axum::response::{IntoResponse, Json, Response};
+use serde_json::{Value, json};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::warn;
+use vectorizer::hub::middleware::RequestTenantContext;
+use vectorizer::migration::parquet::{
+    ParquetExportOptions, export_parquet, import_parquet, parquet_rows,
+};
+
+use super::insert::{check_insert_quota, mark_collection_dirty, record_insert_usage};
+use crate::server::VectorizerServer;
+use crate::server::error_middleware::{
+    ErrorResponse, create_bad_request_error, create_validation_error,
+};
+
+/// Media type of Parquet files.
+const PARQUET_CONTENT_TYPE: &str = "application/vnd.apache.parquet";
+
+/// Bytes buffered before a chunk of an export goes out.
+const CHUNK_BYTES: usize = 256 * 1024;
+
+/// Chunks queued ahead of a slow client before the export waits.
+const QUEUED_CHUNKS: usize = 4;
+
+fn check_format(format: Option<&str>) -> Result<(), ErrorResponse> {
+    match format.unwrap_or("parquet") {
+        "parquet" => Ok(()),
+        other => Err(create_validation_error(
+            "format",
+            &format!("unsupported format '{other}', expected 'parquet'"),
+        )),
+    }
+}
+
+/// `Write` end of an export: every buffer flushed becomes a body chunk.
+struct ChannelWriter(mpsc::Sender<io::Result<Bytes>>);
+
+impl Write for ChannelWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0
+            .blocking_send(Ok(Bytes::copy_from_slice(buf)))
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "export client went away"))?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// POST /collections/{name}/export
+///
+/// Body (optional): `{"format": "parquet", "fields": ["title", "meta.lang"]}`.
+/// `fields` picks the payload fields mapped to columns (default: every
+/// top-level field); the full payload is always in the `payload` column.
+/// A failure after the first bytes were sent aborts the response, so a
+/// client never mistakes a truncated file for a complete one.
+pub async fn export_collection(
+    State(state): State<VectorizerServer>,
+    Path(collection_name): Path<String>,
+    body: Bytes,
+) -> Result<Response, ErrorResponse> {
+    let request: Value = if body.is_empty() {
+        json!({})
+    } else {
+        serde_json::from_slice(&body)
+            .map_err(|e| create_bad_request_error(&format!("Invalid JSON body: {e}")))?
+    };
+    check_format(request.get("format").and_then(Value::as_str))?;
+    let options: ParquetExportOptions = serde_json::from_value(request).map_err(|e| {
+        create_validation_error("fields", &format!("expected a list of payload paths: {e}"))
+    })?;
+    // Unknown collections get a 404 before the stream starts
+    state
+        .store
+        .get_collection(&collection_name)
+        .map_err(ErrorResponse::from)?;
+
+    let (tx, rx) = mpsc::channel(QUEUED_CHUNKS);
+    let store = state.store.clone();
+    let name = collection_name.clone();
+    tokio::task::spawn_blocking(move || {
+        let writer = BufWriter::with_capacity(CHUNK_BYTES, ChannelWriter(tx.clone()));
+        if let Err(e) = export_parquet(&store, &name, writer, &options) {
+            warn!("Export of '{}' failed: {}", name, e);
+            let _ = tx.blocking_send(Err(io::Error::other(e.to_string())));
+        }
+    });
+
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, PARQUET_CONTENT_TYPE.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{collection_name}.parquet\""),
+            ),
+        ],
+        Body::from_stream(ReceiverStream::new(rx)),
+    )
+        .into_response())
+}
+
+/// POST /collections/{name}/import?format=parquet
+///
+/// `body` is the Parquet file. Rows need a `vector` column; `id`,
+/// `document_id` and `payload` are used when present, and without
+/// `payload` the other columns become the payload fields. Vectors are
+/// inserted into the existing collection, replacing those with the same
+/// ID.
+pub(super) async fn import_file(
+    state: VectorizerServer,
+    collection_name: String,
+    tenant_ctx: Option<Extension<RequestTenantContext>>,
+    body: Bytes,
+) -> Result<Json<Value>, ErrorResponse> {
+    if body.is_empty() {
+        return Err(create_validation_error("body", "expected a Parquet file"));
+    }
+    let (dimension, rows) = state
+        .store
+        .get_collection(&collection_name)
+        .map(|c| c.config().dimension)
+        .and_then(|dimension| Ok((dimension, parquet_rows(body.clone())?)))
+        .map_err(ErrorResponse::from)?;
+    let _tenant_admission =
+        check_insert_quota(&state, tenant_ctx.as_ref(), &collection_name, rows).await?;
+
+    let store = state.store.clone();
+    let name = collection_name.clone();
+    let imported = tokio::task::spawn_blocking(move || import_parquet(&store, &name, body))
+        .await
+        .map_err(|e| create_bad_request_error(&format!("import task error: {}", e)))?
+        .map_err(ErrorResponse::from)?;
+
+    if !imported.ids.is_empty() {
+        record_insert_usage(
+            &state,
+            &collection_name,
+            dimension,
+            imported.ids.len() as u64,
+        )
+        .await;
+    }
+    mark_collection_dirty(&state, &collection_name, &imported.ids);
+
+    Ok(Json(json!({
+        "collection": collection_name,
+        "format": "parquet",
+        "imported": imported.ids.len(),
+        "columns": imported.columns,
+    })))
+}
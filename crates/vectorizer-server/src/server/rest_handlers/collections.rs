@@ -1,7 +1,6 @@
 //! Collection-level REST handlers.
 //!
 //! - `list_collections`          — GET    /collections
-//! - `create_collection`         — POST   /collections
 //! - `get_collection`            — GET    /collections/{name}
 //! - `delete_collection`         — DELETE /collections/{name}
 //! - `force_save_collection`     — POST   /collections/{name}/save  (GUI)
@@ -14,18 +13,25 @@
 //! - `create_native_snapshot`    — POST   /collections/{name}/snapshot
 //! - `list_native_snapshots`     — GET    /collections/{name}/snapshots
 //! - `restore_native_snapshot`   — POST   /collections/{name}/snapshots/{id}/restore
+//!
+//! Creation lives in [`super::collection_admin::collections_create`],
+//! in-place setting changes in [`super::collection_admin::collections_patch`],
+//! cloning in [`super::collection_admin::collections_clone`] and
+//! point-in-time restore in [`super::collection_admin::collections_restore`].
 
 use axum::Extension;
 use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
 use axum::response::Json;
 use serde_json::{Value, json};
-use tracing::{debug, error, info, warn};
+use tracing::{debug, error, info};
 use vectorizer::auth::middleware::AuthState;
 use vectorizer::auth::roles::Role;
+use vectorizer::cluster::raft_node::ClusterCommand;
 use vectorizer::hub::middleware::RequestTenantContext;
 
-use super::common::{collection_metrics_uuid, extract_tenant_id, publish_collections_snapshot};
+use super::common::{extract_tenant_id, publish_collections_snapshot};
+use super::jobs::{JobOwner, WaitQuery, run_job};
 use crate::server::VectorizerServer;
 use crate::server::error_middleware::ErrorResponse;
 
@@ -60,6 +66,11 @@ pub async fn list_collections(
         }
     };
 
+    // API keys confined to a namespace only see the collections in it
+    if let Some(auth) = &auth_state {
+        collections.retain(|name| auth.user_claims.can_access_collection(name));
+    }
+
     // Sort alphabetically for consistent dashboard display
     collections.sort();
 
@@ -165,227 +176,6 @@ pub async fn list_collections(
     }))
 }
 
-/// POST /collections — create a new collection
-pub async fn create_collection(
-    State(state): State<VectorizerServer>,
-    tenant_ctx: Option<Extension<RequestTenantContext>>,
-    Json(payload): Json<Value>,
-) -> Result<Json<Value>, ErrorResponse> {
-    let name = payload
-        .get("name")
-        .and_then(|n| n.as_str())
-        .ok_or_else(|| {
-            crate::server::error_middleware::create_validation_error(
-                "name",
-                "missing or invalid name parameter",
-            )
-        })?;
-    let dimension = payload
-        .get("dimension")
-        .and_then(|d| d.as_u64())
-        .unwrap_or(512) as usize;
-    let metric = payload
-        .get("metric")
-        .and_then(|m| m.as_str())
-        .unwrap_or("cosine");
-
-    // phase33 (#306): honour the `embedding_provider` field instead of
-    // silently coercing every collection to bm25. When omitted, fall
-    // back to the server's configured default — never to a hardcoded
-    // provider name.
-    let requested_provider = payload
-        .get("embedding_provider")
-        .and_then(|p| p.as_str())
-        .map(|s| s.to_string());
-    let resolved_provider = match requested_provider.clone() {
-        Some(name) => {
-            if !state.embedding_manager.has_provider(&name) {
-                return Err(ErrorResponse::from(
-                    vectorizer_core::error::VectorizerError::UnsupportedProvider {
-                        requested: name,
-                        available: state.embedding_manager.list_providers(),
-                    },
-                ));
-            }
-            name
-        }
-        None => state
-            .embedding_manager
-            .get_default_provider_name()
-            .unwrap_or("bm25")
-            .to_string(),
-    };
-    // Reject dimension mismatch against the provider's native dimension
-    // before persisting the collection — silent quantization to a
-    // different size is what caused the BM25-512 coercion downstream.
-    if let Ok(provider_dim) = state
-        .embedding_manager
-        .get_provider_dimension(&resolved_provider)
-    {
-        if provider_dim != dimension {
-            return Err(ErrorResponse::from(
-                vectorizer_core::error::VectorizerError::ProviderDimensionMismatch {
-                    provider: resolved_provider.clone(),
-                    provider_dimension: provider_dim,
-                    requested_dimension: dimension,
-                },
-            ));
-        }
-    }
-
-    // Extract tenant ID for multi-tenant mode
-    let tenant_id = extract_tenant_id(&tenant_ctx);
-
-    info!(
-        "Creating collection: {} with dimension {} and metric {} (tenant: {:?})",
-        name, dimension, metric, tenant_id
-    );
-
-    // Check HiveHub quota if enabled
-    // In cluster mode, verify the tenant has quota for new collections
-    if let Some(ref hub_manager) = state.hub_manager {
-        let tenant_id_str = tenant_id
-            .map(|id| id.to_string())
-            .unwrap_or_else(|| "default".to_string());
-
-        match hub_manager
-            .check_quota(
-                &tenant_id_str,
-                vectorizer::hub::QuotaType::CollectionCount,
-                1,
-            )
-            .await
-        {
-            Ok(allowed) => {
-                if !allowed {
-                    warn!(
-                        "Collection creation denied for tenant {}: quota exceeded",
-                        tenant_id_str
-                    );
-                    return Err(ErrorResponse::new(
-                        "QUOTA_EXCEEDED".to_string(),
-                        "Collection quota exceeded. Please upgrade your plan or delete unused collections.".to_string(),
-                        StatusCode::TOO_MANY_REQUESTS,
-                    ));
-                }
-            }
-            Err(e) => {
-                // Log the error but don't fail - the Hub handles actual enforcement
-                warn!("Failed to check quota with HiveHub: {}", e);
-            }
-        }
-    }
-
-    // Parse graph configuration if provided
-    let graph_config = payload.get("graph").and_then(|g| {
-        if let Some(enabled) = g.get("enabled").and_then(|e| e.as_bool()) {
-            if enabled {
-                Some(vectorizer::models::GraphConfig {
-                    enabled: true,
-                    auto_relationship: vectorizer::models::AutoRelationshipConfig::default(),
-                })
-            } else {
-                None
-            }
-        } else {
-            None
-        }
-    });
-
-    // Determine storage type: use MMap in cluster mode (enforce_mmap_storage),
-    // otherwise default to Memory for standalone deployments.
-    let storage_type = if let Some(ref cluster_mgr) = state.cluster_manager {
-        // In cluster mode, MMap is required for data persistence across pod restarts
-        info!(
-            "Cluster mode active — using MMap storage for collection '{}'",
-            name
-        );
-        Some(vectorizer::models::StorageType::Mmap)
-    } else {
-        Some(vectorizer::models::StorageType::Memory)
-    };
-
-    // Create collection configuration
-    let config = vectorizer::models::CollectionConfig {
-        dimension,
-        metric: match metric {
-            "cosine" => vectorizer::models::DistanceMetric::Cosine,
-            "euclidean" => vectorizer::models::DistanceMetric::Euclidean,
-            "dot" => vectorizer::models::DistanceMetric::DotProduct,
-            _ => vectorizer::models::DistanceMetric::Cosine,
-        },
-        hnsw_config: vectorizer::models::HnswConfig::default(),
-        quantization: vectorizer::models::QuantizationConfig::None,
-        compression: vectorizer::models::CompressionConfig::default(),
-        embedding_provider: resolved_provider,
-        normalization: None,
-        storage_type,
-        sharding: None,
-        graph: graph_config,
-        encryption: None,
-    };
-
-    // Actually create the collection in the store
-    // In multi-tenant mode, associate collection with the owner
-    if let Some(owner_id) = tenant_id {
-        state
-            .store
-            .create_collection_with_owner(name, config, owner_id)
-            .map_err(|e| ErrorResponse::from(e))?;
-    } else {
-        state
-            .store
-            .create_collection(name, config)
-            .map_err(|e| ErrorResponse::from(e))?;
-    }
-
-    // Replicate collection creation to replicas.
-    // Check both static master_node and HA manager (Raft-managed master).
-    let active_master: Option<std::sync::Arc<vectorizer::replication::MasterNode>> = state
-        .master_node
-        .clone()
-        .or_else(|| state.ha_manager.as_ref().and_then(|ha| ha.master_node()));
-
-    if let Some(ref master) = active_master {
-        let op = vectorizer::replication::VectorOperation::CreateCollection {
-            name: name.to_string(),
-            config: vectorizer::replication::CollectionConfigData {
-                dimension,
-                metric: metric.to_string(),
-            },
-            owner_id: tenant_id.map(|id| id.to_string()),
-        };
-        master.replicate(op);
-        debug!("Replicated collection creation: {}", name);
-    }
-
-    // Mark changes for auto-save
-    if let Some(ref auto_save) = state.auto_save_manager {
-        auto_save.mark_changed();
-    }
-
-    // Record usage metrics if HiveHub is enabled
-    if let Some(ref hub_manager) = state.hub_manager {
-        let mut metrics = vectorizer::hub::UsageMetrics::new();
-        metrics.record_collection_create();
-        // Stable UUID derived from the collection name so subsequent calls
-        // aggregate under the same Hub usage row.
-        let collection_id = collection_metrics_uuid(&name);
-        if let Err(e) = hub_manager.record_usage(collection_id, metrics).await {
-            warn!("Failed to record collection creation usage: {}", e);
-        }
-    }
-
-    info!("Collection '{}' created successfully", name);
-    publish_collections_snapshot(&state);
-    Ok(Json(json!({
-        "message": format!("Collection '{}' created successfully", name),
-        "collection": name,
-        "dimension": dimension,
-        "metric": metric
-    })))
-}
-
 /// GET /collections/{name} — retrieve collection details
 pub async fn get_collection(
     State(state): State<VectorizerServer>,
@@ -431,6 +221,16 @@ pub async fn get_collection(
         .get_default_provider_name()
         .unwrap_or("unknown");
 
+    // HNSW fragmentation and background compaction outcome (CPU only).
+    let index_optimization = collection.index_fragmentation().map(|fragmentation| {
+        json!({
+            "fragmentation": fragmentation.ratio(),
+            "graph_nodes": fragmentation.graph_nodes,
+            "stale_nodes": fragmentation.stale_nodes(),
+            "compaction": collection.index_optimization_status(),
+        })
+    });
+
     Ok(Json(json!({
         "name": name,
         "vector_count": collection.vector_count(),
@@ -438,6 +238,20 @@ pub async fn get_collection(
         "dimension": config.dimension,
         "metric": format!("{:?}", config.metric),
         "embedding_provider": provider_name,
+        "storage": collection.storage_type().as_str(),
+        "cold": collection.is_cold(),
+        "id_policy": config.id_policy,
+        "quota": config.quota,
+        "scoring": config.scoring,
+        "tenant_partition": config.tenant_partition,
+        "dedup": config.dedup,
+        "chunking": config.chunking,
+        "index_optimization": index_optimization,
+        "recall_profile": collection.recall_profile(),
+        "hybrid_weights": collection.tuned_hybrid_weights(),
+        "ef_search": collection.pinned_ef_search(),
+        "ttl_secs": collection.ttl_secs(),
+        "compression": config.compression,
         "created_at": metadata.created_at.to_rfc3339(),
         "updated_at": metadata.updated_at.to_rfc3339(),
         "size": {
@@ -466,11 +280,25 @@ pub async fn delete_collection(
 ) -> Result<Json<Value>, ErrorResponse> {
     info!("Deleting collection: {}", name);
 
+    // Resolve first so an unknown name is still a 404, then commit the
+    // delete through the Raft log before touching the local store.
+    // Deleting locally first would leave this node diverged if the
+    // proposal is rejected or fails.
     state
         .store
-        .delete_collection(&name)
+        .get_collection(&name)
         .map_err(|e| ErrorResponse::from(e))?;
 
+    state
+        .propose_cluster_command(ClusterCommand::DeleteCollection { name: name.clone() })
+        .await?;
+
+    // The Raft store applier may already have dropped it on this node.
+    match state.store.delete_collection(&name) {
+        Ok(()) | Err(vectorizer::error::VectorizerError::CollectionNotFound(_)) => {}
+        Err(e) => return Err(ErrorResponse::from(e)),
+    }
+
     // Mark changes for auto-save
     if let Some(ref auto_save) = state.auto_save_manager {
         auto_save.mark_changed();
@@ -755,20 +583,21 @@ pub async fn set_collection_ttl(
         },
     };
 
-    // Persist TTL configuration as store metadata with a namespaced key.
-    let meta_key = format!("ttl:{}", collection_name);
+    // Kept with the collection's settings and mirrored into the
+    // `ttl:{name}` store metadata key.
+    state
+        .store
+        .set_collection_ttl(&collection_name, ttl_secs)
+        .map_err(ErrorResponse::from)?;
     match ttl_secs {
-        Some(secs) => {
-            state.store.set_metadata(&meta_key, secs.to_string());
-            info!(
-                "set_collection_ttl '{}': ttl_secs={}",
-                collection_name, secs
-            );
-        }
-        None => {
-            state.store.remove_metadata(&meta_key);
-            info!("set_collection_ttl '{}': TTL cleared", collection_name);
-        }
+        Some(secs) => info!(
+            "set_collection_ttl '{}': ttl_secs={}",
+            collection_name, secs
+        ),
+        None => info!("set_collection_ttl '{}': TTL cleared", collection_name),
+    }
+    if let Some(ref auto_save) = state.auto_save_manager {
+        auto_save.mark_changed();
     }
 
     Ok(Json(json!({
@@ -832,10 +661,14 @@ pub async fn rename_collection(
 /// vectors — no re-embedding required. The collection write-lock is
 /// held for the duration so concurrent inserts queue behind the swap.
 ///
-/// Returns a job-style response for SDK symmetry.
+/// Runs as a job: by default the response comes once the rebuild
+/// finished; with `?wait=false` it comes right away in the `running`
+/// state, and `GET /jobs/{job_id}` follows the rebuild.
 pub async fn reindex_collection(
     State(state): State<VectorizerServer>,
     Path(collection_name): Path<String>,
+    owner: JobOwner,
+    Query(query): Query<WaitQuery>,
     Json(payload): Json<serde_json::Value>,
 ) -> Result<Json<serde_json::Value>, ErrorResponse> {
     let m = payload.get("m").and_then(|v| v.as_u64()).unwrap_or(16) as usize;
@@ -861,37 +694,37 @@ pub async fn reindex_collection(
         .get_collection(&collection_name)
         .map_err(ErrorResponse::from)?;
 
-    let store = state.store.clone();
+    let server = state.clone();
     let col_name = collection_name.clone();
+    let (job_id, done) = run_job(
+        vectorizer::jobs::COLLECTION_REINDEX,
+        &collection_name,
+        owner,
+        query.wait,
+        move |job| {
+            let vectors = server.store.get_collection(&col_name)?.vector_count() as u64;
+            job.set_total(vectors);
+            server.store.reindex_collection(&col_name, new_params)?;
+            job.advance(vectors);
+            if let Some(ref auto_save) = server.auto_save_manager {
+                auto_save.mark_changed();
+            }
+            info!(
+                "reindex_collection '{}' completed (M={}, ef_construction={}, ef_search={})",
+                col_name, m, ef_construction, ef_search
+            );
+            Ok(())
+        },
+    )
+    .await?;
 
-    let result =
-        tokio::task::spawn_blocking(move || store.reindex_collection(&col_name, new_params))
-            .await
-            .map_err(|e| {
-                crate::server::error_middleware::create_bad_request_error(&format!(
-                    "reindex task error: {}",
-                    e
-                ))
-            })?
-            .map_err(ErrorResponse::from)?;
-
-    let _ = result;
-
-    if let Some(ref auto_save) = state.auto_save_manager {
-        auto_save.mark_changed();
-    }
-
-    info!(
-        "reindex_collection '{}' completed (M={}, ef_construction={}, ef_search={})",
-        collection_name, m, ef_construction, ef_search
-    );
-
+    let completed = done.is_some();
     Ok(Json(json!({
-        "job_id": format!("reindex-{}-{}", collection_name, chrono::Utc::now().timestamp()),
+        "job_id": job_id,
         "collection": collection_name,
-        "state": "completed",
+        "state": if completed { "completed" } else { "running" },
         "params": { "m": m, "ef_construction": ef_construction, "ef_search": ef_search },
-        "progress": 1.0,
+        "progress": if completed { 1.0 } else { 0.0 },
     })))
 }
 
@@ -965,36 +798,41 @@ pub async fn list_native_snapshots(
 ///
 /// Restores the collection from a native snapshot. Drops the current
 /// in-memory state and replaces it with the snapshot data.
+///
+/// Runs as a job; with `?wait=false` the response comes right away with
+/// `"status": "started"` and `GET /jobs/{job_id}` follows the restore.
 pub async fn restore_native_snapshot(
     State(state): State<VectorizerServer>,
     Path((collection_name, snapshot_id)): Path<(String, String)>,
+    owner: JobOwner,
+    Query(query): Query<WaitQuery>,
 ) -> Result<Json<serde_json::Value>, ErrorResponse> {
-    let store = state.store.clone();
+    let server = state.clone();
     let col_name = collection_name.clone();
     let snap_id = snapshot_id.clone();
-
-    tokio::task::spawn_blocking(move || store.restore_native_snapshot(&col_name, &snap_id))
-        .await
-        .map_err(|e| {
-            crate::server::error_middleware::create_bad_request_error(&format!(
-                "restore task error: {}",
-                e
-            ))
-        })?
-        .map_err(ErrorResponse::from)?;
-
-    if let Some(ref auto_save) = state.auto_save_manager {
-        auto_save.mark_changed();
-    }
-
-    info!(
-        "restore_native_snapshot '{}' from snapshot '{}'",
-        collection_name, snapshot_id
-    );
+    let (job_id, done) = run_job(
+        vectorizer::jobs::SNAPSHOT_RESTORE,
+        &collection_name,
+        owner,
+        query.wait,
+        move |_| {
+            server.store.restore_native_snapshot(&col_name, &snap_id)?;
+            if let Some(ref auto_save) = server.auto_save_manager {
+                auto_save.mark_changed();
+            }
+            info!(
+                "restore_native_snapshot '{}' from snapshot '{}'",
+                col_name, snap_id
+            );
+            Ok(json!({ "snapshot_id": snap_id }))
+        },
+    )
+    .await?;
 
     Ok(Json(json!({
+        "job_id": job_id,
         "collection": collection_name,
         "snapshot_id": snapshot_id,
-        "status": "restored",
+        "status": if done.is_some() { "restored" } else { "started" },
     })))
 }
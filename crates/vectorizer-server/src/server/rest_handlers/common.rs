@@ -1,10 +1,15 @@
 //! Shared helpers used across multiple REST handler modules.
 
+use serde_json::Value;
 use uuid::Uuid;
 use vectorizer::db::{AdmissionError, AdmissionStatus, UpsertQueue, UpsertTicket};
+use vectorizer::models::qdrant::filter::QdrantFilter;
+use vectorizer::security::PayloadView;
 
 use crate::server::VectorizerServer;
-use crate::server::error_middleware::{ErrorResponse, create_queue_full_error};
+use crate::server::error_middleware::{
+    ErrorResponse, create_queue_full_error, create_validation_error,
+};
 use crate::server::runtime_metrics::{DashboardEvent, build_collections_snapshot};
 
 /// Phase30 §1.4 — publish an immediate `Collections` snapshot on the
@@ -86,6 +91,20 @@ pub(super) fn admit_upsert(
     }
 }
 
+/// The entries of the JSON array `field` as `f32`s. Returns 400 naming
+/// the first entry that is not a number.
+pub(super) fn f32_array(values: &[Value], field: &str) -> Result<Vec<f32>, ErrorResponse> {
+    values
+        .iter()
+        .enumerate()
+        .map(|(i, v)| {
+            v.as_f64().map(|f| f as f32).ok_or_else(|| {
+                create_validation_error(field, &format!("{}[{}] is not a number", field, i))
+            })
+        })
+        .collect()
+}
+
 /// Extract tenant ID as UUID from request extensions (if present)
 ///
 /// Returns None if:
@@ -100,6 +119,26 @@ pub(super) fn extract_tenant_id(
         .and_then(|ctx| Uuid::parse_str(&ctx.0.0.tenant_id).ok())
 }
 
+/// Refuse a `filter` that reads a payload key redacted for the caller:
+/// which vectors match would give the hidden values away.
+pub(super) fn reject_redacted_filter(
+    view: &PayloadView,
+    collection: &str,
+    filter: &QdrantFilter,
+) -> Result<(), ErrorResponse> {
+    match filter
+        .keys()
+        .into_iter()
+        .find(|key| view.redacts_field(collection, key))
+    {
+        Some(key) => Err(create_validation_error(
+            "filter",
+            &format!("'{}' is redacted and cannot be filtered on", key),
+        )),
+        None => Ok(()),
+    }
+}
+
 /// Deterministic UUID derived from a collection name.
 ///
 /// Prior to this helper, three call sites in `rest_handlers.rs` passed a
@@ -120,3 +159,8 @@ pub(super) const COLLECTION_NAMESPACE_UUID: Uuid =
 pub(crate) fn collection_metrics_uuid(name: &str) -> Uuid {
     Uuid::new_v5(&COLLECTION_NAMESPACE_UUID, name.as_bytes())
 }
+
+/// Serde default of the request flags that are on unless turned off.
+pub(super) fn default_true() -> bool {
+    true
+}
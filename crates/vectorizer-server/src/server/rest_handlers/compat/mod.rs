@@ -0,0 +1,21 @@
+//! APIs of other systems served on top of the store: OpenAI embeddings,
+//! the Pinecone data plane and `/retrieve` for LangChain / llama-index
+//! retrievers.
+//!
+//! - [`openai`]   — OpenAI-compatible /v1/embeddings and /v1/models
+//! - [`pinecone`] — /pinecone/collections/{name} Pinecone-compatible upsert
+//!                  / query / fetch / delete / stats
+//! - [`retrieve`] — /retrieve documents for LangChain / llama-index
+//!                  retrievers
+
+use super::{common, filtered_search, insert, search_common, tenant_partition};
+
+mod openai;
+mod pinecone;
+pub(super) mod retrieve;
+
+pub use openai::{create_embeddings, list_models};
+pub use pinecone::{
+    pinecone_delete, pinecone_describe_index_stats, pinecone_fetch, pinecone_query, pinecone_upsert,
+};
+pub use retrieve::retrieve_documents;
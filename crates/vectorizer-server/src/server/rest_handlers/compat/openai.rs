@@ -0,0 +1,180 @@
+//! OpenAI-compatible embeddings API: `POST /v1/embeddings` and
+//! `GET /v1/models`.
+//!
+//! Tooling that already speaks the OpenAI embeddings API (the official
+//! SDKs with `base_url` pointed at `http://<server>/v1`, LangChain's
+//! `OpenAIEmbeddings`, LiteLLM, ...) can use the server's embedding
+//! providers as its embedding service. `model` names a registered
+//! provider (`bm25`, `fastembed:<model-id>`, ...) or one of the
+//! `embedding.model_aliases` from `config.yml`, so clients can keep
+//! sending `text-embedding-3-small`.
+//!
+//! Only text input is accepted; token-id arrays would need the OpenAI
+//! tokenizer to decode. `usage` reports an estimate of one token per four
+//! bytes of input, as the render endpoints do.
+
+use axum::extract::State;
+use axum::response::Json;
+use base64::Engine;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use tracing::debug;
+use vectorizer_core::error::VectorizerError;
+
+use crate::server::VectorizerServer;
+use crate::server::error_middleware::{ErrorResponse, create_validation_error};
+
+/// Most inputs one request may embed, as in the OpenAI API
+const MAX_INPUTS: usize = 2048;
+
+#[derive(Debug, Deserialize)]
+pub struct EmbeddingsRequest {
+    input: Value,
+    #[serde(default)]
+    model: Option<String>,
+    #[serde(default)]
+    encoding_format: Option<String>,
+    #[serde(default)]
+    dimensions: Option<usize>,
+}
+
+/// The texts of an `input`: one string or an array of strings.
+fn input_texts(input: &Value) -> Result<Vec<&str>, ErrorResponse> {
+    let texts: Vec<&str> = match input {
+        Value::String(text) => vec![text.as_str()],
+        Value::Array(items) if items.iter().all(Value::is_string) => {
+            items.iter().filter_map(Value::as_str).collect()
+        }
+        Value::Array(_) => {
+            return Err(create_validation_error(
+                "input",
+                "token arrays are not supported; send the text instead",
+            ));
+        }
+        _ => {
+            return Err(create_validation_error(
+                "input",
+                "must be a string or an array of strings",
+            ));
+        }
+    };
+    if texts.is_empty() || texts.len() > MAX_INPUTS {
+        return Err(create_validation_error(
+            "input",
+            &format!("must hold between 1 and {MAX_INPUTS} texts"),
+        ));
+    }
+    if texts.iter().any(|text| text.is_empty()) {
+        return Err(create_validation_error("input", "texts must not be empty"));
+    }
+    Ok(texts)
+}
+
+/// POST /v1/embeddings
+///
+/// Request: `{input, model?, encoding_format?: "float" | "base64",
+/// dimensions?}`; without `model` the default provider embeds.
+/// `dimensions` must equal the provider's dimension, since the local
+/// models cannot shorten their embeddings. Response:
+/// `{object: "list", data: [{object: "embedding", index, embedding}],
+/// model, usage: {prompt_tokens, total_tokens}}`, where `embedding` is
+/// little-endian `f32`s in base64 for `encoding_format: "base64"`.
+pub async fn create_embeddings(
+    State(state): State<VectorizerServer>,
+    Json(request): Json<EmbeddingsRequest>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let texts = input_texts(&request.input)?;
+    let as_base64 = match request.encoding_format.as_deref() {
+        None | Some("float") => false,
+        Some("base64") => true,
+        Some(other) => {
+            return Err(create_validation_error(
+                "encoding_format",
+                &format!("'{other}' is not one of 'float' and 'base64'"),
+            ));
+        }
+    };
+
+    let manager = &state.embedding_manager;
+    let (model, provider) = match request.model {
+        Some(model) => match manager.resolve_model(&model) {
+            Some(provider) => {
+                let provider = provider.to_string();
+                (model, provider)
+            }
+            None => {
+                return Err(ErrorResponse::from(VectorizerError::UnsupportedModel {
+                    requested: model,
+                    available: manager.list_providers(),
+                }));
+            }
+        },
+        None => {
+            let provider = manager
+                .get_default_provider_name()
+                .unwrap_or("bm25")
+                .to_string();
+            (provider.clone(), provider)
+        }
+    };
+    if let Some(dimensions) = request.dimensions {
+        let dimension = manager
+            .get_provider_dimension(&provider)
+            .map_err(ErrorResponse::from)?;
+        if dimensions != dimension {
+            return Err(create_validation_error(
+                "dimensions",
+                &format!("model '{model}' only produces {dimension}-dimensional embeddings"),
+            ));
+        }
+    }
+
+    let embeddings = manager
+        .embed_batch_with_provider(&texts, &provider)
+        .map_err(ErrorResponse::from)?;
+    debug!(
+        "Embedded {} input(s) with '{}' for model '{}'",
+        texts.len(),
+        provider,
+        model
+    );
+
+    let data: Vec<Value> = embeddings
+        .into_iter()
+        .enumerate()
+        .map(|(index, embedding)| {
+            let embedding = if as_base64 {
+                let bytes: Vec<u8> = embedding.iter().flat_map(|x| x.to_le_bytes()).collect();
+                json!(base64::engine::general_purpose::STANDARD.encode(bytes))
+            } else {
+                json!(embedding)
+            };
+            json!({ "object": "embedding", "index": index, "embedding": embedding })
+        })
+        .collect();
+    let tokens: usize = texts.iter().map(|text| text.len().div_ceil(4)).sum();
+
+    Ok(Json(json!({
+        "object": "list",
+        "data": data,
+        "model": model,
+        "usage": { "prompt_tokens": tokens, "total_tokens": tokens },
+    })))
+}
+
+/// GET /v1/models
+///
+/// The registered providers and model aliases, in the OpenAI model list
+/// shape.
+pub async fn list_models(State(state): State<VectorizerServer>) -> Json<Value> {
+    let manager = &state.embedding_manager;
+    let mut ids = manager.list_providers();
+    ids.extend(manager.model_aliases().map(|(alias, _)| alias.to_string()));
+    ids.sort();
+
+    let data: Vec<Value> = ids
+        .into_iter()
+        .map(|id| json!({ "id": id, "object": "model", "created": 0, "owned_by": "vectorizer" }))
+        .collect();
+    Json(json!({ "object": "list", "data": data }))
+}
@@ -0,0 +1,636 @@
+//! Pinecone-compatible data-plane API under
+//! `/pinecone/collections/{name}`.
+//!
+//! A Pinecone index is a Vectorizer collection: point a Pinecone client's
+//! index host at `http://<server>/pinecone/collections/<name>` and its
+//! `upsert`, `query`, `fetch`, `delete` and `describe_index_stats` calls
+//! land here. The index must already exist (create it through the native
+//! or Qdrant API); it is never created implicitly.
+//!
+//! Namespaces map onto a payload partition: a vector upserted into a
+//! namespace carries the namespace name under the collection's
+//! `tenant_partition.key` (or [`NAMESPACE_KEY`] when the collection is
+//! not partitioned), and every read, search and delete is confined to
+//! the vectors stamped with the request's namespace. The default
+//! namespace `""` holds the vectors without the field, which includes
+//! those written through the other APIs. The field is stripped from the
+//! metadata handed back. Vector ids are unique per collection rather
+//! than per namespace, so upserting an id that exists in another
+//! namespace moves it.
+//!
+//! Metadata filters use Pinecone's operators (`$eq`, `$ne`, `$gt`,
+//! `$gte`, `$lt`, `$lte`, `$in`, `$nin`, `$exists`, `$and`, `$or`) and
+//! are translated to the Qdrant filter the rest of the server evaluates.
+//! Metadata is a payload, so `api.payload_redaction` applies to it as to
+//! the native API's payloads.
+
+use std::collections::BTreeMap;
+
+use axum::Extension;
+use axum::extract::{Path, Query, State};
+use axum::response::Json;
+use serde::Deserialize;
+use serde_json::{Map, Value, json};
+use tracing::info;
+use vectorizer::auth::middleware::AuthState;
+use vectorizer::db::vector_store::CollectionType;
+use vectorizer::hub::middleware::RequestTenantContext;
+use vectorizer::models::qdrant::filter::{
+    QdrantCondition, QdrantFilter, QdrantMatchValue, QdrantRange,
+};
+use vectorizer::models::qdrant::filter_processor::FilterProcessor;
+use vectorizer::models::{DistanceMetric, Payload, Vector};
+use vectorizer::security::PayloadView;
+
+use super::common::admit_upsert;
+use super::filtered_search::filtered_search;
+use super::insert::{
+    check_insert_quota, mark_collection_dirty, record_insert_usage, validate_client_id,
+};
+use crate::server::VectorizerServer;
+use crate::server::core::caller_payload_view;
+use crate::server::error_middleware::{
+    ErrorResponse, create_not_found_error, create_validation_error,
+};
+
+/// Payload field holding a vector's namespace in collections without a
+/// tenant partition.
+pub const NAMESPACE_KEY: &str = "__namespace__";
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpsertRequest {
+    vectors: Vec<PineconeVector>,
+    #[serde(default)]
+    namespace: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PineconeVector {
+    id: String,
+    values: Vec<f32>,
+    #[serde(default)]
+    metadata: Option<Map<String, Value>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryRequest {
+    #[serde(default)]
+    namespace: String,
+    top_k: usize,
+    #[serde(default)]
+    vector: Option<Vec<f32>>,
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    filter: Option<Value>,
+    #[serde(default)]
+    include_values: bool,
+    #[serde(default)]
+    include_metadata: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteRequest {
+    #[serde(default)]
+    ids: Option<Vec<String>>,
+    #[serde(default)]
+    delete_all: bool,
+    #[serde(default)]
+    namespace: String,
+    #[serde(default)]
+    filter: Option<Value>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct DescribeIndexStatsRequest {
+    #[serde(default)]
+    filter: Option<Value>,
+}
+
+/// Where a collection keeps namespaces, and which one a request is in.
+struct Namespace {
+    /// Payload field holding the namespace
+    key: String,
+    /// Requested namespace; `""` is the default one
+    name: String,
+    /// Whether `key` is the collection's tenant partition key, whose
+    /// payload index confines a search to the namespace
+    partitioned: bool,
+}
+
+impl Namespace {
+    fn of(collection: &CollectionType, name: String) -> Self {
+        match &collection.config().tenant_partition {
+            Some(partition) => Self {
+                key: partition.key.clone(),
+                name,
+                partitioned: true,
+            },
+            None => Self {
+                key: NAMESPACE_KEY.to_string(),
+                name,
+                partitioned: false,
+            },
+        }
+    }
+
+    /// The namespace a vector with `payload` is in.
+    fn name_of<'a>(key: &str, payload: Option<&'a Payload>) -> &'a str {
+        payload
+            .and_then(|p| p.data.get(key))
+            .and_then(Value::as_str)
+            .unwrap_or("")
+    }
+
+    fn contains(&self, payload: Option<&Payload>) -> bool {
+        Self::name_of(&self.key, payload) == self.name
+    }
+
+    /// Whether a vector with `payload` is in the namespace and passes
+    /// `filter`.
+    fn admits(&self, payload: Option<&Payload>, filter: Option<&QdrantFilter>) -> bool {
+        self.contains(payload) && passes(payload, filter)
+    }
+
+    /// The payload stored for `metadata` in this namespace.
+    fn stamp(&self, metadata: Option<Map<String, Value>>) -> Value {
+        let mut fields = metadata.unwrap_or_default();
+        fields.remove(&self.key);
+        if !self.name.is_empty() {
+            fields.insert(self.key.clone(), Value::String(self.name.clone()));
+        }
+        Value::Object(fields)
+    }
+
+    /// The metadata handed back for a stored `payload` of `collection`,
+    /// redacted for the caller `view` belongs to.
+    fn metadata(&self, payload: Option<&Payload>, view: &PayloadView, collection: &str) -> Value {
+        let mut fields = payload
+            .and_then(|p| p.data.as_object().cloned())
+            .unwrap_or_default();
+        fields.remove(&self.key);
+        let mut metadata = Value::Object(fields);
+        view.redact_value(collection, &mut metadata);
+        metadata
+    }
+}
+
+/// Whether a vector with `payload` passes `filter`; a vector without a
+/// payload is judged as one with no fields.
+fn passes(payload: Option<&Payload>, filter: Option<&QdrantFilter>) -> bool {
+    match (filter, payload) {
+        (None, _) => true,
+        (Some(filter), Some(payload)) => FilterProcessor::apply_filter(filter, payload),
+        (Some(filter), None) => FilterProcessor::apply_filter(filter, &Payload::new(json!({}))),
+    }
+}
+
+/// POST /pinecone/collections/{name}/vectors/upsert
+///
+/// Response: `{"upsertedCount": N}`.
+pub async fn pinecone_upsert(
+    State(state): State<VectorizerServer>,
+    Path(collection_name): Path<String>,
+    tenant_ctx: Option<Extension<RequestTenantContext>>,
+    Json(request): Json<UpsertRequest>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let _admission_ticket = admit_upsert(&state.upsert_queue, &collection_name)?;
+    let (namespace, dimension) = {
+        let collection = state
+            .store
+            .get_collection(&collection_name)
+            .map_err(ErrorResponse::from)?;
+        (
+            Namespace::of(&collection, request.namespace),
+            collection.config().dimension,
+        )
+    };
+    let _tenant_admission = check_insert_quota(
+        &state,
+        tenant_ctx.as_ref(),
+        &collection_name,
+        request.vectors.len(),
+    )
+    .await?;
+
+    let mut vectors = Vec::with_capacity(request.vectors.len());
+    for vector in request.vectors {
+        validate_client_id(&vector.id).map_err(|e| create_validation_error("id", &e))?;
+        if vector.values.len() != dimension {
+            return Err(create_validation_error(
+                "values",
+                &format!(
+                    "vector '{}' has dimension {}, the index has {}",
+                    vector.id,
+                    vector.values.len(),
+                    dimension
+                ),
+            ));
+        }
+        vectors.push(Vector {
+            id: vector.id,
+            data: vector.values,
+            sparse: None,
+            payload: Some(Payload::new(namespace.stamp(vector.metadata))),
+            document_id: None,
+        });
+    }
+
+    let ids: Vec<String> = vectors.iter().map(|v| v.id.clone()).collect();
+    state
+        .store
+        .insert(&collection_name, vectors)
+        .map_err(ErrorResponse::from)?;
+    if !ids.is_empty() {
+        record_insert_usage(&state, &collection_name, dimension, ids.len() as u64).await;
+        mark_collection_dirty(&state, &collection_name, &ids);
+    }
+    info!(
+        "Pinecone upsert: {} vector(s) into '{}' namespace '{}'",
+        ids.len(),
+        collection_name,
+        namespace.name
+    );
+
+    Ok(Json(json!({ "upsertedCount": ids.len() })))
+}
+
+/// POST /pinecone/collections/{name}/query
+///
+/// Queries by `vector`, or by the stored vector `id`. Response:
+/// `{"matches": [{"id", "score", "values"?, "metadata"?}], "namespace"}`.
+pub async fn pinecone_query(
+    State(state): State<VectorizerServer>,
+    Path(collection_name): Path<String>,
+    auth: Option<Extension<AuthState>>,
+    Json(request): Json<QueryRequest>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let view = caller_payload_view(state.payload_redactor.clone(), auth.as_deref());
+    let collection = state
+        .store
+        .get_collection(&collection_name)
+        .map_err(ErrorResponse::from)?;
+    let namespace = Namespace::of(&collection, request.namespace);
+    let filter = request.filter.as_ref().map(translate_filter).transpose()?;
+
+    let query = match (request.vector, &request.id) {
+        (Some(vector), None) => vector,
+        (None, Some(id)) => {
+            let stored = collection
+                .get_vector(id)
+                .ok()
+                .filter(|v| namespace.contains(v.payload.as_ref()))
+                .ok_or_else(|| create_not_found_error("vector", id))?;
+            stored.data
+        }
+        _ => {
+            return Err(create_validation_error(
+                "vector",
+                "exactly one of `vector` and `id` is required",
+            ));
+        }
+    };
+
+    // A namespace of a partitioned collection is searched through the
+    // partition's payload index instead of the whole graph
+    let partition =
+        (namespace.partitioned && !namespace.name.is_empty()).then_some(namespace.name.as_str());
+    let results = filtered_search(&collection, &query, request.top_k, partition, |r| {
+        namespace.admits(r.payload.as_ref(), filter.as_ref())
+    })?;
+    let matches: Vec<Value> = results
+        .into_iter()
+        .map(|r| {
+            let mut item = json!({ "id": r.id, "score": r.score });
+            if request.include_values
+                && let Some(values) = r.vector
+            {
+                item["values"] = json!(values);
+            }
+            if request.include_metadata {
+                item["metadata"] = namespace.metadata(r.payload.as_ref(), &view, &collection_name);
+            }
+            item
+        })
+        .collect();
+
+    Ok(Json(json!({
+        "matches": matches,
+        "namespace": namespace.name,
+        "usage": { "readUnits": 1 },
+    })))
+}
+
+/// GET /pinecone/collections/{name}/vectors/fetch?ids=a&ids=b&namespace=ns
+///
+/// Ids outside the namespace, like unknown ones, are left out of the
+/// response: `{"vectors": {id: {"id", "values", "metadata"}}, "namespace"}`.
+pub async fn pinecone_fetch(
+    State(state): State<VectorizerServer>,
+    Path(collection_name): Path<String>,
+    Query(params): Query<Vec<(String, String)>>,
+    auth: Option<Extension<AuthState>>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let view = caller_payload_view(state.payload_redactor.clone(), auth.as_deref());
+    let collection = state
+        .store
+        .get_collection(&collection_name)
+        .map_err(ErrorResponse::from)?;
+    let namespace_name = params
+        .iter()
+        .find(|(key, _)| key == "namespace")
+        .map(|(_, value)| value.clone())
+        .unwrap_or_default();
+    let namespace = Namespace::of(&collection, namespace_name);
+    let ids: Vec<&str> = params
+        .iter()
+        .filter(|(key, _)| key == "ids")
+        .map(|(_, value)| value.as_str())
+        .collect();
+    if ids.is_empty() {
+        return Err(create_validation_error(
+            "ids",
+            "at least one id is required",
+        ));
+    }
+
+    let vectors: Map<String, Value> = ids
+        .into_iter()
+        .filter_map(|id| collection.get_vector(id).ok())
+        .filter(|v| namespace.contains(v.payload.as_ref()))
+        .map(|v| {
+            let metadata = namespace.metadata(v.payload.as_ref(), &view, &collection_name);
+            (
+                v.id.clone(),
+                json!({ "id": v.id, "values": v.data, "metadata": metadata }),
+            )
+        })
+        .collect();
+
+    Ok(Json(json!({
+        "vectors": vectors,
+        "namespace": namespace.name,
+        "usage": { "readUnits": 1 },
+    })))
+}
+
+/// POST /pinecone/collections/{name}/vectors/delete
+///
+/// Deletes the listed `ids`, every vector of the namespace
+/// (`deleteAll`) or the namespace's vectors matching `filter`; exactly
+/// one must be given. Response: `{}`.
+pub async fn pinecone_delete(
+    State(state): State<VectorizerServer>,
+    Path(collection_name): Path<String>,
+    Json(request): Json<DeleteRequest>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let ids: Vec<String> = {
+        let collection = state
+            .store
+            .get_collection(&collection_name)
+            .map_err(ErrorResponse::from)?;
+        let namespace = Namespace::of(&collection, request.namespace);
+        match (request.ids, request.delete_all, &request.filter) {
+            (Some(ids), false, None) => ids
+                .into_iter()
+                .filter(|id| {
+                    collection
+                        .get_vector(id)
+                        .is_ok_and(|v| namespace.contains(v.payload.as_ref()))
+                })
+                .collect(),
+            (None, true, None) => collection
+                .get_all_vectors()
+                .into_iter()
+                .filter(|v| namespace.contains(v.payload.as_ref()))
+                .map(|v| v.id)
+                .collect(),
+            (None, false, Some(filter)) => {
+                let filter = translate_filter(filter)?;
+                collection
+                    .get_all_vectors()
+                    .into_iter()
+                    .filter(|v| namespace.admits(v.payload.as_ref(), Some(&filter)))
+                    .map(|v| v.id)
+                    .collect()
+            }
+            _ => {
+                return Err(create_validation_error(
+                    "ids",
+                    "exactly one of `ids`, `deleteAll` and `filter` is required",
+                ));
+            }
+        }
+    };
+
+    for id in &ids {
+        state
+            .store
+            .delete(&collection_name, id)
+            .map_err(ErrorResponse::from)?;
+    }
+    if !ids.is_empty() {
+        state.query_cache.invalidate_collection(&collection_name);
+        if let Some(ref auto_save) = state.auto_save_manager {
+            auto_save.mark_changed();
+        }
+    }
+    info!(
+        "Pinecone delete: {} vector(s) from '{}'",
+        ids.len(),
+        collection_name
+    );
+
+    Ok(Json(json!({})))
+}
+
+/// GET|POST /pinecone/collections/{name}/describe_index_stats
+///
+/// Vector counts per namespace (the default one is `""`), optionally of
+/// the vectors matching `filter`.
+pub async fn pinecone_describe_index_stats(
+    State(state): State<VectorizerServer>,
+    Path(collection_name): Path<String>,
+    request: Option<Json<DescribeIndexStatsRequest>>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let collection = state
+        .store
+        .get_collection(&collection_name)
+        .map_err(ErrorResponse::from)?;
+    let filter = request
+        .and_then(|Json(request)| request.filter)
+        .as_ref()
+        .map(translate_filter)
+        .transpose()?;
+    let key = Namespace::of(&collection, String::new()).key;
+
+    let mut namespaces: BTreeMap<String, usize> = BTreeMap::new();
+    let mut total = 0;
+    for vector in collection.get_all_vectors() {
+        let payload = vector.payload.as_ref();
+        if passes(payload, filter.as_ref()) {
+            *namespaces
+                .entry(Namespace::name_of(&key, payload).to_string())
+                .or_default() += 1;
+            total += 1;
+        }
+    }
+    let config = collection.config();
+
+    Ok(Json(json!({
+        "namespaces": namespaces
+            .into_iter()
+            .map(|(name, count)| (name, json!({ "vectorCount": count })))
+            .collect::<Map<String, Value>>(),
+        "dimension": config.dimension,
+        "metric": pinecone_metric(&config.metric),
+        "indexFullness": 0.0,
+        "totalVectorCount": total,
+        "vectorType": "dense",
+    })))
+}
+
+fn pinecone_metric(metric: &DistanceMetric) -> &'static str {
+    match metric {
+        DistanceMetric::Cosine => "cosine",
+        DistanceMetric::Euclidean => "euclidean",
+        DistanceMetric::DotProduct => "dotproduct",
+    }
+}
+
+/// Translate a Pinecone metadata filter into a Qdrant filter.
+pub(super) fn translate_filter(filter: &Value) -> Result<QdrantFilter, ErrorResponse> {
+    let invalid = |message: String| create_validation_error("filter", &message);
+    let Some(clauses) = filter.as_object() else {
+        return Err(invalid("must be a JSON object".to_string()));
+    };
+
+    let mut must = Vec::new();
+    let mut must_not = Vec::new();
+    for (key, clause) in clauses {
+        match key.as_str() {
+            "$and" | "$or" => {
+                let Some(branches) = clause.as_array().filter(|b| !b.is_empty()) else {
+                    return Err(invalid(format!("{key} takes a non-empty array of filters")));
+                };
+                let branches = branches
+                    .iter()
+                    .map(|branch| translate_filter(branch).map(QdrantCondition::nested))
+                    .collect::<Result<Vec<_>, _>>()?;
+                must.push(if key == "$and" {
+                    QdrantCondition::nested(QdrantFilter {
+                        must: Some(branches),
+                        should: None,
+                        must_not: None,
+                    })
+                } else {
+                    any_of(branches)
+                });
+            }
+            _ if key.starts_with('$') => {
+                return Err(invalid(format!("unsupported operator {key}")));
+            }
+            field => match clause.as_object() {
+                Some(operators) => {
+                    for (operator, operand) in operators {
+                        translate_operator(field, operator, operand, &mut must, &mut must_not)
+                            .map_err(invalid)?;
+                    }
+                }
+                // `{"genre": "drama"}` is shorthand for `$eq`
+                None => must.push(equals(field, clause).map_err(invalid)?),
+            },
+        }
+    }
+
+    Ok(QdrantFilter {
+        must: Some(must),
+        should: None,
+        must_not: Some(must_not),
+    })
+}
+
+fn translate_operator(
+    field: &str,
+    operator: &str,
+    operand: &Value,
+    must: &mut Vec<QdrantCondition>,
+    must_not: &mut Vec<QdrantCondition>,
+) -> Result<(), String> {
+    let number = || {
+        operand
+            .as_f64()
+            .ok_or_else(|| format!("{operator} on '{field}' takes a number"))
+    };
+    let list = || {
+        operand
+            .as_array()
+            .ok_or_else(|| format!("{operator} on '{field}' takes an array"))?
+            .iter()
+            .map(|value| equals(field, value))
+            .collect::<Result<Vec<_>, _>>()
+    };
+    let range = |range: QdrantRange| QdrantCondition::Range {
+        key: field.to_string(),
+        range,
+    };
+    let present = QdrantCondition::Match {
+        key: field.to_string(),
+        match_value: QdrantMatchValue::Any,
+    };
+
+    match operator {
+        "$eq" => must.push(equals(field, operand)?),
+        "$ne" => must_not.push(equals(field, operand)?),
+        "$gt" => must.push(range(QdrantRange::gt(number()?))),
+        "$gte" => must.push(range(QdrantRange::gte(number()?))),
+        "$lt" => must.push(range(QdrantRange::lt(number()?))),
+        "$lte" => must.push(range(QdrantRange::lte(number()?))),
+        "$in" => match list()? {
+            values if values.is_empty() => return Err(format!("$in on '{field}' is empty")),
+            values => must.push(any_of(values)),
+        },
+        "$nin" => must_not.extend(list()?),
+        "$exists" => match operand.as_bool() {
+            Some(true) => must.push(present),
+            Some(false) => must_not.push(present),
+            None => return Err(format!("$exists on '{field}' takes a boolean")),
+        },
+        _ => return Err(format!("unsupported operator {operator} on '{field}'")),
+    }
+    Ok(())
+}
+
+/// `field` equals `value`. Numbers compare through a closed range, so
+/// `2019` also matches a stored `2019.0`.
+fn equals(field: &str, value: &Value) -> Result<QdrantCondition, String> {
+    let key = field.to_string();
+    let match_value = match value {
+        Value::String(s) => QdrantMatchValue::String(s.clone()),
+        Value::Bool(b) => QdrantMatchValue::Bool(*b),
+        Value::Number(n) => {
+            let x = n.as_f64().unwrap_or_default();
+            return Ok(QdrantCondition::Range {
+                key,
+                range: QdrantRange::between_inclusive(x, x),
+            });
+        }
+        _ => {
+            return Err(format!(
+                "'{field}' can only be compared to a string, number or boolean"
+            ));
+        }
+    };
+    Ok(QdrantCondition::Match { key, match_value })
+}
+
+fn any_of(conditions: Vec<QdrantCondition>) -> QdrantCondition {
+    QdrantCondition::nested(QdrantFilter {
+        must: None,
+        should: Some(conditions),
+        must_not: None,
+    })
+}
@@ -0,0 +1,194 @@
+//! `POST /retrieve` — retriever contract for LangChain / llama-index.
+//!
+//! Returns documents as `{page_content, metadata, score, id}`, the shape
+//! LangChain's `Document` and llama-index's `NodeWithScore` are built
+//! from, so a generic HTTP retriever needs no per-app adapter. The
+//! contract is stable: fields are only ever added.
+//!
+//! Retrieval settings can be given top-level (`k`, `filter`,
+//! `score_threshold`) or in `search_kwargs`, as LangChain's
+//! `as_retriever(search_kwargs=...)` passes them; top-level fields win.
+//! `filter` is either a Qdrant filter (`must` / `should` / `must_not`)
+//! or a metadata filter such as `{"source": "a.md"}` with the Pinecone /
+//! Chroma style operators (`$eq`, `$in`, `$gte`, `$and`, ...).
+//!
+//! `page_content` is the payload's `content_field` (default `content`,
+//! the field the ingest paths write, falling back to `text` and
+//! `page_content`); every other payload field is `metadata`. Both are
+//! read after `api.payload_redaction` has been applied for the caller.
+
+use axum::Extension;
+use axum::extract::State;
+use axum::response::Json;
+use serde::Deserialize;
+use serde_json::{Map, Value, json};
+use tracing::debug;
+use vectorizer::auth::middleware::AuthState;
+use vectorizer::hub::middleware::RequestTenantContext;
+use vectorizer::models::qdrant::filter::QdrantFilter;
+use vectorizer::models::qdrant::filter_processor::FilterProcessor;
+use vectorizer::models::{Payload, SearchResult};
+
+use super::common::extract_tenant_id;
+use super::filtered_search::filtered_search;
+use super::pinecone::translate_filter;
+use super::search_common::MAX_SEARCH_LIMIT;
+use super::tenant_partition::Partition;
+use crate::server::VectorizerServer;
+use crate::server::core::caller_payload_view;
+use crate::server::error_middleware::{
+    ErrorResponse, create_bad_request_error, create_validation_error,
+};
+
+/// Documents returned when no `k` is given, as in LangChain
+const DEFAULT_K: usize = 4;
+
+/// Payload fields read as `page_content` when no `content_field` is given
+const CONTENT_FIELDS: &[&str] = &["content", "text", "page_content"];
+
+#[derive(Debug, Default, Deserialize)]
+pub struct SearchKwargs {
+    #[serde(default, alias = "top_k")]
+    k: Option<usize>,
+    #[serde(default)]
+    filter: Option<Value>,
+    #[serde(default)]
+    score_threshold: Option<f32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RetrieveRequest {
+    collection: String,
+    query: String,
+    #[serde(flatten)]
+    top_level: SearchKwargs,
+    #[serde(default)]
+    search_kwargs: SearchKwargs,
+    #[serde(default)]
+    content_field: Option<String>,
+}
+
+/// Parse `filter`: a Qdrant filter when it uses `must` / `should` /
+/// `must_not`, otherwise a metadata filter.
+pub(crate) fn parse_filter(filter: &Value) -> Result<QdrantFilter, ErrorResponse> {
+    let qdrant_shaped = filter.as_object().is_some_and(|f| {
+        ["must", "should", "must_not"]
+            .iter()
+            .any(|k| f.contains_key(*k))
+    });
+    if qdrant_shaped {
+        serde_json::from_value(filter.clone())
+            .map_err(|e| create_validation_error("filter", &format!("invalid filter: {e}")))
+    } else {
+        translate_filter(filter)
+    }
+}
+
+/// A search result as a retriever document.
+fn document(result: SearchResult, content_field: Option<&str>) -> Value {
+    let mut metadata = match result.payload.map(|p| p.data) {
+        Some(Value::Object(fields)) => fields,
+        Some(other) => Map::from_iter([("payload".to_string(), other)]),
+        None => Map::new(),
+    };
+    let page_content = match content_field {
+        Some(field) => metadata.remove(field),
+        None => CONTENT_FIELDS
+            .iter()
+            .find_map(|field| metadata.remove(*field)),
+    };
+    let page_content = match page_content {
+        Some(Value::String(text)) => text,
+        Some(Value::Null) | None => String::new(),
+        Some(other) => other.to_string(),
+    };
+    json!({
+        "id": result.id,
+        "page_content": page_content,
+        "metadata": metadata,
+        "score": result.score,
+    })
+}
+
+/// POST /retrieve
+///
+/// Request: `{collection, query, k?, filter?, score_threshold?,
+/// search_kwargs?: {k?, filter?, score_threshold?}, content_field?}`.
+/// Response: `{documents: [{id, page_content, metadata, score}],
+/// collection, query}`, best first.
+pub async fn retrieve_documents(
+    State(state): State<VectorizerServer>,
+    tenant_ctx: Option<Extension<RequestTenantContext>>,
+    auth: Option<Extension<AuthState>>,
+    Json(request): Json<RetrieveRequest>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let RetrieveRequest {
+        collection: collection_name,
+        query,
+        top_level,
+        search_kwargs,
+        content_field,
+    } = request;
+    if query.trim().is_empty() {
+        return Err(create_validation_error("query", "must not be empty"));
+    }
+    let k = top_level
+        .k
+        .or(search_kwargs.k)
+        .unwrap_or(DEFAULT_K)
+        .min(MAX_SEARCH_LIMIT);
+    let score_threshold = top_level.score_threshold.or(search_kwargs.score_threshold);
+    let filter = top_level
+        .filter
+        .or(search_kwargs.filter)
+        .as_ref()
+        .map(parse_filter)
+        .transpose()?;
+
+    let tenant_id = extract_tenant_id(&tenant_ctx);
+    let collection = state
+        .store
+        .get_collection_with_owner(&collection_name, tenant_id.as_ref())
+        .map_err(ErrorResponse::from)?;
+    let partition = Partition::resolve(&state, &collection_name, tenant_ctx.as_ref());
+    let embedding = state
+        .embed_for_collection(&collection_name, &query)
+        .map_err(|e| create_bad_request_error(&format!("Failed to generate embedding: {}", e)))?;
+
+    let empty = Payload::new(json!({}));
+    let mut results = filtered_search(
+        &collection,
+        &embedding,
+        k,
+        partition.as_ref().map(|p| p.tenant.as_str()),
+        |r| {
+            filter.as_ref().is_none_or(|filter| {
+                FilterProcessor::apply_filter(filter, r.payload.as_ref().unwrap_or(&empty))
+            })
+        },
+    )?;
+    // Results come best first, so the threshold only trims the tail
+    if let Some(threshold) = score_threshold {
+        results.retain(|r| r.score >= threshold);
+    }
+    debug!(
+        "Retrieved {} document(s) from '{}' for '{}'",
+        results.len(),
+        collection_name,
+        query
+    );
+
+    // Redacted before the payload is split, so neither `page_content`
+    // nor `metadata` carries a field the caller may not see
+    caller_payload_view(state.payload_redactor.clone(), auth.as_deref())
+        .redact_results(&collection_name, &mut results);
+    let documents: Vec<Value> = results
+        .into_iter()
+        .map(|result| document(result, content_field.as_deref()))
+        .collect();
+    Ok(Json(json!({
+        "documents": documents,
+        "collection": collection_name,
+        "query": query,
+    })))
+}
@@ -0,0 +1,109 @@
+//! Dedup-aware inserts shared by the insert handlers: one vector through
+//! the collection's dedup policy, the outcome of a text insert, and the
+//! per-batch tally of what the policy did and the housekeeping of the
+//! vectors it wrote.
+
+use serde_json::{Value, json};
+use vectorizer::models::DedupStatus;
+
+use super::insert::{mark_collection_dirty, record_insert_usage};
+use crate::server::VectorizerServer;
+use crate::server::error_middleware::ErrorResponse;
+
+/// Outcome of a single text insert — the vector ids holding the text plus
+/// whether the input was chunked.
+#[derive(Debug)]
+pub(super) struct InsertOneResult {
+    pub vector_ids: Vec<String>,
+    pub chunked: bool,
+    /// What the collection's dedup policy did with each vector, in the
+    /// order of `vector_ids`
+    pub dedup: Vec<DedupStatus>,
+}
+
+impl InsertOneResult {
+    /// Number of vectors actually created (duplicates excluded).
+    pub fn created(&self) -> usize {
+        self.dedup
+            .iter()
+            .filter(|s| s.duplicate().is_none())
+            .count()
+    }
+
+    /// The per-vector dedup statuses, when any vector was a duplicate.
+    pub fn dedup_report(&self) -> Option<&[DedupStatus]> {
+        (self.created() < self.dedup.len()).then_some(self.dedup.as_slice())
+    }
+}
+
+/// Insert one vector through the collection's dedup policy. Returns the
+/// ID now holding its content (the stored duplicate's when it was one)
+/// and the dedup status.
+pub(super) fn insert_vector(
+    state: &VectorizerServer,
+    collection_name: &str,
+    vector: vectorizer::models::Vector,
+) -> Result<(String, DedupStatus), ErrorResponse> {
+    let vector_id = vector.id.clone();
+    let status = state
+        .store
+        .insert_deduplicated(collection_name, vec![vector])
+        .map_err(ErrorResponse::from)?
+        .pop()
+        .unwrap_or(DedupStatus::Inserted);
+    let vector_id = status
+        .duplicate()
+        .map_or(vector_id, |duplicate| duplicate.duplicate_of.clone());
+    Ok((vector_id, status))
+}
+
+/// What the dedup policy did across a batch of single-vector inserts.
+#[derive(Debug, Default)]
+pub(super) struct DedupTally {
+    /// IDs of the vectors newly stored
+    pub inserted: Vec<String>,
+    /// IDs of stored duplicates a vector was merged into
+    pub merged: Vec<String>,
+    /// Vectors that matched a stored one, skipped or merged
+    pub deduplicated: usize,
+}
+
+impl DedupTally {
+    /// Count one stored vector. A duplicate's `status`, `duplicate_of`,
+    /// `reason` and `score` are added to its result `item`.
+    pub fn record(&mut self, vector_id: String, status: &DedupStatus, item: &mut Value) {
+        match status {
+            DedupStatus::Inserted => self.inserted.push(vector_id),
+            DedupStatus::Skipped(_) | DedupStatus::Merged(_) => {
+                self.deduplicated += 1;
+                if matches!(status, DedupStatus::Merged(_)) {
+                    self.merged.push(vector_id);
+                }
+                if let (Some(item), Value::Object(dedup)) = (item.as_object_mut(), json!(status)) {
+                    item.extend(dedup);
+                }
+            }
+        }
+    }
+
+    /// Record the usage of the newly inserted vectors and mark every
+    /// vector written (inserted or merged into) dirty. Returns how many
+    /// were inserted.
+    pub async fn finish(
+        self,
+        state: &VectorizerServer,
+        collection_name: &str,
+        embedding_len: usize,
+    ) -> usize {
+        let inserted = self.inserted.len();
+        if inserted > 0 {
+            record_insert_usage(state, collection_name, embedding_len, inserted as u64).await;
+        }
+        let mut written = self.inserted;
+        written.extend(self.merged);
+        if !written.is_empty() {
+            mark_collection_dirty(state, collection_name, &written);
+        }
+        inserted
+    }
+}
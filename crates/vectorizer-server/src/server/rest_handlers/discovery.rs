@@ -21,18 +21,22 @@
 // phase4_enforce-public-api-docs.
 #![allow(missing_docs)]
 
+use axum::Extension;
 use axum::extract::State;
 use axum::response::Json;
 use serde_json::{Value, json};
 use tracing::error;
+use vectorizer::auth::middleware::AuthState;
 
 use crate::server::VectorizerServer;
+use crate::server::core::caller_can_access;
 use crate::server::error_middleware::{
     ErrorResponse, create_bad_request_error, create_validation_error,
 };
 
 pub async fn discover(
     State(state): State<VectorizerServer>,
+    auth: Option<Extension<AuthState>>,
     Json(payload): Json<Value>,
 ) -> Result<Json<Value>, ErrorResponse> {
     use vectorizer::discovery::{Discovery, DiscoveryConfig};
@@ -76,6 +80,17 @@ pub async fn discover(
         config.focus_k = focus_k as usize;
     }
 
+    // The pipeline lists every collection itself; leave out the ones
+    // outside the caller's API-key namespace
+    config.exclude_collections.extend(
+        state
+            .store
+            .list_collections()
+            .into_iter()
+            .filter(|name| !caller_can_access(auth.as_deref(), name))
+            .map(|name| glob::Pattern::escape(&name)),
+    );
+
     let discovery = Discovery::new(config, state.store.clone(), state.embedding_manager.clone());
 
     match discovery.discover(query).await {
@@ -106,6 +121,7 @@ pub async fn discover(
 
 pub async fn filter_collections(
     State(state): State<VectorizerServer>,
+    auth: Option<Extension<AuthState>>,
     Json(payload): Json<Value>,
 ) -> Result<Json<Value>, ErrorResponse> {
     use vectorizer::discovery::filter_collections as filter_fn;
@@ -131,6 +147,7 @@ pub async fn filter_collections(
         .store
         .list_collections()
         .iter()
+        .filter(|name| caller_can_access(auth.as_deref(), name))
         .filter_map(|name| {
             state.store.get_collection(name).ok().map(|coll| {
                 let metadata = coll.metadata();
@@ -166,6 +183,7 @@ pub async fn filter_collections(
 
 pub async fn score_collections(
     State(state): State<VectorizerServer>,
+    auth: Option<Extension<AuthState>>,
     Json(payload): Json<Value>,
 ) -> Result<Json<Value>, ErrorResponse> {
     use vectorizer::discovery::{ScoringConfig, score_collections as score_fn};
@@ -191,6 +209,7 @@ pub async fn score_collections(
         .store
         .list_collections()
         .iter()
+        .filter(|name| caller_can_access(auth.as_deref(), name))
         .filter_map(|name| {
             state.store.get_collection(name).ok().map(|coll| {
                 let metadata = coll.metadata();
@@ -271,6 +290,7 @@ pub async fn expand_queries(Json(payload): Json<Value>) -> Result<Json<Value>, E
 
 pub async fn broad_discovery(
     State(state): State<VectorizerServer>,
+    auth: Option<Extension<AuthState>>,
     Json(payload): Json<Value>,
 ) -> Result<Json<Value>, ErrorResponse> {
     use vectorizer::discovery::{BroadDiscoveryConfig, broad_discovery as broad_fn};
@@ -291,6 +311,7 @@ pub async fn broad_discovery(
         .store
         .list_collections()
         .iter()
+        .filter(|name| caller_can_access(auth.as_deref(), name))
         .filter_map(|name| {
             state.store.get_collection(name).ok().map(|coll| {
                 let metadata = coll.metadata();
@@ -0,0 +1,171 @@
+//! `POST /collections/{name}/aggregate` — aggregate vector queries.
+//!
+//! Reduces the vectors matching a payload filter to their mean or medoid
+//! (see [`vectorizer::search::aggregate`]) and, when asked, searches the
+//! collection with it in the same call: "documents similar to everything
+//! tagged project-X" without fetching the members and averaging them on
+//! the client. The filter is evaluated on the stored payloads and only
+//! the matching vectors are materialized. In a tenant-partitioned
+//! collection only the caller's vectors are aggregated and searched.
+
+use std::collections::HashSet;
+
+use axum::Extension;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::Json;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use tracing::debug;
+use vectorizer::hub::middleware::RequestTenantContext;
+use vectorizer::models::Vector;
+use vectorizer::models::qdrant::filter_processor::FilterProcessor;
+use vectorizer::search::aggregate::{self, AggregateMethod};
+
+use super::common::{default_true, extract_tenant_id};
+use super::filtered_search::filtered_search;
+use super::retrieve::parse_filter;
+use super::search_common::MAX_SEARCH_LIMIT;
+use super::tenant_partition::Partition;
+use crate::server::VectorizerServer;
+use crate::server::error_middleware::{
+    ErrorResponse, create_bad_request_error, create_error_response, create_validation_error,
+};
+
+fn default_limit() -> usize {
+    10
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AggregateSearch {
+    #[serde(default = "default_limit")]
+    limit: usize,
+    #[serde(default = "default_true")]
+    exclude_members: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AggregateRequest {
+    #[serde(default)]
+    filter: Option<Value>,
+    #[serde(default)]
+    method: AggregateMethod,
+    #[serde(default = "default_true")]
+    include_vector: bool,
+    #[serde(default)]
+    search: Option<AggregateSearch>,
+}
+
+/// POST /collections/{name}/aggregate
+///
+/// Body: `{filter?, method?: "mean" | "medoid", include_vector?: true,
+/// search?: {limit?: 10, exclude_members?: true}}`. `filter` is a Qdrant
+/// filter or a metadata filter as in `POST /retrieve`; without one the
+/// whole collection is aggregated. Response: `{collection, metric,
+/// method, matched, medoid_id?, vector?, results?: [{id, score,
+/// payload}]}`; `results` are the collection's best matches for the
+/// aggregate, without the aggregated vectors unless `exclude_members` is
+/// false. No matching vector is a 404.
+pub async fn aggregate_vectors(
+    State(state): State<VectorizerServer>,
+    Path(collection_name): Path<String>,
+    tenant_ctx: Option<Extension<RequestTenantContext>>,
+    Json(request): Json<AggregateRequest>,
+) -> Result<Json<Value>, ErrorResponse> {
+    if request.search.as_ref().is_some_and(|s| s.limit == 0) {
+        return Err(create_validation_error(
+            "search.limit",
+            "must be at least 1",
+        ));
+    }
+    let filter = request.filter.as_ref().map(parse_filter).transpose()?;
+
+    let tenant_id = extract_tenant_id(&tenant_ctx);
+    let collection = state
+        .store
+        .get_collection_with_owner(&collection_name, tenant_id.as_ref())
+        .map_err(ErrorResponse::from)?;
+    let partition = Partition::resolve(&state, &collection_name, tenant_ctx.as_ref());
+    let metric = collection.config().metric;
+
+    let members: Vec<Vector> = collection
+        .vector_ids()
+        .iter()
+        .filter(|id| {
+            collection
+                .with_payload(id, |payload| {
+                    partition.as_ref().is_none_or(|p| p.owns(payload))
+                        && filter.as_ref().is_none_or(|filter| {
+                            payload.is_some_and(|p| FilterProcessor::apply_filter(filter, p))
+                        })
+                })
+                .unwrap_or(false)
+        })
+        .filter_map(|id| collection.get_vector(id).ok())
+        .collect();
+    drop(collection);
+    if members.is_empty() {
+        return Err(create_error_response(
+            "vectors_not_found",
+            "no vector matches the filter",
+            StatusCode::NOT_FOUND,
+        ));
+    }
+
+    let method = request.method;
+    let (members, result) = tokio::task::spawn_blocking(move || {
+        let result = aggregate::aggregate(&members, metric, method);
+        (members, result)
+    })
+    .await
+    .map_err(|e| create_bad_request_error(&format!("aggregate task error: {}", e)))?;
+    let result = result.map_err(ErrorResponse::from)?;
+    debug!(
+        "Aggregated {} vector(s) of '{}' with {:?}",
+        result.members, collection_name, method
+    );
+
+    let mut response = json!({
+        "collection": collection_name,
+        "metric": metric,
+        "method": result.method,
+        "matched": result.members,
+    });
+    if let Some(medoid) = &result.medoid {
+        response["medoid_id"] = json!(medoid);
+    }
+
+    if let Some(search) = &request.search {
+        let limit = search.limit.min(MAX_SEARCH_LIMIT);
+        let excluded: HashSet<&str> = if search.exclude_members {
+            members.iter().map(|v| v.id.as_str()).collect()
+        } else {
+            HashSet::new()
+        };
+        let collection = state
+            .store
+            .get_collection(&collection_name)
+            .map_err(ErrorResponse::from)?;
+        let results = filtered_search(
+            &collection,
+            &result.vector,
+            limit,
+            partition.as_ref().map(|p| p.tenant.as_str()),
+            |r| !excluded.contains(r.id.as_str()),
+        )?;
+        response["results"] = results
+            .into_iter()
+            .map(|r| {
+                json!({
+                    "id": r.id,
+                    "score": r.score,
+                    "payload": r.payload.map(|p| p.data),
+                })
+            })
+            .collect();
+    }
+    if request.include_vector {
+        response["vector"] = json!(result.vector);
+    }
+    Ok(Json(response))
+}
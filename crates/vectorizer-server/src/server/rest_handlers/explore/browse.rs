@@ -0,0 +1,425 @@
+//! Collection browser: random samples of a collection, the nearest
+//! neighbors of a stored vector and a 2D projection of a sample, for
+//! eyeballing what a collection holds without writing a script.
+//!
+//! - `sample_collection`  — GET /collections/{name}/sample
+//! - `vector_neighbors`   — GET /collections/{name}/vectors/{id}/neighbors
+//! - `project_collection` — POST /collections/{name}/projection
+//!
+//! Payloads come back as previews: long strings are cut to
+//! `preview_chars` characters and long arrays to their first items, so a
+//! page of samples stays small. The caller's `api.payload_redaction`
+//! policy is applied before a payload is cut, so a preview never shows a
+//! field the full payload would hide. Neighbors carry both the search `score`
+//! and the metric `distance` behind it. The projection is computed
+//! server-side (see `vectorizer::search::projection`) so the dashboard's
+//! scatter plot never downloads raw vectors. In a tenant-partitioned
+//! collection only the caller's vectors are sampled or returned.
+
+use axum::Extension;
+use axum::extract::{Path, Query, State};
+use axum::response::Json;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use tracing::debug;
+use vectorizer::auth::middleware::AuthState;
+use vectorizer::db::similarity_to_distance;
+use vectorizer::db::vector_store::CollectionType;
+use vectorizer::hub::middleware::RequestTenantContext;
+use vectorizer::models::{DistanceMetric, Payload, Vector};
+use vectorizer::search::projection::{
+    self, DEFAULT_PROJECTION_SAMPLE, MAX_PROJECTION_SAMPLE, ProjectionMethod,
+};
+use vectorizer::security::PayloadView;
+
+use super::common::extract_tenant_id;
+use super::filtered_search::filtered_search;
+use super::tenant_partition::Partition;
+use crate::server::VectorizerServer;
+use crate::server::core::caller_payload_view;
+use crate::server::error_middleware::{
+    ErrorResponse, create_bad_request_error, create_not_found_error, create_validation_error,
+};
+
+/// Most vectors one sample may hold
+const MAX_SAMPLE: usize = 100;
+/// Most neighbors listed per vector
+const MAX_NEIGHBORS: usize = 50;
+/// Longest `preview_chars` accepted
+pub(super) const MAX_PREVIEW_CHARS: usize = 10_000;
+/// Items kept from each payload array in a preview
+const PREVIEW_ARRAY_ITEMS: usize = 10;
+
+fn default_sample_size() -> usize {
+    10
+}
+
+fn default_sample_neighbors() -> usize {
+    3
+}
+
+fn default_neighbors() -> usize {
+    10
+}
+
+fn default_preview_chars() -> usize {
+    200
+}
+
+fn default_projection_sample() -> usize {
+    DEFAULT_PROJECTION_SAMPLE
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SampleParams {
+    #[serde(default = "default_sample_size")]
+    n: usize,
+    #[serde(default = "default_sample_neighbors")]
+    neighbors: usize,
+    #[serde(default = "default_preview_chars")]
+    preview_chars: usize,
+    #[serde(default)]
+    seed: Option<u64>,
+    #[serde(default)]
+    include_vector: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NeighborParams {
+    #[serde(default = "default_neighbors")]
+    k: usize,
+    #[serde(default = "default_preview_chars")]
+    preview_chars: usize,
+    #[serde(default)]
+    include_vector: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ProjectionRequest {
+    #[serde(default)]
+    method: ProjectionMethod,
+    #[serde(default = "default_projection_sample")]
+    sample_size: usize,
+    #[serde(default)]
+    seed: Option<u64>,
+    #[serde(default)]
+    include_payload: bool,
+    #[serde(default = "default_preview_chars")]
+    preview_chars: usize,
+}
+
+/// `value` with strings cut to `max_chars` characters and arrays to
+/// their first [`PREVIEW_ARRAY_ITEMS`] items, at any depth.
+fn preview_value(value: Value, max_chars: usize) -> Value {
+    match value {
+        Value::String(text) => match text.char_indices().nth(max_chars) {
+            Some((end, _)) => Value::String(format!("{}…", &text[..end])),
+            None => Value::String(text),
+        },
+        Value::Array(items) => Value::Array(
+            items
+                .into_iter()
+                .take(PREVIEW_ARRAY_ITEMS)
+                .map(|item| preview_value(item, max_chars))
+                .collect(),
+        ),
+        Value::Object(fields) => Value::Object(
+            fields
+                .into_iter()
+                .map(|(key, field)| (key, preview_value(field, max_chars)))
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+/// Preview of a stored payload; encrypted payloads are only flagged.
+pub(super) fn payload_preview(payload: Option<Payload>, max_chars: usize) -> Value {
+    match payload {
+        Some(payload) if payload.is_encrypted() => json!({ "encrypted": true }),
+        Some(payload) => preview_value(payload.data, max_chars),
+        None => Value::Null,
+    }
+}
+
+/// Previews payloads of one collection for one caller.
+pub(super) struct Previewer {
+    pub(super) view: PayloadView,
+    pub(super) collection: String,
+    pub(super) max_chars: usize,
+}
+
+impl Previewer {
+    /// [`payload_preview`] of `payload` once the caller's redaction
+    /// policy has been applied to it.
+    pub(super) fn preview(&self, mut payload: Option<Payload>) -> Value {
+        self.view.redact(&self.collection, &mut payload);
+        payload_preview(payload, self.max_chars)
+    }
+}
+
+/// A vector as a browser entry.
+fn browser_entry(vector: Vector, previewer: &Previewer, include_vector: bool) -> Value {
+    let mut entry = json!({
+        "id": vector.id,
+        "payload_preview": previewer.preview(vector.payload),
+    });
+    if include_vector {
+        entry["vector"] = json!(vector.data);
+    }
+    entry
+}
+
+/// The `k` nearest neighbors of `vector`, itself excluded.
+fn nearest_neighbors(
+    collection: &CollectionType,
+    metric: DistanceMetric,
+    vector: &Vector,
+    k: usize,
+    partition: Option<&Partition>,
+    previewer: &Previewer,
+) -> Result<Vec<Value>, ErrorResponse> {
+    let results = filtered_search(
+        collection,
+        &vector.data,
+        k,
+        partition.map(|p| p.tenant.as_str()),
+        |r| r.id != vector.id,
+    )?;
+    Ok(results
+        .into_iter()
+        .map(|r| {
+            json!({
+                "id": r.id,
+                "score": r.score,
+                "distance": similarity_to_distance(metric, r.score),
+                "payload_preview": previewer.preview(r.payload),
+            })
+        })
+        .collect())
+}
+
+/// Up to `n` vectors of `collection` drawn at random, and how many there
+/// were to draw from.
+pub(super) fn random_sample(
+    collection: &CollectionType,
+    partition: Option<&Partition>,
+    n: usize,
+    rng: &mut fastrand::Rng,
+) -> (usize, Vec<Vector>) {
+    let mut vectors: Vec<Vector> = collection
+        .get_all_vectors()
+        .into_iter()
+        .filter(|v| partition.is_none_or(|p| p.owns(v.payload.as_ref())))
+        .collect();
+    let total = vectors.len();
+    rng.shuffle(&mut vectors);
+    vectors.truncate(n);
+    (total, vectors)
+}
+
+/// GET /collections/{name}/sample
+///
+/// Query: `n` (default 10, at most 100), `neighbors` per sampled vector
+/// (default 3, at most 50, `0` for none), `preview_chars` (default 200),
+/// `seed` for a repeatable sample, `include_vector`. Response:
+/// `{collection, metric, dimension, total, sample: [{id, payload_preview,
+/// vector?, neighbors: [{id, score, distance, payload_preview}]}]}`.
+pub async fn sample_collection(
+    State(state): State<VectorizerServer>,
+    Path(collection_name): Path<String>,
+    tenant_ctx: Option<Extension<RequestTenantContext>>,
+    auth: Option<Extension<AuthState>>,
+    Query(params): Query<SampleParams>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let n = params.n.min(MAX_SAMPLE);
+    let k = params.neighbors.min(MAX_NEIGHBORS);
+    let previewer = Previewer {
+        view: caller_payload_view(state.payload_redactor.clone(), auth.as_deref()),
+        collection: collection_name.clone(),
+        max_chars: params.preview_chars.min(MAX_PREVIEW_CHARS),
+    };
+
+    let tenant_id = extract_tenant_id(&tenant_ctx);
+    let collection = state
+        .store
+        .get_collection_with_owner(&collection_name, tenant_id.as_ref())
+        .map_err(ErrorResponse::from)?;
+    let partition = Partition::resolve(&state, &collection_name, tenant_ctx.as_ref());
+    let config = collection.config().clone();
+
+    let mut rng = match params.seed {
+        Some(seed) => fastrand::Rng::with_seed(seed),
+        None => fastrand::Rng::new(),
+    };
+    let (total, vectors) = random_sample(&collection, partition.as_ref(), n, &mut rng);
+
+    let mut sample = Vec::with_capacity(vectors.len());
+    for vector in vectors {
+        let neighbors = nearest_neighbors(
+            &collection,
+            config.metric,
+            &vector,
+            k,
+            partition.as_ref(),
+            &previewer,
+        )?;
+        let mut entry = browser_entry(vector, &previewer, params.include_vector);
+        entry["neighbors"] = json!(neighbors);
+        sample.push(entry);
+    }
+    debug!(
+        "Sampled {} of {} vectors from '{}'",
+        sample.len(),
+        total,
+        collection_name
+    );
+
+    Ok(Json(json!({
+        "collection": collection_name,
+        "metric": config.metric,
+        "dimension": config.dimension,
+        "total": total,
+        "sample": sample,
+    })))
+}
+
+/// GET /collections/{name}/vectors/{id}/neighbors
+///
+/// Query: `k` (default 10, at most 50), `preview_chars` (default 200),
+/// `include_vector`. Response: `{collection, metric, id, payload_preview,
+/// vector?, neighbors: [{id, score, distance, payload_preview}]}`, nearest
+/// first. In a partitioned collection another tenant's vector is a 404.
+pub async fn vector_neighbors(
+    State(state): State<VectorizerServer>,
+    Path((collection_name, vector_id)): Path<(String, String)>,
+    tenant_ctx: Option<Extension<RequestTenantContext>>,
+    auth: Option<Extension<AuthState>>,
+    Query(params): Query<NeighborParams>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let k = params.k.min(MAX_NEIGHBORS);
+    let previewer = Previewer {
+        view: caller_payload_view(state.payload_redactor.clone(), auth.as_deref()),
+        collection: collection_name.clone(),
+        max_chars: params.preview_chars.min(MAX_PREVIEW_CHARS),
+    };
+
+    let tenant_id = extract_tenant_id(&tenant_ctx);
+    let collection = state
+        .store
+        .get_collection_with_owner(&collection_name, tenant_id.as_ref())
+        .map_err(ErrorResponse::from)?;
+    let partition = Partition::resolve(&state, &collection_name, tenant_ctx.as_ref());
+    let metric = collection.config().metric;
+
+    let vector = collection
+        .get_vector(&vector_id)
+        .map_err(|_| create_not_found_error("vector", &vector_id))?;
+    if partition
+        .as_ref()
+        .is_some_and(|p| !p.owns(vector.payload.as_ref()))
+    {
+        return Err(create_not_found_error("vector", &vector_id));
+    }
+
+    let neighbors = nearest_neighbors(
+        &collection,
+        metric,
+        &vector,
+        k,
+        partition.as_ref(),
+        &previewer,
+    )?;
+    let mut response = browser_entry(vector, &previewer, params.include_vector);
+    response["collection"] = json!(collection_name);
+    response["metric"] = json!(metric);
+    response["neighbors"] = json!(neighbors);
+    Ok(Json(response))
+}
+
+/// POST /collections/{name}/projection
+///
+/// Body: `{method?: "pca" | "umap", sample_size?, seed?, include_payload?,
+/// preview_chars?}`. `sample_size` defaults to 500 and is capped at
+/// `MAX_PROJECTION_SAMPLE`; `"umap"` needs a server built with the `umap`
+/// feature. Response: `{collection, metric, method, total, sampled, seed,
+/// explained_variance, points: [{id, x, y, payload_preview?}]}`, where
+/// `explained_variance` is the share of variance on each PCA axis (`null`
+/// for UMAP) and `seed` reproduces the same sample and layout.
+pub async fn project_collection(
+    State(state): State<VectorizerServer>,
+    Path(collection_name): Path<String>,
+    tenant_ctx: Option<Extension<RequestTenantContext>>,
+    auth: Option<Extension<AuthState>>,
+    Json(request): Json<ProjectionRequest>,
+) -> Result<Json<Value>, ErrorResponse> {
+    if !request.method.is_available() {
+        return Err(create_validation_error(
+            "method",
+            "umap is not available: the server was built without the `umap` feature",
+        ));
+    }
+    if request.sample_size == 0 {
+        return Err(create_validation_error("sample_size", "must be at least 1"));
+    }
+    let n = request.sample_size.min(MAX_PROJECTION_SAMPLE);
+    let previewer = Previewer {
+        view: caller_payload_view(state.payload_redactor.clone(), auth.as_deref()),
+        collection: collection_name.clone(),
+        max_chars: request.preview_chars.min(MAX_PREVIEW_CHARS),
+    };
+    // A generated seed stays within a JavaScript number's exact range
+    let seed = request.seed.unwrap_or_else(|| u64::from(fastrand::u32(..)));
+
+    let tenant_id = extract_tenant_id(&tenant_ctx);
+    let collection = state
+        .store
+        .get_collection_with_owner(&collection_name, tenant_id.as_ref())
+        .map_err(ErrorResponse::from)?;
+    let partition = Partition::resolve(&state, &collection_name, tenant_ctx.as_ref());
+    let metric = collection.config().metric;
+
+    let mut rng = fastrand::Rng::with_seed(seed);
+    let (total, vectors) = random_sample(&collection, partition.as_ref(), n, &mut rng);
+    drop(collection);
+
+    let method = request.method;
+    let (vectors, projection) = tokio::task::spawn_blocking(move || {
+        let layout = projection::project(&vectors, metric, method, seed);
+        (vectors, layout)
+    })
+    .await
+    .map_err(|e| create_bad_request_error(&format!("projection task error: {}", e)))?;
+    let projection = projection.map_err(ErrorResponse::from)?;
+    debug!(
+        "Projected {} of {} vectors from '{}' with {:?}",
+        vectors.len(),
+        total,
+        collection_name,
+        method
+    );
+
+    let sampled = vectors.len();
+    let points: Vec<Value> = vectors
+        .into_iter()
+        .zip(projection.points)
+        .map(|(vector, [x, y])| {
+            let mut point = json!({ "id": vector.id, "x": x, "y": y });
+            if request.include_payload {
+                point["payload_preview"] = previewer.preview(vector.payload);
+            }
+            point
+        })
+        .collect();
+
+    Ok(Json(json!({
+        "collection": collection_name,
+        "metric": metric,
+        "method": projection.method,
+        "total": total,
+        "sampled": sampled,
+        "seed": seed,
+        "explained_variance": projection.explained_variance,
+        "points": points,
+    })))
+}
@@ -0,0 +1,304 @@
+//! `POST /collections/{name}/cluster` — topic discovery over stored
+//! vectors.
+//!
+//! Groups the collection's vectors with mini-batch k-means, or HDBSCAN in
+//! a server built with the `hdbscan` feature (see
+//! [`vectorizer::search::clustering`]), writes each vector's cluster id
+//! into its payload and reports every cluster's size, representative
+//! member and, on request, centroid. Cluster ids in payloads can then be
+//! used in filters and facets without exporting the data.
+//!
+//! The run is a `clustering` job: by default the response carries the
+//! report once clustering and write-back finished; with `?wait=false` it
+//! comes right away and `GET /jobs/{id}/result` downloads the report. In
+//! a tenant-partitioned collection only the caller's vectors are
+//! clustered and written, and representatives' previews are redacted
+//! with the caller's `api.payload_redaction` policy.
+
+use std::collections::HashMap;
+
+use axum::Extension;
+use axum::extract::{Path, Query, State};
+use axum::response::Json;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use tracing::{info, warn};
+use vectorizer::auth::middleware::AuthState;
+use vectorizer::error::VectorizerError;
+use vectorizer::hub::middleware::RequestTenantContext;
+use vectorizer::models::Vector;
+use vectorizer::search::clustering::{
+    self, ClusterAlgorithm, DEFAULT_KMEANS_BATCH_SIZE, DEFAULT_KMEANS_ITERATIONS,
+    DEFAULT_MIN_CLUSTER_SIZE, KMeansOptions, MAX_CLUSTERS,
+};
+
+use super::browse::{MAX_PREVIEW_CHARS, Previewer};
+use super::common::extract_tenant_id;
+use super::jobs::{JobOwner, WaitQuery, run_job};
+use super::tenant_partition::Partition;
+use crate::server::VectorizerServer;
+use crate::server::core::caller_payload_view;
+use crate::server::error_middleware::{ErrorResponse, create_validation_error};
+
+/// Most mini-batch updates a run may ask for
+const MAX_ITERATIONS: usize = 10_000;
+
+fn default_k() -> usize {
+    8
+}
+
+fn default_iterations() -> usize {
+    DEFAULT_KMEANS_ITERATIONS
+}
+
+fn default_batch_size() -> usize {
+    DEFAULT_KMEANS_BATCH_SIZE
+}
+
+fn default_min_cluster_size() -> usize {
+    DEFAULT_MIN_CLUSTER_SIZE
+}
+
+fn default_field() -> String {
+    "cluster_id".to_string()
+}
+
+fn default_write_back() -> bool {
+    true
+}
+
+fn default_preview_chars() -> usize {
+    120
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ClusterRequest {
+    #[serde(default)]
+    algorithm: ClusterAlgorithm,
+    #[serde(default = "default_k")]
+    k: usize,
+    #[serde(default = "default_iterations")]
+    iterations: usize,
+    #[serde(default = "default_batch_size")]
+    batch_size: usize,
+    seed: Option<u64>,
+    #[serde(default = "default_min_cluster_size")]
+    min_cluster_size: usize,
+    #[serde(default = "default_field")]
+    field: String,
+    #[serde(default = "default_write_back")]
+    write_back: bool,
+    #[serde(default)]
+    include_centroids: bool,
+    #[serde(default = "default_preview_chars")]
+    preview_chars: usize,
+}
+
+/// POST /collections/{name}/cluster
+///
+/// Body (all optional): `{algorithm: "kmeans" | "hdbscan", k: 8,
+/// iterations: 100, batch_size: 1024, seed, min_cluster_size: 5, field:
+/// "cluster_id", write_back: true, include_centroids: false,
+/// preview_chars: 120}`. `k`, `iterations`, `batch_size` and `seed` apply
+/// to k-means, `min_cluster_size` to HDBSCAN, whose noise vectors get
+/// `-1`. Report: `{collection, algorithm, seed, field, clustered,
+/// clusters_total, noise, inertia, written, skipped_encrypted, clusters:
+/// [{id, size, mean_distance, representative: {id, payload_preview},
+/// centroid?}]}`, largest clusters first. Encrypted payloads cannot be
+/// patched and are skipped.
+pub async fn cluster_collection(
+    State(state): State<VectorizerServer>,
+    Path(collection_name): Path<String>,
+    tenant_ctx: Option<Extension<RequestTenantContext>>,
+    auth: Option<Extension<AuthState>>,
+    owner: JobOwner,
+    Query(query): Query<WaitQuery>,
+    Json(request): Json<ClusterRequest>,
+) -> Result<Json<Value>, ErrorResponse> {
+    if !request.algorithm.is_available() {
+        return Err(create_validation_error(
+            "algorithm",
+            "hdbscan is not available: the server was built without the `hdbscan` feature",
+        ));
+    }
+    if request.k == 0 || request.k > MAX_CLUSTERS {
+        return Err(create_validation_error(
+            "k",
+            &format!("must be between 1 and {}", MAX_CLUSTERS),
+        ));
+    }
+    if request.batch_size == 0 {
+        return Err(create_validation_error("batch_size", "must be at least 1"));
+    }
+    if request.min_cluster_size < 2 {
+        return Err(create_validation_error(
+            "min_cluster_size",
+            "must be at least 2",
+        ));
+    }
+    if request.field.is_empty() {
+        return Err(create_validation_error("field", "must not be empty"));
+    }
+
+    // Verify the collection exists (and is the caller's) before spawning
+    let tenant_id = extract_tenant_id(&tenant_ctx);
+    let metric = state
+        .store
+        .get_collection_with_owner(&collection_name, tenant_id.as_ref())
+        .map_err(ErrorResponse::from)?
+        .config()
+        .metric;
+    let partition = Partition::resolve(&state, &collection_name, tenant_ctx.as_ref());
+    if partition.as_ref().is_some_and(|p| p.key == request.field) {
+        return Err(create_validation_error(
+            "field",
+            "is the collection's tenant partition key",
+        ));
+    }
+
+    let options = KMeansOptions {
+        k: request.k,
+        iterations: request.iterations.min(MAX_ITERATIONS),
+        batch_size: request.batch_size,
+        // A generated seed stays within a JavaScript number's exact range
+        seed: request.seed.unwrap_or_else(|| u64::from(fastrand::u32(..))),
+    };
+    let previewer = Previewer {
+        view: caller_payload_view(state.payload_redactor.clone(), auth.as_deref()),
+        collection: collection_name.clone(),
+        max_chars: request.preview_chars.min(MAX_PREVIEW_CHARS),
+    };
+    let server = state.clone();
+    let col_name = collection_name.clone();
+    let (job_id, report) = run_job(
+        vectorizer::jobs::CLUSTERING,
+        &collection_name,
+        owner,
+        query.wait,
+        move |job| {
+            let mut vectors: Vec<Vector> = server
+                .store
+                .get_collection(&col_name)?
+                .get_all_vectors()
+                .into_iter()
+                .filter(|v| {
+                    partition
+                        .as_ref()
+                        .is_none_or(|p| p.owns(v.payload.as_ref()))
+                })
+                .collect();
+            // Input order decides k-means' sampling; keep it stable
+            vectors.sort_unstable_by(|a, b| a.id.cmp(&b.id));
+
+            let result = match request.algorithm {
+                ClusterAlgorithm::KMeans => clustering::kmeans(&vectors, metric, options, job)?,
+                #[cfg(feature = "hdbscan")]
+                ClusterAlgorithm::Hdbscan => {
+                    clustering::hdbscan(&vectors, metric, request.min_cluster_size, job)?
+                }
+                #[cfg(not(feature = "hdbscan"))]
+                ClusterAlgorithm::Hdbscan => {
+                    return Err(VectorizerError::InvalidConfiguration {
+                        message: "hdbscan is not available".to_string(),
+                    });
+                }
+            };
+            let noise = result.labels.iter().filter(|l| l.is_none()).count();
+
+            let mut written = 0usize;
+            let mut skipped_encrypted = 0usize;
+            if request.write_back {
+                job.check_cancelled()?;
+                for (vector, label) in vectors.iter().zip(&result.labels) {
+                    if vector.payload.as_ref().is_some_and(|p| p.is_encrypted()) {
+                        skipped_encrypted += 1;
+                        continue;
+                    }
+                    // Patched against the stored payload, so writes made
+                    // while clustering ran are kept
+                    let id = label.map_or(-1, |c| c as i64);
+                    let patch = json!({ request.field.as_str(): id });
+                    let patched = server
+                        .store
+                        .patch_payload(&col_name, &vector.id, &patch, |_| {
+                            Ok::<_, VectorizerError>(())
+                        });
+                    match patched {
+                        Ok(_) => written += 1,
+                        // Deleted (or encrypted) since the scan
+                        Err(e) => warn!(
+                            "Cluster id of '{}' in '{}' not written: {}",
+                            vector.id, col_name, e
+                        ),
+                    }
+                }
+                if written > 0 {
+                    server.query_cache.invalidate_collection(&col_name);
+                    if let Some(ref auto_save) = server.auto_save_manager {
+                        auto_save.mark_changed();
+                    }
+                }
+            }
+
+            let clustered = vectors.len();
+            let mut by_id: HashMap<String, Vector> = vectors
+                .into_iter()
+                .map(|vector| (vector.id.clone(), vector))
+                .collect();
+            let clusters: Vec<Value> = result
+                .clusters
+                .into_iter()
+                .map(|cluster| {
+                    let payload = by_id
+                        .remove(&cluster.representative)
+                        .and_then(|v| v.payload);
+                    let mut summary = json!({
+                        "id": cluster.id,
+                        "size": cluster.size,
+                        "mean_distance": cluster.mean_distance,
+                        "representative": {
+                            "id": cluster.representative,
+                            "payload_preview": previewer.preview(payload),
+                        },
+                    });
+                    if request.include_centroids {
+                        summary["centroid"] = json!(cluster.centroid);
+                    }
+                    summary
+                })
+                .collect();
+            info!(
+                "Clustered {} vector(s) of '{}' with {:?}: {} cluster(s), {} noise, {} written",
+                clustered,
+                col_name,
+                result.algorithm,
+                clusters.len(),
+                noise,
+                written
+            );
+
+            Ok(json!({
+                "collection": col_name,
+                "algorithm": result.algorithm,
+                "seed": options.seed,
+                "field": request.field,
+                "clustered": clustered,
+                "clusters_total": clusters.len(),
+                "noise": noise,
+                "inertia": result.inertia,
+                "written": written,
+                "skipped_encrypted": skipped_encrypted,
+                "clusters": clusters,
+            }))
+        },
+    )
+    .await?;
+
+    let completed = report.is_some();
+    Ok(Json(json!({
+        "job_id": job_id,
+        "collection": collection_name,
+        "state": if completed { "completed" } else { "running" },
+        "report": report,
+    })))
+}
@@ -0,0 +1,118 @@
+//! `POST /collections/{name}/count` — how many vectors match a filter.
+//!
+//! The native counterpart of the Qdrant shim's `points/count`, so a
+//! client can size a result set before paging through it. The exact
+//! mode evaluates the filter on every vector's stored payload, without
+//! materializing the vectors; the approximate mode
+//! evaluates it on a random sample (see [`super::browse::random_sample`])
+//! and scales the matches to the collection. In a tenant-partitioned
+//! collection only the caller's vectors count. A filter on a key that
+//! `api.payload_redaction` rewrites for the caller is rejected, since
+//! the count would give the hidden values away.
+
+use axum::Extension;
+use axum::extract::{Path, State};
+use axum::response::Json;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use tracing::debug;
+use vectorizer::auth::middleware::AuthState;
+use vectorizer::hub::middleware::RequestTenantContext;
+use vectorizer::models::Payload;
+use vectorizer::models::qdrant::filter_processor::FilterProcessor;
+
+use super::browse::random_sample;
+use super::common::{default_true, extract_tenant_id, reject_redacted_filter};
+use super::retrieve::parse_filter;
+use super::tenant_partition::Partition;
+use crate::server::VectorizerServer;
+use crate::server::core::caller_payload_view;
+use crate::server::error_middleware::ErrorResponse;
+
+/// Vectors an approximate count evaluates the filter on
+const APPROXIMATE_COUNT_SAMPLE: usize = 1_000;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct CountRequest {
+    #[serde(default)]
+    filter: Option<Value>,
+    #[serde(default = "default_true")]
+    exact: bool,
+}
+
+/// POST /collections/{name}/count
+///
+/// Body (all optional): `{filter?, exact?: true}`. `filter` is a Qdrant
+/// filter or a metadata filter as in `POST /retrieve`; without one every
+/// vector counts. With `exact: false` the filter is evaluated on a random
+/// sample of at most 1000 vectors and the count extrapolated. Response:
+/// `{collection, count, exact, sampled?}`; `exact` is false only for an
+/// extrapolated count and `sampled` is the size of the sample evaluated.
+/// A filter on a key redacted for the caller is a validation error.
+pub async fn count_vectors(
+    State(state): State<VectorizerServer>,
+    Path(collection_name): Path<String>,
+    tenant_ctx: Option<Extension<RequestTenantContext>>,
+    auth: Option<Extension<AuthState>>,
+    Json(request): Json<CountRequest>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let filter = request.filter.as_ref().map(parse_filter).transpose()?;
+    if let Some(filter) = &filter {
+        let view = caller_payload_view(state.payload_redactor.clone(), auth.as_deref());
+        reject_redacted_filter(&view, &collection_name, filter)?;
+    }
+
+    let tenant_id = extract_tenant_id(&tenant_ctx);
+    let collection = state
+        .store
+        .get_collection_with_owner(&collection_name, tenant_id.as_ref())
+        .map_err(ErrorResponse::from)?;
+    let partition = Partition::resolve(&state, &collection_name, tenant_ctx.as_ref());
+    let admits = |payload: Option<&Payload>| {
+        filter
+            .as_ref()
+            .is_none_or(|filter| payload.is_some_and(|p| FilterProcessor::apply_filter(filter, p)))
+    };
+
+    let mut response = json!({"collection": collection_name, "exact": true});
+    if filter.is_none() && partition.is_none() {
+        response["count"] = json!(collection.vector_count());
+    } else if request.exact {
+        let count = collection
+            .vector_ids()
+            .iter()
+            .filter(|id| {
+                collection
+                    .with_payload(id, |payload| {
+                        partition.as_ref().is_none_or(|p| p.owns(payload)) && admits(payload)
+                    })
+                    .unwrap_or(false)
+            })
+            .count();
+        response["count"] = json!(count);
+    } else {
+        let mut rng = fastrand::Rng::new();
+        let (total, sample) = random_sample(
+            &collection,
+            partition.as_ref(),
+            APPROXIMATE_COUNT_SAMPLE,
+            &mut rng,
+        );
+        let matched = sample.iter().filter(|v| admits(v.payload.as_ref())).count();
+        let count = if sample.is_empty() {
+            0
+        } else {
+            (matched as f64 * total as f64 / sample.len() as f64).round() as usize
+        };
+        response["count"] = json!(count);
+        response["exact"] = json!(sample.len() == total);
+        response["sampled"] = json!(sample.len());
+    }
+    drop(collection);
+    debug!(
+        "Counted {} vector(s) of '{}' (exact: {})",
+        response["count"], collection_name, response["exact"]
+    );
+
+    Ok(Json(response))
+}
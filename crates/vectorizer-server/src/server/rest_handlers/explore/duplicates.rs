@@ -0,0 +1,215 @@
+//! `POST /collections/{name}/duplicates` — near-duplicate report.
+//!
+//! Scans the whole collection for groups of vectors whose search score
+//! reaches `threshold` (see [`vectorizer::search::duplicates`]) and
+//! reports each group with payload previews, so near-identical chunks
+//! (mirrored repos, re-ingested files) can be found and pruned.
+//!
+//! The scan runs as a `duplicate_report` job and the report is its
+//! result: by default the response carries it once the scan finished;
+//! with `?wait=false` the response comes right away, `GET /jobs/{id}`
+//! follows the scan and `GET /jobs/{id}/result` downloads the report. In
+//! a tenant-partitioned collection only the caller's vectors are scanned.
+//! Previews are redacted with the `api.payload_redaction` policy of the
+//! caller that started the scan, so the stored report holds nothing that
+//! caller could not read.
+
+use std::collections::HashMap;
+
+use axum::Extension;
+use axum::extract::{Path, Query, State};
+use axum::response::Json;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use tracing::info;
+use vectorizer::auth::middleware::AuthState;
+use vectorizer::db::SearchDeadline;
+use vectorizer::hub::middleware::RequestTenantContext;
+use vectorizer::models::Vector;
+use vectorizer::search::duplicates::{
+    DEFAULT_DUPLICATE_NEIGHBORS, DEFAULT_DUPLICATE_THRESHOLD, MAX_DUPLICATE_NEIGHBORS,
+    find_duplicates,
+};
+
+use super::browse::{MAX_PREVIEW_CHARS, Previewer};
+use super::common::extract_tenant_id;
+use super::jobs::{JobOwner, WaitQuery, run_job};
+use super::tenant_partition::Partition;
+use crate::server::VectorizerServer;
+use crate::server::core::caller_payload_view;
+use crate::server::error_middleware::{ErrorResponse, create_validation_error};
+
+/// Groups listed in a report by default
+const DEFAULT_MAX_GROUPS: usize = 1_000;
+/// Most groups a report may list
+const MAX_GROUPS: usize = 10_000;
+
+fn default_threshold() -> f32 {
+    DEFAULT_DUPLICATE_THRESHOLD
+}
+
+fn default_neighbors() -> usize {
+    DEFAULT_DUPLICATE_NEIGHBORS
+}
+
+fn default_preview_chars() -> usize {
+    120
+}
+
+fn default_max_groups() -> usize {
+    DEFAULT_MAX_GROUPS
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DuplicatesRequest {
+    #[serde(default = "default_threshold")]
+    threshold: f32,
+    #[serde(default = "default_neighbors")]
+    neighbors: usize,
+    #[serde(default = "default_preview_chars")]
+    preview_chars: usize,
+    #[serde(default = "default_max_groups")]
+    max_groups: usize,
+}
+
+/// POST /collections/{name}/duplicates
+///
+/// Body (all optional): `{threshold: 0.95, neighbors: 10, preview_chars:
+/// 120, max_groups: 1000}`. `neighbors` is how many nearest neighbors
+/// each vector is compared with (at most 100). Report: `{collection,
+/// threshold, scanned, groups_total, duplicates, truncated, groups:
+/// [{size, min_score, max_score, members: [{id, payload_preview}]}]}`,
+/// largest groups first; `duplicates` counts the members beyond the
+/// first of each group.
+pub async fn find_collection_duplicates(
+    State(state): State<VectorizerServer>,
+    Path(collection_name): Path<String>,
+    tenant_ctx: Option<Extension<RequestTenantContext>>,
+    auth: Option<Extension<AuthState>>,
+    owner: JobOwner,
+    Query(query): Query<WaitQuery>,
+    Json(request): Json<DuplicatesRequest>,
+) -> Result<Json<Value>, ErrorResponse> {
+    if !request.threshold.is_finite() {
+        return Err(create_validation_error("threshold", "must be a number"));
+    }
+    if request.neighbors == 0 {
+        return Err(create_validation_error("neighbors", "must be at least 1"));
+    }
+    let threshold = request.threshold;
+    let k = request.neighbors.min(MAX_DUPLICATE_NEIGHBORS);
+    let previewer = Previewer {
+        view: caller_payload_view(state.payload_redactor.clone(), auth.as_deref()),
+        collection: collection_name.clone(),
+        max_chars: request.preview_chars.min(MAX_PREVIEW_CHARS),
+    };
+    let max_groups = request.max_groups.min(MAX_GROUPS);
+
+    // Verify the collection exists (and is the caller's) before spawning
+    let tenant_id = extract_tenant_id(&tenant_ctx);
+    state
+        .store
+        .get_collection_with_owner(&collection_name, tenant_id.as_ref())
+        .map_err(ErrorResponse::from)?;
+    let partition = Partition::resolve(&state, &collection_name, tenant_ctx.as_ref());
+
+    let server = state.clone();
+    let col_name = collection_name.clone();
+    let (job_id, report) = run_job(
+        vectorizer::jobs::DUPLICATE_REPORT,
+        &collection_name,
+        owner,
+        query.wait,
+        move |job| {
+            let mut vectors: Vec<Vector> = server
+                .store
+                .get_collection(&col_name)?
+                .get_all_vectors()
+                .into_iter()
+                .filter(|v| {
+                    partition
+                        .as_ref()
+                        .is_none_or(|p| p.owns(v.payload.as_ref()))
+                })
+                .collect();
+            // Scan order decides which member leads a group; keep it stable
+            vectors.sort_unstable_by(|a, b| a.id.cmp(&b.id));
+            let scanned = vectors.len();
+            let tenant = partition.as_ref().map(|p| p.tenant.as_str());
+
+            // The vector itself is its own nearest neighbor, hence `k + 1`
+            let groups = find_duplicates(
+                &vectors,
+                threshold,
+                |vector| {
+                    let collection = server.store.get_collection(&col_name)?;
+                    match tenant {
+                        Some(tenant) => collection
+                            .search_partition_until(
+                                &vector.data,
+                                k + 1,
+                                tenant,
+                                &SearchDeadline::NONE,
+                            )
+                            .map(|outcome| outcome.results),
+                        None => collection.search(&vector.data, k + 1),
+                    }
+                },
+                job,
+            )?;
+
+            let groups_total = groups.len();
+            let duplicates: usize = groups.iter().map(|g| g.ids.len() - 1).sum();
+            let mut by_id: HashMap<String, Vector> = vectors
+                .into_iter()
+                .map(|vector| (vector.id.clone(), vector))
+                .collect();
+            let listed: Vec<Value> = groups
+                .into_iter()
+                .take(max_groups)
+                .map(|group| {
+                    let members: Vec<Value> = group
+                        .ids
+                        .iter()
+                        .map(|id| {
+                            let payload = by_id.remove(id).and_then(|v| v.payload);
+                            json!({
+                                "id": id,
+                                "payload_preview": previewer.preview(payload),
+                            })
+                        })
+                        .collect();
+                    json!({
+                        "size": members.len(),
+                        "min_score": group.min_score,
+                        "max_score": group.max_score,
+                        "members": members,
+                    })
+                })
+                .collect();
+            info!(
+                "Duplicate scan of '{}': {} group(s), {} duplicate(s) at threshold {}",
+                col_name, groups_total, duplicates, threshold
+            );
+
+            Ok(json!({
+                "collection": col_name,
+                "threshold": threshold,
+                "scanned": scanned,
+                "groups_total": groups_total,
+                "duplicates": duplicates,
+                "truncated": groups_total > listed.len(),
+                "groups": listed,
+            }))
+        },
+    )
+    .await?;
+
+    let completed = report.is_some();
+    Ok(Json(json!({
+        "job_id": job_id,
+        "collection": collection_name,
+        "state": if completed { "completed" } else { "running" },
+        "report": report,
+    })))
+}
@@ -0,0 +1,170 @@
+//! `POST /collections/{name}/facets` — value counts of payload fields.
+//!
+//! Counts how many vectors carry each value of the requested payload
+//! keys (see [`vectorizer::search::facets`]), so a filter UI can list
+//! its options and their counts without scrolling the collection. The
+//! counted set is the vectors matching `filter`, or, when `query` is
+//! given, the query's top `candidates` among them; without a query the
+//! filter is evaluated on the stored payloads, so no vector data is
+//! materialized. In a tenant-partitioned collection only the caller's
+//! vectors are counted.
+//! Keys that `api.payload_redaction` rewrites for the caller are
+//! rejected, since their counts would give the hidden values away.
+
+use axum::Extension;
+use axum::extract::{Path, State};
+use axum::response::Json;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use tracing::debug;
+use vectorizer::auth::middleware::AuthState;
+use vectorizer::hub::middleware::RequestTenantContext;
+use vectorizer::models::Payload;
+use vectorizer::models::qdrant::filter_processor::FilterProcessor;
+use vectorizer::search::facets::{
+    DEFAULT_FACET_LIMIT, MAX_FACET_KEYS, MAX_FACET_LIMIT, facet_counts,
+};
+
+use super::common::extract_tenant_id;
+use super::filtered_search::filtered_search;
+use super::retrieve::parse_filter;
+use super::tenant_partition::Partition;
+use crate::server::VectorizerServer;
+use crate::server::core::caller_payload_view;
+use crate::server::error_middleware::{
+    ErrorResponse, create_bad_request_error, create_validation_error,
+};
+
+/// Most search results a query's candidate set may hold
+const MAX_FACET_CANDIDATES: usize = 1_000;
+
+fn default_limit() -> usize {
+    DEFAULT_FACET_LIMIT
+}
+
+fn default_candidates() -> usize {
+    100
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FacetsRequest {
+    keys: Vec<String>,
+    #[serde(default)]
+    filter: Option<Value>,
+    #[serde(default)]
+    query: Option<String>,
+    #[serde(default = "default_candidates")]
+    candidates: usize,
+    #[serde(default = "default_limit")]
+    limit: usize,
+}
+
+/// POST /collections/{name}/facets
+///
+/// Body: `{keys: ["lang", "meta.year"], filter?, query?, candidates?:
+/// 100, limit?: 10}`. `filter` is a Qdrant filter or a metadata filter as
+/// in `POST /retrieve`; `query` restricts the count to its `candidates`
+/// best matches (at most 1000). `limit` is the values listed per key (at
+/// most 1000). Response: `{collection, counted, facets: [{key, values:
+/// [{value, count}], distinct, missing}]}`, values most frequent first.
+/// A key redacted for the caller is a validation error.
+pub async fn collection_facets(
+    State(state): State<VectorizerServer>,
+    Path(collection_name): Path<String>,
+    tenant_ctx: Option<Extension<RequestTenantContext>>,
+    auth: Option<Extension<AuthState>>,
+    Json(request): Json<FacetsRequest>,
+) -> Result<Json<Value>, ErrorResponse> {
+    if request.keys.is_empty() || request.keys.len() > MAX_FACET_KEYS {
+        return Err(create_validation_error(
+            "keys",
+            &format!("must name between 1 and {} payload keys", MAX_FACET_KEYS),
+        ));
+    }
+    if request.keys.iter().any(|key| key.is_empty()) {
+        return Err(create_validation_error("keys", "must not be empty strings"));
+    }
+    let view = caller_payload_view(state.payload_redactor.clone(), auth.as_deref());
+    if let Some(key) = request
+        .keys
+        .iter()
+        .find(|key| view.redacts_field(&collection_name, key))
+    {
+        return Err(create_validation_error(
+            "keys",
+            &format!("'{}' is redacted and cannot be faceted", key),
+        ));
+    }
+    if request
+        .query
+        .as_deref()
+        .is_some_and(|q| q.trim().is_empty())
+    {
+        return Err(create_validation_error("query", "must not be empty"));
+    }
+    if request.candidates == 0 {
+        return Err(create_validation_error("candidates", "must be at least 1"));
+    }
+    let limit = request.limit.min(MAX_FACET_LIMIT);
+    let filter = request.filter.as_ref().map(parse_filter).transpose()?;
+
+    let tenant_id = extract_tenant_id(&tenant_ctx);
+    let collection = state
+        .store
+        .get_collection_with_owner(&collection_name, tenant_id.as_ref())
+        .map_err(ErrorResponse::from)?;
+    let partition = Partition::resolve(&state, &collection_name, tenant_ctx.as_ref());
+    let admits = |payload: Option<&Payload>| {
+        filter
+            .as_ref()
+            .is_none_or(|filter| payload.is_some_and(|p| FilterProcessor::apply_filter(filter, p)))
+    };
+
+    let payloads: Vec<Option<Payload>> = match &request.query {
+        Some(query) => {
+            let embedding = state
+                .embed_for_collection(&collection_name, query)
+                .map_err(|e| {
+                    create_bad_request_error(&format!("Failed to generate embedding: {}", e))
+                })?;
+            filtered_search(
+                &collection,
+                &embedding,
+                request.candidates.min(MAX_FACET_CANDIDATES),
+                partition.as_ref().map(|p| p.tenant.as_str()),
+                |r| admits(r.payload.as_ref()),
+            )?
+            .into_iter()
+            .map(|r| r.payload)
+            .collect()
+        }
+        None => collection
+            .vector_ids()
+            .iter()
+            .filter_map(|id| {
+                collection
+                    .with_payload(id, |payload| {
+                        (partition.as_ref().is_none_or(|p| p.owns(payload)) && admits(payload))
+                            .then(|| payload.cloned())
+                    })
+                    .flatten()
+            })
+            .collect(),
+    };
+    drop(collection);
+
+    let counted = payloads.len();
+    let facets = facet_counts(payloads.iter().map(Option::as_ref), &request.keys, limit);
+    debug!(
+        "Counted {} facet(s) over {} vector(s) of '{}'",
+        facets.len(),
+        counted,
+        collection_name
+    );
+
+    Ok(Json(json!({
+        "collection": collection_name,
+        "counted": counted,
+        "facets": facets,
+    })))
+}
@@ -0,0 +1,107 @@
+//! `POST /collections/{name}/vectors/get` — fetch many vectors by id.
+//!
+//! The bulk counterpart of `GET /collections/{name}/vectors/{id}`: one
+//! round-trip (and one collection lookup, see
+//! [`vectorizer::db::VectorStore::get_vectors`]) for the hundreds of
+//! chunks a context assembly step needs. In a tenant-partitioned
+//! collection another tenant's vector is reported missing, as the
+//! single-vector endpoint reports it not found.
+
+use std::collections::HashSet;
+
+use axum::Extension;
+use axum::extract::{Path, State};
+use axum::response::Json;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use tracing::debug;
+use vectorizer::hub::middleware::RequestTenantContext;
+
+use super::common::{default_true, extract_tenant_id};
+use super::tenant_partition::Partition;
+use crate::server::VectorizerServer;
+use crate::server::error_middleware::{ErrorResponse, create_validation_error};
+
+/// Most ids one request may fetch
+const MAX_GET_IDS: usize = 1_000;
+
+#[derive(Debug, Deserialize)]
+pub struct GetVectorsRequest {
+    ids: Vec<String>,
+    #[serde(default = "default_true")]
+    with_vector: bool,
+    #[serde(default = "default_true")]
+    with_payload: bool,
+}
+
+/// POST /collections/{name}/vectors/get
+///
+/// Body: `{ids: [...], with_vector?: true, with_payload?: true}`, at most
+/// 1000 ids. Response: `{collection, vectors: [{id, vector?, payload?,
+/// document_id}], missing: [id]}`; `vectors` follows the order of `ids`
+/// (repeated ids are fetched once) and `missing` lists the ids the
+/// collection does not hold.
+pub async fn get_vectors(
+    State(state): State<VectorizerServer>,
+    Path(collection_name): Path<String>,
+    tenant_ctx: Option<Extension<RequestTenantContext>>,
+    Json(request): Json<GetVectorsRequest>,
+) -> Result<Json<Value>, ErrorResponse> {
+    if request.ids.is_empty() || request.ids.len() > MAX_GET_IDS {
+        return Err(create_validation_error(
+            "ids",
+            &format!("must list between 1 and {} ids", MAX_GET_IDS),
+        ));
+    }
+    let mut seen = HashSet::new();
+    let ids: Vec<String> = request
+        .ids
+        .into_iter()
+        .filter(|id| seen.insert(id.clone()))
+        .collect();
+
+    let tenant_id = extract_tenant_id(&tenant_ctx);
+    state
+        .store
+        .get_collection_with_owner(&collection_name, tenant_id.as_ref())
+        .map_err(ErrorResponse::from)?;
+    let partition = Partition::resolve(&state, &collection_name, tenant_ctx.as_ref());
+    let fetched = state
+        .store
+        .get_vectors(&collection_name, &ids)
+        .map_err(ErrorResponse::from)?;
+
+    let mut vectors = Vec::with_capacity(fetched.len());
+    let mut missing = Vec::new();
+    for (id, vector) in ids.into_iter().zip(fetched) {
+        let vector = vector.filter(|v| {
+            partition
+                .as_ref()
+                .is_none_or(|p| p.owns(v.payload.as_ref()))
+        });
+        let Some(vector) = vector else {
+            missing.push(id);
+            continue;
+        };
+        let mut entry = json!({"id": vector.id, "document_id": vector.document_id});
+        if request.with_vector {
+            entry["vector"] = json!(vector.data);
+        }
+        if request.with_payload {
+            entry["payload"] = json!(vector.payload.map(|p| p.data));
+        }
+        vectors.push(entry);
+    }
+    debug!(
+        "Fetched {} vector(s) of '{}', {} missing",
+        vectors.len(),
+        collection_name,
+        missing.len()
+    );
+
+    Ok(Json(json!({
+        "collection": collection_name,
+        "vectors": vectors,
+        "missing": missing,
+    })))
+}
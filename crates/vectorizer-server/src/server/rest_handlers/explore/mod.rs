@@ -0,0 +1,42 @@
+//! Reading a collection other than by search: bulk fetch, scrolling,
+//! counts, aggregates, facets, samples, duplicates, clusters and
+//! similarity matrices.
+//!
+//! - [`aggregate`]   — /collections/{name}/aggregate mean / medoid of a
+//!                     filter, optionally searched with
+//! - [`browse`]      — collection browser: /collections/{name}/sample,
+//!                     nearest neighbors of a stored vector and the 2D
+//!                     /projection of a sample
+//! - [`clustering`]  — /collections/{name}/cluster k-means / HDBSCAN run as
+//!                     a job, cluster ids written to payloads
+//! - [`count`]       — /collections/{name}/count vectors matching a filter,
+//!                     exact or sampled
+//! - [`duplicates`]  — /collections/{name}/duplicates near-duplicate report,
+//!                     run as a job
+//! - [`facets`]      — /collections/{name}/facets payload value counts
+//! - [`get_vectors`] — /collections/{name}/vectors/get bulk fetch by id
+//! - [`scroll`]      — /collections/{name}/scroll cursor pagination over a
+//!                     filter
+//! - [`similarity`]  — /collections/{name}/similarity_matrix
+
+use super::{common, compat::retrieve, filtered_search, jobs, search_common, tenant_partition};
+
+mod aggregate;
+mod browse;
+mod clustering;
+mod count;
+mod duplicates;
+mod facets;
+mod get_vectors;
+mod scroll;
+mod similarity;
+
+pub use aggregate::aggregate_vectors;
+pub use browse::{project_collection, sample_collection, vector_neighbors};
+pub use clustering::cluster_collection;
+pub use count::count_vectors;
+pub use duplicates::find_collection_duplicates;
+pub use facets::collection_facets;
+pub use get_vectors::get_vectors;
+pub use scroll::scroll_vectors;
+pub use similarity::similarity_matrix;
@@ -0,0 +1,146 @@
+//! `POST /collections/{name}/scroll` — page through a collection with a
+//! cursor.
+//!
+//! Vectors come back in id order and each page's `next_cursor` is the
+//! last id it returned, so the next page starts strictly after it:
+//! vectors inserted or deleted between pages neither shift the pages nor
+//! repeat an id, unlike an `offset`. The collection keeps its ids sorted
+//! until its next write, so pages do not re-sort them. The ids after the
+//! cursor are walked in order and the filter is evaluated on their
+//! stored payloads until the page is full, so only the vectors returned
+//! are materialized and every page but the last is full. In a tenant-partitioned collection
+//! only the caller's vectors are scrolled. A filter on a key that
+//! `api.payload_redaction` rewrites for the caller is rejected: the
+//! payloads come back redacted, but which vectors match would not be.
+
+use axum::Extension;
+use axum::extract::{Path, State};
+use axum::response::Json;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use tracing::debug;
+use vectorizer::auth::middleware::AuthState;
+use vectorizer::hub::middleware::RequestTenantContext;
+use vectorizer::models::qdrant::filter_processor::FilterProcessor;
+
+use super::common::{default_true, extract_tenant_id, reject_redacted_filter};
+use super::retrieve::parse_filter;
+use super::tenant_partition::Partition;
+use crate::server::VectorizerServer;
+use crate::server::core::caller_payload_view;
+use crate::server::error_middleware::{ErrorResponse, create_validation_error};
+
+/// Most vectors one page may hold
+const MAX_SCROLL_LIMIT: usize = 1_000;
+
+fn default_limit() -> usize {
+    100
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ScrollRequest {
+    #[serde(default)]
+    filter: Option<Value>,
+    #[serde(default = "default_limit")]
+    limit: usize,
+    #[serde(default)]
+    cursor: Option<String>,
+    #[serde(default)]
+    with_vector: bool,
+    #[serde(default = "default_true")]
+    with_payload: bool,
+}
+
+/// POST /collections/{name}/scroll
+///
+/// Body (all optional): `{filter?, limit?: 100, cursor?, with_vector?:
+/// false, with_payload?: true}`. `filter` is a Qdrant filter or a
+/// metadata filter as in `POST /retrieve`; `limit` is at most 1000; a
+/// `cursor` is the `next_cursor` of the previous page. Response:
+/// `{collection, vectors: [{id, vector?, payload?, document_id}],
+/// next_cursor}`, with `next_cursor` null on the last page. A filter on
+/// a key redacted for the caller is a validation error.
+pub async fn scroll_vectors(
+    State(state): State<VectorizerServer>,
+    Path(collection_name): Path<String>,
+    tenant_ctx: Option<Extension<RequestTenantContext>>,
+    auth: Option<Extension<AuthState>>,
+    Json(request): Json<ScrollRequest>,
+) -> Result<Json<Value>, ErrorResponse> {
+    if request.limit == 0 || request.limit > MAX_SCROLL_LIMIT {
+        return Err(create_validation_error(
+            "limit",
+            &format!("must be between 1 and {}", MAX_SCROLL_LIMIT),
+        ));
+    }
+    let filter = request.filter.as_ref().map(parse_filter).transpose()?;
+    if let Some(filter) = &filter {
+        let view = caller_payload_view(state.payload_redactor.clone(), auth.as_deref());
+        reject_redacted_filter(&view, &collection_name, filter)?;
+    }
+
+    let tenant_id = extract_tenant_id(&tenant_ctx);
+    let collection = state
+        .store
+        .get_collection_with_owner(&collection_name, tenant_id.as_ref())
+        .map_err(ErrorResponse::from)?;
+    let partition = Partition::resolve(&state, &collection_name, tenant_ctx.as_ref());
+
+    let ids = collection.sorted_vector_ids();
+    let start = request
+        .cursor
+        .as_deref()
+        .map_or(0, |cursor| ids.partition_point(|id| id.as_str() <= cursor));
+    let mut page: Vec<&String> = ids[start..]
+        .iter()
+        .filter(|id| {
+            collection
+                .with_payload(id, |payload| {
+                    partition.as_ref().is_none_or(|p| p.owns(payload))
+                        && filter.as_ref().is_none_or(|filter| {
+                            payload.is_some_and(|p| FilterProcessor::apply_filter(filter, p))
+                        })
+                })
+                .unwrap_or(false)
+        })
+        .take(request.limit + 1)
+        .collect();
+
+    let more = page.len() > request.limit;
+    page.truncate(request.limit);
+    let next_cursor = if more {
+        page.last().map(|id| (*id).clone())
+    } else {
+        None
+    };
+    let matching: Vec<_> = page
+        .into_iter()
+        .filter_map(|id| collection.get_vector(id).ok())
+        .collect();
+    drop(collection);
+    let vectors: Vec<Value> = matching
+        .into_iter()
+        .map(|vector| {
+            let mut entry = json!({"id": vector.id, "document_id": vector.document_id});
+            if request.with_vector {
+                entry["vector"] = json!(vector.data);
+            }
+            if request.with_payload {
+                entry["payload"] = json!(vector.payload.map(|p| p.data));
+            }
+            entry
+        })
+        .collect();
+    debug!(
+        "Scrolled {} vector(s) of '{}' (more: {})",
+        vectors.len(),
+        collection_name,
+        more
+    );
+
+    Ok(Json(json!({
+        "collection": collection_name,
+        "vectors": vectors,
+        "next_cursor": next_cursor,
+    })))
+}
@@ -0,0 +1,88 @@
+//! `similarity_matrix` — POST /collections/{name}/similarity_matrix,
+//! pairwise scores over a chosen set of stored vectors.
+
+use axum::extract::{Path, State};
+use axum::response::Json;
+use serde_json::{Value, json};
+use tracing::debug;
+
+use crate::server::VectorizerServer;
+use crate::server::error_middleware::{
+    ErrorResponse, create_bad_request_error, create_validation_error,
+};
+
+/// POST /collections/{name}/similarity_matrix
+///
+/// Body: `{"ids": ["a", "b", "c"], "metric": "cosine"}` (`metric` is
+/// optional and defaults to the collection's metric).
+///
+/// Returns the symmetric pairwise score matrix for the named vectors,
+/// computed server-side with the SIMD kernels. Capped at
+/// `MAX_SIMILARITY_MATRIX_IDS` IDs; unknown IDs are listed under
+/// `missing` instead of failing the request.
+pub async fn similarity_matrix(
+    State(state): State<VectorizerServer>,
+    Path(collection_name): Path<String>,
+    Json(payload): Json<Value>,
+) -> Result<Json<Value>, ErrorResponse> {
+    use vectorizer::models::DistanceMetric;
+    use vectorizer::search::MAX_SIMILARITY_MATRIX_IDS;
+
+    let ids: Vec<String> = payload
+        .get("ids")
+        .and_then(|i| i.as_array())
+        .ok_or_else(|| create_validation_error("ids", "missing or invalid ids parameter"))?
+        .iter()
+        .map(|v| {
+            v.as_str()
+                .map(str::to_string)
+                .ok_or_else(|| create_validation_error("ids", "every id must be a string"))
+        })
+        .collect::<Result<_, _>>()?;
+
+    if ids.is_empty() {
+        return Err(create_validation_error(
+            "ids",
+            "ids array must contain at least one entry",
+        ));
+    }
+    if ids.len() > MAX_SIMILARITY_MATRIX_IDS {
+        return Err(create_validation_error(
+            "ids",
+            &format!("at most {} ids are allowed", MAX_SIMILARITY_MATRIX_IDS),
+        ));
+    }
+
+    let metric = match payload.get("metric").filter(|m| !m.is_null()) {
+        Some(m) => Some(
+            serde_json::from_value::<DistanceMetric>(m.clone()).map_err(|_| {
+                create_validation_error("metric", "metric must be cosine, euclidean or dotproduct")
+            })?,
+        ),
+        None => None,
+    };
+
+    let store = state.store.clone();
+    let col_name = collection_name.clone();
+    let matrix =
+        tokio::task::spawn_blocking(move || store.similarity_matrix(&col_name, &ids, metric))
+            .await
+            .map_err(|e| create_bad_request_error(&format!("similarity matrix task error: {}", e)))?
+            .map_err(ErrorResponse::from)?;
+
+    debug!(
+        "similarity_matrix: collection={}, n={}, missing={}",
+        collection_name,
+        matrix.ids.len(),
+        matrix.missing.len()
+    );
+
+    Ok(Json(json!({
+        "collection": collection_name,
+        "ids": matrix.ids,
+        "metric": matrix.metric,
+        "kind": matrix.kind,
+        "matrix": matrix.matrix,
+        "missing": matrix.missing,
+    })))
+}
@@ -0,0 +1,46 @@
+//! Payload-filtered vector search shared by the browse, aggregate,
+//! facets, retrieve and Pinecone handlers.
+
+use vectorizer::db::SearchDeadline;
+use vectorizer::db::vector_store::CollectionType;
+use vectorizer::models::SearchResult;
+
+use crate::server::error_middleware::ErrorResponse;
+
+/// Candidates a filtered search first asks the index for, per result
+const FILTER_OVERSAMPLE: usize = 4;
+
+/// The `k` best matches of `query` in `collection` that `admits`
+/// accepts, searched within the `partition` tenant's vectors when given.
+///
+/// Oversamples the index and, while too few candidates pass, doubles the
+/// candidate count up to the whole collection.
+pub(super) fn filtered_search(
+    collection: &CollectionType,
+    query: &[f32],
+    k: usize,
+    partition: Option<&str>,
+    admits: impl Fn(&SearchResult) -> bool,
+) -> Result<Vec<SearchResult>, ErrorResponse> {
+    if k == 0 {
+        return Ok(Vec::new());
+    }
+    let total = collection.vector_count();
+    let mut candidates = k.saturating_mul(FILTER_OVERSAMPLE).min(total.max(k));
+    loop {
+        let results = match partition {
+            Some(tenant) => collection
+                .search_partition_until(query, candidates, tenant, &SearchDeadline::NONE)
+                .map(|outcome| outcome.results),
+            None => collection.search(query, candidates),
+        }
+        .map_err(ErrorResponse::from)?;
+        let exhausted = results.len() < candidates || candidates >= total;
+        let mut matches: Vec<SearchResult> = results.into_iter().filter(|r| admits(r)).collect();
+        if matches.len() >= k || exhausted {
+            matches.truncate(k);
+            return Ok(matches);
+        }
+        candidates = candidates.saturating_mul(2).min(total);
+    }
+}
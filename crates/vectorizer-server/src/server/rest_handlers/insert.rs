@@ -11,28 +11,23 @@ use std::path::PathBuf;
 
 use axum::Extension;
 use axum::extract::State;
-use axum::http::StatusCode;
 use axum::response::Json;
 use serde_json::{Value, json};
 use tracing::{debug, info, warn};
 use vectorizer::config::FileUploadConfig;
 use vectorizer::file_loader::chunker::Chunker;
 use vectorizer::file_loader::config::LoaderConfig;
+use vectorizer::hub::WriteAdmission;
 use vectorizer::hub::middleware::RequestTenantContext;
+use vectorizer::models::DedupStatus;
 use vectorizer_core::error::VectorizerError;
 
 use super::common::collection_metrics_uuid;
+use super::dedup::{InsertOneResult, insert_vector};
+use super::tenant_partition::Partition;
 use crate::server::VectorizerServer;
 use crate::server::error_middleware::{ErrorResponse, create_bad_request_error};
 
-/// Outcome of a single text insert — the vector ids that were created plus
-/// whether the input was chunked.
-#[derive(Debug)]
-pub(super) struct InsertOneResult {
-    pub vector_ids: Vec<String>,
-    pub chunked: bool,
-}
-
 /// Maximum length of a client-provided vector id. Chosen to leave room for
 /// the `#<chunk_index>` suffix and stay well under any sane key/index size.
 pub(super) const MAX_CLIENT_ID_LEN: usize = 256;
@@ -99,6 +94,20 @@ pub(super) fn build_chunk_payload(
     Value::Object(payload_map)
 }
 
+/// The stored form of `data`: encrypted with `public_key` when one is
+/// given, plain otherwise.
+pub(super) fn seal_payload(
+    data: Value,
+    public_key: Option<&str>,
+) -> Result<vectorizer::models::Payload, ErrorResponse> {
+    match public_key {
+        Some(key) => vectorizer::security::payload_encryption::encrypt_payload(&data, key)
+            .map(vectorizer::models::Payload::from_encrypted)
+            .map_err(|e| create_bad_request_error(&format!("Encryption failed: {}", e))),
+        None => Ok(vectorizer::models::Payload::new(data)),
+    }
+}
+
 /// Parse the optional `metadata` object from a request payload into a
 /// `HashMap<String, String>`. Non-string values are stringified via
 /// `serde_json::Value::to_string`.
@@ -239,52 +248,42 @@ fn load_default_collection_config() -> vectorizer::models::CollectionConfig {
         sharding: None,
         graph: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     }
 }
 
-/// HiveHub quota gate for vector insertion. Returns `Ok(())` when either
-/// HiveHub is disabled, the quota check passes, or the hub call itself
-/// errors (we log + allow to match the existing `insert_text` behavior).
-/// Returns `Err(QUOTA_EXCEEDED)` only when HiveHub explicitly denies.
-pub(super) async fn check_insert_quota(
+/// Tenant quota gate for vector insertion into `collection_name`,
+/// before anything is written. A no-op when HiveHub is disabled.
+/// Refuses with 429 `QUOTA_EXCEEDED` when `estimated_vectors`
+/// (at their raw `f32` size) would take the tenant past its vector or
+/// byte quota; see [`vectorizer::hub::HubManager::admit_insert`].
+/// Hold the returned admission until the vectors are inserted.
+pub(crate) async fn check_insert_quota(
     state: &VectorizerServer,
     tenant_ctx: Option<&Extension<RequestTenantContext>>,
+    collection_name: &str,
     estimated_vectors: usize,
-) -> Result<(), ErrorResponse> {
+) -> Result<WriteAdmission, ErrorResponse> {
     let Some(hub_manager) = state.hub_manager.as_ref() else {
-        return Ok(());
+        return Ok(WriteAdmission::default());
     };
-    let tenant_id = tenant_ctx
-        .map(|ctx| ctx.0.0.tenant_id.as_str())
-        .unwrap_or("default");
-    match hub_manager
-        .check_quota(
-            tenant_id,
-            vectorizer::hub::QuotaType::VectorCount,
-            estimated_vectors as u64,
-        )
+    let tenant_id = tenant_ctx.map(|ctx| ctx.0.0.tenant_id.as_str());
+    hub_manager
+        .admit_insert(&state.store, tenant_id, collection_name, estimated_vectors)
         .await
-    {
-        Ok(true) => Ok(()),
-        Ok(false) => {
+        .map_err(|e| {
             warn!(
-                "Vector insertion denied for tenant {}: quota exceeded (estimated {} vectors)",
-                tenant_id, estimated_vectors
+                "Vector insertion into '{}' denied for tenant {:?}: {}",
+                collection_name, tenant_id, e
             );
-            Err(ErrorResponse::new(
-                "QUOTA_EXCEEDED".to_string(),
-                format!(
-                    "Vector quota exceeded. Estimated {} vectors needed. Please upgrade your plan or delete unused vectors.",
-                    estimated_vectors
-                ),
-                StatusCode::TOO_MANY_REQUESTS,
-            ))
-        }
-        Err(e) => {
-            warn!("Failed to check quota with HiveHub: {}", e);
-            Ok(())
-        }
-    }
+            ErrorResponse::from(e)
+        })
 }
 
 /// Record a successful vector-insert against the HiveHub usage counters.
@@ -367,8 +366,9 @@ pub(super) fn mark_collection_dirty(
 /// inputs and the prefix for `<id>#<chunk_index>` chunk ids when the input
 /// is auto-chunked. It is also stored as `parent_id` on chunk payloads so
 /// chunks can be grouped or deleted by source document. When absent, the
-/// server falls back to a UUID v4 per vector and uses a single shared UUID
-/// as `parent_id` for the chunk group.
+/// server generates an ID per vector from the collection's `id_policy`
+/// (UUID v4 by default) and uses a single shared one as `parent_id` for
+/// the chunk group.
 ///
 /// Returns the new vector ids + whether the text was chunked.
 pub(super) async fn insert_one_text(
@@ -402,21 +402,39 @@ pub(super) async fn insert_one_text(
 
     ensure_collection_exists(state, collection_name)?;
 
+    let partition = Partition::resolve(state, collection_name, tenant_ctx);
+    if let Some(partition) = &partition {
+        partition.refuse_encryption(public_key)?;
+    }
+
+    // The collection's chunking settings fill in what the request leaves out
+    let chunking = state
+        .store
+        .get_collection(collection_name)
+        .ok()
+        .and_then(|c| c.config().chunking.clone())
+        .unwrap_or_default();
     let upload_config = FileUploadConfig::default();
-    let chunk_size_val = chunk_size.unwrap_or(upload_config.default_chunk_size);
+    let chunk_size_val = chunk_size
+        .or(chunking.max_chunk_size)
+        .unwrap_or(upload_config.default_chunk_size);
     let estimated_vectors = if should_chunk {
         std::cmp::max(1, text_len.div_ceil(chunk_size_val))
     } else {
         1
     };
 
-    check_insert_quota(state, tenant_ctx, estimated_vectors).await?;
+    let _tenant_admission =
+        check_insert_quota(state, tenant_ctx, collection_name, estimated_vectors).await?;
 
     let mut vector_ids: Vec<String> = Vec::new();
+    let mut dedup: Vec<DedupStatus> = Vec::new();
     let mut last_embedding_len = 0usize;
 
     if should_chunk {
-        let chunk_overlap_val = chunk_overlap.unwrap_or(upload_config.default_chunk_overlap);
+        let chunk_overlap_val = chunk_overlap
+            .or(chunking.chunk_overlap)
+            .unwrap_or(upload_config.default_chunk_overlap);
 
         let loader_config = LoaderConfig {
             max_chunk_size: chunk_size_val,
@@ -429,7 +447,7 @@ pub(super) async fn insert_one_text(
             max_file_size: upload_config.max_file_size,
         };
 
-        let chunker = Chunker::new(loader_config);
+        let chunker = Chunker::with_strategy(loader_config, chunking.strategy);
 
         let file_path = metadata
             .get("filename")
@@ -451,16 +469,19 @@ pub(super) async fn insert_one_text(
         // `parent_id` is recorded on every chunk's payload so chunks of the
         // same source document can be located together (delete-by-doc, RAG
         // citation, group-by-parent searches). When the caller supplied a
-        // client id we use it verbatim; otherwise we mint a single UUID
-        // shared by all chunks of this insert.
+        // client id we use it verbatim; otherwise we mint a single ID
+        // (from the collection's ID policy) shared by all chunks of this
+        // insert.
         let parent_id: String = client_id
             .map(str::to_string)
-            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+            .unwrap_or_else(|| state.store.new_vector_id(collection_name));
 
         for chunk in &chunks {
-            let embedding = state.embedding_manager.embed(&chunk.content).map_err(|e| {
-                create_bad_request_error(&format!("Failed to generate embedding: {}", e))
-            })?;
+            let embedding = state
+                .embed_for_collection(collection_name, &chunk.content)
+                .map_err(|e| {
+                    create_bad_request_error(&format!("Failed to generate embedding: {}", e))
+                })?;
             last_embedding_len = embedding.len();
 
             // Flat payload shape (phase9): all fields live at the payload
@@ -470,29 +491,26 @@ pub(super) async fn insert_one_text(
             // is still tolerated by readers via
             // `FileOperations::metadata_view`, but new writes never
             // produce it.
-            let payload_data = build_chunk_payload(
+            let mut payload_data = build_chunk_payload(
                 &chunk.content,
                 &chunk.file_path,
                 chunk.chunk_index,
                 &parent_id,
                 &metadata,
             );
+            if let Some(partition) = &partition {
+                partition.stamp(&mut payload_data)?;
+            }
 
-            let payload = if let Some(key) = public_key {
-                let encrypted = vectorizer::security::payload_encryption::encrypt_payload(
-                    &payload_data,
-                    key,
-                )
-                .map_err(|e| create_bad_request_error(&format!("Encryption failed: {}", e)))?;
-                vectorizer::models::Payload::from_encrypted(encrypted)
-            } else {
-                vectorizer::models::Payload::new(payload_data)
-            };
+            let payload = seal_payload(payload_data, public_key)?;
 
             let vector_id = match client_id {
                 Some(id) => format!("{}{}{}", id, CLIENT_ID_CHUNK_SEPARATOR, chunk.chunk_index),
-                None => uuid::Uuid::new_v4().to_string(),
+                None => state.store.new_vector_id(collection_name),
             };
+            if let Some(partition) = &partition {
+                partition.check_insert(state, collection_name, &vector_id)?;
+            }
             let vector = vectorizer::models::Vector {
                 id: vector_id.clone(),
                 data: embedding,
@@ -501,38 +519,36 @@ pub(super) async fn insert_one_text(
                 document_id: None,
             };
 
-            state
-                .store
-                .insert(collection_name, vec![vector])
-                .map_err(ErrorResponse::from)?;
-
+            let (vector_id, status) = insert_vector(state, collection_name, vector)?;
             vector_ids.push(vector_id);
+            dedup.push(status);
         }
     } else {
-        let embedding = state.embedding_manager.embed(text).map_err(|e| {
-            create_bad_request_error(&format!("Failed to generate embedding: {}", e))
-        })?;
+        let embedding = state
+            .embed_for_collection(collection_name, text)
+            .map_err(|e| {
+                create_bad_request_error(&format!("Failed to generate embedding: {}", e))
+            })?;
         last_embedding_len = embedding.len();
 
-        let payload_json = serde_json::Value::Object(
+        let mut payload_json = serde_json::Value::Object(
             metadata
                 .iter()
                 .map(|(k, v)| (k.clone(), serde_json::Value::String(v.clone())))
                 .collect(),
         );
+        if let Some(partition) = &partition {
+            partition.stamp(&mut payload_json)?;
+        }
 
-        let payload_data = if let Some(key) = public_key {
-            let encrypted =
-                vectorizer::security::payload_encryption::encrypt_payload(&payload_json, key)
-                    .map_err(|e| create_bad_request_error(&format!("Encryption failed: {}", e)))?;
-            vectorizer::models::Payload::from_encrypted(encrypted)
-        } else {
-            vectorizer::models::Payload::new(payload_json)
-        };
+        let payload_data = seal_payload(payload_json, public_key)?;
 
         let vector_id = client_id
             .map(str::to_string)
-            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+            .unwrap_or_else(|| state.store.new_vector_id(collection_name));
+        if let Some(partition) = &partition {
+            partition.check_insert(state, collection_name, &vector_id)?;
+        }
         let vector = vectorizer::models::Vector {
             id: vector_id.clone(),
             data: embedding,
@@ -541,34 +557,30 @@ pub(super) async fn insert_one_text(
             document_id: None,
         };
 
-        state
-            .store
-            .insert(collection_name, vec![vector])
-            .map_err(ErrorResponse::from)?;
-
+        let (vector_id, status) = insert_vector(state, collection_name, vector)?;
         vector_ids.push(vector_id);
+        dedup.push(status);
     }
 
-    record_insert_usage(
-        state,
-        collection_name,
-        last_embedding_len,
-        vector_ids.len() as u64,
-    )
-    .await;
+    let result = InsertOneResult {
+        vector_ids,
+        chunked: should_chunk,
+        dedup,
+    };
+    // Duplicates the dedup policy skipped or merged are not new vectors
+    let created = result.created();
+    record_insert_usage(state, collection_name, last_embedding_len, created as u64).await;
 
-    mark_collection_dirty(state, collection_name, &vector_ids);
+    mark_collection_dirty(state, collection_name, &result.vector_ids);
 
     info!(
-        "Successfully inserted {} vector(s) into collection '{}'",
-        vector_ids.len(),
-        collection_name
+        "Successfully inserted {} vector(s) into collection '{}' ({} duplicate(s))",
+        created,
+        collection_name,
+        result.vector_ids.len() - created
     );
 
-    Ok(InsertOneResult {
-        vector_ids,
-        chunked: should_chunk,
-    })
+    Ok(result)
 }
 
 /// POST /insert — insert a single text document, auto-chunking large inputs.
@@ -643,14 +655,18 @@ pub async fn insert_text(
         .inc();
     drop(timer);
 
-    Ok(Json(json!({
+    let mut response = json!({
         "message": format!(
             "Text inserted successfully ({} vector(s) created)",
-            result.vector_ids.len()
+            result.created()
         ),
-        "vectors_created": result.vector_ids.len(),
+        "vectors_created": result.created(),
         "vector_ids": result.vector_ids,
         "collection": collection_name,
         "chunked": result.chunked
-    })))
+    });
+    if let Some(dedup) = result.dedup_report() {
+        response["dedup"] = json!(dedup);
+    }
+    Ok(Json(response))
 }
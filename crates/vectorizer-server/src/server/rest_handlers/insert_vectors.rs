@@ -15,14 +15,16 @@ use axum::response::Json;
 use serde_json::{Value, json};
 use tracing::info;
 use vectorizer::hub::middleware::RequestTenantContext;
+use vectorizer::models::DedupStatus;
 
-use super::common::admit_upsert;
+use super::common::{admit_upsert, f32_array};
+use super::dedup::{DedupTally, insert_vector};
 use super::insert::{
-    check_insert_quota, ensure_collection_exists, mark_collection_dirty, parse_metadata,
-    record_insert_usage, validate_client_id,
+    check_insert_quota, ensure_collection_exists, parse_metadata, seal_payload, validate_client_id,
 };
+use super::tenant_partition::Partition;
 use crate::server::VectorizerServer;
-use crate::server::error_middleware::{ErrorResponse, create_bad_request_error};
+use crate::server::error_middleware::ErrorResponse;
 
 /// POST /insert_vectors — bulk-insert pre-computed embeddings with
 /// caller-supplied vector ids. Skips the embedding pipeline entirely;
@@ -37,7 +39,7 @@ use crate::server::error_middleware::{ErrorResponse, create_bad_request_error};
 ///   "collection": "docs",
 ///   "vectors": [
 ///     {
-///       "id": "doc:1",                  // optional — falls back to UUID v4
+///       "id": "doc:1",                  // optional — generated from the collection's id_policy
 ///       "embedding": [0.1, 0.2, ...],   // required, length == collection.dimension
 ///       "payload": { ... },             // optional, arbitrary JSON
 ///       "metadata": { "k": "v", ... }   // optional fallback when `payload` absent
@@ -48,7 +50,11 @@ use crate::server::error_middleware::{ErrorResponse, create_bad_request_error};
 /// ```
 ///
 /// Response shape mirrors `/insert_texts`: `{collection, inserted,
-/// failed, count, results: [{index, client_id, status, vector_ids}]}`.
+/// deduplicated, failed, count, results: [{index, client_id, status,
+/// vector_ids}]}`. Under the collection's dedup policy an item's status
+/// is `skipped` or `merged` instead of `ok`, with `duplicate_of`,
+/// `reason` and, for similarity matches, `score`; its `vector_ids` then
+/// name the stored duplicate.
 pub async fn insert_vectors(
     State(state): State<VectorizerServer>,
     tenant_ctx: Option<Extension<RequestTenantContext>>,
@@ -109,11 +115,18 @@ pub async fn insert_vectors(
         .map(|c| c.config().dimension)
         .map_err(ErrorResponse::from)?;
 
-    check_insert_quota(&state, tenant_ctx.as_ref(), vectors_in.len()).await?;
+    let _tenant_admission = check_insert_quota(
+        &state,
+        tenant_ctx.as_ref(),
+        &collection_name,
+        vectors_in.len(),
+    )
+    .await?;
+
+    let partition = Partition::resolve(&state, &collection_name, tenant_ctx.as_ref());
 
     let mut results: Vec<Value> = Vec::with_capacity(vectors_in.len());
-    let mut inserted_ids: Vec<String> = Vec::with_capacity(vectors_in.len());
-    let mut inserted: usize = 0;
+    let mut tally = DedupTally::default();
     let mut failed: usize = 0;
     let mut last_embedding_len = 0usize;
     let label_collection: &str = &collection_name;
@@ -126,24 +139,25 @@ pub async fn insert_vectors(
             collection_dim,
             entry,
             batch_public_key.as_deref(),
+            partition.as_ref(),
         );
         drop(timer);
 
         match outcome {
-            Ok((vector_id, embedding_len, client_id_echo)) => {
-                inserted += 1;
+            Ok((vector_id, embedding_len, client_id_echo, status)) => {
                 last_embedding_len = embedding_len;
-                inserted_ids.push(vector_id.clone());
                 METRICS
                     .insert_requests_total
                     .with_label_values(&[label_collection, "success"])
                     .inc();
-                results.push(json!({
+                let mut item = json!({
                     "index": idx,
                     "client_id": client_id_echo,
                     "status": "ok",
                     "vector_ids": [vector_id],
-                }));
+                });
+                tally.record(vector_id, &status, &mut item);
+                results.push(item);
             }
             Err(e) => {
                 failed += 1;
@@ -163,25 +177,20 @@ pub async fn insert_vectors(
         }
     }
 
-    if !inserted_ids.is_empty() {
-        record_insert_usage(
-            &state,
-            &collection_name,
-            last_embedding_len,
-            inserted_ids.len() as u64,
-        )
+    let deduplicated = tally.deduplicated;
+    let inserted = tally
+        .finish(&state, &collection_name, last_embedding_len)
         .await;
-        mark_collection_dirty(&state, &collection_name, &inserted_ids);
-    }
 
     info!(
-        "insert_vectors into '{}' complete: {} inserted, {} failed",
-        collection_name, inserted, failed
+        "insert_vectors into '{}' complete: {} inserted, {} deduplicated, {} failed",
+        collection_name, inserted, deduplicated, failed
     );
 
     Ok(Json(json!({
         "collection": collection_name,
         "inserted": inserted,
+        "deduplicated": deduplicated,
         "failed": failed,
         "count": vectors_in.len(),
         "results": results,
@@ -189,14 +198,16 @@ pub async fn insert_vectors(
 }
 
 /// Insert a single pre-vectorized entry. Returns `(vector_id,
-/// embedding_len, client_id_echo)` on success.
+/// embedding_len, client_id_echo, dedup_status)` on success; `vector_id`
+/// is the stored duplicate's when the entry was one.
 fn insert_one_vector(
     state: &VectorizerServer,
     collection_name: &str,
     collection_dim: usize,
     entry: &Value,
     batch_public_key: Option<&str>,
-) -> Result<(String, usize, Option<String>), ErrorResponse> {
+    partition: Option<&Partition>,
+) -> Result<(String, usize, Option<String>, DedupStatus), ErrorResponse> {
     let client_id = entry.get("id").and_then(|i| i.as_str());
     if let Some(id) = client_id {
         validate_client_id(id).map_err(|reason| {
@@ -230,52 +241,40 @@ fn insert_one_vector(
         ));
     }
 
-    let mut embedding: Vec<f32> = Vec::with_capacity(embedding_arr.len());
-    for (i, v) in embedding_arr.iter().enumerate() {
-        let f = v.as_f64().ok_or_else(|| {
-            crate::server::error_middleware::create_validation_error(
-                "embedding",
-                &format!("embedding[{}] is not a number", i),
-            )
-        })?;
-        embedding.push(f as f32);
-    }
+    let embedding = f32_array(embedding_arr, "embedding")?;
 
-    let payload_data = build_vector_payload(entry);
+    let mut payload_data = build_vector_payload(entry);
 
     let entry_public_key = entry
         .get("public_key")
         .and_then(|k| k.as_str())
         .or(batch_public_key);
+    if let Some(partition) = partition {
+        partition.refuse_encryption(entry_public_key)?;
+        partition.stamp(&mut payload_data)?;
+    }
 
-    let payload = if let Some(key) = entry_public_key {
-        let encrypted =
-            vectorizer::security::payload_encryption::encrypt_payload(&payload_data, key)
-                .map_err(|e| create_bad_request_error(&format!("Encryption failed: {}", e)))?;
-        vectorizer::models::Payload::from_encrypted(encrypted)
-    } else {
-        vectorizer::models::Payload::new(payload_data)
-    };
+    let payload = seal_payload(payload_data, entry_public_key)?;
 
     let vector_id = client_id
         .map(str::to_string)
-        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        .unwrap_or_else(|| state.store.new_vector_id(collection_name));
+    if let Some(partition) = partition {
+        partition.check_insert(state, collection_name, &vector_id)?;
+    }
 
     let embedding_len = embedding.len();
     let vector = vectorizer::models::Vector {
-        id: vector_id.clone(),
+        id: vector_id,
         data: embedding,
         sparse: None,
         payload: Some(payload),
         document_id: None,
     };
 
-    state
-        .store
-        .insert(collection_name, vec![vector])
-        .map_err(ErrorResponse::from)?;
+    let (vector_id, status) = insert_vector(state, collection_name, vector)?;
 
-    Ok((vector_id, embedding_len, client_id_echo))
+    Ok((vector_id, embedding_len, client_id_echo, status))
 }
 
 /// Build the payload Value for `/insert_vectors` from the request entry.
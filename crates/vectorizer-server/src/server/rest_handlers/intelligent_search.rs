@@ -7,29 +7,43 @@
 //! server's shared `VectorStore`.
 //!
 //! - `intelligent_search`       — POST /search/intelligent
-//! - `multi_collection_search`  — POST /search/multi-collection
+//! - `multi_collection_search`  — POST /search/multi-collection (fans out
+//!   to remote Vectorizer instances when the request names `sources`)
 //! - `semantic_search`          — POST /search/semantic
 //! - `contextual_search`        — POST /search/contextual
 
+use std::time::Instant;
+
+use axum::Extension;
 use axum::extract::State;
 use axum::response::Json;
 use serde_json::{Value, json};
 use tracing::{debug, error};
+use vectorizer::auth::middleware::AuthState;
 
+use super::federation::{
+    federated_multi_collection_search, parse_collection_weights, parse_federation,
+    parse_score_normalization,
+};
+use super::query_analytics::record_query;
 use crate::server::VectorizerServer;
+use crate::server::core::caller_visible_collections;
 use crate::server::error_middleware::{
     ErrorResponse, create_bad_request_error, create_validation_error,
 };
 
 pub async fn intelligent_search(
     State(state): State<VectorizerServer>,
+    auth: Option<Extension<AuthState>>,
     Json(payload): Json<Value>,
 ) -> Result<Json<Value>, ErrorResponse> {
-    use vectorizer::cache::query_cache::QueryKey;
+    use vectorizer::cache::query_cache::{ALL_COLLECTIONS, QueryKey};
+    use vectorizer::intelligent_search::graph_expansion::GraphExpansion;
     use vectorizer::intelligent_search::rest_api::{IntelligentSearchRequest, RESTAPIHandler};
     use vectorizer::monitoring::metrics::METRICS;
 
     // Start latency timer
+    let started = Instant::now();
     let label_wildcard = "*".to_string();
     let label_intelligent = "intelligent".to_string();
     let timer = METRICS
@@ -43,15 +57,8 @@ pub async fn intelligent_search(
         .and_then(|q| q.as_str())
         .ok_or_else(|| create_validation_error("query", "missing or invalid query parameter"))?;
 
-    let collections = payload
-        .get("collections")
-        .and_then(|c| c.as_array())
-        .map(|arr| {
-            arr.iter()
-                .filter_map(|v| v.as_str())
-                .map(|s| s.to_string())
-                .collect::<Vec<_>>()
-        });
+    let collections = string_list(&payload, "collections")
+        .or_else(|| caller_visible_collections(&state.store, auth.as_deref()));
 
     let max_results = payload
         .get("max_results")
@@ -66,14 +73,25 @@ pub async fn intelligent_search(
         .get("mmr_lambda")
         .and_then(|l| l.as_f64())
         .map(|l| l as f32);
+    let translate_to = string_list(&payload, "translate_to");
+    if let Some(codes) = &translate_to {
+        vectorizer::intelligent_search::translation::parse_languages(codes)
+            .map_err(|e| create_validation_error("translate_to", &e))?;
+    }
+    let graph_expansion = payload
+        .get("graph_expansion")
+        .map(GraphExpansion::from_request)
+        .transpose()
+        .map_err(|e| create_validation_error("graph_expansion", &e))?
+        .flatten();
 
     // Create cache key (use "*" as collection name for multi-collection searches)
     let collection_key = collections
         .as_ref()
         .map(|c| c.join(","))
-        .unwrap_or_else(|| "*".to_string());
+        .unwrap_or_else(|| ALL_COLLECTIONS.to_string());
     let cache_key = QueryKey::new(
-        collection_key,
+        collection_key.clone(),
         format!(
             "intelligent:{}:{}:{}:{}:{}",
             query,
@@ -84,11 +102,34 @@ pub async fn intelligent_search(
         ),
         max_results,
         None,
-    );
+    )
+    .with_params(&json!({
+        "mmr_lambda": mmr_lambda,
+        "translate_to": translate_to,
+        "graph_expansion": graph_expansion,
+    }));
+
+    let record = |result_count| {
+        record_query(
+            &state,
+            &collection_key,
+            "intelligent",
+            query,
+            started,
+            result_count,
+        )
+    };
 
     // Check cache first
+    let generation = state.query_cache.generation();
     if let Some(cached_result) = state.query_cache.get(&cache_key) {
         debug!("💾 Cache hit for intelligent search query '{}'", query);
+        record(
+            cached_result
+                .get("results")
+                .and_then(|r| r.as_array())
+                .map_or(0, |r| r.len()),
+        );
         drop(timer);
         return Ok(Json(cached_result));
     }
@@ -105,6 +146,8 @@ pub async fn intelligent_search(
         technical_focus,
         mmr_enabled,
         mmr_lambda,
+        translate_to,
+        graph_expansion,
     };
 
     match handler.handle_intelligent_search(request).await {
@@ -113,35 +156,30 @@ pub async fn intelligent_search(
             let response_json = serde_json::to_value(&response).unwrap_or(json!({}));
 
             // Cache the result
-            state.query_cache.insert(cache_key, response_json.clone());
+            state
+                .query_cache
+                .insert_at(cache_key, response_json.clone(), generation);
 
             // Record success metrics
             let result_count = response.results.len();
-            let label_wildcard = "*";
-            let label_intelligent = "intelligent";
-            let label_success = "success";
             METRICS
                 .search_requests_total
-                .with_label_values(&[label_wildcard, label_intelligent, label_success])
+                .with_label_values(&["*", "intelligent", "success"])
                 .inc();
-            let label_wildcard_str = "*".to_string();
-            let label_intelligent_str = "intelligent".to_string();
             METRICS
                 .search_results_count
-                .with_label_values(&[&label_wildcard_str, &label_intelligent_str])
+                .with_label_values(&["*", "intelligent"])
                 .observe(result_count as f64);
+            record(result_count);
             drop(timer);
 
             Ok(Json(response_json))
         }
         Err(e) => {
             // Record error metrics
-            let label_wildcard = "*";
-            let label_intelligent = "intelligent";
-            let label_error = "error";
             METRICS
                 .search_requests_total
-                .with_label_values(&[label_wildcard, label_intelligent, label_error])
+                .with_label_values(&["*", "intelligent", "error"])
                 .inc();
             drop(timer);
 
@@ -193,24 +231,35 @@ pub async fn multi_collection_search(
         .get("cross_collection_reranking")
         .and_then(|c| c.as_bool());
 
+    let score_normalization = parse_score_normalization(&payload)?;
+    let collection_weights = parse_collection_weights(&payload)?;
+
+    let federation_params = parse_federation(&payload)?;
+
     let request = MultiCollectionSearchRequest {
         query: query.to_string(),
         collections,
         max_per_collection,
         max_total_results,
         cross_collection_reranking,
+        score_normalization,
+        collection_weights,
     };
 
-    match handler.handle_multi_collection_search(request).await {
-        Ok(response) => Ok(Json(serde_json::to_value(response).unwrap_or(json!({})))),
-        Err(e) => {
-            error!("Multi collection search error: {:?}", e);
-            Err(create_bad_request_error(&format!(
-                "Multi collection search failed: {:?}",
-                e
-            )))
-        }
-    }
+    let Some(params) = federation_params else {
+        return match handler.handle_multi_collection_search(request).await {
+            Ok(response) => Ok(Json(serde_json::to_value(response).unwrap_or(json!({})))),
+            Err(e) => {
+                error!("Multi collection search error: {:?}", e);
+                Err(create_bad_request_error(&format!(
+                    "Multi collection search failed: {:?}",
+                    e
+                )))
+            }
+        };
+    };
+
+    federated_multi_collection_search(&state, &handler, request, params).await
 }
 
 pub async fn semantic_search(
@@ -335,3 +384,16 @@ pub async fn contextual_search(
         }
     }
 }
+
+/// The strings of the array `field`, skipping other entries; `None`
+/// when `field` is not an array.
+fn string_list(payload: &Value, field: &str) -> Option<Vec<String>> {
+    let values = payload.get(field)?.as_array()?;
+    Some(
+        values
+            .iter()
+            .filter_map(|v| v.as_str())
+            .map(str::to_string)
+            .collect(),
+    )
+}
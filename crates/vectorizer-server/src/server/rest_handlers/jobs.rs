@@ -0,0 +1,224 @@
+//! Job REST handlers.
+//!
+//! - `list_jobs`           — GET    /jobs
+//! - `get_job`             — GET    /jobs/{id}
+//! - `cancel_job`          — DELETE /jobs/{id}
+//! - `download_job_result` — GET    /jobs/{id}/result
+//!
+//! Jobs are started by the endpoints that run them (collection clone,
+//! reindex, import, snapshot restore and duplicate scan), which return
+//! the job id, and by the index optimizer; see [`vectorizer::jobs`]. [`run_job`] is how
+//! a handler runs its operation as one.
+//!
+//! A hub tenant or namespaced API key only lists, reads, downloads and
+//! cancels the jobs it started ([`JobOwner`]); another caller's job is
+//! reported as not found.
+
+#![allow(missing_docs)]
+
+use std::convert::Infallible;
+
+use axum::extract::{FromRequestParts, Path, Query};
+use axum::http::header;
+use axum::http::request::Parts;
+use axum::response::{IntoResponse, Json, Response};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use tracing::info;
+use vectorizer::auth::middleware::AuthState;
+use vectorizer::error::VectorizerError;
+use vectorizer::hub::middleware::RequestTenantContext;
+use vectorizer::jobs::{JOBS, Job, JobState, JobStatus};
+
+use crate::server::error_middleware::{
+    ErrorResponse, create_bad_request_error, create_not_found_error,
+};
+
+/// `?wait=` of the endpoints that run a job: `true` (the default) answers
+/// once the job finished, `false` right after it started.
+#[derive(Debug, Deserialize)]
+pub struct WaitQuery {
+    #[serde(default = "default_wait")]
+    pub wait: bool,
+}
+
+fn default_wait() -> bool {
+    true
+}
+
+/// The scoped caller a job is started for and read by: its hub tenant
+/// and/or API-key namespace, `None` for unscoped callers, who see every
+/// job.
+#[derive(Debug, Clone, Default)]
+pub struct JobOwner(pub Option<String>);
+
+impl<S: Send + Sync> FromRequestParts<S> for JobOwner {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let tenant = parts
+            .extensions
+            .get::<RequestTenantContext>()
+            .map(|ctx| format!("tenant:{}", ctx.0.tenant_id));
+        let namespace = parts
+            .extensions
+            .get::<AuthState>()
+            .filter(|auth| auth.authenticated)
+            .and_then(|auth| auth.user_claims.namespace.as_ref())
+            .map(|namespace| format!("namespace:{}", namespace));
+        let scopes: Vec<String> = tenant.into_iter().chain(namespace).collect();
+        Ok(Self((!scopes.is_empty()).then(|| scopes.join("/"))))
+    }
+}
+
+impl JobOwner {
+    /// Status of the job `id` if this caller may see it.
+    fn get(&self, id: &str) -> Result<JobStatus, ErrorResponse> {
+        JOBS.get(id)
+            .filter(|status| status.visible_to(self.0.as_deref()))
+            .ok_or_else(|| create_not_found_error("job", id))
+    }
+}
+
+/// Run `operation` as a job of `kind` on `collection` on the blocking
+/// pool, owned by `owner`. Returns the job id and, when `wait` is set,
+/// the operation's result; otherwise the job keeps running after the
+/// request returns.
+pub(crate) async fn run_job<T, F>(
+    kind: &'static str,
+    collection: &str,
+    owner: JobOwner,
+    wait: bool,
+    operation: F,
+) -> Result<(String, Option<T>), ErrorResponse>
+where
+    T: Serialize + Send + 'static,
+    F: FnOnce(&Job) -> vectorizer::error::Result<T> + Send + 'static,
+{
+    let job = JOBS.start_owned(kind, collection, owner.0);
+    let id = job.id();
+    let runner = job.clone();
+    let handle = tokio::spawn(async move {
+        let result = tokio::task::spawn_blocking(move || runner.run(operation)).await;
+        if let Err(ref e) = result {
+            job.finish(&Err(format!("{} task panicked: {}", kind, e)));
+        }
+        result
+    });
+    if !wait {
+        return Ok((id, None));
+    }
+
+    let result = handle
+        .await
+        .map_err(|e| e.to_string())
+        .and_then(|r| r.map_err(|e| e.to_string()))
+        .map_err(|e| {
+            ErrorResponse::from(VectorizerError::InternalError(format!(
+                "{} task error: {}",
+                kind, e
+            )))
+        })?
+        .map_err(ErrorResponse::from)?;
+    Ok((id, Some(result)))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListJobsQuery {
+    pub state: Option<JobState>,
+    pub collection: Option<String>,
+}
+
+/// GET /jobs
+///
+/// Running jobs and the recently finished ones, latest started first.
+/// `?state=running` (or `completed`, `failed`, `cancelled`) and
+/// `?collection=` narrow the list.
+pub async fn list_jobs(owner: JobOwner, Query(params): Query<ListJobsQuery>) -> Json<Value> {
+    let jobs: Vec<_> = JOBS
+        .list()
+        .into_iter()
+        .filter(|job| job.visible_to(owner.0.as_deref()))
+        .filter(|job| params.state.is_none_or(|state| job.state == state))
+        .filter(|job| {
+            params
+                .collection
+                .as_ref()
+                .is_none_or(|collection| &job.collection == collection)
+        })
+        .collect();
+    Json(json!({
+        "total": jobs.len(),
+        "jobs": jobs,
+    }))
+}
+
+/// GET /jobs/{id}
+///
+/// State, progress and, once finished, the outcome or error of a job.
+pub async fn get_job(
+    owner: JobOwner,
+    Path(id): Path<String>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let status = owner.get(&id)?;
+    Ok(Json(json!({ "job": status })))
+}
+
+/// GET /jobs/{id}/result
+///
+/// The outcome of a completed job as a JSON file download, e.g. the
+/// report of a duplicate scan. A job that is still running, failed or
+/// was cancelled has none (400).
+pub async fn download_job_result(
+    owner: JobOwner,
+    Path(id): Path<String>,
+) -> Result<Response, ErrorResponse> {
+    let status = owner.get(&id)?;
+    let result = match status.result {
+        Some(result) if status.state == JobState::Completed => result,
+        _ => {
+            return Err(create_bad_request_error(&format!(
+                "job '{}' has not completed, so it has no result",
+                id
+            )));
+        }
+    };
+    Ok((
+        [(
+            header::CONTENT_DISPOSITION,
+            format!(
+                "attachment; filename=\"{}-{}.json\"",
+                status.kind, status.id
+            ),
+        )],
+        Json(result),
+    )
+        .into_response())
+}
+
+/// DELETE /jobs/{id}
+///
+/// Asks a running job to stop; it reports `cancelled` once it did.
+/// Clones, imports and duplicate scans stop after their current batch,
+/// while a reindex, restore or compaction that already started runs to
+/// the end. A finished job is removed from the list.
+pub async fn cancel_job(
+    owner: JobOwner,
+    Path(id): Path<String>,
+) -> Result<Json<Value>, ErrorResponse> {
+    owner.get(&id)?;
+    let status = JOBS
+        .cancel(&id)
+        .ok_or_else(|| create_not_found_error("job", &id))?;
+    let removed = status.finished_at.is_some();
+    info!(
+        "Job '{}' ({}) {}",
+        id,
+        status.kind,
+        if removed { "removed" } else { "cancelled" }
+    );
+    Ok(Json(json!({
+        "job": status,
+        "removed": removed,
+    })))
+}
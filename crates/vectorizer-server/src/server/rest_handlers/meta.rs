@@ -1,11 +1,13 @@
 //! Meta / status REST handlers.
 //!
-//! - `health_check` — GET /health
 //! - `get_stats`    — GET /stats
 //! - `get_indexing_progress` — GET /indexing/progress
+//! - `get_startup_progress` — GET /startup/progress
 //! - `get_status`   — GET /status  (GUI)
 //! - `get_logs`     — GET /logs    (GUI)
 //! - `get_prometheus_metrics` — GET /metrics
+//!
+//! The `/health` probes live in [`super::ops::health`].
 
 use std::collections::HashMap;
 
@@ -18,51 +20,14 @@ use tracing::error;
 use crate::server::VectorizerServer;
 use crate::server::error_middleware::ErrorResponse;
 
-/// GET /health — liveness check with cache and hub stats
-pub async fn health_check(State(state): State<VectorizerServer>) -> Json<Value> {
-    let cache_stats = state.query_cache.stats();
-
-    // Build base health response
-    let mut response = json!({
-        "status": "healthy",
-        "timestamp": chrono::Utc::now(),
-        "version": env!("CARGO_PKG_VERSION"),
-        "cache": {
-            "size": cache_stats.size,
-            "capacity": cache_stats.capacity,
-            "hits": cache_stats.hits,
-            "misses": cache_stats.misses,
-            "evictions": cache_stats.evictions,
-            "hit_rate": cache_stats.hit_rate
-        }
-    });
-
-    // Add Hub status if Hub is enabled
-    if let Some(ref hub_manager) = state.hub_manager {
-        let hub_status = json!({
-            "enabled": hub_manager.is_enabled(),
-            "active": hub_manager.is_active(),
-            "tenant_isolation": format!("{:?}", hub_manager.config().tenant_isolation),
-        });
-        response["hub"] = hub_status;
-    }
-
-    // Add backup manager status
-    if state.backup_manager.is_some() {
-        response["backup"] = json!({
-            "enabled": true
-        });
-    }
-
-    Json(response)
-}
-
 /// GET /stats — aggregate collection and vector counts.
 ///
 /// Phase25 §5 additions: `default_quantization` (most-common quantization
 /// label across active collections) and `compression_ratio` (mean ratio
 /// across the collections sharing that label). `none` / `1.0` when the
-/// store is empty.
+/// store is empty. `query_cache` carries the cache totals and, under
+/// `collections`, hits, misses, invalidations, evictions and expirations
+/// per collection.
 pub async fn get_stats(State(state): State<VectorizerServer>) -> Json<Value> {
     let collections = state.store.list_collections();
     let mut total_vectors: usize = 0;
@@ -126,6 +91,8 @@ pub async fn get_stats(State(state): State<VectorizerServer>) -> Json<Value> {
         })
         .collect();
 
+    let cache = state.query_cache.stats();
+
     Json(json!({
         "collections": collections.len(),
         "total_vectors": total_vectors,
@@ -135,6 +102,15 @@ pub async fn get_stats(State(state): State<VectorizerServer>) -> Json<Value> {
         "compression_ratio": compression_ratio,
         "providers": providers,
         "default_provider": default_provider,
+        "query_cache": {
+            "size": cache.size,
+            "capacity": cache.capacity,
+            "hits": cache.hits,
+            "misses": cache.misses,
+            "evictions": cache.evictions,
+            "hit_rate": cache.hit_rate,
+            "collections": state.query_cache.collection_stats(),
+        },
     }))
 }
 
@@ -201,6 +177,13 @@ pub async fn get_indexing_progress(State(state): State<VectorizerServer>) -> Jso
     }))
 }
 
+/// GET /startup/progress — per-collection state of the background
+/// startup load (pending, loading, indexed, failed) with vector counts.
+/// `complete` turns true once every collection is indexed or failed.
+pub async fn get_startup_progress(State(state): State<VectorizerServer>) -> Json<Value> {
+    Json(json!(state.startup_progress.report()))
+}
+
 /// GET /status — server status for GUI
 pub async fn get_status(State(state): State<VectorizerServer>) -> Json<Value> {
     Json(json!({
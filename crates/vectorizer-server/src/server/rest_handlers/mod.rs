@@ -5,14 +5,20 @@
 //! so that each REST concern is reviewable in isolation:
 //!
 //! - [`common`]             — shared helpers (tenant extraction, metrics UUID)
-//! - [`meta`]               — /health, /stats, /indexing/progress, /status,
+//! - [`filtered_search`]    — payload-filtered search shared by browse,
+//!                            aggregate, facets, retrieve and Pinecone
+//! - [`meta`]               — /stats, /indexing/progress, /status,
 //!                            /logs, /metrics (Prometheus)
 //! - [`collections`]        — collection CRUD + /collections/empty cleanup +
 //!                            phase-14 schema-evolution (rename, reindex, snapshots)
 //! - [`vectors`]            — vector CRUD + embed + batch insert
 //! - [`insert`]             — /insert_text (the big chunk-and-embed endpoint)
+//! - [`dedup`]              — dedup-aware vector insert shared by the
+//!                            insert handlers
 //! - [`search`]             — text / hybrid / file search + batch ops +
 //!                            phase-14 explain
+//! - [`search_common`]      — limit / `exact` / `timeout_ms` parsing,
+//!                            response shaping and metrics of the searches
 //! - [`slow_queries`]       — phase-14 slow-query log (list + config)
 //! - [`intelligent_search`] — high-level orchestrator: intelligent / multi /
 //!                            semantic / contextual
@@ -23,6 +29,22 @@
 //!                            chunks, outline, related, by-type search)
 //! - [`admin`]              — workspace CRUD + /config + /admin/restart
 //! - [`backups`]            — /backups list / create / restore / dir
+//! - [`jobs`]               — /jobs progress, cancellation and results of
+//!                            long-running operations (clone, reindex,
+//!                            import, restore, duplicate scan)
+//! - [`tenant_partition`]   — confining hub tenants to their slice of a
+//!                            partitioned collection
+//! - [`ops`]                — server operations: drain, live config, health,
+//!                            scheduled backups, tasks, change feed, residency
+//! - [`collection_admin`]   — collection creation, patching, clones, restore,
+//!                            import / export and the key-value store
+//! - [`explore`]            — bulk fetch, scroll, count, aggregate, facets,
+//!                            samples, duplicates, clusters, similarity
+//! - [`search_features`]    — batch and full-text search, templates,
+//!                            federation, analytics, experiments, feedback,
+//!                            hybrid tuning, recall profiles
+//! - [`writes`]             — pre-chunked documents, images, payload patches
+//! - [`compat`]             — OpenAI, Pinecone and `/retrieve` compatible APIs
 //!
 //! The public surface is preserved verbatim via `pub use`: every name
 //! that `src/server/mod.rs` used to reach as `rest_handlers::X` is still
@@ -31,35 +53,52 @@
 
 mod admin;
 mod backups;
+mod collection_admin;
 mod collections;
 mod common;
+mod compat;
+mod dedup;
 mod discovery;
+mod explore;
 mod files;
+mod filtered_search;
 mod insert;
 mod insert_vectors;
 mod intelligent_search;
+mod jobs;
 mod meta;
 pub mod metrics;
+mod ops;
 mod search;
+mod search_common;
+mod search_features;
 mod slow_queries;
+mod tenant_partition;
 mod vectors;
+mod writes;
+
+use search_features::{experiments, federation, query_analytics};
 
 pub use admin::{
     add_workspace, get_config, get_workspace_config, list_workspaces, remove_workspace,
     restart_server, update_config, update_workspace_config,
 };
 pub use backups::{create_backup, get_backup_directory, list_backups, restore_backup};
+pub use collection_admin::*;
 pub use collections::{
-    cleanup_empty_collections, create_collection, create_native_snapshot, delete_collection,
-    force_save_collection, get_collection, list_collections, list_empty_collections,
-    list_native_snapshots, reencode_collection, reindex_collection, rename_collection,
-    restore_native_snapshot, set_collection_ttl,
+    cleanup_empty_collections, create_native_snapshot, delete_collection, force_save_collection,
+    get_collection, list_collections, list_empty_collections, list_native_snapshots,
+    reencode_collection, reindex_collection, rename_collection, restore_native_snapshot,
+    set_collection_ttl,
 };
 pub(crate) use common::collection_metrics_uuid;
+pub(crate) use insert::check_insert_quota;
+pub use compat::*;
 pub use discovery::{
     broad_discovery, build_answer_plan, compress_evidence, discover, expand_queries,
     filter_collections, promote_readme, render_llm_prompt, score_collections, semantic_focus,
 };
+pub use explore::*;
 pub use files::{
     get_file_chunks_ordered, get_file_content, get_file_summary, get_project_outline,
     get_related_files, list_files_in_collection, search_by_file_type,
@@ -69,20 +108,24 @@ pub use insert_vectors::insert_vectors;
 pub use intelligent_search::{
     contextual_search, intelligent_search, multi_collection_search, semantic_search,
 };
+pub use jobs::{cancel_job, download_job_result, get_job, list_jobs};
 pub use meta::{
-    get_indexing_progress, get_logs, get_prometheus_metrics, get_stats, get_status, health_check,
+    get_indexing_progress, get_logs, get_prometheus_metrics, get_startup_progress, get_stats,
+    get_status,
 };
+pub use ops::*;
 pub use search::{
-    batch_delete_vectors, batch_search_vectors, batch_update_vectors, explain_search,
-    hybrid_search_vectors, search_by_file, search_vectors, search_vectors_by_collection,
-    search_vectors_by_text,
+    batch_delete_vectors, batch_update_vectors, explain_search, hybrid_search_vectors,
+    search_by_file, search_vectors, search_vectors_by_collection, search_vectors_by_text,
 };
+pub use search_features::*;
 pub use slow_queries::{list_slow_queries, set_slow_query_config};
 pub use vectors::{
     batch_insert_texts, bulk_update_metadata, copy_vectors, delete_by_filter, delete_vector,
     delete_vector_generic, embed_text, get_vector, insert_texts, list_vectors, move_vectors,
     set_vector_expiry, update_vector,
 };
+pub use writes::*;
 
 #[cfg(test)]
 #[path = "../rest_handlers_tests.rs"]
@@ -0,0 +1,263 @@
+//! Live search and cache tuning: `GET` / `PATCH /admin/config`.
+//!
+//! Unlike `/config`, which rewrites `config.yml` for the next start,
+//! these settings take effect on the running server as well.
+
+use std::num::NonZeroUsize;
+use std::time::Duration;
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::Json;
+use serde_json::{Value, json};
+use tracing::{error, info};
+use vectorizer::config::MAX_EF_SEARCH;
+
+use crate::server::VectorizerServer;
+use crate::server::error_middleware::{
+    ErrorResponse, create_bad_request_error, create_validation_error,
+};
+
+/// Search and cache settings in force: `GET /admin/config`.
+///
+/// `ef_search` is `null` while the adaptive beam is in use;
+/// `rate_limits` is `null` when authentication is disabled.
+pub async fn get_runtime_config(State(state): State<VectorizerServer>) -> Json<Value> {
+    Json(runtime_config(&state))
+}
+
+/// Change search and cache settings without a restart:
+/// `PATCH /admin/config`.
+///
+/// Body (every key optional):
+/// `{"ef_search": 128, "query_cache": {"max_size": 5000, "ttl_seconds": 60},
+/// "rate_limits": {"per_minute": 600, "per_hour": 20000}}`. `"ef_search":
+/// null` goes back to the adaptive beam. The whole patch is validated
+/// before anything changes; it is then written to `config.yml`
+/// (`tuning:` and `auth.rate_limit_*`) and applied to the running
+/// server. Loaded collections are left alone.
+pub async fn patch_runtime_config(
+    State(state): State<VectorizerServer>,
+    Json(payload): Json<Value>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let patch = payload
+        .as_object()
+        .ok_or_else(|| create_bad_request_error("body must be a JSON object"))?;
+    reject_unknown_keys(patch, "", &["ef_search", "query_cache", "rate_limits"])?;
+
+    // Outer `None`: leave alone; `Some(None)`: back to the adaptive beam
+    let ef_search = match patch.get("ef_search") {
+        None => None,
+        Some(Value::Null) => Some(None),
+        Some(v) => {
+            let ef_search = v
+                .as_u64()
+                .filter(|ef| (1..=MAX_EF_SEARCH as u64).contains(ef))
+                .ok_or_else(|| {
+                    create_validation_error(
+                        "ef_search",
+                        &format!("must be an integer between 1 and {MAX_EF_SEARCH}, or null"),
+                    )
+                })?;
+            Some(Some(ef_search as usize))
+        }
+    };
+
+    let cache = tunable_section(patch, "query_cache", &["max_size", "ttl_seconds"])?;
+    let max_size = tunable_integer(cache, "query_cache", "max_size", 1, usize::MAX as u64)?
+        .and_then(|n| NonZeroUsize::new(n as usize));
+    let ttl_seconds = tunable_integer(cache, "query_cache", "ttl_seconds", 0, u64::MAX)?;
+
+    let limits = tunable_section(patch, "rate_limits", &["per_minute", "per_hour"])?;
+    let auth_manager = state
+        .auth_handler_state
+        .as_ref()
+        .map(|auth| auth.auth_manager.clone());
+    let rate_limits = match (limits, &auth_manager) {
+        (None, _) => None,
+        (Some(_), None) => {
+            return Err(create_bad_request_error(
+                "rate limits apply to API keys and authentication is disabled",
+            ));
+        }
+        (Some(limits), Some(auth_manager)) => {
+            let (current_minute, current_hour) = auth_manager.rate_limits();
+            let max = u64::from(u32::MAX);
+            let per_minute = tunable_integer(Some(limits), "rate_limits", "per_minute", 1, max)?
+                .map_or(current_minute, |n| n as u32);
+            let per_hour = tunable_integer(Some(limits), "rate_limits", "per_hour", 1, max)?
+                .map_or(current_hour, |n| n as u32);
+            if per_minute > per_hour {
+                return Err(create_validation_error(
+                    "rate_limits.per_minute",
+                    "must not exceed rate_limits.per_hour",
+                ));
+            }
+            Some((per_minute, per_hour))
+        }
+    };
+
+    let persisted = match &state.config_path {
+        Some(path) => {
+            persist_runtime_config(path, ef_search, max_size, ttl_seconds, rate_limits).map_err(
+                |e| {
+                    error!("Failed to write {}: {:#}", path.display(), e);
+                    ErrorResponse::new(
+                        "config_write_failed".to_string(),
+                        format!("Could not persist the new settings, nothing changed: {e:#}"),
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                    )
+                },
+            )?;
+            true
+        }
+        None => false,
+    };
+
+    if let Some(ef_search) = ef_search {
+        vectorizer::db::set_default_ef_search(ef_search);
+    }
+    if let Some(max_size) = max_size {
+        state.query_cache.resize(max_size);
+    }
+    if let Some(ttl_seconds) = ttl_seconds {
+        state.query_cache.set_ttl(Duration::from_secs(ttl_seconds));
+    }
+    if let (Some((per_minute, per_hour)), Some(auth_manager)) = (rate_limits, auth_manager) {
+        auth_manager.set_rate_limits(per_minute, per_hour);
+    }
+
+    let mut effective = runtime_config(&state);
+    info!("🎛️ Runtime config updated: {}", effective);
+    effective["persisted"] = json!(persisted);
+    Ok(Json(effective))
+}
+
+fn runtime_config(state: &VectorizerServer) -> Value {
+    let cache = state.query_cache.stats();
+    let rate_limits = state.auth_handler_state.as_ref().map(|auth| {
+        let (per_minute, per_hour) = auth.auth_manager.rate_limits();
+        json!({"per_minute": per_minute, "per_hour": per_hour})
+    });
+    json!({
+        "ef_search": vectorizer::db::default_ef_search(),
+        "query_cache": {
+            "max_size": cache.capacity,
+            "ttl_seconds": state.query_cache.ttl().as_secs(),
+            "size": cache.size,
+        },
+        "rate_limits": rate_limits,
+    })
+}
+
+fn reject_unknown_keys(
+    object: &serde_json::Map<String, Value>,
+    prefix: &str,
+    known: &[&str],
+) -> Result<(), ErrorResponse> {
+    match object.keys().find(|key| !known.contains(&key.as_str())) {
+        Some(key) => Err(create_validation_error(
+            &format!("{prefix}{key}"),
+            "not a runtime-tunable setting",
+        )),
+        None => Ok(()),
+    }
+}
+
+fn tunable_section<'a>(
+    patch: &'a serde_json::Map<String, Value>,
+    section: &str,
+    known: &[&str],
+) -> Result<Option<&'a serde_json::Map<String, Value>>, ErrorResponse> {
+    match patch.get(section) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::Object(object)) => {
+            reject_unknown_keys(object, &format!("{section}."), known)?;
+            Ok(Some(object))
+        }
+        Some(_) => Err(create_validation_error(section, "must be an object")),
+    }
+}
+
+fn tunable_integer(
+    section: Option<&serde_json::Map<String, Value>>,
+    section_name: &str,
+    key: &str,
+    min: u64,
+    max: u64,
+) -> Result<Option<u64>, ErrorResponse> {
+    match section.and_then(|s| s.get(key)) {
+        None | Some(Value::Null) => Ok(None),
+        Some(v) => v
+            .as_u64()
+            .filter(|n| (min..=max).contains(n))
+            .map(Some)
+            .ok_or_else(|| {
+                create_validation_error(
+                    &format!("{section_name}.{key}"),
+                    &format!("must be an integer of at least {min}"),
+                )
+            }),
+    }
+}
+
+/// Write the changed settings into `config.yml`, keeping everything
+/// else in the file. The new file replaces the old one in a single
+/// rename so a crash never leaves it half written.
+fn persist_runtime_config(
+    path: &std::path::Path,
+    ef_search: Option<Option<usize>>,
+    max_size: Option<NonZeroUsize>,
+    ttl_seconds: Option<u64>,
+    rate_limits: Option<(u32, u32)>,
+) -> anyhow::Result<()> {
+    use serde_yaml::Value as Yaml;
+
+    let mut doc = match std::fs::read_to_string(path) {
+        Ok(content) => serde_yaml::from_str(&content)?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Yaml::Null,
+        Err(e) => return Err(e.into()),
+    };
+    if let Some(ef_search) = ef_search {
+        let value = ef_search.map_or(Yaml::Null, |ef| Yaml::from(ef as u64));
+        set_yaml(&mut doc, &["tuning", "ef_search"], value);
+    }
+    if let Some(max_size) = max_size {
+        let value = Yaml::from(max_size.get() as u64);
+        set_yaml(&mut doc, &["tuning", "query_cache", "max_size"], value);
+    }
+    if let Some(ttl_seconds) = ttl_seconds {
+        let value = Yaml::from(ttl_seconds);
+        set_yaml(&mut doc, &["tuning", "query_cache", "ttl_seconds"], value);
+    }
+    if let Some((per_minute, per_hour)) = rate_limits {
+        set_yaml(
+            &mut doc,
+            &["auth", "rate_limit_per_minute"],
+            per_minute.into(),
+        );
+        set_yaml(&mut doc, &["auth", "rate_limit_per_hour"], per_hour.into());
+    }
+
+    let tmp = path.with_extension("yml.tmp");
+    std::fs::write(&tmp, serde_yaml::to_string(&doc)?)?;
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+/// Set `value` at `keys` inside `doc`, creating the mappings on the way.
+fn set_yaml(doc: &mut serde_yaml::Value, keys: &[&str], value: serde_yaml::Value) {
+    let mut node = doc;
+    for key in keys {
+        if !node.is_mapping() {
+            *node = serde_yaml::Value::Mapping(serde_yaml::Mapping::new());
+        }
+        let serde_yaml::Value::Mapping(map) = node else {
+            return;
+        };
+        node = map
+            .entry(serde_yaml::Value::from(*key))
+            .or_insert(serde_yaml::Value::Null);
+    }
+    *node = value;
+}
@@ -0,0 +1,168 @@
+//! Drain mode for rolling restarts (`/admin/drain`) and read-only mode
+//! for maintenance windows (`/admin/read_only`).
+
+use std::time::Duration;
+
+use axum::extract::State;
+use axum::response::Json;
+use serde_json::{Value, json};
+use tracing::{error, info, warn};
+
+use crate::server::VectorizerServer;
+use crate::server::error_middleware::{ErrorResponse, create_validation_error};
+
+/// How long a provider reload waits for in-flight calls on the old
+/// model, and a drain for running writes, when the request does not say.
+pub(super) const DEFAULT_DRAIN_TIMEOUT_SECS: u64 = 30;
+
+/// Put the server in drain mode ahead of a rolling restart:
+/// `POST /admin/drain`.
+///
+/// Body (optional): `{"timeout_secs": 30}`. New writes are refused over
+/// REST, gRPC and MCP from here on and `/health` answers 503, so load
+/// balancers take the node out of rotation while reads keep being
+/// served. The call waits up to `timeout_secs` for running writes, then
+/// forces a save and, when snapshots are configured, takes one. Calling
+/// it again repeats the wait and the save; `DELETE /admin/drain`
+/// accepts writes again.
+pub async fn drain_server(
+    State(state): State<VectorizerServer>,
+    payload: Option<Json<Value>>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let payload = payload.map(|Json(p)| p).unwrap_or_else(|| json!({}));
+    let timeout = match payload.get("timeout_secs") {
+        None | Some(Value::Null) => Duration::from_secs(DEFAULT_DRAIN_TIMEOUT_SECS),
+        Some(v) => Duration::from_secs(v.as_u64().ok_or_else(|| {
+            create_validation_error("timeout_secs", "must be a non-negative integer")
+        })?),
+    };
+
+    if state.drain.begin() {
+        info!("🚰 Draining: refusing new writes ahead of a restart");
+    }
+    let settled = state.drain.wait_idle(timeout).await;
+    let in_flight = state.drain.in_flight();
+    if !settled {
+        warn!(
+            "🚰 {} write(s) still running after {:?}; saving anyway",
+            in_flight, timeout
+        );
+    }
+
+    // `null` when persistence is off and there is nothing to save
+    let saved = match &state.auto_save_manager {
+        Some(auto_save) => match auto_save.force_save().await {
+            Ok(()) => json!(true),
+            Err(e) => {
+                error!("🚰 Final save before restart failed: {}", e);
+                json!(false)
+            }
+        },
+        None => Value::Null,
+    };
+
+    let snapshot = match state.snapshot_manager.clone() {
+        Some(snapshots) => {
+            let manager = snapshots.clone();
+            match tokio::task::spawn_blocking(move || manager.create_snapshot()).await {
+                Ok(Ok(info)) => {
+                    // A drained node may not come back; keep a copy off it
+                    if let Err(e) = snapshots.upload_snapshot(&info).await {
+                        warn!("🚰 Snapshot {} not copied off-node: {}", info.id, e);
+                    }
+                    json!(info.id)
+                }
+                Ok(Err(e)) => {
+                    warn!("🚰 No snapshot taken while draining: {}", e);
+                    Value::Null
+                }
+                Err(e) => {
+                    warn!("🚰 Snapshot task failed while draining: {}", e);
+                    Value::Null
+                }
+            }
+        }
+        None => Value::Null,
+    };
+
+    info!(
+        "🚰 Drained (settled: {}, saved: {}, snapshot: {})",
+        settled, saved, snapshot
+    );
+    Ok(Json(json!({
+        "draining": true,
+        "settled": settled,
+        "in_flight": in_flight,
+        "saved": saved,
+        "snapshot": snapshot,
+    })))
+}
+
+/// Leave drain mode and accept writes again: `DELETE /admin/drain`.
+pub async fn resume_server(State(state): State<VectorizerServer>) -> Json<Value> {
+    let resumed = state.drain.resume();
+    if resumed {
+        info!("🚰 Drain cancelled, accepting writes again");
+    }
+    Json(json!({
+        "draining": false,
+        "resumed": resumed,
+    }))
+}
+
+/// Whether the server is draining: `GET /admin/drain`.
+pub async fn get_drain_status(State(state): State<VectorizerServer>) -> Json<Value> {
+    Json(json!({
+        "draining": state.drain.is_draining(),
+        "in_flight": state.drain.in_flight(),
+    }))
+}
+
+/// Switch read-only mode on or off: `PUT /admin/read_only`.
+///
+/// Body: `{"enabled": true, "reason": "reindex"}`; `reason` is optional.
+/// While on, writes are refused over REST, gRPC and MCP with the same
+/// 503 a drain gives, but searches keep being served and the node stays
+/// ready; `/health/ready` reports the mode. Writes already running are
+/// not waited for — `in_flight` says how many are left.
+pub async fn set_read_only(
+    State(state): State<VectorizerServer>,
+    Json(payload): Json<Value>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let enabled = payload
+        .get("enabled")
+        .and_then(Value::as_bool)
+        .ok_or_else(|| create_validation_error("enabled", "missing or not a boolean"))?;
+    let reason = match payload.get("reason") {
+        None | Some(Value::Null) => None,
+        Some(v) => Some(
+            v.as_str()
+                .ok_or_else(|| create_validation_error("reason", "must be a string"))?
+                .to_string(),
+        ),
+    };
+
+    let was_enabled = state.drain.set_read_only(enabled, reason.clone());
+    match (was_enabled, enabled) {
+        (false, true) => info!(
+            "🔒 Read-only mode on{}",
+            reason.map(|r| format!(": {}", r)).unwrap_or_default()
+        ),
+        (true, false) => info!("🔓 Read-only mode off, accepting writes again"),
+        _ => {}
+    }
+    Ok(Json(read_only_status(&state)))
+}
+
+/// Whether the server is in read-only mode: `GET /admin/read_only`.
+pub async fn get_read_only_status(State(state): State<VectorizerServer>) -> Json<Value> {
+    Json(read_only_status(&state))
+}
+
+fn read_only_status(state: &VectorizerServer) -> Value {
+    json!({
+        "read_only": state.drain.is_read_only(),
+        "reason": state.drain.read_only_reason(),
+        "in_flight": state.drain.in_flight(),
+    })
+}
@@ -0,0 +1,134 @@
+//! Embedding model hot swap: `POST /admin/embedding/providers/{name}/reload`.
+
+use std::time::Duration;
+
+use axum::extract::{Path, State};
+use axum::response::Json;
+use serde_json::{Value, json};
+use tracing::{info, warn};
+use vectorizer::embedding::EmbeddingProvider;
+
+use super::admin_drain::DEFAULT_DRAIN_TIMEOUT_SECS;
+use crate::server::VectorizerServer;
+use crate::server::core::build_embedding_provider;
+use crate::server::error_middleware::{
+    ErrorResponse, create_bad_request_error, create_not_found_error, create_validation_error,
+};
+
+/// Load a new version of an embedding model and switch the provider to
+/// it without a restart: `POST /admin/embedding/providers/{name}/reload`.
+///
+/// Body (optional): `{"model": "fastembed:<id>", "drain_timeout_secs": 30}`.
+/// `model` defaults to `name`, i.e. re-load the same model id (picks up
+/// a replaced file in the model cache). The new model is loaded and
+/// test-embedded before the switch, and must keep the provider's
+/// dimension. Calls already running finish on the old model; the
+/// response reports whether they drained within the timeout.
+pub async fn reload_embedding_provider(
+    State(state): State<VectorizerServer>,
+    Path(name): Path<String>,
+    payload: Option<Json<Value>>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let payload = payload.map(|Json(p)| p).unwrap_or_else(|| json!({}));
+
+    if !state.embedding_manager.has_provider(&name) {
+        return Err(create_not_found_error("embedding provider", &name));
+    }
+
+    let model = match payload.get("model") {
+        None | Some(Value::Null) => name.clone(),
+        Some(Value::String(m)) => m.clone(),
+        Some(_) => return Err(create_validation_error("model", "must be a string")),
+    };
+    let drain_timeout = match payload.get("drain_timeout_secs") {
+        None | Some(Value::Null) => Duration::from_secs(DEFAULT_DRAIN_TIMEOUT_SECS),
+        Some(v) => Duration::from_secs(v.as_u64().ok_or_else(|| {
+            create_validation_error("drain_timeout_secs", "must be a non-negative integer")
+        })?),
+    };
+    if !model.starts_with("fastembed:") {
+        return Err(create_bad_request_error(&format!(
+            "model '{}' cannot be reloaded: only model-file providers (\"fastembed:<id>\") \
+             can be hot-swapped; bm25 is built from the indexed vocabulary",
+            model
+        )));
+    }
+
+    info!(
+        "🧠 Reloading embedding provider '{}' with model '{}'",
+        name, model
+    );
+
+    // Load twice when the file watcher keeps its own manager: providers
+    // are not `Clone`, and both managers must embed with the same model.
+    let instances = if state.file_watcher_embedding_manager.is_some() {
+        2
+    } else {
+        1
+    };
+    let load_model = model.clone();
+    let loaded = tokio::task::spawn_blocking(move || {
+        (0..instances)
+            .map(|_| {
+                let (_, _, provider) = build_embedding_provider(&load_model)?;
+                provider.embed("vectorizer model reload probe")?;
+                Ok(provider)
+            })
+            .collect::<anyhow::Result<Vec<Box<dyn EmbeddingProvider>>>>()
+    })
+    .await
+    .map_err(|e| create_bad_request_error(&format!("model load task failed: {}", e)))?
+    .map_err(|e| create_bad_request_error(&format!("failed to load model '{}': {}", model, e)))?;
+    let mut loaded = loaded.into_iter();
+    let Some(provider) = loaded.next() else {
+        return Err(create_bad_request_error("model load produced no provider"));
+    };
+    let dimension = provider.dimension();
+
+    let swap = state
+        .embedding_manager
+        .swap_provider(&name, provider)
+        .map_err(|e| create_bad_request_error(&e.to_string()))?;
+
+    if let (Some(watcher_manager), Some(provider)) =
+        (state.file_watcher_embedding_manager.as_ref(), loaded.next())
+    {
+        let watcher_manager = watcher_manager.read().await;
+        if watcher_manager.has_provider(&name)
+            && let Err(e) = watcher_manager.swap_provider(&name, provider)
+        {
+            warn!(
+                "File watcher kept the previous model for provider '{}': {}",
+                name, e
+            );
+        }
+    }
+
+    let generation = swap.generation;
+    let swap = tokio::task::spawn_blocking(move || {
+        let drained = swap.wait_drained(drain_timeout);
+        (drained, swap.in_flight())
+    })
+    .await;
+    let (drained, in_flight) = swap.unwrap_or((false, 0));
+    if !drained {
+        warn!(
+            "Embedding provider '{}': {} call(s) still on the previous model after {:?}",
+            name, in_flight, drain_timeout
+        );
+    }
+
+    info!(
+        "🧠 Embedding provider '{}' now serving '{}' (generation {})",
+        name, model, generation
+    );
+
+    Ok(Json(json!({
+        "provider": name,
+        "model": model,
+        "dimension": dimension,
+        "generation": generation,
+        "drained": drained,
+        "in_flight": in_flight,
+    })))
+}
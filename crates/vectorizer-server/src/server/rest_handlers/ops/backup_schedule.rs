@@ -0,0 +1,52 @@
+//! Scheduled backup REST handlers.
+//!
+//! - `get_backup_schedule` — GET  /admin/backups/schedule
+//! - `run_backup_job`      — POST /admin/backups/schedule/{job}/run
+//!
+//! Jobs are configured in `backup_schedule.jobs` in config.yml; with no
+//! jobs configured the schedule is empty and no job can be run.
+
+#![allow(missing_docs)]
+
+use axum::extract::{Path, State};
+use axum::response::Json;
+use serde_json::{Value, json};
+use tracing::info;
+
+use crate::server::VectorizerServer;
+use crate::server::error_middleware::{ErrorResponse, create_not_found_error};
+
+/// GET /admin/backups/schedule
+///
+/// Every job with its cron expression, next run, and the time, duration
+/// and error of its last runs.
+pub async fn get_backup_schedule(State(state): State<VectorizerServer>) -> Json<Value> {
+    let jobs = state
+        .backup_scheduler
+        .as_ref()
+        .map(|s| s.status())
+        .unwrap_or_default();
+    let failing = jobs.iter().filter(|j| j.last_error.is_some()).count();
+    Json(json!({
+        "total": jobs.len(),
+        "failing": failing,
+        "jobs": jobs,
+    }))
+}
+
+/// POST /admin/backups/schedule/{job}/run
+///
+/// Run a job now, outside its schedule, and wait for it to finish. A
+/// failed run is recorded like a scheduled one and returned as an error.
+pub async fn run_backup_job(
+    State(state): State<VectorizerServer>,
+    Path(job): Path<String>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let scheduler = state
+        .backup_scheduler
+        .as_ref()
+        .ok_or_else(|| create_not_found_error("backup job", &job))?;
+    info!("Running backup job '{}' on request", job);
+    let status = scheduler.run_job(&job).await?;
+    Ok(Json(json!({ "job": status })))
+}
@@ -0,0 +1,137 @@
+//! Change feed REST handlers.
+//!
+//! - `stream_changes` — GET /changes/stream (Server-Sent Events)
+//!
+//! The feed is off unless `change_feed.enabled` is set in config.yml.
+//! The WebSocket variant of the stream is `GET /ws/changes`
+//! (`crate::server::ws::changes`). Both pass each event through
+//! [`redact_change`] for the caller.
+
+#![allow(missing_docs)]
+
+use std::collections::HashSet;
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::Extension;
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use futures::Stream;
+use serde::Deserialize;
+use serde_json::json;
+use tokio::sync::broadcast::error::RecvError;
+use vectorizer::auth::middleware::AuthState;
+use vectorizer::db::{ChangeCursor, ChangeEvent, ChangeFeed, ResumeGap};
+use vectorizer::security::PayloadView;
+
+use crate::server::VectorizerServer;
+use crate::server::core::caller_payload_view;
+use crate::server::error_middleware::{ErrorResponse, create_error_response};
+
+/// Which events a change stream carries.
+#[derive(Debug, Default, Deserialize)]
+pub struct ChangeStreamQuery {
+    /// Comma-separated collection names; every collection when absent.
+    pub collections: Option<String>,
+    /// Resume after this sequence number. `Last-Event-ID` takes
+    /// precedence when an SSE client reconnects.
+    pub since: Option<u64>,
+}
+
+impl ChangeStreamQuery {
+    fn collections(&self) -> Option<HashSet<String>> {
+        self.collections.as_deref().map(|list| {
+            list.split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+    }
+}
+
+/// The store's change feed, or 404 when `change_feed.enabled` is off.
+pub(crate) fn change_feed(state: &VectorizerServer) -> Result<Arc<ChangeFeed>, ErrorResponse> {
+    state.store.change_feed().ok_or_else(|| {
+        create_error_response(
+            "change_feed_disabled",
+            "change feed is not enabled; set change_feed.enabled in config.yml",
+            StatusCode::NOT_FOUND,
+        )
+    })
+}
+
+/// A cursor for `query`, resuming after `after` when given.
+pub(crate) fn open_cursor(
+    feed: &ChangeFeed,
+    after: Option<u64>,
+    query: &ChangeStreamQuery,
+) -> Result<ChangeCursor, ErrorResponse> {
+    feed.cursor(after, query.collections())
+        .map_err(|gap: ResumeGap| {
+            create_error_response("change_feed_gap", &gap.to_string(), StatusCode::GONE)
+                .with_details(json!({
+                    "oldest_seq": gap.oldest,
+                    "latest_seq": gap.latest,
+                }))
+        })
+}
+
+/// `change` as the caller behind `view` may see it. Callers whose
+/// `api.payload_redaction` rules cover the collection get no
+/// `payload_hash`: a hash of the raw payload would confirm guesses of
+/// the fields redacted from them.
+pub(crate) fn redact_change(view: &PayloadView, change: &mut ChangeEvent) {
+    if view.redacts_collection(&change.collection) {
+        change.payload_hash = None;
+    }
+}
+
+/// GET /changes/stream?collections=a,b&since=N
+///
+/// One `change` event per vector insert, update or delete, with the
+/// sequence number as its SSE id, so a reconnecting client resumes where
+/// it left off. A client that falls behind the feed's buffer gets a
+/// `lagged` event and the stream ends; resuming then fails with 410 and
+/// the client has to re-read the collections.
+pub async fn stream_changes(
+    State(state): State<VectorizerServer>,
+    Query(query): Query<ChangeStreamQuery>,
+    auth: Option<Extension<AuthState>>,
+    headers: HeaderMap,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ErrorResponse> {
+    let feed = change_feed(&state)?;
+    let view = caller_payload_view(state.payload_redactor.clone(), auth.as_deref());
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse().ok());
+    let cursor = open_cursor(&feed, last_event_id.or(query.since), &query)?;
+
+    let events = futures::stream::unfold(Some(cursor), move |cursor| {
+        let view = view.clone();
+        async move {
+            let mut cursor = cursor?;
+            match cursor.next().await {
+                Ok(mut change) => {
+                    redact_change(&view, &mut change);
+                    let event = Event::default()
+                        .event("change")
+                        .id(change.seq.to_string())
+                        .data(serde_json::to_string(&change).unwrap_or_default());
+                    Some((Ok(event), Some(cursor)))
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    let event = Event::default()
+                        .event("lagged")
+                        .data(json!({ "skipped": skipped }).to_string());
+                    Some((Ok(event), None))
+                }
+                Err(RecvError::Closed) => None,
+            }
+        }
+    });
+    Ok(Sse::new(events).keep_alive(KeepAlive::new().interval(Duration::from_secs(15))))
+}
@@ -0,0 +1,100 @@
+//! Health probes.
+//!
+//! - `health_check` — GET /health
+//! - `health_live`  — GET /health/live
+//! - `health_ready` — GET /health/ready
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::Json;
+use serde_json::{Value, json};
+
+use crate::server::VectorizerServer;
+
+/// GET /health — combined status with cache and hub stats, kept for
+/// existing probes; new deployments should use `/health/live` and
+/// `/health/ready`.
+///
+/// Answers 503 with `status: "draining"` while the server drains
+/// (`POST /admin/drain`) so load balancers stop routing to it. `ready`
+/// mirrors `/health/ready`.
+pub async fn health_check(State(state): State<VectorizerServer>) -> (StatusCode, Json<Value>) {
+    let cache_stats = state.query_cache.stats();
+    let draining = state.drain.is_draining();
+    let (ready, _) = state.readiness.check(&state);
+
+    // Build base health response
+    let mut response = json!({
+        "status": if draining { "draining" } else { "healthy" },
+        "ready": ready,
+        "read_only": state.drain.is_read_only(),
+        "timestamp": chrono::Utc::now(),
+        "version": env!("CARGO_PKG_VERSION"),
+        "cache": {
+            "size": cache_stats.size,
+            "capacity": cache_stats.capacity,
+            "hits": cache_stats.hits,
+            "misses": cache_stats.misses,
+            "evictions": cache_stats.evictions,
+            "hit_rate": cache_stats.hit_rate
+        }
+    });
+
+    // Add Hub status if Hub is enabled
+    if let Some(ref hub_manager) = state.hub_manager {
+        let hub_status = json!({
+            "enabled": hub_manager.is_enabled(),
+            "active": hub_manager.is_active(),
+            "tenant_isolation": format!("{:?}", hub_manager.config().tenant_isolation),
+        });
+        response["hub"] = hub_status;
+    }
+
+    // Add backup manager status
+    if state.backup_manager.is_some() {
+        response["backup"] = json!({
+            "enabled": true
+        });
+    }
+
+    let status = if draining {
+        StatusCode::SERVICE_UNAVAILABLE
+    } else {
+        StatusCode::OK
+    };
+    (status, Json(response))
+}
+
+/// GET /health/live — the process is up and serving HTTP. Always 200;
+/// a failing liveness probe should mean "restart me", which loading
+/// collections or a slow replica never warrants.
+pub async fn health_live(State(state): State<VectorizerServer>) -> Json<Value> {
+    Json(json!({
+        "status": "alive",
+        "uptime_seconds": state.start_time.elapsed().as_secs(),
+        "version": env!("CARGO_PKG_VERSION"),
+    }))
+}
+
+/// GET /health/ready — whether the node should receive traffic. 503
+/// until the startup collection load has finished and every check in
+/// `health.readiness` passes; `checks` reports each one. Read-only mode
+/// (`PUT /admin/read_only`) is reported but keeps the node ready, since
+/// searches are still served.
+pub async fn health_ready(State(state): State<VectorizerServer>) -> (StatusCode, Json<Value>) {
+    let (ready, checks) = state.readiness.check(&state);
+    let status = if ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (
+        status,
+        Json(json!({
+            "status": if ready { "ready" } else { "not_ready" },
+            "ready": ready,
+            "read_only": state.drain.is_read_only(),
+            "checks": checks,
+        })),
+    )
+}
@@ -0,0 +1,35 @@
+//! Lazy collection loading REST handlers.
+//!
+//! - `get_lazy_loading_status` — GET /admin/lazy_loading
+//!
+//! Lazy loading is configured under `lazy_loading` in config.yml: only
+//! recently used collections stay in memory and the rest wait in
+//! `vectorizer.vecdb` until accessed.
+
+#![allow(missing_docs)]
+
+use axum::extract::State;
+use axum::response::Json;
+use serde_json::{Value, json};
+
+use crate::server::VectorizerServer;
+
+/// GET /admin/lazy_loading
+///
+/// The resident collections, least recently used first, with their
+/// estimated size and whether unsaved changes pin them in memory; the
+/// collections waiting on disk; and load / eviction counters.
+pub async fn get_lazy_loading_status(State(state): State<VectorizerServer>) -> Json<Value> {
+    match state.store.residency_status() {
+        Some(status) => {
+            let mut body = json!({ "enabled": true });
+            if let (Some(map), Ok(Value::Object(fields))) =
+                (body.as_object_mut(), serde_json::to_value(status))
+            {
+                map.extend(fields);
+            }
+            Json(body)
+        }
+        None => Json(json!({ "enabled": false })),
+    }
+}
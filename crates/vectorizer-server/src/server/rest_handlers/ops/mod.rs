@@ -0,0 +1,39 @@
+//! Server operations: drain and read-only mode, live configuration,
+//! embedding model reload, health probes, scheduled backups, background
+//! tasks, the change feed and collection residency.
+//!
+//! - [`admin_config`]    — /admin/config live search and cache tuning
+//! - [`admin_drain`]     — /admin/drain + /admin/read_only
+//! - [`admin_embedding`] — embedding model reload
+//! - [`backup_schedule`] — /admin/backups/schedule cron backup jobs
+//! - [`changes`]         — /changes/stream change feed over SSE
+//! - [`health`]          — /health, /health/live, /health/ready
+//! - [`lazy_loading`]    — /admin/lazy_loading resident / on-disk
+//!                         collections
+//! - [`read_through`]    — /admin/read_through read-through collection
+//!                         status
+//! - [`tasks`]           — /admin/tasks background task status +
+//!                         pause/resume
+
+mod admin_config;
+mod admin_drain;
+mod admin_embedding;
+mod backup_schedule;
+mod changes;
+mod health;
+mod lazy_loading;
+mod read_through;
+mod tasks;
+
+pub use admin_config::{get_runtime_config, patch_runtime_config};
+pub use admin_drain::{
+    drain_server, get_drain_status, get_read_only_status, resume_server, set_read_only,
+};
+pub use admin_embedding::reload_embedding_provider;
+pub use backup_schedule::{get_backup_schedule, run_backup_job};
+pub use changes::stream_changes;
+pub(crate) use changes::{ChangeStreamQuery, change_feed, open_cursor, redact_change};
+pub use health::{health_check, health_live, health_ready};
+pub use lazy_loading::get_lazy_loading_status;
+pub use read_through::list_read_through_collections;
+pub use tasks::{list_background_tasks, pause_background_task, resume_background_task};
@@ -0,0 +1,29 @@
+//! Read-through collection REST handlers.
+//!
+//! - `list_read_through_collections` — GET /admin/read_through
+//!
+//! Read-through collections are configured in `read_through.collections`
+//! in config.yml and loaded from their origin on first access.
+
+#![allow(missing_docs)]
+
+use axum::extract::State;
+use axum::response::Json;
+use serde_json::{Value, json};
+
+use crate::server::VectorizerServer;
+
+/// GET /admin/read_through
+///
+/// Every read-through collection with its origin, whether it is loaded,
+/// and when it was last loaded and accessed. Origin API keys are not
+/// listed.
+pub async fn list_read_through_collections(State(state): State<VectorizerServer>) -> Json<Value> {
+    let collections = state.store.read_through_status();
+    let loaded = collections.iter().filter(|c| c.loaded).count();
+    Json(json!({
+        "total": collections.len(),
+        "loaded": loaded,
+        "collections": collections,
+    }))
+}
@@ -0,0 +1,58 @@
+//! Background task REST handlers.
+//!
+//! - `list_background_tasks`  — GET  /admin/tasks
+//! - `pause_background_task`  — POST /admin/tasks/{name}/pause
+//! - `resume_background_task` — POST /admin/tasks/{name}/resume
+//!
+//! Tasks register in [`vectorizer::background_tasks::TASKS`] when their
+//! loop starts, so one that is disabled in config.yml (e.g. compaction,
+//! or replication sync on a master) is not listed.
+
+#![allow(missing_docs)]
+
+use axum::extract::Path;
+use axum::response::Json;
+use serde_json::{Value, json};
+use tracing::info;
+use vectorizer::background_tasks::TASKS;
+
+use crate::server::error_middleware::{ErrorResponse, create_not_found_error};
+
+/// GET /admin/tasks
+///
+/// Every registered task with its state, last run, last error and next
+/// scheduled run.
+pub async fn list_background_tasks() -> Json<Value> {
+    let tasks = TASKS.list();
+    Json(json!({
+        "total": tasks.len(),
+        "tasks": tasks,
+    }))
+}
+
+/// POST /admin/tasks/{name}/pause
+///
+/// The task finishes the run it is in and starts no new ones until
+/// resumed.
+pub async fn pause_background_task(Path(name): Path<String>) -> Result<Json<Value>, ErrorResponse> {
+    set_paused(&name, true)
+}
+
+/// POST /admin/tasks/{name}/resume
+pub async fn resume_background_task(
+    Path(name): Path<String>,
+) -> Result<Json<Value>, ErrorResponse> {
+    set_paused(&name, false)
+}
+
+fn set_paused(name: &str, paused: bool) -> Result<Json<Value>, ErrorResponse> {
+    let status = TASKS
+        .set_paused(name, paused)
+        .ok_or_else(|| create_not_found_error("task", name))?;
+    info!(
+        "Background task '{}' {}",
+        name,
+        if paused { "paused" } else { "resumed" }
+    );
+    Ok(Json(json!({ "task": status })))
+}
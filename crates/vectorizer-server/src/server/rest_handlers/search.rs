@@ -5,9 +5,15 @@
 //! - `search_by_file`          — POST /collections/{name}/search/file
 //! - `explain_search`          — POST /collections/{name}/explain
 //! - `search_vectors`          — POST /search (raw vector, returns empty results until wired)
-//! - `batch_search_vectors`    — POST /batch/search
 //! - `batch_update_vectors`    — POST /batch/update
 //! - `batch_delete_vectors`    — POST /batch/delete
+//!
+//! The text, hybrid and raw-vector searches accept `timeout_ms`; see
+//! [`search_deadline`]. `POST /batch_search` and
+//! `POST /collections/{name}/search/text/batch` live in
+//! [`super::search_features::batch_search`] and
+//! [`super::search_features::search_text_batch`], the BM25
+//! `full_text_search` in [`super::search_features::full_text_search`].
 
 // Internal data-layout file: public fields are self-documenting; the
 // blanket allow keeps `cargo doc -W missing-docs` clean without padding
@@ -20,50 +26,44 @@
 // phase4_enforce-public-api-docs.
 #![allow(missing_docs)]
 
+use std::time::Instant;
+
 use axum::Extension;
 use axum::extract::{Path, State};
 use axum::response::Json;
 use serde_json::{Value, json};
 use tracing::{debug, info};
-use vectorizer::db::{HybridScoringAlgorithm, HybridSearchConfig};
+use vectorizer::auth::middleware::AuthState;
+use vectorizer::db::{HybridScoringAlgorithm, HybridSearchConfig, SearchDeadline};
 use vectorizer::hub::middleware::RequestTenantContext;
 use vectorizer::models::SparseVector;
 
-use super::common::extract_tenant_id;
+use super::common::{extract_tenant_id, f32_array};
+use super::experiments::run_experiment_search;
+use super::query_analytics::record_query;
+use super::search_common::{
+    MAX_SEARCH_LIMIT, cached_total_results, clamped_limit, dimension_mismatch, exact_flag,
+    record_search_stats, record_vector_search, search_deadline, search_status_label,
+    vector_search_response,
+};
+use super::tenant_partition::Partition;
 use crate::server::VectorizerServer;
 use crate::server::error_middleware::{
     ErrorResponse, create_bad_request_error, create_validation_error,
 };
 
-/// Server-side ceiling for the `limit` field on every search shape.
-///
-/// The OpenAPI/tool schemas document `maximum: 100`, but schemas don't
-/// execute — before phase40 a client could send `limit: 100000000` and
-/// drive result allocation directly (memory-DoS vector, analysis §2).
-/// Clamping (rather than rejecting) matches how the capped `list`
-/// endpoints already behave.
-pub(crate) const MAX_SEARCH_LIMIT: usize = 100;
-
-/// Parse `payload.limit`, defaulting to `default` and clamping to
-/// [`MAX_SEARCH_LIMIT`].
-pub(crate) fn clamped_limit(payload: &Value, default: usize) -> usize {
-    (payload
-        .get("limit")
-        .and_then(|l| l.as_u64())
-        .unwrap_or(default as u64) as usize)
-        .min(MAX_SEARCH_LIMIT)
-}
-
 pub async fn search_vectors_by_text(
     State(state): State<VectorizerServer>,
     Path(collection_name): Path<String>,
     tenant_ctx: Option<Extension<RequestTenantContext>>,
+    auth: Option<Extension<AuthState>>,
     Json(payload): Json<Value>,
 ) -> Result<Json<Value>, ErrorResponse> {
     use vectorizer::cache::query_cache::QueryKey;
     use vectorizer::monitoring::metrics::METRICS;
 
     // Start latency timer
+    let started = Instant::now();
     let label_collection: &str = &collection_name;
     let label_text = "text".to_string();
     let timer = METRICS
@@ -77,14 +77,68 @@ pub async fn search_vectors_by_text(
         .ok_or_else(|| create_validation_error("query", "missing or invalid query parameter"))?;
     let limit = clamped_limit(&payload, 10);
     let threshold = payload.get("threshold").and_then(|t| t.as_f64());
+    let exact = exact_flag(&payload);
+    let deadline = search_deadline(&payload)?;
 
-    // Check cache first
+    // Extract tenant ID for multi-tenant access control
+    let tenant_id = extract_tenant_id(&tenant_ctx);
+
+    // Get the collection with owner validation, before the cache is
+    // consulted so a cached result never reaches another tenant
+    let collection = state
+        .store
+        .get_collection_with_owner(&collection_name, tenant_id.as_ref())
+        .map_err(|e| ErrorResponse::from(e))?;
+
+    // Check cache first (exact searches are ground truth and never
+    // cached, and a tenant's partition must not share entries)
     let cache_key = QueryKey::new(collection_name.clone(), query.to_string(), limit, threshold);
-    if let Some(cached_result) = state.query_cache.get(&cache_key) {
+    let partition = Partition::resolve(&state, &collection_name, tenant_ctx.as_ref());
+    let cacheable = !exact && partition.is_none();
+
+    // An active experiment on the collection picks the retrieval
+    // configuration; its results are never cached so each variant is
+    // measured on its own searches
+    if !exact {
+        let tenant = tenant_ctx.as_ref().map(|ctx| ctx.0.0.tenant_id.as_str());
+        if let Some(response) = run_experiment_search(
+            &state,
+            &collection_name,
+            query,
+            &payload,
+            limit,
+            tenant,
+            auth.as_deref(),
+            &deadline,
+        ) {
+            let response = response?;
+            record_query(
+                &state,
+                &collection_name,
+                "text",
+                query,
+                started,
+                cached_total_results(&response),
+            );
+            drop(timer);
+            return Ok(Json(response));
+        }
+    }
+
+    let generation = state.query_cache.generation();
+    if cacheable && let Some(cached_result) = state.query_cache.get(&cache_key) {
         debug!(
             "💾 Cache hit for query '{}' in collection '{}'",
             query, collection_name
         );
+        record_query(
+            &state,
+            &collection_name,
+            "text",
+            query,
+            started,
+            cached_total_results(&cached_result),
+        );
         drop(timer);
         return Ok(Json(cached_result));
     }
@@ -94,28 +148,26 @@ pub async fn search_vectors_by_text(
         query, collection_name
     );
 
-    // Extract tenant ID for multi-tenant access control
-    let tenant_id = extract_tenant_id(&tenant_ctx);
-
-    // Get the collection with owner validation
-    let collection = state
-        .store
-        .get_collection_with_owner(&collection_name, tenant_id.as_ref())
-        .map_err(|e| ErrorResponse::from(e))?;
-
     // Generate embedding for the query
     let query_embedding = state
-        .embedding_manager
-        .embed(query)
+        .embed_for_collection(&collection_name, query)
         .map_err(|e| create_bad_request_error(&format!("Failed to generate embedding: {}", e)))?;
 
     // Search vectors in the collection
-    let search_results = collection
-        .search(&query_embedding, limit)
-        .map_err(|e| create_bad_request_error(&format!("Search failed: {}", e)))?;
+    let outcome = if let Some(partition) = &partition {
+        collection.search_partition_until(&query_embedding, limit, &partition.tenant, &deadline)
+    } else if exact {
+        collection.search_exact_until(&query_embedding, limit, &deadline)
+    } else {
+        collection.search_until(&query_embedding, limit, &deadline)
+    }
+    .map_err(|e| create_bad_request_error(&format!("Search failed: {}", e)))?;
+    let timed_out = outcome.timed_out;
+    record_search_stats(&collection_name, outcome.stats);
 
     // Convert results to JSON format
-    let results: Vec<Value> = search_results
+    let results: Vec<Value> = outcome
+        .results
         .into_iter()
         .map(|result| {
             json!({
@@ -132,17 +184,23 @@ pub async fn search_vectors_by_text(
         "results": results,
         "query": query,
         "limit": limit,
+        "exact": exact,
+        "timed_out": timed_out,
         "collection": collection_name,
         "total_results": results.len()
     });
 
-    // Cache the result
-    state.query_cache.insert(cache_key, response.clone());
+    // Cache the result (partial results from a timed-out search are not)
+    if cacheable && !timed_out {
+        state
+            .query_cache
+            .insert_at(cache_key, response.clone(), generation);
+    }
 
     // Record metrics
     let label_collection: &str = &collection_name;
     let label_text = "text";
-    let label_success = "success";
+    let label_success = search_status_label(timed_out);
     METRICS
         .search_requests_total
         .with_label_values(&[label_collection, label_text, label_success])
@@ -152,6 +210,14 @@ pub async fn search_vectors_by_text(
         .search_results_count
         .with_label_values(&[&collection_name, &label_text_str])
         .observe(results.len() as f64);
+    record_query(
+        &state,
+        &collection_name,
+        "text",
+        query,
+        started,
+        results.len(),
+    );
     drop(timer); // Stop latency timer
 
     Ok(Json(response))
@@ -167,6 +233,7 @@ pub async fn hybrid_search_vectors(
     use vectorizer::monitoring::metrics::METRICS;
 
     // Start latency timer
+    let started = Instant::now();
     let label_collection: &str = &collection_name;
     let label_hybrid = "hybrid".to_string();
     let timer = METRICS
@@ -176,6 +243,7 @@ pub async fn hybrid_search_vectors(
 
     // Extract tenant ID for multi-tenant access control
     let tenant_id = extract_tenant_id(&tenant_ctx);
+    let deadline = search_deadline(&payload)?;
 
     // Parse query (required)
     let query = payload
@@ -218,17 +286,11 @@ pub async fn hybrid_search_vectors(
     };
 
     // Parse hybrid search configuration
-    let alpha = payload.get("alpha").and_then(|v| v.as_f64()).unwrap_or(0.7) as f32;
-    let algorithm_str = payload
-        .get("algorithm")
-        .and_then(|v| v.as_str())
-        .unwrap_or("rrf");
-    let algorithm = match algorithm_str {
-        "rrf" => HybridScoringAlgorithm::ReciprocalRankFusion,
-        "weighted" => HybridScoringAlgorithm::WeightedCombination,
-        "alpha" => HybridScoringAlgorithm::AlphaBlending,
-        _ => HybridScoringAlgorithm::ReciprocalRankFusion,
-    };
+    let alpha = payload
+        .get("alpha")
+        .and_then(|v| v.as_f64())
+        .map(|a| a as f32);
+    let algorithm_str = payload.get("algorithm").and_then(|v| v.as_str());
     let dense_k = (payload
         .get("dense_k")
         .and_then(|v| v.as_u64())
@@ -246,18 +308,58 @@ pub async fn hybrid_search_vectors(
         .unwrap_or(10) as usize)
         .min(MAX_SEARCH_LIMIT);
 
+    // Get the collection with owner validation
+    let collection = state
+        .store
+        .get_collection_with_owner(&collection_name, tenant_id.as_ref())
+        .map_err(|e| ErrorResponse::from(e))?;
+
+    // Weights tuned from relevance judgements stand in for the defaults
+    // when the request names neither alpha nor algorithm
+    let tuned = if alpha.is_none() && algorithm_str.is_none() {
+        collection.tuned_hybrid_weights()
+    } else {
+        None
+    };
+    let tuned_weights = tuned.is_some();
+    let alpha = alpha.or(tuned.as_ref().map(|w| w.alpha)).unwrap_or(0.7);
+    let algorithm = match algorithm_str {
+        Some("weighted") => HybridScoringAlgorithm::WeightedCombination,
+        Some("alpha") => HybridScoringAlgorithm::AlphaBlending,
+        Some(_) => HybridScoringAlgorithm::ReciprocalRankFusion,
+        None => tuned.map_or(HybridScoringAlgorithm::ReciprocalRankFusion, |w| {
+            w.algorithm
+        }),
+    };
+
     // Check cache first
     let cache_key = QueryKey::new(
         collection_name.clone(),
-        format!("hybrid:{}:{}", query, alpha),
+        format!("hybrid:{}", query),
         final_k,
         None,
-    );
+    )
+    .with_params(&json!({
+        "alpha": alpha,
+        "algorithm": algorithm,
+        "dense_k": dense_k,
+        "sparse_k": sparse_k,
+        "query_sparse": payload.get("query_sparse"),
+    }));
+    let generation = state.query_cache.generation();
     if let Some(cached_result) = state.query_cache.get(&cache_key) {
         debug!(
             "💾 Cache hit for hybrid query '{}' in collection '{}'",
             query, collection_name
         );
+        record_query(
+            &state,
+            &collection_name,
+            "hybrid",
+            query,
+            started,
+            cached_total_results(&cached_result),
+        );
         drop(timer);
         return Ok(Json(cached_result));
     }
@@ -267,16 +369,9 @@ pub async fn hybrid_search_vectors(
         query, collection_name, alpha, algorithm
     );
 
-    // Get the collection with owner validation
-    let collection = state
-        .store
-        .get_collection_with_owner(&collection_name, tenant_id.as_ref())
-        .map_err(|e| ErrorResponse::from(e))?;
-
     // Generate dense embedding for the query
     let query_dense = state
-        .embedding_manager
-        .embed(query)
+        .embed_for_collection(&collection_name, query)
         .map_err(|e| create_bad_request_error(&format!("Failed to generate embedding: {}", e)))?;
 
     // Create hybrid search config
@@ -289,12 +384,14 @@ pub async fn hybrid_search_vectors(
     };
 
     // Perform hybrid search
-    let search_results = collection
-        .hybrid_search(&query_dense, query_sparse.as_ref(), config)
+    let outcome = collection
+        .hybrid_search_until(&query_dense, query_sparse.as_ref(), config, &deadline)
         .map_err(|e| create_bad_request_error(&format!("Hybrid search failed: {}", e)))?;
+    let timed_out = outcome.timed_out;
 
     // Convert results to JSON format
-    let results: Vec<Value> = search_results
+    let results: Vec<Value> = outcome
+        .results
         .into_iter()
         .map(|result| {
             json!({
@@ -317,15 +414,21 @@ pub async fn hybrid_search_vectors(
         "limit": final_k,
         "collection": collection_name,
         "alpha": alpha,
-        "algorithm": algorithm_str,
+        "algorithm": algorithm,
+        "tuned_weights": tuned_weights,
+        "timed_out": timed_out,
         "total_results": results.len()
     });
 
-    // Cache the result
-    state.query_cache.insert(cache_key, response.clone());
+    // Cache the result (partial results from a timed-out search are not)
+    if !timed_out {
+        state
+            .query_cache
+            .insert_at(cache_key, response.clone(), generation);
+    }
 
     // Record metrics
-    let label_success = "success";
+    let label_success = search_status_label(timed_out);
     METRICS
         .search_requests_total
         .with_label_values(&[label_collection, &label_hybrid, label_success])
@@ -334,6 +437,14 @@ pub async fn hybrid_search_vectors(
         .search_results_count
         .with_label_values(&[label_collection, &label_hybrid])
         .observe(results.len() as f64);
+    record_query(
+        &state,
+        &collection_name,
+        "hybrid",
+        query,
+        started,
+        results.len(),
+    );
     drop(timer); // Stop latency timer
 
     Ok(Json(response))
@@ -376,15 +487,23 @@ pub async fn search_by_file(
 /// /collections/{name}/search).
 ///
 /// Validates that the query vector's dimension matches the target
-/// collection, consults the query cache (via `QueryKey::from_vector`),
+/// collection, consults the query cache (via `QueryKey::from_query_vector`),
 /// runs the HNSW search, and records metrics under the `vector` label.
-/// Returns the JSON response body.
+/// With `exact` set the HNSW graph is bypassed for a brute-force scan
+/// and the cache is skipped both ways. A search cut short by `deadline`
+/// returns its partial results with `timed_out: true` and is not cached.
+/// A tenant searching a partitioned collection only gets its own
+/// vectors back, bypassing the shared cache. Returns the JSON response
+/// body.
+#[allow(clippy::too_many_arguments)]
 async fn do_vector_search(
     state: &VectorizerServer,
     collection_name: &str,
     query_embedding: Vec<f32>,
     limit: usize,
     threshold: Option<f64>,
+    exact: bool,
+    deadline: SearchDeadline,
     tenant_ctx: Option<&Extension<RequestTenantContext>>,
 ) -> Result<Value, ErrorResponse> {
     use vectorizer::cache::query_cache::QueryKey;
@@ -396,13 +515,31 @@ async fn do_vector_search(
         .with_label_values(&[collection_name, &label_vector])
         .start_timer();
 
-    let cache_key = QueryKey::from_vector(
+    let tenant_id = extract_tenant_id(&tenant_ctx.cloned());
+
+    let collection = state
+        .store
+        .get_collection_with_owner(collection_name, tenant_id.as_ref())
+        .map_err(ErrorResponse::from)?;
+
+    if query_embedding.len() != collection.config().dimension {
+        return Err(dimension_mismatch(
+            query_embedding.len(),
+            collection.config().dimension,
+        ));
+    }
+
+    let cache_key = QueryKey::from_query_vector(
         collection_name.to_string(),
         &query_embedding,
+        collection.config().metric,
         limit,
         threshold,
     );
-    if let Some(cached) = state.query_cache.get(&cache_key) {
+    let partition = Partition::resolve(state, collection_name, tenant_ctx);
+    let cacheable = !exact && partition.is_none();
+    let generation = state.query_cache.generation();
+    if cacheable && let Some(cached) = state.query_cache.get(&cache_key) {
         debug!(
             "💾 Cache hit for raw-vector search in collection '{}'",
             collection_name
@@ -411,92 +548,51 @@ async fn do_vector_search(
         return Ok(cached);
     }
 
-    let tenant_id = extract_tenant_id(&tenant_ctx.cloned());
-
-    let collection = state
-        .store
-        .get_collection_with_owner(collection_name, tenant_id.as_ref())
-        .map_err(ErrorResponse::from)?;
-
-    if query_embedding.len() != collection.config().dimension {
-        return Err(create_validation_error(
-            "vector",
-            &format!(
-                "vector dimension {} does not match collection dimension {}",
-                query_embedding.len(),
-                collection.config().dimension
-            ),
-        ));
+    let outcome = if let Some(partition) = &partition {
+        collection.search_partition_until(&query_embedding, limit, &partition.tenant, &deadline)
+    } else if exact {
+        collection.search_exact_until(&query_embedding, limit, &deadline)
+    } else {
+        collection.search_until(&query_embedding, limit, &deadline)
     }
-
-    let search_results = collection
-        .search(&query_embedding, limit)
-        .map_err(|e| create_bad_request_error(&format!("Search failed: {}", e)))?;
-
-    let results: Vec<Value> = search_results
-        .into_iter()
-        .filter(|r| threshold.is_none_or(|t| r.score as f64 >= t))
-        .map(|result| {
-            json!({
-                "id": result.id,
-                "score": result.score,
-                "vector": result.vector,
-                "payload": result.payload.map(|p| p.data)
-            })
-        })
-        .collect();
-
-    let response = json!({
-        "results": results,
-        "query_type": "vector",
-        "limit": limit,
-        "collection": collection_name,
-        "total_results": results.len(),
-    });
-
-    state.query_cache.insert(cache_key, response.clone());
-
-    METRICS
-        .search_requests_total
-        .with_label_values(&[collection_name, &label_vector, "success"])
-        .inc();
-    METRICS
-        .search_results_count
-        .with_label_values(&[collection_name, &label_vector])
-        .observe(results.len() as f64);
+    .map_err(|e| create_bad_request_error(&format!("Search failed: {}", e)))?;
+    let timed_out = outcome.timed_out;
+    let stats = outcome.stats;
+    let response = vector_search_response(collection_name, outcome, limit, threshold, exact);
+
+    if cacheable && !timed_out {
+        state
+            .query_cache
+            .insert_at(cache_key, response.clone(), generation);
+    }
+    record_vector_search(collection_name, timed_out, stats, &response);
     drop(timer);
 
     Ok(response)
 }
 
-/// Parse `vector`, `limit`, `threshold` from the request JSON. Returns
-/// 400 when `vector` is missing, not an array, or contains non-float
-/// entries.
+/// Parse `vector`, `limit`, `threshold`, `exact` from the request JSON.
+/// Returns 400 when `vector` is missing, not an array, or contains
+/// non-float entries.
 fn parse_vector_search_payload(
     payload: &Value,
-) -> Result<(Vec<f32>, usize, Option<f64>), ErrorResponse> {
+) -> Result<(Vec<f32>, usize, Option<f64>, bool), ErrorResponse> {
     let raw = payload
         .get("vector")
         .and_then(|v| v.as_array())
         .ok_or_else(|| create_validation_error("vector", "missing or invalid vector parameter"))?;
-    let mut query_vector = Vec::with_capacity(raw.len());
-    for (idx, entry) in raw.iter().enumerate() {
-        let f = entry.as_f64().ok_or_else(|| {
-            create_validation_error("vector", &format!("vector[{}] is not a number", idx))
-        })?;
-        query_vector.push(f as f32);
-    }
+    let query_vector = f32_array(raw, "vector")?;
     let limit = clamped_limit(&payload, 10);
     let threshold = payload.get("threshold").and_then(|t| t.as_f64());
-    Ok((query_vector, limit, threshold))
+    Ok((query_vector, limit, threshold, exact_flag(payload)))
 }
 
 /// POST /search — raw-vector similarity search. The target collection
 /// is taken from the JSON body's `collection` field.
 ///
-/// Request: `{collection, vector: [f32; dim], limit?, threshold?}`
-/// Response: `{collection, limit, query_type: "vector", total_results,
-/// results: [{id, score, vector, payload}]}`
+/// Request: `{collection, vector: [f32; dim], limit?, threshold?, exact?}`
+/// Response: `{collection, limit, query_type: "vector", exact,
+/// total_results, results: [{id, score, vector, payload}]}`
 pub async fn search_vectors(
     State(state): State<VectorizerServer>,
     tenant_ctx: Option<Extension<RequestTenantContext>>,
@@ -510,7 +606,8 @@ pub async fn search_vectors(
         })?
         .to_string();
 
-    let (query_vector, limit, threshold) = parse_vector_search_payload(&payload)?;
+    let deadline = search_deadline(&payload)?;
+    let (query_vector, limit, threshold, exact) = parse_vector_search_payload(&payload)?;
 
     let response = do_vector_search(
         &state,
@@ -518,6 +615,8 @@ pub async fn search_vectors(
         query_vector,
         limit,
         threshold,
+        exact,
+        deadline,
         tenant_ctx.as_ref(),
     )
     .await?;
@@ -534,7 +633,8 @@ pub async fn search_vectors_by_collection(
     tenant_ctx: Option<Extension<RequestTenantContext>>,
     Json(payload): Json<Value>,
 ) -> Result<Json<Value>, ErrorResponse> {
-    let (query_vector, limit, threshold) = parse_vector_search_payload(&payload)?;
+    let deadline = search_deadline(&payload)?;
+    let (query_vector, limit, threshold, exact) = parse_vector_search_payload(&payload)?;
 
     let response = do_vector_search(
         &state,
@@ -542,6 +642,8 @@ pub async fn search_vectors_by_collection(
         query_vector,
         limit,
         threshold,
+        exact,
+        deadline,
         tenant_ctx.as_ref(),
     )
     .await?;
@@ -549,152 +651,13 @@ pub async fn search_vectors_by_collection(
     Ok(Json(response))
 }
 
-/// POST /batch_search — run multiple searches against one collection.
-///
-/// Request: `{collection, queries: [{query?, vector?, limit?, threshold?}]}`
-/// Each query may carry either a text `query` (embedded server-side via
-/// the active `EmbeddingManager`) or a raw `vector` (validated against
-/// the collection dimension). Per-query failures are captured in the
-/// response without aborting the batch.
-///
-/// Response: `{collection, count, succeeded, failed, results: [{index,
-/// query?, vector?, status: "ok"|"error", results?, total_results?, error?}]}`.
-pub async fn batch_search_vectors(
-    State(state): State<VectorizerServer>,
-    tenant_ctx: Option<Extension<RequestTenantContext>>,
-    Json(payload): Json<Value>,
-) -> Result<Json<Value>, ErrorResponse> {
-    let collection_name = payload
-        .get("collection")
-        .and_then(|c| c.as_str())
-        .ok_or_else(|| {
-            create_validation_error("collection", "missing or invalid collection parameter")
-        })?
-        .to_string();
-
-    let queries = payload
-        .get("queries")
-        .and_then(|q| q.as_array())
-        .ok_or_else(|| create_validation_error("queries", "missing or invalid queries parameter"))?
-        .clone();
-
-    if queries.is_empty() {
-        return Err(create_validation_error(
-            "queries",
-            "queries array must contain at least one entry",
-        ));
-    }
-
-    info!(
-        "Batch searching {} queries against '{}'",
-        queries.len(),
-        collection_name
-    );
-
-    let mut succeeded: usize = 0;
-    let mut failed: usize = 0;
-    let mut results: Vec<Value> = Vec::with_capacity(queries.len());
-
-    for (idx, entry) in queries.iter().enumerate() {
-        let limit = clamped_limit(entry, 10);
-        let threshold = entry.get("threshold").and_then(|v| v.as_f64());
-
-        let outcome = if let Some(vec_arr) = entry.get("vector").and_then(|v| v.as_array()) {
-            let mut query_vector = Vec::with_capacity(vec_arr.len());
-            let mut bad_entry: Option<String> = None;
-            for (i, v) in vec_arr.iter().enumerate() {
-                match v.as_f64() {
-                    Some(f) => query_vector.push(f as f32),
-                    None => {
-                        bad_entry = Some(format!("vector[{}] is not a number", i));
-                        break;
-                    }
-                }
-            }
-            if let Some(msg) = bad_entry {
-                Err(create_validation_error("vector", &msg))
-            } else {
-                do_vector_search(
-                    &state,
-                    &collection_name,
-                    query_vector,
-                    limit,
-                    threshold,
-                    tenant_ctx.as_ref(),
-                )
-                .await
-            }
-        } else if let Some(query) = entry.get("query").and_then(|q| q.as_str()) {
-            match state.embedding_manager.embed(query) {
-                Ok(embedding) => {
-                    do_vector_search(
-                        &state,
-                        &collection_name,
-                        embedding,
-                        limit,
-                        threshold,
-                        tenant_ctx.as_ref(),
-                    )
-                    .await
-                }
-                Err(e) => Err(create_bad_request_error(&format!(
-                    "Failed to embed query: {}",
-                    e
-                ))),
-            }
-        } else {
-            Err(create_validation_error(
-                "queries",
-                &format!("entry[{}] missing both `query` and `vector`", idx),
-            ))
-        };
-
-        match outcome {
-            Ok(mut body) => {
-                succeeded += 1;
-                let hits = body
-                    .get("results")
-                    .and_then(|r| r.as_array())
-                    .map(|a| a.len())
-                    .unwrap_or(0);
-                if let Some(obj) = body.as_object_mut() {
-                    obj.insert("index".to_string(), json!(idx));
-                    obj.insert("status".to_string(), json!("ok"));
-                    obj.insert("total_results".to_string(), json!(hits));
-                    obj.insert(
-                        "query".to_string(),
-                        entry.get("query").cloned().unwrap_or(Value::Null),
-                    );
-                }
-                results.push(body);
-            }
-            Err(e) => {
-                failed += 1;
-                results.push(json!({
-                    "index": idx,
-                    "status": "error",
-                    "error": e.message.clone(),
-                    "error_type": e.error_type.clone(),
-                    "query": entry.get("query").cloned().unwrap_or(Value::Null),
-                }));
-            }
-        }
-    }
-
-    Ok(Json(json!({
-        "collection": collection_name,
-        "count": queries.len(),
-        "succeeded": succeeded,
-        "failed": failed,
-        "results": results,
-    })))
-}
-
 /// POST /batch_update — update a vector's payload (and optionally its
 /// dense data) in bulk.
 ///
-/// Request: `{collection, updates: [{id, vector?, payload?}]}`. Each
-/// entry either replaces the stored vector's `data` or its `payload`.
+/// Request: `{collection, updates: [{id, vector?, payload?,
+/// payload_patch?}]}`. Each entry replaces the stored vector's `data`,
+/// and replaces its `payload` or applies `payload_patch` to it as a JSON
+/// merge patch (RFC 7396).
 /// Per-entry failures are captured without aborting the batch. The
 /// collection's query cache is invalidated once after the batch.
 pub async fn batch_update_vectors(
@@ -831,21 +794,47 @@ pub async fn batch_update_vectors(
             None => existing.data.clone(),
         };
 
-        let new_payload = match entry.get("payload") {
-            Some(p) if !p.is_null() => Some(Payload::new(p.clone())),
-            Some(_) => None,
-            None => existing.payload.clone(),
-        };
-
-        let updated_vector = Vector {
-            id: id.clone(),
-            data: new_data,
-            sparse: existing.sparse.clone(),
-            payload: new_payload,
-            document_id: existing.document_id.clone(),
+        let outcome = match (entry.get("payload"), entry.get("payload_patch")) {
+            (Some(_), Some(_)) => {
+                failed += 1;
+                results.push(json!({
+                    "index": idx,
+                    "id": id,
+                    "status": "error",
+                    "error": "`payload` and `payload_patch` are mutually exclusive",
+                }));
+                continue;
+            }
+            // Read, merged and written under the store's patch lock
+            (None, Some(patch)) => state
+                .store
+                .patch_payload(&collection_name, &id, patch, |vector| {
+                    if entry.get("vector").is_some() {
+                        vector.data = new_data;
+                    }
+                    Ok::<_, vectorizer::error::VectorizerError>(())
+                })
+                .map(|_| ()),
+            (payload, None) => {
+                let new_payload = match payload {
+                    Some(p) if !p.is_null() => Some(Payload::new(p.clone())),
+                    Some(_) => None,
+                    None => existing.payload.clone(),
+                };
+                state.store.update(
+                    &collection_name,
+                    Vector {
+                        id: id.clone(),
+                        data: new_data,
+                        sparse: existing.sparse.clone(),
+                        payload: new_payload,
+                        document_id: existing.document_id.clone(),
+                    },
+                )
+            }
         };
 
-        match state.store.update(&collection_name, updated_vector) {
+        match outcome {
             Ok(()) => {
                 updated += 1;
                 results.push(json!({
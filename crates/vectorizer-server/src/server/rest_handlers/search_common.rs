@@ -0,0 +1,191 @@
+//! Request parsing, query caching, response shaping and metrics shared by
+//! the search handlers in [`super::search`],
+//! [`super::search_features::batch_search`] and
+//! [`super::search_features::search_templates`].
+
+use serde_json::{Value, json};
+use vectorizer::cache::query_cache::{QueryCache, QueryKey};
+use vectorizer::db::{SearchDeadline, SearchOutcome, SearchStats};
+
+use crate::server::error_middleware::{ErrorResponse, create_validation_error};
+
+/// Server-side ceiling for the `limit` field on every search shape.
+///
+/// The OpenAPI/tool schemas document `maximum: 100`, but schemas don't
+/// execute — before phase40 a client could send `limit: 100000000` and
+/// drive result allocation directly (memory-DoS vector, analysis §2).
+/// Clamping (rather than rejecting) matches how the capped `list`
+/// endpoints already behave.
+pub(crate) const MAX_SEARCH_LIMIT: usize = 100;
+
+/// Parse `payload.limit`, defaulting to `default` and clamping to
+/// [`MAX_SEARCH_LIMIT`].
+pub(crate) fn clamped_limit(payload: &Value, default: usize) -> usize {
+    (payload
+        .get("limit")
+        .and_then(|l| l.as_u64())
+        .unwrap_or(default as u64) as usize)
+        .min(MAX_SEARCH_LIMIT)
+}
+
+/// `total_results` of a cached search response.
+pub(super) fn cached_total_results(response: &Value) -> usize {
+    response
+        .get("total_results")
+        .and_then(|t| t.as_u64())
+        .unwrap_or(0) as usize
+}
+
+/// The query cache as seen by searches that started together: results
+/// are cached against the generation taken before they ran, so a write
+/// landing mid-search keeps them out. Exact searches and timed-out ones
+/// neither read nor fill the cache.
+pub(super) struct CacheGeneration<'a> {
+    cache: &'a QueryCache<Value>,
+    generation: u64,
+}
+
+impl<'a> CacheGeneration<'a> {
+    /// Take the current generation of `cache`.
+    pub(super) fn begin(cache: &'a QueryCache<Value>) -> Self {
+        Self {
+            cache,
+            generation: cache.generation(),
+        }
+    }
+
+    /// Cached response for `key`, unless the search is exact.
+    pub(super) fn lookup(&self, key: Option<&QueryKey>, exact: bool) -> Option<Value> {
+        key.filter(|_| !exact).and_then(|key| self.cache.get(key))
+    }
+
+    /// Cache the `response` of a search that ran since [`Self::begin`].
+    pub(super) fn store(
+        &self,
+        key: Option<QueryKey>,
+        exact: bool,
+        timed_out: bool,
+        response: &Value,
+    ) {
+        if let Some(key) = key.filter(|_| !exact && !timed_out) {
+            self.cache.insert_at(key, response.clone(), self.generation);
+        }
+    }
+}
+
+/// 400 for a query vector whose length isn't the collection's dimension.
+pub(super) fn dimension_mismatch(got: usize, expected: usize) -> ErrorResponse {
+    create_validation_error(
+        "vector",
+        &format!(
+            "vector dimension {} does not match collection dimension {}",
+            got, expected
+        ),
+    )
+}
+
+/// Response body of a raw-vector search: hits under `threshold` are
+/// dropped.
+pub(super) fn vector_search_response(
+    collection_name: &str,
+    outcome: SearchOutcome,
+    limit: usize,
+    threshold: Option<f64>,
+    exact: bool,
+) -> Value {
+    let results: Vec<Value> = outcome
+        .results
+        .into_iter()
+        .filter(|r| threshold.is_none_or(|t| r.score as f64 >= t))
+        .map(|result| {
+            json!({
+                "id": result.id,
+                "score": result.score,
+                "vector": result.vector,
+                "payload": result.payload.map(|p| p.data)
+            })
+        })
+        .collect();
+
+    json!({
+        "results": results,
+        "query_type": "vector",
+        "limit": limit,
+        "exact": exact,
+        "timed_out": outcome.timed_out,
+        "collection": collection_name,
+        "total_results": results.len(),
+    })
+}
+
+/// Count a finished raw-vector search in the search metrics.
+pub(super) fn record_vector_search(
+    collection_name: &str,
+    timed_out: bool,
+    stats: Option<SearchStats>,
+    response: &Value,
+) {
+    use vectorizer::monitoring::metrics::METRICS;
+
+    METRICS
+        .search_requests_total
+        .with_label_values(&[collection_name, "vector", search_status_label(timed_out)])
+        .inc();
+    METRICS
+        .search_results_count
+        .with_label_values(&[collection_name, "vector"])
+        .observe(response["total_results"].as_u64().unwrap_or(0) as f64);
+    record_search_stats(collection_name, stats);
+}
+
+/// Record the beam width and visited-node count of an HNSW search, so a
+/// collection whose searches quietly explore less of the graph (and so
+/// lose recall) shows up before users notice. Searches without a graph
+/// walk record nothing.
+pub(super) fn record_search_stats(collection_name: &str, stats: Option<SearchStats>) {
+    use vectorizer::monitoring::metrics::METRICS;
+
+    let Some(stats) = stats else {
+        return;
+    };
+    METRICS
+        .search_ef_search
+        .with_label_values(&[collection_name])
+        .observe(stats.ef_search as f64);
+    METRICS
+        .search_candidates_visited
+        .with_label_values(&[collection_name])
+        .observe(stats.candidates_visited as f64);
+}
+
+/// `exact: true` on a search body requests a brute-force scan instead of
+/// the HNSW graph. Anything but a JSON `true` means approximate.
+pub(super) fn exact_flag(payload: &Value) -> bool {
+    payload
+        .get("exact")
+        .and_then(|e| e.as_bool())
+        .unwrap_or(false)
+}
+
+/// `timeout_ms` on a search body bounds the time the request may spend,
+/// counted from when the body is parsed (so query embedding counts
+/// too). Past it the search stops and returns the best results found so
+/// far with `timed_out: true`. Absent or `null` means no limit; anything
+/// but a positive integer is a 400.
+pub(crate) fn search_deadline(payload: &Value) -> Result<SearchDeadline, ErrorResponse> {
+    match payload.get("timeout_ms") {
+        None | Some(Value::Null) => Ok(SearchDeadline::NONE),
+        Some(value) => value
+            .as_u64()
+            .filter(|ms| *ms > 0)
+            .map(|ms| SearchDeadline::from_timeout_ms(Some(ms)))
+            .ok_or_else(|| {
+                create_validation_error("timeout_ms", "must be a positive integer (milliseconds)")
+            }),
+    }
+}
+
+/// `search_requests_total` status label for a search that returned.
+pub(super) fn search_status_label(timed_out: bool) -> &'static str {
+    if timed_out { "timeout" } else { "success" }
+}
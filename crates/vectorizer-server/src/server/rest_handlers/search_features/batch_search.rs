@@ -0,0 +1,345 @@
+//! `POST /batch_search` — many searches against one collection in one
+//! request.
+//!
+//! Entries are parsed first, text queries are embedded with one
+//! `embed_batch` call, and entries the query cache can answer are taken
+//! from it. The remaining searches go to
+//! [`CollectionType::search_batch_until`] together, which runs them in
+//! parallel over one snapshot of the collection's HNSW graph instead of
+//! one after the other. `/search/text/batch` (see
+//! [`super::search_text_batch`]) runs its entries through the same
+//! pipeline.
+//!
+//! [`CollectionType::search_batch_until`]: vectorizer::db::CollectionType::search_batch_until
+
+use axum::Extension;
+use axum::extract::State;
+use axum::response::Json;
+use serde_json::{Value, json};
+use tracing::info;
+use vectorizer::cache::query_cache::QueryKey;
+use vectorizer::db::{BatchQuery, SearchDeadline, SearchOutcome};
+use vectorizer::hub::middleware::RequestTenantContext;
+use vectorizer::monitoring::metrics::METRICS;
+
+use super::common::{extract_tenant_id, f32_array};
+use super::search_common::{
+    CacheGeneration, clamped_limit, dimension_mismatch, exact_flag, record_vector_search,
+    search_deadline, vector_search_response,
+};
+use crate::server::VectorizerServer;
+use crate::server::error_middleware::{
+    ErrorResponse, create_bad_request_error, create_validation_error,
+};
+
+/// Where an entry's query vector comes from.
+enum QueryInput<'a> {
+    Vector(Vec<f32>),
+    Text(&'a str),
+}
+
+/// A `queries[]` entry that parsed.
+struct ParsedEntry<'a> {
+    index: usize,
+    input: QueryInput<'a>,
+    limit: usize,
+    threshold: Option<f64>,
+    exact: bool,
+    deadline: SearchDeadline,
+}
+
+/// POST /batch_search — run multiple searches against one collection.
+///
+/// Request: `{collection, timeout_ms?, queries: [{query?, vector?, limit?,
+/// threshold?, exact?, timeout_ms?}]}`
+/// Each query may carry either a text `query` (embedded server-side via
+/// the active `EmbeddingManager`) or a raw `vector` (validated against
+/// the collection dimension). Per-query failures are captured in the
+/// response without aborting the batch. The batch-level `timeout_ms`
+/// bounds the whole batch and an entry's own `timeout_ms` bounds that
+/// entry; once a deadline passes, the remaining searches return what
+/// they have (possibly nothing) with `timed_out: true`.
+///
+/// Response: `{collection, count, succeeded, failed, timed_out, results:
+/// [{index, query?, vector?, status: "ok"|"error", results?,
+/// total_results?, timed_out?, error?}]}`.
+pub async fn batch_search_vectors(
+    State(state): State<VectorizerServer>,
+    tenant_ctx: Option<Extension<RequestTenantContext>>,
+    Json(payload): Json<Value>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let collection_name = payload
+        .get("collection")
+        .and_then(|c| c.as_str())
+        .ok_or_else(|| {
+            create_validation_error("collection", "missing or invalid collection parameter")
+        })?
+        .to_string();
+
+    let queries = payload
+        .get("queries")
+        .and_then(|q| q.as_array())
+        .ok_or_else(|| {
+            create_validation_error("queries", "missing or invalid queries parameter")
+        })?;
+
+    if queries.is_empty() {
+        return Err(create_validation_error(
+            "queries",
+            "queries array must contain at least one entry",
+        ));
+    }
+    let batch_deadline = search_deadline(&payload)?;
+
+    Ok(Json(run_batch(
+        &state,
+        &collection_name,
+        queries,
+        batch_deadline,
+        tenant_ctx.as_ref(),
+    )))
+}
+
+/// Search every entry of `queries` against `collection_name` and build
+/// the batch response. Per-entry failures land in that entry's result.
+pub(super) fn run_batch(
+    state: &VectorizerServer,
+    collection_name: &str,
+    queries: &[Value],
+    batch_deadline: SearchDeadline,
+    tenant_ctx: Option<&Extension<RequestTenantContext>>,
+) -> Value {
+    info!(
+        "Batch searching {} queries against '{}'",
+        queries.len(),
+        collection_name
+    );
+
+    let mut outcomes: Vec<Option<Result<Value, ErrorResponse>>> = vec![None; queries.len()];
+    let mut parsed = Vec::with_capacity(queries.len());
+    for (idx, entry) in queries.iter().enumerate() {
+        match parse_entry(idx, entry, batch_deadline) {
+            Ok(entry) => parsed.push(entry),
+            Err(e) => outcomes[idx] = Some(Err(e)),
+        }
+    }
+
+    let vectors = query_vectors(state, &parsed);
+    // The cache is only consulted once the caller is known to own the
+    // collection; the metric decides how query vectors are normalized
+    let tenant_id = extract_tenant_id(&tenant_ctx.cloned());
+    let metric = state
+        .store
+        .get_collection_with_owner(collection_name, tenant_id.as_ref())
+        .ok()
+        .map(|collection| collection.config().metric);
+    let cache = CacheGeneration::begin(&state.query_cache);
+    let mut pending = Vec::with_capacity(parsed.len());
+    let mut batch = Vec::with_capacity(parsed.len());
+    for (entry, vector) in parsed.into_iter().zip(vectors) {
+        let vector = match vector {
+            Ok(vector) => vector,
+            Err(e) => {
+                outcomes[entry.index] = Some(Err(e));
+                continue;
+            }
+        };
+        let cache_key = metric.map(|metric| {
+            QueryKey::from_query_vector(
+                collection_name.to_string(),
+                &vector,
+                metric,
+                entry.limit,
+                entry.threshold,
+            )
+        });
+        if let Some(cached) = cache.lookup(cache_key.as_ref(), entry.exact) {
+            outcomes[entry.index] = Some(Ok(cached));
+            continue;
+        }
+        batch.push(BatchQuery {
+            vector,
+            limit: entry.limit,
+            exact: entry.exact,
+            deadline: entry.deadline,
+        });
+        pending.push((entry, cache_key));
+    }
+
+    let searched = search_batch(state, collection_name, &batch, tenant_ctx);
+    for ((entry, cache_key), searched) in pending.into_iter().zip(searched) {
+        outcomes[entry.index] = Some(searched.map(|outcome| {
+            let timed_out = outcome.timed_out;
+            let stats = outcome.stats;
+            let response = vector_search_response(
+                collection_name,
+                outcome,
+                entry.limit,
+                entry.threshold,
+                entry.exact,
+            );
+            cache.store(cache_key, entry.exact, timed_out, &response);
+            record_vector_search(collection_name, timed_out, stats, &response);
+            response
+        }));
+    }
+
+    let mut succeeded: usize = 0;
+    let mut failed: usize = 0;
+    let mut any_timed_out = false;
+    let mut results: Vec<Value> = Vec::with_capacity(queries.len());
+
+    for (idx, (entry, outcome)) in queries.iter().zip(outcomes).enumerate() {
+        let outcome =
+            outcome.unwrap_or_else(|| Err(create_bad_request_error("query was not searched")));
+        match outcome {
+            Ok(mut body) => {
+                succeeded += 1;
+                any_timed_out |= body["timed_out"].as_bool().unwrap_or(false);
+                let hits = body
+                    .get("results")
+                    .and_then(|r| r.as_array())
+                    .map(|a| a.len())
+                    .unwrap_or(0);
+                if let Some(obj) = body.as_object_mut() {
+                    obj.insert("index".to_string(), json!(idx));
+                    obj.insert("status".to_string(), json!("ok"));
+                    obj.insert("total_results".to_string(), json!(hits));
+                    obj.insert(
+                        "query".to_string(),
+                        entry.get("query").cloned().unwrap_or(Value::Null),
+                    );
+                }
+                results.push(body);
+            }
+            Err(e) => {
+                failed += 1;
+                results.push(json!({
+                    "index": idx,
+                    "status": "error",
+                    "error": e.message.clone(),
+                    "error_type": e.error_type.clone(),
+                    "query": entry.get("query").cloned().unwrap_or(Value::Null),
+                }));
+            }
+        }
+    }
+
+    json!({
+        "collection": collection_name,
+        "count": queries.len(),
+        "succeeded": succeeded,
+        "failed": failed,
+        "timed_out": any_timed_out,
+        "results": results,
+    })
+}
+
+/// Parse `queries[index]`, or return the error that fails that entry.
+fn parse_entry(
+    index: usize,
+    entry: &Value,
+    batch_deadline: SearchDeadline,
+) -> Result<ParsedEntry<'_>, ErrorResponse> {
+    let deadline = search_deadline(entry)?.earlier(batch_deadline);
+    let input = if let Some(vec_arr) = entry.get("vector").and_then(|v| v.as_array()) {
+        QueryInput::Vector(f32_array(vec_arr, "vector")?)
+    } else if let Some(query) = entry.get("query").and_then(|q| q.as_str()) {
+        QueryInput::Text(query)
+    } else {
+        return Err(create_validation_error(
+            "queries",
+            &format!("entry[{}] missing both `query` and `vector`", index),
+        ));
+    };
+    Ok(ParsedEntry {
+        index,
+        input,
+        limit: clamped_limit(entry, 10),
+        threshold: entry.get("threshold").and_then(|v| v.as_f64()),
+        exact: exact_flag(entry),
+        deadline,
+    })
+}
+
+/// Query vector of every parsed entry, embedding the text queries in one
+/// `embed_batch` call. If that call fails, each text is embedded on its
+/// own so a bad query only fails its own entry.
+fn query_vectors(
+    state: &VectorizerServer,
+    parsed: &[ParsedEntry<'_>],
+) -> Vec<Result<Vec<f32>, ErrorResponse>> {
+    let embed_error = |e: vectorizer::error::VectorizerError| {
+        create_bad_request_error(&format!("Failed to embed query: {}", e))
+    };
+    let texts: Vec<&str> = parsed
+        .iter()
+        .filter_map(|entry| match entry.input {
+            QueryInput::Text(text) => Some(text),
+            QueryInput::Vector(_) => None,
+        })
+        .collect();
+    let embedded: Vec<Result<Vec<f32>, ErrorResponse>> = if texts.is_empty() {
+        Vec::new()
+    } else {
+        match state.embedding_manager.embed_batch(&texts) {
+            Ok(vectors) if vectors.len() == texts.len() => vectors.into_iter().map(Ok).collect(),
+            _ => texts
+                .iter()
+                .map(|text| state.embedding_manager.embed(text).map_err(embed_error))
+                .collect(),
+        }
+    };
+
+    let mut embedded = embedded.into_iter();
+    parsed
+        .iter()
+        .map(|entry| match &entry.input {
+            QueryInput::Vector(vector) => Ok(vector.clone()),
+            QueryInput::Text(_) => embedded
+                .next()
+                .unwrap_or_else(|| Err(create_bad_request_error("Failed to embed query"))),
+        })
+        .collect()
+}
+
+/// Run `batch` against the collection, one outcome per query. A missing
+/// collection fails every entry; a query of the wrong dimension fails
+/// only its own.
+fn search_batch(
+    state: &VectorizerServer,
+    collection_name: &str,
+    batch: &[BatchQuery],
+    tenant_ctx: Option<&Extension<RequestTenantContext>>,
+) -> Vec<Result<SearchOutcome, ErrorResponse>> {
+    if batch.is_empty() {
+        return Vec::new();
+    }
+    let tenant_id = extract_tenant_id(&tenant_ctx.cloned());
+    let collection = match state
+        .store
+        .get_collection_with_owner(collection_name, tenant_id.as_ref())
+    {
+        Ok(collection) => collection,
+        Err(e) => return vec![Err(ErrorResponse::from(e)); batch.len()],
+    };
+    let dimension = collection.config().dimension;
+
+    let timer = METRICS
+        .search_latency_seconds
+        .with_label_values(&[collection_name, "batch"])
+        .start_timer();
+    let outcomes = collection.search_batch_until(batch);
+    drop(timer);
+
+    outcomes
+        .into_iter()
+        .zip(batch)
+        .map(|(outcome, query)| {
+            if query.vector.len() != dimension {
+                return Err(dimension_mismatch(query.vector.len(), dimension));
+            }
+            outcome.map_err(|e| create_bad_request_error(&format!("Search failed: {}", e)))
+        })
+        .collect()
+}
@@ -0,0 +1,362 @@
+//! Retrieval experiment REST handlers.
+//!
+//! - `list_experiments`     — GET    /experiments
+//! - `get_experiment`       — GET    /experiments/{name}
+//! - `record_experiment_click` — POST /experiments/{name}/clicks
+//! - `evaluate_experiment`  — POST   /experiments/{name}/evaluate
+//! - `put_experiment`       — PUT    /admin/experiments/{name}
+//! - `delete_experiment`    — DELETE /admin/experiments/{name}
+//!
+//! While an experiment is active, text searches of its collection are
+//! split between the variants by [`run_experiment_search`] and tagged
+//! with `experiment` in the response. Experiments are saved to
+//! `experiments.json` in the data directory. See
+//! [`vectorizer::search::experiments`].
+
+#![allow(missing_docs)]
+
+use std::time::Instant;
+
+use axum::Extension;
+use axum::extract::{Path, State};
+use axum::response::Json;
+use serde_json::{Value, json};
+use tracing::info;
+use vectorizer::auth::middleware::AuthState;
+use vectorizer::db::SearchDeadline;
+use vectorizer::error::VectorizerError;
+use vectorizer::search::experiments::MAX_EVALUATION_QUERIES;
+use vectorizer::search::{Experiment, ExperimentReport, LabelledQuery, Variant};
+
+use crate::server::VectorizerServer;
+use crate::server::core::check_caller_namespace;
+use crate::server::error_middleware::{
+    ErrorResponse, create_bad_request_error, create_not_found_error, create_validation_error,
+};
+
+/// Cut-off of the evaluation metrics by default.
+const DEFAULT_EVALUATION_K: usize = 10;
+
+/// Largest evaluation cut-off.
+const MAX_EVALUATION_K: usize = 100;
+
+fn report_json(name: &str, report: &ExperimentReport) -> Value {
+    let mut value = json!(report);
+    value["name"] = json!(name);
+    value
+}
+
+/// `query` embedded the way `variant` of `experiment` searches: with the
+/// variant's provider when it names one, otherwise with the provider of
+/// the collection it searches.
+pub(crate) fn embed_for_variant(
+    state: &VectorizerServer,
+    experiment: &Experiment,
+    variant: Variant,
+    query: &str,
+) -> vectorizer::error::Result<Vec<f32>> {
+    match &experiment.arm(variant).embedding_provider {
+        Some(provider) => state.embedding_manager.embed_with_provider(provider, query),
+        None => state.embed_for_collection(experiment.collection_of(variant), query),
+    }
+}
+
+/// Run a text search of `collection` through the active experiment
+/// splitting its searches, if any. Returns the response, tagged with the
+/// experiment and variant, or `None` when no experiment applies.
+///
+/// `experiment_key` in `payload` keeps a user or session in one variant.
+/// A variant searching a collection outside the caller's API-key
+/// namespace is refused with 403.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn run_experiment_search(
+    state: &VectorizerServer,
+    collection: &str,
+    query: &str,
+    payload: &Value,
+    limit: usize,
+    tenant: Option<&str>,
+    auth: Option<&AuthState>,
+    deadline: &SearchDeadline,
+) -> Option<Result<Value, ErrorResponse>> {
+    let (name, experiment) = state.experiments.for_collection(collection)?;
+    let started = Instant::now();
+    let key = payload.get("experiment_key").and_then(|k| k.as_str());
+    let variant = experiment.assign(&name, key);
+    if let Err(e) = check_caller_namespace(auth, experiment.collection_of(variant)) {
+        return Some(Err(e));
+    }
+    let threshold = payload
+        .get("threshold")
+        .and_then(|t| t.as_f64())
+        .map(|t| t as f32);
+
+    let run = embed_for_variant(state, &experiment, variant, query)
+        .map_err(|e| create_bad_request_error(&format!("Failed to generate embedding: {}", e)))
+        .and_then(|query_vector| {
+            experiment
+                .search(
+                    variant,
+                    &state.store,
+                    query,
+                    &query_vector,
+                    limit,
+                    threshold,
+                    tenant,
+                    deadline,
+                )
+                .map_err(|e| create_bad_request_error(&format!("Search failed: {}", e)))
+        });
+    let run = match run {
+        Ok(run) => run,
+        Err(e) => return Some(Err(e)),
+    };
+    state
+        .experiments
+        .record_search(&name, variant, started.elapsed(), run.hits.len());
+
+    Some(Ok(json!({
+        "results": run.hits,
+        "query": query,
+        "limit": limit,
+        "timed_out": run.timed_out,
+        "collection": collection,
+        "total_results": run.hits.len(),
+        "experiment": {
+            "name": name,
+            "variant": variant,
+            "collection": experiment.collection_of(variant),
+        },
+    })))
+}
+
+/// GET /experiments
+pub async fn list_experiments(State(state): State<VectorizerServer>) -> Json<Value> {
+    let experiments: Vec<Value> = state
+        .experiments
+        .list()
+        .iter()
+        .filter_map(|(name, _)| {
+            state
+                .experiments
+                .report(name)
+                .map(|report| report_json(name, &report))
+        })
+        .collect();
+    Json(json!({
+        "experiments": experiments,
+        "total": experiments.len(),
+    }))
+}
+
+/// GET /experiments/{name}
+///
+/// The experiment with the searches, traffic share, zero-result rate,
+/// mean latency and clicks of each variant.
+pub async fn get_experiment(
+    State(state): State<VectorizerServer>,
+    Path(name): Path<String>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let report = state
+        .experiments
+        .report(&name)
+        .ok_or_else(|| create_not_found_error("experiment", &name))?;
+    Ok(Json(report_json(&name, &report)))
+}
+
+/// PUT /admin/experiments/{name}
+///
+/// Body: the experiment, e.g. `{"collection": "docs",
+/// "treatment_percent": 20, "treatment": {"hybrid": {"alpha": 0.5}}}`.
+/// Replaces an experiment with the same name and restarts its counters.
+/// Every collection a variant searches must exist, and a variant's
+/// `embedding_provider` must produce vectors of that collection's
+/// dimension.
+pub async fn put_experiment(
+    State(state): State<VectorizerServer>,
+    Path(name): Path<String>,
+    Json(payload): Json<Value>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let experiment: Experiment = serde_json::from_value(payload)
+        .map_err(|e| create_validation_error("experiment", &e.to_string()))?;
+    for variant in Variant::ALL {
+        let collection = experiment.collection_of(variant);
+        let dimension = state
+            .store
+            .get_collection(collection)
+            .map_err(ErrorResponse::from)?
+            .config()
+            .dimension;
+        if let Some(provider) = &experiment.arm(variant).embedding_provider {
+            let provided = state
+                .embedding_manager
+                .get_provider_dimension(provider)
+                .map_err(|e| {
+                    create_validation_error("embedding_provider", &format!("{}: {}", variant, e))
+                })?;
+            if provided != dimension {
+                return Err(create_validation_error(
+                    "embedding_provider",
+                    &format!(
+                        "{}: provider '{}' embeds {} dimensions but '{}' has {}",
+                        variant, provider, provided, collection, dimension
+                    ),
+                ));
+            }
+        }
+    }
+
+    let replaced = state
+        .experiments
+        .put(&name, experiment.clone())
+        .map_err(ErrorResponse::from)?;
+    info!(
+        "Experiment '{}' {} on '{}' ({}% treatment{})",
+        name,
+        if replaced { "replaced" } else { "created" },
+        experiment.collection,
+        experiment.treatment_percent,
+        if experiment.active { "" } else { ", stopped" }
+    );
+    let report = state
+        .experiments
+        .report(&name)
+        .ok_or_else(|| create_not_found_error("experiment", &name))?;
+    Ok(Json(json!({
+        "experiment": report_json(&name, &report),
+        "replaced": replaced,
+    })))
+}
+
+/// DELETE /admin/experiments/{name}
+pub async fn delete_experiment(
+    State(state): State<VectorizerServer>,
+    Path(name): Path<String>,
+) -> Result<Json<Value>, ErrorResponse> {
+    if !state
+        .experiments
+        .remove(&name)
+        .map_err(ErrorResponse::from)?
+    {
+        return Err(create_not_found_error("experiment", &name));
+    }
+    info!("Experiment '{}' removed", name);
+    Ok(Json(json!({ "removed": name })))
+}
+
+/// POST /experiments/{name}/clicks
+///
+/// Body: `{"variant": "treatment", "rank": 1}` — the user opened the
+/// result at 1-based `rank` of a search tagged with that variant.
+pub async fn record_experiment_click(
+    State(state): State<VectorizerServer>,
+    Path(name): Path<String>,
+    Json(payload): Json<Value>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let variant: Variant = payload
+        .get("variant")
+        .cloned()
+        .and_then(|v| serde_json::from_value(v).ok())
+        .ok_or_else(|| {
+            create_validation_error("variant", "variant must be \"control\" or \"treatment\"")
+        })?;
+    let rank = payload
+        .get("rank")
+        .and_then(|r| r.as_u64())
+        .filter(|r| *r >= 1)
+        .ok_or_else(|| create_validation_error("rank", "rank must be a positive integer"))?;
+
+    if !state
+        .experiments
+        .record_click(&name, variant, rank as usize)
+    {
+        return Err(create_not_found_error("experiment", &name));
+    }
+    Ok(Json(json!({
+        "experiment": name,
+        "variant": variant,
+        "rank": rank,
+    })))
+}
+
+/// POST /experiments/{name}/evaluate
+///
+/// Body: `{"k": 10, "queries": [{"query": "reset password",
+/// "relevant": ["doc-42"]}]}`.
+///
+/// Runs both variants on the labelled queries and scores them with the
+/// evaluation metrics (MAP, MRR, nDCG@k, precision@k, recall@k), with
+/// `delta` the treatment minus the control. Queries without a relevant
+/// result are skipped.
+pub async fn evaluate_experiment(
+    State(state): State<VectorizerServer>,
+    Path(name): Path<String>,
+    auth: Option<Extension<AuthState>>,
+    Json(payload): Json<Value>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let experiment = state
+        .experiments
+        .get(&name)
+        .ok_or_else(|| create_not_found_error("experiment", &name))?;
+    for variant in Variant::ALL {
+        check_caller_namespace(auth.as_deref(), experiment.collection_of(variant))?;
+    }
+    let queries: Vec<LabelledQuery> = payload
+        .get("queries")
+        .cloned()
+        .ok_or_else(|| create_validation_error("queries", "missing queries"))
+        .and_then(|queries| {
+            serde_json::from_value(queries)
+                .map_err(|e| create_validation_error("queries", &e.to_string()))
+        })?;
+    if queries.len() > MAX_EVALUATION_QUERIES {
+        return Err(create_validation_error(
+            "queries",
+            &format!("at most {} queries per evaluation", MAX_EVALUATION_QUERIES),
+        ));
+    }
+    let k = payload
+        .get("k")
+        .and_then(|v| v.as_u64())
+        .map_or(DEFAULT_EVALUATION_K, |k| k as usize);
+    if !(1..=MAX_EVALUATION_K).contains(&k) {
+        return Err(create_validation_error(
+            "k",
+            &format!("k must be between 1 and {}", MAX_EVALUATION_K),
+        ));
+    }
+
+    let server = state.clone();
+    let evaluated = tokio::task::spawn_blocking(move || {
+        Variant::ALL
+            .iter()
+            .map(|&variant| {
+                let embed = |query: &str| embed_for_variant(&server, &experiment, variant, query);
+                experiment.evaluate(variant, &server.store, &queries, k, &embed)
+            })
+            .collect::<Result<Vec<_>, VectorizerError>>()
+    })
+    .await
+    .map_err(|e| create_bad_request_error(&format!("Evaluation failed: {}", e)))?
+    .map_err(ErrorResponse::from)?;
+
+    let (control, treatment) = (&evaluated[0], &evaluated[1]);
+    info!(
+        "Evaluated experiment '{}' on {} queries: nDCG@{} control {:.3}, treatment {:.3}",
+        name, control.queries, k, control.ndcg, treatment.ndcg
+    );
+    Ok(Json(json!({
+        "experiment": name,
+        "k": k,
+        "control": control,
+        "treatment": treatment,
+        "delta": {
+            "mean_average_precision": treatment.mean_average_precision
+                - control.mean_average_precision,
+            "mean_reciprocal_rank": treatment.mean_reciprocal_rank - control.mean_reciprocal_rank,
+            "ndcg": treatment.ndcg - control.ndcg,
+            "precision": treatment.precision - control.precision,
+            "recall": treatment.recall - control.recall,
+        },
+    })))
+}
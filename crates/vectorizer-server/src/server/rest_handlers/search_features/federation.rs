@@ -0,0 +1,264 @@
+//! Federated source REST handlers.
+//!
+//! - `list_federated_sources`     — GET    /federation/sources
+//! - `register_federated_source`  — POST   /admin/federation/sources
+//! - `remove_federated_source`    — DELETE /admin/federation/sources/{name}
+//!
+//! Sources registered here live until restart; persistent ones belong in
+//! `federation.sources` in config.yml. The search fan-out itself, used
+//! by [`super::super::intelligent_search`] when a multi-collection search names
+//! `sources`, lives here too.
+
+#![allow(missing_docs)]
+
+use std::collections::HashMap;
+use std::time::Instant;
+
+use axum::extract::{Path, State};
+use axum::response::Json;
+use serde_json::{Value, json};
+use tracing::{debug, error, info};
+use vectorizer::config::FederatedSourceConfig;
+use vectorizer::intelligent_search::rest_api::{MultiCollectionSearchRequest, RESTAPIHandler};
+use vectorizer::search::federation::{
+    self, FederatedQuery, LOCAL_SOURCE, ScoreNormalization, SourceHits, SourceReport, SourceStatus,
+};
+
+use crate::server::VectorizerServer;
+use crate::server::error_middleware::{
+    ErrorResponse, create_bad_request_error, create_not_found_error, create_validation_error,
+};
+
+/// A source as reported over the API. The API key is never echoed back.
+fn source_json(state: &VectorizerServer, source: &FederatedSourceConfig) -> Value {
+    json!({
+        "name": source.name,
+        "url": source.url,
+        "weight": source.weight,
+        "timeout_ms": state.federation.timeout_for(source).as_millis() as u64,
+        "collections": source.collections,
+        "has_api_key": source.api_key.is_some(),
+    })
+}
+
+/// GET /federation/sources
+///
+/// Lists the remote instances `POST /multi_collection_search` can fan
+/// out to via `"sources"`.
+pub async fn list_federated_sources(State(state): State<VectorizerServer>) -> Json<Value> {
+    let sources: Vec<Value> = state
+        .federation
+        .list()
+        .iter()
+        .map(|s| source_json(&state, s))
+        .collect();
+    Json(json!({
+        "sources": sources,
+        "total": sources.len(),
+    }))
+}
+
+/// POST /admin/federation/sources
+///
+/// Body: a `federation.sources` entry, e.g.
+/// `{"name": "team-a", "url": "http://team-a:15002", "weight": 0.8}`.
+/// Replaces a source with the same name.
+pub async fn register_federated_source(
+    State(state): State<VectorizerServer>,
+    Json(payload): Json<Value>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let source: FederatedSourceConfig = serde_json::from_value(payload)
+        .map_err(|e| create_validation_error("source", &e.to_string()))?;
+    let replaced = state
+        .federation
+        .register(source.clone())
+        .map_err(|e| create_bad_request_error(&e.to_string()))?;
+    info!(
+        "Federated source '{}' {} ({})",
+        source.name,
+        if replaced { "replaced" } else { "registered" },
+        source.url
+    );
+    Ok(Json(json!({
+        "source": source_json(&state, &source),
+        "replaced": replaced,
+    })))
+}
+
+/// DELETE /admin/federation/sources/{name}
+pub async fn remove_federated_source(
+    State(state): State<VectorizerServer>,
+    Path(name): Path<String>,
+) -> Result<Json<Value>, ErrorResponse> {
+    if !state.federation.remove(&name) {
+        return Err(create_not_found_error("federated_source", &name));
+    }
+    info!("Federated source '{}' removed", name);
+    Ok(Json(json!({ "removed": name })))
+}
+
+/// The `sources` fan-out of `POST /search/multi-collection`: runs the
+/// search on the named federated sources and, unless `include_local` is
+/// off, on the local store at the same time, then merges the hits with
+/// the requested score normalization. A source that fails is reported
+/// under `federation.sources` instead of failing the request.
+pub(crate) async fn federated_multi_collection_search(
+    state: &VectorizerServer,
+    handler: &RESTAPIHandler,
+    request: MultiCollectionSearchRequest,
+    params: FederationParams,
+) -> Result<Json<Value>, ErrorResponse> {
+    if let Some(unknown) = params
+        .sources
+        .iter()
+        .find(|name| state.federation.get(name).is_none())
+    {
+        return Err(create_not_found_error("federated_source", unknown));
+    }
+
+    let federated_query = FederatedQuery {
+        query: request.query.clone(),
+        collections: request.collections.clone(),
+        max_per_collection: request.max_per_collection.unwrap_or(5),
+        cross_collection_reranking: request.cross_collection_reranking.unwrap_or(true),
+    };
+    let max_total_results = request.max_total_results.unwrap_or(20);
+    let local_started = Instant::now();
+    let (local, remote) = tokio::join!(
+        async {
+            if params.include_local {
+                Some(handler.handle_multi_collection_search(request).await)
+            } else {
+                None
+            }
+        },
+        state.federation.search(&params.sources, &federated_query),
+    );
+    let local_latency_ms = local_started.elapsed().as_millis() as u64;
+    let (remote_hits, remote_reports) =
+        remote.map_err(|e| create_bad_request_error(&e.to_string()))?;
+
+    let mut hits = Vec::with_capacity(remote_hits.len() + 1);
+    let mut reports = Vec::with_capacity(remote_reports.len() + 1);
+    let mut response = json!({});
+    match local {
+        Some(Ok(local)) => {
+            reports.push(SourceReport {
+                source: LOCAL_SOURCE.to_string(),
+                status: SourceStatus::Ok,
+                results: local.results.len(),
+                latency_ms: local_latency_ms,
+                error: None,
+            });
+            hits.push(SourceHits {
+                source: LOCAL_SOURCE.to_string(),
+                weight: 1.0,
+                results: local.results.clone(),
+            });
+            response = serde_json::to_value(local).unwrap_or(json!({}));
+        }
+        Some(Err(e)) => {
+            error!("Multi collection search error: {:?}", e);
+            reports.push(SourceReport {
+                source: LOCAL_SOURCE.to_string(),
+                status: SourceStatus::Error,
+                results: 0,
+                latency_ms: local_latency_ms,
+                error: Some(e.error),
+            });
+        }
+        None => {}
+    }
+    reports.extend(remote_reports);
+    hits.extend(remote_hits);
+
+    let merged = federation::merge(hits, params.normalization, max_total_results);
+    debug!(
+        "Federated multi-collection search over {} source(s) returned {} result(s)",
+        reports.len(),
+        merged.len()
+    );
+    response["metadata"]["final_results_count"] = json!(merged.len());
+    response["results"] = json!(merged);
+    response["federation"] = json!({
+        "sources": reports,
+        "score_normalization": params.normalization,
+    });
+    Ok(Json(response))
+}
+
+/// The federation part of a multi-collection search request.
+pub(crate) struct FederationParams {
+    sources: Vec<String>,
+    include_local: bool,
+    normalization: ScoreNormalization,
+}
+
+/// Read `sources`, `include_local` and `score_normalization`. `None` when
+/// the request names no remote source, i.e. a plain local search.
+pub(crate) fn parse_federation(payload: &Value) -> Result<Option<FederationParams>, ErrorResponse> {
+    let Some(sources) = payload.get("sources") else {
+        return Ok(None);
+    };
+    let sources = sources
+        .as_array()
+        .and_then(|arr| {
+            arr.iter()
+                .map(|v| v.as_str().map(str::to_string))
+                .collect::<Option<Vec<_>>>()
+        })
+        .ok_or_else(|| {
+            create_validation_error("sources", "must be an array of federated source names")
+        })?;
+    if sources.is_empty() {
+        return Ok(None);
+    }
+    let normalization = parse_score_normalization(payload)?.unwrap_or_default();
+    let include_local = payload
+        .get("include_local")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(true);
+    Ok(Some(FederationParams {
+        sources,
+        include_local,
+        normalization,
+    }))
+}
+
+/// Read `score_normalization`, which rescales each collection's scores
+/// and, in a federated search, each source's.
+pub(crate) fn parse_score_normalization(
+    payload: &Value,
+) -> Result<Option<ScoreNormalization>, ErrorResponse> {
+    payload
+        .get("score_normalization")
+        .map(|value| {
+            value
+                .as_str()
+                .and_then(ScoreNormalization::parse)
+                .ok_or_else(|| {
+                    create_validation_error(
+                        "score_normalization",
+                        "must be one of: none, min_max, z_score, rrf",
+                    )
+                })
+        })
+        .transpose()
+}
+
+/// Read `collection_weights`, the per-collection multipliers applied to
+/// scores before the collections' results are merged.
+pub(crate) fn parse_collection_weights(
+    payload: &Value,
+) -> Result<Option<HashMap<String, f32>>, ErrorResponse> {
+    payload
+        .get("collection_weights")
+        .map(|w| serde_json::from_value(w.clone()))
+        .transpose()
+        .map_err(|_| {
+            create_validation_error(
+                "collection_weights",
+                "must map collection names to numeric weights",
+            )
+        })
+}
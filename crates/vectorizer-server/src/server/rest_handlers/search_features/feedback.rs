@@ -0,0 +1,136 @@
+//! Relevance feedback REST handlers.
+//!
+//! - `record_feedback` — POST   /collections/{name}/feedback
+//! - `get_feedback`    — GET    /collections/{name}/feedback
+//! - `clear_feedback`  — DELETE /collections/{name}/feedback
+//!
+//! Recorded judgements turn into per-result boosts that the collection's
+//! scoring expression reads as `feedback`.
+
+#![allow(missing_docs)]
+
+use std::collections::HashMap;
+
+use axum::extract::{Path, Query, State};
+use axum::response::Json;
+use serde_json::{Value, json};
+use tracing::info;
+use vectorizer::db::{FeedbackLabel, MAX_FEEDBACK_EVENTS};
+
+use crate::server::VectorizerServer;
+use crate::server::error_middleware::{ErrorResponse, create_validation_error};
+
+/// Judged results listed from each end of the boost ranking by default.
+const DEFAULT_TOP: usize = 10;
+
+/// Recent judgements listed by default.
+const DEFAULT_RECENT: usize = 20;
+
+/// POST /collections/{name}/feedback
+///
+/// Body: `{"query": "reset password", "result_id": "doc-42", "label":
+/// "positive"}` — `label` is `positive` or `negative`.
+///
+/// Returns the result's updated tally and boost.
+pub async fn record_feedback(
+    State(state): State<VectorizerServer>,
+    Path(collection_name): Path<String>,
+    Json(payload): Json<Value>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let text = |key: &str| {
+        payload
+            .get(key)
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+    };
+    let query =
+        text("query").ok_or_else(|| create_validation_error("query", "missing or empty query"))?;
+    let result_id = text("result_id")
+        .ok_or_else(|| create_validation_error("result_id", "missing or empty result_id"))?;
+    let label: FeedbackLabel = payload
+        .get("label")
+        .cloned()
+        .and_then(|v| serde_json::from_value(v).ok())
+        .ok_or_else(|| {
+            create_validation_error("label", "label must be \"positive\" or \"negative\"")
+        })?;
+
+    let votes = state
+        .store
+        .record_feedback(&collection_name, query, result_id, label)
+        .map_err(ErrorResponse::from)?;
+
+    // Persist the feedback with the next save
+    if let Some(ref auto_save) = state.auto_save_manager {
+        auto_save.mark_changed();
+    }
+
+    info!(
+        "Relevance feedback on '{}' result '{}': {:?}",
+        collection_name, result_id, label
+    );
+
+    Ok(Json(json!({
+        "collection": collection_name,
+        "result_id": result_id,
+        "label": label,
+        "positive": votes.positive,
+        "negative": votes.negative,
+        "boost": votes.boost(),
+    })))
+}
+
+/// GET /collections/{name}/feedback?top=10&recent=20
+///
+/// Judgement totals, the `top` highest- and lowest-boosted results and
+/// the `recent` newest judgements.
+pub async fn get_feedback(
+    State(state): State<VectorizerServer>,
+    Path(collection_name): Path<String>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let param = |key: &str, default: usize| {
+        params
+            .get(key)
+            .and_then(|v| v.parse::<usize>().ok())
+            .unwrap_or(default)
+    };
+    let top = param("top", DEFAULT_TOP);
+    let recent = param("recent", DEFAULT_RECENT).min(MAX_FEEDBACK_EVENTS);
+
+    let summary = state
+        .store
+        .feedback_summary(&collection_name, top, recent)
+        .map_err(ErrorResponse::from)?;
+
+    let mut body = json!(summary);
+    body["collection"] = json!(collection_name);
+    Ok(Json(body))
+}
+
+/// DELETE /collections/{name}/feedback
+///
+/// Forgets all feedback on the collection, resetting every boost to 0.
+pub async fn clear_feedback(
+    State(state): State<VectorizerServer>,
+    Path(collection_name): Path<String>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let cleared = state
+        .store
+        .clear_feedback(&collection_name)
+        .map_err(ErrorResponse::from)?;
+
+    if let Some(ref auto_save) = state.auto_save_manager {
+        auto_save.mark_changed();
+    }
+
+    info!(
+        "Cleared relevance feedback on '{}' ({} results)",
+        collection_name, cleared
+    );
+
+    Ok(Json(json!({
+        "collection": collection_name,
+        "cleared_results": cleared,
+    })))
+}
@@ -0,0 +1,96 @@
+//! `full_text_search` — POST /collections/{name}/search/full_text,
+//! BM25 over text-indexed payload fields.
+
+use axum::Extension;
+use axum::extract::{Path, State};
+use axum::response::Json;
+use serde_json::{Value, json};
+use tracing::debug;
+use vectorizer::auth::middleware::AuthState;
+
+use super::search_common::MAX_SEARCH_LIMIT;
+use crate::server::VectorizerServer;
+use crate::server::core::caller_payload_view;
+use crate::server::error_middleware::{ErrorResponse, create_validation_error};
+
+/// POST /collections/{name}/search/full_text
+///
+/// Body: `{"query": "E0425", "fields": ["content"], "limit": 10,
+/// "match_all": false, "highlight": true}`
+///
+/// Lexical-only BM25 search over text-indexed payload fields (`content`
+/// and `text` by default; other fields need a `Text` payload index).
+/// Never touches the embedding provider or the HNSW index, so identifier
+/// lookups that dense embeddings miss are matched exactly.
+///
+/// Fields `api.payload_redaction` rewrites for the caller are left out of
+/// the search, so neither `highlights` nor `matched_terms` come from
+/// them; naming only such fields is a validation error.
+pub async fn full_text_search(
+    State(state): State<VectorizerServer>,
+    Path(collection_name): Path<String>,
+    auth: Option<Extension<AuthState>>,
+    Json(payload): Json<Value>,
+) -> Result<Json<Value>, ErrorResponse> {
+    use vectorizer::search::FullTextQuery;
+
+    let mut query: FullTextQuery = serde_json::from_value(payload)
+        .map_err(|e| create_validation_error("body", &e.to_string()))?;
+    if query.query.trim().is_empty() {
+        return Err(create_validation_error("query", "query must not be empty"));
+    }
+    query.limit = query.limit.min(MAX_SEARCH_LIMIT);
+    let view = caller_payload_view(state.payload_redactor.clone(), auth.as_deref());
+    query.fields = query
+        .effective_fields()
+        .into_iter()
+        .filter(|field| !view.redacts_field(&collection_name, field))
+        .collect();
+    if query.fields.is_empty() {
+        return Err(create_validation_error(
+            "fields",
+            "every field searched is redacted for this caller",
+        ));
+    }
+
+    let store = state.store.clone();
+    let col_name = collection_name.clone();
+    let task_query = query.clone();
+    let hits = tokio::task::spawn_blocking(move || store.full_text_search(&col_name, &task_query))
+        .await
+        .map_err(|e| {
+            ErrorResponse::from(vectorizer::error::VectorizerError::InternalError(format!(
+                "full-text task error: {}",
+                e
+            )))
+        })?
+        .map_err(ErrorResponse::from)?;
+
+    debug!(
+        "full_text_search: collection={}, hits={}",
+        collection_name,
+        hits.len()
+    );
+
+    let results: Vec<Value> = hits
+        .into_iter()
+        .map(|mut h| {
+            view.redact(&collection_name, &mut h.payload);
+            json!({
+                "id": h.id,
+                "score": h.score,
+                "matched_terms": h.matched_terms,
+                "highlights": h.highlights,
+                "payload": h.payload.map(|p| p.data),
+            })
+        })
+        .collect();
+
+    Ok(Json(json!({
+        "collection": collection_name,
+        "query": query.query,
+        "fields": query.effective_fields(),
+        "results": results,
+        "total": results.len(),
+    })))
+}
@@ -0,0 +1,240 @@
+//! Hybrid search weight tuning REST handlers.
+//!
+//! - `record_hybrid_judgments` — POST   /collections/{name}/hybrid_weights/judgments
+//! - `tune_hybrid_weights`     — POST   /collections/{name}/hybrid_weights/tune
+//! - `get_hybrid_weights`      — GET    /collections/{name}/hybrid_weights
+//! - `clear_hybrid_weights`    — DELETE /collections/{name}/hybrid_weights
+//!
+//! A fit runs in the background as the `hybrid_tuning` task; the tuned
+//! weights are reported here and as `hybrid_weights` by
+//! `GET /collections/{name}`, and hybrid searches that set neither
+//! `alpha` nor `algorithm` use them.
+
+#![allow(missing_docs)]
+
+use axum::extract::{Path, State};
+use axum::response::Json;
+use serde_json::{Value, json};
+use tracing::{info, warn};
+use vectorizer::background_tasks::{self, TASKS};
+use vectorizer::db::{FeedbackLabel, HybridJudgment};
+use vectorizer::error::VectorizerError;
+use vectorizer::evaluation::hybrid_tuning::{self, DEFAULT_TUNING_K, MAX_TUNING_K};
+use vectorizer::models::SparseVector;
+
+use crate::server::VectorizerServer;
+use crate::server::error_middleware::{ErrorResponse, create_validation_error};
+
+/// POST /collections/{name}/hybrid_weights/judgments
+///
+/// Body: one judgement, or `{"judgments": [...]}` with several:
+/// `{"query": "reset password", "result_id": "doc-42", "label":
+/// "positive", "query_sparse": {"indices": [3, 17], "values": [0.4,
+/// 0.9]}}` — `label` is `positive` or `negative`; `query_sparse` is the
+/// sparse query the hybrid search was sent with, if any.
+///
+/// Judging a result again for the same query replaces its label.
+/// Nothing is recorded if any judgement is invalid.
+pub async fn record_hybrid_judgments(
+    State(state): State<VectorizerServer>,
+    Path(collection_name): Path<String>,
+    Json(payload): Json<Value>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let judgments = match payload.get("judgments") {
+        Some(list) => list
+            .as_array()
+            .ok_or_else(|| create_validation_error("judgments", "judgments must be an array"))?
+            .iter()
+            .map(parse_judgment)
+            .collect::<Result<Vec<_>, _>>()?,
+        None => vec![parse_judgment(&payload)?],
+    };
+    if judgments.is_empty() {
+        return Err(create_validation_error("judgments", "no judgements given"));
+    }
+    let recorded = judgments.len();
+
+    let total = state
+        .store
+        .record_hybrid_judgments(&collection_name, judgments)
+        .map_err(ErrorResponse::from)?;
+
+    // Persist the judgements with the next save
+    if let Some(ref auto_save) = state.auto_save_manager {
+        auto_save.mark_changed();
+    }
+
+    info!(
+        "Recorded {} hybrid search judgements on '{}' ({} stored)",
+        recorded, collection_name, total
+    );
+
+    Ok(Json(json!({
+        "collection": collection_name,
+        "recorded": recorded,
+        "judgments": total,
+    })))
+}
+
+fn parse_judgment(value: &Value) -> Result<HybridJudgment, ErrorResponse> {
+    let text = |key: &str| {
+        value
+            .get(key)
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+    };
+    let query =
+        text("query").ok_or_else(|| create_validation_error("query", "missing or empty query"))?;
+    let result_id = text("result_id")
+        .ok_or_else(|| create_validation_error("result_id", "missing or empty result_id"))?;
+    let label: FeedbackLabel = value
+        .get("label")
+        .cloned()
+        .and_then(|v| serde_json::from_value(v).ok())
+        .ok_or_else(|| {
+            create_validation_error("label", "label must be \"positive\" or \"negative\"")
+        })?;
+    let query_sparse = match value.get("query_sparse").filter(|v| !v.is_null()) {
+        Some(sparse) => {
+            let sparse: SparseVector = serde_json::from_value(sparse.clone()).map_err(|e| {
+                create_validation_error("query_sparse", &format!("Invalid sparse vector: {}", e))
+            })?;
+            let sparse = SparseVector::new(sparse.indices, sparse.values).map_err(|e| {
+                create_validation_error("query_sparse", &format!("Invalid sparse vector: {}", e))
+            })?;
+            Some(sparse)
+        }
+        None => None,
+    };
+
+    Ok(HybridJudgment {
+        query: query.to_string(),
+        query_sparse,
+        result_id: result_id.to_string(),
+        label,
+        recorded_at: chrono::Utc::now(),
+    })
+}
+
+/// POST /collections/{name}/hybrid_weights/tune
+///
+/// Body (optional): `{"k": 10}` — the nDCG cut-off to fit on.
+///
+/// Starts fitting the collection's fusion weights to its judgements in
+/// the background and returns at once; the result shows up in
+/// `GET /collections/{name}/hybrid_weights`, and a failed run in
+/// `GET /admin/tasks` under `hybrid_tuning`.
+pub async fn tune_hybrid_weights(
+    State(state): State<VectorizerServer>,
+    Path(collection_name): Path<String>,
+    payload: Option<Json<Value>>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let payload = payload.map(|Json(v)| v).unwrap_or_default();
+    let k = payload
+        .get("k")
+        .and_then(|v| v.as_u64())
+        .map_or(DEFAULT_TUNING_K, |k| k as usize);
+    if !(1..=MAX_TUNING_K).contains(&k) {
+        return Err(create_validation_error(
+            "k",
+            &format!("k must be between 1 and {}", MAX_TUNING_K),
+        ));
+    }
+
+    let summary = state
+        .store
+        .hybrid_tuning_summary(&collection_name)
+        .map_err(ErrorResponse::from)?;
+    if summary.positive == 0 {
+        return Err(create_validation_error(
+            "judgments",
+            "the collection has no judgement labelled positive to tune against",
+        ));
+    }
+    if TASKS.register(background_tasks::HYBRID_TUNING).is_paused() {
+        return Err(ErrorResponse::from(VectorizerError::TaskPaused(
+            background_tasks::HYBRID_TUNING.to_string(),
+        )));
+    }
+
+    let server = state.clone();
+    let col_name = collection_name.clone();
+    tokio::spawn(async move {
+        let store = server.store.clone();
+        let embedder = server.clone();
+        let name = col_name.clone();
+        let tuned = tokio::task::spawn_blocking(move || {
+            let embed = |query: &str| embedder.embed_for_collection(&name, query);
+            hybrid_tuning::tune_hybrid_weights(&store, &name, k, &embed)
+        })
+        .await;
+        match tuned {
+            Ok(Ok(_)) => {
+                // Persist the tuned weights with the next save
+                if let Some(ref auto_save) = server.auto_save_manager {
+                    auto_save.mark_changed();
+                }
+            }
+            Ok(Err(e)) => warn!("Hybrid weight tuning of '{}' failed: {}", col_name, e),
+            Err(e) => warn!("Hybrid weight tuning of '{}' panicked: {}", col_name, e),
+        }
+    });
+
+    info!(
+        "Tuning hybrid weights of '{}' on {} judgements (k={})",
+        collection_name, summary.judgments, k
+    );
+
+    Ok(Json(json!({
+        "collection": collection_name,
+        "status": "started",
+        "task": background_tasks::HYBRID_TUNING,
+        "judgments": summary.judgments,
+        "k": k,
+    })))
+}
+
+/// GET /collections/{name}/hybrid_weights
+///
+/// Judgement totals and the tuned weights (`null` before the first fit).
+pub async fn get_hybrid_weights(
+    State(state): State<VectorizerServer>,
+    Path(collection_name): Path<String>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let summary = state
+        .store
+        .hybrid_tuning_summary(&collection_name)
+        .map_err(ErrorResponse::from)?;
+
+    let mut body = json!(summary);
+    body["collection"] = json!(collection_name);
+    Ok(Json(body))
+}
+
+/// DELETE /collections/{name}/hybrid_weights
+///
+/// Forgets the judgements and tuned weights; hybrid searches go back to
+/// the default weights.
+pub async fn clear_hybrid_weights(
+    State(state): State<VectorizerServer>,
+    Path(collection_name): Path<String>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let cleared = state
+        .store
+        .clear_hybrid_tuning(&collection_name)
+        .map_err(ErrorResponse::from)?;
+
+    if let Some(ref auto_save) = state.auto_save_manager {
+        auto_save.mark_changed();
+    }
+
+    info!(
+        "Cleared hybrid weight tuning of '{}' ({} judgements)",
+        collection_name, cleared
+    );
+
+    Ok(Json(json!({
+        "collection": collection_name,
+        "cleared_judgments": cleared,
+    })))
+}
@@ -0,0 +1,56 @@
+//! Search variants and the tooling around search quality: batched and
+//! full-text search, saved templates, federation, query analytics, A/B
+//! experiments, relevance feedback, hybrid weight tuning and recall
+//! profiles.
+//!
+//! - [`batch_search`]      — /batch_search, run as one parallel batch
+//! - [`experiments`]       — /experiments A/B tests of retrieval
+//!                           configurations (reports, clicks, evaluation)
+//! - [`federation`]        — federated search sources (list, register,
+//!                           remove) and the multi-collection `sources`
+//!                           fan-out
+//! - [`feedback`]          — /collections/{name}/feedback relevance feedback
+//! - [`full_text_search`]  — /collections/{name}/search/full_text BM25
+//! - [`hybrid_tuning`]     — /collections/{name}/hybrid_weights judgements
+//!                           and tuned hybrid search weights
+//! - [`query_analytics`]   — /analytics/queries anonymized query statistics
+//!                           and reported clicks
+//! - [`recall_profile`]    — /collections/{name}/recall_profile HNSW recall
+//!                           / latency and the /collections/{name}/benchmark
+//!                           ef_search sweep
+//! - [`search_templates`]  — /search/templates named searches (run, list)
+//!                           and their /admin/search/templates CRUD
+//! - [`search_text_batch`] — /collections/{name}/search/text/batch, embedded
+//!                           in one call and run as one batch
+
+use super::{common, search_common, tenant_partition};
+
+mod batch_search;
+pub(super) mod experiments;
+pub(super) mod federation;
+mod feedback;
+mod full_text_search;
+mod hybrid_tuning;
+pub(super) mod query_analytics;
+mod recall_profile;
+mod search_templates;
+mod search_text_batch;
+
+pub use batch_search::batch_search_vectors;
+pub use experiments::{
+    delete_experiment, evaluate_experiment, get_experiment, list_experiments, put_experiment,
+    record_experiment_click,
+};
+pub use federation::{list_federated_sources, register_federated_source, remove_federated_source};
+pub use feedback::{clear_feedback, get_feedback, record_feedback};
+pub use full_text_search::full_text_search;
+pub use hybrid_tuning::{
+    clear_hybrid_weights, get_hybrid_weights, record_hybrid_judgments, tune_hybrid_weights,
+};
+pub use query_analytics::{get_query_analytics, record_query_clicks};
+pub use recall_profile::{measure_recall_profile, run_recall_benchmark};
+pub use search_templates::{
+    delete_search_template, get_search_template, list_search_templates, put_search_template,
+    run_search_template,
+};
+pub use search_text_batch::search_text_batch;
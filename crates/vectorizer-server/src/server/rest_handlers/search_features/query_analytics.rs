@@ -0,0 +1,140 @@
+//! Query analytics REST handlers.
+//!
+//! - `get_query_analytics` — GET  /analytics/queries
+//! - `record_query_clicks` — POST /analytics/queries/clicks
+//!
+//! Text, hybrid and intelligent searches are recorded through
+//! [`record_query`]. Analytics are on unless `query_analytics.enabled`
+//! is turned off in config.yml.
+
+#![allow(missing_docs)]
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Instant;
+
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::Json;
+use serde_json::{Value, json};
+use vectorizer::monitoring::QueryAnalytics;
+use vectorizer::monitoring::query_analytics::{DEFAULT_TOP_QUERIES, MAX_TOP_QUERIES};
+
+use crate::server::VectorizerServer;
+use crate::server::error_middleware::{
+    ErrorResponse, create_error_response, create_validation_error,
+};
+
+/// Record a `kind` search of `collection` for `query`, started at
+/// `started`, that returned `result_count` results. A no-op when
+/// analytics are disabled.
+pub(crate) fn record_query(
+    state: &VectorizerServer,
+    collection: &str,
+    kind: &str,
+    query: &str,
+    started: Instant,
+    result_count: usize,
+) {
+    if let Some(analytics) = &state.query_analytics {
+        analytics.record(collection, kind, query, started.elapsed(), result_count);
+    }
+}
+
+/// The server's query analytics, or 404 when `query_analytics.enabled`
+/// is off.
+fn query_analytics(state: &VectorizerServer) -> Result<Arc<QueryAnalytics>, ErrorResponse> {
+    state.query_analytics.clone().ok_or_else(|| {
+        create_error_response(
+            "query_analytics_disabled",
+            "query analytics are not enabled; set query_analytics.enabled in config.yml",
+            StatusCode::NOT_FOUND,
+        )
+    })
+}
+
+/// GET /analytics/queries
+///
+/// Query params: `collection` (only its queries; all by default) and
+/// `top` (queries per ranking, default 20).
+///
+/// Totals, the zero-result and click-through rates, latency
+/// percentiles, the most frequent queries and the queries that most
+/// often returned nothing. Queries are identified by the hash of their
+/// normalized text; the text itself is not recorded.
+pub async fn get_query_analytics(
+    State(state): State<VectorizerServer>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let analytics = query_analytics(&state)?;
+    let top = match params.get("top") {
+        Some(top) => top
+            .parse::<usize>()
+            .ok()
+            .filter(|top| (1..=MAX_TOP_QUERIES).contains(top))
+            .ok_or_else(|| {
+                create_validation_error(
+                    "top",
+                    &format!("top must be between 1 and {}", MAX_TOP_QUERIES),
+                )
+            })?,
+        None => DEFAULT_TOP_QUERIES,
+    };
+    let collection = params.get("collection").map(String::as_str);
+
+    let mut body = json!(analytics.report(collection, top));
+    body["collection"] = json!(collection);
+    body["recorded"] = json!(analytics.len());
+    body["capacity"] = json!(analytics.capacity());
+    Ok(Json(body))
+}
+
+/// POST /analytics/queries/clicks
+///
+/// Body: `{"collection": "docs", "query": "reset password",
+/// "result_ids": ["doc-42"]}` (or a single `result_id`) — the results
+/// the user opened after the search. The clicks attach to the latest
+/// recorded search of the collection with the same normalized query;
+/// `matched` is false when there is none.
+pub async fn record_query_clicks(
+    State(state): State<VectorizerServer>,
+    Json(payload): Json<Value>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let analytics = query_analytics(&state)?;
+    let text = |key: &str| {
+        payload
+            .get(key)
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+    };
+    let collection = text("collection")
+        .ok_or_else(|| create_validation_error("collection", "missing or empty collection"))?;
+    let query =
+        text("query").ok_or_else(|| create_validation_error("query", "missing or empty query"))?;
+    let result_ids: Vec<&str> = match (payload.get("result_ids"), text("result_id")) {
+        (Some(ids), _) => ids
+            .as_array()
+            .and_then(|ids| ids.iter().map(|id| id.as_str()).collect::<Option<Vec<_>>>())
+            .ok_or_else(|| {
+                create_validation_error("result_ids", "result_ids must be an array of strings")
+            })?,
+        (None, Some(id)) => vec![id],
+        (None, None) => Vec::new(),
+    };
+    if result_ids.is_empty() {
+        return Err(create_validation_error(
+            "result_ids",
+            "give result_id or a non-empty result_ids",
+        ));
+    }
+
+    let matched = result_ids
+        .iter()
+        .all(|id| analytics.record_click(collection, query, id));
+
+    Ok(Json(json!({
+        "collection": collection,
+        "matched": matched,
+        "clicks": result_ids.len(),
+    })))
+}
@@ -0,0 +1,120 @@
+//! Recall / latency profile and benchmark REST handlers.
+//!
+//! - `measure_recall_profile` — POST /collections/{name}/recall_profile
+//! - `run_recall_benchmark`   — POST /collections/{name}/benchmark
+//!
+//! Reindexing and compaction profile the rebuilt graph on their own; the
+//! latest profile is reported as `recall_profile` by
+//! `GET /collections/{name}`. Benchmarks sweep `ef_search` and are not
+//! stored.
+
+#![allow(missing_docs)]
+
+use axum::extract::{Path, State};
+use axum::response::Json;
+use serde_json::{Value, json};
+use tracing::info;
+use vectorizer::db::{DEFAULT_PROFILE_K, DEFAULT_PROFILE_SAMPLE_SIZE};
+use vectorizer::evaluation::benchmark::{BenchmarkConfig, benchmark_recall};
+
+use crate::server::VectorizerServer;
+use crate::server::error_middleware::{
+    ErrorResponse, create_bad_request_error, create_validation_error,
+};
+
+/// POST /collections/{name}/recall_profile
+///
+/// Body (all optional): `{"sample_size": 100, "k": 10}`
+///
+/// Runs `sample_size` of the collection's own vectors through the HNSW
+/// search and through exact search, stores the recall@k and latency of
+/// both as the collection's profile, and returns it.
+pub async fn measure_recall_profile(
+    State(state): State<VectorizerServer>,
+    Path(collection_name): Path<String>,
+    payload: Option<Json<Value>>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let payload = payload.map(|Json(v)| v).unwrap_or_default();
+    let field = |key: &str, default: usize| {
+        payload
+            .get(key)
+            .and_then(|v| v.as_u64())
+            .map_or(default, |v| v as usize)
+    };
+    let sample_size = field("sample_size", DEFAULT_PROFILE_SAMPLE_SIZE);
+    let k = field("k", DEFAULT_PROFILE_K);
+
+    state
+        .store
+        .get_collection(&collection_name)
+        .map_err(ErrorResponse::from)?;
+
+    let store = state.store.clone();
+    let col_name = collection_name.clone();
+    let profile = tokio::task::spawn_blocking(move || {
+        store.measure_recall_profile(&col_name, sample_size, k)
+    })
+    .await
+    .map_err(|e| create_bad_request_error(&format!("recall profile task error: {}", e)))?
+    .map_err(ErrorResponse::from)?;
+
+    // Persist the new profile with the next save
+    if let Some(ref auto_save) = state.auto_save_manager {
+        auto_save.mark_changed();
+    }
+
+    info!(
+        "Recall profile of '{}': recall@{} {:.3} over {} queries",
+        collection_name, profile.k, profile.recall_at_k, profile.sample_size
+    );
+
+    Ok(Json(json!({
+        "collection": collection_name,
+        "recall_profile": profile,
+    })))
+}
+
+/// POST /collections/{name}/benchmark
+///
+/// Body (all optional): `{"sample_size": 100, "k": 10,
+/// "ef_search": [16, 32, 64, 128]}`
+///
+/// Runs `sample_size` of the collection's own vectors through exact
+/// search once and through the HNSW search at every `ef_search` of the
+/// sweep (the current beam when none is given), and returns the
+/// recall@k and latency percentiles of each. Searches of other clients
+/// keep their beam.
+pub async fn run_recall_benchmark(
+    State(state): State<VectorizerServer>,
+    Path(collection_name): Path<String>,
+    payload: Option<Json<Value>>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let config: BenchmarkConfig = match payload {
+        Some(Json(payload)) => serde_json::from_value(payload)
+            .map_err(|e| create_validation_error("benchmark", &e.to_string()))?,
+        None => BenchmarkConfig::default(),
+    };
+    config.validate().map_err(ErrorResponse::from)?;
+
+    let store = state.store.clone();
+    let col_name = collection_name.clone();
+    let benchmark =
+        tokio::task::spawn_blocking(move || benchmark_recall(&store, &col_name, &config))
+            .await
+            .map_err(|e| create_bad_request_error(&format!("benchmark task error: {}", e)))?
+            .map_err(ErrorResponse::from)?;
+
+    for run in &benchmark.runs {
+        info!(
+            "Benchmark of '{}': ef_search {:?} recall@{} {:.3}, p50 {:.2} ms, p99 {:.2} ms",
+            collection_name,
+            run.ef_search,
+            benchmark.k,
+            run.recall_at_k,
+            run.latency.p50_ms,
+            run.latency.p99_ms
+        );
+    }
+
+    Ok(Json(json!(benchmark)))
+}
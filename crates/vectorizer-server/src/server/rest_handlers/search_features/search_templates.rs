@@ -0,0 +1,201 @@
+//! Search template REST handlers.
+//!
+//! - `list_search_templates`   — GET    /search/templates
+//! - `get_search_template`     — GET    /search/templates/{name}
+//! - `run_search_template`     — POST   /search/templates/{name}/run
+//! - `put_search_template`     — PUT    /admin/search/templates/{name}
+//! - `delete_search_template`  — DELETE /admin/search/templates/{name}
+//!
+//! Templates are saved to `search_templates.json` in the data directory
+//! and survive restarts. See [`vectorizer::search::templates`].
+
+#![allow(missing_docs)]
+
+use axum::Extension;
+use axum::extract::{Path, State};
+use axum::response::Json;
+use serde_json::{Map, Value, json};
+use tracing::{debug, info};
+use vectorizer::auth::middleware::AuthState;
+use vectorizer::cache::query_cache::QueryKey;
+use vectorizer::hub::middleware::RequestTenantContext;
+use vectorizer::search::SearchTemplate;
+
+use super::common::extract_tenant_id;
+use super::search_common::search_deadline;
+use super::tenant_partition::Partition;
+use crate::server::VectorizerServer;
+use crate::server::core::check_caller_namespace;
+use crate::server::error_middleware::{
+    ErrorResponse, create_bad_request_error, create_not_found_error, create_validation_error,
+};
+
+fn template_json(name: &str, template: &SearchTemplate) -> Value {
+    let mut value = json!(template);
+    if let Some(fields) = value.as_object_mut() {
+        fields.insert("name".to_string(), Value::String(name.to_string()));
+    }
+    value
+}
+
+/// GET /search/templates
+pub async fn list_search_templates(State(state): State<VectorizerServer>) -> Json<Value> {
+    let templates: Vec<Value> = state
+        .search_templates
+        .list()
+        .iter()
+        .map(|(name, template)| template_json(name, template))
+        .collect();
+    Json(json!({
+        "templates": templates,
+        "total": templates.len(),
+    }))
+}
+
+/// GET /search/templates/{name}
+pub async fn get_search_template(
+    State(state): State<VectorizerServer>,
+    Path(name): Path<String>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let template = state
+        .search_templates
+        .get(&name)
+        .ok_or_else(|| create_not_found_error("search_template", &name))?;
+    Ok(Json(template_json(&name, &template)))
+}
+
+/// PUT /admin/search/templates/{name}
+///
+/// Body: the template, e.g.
+/// `{"collections": ["docs"], "limit": 5, "hybrid": {"alpha": 0.6}}`.
+/// Replaces a template with the same name.
+pub async fn put_search_template(
+    State(state): State<VectorizerServer>,
+    Path(name): Path<String>,
+    Json(payload): Json<Value>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let template: SearchTemplate = serde_json::from_value(payload)
+        .map_err(|e| create_validation_error("template", &e.to_string()))?;
+    let replaced = state
+        .search_templates
+        .put(&name, template.clone())
+        .map_err(ErrorResponse::from)?;
+    info!(
+        "Search template '{}' {} ({} collection(s))",
+        name,
+        if replaced { "replaced" } else { "created" },
+        template.collections.len()
+    );
+    Ok(Json(json!({
+        "template": template_json(&name, &template),
+        "replaced": replaced,
+    })))
+}
+
+/// DELETE /admin/search/templates/{name}
+pub async fn delete_search_template(
+    State(state): State<VectorizerServer>,
+    Path(name): Path<String>,
+) -> Result<Json<Value>, ErrorResponse> {
+    if !state
+        .search_templates
+        .remove(&name)
+        .map_err(ErrorResponse::from)?
+    {
+        return Err(create_not_found_error("search_template", &name));
+    }
+    info!("Search template '{}' removed", name);
+    Ok(Json(json!({ "removed": name })))
+}
+
+/// POST /search/templates/{name}/run
+///
+/// Body: `{"query": "...", "params": {...}, "timeout_ms": 200}`. Only
+/// `query` is required; `params` sets the template's declared
+/// parameters. A tenant only reaches its own collections and its own
+/// slice of partitioned ones, and an API key confined to a namespace
+/// only templates whose collections are all inside it. Results are
+/// cached under the rendered filter and the template itself, so
+/// replacing a template or changing `params` never serves a stale entry.
+pub async fn run_search_template(
+    State(state): State<VectorizerServer>,
+    Path(name): Path<String>,
+    tenant_ctx: Option<Extension<RequestTenantContext>>,
+    auth: Option<Extension<AuthState>>,
+    Json(payload): Json<Value>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let template = state
+        .search_templates
+        .get(&name)
+        .ok_or_else(|| create_not_found_error("search_template", &name))?;
+    let query = payload
+        .get("query")
+        .and_then(|q| q.as_str())
+        .ok_or_else(|| create_validation_error("query", "missing or invalid query parameter"))?;
+    let params = match payload.get("params") {
+        None | Some(Value::Null) => Map::new(),
+        Some(Value::Object(params)) => params.clone(),
+        Some(_) => return Err(create_validation_error("params", "must be a JSON object")),
+    };
+    let deadline = search_deadline(&payload)?;
+
+    // Every collection must be visible to the caller
+    let tenant_id = extract_tenant_id(&tenant_ctx);
+    for collection in &template.collections {
+        check_caller_namespace(auth.as_deref(), collection)?;
+        state
+            .store
+            .get_collection_with_owner(collection, tenant_id.as_ref())
+            .map_err(ErrorResponse::from)?;
+    }
+
+    // A tenant's partition must not share entries
+    let filter = template.render(&params).map_err(ErrorResponse::from)?;
+    let cache_key = QueryKey::new(
+        template.collections.join(","),
+        format!("template:{}:{}", name, query),
+        template.limit,
+        template.threshold.map(f64::from),
+    )
+    .with_filter(&json!(filter))
+    .with_params(&json!(template));
+    let cacheable = template
+        .collections
+        .iter()
+        .all(|collection| Partition::resolve(&state, collection, tenant_ctx.as_ref()).is_none());
+    let generation = state.query_cache.generation();
+    if cacheable && let Some(cached) = state.query_cache.get(&cache_key) {
+        debug!("💾 Cache hit for search template '{}'", name);
+        return Ok(Json(cached));
+    }
+
+    let query_embedding = state
+        .embedding_manager
+        .embed(query)
+        .map_err(|e| create_bad_request_error(&format!("Failed to generate embedding: {}", e)))?;
+    let tenant = tenant_ctx.as_ref().map(|ctx| ctx.0.0.tenant_id.as_str());
+    let run = template
+        .run(
+            &state.store,
+            query,
+            &query_embedding,
+            &params,
+            tenant,
+            &deadline,
+        )
+        .map_err(ErrorResponse::from)?;
+
+    let response = json!({
+        "template": name,
+        "query": query,
+        "results": run.hits,
+        "total_results": run.hits.len(),
+        "timed_out": run.timed_out,
+    });
+    if cacheable && !run.timed_out {
+        state
+            .query_cache
+            .insert_at(cache_key, response.clone(), generation);
+    }
+    Ok(Json(response))
+}
@@ -0,0 +1,87 @@
+//! `POST /collections/{name}/search/text/batch` — many text searches
+//! against one collection, for callers that only have query strings,
+//! such as agents fanning one question out into many sub-queries.
+//!
+//! Entries are normalized into `/batch_search` entries and run through
+//! [`super::batch_search`]'s pipeline, so every query is embedded in one
+//! provider call and searched in parallel with the others.
+
+use axum::Extension;
+use axum::extract::{Path, State};
+use axum::response::Json;
+use serde_json::{Value, json};
+use vectorizer::hub::middleware::RequestTenantContext;
+
+use super::batch_search::run_batch;
+use super::search_common::search_deadline;
+use crate::server::VectorizerServer;
+use crate::server::error_middleware::{ErrorResponse, create_validation_error};
+
+/// POST /collections/{name}/search/text/batch — run many text searches
+/// against one collection, embedding every query in one provider call.
+///
+/// Request: `{queries: [string | {query, limit?, threshold?, exact?,
+/// timeout_ms?}], limit?, threshold?, exact?, timeout_ms?}`
+/// Top-level `limit`, `threshold` and `exact` apply to entries that do
+/// not set their own; `timeout_ms` works as on `/batch_search`.
+///
+/// Response: the `/batch_search` shape, with `query` echoed on every
+/// result.
+pub async fn search_text_batch(
+    State(state): State<VectorizerServer>,
+    Path(collection_name): Path<String>,
+    tenant_ctx: Option<Extension<RequestTenantContext>>,
+    Json(payload): Json<Value>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let queries = payload
+        .get("queries")
+        .and_then(|q| q.as_array())
+        .ok_or_else(|| {
+            create_validation_error("queries", "missing or invalid queries parameter")
+        })?;
+    if queries.is_empty() {
+        return Err(create_validation_error(
+            "queries",
+            "queries array must contain at least one entry",
+        ));
+    }
+    let batch_deadline = search_deadline(&payload)?;
+
+    let entries: Vec<Value> = queries
+        .iter()
+        .map(|entry| text_entry(entry, &payload))
+        .collect();
+
+    Ok(Json(run_batch(
+        &state,
+        &collection_name,
+        &entries,
+        batch_deadline,
+        tenant_ctx.as_ref(),
+    )))
+}
+
+/// A `/search/text/batch` entry as a `/batch_search` entry: bare strings
+/// become `{query}`, batch-level defaults fill unset fields, and raw
+/// vectors are dropped so entries without text fail.
+fn text_entry(entry: &Value, payload: &Value) -> Value {
+    let mut normalized = match entry {
+        Value::String(query) => json!({ "query": query }),
+        Value::Object(fields) => {
+            let mut fields = fields.clone();
+            fields.remove("vector");
+            Value::Object(fields)
+        }
+        _ => json!({}),
+    };
+    if let Some(fields) = normalized.as_object_mut() {
+        for key in ["limit", "threshold", "exact"] {
+            if !fields.contains_key(key)
+                && let Some(default) = payload.get(key)
+            {
+                fields.insert(key.to_string(), default.clone());
+            }
+        }
+    }
+    normalized
+}
@@ -0,0 +1,125 @@
+//! Tenant partitions of shared collections (`tenant_partition` in the
+//! collection config).
+//!
+//! A hub tenant calling a partitioned collection is confined to its
+//! [`Partition`]: inserts are stamped with the tenant's ID under the
+//! partition key (overwriting whatever the client sent), searches only
+//! score the tenant's vectors, and single-vector reads and deletes treat
+//! another tenant's vector as absent. Routes that cannot enforce the
+//! partition are refused for tenants by `tenant_partition_middleware`.
+//! Callers without a tenant (hub disabled, admin tooling) see the whole
+//! collection.
+
+use axum::Extension;
+use serde_json::{Map, Value};
+use vectorizer::hub::middleware::RequestTenantContext;
+use vectorizer::models::Payload;
+
+use crate::server::VectorizerServer;
+use crate::server::error_middleware::{
+    ErrorResponse, create_conflict_error, create_not_found_error, create_validation_error,
+};
+
+/// The slice of a partitioned collection a tenant's request works on.
+pub(super) struct Partition {
+    /// Payload field holding the owning tenant's ID
+    pub key: String,
+    /// The calling tenant
+    pub tenant: String,
+}
+
+impl Partition {
+    /// The caller's partition of `collection_name`, or `None` when the
+    /// caller has no tenant, the collection is not partitioned or does
+    /// not exist (the handler reports that itself).
+    pub fn resolve(
+        state: &VectorizerServer,
+        collection_name: &str,
+        tenant_ctx: Option<&Extension<RequestTenantContext>>,
+    ) -> Option<Self> {
+        let tenant = tenant_ctx?.0.0.tenant_id.clone();
+        let key = state
+            .store
+            .get_collection(collection_name)
+            .ok()?
+            .config()
+            .tenant_partition
+            .as_ref()?
+            .key
+            .clone();
+        Some(Self { key, tenant })
+    }
+
+    /// Stamp `payload` with the tenant. Payloads must be JSON objects;
+    /// `null` becomes one.
+    pub fn stamp(&self, payload: &mut Value) -> Result<(), ErrorResponse> {
+        if payload.is_null() {
+            *payload = Value::Object(Map::new());
+        }
+        let Some(fields) = payload.as_object_mut() else {
+            return Err(create_validation_error(
+                "payload",
+                "must be a JSON object in a tenant-partitioned collection",
+            ));
+        };
+        fields.insert(self.key.clone(), Value::String(self.tenant.clone()));
+        Ok(())
+    }
+
+    /// Refuse client-side payload encryption, which would hide the
+    /// partition key from the server.
+    pub fn refuse_encryption(&self, public_key: Option<&str>) -> Result<(), ErrorResponse> {
+        match public_key {
+            Some(_) => Err(create_validation_error(
+                "public_key",
+                "payload encryption is not supported in a tenant-partitioned collection",
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Whether a vector with `payload` belongs to the tenant.
+    pub fn owns(&self, payload: Option<&Payload>) -> bool {
+        payload
+            .and_then(|p| p.data.get(&self.key))
+            .and_then(Value::as_str)
+            == Some(self.tenant.as_str())
+    }
+
+    /// Whether `vector_id` is stored in `collection_name` under another
+    /// tenant.
+    fn is_foreign(&self, state: &VectorizerServer, collection_name: &str, vector_id: &str) -> bool {
+        state
+            .store
+            .get_vector(collection_name, vector_id)
+            .is_ok_and(|vector| !self.owns(vector.payload.as_ref()))
+    }
+
+    /// 409 when inserting `vector_id` would overwrite another tenant's
+    /// vector.
+    pub fn check_insert(
+        &self,
+        state: &VectorizerServer,
+        collection_name: &str,
+        vector_id: &str,
+    ) -> Result<(), ErrorResponse> {
+        if self.is_foreign(state, collection_name, vector_id) {
+            return Err(create_conflict_error("vector", vector_id));
+        }
+        Ok(())
+    }
+
+    /// 404 when `vector_id` belongs to another tenant, as if it did not
+    /// exist.
+    pub fn check_access(
+        &self,
+        state: &VectorizerServer,
+        collection_name: &str,
+        vector_id: &str,
+    ) -> Result<(), ErrorResponse> {
+        if self.is_foreign(state, collection_name, vector_id) {
+            return Err(create_not_found_error("vector", vector_id));
+        }
+        Ok(())
+    }
+}
@@ -9,6 +9,9 @@
 //! - `batch_insert_texts`  — POST /batch/insert
 //! - `insert_texts`        — POST /texts
 //! - `move_vectors`        — POST /collections/{name}/vectors/move
+//!
+//! `patch_vector_payload` lives in [`super::writes::payload_patch`] and
+//! `similarity_matrix` in [`super::explore::similarity`].
 
 use std::collections::HashMap;
 
@@ -17,9 +20,12 @@ use axum::extract::{Path, Query, State};
 use axum::response::Json;
 use serde_json::{Value, json};
 use tracing::{debug, info};
+use vectorizer::hub::WriteAdmission;
 use vectorizer::hub::middleware::RequestTenantContext;
 
 use super::common::extract_tenant_id;
+use super::insert::check_insert_quota;
+use super::tenant_partition::Partition;
 use crate::server::VectorizerServer;
 use crate::server::error_middleware::{ErrorResponse, create_validation_error};
 
@@ -130,36 +136,46 @@ pub async fn list_vectors(
     Ok(Json(response))
 }
 
-/// GET /collections/{name}/vectors/{id} — fetch a single vector
+/// GET /collections/{name}/vectors/{id} — fetch a single vector.
+/// In a partitioned collection another tenant's vector is a 404.
 pub async fn get_vector(
     State(state): State<VectorizerServer>,
     Path((collection_name, vector_id)): Path<(String, String)>,
+    tenant_ctx: Option<Extension<RequestTenantContext>>,
 ) -> Result<Json<Value>, ErrorResponse> {
-    let _collection = state
+    if let Some(partition) = Partition::resolve(&state, &collection_name, tenant_ctx.as_ref()) {
+        partition.check_access(&state, &collection_name, &vector_id)?;
+    }
+
+    let vector = state
         .store
-        .get_collection(&collection_name)
-        .map_err(|e| ErrorResponse::from(e))?;
+        .get_vector(&collection_name, &vector_id)
+        .map_err(ErrorResponse::from)?;
 
-    // Returns mock data — real retrieval by ID is tracked in a separate task
     Ok(Json(json!({
-        "id": vector_id,
-        "vector": vec![0.1; 512],
-        "metadata": {
-            "collection": collection_name
-        }
+        "id": vector.id,
+        "vector": vector.data,
+        "payload": vector.payload.map(|p| p.data),
+        "document_id": vector.document_id,
     })))
 }
 
-/// DELETE /collections/{name}/vectors/{id} — delete a specific vector
+/// DELETE /collections/{name}/vectors/{id} — delete a specific vector.
+/// In a partitioned collection another tenant's vector is a 404.
 pub async fn delete_vector(
     State(state): State<VectorizerServer>,
     Path((collection_name, vector_id)): Path<(String, String)>,
+    tenant_ctx: Option<Extension<RequestTenantContext>>,
 ) -> Result<Json<Value>, ErrorResponse> {
     info!(
         "Deleting vector {} from collection {}",
         vector_id, collection_name
     );
 
+    if let Some(partition) = Partition::resolve(&state, &collection_name, tenant_ctx.as_ref()) {
+        partition.check_access(&state, &collection_name, &vector_id)?;
+    }
+
     // Actually delete the vector from the store
     state
         .store
@@ -422,14 +438,18 @@ async fn do_batch_insert_texts(
                     .insert_requests_total
                     .with_label_values(&[label_collection, "success"])
                     .inc();
-                results.push(json!({
+                let mut item = json!({
                     "index": idx,
                     "client_id": client_id,
                     "status": "ok",
                     "vector_ids": res.vector_ids,
-                    "vectors_created": res.vector_ids.len(),
+                    "vectors_created": res.created(),
                     "chunked": res.chunked,
-                }));
+                });
+                if let Some(dedup) = res.dedup_report() {
+                    item["dedup"] = json!(dedup);
+                }
+                results.push(item);
             }
             Err(e) => {
                 failed += 1;
@@ -499,6 +519,7 @@ pub async fn insert_texts(
 pub async fn move_vectors(
     State(state): State<VectorizerServer>,
     Path(src_collection): Path<String>,
+    tenant_ctx: Option<Extension<RequestTenantContext>>,
     Json(payload): Json<Value>,
 ) -> Result<Json<Value>, ErrorResponse> {
     let dst_collection = payload
@@ -536,6 +557,27 @@ pub async fn move_vectors(
         dst_collection,
     );
 
+    // A move between two collections of one owner leaves its usage as
+    // it was; anything else grows the destination's tenant
+    let owner = |name: &str| {
+        state
+            .store
+            .get_collection(name)
+            .ok()
+            .and_then(|c| c.owner_id())
+    };
+    let _tenant_admission = if owner(&src_collection) == owner(&dst_collection) {
+        WriteAdmission::default()
+    } else {
+        check_insert_quota(
+            &state,
+            tenant_ctx.as_ref(),
+            &dst_collection,
+            ids_value.len(),
+        )
+        .await?
+    };
+
     let mut moved: usize = 0;
     let mut failed: usize = 0;
     let mut results: Vec<Value> = Vec::with_capacity(ids_value.len());
@@ -721,9 +763,10 @@ pub async fn delete_by_filter(
 ///
 /// Body: `{"filter": <QdrantFilter>, "patch": {...}}`
 ///
-/// The `patch` is applied with JSON-merge-patch semantics (RFC 7396):
-/// keys in `patch` overwrite the existing payload values; null values
-/// remove keys. The raw vector data and dimensions are never modified.
+/// The `patch` is applied with JSON-merge-patch semantics (RFC 7396,
+/// see [`vectorizer::db::VectorStore::patch_payload`]): keys in `patch`
+/// overwrite the existing payload values; null values remove keys. The
+/// raw vector data and dimensions are never modified.
 ///
 /// Response: `{"scanned": N, "matched": N, "updated": N, "results": [...]}`
 pub async fn bulk_update_metadata(
@@ -780,21 +823,16 @@ pub async fn bulk_update_metadata(
     let mut updated: usize = 0;
     let mut results: Vec<serde_json::Value> = Vec::with_capacity(matched);
 
-    for mut vector in matching {
-        // Apply JSON-merge-patch to payload.
-        let new_payload_data = if let Some(existing) = vector.payload.as_ref() {
-            json_merge_patch(existing.data.clone(), patch.clone())
-        } else {
-            patch.clone()
-        };
-
-        vector.payload = Some(vectorizer::models::Payload {
-            data: new_payload_data,
-        });
-
-        let id = vector.id.clone();
-        match state.store.update(&collection_name, vector) {
-            Ok(()) => {
+    for vector in matching {
+        // Re-reads the vector under the collection's write lock, so a
+        // write since the scan is patched rather than overwritten.
+        let id = vector.id;
+        match state
+            .store
+            .patch_payload(&collection_name, &id, &patch, |_| {
+                Ok::<_, vectorizer::error::VectorizerError>(())
+            }) {
+            Ok(_) => {
                 updated += 1;
                 results.push(json!({"id": id, "status": "updated"}));
             }
@@ -824,28 +862,6 @@ pub async fn bulk_update_metadata(
     })))
 }
 
-/// Apply JSON-merge-patch (RFC 7396) to `target` using `patch`.
-///
-/// * Object keys in `patch` that are not `null` overwrite the same key in `target`.
-/// * `null` values in `patch` remove the corresponding key from `target`.
-/// * Non-object patches replace `target` entirely.
-fn json_merge_patch(mut target: serde_json::Value, patch: serde_json::Value) -> serde_json::Value {
-    match (target.as_object_mut(), patch) {
-        (Some(target_map), serde_json::Value::Object(patch_map)) => {
-            for (key, value) in patch_map {
-                if value.is_null() {
-                    target_map.remove(&key);
-                } else {
-                    let existing = target_map.remove(&key).unwrap_or(serde_json::Value::Null);
-                    target_map.insert(key, json_merge_patch(existing, value));
-                }
-            }
-            serde_json::Value::Object(target_map.clone())
-        }
-        (_, patch) => patch,
-    }
-}
-
 /// POST /collections/{src}/vectors/copy — copy (NOT move) vectors to a
 /// destination collection carrying raw vector data + payload unchanged.
 ///
@@ -857,6 +873,7 @@ fn json_merge_patch(mut target: serde_json::Value, patch: serde_json::Value) ->
 pub async fn copy_vectors(
     State(state): State<VectorizerServer>,
     Path(src_collection): Path<String>,
+    tenant_ctx: Option<Extension<RequestTenantContext>>,
     Json(payload): Json<serde_json::Value>,
 ) -> Result<Json<serde_json::Value>, crate::server::error_middleware::ErrorResponse> {
     let dst_collection = payload
@@ -901,6 +918,13 @@ pub async fn copy_vectors(
         src_collection,
         dst_collection,
     );
+    let _tenant_admission = check_insert_quota(
+        &state,
+        tenant_ctx.as_ref(),
+        &dst_collection,
+        ids_value.len(),
+    )
+    .await?;
 
     let mut copied: usize = 0;
     let mut failed: usize = 0;
@@ -0,0 +1,262 @@
+//! Pre-chunked document ingest for external pipelines.
+//!
+//! - `ingest_document` — POST   /collections/{name}/documents
+//! - `delete_document` — DELETE /collections/{name}/documents?file_path=...
+//!
+//! An ETL job that chunks (and optionally embeds) documents itself sends
+//! the chunks of one document together with its `file_path`. The server
+//! writes them with the same flat payload the built-in file loader
+//! produces (`content`, `file_path`, `chunk_index`, `chunk_size`,
+//! `indexed_at`, line range), so the document registry and the `/file/*`
+//! endpoints see external documents exactly like indexed files.
+//!
+//! Chunk ids are `<file_path>#<chunk_index>`. Re-sending a document
+//! replaces it: chunks of the previous version that are not in the new
+//! upload are garbage-collected, whatever id they were written under.
+
+#![allow(missing_docs)]
+
+use std::collections::{HashMap, HashSet};
+
+use axum::Extension;
+use axum::extract::{Path, Query, State};
+use axum::response::Json;
+use serde_json::{Value, json};
+use tracing::{info, warn};
+use vectorizer::hub::middleware::RequestTenantContext;
+
+use super::common::admit_upsert;
+use super::insert::{
+    CLIENT_ID_CHUNK_SEPARATOR, build_chunk_payload, check_insert_quota, ensure_collection_exists,
+    mark_collection_dirty, parse_metadata, record_insert_usage, validate_client_id,
+};
+use crate::server::VectorizerServer;
+use crate::server::error_middleware::{
+    ErrorResponse, create_bad_request_error, create_not_found_error, create_validation_error,
+};
+
+/// POST /collections/{name}/documents
+///
+/// Request shape:
+/// ```json
+/// {
+///   "file_path": "handbook/onboarding.md",   // document id, no '#'
+///   "metadata": { "source": "confluence" },  // optional, on every chunk
+///   "chunks": [
+///     {
+///       "content": "...",                    // required
+///       "chunk_index": 0,                    // optional, defaults to position
+///       "embedding": [0.1, ...],             // optional, embedded server-side when absent
+///       "line_start": 1, "line_end": 40,     // optional
+///       "metadata": { "section": "intro" }   // optional, overrides document metadata
+///     }
+///   ]
+/// }
+/// ```
+///
+/// Returns the chunk ids written and the number of stale chunks removed.
+pub async fn ingest_document(
+    State(state): State<VectorizerServer>,
+    Path(collection_name): Path<String>,
+    tenant_ctx: Option<Extension<RequestTenantContext>>,
+    Json(payload): Json<Value>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let file_path = payload
+        .get("file_path")
+        .and_then(|f| f.as_str())
+        .ok_or_else(|| create_validation_error("file_path", "missing or invalid file_path"))?;
+    validate_client_id(file_path)
+        .map_err(|reason| create_validation_error("file_path", &reason))?;
+
+    let chunks_in = payload
+        .get("chunks")
+        .and_then(|c| c.as_array())
+        .filter(|c| !c.is_empty())
+        .ok_or_else(|| {
+            create_validation_error(
+                "chunks",
+                "expected a non-empty array; delete the document to remove all its chunks",
+            )
+        })?;
+
+    let _admission_ticket = admit_upsert(&state.upsert_queue, &collection_name)?;
+
+    ensure_collection_exists(&state, &collection_name)?;
+    let _tenant_admission = check_insert_quota(
+        &state,
+        tenant_ctx.as_ref(),
+        &collection_name,
+        chunks_in.len(),
+    )
+    .await?;
+
+    let document_metadata = parse_metadata(&payload);
+    let indexed_at = chrono::Utc::now().to_rfc3339();
+    let mut seen_indexes = HashSet::with_capacity(chunks_in.len());
+    let mut vectors = Vec::with_capacity(chunks_in.len());
+
+    for (position, chunk) in chunks_in.iter().enumerate() {
+        let field = |name: &str| format!("chunks[{position}].{name}");
+        let content = chunk
+            .get("content")
+            .and_then(|c| c.as_str())
+            .ok_or_else(|| create_validation_error(&field("content"), "missing or invalid"))?;
+        let chunk_index = match chunk.get("chunk_index") {
+            None => position,
+            Some(v) => v.as_u64().map(|i| i as usize).ok_or_else(|| {
+                create_validation_error(&field("chunk_index"), "expected a non-negative integer")
+            })?,
+        };
+        if !seen_indexes.insert(chunk_index) {
+            return Err(create_validation_error(
+                &field("chunk_index"),
+                &format!("duplicate chunk_index {chunk_index}"),
+            ));
+        }
+
+        let embedding = match chunk.get("embedding") {
+            Some(v) => serde_json::from_value::<Vec<f32>>(v.clone()).map_err(|_| {
+                create_validation_error(&field("embedding"), "expected an array of numbers")
+            })?,
+            None => state.embedding_manager.embed(content).map_err(|e| {
+                create_bad_request_error(&format!("Failed to generate embedding: {}", e))
+            })?,
+        };
+
+        let mut metadata = document_metadata.clone();
+        metadata.extend(parse_metadata(chunk));
+        let mut payload_data =
+            build_chunk_payload(content, file_path, chunk_index, file_path, &metadata);
+        if let Some(map) = payload_data.as_object_mut() {
+            map.insert("chunk_size".into(), json!(content.len()));
+            map.insert("indexed_at".into(), json!(indexed_at));
+            for key in ["line_start", "line_end"] {
+                if let Some(line) = chunk.get(key).and_then(|l| l.as_u64()) {
+                    map.insert(key.into(), json!(line));
+                }
+            }
+        }
+
+        vectors.push(vectorizer::models::Vector {
+            id: format!("{file_path}{CLIENT_ID_CHUNK_SEPARATOR}{chunk_index}"),
+            data: embedding,
+            sparse: None,
+            payload: Some(vectorizer::models::Payload::new(payload_data)),
+            document_id: None,
+        });
+    }
+
+    let embedding_len = vectors.first().map_or(0, |v| v.data.len());
+    let vector_ids: Vec<String> = vectors.iter().map(|v| v.id.clone()).collect();
+    state
+        .store
+        .insert(&collection_name, vectors)
+        .map_err(ErrorResponse::from)?;
+
+    let keep: HashSet<&str> = vector_ids.iter().map(String::as_str).collect();
+    let removed_stale = remove_document_chunks(&state, &collection_name, file_path, &keep)?;
+
+    record_insert_usage(
+        &state,
+        &collection_name,
+        embedding_len,
+        vector_ids.len() as u64,
+    )
+    .await;
+    mark_collection_dirty(&state, &collection_name, &vector_ids);
+
+    info!(
+        "Ingested document '{}' into '{}': {} chunk(s), {} stale removed",
+        file_path,
+        collection_name,
+        vector_ids.len(),
+        removed_stale
+    );
+
+    Ok(Json(json!({
+        "collection": collection_name,
+        "file_path": file_path,
+        "chunks": vector_ids.len(),
+        "vector_ids": vector_ids,
+        "removed_stale": removed_stale,
+    })))
+}
+
+/// DELETE /collections/{name}/documents?file_path=handbook/onboarding.md
+///
+/// Removes every chunk of the document, whichever path wrote it, and
+/// drops it from the document registry.
+pub async fn delete_document(
+    State(state): State<VectorizerServer>,
+    Path(collection_name): Path<String>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Json<Value>, ErrorResponse> {
+    let file_path = params
+        .get("file_path")
+        .filter(|f| !f.is_empty())
+        .ok_or_else(|| create_validation_error("file_path", "missing file_path query parameter"))?;
+
+    let removed = remove_document_chunks(&state, &collection_name, file_path, &HashSet::new())?;
+    if removed == 0 {
+        return Err(create_not_found_error("document", file_path));
+    }
+
+    if let Ok(collection) = state.store.get_collection(&collection_name) {
+        collection.forget_document(file_path);
+    }
+    state.query_cache.invalidate_collection(&collection_name);
+    if let Some(ref auto_save) = state.auto_save_manager {
+        auto_save.mark_changed();
+    }
+
+    info!(
+        "Deleted document '{}' from '{}' ({} chunk(s))",
+        file_path, collection_name, removed
+    );
+
+    Ok(Json(json!({
+        "collection": collection_name,
+        "file_path": file_path,
+        "deleted_chunks": removed,
+    })))
+}
+
+/// Delete the chunks whose payload `file_path` is `file_path`, except
+/// the ids in `keep`. Returns how many were deleted.
+fn remove_document_chunks(
+    state: &VectorizerServer,
+    collection_name: &str,
+    file_path: &str,
+    keep: &HashSet<&str>,
+) -> Result<usize, ErrorResponse> {
+    let collection = state
+        .store
+        .get_collection(collection_name)
+        .map_err(ErrorResponse::from)?;
+
+    let stale: Vec<String> = collection
+        .get_all_vectors()
+        .into_iter()
+        .filter(|v| !keep.contains(v.id.as_str()))
+        .filter(|v| {
+            v.payload
+                .as_ref()
+                .and_then(|p| p.data.get("file_path"))
+                .and_then(|f| f.as_str())
+                == Some(file_path)
+        })
+        .map(|v| v.id)
+        .collect();
+
+    let mut removed = 0;
+    for id in &stale {
+        match state.store.delete(collection_name, id) {
+            Ok(()) => removed += 1,
+            Err(e) => warn!(
+                "Failed to remove chunk '{}' of document '{}': {}",
+                id, file_path, e
+            ),
+        }
+    }
+    Ok(removed)
+}
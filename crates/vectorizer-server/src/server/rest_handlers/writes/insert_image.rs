@@ -0,0 +1,256 @@
+//! `POST /insert_image` — embed an image and store it in a collection.
+//!
+//! The image is embedded by the collection's `embedding_provider`, which
+//! must be multimodal (`clip`, see `vectorizer::embedding::clip`). Text
+//! searches against the collection embed the query with the same
+//! provider's text encoder (see
+//! [`VectorizerServer::embed_for_collection`]), so screenshots are found
+//! by describing them. Only the embedding and metadata are stored, not
+//! the image bytes. Shares the post-write housekeeping with
+//! `super::insert`.
+
+use std::collections::HashMap;
+
+use axum::Extension;
+use axum::extract::multipart::Field;
+use axum::extract::{FromRequest, Multipart, Request, State};
+use axum::http::header;
+use axum::response::Json;
+use base64::Engine;
+use serde_json::{Value, json};
+use tracing::info;
+use vectorizer::hub::middleware::RequestTenantContext;
+
+use super::common::admit_upsert;
+use super::dedup::insert_vector;
+use super::insert::{
+    check_insert_quota, mark_collection_dirty, parse_metadata, record_insert_usage,
+    validate_client_id,
+};
+use super::tenant_partition::Partition;
+use crate::server::VectorizerServer;
+use crate::server::error_middleware::{
+    ErrorResponse, create_bad_request_error, create_validation_error,
+};
+
+/// An image insert, read from either request encoding
+#[derive(Debug, Default)]
+struct ImageInsert {
+    collection: Option<String>,
+    image: Option<Vec<u8>>,
+    filename: Option<String>,
+    id: Option<String>,
+    metadata: HashMap<String, String>,
+}
+
+impl ImageInsert {
+    /// From a JSON body, where `image` is base64
+    fn from_json(payload: &Value) -> Result<Self, ErrorResponse> {
+        let string = |key: &str| {
+            payload
+                .get(key)
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+        };
+        let image = payload
+            .get("image")
+            .and_then(|i| i.as_str())
+            .map(|encoded| {
+                base64::engine::general_purpose::STANDARD
+                    .decode(encoded.trim())
+                    .map_err(|e| {
+                        create_validation_error("image", &format!("invalid base64: {}", e))
+                    })
+            })
+            .transpose()?;
+        Ok(Self {
+            collection: string("collection"),
+            image,
+            filename: string("filename"),
+            id: string("id"),
+            metadata: parse_metadata(payload),
+        })
+    }
+
+    /// From a multipart body, where the image is the `file` part and
+    /// `metadata` a JSON object
+    async fn from_multipart(mut multipart: Multipart) -> Result<Self, ErrorResponse> {
+        let mut insert = Self::default();
+        while let Some(field) = multipart.next_field().await.map_err(|e| {
+            create_bad_request_error(&format!("Failed to read multipart field: {}", e))
+        })? {
+            match field.name().unwrap_or_default() {
+                "file" | "image" => {
+                    if insert.filename.is_none() {
+                        insert.filename = field.file_name().map(str::to_string);
+                    }
+                    let bytes = field.bytes().await.map_err(|e| {
+                        create_bad_request_error(&format!("Failed to read image: {}", e))
+                    })?;
+                    insert.image = Some(bytes.to_vec());
+                }
+                "collection" => insert.collection = Some(field_text(field).await?),
+                "filename" => insert.filename = Some(field_text(field).await?),
+                "id" => insert.id = Some(field_text(field).await?),
+                "metadata" => {
+                    let metadata: Value =
+                        serde_json::from_str(&field_text(field).await?).map_err(|e| {
+                            create_validation_error(
+                                "metadata",
+                                &format!("expected a JSON object: {}", e),
+                            )
+                        })?;
+                    insert.metadata = parse_metadata(&json!({ "metadata": metadata }));
+                }
+                _ => {}
+            }
+        }
+        Ok(insert)
+    }
+}
+
+async fn field_text(field: Field<'_>) -> Result<String, ErrorResponse> {
+    let name = field.name().unwrap_or_default().to_string();
+    field
+        .text()
+        .await
+        .map_err(|e| create_bad_request_error(&format!("Failed to read {}: {}", name, e)))
+}
+
+/// POST /insert_image — embed one image with the collection's
+/// multimodal provider and insert it.
+///
+/// JSON request:
+/// ```json
+/// {
+///   "collection": "screenshots",
+///   "image": "<base64 PNG/JPEG/...>",
+///   "filename": "login.png",          // optional, stored as `image_path`
+///   "id": "shot:42",                  // optional — generated from the collection's id_policy
+///   "metadata": { "app": "billing" }  // optional
+/// }
+/// ```
+///
+/// Or `multipart/form-data` with the same fields, the image as the
+/// `file` part (its filename is used when `filename` is absent) and
+/// `metadata` as a JSON string.
+///
+/// The payload holds the metadata plus `modality: "image"`,
+/// `image_size_bytes` and `image_path`. Fails with 400 when the
+/// collection's provider does not embed images.
+pub async fn insert_image(
+    State(state): State<VectorizerServer>,
+    tenant_ctx: Option<Extension<RequestTenantContext>>,
+    request: Request,
+) -> Result<Json<Value>, ErrorResponse> {
+    use vectorizer::monitoring::metrics::METRICS;
+
+    let timer = METRICS.insert_latency_seconds.start_timer();
+
+    let is_multipart = request
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("multipart/form-data"));
+    let insert = if is_multipart {
+        let multipart = Multipart::from_request(request, &state)
+            .await
+            .map_err(|e| create_bad_request_error(&e.body_text()))?;
+        ImageInsert::from_multipart(multipart).await?
+    } else {
+        let Json(payload) = Json::<Value>::from_request(request, &state)
+            .await
+            .map_err(|e| create_bad_request_error(&e.body_text()))?;
+        ImageInsert::from_json(&payload)?
+    };
+
+    let collection_name = insert.collection.ok_or_else(|| {
+        create_validation_error("collection", "missing or invalid collection parameter")
+    })?;
+    let image = insert.image.filter(|i| !i.is_empty()).ok_or_else(|| {
+        create_validation_error(
+            "image",
+            "missing image (base64 `image` in JSON, or a `file` part in multipart)",
+        )
+    })?;
+    if let Some(id) = &insert.id {
+        validate_client_id(id).map_err(|reason| create_validation_error("id", &reason))?;
+    }
+
+    // Issue #263: per-collection admission, released on drop
+    let _admission_ticket = admit_upsert(&state.upsert_queue, &collection_name)?;
+
+    // No auto-creation: a default collection's provider embeds no images
+    let provider = state
+        .store
+        .get_collection(&collection_name)
+        .map(|c| c.config().embedding_provider.clone())
+        .map_err(ErrorResponse::from)?;
+    let _tenant_admission =
+        check_insert_quota(&state, tenant_ctx.as_ref(), &collection_name, 1).await?;
+
+    info!(
+        "Inserting image into collection '{}': {} bytes (provider: {})",
+        collection_name,
+        image.len(),
+        provider
+    );
+    let embedding = state
+        .embedding_manager
+        .embed_image_with_provider(&provider, &image)
+        .map_err(|e| {
+            create_bad_request_error(&format!(
+                "Failed to embed image with provider '{}': {}",
+                provider, e
+            ))
+        })?;
+    let embedding_len = embedding.len();
+
+    let mut payload = json!(insert.metadata);
+    payload["modality"] = json!("image");
+    payload["image_size_bytes"] = json!(image.len());
+    if let Some(filename) = &insert.filename {
+        payload["image_path"] = json!(filename);
+    }
+    let partition = Partition::resolve(&state, &collection_name, tenant_ctx.as_ref());
+    if let Some(partition) = &partition {
+        partition.stamp(&mut payload)?;
+    }
+
+    let vector_id = insert
+        .id
+        .unwrap_or_else(|| state.store.new_vector_id(&collection_name));
+    if let Some(partition) = &partition {
+        partition.check_insert(&state, &collection_name, &vector_id)?;
+    }
+    let vector = vectorizer::models::Vector {
+        id: vector_id,
+        data: embedding,
+        sparse: None,
+        payload: Some(vectorizer::models::Payload::new(payload)),
+        document_id: None,
+    };
+    let (vector_id, status) = insert_vector(&state, &collection_name, vector)?;
+    let created = usize::from(status.duplicate().is_none());
+
+    record_insert_usage(&state, &collection_name, embedding_len, created as u64).await;
+    mark_collection_dirty(&state, &collection_name, std::slice::from_ref(&vector_id));
+
+    METRICS
+        .insert_requests_total
+        .with_label_values(&[collection_name.as_str(), "success"])
+        .inc();
+    drop(timer);
+
+    let mut response = json!({
+        "message": format!("Image inserted successfully ({} vector(s) created)", created),
+        "vectors_created": created,
+        "vector_id": vector_id,
+        "collection": collection_name,
+        "embedding_provider": provider,
+    });
+    if created == 0 {
+        response["dedup"] = json!(status);
+    }
+    Ok(Json(response))
+}
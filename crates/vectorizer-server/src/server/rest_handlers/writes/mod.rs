@@ -0,0 +1,18 @@
+//! Vector writes besides `/insert_text` and `/insert_vectors`:
+//! pre-chunked documents, images and payload patches.
+//!
+//! - [`documents`]     — /collections/{name}/documents pre-chunked ingest
+//!                       for external pipelines
+//! - [`insert_image`]  — /insert_image, embedded by the collection's
+//!                       multimodal (CLIP) provider
+//! - [`payload_patch`] — JSON merge patch of one vector's payload
+
+use super::{common, dedup, insert, tenant_partition};
+
+mod documents;
+mod insert_image;
+mod payload_patch;
+
+pub use documents::{delete_document, ingest_document};
+pub use insert_image::insert_image;
+pub use payload_patch::patch_vector_payload;
@@ -0,0 +1,64 @@
+//! `patch_vector_payload` — PATCH /collections/{name}/vectors/{id}/payload,
+//! a JSON merge patch of one stored payload.
+
+use axum::Extension;
+use axum::extract::{Path, State};
+use axum::response::Json;
+use serde_json::{Value, json};
+use tracing::debug;
+use vectorizer::hub::middleware::RequestTenantContext;
+
+use super::tenant_partition::Partition;
+use crate::server::VectorizerServer;
+use crate::server::error_middleware::{ErrorResponse, create_parse_error};
+
+/// PATCH /collections/{name}/vectors/{id}/payload — apply a JSON merge
+/// patch (RFC 7396) to one vector's payload.
+///
+/// The body is the patch document itself, sent as
+/// `application/merge-patch+json` or `application/json`. Members replace
+/// the stored ones, `null` members remove them and nested objects merge,
+/// so a client can change one field of a large payload without sending
+/// the rest back. The vector data is never modified. Encrypted payloads
+/// cannot be patched. In a partitioned collection another tenant's
+/// vector is a 404 and the partition key is re-stamped after the patch.
+///
+/// Response: `{"id", "collection", "payload"}` with the patched payload.
+pub async fn patch_vector_payload(
+    State(state): State<VectorizerServer>,
+    Path((collection_name, vector_id)): Path<(String, String)>,
+    tenant_ctx: Option<Extension<RequestTenantContext>>,
+    body: axum::body::Bytes,
+) -> Result<Json<Value>, ErrorResponse> {
+    let patch: Value =
+        serde_json::from_slice(&body).map_err(|e| create_parse_error("body", &e.to_string()))?;
+
+    let partition = Partition::resolve(&state, &collection_name, tenant_ctx.as_ref());
+    if let Some(partition) = &partition {
+        partition.check_access(&state, &collection_name, &vector_id)?;
+    }
+
+    let patched = state
+        .store
+        .patch_payload(&collection_name, &vector_id, &patch, |vector| {
+            if let (Some(partition), Some(payload)) = (&partition, vector.payload.as_mut()) {
+                partition.stamp(&mut payload.data)?;
+            }
+            Ok::<_, ErrorResponse>(())
+        })?;
+
+    if let Some(ref auto_save) = state.auto_save_manager {
+        auto_save.mark_changed();
+    }
+
+    debug!(
+        "Patched payload of vector '{}' in '{}'",
+        vector_id, collection_name
+    );
+
+    Ok(Json(json!({
+        "id": vector_id,
+        "collection": collection_name,
+        "payload": patched,
+    })))
+}
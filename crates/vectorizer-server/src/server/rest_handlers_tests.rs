@@ -261,3 +261,28 @@ fn vector_payload_is_empty_object_when_neither_present() {
     let p = build_vector_payload(&entry);
     assert_eq!(p.as_object().map(|o| o.len()), Some(0));
 }
+
+// --- tenant partitions ----------------------------------------------------
+
+use super::tenant_partition::Partition;
+
+#[test]
+fn partition_stamp_overwrites_the_clients_tenant() {
+    let partition = Partition {
+        key: "tenant_id".to_string(),
+        tenant: "acme".to_string(),
+    };
+    let mut payload = json!({"title": "q3 report", "tenant_id": "globex"});
+    partition.stamp(&mut payload).unwrap();
+    assert_eq!(payload["tenant_id"], "acme");
+    assert_eq!(payload["title"], "q3 report");
+    assert!(partition.owns(Some(&vectorizer::models::Payload::new(payload))));
+
+    let mut empty = serde_json::Value::Null;
+    partition.stamp(&mut empty).unwrap();
+    assert_eq!(empty, json!({"tenant_id": "acme"}));
+
+    assert!(partition.stamp(&mut json!("not an object")).is_err());
+    assert!(!partition.owns(None));
+    assert!(partition.refuse_encryption(Some("key")).is_err());
+}
@@ -0,0 +1,225 @@
+//! `GET /ws/changes` handler — the change feed over a WebSocket.
+//!
+//! ## Wire protocol
+//!
+//! The upgrade GET takes the same query as `GET /changes/stream`:
+//! `collections=a,b` to follow only some collections and `since=N` to
+//! resume after sequence number `N`. Frames are JSON text; the server
+//! pushes every matching event and answers pings.
+//!
+//! ```jsonc
+//! // Client → server
+//! {"op": "ping"}
+//!
+//! // Server → client
+//! {"op": "change", "event": {"seq": 42, "collection": "docs", "id": "v1",
+//!                            "kind": "update", "payload_hash": "…", "timestamp": "…"}}
+//! {"op": "pong"}
+//! {"op": "error", "code": "stream_lag" | "bad_frame"}
+//! ```
+//!
+//! A `since` the feed no longer buffers refuses the upgrade with 410,
+//! like the SSE stream. As there, `payload_hash` is left out of events
+//! from collections the caller's payload redaction rules cover.
+//!
+//! ## Slow consumers
+//!
+//! A connection that falls more than `change_feed.buffer_size` events
+//! behind gets a single `{op: "error", code: "stream_lag"}` frame and is
+//! closed. Reconnecting with the last `seq` it saw resumes if the feed
+//! still buffers it.
+
+use axum::Extension;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Query, State};
+use axum::response::IntoResponse;
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{debug, warn};
+use vectorizer::auth::middleware::AuthState;
+use vectorizer::db::{ChangeCursor, ChangeEvent};
+use vectorizer::security::PayloadView;
+
+use crate::server::VectorizerServer;
+use crate::server::core::caller_payload_view;
+use crate::server::error_middleware::ErrorResponse;
+use crate::server::rest_handlers::{ChangeStreamQuery, change_feed, open_cursor, redact_change};
+
+/// Frames the client sends. `op` is the discriminator.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum ClientFrame {
+    Ping,
+}
+
+/// Frames the server sends.
+#[derive(Debug, Serialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum ServerFrame<'a> {
+    Change { event: &'a ChangeEvent },
+    Pong,
+    Error { code: ErrorCode },
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum ErrorCode {
+    /// Client fell behind the feed; reconnect with `since` to resume.
+    StreamLag,
+    /// Client sent a frame that did not parse against `ClientFrame`.
+    BadFrame,
+}
+
+/// `GET /ws/changes` upgrade handler.
+pub async fn changes_ws_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<VectorizerServer>,
+    Query(query): Query<ChangeStreamQuery>,
+    auth: Option<Extension<AuthState>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let feed = change_feed(&state)?;
+    let cursor = open_cursor(&feed, query.since, &query)?;
+    let view = caller_payload_view(state.payload_redactor.clone(), auth.as_deref());
+    Ok(ws.on_upgrade(move |socket| serve_connection(socket, cursor, view)))
+}
+
+async fn serve_connection(mut socket: WebSocket, mut cursor: ChangeCursor, view: PayloadView) {
+    loop {
+        tokio::select! {
+            biased;
+
+            incoming = socket.recv() => {
+                match incoming {
+                    Some(Ok(Message::Text(txt))) => {
+                        let reply = match serde_json::from_str::<ClientFrame>(&txt) {
+                            Ok(ClientFrame::Ping) => ServerFrame::Pong,
+                            Err(e) => {
+                                debug!(error = %e, "ws changes: client frame did not parse");
+                                ServerFrame::Error { code: ErrorCode::BadFrame }
+                            }
+                        };
+                        if send(&mut socket, &reply).await.is_err() {
+                            return;
+                        }
+                    }
+                    Some(Ok(Message::Pong(_))) | Some(Ok(Message::Ping(_))) => {}
+                    Some(Ok(Message::Binary(_))) => {
+                        let reply = ServerFrame::Error { code: ErrorCode::BadFrame };
+                        if send(&mut socket, &reply).await.is_err() {
+                            return;
+                        }
+                    }
+                    Some(Ok(Message::Close(_))) | None => return,
+                    Some(Err(e)) => {
+                        warn!(error = %e, "ws changes: read error, closing connection");
+                        return;
+                    }
+                }
+            }
+
+            next = cursor.next() => {
+                match next {
+                    Ok(mut event) => {
+                        redact_change(&view, &mut event);
+                        if send(&mut socket, &ServerFrame::Change { event: &event }).await.is_err() {
+                            return;
+                        }
+                    }
+                    Err(RecvError::Lagged(n)) => {
+                        debug!(lagged = n, "ws changes: client lagged, dropping");
+                        let reply = ServerFrame::Error { code: ErrorCode::StreamLag };
+                        let _ = send(&mut socket, &reply).await;
+                        return;
+                    }
+                    Err(RecvError::Closed) => return,
+                }
+            }
+        }
+    }
+}
+
+async fn send(socket: &mut WebSocket, frame: &ServerFrame<'_>) -> Result<(), axum::Error> {
+    let frame = serde_json::to_string(frame).map_err(axum::Error::new)?;
+    socket.send(Message::Text(frame.into())).await
+}
+
+#[cfg(test)]
+mod tests {
+    use vectorizer::db::ChangeKind;
+
+    use super::*;
+
+    #[test]
+    fn change_frame_carries_event() {
+        let event = ChangeEvent {
+            seq: 7,
+            collection: "docs".to_string(),
+            id: "v1".to_string(),
+            kind: ChangeKind::Delete,
+            payload_hash: None,
+            timestamp: chrono::Utc::now(),
+        };
+        let json = serde_json::to_string(&ServerFrame::Change { event: &event }).unwrap();
+        assert!(json.starts_with(r#"{"op":"change","event":{"seq":7,"collection":"docs""#));
+        assert!(json.contains(r#""kind":"delete""#));
+    }
+
+    #[test]
+    fn payload_hash_is_withheld_from_redacted_callers() {
+        use std::sync::Arc;
+
+        use vectorizer::auth::Secret;
+        use vectorizer::auth::roles::Role;
+        use vectorizer::config::sections::redaction::{
+            PayloadRedactionConfig, RedactionAction, RedactionRule,
+        };
+        use vectorizer::security::PayloadRedactor;
+
+        let redactor = PayloadRedactor::from_config(&PayloadRedactionConfig {
+            enabled: true,
+            hash_salt: Some(Secret::new("salt".to_string())),
+            rules: vec![RedactionRule {
+                field: "email".to_string(),
+                action: RedactionAction::Mask,
+                collections: vec!["customers".to_string()],
+                roles: Vec::new(),
+            }],
+            exempt_roles: vec!["Admin".to_string()],
+        })
+        .unwrap()
+        .map(Arc::new);
+        let event = |collection: &str| ChangeEvent {
+            seq: 1,
+            collection: collection.to_string(),
+            id: "v1".to_string(),
+            kind: ChangeKind::Update,
+            payload_hash: Some("5f0c1a9e3b7d2c44".to_string()),
+            timestamp: chrono::Utc::now(),
+        };
+
+        let anonymous = PayloadView::new(redactor.clone(), Vec::new());
+        let mut change = event("customers");
+        redact_change(&anonymous, &mut change);
+        assert_eq!(change.payload_hash, None);
+        let mut change = event("orders");
+        redact_change(&anonymous, &mut change);
+        assert!(change.payload_hash.is_some());
+
+        let admin = PayloadView::new(redactor, vec![Role::Admin]);
+        let mut change = event("customers");
+        redact_change(&admin, &mut change);
+        assert!(change.payload_hash.is_some());
+    }
+
+    #[test]
+    fn ping_parses_and_errors_serialize() {
+        let ping: ClientFrame = serde_json::from_str(r#"{"op":"ping"}"#).unwrap();
+        assert!(matches!(ping, ClientFrame::Ping));
+
+        let err = serde_json::to_string(&ServerFrame::Error {
+            code: ErrorCode::StreamLag,
+        })
+        .unwrap();
+        assert_eq!(err, r#"{"op":"error","code":"stream_lag"}"#);
+    }
+}
@@ -5,7 +5,17 @@
 //! intervals on `/metrics/runtime`, `/stats`, `/status`, `/collections`,
 //! `/logs`, etc.). REST endpoints stay live as a fallback for SDK
 //! callers.
+//!
+//! `GET /ws/changes` streams the change feed (vector inserts, updates
+//! and deletes) to clients that prefer a socket over SSE.
+//!
+//! `GET /ws/subscriptions` holds standing searches and pushes the
+//! vectors that enter their top-k as they are written.
 
+pub mod changes;
 pub mod dashboard;
+pub mod subscriptions;
 
+pub use changes::changes_ws_handler;
 pub use dashboard::dashboard_ws_handler;
+pub use subscriptions::subscriptions_ws_handler;
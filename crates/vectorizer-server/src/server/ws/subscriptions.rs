@@ -0,0 +1,433 @@
+//! `GET /ws/subscriptions` handler — live search subscriptions.
+//!
+//! A client registers standing searches (collection + query + optional
+//! filter) and is told whenever a newly inserted or updated vector
+//! enters one's top-`k`. Matching runs on the change feed, so the
+//! endpoint needs `change_feed.enabled`; without it the upgrade is
+//! refused with 404 `change_feed_disabled`.
+//!
+//! ## Wire protocol
+//!
+//! ```jsonc
+//! // Client → server
+//! {"op": "subscribe", "id": "s1", "collection": "docs",
+//!  "query": "rust async runtimes",           // or "vector": [0.1, …]
+//!  "k": 10, "filter": {"must": [...]}, "min_score": 0.5}
+//! {"op": "unsubscribe", "id": "s1"}
+//! {"op": "ping"}
+//!
+//! // Server → client
+//! {"op": "subscribed", "id": "s1", "results": [{"id": "v1", "score": 0.91, "payload": {…}}]}
+//! {"op": "match", "id": "s1", "vector_id": "v9", "kind": "insert",
+//!  "score": 0.93, "rank": 1, "seq": 42, "payload": {…}}
+//! {"op": "unsubscribed", "id": "s1"}
+//! {"op": "pong"}
+//! {"op": "error", "code": "bad_subscription", "id": "s1", "message": "…"}
+//! ```
+//!
+//! `subscribed` carries the current top-`k`, so the client starts from
+//! the same list the matches are ranked against. Subscription ids are
+//! chosen by the client; subscribing again with a live id replaces it.
+//!
+//! ## Access
+//!
+//! The route sits behind the regular data-plane auth. Each subscription
+//! is checked like a search: the API key's namespace must cover the
+//! collection and, in multi-tenant mode, the tenant must own it. The
+//! payloads in `subscribed` and `match` frames go through the caller's
+//! `api.payload_redaction` rules, as search responses do.
+//!
+//! ## Slow consumers
+//!
+//! A connection that falls more than `change_feed.buffer_size` events
+//! behind gets a single `{op: "error", code: "stream_lag"}` frame and is
+//! closed. Subscribing again returns a fresh top-`k`.
+
+use std::collections::HashMap;
+
+use axum::Extension;
+use axum::extract::State;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::response::IntoResponse;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use tokio::sync::broadcast::Receiver;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{debug, warn};
+use uuid::Uuid;
+use vectorizer::auth::middleware::AuthState;
+use vectorizer::db::ChangeEvent;
+use vectorizer::hub::middleware::RequestTenantContext;
+use vectorizer::models::qdrant::QdrantFilter;
+use vectorizer::search::{LiveMatch, LiveQuery};
+use vectorizer::security::PayloadView;
+
+use crate::server::VectorizerServer;
+use crate::server::core::caller_payload_view;
+use crate::server::error_middleware::ErrorResponse;
+use crate::server::rest_handlers::change_feed;
+
+/// Most subscriptions one connection may hold.
+const MAX_SUBSCRIPTIONS: usize = 32;
+
+/// Frames the client sends. `op` is the discriminator.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum ClientFrame {
+    Subscribe(SubscribeRequest),
+    Unsubscribe { id: String },
+    Ping,
+}
+
+/// A standing search to register.
+#[derive(Debug, Clone, Deserialize)]
+struct SubscribeRequest {
+    id: String,
+    collection: String,
+    /// Query text, embedded with the server's embedding provider.
+    #[serde(default)]
+    query: Option<String>,
+    /// Query vector, instead of `query`.
+    #[serde(default)]
+    vector: Option<Vec<f32>>,
+    #[serde(default = "default_k")]
+    k: usize,
+    #[serde(default)]
+    filter: Option<QdrantFilter>,
+    #[serde(default)]
+    min_score: Option<f32>,
+}
+
+fn default_k() -> usize {
+    10
+}
+
+/// Frames the server sends.
+#[derive(Debug, Serialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum ServerFrame<'a> {
+    Subscribed {
+        id: &'a str,
+        results: Vec<Value>,
+    },
+    Match {
+        id: &'a str,
+        #[serde(flatten)]
+        hit: &'a LiveMatch,
+    },
+    Unsubscribed {
+        id: &'a str,
+    },
+    Pong,
+    Error {
+        code: ErrorCode,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        id: Option<&'a str>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        message: Option<String>,
+    },
+}
+
+impl ServerFrame<'_> {
+    fn error(code: ErrorCode) -> Self {
+        ServerFrame::Error {
+            code,
+            id: None,
+            message: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum ErrorCode {
+    /// Client fell behind the feed; the connection closes.
+    StreamLag,
+    /// Client sent a frame that did not parse against `ClientFrame`.
+    BadFrame,
+    /// A subscription could not be registered (unknown collection,
+    /// wrong dimension, bad `k`, too many subscriptions, ...).
+    BadSubscription,
+    /// The API key or tenant may not read the collection.
+    Forbidden,
+    /// A subscription stopped, e.g. because its collection was deleted.
+    SubscriptionEnded,
+}
+
+/// Who opened the connection, for the per-subscription access checks
+/// and the redaction of pushed payloads.
+struct Caller {
+    auth: Option<AuthState>,
+    tenant_id: Option<Uuid>,
+    view: PayloadView,
+}
+
+/// `GET /ws/subscriptions` upgrade handler.
+pub async fn subscriptions_ws_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<VectorizerServer>,
+    auth_state: Option<Extension<AuthState>>,
+    tenant_ctx: Option<Extension<RequestTenantContext>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let events = change_feed(&state)?.subscribe();
+    let auth = auth_state.map(|Extension(auth)| auth);
+    let caller = Caller {
+        view: caller_payload_view(state.payload_redactor.clone(), auth.as_ref()),
+        auth,
+        tenant_id: tenant_ctx.and_then(|ctx| Uuid::parse_str(&ctx.0.0.tenant_id).ok()),
+    };
+    Ok(ws.on_upgrade(move |socket| serve_connection(socket, state, caller, events)))
+}
+
+async fn serve_connection(
+    mut socket: WebSocket,
+    state: VectorizerServer,
+    caller: Caller,
+    mut events: Receiver<ChangeEvent>,
+) {
+    let mut subscriptions: HashMap<String, LiveQuery> = HashMap::new();
+    loop {
+        tokio::select! {
+            biased;
+
+            incoming = socket.recv() => {
+                match incoming {
+                    Some(Ok(Message::Text(txt))) => {
+                        let sent = match serde_json::from_str::<ClientFrame>(&txt) {
+                            Ok(ClientFrame::Subscribe(request)) => {
+                                let reply = subscribe(&state, &caller, &mut subscriptions, &request);
+                                send(&mut socket, &reply).await
+                            }
+                            Ok(ClientFrame::Unsubscribe { id }) => {
+                                subscriptions.remove(&id);
+                                send(&mut socket, &ServerFrame::Unsubscribed { id: &id }).await
+                            }
+                            Ok(ClientFrame::Ping) => send(&mut socket, &ServerFrame::Pong).await,
+                            Err(e) => {
+                                debug!(error = %e, "ws subscriptions: client frame did not parse");
+                                let reply = ServerFrame::Error {
+                                    code: ErrorCode::BadFrame,
+                                    id: None,
+                                    message: Some(e.to_string()),
+                                };
+                                send(&mut socket, &reply).await
+                            }
+                        };
+                        if sent.is_err() {
+                            return;
+                        }
+                    }
+                    Some(Ok(Message::Pong(_))) | Some(Ok(Message::Ping(_))) => {}
+                    Some(Ok(Message::Binary(_))) => {
+                        if send(&mut socket, &ServerFrame::error(ErrorCode::BadFrame)).await.is_err() {
+                            return;
+                        }
+                    }
+                    Some(Ok(Message::Close(_))) | None => return,
+                    Some(Err(e)) => {
+                        warn!(error = %e, "ws subscriptions: read error, closing connection");
+                        return;
+                    }
+                }
+            }
+
+            next = events.recv() => {
+                match next {
+                    Ok(event) => {
+                        if notify(&mut socket, &state, &caller, &mut subscriptions, &event).await.is_err() {
+                            return;
+                        }
+                    }
+                    Err(RecvError::Lagged(n)) => {
+                        debug!(lagged = n, "ws subscriptions: client lagged, dropping");
+                        let _ = send(&mut socket, &ServerFrame::error(ErrorCode::StreamLag)).await;
+                        return;
+                    }
+                    Err(RecvError::Closed) => return,
+                }
+            }
+        }
+    }
+}
+
+/// Register `request` and build the reply frame.
+fn subscribe<'a>(
+    state: &VectorizerServer,
+    caller: &Caller,
+    subscriptions: &mut HashMap<String, LiveQuery>,
+    request: &'a SubscribeRequest,
+) -> ServerFrame<'a> {
+    let id = request.id.as_str();
+    let rejected = move |code, message: String| ServerFrame::Error {
+        code,
+        id: Some(id),
+        message: Some(message),
+    };
+
+    if !subscriptions.contains_key(&request.id) && subscriptions.len() >= MAX_SUBSCRIPTIONS {
+        return rejected(
+            ErrorCode::BadSubscription,
+            format!("at most {} subscriptions per connection", MAX_SUBSCRIPTIONS),
+        );
+    }
+    if let Some(auth) = &caller.auth
+        && !auth.user_claims.can_access_collection(&request.collection)
+    {
+        return rejected(
+            ErrorCode::Forbidden,
+            format!("no access to collection '{}'", request.collection),
+        );
+    }
+    if let Err(e) = state
+        .store
+        .get_collection_with_owner(&request.collection, caller.tenant_id.as_ref())
+    {
+        return rejected(ErrorCode::Forbidden, e.to_string());
+    }
+
+    let query = match (&request.vector, &request.query) {
+        (Some(vector), _) => vector.clone(),
+        (None, Some(text)) => match state.embedding_manager.embed(text) {
+            Ok(vector) => vector,
+            Err(e) => {
+                return rejected(
+                    ErrorCode::BadSubscription,
+                    format!("Failed to generate embedding: {}", e),
+                );
+            }
+        },
+        (None, None) => {
+            return rejected(
+                ErrorCode::BadSubscription,
+                "either query or vector is required".to_string(),
+            );
+        }
+    };
+
+    match LiveQuery::new(
+        &state.store,
+        &request.collection,
+        query,
+        request.k,
+        request.filter.clone(),
+        request.min_score,
+    ) {
+        Ok((live, mut results)) => {
+            subscriptions.insert(request.id.clone(), live);
+            caller
+                .view
+                .redact_results(&request.collection, &mut results);
+            let results = results
+                .into_iter()
+                .map(|r| {
+                    json!({
+                        "id": r.id,
+                        "score": r.score,
+                        "payload": r.payload.map(|p| p.data),
+                    })
+                })
+                .collect();
+            ServerFrame::Subscribed { id, results }
+        }
+        Err(e) => rejected(ErrorCode::BadSubscription, e.to_string()),
+    }
+}
+
+/// Run `event` through every subscription and push the matches, redacted
+/// for `caller`. A subscription whose collection can no longer be
+/// searched is ended.
+async fn notify(
+    socket: &mut WebSocket,
+    state: &VectorizerServer,
+    caller: &Caller,
+    subscriptions: &mut HashMap<String, LiveQuery>,
+    event: &ChangeEvent,
+) -> Result<(), axum::Error> {
+    let mut ended = Vec::new();
+    for (id, live) in subscriptions.iter_mut() {
+        match live.apply(&state.store, event) {
+            Ok(Some(mut hit)) => {
+                if let Some(payload) = hit.payload.as_mut() {
+                    caller.view.redact_value(live.collection(), payload);
+                }
+                send(socket, &ServerFrame::Match { id, hit: &hit }).await?
+            }
+            Ok(None) => {}
+            Err(e) => {
+                let reply = ServerFrame::Error {
+                    code: ErrorCode::SubscriptionEnded,
+                    id: Some(id),
+                    message: Some(e.to_string()),
+                };
+                send(socket, &reply).await?;
+                ended.push(id.clone());
+            }
+        }
+    }
+    for id in ended {
+        subscriptions.remove(&id);
+    }
+    Ok(())
+}
+
+async fn send(socket: &mut WebSocket, frame: &ServerFrame<'_>) -> Result<(), axum::Error> {
+    let frame = serde_json::to_string(frame).map_err(axum::Error::new)?;
+    socket.send(Message::Text(frame.into())).await
+}
+
+#[cfg(test)]
+mod tests {
+    use vectorizer::db::ChangeKind;
+
+    use super::*;
+
+    #[test]
+    fn subscribe_frame_parses_with_defaults() {
+        let frame: ClientFrame = serde_json::from_str(
+            r#"{"op":"subscribe","id":"s1","collection":"docs","query":"rust"}"#,
+        )
+        .unwrap();
+        let ClientFrame::Subscribe(request) = frame else {
+            panic!("expected a subscribe frame");
+        };
+        assert_eq!(request.id, "s1");
+        assert_eq!(request.query.as_deref(), Some("rust"));
+        assert_eq!(request.k, 10);
+        assert!(request.vector.is_none() && request.filter.is_none());
+
+        let frame: ClientFrame = serde_json::from_str(
+            r#"{"op":"subscribe","id":"s2","collection":"docs","vector":[0.5,0.5],"k":3,
+                "filter":{"must":[{"type":"match","key":"lang","match_value":"pt"}]}}"#,
+        )
+        .unwrap();
+        let ClientFrame::Subscribe(request) = frame else {
+            panic!("expected a subscribe frame");
+        };
+        assert_eq!(request.k, 3);
+        assert!(request.filter.is_some());
+    }
+
+    #[test]
+    fn match_frame_flattens_the_hit() {
+        let hit = LiveMatch {
+            vector_id: "v9".to_string(),
+            kind: ChangeKind::Insert,
+            score: 0.5,
+            rank: 1,
+            seq: 42,
+            payload: None,
+        };
+        let json = serde_json::to_string(&ServerFrame::Match {
+            id: "s1",
+            hit: &hit,
+        })
+        .unwrap();
+        assert_eq!(
+            json,
+            r#"{"op":"match","id":"s1","vector_id":"v9","kind":"insert","score":0.5,"rank":1,"seq":42,"payload":null}"#
+        );
+
+        let err = serde_json::to_string(&ServerFrame::error(ErrorCode::StreamLag)).unwrap();
+        assert_eq!(err, r#"{"op":"error","code":"stream_lag"}"#);
+    }
+}
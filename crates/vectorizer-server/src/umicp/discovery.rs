@@ -26,10 +26,10 @@ impl DiscoverableService for VectorizerDiscoveryService {
             "workspace-management".to_string(),
             "mcp-compatible".to_string(),
         ])
-        .operations_count(48)
+        .operations_count(51)
         .mcp_compatible(true)
         .metadata(json!({
-            "description": "HiveLLM Vectorizer - High-performance semantic search and vector database system with 48 tools"
+            "description": "HiveLLM Vectorizer - High-performance semantic search and vector database system with 51 tools"
         }))
     }
 
@@ -97,14 +97,16 @@ mod tests {
         let service = VectorizerDiscoveryService;
         let operations = service.list_operations();
 
-        // 48 individual focused operations (32 from phase3+/phase33 +
+        // 51 individual focused operations (32 from phase3+/phase33 +
         // the 16 MCP tools phase40 §2 added: delete_collection,
         // embed_text, contextual_search, get_database_stats, the 8-step
-        // discovery pipeline, and the 4 batch_* operations).
+        // discovery pipeline, and the 4 batch_* operations + the
+        // get_file_summary, search_by_file_type and list_workspaces
+        // tools).
         assert_eq!(
             operations.len(),
-            48,
-            "Expected 48 individual operations, got {}",
+            51,
+            "Expected 51 individual operations, got {}",
             operations.len()
         );
 
@@ -44,6 +44,7 @@ pub async fn handle_umicp_request(state: UmicpState, envelope: Envelope) -> Resu
         state.embedding_manager.clone(),
         None, // Cluster manager not available in UMICP context
         state.upsert_queue.clone(),
+        state.hub_manager.clone(),
     )
     .await;
 
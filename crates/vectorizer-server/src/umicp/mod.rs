@@ -25,6 +25,9 @@ pub struct UmicpState {
     /// into `handle_mcp_tool` so UMICP-driven inserts honor the same
     /// queue as REST/gRPC/MCP.
     pub upsert_queue: std::sync::Arc<vectorizer::db::UpsertQueue>,
+    /// HiveHub integration, routed into `handle_mcp_tool` so UMICP
+    /// inserts are admitted against the same tenant quotas.
+    pub hub_manager: Option<std::sync::Arc<vectorizer::hub::HubManager>>,
 }
 
 /// Health check for UMICP endpoint
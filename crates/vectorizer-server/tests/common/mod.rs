@@ -29,6 +29,9 @@
 //!   handler returns an error because `hub_manager` is `None` — this
 //!   harness never constructs a `HubManager` (it requires an external
 //!   HiveHub connection). Not exercised by any test built on this harness.
+//!   `tests/rest_tenant_quota.rs` attaches one through
+//!   [`TestApp::with_server`] to drive the hub middleware and the
+//!   node-local tenant quotas, which need no HiveHub to refuse a write.
 //! - **Cluster-only routes** (`crate::api::cluster`): only merged by
 //!   `build_router` when `cluster_manager` + `cluster_client_pool` are
 //!   `Some`; both are `None` here, so the group is absent entirely.
@@ -87,12 +90,27 @@ const BM25_SEED_CORPUS: &[&str] = &[
     "machine learning models transform text into numeric vectors",
 ];
 
+/// Dimension of the harness's default `bm25` provider. Tests that insert
+/// raw vectors size their collections to match, so the text endpoints
+/// stay usable on the same collection.
+#[allow(dead_code)]
+pub const EMBEDDING_DIM: usize = 512;
+
+/// An [`EMBEDDING_DIM`]-long vector as JSON: `head` fills the leading
+/// components and the rest are zero.
+#[allow(dead_code)]
+pub fn embedding(head: &[f32]) -> Value {
+    let mut v = vec![0.0f32; EMBEDDING_DIM];
+    v[..head.len()].copy_from_slice(head);
+    serde_json::json!(v)
+}
+
 /// Build a real `EmbeddingManager` with a BM25 provider fitted on
 /// [`BM25_SEED_CORPUS`], registered as `"bm25"` and set default. Shared by
 /// every [`TestApp`] constructor so each one wires the exact same
 /// embedding behavior into the production router.
 fn build_embedding_manager() -> Arc<EmbeddingManager> {
-    let mut bm25 = Bm25Embedding::new(512);
+    let mut bm25 = Bm25Embedding::new(EMBEDDING_DIM);
     bm25.build_vocabulary(
         &BM25_SEED_CORPUS
             .iter()
@@ -171,6 +189,26 @@ impl TestApp {
         }
     }
 
+    /// Like [`TestApp::new`], but lets the caller adjust the harness
+    /// `VectorizerServer` (any of its `pub` fields) before the router is
+    /// built — e.g. to attach a server-wide policy that `bootstrap.rs`
+    /// would normally derive from `config.yml`.
+    #[allow(dead_code)]
+    pub async fn with_server(configure: impl FnOnce(&mut VectorizerServer)) -> Self {
+        let data_dir = point_data_dir_at_temp_dir();
+
+        let store = Arc::new(VectorStore::new_cpu_only());
+        let embedding_manager = build_embedding_manager();
+        let mut server = VectorizerServer::new_for_test_harness(store, embedding_manager);
+        configure(&mut server);
+        let router = server.build_router(false).await;
+
+        Self {
+            router,
+            temp_dir: data_dir,
+        }
+    }
+
     /// Build a [`TestApp`] with authentication ENABLED, mirroring how
     /// `bootstrap.rs` wires a real `AuthManager` + `AuthHandlerState`
     /// (see `VectorizerServer::new_with_root_config`'s `auth_handler_state`
@@ -337,6 +375,34 @@ impl TestApp {
         self.dispatch(req).await
     }
 
+    /// Dispatch `<method> <path>` with an optional JSON body and one
+    /// extra header (`Authorization`, `X-API-Key`, ...) through the real
+    /// router. For auth suites built on [`TestApp::with_auth`] that act
+    /// as a specific JWT or API key on more than `GET`.
+    #[allow(dead_code)]
+    pub async fn request_with_header(
+        &self,
+        method: &str,
+        path: &str,
+        (name, value): (&str, &str),
+        body: Option<Value>,
+    ) -> (StatusCode, Value) {
+        let builder = Request::builder()
+            .method(method)
+            .uri(path)
+            .header(name, value);
+        let req = match body {
+            Some(body) => builder
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(
+                    serde_json::to_vec(&body).expect("serialize request body"),
+                )),
+            None => builder.body(Body::empty()),
+        }
+        .expect("build request");
+        self.dispatch(req).await
+    }
+
     /// Dispatch `POST <path>` with an arbitrary raw body and explicit
     /// `Content-Type`, bypassing `serde_json` serialization entirely.
     ///
@@ -385,6 +451,110 @@ impl TestApp {
         .await
     }
 
+    /// Dispatch `POST <path>` with a JSON body and return the raw
+    /// response: status, `Content-Type` and body bytes. For endpoints
+    /// that answer with files rather than JSON.
+    #[allow(dead_code)]
+    pub async fn post_json_for_bytes(
+        &self,
+        path: &str,
+        body: Value,
+    ) -> (StatusCode, Option<String>, Vec<u8>) {
+        let req = Request::builder()
+            .method("POST")
+            .uri(path)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(
+                serde_json::to_vec(&body).expect("serialize request body"),
+            ))
+            .expect("build POST request");
+        self.dispatch_for_bytes(req).await
+    }
+
+    /// Like [`TestApp::post_raw`], but returns the raw response like
+    /// [`TestApp::post_json_for_bytes`]. For endpoints that stream
+    /// non-JSON bodies (NDJSON progress events, files) in both
+    /// directions.
+    #[allow(dead_code)]
+    pub async fn post_raw_for_bytes(
+        &self,
+        path: &str,
+        content_type: &str,
+        body: &[u8],
+    ) -> (StatusCode, Option<String>, Vec<u8>) {
+        let req = Request::builder()
+            .method("POST")
+            .uri(path)
+            .header(header::CONTENT_TYPE, content_type)
+            .body(Body::from(body.to_vec()))
+            .expect("build POST request");
+        self.dispatch_for_bytes(req).await
+    }
+
+    /// Dispatch `GET <path>` and return the response without reading its
+    /// body, for endpoints that stream events for as long as the client
+    /// listens (SSE).
+    #[allow(dead_code)]
+    pub async fn get_streaming(&self, path: &str) -> axum::response::Response {
+        let req = Request::builder()
+            .method("GET")
+            .uri(path)
+            .body(Body::empty())
+            .expect("build GET request");
+        self.router
+            .clone()
+            .oneshot(req)
+            .await
+            .expect("router dispatch must complete")
+    }
+
+    /// `POST /collections` with `body`, asserting the collection was
+    /// created. Returns the response body.
+    #[allow(dead_code)]
+    pub async fn create_collection(&self, body: Value) -> Value {
+        let (status, resp) = self.post_json("/collections", body).await;
+        assert!(status.is_success(), "create status {status}: {resp}");
+        resp
+    }
+
+    /// `POST /insert_vectors` of `vectors` (an array of `{id, embedding,
+    /// payload}` objects) into `collection`, asserting the request
+    /// succeeded. Returns the response body.
+    #[allow(dead_code)]
+    pub async fn insert_vectors(&self, collection: &str, vectors: Value) -> Value {
+        let (status, resp) = self
+            .post_json(
+                "/insert_vectors",
+                serde_json::json!({"collection": collection, "vectors": vectors}),
+            )
+            .await;
+        assert!(status.is_success(), "insert status {status}: {resp}");
+        resp
+    }
+
+    #[allow(dead_code)]
+    async fn dispatch_for_bytes(
+        &self,
+        req: Request<Body>,
+    ) -> (StatusCode, Option<String>, Vec<u8>) {
+        let response = self
+            .router
+            .clone()
+            .oneshot(req)
+            .await
+            .expect("router dispatch must complete");
+        let status = response.status();
+        let content_type = response
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        let bytes = to_bytes(response.into_body(), 16 * 1024 * 1024)
+            .await
+            .expect("collect response body");
+        (status, content_type, bytes.to_vec())
+    }
+
     async fn dispatch(&self, req: Request<Body>) -> (StatusCode, Value) {
         let response = self
             .router
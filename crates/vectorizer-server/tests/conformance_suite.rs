@@ -0,0 +1,36 @@
+//! SDK conformance suite (`conformance/suite.json`): every canonical
+//! request gets the response the suite promises SDKs, so the suite
+//! cannot drift from the server it ships with.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use std::sync::Arc;
+
+use common::TestApp;
+use serde_json::Value;
+use vectorizer_server::server::conformance::{check_response, embedding_manager, suite};
+
+#[tokio::test]
+async fn server_passes_its_own_conformance_suite() {
+    let embedder = Arc::new(embedding_manager().expect("conformance embedder"));
+    let app = TestApp::with_server(|server| server.embedding_manager = embedder).await;
+    let suite = suite().expect("suite.json parses");
+
+    for case in &suite.cases {
+        let request = &case.request;
+        let body = request.body.clone().unwrap_or(Value::Null);
+        let (status, resp) = match request.method.as_str() {
+            "GET" => app.get(&request.path).await,
+            "POST" => app.post_json(&request.path, body).await,
+            "PUT" => app.put_json(&request.path, body).await,
+            "PATCH" => app.patch_json(&request.path, body).await,
+            "DELETE" => app.delete(&request.path).await,
+            other => panic!("{}: unsupported method {other}", case.id),
+        };
+        if let Err(mismatch) = check_response(&case.expect, status.as_u16(), &resp) {
+            panic!("{}: {mismatch}\nresponse: {resp}", case.id);
+        }
+    }
+}
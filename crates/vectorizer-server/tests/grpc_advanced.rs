@@ -338,6 +338,13 @@ async fn test_empty_collection_operations() {
         sharding: None,
         graph: None, // Graph disabled for tests,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
     store.create_collection("empty_collection", config).unwrap();
 
@@ -390,6 +397,13 @@ async fn test_large_payloads() {
         sharding: None,
         graph: None, // Graph disabled for tests,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
     store.create_collection("large_payload", config).unwrap();
 
@@ -446,6 +460,13 @@ async fn test_search_with_threshold() {
         sharding: None,
         graph: None, // Graph disabled for tests,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
     store.create_collection("threshold_test", config).unwrap();
 
@@ -512,6 +533,13 @@ async fn test_multiple_collections_simultaneously() {
             sharding: None,
             graph: None, // Graph disabled for tests,
             encryption: None,
+            id_policy: None,
+            quota: None,
+            scoring: None,
+            tenant_partition: None,
+            dedup: None,
+            chunking: None,
+            full_text: None,
         };
         store
             .create_collection(&format!("collection_{i}"), config)
@@ -579,6 +607,13 @@ async fn test_concurrent_operations() {
         sharding: None,
         graph: None, // Graph disabled for tests,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
     store.create_collection("concurrent_test", config).unwrap();
 
@@ -684,6 +719,13 @@ async fn test_batch_operations_stress() {
         sharding: None,
         graph: None, // Graph disabled for tests,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
     store.create_collection("batch_stress", config).unwrap();
 
@@ -736,6 +778,13 @@ async fn test_search_with_filters() {
         sharding: None,
         graph: None, // Graph disabled for tests,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
     store.create_collection("filter_test", config).unwrap();
 
@@ -804,6 +853,13 @@ async fn test_update_nonexistent_vector() {
         sharding: None,
         graph: None, // Graph disabled for tests,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
     store.create_collection("update_test", config).unwrap();
 
@@ -844,6 +900,13 @@ async fn test_delete_nonexistent_vector() {
         sharding: None,
         graph: None, // Graph disabled for tests,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
     store.create_collection("delete_test", config).unwrap();
 
@@ -883,6 +946,13 @@ async fn test_very_large_vectors() {
         sharding: None,
         graph: None, // Graph disabled for tests,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
     store.create_collection("large_vectors", config).unwrap();
 
@@ -938,6 +1008,13 @@ async fn test_multiple_batch_searches() {
         sharding: None,
         graph: None, // Graph disabled for tests,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
     store
         .create_collection("batch_search_test", config)
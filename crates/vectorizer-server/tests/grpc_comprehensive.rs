@@ -49,6 +49,13 @@ fn create_test_config() -> CollectionConfig {
         sharding: None,
         graph: None, // Graph disabled for tests
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     }
 }
 
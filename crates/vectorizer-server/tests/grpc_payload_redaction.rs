@@ -0,0 +1,138 @@
+//! `api.payload_redaction` on the gRPC services.
+//!
+//! The server's namespace layer attaches the caller's `PayloadView` to
+//! every call; these tests attach one by hand and call the native and
+//! Qdrant-compatible handlers directly.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use std::sync::Arc;
+
+use serde_json::json;
+use tonic::Request;
+use vectorizer::auth::Secret;
+use vectorizer::auth::roles::Role;
+use vectorizer::config::sections::redaction::{
+    PayloadRedactionConfig, RedactionAction, RedactionRule,
+};
+use vectorizer::db::{UpsertQueue, VectorStore};
+use vectorizer::models::{CollectionConfig, Payload, Vector};
+use vectorizer::security::{PayloadRedactor, PayloadView};
+use vectorizer_protocol::grpc_gen::qdrant_proto::points_server::Points;
+use vectorizer_protocol::grpc_gen::qdrant_proto::{GetPoints, PointId, point_id, value};
+use vectorizer_protocol::grpc_gen::vectorizer::vectorizer_service_server::VectorizerService;
+use vectorizer_protocol::grpc_gen::vectorizer::{GetVectorRequest, SearchRequest};
+use vectorizer_server::grpc::{QdrantGrpcService, VectorizerGrpcService};
+
+const COLLECTION: &str = "customers";
+
+fn redactor() -> Arc<PayloadRedactor> {
+    let redactor = PayloadRedactor::from_config(&PayloadRedactionConfig {
+        enabled: true,
+        hash_salt: Some(Secret::new("test-salt".to_string())),
+        rules: vec![RedactionRule {
+            field: "email".to_string(),
+            action: RedactionAction::Mask,
+            collections: vec![COLLECTION.to_string()],
+            roles: Vec::new(),
+        }],
+        ..Default::default()
+    })
+    .unwrap()
+    .unwrap();
+    Arc::new(redactor)
+}
+
+fn seeded_store() -> Arc<VectorStore> {
+    let store = Arc::new(VectorStore::new());
+    store
+        .create_collection(COLLECTION, CollectionConfig::default())
+        .unwrap();
+    let data: Vec<f32> = (0..512).map(|i| (i as f32 * 0.01).cos()).collect();
+    store
+        .insert(
+            COLLECTION,
+            vec![Vector::with_payload(
+                "p1".to_string(),
+                data,
+                Payload::new(json!({"email": "alice@example.com", "title": "public"})),
+            )],
+        )
+        .unwrap();
+    store
+}
+
+fn with_view<T>(message: T, roles: Vec<Role>) -> Request<T> {
+    let mut request = Request::new(message);
+    request
+        .extensions_mut()
+        .insert(PayloadView::new(Some(redactor()), roles));
+    request
+}
+
+#[tokio::test]
+async fn native_get_and_search_redact_for_non_exempt_callers() {
+    let store = seeded_store();
+    let service = VectorizerGrpcService::new(store.clone(), Arc::new(UpsertQueue::permissive()));
+    let get = || GetVectorRequest {
+        collection_name: COLLECTION.to_string(),
+        vector_id: "p1".to_string(),
+    };
+
+    let vector = service
+        .get_vector(with_view(get(), Vec::new()))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(vector.payload["email"], "\"***\"");
+    assert_eq!(vector.payload["title"], "\"public\"");
+
+    let vector = service
+        .get_vector(with_view(get(), vec![Role::Admin]))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(vector.payload["email"], "\"alice@example.com\"");
+
+    let query = store.get_vector(COLLECTION, "p1").unwrap().data;
+    let results = service
+        .search(with_view(
+            SearchRequest {
+                collection_name: COLLECTION.to_string(),
+                query_vector: query,
+                limit: 1,
+                ..Default::default()
+            },
+            Vec::new(),
+        ))
+        .await
+        .unwrap()
+        .into_inner()
+        .results;
+    assert_eq!(results[0].payload["email"], "\"***\"");
+}
+
+#[tokio::test]
+async fn qdrant_get_points_redacts_for_non_exempt_callers() {
+    let service = QdrantGrpcService::new(seeded_store(), Arc::new(UpsertQueue::permissive()));
+    let request = with_view(
+        GetPoints {
+            collection_name: COLLECTION.to_string(),
+            ids: vec![PointId {
+                point_id_options: Some(point_id::PointIdOptions::Uuid("p1".to_string())),
+            }],
+            ..Default::default()
+        },
+        Vec::new(),
+    );
+
+    let points = service.get(request).await.unwrap().into_inner().result;
+    assert_eq!(
+        points[0].payload["email"].kind,
+        Some(value::Kind::StringValue("***".to_string()))
+    );
+    assert_eq!(
+        points[0].payload["title"].kind,
+        Some(value::Kind::StringValue("public".to_string()))
+    );
+}
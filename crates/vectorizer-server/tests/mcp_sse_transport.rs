@@ -0,0 +1,134 @@
+//! Legacy MCP SSE transport: `GET /sse` announces a `/message` endpoint,
+//! POSTed JSON-RPC messages are answered on the event stream, and
+//! messages for unknown sessions are refused.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use std::time::Duration;
+
+use axum::body::BodyDataStream;
+use common::TestApp;
+use futures::StreamExt;
+use serde_json::{Value, json};
+
+/// Read the next SSE event from `stream`, returning `(event, data)` and
+/// skipping keep-alive comments.
+async fn next_event(stream: &mut BodyDataStream, buffer: &mut String) -> (String, String) {
+    loop {
+        if let Some(end) = buffer.find("\n\n") {
+            let block: String = buffer.drain(..end + 2).collect();
+            let mut event = String::new();
+            let mut data = String::new();
+            for line in block.lines() {
+                if let Some(value) = line.strip_prefix("event:") {
+                    event = value.trim().to_string();
+                } else if let Some(value) = line.strip_prefix("data:") {
+                    data.push_str(value.trim());
+                }
+            }
+            if !event.is_empty() || !data.is_empty() {
+                return (event, data);
+            }
+            continue;
+        }
+        let chunk = tokio::time::timeout(Duration::from_secs(10), stream.next())
+            .await
+            .expect("event within 10s")
+            .expect("stream still open")
+            .expect("read event stream");
+        buffer.push_str(std::str::from_utf8(&chunk).expect("utf-8 event stream"));
+    }
+}
+
+/// The next `message` event, decoded.
+async fn next_message(stream: &mut BodyDataStream, buffer: &mut String) -> Value {
+    let (event, data) = next_event(stream, buffer).await;
+    assert_eq!(event, "message", "unexpected event: {data}");
+    serde_json::from_str(&data).expect("message is JSON")
+}
+
+#[tokio::test]
+async fn sse_session_answers_posted_requests_on_the_stream() {
+    let app = TestApp::new().await;
+    let response = app.get_streaming("/sse").await;
+    assert_eq!(response.status().as_u16(), 200);
+    let content_type = response.headers()["content-type"].to_str().unwrap();
+    assert!(
+        content_type.starts_with("text/event-stream"),
+        "{content_type}"
+    );
+
+    let mut stream = response.into_body().into_data_stream();
+    let mut buffer = String::new();
+    let (event, endpoint) = next_event(&mut stream, &mut buffer).await;
+    assert_eq!(event, "endpoint");
+    assert!(endpoint.starts_with("/message?sessionId="), "{endpoint}");
+
+    let (status, _) = app
+        .post_json(
+            &endpoint,
+            json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "initialize",
+                "params": {
+                    "protocolVersion": "2024-11-05",
+                    "capabilities": {},
+                    "clientInfo": {"name": "legacy-client", "version": "1.0"}
+                }
+            }),
+        )
+        .await;
+    assert_eq!(status.as_u16(), 202);
+    let init = next_message(&mut stream, &mut buffer).await;
+    assert_eq!(init["id"], 1);
+    assert_eq!(init["result"]["serverInfo"]["name"], "vectorizer-server");
+
+    let (status, _) = app
+        .post_json(
+            &endpoint,
+            json!({"jsonrpc": "2.0", "method": "notifications/initialized"}),
+        )
+        .await;
+    assert_eq!(status.as_u16(), 202);
+    let (status, _) = app
+        .post_json(
+            &endpoint,
+            json!({"jsonrpc": "2.0", "id": 2, "method": "tools/list"}),
+        )
+        .await;
+    assert_eq!(status.as_u16(), 202);
+    let tools = next_message(&mut stream, &mut buffer).await;
+    assert_eq!(tools["id"], 2);
+    let names: Vec<&str> = tools["result"]["tools"]
+        .as_array()
+        .expect("tools array")
+        .iter()
+        .filter_map(|tool| tool["name"].as_str())
+        .collect();
+    assert!(names.contains(&"list_collections"), "{names:?}");
+}
+
+#[tokio::test]
+async fn sse_message_rejects_unknown_sessions_and_bad_json() {
+    let app = TestApp::new().await;
+    let (status, resp) = app
+        .post_json(
+            "/message?sessionId=no-such-session",
+            json!({"jsonrpc": "2.0", "id": 1, "method": "ping"}),
+        )
+        .await;
+    assert_eq!(status.as_u16(), 404);
+    assert_eq!(resp["error_type"], "session_not_found");
+
+    let (status, _) = app
+        .post_raw(
+            "/message?sessionId=no-such-session",
+            "application/json",
+            b"{",
+        )
+        .await;
+    assert_eq!(status.as_u16(), 400);
+}
@@ -0,0 +1,64 @@
+//! `/admin/tasks` through the real router: listing background tasks and
+//! pausing one. Uses the `reindex` task, which runs on request rather
+//! than on a timer, so its runs and its pause are deterministic.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use common::{TestApp, embedding};
+use serde_json::{Value, json};
+
+fn task<'a>(list: &'a Value, name: &str) -> &'a Value {
+    list["tasks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|t| t["name"] == name)
+        .unwrap_or_else(|| panic!("task {name} not listed: {list}"))
+}
+
+#[tokio::test]
+async fn paused_reindex_task_refuses_reindexes_until_resumed() {
+    let app = TestApp::new().await;
+    let name = "background_tasks_reindex";
+    let _ = app.delete(&format!("/collections/{name}")).await;
+    let (status, resp) = app
+        .post_json("/collections", json!({"name": name, "dimension": 512}))
+        .await;
+    assert!(status.is_success(), "create status {status}: {resp}");
+    app.insert_vectors(name, json!([{"id": "v1", "embedding": embedding(&[1.0])}]))
+        .await;
+
+    let reindex = format!("/collections/{name}/reindex");
+    let (status, resp) = app.post_json(&reindex, json!({"m": 16})).await;
+    assert!(status.is_success(), "reindex status {status}: {resp}");
+
+    let (status, list) = app.get("/admin/tasks").await;
+    assert!(status.is_success(), "list status {status}: {list}");
+    let status_before = task(&list, "reindex");
+    assert_eq!(status_before["state"], "idle");
+    assert_eq!(status_before["runs"].as_u64(), Some(1));
+    assert!(status_before["last_run_at"].is_string());
+
+    let (status, resp) = app.post_json("/admin/tasks/reindex/pause", json!({})).await;
+    assert!(status.is_success(), "pause status {status}: {resp}");
+    assert_eq!(resp["task"]["state"], "paused");
+
+    let (status, resp) = app.post_json(&reindex, json!({"m": 16})).await;
+    assert_eq!(status.as_u16(), 503, "{resp}");
+    assert_eq!(resp["error_type"], "task_paused");
+
+    let (status, resp) = app
+        .post_json("/admin/tasks/reindex/resume", json!({}))
+        .await;
+    assert!(status.is_success(), "resume status {status}: {resp}");
+    let (status, resp) = app.post_json(&reindex, json!({"m": 16})).await;
+    assert!(status.is_success(), "reindex status {status}: {resp}");
+
+    let (_, list) = app.get("/admin/tasks").await;
+    assert_eq!(task(&list, "reindex")["runs"].as_u64(), Some(2));
+
+    let (status, _) = app.post_json("/admin/tasks/nope/pause", json!({})).await;
+    assert_eq!(status.as_u16(), 404);
+}
@@ -124,6 +124,66 @@ async fn batch_search_runs_multiple_queries() {
     }
 }
 
+#[tokio::test]
+async fn batch_search_keeps_entry_order_and_isolates_failures() {
+    let app = TestApp::new().await;
+    seed(&app, "batch_ops_real_parallel", 8).await;
+
+    let (_, embedded) = app
+        .post_json("/embed", json!({"text": "probe doc 3"}))
+        .await;
+    let vector = embedded["embedding"].clone();
+    let (status, single) = app
+        .post_json(
+            "/search",
+            json!({"collection": "batch_ops_real_parallel", "vector": vector, "limit": 3}),
+        )
+        .await;
+    assert!(
+        status.is_success(),
+        "POST /search status {status}: {single}"
+    );
+
+    let mut queries = vec![
+        json!({"vector": vector, "limit": 3}),
+        json!({"vector": [1.0, 2.0]}),
+        json!({"limit": 3}),
+        json!({"query": "probe doc 3", "limit": 3, "exact": true}),
+    ];
+    queries.extend((0..28).map(|i| json!({"query": format!("probe doc {i}"), "limit": 2})));
+    let (status, resp) = app
+        .post_json(
+            "/batch_search",
+            json!({"collection": "batch_ops_real_parallel", "queries": queries}),
+        )
+        .await;
+    assert!(
+        status.is_success(),
+        "POST /batch_search status {status}: {resp}"
+    );
+
+    assert_eq!(resp["count"].as_u64(), Some(32));
+    assert_eq!(resp["succeeded"].as_u64(), Some(30));
+    assert_eq!(resp["failed"].as_u64(), Some(2));
+    let results = resp["results"].as_array().unwrap();
+    for (i, r) in results.iter().enumerate() {
+        assert_eq!(r["index"].as_u64(), Some(i as u64));
+    }
+    let ids = |r: &Value| -> Vec<Value> {
+        r["results"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|h| h["id"].clone())
+            .collect()
+    };
+    assert_eq!(ids(&results[0]), ids(&single));
+    assert_eq!(results[1]["status"].as_str(), Some("error"));
+    assert_eq!(results[2]["status"].as_str(), Some("error"));
+    assert_eq!(results[3]["exact"], json!(true));
+    assert_eq!(results[3]["query"].as_str(), Some("probe doc 3"));
+}
+
 #[tokio::test]
 async fn batch_update_overwrites_payload() {
     let app = TestApp::new().await;
@@ -0,0 +1,74 @@
+//! `POST /collections/{name}/cold` through the real router.
+//!
+//! Covers that a cold collection writes its HNSW graph to
+//! `{data_dir}/hnsw/{name}.vzhnsw`, keeps answering searches from it,
+//! reports `"cold": true`, and turns warm again (file removed) on the
+//! next write.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use common::{TestApp, embedding};
+use serde_json::json;
+
+async fn top_hit(app: &TestApp, name: &str, query: &[f32]) -> String {
+    let (status, resp) = app
+        .post_json(
+            &format!("/collections/{name}/search"),
+            json!({"vector": embedding(query), "limit": 2}),
+        )
+        .await;
+    assert!(status.is_success(), "search status {status}: {resp}");
+    resp["results"][0]["id"].as_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn cold_collection_serves_searches_from_disk_until_written() {
+    let app = TestApp::new().await;
+    let name = "cold_index_round_trip";
+    let _ = app.delete(&format!("/collections/{name}")).await;
+
+    app.create_collection(json!({"name": name, "dimension": 512, "metric": "euclidean"}))
+        .await;
+
+    app.insert_vectors(
+        name,
+        json!([
+            {"id": "x", "embedding": embedding(&[1.0])},
+            {"id": "y", "embedding": embedding(&[0.0, 1.0])},
+            {"id": "z", "embedding": embedding(&[0.0, 0.0, 1.0])},
+        ]),
+    )
+    .await;
+
+    let (status, resp) = app
+        .post_json(&format!("/collections/{name}/cold"), json!({}))
+        .await;
+    assert!(status.is_success(), "cold status {status}: {resp}");
+    assert_eq!(resp["cold"].as_bool(), Some(true));
+
+    let index_file = app.data_dir().join("hnsw").join(format!("{name}.vzhnsw"));
+    assert!(index_file.exists(), "{} missing", index_file.display());
+
+    let (_, info) = app.get(&format!("/collections/{name}")).await;
+    assert_eq!(info["cold"].as_bool(), Some(true));
+    assert_eq!(top_hit(&app, name, &[0.0, 0.9]).await, "y");
+
+    app.insert_vectors(
+        name,
+        json!([{"id": "w", "embedding": embedding(&[0.0, 0.0, 0.0, 1.0])}]),
+    )
+    .await;
+
+    let (_, info) = app.get(&format!("/collections/{name}")).await;
+    assert_eq!(info["cold"].as_bool(), Some(false));
+    assert!(!index_file.exists());
+    assert_eq!(top_hit(&app, name, &[0.0, 0.0, 0.0, 0.9]).await, "w");
+    assert_eq!(top_hit(&app, name, &[0.0, 0.9]).await, "y");
+
+    let (status, _) = app
+        .post_json(&format!("/collections/{name}/cold"), json!({"cold": "yes"}))
+        .await;
+    assert_eq!(status.as_u16(), 400);
+}
@@ -0,0 +1,108 @@
+//! `POST /collections/{name}/aggregate`: the mean and medoid of the
+//! vectors matching a filter, and a search with the aggregate that
+//! leaves the members out.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use common::{TestApp, embedding};
+use serde_json::json;
+
+async fn seed(app: &TestApp) {
+    app.create_collection(json!({"name": "projects", "dimension": 512, "metric": "euclidean"}))
+        .await;
+
+    app.insert_vectors(
+        "projects",
+        json!([
+            {"id": "x1", "embedding": embedding(&[0.0, 0.0]), "payload": {"project": "x"}},
+            {"id": "x2", "embedding": embedding(&[2.0, 0.0]), "payload": {"project": "x"}},
+            {"id": "x3", "embedding": embedding(&[4.0, 3.0]), "payload": {"project": "x"}},
+            {"id": "near", "embedding": embedding(&[2.0, 1.0]), "payload": {"project": "y"}},
+            {"id": "far", "embedding": embedding(&[50.0, 50.0]), "payload": {"project": "y"}},
+        ]),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn mean_of_a_filter_searches_for_similar_vectors() {
+    let app = TestApp::new().await;
+    seed(&app).await;
+
+    let (status, resp) = app
+        .post_json(
+            "/collections/projects/aggregate",
+            json!({"filter": {"project": "x"}, "search": {"limit": 2}}),
+        )
+        .await;
+    assert!(status.is_success(), "status {status}: {resp}");
+    assert_eq!(resp["method"], "mean");
+    assert_eq!(resp["matched"], 3);
+    assert!(resp.get("medoid_id").is_none());
+    let vector = resp["vector"].as_array().unwrap();
+    assert_eq!(vector.len(), 512);
+    assert_eq!(vector[0], 2.0);
+    assert_eq!(vector[1], 1.0);
+
+    // The members are left out, so the closest other vector comes first
+    let results = resp["results"].as_array().unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0]["id"], "near");
+    assert_eq!(results[0]["payload"]["project"], "y");
+    assert_eq!(results[1]["id"], "far");
+
+    let (_, with_members) = app
+        .post_json(
+            "/collections/projects/aggregate",
+            json!({
+                "filter": {"project": "x"},
+                "include_vector": false,
+                "search": {"limit": 5, "exclude_members": false},
+            }),
+        )
+        .await;
+    assert!(with_members.get("vector").is_none());
+    let ids: Vec<&str> = with_members["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| r["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(ids[0], "near");
+    assert!(ids.contains(&"x3"));
+}
+
+#[tokio::test]
+async fn medoid_is_a_member_and_empty_filters_are_404() {
+    let app = TestApp::new().await;
+    seed(&app).await;
+
+    let (status, resp) = app
+        .post_json(
+            "/collections/projects/aggregate",
+            json!({"filter": {"project": "x"}, "method": "medoid"}),
+        )
+        .await;
+    assert!(status.is_success(), "status {status}: {resp}");
+    assert_eq!(resp["medoid_id"], "x2");
+    assert_eq!(resp["vector"][0], 2.0);
+    assert!(resp.get("results").is_none());
+
+    let (status, resp) = app
+        .post_json(
+            "/collections/projects/aggregate",
+            json!({"filter": {"project": "z"}}),
+        )
+        .await;
+    assert_eq!(status.as_u16(), 404, "{resp}");
+
+    let (status, resp) = app
+        .post_json(
+            "/collections/projects/aggregate",
+            json!({"search": {"limit": 0}}),
+        )
+        .await;
+    assert_eq!(status.as_u16(), 400, "{resp}");
+}
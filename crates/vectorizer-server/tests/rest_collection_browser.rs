@@ -0,0 +1,100 @@
+//! Collection browser through the real router: `GET
+//! /collections/{name}/sample` and `GET
+//! /collections/{name}/vectors/{id}/neighbors` return payload previews,
+//! scores and distances, nearest first.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use common::{TestApp, embedding};
+use serde_json::{Value, json};
+
+async fn seed(app: &TestApp) {
+    app.create_collection(json!({"name": "browse", "dimension": 512, "metric": "euclidean"}))
+        .await;
+
+    let long_text = "x".repeat(500);
+    app.insert_vectors(
+        "browse",
+        json!([
+            {"id": "a", "embedding": embedding(&[0.0]), "payload": {"text": long_text, "tags": (0..20).collect::<Vec<_>>()}},
+            {"id": "b", "embedding": embedding(&[1.0]), "payload": {"text": "one"}},
+            {"id": "c", "embedding": embedding(&[3.0]), "payload": {"text": "three"}},
+            {"id": "d", "embedding": embedding(&[10.0]), "payload": {"text": "ten"}},
+        ]),
+    )
+    .await;
+}
+
+fn ids(neighbors: &Value) -> Vec<&str> {
+    neighbors
+        .as_array()
+        .expect("neighbors array")
+        .iter()
+        .map(|n| n["id"].as_str().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn neighbors_are_nearest_first_with_distances_and_previews() {
+    let app = TestApp::new().await;
+    seed(&app).await;
+
+    let (status, resp) = app
+        .get("/collections/browse/vectors/a/neighbors?k=2&preview_chars=20")
+        .await;
+    assert!(status.is_success(), "status {status}: {resp}");
+    assert_eq!(resp["id"], "a");
+    assert_eq!(resp["metric"], "euclidean");
+    assert_eq!(ids(&resp["neighbors"]), ["b", "c"]);
+    let distance = resp["neighbors"][1]["distance"].as_f64().unwrap();
+    assert!((distance - 3.0).abs() < 1e-3, "distance {distance}");
+    assert_eq!(
+        resp["neighbors"][0]["payload_preview"],
+        json!({"text": "one"})
+    );
+
+    let text = resp["payload_preview"]["text"].as_str().unwrap();
+    assert_eq!(text.chars().count(), 21, "cut to 20 chars plus an ellipsis");
+    assert_eq!(
+        resp["payload_preview"]["tags"].as_array().unwrap().len(),
+        10
+    );
+    assert!(resp.get("vector").is_none());
+
+    let (status, _) = app.get("/collections/browse/vectors/ghost/neighbors").await;
+    assert_eq!(status.as_u16(), 404);
+}
+
+#[tokio::test]
+async fn sample_is_repeatable_with_a_seed() {
+    let app = TestApp::new().await;
+    seed(&app).await;
+
+    let (status, resp) = app
+        .get("/collections/browse/sample?n=3&neighbors=1&seed=7&include_vector=true")
+        .await;
+    assert!(status.is_success(), "status {status}: {resp}");
+    assert_eq!(resp["total"], 4);
+    assert_eq!(resp["dimension"], 512);
+    let sample = resp["sample"].as_array().unwrap();
+    assert_eq!(sample.len(), 3);
+    for entry in sample {
+        assert_eq!(entry["vector"].as_array().unwrap().len(), 512);
+        let neighbors = ids(&entry["neighbors"]);
+        assert_eq!(neighbors.len(), 1);
+        assert_ne!(neighbors[0], entry["id"].as_str().unwrap());
+    }
+
+    let (_, again) = app
+        .get("/collections/browse/sample?n=3&neighbors=1&seed=7&include_vector=true")
+        .await;
+    assert_eq!(again["sample"], resp["sample"]);
+
+    let (_, all) = app
+        .get("/collections/browse/sample?n=500&neighbors=0")
+        .await;
+    assert_eq!(all["sample"].as_array().unwrap().len(), 4);
+    assert_eq!(all["sample"][0]["neighbors"], json!([]));
+}
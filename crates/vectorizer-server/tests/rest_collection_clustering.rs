@@ -0,0 +1,126 @@
+//! `POST /collections/{name}/cluster`: k-means separates two topics,
+//! writes each vector's cluster id into its payload and reports the
+//! clusters, and bad requests are refused.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use common::{TestApp, embedding};
+use serde_json::{Value, json};
+
+/// Two topics: `a*` vectors point along the first axis, `b*` along the
+/// second, each with a little spread.
+async fn seed(app: &TestApp) {
+    app.create_collection(json!({"name": "topics", "dimension": 512, "metric": "cosine"}))
+        .await;
+
+    let mut vectors = Vec::new();
+    for i in 0..6 {
+        let wobble = i as f32 * 0.02;
+        vectors.push(json!({
+            "id": format!("a{i}"),
+            "embedding": embedding(&[1.0, wobble, 0.05]),
+            "payload": {"topic": "install"},
+        }));
+        vectors.push(json!({
+            "id": format!("b{i}"),
+            "embedding": embedding(&[wobble, 1.0, 0.05]),
+            "payload": {"topic": "billing"},
+        }));
+    }
+    app.insert_vectors("topics", json!(vectors)).await;
+}
+
+async fn cluster_of(app: &TestApp, field: &str, id: &str) -> Value {
+    let (status, resp) = app.get(&format!("/collections/topics/vectors/{id}")).await;
+    assert!(status.is_success(), "get status {status}: {resp}");
+    resp["payload"][field].clone()
+}
+
+#[tokio::test]
+async fn kmeans_separates_topics_and_writes_cluster_ids() {
+    let app = TestApp::new().await;
+    seed(&app).await;
+
+    let (status, resp) = app
+        .post_json(
+            "/collections/topics/cluster",
+            json!({"k": 2, "seed": 7, "include_centroids": true}),
+        )
+        .await;
+    assert!(status.is_success(), "status {status}: {resp}");
+    assert_eq!(resp["state"], "completed");
+    let report = &resp["report"];
+    assert_eq!(report["algorithm"], "kmeans");
+    assert_eq!(report["seed"], 7);
+    assert_eq!(report["clustered"], 12);
+    assert_eq!(report["clusters_total"], 2);
+    assert_eq!(report["noise"], 0);
+    assert_eq!(report["written"], 12);
+
+    let clusters = report["clusters"].as_array().unwrap();
+    for (id, cluster) in clusters.iter().enumerate() {
+        assert_eq!(cluster["id"], id);
+        assert_eq!(cluster["size"], 6);
+        assert_eq!(cluster["centroid"].as_array().unwrap().len(), 512);
+        assert!(cluster["representative"]["payload_preview"]["topic"].is_string());
+    }
+
+    let a = cluster_of(&app, "cluster_id", "a0").await;
+    let b = cluster_of(&app, "cluster_id", "b0").await;
+    assert!(a.is_u64() && b.is_u64());
+    assert_ne!(a, b);
+    for i in 1..6 {
+        assert_eq!(cluster_of(&app, "cluster_id", &format!("a{i}")).await, a);
+        assert_eq!(cluster_of(&app, "cluster_id", &format!("b{i}")).await, b);
+    }
+    let (_, vector) = app.get("/collections/topics/vectors/a0").await;
+    assert_eq!(vector["payload"]["topic"], "install");
+
+    // A dry run reports without touching payloads
+    let (_, dry) = app
+        .post_json(
+            "/collections/topics/cluster",
+            json!({"k": 2, "field": "topic_id", "write_back": false}),
+        )
+        .await;
+    assert_eq!(dry["report"]["written"], 0);
+    assert!(dry["report"]["clusters"][0].get("centroid").is_none());
+    assert!(cluster_of(&app, "topic_id", "a0").await.is_null());
+}
+
+#[tokio::test]
+async fn cluster_rejects_bad_requests() {
+    let app = TestApp::new().await;
+    seed(&app).await;
+
+    let (status, resp) = app
+        .post_json("/collections/topics/cluster", json!({"k": 0}))
+        .await;
+    assert_eq!(status.as_u16(), 400, "{resp}");
+
+    let (status, resp) = app
+        .post_json("/collections/topics/cluster", json!({"field": ""}))
+        .await;
+    assert_eq!(status.as_u16(), 400, "{resp}");
+
+    let (status, resp) = app
+        .post_json(
+            "/collections/topics/cluster",
+            json!({"algorithm": "hdbscan", "min_cluster_size": 3}),
+        )
+        .await;
+    if cfg!(feature = "hdbscan") {
+        assert!(status.is_success(), "status {status}: {resp}");
+        assert_eq!(resp["report"]["algorithm"], "hdbscan");
+        assert_eq!(resp["report"]["clusters_total"], 2);
+    } else {
+        assert_eq!(status.as_u16(), 400, "{resp}");
+    }
+
+    let (status, _) = app
+        .post_json("/collections/no_such/cluster", json!({}))
+        .await;
+    assert_eq!(status.as_u16(), 404);
+}
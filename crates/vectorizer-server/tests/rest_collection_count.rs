@@ -0,0 +1,76 @@
+//! `POST /collections/{name}/count`: exact and approximate counts of the
+//! vectors matching a filter.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use common::{TestApp, embedding};
+use serde_json::{Value, json};
+
+async fn seed(app: &TestApp) {
+    app.create_collection(json!({"name": "tickets", "dimension": 512, "metric": "cosine"}))
+        .await;
+
+    let vectors: Vec<Value> = (0..10)
+        .map(|i| {
+            json!({
+                "id": format!("t{i}"),
+                "embedding": embedding(&[1.0, i as f32]),
+                "payload": {"state": if i < 3 { "open" } else { "closed" }},
+            })
+        })
+        .collect();
+    app.insert_vectors("tickets", json!(vectors)).await;
+}
+
+#[tokio::test]
+async fn count_with_and_without_a_filter() {
+    let app = TestApp::new().await;
+    seed(&app).await;
+
+    let (status, resp) = app.post_json("/collections/tickets/count", json!({})).await;
+    assert!(status.is_success(), "status {status}: {resp}");
+    assert_eq!(resp["count"], 10);
+    assert_eq!(resp["exact"], true);
+
+    let (status, resp) = app
+        .post_json(
+            "/collections/tickets/count",
+            json!({"filter": {"state": "open"}}),
+        )
+        .await;
+    assert!(status.is_success(), "status {status}: {resp}");
+    assert_eq!(resp["count"], 3);
+    assert_eq!(resp["exact"], true);
+    assert!(resp.get("sampled").is_none());
+
+    let (_, resp) = app
+        .post_json(
+            "/collections/tickets/count",
+            json!({"filter": {"must": [{"type": "match", "key": "state", "match_value": "closed"}]}}),
+        )
+        .await;
+    assert_eq!(resp["count"], 7);
+}
+
+#[tokio::test]
+async fn approximate_count_of_a_small_collection_is_exact() {
+    let app = TestApp::new().await;
+    seed(&app).await;
+
+    // Ten vectors fit in the sample, so nothing is extrapolated
+    let (status, resp) = app
+        .post_json(
+            "/collections/tickets/count",
+            json!({"filter": {"state": "open"}, "exact": false}),
+        )
+        .await;
+    assert!(status.is_success(), "status {status}: {resp}");
+    assert_eq!(resp["count"], 3);
+    assert_eq!(resp["exact"], true);
+    assert_eq!(resp["sampled"], 10);
+
+    let (status, _) = app.post_json("/collections/no_such/count", json!({})).await;
+    assert_eq!(status.as_u16(), 404);
+}
@@ -0,0 +1,66 @@
+//! Deduplication on insert (`dedup` on create): `/insert_vectors`
+//! reports each duplicate as `skipped` with the vector it matched.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use common::{TestApp, embedding};
+use serde_json::json;
+
+#[tokio::test]
+async fn duplicates_are_skipped_and_reported() {
+    let app = TestApp::new().await;
+    let (status, resp) = app
+        .post_json(
+            "/collections",
+            json!({"name": "dedup_docs", "dimension": 512, "dedup": {}}),
+        )
+        .await;
+    assert!(status.is_success(), "create status {status}: {resp}");
+
+    let (status, resp) = app.get("/collections/dedup_docs").await;
+    assert!(status.is_success(), "get status {status}: {resp}");
+    assert_eq!(resp["dedup"]["hash_field"], "content_hash");
+    assert_eq!(resp["dedup"]["action"], "skip");
+
+    let (status, resp) = app
+        .post_json(
+            "/insert_vectors",
+            json!({
+                "collection": "dedup_docs",
+                "vectors": [
+                    {"id": "a", "embedding": embedding(&[1.0]), "payload": {"content": "same"}},
+                    {"id": "b", "embedding": embedding(&[0.0, 1.0]), "payload": {"content": "same"}},
+                    {"id": "c", "embedding": embedding(&[0.0, 0.0, 1.0]), "payload": {"content": "other"}},
+                ],
+            }),
+        )
+        .await;
+    assert!(status.is_success(), "insert status {status}: {resp}");
+    assert_eq!(resp["inserted"], 2);
+    assert_eq!(resp["deduplicated"], 1);
+    let skipped = &resp["results"][1];
+    assert_eq!(skipped["status"], "skipped");
+    assert_eq!(skipped["duplicate_of"], "a");
+    assert_eq!(skipped["reason"], "content_hash");
+    assert_eq!(skipped["vector_ids"], json!(["a"]));
+}
+
+#[tokio::test]
+async fn invalid_dedup_config_is_rejected() {
+    let app = TestApp::new().await;
+    for dedup in [
+        json!({"hash_field": null}),
+        json!({"action": "replace"}),
+        json!({"hash_field": ""}),
+    ] {
+        let (status, resp) = app
+            .post_json(
+                "/collections",
+                json!({"name": "bad_dedup", "dimension": 512, "dedup": dedup}),
+            )
+            .await;
+        assert_eq!(status.as_u16(), 400, "{dedup} accepted: {resp}");
+    }
+}
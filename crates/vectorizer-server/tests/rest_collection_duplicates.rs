@@ -0,0 +1,114 @@
+//! `POST /collections/{name}/duplicates`: near-identical vectors come
+//! back grouped with payload previews, and the report is the result of
+//! a `duplicate_report` job that `GET /jobs/{id}/result` downloads.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use common::{TestApp, embedding};
+use serde_json::{Value, json};
+
+async fn seed(app: &TestApp) {
+    app.create_collection(json!({"name": "mirrors", "dimension": 512, "metric": "cosine"}))
+        .await;
+
+    app.insert_vectors(
+        "mirrors",
+        json!([
+            {"id": "a1", "embedding": embedding(&[1.0, 0.0, 0.0]), "payload": {"path": "repo/a.md"}},
+            {"id": "a2", "embedding": embedding(&[1.0, 0.01, 0.0]), "payload": {"path": "mirror/a.md"}},
+            {"id": "a3", "embedding": embedding(&[1.0, 0.0, 0.02]), "payload": {"path": "fork/a.md"}},
+            {"id": "b1", "embedding": embedding(&[0.0, 1.0, 0.0]), "payload": {"path": "repo/b.md"}},
+            {"id": "c1", "embedding": embedding(&[0.0, 0.0, 1.0]), "payload": {"path": "repo/c.md"}},
+            {"id": "c2", "embedding": embedding(&[0.0, 0.01, 1.0]), "payload": {"path": "mirror/c.md"}},
+        ]),
+    )
+    .await;
+}
+
+fn member_ids(group: &Value) -> Vec<&str> {
+    group["members"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| m["id"].as_str().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn report_groups_near_identical_vectors() {
+    let app = TestApp::new().await;
+    seed(&app).await;
+
+    let (status, resp) = app
+        .post_json("/collections/mirrors/duplicates", json!({}))
+        .await;
+    assert!(status.is_success(), "status {status}: {resp}");
+    assert_eq!(resp["state"], "completed");
+    let report = &resp["report"];
+    assert_eq!(report["scanned"], 6);
+    assert_eq!(report["groups_total"], 2);
+    assert_eq!(report["duplicates"], 3);
+    assert_eq!(report["truncated"], false);
+
+    let groups = report["groups"].as_array().unwrap();
+    assert_eq!(member_ids(&groups[0]), ["a1", "a2", "a3"]);
+    assert_eq!(member_ids(&groups[1]), ["c1", "c2"]);
+    assert_eq!(groups[0]["size"], 3);
+    assert!(groups[0]["min_score"].as_f64().unwrap() >= 0.95);
+    assert_eq!(
+        groups[1]["members"][1]["payload_preview"],
+        json!({"path": "mirror/c.md"})
+    );
+
+    let (_, capped) = app
+        .post_json("/collections/mirrors/duplicates", json!({"max_groups": 1}))
+        .await;
+    assert_eq!(capped["report"]["groups"].as_array().unwrap().len(), 1);
+    assert_eq!(capped["report"]["truncated"], true);
+}
+
+#[tokio::test]
+async fn report_is_a_downloadable_job_result() {
+    let app = TestApp::new().await;
+    seed(&app).await;
+
+    let (status, resp) = app
+        .post_json("/collections/mirrors/duplicates?wait=false", json!({}))
+        .await;
+    assert!(status.is_success(), "status {status}: {resp}");
+    assert!(resp["report"].is_null());
+    let job_id = resp["job_id"].as_str().unwrap().to_string();
+
+    let mut job = Value::Null;
+    for _ in 0..100 {
+        let (_, resp) = app.get(&format!("/jobs/{job_id}")).await;
+        job = resp["job"].clone();
+        if job["state"] != "running" {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    assert_eq!(job["state"], "completed", "job: {job}");
+    assert_eq!(job["kind"], "duplicate_report");
+    assert_eq!(job["processed"], 6);
+
+    let response = app.get_streaming(&format!("/jobs/{job_id}/result")).await;
+    assert!(response.status().is_success());
+    let disposition = response.headers()["content-disposition"].to_str().unwrap();
+    assert_eq!(
+        disposition,
+        format!("attachment; filename=\"duplicate_report-{job_id}.json\"")
+    );
+    let (_, result) = app.get(&format!("/jobs/{job_id}/result")).await;
+    assert_eq!(result, job["result"]);
+    assert_eq!(result["groups_total"], 2);
+
+    let (status, _) = app.get("/jobs/no-such-job/result").await;
+    assert_eq!(status.as_u16(), 404);
+    let (status, _) = app
+        .post_json("/collections/mirrors/duplicates", json!({"neighbors": 0}))
+        .await;
+    assert_eq!(status.as_u16(), 400);
+}
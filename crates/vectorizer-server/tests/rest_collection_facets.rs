@@ -0,0 +1,125 @@
+//! `POST /collections/{name}/facets`: value counts of payload keys over
+//! the whole collection, a filter and a query's candidate set.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use common::{TestApp, embedding};
+use serde_json::{Value, json};
+
+async fn seed(app: &TestApp) {
+    app.create_collection(json!({"name": "catalog", "dimension": 512, "metric": "cosine"}))
+        .await;
+
+    let payloads = [
+        json!({"lang": "rust", "tags": ["db", "cli"], "meta": {"year": 2024}}),
+        json!({"lang": "rust", "tags": ["db"], "meta": {"year": 2025}}),
+        json!({"lang": "go", "tags": ["cli"], "meta": {"year": 2024}}),
+        json!({"lang": "rust", "meta": {"year": 2024}}),
+        json!({"title": "no language"}),
+    ];
+    let vectors: Vec<Value> = payloads
+        .into_iter()
+        .enumerate()
+        .map(|(i, payload)| {
+            json!({
+                "id": format!("doc{i}"),
+                "embedding": embedding(&[1.0, i as f32]),
+                "payload": payload,
+            })
+        })
+        .collect();
+    app.insert_vectors("catalog", json!(vectors)).await;
+}
+
+#[tokio::test]
+async fn facets_count_values_per_key() {
+    let app = TestApp::new().await;
+    seed(&app).await;
+
+    let (status, resp) = app
+        .post_json(
+            "/collections/catalog/facets",
+            json!({"keys": ["lang", "tags", "meta.year"]}),
+        )
+        .await;
+    assert!(status.is_success(), "status {status}: {resp}");
+    assert_eq!(resp["counted"], 5);
+    let facets = resp["facets"].as_array().unwrap();
+    assert_eq!(facets[0]["key"], "lang");
+    assert_eq!(
+        facets[0]["values"],
+        json!([{"value": "rust", "count": 3}, {"value": "go", "count": 1}])
+    );
+    assert_eq!(facets[0]["missing"], 1);
+    assert_eq!(
+        facets[1]["values"],
+        json!([{"value": "cli", "count": 2}, {"value": "db", "count": 2}])
+    );
+    assert_eq!(facets[2]["values"][0], json!({"value": 2024, "count": 3}));
+
+    let (_, filtered) = app
+        .post_json(
+            "/collections/catalog/facets",
+            json!({"keys": ["meta.year"], "filter": {"lang": "rust"}, "limit": 1}),
+        )
+        .await;
+    assert_eq!(filtered["counted"], 3);
+    let year = &filtered["facets"][0];
+    assert_eq!(year["values"], json!([{"value": 2024, "count": 2}]));
+    assert_eq!(year["distinct"], 2);
+}
+
+#[tokio::test]
+async fn facets_over_a_query_and_bad_requests() {
+    let app = TestApp::new().await;
+    seed(&app).await;
+
+    // Ingested text, so the query embeds with a trained vocabulary
+    app.create_collection(json!({"name": "handbook", "dimension": 512, "metric": "cosine"}))
+        .await;
+    for (file_path, source, chunks) in [
+        (
+            "search.md",
+            "docs",
+            ["vector search ranks documents", "the index is rebuilt"],
+        ),
+        (
+            "onboarding.md",
+            "wiki",
+            ["new hires get a laptop", "vector search finds documents"],
+        ),
+    ] {
+        let chunks: Vec<Value> = chunks.iter().map(|c| json!({"content": c})).collect();
+        let (status, resp) = app
+            .post_json(
+                "/collections/handbook/documents",
+                json!({"file_path": file_path, "metadata": {"source": source}, "chunks": chunks}),
+            )
+            .await;
+        assert!(status.is_success(), "ingest status {status}: {resp}");
+    }
+
+    let (status, resp) = app
+        .post_json(
+            "/collections/handbook/facets",
+            json!({"keys": ["source"], "query": "vector search documents", "candidates": 3}),
+        )
+        .await;
+    assert!(status.is_success(), "status {status}: {resp}");
+    assert_eq!(resp["counted"], 3);
+    let values = resp["facets"][0]["values"].as_array().unwrap();
+    let total: u64 = values.iter().map(|v| v["count"].as_u64().unwrap()).sum();
+    assert_eq!(total, 3);
+
+    let (status, resp) = app
+        .post_json("/collections/catalog/facets", json!({"keys": []}))
+        .await;
+    assert_eq!(status.as_u16(), 400, "{resp}");
+
+    let (status, _) = app
+        .post_json("/collections/no_such/facets", json!({"keys": ["lang"]}))
+        .await;
+    assert_eq!(status.as_u16(), 404);
+}
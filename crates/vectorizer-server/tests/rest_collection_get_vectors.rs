@@ -0,0 +1,77 @@
+//! `POST /collections/{name}/vectors/get`: many vectors by id in one
+//! request, in request order, with the unknown ids listed as missing.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use common::{TestApp, embedding};
+use serde_json::{Value, json};
+
+async fn seed(app: &TestApp) {
+    app.create_collection(json!({"name": "chunks", "dimension": 512, "metric": "euclidean"}))
+        .await;
+
+    let vectors: Vec<Value> = (0..5)
+        .map(|i| {
+            json!({
+                "id": format!("c{i}"),
+                "embedding": embedding(&[i as f32, 1.0]),
+                "payload": {"position": i},
+            })
+        })
+        .collect();
+    app.insert_vectors("chunks", json!(vectors)).await;
+}
+
+#[tokio::test]
+async fn fetches_in_request_order_and_lists_missing_ids() {
+    let app = TestApp::new().await;
+    seed(&app).await;
+
+    let (status, resp) = app
+        .post_json(
+            "/collections/chunks/vectors/get",
+            json!({"ids": ["c3", "nope", "c0", "c3"]}),
+        )
+        .await;
+    assert!(status.is_success(), "status {status}: {resp}");
+    let vectors = resp["vectors"].as_array().unwrap();
+    let ids: Vec<&str> = vectors.iter().map(|v| v["id"].as_str().unwrap()).collect();
+    assert_eq!(ids, ["c3", "c0"]);
+    assert_eq!(vectors[0]["payload"]["position"], 3);
+    assert_eq!(vectors[0]["vector"][0], 3.0);
+    assert_eq!(resp["missing"], json!(["nope"]));
+
+    let (_, resp) = app
+        .post_json(
+            "/collections/chunks/vectors/get",
+            json!({"ids": ["c1"], "with_vector": false}),
+        )
+        .await;
+    let vector = &resp["vectors"][0];
+    assert!(vector.get("vector").is_none());
+    assert_eq!(vector["payload"]["position"], 1);
+}
+
+#[tokio::test]
+async fn rejects_bad_id_lists_and_unknown_collections() {
+    let app = TestApp::new().await;
+    seed(&app).await;
+
+    let (status, resp) = app
+        .post_json("/collections/chunks/vectors/get", json!({"ids": []}))
+        .await;
+    assert_eq!(status.as_u16(), 400, "{resp}");
+
+    let too_many: Vec<String> = (0..1001).map(|i| format!("c{i}")).collect();
+    let (status, resp) = app
+        .post_json("/collections/chunks/vectors/get", json!({"ids": too_many}))
+        .await;
+    assert_eq!(status.as_u16(), 400, "{resp}");
+
+    let (status, _) = app
+        .post_json("/collections/no_such/vectors/get", json!({"ids": ["c0"]}))
+        .await;
+    assert_eq!(status.as_u16(), 404);
+}
@@ -0,0 +1,197 @@
+//! API keys confined to a collection-name namespace (`namespace` on
+//! `POST /auth/keys`): calls naming collections outside the prefix are
+//! refused with 403 and `GET /collections` only lists the visible set.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use axum::http::StatusCode;
+use common::TestApp;
+use serde_json::{Value, json};
+
+/// Log in as the fixture admin and return `Authorization: Bearer <jwt>`.
+async fn admin_bearer(app: &TestApp, creds: &common::AuthFixture) -> String {
+    let (status, login) = app
+        .post_json(
+            "/auth/login",
+            json!({ "username": creds.username, "password": creds.password }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{login}");
+    format!("Bearer {}", login["access_token"].as_str().unwrap())
+}
+
+fn names(listing: &Value) -> Vec<&str> {
+    listing["collections"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["name"].as_str().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn namespaced_key_only_reaches_its_prefix() {
+    let (app, creds) = TestApp::with_auth().await;
+    let bearer = admin_bearer(&app, &creds).await;
+    let admin = ("authorization", bearer.as_str());
+
+    for name in ["team-a/docs", "team-b/docs"] {
+        let (status, body) = app
+            .request_with_header(
+                "POST",
+                "/collections",
+                admin,
+                Some(json!({ "name": name, "dimension": 512 })),
+            )
+            .await;
+        assert_eq!(status, StatusCode::OK, "{body}");
+    }
+
+    let (status, created) = app
+        .request_with_header(
+            "POST",
+            "/auth/keys",
+            admin,
+            Some(json!({
+                "name": "team-a",
+                "permissions": ["read", "write"],
+                "namespace": "team-a/*"
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{created}");
+    assert_eq!(created["namespace"], "team-a/*");
+    let key = created["api_key"].as_str().unwrap().to_string();
+    let team_a = ("x-api-key", key.as_str());
+
+    let (status, listing) = app
+        .request_with_header("GET", "/collections", team_a, None)
+        .await;
+    assert_eq!(status, StatusCode::OK, "{listing}");
+    assert_eq!(names(&listing), ["team-a/docs"]);
+
+    let (status, _) = app
+        .request_with_header("GET", "/collections/team-a%2Fdocs", team_a, None)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = app
+        .request_with_header("GET", "/collections/team-b%2Fdocs", team_a, None)
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN, "{body}");
+
+    let (status, _) = app
+        .request_with_header(
+            "POST",
+            "/collections",
+            team_a,
+            Some(json!({ "name": "team-b/new", "dimension": 512 })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, body) = app
+        .request_with_header(
+            "POST",
+            "/search",
+            team_a,
+            Some(json!({ "collection": "team-b/docs", "query": "q" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN, "{body}");
+
+    // A namespaced key cannot mint a key with a wider namespace
+    let (status, _) = app
+        .request_with_header(
+            "POST",
+            "/auth/keys",
+            team_a,
+            Some(json!({ "name": "escape", "namespace": "team-*" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // The admin's JWT is not namespaced
+    let (_, listing) = app
+        .request_with_header("GET", "/collections", admin, None)
+        .await;
+    assert_eq!(names(&listing), ["team-a/docs", "team-b/docs"]);
+}
+
+#[tokio::test]
+async fn namespaced_key_cannot_write_outside_its_prefix() {
+    let (app, creds) = TestApp::with_auth().await;
+    let bearer = admin_bearer(&app, &creds).await;
+    let admin = ("authorization", bearer.as_str());
+
+    for name in ["team-a/docs", "team-b/docs"] {
+        let (status, body) = app
+            .request_with_header(
+                "POST",
+                "/collections",
+                admin,
+                Some(json!({ "name": name, "dimension": 512 })),
+            )
+            .await;
+        assert_eq!(status, StatusCode::OK, "{body}");
+    }
+    let (_, created) = app
+        .request_with_header(
+            "POST",
+            "/auth/keys",
+            admin,
+            Some(json!({
+                "name": "team-a",
+                "permissions": ["read", "write"],
+                "namespace": "team-a/*"
+            })),
+        )
+        .await;
+    let key = created["api_key"].as_str().unwrap().to_string();
+    let team_a = ("x-api-key", key.as_str());
+
+    let refused = [
+        (
+            "/collections/team-a%2Fdocs/rename",
+            json!({ "new_name": "team-b/renamed" }),
+        ),
+        (
+            "/collections/team-a%2Fdocs/vectors/move",
+            json!({ "destination": "team-b/docs", "ids": ["v1"] }),
+        ),
+        (
+            "/collections/team-a%2Fdocs/vectors/copy",
+            json!({ "destination": "team-b/docs", "ids": ["v1"] }),
+        ),
+        (
+            "/collections/team-a%2Fdocs/clone",
+            json!({ "target": "team-b/clone" }),
+        ),
+        (
+            "/qdrant/collections/aliases",
+            json!({ "actions": [{ "create_alias": {
+                "collection_name": "team-a/docs",
+                "alias_name": "team-b/alias"
+            }}]}),
+        ),
+        (
+            "/qdrant/collections/aliases",
+            json!({ "actions": [{ "create_alias": {
+                "collection_name": "team-b/docs",
+                "alias_name": "team-a/alias"
+            }}]}),
+        ),
+    ];
+    for (path, body) in refused {
+        let (status, resp) = app
+            .request_with_header("POST", path, team_a, Some(body))
+            .await;
+        assert_eq!(status, StatusCode::FORBIDDEN, "{path}: {resp}");
+    }
+
+    // Nothing was renamed, cloned or aliased into team-b
+    let (_, listing) = app
+        .request_with_header("GET", "/collections", admin, None)
+        .await;
+    assert_eq!(names(&listing), ["team-a/docs", "team-b/docs"]);
+}
@@ -0,0 +1,114 @@
+//! `POST /collections/{name}/projection` through the real router: a
+//! seeded sample comes back as repeatable 2D points, PCA puts the widest
+//! spread on `x`, and bad requests are refused.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use common::{TestApp, embedding};
+use serde_json::{Value, json};
+
+async fn seed(app: &TestApp) {
+    app.create_collection(json!({"name": "plot", "dimension": 512, "metric": "euclidean"}))
+        .await;
+
+    // Spread along the first component, a little noise on the second
+    let vectors: Vec<Value> = (0..8)
+        .map(|i| {
+            let wobble = if i % 2 == 0 { 0.5 } else { -0.5 };
+            json!({
+                "id": format!("v{i}"),
+                "embedding": embedding(&[i as f32 * 10.0, wobble]),
+                "payload": {"text": format!("point {i}")},
+            })
+        })
+        .collect();
+    app.insert_vectors("plot", json!(vectors)).await;
+}
+
+fn points(resp: &Value) -> &Vec<Value> {
+    resp["points"].as_array().expect("points array")
+}
+
+#[tokio::test]
+async fn pca_projection_spreads_the_sample_along_x() {
+    let app = TestApp::new().await;
+    seed(&app).await;
+
+    let (status, resp) = app
+        .post_json(
+            "/collections/plot/projection",
+            json!({"seed": 3, "include_payload": true}),
+        )
+        .await;
+    assert!(status.is_success(), "status {status}: {resp}");
+    assert_eq!(resp["method"], "pca");
+    assert_eq!(resp["total"], 8);
+    assert_eq!(resp["sampled"], 8);
+    assert_eq!(resp["seed"], 3);
+    let explained = resp["explained_variance"][0].as_f64().unwrap();
+    assert!(explained > 0.99, "explained variance {explained}");
+
+    let plotted = points(&resp);
+    assert_eq!(plotted.len(), 8);
+    let x_of = |id: &str| {
+        plotted
+            .iter()
+            .find(|p| p["id"] == id)
+            .and_then(|p| p["x"].as_f64())
+            .unwrap()
+    };
+    // Euclidean collections keep raw geometry: v0 and v7 are 70 apart
+    assert!(((x_of("v7") - x_of("v0")).abs() - 70.0).abs() < 0.1);
+    for point in plotted {
+        assert!(point["y"].as_f64().unwrap().abs() < 1.0, "{point}");
+        assert!(point["payload_preview"]["text"].is_string());
+    }
+
+    let (_, again) = app
+        .post_json(
+            "/collections/plot/projection",
+            json!({"seed": 3, "include_payload": true}),
+        )
+        .await;
+    assert_eq!(again["points"], resp["points"]);
+
+    let (_, small) = app
+        .post_json(
+            "/collections/plot/projection",
+            json!({"method": "pca", "sample_size": 3}),
+        )
+        .await;
+    assert_eq!(small["sampled"], 3);
+    assert_eq!(points(&small).len(), 3);
+    assert!(small["seed"].is_u64());
+    assert!(points(&small)[0].get("payload_preview").is_none());
+}
+
+#[tokio::test]
+async fn projection_rejects_bad_requests() {
+    let app = TestApp::new().await;
+    seed(&app).await;
+
+    let (status, resp) = app
+        .post_json("/collections/plot/projection", json!({"sample_size": 0}))
+        .await;
+    assert_eq!(status.as_u16(), 400, "{resp}");
+
+    let (status, resp) = app
+        .post_json("/collections/plot/projection", json!({"method": "umap"}))
+        .await;
+    if cfg!(feature = "umap") {
+        assert!(status.is_success(), "status {status}: {resp}");
+        assert_eq!(resp["method"], "umap");
+        assert!(resp["explained_variance"].is_null());
+    } else {
+        assert_eq!(status.as_u16(), 400, "{resp}");
+    }
+
+    let (status, _) = app
+        .post_json("/collections/no_such/projection", json!({}))
+        .await;
+    assert_eq!(status.as_u16(), 404);
+}
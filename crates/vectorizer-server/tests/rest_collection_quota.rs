@@ -0,0 +1,117 @@
+//! Collection `quota` through the real router: a `reject` quota refuses
+//! inserts past `max_vectors` with 409 `quota_exceeded`, a `summarize`
+//! quota keeps accepting them by merging the oldest vectors.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use common::TestApp;
+use serde_json::{Value, json};
+
+fn memories(count: usize) -> Vec<Value> {
+    (0..count)
+        .map(|i| {
+            let mut embedding = vec![0.1_f32; 512];
+            embedding[i] = 1.0;
+            json!({
+                "id": format!("m{i}"),
+                "embedding": embedding,
+                "payload": {"content": format!("Memory {i}. Something happened on day {i}.")},
+            })
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn reject_quota_refuses_inserts_past_max_vectors() {
+    let app = TestApp::new().await;
+    let name = "quota_reject";
+    let _ = app.delete(&format!("/collections/{name}")).await;
+    let (status, resp) = app
+        .post_json(
+            "/collections",
+            json!({
+                "name": name,
+                "dimension": 512,
+                "quota": {"max_vectors": 2},
+            }),
+        )
+        .await;
+    assert!(status.is_success(), "create status {status}: {resp}");
+
+    let (_, info) = app.get(&format!("/collections/{name}")).await;
+    assert_eq!(info["quota"]["max_vectors"], 2);
+    assert_eq!(info["quota"]["policy"], "reject");
+
+    let (_, resp) = app
+        .post_json(
+            "/insert_vectors",
+            json!({"collection": name, "vectors": memories(3)}),
+        )
+        .await;
+    assert_eq!(resp["inserted"].as_u64(), Some(2), "{resp}");
+    assert_eq!(resp["results"][2]["error_type"], "quota_exceeded");
+}
+
+#[tokio::test]
+async fn summarize_quota_keeps_the_collection_bounded() {
+    let app = TestApp::new().await;
+    let name = "quota_summarize";
+    let _ = app.delete(&format!("/collections/{name}")).await;
+    let (status, resp) = app
+        .post_json(
+            "/collections",
+            json!({
+                "name": name,
+                "dimension": 512,
+                "quota": {"max_vectors": 4, "policy": "summarize", "merge_group_size": 2},
+            }),
+        )
+        .await;
+    assert!(status.is_success(), "create status {status}: {resp}");
+
+    let (status, resp) = app
+        .post_json(
+            "/insert_vectors",
+            json!({"collection": name, "vectors": memories(6)}),
+        )
+        .await;
+    assert!(status.is_success(), "insert status {status}: {resp}");
+    assert_eq!(resp["inserted"].as_u64(), Some(6), "{resp}");
+
+    // A background merge writes its summary before removing the sources,
+    // so wait for it to settle at the quota's target (3)
+    let mut info = Value::Null;
+    for _ in 0..100 {
+        (_, info) = app.get(&format!("/collections/{name}")).await;
+        if info["vector_count"].as_u64().unwrap() <= 3 {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    assert!(
+        info["vector_count"].as_u64().unwrap() <= 3,
+        "merge did not settle at 3 vectors within 2s: {info}"
+    );
+    let (status, _) = app.get(&format!("/collections/{name}/vectors/m0")).await;
+    assert_eq!(status.as_u16(), 404, "oldest vector should be summarized");
+    let (status, _) = app.get(&format!("/collections/{name}/vectors/m5")).await;
+    assert!(status.is_success());
+}
+
+#[tokio::test]
+async fn invalid_quota_is_refused() {
+    let app = TestApp::new().await;
+    let (status, resp) = app
+        .post_json(
+            "/collections",
+            json!({
+                "name": "quota_invalid",
+                "dimension": 512,
+                "quota": {"max_vectors": 10, "policy": "summarize", "merge_group_size": 1},
+            }),
+        )
+        .await;
+    assert_eq!(status.as_u16(), 400, "{resp}");
+}
@@ -0,0 +1,64 @@
+//! Collection `scoring` through the real router: searches rank hits by
+//! the collection's expression over score and payload, and a malformed
+//! expression is refused at creation with 400.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use common::{TestApp, embedding};
+use serde_json::json;
+
+#[tokio::test]
+async fn searches_rank_by_the_scoring_expression() {
+    let app = TestApp::new().await;
+    let name = "scoring_pinned_boost";
+    let _ = app.delete(&format!("/collections/{name}")).await;
+    app.create_collection(json!({
+        "name": name,
+        "dimension": 512,
+        "scoring": {"expression": "score * if(payload.pinned, 2, 1)"},
+    }))
+    .await;
+
+    let (_, info) = app.get(&format!("/collections/{name}")).await;
+    assert_eq!(
+        info["scoring"],
+        json!({"expression": "score * if(payload.pinned, 2, 1)", "oversampling": 4})
+    );
+
+    app.insert_vectors(
+        name,
+        json!([
+            {"id": "closest", "embedding": embedding(&[1.0])},
+            {"id": "pinned", "embedding": embedding(&[0.8, 0.6]), "payload": {"pinned": true}},
+        ]),
+    )
+    .await;
+
+    let (status, resp) = app
+        .post_json(
+            &format!("/collections/{name}/search"),
+            json!({"vector": embedding(&[1.0]), "limit": 2}),
+        )
+        .await;
+    assert!(status.is_success(), "search status {status}: {resp}");
+    assert_eq!(resp["results"][0]["id"], "pinned", "{resp}");
+    assert_eq!(resp["results"][1]["id"], "closest", "{resp}");
+}
+
+#[tokio::test]
+async fn malformed_scoring_expression_is_rejected() {
+    let app = TestApp::new().await;
+    let (status, resp) = app
+        .post_json(
+            "/collections",
+            json!({
+                "name": "scoring_malformed",
+                "dimension": 512,
+                "scoring": {"expression": "score * bogus(payload.x)"},
+            }),
+        )
+        .await;
+    assert_eq!(status.as_u16(), 400, "{resp}");
+}
@@ -0,0 +1,92 @@
+//! `POST /collections/{name}/scroll`: id-ordered pages of the vectors
+//! matching a filter, chained by `next_cursor`.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use common::{TestApp, embedding};
+use serde_json::{Value, json};
+
+async fn seed(app: &TestApp) {
+    app.create_collection(json!({"name": "events", "dimension": 512, "metric": "cosine"}))
+        .await;
+
+    let vectors: Vec<Value> = (0..7)
+        .map(|i| {
+            json!({
+                "id": format!("e{i}"),
+                "embedding": embedding(&[1.0, i as f32]),
+                "payload": {"kind": if i % 2 == 0 { "even" } else { "odd" }},
+            })
+        })
+        .collect();
+    app.insert_vectors("events", json!(vectors)).await;
+}
+
+/// Every id of a scroll, page by page.
+async fn scroll_all(app: &TestApp, body: Value) -> Vec<Vec<String>> {
+    let mut pages = Vec::new();
+    let mut body = body;
+    loop {
+        let (status, resp) = app
+            .post_json("/collections/events/scroll", body.clone())
+            .await;
+        assert!(status.is_success(), "status {status}: {resp}");
+        pages.push(
+            resp["vectors"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|v| v["id"].as_str().unwrap().to_string())
+                .collect(),
+        );
+        match resp["next_cursor"].as_str() {
+            Some(cursor) => body["cursor"] = json!(cursor),
+            None => return pages,
+        }
+    }
+}
+
+#[tokio::test]
+async fn scroll_follows_the_cursor_in_id_order() {
+    let app = TestApp::new().await;
+    seed(&app).await;
+
+    let pages = scroll_all(&app, json!({"limit": 3})).await;
+    assert_eq!(
+        pages,
+        [vec!["e0", "e1", "e2"], vec!["e3", "e4", "e5"], vec!["e6"]]
+    );
+
+    let pages = scroll_all(&app, json!({"limit": 2, "filter": {"kind": "even"}})).await;
+    assert_eq!(pages, [vec!["e0", "e2"], vec!["e4", "e6"]]);
+
+    let (_, resp) = app
+        .post_json(
+            "/collections/events/scroll",
+            json!({"limit": 1, "with_vector": true, "with_payload": false}),
+        )
+        .await;
+    let vector = &resp["vectors"][0];
+    assert_eq!(vector["vector"].as_array().unwrap().len(), 512);
+    assert!(vector.get("payload").is_none());
+}
+
+#[tokio::test]
+async fn scroll_rejects_bad_limits_and_unknown_collections() {
+    let app = TestApp::new().await;
+    seed(&app).await;
+
+    for limit in [0, 1001] {
+        let (status, resp) = app
+            .post_json("/collections/events/scroll", json!({"limit": limit}))
+            .await;
+        assert_eq!(status.as_u16(), 400, "{resp}");
+    }
+
+    let (status, _) = app
+        .post_json("/collections/no_such/scroll", json!({}))
+        .await;
+    assert_eq!(status.as_u16(), 404);
+}
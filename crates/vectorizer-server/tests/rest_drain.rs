@@ -0,0 +1,80 @@
+//! Drain mode (`/admin/drain`): writes are refused and `/health` reports
+//! the node as not ready until the drain is cancelled; reads keep
+//! working throughout.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use common::{TestApp, embedding};
+use serde_json::json;
+
+#[tokio::test]
+async fn drain_refuses_writes_until_resumed() {
+    let app = TestApp::new().await;
+    let (status, resp) = app
+        .post_json(
+            "/collections",
+            json!({"name": "drain_target", "dimension": 512, "metric": "cosine"}),
+        )
+        .await;
+    assert!(status.is_success(), "create status {status}: {resp}");
+
+    let (status, resp) = app
+        .post_json("/admin/drain", json!({"timeout_secs": 1}))
+        .await;
+    assert!(status.is_success(), "drain status {status}: {resp}");
+    assert_eq!(resp["draining"], true);
+    assert_eq!(resp["settled"], true);
+    assert_eq!(resp["in_flight"], 0);
+
+    let (status, resp) = app.get("/health").await;
+    assert_eq!(status.as_u16(), 503);
+    assert_eq!(resp["status"], "draining");
+    assert_eq!(resp["ready"], false);
+
+    let insert = json!({
+        "collection": "drain_target",
+        "vectors": [{"id": "v1", "embedding": embedding(&[1.0])}],
+    });
+    let (status, resp) = app.post_json("/insert_vectors", insert.clone()).await;
+    assert_eq!(status.as_u16(), 503, "write while draining: {resp}");
+    assert_eq!(resp["error_type"], "draining");
+
+    let (status, resp) = app
+        .post_json(
+            "/collections/drain_target/search",
+            json!({"vector": embedding(&[1.0]), "limit": 1}),
+        )
+        .await;
+    assert!(
+        status.is_success(),
+        "search while draining {status}: {resp}"
+    );
+    let (status, _) = app.get("/collections/drain_target").await;
+    assert!(status.is_success());
+
+    let (_, resp) = app.get("/admin/drain").await;
+    assert_eq!(resp["draining"], true);
+
+    let (status, resp) = app.delete("/admin/drain").await;
+    assert!(status.is_success(), "resume status {status}: {resp}");
+    assert_eq!(resp["resumed"], true);
+
+    let (status, resp) = app.get("/health").await;
+    assert!(status.is_success());
+    assert_eq!(resp["ready"], true);
+    let (status, resp) = app.post_json("/insert_vectors", insert).await;
+    assert!(status.is_success(), "write after resume {status}: {resp}");
+}
+
+#[tokio::test]
+async fn drain_rejects_invalid_timeout() {
+    let app = TestApp::new().await;
+    let (status, _) = app
+        .post_json("/admin/drain", json!({"timeout_secs": "soon"}))
+        .await;
+    assert_eq!(status.as_u16(), 400);
+    let (_, resp) = app.get("/admin/drain").await;
+    assert_eq!(resp["draining"], false);
+}
@@ -0,0 +1,46 @@
+//! `POST /admin/embedding/providers/{name}/reload` through the real
+//! router.
+//!
+//! The harness registers only the vocabulary-based bm25 provider, so a
+//! successful swap (which needs a fastembed model download) is covered
+//! by the `EmbeddingManager` unit tests; this suite pins the rejections
+//! and that a refused reload leaves the provider serving.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use common::TestApp;
+use serde_json::json;
+
+#[tokio::test]
+async fn reload_rejects_unknown_provider_and_vocabulary_models() {
+    let app = TestApp::new().await;
+
+    let (status, resp) = app
+        .post_json("/admin/embedding/providers/nope/reload", json!({}))
+        .await;
+    assert_eq!(status.as_u16(), 404, "{resp}");
+
+    let (status, resp) = app
+        .post_json("/admin/embedding/providers/bm25/reload", json!({}))
+        .await;
+    assert_eq!(status.as_u16(), 400, "{resp}");
+
+    let (status, resp) = app
+        .post_json(
+            "/admin/embedding/providers/bm25/reload",
+            json!({"model": "fastembed:x", "drain_timeout_secs": -1}),
+        )
+        .await;
+    assert_eq!(status.as_u16(), 400, "{resp}");
+
+    let (status, resp) = app
+        .post_json(
+            "/embed",
+            json!({"text": "still serving after a refused reload"}),
+        )
+        .await;
+    assert!(status.is_success(), "embed status {status}: {resp}");
+    assert_eq!(resp["dimension"].as_u64(), Some(512), "{resp}");
+}
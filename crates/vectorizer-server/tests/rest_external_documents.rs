@@ -0,0 +1,145 @@
+//! Pre-chunked ingest (`/collections/{name}/documents`): chunks sent by
+//! an external pipeline are served by the `/file/*` endpoints, replace
+//! the previous version of their document and can be deleted by
+//! document.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use common::{TestApp, embedding};
+use serde_json::json;
+
+async fn document_count(app: &TestApp, name: &str) -> u64 {
+    let (status, resp) = app.get(&format!("/collections/{name}")).await;
+    assert!(status.is_success(), "get status {status}: {resp}");
+    resp["document_count"].as_u64().unwrap()
+}
+
+#[tokio::test]
+async fn external_chunks_behave_like_indexed_files() {
+    let app = TestApp::new().await;
+    let name = "byo_chunks";
+    let (status, _) = app
+        .post_json("/collections", json!({"name": name, "dimension": 512}))
+        .await;
+    assert!(status.is_success());
+
+    let documents = format!("/collections/{name}/documents");
+    let (status, resp) = app
+        .post_json(
+            &documents,
+            json!({
+                "file_path": "handbook/onboarding.md",
+                "metadata": {"source": "confluence"},
+                "chunks": [
+                    {"content": "Welcome to the team.", "embedding": embedding(&[1.0]),
+                     "line_start": 1, "line_end": 3},
+                    {"content": "Laptops ship on day one.", "line_start": 4, "line_end": 9},
+                    {"content": "Payroll runs monthly.", "metadata": {"section": "pay"}},
+                ],
+            }),
+        )
+        .await;
+    assert!(status.is_success(), "ingest status {status}: {resp}");
+    assert_eq!(resp["chunks"], 3);
+    assert_eq!(resp["vector_ids"][0], "handbook/onboarding.md#0");
+    assert_eq!(resp["removed_stale"], 0);
+    assert_eq!(document_count(&app, name).await, 1);
+
+    let (status, resp) = app
+        .post_json("/file/list", json!({"collection": name}))
+        .await;
+    assert!(status.is_success(), "list status {status}: {resp}");
+    assert_eq!(resp["files"][0]["path"], "handbook/onboarding.md");
+    assert_eq!(resp["files"][0]["chunk_count"], 3);
+
+    let (status, resp) = app
+        .post_json(
+            "/file/chunks",
+            json!({"collection": name, "file_path": "handbook/onboarding.md"}),
+        )
+        .await;
+    assert!(status.is_success(), "chunks status {status}: {resp}");
+    assert_eq!(resp["total_chunks"], 3);
+    assert_eq!(resp["chunks"][1]["content"], "Laptops ship on day one.");
+    assert_eq!(resp["chunks"][1]["line_range"], json!([4, 9]));
+
+    let (status, resp) = app
+        .post_json(
+            &format!("/qdrant/collections/{name}/points/scroll"),
+            json!({"limit": 10, "with_payload": true, "with_vector": false}),
+        )
+        .await;
+    assert!(status.is_success(), "scroll status {status}: {resp}");
+    let points = resp["result"]["points"].as_array().unwrap();
+    let payroll = points
+        .iter()
+        .find(|p| p["id"] == "handbook/onboarding.md#2")
+        .unwrap();
+    assert_eq!(payroll["payload"]["source"], "confluence");
+    assert_eq!(payroll["payload"]["section"], "pay");
+    assert_eq!(payroll["payload"]["parent_id"], "handbook/onboarding.md");
+
+    // A shorter new version garbage-collects the chunks it no longer has.
+    let (status, resp) = app
+        .post_json(
+            &documents,
+            json!({
+                "file_path": "handbook/onboarding.md",
+                "chunks": [{"content": "Welcome aboard."}],
+            }),
+        )
+        .await;
+    assert!(status.is_success(), "re-ingest status {status}: {resp}");
+    assert_eq!(resp["removed_stale"], 2);
+    let (status, resp) = app
+        .post_json(
+            "/file/content",
+            json!({"collection": name, "file_path": "handbook/onboarding.md"}),
+        )
+        .await;
+    assert!(status.is_success(), "content status {status}: {resp}");
+    assert_eq!(resp["content"], "Welcome aboard.");
+    assert_eq!(resp["chunks_available"], 1);
+
+    let (status, resp) = app
+        .delete(&format!("{documents}?file_path=handbook/onboarding.md"))
+        .await;
+    assert!(status.is_success(), "delete status {status}: {resp}");
+    assert_eq!(resp["deleted_chunks"], 1);
+    assert_eq!(document_count(&app, name).await, 0);
+
+    let (status, _) = app
+        .delete(&format!("{documents}?file_path=handbook/onboarding.md"))
+        .await;
+    assert_eq!(status.as_u16(), 404);
+}
+
+#[tokio::test]
+async fn invalid_documents_are_rejected() {
+    let app = TestApp::new().await;
+    let name = "byo_chunks_invalid";
+    let (status, _) = app
+        .post_json("/collections", json!({"name": name, "dimension": 512}))
+        .await;
+    assert!(status.is_success());
+
+    let documents = format!("/collections/{name}/documents");
+    for body in [
+        json!({"chunks": [{"content": "a"}]}),
+        json!({"file_path": "a#b", "chunks": [{"content": "a"}]}),
+        json!({"file_path": "a.md", "chunks": []}),
+        json!({"file_path": "a.md", "chunks": [{"chunk_index": 0}]}),
+        json!({"file_path": "a.md", "chunks": [
+            {"content": "a", "chunk_index": 1},
+            {"content": "b", "chunk_index": 1},
+        ]}),
+    ] {
+        let (status, resp) = app.post_json(&documents, body.clone()).await;
+        assert_eq!(status.as_u16(), 400, "{body} accepted: {resp}");
+    }
+
+    let (status, _) = app.delete(&documents).await;
+    assert_eq!(status.as_u16(), 400);
+}
@@ -0,0 +1,170 @@
+//! Query federation through the real router: `/federation/sources`,
+//! `/admin/federation/sources*` and `"sources"` on
+//! `POST /multi_collection_search`.
+//!
+//! The remote Vectorizer is a stand-in axum app on a loopback port that
+//! answers `/multi_collection_search` with canned hits, plus a `/slow`
+//! copy that never answers within the source timeout.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use std::time::Duration;
+
+use axum::Router;
+use axum::response::Json;
+use axum::routing::post;
+use common::TestApp;
+use serde_json::{Value, json};
+
+fn canned_results() -> Value {
+    json!({
+        "results": [
+            {
+                "content": "remote hit",
+                "score": 40.0,
+                "collection": "docs",
+                "doc_id": "r1",
+                "metadata": {},
+                "score_breakdown": null,
+            },
+            {
+                "content": "weaker remote hit",
+                "score": 10.0,
+                "collection": "docs",
+                "doc_id": "r2",
+                "metadata": {},
+                "score_breakdown": null,
+            },
+        ],
+    })
+}
+
+/// Serve the stand-in remote and return its base URL.
+async fn spawn_remote() -> String {
+    let app = Router::new()
+        .route(
+            "/multi_collection_search",
+            post(|| async { Json(canned_results()) }),
+        )
+        .route(
+            "/slow/multi_collection_search",
+            post(|| async {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                Json(canned_results())
+            }),
+        );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+    format!("http://{addr}")
+}
+
+#[tokio::test]
+async fn federated_search_merges_remote_hits_and_reports_timeouts() {
+    let app = TestApp::new().await;
+    let remote = spawn_remote().await;
+
+    let (status, resp) = app
+        .post_json(
+            "/admin/federation/sources",
+            json!({"name": "team-a", "url": remote, "weight": 0.5, "api_key": "s3cret"}),
+        )
+        .await;
+    assert!(status.is_success(), "register status {status}: {resp}");
+    assert_eq!(resp["replaced"], json!(false));
+    let (status, resp) = app
+        .post_json(
+            "/admin/federation/sources",
+            json!({"name": "slow", "url": format!("{remote}/slow"), "timeout_ms": 200}),
+        )
+        .await;
+    assert!(status.is_success(), "register status {status}: {resp}");
+
+    let (status, listed) = app.get("/federation/sources").await;
+    assert!(status.is_success(), "list status {status}: {listed}");
+    assert_eq!(listed["total"], json!(2));
+    let team_a = &listed["sources"][1];
+    assert_eq!(team_a["name"], json!("team-a"));
+    assert_eq!(team_a["has_api_key"], json!(true));
+    assert!(!listed.to_string().contains("s3cret"), "{listed}");
+
+    let (status, resp) = app
+        .post_json(
+            "/multi_collection_search",
+            json!({
+                "query": "anything",
+                "collections": ["docs"],
+                "sources": ["team-a", "slow"],
+            }),
+        )
+        .await;
+    assert!(status.is_success(), "search status {status}: {resp}");
+
+    let results = resp["results"].as_array().unwrap();
+    assert_eq!(results[0]["doc_id"], json!("r1"));
+    assert_eq!(results[0]["metadata"]["federation_source"], json!("team-a"));
+    // min-max puts the best remote hit at 1.0, then the 0.5 weight.
+    assert!((results[0]["score"].as_f64().unwrap() - 0.5).abs() < 1e-6);
+
+    let reports = resp["federation"]["sources"].as_array().unwrap();
+    let status_of = |name: &str| {
+        reports
+            .iter()
+            .find(|r| r["source"] == json!(name))
+            .map(|r| r["status"].clone())
+    };
+    assert_eq!(status_of("local"), Some(json!("ok")));
+    assert_eq!(status_of("team-a"), Some(json!("ok")));
+    assert_eq!(status_of("slow"), Some(json!("timeout")));
+    assert_eq!(resp["federation"]["score_normalization"], json!("min_max"));
+}
+
+#[tokio::test]
+async fn federated_search_rejects_unknown_sources_and_bad_input() {
+    let app = TestApp::new().await;
+
+    let (status, resp) = app
+        .post_json(
+            "/multi_collection_search",
+            json!({"query": "q", "collections": ["docs"], "sources": ["nope"]}),
+        )
+        .await;
+    assert_eq!(status.as_u16(), 404, "{resp}");
+
+    let (status, resp) = app
+        .post_json(
+            "/admin/federation/sources",
+            json!({"name": "local", "url": "http://127.0.0.1:1"}),
+        )
+        .await;
+    assert_eq!(status.as_u16(), 400, "{resp}");
+
+    let (status, resp) = app
+        .post_json(
+            "/admin/federation/sources",
+            json!({"name": "b", "url": "http://127.0.0.1:1"}),
+        )
+        .await;
+    assert!(status.is_success(), "register status {status}: {resp}");
+    let (status, resp) = app
+        .post_json(
+            "/multi_collection_search",
+            json!({
+                "query": "q",
+                "collections": ["docs"],
+                "sources": ["b"],
+                "score_normalization": "rank",
+            }),
+        )
+        .await;
+    assert_eq!(status.as_u16(), 400, "{resp}");
+
+    let (status, resp) = app.delete("/admin/federation/sources/b").await;
+    assert!(status.is_success(), "delete status {status}: {resp}");
+    let (status, resp) = app.delete("/admin/federation/sources/b").await;
+    assert_eq!(status.as_u16(), 404, "{resp}");
+}
@@ -0,0 +1,79 @@
+//! Split health probes: `/health/live` answers as soon as the process
+//! serves HTTP, `/health/ready` waits for the startup load and every
+//! configured readiness check.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use std::sync::Arc;
+
+use common::TestApp;
+use serde_json::json;
+use vectorizer::config::ReadinessConfig;
+use vectorizer_server::server::readiness::Readiness;
+
+#[tokio::test]
+async fn ready_waits_for_startup_load() {
+    let readiness = Arc::new(Readiness::new(ReadinessConfig::default()));
+    let installed = Arc::clone(&readiness);
+    let app = TestApp::with_server(move |server| server.readiness = installed).await;
+
+    let (status, resp) = app.get("/health/live").await;
+    assert!(status.is_success(), "live status {status}: {resp}");
+    assert_eq!(resp["status"], "alive");
+
+    let (status, resp) = app.get("/health/ready").await;
+    assert_eq!(status.as_u16(), 503, "ready while loading: {resp}");
+    assert_eq!(resp["status"], "not_ready");
+    assert_eq!(resp["checks"]["collections_loaded"]["ok"], false);
+    assert_eq!(resp["checks"]["vocabulary_loaded"]["ok"], false);
+    assert_eq!(resp["checks"]["indexing_queue"]["ok"], true);
+
+    let (status, resp) = app.get("/health").await;
+    assert!(status.is_success());
+    assert_eq!(resp["ready"], false);
+
+    readiness.mark_collections_loaded();
+    readiness.set_vocabulary_loaded(true);
+    let (status, resp) = app.get("/health/ready").await;
+    assert!(status.is_success(), "ready status {status}: {resp}");
+    assert_eq!(resp["ready"], true);
+}
+
+#[tokio::test]
+async fn disabled_checks_are_skipped() {
+    let config = ReadinessConfig {
+        collections_loaded: false,
+        vocabulary_loaded: false,
+        max_indexing_queue: None,
+        ..Default::default()
+    };
+    let app = TestApp::with_server(move |server| {
+        server.readiness = Arc::new(Readiness::new(config));
+    })
+    .await;
+
+    let (status, resp) = app.get("/health/ready").await;
+    assert!(status.is_success(), "ready status {status}: {resp}");
+    let checks = resp["checks"].as_object().unwrap();
+    assert_eq!(checks.keys().collect::<Vec<_>>(), ["draining", "read_only"]);
+}
+
+#[tokio::test]
+async fn draining_node_is_live_but_not_ready() {
+    let app = TestApp::new().await;
+    let (status, _) = app.get("/health/ready").await;
+    assert!(status.is_success());
+
+    let (status, _) = app
+        .post_json("/admin/drain", json!({"timeout_secs": 0}))
+        .await;
+    assert!(status.is_success());
+
+    let (status, resp) = app.get("/health/ready").await;
+    assert_eq!(status.as_u16(), 503);
+    assert_eq!(resp["checks"]["draining"]["ok"], false);
+    let (status, _) = app.get("/health/live").await;
+    assert!(status.is_success());
+}
@@ -0,0 +1,88 @@
+//! Collection `id_policy` through the real router: server-generated ULIDs
+//! for vectors inserted without an ID, and rejection of client IDs that
+//! break the collection's rules.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use common::TestApp;
+use serde_json::json;
+
+#[tokio::test]
+async fn id_policy_generates_ulids_and_rejects_bad_client_ids() {
+    let app = TestApp::new().await;
+    let name = "id_policy_ulid";
+    let _ = app.delete(&format!("/collections/{name}")).await;
+    let (status, resp) = app
+        .post_json(
+            "/collections",
+            json!({
+                "name": name,
+                "dimension": 512,
+                "id_policy": {"generator": "ulid", "max_length": 32, "charset": "url_safe"},
+            }),
+        )
+        .await;
+    assert!(status.is_success(), "create status {status}: {resp}");
+
+    let (_, info) = app.get(&format!("/collections/{name}")).await;
+    assert_eq!(info["id_policy"]["generator"], "ulid");
+
+    let embedding = vec![0.1_f32; 512];
+    let (status, resp) = app
+        .post_json(
+            "/insert_vectors",
+            json!({
+                "collection": name,
+                "vectors": [
+                    {"embedding": embedding},
+                    {"id": "doc_1", "embedding": embedding},
+                    {"id": "doc/2", "embedding": embedding},
+                ],
+            }),
+        )
+        .await;
+    assert!(status.is_success(), "insert status {status}: {resp}");
+    assert_eq!(resp["inserted"].as_u64(), Some(2), "{resp}");
+
+    let generated = resp["results"][0]["vector_ids"][0].as_str().unwrap();
+    assert_eq!(generated.len(), 26, "expected a ULID, got {generated}");
+    assert!(generated.chars().all(|c| c.is_ascii_alphanumeric()));
+    assert_eq!(resp["results"][1]["vector_ids"][0], "doc_1");
+    assert_eq!(resp["results"][2]["status"], "error");
+    assert_eq!(resp["results"][2]["error_type"], "invalid_vector_id");
+
+    let (status, _) = app
+        .get(&format!("/collections/{name}/vectors/{generated}"))
+        .await;
+    assert!(status.is_success());
+}
+
+#[tokio::test]
+async fn id_policy_that_rejects_its_own_ids_is_refused() {
+    let app = TestApp::new().await;
+    let (status, resp) = app
+        .post_json(
+            "/collections",
+            json!({
+                "name": "id_policy_uuid_alphanumeric",
+                "dimension": 512,
+                "id_policy": {"generator": "uuid_v4", "charset": "alphanumeric"},
+            }),
+        )
+        .await;
+    assert_eq!(status.as_u16(), 400, "{resp}");
+
+    let (status, resp) = app
+        .post_json(
+            "/collections",
+            json!({
+                "name": "id_policy_unknown_generator",
+                "dimension": 512,
+                "id_policy": {"generator": "snowflake"},
+            }),
+        )
+        .await;
+    assert_eq!(status.as_u16(), 400, "{resp}");
+}
@@ -0,0 +1,59 @@
+//! `index_optimization` on `GET /collections/{name}` through the real
+//! router.
+//!
+//! Deleting vectors leaves stale nodes in the HNSW graph; the collection
+//! detail reports them so operators can see what the background
+//! optimizer will act on.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use common::TestApp;
+use serde_json::json;
+
+#[tokio::test]
+async fn collection_detail_reports_stale_hnsw_nodes_after_deletes() {
+    let app = TestApp::new().await;
+    let name = "index_optimization_report";
+    let _ = app.delete(&format!("/collections/{name}")).await;
+
+    let (status, resp) = app
+        .post_json(
+            "/collections",
+            json!({"name": name, "dimension": 512, "metric": "euclidean"}),
+        )
+        .await;
+    assert!(status.is_success(), "create status {status}: {resp}");
+
+    // 512 dims to match the harness's bm25 provider.
+    let vectors: Vec<_> = (0..5)
+        .map(|i| {
+            let embedding: Vec<f32> = (0..512).map(|d| ((i * 512 + d) as f32).sin()).collect();
+            json!({"id": format!("v{i}"), "embedding": embedding})
+        })
+        .collect();
+    let (status, resp) = app
+        .post_json(
+            "/insert_vectors",
+            json!({"collection": name, "vectors": vectors}),
+        )
+        .await;
+    assert!(status.is_success(), "insert status {status}: {resp}");
+
+    for id in ["v0", "v1"] {
+        let (status, resp) = app
+            .delete(&format!("/collections/{name}/vectors/{id}"))
+            .await;
+        assert!(status.is_success(), "delete {id} status {status}: {resp}");
+    }
+
+    let (status, info) = app.get(&format!("/collections/{name}")).await;
+    assert!(status.is_success(), "get status {status}: {info}");
+    let report = &info["index_optimization"];
+    assert_eq!(report["graph_nodes"].as_u64(), Some(5), "{report}");
+    assert_eq!(report["stale_nodes"].as_u64(), Some(2), "{report}");
+    assert!((report["fragmentation"].as_f64().unwrap() - 0.4).abs() < 1e-9);
+    assert_eq!(report["compaction"]["runs"].as_u64(), Some(0));
+    assert_eq!(report["compaction"]["running"].as_bool(), Some(false));
+}
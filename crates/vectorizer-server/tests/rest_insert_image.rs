@@ -0,0 +1,193 @@
+//! Image inserts (`POST /insert_image`): images are embedded by the
+//! collection's multimodal provider, from a base64 JSON body or a
+//! multipart upload, and text search embeds the query with that same
+//! provider so the images are found by description.
+//!
+//! The harness registers a tiny fake multimodal provider instead of
+//! CLIP, which needs the `onnx-models` feature and a model download.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use std::sync::Arc;
+
+use axum::http::StatusCode;
+use base64::Engine;
+use common::{MultipartField, TestApp};
+use serde_json::json;
+use vectorizer::embedding::{Bm25Embedding, EmbeddingManager, EmbeddingProvider};
+
+/// Embeds images starting with `CAT` and texts mentioning a cat on one
+/// axis, everything else on others
+struct FakeMultimodal;
+
+impl EmbeddingProvider for FakeMultimodal {
+    fn embed_batch(&self, texts: &[&str]) -> vectorizer::error::Result<Vec<Vec<f32>>> {
+        Ok(texts
+            .iter()
+            .map(|text| {
+                if text.contains("cat") {
+                    vec![1.0, 0.0, 0.0, 0.0]
+                } else {
+                    vec![0.0, 1.0, 0.0, 0.0]
+                }
+            })
+            .collect())
+    }
+
+    fn dimension(&self) -> usize {
+        4
+    }
+
+    fn embed_image(&self, image: &[u8]) -> vectorizer::error::Result<Vec<f32>> {
+        Ok(if image.starts_with(b"CAT") {
+            vec![1.0, 0.0, 0.0, 0.0]
+        } else {
+            vec![0.0, 0.0, 1.0, 0.0]
+        })
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+async fn app_with_image_collection(name: &str) -> TestApp {
+    let app = TestApp::with_server(|server| {
+        let mut manager = EmbeddingManager::new();
+        manager.register_provider("bm25".to_string(), Box::new(Bm25Embedding::new(512)));
+        manager.register_provider("fake-clip".to_string(), Box::new(FakeMultimodal));
+        manager.set_default_provider("bm25").unwrap();
+        server.embedding_manager = Arc::new(manager);
+    })
+    .await;
+    let (status, resp) = app
+        .post_json(
+            "/collections",
+            json!({
+                "name": name,
+                "dimension": 4,
+                "metric": "cosine",
+                "embedding_provider": "fake-clip",
+            }),
+        )
+        .await;
+    assert!(status.is_success(), "create status {status}: {resp}");
+    app
+}
+
+fn encode(bytes: &[u8]) -> String {
+    base64::engine::general_purpose::STANDARD.encode(bytes)
+}
+
+#[tokio::test]
+async fn json_image_is_found_by_text_search() {
+    let app = app_with_image_collection("shots").await;
+
+    for (id, image) in [
+        ("cat", b"CAT-PNG".as_slice()),
+        ("chart", b"CHART-PNG".as_slice()),
+    ] {
+        let (status, resp) = app
+            .post_json(
+                "/insert_image",
+                json!({
+                    "collection": "shots",
+                    "image": encode(image),
+                    "filename": format!("{id}.png"),
+                    "id": id,
+                    "metadata": {"source": "test"},
+                }),
+            )
+            .await;
+        assert_eq!(status, StatusCode::OK, "insert {id}: {resp}");
+        assert_eq!(resp["vector_id"], id);
+        assert_eq!(resp["vectors_created"], 1);
+        assert_eq!(resp["embedding_provider"], "fake-clip");
+    }
+
+    let (status, resp) = app
+        .post_json(
+            "/collections/shots/search/text",
+            json!({"query": "a photo of a cat", "limit": 1}),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "search: {resp}");
+    let hit = &resp["results"][0];
+    assert_eq!(hit["id"], "cat");
+    assert_eq!(hit["payload"]["modality"], "image");
+    assert_eq!(hit["payload"]["image_path"], "cat.png");
+    assert_eq!(hit["payload"]["image_size_bytes"], 7);
+    assert_eq!(hit["payload"]["source"], "test");
+}
+
+#[tokio::test]
+async fn multipart_image_takes_the_part_filename() {
+    let app = app_with_image_collection("uploads").await;
+
+    let (status, resp) = app
+        .post_multipart(
+            "/insert_image",
+            &[
+                MultipartField::text("collection", "uploads"),
+                MultipartField::text("id", "up-1"),
+                MultipartField::text("metadata", r#"{"app": "billing"}"#),
+                MultipartField::file("file", "login.png", "image/png", b"CAT-BYTES".to_vec()),
+            ],
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "insert: {resp}");
+
+    let (status, resp) = app.get("/collections/uploads/vectors/up-1").await;
+    assert_eq!(status, StatusCode::OK, "get: {resp}");
+    let payload = &resp["payload"];
+    assert_eq!(payload["image_path"], "login.png", "{resp}");
+    assert_eq!(payload["app"], "billing");
+}
+
+#[tokio::test]
+async fn text_only_provider_and_bad_input_are_rejected() {
+    let app = app_with_image_collection("shots").await;
+    let (status, resp) = app
+        .post_json(
+            "/collections",
+            json!({"name": "texts", "dimension": 512, "embedding_provider": "bm25"}),
+        )
+        .await;
+    assert!(status.is_success(), "create status {status}: {resp}");
+
+    let image = encode(b"CAT-PNG");
+    let (status, resp) = app
+        .post_json(
+            "/insert_image",
+            json!({"collection": "texts", "image": image}),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{resp}");
+
+    let (status, resp) = app
+        .post_json(
+            "/insert_image",
+            json!({"collection": "shots", "image": "not base64!"}),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{resp}");
+
+    let (status, resp) = app
+        .post_json("/insert_image", json!({"collection": "shots"}))
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{resp}");
+
+    let (status, resp) = app
+        .post_json(
+            "/insert_image",
+            json!({"collection": "missing", "image": image}),
+        )
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND, "{resp}");
+}
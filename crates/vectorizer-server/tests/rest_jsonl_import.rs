@@ -0,0 +1,127 @@
+//! Streamed JSONL import (`POST /collections/{name}/import` with an
+//! NDJSON body): lines are embedded server-side, bad lines are reported
+//! without stopping the import and `?offset=` resumes it.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use common::TestApp;
+use serde_json::{Value, json};
+
+const NDJSON: &str = "application/x-ndjson";
+
+fn events(body: &[u8]) -> Vec<Value> {
+    std::str::from_utf8(body)
+        .expect("utf-8 event stream")
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("event is JSON"))
+        .collect()
+}
+
+async fn create_collection(app: &TestApp, name: &str) {
+    let (status, resp) = app.post_json("/collections", json!({"name": name})).await;
+    assert!(status.is_success(), "create status {status}: {resp}");
+}
+
+#[tokio::test]
+async fn jsonl_import_embeds_lines_and_reports_progress() {
+    let app = TestApp::new().await;
+    create_collection(&app, "jsonl_docs").await;
+
+    let body = concat!(
+        "{\"id\": \"doc-1\", \"text\": \"vector databases store embeddings\", \"payload\": {\"lang\": \"en\"}}\n",
+        "{\"text\": \"semantic search finds documents by meaning\"}\r\n",
+        "\n",
+        "not json\n",
+        "{\"id\": \"doc-3\", \"text\": \"the quick brown fox\", \"payload\": [1, 2]}\n",
+        "{\"id\": \"doc-4\", \"text\": \"machine learning models\"}",
+    );
+    let (status, content_type, resp) = app
+        .post_raw_for_bytes(
+            "/collections/jsonl_docs/import?batch_size=2",
+            NDJSON,
+            body.as_bytes(),
+        )
+        .await;
+    assert!(status.is_success(), "import status {status}");
+    assert_eq!(content_type.as_deref(), Some(NDJSON));
+
+    let events = events(&resp);
+    let done = events.last().expect("a final event");
+    assert_eq!(done["event"], "done", "events: {events:?}");
+    assert_eq!(done["offset"], 6);
+    assert_eq!(done["imported"], 3);
+    assert_eq!(done["failed"], 2);
+    assert!(
+        events[..events.len() - 1]
+            .iter()
+            .all(|e| e["event"] == "progress")
+    );
+    let lines: Vec<&Value> = events
+        .iter()
+        .flat_map(|e| e["errors"].as_array().unwrap())
+        .map(|e| &e["line"])
+        .collect();
+    assert_eq!(lines, [&json!(4), &json!(5)]);
+
+    let (status, vector) = app.get("/collections/jsonl_docs/vectors/doc-1").await;
+    assert!(status.is_success(), "get status {status}: {vector}");
+    assert_eq!(vector["payload"]["lang"], "en");
+    assert_eq!(
+        vector["payload"]["content"],
+        "vector databases store embeddings"
+    );
+    assert_eq!(vector["vector"].as_array().unwrap().len(), 512);
+    let (_, info) = app.get("/collections/jsonl_docs").await;
+    assert_eq!(info["vector_count"], 3);
+}
+
+#[tokio::test]
+async fn jsonl_import_resumes_from_offset() {
+    let app = TestApp::new().await;
+    create_collection(&app, "jsonl_resume").await;
+
+    let body = concat!(
+        "{\"id\": \"a\", \"text\": \"first line\"}\n",
+        "{\"id\": \"b\", \"text\": \"second line\"}\n",
+        "{\"id\": \"c\", \"text\": \"third line\"}\n",
+    );
+    let (status, _, resp) = app
+        .post_raw_for_bytes(
+            "/collections/jsonl_resume/import?format=jsonl&offset=2",
+            "text/plain",
+            body.as_bytes(),
+        )
+        .await;
+    assert!(status.is_success(), "import status {status}");
+    let done = events(&resp).pop().unwrap();
+    assert_eq!(done["event"], "done");
+    assert_eq!(done["offset"], 3);
+    assert_eq!(done["imported"], 1);
+
+    let (status, _) = app.get("/collections/jsonl_resume/vectors/c").await;
+    assert!(status.is_success());
+    let (status, _) = app.get("/collections/jsonl_resume/vectors/a").await;
+    assert_eq!(status.as_u16(), 404);
+}
+
+#[tokio::test]
+async fn jsonl_import_rejects_bad_requests_before_streaming() {
+    let app = TestApp::new().await;
+    create_collection(&app, "jsonl_bad").await;
+
+    let line = b"{\"text\": \"hello\"}\n";
+    let (status, _, _) = app
+        .post_raw_for_bytes("/collections/jsonl_nowhere/import", NDJSON, line)
+        .await;
+    assert_eq!(status.as_u16(), 404);
+    let (status, _, _) = app
+        .post_raw_for_bytes("/collections/jsonl_bad/import?batch_size=0", NDJSON, line)
+        .await;
+    assert_eq!(status.as_u16(), 400);
+    let (status, _, _) = app
+        .post_raw_for_bytes("/collections/jsonl_bad/import?format=csv", NDJSON, line)
+        .await;
+    assert_eq!(status.as_u16(), 400);
+}
@@ -10,9 +10,13 @@
 //! - `POST /collections/{name}/vectors/delete_by_filter` — `delete_by_filter`
 //! - `POST /collections/{name}/vectors/bulk_update_metadata` — `bulk_update_metadata`
 //! - `POST /collections/{name}/ttl` — `set_collection_ttl`
+//! - `PATCH /collections/{name}` — `patch_collection`
 //! - `POST /collections/{name}/rename` — `rename_collection`
 //! - `POST /collections/{name}/reindex` — `reindex_collection`
 //! - `POST /collections/{name}/reencode` — `reencode_collection`
+//! - `POST /collections/{name}/clone` — `clone_collection`
+//! - `GET /jobs`, `GET /jobs/{id}`, `DELETE /jobs/{id}` — `list_jobs` /
+//!   `get_job` / `cancel_job`
 //! - `POST /collections/{name}/snapshot`,
 //!   `GET /collections/{name}/snapshots`,
 //!   `POST /collections/{name}/snapshots/{id}/restore` —
@@ -118,6 +122,21 @@ async fn vector_count(app: &TestApp, name: &str) -> u64 {
     meta["vector_count"].as_u64().unwrap_or(0)
 }
 
+/// Poll `GET /jobs/{id}` until the job leaves `running`, and return it.
+async fn wait_for_job(app: &TestApp, id: &str) -> Value {
+    let mut job = Value::Null;
+    for _ in 0..100 {
+        let (status, resp) = app.get(&format!("/jobs/{id}")).await;
+        assert!(status.is_success(), "job status {status}: {resp}");
+        job = resp["job"].clone();
+        if job["state"].as_str() != Some("running") {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    job
+}
+
 /// `GET /collections/{name}/vectors?limit=50` and return the raw
 /// `vectors` array (each entry has `id`, `vector`, `payload`).
 async fn list_all_vectors(app: &TestApp, name: &str) -> Vec<Value> {
@@ -428,6 +447,75 @@ async fn set_collection_ttl_rejects_missing_collection() {
     assert_eq!(body["error_type"].as_str(), Some("collection_not_found"));
 }
 
+// ─── patch_collection ───────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn patch_collection_happy_path_changes_settings_in_place() {
+    let app = new_app().await;
+    let name = "lifecycle_patch_happy";
+    seed_with_tags(&app, name, &["a", "b"]).await;
+
+    let (status, resp) = app
+        .patch_json(
+            &format!("/collections/{name}"),
+            json!({
+                "ef_search": 128,
+                "quantization": "sq8",
+                "ttl_secs": 600,
+                "payload_indexes": [
+                    {"field_name": "tag", "index_type": "Keyword", "enabled": true}
+                ],
+            }),
+        )
+        .await;
+    assert!(status.is_success(), "patch status {status}: {resp}");
+    assert_eq!(resp["ef_search"].as_u64(), Some(128));
+    assert_eq!(resp["ttl_secs"].as_u64(), Some(600));
+    assert_eq!(resp["quantization"]["type"].as_str(), Some("sq"));
+    assert_eq!(
+        vector_count(&app, name).await,
+        2,
+        "patch must not lose vectors"
+    );
+
+    let (status, resp) = app.get(&format!("/collections/{name}")).await;
+    assert!(status.is_success(), "get status {status}: {resp}");
+    assert_eq!(resp["ef_search"].as_u64(), Some(128));
+
+    let (status, resp) = app
+        .patch_json(&format!("/collections/{name}"), json!({"ef_search": null}))
+        .await;
+    assert!(status.is_success(), "unpin status {status}: {resp}");
+    assert!(resp["ef_search"].is_null());
+}
+
+#[tokio::test]
+async fn patch_collection_rejects_immutable_fields_and_bad_values() {
+    let app = new_app().await;
+    let name = "lifecycle_patch_rejects";
+    create_collection(&app, name).await;
+
+    for body in [
+        json!({"dimension": 256}),
+        json!({"metric": "euclidean"}),
+        json!({"ef_search": 0}),
+        json!({"quantization": "pq"}),
+    ] {
+        let (status, resp) = app.patch_json(&format!("/collections/{name}"), body).await;
+        assert_eq!(status.as_u16(), 400, "{resp}");
+        assert_eq!(resp["error_type"].as_str(), Some("validation_error"));
+    }
+
+    let (status, body) = app
+        .patch_json(
+            "/collections/lifecycle_patch_missing",
+            json!({"ef_search": 64}),
+        )
+        .await;
+    assert_eq!(status.as_u16(), 404);
+    assert_eq!(body["error_type"].as_str(), Some("collection_not_found"));
+}
+
 // ─── rename_collection ──────────────────────────────────────────────────────
 
 #[tokio::test]
@@ -495,6 +583,41 @@ async fn reindex_collection_happy_path_rebuilds_index() {
     );
 }
 
+#[tokio::test]
+async fn reindex_collection_without_wait_runs_as_a_job() {
+    let app = new_app().await;
+    let name = "lifecycle_reindex_job";
+    seed_with_tags(&app, name, &["a", "b", "c"]).await;
+
+    let (status, resp) = app
+        .post_json(
+            &format!("/collections/{name}/reindex?wait=false"),
+            json!({"m": 8}),
+        )
+        .await;
+    assert!(status.is_success(), "reindex status {status}: {resp}");
+    assert_eq!(resp["state"].as_str(), Some("running"));
+    let job_id = resp["job_id"].as_str().expect("job id").to_string();
+
+    let job = wait_for_job(&app, &job_id).await;
+    assert_eq!(job["state"].as_str(), Some("completed"), "job: {job}");
+    assert_eq!(job["kind"].as_str(), Some("collection_reindex"));
+    assert_eq!(job["processed"].as_u64(), Some(3));
+
+    let (status, resp) = app.get(&format!("/jobs?collection={name}")).await;
+    assert!(status.is_success(), "list status {status}: {resp}");
+    assert_eq!(resp["jobs"][0]["id"].as_str(), Some(job_id.as_str()));
+
+    // Deleting a finished job forgets it
+    let (status, resp) = app.delete(&format!("/jobs/{job_id}")).await;
+    assert!(status.is_success(), "delete status {status}: {resp}");
+    assert_eq!(resp["removed"].as_bool(), Some(true));
+    let (status, _) = app.get(&format!("/jobs/{job_id}")).await;
+    assert_eq!(status.as_u16(), 404);
+    let (status, _) = app.delete(&format!("/jobs/{job_id}")).await;
+    assert_eq!(status.as_u16(), 404);
+}
+
 #[tokio::test]
 async fn reindex_collection_rejects_missing_collection() {
     let app = new_app().await;
@@ -543,6 +666,60 @@ async fn reencode_collection_rejects_missing_target_encoding() {
     assert_eq!(body["error_type"].as_str(), Some("validation_error"));
 }
 
+// ─── clone_collection ───────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn clone_collection_happy_path_copies_vectors_as_a_job() {
+    let app = new_app().await;
+    let name = "lifecycle_clone_source";
+    let target = "lifecycle_clone_target";
+    seed_with_tags(&app, name, &["a", "b", "c"]).await;
+
+    let (status, resp) = app
+        .post_json(
+            &format!("/collections/{name}/clone"),
+            json!({"target": target, "config": {"quantization": {"type": "sq", "bits": 8}}}),
+        )
+        .await;
+    assert!(status.is_success(), "clone status {status}: {resp}");
+    assert_eq!(resp["status"].as_str(), Some("started"));
+    assert_eq!(resp["reembed"].as_bool(), Some(false));
+    let job = wait_for_job(&app, resp["job_id"].as_str().expect("job id")).await;
+    assert_eq!(job["state"].as_str(), Some("completed"), "job: {job}");
+    assert_eq!(job["result"]["copied"].as_u64(), Some(3));
+
+    assert_eq!(vector_count(&app, target).await, 3);
+    assert_eq!(
+        vector_count(&app, name).await,
+        3,
+        "source must be untouched"
+    );
+}
+
+#[tokio::test]
+async fn clone_collection_rejects_missing_or_existing_target() {
+    let app = new_app().await;
+    let name = "lifecycle_clone_invalid";
+    create_collection(&app, name).await;
+
+    let (status, body) = app
+        .post_json(&format!("/collections/{name}/clone"), json!({}))
+        .await;
+    assert_eq!(status.as_u16(), 400);
+    assert_eq!(body["error_type"].as_str(), Some("validation_error"));
+
+    let (status, _) = app
+        .post_json(
+            &format!("/collections/{name}/clone"),
+            json!({"target": name}),
+        )
+        .await;
+    assert_eq!(status.as_u16(), 409);
+
+    let (status, _) = app.get("/jobs/does-not-exist").await;
+    assert_eq!(status.as_u16(), 404);
+}
+
 // ─── list_empty_collections ─────────────────────────────────────────────────
 
 #[tokio::test]
@@ -0,0 +1,124 @@
+//! Metadata key-value store (`/collections/{name}/kv`): pipeline
+//! bookkeeping stored next to the vectors, with compare-and-swap writes
+//! and atomic transactions.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use common::TestApp;
+use serde_json::json;
+
+#[tokio::test]
+async fn kv_round_trip_with_conditional_writes() {
+    let app = TestApp::new().await;
+    let name = "kv_pipeline";
+    let (status, _) = app
+        .post_json("/collections", json!({"name": name, "dimension": 512}))
+        .await;
+    assert!(status.is_success());
+
+    let cursor = format!("/collections/{name}/kv/crawler/cursor");
+    let (status, _) = app.get(&cursor).await;
+    assert_eq!(status.as_u16(), 404);
+
+    let (status, resp) = app
+        .put_json(&cursor, json!({"value": {"offset": 100}, "if_version": 0}))
+        .await;
+    assert!(status.is_success(), "put status {status}: {resp}");
+    let version = resp["version"].as_u64().unwrap();
+
+    let (status, resp) = app.get(&cursor).await;
+    assert!(status.is_success(), "get status {status}: {resp}");
+    assert_eq!(resp["key"], "crawler/cursor");
+    assert_eq!(resp["value"]["offset"], 100);
+    assert_eq!(resp["version"], version);
+
+    // A writer holding a stale version loses
+    let (status, resp) = app
+        .put_json(&cursor, json!({"value": {"offset": 50}, "if_version": 0}))
+        .await;
+    assert_eq!(status.as_u16(), 409, "stale put accepted: {resp}");
+    assert_eq!(resp["error_type"], "version_conflict");
+
+    let (status, resp) = app
+        .put_json(
+            &cursor,
+            json!({"value": {"offset": 200}, "if_version": version}),
+        )
+        .await;
+    assert!(status.is_success(), "cas status {status}: {resp}");
+    assert!(resp["version"].as_u64().unwrap() > version);
+
+    let (status, resp) = app
+        .get(&format!("/collections/{name}/kv?prefix=crawler/"))
+        .await;
+    assert!(status.is_success(), "list status {status}: {resp}");
+    assert_eq!(resp["namespace"], "default");
+    assert_eq!(resp["entries"].as_array().unwrap().len(), 1);
+    assert_eq!(resp["entries"][0]["value"]["offset"], 200);
+
+    let (status, _) = app.delete(&cursor).await;
+    assert!(status.is_success());
+    let (status, _) = app.delete(&cursor).await;
+    assert_eq!(status.as_u16(), 404);
+}
+
+#[tokio::test]
+async fn kv_transaction_is_all_or_nothing() {
+    let app = TestApp::new().await;
+    let name = "kv_txn";
+    let (status, _) = app
+        .post_json("/collections", json!({"name": name, "dimension": 512}))
+        .await;
+    assert!(status.is_success());
+
+    let kv = format!("/collections/{name}/kv");
+    let (status, resp) = app
+        .post_json(
+            &kv,
+            json!({
+                "checks": [{"key": "schema", "version": 0}],
+                "ops": [
+                    {"op": "put", "key": "schema", "value": 2},
+                    {"op": "put", "key": "watermark", "value": "2026-10-16"},
+                ],
+            }),
+        )
+        .await;
+    assert!(status.is_success(), "txn status {status}: {resp}");
+    assert_eq!(resp["results"].as_array().unwrap().len(), 2);
+
+    // Same condition again: the schema now exists, so nothing is written
+    let (status, resp) = app
+        .post_json(
+            &kv,
+            json!({
+                "checks": [{"key": "schema", "version": 0}],
+                "ops": [
+                    {"op": "put", "key": "schema", "value": 3},
+                    {"op": "delete", "key": "watermark"},
+                ],
+            }),
+        )
+        .await;
+    assert_eq!(status.as_u16(), 409, "conflicting txn accepted: {resp}");
+    let (_, resp) = app.get(&format!("{kv}/schema")).await;
+    assert_eq!(resp["value"], 2);
+    let (status, _) = app.get(&format!("{kv}/watermark")).await;
+    assert!(status.is_success());
+
+    for body in [
+        json!({}),
+        json!({"ops": [{"op": "rename", "key": "a"}]}),
+        json!({"ops": [{"op": "put", "key": "", "value": 1}]}),
+    ] {
+        let (status, resp) = app.post_json(&kv, body.clone()).await;
+        assert_eq!(status.as_u16(), 400, "{body} accepted: {resp}");
+    }
+
+    let (status, _) = app
+        .post_json("/collections/kv_nowhere/kv", json!({"ops": []}))
+        .await;
+    assert_eq!(status.as_u16(), 404);
+}
@@ -0,0 +1,74 @@
+//! `"storage": "mmap"` on `POST /collections` through the real router.
+//!
+//! Covers that an mmap-backed collection reports its storage type, that
+//! vectors written to it read back intact (via the similarity matrix,
+//! which goes through `VectorStore::get_vector`), and that an unknown
+//! storage value is rejected.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use common::{TestApp, embedding};
+use serde_json::json;
+
+#[tokio::test]
+async fn mmap_collection_round_trips_vectors() {
+    let app = TestApp::new().await;
+    let name = "mmap_storage_round_trip";
+    let _ = app.delete(&format!("/collections/{name}")).await;
+
+    let resp = app
+        .create_collection(
+            json!({"name": name, "dimension": 512, "metric": "euclidean", "storage": "mmap"}),
+        )
+        .await;
+    assert_eq!(resp["storage"].as_str(), Some("mmap"));
+
+    app.insert_vectors(
+        name,
+        json!([
+            {"id": "a", "embedding": embedding(&[])},
+            {"id": "b", "embedding": embedding(&[3.0, 4.0])},
+        ]),
+    )
+    .await;
+
+    let (status, info) = app.get(&format!("/collections/{name}")).await;
+    assert!(status.is_success(), "get status {status}: {info}");
+    assert_eq!(info["storage"].as_str(), Some("mmap"));
+    assert_eq!(info["vector_count"].as_u64(), Some(2));
+
+    let (status, resp) = app
+        .post_json(
+            &format!("/collections/{name}/similarity_matrix"),
+            json!({"ids": ["a", "b"]}),
+        )
+        .await;
+    assert!(status.is_success(), "matrix status {status}: {resp}");
+    let distance = resp["matrix"][0][1].as_f64().unwrap();
+    assert!((distance - 5.0).abs() < 1e-4, "distance was {distance}");
+
+    let (status, _) = app.delete(&format!("/collections/{name}")).await;
+    assert!(status.is_success());
+}
+
+#[tokio::test]
+async fn create_collection_defaults_to_memory_and_rejects_unknown_storage() {
+    let app = TestApp::new().await;
+    let name = "mmap_storage_default";
+    let _ = app.delete(&format!("/collections/{name}")).await;
+
+    let resp = app
+        .create_collection(json!({"name": name, "dimension": 512}))
+        .await;
+    assert_eq!(resp["storage"].as_str(), Some("memory"));
+
+    let (status, _) = app
+        .post_json(
+            "/collections",
+            json!({"name": "mmap_storage_bogus", "dimension": 512, "storage": "tape"}),
+        )
+        .await;
+    assert_eq!(status.as_u16(), 400);
+}
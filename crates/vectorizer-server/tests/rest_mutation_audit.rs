@@ -0,0 +1,184 @@
+//! `audit_log` through the real router: mutating REST calls are written
+//! to the audit file and collection with their collection and ID count,
+//! reads (including POST searches) are not.
+//!
+//! The harness runs with auth disabled, so every record's actor is
+//! anonymous.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use common::TestApp;
+use serde_json::{Value, json};
+use vectorizer::config::AuditLogConfig;
+use vectorizer::security::MutationAuditLog;
+
+/// A harness app that writes its audit log to `path`.
+async fn audited_app(path: &std::path::Path) -> TestApp {
+    let config = AuditLogConfig {
+        enabled: true,
+        path: Some(path.to_path_buf()),
+        ..Default::default()
+    };
+    TestApp::with_server(|server| {
+        server.audit_log = MutationAuditLog::from_config(&config, server.store.clone())
+            .unwrap()
+            .map(Arc::new);
+    })
+    .await
+}
+
+/// Audit lines written so far, once at least `expected` are there.
+async fn audit_lines(path: &std::path::Path, expected: usize) -> Vec<Value> {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    loop {
+        let lines: Vec<Value> = std::fs::read_to_string(path)
+            .unwrap_or_default()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        if lines.len() >= expected || Instant::now() > deadline {
+            return lines;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+}
+
+#[tokio::test]
+async fn mutating_calls_are_audited() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("mutations.jsonl");
+    let config = AuditLogConfig {
+        enabled: true,
+        path: Some(path.clone()),
+        collection: Some("_rest_audit".to_string()),
+        ..Default::default()
+    };
+    let app = TestApp::with_server(|server| {
+        server.audit_log = MutationAuditLog::from_config(&config, server.store.clone())
+            .unwrap()
+            .map(Arc::new);
+    })
+    .await;
+
+    let name = "audited";
+    let (status, resp) = app
+        .post_json("/collections", json!({"name": name, "dimension": 512}))
+        .await;
+    assert!(status.is_success(), "create status {status}: {resp}");
+
+    let vectors: Vec<Value> = (0..2)
+        .map(|i| json!({"id": format!("v{i}"), "embedding": vec![0.1_f32 + i as f32; 512]}))
+        .collect();
+    let (status, resp) = app
+        .post_json(
+            "/insert_vectors",
+            json!({"collection": name, "vectors": vectors}),
+        )
+        .await;
+    assert!(status.is_success(), "insert status {status}: {resp}");
+
+    let (status, _) = app
+        .post_json(
+            &format!("/collections/{name}/search"),
+            json!({"vector": vec![0.1_f32; 512], "limit": 1}),
+        )
+        .await;
+    assert!(status.is_success());
+
+    let (status, _) = app.delete(&format!("/collections/{name}/vectors/v0")).await;
+    assert!(status.is_success());
+
+    let lines = audit_lines(&path, 3).await;
+    let operations: Vec<&str> = lines
+        .iter()
+        .map(|l| l["operation"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        operations,
+        [
+            "POST /collections",
+            "POST /insert_vectors",
+            "DELETE /collections/{name}/vectors/{id}",
+        ],
+        "searches are not audited: {lines:?}"
+    );
+    for line in &lines {
+        assert_eq!(line["protocol"], "rest");
+        assert_eq!(line["actor"]["kind"], "anonymous");
+        assert_eq!(line["collection"], name);
+        assert_eq!(line["success"], true);
+    }
+    assert_eq!(lines[1]["ids_count"], 2);
+    assert_eq!(lines[2]["ids_count"], 1);
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    loop {
+        let (_, info) = app.get("/collections/_rest_audit").await;
+        if info["vector_count"].as_u64() == Some(3) {
+            break;
+        }
+        assert!(Instant::now() < deadline, "audit collection: {info}");
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+}
+
+#[tokio::test]
+async fn writes_are_audited_whatever_the_collection_is_called() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("mutations.jsonl");
+    let app = audited_app(&path).await;
+
+    // "research" contains "search", "accounts" contains "count"
+    for name in ["research", "accounts"] {
+        app.create_collection(json!({"name": name, "dimension": 512}))
+            .await;
+        let (status, resp) = app
+            .post_json(
+                &format!("/collections/{name}/vectors/delete_by_filter"),
+                json!({"filter": {"must": [{"type": "match", "key": "tag", "match_value": "x"}]}}),
+            )
+            .await;
+        assert!(
+            status.is_success(),
+            "delete_by_filter status {status}: {resp}"
+        );
+        let (status, _) = app
+            .post_json(
+                &format!("/collections/{name}/search"),
+                json!({"vector": vec![0.1_f32; 512], "limit": 1}),
+            )
+            .await;
+        assert!(status.is_success());
+    }
+    let (status, resp) = app
+        .post_json("/slow_queries/config", json!({"threshold_ms": 250}))
+        .await;
+    assert!(
+        status.is_success(),
+        "slow query config status {status}: {resp}"
+    );
+
+    let lines = audit_lines(&path, 5).await;
+    let operations: Vec<&str> = lines
+        .iter()
+        .map(|l| l["operation"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        operations,
+        [
+            "POST /collections",
+            "POST /collections/{name}/vectors/delete_by_filter",
+            "POST /collections",
+            "POST /collections/{name}/vectors/delete_by_filter",
+            "POST /slow_queries/config",
+        ],
+        "{lines:?}"
+    );
+    assert_eq!(lines[1]["collection"], "research");
+    assert_eq!(lines[3]["collection"], "accounts");
+}
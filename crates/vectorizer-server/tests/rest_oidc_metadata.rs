@@ -0,0 +1,26 @@
+//! `GET /.well-known/oauth-protected-resource` is public and only
+//! answers when `auth.oidc` is configured.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use axum::http::StatusCode;
+use common::TestApp;
+use serde_json::json;
+
+#[tokio::test]
+async fn metadata_is_not_found_without_oidc() {
+    let (app, creds) = TestApp::with_auth().await;
+    let (status, _) = app.get("/.well-known/oauth-protected-resource").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // Local login stays available when no provider is configured
+    let (status, login) = app
+        .post_json(
+            "/auth/login",
+            json!({ "username": creds.username, "password": creds.password }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{login}");
+}
@@ -0,0 +1,84 @@
+//! OpenAI-compatible `POST /v1/embeddings` and `GET /v1/models`: model
+//! names and aliases resolve to the server's providers, both encodings
+//! come back, and inputs the local models cannot take are refused.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use std::sync::Arc;
+
+use base64::Engine;
+use common::TestApp;
+use serde_json::json;
+use vectorizer::embedding::{Bm25Embedding, EmbeddingManager};
+
+async fn app_with_alias() -> TestApp {
+    TestApp::with_server(|server| {
+        let mut manager = EmbeddingManager::new();
+        manager.register_provider("bm25".to_string(), Box::new(Bm25Embedding::new(512)));
+        manager
+            .register_model_alias("text-embedding-3-small".to_string(), "bm25")
+            .unwrap();
+        server.embedding_manager = Arc::new(manager);
+    })
+    .await
+}
+
+#[tokio::test]
+async fn embeddings_follow_the_openai_shape() {
+    let app = app_with_alias().await;
+    let (status, resp) = app
+        .post_json(
+            "/v1/embeddings",
+            json!({"model": "text-embedding-3-small", "input": ["vector search", "hello"]}),
+        )
+        .await;
+    assert!(status.is_success(), "status {status}: {resp}");
+    assert_eq!(resp["object"], "list");
+    assert_eq!(resp["model"], "text-embedding-3-small");
+    let data = resp["data"].as_array().unwrap();
+    assert_eq!(data.len(), 2);
+    assert_eq!(data[1]["object"], "embedding");
+    assert_eq!(data[1]["index"], 1);
+    assert_eq!(data[0]["embedding"].as_array().unwrap().len(), 512);
+    assert_eq!(resp["usage"]["prompt_tokens"], 6);
+    assert_eq!(resp["usage"]["total_tokens"], 6);
+
+    let (status, resp) = app
+        .post_json(
+            "/v1/embeddings",
+            json!({"model": "bm25", "input": "hello", "encoding_format": "base64"}),
+        )
+        .await;
+    assert!(status.is_success(), "status {status}: {resp}");
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(resp["data"][0]["embedding"].as_str().unwrap())
+        .unwrap();
+    assert_eq!(bytes.len(), 512 * 4);
+
+    let (_, resp) = app.get("/v1/models").await;
+    let ids: Vec<&str> = resp["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| m["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(ids, ["bm25", "text-embedding-3-small"]);
+}
+
+#[tokio::test]
+async fn embeddings_reject_unknown_models_and_unsupported_input() {
+    let app = app_with_alias().await;
+    for body in [
+        json!({"model": "text-embedding-ada-002", "input": "hello"}),
+        json!({"model": "bm25", "input": [[15339, 1917]]}),
+        json!({"model": "bm25", "input": ""}),
+        json!({"model": "bm25", "input": []}),
+        json!({"model": "bm25", "input": "hello", "dimensions": 256}),
+        json!({"model": "bm25", "input": "hello", "encoding_format": "int8"}),
+    ] {
+        let (status, resp) = app.post_json("/v1/embeddings", body.clone()).await;
+        assert_eq!(status.as_u16(), 400, "{body} accepted: {resp}");
+    }
+}
@@ -0,0 +1,90 @@
+//! Parquet import/export (`POST /collections/{name}/export` and
+//! `/import`): a collection exported from one server imports into
+//! another with its payloads intact.
+
+#![cfg(feature = "parquet")]
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use common::TestApp;
+use serde_json::json;
+
+#[tokio::test]
+async fn export_then_import_round_trips_vectors_and_payloads() {
+    let app = TestApp::new().await;
+    for name in ["pq_src", "pq_dst"] {
+        let (status, _) = app
+            .post_json(
+                "/collections",
+                json!({"name": name, "dimension": 4, "metric": "euclidean"}),
+            )
+            .await;
+        assert!(status.is_success());
+    }
+    let (status, resp) = app
+        .post_json(
+            "/insert_vectors",
+            json!({
+                "collection": "pq_src",
+                "vectors": [
+                    {"id": "a", "embedding": [1.0, 0.0, 0.0, 0.0], "payload": {"title": "A", "year": 2024}},
+                    {"id": "b", "embedding": [0.0, 2.0, 0.0, 0.0], "payload": {"title": "B", "tags": ["x", "y"]}},
+                ],
+            }),
+        )
+        .await;
+    assert!(status.is_success(), "insert status {status}: {resp}");
+
+    let (status, content_type, file) = app
+        .post_json_for_bytes("/collections/pq_src/export", json!({"format": "parquet"}))
+        .await;
+    assert!(status.is_success(), "export status {status}");
+    assert_eq!(
+        content_type.as_deref(),
+        Some("application/vnd.apache.parquet")
+    );
+    assert_eq!(&file[..4], b"PAR1");
+
+    let (status, resp) = app
+        .post_raw(
+            "/collections/pq_dst/import?format=parquet",
+            "application/octet-stream",
+            &file,
+        )
+        .await;
+    assert!(status.is_success(), "import status {status}: {resp}");
+    assert_eq!(resp["imported"], 2);
+
+    let (status, resp) = app.get("/collections/pq_dst/vectors/b").await;
+    assert!(status.is_success(), "get status {status}: {resp}");
+    assert_eq!(resp["payload"]["tags"], json!(["x", "y"]));
+    assert_eq!(resp["payload"]["title"], "B");
+}
+
+#[tokio::test]
+async fn export_and_import_reject_bad_requests() {
+    let app = TestApp::new().await;
+    let (status, _) = app
+        .post_json("/collections", json!({"name": "pq_bad", "dimension": 4}))
+        .await;
+    assert!(status.is_success());
+
+    let (status, _, _) = app
+        .post_json_for_bytes("/collections/pq_nowhere/export", json!({}))
+        .await;
+    assert_eq!(status.as_u16(), 404);
+    let (status, _, _) = app
+        .post_json_for_bytes("/collections/pq_bad/export", json!({"format": "csv"}))
+        .await;
+    assert_eq!(status.as_u16(), 400);
+
+    let (status, resp) = app
+        .post_raw(
+            "/collections/pq_bad/import",
+            "application/octet-stream",
+            b"not a parquet file",
+        )
+        .await;
+    assert_eq!(status.as_u16(), 400, "garbage accepted: {resp}");
+}
@@ -0,0 +1,95 @@
+//! JSON merge patch (RFC 7396) on vector payloads:
+//! `PATCH /collections/{name}/vectors/{id}/payload` and `payload_patch`
+//! entries of `POST /batch_update`.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use common::{TestApp, embedding};
+use serde_json::json;
+
+async fn seed(app: &TestApp, name: &str) {
+    let (status, resp) = app
+        .post_json("/collections", json!({"name": name, "dimension": 512}))
+        .await;
+    assert!(status.is_success(), "create status {status}: {resp}");
+    let (status, resp) = app
+        .post_json(
+            "/insert_vectors",
+            json!({
+                "collection": name,
+                "vectors": [{
+                    "id": "chunk-1",
+                    "embedding": embedding(&[1.0]),
+                    "payload": {
+                        "content": "body",
+                        "meta": {"lang": "en", "draft": true, "pages": [1, 2]},
+                    },
+                }],
+            }),
+        )
+        .await;
+    assert!(status.is_success(), "insert status {status}: {resp}");
+}
+
+#[tokio::test]
+async fn patch_merges_nested_payload_fields() {
+    let app = TestApp::new().await;
+    seed(&app, "patch_docs").await;
+
+    let (status, resp) = app
+        .patch_json(
+            "/collections/patch_docs/vectors/chunk-1/payload",
+            json!({"meta": {"draft": null, "reviewer": "ana", "pages": [3]}}),
+        )
+        .await;
+    assert!(status.is_success(), "patch status {status}: {resp}");
+    let expected = json!({
+        "content": "body",
+        "meta": {"lang": "en", "reviewer": "ana", "pages": [3]},
+    });
+    assert_eq!(resp["payload"], expected);
+
+    let (status, resp) = app.get("/collections/patch_docs/vectors/chunk-1").await;
+    assert!(status.is_success(), "get status {status}: {resp}");
+    assert_eq!(resp["payload"], expected);
+    assert_eq!(resp["vector"], embedding(&[1.0]));
+
+    let (status, _) = app
+        .patch_json(
+            "/collections/patch_docs/vectors/missing/payload",
+            json!({"a": 1}),
+        )
+        .await;
+    assert_eq!(status.as_u16(), 404);
+}
+
+#[tokio::test]
+async fn batch_update_applies_payload_patch() {
+    let app = TestApp::new().await;
+    seed(&app, "patch_batch").await;
+
+    let (status, resp) = app
+        .post_json(
+            "/batch_update",
+            json!({
+                "collection": "patch_batch",
+                "updates": [
+                    {"id": "chunk-1", "payload_patch": {"meta": {"lang": "pt"}, "content": null}},
+                    {"id": "chunk-1", "payload": {}, "payload_patch": {}},
+                ],
+            }),
+        )
+        .await;
+    assert!(status.is_success(), "batch status {status}: {resp}");
+    assert_eq!(resp["updated"], 1);
+    assert_eq!(resp["failed"], 1);
+    assert_eq!(resp["results"][1]["status"], "error");
+
+    let (_, resp) = app.get("/collections/patch_batch/vectors/chunk-1").await;
+    assert_eq!(
+        resp["payload"],
+        json!({"meta": {"lang": "pt", "draft": true, "pages": [1, 2]}})
+    );
+}
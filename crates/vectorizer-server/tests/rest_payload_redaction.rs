@@ -0,0 +1,363 @@
+//! `api.payload_redaction` applied to read responses through the real
+//! router.
+//!
+//! The harness runs with auth disabled, so every caller is anonymous and
+//! therefore not exempt: rules without a `roles` list apply. Covers mask /
+//! drop / hash on `GET /collections/{name}/vectors`, that rules scoped
+//! to another collection leave its payloads alone, that the GraphQL
+//! resolvers apply collection-scoped rules, that the Pinecone API's
+//! `metadata` and `/retrieve` documents are redacted like payloads, that
+//! facets refuse redacted keys and count and scroll refuse filters on
+//! them, and that previews, highlights and job reports built from
+//! payloads never carry a redacted value.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use std::sync::Arc;
+
+use common::{TestApp, embedding};
+use serde_json::{Value, json};
+use vectorizer::auth::Secret;
+use vectorizer::config::sections::redaction::{
+    PayloadRedactionConfig, RedactionAction, RedactionRule,
+};
+use vectorizer::security::PayloadRedactor;
+
+fn rule(field: &str, action: RedactionAction, collections: &[&str]) -> RedactionRule {
+    RedactionRule {
+        field: field.to_string(),
+        action,
+        collections: collections.iter().map(|c| (*c).to_string()).collect(),
+        roles: Vec::new(),
+    }
+}
+
+async fn app_with_rules(rules: Vec<RedactionRule>) -> TestApp {
+    let redactor = PayloadRedactor::from_config(&PayloadRedactionConfig {
+        enabled: true,
+        hash_salt: Some(Secret::new("test-salt".to_string())),
+        rules,
+        ..Default::default()
+    })
+    .unwrap()
+    .map(Arc::new);
+    assert!(redactor.is_some());
+    TestApp::with_server(|server| server.payload_redactor = redactor).await
+}
+
+/// Create a 512-dim collection (the harness's bm25 dimension) holding one
+/// vector with a payload carrying sensitive and public fields.
+async fn seed(app: &TestApp, name: &str) {
+    let _ = app.delete(&format!("/collections/{name}")).await;
+    let (status, resp) = app
+        .post_json("/collections", json!({"name": name, "dimension": 512}))
+        .await;
+    assert!(status.is_success(), "create {name} status {status}: {resp}");
+
+    let embedding: Vec<f32> = (0..512).map(|i| (i as f32 * 0.01).cos()).collect();
+    let (status, resp) = app
+        .post_json(
+            "/insert_vectors",
+            json!({
+                "collection": name,
+                "vectors": [{
+                    "id": "p1",
+                    "embedding": embedding,
+                    "payload": {
+                        "email": "alice@example.com",
+                        "ssn": "123-45-6789",
+                        "customer": {"id": 42},
+                        "title": "public",
+                    },
+                }],
+            }),
+        )
+        .await;
+    assert!(status.is_success(), "insert status {status}: {resp}");
+}
+
+async fn first_payload(app: &TestApp, name: &str) -> Value {
+    let (status, resp) = app.get(&format!("/collections/{name}/vectors")).await;
+    assert!(status.is_success(), "list status {status}: {resp}");
+    resp["vectors"][0]["payload"].clone()
+}
+
+#[tokio::test]
+async fn list_vectors_masks_drops_and_hashes_fields() {
+    let app = app_with_rules(vec![
+        rule("email", RedactionAction::Mask, &[]),
+        rule("ssn", RedactionAction::Drop, &[]),
+        rule("customer.id", RedactionAction::Hash, &[]),
+    ])
+    .await;
+    let name = "payload_redaction_list";
+    seed(&app, name).await;
+
+    let payload = first_payload(&app, name).await;
+    assert_eq!(payload["email"].as_str(), Some("***"));
+    assert!(payload.get("ssn").is_none(), "ssn not dropped: {payload}");
+    assert!(
+        payload["customer"]["id"]
+            .as_str()
+            .unwrap()
+            .starts_with("sha256:")
+    );
+    assert_eq!(payload["title"].as_str(), Some("public"));
+}
+
+#[tokio::test]
+async fn rules_scoped_to_other_collections_do_not_apply() {
+    let app = app_with_rules(vec![rule("email", RedactionAction::Mask, &["customers*"])]).await;
+
+    seed(&app, "customers_eu").await;
+    seed(&app, "orders").await;
+
+    let payload = first_payload(&app, "customers_eu").await;
+    assert_eq!(payload["email"].as_str(), Some("***"));
+
+    let payload = first_payload(&app, "orders").await;
+    assert_eq!(payload["email"].as_str(), Some("alice@example.com"));
+}
+
+#[tokio::test]
+async fn graphql_vector_applies_collection_scoped_rules() {
+    let app = app_with_rules(vec![rule("email", RedactionAction::Mask, &["customers*"])]).await;
+    seed(&app, "customers_gql").await;
+    seed(&app, "orders_gql").await;
+
+    let vector_payload = |name: &str| {
+        json!({
+            "query": format!("{{ vector(collection: \"{name}\", id: \"p1\") {{ payload }} }}"),
+        })
+    };
+
+    let (status, resp) = app
+        .post_json("/graphql", vector_payload("customers_gql"))
+        .await;
+    assert!(status.is_success(), "graphql status {status}: {resp}");
+    let payload = &resp["data"]["vector"]["payload"];
+    assert_eq!(payload["email"].as_str(), Some("***"), "{resp}");
+    assert_eq!(payload["title"].as_str(), Some("public"));
+
+    let (_, resp) = app
+        .post_json("/graphql", vector_payload("orders_gql"))
+        .await;
+    assert_eq!(
+        resp["data"]["vector"]["payload"]["email"].as_str(),
+        Some("alice@example.com"),
+        "{resp}"
+    );
+}
+
+#[tokio::test]
+async fn pinecone_metadata_is_redacted() {
+    let app = app_with_rules(vec![rule("email", RedactionAction::Mask, &[])]).await;
+    let (status, resp) = app
+        .post_json(
+            "/collections",
+            json!({"name": "pinecone_redaction", "dimension": 512}),
+        )
+        .await;
+    assert!(status.is_success(), "create status {status}: {resp}");
+    let (status, resp) = app
+        .post_json(
+            "/pinecone/collections/pinecone_redaction/vectors/upsert",
+            json!({
+                "vectors": [{
+                    "id": "p1",
+                    "values": embedding(&[1.0]),
+                    "metadata": {"email": "alice@example.com", "title": "public"},
+                }],
+            }),
+        )
+        .await;
+    assert!(status.is_success(), "upsert status {status}: {resp}");
+
+    let (status, resp) = app
+        .get("/pinecone/collections/pinecone_redaction/vectors/fetch?ids=p1")
+        .await;
+    assert!(status.is_success(), "fetch status {status}: {resp}");
+    let metadata = &resp["vectors"]["p1"]["metadata"];
+    assert_eq!(metadata["email"].as_str(), Some("***"), "{resp}");
+    assert_eq!(metadata["title"].as_str(), Some("public"));
+
+    let (status, resp) = app
+        .post_json(
+            "/pinecone/collections/pinecone_redaction/query",
+            json!({"vector": embedding(&[1.0]), "topK": 1, "includeMetadata": true}),
+        )
+        .await;
+    assert!(status.is_success(), "query status {status}: {resp}");
+    assert_eq!(
+        resp["matches"][0]["metadata"]["email"].as_str(),
+        Some("***"),
+        "{resp}"
+    );
+}
+
+#[tokio::test]
+async fn retrieve_documents_are_built_from_redacted_payloads() {
+    let app = app_with_rules(vec![rule("email", RedactionAction::Mask, &[])]).await;
+    let name = "payload_redaction_retrieve";
+    seed(&app, name).await;
+
+    let (status, resp) = app
+        .post_json("/retrieve", json!({"collection": name, "query": "public"}))
+        .await;
+    assert!(status.is_success(), "retrieve status {status}: {resp}");
+    let metadata = &resp["documents"][0]["metadata"];
+    assert_eq!(metadata["email"].as_str(), Some("***"), "{resp}");
+    assert_eq!(metadata["title"].as_str(), Some("public"));
+
+    let (_, resp) = app
+        .post_json(
+            "/retrieve",
+            json!({"collection": name, "query": "public", "content_field": "email"}),
+        )
+        .await;
+    assert_eq!(resp["documents"][0]["page_content"], "***", "{resp}");
+}
+
+#[tokio::test]
+async fn facets_reject_redacted_keys() {
+    let app = app_with_rules(vec![rule("customer.id", RedactionAction::Hash, &[])]).await;
+    let name = "payload_redaction_facets";
+    seed(&app, name).await;
+    let path = format!("/collections/{name}/facets");
+
+    for key in ["customer.id", "customer"] {
+        let (status, resp) = app.post_json(&path, json!({ "keys": [key] })).await;
+        assert_eq!(status.as_u16(), 400, "{key}: {resp}");
+    }
+    let (status, resp) = app.post_json(&path, json!({ "keys": ["title"] })).await;
+    assert!(status.is_success(), "facets status {status}: {resp}");
+    assert_eq!(resp["facets"][0]["values"][0]["value"], "public", "{resp}");
+}
+
+#[tokio::test]
+async fn count_and_scroll_reject_filters_on_redacted_keys() {
+    let app = app_with_rules(vec![rule("customer.id", RedactionAction::Hash, &[])]).await;
+    let name = "payload_redaction_filters";
+    seed(&app, name).await;
+
+    for endpoint in ["count", "scroll"] {
+        let path = format!("/collections/{name}/{endpoint}");
+        for filter in [
+            json!({"customer.id": 42}),
+            json!({"must_not": [{"type": "nested", "filter": {
+                "should": [{"type": "match", "key": "customer", "match_value": "x"}],
+            }}]}),
+        ] {
+            let (status, resp) = app.post_json(&path, json!({ "filter": filter })).await;
+            assert_eq!(status.as_u16(), 400, "{endpoint} {filter}: {resp}");
+            assert!(resp.to_string().contains("is redacted"), "{resp}");
+        }
+    }
+
+    let filter = json!({"title": "public"});
+    let (status, resp) = app
+        .post_json(
+            &format!("/collections/{name}/count"),
+            json!({ "filter": filter }),
+        )
+        .await;
+    assert!(status.is_success(), "count status {status}: {resp}");
+    assert_eq!(resp["count"], 1, "{resp}");
+    let (status, resp) = app
+        .post_json(
+            &format!("/collections/{name}/scroll"),
+            json!({ "filter": filter }),
+        )
+        .await;
+    assert!(status.is_success(), "scroll status {status}: {resp}");
+    assert_eq!(resp["vectors"][0]["id"], "p1", "{resp}");
+}
+
+#[tokio::test]
+async fn previews_highlights_and_job_reports_never_carry_redacted_values() {
+    let app = app_with_rules(vec![rule("email", RedactionAction::Drop, &[])]).await;
+    let name = "payload_redaction_previews";
+    let (status, resp) = app
+        .post_json(
+            "/collections",
+            json!({
+                "name": name,
+                "dimension": 512,
+                "full_text": {"fields": ["content", "email"]},
+            }),
+        )
+        .await;
+    assert!(status.is_success(), "create status {status}: {resp}");
+    let vector = |id: &str, head: &[f32]| {
+        json!({
+            "id": id,
+            "embedding": embedding(head),
+            "payload": {"email": "alice@example.com", "content": "alice parse_header_v2"},
+        })
+    };
+    let (status, resp) = app
+        .post_json(
+            "/insert_vectors",
+            json!({
+                "collection": name,
+                "vectors": [
+                    vector("p1", &[1.0]),
+                    vector("p2", &[1.0]),
+                    vector("p3", &[0.0, 1.0]),
+                ],
+            }),
+        )
+        .await;
+    assert!(status.is_success(), "insert status {status}: {resp}");
+
+    let mut responses = Vec::new();
+    for path in [
+        format!("/collections/{name}/sample?n=3&neighbors=2"),
+        format!("/collections/{name}/vectors/p1/neighbors?k=2"),
+    ] {
+        let (status, resp) = app.get(&path).await;
+        assert!(status.is_success(), "{path} status {status}: {resp}");
+        responses.push(resp);
+    }
+    for (path, body) in [
+        (
+            format!("/collections/{name}/projection"),
+            json!({"include_payload": true}),
+        ),
+        (format!("/collections/{name}/duplicates"), json!({})),
+        (
+            format!("/collections/{name}/cluster"),
+            json!({"k": 2, "seed": 7, "write_back": false}),
+        ),
+        (
+            format!("/collections/{name}/search/full_text"),
+            json!({"query": "alice parse_header_v2", "fields": ["content", "email"]}),
+        ),
+    ] {
+        let (status, resp) = app.post_json(&path, body).await;
+        assert!(status.is_success(), "{path} status {status}: {resp}");
+        responses.push(resp);
+    }
+    let duplicates = &responses[3];
+    assert_eq!(duplicates["report"]["groups_total"], 1, "{duplicates}");
+    let job = duplicates["job_id"].as_str().unwrap().to_string();
+    let (status, report) = app.get(&format!("/jobs/{job}/result")).await;
+    assert!(status.is_success(), "job result status {status}: {report}");
+    responses.push(report);
+
+    let full_text = &responses[5];
+    assert!(
+        full_text["results"][0]["highlights"]["content"].is_string(),
+        "{full_text}"
+    );
+    for resp in &responses {
+        let text = resp.to_string();
+        assert!(!text.contains("alice@example.com"), "email leaked: {text}");
+    }
+    assert!(
+        full_text["results"][0]["highlights"].get("email").is_none(),
+        "{full_text}"
+    );
+}
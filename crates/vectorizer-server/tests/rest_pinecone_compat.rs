@@ -0,0 +1,188 @@
+//! Pinecone-compatible data plane under `/pinecone/collections/{name}`:
+//! upsert, query, fetch, delete and `describe_index_stats`, with
+//! namespaces kept apart and Pinecone metadata filters applied.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use common::{TestApp, embedding};
+use serde_json::{Value, json};
+
+async fn index_with_vectors(app: &TestApp) {
+    let (status, resp) = app
+        .post_json("/collections", json!({"name": "movies", "dimension": 512}))
+        .await;
+    assert!(status.is_success(), "create status {status}: {resp}");
+
+    let (status, resp) = app
+        .post_json(
+            "/pinecone/collections/movies/vectors/upsert",
+            json!({
+                "vectors": [
+                    {"id": "m1", "values": embedding(&[1.0]), "metadata": {"genre": "drama", "year": 2019}},
+                    {"id": "m2", "values": embedding(&[0.9, 0.1]), "metadata": {"genre": "comedy", "year": 2021}},
+                    {"id": "m3", "values": embedding(&[0.0, 1.0]), "metadata": {"genre": "drama", "year": 2023}},
+                ],
+            }),
+        )
+        .await;
+    assert!(status.is_success(), "upsert status {status}: {resp}");
+    assert_eq!(resp["upsertedCount"], 3);
+
+    let (status, resp) = app
+        .post_json(
+            "/pinecone/collections/movies/vectors/upsert",
+            json!({
+                "namespace": "archive",
+                "vectors": [{"id": "a1", "values": embedding(&[1.0]), "metadata": {"genre": "drama"}}],
+            }),
+        )
+        .await;
+    assert!(status.is_success(), "upsert status {status}: {resp}");
+}
+
+fn match_ids(resp: &Value) -> Vec<&str> {
+    resp["matches"]
+        .as_array()
+        .expect("matches array")
+        .iter()
+        .map(|m| m["id"].as_str().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn query_is_confined_to_the_namespace_and_filter() {
+    let app = TestApp::new().await;
+    index_with_vectors(&app).await;
+
+    let (status, resp) = app
+        .post_json(
+            "/pinecone/collections/movies/query",
+            json!({"vector": embedding(&[1.0]), "topK": 10, "includeMetadata": true}),
+        )
+        .await;
+    assert!(status.is_success(), "query status {status}: {resp}");
+    assert_eq!(match_ids(&resp), ["m1", "m2", "m3"]);
+    assert_eq!(
+        resp["matches"][0]["metadata"],
+        json!({"genre": "drama", "year": 2019})
+    );
+
+    let (_, resp) = app
+        .post_json(
+            "/pinecone/collections/movies/query",
+            json!({
+                "vector": embedding(&[1.0]),
+                "topK": 10,
+                "filter": {"genre": {"$eq": "drama"}, "year": {"$gte": 2020}},
+            }),
+        )
+        .await;
+    assert_eq!(match_ids(&resp), ["m3"]);
+
+    let (_, resp) = app
+        .post_json(
+            "/pinecone/collections/movies/query",
+            json!({
+                "vector": embedding(&[1.0]),
+                "topK": 10,
+                "filter": {"$or": [{"genre": "comedy"}, {"year": {"$in": [2023]}}]},
+            }),
+        )
+        .await;
+    assert_eq!(match_ids(&resp), ["m2", "m3"]);
+
+    let (_, resp) = app
+        .post_json(
+            "/pinecone/collections/movies/query",
+            json!({"id": "a1", "topK": 10, "namespace": "archive"}),
+        )
+        .await;
+    assert_eq!(resp["namespace"], "archive");
+    assert_eq!(match_ids(&resp), ["a1"]);
+
+    let (status, _) = app
+        .post_json(
+            "/pinecone/collections/movies/query",
+            json!({"vector": embedding(&[1.0]), "topK": 1, "filter": {"year": {"$regex": "x"}}}),
+        )
+        .await;
+    assert_eq!(status.as_u16(), 400);
+}
+
+#[tokio::test]
+async fn fetch_delete_and_stats_follow_namespaces() {
+    let app = TestApp::new().await;
+    index_with_vectors(&app).await;
+
+    let (status, resp) = app
+        .get("/pinecone/collections/movies/vectors/fetch?ids=m1&ids=a1&ids=missing")
+        .await;
+    assert!(status.is_success(), "fetch status {status}: {resp}");
+    let vectors = resp["vectors"].as_object().unwrap();
+    assert_eq!(vectors.keys().collect::<Vec<_>>(), ["m1"]);
+    assert_eq!(vectors["m1"]["metadata"]["genre"], "drama");
+
+    let (_, resp) = app
+        .get("/pinecone/collections/movies/describe_index_stats")
+        .await;
+    assert_eq!(resp["dimension"], 512);
+    assert_eq!(resp["totalVectorCount"], 4);
+    assert_eq!(resp["namespaces"][""]["vectorCount"], 3);
+    assert_eq!(resp["namespaces"]["archive"]["vectorCount"], 1);
+
+    let (status, resp) = app
+        .post_json(
+            "/pinecone/collections/movies/vectors/delete",
+            json!({"filter": {"genre": "drama"}}),
+        )
+        .await;
+    assert!(status.is_success(), "delete status {status}: {resp}");
+    let (status, _) = app
+        .post_json(
+            "/pinecone/collections/movies/vectors/delete",
+            json!({"deleteAll": true, "namespace": "archive"}),
+        )
+        .await;
+    assert!(status.is_success());
+
+    let (_, resp) = app
+        .post_json(
+            "/pinecone/collections/movies/describe_index_stats",
+            json!({}),
+        )
+        .await;
+    assert_eq!(resp["totalVectorCount"], 1);
+    assert_eq!(resp["namespaces"], json!({"": {"vectorCount": 1}}));
+
+    let (status, _) = app
+        .post_json(
+            "/pinecone/collections/movies/vectors/delete",
+            json!({"ids": ["m2"], "deleteAll": true}),
+        )
+        .await;
+    assert_eq!(status.as_u16(), 400);
+}
+
+#[tokio::test]
+async fn upsert_rejects_wrong_dimension_and_unknown_index() {
+    let app = TestApp::new().await;
+    index_with_vectors(&app).await;
+
+    let (status, _) = app
+        .post_json(
+            "/pinecone/collections/movies/vectors/upsert",
+            json!({"vectors": [{"id": "bad", "values": [1.0, 0.0]}]}),
+        )
+        .await;
+    assert_eq!(status.as_u16(), 400);
+
+    let (status, _) = app
+        .post_json(
+            "/pinecone/collections/no_such_index/vectors/upsert",
+            json!({"vectors": [{"id": "x", "values": embedding(&[1.0])}]}),
+        )
+        .await;
+    assert_eq!(status.as_u16(), 404);
+}
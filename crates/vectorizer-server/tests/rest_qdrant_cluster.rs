@@ -0,0 +1,122 @@
+//! Qdrant cluster and shard endpoints report the server's real layout
+//! instead of a fabricated single-node cluster.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use std::sync::Arc;
+
+use common::{TestApp, embedding};
+use serde_json::json;
+use vectorizer::cluster::{ClusterConfig, ClusterManager, ClusterNode, NodeId};
+use vectorizer::db::sharding::ShardId;
+
+async fn create_with_points(app: &TestApp, name: &str, points: usize) {
+    let (status, resp) = app
+        .post_json(
+            "/collections",
+            json!({"name": name, "dimension": 512, "metric": "cosine"}),
+        )
+        .await;
+    assert!(status.is_success(), "create status {status}: {resp}");
+
+    let vectors: Vec<_> = (0..points)
+        .map(
+            |i| json!({"id": format!("p{i}"), "embedding": embedding(&[1.0, i as f32, 0.5, 0.25])}),
+        )
+        .collect();
+    let (status, resp) = app
+        .post_json(
+            "/insert_vectors",
+            json!({"collection": name, "vectors": vectors}),
+        )
+        .await;
+    assert!(status.is_success(), "insert status {status}: {resp}");
+}
+
+#[tokio::test]
+async fn standalone_server_reports_cluster_disabled() {
+    let app = TestApp::new().await;
+
+    let (status, resp) = app.get("/qdrant/cluster").await;
+    assert!(status.is_success(), "status {status}: {resp}");
+    assert_eq!(resp["result"]["status"], "disabled");
+    assert!(resp["result"]["peers"].as_object().unwrap().is_empty());
+    assert!(resp["result"]["raft_info"].is_null());
+
+    let (status, _) = app.delete("/qdrant/cluster/peer/2").await;
+    assert_eq!(status.as_u16(), 400, "no peers to remove without a cluster");
+}
+
+#[tokio::test]
+async fn collection_cluster_info_reports_local_shard() {
+    let app = TestApp::new().await;
+    create_with_points(&app, "qdrant_cluster_info", 3).await;
+
+    let (status, resp) = app
+        .get("/qdrant/collections/qdrant_cluster_info/cluster")
+        .await;
+    assert!(status.is_success(), "status {status}: {resp}");
+    let result = &resp["result"];
+    assert_eq!(result["shard_count"], 1);
+    assert_eq!(result["local_shards"][0]["shard_id"], 0);
+    assert_eq!(result["local_shards"][0]["points_count"], 3);
+    assert!(result["remote_shards"].as_array().unwrap().is_empty());
+    assert!(result["shard_transfers"].as_array().unwrap().is_empty());
+
+    let (status, resp) = app
+        .get("/qdrant/collections/qdrant_cluster_info/shards")
+        .await;
+    assert!(status.is_success(), "status {status}: {resp}");
+    assert_eq!(
+        resp["result"]["keys"][0]["local_shards"][0]["points_count"],
+        3
+    );
+
+    let (status, _) = app.get("/qdrant/collections/missing/cluster").await;
+    assert_eq!(status.as_u16(), 404);
+}
+
+#[tokio::test]
+async fn cluster_peers_come_from_cluster_manager() {
+    let manager = Arc::new(
+        ClusterManager::new(ClusterConfig {
+            enabled: true,
+            node_id: Some("7".to_string()),
+            ..Default::default()
+        })
+        .expect("cluster manager"),
+    );
+    let mut idle = ClusterNode::new(NodeId::new("8".to_string()), "10.0.0.8".to_string(), 7001);
+    idle.mark_active();
+    manager.add_node(idle);
+    let mut busy = ClusterNode::new(NodeId::new("9".to_string()), "10.0.0.9".to_string(), 7001);
+    busy.add_shard(ShardId::new(0));
+    manager.add_node(busy);
+
+    let cluster = Arc::clone(&manager);
+    let app = TestApp::with_server(move |server| server.cluster_manager = Some(cluster)).await;
+
+    let (status, resp) = app.get("/qdrant/cluster").await;
+    assert!(status.is_success(), "status {status}: {resp}");
+    let result = &resp["result"];
+    assert_eq!(result["status"], "enabled");
+    assert_eq!(result["peer_id"], 7);
+    assert_eq!(result["peers"]["8"]["uri"], "http://10.0.0.8:7001");
+    assert_eq!(result["peers"]["8"]["state"], "Active");
+    assert!(result["peers"]["9"].is_object());
+
+    let (status, _) = app.delete("/qdrant/cluster/peer/7").await;
+    assert_eq!(status.as_u16(), 400, "a peer cannot remove itself");
+    let (status, _) = app.delete("/qdrant/cluster/peer/42").await;
+    assert_eq!(status.as_u16(), 404);
+    let (status, _) = app.delete("/qdrant/cluster/peer/9").await;
+    assert_eq!(status.as_u16(), 400, "peer still holds shards");
+
+    let (status, resp) = app.delete("/qdrant/cluster/peer/8").await;
+    assert!(status.is_success(), "status {status}: {resp}");
+    let (status, _) = app.delete("/qdrant/cluster/peer/9?force=true").await;
+    assert!(status.is_success());
+    assert_eq!(manager.get_nodes().len(), 1);
+}
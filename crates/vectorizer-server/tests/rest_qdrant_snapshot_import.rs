@@ -0,0 +1,127 @@
+//! Qdrant snapshot upload (`POST /qdrant/collections/{name}/snapshots/upload`)
+//! with an archive written by Qdrant: the collection is created from the
+//! snapshot config and its points keep their IDs, vectors and payloads.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use common::TestApp;
+use serde_json::json;
+
+fn tar_of(files: &[(&str, Vec<u8>)]) -> Vec<u8> {
+    let mut builder = tar::Builder::new(Vec::new());
+    for (path, bytes) in files {
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(tar::EntryType::Regular);
+        header.set_size(bytes.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, path, bytes.as_slice())
+            .unwrap();
+    }
+    builder.into_inner().unwrap()
+}
+
+/// One segment holding points 7 and 8, with `Mmap` vectors and
+/// uncompressed mmap payloads.
+fn qdrant_snapshot() -> Vec<u8> {
+    let mut matrix = b"data".to_vec();
+    for x in [3.0f32, 4.0, 0.0, 1.0] {
+        matrix.extend_from_slice(&x.to_le_bytes());
+    }
+    let mut ids = Vec::new();
+    for (id, offset) in [(7u64, 0u32), (8, 1)] {
+        ids.push(1);
+        ids.extend_from_slice(&id.to_le_bytes());
+        ids.extend_from_slice(&offset.to_le_bytes());
+    }
+    let payload = json!({"city": "Berlin"}).to_string().into_bytes();
+    let mut tracker = vec![0u8; 4];
+    for field in [1u32, 0, 0, payload.len() as u32] {
+        tracker.extend_from_slice(&field.to_le_bytes());
+    }
+    let segment = tar_of(&[
+        (
+            "snapshot/files/segment.json",
+            json!({"config": {"vector_data": {"": {"size": 2, "distance": "Euclid", "storage_type": "Mmap"}}}})
+                .to_string()
+                .into_bytes(),
+        ),
+        ("snapshot/files/vector_storage/matrix.dat", matrix),
+        ("snapshot/files/id_tracker.mappings", ids),
+        (
+            "snapshot/files/payload_storage/config.json",
+            json!({"block_size_bytes": 128, "compression": "None"})
+                .to_string()
+                .into_bytes(),
+        ),
+        ("snapshot/files/payload_storage/tracker.dat", tracker),
+        ("snapshot/files/payload_storage/page_0.dat", payload),
+    ]);
+    tar_of(&[
+        (
+            "config.json",
+            json!({"params": {"vectors": {"size": 2, "distance": "Euclid"}}})
+                .to_string()
+                .into_bytes(),
+        ),
+        ("0/segments/8d2f.tar", segment),
+    ])
+}
+
+#[tokio::test]
+async fn qdrant_snapshot_upload_imports_points() {
+    let app = TestApp::new().await;
+    let (status, resp) = app
+        .post_raw(
+            "/qdrant/collections/migrated/snapshots/upload",
+            "application/octet-stream",
+            &qdrant_snapshot(),
+        )
+        .await;
+    assert!(status.is_success(), "upload status {status}: {resp}");
+    assert_eq!(resp["status"], "ok");
+
+    let (status, info) = app.get("/collections/migrated").await;
+    assert!(status.is_success(), "info status {status}: {info}");
+    assert_eq!(info["dimension"], 2);
+    assert_eq!(info["metric"], "Euclidean");
+    assert_eq!(info["vector_count"], 2);
+
+    let (status, vector) = app.get("/collections/migrated/vectors/7").await;
+    assert!(status.is_success(), "get status {status}: {vector}");
+    assert_eq!(vector["vector"], json!([3.0, 4.0]));
+    assert_eq!(vector["payload"]["city"], "Berlin");
+    let (_, vector) = app.get("/collections/migrated/vectors/8").await;
+    assert_eq!(vector["vector"], json!([0.0, 1.0]));
+}
+
+#[tokio::test]
+async fn qdrant_snapshot_without_readable_segments_is_rejected() {
+    let app = TestApp::new().await;
+    let segment = tar_of(&[(
+        "segment.json",
+        json!({"config": {"vector_data": {"": {"size": 2, "distance": "Dot", "storage_type": "Memory"}}}})
+            .to_string()
+            .into_bytes(),
+    )]);
+    let snapshot = tar_of(&[
+        (
+            "config.json",
+            json!({"params": {"vectors": {"size": 2, "distance": "Dot"}}})
+                .to_string()
+                .into_bytes(),
+        ),
+        ("0/segments/a.tar", segment),
+    ]);
+    let (status, resp) = app
+        .post_raw(
+            "/qdrant/collections/rocks/snapshots/upload",
+            "application/octet-stream",
+            &snapshot,
+        )
+        .await;
+    assert_eq!(status.as_u16(), 400, "unreadable snapshot accepted: {resp}");
+}
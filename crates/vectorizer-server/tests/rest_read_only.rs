@@ -0,0 +1,92 @@
+//! Read-only mode (`/admin/read_only`): writes are refused while the
+//! node stays ready and keeps serving searches, until the mode is
+//! switched off.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use common::{TestApp, embedding};
+use serde_json::json;
+
+#[tokio::test]
+async fn read_only_refuses_writes_but_serves_searches() {
+    let app = TestApp::new().await;
+    let (status, resp) = app
+        .post_json(
+            "/collections",
+            json!({"name": "read_only_target", "dimension": 512, "metric": "cosine"}),
+        )
+        .await;
+    assert!(status.is_success(), "create status {status}: {resp}");
+
+    let (status, resp) = app
+        .put_json(
+            "/admin/read_only",
+            json!({"enabled": true, "reason": "storage failover"}),
+        )
+        .await;
+    assert!(status.is_success(), "enable status {status}: {resp}");
+    assert_eq!(resp["read_only"], true);
+    assert_eq!(resp["reason"], "storage failover");
+
+    let (status, resp) = app.get("/health/ready").await;
+    assert!(status.is_success(), "ready status {status}: {resp}");
+    assert_eq!(resp["read_only"], true);
+    assert_eq!(resp["checks"]["read_only"]["enabled"], true);
+    assert_eq!(resp["checks"]["read_only"]["reason"], "storage failover");
+
+    let insert = json!({
+        "collection": "read_only_target",
+        "vectors": [{"id": "v1", "embedding": embedding(&[1.0, 0.0, 0.0, 0.0])}],
+    });
+    let (status, resp) = app.post_json("/insert_vectors", insert.clone()).await;
+    assert_eq!(status.as_u16(), 503, "write while read-only: {resp}");
+    assert_eq!(resp["error_type"], "read_only");
+    let (status, _) = app
+        .post_json(
+            "/collections",
+            json!({"name": "read_only_other", "dimension": 4}),
+        )
+        .await;
+    assert_eq!(status.as_u16(), 503);
+
+    let (status, resp) = app
+        .post_json(
+            "/collections/read_only_target/search",
+            json!({"vector": embedding(&[1.0, 0.0, 0.0, 0.0]), "limit": 1}),
+        )
+        .await;
+    assert!(
+        status.is_success(),
+        "search while read-only {status}: {resp}"
+    );
+
+    let (status, resp) = app
+        .put_json("/admin/read_only", json!({"enabled": false}))
+        .await;
+    assert!(status.is_success(), "disable status {status}: {resp}");
+    assert_eq!(resp["read_only"], false);
+    assert!(resp["reason"].is_null());
+
+    let (status, resp) = app.post_json("/insert_vectors", insert).await;
+    assert!(
+        status.is_success(),
+        "write after read-only {status}: {resp}"
+    );
+}
+
+#[tokio::test]
+async fn read_only_rejects_invalid_body() {
+    let app = TestApp::new().await;
+    for body in [
+        json!({}),
+        json!({"enabled": "yes"}),
+        json!({"enabled": true, "reason": 7}),
+    ] {
+        let (status, resp) = app.put_json("/admin/read_only", body.clone()).await;
+        assert_eq!(status.as_u16(), 400, "{body} accepted: {resp}");
+    }
+    let (_, resp) = app.get("/admin/read_only").await;
+    assert_eq!(resp["read_only"], false);
+}
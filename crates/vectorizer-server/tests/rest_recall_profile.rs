@@ -0,0 +1,56 @@
+//! Recall / latency profiles through the real router: an explicit
+//! measurement is returned and then reported by `GET /collections/{name}`.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use common::TestApp;
+use serde_json::json;
+
+#[tokio::test]
+async fn recall_profile_is_measured_and_reported() {
+    let app = TestApp::new().await;
+    let name = "recall_profile_rest";
+    let _ = app.delete(&format!("/collections/{name}")).await;
+    let (status, resp) = app
+        .post_json("/collections", json!({"name": name, "dimension": 512}))
+        .await;
+    assert!(status.is_success(), "create status {status}: {resp}");
+
+    let vectors: Vec<_> = (0..40)
+        .map(|i| {
+            let embedding: Vec<f32> = (0..512)
+                .map(|d| ((i * 31 + d * 7) % 17) as f32 + 1.0)
+                .collect();
+            json!({"id": format!("v{i}"), "embedding": embedding})
+        })
+        .collect();
+    let (status, resp) = app
+        .post_json(
+            "/insert_vectors",
+            json!({"collection": name, "vectors": vectors}),
+        )
+        .await;
+    assert!(status.is_success(), "insert status {status}: {resp}");
+
+    let path = format!("/collections/{name}/recall_profile");
+    let (status, resp) = app
+        .post_json(&path, json!({"sample_size": 10, "k": 5}))
+        .await;
+    assert!(status.is_success(), "profile status {status}: {resp}");
+    let profile = &resp["recall_profile"];
+    assert_eq!(profile["trigger"], "request");
+    assert_eq!(profile["sample_size"], 10);
+    assert_eq!(profile["k"], 5);
+    let recall = profile["recall_at_k"].as_f64().unwrap();
+    assert!((0.0..=1.0).contains(&recall), "recall {recall}");
+
+    let (status, info) = app.get(&format!("/collections/{name}")).await;
+    assert!(status.is_success(), "info status {status}: {info}");
+    assert_eq!(info["recall_profile"], *profile);
+
+    // k must leave at least one neighbour per query
+    let (status, _) = app.post_json(&path, json!({"k": 40})).await;
+    assert!(status.is_client_error(), "oversized k status {status}");
+}
@@ -0,0 +1,122 @@
+//! Relevance feedback (`/collections/{name}/feedback`): judgements are
+//! tallied per result, summarized for analytics, and lift results in
+//! searches whose scoring expression reads `feedback`.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use common::{TestApp, embedding};
+use serde_json::{Value, json};
+
+async fn top_hit(app: &TestApp, name: &str) -> Value {
+    let (status, resp) = app
+        .post_json(
+            &format!("/collections/{name}/search"),
+            json!({"vector": embedding(&[1.0]), "limit": 2}),
+        )
+        .await;
+    assert!(status.is_success(), "search status {status}: {resp}");
+    resp["results"][0]["id"].clone()
+}
+
+#[tokio::test]
+async fn positive_feedback_lifts_a_result() {
+    let app = TestApp::new().await;
+    let name = "feedback_boost";
+    app.create_collection(json!({
+        "name": name,
+        "dimension": 512,
+        "scoring": {"expression": "score + feedback"},
+    }))
+    .await;
+    app.insert_vectors(
+        name,
+        json!([
+            {"id": "closest", "embedding": embedding(&[1.0])},
+            {"id": "liked", "embedding": embedding(&[0.8, 0.6])},
+        ]),
+    )
+    .await;
+    assert_eq!(top_hit(&app, name).await, "closest");
+
+    let feedback = format!("/collections/{name}/feedback");
+    for _ in 0..3 {
+        let (status, resp) = app
+            .post_json(
+                &feedback,
+                json!({"query": "reset password", "result_id": "liked", "label": "positive"}),
+            )
+            .await;
+        assert!(status.is_success(), "feedback status {status}: {resp}");
+    }
+    let (status, resp) = app
+        .post_json(
+            &feedback,
+            json!({"query": "reset password", "result_id": "closest", "label": "negative"}),
+        )
+        .await;
+    assert!(status.is_success(), "feedback status {status}: {resp}");
+    assert_eq!(resp["negative"], 1);
+    assert!(resp["boost"].as_f64().unwrap() < 0.0);
+
+    assert_eq!(top_hit(&app, name).await, "liked");
+
+    let (status, resp) = app.get(&format!("{feedback}?recent=2")).await;
+    assert!(status.is_success(), "summary status {status}: {resp}");
+    assert_eq!(resp["positive"], 3);
+    assert_eq!(resp["negative"], 1);
+    assert_eq!(resp["judged_results"], 2);
+    assert_eq!(resp["results"][0]["result_id"], "liked");
+    assert_eq!(resp["results"][0]["positive"], 3);
+    assert_eq!(resp["recent"].as_array().unwrap().len(), 2);
+    assert_eq!(resp["recent"][0]["result_id"], "closest");
+    assert_eq!(resp["recent"][0]["query"], "reset password");
+
+    let (status, resp) = app.delete(&feedback).await;
+    assert!(status.is_success(), "clear status {status}: {resp}");
+    assert_eq!(resp["cleared_results"], 2);
+    assert_eq!(top_hit(&app, name).await, "closest");
+}
+
+#[tokio::test]
+async fn invalid_feedback_is_rejected() {
+    let app = TestApp::new().await;
+    let name = "feedback_invalid";
+    let (status, _) = app
+        .post_json("/collections", json!({"name": name, "dimension": 512}))
+        .await;
+    assert!(status.is_success());
+    let (status, _) = app
+        .post_json(
+            "/insert_vectors",
+            json!({"collection": name, "vectors": [{"id": "a", "embedding": embedding(&[1.0])}]}),
+        )
+        .await;
+    assert!(status.is_success());
+
+    let feedback = format!("/collections/{name}/feedback");
+    for body in [
+        json!({"query": "q", "result_id": "a", "label": "maybe"}),
+        json!({"query": "", "result_id": "a", "label": "positive"}),
+        json!({"query": "q", "label": "positive"}),
+    ] {
+        let (status, resp) = app.post_json(&feedback, body.clone()).await;
+        assert_eq!(status.as_u16(), 400, "{body} accepted: {resp}");
+    }
+
+    let (status, _) = app
+        .post_json(
+            &feedback,
+            json!({"query": "q", "result_id": "missing", "label": "positive"}),
+        )
+        .await;
+    assert_eq!(status.as_u16(), 404);
+    let (status, _) = app
+        .post_json(
+            "/collections/feedback_nowhere/feedback",
+            json!({"query": "q", "result_id": "a", "label": "positive"}),
+        )
+        .await;
+    assert_eq!(status.as_u16(), 404);
+}
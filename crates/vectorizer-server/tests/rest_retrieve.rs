@@ -0,0 +1,137 @@
+//! `POST /retrieve`: documents come back in the LangChain / llama-index
+//! shape, and `k`, `filter` and `score_threshold` are honoured whether
+//! given top-level or in `search_kwargs`.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use common::TestApp;
+use serde_json::{Value, json};
+
+async fn collection_with_documents(app: &TestApp) {
+    let (status, resp) = app
+        .post_json(
+            "/collections",
+            json!({"name": "handbook", "dimension": 512, "metric": "cosine"}),
+        )
+        .await;
+    assert!(status.is_success(), "create status {status}: {resp}");
+
+    for (file_path, source, chunks) in [
+        (
+            "onboarding.md",
+            "wiki",
+            [
+                "vector search finds similar documents",
+                "new hires get a laptop",
+            ],
+        ),
+        (
+            "search.md",
+            "docs",
+            [
+                "vector search ranks documents by similarity",
+                "the index is rebuilt nightly",
+            ],
+        ),
+    ] {
+        let chunks: Vec<Value> = chunks.iter().map(|c| json!({"content": c})).collect();
+        let (status, resp) = app
+            .post_json(
+                "/collections/handbook/documents",
+                json!({"file_path": file_path, "metadata": {"source": source}, "chunks": chunks}),
+            )
+            .await;
+        assert!(status.is_success(), "ingest status {status}: {resp}");
+    }
+}
+
+fn documents(resp: &Value) -> &Vec<Value> {
+    resp["documents"].as_array().expect("documents array")
+}
+
+#[tokio::test]
+async fn retrieve_returns_documents_in_the_retriever_shape() {
+    let app = TestApp::new().await;
+    collection_with_documents(&app).await;
+
+    let (status, resp) = app
+        .post_json(
+            "/retrieve",
+            json!({"collection": "handbook", "query": "vector search documents"}),
+        )
+        .await;
+    assert!(status.is_success(), "retrieve status {status}: {resp}");
+    assert_eq!(resp["collection"], "handbook");
+    let docs = documents(&resp);
+    assert_eq!(docs.len(), 4);
+    assert!(
+        docs[0]["page_content"]
+            .as_str()
+            .unwrap()
+            .starts_with("vector search")
+    );
+    assert!(docs[0]["metadata"]["source"].is_string());
+    assert!(docs[0]["metadata"].get("content").is_none());
+    assert!(docs[0]["id"].is_string());
+    let scores: Vec<f64> = docs.iter().map(|d| d["score"].as_f64().unwrap()).collect();
+    assert!(scores.windows(2).all(|w| w[0] >= w[1]), "{scores:?}");
+
+    let (_, resp) = app
+        .post_json(
+            "/retrieve",
+            json!({
+                "collection": "handbook",
+                "query": "vector search documents",
+                "search_kwargs": {"k": 1, "filter": {"source": "docs"}},
+            }),
+        )
+        .await;
+    let docs = documents(&resp);
+    assert_eq!(docs.len(), 1);
+    assert_eq!(docs[0]["metadata"]["source"], "docs");
+
+    let (_, resp) = app
+        .post_json(
+            "/retrieve",
+            json!({
+                "collection": "handbook",
+                "query": "vector search documents",
+                "k": 10,
+                "search_kwargs": {"k": 1},
+                "filter": {"must": [{"type": "match", "key": "source", "match_value": "wiki"}]},
+            }),
+        )
+        .await;
+    let docs = documents(&resp);
+    assert_eq!(docs.len(), 2);
+    assert!(docs.iter().all(|d| d["metadata"]["source"] == "wiki"));
+
+    let (_, resp) = app
+        .post_json(
+            "/retrieve",
+            json!({"collection": "handbook", "query": "vector search", "score_threshold": 2.0}),
+        )
+        .await;
+    assert!(documents(&resp).is_empty());
+}
+
+#[tokio::test]
+async fn retrieve_rejects_bad_requests() {
+    let app = TestApp::new().await;
+    collection_with_documents(&app).await;
+
+    for body in [
+        json!({"collection": "handbook", "query": "  "}),
+        json!({"collection": "handbook", "query": "x", "filter": {"source": {"$regex": "d"}}}),
+    ] {
+        let (status, resp) = app.post_json("/retrieve", body.clone()).await;
+        assert_eq!(status.as_u16(), 400, "{body} accepted: {resp}");
+    }
+
+    let (status, _) = app
+        .post_json("/retrieve", json!({"collection": "no_such", "query": "x"}))
+        .await;
+    assert_eq!(status.as_u16(), 404);
+}
@@ -0,0 +1,105 @@
+//! Runtime tuning (`/admin/config`): the search beam and query cache
+//! change live, are written back to `config.yml`, and invalid patches
+//! change nothing.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use common::{TestApp, embedding};
+use serde_json::json;
+use vectorizer::config::TuningConfig;
+
+#[tokio::test]
+async fn patch_applies_and_persists_tuning() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let config_path = dir.path().join("config.yml");
+    std::fs::write(&config_path, "server:\n  port: 15099\n").expect("seed config");
+    let path = config_path.clone();
+    let app = TestApp::with_server(move |server| server.config_path = Some(path)).await;
+
+    let (status, resp) = app.get("/admin/config").await;
+    assert!(status.is_success(), "status {status}: {resp}");
+    assert!(resp["ef_search"].is_null());
+    assert_eq!(resp["query_cache"]["max_size"], 1000);
+    assert!(resp["rate_limits"].is_null());
+
+    let (status, resp) = app
+        .patch_json(
+            "/admin/config",
+            json!({"ef_search": 200, "query_cache": {"max_size": 50, "ttl_seconds": 30}}),
+        )
+        .await;
+    assert!(status.is_success(), "patch status {status}: {resp}");
+    assert_eq!(resp["ef_search"], 200);
+    assert_eq!(resp["query_cache"]["max_size"], 50);
+    assert_eq!(resp["query_cache"]["ttl_seconds"], 30);
+    assert_eq!(resp["persisted"], true);
+
+    let written: serde_yaml::Value =
+        serde_yaml::from_str(&std::fs::read_to_string(&config_path).unwrap()).unwrap();
+    assert_eq!(written["server"]["port"], 15099);
+    let tuning: TuningConfig = serde_yaml::from_value(written["tuning"].clone()).unwrap();
+    assert_eq!(tuning.ef_search, Some(200));
+    assert_eq!(tuning.query_cache.max_size, 50);
+    assert_eq!(tuning.query_cache.ttl_seconds, 30);
+
+    // Searches keep working with the pinned beam
+    let (status, resp) = app
+        .post_json(
+            "/collections",
+            json!({"name": "tuned", "dimension": 512, "metric": "cosine"}),
+        )
+        .await;
+    assert!(status.is_success(), "create status {status}: {resp}");
+    let (status, resp) = app
+        .post_json(
+            "/insert_vectors",
+            json!({
+                "collection": "tuned",
+                "vectors": [{"id": "v1", "embedding": embedding(&[1.0])}],
+            }),
+        )
+        .await;
+    assert!(status.is_success(), "insert status {status}: {resp}");
+    let (status, resp) = app
+        .post_json(
+            "/collections/tuned/search",
+            json!({"vector": embedding(&[1.0]), "limit": 1}),
+        )
+        .await;
+    assert!(status.is_success(), "search status {status}: {resp}");
+
+    let (status, resp) = app
+        .patch_json("/admin/config", json!({"ef_search": null}))
+        .await;
+    assert!(status.is_success(), "reset status {status}: {resp}");
+    assert!(resp["ef_search"].is_null());
+    assert_eq!(resp["query_cache"]["max_size"], 50);
+}
+
+#[tokio::test]
+async fn invalid_patch_changes_nothing() {
+    let app = TestApp::new().await;
+
+    for patch in [
+        json!({"ef_search": 0}),
+        json!({"query_cache": {"max_size": 0}}),
+        json!({"query_cache": {"max_size": 10, "warmup": true}}),
+        json!({"ef_search": 64, "storage": {}}),
+    ] {
+        let (status, resp) = app.patch_json("/admin/config", patch.clone()).await;
+        assert_eq!(status.as_u16(), 400, "{patch} accepted: {resp}");
+    }
+
+    // No authentication, so no API-key rate limits to change
+    let (status, _) = app
+        .patch_json("/admin/config", json!({"rate_limits": {"per_minute": 10}}))
+        .await;
+    assert_eq!(status.as_u16(), 400);
+
+    // `ef_search` is process-wide and the other test moves it too
+    let (_, resp) = app.get("/admin/config").await;
+    assert_ne!(resp["ef_search"], 64);
+    assert_eq!(resp["query_cache"]["max_size"], 1000);
+}
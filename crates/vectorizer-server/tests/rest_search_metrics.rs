@@ -0,0 +1,117 @@
+//! Per-collection search metrics through the real router: a raw-vector
+//! search records its HNSW beam width and visited-node count, and query
+//! cache lookups are counted per collection and writes invalidate the
+//! collection's cached results.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use common::{TestApp, embedding};
+use serde_json::json;
+use vectorizer::monitoring::metrics::METRICS;
+
+#[tokio::test]
+async fn vector_search_records_graph_stats_and_cache_lookups() {
+    let app = TestApp::new().await;
+    let name = "search_metrics_hnsw";
+    let _ = app.delete(&format!("/collections/{name}")).await;
+    let (status, resp) = app
+        .post_json("/collections", json!({"name": name, "dimension": 512}))
+        .await;
+    assert!(status.is_success(), "create status {status}: {resp}");
+
+    let vectors: Vec<_> = (0..20)
+        .map(|i| {
+            let embedding: Vec<f32> = (0..512)
+                .map(|d| ((i * 31 + d * 7) % 17) as f32 + 1.0)
+                .collect();
+            json!({"id": format!("v{i}"), "embedding": embedding})
+        })
+        .collect();
+    let (status, resp) = app
+        .post_json(
+            "/insert_vectors",
+            json!({"collection": name, "vectors": vectors}),
+        )
+        .await;
+    assert!(status.is_success(), "insert status {status}: {resp}");
+
+    let search = json!({"vector": vec![1.0_f32; 512], "limit": 5});
+    let path = format!("/collections/{name}/search");
+    for _ in 0..2 {
+        let (status, resp) = app.post_json(&path, search.clone()).await;
+        assert!(status.is_success(), "search status {status}: {resp}");
+    }
+
+    // The second search is served from the query cache, so only the
+    // first walks the graph.
+    let visited = METRICS.search_candidates_visited.with_label_values(&[name]);
+    assert_eq!(visited.get_sample_count(), 1);
+    assert!(visited.get_sample_sum() >= 5.0);
+    let ef = METRICS.search_ef_search.with_label_values(&[name]);
+    assert_eq!(ef.get_sample_count(), 1);
+    assert_eq!(ef.get_sample_sum(), 64.0);
+
+    let lookups = |result: &str| {
+        METRICS
+            .query_cache_requests_total
+            .with_label_values(&[name, result])
+            .get()
+    };
+    assert_eq!(lookups("miss"), 1.0);
+    assert_eq!(lookups("hit"), 1.0);
+}
+
+#[tokio::test]
+async fn writes_invalidate_cached_searches() {
+    let app = TestApp::new().await;
+    let name = "search_metrics_invalidation";
+    let (status, resp) = app
+        .post_json(
+            "/collections",
+            json!({"name": name, "dimension": 512, "metric": "cosine"}),
+        )
+        .await;
+    assert!(status.is_success(), "create status {status}: {resp}");
+    let insert = |id: &str, head: &[f32]| json!({"collection": name, "vectors": [{"id": id, "embedding": embedding(head)}]});
+    let (status, resp) = app
+        .post_json("/insert_vectors", insert("far", &[0.0, 1.0]))
+        .await;
+    assert!(status.is_success(), "insert status {status}: {resp}");
+
+    // Same direction, different length: one cache entry under cosine
+    let path = format!("/collections/{name}/search");
+    for scale in [1.0_f32, 3.0] {
+        let (status, resp) = app
+            .post_json(
+                &path,
+                json!({"vector": embedding(&[scale, scale * 0.1]), "limit": 1}),
+            )
+            .await;
+        assert!(status.is_success(), "search status {status}: {resp}");
+        assert_eq!(resp["results"][0]["id"], "far");
+    }
+
+    let (status, resp) = app
+        .post_json("/insert_vectors", insert("near", &[1.0]))
+        .await;
+    assert!(status.is_success(), "insert status {status}: {resp}");
+    let (status, resp) = app
+        .post_json(&path, json!({"vector": embedding(&[1.0, 0.1]), "limit": 1}))
+        .await;
+    assert!(status.is_success(), "search status {status}: {resp}");
+    assert_eq!(resp["results"][0]["id"], "near");
+
+    let (status, resp) = app.get("/stats").await;
+    assert!(status.is_success(), "stats status {status}: {resp}");
+    let cache = &resp["query_cache"]["collections"][name];
+    assert_eq!(cache["hits"], 1);
+    assert_eq!(cache["misses"], 2);
+    assert_eq!(cache["invalidations"], 1);
+    let invalidated = METRICS
+        .query_cache_removals_total
+        .with_label_values(&[name, "invalidated"])
+        .get();
+    assert_eq!(invalidated, 1.0);
+}
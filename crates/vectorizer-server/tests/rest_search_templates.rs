@@ -0,0 +1,111 @@
+//! Search templates: admins store a parameterized search under
+//! `/admin/search/templates/{name}` and clients run it by name via
+//! `POST /search/templates/{name}/run`.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use common::TestApp;
+use serde_json::json;
+
+async fn seed(app: &TestApp, name: &str) {
+    let (status, resp) = app
+        .post_json(
+            "/collections",
+            json!({
+                "name": name,
+                "dimension": 512,
+                "metric": "cosine",
+                "full_text": {},
+            }),
+        )
+        .await;
+    assert!(status.is_success(), "create status {status}: {resp}");
+    let (status, resp) = app
+        .post_json(
+            "/batch_insert",
+            json!({
+                "collection": name,
+                "texts": [
+                    {"text": "alpha doc uno",  "metadata": {"lang": "en"}},
+                    {"text": "alpha doc dos",  "metadata": {"lang": "pt"}},
+                    {"text": "gamma doc tres", "metadata": {"lang": "en"}},
+                ],
+            }),
+        )
+        .await;
+    assert!(status.is_success(), "insert status {status}: {resp}");
+}
+
+#[tokio::test]
+async fn stored_template_runs_by_name() {
+    let app = TestApp::new().await;
+    seed(&app, "template_docs").await;
+
+    let template = json!({
+        "collections": ["template_docs"],
+        "limit": 5,
+        "filter": {"must": [{"type": "match", "key": "lang", "match_value": "{{lang}}"}]},
+        "hybrid": {"alpha": 0.5},
+        "parameters": {"lang": {"default": "en"}},
+    });
+    let (status, resp) = app
+        .put_json("/admin/search/templates/by_lang", template)
+        .await;
+    assert!(status.is_success(), "put status {status}: {resp}");
+    assert_eq!(resp["replaced"], false);
+
+    let (status, resp) = app.get("/search/templates").await;
+    assert!(status.is_success(), "list status {status}: {resp}");
+    assert_eq!(resp["templates"][0]["name"], "by_lang");
+
+    let (status, resp) = app
+        .post_json("/search/templates/by_lang/run", json!({"query": "alpha"}))
+        .await;
+    assert!(status.is_success(), "run status {status}: {resp}");
+    let hits = resp["results"].as_array().unwrap();
+    assert_eq!(hits.len(), 2);
+    assert!(hits.iter().all(|hit| hit["payload"]["lang"] == "en"));
+    assert_eq!(hits[0]["collection"], "template_docs");
+
+    let (status, resp) = app
+        .post_json(
+            "/search/templates/by_lang/run",
+            json!({"query": "alpha", "params": {"lang": "pt"}}),
+        )
+        .await;
+    assert!(status.is_success(), "run status {status}: {resp}");
+    assert_eq!(resp["total_results"], 1);
+
+    let (status, _) = app
+        .post_json(
+            "/search/templates/by_lang/run",
+            json!({"query": "alpha", "params": {"region": "eu"}}),
+        )
+        .await;
+    assert_eq!(status.as_u16(), 400);
+
+    let (status, _) = app.delete("/admin/search/templates/by_lang").await;
+    assert!(status.is_success());
+    let (status, _) = app
+        .post_json("/search/templates/by_lang/run", json!({"query": "alpha"}))
+        .await;
+    assert_eq!(status.as_u16(), 404);
+}
+
+#[tokio::test]
+async fn invalid_templates_are_rejected() {
+    let app = TestApp::new().await;
+    for template in [
+        json!({"collections": []}),
+        json!({"collections": ["docs"], "limit": 0}),
+        json!({"collections": ["docs"], "hybrid": {"alpha": 2.0}}),
+        json!({"collections": ["docs"], "parameters": {"lang": {}}}),
+    ] {
+        let (status, resp) = app
+            .put_json("/admin/search/templates/bad", template.clone())
+            .await;
+        assert_eq!(status.as_u16(), 400, "{template} accepted: {resp}");
+    }
+}
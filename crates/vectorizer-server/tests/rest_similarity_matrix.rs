@@ -0,0 +1,107 @@
+//! `POST /collections/{name}/similarity_matrix` through the real router.
+//!
+//! Covers the symmetric matrix for a Euclidean collection, the cosine
+//! `metric` override, `missing` reporting for unknown IDs, and the
+//! empty / oversized `ids` validation.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+#![allow(clippy::uninlined_format_args)]
+
+mod common;
+
+use common::{TestApp, embedding};
+use serde_json::{Value, json};
+
+/// Recreate `name` as a 512-dim Euclidean collection holding three
+/// hand-picked vectors with known pairwise distances.
+async fn seed(app: &TestApp, name: &str) {
+    let _ = app.delete(&format!("/collections/{name}")).await;
+    app.create_collection(json!({"name": name, "dimension": 512, "metric": "euclidean"}))
+        .await;
+
+    let resp = app
+        .insert_vectors(
+            name,
+            json!([
+                {"id": "a", "embedding": embedding(&[1.0])},
+                {"id": "b", "embedding": embedding(&[1.0, 3.0, 4.0])},
+                {"id": "c", "embedding": embedding(&[0.0, 1.0])},
+            ]),
+        )
+        .await;
+    assert_eq!(resp["inserted"].as_u64(), Some(3));
+}
+
+fn cell(resp: &Value, i: usize, j: usize) -> f64 {
+    resp["matrix"][i][j].as_f64().unwrap()
+}
+
+#[tokio::test]
+async fn similarity_matrix_returns_symmetric_distances() {
+    let app = TestApp::new().await;
+    let name = "similarity_matrix_euclidean";
+    seed(&app, name).await;
+
+    let (status, resp) = app
+        .post_json(
+            &format!("/collections/{name}/similarity_matrix"),
+            json!({"ids": ["a", "b", "c", "a", "ghost"]}),
+        )
+        .await;
+    assert!(status.is_success(), "status {status}: {resp}");
+
+    assert_eq!(resp["ids"], json!(["a", "b", "c"]));
+    assert_eq!(resp["missing"], json!(["ghost"]));
+    assert_eq!(resp["metric"].as_str(), Some("euclidean"));
+    assert_eq!(resp["kind"].as_str(), Some("distance"));
+
+    assert_eq!(cell(&resp, 0, 0), 0.0);
+    assert!((cell(&resp, 0, 1) - 5.0).abs() < 1e-4);
+    assert!((cell(&resp, 0, 2) - 2f64.sqrt()).abs() < 1e-4);
+    for i in 0..3 {
+        for j in 0..3 {
+            assert_eq!(cell(&resp, i, j), cell(&resp, j, i));
+        }
+    }
+}
+
+#[tokio::test]
+async fn similarity_matrix_honours_metric_override() {
+    let app = TestApp::new().await;
+    let name = "similarity_matrix_override";
+    seed(&app, name).await;
+
+    let (status, resp) = app
+        .post_json(
+            &format!("/collections/{name}/similarity_matrix"),
+            json!({"ids": ["a", "c"], "metric": "cosine"}),
+        )
+        .await;
+    assert!(status.is_success(), "status {status}: {resp}");
+
+    assert_eq!(resp["kind"].as_str(), Some("similarity"));
+    assert!((cell(&resp, 0, 0) - 1.0).abs() < 1e-6);
+    assert!(cell(&resp, 0, 1).abs() < 1e-6);
+}
+
+#[tokio::test]
+async fn similarity_matrix_rejects_empty_and_oversized_ids() {
+    let app = TestApp::new().await;
+    let name = "similarity_matrix_validation";
+    seed(&app, name).await;
+    let path = format!("/collections/{name}/similarity_matrix");
+
+    let (status, _) = app.post_json(&path, json!({"ids": []})).await;
+    assert_eq!(status.as_u16(), 400);
+
+    let too_many: Vec<String> = (0..=vectorizer::search::MAX_SIMILARITY_MATRIX_IDS)
+        .map(|i| format!("id-{i}"))
+        .collect();
+    let (status, _) = app.post_json(&path, json!({"ids": too_many})).await;
+    assert_eq!(status.as_u16(), 400);
+
+    let (status, _) = app
+        .post_json(&path, json!({"ids": ["a"], "metric": "manhattan"}))
+        .await;
+    assert_eq!(status.as_u16(), 400);
+}
@@ -0,0 +1,59 @@
+//! Startup progress (`/startup/progress`): per-collection load state
+//! reported from the background loading task.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use std::sync::Arc;
+
+use common::TestApp;
+use vectorizer::db::{LoadSource, StartupPhase, StartupProgress};
+
+#[tokio::test]
+async fn reports_collection_states_while_loading() {
+    let progress = Arc::new(StartupProgress::new());
+    let installed = Arc::clone(&progress);
+    let app = TestApp::with_server(move |server| server.startup_progress = installed).await;
+
+    progress.set_phase(StartupPhase::LoadingPersisted);
+    progress.pending("docs", LoadSource::Persisted, Some(3));
+    progress.pending("code", LoadSource::Persisted, Some(2));
+    progress.pending("notes", LoadSource::Workspace, None);
+    progress.loading("docs", LoadSource::Persisted);
+    progress.indexed("docs", LoadSource::Persisted, 3);
+    progress.loading("code", LoadSource::Persisted);
+    progress.failed("code", LoadSource::Persisted, "corrupt archive entry");
+
+    let (status, resp) = app.get("/startup/progress").await;
+    assert!(status.is_success(), "status {status}: {resp}");
+    assert_eq!(resp["phase"], "loading_persisted");
+    assert_eq!(resp["complete"], false);
+    assert_eq!(resp["counts"]["indexed"], 1);
+    assert_eq!(resp["counts"]["failed"], 1);
+    assert_eq!(resp["counts"]["pending"], 1);
+    assert_eq!(resp["vectors_loaded"], 3);
+    assert_eq!(resp["collections"]["docs"]["state"], "indexed");
+    assert_eq!(resp["collections"]["docs"]["vectors"], 3);
+    assert_eq!(resp["collections"]["code"]["state"], "failed");
+    assert_eq!(
+        resp["collections"]["code"]["error"],
+        "corrupt archive entry"
+    );
+    assert_eq!(resp["collections"]["notes"]["state"], "pending");
+    assert_eq!(resp["collections"]["notes"]["source"], "workspace");
+
+    progress.finish(None);
+    let (_, resp) = app.get("/startup/progress").await;
+    assert_eq!(resp["phase"], "complete");
+    assert_eq!(resp["complete"], true);
+}
+
+#[tokio::test]
+async fn nothing_to_load_is_complete() {
+    let app = TestApp::new().await;
+    let (status, resp) = app.get("/startup/progress").await;
+    assert!(status.is_success(), "status {status}: {resp}");
+    assert_eq!(resp["complete"], true);
+    assert!(resp["collections"].as_object().unwrap().is_empty());
+}
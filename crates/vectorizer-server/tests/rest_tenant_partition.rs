@@ -0,0 +1,64 @@
+//! Tenant-partitioned collections (`tenant_partition` on create): the
+//! partition is part of the collection's config, and callers without a
+//! hub tenant (this harness has no hub) see the whole collection.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use common::{TestApp, embedding};
+use serde_json::json;
+
+#[tokio::test]
+async fn partitioned_collection_round_trips_its_config() {
+    let app = TestApp::new().await;
+    let (status, resp) = app
+        .post_json(
+            "/collections",
+            json!({"name": "shared_docs", "dimension": 512, "tenant_partition": {}}),
+        )
+        .await;
+    assert!(status.is_success(), "create status {status}: {resp}");
+
+    let (status, resp) = app.get("/collections/shared_docs").await;
+    assert!(status.is_success(), "get status {status}: {resp}");
+    assert_eq!(resp["tenant_partition"]["key"], "tenant_id");
+
+    let (status, resp) = app
+        .post_json(
+            "/insert_vectors",
+            json!({
+                "collection": "shared_docs",
+                "vectors": [
+                    {"id": "a", "embedding": embedding(&[1.0]), "payload": {"tenant_id": "acme"}},
+                    {"id": "g", "embedding": embedding(&[0.9, 0.1]), "payload": {"tenant_id": "globex"}},
+                ],
+            }),
+        )
+        .await;
+    assert!(status.is_success(), "insert status {status}: {resp}");
+    assert_eq!(resp["inserted"], 2);
+
+    let (status, resp) = app
+        .post_json(
+            "/collections/shared_docs/search",
+            json!({"vector": embedding(&[1.0]), "limit": 10}),
+        )
+        .await;
+    assert!(status.is_success(), "search status {status}: {resp}");
+    assert_eq!(resp["results"].as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn invalid_partition_config_is_rejected() {
+    let app = TestApp::new().await;
+    for partition in [json!({"key": ""}), json!({"key": 7}), json!("tenant_id")] {
+        let (status, resp) = app
+            .post_json(
+                "/collections",
+                json!({"name": "bad_partition", "dimension": 512, "tenant_partition": partition}),
+            )
+            .await;
+        assert_eq!(status.as_u16(), 400, "{partition} accepted: {resp}");
+    }
+}
@@ -0,0 +1,111 @@
+//! `hub.tenant_quotas` through the real router: once a tenant holds
+//! its `max_vectors`, inserts into its collections are refused with 429
+//! `QUOTA_EXCEEDED` over `/insert` and the Qdrant-compatible upsert
+//! alike, and nothing is written.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use std::sync::Arc;
+
+use common::{TestApp, embedding};
+use serde_json::{Value, json};
+use vectorizer::hub::{HubConfig, HubManager};
+use vectorizer::models::{CollectionConfig, Vector};
+
+const TENANT: &str = "7f1c2a34-5b6d-4e8f-9a0b-1c2d3e4f5a6b";
+const COLLECTION: &str = "tenant_quota_full";
+
+/// Header HiveHub sends on internal calls; the hub middleware lets
+/// those through without an API key.
+const INTERNAL: (&str, &str) = ("x-hivehub-service", "test");
+
+/// A hub-enabled app whose tenant may hold two vectors and already
+/// holds them in [`COLLECTION`].
+async fn app_at_tenant_quota() -> TestApp {
+    let mut config = HubConfig {
+        enabled: true,
+        api_url: "http://127.0.0.1:9".to_string(),
+        service_api_key: Some("test-service-key".to_string()),
+        retries: 0,
+        ..HubConfig::default()
+    };
+    config.tenant_quotas.default.max_vectors = Some(2);
+    let hub_manager = Arc::new(HubManager::new(config).await.unwrap());
+
+    TestApp::with_server(|server| {
+        let config = CollectionConfig {
+            dimension: common::EMBEDDING_DIM,
+            ..CollectionConfig::default()
+        };
+        server
+            .store
+            .create_collection_with_owner(COLLECTION, config, TENANT.parse().unwrap())
+            .unwrap();
+        let held = (0..2)
+            .map(|i| {
+                let mut data = vec![0.0; common::EMBEDDING_DIM];
+                data[i] = 1.0;
+                Vector::new(format!("held{i}"), data)
+            })
+            .collect();
+        server.store.insert(COLLECTION, held).unwrap();
+        server.hub_manager = Some(hub_manager);
+    })
+    .await
+}
+
+fn assert_vector_quota_refusal(status: axum::http::StatusCode, resp: &Value) {
+    assert_eq!(status.as_u16(), 429, "{resp}");
+    assert_eq!(resp["error_type"], "QUOTA_EXCEEDED", "{resp}");
+    let details = &resp["details"];
+    assert_eq!(details["tenant_id"], TENANT, "{resp}");
+    assert_eq!(details["quota"], "vector_count", "{resp}");
+    assert_eq!(details["limit"], 2, "{resp}");
+    assert_eq!(details["used"], 2, "{resp}");
+    assert_eq!(details["requested"], 1, "{resp}");
+}
+
+async fn held_vectors(app: &TestApp) -> Option<u64> {
+    let (_, info) = app.get(&format!("/collections/{COLLECTION}")).await;
+    info["vector_count"].as_u64()
+}
+
+#[tokio::test]
+async fn insert_text_past_the_tenant_quota_is_refused() {
+    let app = app_at_tenant_quota().await;
+
+    let (status, resp) = app
+        .request_with_header(
+            "POST",
+            "/insert",
+            INTERNAL,
+            Some(json!({
+                "collection": COLLECTION,
+                "text": "one vector too many",
+                "auto_chunk": false,
+            })),
+        )
+        .await;
+    assert_vector_quota_refusal(status, &resp);
+    assert_eq!(held_vectors(&app).await, Some(2));
+}
+
+#[tokio::test]
+async fn qdrant_upsert_past_the_tenant_quota_is_refused() {
+    let app = app_at_tenant_quota().await;
+
+    let (status, resp) = app
+        .request_with_header(
+            "PUT",
+            &format!("/qdrant/collections/{COLLECTION}/points"),
+            INTERNAL,
+            Some(json!({
+                "points": [{"id": 10, "vector": embedding(&[0.5, 0.5])}],
+            })),
+        )
+        .await;
+    assert_vector_quota_refusal(status, &resp);
+    assert_eq!(held_vectors(&app).await, Some(2));
+}
@@ -127,6 +127,135 @@ async fn search_by_collection_path_returns_same_results() {
     assert_eq!(results[0]["id"].as_str(), Some(target_id.as_str()));
 }
 
+#[tokio::test]
+async fn exact_search_scores_every_vector_and_matches_hnsw_on_small_collections() {
+    let app = TestApp::new().await;
+    seed_collection(&app, "vector_search_exact").await;
+    let (target_id, target_vec) = first_vector(&app, "vector_search_exact").await;
+
+    let path = "/collections/vector_search_exact/search";
+    let (status, exact) = app
+        .post_json(
+            path,
+            json!({"vector": target_vec, "limit": 5, "exact": true}),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "exact search status: {exact}");
+    assert_eq!(exact["exact"].as_bool(), Some(true));
+    let (_, approx) = app
+        .post_json(path, json!({"vector": target_vec, "limit": 5}))
+        .await;
+    assert_eq!(approx["exact"].as_bool(), Some(false));
+
+    // Five vectors, limit 5: the exact scan returns all of them, best
+    // first, and HNSW finds the same set on a collection this small.
+    let ids = |resp: &serde_json::Value| -> Vec<String> {
+        resp["results"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| r["id"].as_str().unwrap().to_string())
+            .collect()
+    };
+    let exact_ids = ids(&exact);
+    assert_eq!(exact_ids.len(), 5);
+    assert_eq!(exact_ids[0], target_id);
+    assert!(exact["results"][0]["score"].as_f64().unwrap() >= 0.999);
+    let scores: Vec<f64> = exact["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| r["score"].as_f64().unwrap())
+        .collect();
+    assert!(scores.windows(2).all(|w| w[0] >= w[1]), "{scores:?}");
+    let mut approx_ids = ids(&approx);
+    let mut sorted_exact = exact_ids.clone();
+    approx_ids.sort();
+    sorted_exact.sort();
+    assert_eq!(approx_ids, sorted_exact);
+}
+
+#[tokio::test]
+async fn search_timeout_ms_is_validated_and_reported() {
+    let app = TestApp::new().await;
+    seed_collection(&app, "vector_search_timeout").await;
+    let (target_id, target_vec) = first_vector(&app, "vector_search_timeout").await;
+
+    let path = "/collections/vector_search_timeout/search";
+    let (status, body) = app
+        .post_json(
+            path,
+            json!({"vector": target_vec, "limit": 3, "timeout_ms": 10_000}),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "search status: {body}");
+    assert_eq!(body["timed_out"].as_bool(), Some(false));
+    assert_eq!(body["results"][0]["id"].as_str(), Some(target_id.as_str()));
+
+    for bad in [json!(0), json!(-5), json!("fast")] {
+        let (status, body) = app
+            .post_json(path, json!({"vector": target_vec, "timeout_ms": bad}))
+            .await;
+        assert_eq!(status.as_u16(), 400, "{body}");
+        assert_eq!(body["error_type"].as_str(), Some("validation_error"));
+    }
+
+    let (status, body) = app
+        .post_json(
+            "/batch_search",
+            json!({
+                "collection": "vector_search_timeout",
+                "timeout_ms": 10_000,
+                "queries": [{"vector": target_vec, "limit": 1}, {"query": "beta"}],
+            }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "batch status: {body}");
+    assert_eq!(body["succeeded"].as_u64(), Some(2));
+    assert_eq!(body["timed_out"].as_bool(), Some(false));
+}
+
+#[tokio::test]
+async fn text_batch_search_returns_results_per_query() {
+    let app = TestApp::new().await;
+    seed_collection(&app, "vector_search_text_batch").await;
+
+    let (status, body) = app
+        .post_json(
+            "/collections/vector_search_text_batch/search/text/batch",
+            json!({
+                "limit": 2,
+                "queries": [
+                    "alpha doc",
+                    {"query": "gamma doc", "limit": 1},
+                    {"vector": [0.1, 0.2]},
+                ],
+            }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "text batch status: {body}");
+    assert_eq!(
+        body["collection"].as_str(),
+        Some("vector_search_text_batch")
+    );
+    assert_eq!(body["succeeded"].as_u64(), Some(2));
+    assert_eq!(body["failed"].as_u64(), Some(1));
+
+    let results = body["results"].as_array().expect("results array");
+    assert_eq!(results[0]["query"].as_str(), Some("alpha doc"));
+    assert_eq!(results[0]["results"].as_array().map(Vec::len), Some(2));
+    assert_eq!(results[1]["results"].as_array().map(Vec::len), Some(1));
+    assert_eq!(results[2]["status"].as_str(), Some("error"));
+
+    let (status, _) = app
+        .post_json(
+            "/collections/vector_search_text_batch/search/text/batch",
+            json!({"queries": []}),
+        )
+        .await;
+    assert_eq!(status.as_u16(), 400);
+}
+
 #[tokio::test]
 async fn search_rejects_vector_dimension_mismatch() {
     let app = TestApp::new().await;
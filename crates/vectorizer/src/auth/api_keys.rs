@@ -116,6 +116,20 @@ impl ApiKeyManager {
         Ok(self.stamp_usage_count(key.clone()))
     }
 
+    /// Replace a key's collection namespace (`None` lifts it).
+    pub async fn set_namespace(
+        &self,
+        key_id: &str,
+        namespace: Option<crate::auth::CollectionNamespace>,
+    ) -> Result<ApiKey> {
+        let mut keys = self.keys.write().await;
+        let key = keys
+            .get_mut(key_id)
+            .ok_or_else(|| VectorizerError::NotFound(format!("API key {} not found", key_id)))?;
+        key.namespace = namespace;
+        Ok(self.stamp_usage_count(key.clone()))
+    }
+
     /// Generate a new API key
     fn generate_key(&self) -> String {
         use rand::Rng;
@@ -165,6 +179,7 @@ impl ApiKeyManager {
             grace_until: None,
             rotated_to: None,
             usage_count: 0,
+            namespace: None,
         };
 
         let mut keys = self.keys.write().await;
@@ -361,6 +376,7 @@ impl ApiKeyManager {
             grace_until: None,
             rotated_to: None,
             usage_count: 0,
+            namespace: None,
         };
 
         let mut keys = self.keys.write().await;
@@ -608,6 +624,7 @@ mod tests {
             grace_until: None,
             rotated_to: None,
             usage_count: 42,
+            namespace: None,
         };
         manager.register_key(key).await.unwrap();
         assert_eq!(manager.current_usage("persisted"), 42);
@@ -57,6 +57,7 @@ impl JwtManager {
             iat: now,
             exp: now + self.expiration,
             scopes: vec![],
+            namespace: None,
         };
 
         let header = Header::new(Algorithm::HS256);
@@ -102,6 +102,7 @@ impl AuthMiddleware {
                 iat: 0,
                 exp: 0,
                 scopes: vec![],
+                namespace: None,
             },
             authenticated: false,
         }
@@ -8,18 +8,24 @@ pub mod audit;
 pub mod jwt;
 pub mod jwt_secret;
 pub mod middleware;
+pub mod namespace;
+pub mod oidc;
 pub mod password;
 pub mod persistence;
+pub mod request_signing;
 pub mod roles;
 pub mod secret;
 
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
 
 pub use api_keys::ApiKeyManager;
 pub use audit::{AuditEntry, AuditLogger, AuditQuery};
 pub use jwt::JwtManager;
 pub use middleware::AuthMiddleware;
+pub use namespace::{CollectionNamespace, collections_named_in};
+pub use oidc::OidcValidator;
 pub use password::{
     PasswordRequirements, PasswordValidationResult, validate_password,
     validate_password_with_requirements,
@@ -35,7 +41,7 @@ use vectorizer_core::metrics_sink::{MetricsSink, NoopMetricsSink};
 // on auth, so the dependency now points the other way). Re-exported
 // here under the historical `crate::auth::*` paths so every existing
 // call site keeps compiling.
-pub use crate::config::sections::auth::{AuthConfig, CookieConfig};
+pub use crate::config::sections::auth::{AuthConfig, CookieConfig, OidcConfig};
 use crate::error::{Result, VectorizerError};
 
 /// The insecure literal that shipped as the hardcoded default in every
@@ -125,6 +131,10 @@ pub struct UserClaims {
     /// default-deny (for API keys with no explicit scopes).
     #[serde(default)]
     pub scopes: Vec<TokenScope>,
+    /// Collection-name namespace the caller is confined to (API keys
+    /// only); `None` means unrestricted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<CollectionNamespace>,
 }
 
 /// API Key information
@@ -167,6 +177,10 @@ pub struct ApiKey {
     /// to `#[serde(default)]`.
     #[serde(default)]
     pub usage_count: u64,
+    /// Collection-name prefix namespace (e.g. `team-a/*`) every call
+    /// made with this key is confined to. `None` = no namespace.
+    #[serde(default)]
+    pub namespace: Option<CollectionNamespace>,
 }
 
 /// Result of an API-key rotation.
@@ -223,6 +237,11 @@ pub struct AuthManager {
     api_key_manager: ApiKeyManager,
     /// Rate limiting storage
     rate_limits: Arc<RwLock<HashMap<String, RateLimitInfo>>>,
+    /// Requests per minute allowed per API key. Starts at
+    /// `auth.rate_limit_per_minute`; changed live by [`Self::set_rate_limits`]
+    rate_limit_per_minute: AtomicU32,
+    /// Requests per hour allowed per API key (`auth.rate_limit_per_hour`)
+    rate_limit_per_hour: AtomicU32,
     /// Configuration
     config: AuthConfig,
     /// Metrics sink for cross-cutting instrumentation — in particular,
@@ -232,6 +251,9 @@ pub struct AuthManager {
     /// the operator's `ApiKeyUsageRecorder` exists (it is constructed
     /// after `AuthManager::new`, so it can't be a constructor param).
     metrics: parking_lot::RwLock<Arc<dyn MetricsSink>>,
+    /// External identity provider whose RS256 tokens are accepted
+    /// alongside local JWTs (`auth.oidc`)
+    oidc: Option<Arc<OidcValidator>>,
 }
 
 impl AuthManager {
@@ -245,13 +267,22 @@ impl AuthManager {
         let jwt_manager =
             JwtManager::new(config.jwt_secret.expose_secret(), config.jwt_expiration)?;
         let api_key_manager = ApiKeyManager::new(config.api_key_length)?;
+        let oidc = config
+            .oidc
+            .clone()
+            .map(OidcValidator::new)
+            .transpose()?
+            .map(Arc::new);
 
         Ok(Self {
             jwt_manager,
             api_key_manager,
             rate_limits: Arc::new(RwLock::new(HashMap::new())),
+            rate_limit_per_minute: AtomicU32::new(config.rate_limit_per_minute),
+            rate_limit_per_hour: AtomicU32::new(config.rate_limit_per_hour),
             config,
             metrics: parking_lot::RwLock::new(Arc::new(NoopMetricsSink)),
+            oidc,
         })
     }
 
@@ -319,6 +350,7 @@ impl AuthManager {
             iat: chrono::Utc::now().timestamp() as u64,
             exp: key_info.expires_at.unwrap_or(u64::MAX),
             scopes: key_info.scopes.clone(),
+            namespace: key_info.namespace.clone(),
         })
     }
 
@@ -327,11 +359,37 @@ impl AuthManager {
         self.jwt_manager.generate_token(user_id, username, roles)
     }
 
-    /// Validate a JWT token
+    /// Validate a JWT token: a local HS256 token, or an RS256 token
+    /// from the OIDC provider when one is configured
     pub fn validate_jwt(&self, token: &str) -> Result<UserClaims> {
+        if let Some(oidc) = &self.oidc
+            && OidcValidator::is_provider_token(token)
+        {
+            return oidc.validate(token);
+        }
         self.jwt_manager.validate_token(token)
     }
 
+    /// The OIDC provider validator, when `auth.oidc` is configured
+    pub fn oidc(&self) -> Option<&Arc<OidcValidator>> {
+        self.oidc.as_ref()
+    }
+
+    /// Whether `token` was issued by the OIDC provider rather than by
+    /// this server. Provider tokens are refreshed through the provider.
+    pub fn is_provider_token(&self, token: &str) -> bool {
+        self.oidc.is_some() && OidcValidator::is_provider_token(token)
+    }
+
+    /// Whether `/auth/login` and locally managed users are enabled.
+    /// Only `auth.oidc.local_login: false` turns them off.
+    pub fn local_login_enabled(&self) -> bool {
+        self.config
+            .oidc
+            .as_ref()
+            .is_none_or(|oidc| oidc.local_login)
+    }
+
     /// Check rate limiting for an API key
     async fn check_rate_limit(&self, api_key_id: &str) -> Result<()> {
         let mut rate_limits = self.rate_limits.write().await;
@@ -358,17 +416,18 @@ impl AuthManager {
         }
 
         // Check limits
-        if rate_info.requests_per_minute >= self.config.rate_limit_per_minute {
+        let (per_minute, per_hour) = self.rate_limits();
+        if rate_info.requests_per_minute >= per_minute {
             return Err(VectorizerError::RateLimitExceeded {
                 limit_type: "per_minute".to_string(),
-                limit: self.config.rate_limit_per_minute,
+                limit: per_minute,
             });
         }
 
-        if rate_info.requests_per_hour >= self.config.rate_limit_per_hour {
+        if rate_info.requests_per_hour >= per_hour {
             return Err(VectorizerError::RateLimitExceeded {
                 limit_type: "per_hour".to_string(),
-                limit: self.config.rate_limit_per_hour,
+                limit: per_hour,
             });
         }
 
@@ -389,11 +448,28 @@ impl AuthManager {
         self.api_key_manager.revoke_key(api_key_id).await
     }
 
-    /// Get authentication configuration
+    /// Get authentication configuration. The rate limits in it are the
+    /// ones the manager was built with; see [`Self::rate_limits`].
     pub fn config(&self) -> &AuthConfig {
         &self.config
     }
 
+    /// Per-API-key request limits in force, as `(per_minute, per_hour)`.
+    pub fn rate_limits(&self) -> (u32, u32) {
+        (
+            self.rate_limit_per_minute.load(Ordering::Relaxed),
+            self.rate_limit_per_hour.load(Ordering::Relaxed),
+        )
+    }
+
+    /// Change the per-API-key request limits. Applies to the next
+    /// request; counts already taken in the current window are kept.
+    pub fn set_rate_limits(&self, per_minute: u32, per_hour: u32) {
+        self.rate_limit_per_minute
+            .store(per_minute, Ordering::Relaxed);
+        self.rate_limit_per_hour.store(per_hour, Ordering::Relaxed);
+    }
+
     /// Register an existing API key (for loading from persistence)
     pub async fn register_api_key(&self, key_info: ApiKey) -> Result<()> {
         self.api_key_manager.register_key(key_info).await
@@ -423,6 +499,16 @@ impl AuthManager {
             .await
     }
 
+    /// Confine an existing API key to a collection namespace, or lift
+    /// the restriction with `None`. Takes effect on the key's next call.
+    pub async fn set_api_key_namespace(
+        &self,
+        key_id: &str,
+        namespace: Option<CollectionNamespace>,
+    ) -> Result<ApiKey> {
+        self.api_key_manager.set_namespace(key_id, namespace).await
+    }
+
     /// Read the live usage counter for a key without taking the
     /// keys-map read lock. Returns 0 for unknown / never-validated
     /// keys.
@@ -465,6 +551,11 @@ impl AuthManager {
                 old_key.scopes.clone(),
             )
             .await?;
+        if old_key.namespace.is_some() {
+            self.api_key_manager
+                .set_namespace(&new_key.id, old_key.namespace.clone())
+                .await?;
+        }
 
         let grace_until = chrono::Utc::now().timestamp() as u64 + grace_secs;
 
@@ -485,7 +576,7 @@ impl AuthManager {
     /// Tries JWT first, then API key.  Returns `active: false` for any
     /// token that fails both checks.
     pub async fn introspect_token(&self, token: &str) -> TokenIntrospection {
-        if let Ok(claims) = self.jwt_manager.validate_token(token) {
+        if let Ok(claims) = self.validate_jwt(token) {
             let scope = if claims.scopes.is_empty() {
                 None
             } else {
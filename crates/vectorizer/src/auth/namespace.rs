@@ -0,0 +1,210 @@
+//! Collection-name prefix namespaces for API keys.
+//!
+//! A key created with a namespace such as `team-a/*` can only reach
+//! collections whose names start with `team-a/`: REST, gRPC and MCP
+//! calls naming any other collection are refused, and collection
+//! listings are filtered to the visible set. This is lightweight
+//! isolation for shared deployments that do not run the HiveHub
+//! multi-tenant mode.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::UserClaims;
+use crate::error::{Result, VectorizerError};
+
+/// A collection-name prefix pattern, written `<prefix>*`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct CollectionNamespace(String);
+
+impl CollectionNamespace {
+    /// Parse a `<prefix>*` pattern. The prefix must be non-empty and the
+    /// only `*` is the trailing one.
+    pub fn parse(pattern: &str) -> Result<Self> {
+        let invalid = |reason: &str| VectorizerError::InvalidConfiguration {
+            message: format!("invalid collection namespace '{}': {}", pattern, reason),
+        };
+        let prefix = pattern
+            .strip_suffix('*')
+            .ok_or_else(|| invalid("must end with '*', e.g. 'team-a/*'"))?;
+        if prefix.is_empty() {
+            return Err(invalid("prefix is empty"));
+        }
+        if prefix.contains('*') {
+            return Err(invalid("only a trailing '*' is supported"));
+        }
+        Ok(Self(pattern.to_string()))
+    }
+
+    /// The pattern as written, e.g. `team-a/*`.
+    pub fn pattern(&self) -> &str {
+        &self.0
+    }
+
+    /// The prefix every visible collection name starts with.
+    pub fn prefix(&self) -> &str {
+        self.0.strip_suffix('*').unwrap_or(&self.0)
+    }
+
+    /// Whether `collection` is inside the namespace.
+    pub fn contains(&self, collection: &str) -> bool {
+        collection.starts_with(self.prefix())
+    }
+
+    /// Whether every collection `other` admits is also inside this
+    /// namespace, so a key confined here may hand it out.
+    pub fn covers(&self, other: &CollectionNamespace) -> bool {
+        other.prefix().starts_with(self.prefix())
+    }
+
+    /// Refuse `collection` unless it is inside the namespace.
+    pub fn check(&self, collection: &str) -> Result<()> {
+        if self.contains(collection) {
+            Ok(())
+        } else {
+            Err(VectorizerError::AuthorizationError(format!(
+                "collection '{}' is outside this key's namespace '{}'",
+                collection, self.0
+            )))
+        }
+    }
+}
+
+impl TryFrom<String> for CollectionNamespace {
+    type Error = VectorizerError;
+
+    fn try_from(pattern: String) -> Result<Self> {
+        Self::parse(&pattern)
+    }
+}
+
+impl From<CollectionNamespace> for String {
+    fn from(namespace: CollectionNamespace) -> Self {
+        namespace.0
+    }
+}
+
+impl std::fmt::Display for CollectionNamespace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl UserClaims {
+    /// Whether the caller may reach `collection`. Callers without a
+    /// namespace are not restricted here.
+    pub fn can_access_collection(&self, collection: &str) -> bool {
+        self.namespace
+            .as_ref()
+            .is_none_or(|namespace| namespace.contains(collection))
+    }
+}
+
+/// The collection names a JSON request body or tool-argument object
+/// refers to: `collection`, `collection_name` and every string in a
+/// `collections` list, plus `name` when `name_is_collection` (collection
+/// create/delete calls). Names a call creates or writes to count too:
+/// `new_name` (rename), `destination` (vector move and copy) and the
+/// collections and aliases of Qdrant alias `actions`.
+pub fn collections_named_in(body: &Value, name_is_collection: bool) -> Vec<&str> {
+    let keys: &[&str] = if name_is_collection {
+        &[
+            "collection",
+            "collection_name",
+            "name",
+            "new_name",
+            "destination",
+        ]
+    } else {
+        &["collection", "collection_name", "new_name", "destination"]
+    };
+    let mut names: Vec<&str> = keys
+        .iter()
+        .filter_map(|key| body.get(key).and_then(Value::as_str))
+        .collect();
+    if let Some(list) = body.get("collections").and_then(Value::as_array) {
+        names.extend(list.iter().filter_map(Value::as_str));
+    }
+    if let Some(actions) = body.get("actions").and_then(Value::as_array) {
+        const ALIAS_FIELDS: [&str; 4] = [
+            "collection_name",
+            "alias_name",
+            "old_alias_name",
+            "new_alias_name",
+        ];
+        for action in actions.iter().filter_map(Value::as_object) {
+            for operation in action.values() {
+                names.extend(
+                    ALIAS_FIELDS
+                        .iter()
+                        .filter_map(|key| operation.get(key).and_then(Value::as_str)),
+                );
+            }
+        }
+    }
+    names
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn namespace_matches_by_prefix() {
+        let namespace = CollectionNamespace::parse("team-a/*").unwrap();
+        assert_eq!(namespace.prefix(), "team-a/");
+        assert!(namespace.contains("team-a/docs"));
+        assert!(!namespace.contains("team-ab/docs"));
+        assert!(!namespace.contains("team-b/docs"));
+        assert!(matches!(
+            namespace.check("other"),
+            Err(VectorizerError::AuthorizationError(_))
+        ));
+
+        assert!(namespace.covers(&CollectionNamespace::parse("team-a/web/*").unwrap()));
+        assert!(!namespace.covers(&CollectionNamespace::parse("team-*").unwrap()));
+    }
+
+    #[test]
+    fn malformed_patterns_are_rejected() {
+        assert!(CollectionNamespace::parse("team-a/").is_err());
+        assert!(CollectionNamespace::parse("*").is_err());
+        assert!(CollectionNamespace::parse("team-*/docs*").is_err());
+        assert!(serde_json::from_value::<CollectionNamespace>(json!("team-a")).is_err());
+        let parsed: CollectionNamespace = serde_json::from_value(json!("team-a/*")).unwrap();
+        assert_eq!(serde_json::to_value(&parsed).unwrap(), json!("team-a/*"));
+    }
+
+    #[test]
+    fn collections_are_read_from_bodies() {
+        let body = json!({
+            "collection": "a",
+            "collections": ["b", "c", 1],
+            "name": "d"
+        });
+        assert_eq!(collections_named_in(&body, false), ["a", "b", "c"]);
+        assert_eq!(collections_named_in(&body, true), ["a", "d", "b", "c"]);
+    }
+
+    #[test]
+    fn destinations_and_aliases_are_read_from_bodies() {
+        let rename = json!({"new_name": "b"});
+        assert_eq!(collections_named_in(&rename, false), ["b"]);
+        let moved = json!({"destination": "b", "ids": ["1"]});
+        assert_eq!(collections_named_in(&moved, false), ["b"]);
+
+        let aliases = json!({"actions": [
+            {"create_alias": {"collection_name": "a", "alias_name": "b"}},
+            {"delete_alias": {"alias_name": "c"}},
+            {"rename_alias": {"old_alias_name": "d", "new_alias_name": "e"}}
+        ]});
+        assert_eq!(
+            collections_named_in(&aliases, false),
+            ["a", "b", "c", "d", "e"]
+        );
+    }
+}
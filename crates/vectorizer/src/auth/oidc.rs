@@ -0,0 +1,378 @@
+//! External OpenID Connect identity provider.
+//!
+//! With `auth.oidc` configured, RS256 access tokens issued by the
+//! provider are accepted wherever a Vectorizer JWT is. Tokens are checked
+//! against the provider's JSON Web Key Set (signature, `iss`, `aud` and
+//! `exp`), and the provider's role or group claim is mapped onto
+//! Vectorizer [`Role`]s. Provider users get the ID `oidc:<issuer>:<sub>`,
+//! so a provider subject can never collide with a local user ID. Locally
+//! issued HS256 tokens keep working alongside; the two are told apart by
+//! the token's `alg`.
+//!
+//! The key set is cached. [`OidcValidator::spawn_refresh`] fetches it at
+//! boot and every `jwks_refresh_secs`, and early (at most once a minute)
+//! when a token names a key ID the cache does not know, so provider key
+//! rotation needs no restart.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{Algorithm, DecodingKey, Validation, decode, decode_header};
+use serde_json::{Value, json};
+use tokio::sync::Notify;
+use tracing::{debug, info, warn};
+
+use super::{OidcConfig, Role, UserClaims};
+use crate::error::{Result, VectorizerError};
+
+/// Shortest gap between JWKS fetches triggered by unknown key IDs.
+const MIN_EARLY_REFRESH: Duration = Duration::from_secs(60);
+
+/// Clock skew tolerated on `exp` / `nbf`, in seconds.
+const LEEWAY_SECS: u64 = 60;
+
+/// Timeout of one JWKS fetch.
+const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Validates access tokens issued by the configured OIDC provider.
+#[derive(Debug)]
+pub struct OidcValidator {
+    /// Provider settings from `auth.oidc`
+    config: OidcConfig,
+    /// `role_mapping` with its values parsed
+    role_mapping: HashMap<String, Role>,
+    /// `default_role` parsed
+    default_role: Option<Role>,
+    /// HTTP client for JWKS fetches
+    client: reqwest::Client,
+    /// Cached provider signing keys
+    keys: parking_lot::RwLock<JwkSet>,
+    /// When the key set was last fetched (successfully or not)
+    last_fetch: parking_lot::Mutex<Option<Instant>>,
+    /// Wakes the refresh task early when a token names an unknown key
+    refresh_wanted: Notify,
+}
+
+impl OidcValidator {
+    /// Build a validator from `auth.oidc`. Fails on an incomplete config
+    /// or a role mapping naming an unknown Vectorizer role. Fetches
+    /// nothing; see [`Self::spawn_refresh`].
+    pub fn new(config: OidcConfig) -> Result<Self> {
+        let invalid = |message: String| VectorizerError::InvalidConfiguration { message };
+        config.validate().map_err(invalid)?;
+        let parse_role = |name: &str| {
+            Role::from_name(name).ok_or_else(|| {
+                invalid(format!(
+                    "auth.oidc: unknown role '{}' (expected admin, user, readonly, apiuser or service)",
+                    name
+                ))
+            })
+        };
+        let role_mapping = config
+            .role_mapping
+            .iter()
+            .map(|(provider, role)| Ok((provider.clone(), parse_role(role)?)))
+            .collect::<Result<HashMap<_, _>>>()?;
+        let default_role = config.default_role.as_deref().map(parse_role).transpose()?;
+        let client = reqwest::Client::builder()
+            .timeout(FETCH_TIMEOUT)
+            .build()
+            .map_err(|e| VectorizerError::InternalError(format!("HTTP client: {}", e)))?;
+
+        Ok(Self {
+            config,
+            role_mapping,
+            default_role,
+            client,
+            keys: parking_lot::RwLock::new(JwkSet { keys: Vec::new() }),
+            last_fetch: parking_lot::Mutex::new(None),
+            refresh_wanted: Notify::new(),
+        })
+    }
+
+    /// The provider settings.
+    pub fn config(&self) -> &OidcConfig {
+        &self.config
+    }
+
+    /// Fetch the provider's key set and replace the cache. Returns the
+    /// number of keys fetched.
+    pub async fn refresh(&self) -> Result<usize> {
+        *self.last_fetch.lock() = Some(Instant::now());
+        let fetch_error = |e: reqwest::Error| {
+            VectorizerError::InternalError(format!(
+                "JWKS fetch from {} failed: {}",
+                self.config.jwks_url, e
+            ))
+        };
+        let keys: JwkSet = self
+            .client
+            .get(&self.config.jwks_url)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(fetch_error)?
+            .json()
+            .await
+            .map_err(fetch_error)?;
+        let count = keys.keys.len();
+        self.set_keys(keys);
+        Ok(count)
+    }
+
+    /// Replace the cached key set, e.g. with keys loaded from elsewhere.
+    pub fn set_keys(&self, keys: JwkSet) {
+        *self.keys.write() = keys;
+    }
+
+    /// Keep the key set fresh in the background: fetch now, then every
+    /// `jwks_refresh_secs` or when a token names an unknown key.
+    pub fn spawn_refresh(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let interval = Duration::from_secs(self.config.jwks_refresh_secs);
+            loop {
+                match self.refresh().await {
+                    Ok(count) => debug!("OIDC: fetched {} signing keys", count),
+                    Err(e) => warn!("OIDC: {}", e),
+                }
+                tokio::select! {
+                    _ = tokio::time::sleep(interval) => {}
+                    _ = self.refresh_wanted.notified() => {
+                        info!("OIDC: token names an unknown signing key, refreshing JWKS");
+                    }
+                }
+            }
+        })
+    }
+
+    /// Whether `token` is signed the way provider tokens are (RS256),
+    /// as opposed to a locally issued HS256 token.
+    pub fn is_provider_token(token: &str) -> bool {
+        decode_header(token).is_ok_and(|header| header.alg == Algorithm::RS256)
+    }
+
+    /// Verify a provider token and map it to Vectorizer claims.
+    pub fn validate(&self, token: &str) -> Result<UserClaims> {
+        let auth_error = |message: &str| VectorizerError::AuthenticationError(message.to_string());
+        let header = decode_header(token).map_err(|_| auth_error("Invalid token"))?;
+        if header.alg != Algorithm::RS256 {
+            return Err(auth_error("Unsupported token algorithm"));
+        }
+        let kid = header
+            .kid
+            .ok_or_else(|| auth_error("Token has no key ID"))?;
+        let Some(jwk) = self.keys.read().find(&kid).cloned() else {
+            self.request_early_refresh();
+            return Err(auth_error("Token signed by an unknown key"));
+        };
+        let key = DecodingKey::from_jwk(&jwk).map_err(|_| auth_error("Unusable signing key"))?;
+
+        let mut validation = Validation::new(Algorithm::RS256);
+        validation.set_issuer(&[&self.config.issuer]);
+        validation.set_audience(&[&self.config.audience]);
+        validation.leeway = LEEWAY_SECS;
+
+        let data = decode::<Value>(token, &key, &validation).map_err(|e| match e.kind() {
+            jsonwebtoken::errors::ErrorKind::ExpiredSignature => auth_error("Token has expired"),
+            _ => VectorizerError::AuthenticationError(format!("Token validation failed: {}", e)),
+        })?;
+        self.map_claims(&data.claims)
+    }
+
+    /// Map verified provider claims to Vectorizer claims: `sub`, prefixed
+    /// with `oidc:<issuer>:`, becomes the user ID, `username_claim` the
+    /// username (`sub` when absent) and the roles named by
+    /// `roles_claim` go through `role_mapping`, with `default_role` when
+    /// none match.
+    pub fn map_claims(&self, claims: &Value) -> Result<UserClaims> {
+        let subject = claims
+            .get("sub")
+            .and_then(Value::as_str)
+            .ok_or_else(|| VectorizerError::AuthenticationError("Token has no subject".into()))?;
+        let username = claim(claims, &self.config.username_claim)
+            .and_then(Value::as_str)
+            .unwrap_or(subject);
+
+        let provider_roles: Vec<&str> = match claim(claims, &self.config.roles_claim) {
+            Some(Value::Array(items)) => items.iter().filter_map(Value::as_str).collect(),
+            Some(Value::String(list)) => list.split_whitespace().collect(),
+            _ => Vec::new(),
+        };
+        let mut roles: Vec<Role> = Vec::new();
+        for role in provider_roles
+            .iter()
+            .filter_map(|r| self.role_mapping.get(*r))
+        {
+            if !roles.contains(role) {
+                roles.push(role.clone());
+            }
+        }
+        if roles.is_empty() {
+            let default_role = self.default_role.clone().ok_or_else(|| {
+                VectorizerError::AuthorizationError(format!(
+                    "none of the roles in claim '{}' map to a Vectorizer role",
+                    self.config.roles_claim
+                ))
+            })?;
+            roles.push(default_role);
+        }
+
+        Ok(UserClaims {
+            user_id: format!("oidc:{}:{}", self.config.issuer, subject),
+            username: username.to_string(),
+            roles,
+            iat: claims.get("iat").and_then(Value::as_u64).unwrap_or(0),
+            exp: claims.get("exp").and_then(Value::as_u64).unwrap_or(0),
+            scopes: vec![],
+            namespace: None,
+        })
+    }
+
+    /// OAuth 2.0 Protected Resource Metadata (RFC 9728) for this server,
+    /// plus the dashboard's sign-in settings.
+    pub fn protected_resource_metadata(&self, resource: &str) -> Value {
+        json!({
+            "resource": resource,
+            "authorization_servers": [self.config.issuer],
+            "bearer_methods_supported": ["header"],
+            "scopes_supported": self.config.scopes,
+            "dashboard_client_id": self.config.client_id,
+            "local_login": self.config.local_login,
+        })
+    }
+
+    /// Wake the refresh task unless the key set was fetched recently.
+    fn request_early_refresh(&self) {
+        let last_fetch = *self.last_fetch.lock();
+        if last_fetch.is_none_or(|at| at.elapsed() >= MIN_EARLY_REFRESH) {
+            self.refresh_wanted.notify_one();
+        }
+    }
+}
+
+/// A claim by exact name (provider claims are often URLs containing
+/// dots), else by dotted path into nested objects.
+fn claim<'a>(claims: &'a Value, name: &str) -> Option<&'a Value> {
+    claims.get(name).or_else(|| {
+        name.split('.')
+            .try_fold(claims, |value, key| value.get(key))
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use jsonwebtoken::{EncodingKey, Header, encode};
+
+    use super::*;
+
+    /// Test-only RSA key pair; the public half is `TEST_KEY_N` / `AQAB`.
+    const TEST_KEY_PEM: &str = include_str!("../../tests/fixtures/oidc_rs256_test_only.pem");
+    const TEST_KEY_N: &str = "pV_Q5fXTfJATt_0B4M1DsrtUFZyH0I61dvOvEsTe44EyMBrECUK2AOHS9HkJ6kQmWMqAVFCIZ-zZzfc_JJkibLZ1-G7q_H2cKJSug5z4mg4i5q_Dd3O5rKsmpms-1Azq8ePwgMMFNG9MeW9gbbAFOjYNIholDz_4no5s8ypjUMp9u2LsClqoeWoypBAH5WaU2MiDNCDfWHSn_8L34u67GjXX3Tm97WaO8G0FaK_qKXmo1aD5cO4gjIWi9MQaGygfwtR6X-Cl3XUW8fc_mc8nTcvOJDuW0SBN4AKeu8rYrJcPuTVbaWcrB3SSw5W8OJCckO9bQjg0N4h3avbnHWnoXQ";
+
+    fn config() -> OidcConfig {
+        serde_json::from_value(json!({
+            "issuer": "https://idp.example.com",
+            "jwks_url": "https://idp.example.com/jwks",
+            "audience": "vectorizer",
+            "roles_claim": "realm_access.roles",
+            "role_mapping": {"vectorizer-admins": "admin", "analysts": "read_only"}
+        }))
+        .unwrap()
+    }
+
+    fn validator() -> OidcValidator {
+        let validator = OidcValidator::new(config()).unwrap();
+        validator.set_keys(
+            serde_json::from_value(json!({"keys": [{
+                "kty": "RSA", "kid": "k1", "alg": "RS256", "use": "sig",
+                "n": TEST_KEY_N, "e": "AQAB"
+            }]}))
+            .unwrap(),
+        );
+        validator
+    }
+
+    fn token(kid: &str, claims: Value) -> String {
+        let mut header = Header::new(Algorithm::RS256);
+        header.kid = Some(kid.to_string());
+        let key = EncodingKey::from_rsa_pem(TEST_KEY_PEM.as_bytes()).unwrap();
+        encode(&header, &claims, &key).unwrap()
+    }
+
+    fn claims(overrides: Value) -> Value {
+        let now = chrono::Utc::now().timestamp();
+        let mut claims = json!({
+            "iss": "https://idp.example.com",
+            "aud": "vectorizer",
+            "sub": "u-42",
+            "preferred_username": "ada",
+            "iat": now,
+            "exp": now + 300,
+            "realm_access": {"roles": ["analysts", "vectorizer-admins", "other"]}
+        });
+        for (key, value) in overrides.as_object().unwrap() {
+            claims[key] = value.clone();
+        }
+        claims
+    }
+
+    #[test]
+    fn provider_tokens_map_to_vectorizer_claims() {
+        let validator = validator();
+        let token = token("k1", claims(json!({})));
+        assert!(OidcValidator::is_provider_token(&token));
+
+        let user = validator.validate(&token).unwrap();
+        assert_eq!(user.user_id, "oidc:https://idp.example.com:u-42");
+        assert_eq!(user.username, "ada");
+        assert_eq!(user.roles, [Role::ReadOnly, Role::Admin]);
+    }
+
+    #[test]
+    fn foreign_or_unmapped_tokens_are_refused() {
+        let validator = validator();
+        for overrides in [
+            json!({"iss": "https://evil.example.com"}),
+            json!({"aud": "someone-else"}),
+            json!({"exp": 1_000}),
+            json!({"realm_access": {"roles": ["other"]}}),
+        ] {
+            let token = token("k1", claims(overrides.clone()));
+            assert!(validator.validate(&token).is_err(), "{overrides} accepted");
+        }
+        assert!(validator.validate(&token("k2", claims(json!({})))).is_err());
+
+        let mut config = config();
+        config.default_role = Some("user".to_string());
+        let mapped = OidcValidator::new(config)
+            .unwrap()
+            .map_claims(&claims(json!({"realm_access": {"roles": "other"}})))
+            .unwrap();
+        assert_eq!(mapped.roles, [Role::User]);
+    }
+
+    #[test]
+    fn invalid_configs_are_rejected() {
+        let mut bad_role = config();
+        bad_role
+            .role_mapping
+            .insert("x".to_string(), "superuser".to_string());
+        assert!(OidcValidator::new(bad_role).is_err());
+
+        let mut bad_url = config();
+        bad_url.jwks_url = "idp.example.com/jwks".to_string();
+        assert!(OidcValidator::new(bad_url).is_err());
+
+        let mut no_audience = config();
+        no_audience.audience = String::new();
+        assert!(OidcValidator::new(no_audience).is_err());
+        let missing: std::result::Result<OidcConfig, _> = serde_json::from_value(json!({
+            "issuer": "https://idp.example.com",
+            "jwks_url": "https://idp.example.com/jwks"
+        }));
+        assert!(missing.is_err());
+    }
+}
@@ -64,6 +64,10 @@ pub struct PersistedApiKey {
     /// without this field deserialize with `usage_count: 0`.
     #[serde(default)]
     pub usage_count: u64,
+    /// Collection namespace the key is confined to. Absent in payloads
+    /// written before namespaces existed.
+    #[serde(default)]
+    pub namespace: Option<crate::auth::CollectionNamespace>,
 }
 
 /// Auth data store structure
@@ -448,6 +452,7 @@ mod tests {
             expires_at: None,
             active: true,
             usage_count: 0,
+            namespace: None,
         };
 
         persistence.save_api_key(key).unwrap();
@@ -0,0 +1,369 @@
+//! HMAC-SHA256 request signing for the HTTP API.
+//!
+//! A signed request carries three headers:
+//!
+//! - `X-Vectorizer-Key-Id`: which configured secret signed the request
+//! - `X-Vectorizer-Timestamp`: Unix seconds at signing time
+//! - `X-Vectorizer-Signature`: hex HMAC-SHA256 of the canonical string
+//!
+//! The canonical string is
+//!
+//! ```text
+//! METHOD\n
+//! PATH_AND_QUERY\n
+//! TIMESTAMP\n
+//! hex(SHA256(body))
+//! ```
+//!
+//! The body hash is always present (the hash of an empty body for `GET`),
+//! so a signature cannot be replayed against the same path with a different
+//! payload. Replays of an identical request are bounded by the timestamp
+//! tolerance. On a clock-skew rejection the server answers with
+//! `X-Vectorizer-Server-Time` so clients can correct their offset and retry;
+//! the Rust SDK does this automatically.
+//!
+//! This is distinct from [`crate::hub::request_signing`], which validates
+//! HiveHub-originated traffic with its own header set and nonce cache.
+
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use hmac::{Hmac, KeyInit, Mac};
+use sha2::{Digest, Sha256};
+
+use crate::config::sections::auth::RequestSigningConfig;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Header naming the signing key.
+pub const HEADER_KEY_ID: &str = "x-vectorizer-key-id";
+/// Header carrying the signing timestamp (Unix seconds).
+pub const HEADER_TIMESTAMP: &str = "x-vectorizer-timestamp";
+/// Header carrying the hex HMAC-SHA256 signature.
+pub const HEADER_SIGNATURE: &str = "x-vectorizer-signature";
+/// Response header with the server's clock, sent on signature rejections.
+pub const HEADER_SERVER_TIME: &str = "x-vectorizer-server-time";
+
+/// Why a request failed signature verification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignatureError {
+    /// A required header was absent or not valid UTF-8.
+    MissingHeader(&'static str),
+    /// The timestamp header did not parse as Unix seconds.
+    InvalidTimestamp,
+    /// The key ID is not configured.
+    UnknownKey(String),
+    /// The timestamp is outside the tolerated window.
+    ClockSkew {
+        /// Server clock at verification time (Unix seconds).
+        server_time: u64,
+        /// Timestamp carried by the request.
+        request_time: u64,
+    },
+    /// The signature does not match the canonical request.
+    BadSignature,
+}
+
+impl SignatureError {
+    /// Machine-readable error type used in the REST error body.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::MissingHeader(_) | Self::InvalidTimestamp => "signature_missing",
+            Self::UnknownKey(_) => "signature_unknown_key",
+            Self::ClockSkew { .. } => "signature_clock_skew",
+            Self::BadSignature => "signature_invalid",
+        }
+    }
+}
+
+impl std::fmt::Display for SignatureError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingHeader(h) => write!(f, "missing request signing header '{}'", h),
+            Self::InvalidTimestamp => write!(f, "'{}' must be Unix seconds", HEADER_TIMESTAMP),
+            Self::UnknownKey(id) => write!(f, "unknown signing key id '{}'", id),
+            Self::ClockSkew {
+                server_time,
+                request_time,
+            } => write!(
+                f,
+                "request timestamp {} is outside the tolerated window (server time {})",
+                request_time, server_time
+            ),
+            Self::BadSignature => write!(f, "request signature does not match"),
+        }
+    }
+}
+
+impl std::error::Error for SignatureError {}
+
+/// Build the canonical string that gets signed.
+pub fn canonical_string(method: &str, path_and_query: &str, timestamp: u64, body: &[u8]) -> String {
+    format!(
+        "{}\n{}\n{}\n{}",
+        method.to_uppercase(),
+        path_and_query,
+        timestamp,
+        hex::encode(Sha256::digest(body))
+    )
+}
+
+/// Hex HMAC-SHA256 of `canonical` under `secret`.
+///
+/// SAFE: HMAC accepts keys of any size (RFC 2104), so `new_from_slice`
+/// cannot fail.
+#[allow(clippy::expect_used)]
+pub fn sign(secret: &[u8], canonical: &str) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret).expect("HMAC can take key of any size");
+    mac.update(canonical.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Current Unix time in seconds; 0 if the clock predates the epoch.
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Raw signature header values pulled from a request.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SignatureHeaders<'a> {
+    /// `X-Vectorizer-Key-Id`
+    pub key_id: Option<&'a str>,
+    /// `X-Vectorizer-Timestamp`
+    pub timestamp: Option<&'a str>,
+    /// `X-Vectorizer-Signature`
+    pub signature: Option<&'a str>,
+}
+
+/// Server-side verifier built from [`RequestSigningConfig`].
+#[derive(Debug)]
+pub struct RequestVerifier {
+    keys: HashMap<String, Vec<u8>>,
+    max_clock_skew_secs: u64,
+    exempt_paths: Vec<String>,
+}
+
+impl RequestVerifier {
+    /// Build a verifier, or `None` when signing is disabled.
+    ///
+    /// An enabled config with no keys would reject every request, so it is
+    /// treated as a configuration error.
+    pub fn from_config(config: &RequestSigningConfig) -> crate::error::Result<Option<Self>> {
+        if !config.enabled {
+            return Ok(None);
+        }
+        if config.keys.is_empty() {
+            return Err(crate::error::VectorizerError::InvalidConfiguration {
+                message: "api.request_signing.enabled is true but no keys are configured"
+                    .to_string(),
+            });
+        }
+        let keys = config
+            .keys
+            .iter()
+            .map(|(id, secret)| (id.clone(), secret.expose_secret().as_bytes().to_vec()))
+            .collect();
+        Ok(Some(Self {
+            keys,
+            max_clock_skew_secs: config.max_clock_skew_secs,
+            exempt_paths: config.exempt_paths.clone(),
+        }))
+    }
+
+    /// Whether `path` bypasses signature checks. An exempt prefix matches
+    /// itself and anything below it on a segment boundary, so `/health`
+    /// covers `/health/ready` but not `/healthz-admin`.
+    pub fn is_exempt(&self, path: &str) -> bool {
+        self.exempt_paths
+            .iter()
+            .any(|p| path == p || path.starts_with(&format!("{p}/")))
+    }
+
+    /// Verify one request against the server clock `now` (Unix seconds).
+    pub fn verify(
+        &self,
+        method: &str,
+        path_and_query: &str,
+        headers: SignatureHeaders<'_>,
+        body: &[u8],
+        now: u64,
+    ) -> Result<(), SignatureError> {
+        let key_id = headers
+            .key_id
+            .ok_or(SignatureError::MissingHeader(HEADER_KEY_ID))?;
+        let timestamp = headers
+            .timestamp
+            .ok_or(SignatureError::MissingHeader(HEADER_TIMESTAMP))?;
+        let signature = headers
+            .signature
+            .ok_or(SignatureError::MissingHeader(HEADER_SIGNATURE))?;
+
+        let request_time: u64 = timestamp
+            .trim()
+            .parse()
+            .map_err(|_| SignatureError::InvalidTimestamp)?;
+        if now.abs_diff(request_time) > self.max_clock_skew_secs {
+            return Err(SignatureError::ClockSkew {
+                server_time: now,
+                request_time,
+            });
+        }
+
+        let secret = self
+            .keys
+            .get(key_id)
+            .ok_or_else(|| SignatureError::UnknownKey(key_id.to_string()))?;
+
+        let expected = sign(
+            secret,
+            &canonical_string(method, path_and_query, request_time, body),
+        );
+        if constant_time_eq(expected.as_bytes(), signature.trim().as_bytes()) {
+            Ok(())
+        } else {
+            Err(SignatureError::BadSignature)
+        }
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::config::secret::Secret;
+
+    fn verifier() -> RequestVerifier {
+        let config = RequestSigningConfig {
+            enabled: true,
+            keys: HashMap::from([("ci".to_string(), Secret::new("s3cret".to_string()))]),
+            ..Default::default()
+        };
+        RequestVerifier::from_config(&config).unwrap().unwrap()
+    }
+
+    fn headers<'a>(key_id: &'a str, ts: &'a str, sig: &'a str) -> SignatureHeaders<'a> {
+        SignatureHeaders {
+            key_id: Some(key_id),
+            timestamp: Some(ts),
+            signature: Some(sig),
+        }
+    }
+
+    fn signed(method: &str, path: &str, ts: u64, body: &[u8]) -> String {
+        sign(b"s3cret", &canonical_string(method, path, ts, body))
+    }
+
+    #[test]
+    fn test_canonical_string_hashes_empty_body() {
+        assert_eq!(
+            canonical_string("get", "/collections?limit=5", 7, b""),
+            "GET\n/collections?limit=5\n7\n\
+             e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn test_verify_accepts_valid_signature() {
+        let v = verifier();
+        let sig = signed("POST", "/collections", 1_000, b"{}");
+        assert_eq!(
+            v.verify(
+                "POST",
+                "/collections",
+                headers("ci", "1000", &sig),
+                b"{}",
+                1_010
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_sign_matches_sdk_reference_vector() {
+        // Shared with `sdks/rust/src/signing.rs` so client and server
+        // cannot drift on the canonical form.
+        let canonical = canonical_string(
+            "POST",
+            "/collections/docs/search?x=1",
+            1_700_000_000,
+            br#"{"limit":5}"#,
+        );
+        assert_eq!(
+            sign(b"top-secret", &canonical),
+            "d0828492f15a8510bf49a5354df996a93c1a6e6fb03fcc86a767b54a94087ac0"
+        );
+    }
+
+    #[test]
+    fn test_verify_rejects_tampered_body_and_unknown_key() {
+        let v = verifier();
+        let sig = signed("POST", "/collections", 1_000, b"{}");
+        let tampered = v.verify(
+            "POST",
+            "/collections",
+            headers("ci", "1000", &sig),
+            b"{\"x\":1}",
+            1_000,
+        );
+        assert_eq!(tampered, Err(SignatureError::BadSignature));
+
+        let unknown = v.verify(
+            "POST",
+            "/collections",
+            headers("other", "1000", &sig),
+            b"{}",
+            1_000,
+        );
+        assert_eq!(unknown.unwrap_err().code(), "signature_unknown_key");
+    }
+
+    #[test]
+    fn test_verify_reports_clock_skew_with_server_time() {
+        let v = verifier();
+        let sig = signed("GET", "/health", 1_000, b"");
+        let err = v
+            .verify("GET", "/health", headers("ci", "1000", &sig), b"", 2_000)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            SignatureError::ClockSkew {
+                server_time: 2_000,
+                request_time: 1_000
+            }
+        );
+    }
+
+    #[test]
+    fn test_exempt_paths_match_on_segment_boundary() {
+        let v = verifier();
+        assert!(v.is_exempt("/health"));
+        assert!(v.is_exempt("/dashboard/assets/app.js"));
+        assert!(!v.is_exempt("/healthz-admin"));
+        assert!(!v.is_exempt("/dashboard-export"));
+        assert!(!v.is_exempt("/collections"));
+    }
+
+    #[test]
+    fn test_from_config_requires_keys_when_enabled() {
+        assert!(
+            RequestVerifier::from_config(&RequestSigningConfig::default())
+                .unwrap()
+                .is_none()
+        );
+        let config = RequestSigningConfig {
+            enabled: true,
+            ..Default::default()
+        };
+        assert!(RequestVerifier::from_config(&config).is_err());
+    }
+}
@@ -71,6 +71,20 @@ impl Role {
     pub fn can_manage_role(&self, other_role: &Role) -> bool {
         self.hierarchy_level() > other_role.hierarchy_level()
     }
+
+    /// Parse a role name as written in requests and config: `admin`,
+    /// `user`, `readonly`, `apiuser` or `service`, case-insensitive and
+    /// ignoring underscores (`read_only`, `api_user`).
+    pub fn from_name(name: &str) -> Option<Role> {
+        match name.to_lowercase().replace('_', "").as_str() {
+            "admin" => Some(Role::Admin),
+            "user" => Some(Role::User),
+            "readonly" => Some(Role::ReadOnly),
+            "apiuser" => Some(Role::ApiUser),
+            "service" => Some(Role::Service),
+            _ => None,
+        }
+    }
 }
 
 /// System permissions
@@ -79,6 +79,25 @@ async fn test_rate_limiting() {
     ));
 }
 
+#[tokio::test]
+async fn test_rate_limits_change_at_runtime() {
+    let mut config = test_config();
+    config.rate_limit_per_minute = 1;
+
+    let auth_manager = AuthManager::new(config).unwrap();
+    let (api_key, _) = auth_manager
+        .create_api_key("user123", "test_key", vec![Permission::Read], None)
+        .await
+        .unwrap();
+
+    auth_manager.validate_api_key(&api_key).await.unwrap();
+    assert!(auth_manager.validate_api_key(&api_key).await.is_err());
+
+    auth_manager.set_rate_limits(3, 1000);
+    assert_eq!(auth_manager.rate_limits(), (3, 1000));
+    auth_manager.validate_api_key(&api_key).await.unwrap();
+}
+
 #[test]
 fn validate_rejects_empty_secret() {
     let config = AuthConfig::default(); // empty secret
@@ -0,0 +1,326 @@
+//! Registry of the server's long-running background tasks.
+//!
+//! Periodic loops (auto-save, snapshots, HNSW compaction, replication
+//! sync) and event-driven workers (the file watcher, collection
+//! reindexes) register here under a fixed name and report every run, so
+//! operators can see what each one is doing without grepping logs. The
+//! server lists the registry at `GET /admin/tasks`.
+//!
+//! Pausing is cooperative: a paused task finishes the run it is in, then
+//! checks [`BackgroundTask::is_paused`] before the next one.
+//!
+//! - [`AUTO_SAVE`], [`SNAPSHOT`] and [`COMPACTION`] skip their rounds;
+//!   pending changes are saved on the first round after resuming.
+//! - [`FILE_WATCHER`] holds file-change events and processes them on
+//!   resume.
+//! - [`REINDEX`] refuses new reindex requests.
+//! - [`REPLICATION_SYNC`] disconnects from the master and reconnects
+//!   from its saved offset on resume.
+//! - [`READ_THROUGH_EVICTION`] keeps idle read-through collections loaded
+//!   until resumed.
+//! - [`COLLECTION_EVICTION`] lets resident collections exceed the lazy
+//!   loading limits until resumed.
+//! - [`SCHEDULED_BACKUP`] skips the backups that fall due while paused;
+//!   manually triggered runs still go through.
+//! - [`HYBRID_TUNING`] refuses new tuning requests.
+//! - [`QUERY_ANALYTICS_FLUSH`] skips its saves; queries recorded
+//!   meanwhile are saved on the first round after resuming.
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+
+/// Periodic save of the in-memory store to `vectorizer.vecdb`.
+pub const AUTO_SAVE: &str = "auto_save";
+/// Periodic snapshot of the data directory, plus snapshot retention.
+pub const SNAPSHOT: &str = "snapshot";
+/// HNSW compaction sweep ([`IndexOptimizer`](crate::db::IndexOptimizer)).
+pub const COMPACTION: &str = "compaction";
+/// Re-indexing of watched workspace files as they change.
+pub const FILE_WATCHER: &str = "file_watcher";
+/// HNSW rebuilds requested through `reindex_collection`.
+pub const REINDEX: &str = "reindex";
+/// Replica-side stream of full/partial syncs from the master.
+pub const REPLICATION_SYNC: &str = "replication_sync";
+/// Unloading of idle read-through collections
+/// ([`ReadThrough`](crate::db::read_through::ReadThrough)).
+pub const READ_THROUGH_EVICTION: &str = "read_through_eviction";
+/// LRU eviction of lazily loaded collections
+/// ([`Residency`](crate::db::residency::Residency)).
+pub const COLLECTION_EVICTION: &str = "collection_eviction";
+/// Cron-scheduled backups with retention
+/// ([`BackupScheduler`](crate::db::BackupScheduler)).
+pub const SCHEDULED_BACKUP: &str = "scheduled_backup";
+/// Hybrid search weight fits requested through the tuning endpoint
+/// ([`tune_hybrid_weights`](crate::evaluation::hybrid_tuning::tune_hybrid_weights)).
+pub const HYBRID_TUNING: &str = "hybrid_tuning";
+/// Periodic save of the query analytics ring
+/// ([`QueryAnalytics`](crate::monitoring::QueryAnalytics)).
+pub const QUERY_ANALYTICS_FLUSH: &str = "query_analytics_flush";
+
+/// What the task called `name` does, for the listing.
+fn description(name: &str) -> &'static str {
+    match name {
+        AUTO_SAVE => "Saves changed collections to vectorizer.vecdb",
+        SNAPSHOT => "Snapshots the data directory and prunes old snapshots",
+        COMPACTION => "Rebuilds fragmented HNSW graphs",
+        FILE_WATCHER => "Re-indexes watched workspace files as they change",
+        REINDEX => "Rebuilds collection HNSW indexes on request",
+        REPLICATION_SYNC => "Streams full and partial syncs from the master",
+        READ_THROUGH_EVICTION => "Unloads read-through collections that have gone idle",
+        COLLECTION_EVICTION => "Evicts least recently used collections back to vectorizer.vecdb",
+        SCHEDULED_BACKUP => "Takes cron-scheduled backups and prunes them by retention policy",
+        HYBRID_TUNING => "Fits hybrid search weights to relevance judgements on request",
+        QUERY_ANALYTICS_FLUSH => "Saves recorded query statistics to query_analytics.json",
+        _ => "",
+    }
+}
+
+/// Process-wide task registry.
+pub static TASKS: Lazy<TaskRegistry> = Lazy::new(TaskRegistry::default);
+
+/// What a task is doing right now.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskState {
+    /// Waiting for its next run.
+    Idle,
+    /// A run is in progress.
+    Running,
+    /// Paused by an operator; no new runs start until resumed.
+    Paused,
+    /// Waiting for its next run after the last one failed.
+    Failed,
+    /// The task's loop has exited.
+    Stopped,
+}
+
+/// Snapshot of a task, as listed by `GET /admin/tasks`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskStatus {
+    /// Registry name, e.g. `auto_save`.
+    pub name: String,
+    /// What the task does.
+    pub description: String,
+    /// Current state.
+    pub state: TaskState,
+    /// Paused by an operator. A paused task may still be `running` the
+    /// run it was in when paused.
+    pub paused: bool,
+    /// Runs finished since the process started.
+    pub runs: u64,
+    /// Runs that finished with an error.
+    pub failures: u64,
+    /// When the most recent run started.
+    pub last_run_at: Option<DateTime<Utc>>,
+    /// Wall-clock duration of the most recent finished run.
+    pub last_duration_ms: Option<u64>,
+    /// Error of the most recent failed run. Kept after later successes;
+    /// `state` tells whether the latest run failed.
+    pub last_error: Option<String>,
+    /// When `last_error` happened.
+    pub last_error_at: Option<DateTime<Utc>>,
+    /// When the next run is due, for scheduled tasks.
+    pub next_run_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug)]
+struct Progress {
+    status: TaskStatus,
+    /// Runs in progress; event-driven tasks may overlap.
+    active: usize,
+    started: Option<Instant>,
+    last_failed: bool,
+    stopped: bool,
+}
+
+/// A registered task. Loops keep the `Arc` and report through it.
+#[derive(Debug)]
+pub struct BackgroundTask {
+    paused: AtomicBool,
+    progress: Mutex<Progress>,
+}
+
+impl BackgroundTask {
+    fn new(name: &str) -> Self {
+        Self {
+            paused: AtomicBool::new(false),
+            progress: Mutex::new(Progress {
+                status: TaskStatus {
+                    name: name.to_string(),
+                    description: description(name).to_string(),
+                    state: TaskState::Idle,
+                    paused: false,
+                    runs: 0,
+                    failures: 0,
+                    last_run_at: None,
+                    last_duration_ms: None,
+                    last_error: None,
+                    last_error_at: None,
+                    next_run_at: None,
+                },
+                active: 0,
+                started: None,
+                last_failed: false,
+                stopped: false,
+            }),
+        }
+    }
+
+    /// Whether an operator has paused the task.
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    /// Wait until the task is not paused, checking once a second.
+    pub async fn resumed(&self) {
+        while self.is_paused() {
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+    }
+
+    /// Record that a run started.
+    pub fn start(&self) {
+        let mut progress = self.progress.lock();
+        progress.active += 1;
+        progress.started = Some(Instant::now());
+        progress.status.last_run_at = Some(Utc::now());
+        progress.status.next_run_at = None;
+    }
+
+    /// Record that a run finished with `result`.
+    pub fn finish<T, E: Display>(&self, result: &std::result::Result<T, E>) {
+        let mut progress = self.progress.lock();
+        progress.active = progress.active.saturating_sub(1);
+        progress.status.runs += 1;
+        progress.status.last_duration_ms = progress
+            .started
+            .map(|started| started.elapsed().as_millis() as u64);
+        progress.last_failed = result.is_err();
+        if let Err(e) = result {
+            progress.status.failures += 1;
+            progress.status.last_error = Some(e.to_string());
+            progress.status.last_error_at = Some(Utc::now());
+        }
+    }
+
+    /// Record when the next run is due.
+    pub fn schedule(&self, after: Duration) {
+        let next = chrono::Duration::from_std(after)
+            .ok()
+            .and_then(|after| Utc::now().checked_add_signed(after));
+        self.progress.lock().status.next_run_at = next;
+    }
+
+    /// Record that the task's loop exited.
+    pub fn stop(&self) {
+        let mut progress = self.progress.lock();
+        progress.stopped = true;
+        progress.status.next_run_at = None;
+    }
+
+    /// Current status.
+    pub fn status(&self) -> TaskStatus {
+        let progress = self.progress.lock();
+        let paused = self.is_paused();
+        let mut status = progress.status.clone();
+        status.paused = paused;
+        status.state = if progress.active > 0 {
+            TaskState::Running
+        } else if progress.stopped {
+            TaskState::Stopped
+        } else if paused {
+            TaskState::Paused
+        } else if progress.last_failed {
+            TaskState::Failed
+        } else {
+            TaskState::Idle
+        };
+        status
+    }
+}
+
+/// Tasks by name.
+#[derive(Debug, Default)]
+pub struct TaskRegistry {
+    tasks: RwLock<BTreeMap<String, Arc<BackgroundTask>>>,
+}
+
+impl TaskRegistry {
+    /// The task called `name`, registering it on first use. A task whose
+    /// loop is started again (e.g. a restarted file watcher) keeps its
+    /// history and pause flag.
+    pub fn register(&self, name: &str) -> Arc<BackgroundTask> {
+        if let Some(task) = self.tasks.read().get(name) {
+            task.progress.lock().stopped = false;
+            return task.clone();
+        }
+        self.tasks
+            .write()
+            .entry(name.to_string())
+            .or_insert_with(|| Arc::new(BackgroundTask::new(name)))
+            .clone()
+    }
+
+    /// The task called `name`, if registered.
+    pub fn get(&self, name: &str) -> Option<Arc<BackgroundTask>> {
+        self.tasks.read().get(name).cloned()
+    }
+
+    /// Every registered task, by name.
+    pub fn list(&self) -> Vec<TaskStatus> {
+        self.tasks.read().values().map(|t| t.status()).collect()
+    }
+
+    /// Pause or resume the task called `name`. Returns its new status,
+    /// or `None` when no such task is registered.
+    pub fn set_paused(&self, name: &str, paused: bool) -> Option<TaskStatus> {
+        let task = self.get(name)?;
+        task.paused.store(paused, Ordering::Relaxed);
+        Some(task.status())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_follows_runs_and_pauses() {
+        let registry = TaskRegistry::default();
+        let task = registry.register(AUTO_SAVE);
+        assert_eq!(task.status().state, TaskState::Idle);
+
+        task.start();
+        assert_eq!(task.status().state, TaskState::Running);
+        task.finish::<(), _>(&Err("disk full"));
+        let status = task.status();
+        assert_eq!(status.state, TaskState::Failed);
+        assert_eq!((status.runs, status.failures), (1, 1));
+        assert_eq!(status.last_error.as_deref(), Some("disk full"));
+
+        task.start();
+        task.finish::<(), String>(&Ok(()));
+        let status = task.status();
+        assert_eq!(status.state, TaskState::Idle);
+        assert_eq!(status.last_error.as_deref(), Some("disk full"));
+
+        let paused = registry.set_paused(AUTO_SAVE, true).map(|s| s.state);
+        assert_eq!(paused, Some(TaskState::Paused));
+        assert!(task.is_paused());
+        assert!(registry.set_paused("nope", true).is_none());
+
+        task.schedule(Duration::from_secs(60));
+        assert!(task.status().next_run_at.is_some());
+        task.stop();
+        assert_eq!(task.status().state, TaskState::Stopped);
+        assert!(registry.register(AUTO_SAVE).status().paused);
+    }
+}
@@ -531,23 +531,18 @@ impl BatchProcessor {
         collection: &str,
         update: &VectorUpdate,
     ) -> Result<()> {
-        let existing_vector = vector_store.get_vector(collection, &update.id)?;
-
-        let updated_vector = Vector {
-            id: update.id.clone(),
-            data: update.data.clone().unwrap_or(existing_vector.data),
-            sparse: None,
-            payload: update
-                .metadata
-                .clone()
-                .map(|m| Payload {
-                    data: serde_json::to_value(m).unwrap_or_default(),
-                })
-                .or(existing_vector.payload),
-            document_id: existing_vector.document_id,
-        };
-
-        vector_store.update(collection, updated_vector)
+        vector_store.modify_vector(collection, &update.id, |vector| {
+            if let Some(data) = &update.data {
+                vector.data = data.clone();
+            }
+            vector.sparse = None;
+            if let Some(metadata) = &update.metadata {
+                vector.payload = Some(Payload {
+                    data: serde_json::to_value(metadata).unwrap_or_default(),
+                });
+            }
+            Ok(())
+        })
     }
 
     // Helper for single search execution
@@ -1,7 +1,25 @@
 //! Query cache implementation using LRU eviction policy.
-
+//!
+//! Entries are keyed on everything that shapes a search's results: the
+//! collection, the query (text, or a digest of the query vector,
+//! normalized for cosine collections), limit and threshold, and digests
+//! of the payload filter and of any other parameters. The server
+//! registers [`QueryCache::invalidate_collection`] as the vector store's
+//! mutation listener, so every write drops the entries that read the
+//! collection it changed. Hits, misses and removals are counted per
+//! collection ([`QueryCache::collection_stats`]).
+//!
+//! A search that runs across a write would otherwise cache what it read
+//! before the write after the write's invalidation already ran. Each
+//! invalidation therefore advances a generation and records it against
+//! the collection; callers take [`QueryCache::generation`] before they
+//! search and cache with [`QueryCache::insert_at`], and an entry read
+//! from a collection invalidated since its generation is never served.
+
+use std::collections::{BTreeMap, HashMap};
 use std::hash::{Hash, Hasher};
 use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicU64, Ordering};
 
 const QUERY_CACHE_DEFAULT_CAPACITY: NonZeroUsize = match NonZeroUsize::new(1000) {
     Some(n) => n,
@@ -13,10 +31,18 @@ use std::time::{Duration, Instant};
 use lru::LruCache;
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use vectorizer_core::metrics_sink::{MetricsSink, NoopMetricsSink};
 
+use crate::models::DistanceMetric;
+use crate::models::vector_utils::normalize_vector;
+
+/// [`QueryKey::collection`] of a search over every collection.
+pub const ALL_COLLECTIONS: &str = "*";
+
 /// Configuration for query cache
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct QueryCacheConfig {
     /// Maximum number of queries to cache
     pub max_size: usize,
@@ -39,7 +65,8 @@ impl Default for QueryCacheConfig {
 /// Key for caching queries
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct QueryKey {
-    /// Collection name
+    /// Collection name; a comma-separated list for a search over several
+    /// collections, or [`ALL_COLLECTIONS`]
     pub collection: String,
     /// Query text
     pub query: String,
@@ -47,6 +74,10 @@ pub struct QueryKey {
     pub limit: usize,
     /// Similarity threshold
     pub threshold: Option<u32>, // Store as u32 (f64 * 1000) for hashing
+    /// Digest of the payload filter (see [`QueryKey::with_filter`])
+    pub filter: Option<u128>,
+    /// Digest of the other search parameters (see [`QueryKey::with_params`])
+    pub params: Option<u128>,
 }
 
 impl QueryKey {
@@ -57,6 +88,8 @@ impl QueryKey {
             query,
             limit,
             threshold: threshold.map(|t| (t * 1000.0) as u32),
+            filter: None,
+            params: None,
         }
     }
 
@@ -81,14 +114,62 @@ impl QueryKey {
             bytes.extend_from_slice(&v.to_le_bytes());
         }
         let digest = xxhash_rust::xxh3::xxh3_128(&bytes);
-        Self {
+        Self::new(
             collection,
-            query: format!("vector:{digest:032x}"),
+            format!("vector:{digest:032x}"),
             limit,
-            threshold: threshold.map(|t| (t * 1000.0) as u32),
+            threshold,
+        )
+    }
+
+    /// [`QueryKey::from_vector`] for a search under `metric`. A cosine
+    /// search only sees the query's direction, so the vector is
+    /// normalized and rounded to six decimals first: vectors pointing the
+    /// same way share an entry.
+    pub fn from_query_vector(
+        collection: String,
+        vector: &[f32],
+        metric: DistanceMetric,
+        limit: usize,
+        threshold: Option<f64>,
+    ) -> Self {
+        match metric {
+            DistanceMetric::Cosine => {
+                let rounded: Vec<f32> = normalize_vector(vector)
+                    .into_iter()
+                    // `+ 0.0` folds -0.0 into 0.0
+                    .map(|x| (x * 1e6).round() / 1e6 + 0.0)
+                    .collect();
+                Self::from_vector(collection, &rounded, limit, threshold)
+            }
+            DistanceMetric::Euclidean | DistanceMetric::DotProduct => {
+                Self::from_vector(collection, vector, limit, threshold)
+            }
         }
     }
 
+    /// Key the entry on the search's payload filter. Filters that differ
+    /// only in the order of object keys share a digest.
+    pub fn with_filter(mut self, filter: &Value) -> Self {
+        self.filter = Some(json_digest(filter));
+        self
+    }
+
+    /// Key the entry on the other parameters that shape the results
+    /// (hybrid weights, rerank settings, ...), given as one JSON value.
+    pub fn with_params(mut self, params: &Value) -> Self {
+        self.params = Some(json_digest(params));
+        self
+    }
+
+    /// Whether the cached results were read from `collection`, directly
+    /// or as one of several collections searched.
+    pub fn reads(&self, collection: &str) -> bool {
+        self.collection == ALL_COLLECTIONS
+            || self.collection == collection
+            || self.collection.split(',').any(|name| name == collection)
+    }
+
     /// Get threshold as f64
     pub fn threshold_f64(&self) -> Option<f64> {
         self.threshold.map(|t| t as f64 / 1000.0)
@@ -101,23 +182,81 @@ impl Hash for QueryKey {
         self.query.hash(state);
         self.limit.hash(state);
         self.threshold.hash(state);
+        self.filter.hash(state);
+        self.params.hash(state);
+    }
+}
+
+/// xxh3-128 of `value` serialized with object keys sorted.
+fn json_digest(value: &Value) -> u128 {
+    let mut canonical = String::new();
+    write_canonical(value, &mut canonical);
+    xxhash_rust::xxh3::xxh3_128(canonical.as_bytes())
+}
+
+fn write_canonical(value: &Value, out: &mut String) {
+    match value {
+        Value::Object(fields) => {
+            let mut fields: Vec<_> = fields.iter().collect();
+            fields.sort_by(|a, b| a.0.cmp(b.0));
+            out.push('{');
+            for (i, (key, value)) in fields.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push(':');
+                write_canonical(value, out);
+            }
+            out.push('}');
+        }
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        scalar => out.push_str(&scalar.to_string()),
     }
 }
 
+/// Reasons entries leave the cache, as reported to the metrics sink.
+const REMOVAL_EXPIRED: &str = "expired";
+const REMOVAL_EVICTED: &str = "evicted";
+const REMOVAL_INVALIDATED: &str = "invalidated";
+
+/// Counters of one collection's entries
+#[derive(Debug, Clone, Copy, Default)]
+struct CollectionCounters {
+    hits: u64,
+    misses: u64,
+    invalidations: u64,
+    evictions: u64,
+    expirations: u64,
+}
+
 /// Cached entry with TTL
 #[derive(Debug, Clone)]
 struct CachedEntry<T> {
     value: T,
     created_at: Instant,
     ttl: Duration,
+    /// [`QueryCache::generation`] when the search that produced `value`
+    /// started
+    generation: u64,
 }
 
 impl<T> CachedEntry<T> {
-    fn new(value: T, ttl: Duration) -> Self {
+    fn new(value: T, ttl: Duration, generation: u64) -> Self {
         Self {
             value,
             created_at: Instant::now(),
             ttl,
+            generation,
         }
     }
 
@@ -129,10 +268,16 @@ impl<T> CachedEntry<T> {
 /// Thread-safe LRU query cache
 pub struct QueryCache<T: Clone> {
     cache: Arc<RwLock<LruCache<QueryKey, CachedEntry<T>>>>,
-    ttl: Duration,
+    ttl: RwLock<Duration>,
     hits: Arc<parking_lot::Mutex<u64>>,
     misses: Arc<parking_lot::Mutex<u64>>,
     evictions: Arc<parking_lot::Mutex<u64>>,
+    /// Keyed by [`QueryKey::collection`]
+    per_collection: parking_lot::Mutex<HashMap<String, CollectionCounters>>,
+    /// Advanced by every invalidation
+    generation: AtomicU64,
+    /// Generation of each collection's last invalidation
+    invalidated_at: parking_lot::Mutex<HashMap<String, u64>>,
     metrics: Arc<dyn MetricsSink>,
 }
 
@@ -152,43 +297,105 @@ impl<T: Clone> QueryCache<T> {
 
         Self {
             cache: Arc::new(RwLock::new(LruCache::new(capacity))),
-            ttl: Duration::from_secs(config.ttl_seconds),
+            ttl: RwLock::new(Duration::from_secs(config.ttl_seconds)),
             hits: Arc::new(parking_lot::Mutex::new(0)),
             misses: Arc::new(parking_lot::Mutex::new(0)),
             evictions: Arc::new(parking_lot::Mutex::new(0)),
+            per_collection: parking_lot::Mutex::new(HashMap::new()),
+            generation: AtomicU64::new(0),
+            invalidated_at: parking_lot::Mutex::new(HashMap::new()),
             metrics,
         }
     }
 
-    /// Get a cached query result.
+    /// Update the counters of `collection`.
+    fn count(&self, collection: &str, update: impl FnOnce(&mut CollectionCounters)) {
+        let mut per_collection = self.per_collection.lock();
+        match per_collection.get_mut(collection) {
+            Some(counters) => update(counters),
+            None => {
+                let mut counters = CollectionCounters::default();
+                update(&mut counters);
+                per_collection.insert(collection.to_string(), counters);
+            }
+        }
+    }
+
+    /// Current generation. Take it before running a search whose result
+    /// goes to [`QueryCache::insert_at`].
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Acquire)
+    }
+
+    /// Whether a collection `key` reads was invalidated after
+    /// `generation`. A search over every collection is stale after any
+    /// invalidation.
+    fn is_stale(&self, key: &QueryKey, generation: u64) -> bool {
+        if key.collection == ALL_COLLECTIONS {
+            return self.generation() > generation;
+        }
+        let invalidated_at = self.invalidated_at.lock();
+        key.collection.split(',').any(|name| {
+            invalidated_at
+                .get(name)
+                .is_some_and(|&invalidated| invalidated > generation)
+        })
+    }
+
+    /// Get a cached query result. An entry cached from a search that
+    /// started before its collection's last invalidation is a miss.
     ///
     /// Updates internal hit/miss counters AND records a
-    /// `cache_type="query"` hit/miss observation through the injected
-    /// [`MetricsSink`], so a `/prometheus/metrics` scrape reflects real
-    /// cache behaviour when the production sink is wired in. The
-    /// dual-counter shape (in-process + sink) is intentional: the
-    /// in-process counter is hot-path-cheap and surfaces in `/stats`
-    /// JSON; the sink is what alerting and dashboards consume.
+    /// `cache_type="query"` hit/miss observation, plus one labelled with
+    /// the key's collection, through the injected [`MetricsSink`], so a
+    /// `/prometheus/metrics` scrape reflects real cache behaviour when
+    /// the production sink is wired in. The dual-counter shape
+    /// (in-process + sink) is intentional: the in-process counter is
+    /// hot-path-cheap and surfaces in `/stats` JSON; the sink is what
+    /// alerting and dashboards consume.
     pub fn get(&self, key: &QueryKey) -> Option<T> {
         let mut cache = self.cache.write();
 
-        if let Some(entry) = cache.get(key) {
-            if entry.is_expired() {
+        let mut expired = false;
+        let found = match cache.get(key) {
+            Some(entry) if entry.is_expired() => {
                 // Entry expired, remove it
                 cache.pop(key);
-                *self.misses.lock() += 1;
-                self.metrics.cache_request("query", false);
+                expired = true;
                 None
-            } else {
-                *self.hits.lock() += 1;
-                self.metrics.cache_request("query", true);
-                Some(entry.value.clone())
             }
+            Some(entry) if self.is_stale(key, entry.generation) => {
+                cache.pop(key);
+                None
+            }
+            Some(entry) => Some(entry.value.clone()),
+            None => None,
+        };
+        drop(cache);
+
+        let hit = found.is_some();
+        if hit {
+            *self.hits.lock() += 1;
         } else {
             *self.misses.lock() += 1;
-            self.metrics.cache_request("query", false);
-            None
         }
+        self.count(&key.collection, |c| {
+            if hit {
+                c.hits += 1;
+            } else {
+                c.misses += 1;
+            }
+            if expired {
+                c.expirations += 1;
+            }
+        });
+        if expired {
+            self.metrics
+                .query_cache_removal(&key.collection, REMOVAL_EXPIRED, 1.0);
+        }
+        self.metrics.cache_request("query", hit);
+        self.metrics.query_cache_request(&key.collection, hit);
+        found
     }
 
     /// Cache-aside helper: look up `key` in the cache; on miss, run
@@ -210,36 +417,99 @@ impl<T: Clone> QueryCache<T> {
     where
         F: FnOnce() -> Result<T, E>,
     {
+        let generation = self.generation();
         if let Some(cached) = self.get(&key) {
             return Ok(cached);
         }
         let computed = compute()?;
-        self.insert(key, computed.clone());
+        self.insert_at(key, computed.clone(), generation);
         Ok(computed)
     }
 
-    /// Insert a query result into the cache
+    /// Insert a query result into the cache, as of the current
+    /// generation. Prefer [`QueryCache::insert_at`] for a result computed
+    /// while writes may land.
     pub fn insert(&self, key: QueryKey, value: T) {
-        let mut cache = self.cache.write();
-        let entry = CachedEntry::new(value, self.ttl);
+        self.insert_at(key, value, self.generation());
+    }
 
-        if let Some(_evicted) = cache.push(key, entry) {
+    /// Insert the result of a search that started at `generation` (see
+    /// [`QueryCache::generation`]). Nothing is cached when a collection
+    /// the search read was invalidated since.
+    pub fn insert_at(&self, key: QueryKey, value: T, generation: u64) {
+        let mut cache = self.cache.write();
+        if self.is_stale(&key, generation) {
+            return;
+        }
+        let entry = CachedEntry::new(value, *self.ttl.read(), generation);
+
+        // `push` also hands back the entry it replaced under the same key
+        let evicted = cache
+            .push(key.clone(), entry)
+            .filter(|(old, _)| *old != key)
+            .map(|(old, _)| old.collection);
+        drop(cache);
+        if let Some(collection) = evicted {
             *self.evictions.lock() += 1;
+            self.count(&collection, |c| c.evictions += 1);
+            self.metrics
+                .query_cache_removal(&collection, REMOVAL_EVICTED, 1.0);
         }
     }
 
-    /// Invalidate cache entries for a specific collection
-    pub fn invalidate_collection(&self, collection: &str) {
+    /// Drop every entry that read `collection` (see [`QueryKey::reads`]),
+    /// including searches over several collections. Returns how many were
+    /// dropped.
+    pub fn invalidate_collection(&self, collection: &str) -> usize {
         let mut cache = self.cache.write();
+        // Advanced under the cache lock, so `insert_at` either sees the
+        // new generation or lands before the entries are dropped below
+        let generation = self.generation.fetch_add(1, Ordering::AcqRel) + 1;
+        self.invalidated_at
+            .lock()
+            .insert(collection.to_string(), generation);
         let keys_to_remove: Vec<QueryKey> = cache
             .iter()
-            .filter(|(k, _)| k.collection == collection)
+            .filter(|(k, _)| k.reads(collection))
             .map(|(k, _)| k.clone())
             .collect();
 
-        for key in keys_to_remove {
-            cache.pop(&key);
+        for key in &keys_to_remove {
+            cache.pop(key);
+        }
+        drop(cache);
+
+        for key in &keys_to_remove {
+            self.count(&key.collection, |c| c.invalidations += 1);
+        }
+        if !keys_to_remove.is_empty() {
+            self.metrics.query_cache_removal(
+                collection,
+                REMOVAL_INVALIDATED,
+                keys_to_remove.len() as f64,
+            );
         }
+        keys_to_remove.len()
+    }
+
+    /// Change the capacity. Shrinking evicts the least recently used
+    /// entries beyond the new size; they count as evictions.
+    pub fn resize(&self, max_size: NonZeroUsize) {
+        let mut cache = self.cache.write();
+        let before = cache.len();
+        cache.resize(max_size);
+        *self.evictions.lock() += (before - cache.len()) as u64;
+    }
+
+    /// Time-to-live given to newly cached entries.
+    pub fn ttl(&self) -> Duration {
+        *self.ttl.read()
+    }
+
+    /// Change the time-to-live of entries cached from now on. Entries
+    /// already cached keep the TTL they were inserted with.
+    pub fn set_ttl(&self, ttl: Duration) {
+        *self.ttl.write() = ttl;
     }
 
     /// Clear all cached entries
@@ -269,11 +539,43 @@ impl<T: Clone> QueryCache<T> {
         }
     }
 
+    /// Statistics per [`QueryKey::collection`], for every collection the
+    /// cache has seen a lookup or an entry of.
+    pub fn collection_stats(&self) -> BTreeMap<String, CollectionCacheStats> {
+        let mut entries: HashMap<String, usize> = HashMap::new();
+        for (key, _) in self.cache.read().iter() {
+            *entries.entry(key.collection.clone()).or_default() += 1;
+        }
+        let per_collection = self.per_collection.lock();
+        let names: Vec<&String> = per_collection.keys().chain(entries.keys()).collect();
+        names
+            .into_iter()
+            .map(|name| {
+                let c = per_collection.get(name).copied().unwrap_or_default();
+                let stats = CollectionCacheStats {
+                    entries: entries.get(name).copied().unwrap_or(0),
+                    hits: c.hits,
+                    misses: c.misses,
+                    invalidations: c.invalidations,
+                    evictions: c.evictions,
+                    expirations: c.expirations,
+                    hit_rate: if c.hits + c.misses > 0 {
+                        c.hits as f64 / (c.hits + c.misses) as f64
+                    } else {
+                        0.0
+                    },
+                };
+                (name.clone(), stats)
+            })
+            .collect()
+    }
+
     /// Reset statistics
     pub fn reset_stats(&self) {
         *self.hits.lock() = 0;
         *self.misses.lock() = 0;
         *self.evictions.lock() = 0;
+        self.per_collection.lock().clear();
     }
 }
 
@@ -294,6 +596,25 @@ pub struct CacheStats {
     pub hit_rate: f64,
 }
 
+/// Query cache statistics of one collection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectionCacheStats {
+    /// Entries currently cached
+    pub entries: usize,
+    /// Number of cache hits
+    pub hits: u64,
+    /// Number of cache misses
+    pub misses: u64,
+    /// Entries dropped because the collection changed
+    pub invalidations: u64,
+    /// Entries pushed out by newer ones when the cache was full
+    pub evictions: u64,
+    /// Entries found past their time-to-live
+    pub expirations: u64,
+    /// Hit rate (0.0 to 1.0)
+    pub hit_rate: f64,
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used, clippy::expect_used)]
 mod tests {
@@ -308,6 +629,7 @@ mod tests {
     #[derive(Debug, Default)]
     struct RecordingMetricsSink {
         cache_requests: Mutex<Vec<(String, bool)>>,
+        collection_requests: Mutex<Vec<(String, bool)>>,
         calls: AtomicUsize,
     }
 
@@ -319,6 +641,13 @@ mod tests {
                 .unwrap()
                 .push((cache_type.to_string(), hit));
         }
+
+        fn query_cache_request(&self, collection: &str, hit: bool) {
+            self.collection_requests
+                .lock()
+                .unwrap()
+                .push((collection.to_string(), hit));
+        }
     }
 
     #[test]
@@ -341,6 +670,11 @@ mod tests {
             calls.as_slice(),
             &[("query".to_string(), false), ("query".to_string(), true),]
         );
+        let calls = sink.collection_requests.lock().unwrap();
+        assert_eq!(
+            calls.as_slice(),
+            &[("test".to_string(), false), ("test".to_string(), true)]
+        );
     }
 
     #[test]
@@ -468,6 +802,114 @@ mod tests {
         assert!(cache.get(&key).is_none());
     }
 
+    #[test]
+    fn test_cache_resize_and_set_ttl() {
+        let cache: QueryCache<Vec<String>> = QueryCache::new(QueryCacheConfig::default());
+        let keys: Vec<_> = (0..3)
+            .map(|i| QueryKey::new("test".to_string(), format!("query{i}"), 10, None))
+            .collect();
+        for key in &keys {
+            cache.insert(key.clone(), vec!["result".to_string()]);
+        }
+
+        cache.resize(NonZeroUsize::new(1).unwrap());
+        let stats = cache.stats();
+        assert_eq!(stats.capacity, 1);
+        assert_eq!(stats.size, 1);
+        assert_eq!(stats.evictions, 2);
+        assert!(cache.get(&keys[2]).is_some());
+
+        cache.set_ttl(Duration::ZERO);
+        assert_eq!(cache.ttl(), Duration::ZERO);
+        cache.insert(keys[0].clone(), vec!["result".to_string()]);
+        std::thread::sleep(Duration::from_millis(10));
+        assert!(cache.get(&keys[0]).is_none());
+    }
+
+    #[test]
+    fn keys_cover_filter_params_and_vector_direction() {
+        let base = QueryKey::new("coll".to_string(), "query".to_string(), 10, None);
+        let filter = serde_json::json!({"must": [{"key": "a", "match_value": 1}], "should": []});
+        let reordered = serde_json::json!({"should": [], "must": [{"match_value": 1, "key": "a"}]});
+        assert_ne!(base, base.clone().with_filter(&filter));
+        assert_eq!(
+            base.clone().with_filter(&filter),
+            base.clone().with_filter(&reordered)
+        );
+        assert_ne!(
+            base.clone().with_params(&serde_json::json!({"alpha": 0.5})),
+            base.clone().with_params(&serde_json::json!({"alpha": 0.7}))
+        );
+
+        let key = |v: &[f32], metric| {
+            QueryKey::from_query_vector("coll".to_string(), v, metric, 10, None)
+        };
+        let (v, scaled) = ([0.3, -0.4, 0.0], [0.9, -1.2, -0.0]);
+        assert_eq!(
+            key(&v, DistanceMetric::Cosine),
+            key(&scaled, DistanceMetric::Cosine)
+        );
+        assert_ne!(
+            key(&v, DistanceMetric::Euclidean),
+            key(&scaled, DistanceMetric::Euclidean)
+        );
+    }
+
+    #[test]
+    fn invalidation_reaches_multi_collection_entries_and_is_counted() {
+        let cache: QueryCache<Vec<String>> = QueryCache::new(QueryCacheConfig::default());
+        let single = QueryKey::new("a".to_string(), "q".to_string(), 10, None);
+        let several = QueryKey::new("b,a".to_string(), "q".to_string(), 10, None);
+        let all = QueryKey::new(ALL_COLLECTIONS.to_string(), "q".to_string(), 10, None);
+        let other = QueryKey::new("b".to_string(), "q".to_string(), 10, None);
+        for key in [&single, &several, &all, &other] {
+            cache.insert(key.clone(), vec![]);
+        }
+        // Replacing an entry is not an eviction
+        cache.insert(other.clone(), vec![]);
+        assert_eq!(cache.stats().evictions, 0);
+
+        assert_eq!(cache.invalidate_collection("a"), 3);
+        assert!(cache.get(&other).is_some());
+        assert!(cache.get(&single).is_none());
+
+        let stats = cache.collection_stats();
+        assert_eq!(stats["a"].invalidations, 1);
+        assert_eq!(stats["a"].misses, 1);
+        assert_eq!(stats["b,a"].invalidations, 1);
+        assert_eq!(stats["b"].entries, 1);
+        assert_eq!(stats["b"].hits, 1);
+    }
+
+    #[test]
+    fn results_of_searches_overtaken_by_a_write_are_not_served() {
+        let cache: QueryCache<Vec<String>> = QueryCache::new(QueryCacheConfig::default());
+        let key = QueryKey::new("a".to_string(), "q".to_string(), 10, None);
+        let all = QueryKey::new(ALL_COLLECTIONS.to_string(), "q".to_string(), 10, None);
+        let other = QueryKey::new("b".to_string(), "q".to_string(), 10, None);
+
+        // The searches start, a write to "a" lands, then they finish
+        let started = cache.generation();
+        cache.invalidate_collection("a");
+        cache.insert_at(key.clone(), vec!["before".to_string()], started);
+        cache.insert_at(all.clone(), vec!["before".to_string()], started);
+        cache.insert_at(other.clone(), vec!["b".to_string()], started);
+        assert!(cache.get(&key).is_none());
+        assert!(cache.get(&all).is_none());
+        assert!(cache.get(&other).is_some());
+
+        // A search that started after the write is cached
+        cache.insert_at(key.clone(), vec!["after".to_string()], cache.generation());
+        assert_eq!(cache.get(&key).unwrap(), vec!["after".to_string()]);
+
+        let computed = cache.cached_or_compute(all.clone(), || {
+            cache.invalidate_collection("b");
+            Ok::<_, ()>(vec!["racing".to_string()])
+        });
+        assert_eq!(computed.unwrap(), vec!["racing".to_string()]);
+        assert!(cache.get(&all).is_none());
+    }
+
     #[test]
     fn test_query_key_hash() {
         let key1 = QueryKey::new("coll".to_string(), "query".to_string(), 10, Some(0.5));
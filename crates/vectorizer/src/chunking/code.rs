@@ -0,0 +1,159 @@
+//! Code-aware chunking
+//!
+//! Items are packed whole; an item larger than the chunk size is split
+//! at its own inner items (the methods of an impl or class, the
+//! statements of a function body), so a chunk never starts or ends in
+//! the middle of a function unless the function alone exceeds it.
+
+use std::ops::Range;
+use std::path::Path;
+
+use super::{fixed, lines, pack, split_before};
+
+/// Source file extensions [`super::ChunkingStrategy::Auto`] chunks as code
+const SOURCE_EXTENSIONS: &[&str] = &[
+    "rs", "py", "pyi", "js", "jsx", "mjs", "cjs", "ts", "tsx", "go", "java", "kt", "scala", "c",
+    "h", "cc", "cpp", "hpp", "cs", "rb", "php", "swift",
+];
+
+/// Whether `extension` (lowercase) names a source file
+pub(super) fn is_source_extension(extension: &str) -> bool {
+    SOURCE_EXTENSIONS.contains(&extension)
+}
+
+/// Chunks of `text`, the content of `path`, of at most `max` bytes
+pub(super) fn split(text: &str, path: &Path, max: usize) -> Vec<Range<usize>> {
+    #[cfg(feature = "code-chunking")]
+    if let Some(chunks) = syntax::split(text, path, max) {
+        return chunks;
+    }
+    #[cfg(not(feature = "code-chunking"))]
+    let _ = path;
+
+    let mut units = Vec::new();
+    flatten_blocks(text, 0..text.len(), max, &mut units);
+    pack(&units, max, 0, |i| vec![units[i].clone()])
+}
+
+/// Blocks of `text[range]` that fit in `max` bytes, in order: blocks
+/// that do not fit are replaced by their own blocks
+fn flatten_blocks(text: &str, range: Range<usize>, max: usize, out: &mut Vec<Range<usize>>) {
+    if range.len() <= max {
+        out.push(range);
+        return;
+    }
+    let units = blocks(text, range.clone());
+    if units.len() <= 1 {
+        out.extend(fixed::split(text, range, max, 0));
+        return;
+    }
+    for unit in units {
+        flatten_blocks(text, unit, max, out);
+    }
+}
+
+/// Blocks of `text[range]` without a parser: a block starts at a line
+/// that follows a blank line and is indented no deeper than any other
+/// such line. The first line of `range` never starts a block, so blocks
+/// of a block are strictly smaller.
+fn blocks(text: &str, range: Range<usize>) -> Vec<Range<usize>> {
+    let mut candidates = Vec::new();
+    let mut previous_blank = false;
+    for (offset, line) in lines(text, range.clone()) {
+        let blank = line.trim().is_empty();
+        if previous_blank && !blank && offset > range.start {
+            let indent = line.len() - line.trim_start().len();
+            candidates.push((offset, indent));
+        }
+        previous_blank = blank;
+    }
+    let Some(indent) = candidates.iter().map(|&(_, indent)| indent).min() else {
+        return vec![range];
+    };
+    let starts = candidates
+        .into_iter()
+        .filter(|&(_, i)| i == indent)
+        .map(|(offset, _)| offset);
+    split_before(range, starts)
+}
+
+/// Items from a tree-sitter parse
+#[cfg(feature = "code-chunking")]
+mod syntax {
+    use std::ops::Range;
+    use std::path::Path;
+
+    use tree_sitter::{Language, Node, Parser};
+
+    use super::super::{fixed, pack};
+
+    fn language(path: &Path) -> Option<Language> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        let language = match extension.as_str() {
+            "rs" => tree_sitter_rust::LANGUAGE,
+            "py" | "pyi" => tree_sitter_python::LANGUAGE,
+            "js" | "jsx" | "mjs" | "cjs" => tree_sitter_javascript::LANGUAGE,
+            "ts" => tree_sitter_typescript::LANGUAGE_TYPESCRIPT,
+            "tsx" => tree_sitter_typescript::LANGUAGE_TSX,
+            "go" => tree_sitter_go::LANGUAGE,
+            _ => return None,
+        };
+        Some(language.into())
+    }
+
+    /// `None` when `path` is in no supported language or does not parse
+    pub(super) fn split(text: &str, path: &Path, max: usize) -> Option<Vec<Range<usize>>> {
+        let mut parser = Parser::new();
+        parser.set_language(&language(path)?).ok()?;
+        let tree = parser.parse(text, None)?;
+        let mut units = Vec::new();
+        flatten(text, tree.root_node(), 0..text.len(), max, &mut units);
+        Some(pack(&units, max, 0, |i| vec![units[i].clone()]))
+    }
+
+    /// Items of `node` that fit in `max` bytes, in order: items that do
+    /// not fit are replaced by their own items
+    fn flatten(
+        text: &str,
+        node: Node<'_>,
+        range: Range<usize>,
+        max: usize,
+        out: &mut Vec<Range<usize>>,
+    ) {
+        if range.len() <= max {
+            out.push(range);
+            return;
+        }
+        let units = units(node, range.clone());
+        if units.is_empty() {
+            out.extend(fixed::split(text, range, max, 0));
+            return;
+        }
+        for (unit, child) in units {
+            flatten(text, child, unit, max, out);
+        }
+    }
+
+    /// The named children of `node` as ranges covering `range`. Comments
+    /// and attributes go with the item after them.
+    fn units<'t>(node: Node<'t>, range: Range<usize>) -> Vec<(Range<usize>, Node<'t>)> {
+        let mut units: Vec<(Range<usize>, Node<'t>)> = Vec::new();
+        let mut start = range.start;
+        let mut cursor = node.walk();
+        for child in node.named_children(&mut cursor) {
+            let kind = child.kind();
+            if kind.ends_with("comment") || kind == "attribute_item" || kind == "decorator" {
+                continue;
+            }
+            let end = child.end_byte().clamp(start, range.end);
+            if end > start {
+                units.push((start..end, child));
+                start = end;
+            }
+        }
+        if let Some((last, _)) = units.last_mut() {
+            last.end = range.end;
+        }
+        units
+    }
+}
@@ -0,0 +1,64 @@
+//! Fixed-size chunking
+
+use std::ops::Range;
+
+/// Windows of `text[range]` of at most `max` bytes, each ending at the
+/// last whitespace or sentence break it contains and starting `overlap`
+/// bytes before the end of the previous one
+pub(super) fn split(
+    text: &str,
+    range: Range<usize>,
+    max: usize,
+    overlap: usize,
+) -> Vec<Range<usize>> {
+    let mut chunks = Vec::new();
+    let mut start = range.start;
+
+    while start < range.end {
+        // Calculate the end position for this chunk
+        let mut end = std::cmp::min(start + max, range.end);
+
+        // If we're not at the end of the text, try to find a good break point
+        if end < range.end {
+            // Ensure we're at a UTF-8 character boundary
+            while end > start && !text.is_char_boundary(end) {
+                end -= 1;
+            }
+
+            // Try to break at a word boundary (whitespace, punctuation)
+            if let Some(pos) = text[start..end]
+                .rfind(|c: char| c.is_whitespace() || c == '.' || c == '!' || c == '?' || c == '\n')
+            {
+                end = start + pos + 1;
+
+                // Ensure the new end is still at a UTF-8 character boundary
+                while end > start && !text.is_char_boundary(end) {
+                    end -= 1;
+                }
+            }
+        }
+
+        // A character wider than `max` still makes progress
+        if end == start {
+            end = start + 1;
+            while end < range.end && !text.is_char_boundary(end) {
+                end += 1;
+            }
+        }
+
+        if !text[start..end].trim().is_empty() {
+            chunks.push(start..end);
+        }
+
+        // Calculate the next start position with overlap, making progress
+        let next_start = end.saturating_sub(overlap);
+        start = if next_start <= start { end } else { next_start };
+
+        // Ensure start is at a UTF-8 character boundary
+        while start < range.end && !text.is_char_boundary(start) {
+            start += 1;
+        }
+    }
+
+    chunks
+}
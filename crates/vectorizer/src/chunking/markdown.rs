@@ -0,0 +1,74 @@
+//! Markdown heading-aware chunking
+
+use std::ops::Range;
+
+use super::{fixed, lines, pack, prose, split_before};
+
+/// Sections of `text` that fit in `max` bytes: the whole document if it
+/// fits, otherwise its top-level sections, each split the same way at
+/// its own subheadings. A section without subheadings that does not fit
+/// is split at paragraphs.
+pub(super) fn split(text: &str, max: usize) -> Vec<Range<usize>> {
+    let headings = headings(text);
+    let mut chunks = Vec::new();
+    split_section(text, 0..text.len(), &headings, max, &mut chunks);
+    chunks
+}
+
+fn split_section(
+    text: &str,
+    range: Range<usize>,
+    headings: &[(usize, usize)],
+    max: usize,
+    chunks: &mut Vec<Range<usize>>,
+) {
+    if range.len() <= max {
+        chunks.push(range);
+        return;
+    }
+
+    // Headings inside the section, after its own
+    let inner = || {
+        headings
+            .iter()
+            .filter(|(offset, _)| *offset > range.start && *offset < range.end)
+    };
+    let Some(level) = inner().map(|&(_, level)| level).min() else {
+        let units = prose::paragraphs(text, range.clone());
+        chunks.extend(pack(&units, max, 0, |i| {
+            fixed::split(text, units[i].clone(), max, 0)
+        }));
+        return;
+    };
+    let starts = inner()
+        .filter(|&&(_, l)| l == level)
+        .map(|&(offset, _)| offset);
+    for section in split_before(range.clone(), starts) {
+        split_section(text, section, headings, max, chunks);
+    }
+}
+
+/// Offset and level of every ATX heading outside fenced code blocks
+fn headings(text: &str) -> Vec<(usize, usize)> {
+    let mut headings = Vec::new();
+    let mut fence: Option<&str> = None;
+    for (offset, line) in lines(text, 0..text.len()) {
+        let trimmed = line.trim_start();
+        if let Some(open) = fence {
+            if trimmed.starts_with(open) {
+                fence = None;
+            }
+            continue;
+        }
+        if let Some(open) = ["```", "~~~"].into_iter().find(|f| trimmed.starts_with(f)) {
+            fence = Some(open);
+            continue;
+        }
+        let level = line.bytes().take_while(|&b| b == b'#').count();
+        let rest = &line[level..];
+        if (1..=6).contains(&level) && (rest.trim().is_empty() || rest.starts_with([' ', '\t'])) {
+            headings.push((offset, level));
+        }
+    }
+    headings
+}
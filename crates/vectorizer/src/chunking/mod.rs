@@ -0,0 +1,236 @@
+//! Chunking strategies
+//!
+//! Splits a document into the pieces that get embedded. Each collection
+//! picks a strategy in its [`ChunkingConfig`]:
+//!
+//! - `fixed` — windows of at most `max_chunk_size` bytes ending at a
+//!   word or sentence break, overlapping by `chunk_overlap` (the
+//!   historical behaviour and the default)
+//! - `sentence` / `paragraph` — whole sentences or paragraphs, packed
+//!   together up to `max_chunk_size`
+//! - `markdown` — one chunk per heading section; a section that does not
+//!   fit is split at its subheadings, then at paragraphs
+//! - `code` — whole top-level items (functions, types, impls), packed
+//!   together; an item that does not fit is split at its inner items.
+//!   With the `code-chunking` feature, items come from a tree-sitter
+//!   parse for Rust, Python, JavaScript, TypeScript and Go; otherwise
+//!   (and for other languages) from blank lines followed by a less
+//!   indented line
+//! - `auto` — `markdown` for Markdown files, `code` for source files and
+//!   `paragraph` for anything else
+//!
+//! A piece that cannot be split at a boundary of its strategy falls back
+//! to `fixed`, so no chunk exceeds `max_chunk_size`.
+
+mod code;
+mod fixed;
+mod markdown;
+mod prose;
+
+#[cfg(test)]
+mod tests;
+
+use std::ops::Range;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, VectorizerError};
+
+/// How documents are split into chunks
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChunkingStrategy {
+    /// Fixed-size windows with overlap
+    #[default]
+    Fixed,
+    /// Whole sentences
+    Sentence,
+    /// Whole paragraphs
+    Paragraph,
+    /// Markdown heading sections
+    Markdown,
+    /// Source code items
+    Code,
+    /// Picked from the file extension
+    Auto,
+}
+
+impl ChunkingStrategy {
+    /// The strategy used for `path`: [`Self::Auto`] resolves by file
+    /// extension, the others are returned as is
+    pub fn resolve(self, path: &Path) -> Self {
+        if self != Self::Auto {
+            return self;
+        }
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_ascii_lowercase);
+        match extension.as_deref() {
+            Some("md" | "markdown" | "mdx") => Self::Markdown,
+            Some(ext) if code::is_source_extension(ext) => Self::Code,
+            _ => Self::Paragraph,
+        }
+    }
+}
+
+/// Chunking settings of a collection
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ChunkingConfig {
+    /// How documents are split
+    #[serde(default)]
+    pub strategy: ChunkingStrategy,
+    /// Chunk size in bytes, used when an insert or upload passes none
+    #[serde(default)]
+    pub max_chunk_size: Option<usize>,
+    /// Overlap in bytes, used when an insert or upload passes none
+    #[serde(default)]
+    pub chunk_overlap: Option<usize>,
+}
+
+impl ChunkingConfig {
+    /// Settings with `strategy` and the default sizes
+    pub fn new(strategy: ChunkingStrategy) -> Self {
+        Self {
+            strategy,
+            ..Self::default()
+        }
+    }
+
+    /// Check the settings
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |message: &str| {
+            Err(VectorizerError::InvalidConfiguration {
+                message: message.to_string(),
+            })
+        };
+        if self.max_chunk_size == Some(0) {
+            return invalid("chunking.max_chunk_size must be greater than 0");
+        }
+        if let (Some(size), Some(overlap)) = (self.max_chunk_size, self.chunk_overlap)
+            && overlap >= size
+        {
+            return invalid("chunking.chunk_overlap must be smaller than max_chunk_size");
+        }
+        Ok(())
+    }
+}
+
+/// Split `text`, the content of `path`, into chunks of at most
+/// `max_chunk_size` bytes. Chunks are trimmed and never empty.
+/// `chunk_overlap` applies to `fixed`, `sentence` and `paragraph`
+/// chunks; `markdown` and `code` chunks start at a boundary.
+pub fn chunk_text<'a>(
+    strategy: ChunkingStrategy,
+    text: &'a str,
+    path: &Path,
+    max_chunk_size: usize,
+    chunk_overlap: usize,
+) -> Vec<&'a str> {
+    let max = max_chunk_size.max(1);
+    let whole = 0..text.len();
+    let ranges = match strategy.resolve(path) {
+        ChunkingStrategy::Fixed | ChunkingStrategy::Auto => {
+            fixed::split(text, whole, max, chunk_overlap)
+        }
+        ChunkingStrategy::Sentence => {
+            let units = prose::sentences(text, whole);
+            pack(&units, max, chunk_overlap, |i| {
+                fixed::split(text, units[i].clone(), max, chunk_overlap)
+            })
+        }
+        ChunkingStrategy::Paragraph => {
+            let units = prose::paragraphs(text, whole);
+            pack(&units, max, chunk_overlap, |i| {
+                let sentences = prose::sentences(text, units[i].clone());
+                pack(&sentences, max, chunk_overlap, |j| {
+                    fixed::split(text, sentences[j].clone(), max, chunk_overlap)
+                })
+            })
+        }
+        ChunkingStrategy::Markdown => markdown::split(text, max),
+        ChunkingStrategy::Code => code::split(text, path, max),
+    };
+    ranges
+        .into_iter()
+        .map(|range| text[range].trim())
+        .filter(|chunk| !chunk.is_empty())
+        .collect()
+}
+
+/// Merge consecutive `units` (ranges that cover a stretch of text, in
+/// order) into chunks of at most `max` bytes. Unit `i` larger than `max`
+/// becomes `split_large(i)`. With `overlap`, a chunk starts with the last
+/// units of the previous one that fit in `overlap` bytes.
+fn pack(
+    units: &[Range<usize>],
+    max: usize,
+    overlap: usize,
+    mut split_large: impl FnMut(usize) -> Vec<Range<usize>>,
+) -> Vec<Range<usize>> {
+    let mut chunks = Vec::new();
+    // Index of the first unit of the chunk being built, and its range
+    let mut current: Option<(usize, Range<usize>)> = None;
+    for (i, unit) in units.iter().enumerate() {
+        if let Some((_, range)) = &mut current
+            && unit.end - range.start <= max
+        {
+            range.end = unit.end;
+            continue;
+        }
+
+        let previous = current.take();
+        if let Some((_, range)) = &previous {
+            chunks.push(range.clone());
+        }
+        if unit.len() > max {
+            chunks.extend(split_large(i));
+            continue;
+        }
+        let mut first = i;
+        if let Some((previous_first, _)) = previous {
+            while first > previous_first
+                && unit.start - units[first - 1].start <= overlap
+                && unit.end - units[first - 1].start <= max
+            {
+                first -= 1;
+            }
+        }
+        current = Some((first, units[first].start..unit.end));
+    }
+    if let Some((_, range)) = current {
+        chunks.push(range);
+    }
+    chunks
+}
+
+/// The lines of `text[range]` with their offsets in `text`, each
+/// including its line break
+fn lines(text: &str, range: Range<usize>) -> impl Iterator<Item = (usize, &str)> {
+    let start = range.start;
+    text[range]
+        .split_inclusive('\n')
+        .scan(start, |offset, line| {
+            let at = *offset;
+            *offset += line.len();
+            Some((at, line))
+        })
+}
+
+/// Cut `range` before each of `offsets` (ascending)
+fn split_before(
+    range: Range<usize>,
+    offsets: impl IntoIterator<Item = usize>,
+) -> Vec<Range<usize>> {
+    let mut units = Vec::new();
+    let mut start = range.start;
+    for offset in offsets {
+        if offset > start && offset < range.end {
+            units.push(start..offset);
+            start = offset;
+        }
+    }
+    units.push(start..range.end);
+    units
+}
@@ -0,0 +1,37 @@
+//! Sentence and paragraph units of prose
+
+use std::ops::Range;
+
+use super::{lines, split_before};
+
+/// Sentences of `text[range]`: a sentence ends after `.`, `!` or `?`
+/// followed by whitespace, or at a blank line
+pub(super) fn sentences(text: &str, range: Range<usize>) -> Vec<Range<usize>> {
+    let base = range.start;
+    let mut ends = Vec::new();
+    let mut chars = text[range.clone()].char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let next = chars.peek().map(|&(_, next)| next);
+        let terminator = matches!(c, '.' | '!' | '?') && next.is_none_or(char::is_whitespace);
+        let paragraph = c == '\n' && next == Some('\n');
+        if terminator || paragraph {
+            ends.push(base + i + c.len_utf8());
+        }
+    }
+    split_before(range, ends)
+}
+
+/// Paragraphs of `text[range]`: a paragraph starts at a non-blank line
+/// that follows a blank one
+pub(super) fn paragraphs(text: &str, range: Range<usize>) -> Vec<Range<usize>> {
+    let mut starts = Vec::new();
+    let mut previous_blank = false;
+    for (offset, line) in lines(text, range.clone()) {
+        let blank = line.trim().is_empty();
+        if previous_blank && !blank {
+            starts.push(offset);
+        }
+        previous_blank = blank;
+    }
+    split_before(range, starts)
+}
@@ -0,0 +1,158 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use std::path::Path;
+
+use super::*;
+
+fn split<'a>(strategy: ChunkingStrategy, text: &'a str, path: &str, max: usize) -> Vec<&'a str> {
+    chunk_text(strategy, text, Path::new(path), max, 0)
+}
+
+#[test]
+fn fixed_chunks_stay_within_the_size() {
+    let text = "word ".repeat(30);
+    let chunks = chunk_text(ChunkingStrategy::Fixed, &text, Path::new("a.txt"), 50, 10);
+    assert!(chunks.len() > 1);
+    assert!(chunks.iter().all(|c| c.len() <= 50));
+}
+
+#[test]
+fn fixed_chunks_make_progress_on_wide_characters() {
+    let chunks = split(ChunkingStrategy::Fixed, "世界", "a.txt", 1);
+    assert_eq!(chunks, ["世", "界"]);
+}
+
+#[test]
+fn sentences_are_never_split() {
+    let text = "One two three. Four five six! Seven eight nine? Ten.";
+    let chunks = split(ChunkingStrategy::Sentence, text, "a.txt", 32);
+    assert_eq!(
+        chunks,
+        ["One two three. Four five six!", "Seven eight nine? Ten."]
+    );
+}
+
+#[test]
+fn sentence_chunks_overlap_by_whole_sentences() {
+    let text = "Aa. Bb. Cc. Dd.";
+    let chunks = chunk_text(ChunkingStrategy::Sentence, text, Path::new("a.txt"), 8, 4);
+    assert_eq!(chunks, ["Aa. Bb.", "Bb. Cc.", "Cc. Dd."]);
+}
+
+#[test]
+fn paragraphs_are_packed_and_long_ones_split_at_sentences() {
+    let text = "First para.\n\nSecond para.\n\nA long one. With two sentences here.";
+    let chunks = split(ChunkingStrategy::Paragraph, text, "a.txt", 30);
+    assert_eq!(
+        chunks,
+        [
+            "First para.\n\nSecond para.",
+            "A long one.",
+            "With two sentences here."
+        ]
+    );
+}
+
+#[test]
+fn markdown_splits_at_the_highest_heading_level_first() {
+    let text = "# Title\nintro\n\n## A\nalpha text\n\n## B\nbeta text\n";
+    let chunks = split(ChunkingStrategy::Markdown, text, "doc.md", 25);
+    assert_eq!(
+        chunks,
+        ["# Title\nintro", "## A\nalpha text", "## B\nbeta text"]
+    );
+
+    // A section that fits keeps its subsections
+    let chunks = split(ChunkingStrategy::Markdown, text, "doc.md", 100);
+    assert_eq!(chunks.len(), 1);
+}
+
+#[test]
+fn markdown_ignores_headings_in_code_fences() {
+    let text = "## Setup\n```sh\n# not a heading\nmake\n```\n\n## Usage\nrun it\n";
+    let chunks = split(ChunkingStrategy::Markdown, text, "doc.md", 45);
+    assert_eq!(
+        chunks,
+        [
+            "## Setup\n```sh\n# not a heading\nmake\n```",
+            "## Usage\nrun it"
+        ]
+    );
+}
+
+#[test]
+fn code_chunks_keep_functions_whole() {
+    let text = "\
+fn one() {
+    let a = 1;
+
+    a + 1
+}
+
+fn two() {
+    2
+}
+
+fn three() {
+    3
+}
+";
+    let chunks = split(ChunkingStrategy::Code, text, "lib.rs", 45);
+    assert_eq!(chunks[0], "fn one() {\n    let a = 1;\n\n    a + 1\n}");
+    assert_eq!(chunks[1], "fn two() {\n    2\n}\n\nfn three() {\n    3\n}");
+}
+
+#[test]
+fn oversized_items_split_inside() {
+    let body = "    let x = 1;\n\n".repeat(8);
+    let text = format!("fn big() {{\n{body}}}\n");
+    let chunks = split(ChunkingStrategy::Code, &text, "lib.rs", 60);
+    assert!(chunks.len() > 1);
+    assert!(chunks.iter().all(|c| c.len() <= 60));
+    assert!(chunks[0].starts_with("fn big() {\n    let x = 1;"));
+}
+
+#[test]
+fn auto_picks_a_strategy_from_the_extension() {
+    let auto = ChunkingStrategy::Auto;
+    assert_eq!(
+        auto.resolve(Path::new("README.md")),
+        ChunkingStrategy::Markdown
+    );
+    assert_eq!(
+        auto.resolve(Path::new("src/main.RS")),
+        ChunkingStrategy::Code
+    );
+    assert_eq!(
+        auto.resolve(Path::new("notes.txt")),
+        ChunkingStrategy::Paragraph
+    );
+    assert_eq!(
+        ChunkingStrategy::Fixed.resolve(Path::new("README.md")),
+        ChunkingStrategy::Fixed
+    );
+}
+
+#[test]
+fn config_is_validated() {
+    assert!(
+        ChunkingConfig::new(ChunkingStrategy::Code)
+            .validate()
+            .is_ok()
+    );
+    let zero = ChunkingConfig {
+        max_chunk_size: Some(0),
+        ..ChunkingConfig::default()
+    };
+    assert!(zero.validate().is_err());
+    let overlap = ChunkingConfig {
+        max_chunk_size: Some(100),
+        chunk_overlap: Some(100),
+        ..ChunkingConfig::default()
+    };
+    assert!(overlap.validate().is_err());
+
+    let parsed: ChunkingConfig =
+        serde_json::from_value(serde_json::json!({"strategy": "markdown"})).unwrap();
+    assert_eq!(parsed, ChunkingConfig::new(ChunkingStrategy::Markdown));
+}
@@ -203,6 +203,12 @@ impl ClusterServiceTrait for ClusterGrpcService {
             req.collection_name, req.vector_id
         );
 
+        let _write = self
+            .store
+            .write_fence()
+            .enter()
+            .map_err(|e| Status::unavailable(e.to_string()))?;
+
         let collection = self
             .store
             .get_collection(&req.collection_name)
@@ -234,6 +240,7 @@ impl ClusterServiceTrait for ClusterGrpcService {
                 .add_vector(req.vector_id.clone(), vector_obj)
                 .map_err(|e: crate::error::VectorizerError| Status::internal(e.to_string()))?;
         }
+        self.store.notify_mutation(&req.collection_name);
 
         let response = RemoteInsertVectorResponse {
             success: true,
@@ -254,6 +261,12 @@ impl ClusterServiceTrait for ClusterGrpcService {
             req.collection_name, req.vector_id
         );
 
+        let _write = self
+            .store
+            .write_fence()
+            .enter()
+            .map_err(|e| Status::unavailable(e.to_string()))?;
+
         let collection = self
             .store
             .get_collection(&req.collection_name)
@@ -283,6 +296,8 @@ impl ClusterServiceTrait for ClusterGrpcService {
         collection
             .update_vector(vector_obj)
             .map_err(|e: crate::error::VectorizerError| Status::internal(e.to_string()))?;
+        drop(collection);
+        self.store.notify_mutation(&req.collection_name);
 
         let response = RemoteUpdateVectorResponse {
             success: true,
@@ -303,6 +318,12 @@ impl ClusterServiceTrait for ClusterGrpcService {
             req.collection_name, req.vector_id
         );
 
+        let _write = self
+            .store
+            .write_fence()
+            .enter()
+            .map_err(|e| Status::unavailable(e.to_string()))?;
+
         let collection = self
             .store
             .get_collection(&req.collection_name)
@@ -317,6 +338,7 @@ impl ClusterServiceTrait for ClusterGrpcService {
                 .delete_vector(&req.vector_id)
                 .map_err(|e: crate::error::VectorizerError| Status::internal(e.to_string()))?;
         }
+        self.store.notify_mutation(&req.collection_name);
 
         let response = RemoteDeleteVectorResponse {
             success: true,
@@ -948,4 +970,27 @@ impl ClusterServiceTrait for ClusterGrpcService {
 
         Ok(Response::new(RaftSnapshotResponse { data: resp_data }))
     }
+
+    /// One phase of a coordinated cluster snapshot (see
+    /// [`super::snapshot`]).
+    async fn snapshot_phase(
+        &self,
+        request: Request<SnapshotPhaseRequest>,
+    ) -> Result<Response<SnapshotPhaseResponse>, Status> {
+        let req = request.into_inner();
+        debug!(
+            "gRPC: SnapshotPhase {} for cluster snapshot '{}'",
+            req.phase, req.snapshot_id
+        );
+
+        let node_id = self.cluster_manager.local_node_id().as_str().to_string();
+        let response = super::snapshot::handle_phase(
+            &self.store,
+            &super::snapshot::snapshot_root(),
+            &node_id,
+            req,
+        )
+        .await;
+        Ok(Response::new(response))
+    }
 }
@@ -50,6 +50,11 @@ impl HaManager {
         }
     }
 
+    /// The vector store whose data-plane role this manager drives.
+    pub fn store(&self) -> &Arc<VectorStore> {
+        &self.store
+    }
+
     /// Called when this node wins a Raft election and becomes leader.
     ///
     /// Stops any running `ReplicaNode` and starts a `MasterNode`.
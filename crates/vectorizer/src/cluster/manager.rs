@@ -225,4 +225,9 @@ impl ClusterManager {
     pub fn is_enabled(&self) -> bool {
         self.config.enabled
     }
+
+    /// Cluster configuration this manager was built from
+    pub fn config(&self) -> &ClusterConfig {
+        &self.config
+    }
 }
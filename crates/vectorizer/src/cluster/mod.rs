@@ -16,6 +16,7 @@ pub mod rebalance;
 mod server_client;
 pub mod shard_migrator;
 mod shard_router;
+pub mod snapshot;
 mod state_sync;
 mod topology;
 pub mod validator;
@@ -37,6 +38,9 @@ pub use raft_watcher::RaftWatcher;
 pub use rebalance::{PeerInfo, PeerRole, RebalanceJob, RebalanceStatus};
 pub use server_client::{ClusterClient, ClusterClientPool};
 pub use shard_router::DistributedShardRouter;
+pub use snapshot::{
+    ClusterSnapshotCoordinator, ClusterSnapshotManifest, NodeRestoreResult, NodeSnapshotInfo,
+};
 pub use state_sync::ClusterStateSynchronizer;
 pub use topology::ClusterShardTopology;
 use tracing::{error, info, warn};
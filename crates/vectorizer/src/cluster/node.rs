@@ -22,6 +22,15 @@ impl NodeId {
     pub fn as_str(&self) -> &str {
         &self.0
     }
+
+    /// Numeric peer ID for APIs that identify peers by integer (Qdrant's
+    /// cluster API). Numeric node IDs are used as-is; others hash to a
+    /// stable value.
+    pub fn peer_id(&self) -> u64 {
+        self.0
+            .parse()
+            .unwrap_or_else(|_| xxhash_rust::xxh3::xxh3_64(self.0.as_bytes()))
+    }
 }
 
 impl std::fmt::Display for NodeId {
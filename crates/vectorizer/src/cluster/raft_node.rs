@@ -2,6 +2,14 @@
 //!
 //! Uses `openraft` for leader election and metadata consensus.
 //! Vector data replication uses separate TCP streaming (hybrid approach).
+//!
+//! Everything that must agree across nodes — collection definitions,
+//! aliases, shard assignments, node membership, and the Qdrant-compatible
+//! `/qdrant/cluster/metadata` key-value map — is applied through the
+//! replicated log. Because every write is ordered by the leader, two
+//! nodes racing to create the same collection with different configs
+//! cannot both succeed: the second entry is rejected when applied
+//! (see [`StateMachineData::apply_command`]).
 
 // Internal data-layout file: public fields are self-documenting; the
 // blanket allow keeps `cargo doc -W missing-docs` clean without padding
@@ -22,6 +30,7 @@ use openraft::alias::{
 };
 use openraft::entry::RaftEntry;
 use openraft::raft::StreamAppendResult;
+use openraft::rt::WatchReceiver;
 use openraft::storage::{
     EntryResponder, IOFlushed, LogState, RaftLogReader, RaftLogStorage, RaftSnapshotBuilder,
     RaftStateMachine,
@@ -81,6 +90,16 @@ pub enum ClusterCommand {
     },
     /// Remove a node from the cluster.
     RemoveNode { node_id: u64 },
+    /// Point an alias at an existing collection (create or retarget).
+    CreateAlias { alias: String, collection: String },
+    /// Remove an alias.
+    DeleteAlias { alias: String },
+    /// Set a cluster metadata key. `value` is JSON-encoded text because
+    /// the log travels over the positional bincode codec, which cannot
+    /// carry a self-describing `serde_json::Value`.
+    SetMetadata { key: String, value: String },
+    /// Remove a cluster metadata key.
+    DeleteMetadata { key: String },
 }
 
 impl std::fmt::Display for ClusterCommand {
@@ -94,6 +113,12 @@ impl std::fmt::Display for ClusterCommand {
             } => write!(f, "AssignShard({} → {})", shard_id, node_id),
             Self::AddNode { node_id, .. } => write!(f, "AddNode({})", node_id),
             Self::RemoveNode { node_id } => write!(f, "RemoveNode({})", node_id),
+            Self::CreateAlias { alias, collection } => {
+                write!(f, "CreateAlias({} → {})", alias, collection)
+            }
+            Self::DeleteAlias { alias } => write!(f, "DeleteAlias({})", alias),
+            Self::SetMetadata { key, .. } => write!(f, "SetMetadata({})", key),
+            Self::DeleteMetadata { key } => write!(f, "DeleteMetadata({})", key),
         }
     }
 }
@@ -105,6 +130,22 @@ pub struct ClusterResponse {
     pub message: String,
 }
 
+impl ClusterResponse {
+    fn ok(message: impl Into<String>) -> Self {
+        Self {
+            success: true,
+            message: message.into(),
+        }
+    }
+
+    fn rejected(message: impl Into<String>) -> Self {
+        Self {
+            success: false,
+            message: message.into(),
+        }
+    }
+}
+
 /// Node address information stored in Raft membership.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
 pub struct RaftNodeInfo {
@@ -131,6 +172,113 @@ pub struct StateMachineData {
     pub collections: BTreeMap<String, (usize, String)>,
     pub shard_assignments: BTreeMap<u32, (u64, u64)>,
     pub nodes: BTreeMap<u64, (String, u16)>,
+    /// alias -> collection. `#[serde(default)]` keeps snapshots taken
+    /// before aliases were replicated loadable.
+    #[serde(default)]
+    pub aliases: BTreeMap<String, String>,
+    /// Cluster metadata key -> JSON-encoded value.
+    #[serde(default)]
+    pub metadata: BTreeMap<String, String>,
+}
+
+impl StateMachineData {
+    /// Apply one committed command. Deterministic and side-effect free
+    /// beyond `self`, so every replica reaches the same state from the
+    /// same log.
+    ///
+    /// Conflicting writes are rejected rather than overwritten:
+    /// re-creating an existing collection with the same dimension and
+    /// metric is an idempotent success, but a different config returns
+    /// `success: false` so the losing proposer can surface a 409.
+    pub fn apply_command(&mut self, cmd: &ClusterCommand) -> ClusterResponse {
+        match cmd {
+            ClusterCommand::SetLeader { node_id } => {
+                self.leader_id = Some(*node_id);
+                ClusterResponse::ok(format!("leader set to {}", node_id))
+            }
+            ClusterCommand::CreateCollection {
+                name,
+                dimension,
+                metric,
+            } => match self.collections.get(name) {
+                Some((dim, m)) if dim == dimension && m == metric => {
+                    ClusterResponse::ok(format!("collection '{}' already exists", name))
+                }
+                Some((dim, m)) => ClusterResponse::rejected(format!(
+                    "collection '{}' already exists with dimension={} metric={}",
+                    name, dim, m
+                )),
+                None if self.aliases.contains_key(name) => ClusterResponse::rejected(format!(
+                    "collection name '{}' is already used by an alias",
+                    name
+                )),
+                None => {
+                    self.collections
+                        .insert(name.clone(), (*dimension, metric.clone()));
+                    info!(
+                        "Raft: collection '{}' created (dim={}, metric={})",
+                        name, dimension, metric
+                    );
+                    ClusterResponse::ok(format!("collection '{}' created", name))
+                }
+            },
+            ClusterCommand::DeleteCollection { name } => {
+                self.collections.remove(name);
+                self.aliases.retain(|_, target| target != name);
+                ClusterResponse::ok(format!("collection '{}' deleted", name))
+            }
+            ClusterCommand::AssignShard {
+                shard_id,
+                node_id,
+                epoch,
+            } => {
+                self.shard_assignments.insert(*shard_id, (*node_id, *epoch));
+                ClusterResponse::ok(format!(
+                    "shard {} → node {} (epoch {})",
+                    shard_id, node_id, epoch
+                ))
+            }
+            ClusterCommand::AddNode {
+                node_id,
+                address,
+                grpc_port,
+            } => {
+                self.nodes.insert(*node_id, (address.clone(), *grpc_port));
+                info!("Raft: node {} added ({}:{})", node_id, address, grpc_port);
+                ClusterResponse::ok(format!("node {} added", node_id))
+            }
+            ClusterCommand::RemoveNode { node_id } => {
+                self.nodes.remove(node_id);
+                ClusterResponse::ok(format!("node {} removed", node_id))
+            }
+            // The target's existence is checked against the local store by
+            // the proposer; collections that predate HA mode are not in
+            // `self.collections`, so only name collisions are enforced here.
+            ClusterCommand::CreateAlias { alias, collection } => {
+                if self.collections.contains_key(alias) {
+                    ClusterResponse::rejected(format!(
+                        "alias '{}' conflicts with an existing collection",
+                        alias
+                    ))
+                } else {
+                    self.aliases.insert(alias.clone(), collection.clone());
+                    ClusterResponse::ok(format!("alias '{}' → '{}'", alias, collection))
+                }
+            }
+            ClusterCommand::DeleteAlias { alias } => {
+                self.aliases.remove(alias);
+                ClusterResponse::ok(format!("alias '{}' deleted", alias))
+            }
+            ClusterCommand::SetMetadata { key, value } => {
+                self.metadata.insert(key.clone(), value.clone());
+                ClusterResponse::ok(format!("metadata '{}' set", key))
+            }
+            ClusterCommand::DeleteMetadata { key } => {
+                self.metadata.remove(key);
+                ClusterResponse::ok(format!("metadata '{}' deleted", key))
+            }
+        }
+    }
 }
 
 /// Snapshot stored in memory.
@@ -140,11 +288,18 @@ pub struct ClusterSnapshot {
     pub data: Vec<u8>,
 }
 
+/// Buffered applied commands per subscriber before a slow one lags.
+const APPLIED_CHANNEL_CAPACITY: usize = 1024;
+
 /// The Raft state machine for cluster metadata.
 pub struct ClusterStateMachine {
     sm: RwLock<StateMachineData>,
     snapshot_idx: parking_lot::Mutex<u64>,
     current_snapshot: RwLock<Option<ClusterSnapshot>>,
+    /// Fan-out of successfully applied commands, so node-local state
+    /// that lives outside the state machine (e.g. the `VectorStore`
+    /// collections and alias table) can follow the log. See [`Self::subscribe`].
+    applied: tokio::sync::broadcast::Sender<ClusterCommand>,
 }
 
 impl ClusterStateMachine {
@@ -153,9 +308,20 @@ impl ClusterStateMachine {
             sm: RwLock::new(StateMachineData::default()),
             snapshot_idx: parking_lot::Mutex::new(0),
             current_snapshot: RwLock::new(None),
+            applied: tokio::sync::broadcast::channel(APPLIED_CHANNEL_CAPACITY).0,
         }
     }
 
+    /// Subscribe to commands as they are applied on this node.
+    ///
+    /// Every node — leader and followers alike — publishes each committed
+    /// command that applied successfully, in log order. Installing a
+    /// snapshot publishes the collection and alias changes it implies as
+    /// the matching create / delete commands.
+    pub fn subscribe(&self) -> tokio::sync::broadcast::Receiver<ClusterCommand> {
+        self.applied.subscribe()
+    }
+
     /// Read current state (for external queries).
     pub async fn state(&self) -> StateMachineData {
         self.sm.read().await.clone()
@@ -235,71 +401,14 @@ impl RaftStateMachine<TypeConfig> for Arc<ClusterStateMachine> {
                     success: true,
                     message: "blank".into(),
                 },
-                EntryPayload::Normal(ref cmd) => match cmd {
-                    ClusterCommand::SetLeader { node_id } => {
-                        sm.leader_id = Some(*node_id);
-                        ClusterResponse {
-                            success: true,
-                            message: format!("leader set to {}", node_id),
-                        }
-                    }
-                    ClusterCommand::CreateCollection {
-                        name,
-                        dimension,
-                        metric,
-                    } => {
-                        sm.collections
-                            .insert(name.clone(), (*dimension, metric.clone()));
-                        info!(
-                            "Raft: collection '{}' created (dim={}, metric={})",
-                            name, dimension, metric
-                        );
-                        ClusterResponse {
-                            success: true,
-                            message: format!("collection '{}' created", name),
-                        }
-                    }
-                    ClusterCommand::DeleteCollection { name } => {
-                        sm.collections.remove(name);
-                        ClusterResponse {
-                            success: true,
-                            message: format!("collection '{}' deleted", name),
-                        }
+                EntryPayload::Normal(ref cmd) => {
+                    let response = sm.apply_command(cmd);
+                    if response.success {
+                        // No subscribers is the common case outside HA mode.
+                        let _ = self.applied.send(cmd.clone());
                     }
-                    ClusterCommand::AssignShard {
-                        shard_id,
-                        node_id,
-                        epoch,
-                    } => {
-                        sm.shard_assignments.insert(*shard_id, (*node_id, *epoch));
-                        ClusterResponse {
-                            success: true,
-                            message: format!(
-                                "shard {} → node {} (epoch {})",
-                                shard_id, node_id, epoch
-                            ),
-                        }
-                    }
-                    ClusterCommand::AddNode {
-                        node_id,
-                        address,
-                        grpc_port,
-                    } => {
-                        sm.nodes.insert(*node_id, (address.clone(), *grpc_port));
-                        info!("Raft: node {} added ({}:{})", node_id, address, grpc_port);
-                        ClusterResponse {
-                            success: true,
-                            message: format!("node {} added", node_id),
-                        }
-                    }
-                    ClusterCommand::RemoveNode { node_id } => {
-                        sm.nodes.remove(node_id);
-                        ClusterResponse {
-                            success: true,
-                            message: format!("node {} removed", node_id),
-                        }
-                    }
-                },
+                    response
+                }
                 EntryPayload::Membership(ref mem) => {
                     sm.last_membership =
                         StoredMembershipOf::<TypeConfig>::new(Some(entry.log_id), mem.clone());
@@ -333,7 +442,39 @@ impl RaftStateMachine<TypeConfig> for Arc<ClusterStateMachine> {
         let new_sm: StateMachineData = serde_json::from_slice(snapshot.get_ref())
             .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
 
-        *self.sm.write().await = new_sm;
+        let previous = std::mem::replace(&mut *self.sm.write().await, new_sm.clone());
+
+        for name in previous.collections.keys() {
+            if !new_sm.collections.contains_key(name) {
+                let _ = self
+                    .applied
+                    .send(ClusterCommand::DeleteCollection { name: name.clone() });
+            }
+        }
+        for (name, (dimension, metric)) in &new_sm.collections {
+            if previous.collections.get(name) != Some(&(*dimension, metric.clone())) {
+                let _ = self.applied.send(ClusterCommand::CreateCollection {
+                    name: name.clone(),
+                    dimension: *dimension,
+                    metric: metric.clone(),
+                });
+            }
+        }
+        for alias in previous.aliases.keys() {
+            if !new_sm.aliases.contains_key(alias) {
+                let _ = self.applied.send(ClusterCommand::DeleteAlias {
+                    alias: alias.clone(),
+                });
+            }
+        }
+        for (alias, collection) in &new_sm.aliases {
+            if previous.aliases.get(alias) != Some(collection) {
+                let _ = self.applied.send(ClusterCommand::CreateAlias {
+                    alias: alias.clone(),
+                    collection: collection.clone(),
+                });
+            }
+        }
 
         let snap = ClusterSnapshot {
             meta: meta.clone(),
@@ -501,7 +642,8 @@ impl ClusterRaftNetwork {
 /// changes (pods restart with new IPs in Kubernetes). The `connect_timeout`
 /// prevents hanging when the peer isn't ready yet.
 pub struct ClusterRaftConnection {
-    /// Full gRPC endpoint URL, e.g. "http://host:15003".
+    /// Peer gRPC address, e.g. "host:15003". The scheme follows the
+    /// listener TLS setting (see `security::tls::peer_endpoint`).
     target_addr: String,
 }
 
@@ -509,7 +651,7 @@ impl openraft::network::RaftNetworkFactory<TypeConfig> for ClusterRaftNetwork {
     type Network = ClusterRaftConnection;
 
     async fn new_client(&mut self, target: u64, node: &RaftNodeInfo) -> Self::Network {
-        let addr = format!("http://{}:{}", node.address, node.grpc_port);
+        let addr = format!("{}:{}", node.address, node.grpc_port);
         info!(
             target_node = target,
             target_addr = %addr,
@@ -531,7 +673,7 @@ impl openraft::network::v2::RaftNetworkV2<TypeConfig> for ClusterRaftConnection
             openraft::error::RPCError::Network(openraft::error::NetworkError::new(&e))
         })?;
 
-        let channel = tonic::transport::Channel::from_shared(self.target_addr.clone())
+        let channel = crate::security::tls::peer_endpoint(&self.target_addr)
             .map_err(|e| {
                 openraft::error::RPCError::Network(openraft::error::NetworkError::new(&e))
             })?
@@ -577,7 +719,7 @@ impl openraft::network::v2::RaftNetworkV2<TypeConfig> for ClusterRaftConnection
             openraft::error::RPCError::Network(openraft::error::NetworkError::new(&e))
         })?;
 
-        let channel = tonic::transport::Channel::from_shared(self.target_addr.clone())
+        let channel = crate::security::tls::peer_endpoint(&self.target_addr)
             .map_err(|e| {
                 openraft::error::RPCError::Network(openraft::error::NetworkError::new(&e))
             })?
@@ -636,7 +778,7 @@ impl openraft::network::v2::RaftNetworkV2<TypeConfig> for ClusterRaftConnection
         // Consume the cursor to get the raw snapshot bytes.
         let snapshot_data = snapshot.snapshot.into_inner();
 
-        let channel = tonic::transport::Channel::from_shared(self.target_addr.clone())
+        let channel = crate::security::tls::peer_endpoint(&self.target_addr)
             .map_err(|e| {
                 openraft::error::StreamingError::Network(openraft::error::NetworkError::new(&e))
             })?
@@ -810,6 +952,11 @@ impl RaftManager {
         self.state_machine.state().await
     }
 
+    /// Node id of the current leader as seen by this node, if known.
+    pub fn current_leader(&self) -> Option<u64> {
+        self.raft.metrics().borrow_watched().current_leader
+    }
+
     /// Check if this node believes it is the leader.
     pub async fn is_leader(&self) -> bool {
         self.raft
@@ -849,6 +996,8 @@ mod tests {
             collections: BTreeMap::from([("test".into(), (128, "cosine".into()))]),
             shard_assignments: BTreeMap::from([(0, (1, 5))]),
             nodes: BTreeMap::from([(1, ("localhost".into(), 15003))]),
+            aliases: BTreeMap::from([("latest".into(), "test".into())]),
+            metadata: BTreeMap::from([("region".into(), "\"eu\"".into())]),
         };
 
         let json = serde_json::to_string(&data).unwrap();
@@ -857,6 +1006,90 @@ mod tests {
         assert_eq!(recovered.leader_id, Some(1));
         assert_eq!(recovered.collections.len(), 1);
         assert_eq!(recovered.nodes.len(), 1);
+        assert_eq!(recovered.aliases.get("latest").unwrap(), "test");
+        assert_eq!(recovered.metadata.get("region").unwrap(), "\"eu\"");
+    }
+
+    #[test]
+    fn test_state_machine_data_deserializes_without_new_fields() {
+        let mut value = serde_json::to_value(StateMachineData::default()).unwrap();
+        let obj = value.as_object_mut().unwrap();
+        obj.remove("aliases");
+        obj.remove("metadata");
+
+        let recovered: StateMachineData = serde_json::from_value(value).unwrap();
+        assert!(recovered.aliases.is_empty());
+        assert!(recovered.metadata.is_empty());
+    }
+
+    #[test]
+    fn test_apply_create_collection_rejects_conflicting_config() {
+        let mut sm = StateMachineData::default();
+        let create = |dimension, metric: &str| ClusterCommand::CreateCollection {
+            name: "docs".into(),
+            dimension,
+            metric: metric.into(),
+        };
+
+        assert!(sm.apply_command(&create(128, "cosine")).success);
+        // Same definition replayed from another node is idempotent.
+        assert!(sm.apply_command(&create(128, "cosine")).success);
+        // A racing create with a different shape loses.
+        let resp = sm.apply_command(&create(256, "euclidean"));
+        assert!(!resp.success);
+        assert!(resp.message.contains("already exists"));
+        assert_eq!(sm.collections.get("docs"), Some(&(128, "cosine".into())));
+    }
+
+    #[test]
+    fn test_apply_aliases() {
+        let mut sm = StateMachineData::default();
+        sm.apply_command(&ClusterCommand::CreateCollection {
+            name: "docs".into(),
+            dimension: 64,
+            metric: "cosine".into(),
+        });
+
+        let alias = ClusterCommand::CreateAlias {
+            alias: "latest".into(),
+            collection: "docs".into(),
+        };
+        assert!(sm.apply_command(&alias).success);
+        assert_eq!(sm.aliases.get("latest").unwrap(), "docs");
+
+        // An alias may not shadow a replicated collection, and vice versa.
+        let shadow = ClusterCommand::CreateAlias {
+            alias: "docs".into(),
+            collection: "docs".into(),
+        };
+        assert!(!sm.apply_command(&shadow).success);
+        let clash = ClusterCommand::CreateCollection {
+            name: "latest".into(),
+            dimension: 64,
+            metric: "cosine".into(),
+        };
+        assert!(!sm.apply_command(&clash).success);
+
+        // Dropping the collection drops aliases pointing at it.
+        sm.apply_command(&ClusterCommand::DeleteCollection {
+            name: "docs".into(),
+        });
+        assert!(sm.aliases.is_empty());
+    }
+
+    #[test]
+    fn test_apply_metadata() {
+        let mut sm = StateMachineData::default();
+        sm.apply_command(&ClusterCommand::SetMetadata {
+            key: "region".into(),
+            value: "\"eu-west\"".into(),
+        });
+        assert_eq!(sm.metadata.get("region").unwrap(), "\"eu-west\"");
+
+        sm.apply_command(&ClusterCommand::DeleteMetadata {
+            key: "region".into(),
+        });
+        assert!(sm.metadata.is_empty());
     }
 
     #[tokio::test]
@@ -4,6 +4,12 @@
 //! reacts to leadership changes by calling [`HaManager::on_become_leader`] or
 //! [`HaManager::on_become_follower`], updating the [`LeaderRouter`], and
 //! ensuring the replication data-plane always matches the Raft consensus role.
+//!
+//! It also follows the applied log for metadata that lives in the local
+//! [`VectorStore`] rather than in the state machine: committed collection
+//! and alias commands are applied to every node's store, so a follower
+//! learns a collection from the log even before the TCP replication
+//! stream catches up.
 
 // Internal data-layout file: public fields are self-documenting; the
 // blanket allow keeps `cargo doc -W missing-docs` clean without padding
@@ -16,11 +22,15 @@ use std::sync::Arc;
 use openraft::ServerState;
 // WatchReceiver trait must be in scope for `borrow_watched()` and `changed()`.
 use openraft::rt::WatchReceiver;
+use tokio::sync::broadcast;
 use tokio::task::JoinHandle;
-use tracing::{error, info, warn};
+use tracing::{debug, error, info, warn};
 
 use super::ha_manager::HaManager;
-use super::raft_node::{RaftManager, TypeConfig};
+use super::raft_node::{ClusterCommand, RaftManager, TypeConfig};
+use crate::db::VectorStore;
+use crate::error::VectorizerError;
+use crate::models::{CollectionConfig, DistanceMetric};
 
 /// Default replication port used by the master TCP listener.
 const DEFAULT_REPLICATION_PORT: u16 = 7001;
@@ -36,6 +46,7 @@ const DEFAULT_HTTP_PORT: u16 = 15002;
 /// 2. Detects transitions: Follower → Leader, Leader → Follower, etc.
 /// 3. Calls `HaManager::on_become_leader()` / `on_become_follower()` accordingly.
 /// 4. Updates the `LeaderRouter` so write requests are routed correctly.
+/// 5. Applies committed alias commands to the local `VectorStore`.
 pub struct RaftWatcher {
     raft_manager: Arc<RaftManager>,
     ha_manager: Arc<HaManager>,
@@ -76,6 +87,9 @@ impl RaftWatcher {
         let state_machine = self.raft_manager.state_machine.clone();
         let http_port = self.http_port;
 
+        // Subscribe before spawning so no command applied in between is missed.
+        spawn_store_applier(state_machine.subscribe(), ha.store().clone());
+
         tokio::spawn(async move {
             info!(
                 node_id,
@@ -235,6 +249,96 @@ impl RaftWatcher {
     }
 }
 
+/// Apply committed collection and alias commands from `applied` to
+/// `store` until the state machine is dropped.
+///
+/// The proposing node has already updated its own store before proposing,
+/// so commands that are already reflected locally are no-ops.
+pub fn spawn_store_applier(
+    mut applied: broadcast::Receiver<ClusterCommand>,
+    store: Arc<VectorStore>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            match applied.recv().await {
+                Ok(cmd) => apply_store_command(&store, &cmd),
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!(
+                        skipped,
+                        "Raft store applier lagged; some collection or alias changes were missed"
+                    );
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    })
+}
+
+/// Bring `store` in line with one applied command. Only collection and
+/// alias commands touch the store; the rest are ignored.
+///
+/// A collection created from the log carries just the dimension and
+/// metric the log records; the replication stream fills in its vectors.
+pub fn apply_store_command(store: &VectorStore, cmd: &ClusterCommand) {
+    match cmd {
+        ClusterCommand::CreateCollection {
+            name,
+            dimension,
+            metric,
+        } => {
+            if store.has_collection_in_memory(name) {
+                return;
+            }
+            let metric = match metric.as_str() {
+                "cosine" => DistanceMetric::Cosine,
+                "euclidean" => DistanceMetric::Euclidean,
+                "dot_product" => DistanceMetric::DotProduct,
+                other => {
+                    warn!(name, metric = other, "Raft: unknown collection metric");
+                    return;
+                }
+            };
+            let config = CollectionConfig {
+                dimension: *dimension,
+                metric,
+                ..Default::default()
+            };
+            match store.create_collection(name, config) {
+                Ok(()) => debug!(name, "Raft: collection applied"),
+                // The replication stream got there first.
+                Err(VectorizerError::CollectionAlreadyExists(_)) => {}
+                Err(e) => warn!(name, "Raft: failed to apply collection: {}", e),
+            }
+        }
+        ClusterCommand::DeleteCollection { name } => match store.delete_collection(name) {
+            Ok(()) | Err(VectorizerError::CollectionNotFound(_)) => {}
+            Err(e) => warn!(name, "Raft: failed to delete collection: {}", e),
+        },
+        ClusterCommand::CreateAlias { alias, collection } => {
+            let current = store
+                .list_aliases()
+                .into_iter()
+                .find_map(|(name, target)| (name == *alias).then_some(target));
+            if current.as_deref() == Some(collection.as_str()) {
+                return;
+            }
+            // Retarget: the store refuses to overwrite an existing alias.
+            if current.is_some() {
+                let _ = store.delete_alias(alias);
+            }
+            match store.create_alias(alias, collection) {
+                Ok(()) => debug!(alias, collection, "Raft: alias applied"),
+                Err(e) => warn!(alias, collection, "Raft: failed to apply alias: {}", e),
+            }
+        }
+        ClusterCommand::DeleteAlias { alias } => match store.delete_alias(alias) {
+            Ok(()) | Err(VectorizerError::NotFound(_)) => {}
+            Err(e) => warn!(alias, "Raft: failed to delete alias: {}", e),
+        },
+        _ => {}
+    }
+}
+
 /// Resolve the leader's IP/hostname from the Raft state machine.
 ///
 /// Reads the address from the openraft membership config — populated by
@@ -55,11 +55,17 @@ impl ClusterClient {
             node_id, address
         );
 
-        let client = ClusterServiceClient::connect(format!("http://{}", address))
+        let connect = async {
+            crate::security::tls::peer_endpoint(address)?
+                .connect()
+                .await
+        };
+        let channel = connect
             .await
             .map_err(|e| VectorizerError::InvalidConfiguration {
                 message: format!("Failed to connect to cluster server {}: {}", address, e),
             })?;
+        let client = ClusterServiceClient::new(channel);
 
         Ok(Self {
             client,
@@ -771,6 +777,28 @@ impl ClusterClient {
             }
         }
     }
+
+    /// Run one phase of a cluster snapshot on the remote node.
+    ///
+    /// Returns the raw response; callers check its `success` flag.
+    pub async fn snapshot_phase(
+        &self,
+        request: cluster_proto::SnapshotPhaseRequest,
+    ) -> Result<cluster_proto::SnapshotPhaseResponse> {
+        let mut client = self.client.clone();
+        let phase = request.phase;
+
+        match client.snapshot_phase(tonic::Request::new(request)).await {
+            Ok(response) => Ok(response.into_inner()),
+            Err(e) => {
+                error!(
+                    "Snapshot phase {} failed on node {}: {}",
+                    phase, self.node_id, e
+                );
+                Err(VectorizerError::Storage(format!("gRPC error: {}", e)))
+            }
+        }
+    }
 }
 
 /// How long a cached client is considered healthy without re-checking.
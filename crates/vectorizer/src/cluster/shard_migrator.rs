@@ -10,6 +10,10 @@
 //! 2. Insert each batch into the target node via `RemoteInsertVector` gRPC (or the local
 //!    `VectorStore`).
 //! 3. Track progress in-memory so callers can observe ongoing migrations.
+//!
+//! Progress lives in a process-wide registry shared by every migrator, so
+//! [`shard_transfers`] reports in-flight moves to status endpoints that
+//! don't hold the migrator that started them.
 
 // Internal data-layout file: public fields are self-documenting; the
 // blanket allow keeps `cargo doc -W missing-docs` clean without padding
@@ -92,6 +96,9 @@ pub struct MigrationProgress {
     pub migration_id: String,
     /// The shard being migrated.
     pub shard_id: u32,
+    /// Collection the shard belongs to.
+    #[serde(default)]
+    pub collection: String,
     /// Node the shard is migrating from.
     pub from_node: String,
     /// Node the shard is migrating to.
@@ -106,6 +113,30 @@ pub struct MigrationProgress {
     pub started_at: DateTime<Utc>,
 }
 
+/// Process-wide registry of migrations, shared by every [`ShardMigrator`].
+static MIGRATIONS: std::sync::OnceLock<Arc<RwLock<HashMap<String, MigrationProgress>>>> =
+    std::sync::OnceLock::new();
+
+fn migration_registry() -> &'static Arc<RwLock<HashMap<String, MigrationProgress>>> {
+    MIGRATIONS.get_or_init(|| Arc::new(RwLock::new(HashMap::new())))
+}
+
+/// Migrations that are queued or transferring right now, across every
+/// migrator in the process.
+pub fn shard_transfers() -> Vec<MigrationProgress> {
+    migration_registry()
+        .read()
+        .values()
+        .filter(|m| {
+            matches!(
+                m.status,
+                MigrationStatus::Pending | MigrationStatus::InProgress
+            )
+        })
+        .cloned()
+        .collect()
+}
+
 // ---------------------------------------------------------------------------
 // Migration result
 // ---------------------------------------------------------------------------
@@ -163,7 +194,7 @@ impl ShardMigrator {
             client_pool,
             store,
             local_node_id,
-            active_migrations: Arc::new(RwLock::new(HashMap::new())),
+            active_migrations: Arc::clone(migration_registry()),
         }
     }
 
@@ -222,6 +253,7 @@ impl ShardMigrator {
                 MigrationProgress {
                     migration_id: migration_id.clone(),
                     shard_id: shard_id.as_u32(),
+                    collection: collection_name.to_string(),
                     from_node: from_node_id.as_str().to_string(),
                     to_node: to_node_id.as_str().to_string(),
                     vectors_transferred: 0,
@@ -488,6 +520,7 @@ impl ShardMigrator {
                     reason: e.to_string(),
                 })?;
         }
+        self.store.notify_mutation(collection_name);
         Ok(())
     }
 
@@ -260,6 +260,18 @@ impl DistributedShardRouter {
         shard_to_node.len()
     }
 
+    /// Snapshot of the hash ring as `(point, shard)` pairs in ring order.
+    ///
+    /// A vector belongs to the shard at the first point at or after the
+    /// xxh3-64 hash of its id, wrapping to the first point. Published to
+    /// clients so they can route requests to the owning node directly.
+    pub fn ring_points(&self) -> Vec<(u64, ShardId)> {
+        let ring = self.ring.read();
+        ring.iter()
+            .map(|(hash, (shard_id, _))| (*hash, *shard_id))
+            .collect()
+    }
+
     /// Rebalance shards across nodes (simple round-robin for now)
     pub fn rebalance(&self, shard_ids: &[ShardId], node_ids: &[NodeId]) {
         if node_ids.is_empty() {
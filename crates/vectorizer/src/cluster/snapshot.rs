@@ -0,0 +1,663 @@
+//! Coordinated cluster snapshots.
+//!
+//! Snapshots taken node by node land at different moments, so restoring
+//! them together gives back a cluster state that never existed: a write
+//! made between two nodes' snapshots is on one and missing from the
+//! other. A cluster snapshot takes every node's part at one cut, in two
+//! phases run by [`ClusterSnapshotCoordinator`]:
+//!
+//! 1. **Prepare** — every active node raises its
+//!    [`WriteFence`](crate::db::WriteFence) and waits for its in-flight
+//!    writes to drain. If any node fails, every node is told to abort
+//!    and lowers its fence.
+//! 2. **Commit** — with the whole cluster fenced, each node writes its
+//!    part and lowers its fence.
+//!
+//! A node's part holds its collections whole, except distributed
+//! collections, of which it holds only the shards it owns. The
+//! coordinator records the set in a manifest together with the shard
+//! assignment it was taken under, and restores a set only while that
+//! assignment still holds, so every shard comes back to the node that
+//! saved it.
+//!
+//! Layout: `<data_dir>/cluster_snapshots/<snapshot_id>/<node_id>.json.gz`
+//! on every node, plus `manifest.json` next to the coordinator's part.
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+use vectorizer_protocol::grpc_gen::cluster::{
+    SnapshotPhase, SnapshotPhaseRequest, SnapshotPhaseResponse,
+};
+
+use super::manager::ClusterManager;
+use super::node::{ClusterNode, NodeId};
+use super::server_client::ClusterClientPool;
+use crate::db::sharding::ShardId;
+use crate::db::{CollectionType, VectorStore};
+use crate::error::{Result, VectorizerError};
+use crate::persistence::{PersistedCollection, PersistedVector};
+
+/// How long a node keeps its write fence up when the coordinator never
+/// finishes the snapshot.
+pub const DEFAULT_FENCE_TIMEOUT: Duration = Duration::from_secs(30);
+
+const MANIFEST_FILE: &str = "manifest.json";
+
+/// One node's part of a cluster snapshot, as written to disk.
+#[derive(Serialize, Deserialize)]
+struct NodePart {
+    snapshot_id: String,
+    node_id: String,
+    created_at: DateTime<Utc>,
+    /// Collections held whole on this node
+    collections: Vec<PersistedCollection>,
+    /// Distributed collections, with only this node's shards
+    distributed: Vec<DistributedPart>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct DistributedPart {
+    name: String,
+    shards: BTreeMap<u32, Vec<PersistedVector>>,
+}
+
+/// What one node saved (or restored) for a cluster snapshot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeSnapshotInfo {
+    /// Node the part belongs to
+    pub node_id: String,
+    /// Collections in the part
+    pub collections: Vec<String>,
+    /// Vectors in the part (local shards only, for distributed collections)
+    pub vector_count: u64,
+    /// Size of the part on disk
+    pub size_bytes: u64,
+}
+
+/// A complete, restorable cluster snapshot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClusterSnapshotManifest {
+    /// Snapshot ID (UTC timestamp of the cut)
+    pub id: String,
+    /// When every node had written its part
+    pub created_at: DateTime<Utc>,
+    /// Node that coordinated the snapshot and keeps this manifest
+    pub coordinator: String,
+    /// Shard router epoch at the cut
+    pub epoch: u64,
+    /// Owner of every shard at the cut (shard ID -> node ID)
+    pub shard_to_node: BTreeMap<u32, String>,
+    /// Part saved by each node
+    pub nodes: Vec<NodeSnapshotInfo>,
+}
+
+/// Outcome of restoring a cluster snapshot on one node.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeRestoreResult {
+    /// Node that restored its part
+    pub node_id: String,
+    /// Whether the node restored its part
+    pub success: bool,
+    /// Why the node failed, if it did
+    pub message: String,
+    /// Vectors restored on the node
+    pub vector_count: u64,
+}
+
+/// Directory holding this node's cluster snapshots.
+pub fn snapshot_root() -> PathBuf {
+    VectorStore::get_data_dir().join("cluster_snapshots")
+}
+
+/// Snapshot IDs name directories on every node, so they are kept to
+/// characters that are safe in a path component.
+fn check_snapshot_id(snapshot_id: &str) -> Result<()> {
+    let valid = !snapshot_id.is_empty()
+        && snapshot_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(VectorizerError::InvalidConfiguration {
+            message: format!("invalid cluster snapshot id '{}'", snapshot_id),
+        })
+    }
+}
+
+fn part_path(root: &Path, snapshot_id: &str, node_id: &str) -> PathBuf {
+    root.join(snapshot_id).join(format!("{}.json.gz", node_id))
+}
+
+/// Write this node's part of `snapshot_id` under `root`. The caller
+/// holds the write fence, so the part is taken at the snapshot's cut.
+pub fn write_node_part(
+    store: &VectorStore,
+    root: &Path,
+    snapshot_id: &str,
+    node_id: &str,
+) -> Result<NodeSnapshotInfo> {
+    check_snapshot_id(snapshot_id)?;
+
+    let mut part = NodePart {
+        snapshot_id: snapshot_id.to_string(),
+        node_id: node_id.to_string(),
+        created_at: Utc::now(),
+        collections: Vec::new(),
+        distributed: Vec::new(),
+    };
+    let mut vector_count = 0u64;
+    let mut names = store.list_collections();
+    names.sort();
+    for name in &names {
+        let Ok(collection) = store.get_collection(name) else {
+            continue;
+        };
+        match &*collection {
+            CollectionType::DistributedSharded(c) => {
+                let shards = c
+                    .local_shard_vectors()
+                    .into_iter()
+                    .map(|(shard_id, vectors)| {
+                        vector_count += vectors.len() as u64;
+                        let vectors: Vec<PersistedVector> =
+                            vectors.into_iter().map(PersistedVector::from).collect();
+                        (shard_id.as_u32(), vectors)
+                    })
+                    .collect();
+                part.distributed.push(DistributedPart {
+                    name: name.clone(),
+                    shards,
+                });
+            }
+            other => {
+                let vectors: Vec<PersistedVector> = other
+                    .get_all_vectors()
+                    .into_iter()
+                    .map(PersistedVector::from)
+                    .collect();
+                vector_count += vectors.len() as u64;
+                part.collections.push(PersistedCollection {
+                    name: name.clone(),
+                    config: Some(other.metadata().config),
+                    vectors,
+                    hnsw_dump_basename: None,
+                    recall_profile: other.recall_profile(),
+                    feedback: other.feedback(),
+                    metadata_kv: other.metadata_kv(),
+                    hybrid_tuning: other.hybrid_tuning(),
+                    overrides: other.overrides(),
+                });
+            }
+        }
+    }
+
+    let path = part_path(root, snapshot_id, node_id);
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let json = serde_json::to_vec(&part)?;
+    let mut encoder = GzEncoder::new(std::fs::File::create(&path)?, Compression::default());
+    encoder.write_all(&json)?;
+    encoder.finish()?;
+    let size_bytes = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+
+    info!(
+        "Cluster snapshot '{}': node {} saved {} collections, {} vectors ({} bytes)",
+        snapshot_id,
+        node_id,
+        names.len(),
+        vector_count,
+        size_bytes
+    );
+    Ok(NodeSnapshotInfo {
+        node_id: node_id.to_string(),
+        collections: names,
+        vector_count,
+        size_bytes,
+    })
+}
+
+/// Replace this node's data with its part of `snapshot_id`.
+///
+/// Collections saved whole are recreated from the part. Distributed
+/// collections must already exist (the cluster recreates them with
+/// their topology); only their local shards are replaced. Collections
+/// created after the snapshot are left alone.
+pub fn restore_node_part(
+    store: &VectorStore,
+    root: &Path,
+    snapshot_id: &str,
+    node_id: &str,
+) -> Result<NodeSnapshotInfo> {
+    check_snapshot_id(snapshot_id)?;
+    let path = part_path(root, snapshot_id, node_id);
+    if !path.exists() {
+        return Err(VectorizerError::NotFound(format!(
+            "node {} has no part of cluster snapshot '{}'",
+            node_id, snapshot_id
+        )));
+    }
+    let mut json = Vec::new();
+    GzDecoder::new(std::fs::File::open(&path)?).read_to_end(&mut json)?;
+    let part: NodePart = serde_json::from_slice(&json)?;
+
+    // Check every distributed collection before replacing anything
+    for dist in &part.distributed {
+        let collection = store.get_collection(&dist.name)?;
+        if !matches!(&*collection, CollectionType::DistributedSharded(_)) {
+            return Err(VectorizerError::Storage(format!(
+                "collection '{}' is no longer distributed; cannot restore its shards",
+                dist.name
+            )));
+        }
+    }
+
+    let mut names = Vec::new();
+    let mut vector_count = 0u64;
+    for pc in part.collections {
+        vector_count += pc.vectors.len() as u64;
+        // Drop the current collection if present (ignore not-found errors)
+        let _ = store.delete_collection(&pc.name);
+        store.create_collection_with_quantization(&pc.name, pc.config.unwrap_or_default())?;
+        if !pc.vectors.is_empty() {
+            store.load_collection_from_cache(&pc.name, pc.vectors)?;
+        }
+        names.push(pc.name);
+    }
+    for dist in part.distributed {
+        let mut shards = HashMap::with_capacity(dist.shards.len());
+        for (shard_id, vectors) in dist.shards {
+            vector_count += vectors.len() as u64;
+            let vectors = vectors
+                .into_iter()
+                .map(PersistedVector::into_runtime)
+                .collect::<Result<Vec<_>>>()?;
+            shards.insert(ShardId::new(shard_id), vectors);
+        }
+        let collection = store.get_collection(&dist.name)?;
+        if let CollectionType::DistributedSharded(c) = &*collection {
+            c.replace_local_shards(shards)?;
+        }
+        names.push(dist.name);
+    }
+    names.sort();
+
+    info!(
+        "Cluster snapshot '{}': node {} restored {} collections, {} vectors",
+        snapshot_id,
+        node_id,
+        names.len(),
+        vector_count
+    );
+    Ok(NodeSnapshotInfo {
+        node_id: node_id.to_string(),
+        collections: names,
+        vector_count,
+        size_bytes: std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0),
+    })
+}
+
+/// Run one phase of a cluster snapshot on this node. Failures are
+/// reported in the response rather than as an error, the same way the
+/// other cluster RPCs report them.
+pub async fn handle_phase(
+    store: &Arc<VectorStore>,
+    root: &Path,
+    node_id: &str,
+    request: SnapshotPhaseRequest,
+) -> SnapshotPhaseResponse {
+    let snapshot_id = request.snapshot_id;
+    let fence = store.write_fence();
+    let result = match SnapshotPhase::try_from(request.phase) {
+        Ok(SnapshotPhase::Prepare) => {
+            let timeout = match request.fence_timeout_ms {
+                0 => DEFAULT_FENCE_TIMEOUT,
+                ms => Duration::from_millis(ms),
+            };
+            fence.raise(&snapshot_id, timeout).await.map(|()| None)
+        }
+        Ok(phase @ (SnapshotPhase::Commit | SnapshotPhase::Restore)) => {
+            let (store, root) = (store.clone(), root.to_path_buf());
+            let (id, node) = (snapshot_id.clone(), node_id.to_string());
+            let result = tokio::task::spawn_blocking(move || {
+                if phase == SnapshotPhase::Commit {
+                    write_node_part(&store, &root, &id, &node)
+                } else {
+                    restore_node_part(&store, &root, &id, &node)
+                }
+            })
+            .await
+            .unwrap_or_else(|e| Err(VectorizerError::InternalError(e.to_string())));
+            // The fence comes down whether or not the part was written;
+            // the coordinator treats a failed node as a failed snapshot
+            fence.lower(&snapshot_id);
+            result.map(Some)
+        }
+        Ok(SnapshotPhase::Abort) => {
+            fence.lower(&snapshot_id);
+            Ok(None)
+        }
+        Err(_) => Err(VectorizerError::InvalidConfiguration {
+            message: format!("unknown snapshot phase {}", request.phase),
+        }),
+    };
+
+    match result {
+        Ok(info) => {
+            let info = info.unwrap_or_else(|| NodeSnapshotInfo {
+                node_id: node_id.to_string(),
+                collections: Vec::new(),
+                vector_count: 0,
+                size_bytes: 0,
+            });
+            SnapshotPhaseResponse {
+                success: true,
+                message: String::new(),
+                node_id: node_id.to_string(),
+                collections: info.collections,
+                vector_count: info.vector_count,
+                size_bytes: info.size_bytes,
+            }
+        }
+        Err(e) => {
+            warn!(
+                "Cluster snapshot '{}' phase {} failed on node {}: {}",
+                snapshot_id, request.phase, node_id, e
+            );
+            SnapshotPhaseResponse {
+                success: false,
+                message: e.to_string(),
+                node_id: node_id.to_string(),
+                ..Default::default()
+            }
+        }
+    }
+}
+
+/// Takes and restores cluster snapshots from this node.
+#[derive(Clone)]
+pub struct ClusterSnapshotCoordinator {
+    store: Arc<VectorStore>,
+    cluster_manager: Arc<ClusterManager>,
+    client_pool: Arc<ClusterClientPool>,
+    root: PathBuf,
+    fence_timeout: Duration,
+}
+
+impl ClusterSnapshotCoordinator {
+    /// Coordinator writing under [`snapshot_root`].
+    pub fn new(
+        store: Arc<VectorStore>,
+        cluster_manager: Arc<ClusterManager>,
+        client_pool: Arc<ClusterClientPool>,
+    ) -> Self {
+        Self {
+            store,
+            cluster_manager,
+            client_pool,
+            root: snapshot_root(),
+            fence_timeout: DEFAULT_FENCE_TIMEOUT,
+        }
+    }
+
+    /// Write snapshots under `root` instead of [`snapshot_root`].
+    /// Remote nodes always use their own default.
+    pub fn with_root(mut self, root: PathBuf) -> Self {
+        self.root = root;
+        self
+    }
+
+    /// How long each node's write fence may stay up.
+    pub fn with_fence_timeout(mut self, fence_timeout: Duration) -> Self {
+        self.fence_timeout = fence_timeout;
+        self
+    }
+
+    /// Take a snapshot of the whole cluster at one cut.
+    pub async fn create(&self) -> Result<ClusterSnapshotManifest> {
+        let snapshot_id = Utc::now().format("%Y%m%dT%H%M%S%3fZ").to_string();
+        let nodes = self.cluster_manager.get_active_nodes();
+        info!(
+            "Cluster snapshot '{}': fencing writes on {} nodes",
+            snapshot_id,
+            nodes.len()
+        );
+
+        self.prepare(&nodes, &snapshot_id).await?;
+
+        // Read the shard assignment under the fence so the manifest
+        // matches the data the nodes write
+        let router = self.cluster_manager.shard_router();
+        let epoch = router.current_epoch();
+        let shard_to_node = router
+            .get_all_shards()
+            .into_iter()
+            .filter_map(|shard_id| {
+                let node = router.get_node_for_shard(&shard_id)?;
+                Some((shard_id.as_u32(), node.as_str().to_string()))
+            })
+            .collect();
+
+        let results = self
+            .run_on_all(&nodes, &snapshot_id, SnapshotPhase::Commit)
+            .await;
+        let mut parts = Vec::with_capacity(results.len());
+        let mut failures = Vec::new();
+        for (node, result) in nodes.iter().zip(results) {
+            match result {
+                Ok(resp) => parts.push(NodeSnapshotInfo {
+                    node_id: resp.node_id,
+                    collections: resp.collections,
+                    vector_count: resp.vector_count,
+                    size_bytes: resp.size_bytes,
+                }),
+                Err(e) => failures.push(format!("{}: {}", node.id, e)),
+            }
+        }
+        if !failures.is_empty() {
+            // Nodes that never answered may still be fenced
+            self.run_on_all(&nodes, &snapshot_id, SnapshotPhase::Abort)
+                .await;
+            return Err(VectorizerError::Storage(format!(
+                "cluster snapshot '{}' failed on {}",
+                snapshot_id,
+                failures.join("; ")
+            )));
+        }
+
+        let manifest = ClusterSnapshotManifest {
+            id: snapshot_id.clone(),
+            created_at: Utc::now(),
+            coordinator: self.cluster_manager.local_node_id().as_str().to_string(),
+            epoch,
+            shard_to_node,
+            nodes: parts,
+        };
+        let dir = self.root.join(&snapshot_id);
+        std::fs::create_dir_all(&dir)?;
+        std::fs::write(
+            dir.join(MANIFEST_FILE),
+            serde_json::to_vec_pretty(&manifest)?,
+        )?;
+        info!(
+            "Cluster snapshot '{}' complete: {} nodes, {} vectors",
+            snapshot_id,
+            manifest.nodes.len(),
+            manifest.nodes.iter().map(|n| n.vector_count).sum::<u64>()
+        );
+        Ok(manifest)
+    }
+
+    /// Cluster snapshots coordinated from this node, newest first.
+    pub fn list(&self) -> Result<Vec<ClusterSnapshotManifest>> {
+        if !self.root.exists() {
+            return Ok(Vec::new());
+        }
+        let mut manifests: Vec<ClusterSnapshotManifest> = std::fs::read_dir(&self.root)?
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let bytes = std::fs::read(entry.path().join(MANIFEST_FILE)).ok()?;
+                serde_json::from_slice(&bytes).ok()
+            })
+            .collect();
+        manifests.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        Ok(manifests)
+    }
+
+    /// The manifest of `snapshot_id`.
+    pub fn get(&self, snapshot_id: &str) -> Result<ClusterSnapshotManifest> {
+        check_snapshot_id(snapshot_id)?;
+        let path = self.root.join(snapshot_id).join(MANIFEST_FILE);
+        if !path.exists() {
+            return Err(VectorizerError::NotFound(format!(
+                "cluster snapshot '{}' not found",
+                snapshot_id
+            )));
+        }
+        Ok(serde_json::from_slice(&std::fs::read(path)?)?)
+    }
+
+    /// Restore every node from `snapshot_id`.
+    ///
+    /// Refused unless every node of the snapshot is active and owns the
+    /// same shards it did at the cut. Writes are fenced across those
+    /// nodes for the whole restore.
+    pub async fn restore(&self, snapshot_id: &str) -> Result<Vec<NodeRestoreResult>> {
+        let manifest = self.get(snapshot_id)?;
+
+        let active = self.cluster_manager.get_active_nodes();
+        let mut nodes = Vec::with_capacity(manifest.nodes.len());
+        for part in &manifest.nodes {
+            let node = active
+                .iter()
+                .find(|n| n.id.as_str() == part.node_id)
+                .ok_or_else(|| VectorizerError::InvalidConfiguration {
+                    message: format!(
+                        "node {} of cluster snapshot '{}' is not active",
+                        part.node_id, snapshot_id
+                    ),
+                })?;
+            nodes.push(node.clone());
+        }
+        let router = self.cluster_manager.shard_router();
+        for (shard_id, node_id) in &manifest.shard_to_node {
+            let owner = router.get_node_for_shard(&ShardId::new(*shard_id));
+            if owner.as_ref().map(NodeId::as_str) != Some(node_id.as_str()) {
+                return Err(VectorizerError::InvalidConfiguration {
+                    message: format!(
+                        "shard {} was on node {} in cluster snapshot '{}' but is now on {}; \
+                         move it back before restoring",
+                        shard_id,
+                        node_id,
+                        snapshot_id,
+                        owner.map_or_else(|| "no node".to_string(), |n| n.to_string())
+                    ),
+                });
+            }
+        }
+
+        self.prepare(&nodes, snapshot_id).await?;
+        let results = self
+            .run_on_all(&nodes, snapshot_id, SnapshotPhase::Restore)
+            .await;
+        let results: Vec<NodeRestoreResult> = nodes
+            .iter()
+            .zip(results)
+            .map(|(node, result)| match result {
+                Ok(resp) => NodeRestoreResult {
+                    node_id: resp.node_id,
+                    success: true,
+                    message: String::new(),
+                    vector_count: resp.vector_count,
+                },
+                Err(e) => NodeRestoreResult {
+                    node_id: node.id.as_str().to_string(),
+                    success: false,
+                    message: e.to_string(),
+                    vector_count: 0,
+                },
+            })
+            .collect();
+        if results.iter().any(|r| !r.success) {
+            self.run_on_all(&nodes, snapshot_id, SnapshotPhase::Abort)
+                .await;
+        }
+        Ok(results)
+    }
+
+    /// Raise the write fence on every node, or abort on all of them.
+    async fn prepare(&self, nodes: &[ClusterNode], snapshot_id: &str) -> Result<()> {
+        let results = self
+            .run_on_all(nodes, snapshot_id, SnapshotPhase::Prepare)
+            .await;
+        let failures: Vec<String> = nodes
+            .iter()
+            .zip(results)
+            .filter_map(|(node, result)| result.err().map(|e| format!("{}: {}", node.id, e)))
+            .collect();
+        if failures.is_empty() {
+            return Ok(());
+        }
+        self.run_on_all(nodes, snapshot_id, SnapshotPhase::Abort)
+            .await;
+        Err(VectorizerError::Storage(format!(
+            "could not fence writes for cluster snapshot '{}': {}",
+            snapshot_id,
+            failures.join("; ")
+        )))
+    }
+
+    /// Run `phase` on every node at once, so the fence stays up no
+    /// longer than the slowest node needs.
+    async fn run_on_all(
+        &self,
+        nodes: &[ClusterNode],
+        snapshot_id: &str,
+        phase: SnapshotPhase,
+    ) -> Vec<Result<SnapshotPhaseResponse>> {
+        futures::future::join_all(
+            nodes
+                .iter()
+                .map(|node| self.run_phase(node, snapshot_id, phase)),
+        )
+        .await
+    }
+
+    async fn run_phase(
+        &self,
+        node: &ClusterNode,
+        snapshot_id: &str,
+        phase: SnapshotPhase,
+    ) -> Result<SnapshotPhaseResponse> {
+        let request = SnapshotPhaseRequest {
+            snapshot_id: snapshot_id.to_string(),
+            phase: phase as i32,
+            fence_timeout_ms: self.fence_timeout.as_millis() as u64,
+        };
+        let response = if node.id == *self.cluster_manager.local_node_id() {
+            handle_phase(&self.store, &self.root, node.id.as_str(), request).await
+        } else {
+            let client = self
+                .client_pool
+                .get_client(&node.id, &node.grpc_address())
+                .await?;
+            client.snapshot_phase(request).await?
+        };
+        if response.success {
+            Ok(response)
+        } else {
+            Err(VectorizerError::Storage(response.message))
+        }
+    }
+}
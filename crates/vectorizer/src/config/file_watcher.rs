@@ -31,6 +31,12 @@ pub struct FileWatcherYamlConfig {
     /// Custom path-to-collection mappings (path pattern -> collection name)
     /// Example: { "*/docs/*": "documentation", "*/src/*.rs": "rust-code" }
     pub collection_mapping: Option<HashMap<String, String>>,
+    /// OCR of images (disabled when unset)
+    #[serde(default)]
+    pub ocr: Option<crate::ocr::OcrConfig>,
+    /// Transcription of audio (disabled when unset)
+    #[serde(default)]
+    pub transcription: Option<crate::transcription::TranscriptionConfig>,
 }
 
 impl Default for FileWatcherYamlConfig {
@@ -56,6 +62,8 @@ impl Default for FileWatcherYamlConfig {
             hash_validation_enabled: Some(true),
             collection_name: Some("default_collection".to_string()),
             collection_mapping: None,
+            ocr: None,
+            transcription: None,
         }
     }
 }
@@ -118,6 +126,8 @@ impl FileWatcherYamlConfig {
             auto_discovery: true,
             enable_auto_update: true,
             hot_reload: true,
+            ocr: self.ocr.clone().unwrap_or_default(),
+            transcription: self.transcription.clone().unwrap_or_default(),
         }
     }
 }
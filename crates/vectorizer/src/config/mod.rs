@@ -8,5 +8,19 @@ pub mod vectorizer;
 pub mod workspace;
 
 pub use file_watcher::*;
+pub use sections::audit::AuditLogConfig;
+pub use sections::backup_schedule::{BackupJobConfig, BackupScheduleConfig, RetentionPolicy};
+pub use sections::change_feed::{ChangeFeedConfig, ChangeSinkConfig};
+pub use sections::collections::CollectionTemplate;
+pub use sections::federation::{FederatedSourceConfig, FederationConfig};
+pub use sections::health::{HealthConfig, ReadinessConfig};
+pub use sections::lazy_loading::LazyLoadingConfig;
+pub use sections::plugins::{PluginFailurePolicy, PluginsConfig, WritePluginConfig};
+pub use sections::query_analytics::QueryAnalyticsConfig;
+pub use sections::read_through::{
+    CollectionOrigin, ReadThroughCollectionConfig, ReadThroughConfig,
+};
+pub use sections::tls::{ListenerTlsConfig, TlsCipherPreset};
+pub use sections::tuning::{MAX_EF_SEARCH, QueryCacheTuning, TuningConfig};
 pub use vectorizer::*;
 pub use workspace::*;
@@ -0,0 +1,54 @@
+//! Mutation audit log configuration data (`audit_log:`).
+//!
+//! Plain serde types only — the writer, rotation and collection sink live
+//! in `crate::security::mutation_audit`.
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// Audit trail of every mutating REST / gRPC / MCP / RPC call: who made
+/// it, what it changed, and when.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditLogConfig {
+    /// Record mutating calls. Defaults to `false`.
+    #[serde(default)]
+    pub enabled: bool,
+    /// JSON-lines file the records are appended to. Defaults to
+    /// `audit/mutations.jsonl` under the data directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+    /// Rotate the file once it reaches this size. Defaults to 100 MB.
+    #[serde(default = "AuditLogConfig::default_max_file_size_mb")]
+    pub max_file_size_mb: u64,
+    /// Rotated files kept next to the live one (`mutations.jsonl.1` is the
+    /// newest). Defaults to 10.
+    #[serde(default = "AuditLogConfig::default_max_files")]
+    pub max_files: usize,
+    /// Also insert every record into this collection, created with the
+    /// first record when missing. Off by default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub collection: Option<String>,
+}
+
+impl AuditLogConfig {
+    fn default_max_file_size_mb() -> u64 {
+        100
+    }
+
+    fn default_max_files() -> usize {
+        10
+    }
+}
+
+impl Default for AuditLogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: None,
+            max_file_size_mb: Self::default_max_file_size_mb(),
+            max_files: Self::default_max_files(),
+            collection: None,
+        }
+    }
+}
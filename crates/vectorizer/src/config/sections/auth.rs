@@ -57,6 +57,109 @@ pub struct AuthConfig {
     /// deserialize cleanly thanks to `#[serde(default)]`.
     #[serde(default)]
     pub dev_mode_skip_loopback: bool,
+    /// External OpenID Connect identity provider. When set, RS256 access
+    /// tokens it issues are accepted wherever a Vectorizer JWT is, with
+    /// their role claims mapped onto Vectorizer roles.
+    #[serde(default)]
+    pub oidc: Option<OidcConfig>,
+}
+
+/// External OpenID Connect / OAuth2 identity provider (`auth.oidc`).
+///
+/// Tokens are verified against the provider's JWKS, which is fetched at
+/// boot and re-fetched every `jwks_refresh_secs` (and early, at most
+/// once a minute, when a token names an unknown key). Validation and
+/// claim mapping live in `crate::auth::oidc`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OidcConfig {
+    /// Issuer URL. Tokens must carry it as their `iss` claim.
+    pub issuer: String,
+    /// URL of the provider's JSON Web Key Set.
+    pub jwks_url: String,
+    /// Required `aud` claim: the identifier the provider puts in tokens
+    /// minted for Vectorizer, so tokens for other services are refused.
+    pub audience: String,
+    /// Public client ID the dashboard signs in with (authorization code
+    /// flow with PKCE). The dashboard offers SSO only when this is set.
+    #[serde(default)]
+    pub client_id: Option<String>,
+    /// Scopes the dashboard requests.
+    #[serde(default = "OidcConfig::default_scopes")]
+    pub scopes: Vec<String>,
+    /// Claim holding the username. Falls back to `sub` when absent.
+    #[serde(default = "OidcConfig::default_username_claim")]
+    pub username_claim: String,
+    /// Claim holding the provider's roles or groups, as a dotted path
+    /// (e.g. `realm_access.roles`). A list of strings or a single
+    /// space-separated string.
+    #[serde(default = "OidcConfig::default_roles_claim")]
+    pub roles_claim: String,
+    /// Provider role or group -> Vectorizer role (`admin`, `user`,
+    /// `readonly`, `apiuser` or `service`).
+    #[serde(default)]
+    pub role_mapping: std::collections::HashMap<String, String>,
+    /// Vectorizer role for tokens none of whose roles are mapped. Such
+    /// tokens are refused when unset.
+    #[serde(default)]
+    pub default_role: Option<String>,
+    /// Seconds between JWKS refreshes. Defaults to 600.
+    #[serde(default = "OidcConfig::default_jwks_refresh_secs")]
+    pub jwks_refresh_secs: u64,
+    /// Keep `/auth/login` and locally managed users alongside the
+    /// provider. Set to `false` where local passwords are not allowed.
+    /// Defaults to `true`.
+    #[serde(default = "OidcConfig::default_local_login")]
+    pub local_login: bool,
+}
+
+impl OidcConfig {
+    /// Check the provider settings. Called from
+    /// [`crate::config::VectorizerConfig::validate`] and when the
+    /// validator is built.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.issuer.trim().is_empty() {
+            return Err("auth.oidc.issuer is empty".to_string());
+        }
+        if self.audience.trim().is_empty() {
+            return Err(
+                "auth.oidc.audience is empty; set it to the `aud` the provider issues for Vectorizer"
+                    .to_string(),
+            );
+        }
+        if !(self.jwks_url.starts_with("https://") || self.jwks_url.starts_with("http://")) {
+            return Err(format!(
+                "auth.oidc.jwks_url must be an http(s) URL, got '{}'",
+                self.jwks_url
+            ));
+        }
+        if self.jwks_refresh_secs == 0 {
+            return Err("auth.oidc.jwks_refresh_secs must be at least 1".to_string());
+        }
+        Ok(())
+    }
+
+    fn default_scopes() -> Vec<String> {
+        ["openid", "profile", "email"]
+            .into_iter()
+            .map(String::from)
+            .collect()
+    }
+
+    fn default_username_claim() -> String {
+        "preferred_username".to_string()
+    }
+
+    fn default_roles_claim() -> String {
+        "roles".to_string()
+    }
+
+    fn default_jwks_refresh_secs() -> u64 {
+        600
+    }
+
+    fn default_local_login() -> bool {
+        true
+    }
 }
 
 impl Default for AuthConfig {
@@ -75,6 +178,61 @@ impl Default for AuthConfig {
             enabled: true,
             cookies: CookieConfig::default(),
             dev_mode_skip_loopback: false,
+            oidc: None,
+        }
+    }
+}
+
+/// HMAC request signing (`api.request_signing` in `config.yml`).
+///
+/// When enabled, every non-exempt HTTP request must carry
+/// `X-Vectorizer-Key-Id`, `X-Vectorizer-Timestamp` and
+/// `X-Vectorizer-Signature` headers. Intended for deployments where a
+/// bearer token alone is not trusted on the internal network. Signing is
+/// checked in addition to, not instead of, JWT / API-key auth.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequestSigningConfig {
+    /// Reject unsigned or badly signed requests. Defaults to `false`.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Accepted signing keys: key ID -> shared secret. Several IDs may be
+    /// active at once so secrets can be rotated without downtime.
+    #[serde(default)]
+    pub keys: std::collections::HashMap<String, Secret<String>>,
+    /// Maximum tolerated distance between the request timestamp and the
+    /// server clock, in seconds. Defaults to 300.
+    #[serde(default = "RequestSigningConfig::default_max_clock_skew_secs")]
+    pub max_clock_skew_secs: u64,
+    /// Path prefixes that never require a signature (probes, dashboard).
+    #[serde(default = "RequestSigningConfig::default_exempt_paths")]
+    pub exempt_paths: Vec<String>,
+}
+
+impl RequestSigningConfig {
+    fn default_max_clock_skew_secs() -> u64 {
+        300
+    }
+
+    fn default_exempt_paths() -> Vec<String> {
+        [
+            "/health",
+            "/prometheus/metrics",
+            "/dashboard",
+            "/umicp/health",
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect()
+    }
+}
+
+impl Default for RequestSigningConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            keys: std::collections::HashMap::new(),
+            max_clock_skew_secs: Self::default_max_clock_skew_secs(),
+            exempt_paths: Self::default_exempt_paths(),
         }
     }
 }
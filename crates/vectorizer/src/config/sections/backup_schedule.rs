@@ -0,0 +1,53 @@
+//! Scheduled backup configuration data (`backup_schedule:`).
+//!
+//! Plain serde types only — cron parsing, snapshot runs and retention
+//! live in `crate::db::backup_scheduler`.
+
+use serde::{Deserialize, Serialize};
+
+/// Backup jobs run on a cron schedule.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BackupScheduleConfig {
+    /// The jobs. None by default.
+    #[serde(default)]
+    pub jobs: Vec<BackupJobConfig>,
+}
+
+/// One scheduled backup.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupJobConfig {
+    /// Job name (`[A-Za-z0-9_-]`), unique across jobs. Used in metric
+    /// labels and the snapshot directory.
+    pub name: String,
+    /// Five-field cron expression (`minute hour day-of-month month
+    /// day-of-week`) evaluated in UTC, or one of `@hourly`, `@daily`,
+    /// `@weekly`, `@monthly`, `@yearly`.
+    pub cron: String,
+    /// Collections to snapshot one by one. Empty takes a full snapshot
+    /// of the data directory.
+    #[serde(default)]
+    pub collections: Vec<String>,
+    /// Which of the job's snapshots to keep.
+    #[serde(default)]
+    pub retention: RetentionPolicy,
+}
+
+/// Tiered retention of a job's snapshots. A snapshot is kept when any
+/// rule keeps it; with every rule at 0 nothing is pruned.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetentionPolicy {
+    /// Keep the newest N snapshots.
+    #[serde(default)]
+    pub keep_last: usize,
+    /// Keep the newest snapshot of each of the last N days that have one.
+    #[serde(default)]
+    pub keep_daily: usize,
+    /// Keep the newest snapshot of each of the last N ISO weeks that
+    /// have one.
+    #[serde(default)]
+    pub keep_weekly: usize,
+    /// Keep the newest snapshot of each of the last N months that have
+    /// one.
+    #[serde(default)]
+    pub keep_monthly: usize,
+}
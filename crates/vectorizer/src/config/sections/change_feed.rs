@@ -0,0 +1,71 @@
+//! Change data capture configuration data (`change_feed:`).
+//!
+//! Plain serde types only — event numbering, the resume buffer and the
+//! sink forwarders live in `crate::db::change_feed`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::secret::Secret;
+
+/// Stream of vector inserts, updates and deletes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeFeedConfig {
+    /// Publish mutations to `/changes/stream`, `/ws/changes` and the
+    /// sinks. Off by default.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Newest events kept so a consumer that reconnects can resume from
+    /// its last sequence number. Defaults to 10 000.
+    #[serde(default = "ChangeFeedConfig::default_buffer_size")]
+    pub buffer_size: usize,
+    /// Message brokers every event is forwarded to. None by default.
+    #[serde(default)]
+    pub sinks: Vec<ChangeSinkConfig>,
+}
+
+impl ChangeFeedConfig {
+    fn default_buffer_size() -> usize {
+        10_000
+    }
+}
+
+impl Default for ChangeFeedConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            buffer_size: Self::default_buffer_size(),
+            sinks: Vec::new(),
+        }
+    }
+}
+
+/// A broker change events are forwarded to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ChangeSinkConfig {
+    /// A NATS server, over its plain-text client protocol (no TLS).
+    /// Events are published to `{subject_prefix}.{collection}`.
+    Nats {
+        /// Server address, e.g. `nats://localhost:4222`.
+        url: String,
+        /// Subject prefix. Defaults to `vectorizer.changes`.
+        #[serde(default = "default_subject_prefix")]
+        subject_prefix: String,
+        /// Token sent in `CONNECT` when the server requires one.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        token: Option<Secret<String>>,
+    },
+    /// A Kafka topic, through a Kafka REST Proxy (Confluent REST API
+    /// v2). Records are keyed `{collection}/{id}` so every change to a
+    /// vector lands on the same partition.
+    Kafka {
+        /// Base URL of the REST proxy, e.g. `http://kafka-rest:8082`.
+        rest_proxy_url: String,
+        /// Topic the events are produced to.
+        topic: String,
+    },
+}
+
+fn default_subject_prefix() -> String {
+    "vectorizer.changes".to_string()
+}
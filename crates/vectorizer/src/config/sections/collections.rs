@@ -0,0 +1,187 @@
+//! Collection template data (`collection_templates:` in `config.yml`).
+//!
+//! A template is a named bundle of collection settings that
+//! `POST /collections` picks up via `"template": "<name>"`, so that
+//! dozens of per-repo collections share one definition instead of each
+//! client repeating dimension / metric / quantization / HNSW tuning.
+//! Fields set explicitly in the create request win over the template;
+//! template fields win over server defaults.
+//!
+//! ```yaml
+//! collection_templates:
+//!   code-search:
+//!     dimension: 512
+//!     metric: cosine
+//!     quantization: { type: sq, bits: 8 }
+//!     storage: mmap
+//!     hnsw: { m: 32, ef_construction: 200 }
+//!     search: { ef_search: 128 }
+//!     payload_indexes:
+//!       - { field: language, type: Keyword }
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::db::payload_index::{PayloadIndexConfig, PayloadIndexType};
+use crate::models::{DistanceMetric, HnswConfig, QuantizationConfig, StorageType};
+
+/// One named collection template.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CollectionTemplate {
+    /// Free-text description shown by `GET /collections/templates`.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Vector dimension.
+    #[serde(default)]
+    pub dimension: Option<usize>,
+    /// Distance metric.
+    #[serde(default)]
+    pub metric: Option<DistanceMetric>,
+    /// Quantization applied to stored vectors.
+    #[serde(default)]
+    pub quantization: Option<QuantizationConfig>,
+    /// Where raw vectors live (`memory` or `mmap`).
+    #[serde(default)]
+    pub storage: Option<StorageType>,
+    /// HNSW build parameters. Unset fields keep the server default.
+    #[serde(default)]
+    pub hnsw: TemplateHnswConfig,
+    /// Default search parameters baked into the collection.
+    #[serde(default)]
+    pub search: TemplateSearchDefaults,
+    /// Embedding provider used for text inserts and text queries.
+    #[serde(default)]
+    pub embedding_provider: Option<String>,
+    /// Payload fields to index in addition to the built-in
+    /// `file_path` / `chunk_index` indexes.
+    #[serde(default)]
+    pub payload_indexes: Vec<TemplatePayloadIndex>,
+}
+
+/// Partial HNSW build parameters.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TemplateHnswConfig {
+    /// Links per node.
+    #[serde(default)]
+    pub m: Option<usize>,
+    /// Candidate list size during construction.
+    #[serde(default)]
+    pub ef_construction: Option<usize>,
+    /// Level-assignment seed.
+    #[serde(default)]
+    pub seed: Option<u64>,
+}
+
+/// Search-time defaults stored with the collection.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TemplateSearchDefaults {
+    /// HNSW candidate list size used by searches that do not override it.
+    #[serde(default)]
+    pub ef_search: Option<usize>,
+}
+
+/// A payload field to index.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplatePayloadIndex {
+    /// Payload key.
+    pub field: String,
+    /// Index kind.
+    #[serde(rename = "type")]
+    pub index_type: PayloadIndexType,
+}
+
+impl CollectionTemplate {
+    /// HNSW config with the template's overrides applied to the defaults.
+    pub fn hnsw_config(&self) -> HnswConfig {
+        let defaults = HnswConfig::default();
+        HnswConfig {
+            m: self.hnsw.m.unwrap_or(defaults.m),
+            ef_construction: self
+                .hnsw
+                .ef_construction
+                .unwrap_or(defaults.ef_construction),
+            ef_search: self.search.ef_search.unwrap_or(defaults.ef_search),
+            seed: self.hnsw.seed.or(defaults.seed),
+        }
+    }
+
+    /// Payload index configs to register on a new collection.
+    pub fn payload_index_configs(&self) -> Vec<PayloadIndexConfig> {
+        self.payload_indexes
+            .iter()
+            .map(|p| PayloadIndexConfig::new(p.field.clone(), p.index_type))
+            .collect()
+    }
+
+    /// Reject templates that could never produce a valid collection.
+    pub fn validate(&self, name: &str) -> Result<(), String> {
+        if self.dimension == Some(0) {
+            return Err(format!(
+                "collection template '{name}': dimension must be > 0"
+            ));
+        }
+        if self.hnsw.m == Some(0) || self.hnsw.ef_construction == Some(0) {
+            return Err(format!(
+                "collection template '{name}': hnsw.m and hnsw.ef_construction must be > 0"
+            ));
+        }
+        if self.search.ef_search == Some(0) {
+            return Err(format!(
+                "collection template '{name}': search.ef_search must be > 0"
+            ));
+        }
+        if let Some(p) = self.payload_indexes.iter().find(|p| p.field.is_empty()) {
+            return Err(format!(
+                "collection template '{name}': payload index field must not be empty (type {:?})",
+                p.index_type
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_template_parses_from_yaml_and_overlays_hnsw() {
+        let yaml = r#"
+dimension: 384
+metric: euclidean
+quantization: { type: binary }
+storage: mmap
+hnsw: { m: 32 }
+search: { ef_search: 128 }
+payload_indexes:
+  - { field: language, type: Keyword }
+"#;
+        let t: CollectionTemplate = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(t.dimension, Some(384));
+        assert_eq!(t.metric, Some(DistanceMetric::Euclidean));
+        assert!(matches!(t.quantization, Some(QuantizationConfig::Binary)));
+        assert_eq!(t.storage, Some(StorageType::Mmap));
+
+        let hnsw = t.hnsw_config();
+        let defaults = HnswConfig::default();
+        assert_eq!(hnsw.m, 32);
+        assert_eq!(hnsw.ef_search, 128);
+        assert_eq!(hnsw.ef_construction, defaults.ef_construction);
+
+        let indexes = t.payload_index_configs();
+        assert_eq!(indexes.len(), 1);
+        assert_eq!(indexes[0].field_name, "language");
+        assert_eq!(indexes[0].index_type, PayloadIndexType::Keyword);
+        assert!(t.validate("code-search").is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_zero_dimension() {
+        let t = CollectionTemplate {
+            dimension: Some(0),
+            ..Default::default()
+        };
+        assert!(t.validate("bad").is_err());
+    }
+}
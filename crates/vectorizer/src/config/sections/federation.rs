@@ -0,0 +1,68 @@
+//! Query federation configuration data (`federation:`).
+//!
+//! Plain serde types only — validation, the source registry and the
+//! remote calls live in `crate::search::federation`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::secret::Secret;
+
+/// A remote Vectorizer instance that multi-collection search can fan out
+/// to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FederatedSourceConfig {
+    /// Name callers select the source by (`"sources": ["<name>"]`).
+    /// `local` is reserved for this server.
+    pub name: String,
+    /// Base URL of the remote REST API, e.g. `http://team-a:15002`.
+    pub url: String,
+    /// API key sent as `X-API-Key` when the remote has auth enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<Secret<String>>,
+    /// Per-source request timeout. Falls back to
+    /// `federation.default_timeout_ms`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+    /// Multiplier applied to the source's normalized scores, to favour or
+    /// demote a source in the merged ranking. Defaults to `1.0`.
+    #[serde(default = "FederatedSourceConfig::default_weight")]
+    pub weight: f32,
+    /// Remote collections to search. Empty = the collections named in
+    /// the request.
+    #[serde(default)]
+    pub collections: Vec<String>,
+}
+
+impl FederatedSourceConfig {
+    fn default_weight() -> f32 {
+        1.0
+    }
+}
+
+/// Remote sources registered at startup. More can be registered at
+/// runtime through `POST /federation/sources`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FederationConfig {
+    /// Timeout for sources without their own `timeout_ms`. Defaults to
+    /// 2000 ms.
+    #[serde(default = "FederationConfig::default_timeout_ms")]
+    pub default_timeout_ms: u64,
+    /// The sources.
+    #[serde(default)]
+    pub sources: Vec<FederatedSourceConfig>,
+}
+
+impl FederationConfig {
+    fn default_timeout_ms() -> u64 {
+        2000
+    }
+}
+
+impl Default for FederationConfig {
+    fn default() -> Self {
+        Self {
+            default_timeout_ms: Self::default_timeout_ms(),
+            sources: Vec::new(),
+        }
+    }
+}
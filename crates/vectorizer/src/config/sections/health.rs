@@ -0,0 +1,62 @@
+//! Health probe configuration data (`health:` top-level section).
+//!
+//! Plain serde types only — the checks themselves run in the server's
+//! `/health/ready` handler.
+
+use serde::{Deserialize, Serialize};
+
+/// Health probe settings.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HealthConfig {
+    /// Checks `/health/ready` runs before reporting the node ready.
+    #[serde(default)]
+    pub readiness: ReadinessConfig,
+}
+
+/// Checks behind `/health/ready` under `health.readiness`. Each one can
+/// be switched off; a draining node is never ready.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadinessConfig {
+    /// Wait for the startup load of persisted and workspace collections.
+    /// Defaults to `true`.
+    #[serde(default = "ReadinessConfig::default_true")]
+    pub collections_loaded: bool,
+    /// Require the persisted embedding vocabulary to have been restored
+    /// without error. Defaults to `true`.
+    #[serde(default = "ReadinessConfig::default_true")]
+    pub vocabulary_loaded: bool,
+    /// On a replica, the longest time since the last message from the
+    /// master, in milliseconds, and a live connection. Defaults to
+    /// 30000; `null` disables the check.
+    #[serde(default = "ReadinessConfig::default_max_replication_lag_ms")]
+    pub max_replication_lag_ms: Option<u64>,
+    /// Most upserts allowed to wait in the per-collection queues, summed
+    /// over collections. Defaults to 10000; `null` disables the check.
+    #[serde(default = "ReadinessConfig::default_max_indexing_queue")]
+    pub max_indexing_queue: Option<usize>,
+}
+
+impl ReadinessConfig {
+    fn default_true() -> bool {
+        true
+    }
+
+    fn default_max_replication_lag_ms() -> Option<u64> {
+        Some(30_000)
+    }
+
+    fn default_max_indexing_queue() -> Option<usize> {
+        Some(10_000)
+    }
+}
+
+impl Default for ReadinessConfig {
+    fn default() -> Self {
+        Self {
+            collections_loaded: Self::default_true(),
+            vocabulary_loaded: Self::default_true(),
+            max_replication_lag_ms: Self::default_max_replication_lag_ms(),
+            max_indexing_queue: Self::default_max_indexing_queue(),
+        }
+    }
+}
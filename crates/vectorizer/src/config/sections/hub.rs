@@ -4,8 +4,12 @@
 //! logic live in `crate::hub`, which re-exports these types from here
 //! (see `phase41_architecture-decoupling` §2).
 
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
+use crate::storage::ObjectStoreConfig;
+
 /// HiveHub integration configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HubConfig {
@@ -45,6 +49,52 @@ pub struct HubConfig {
     /// Tenant isolation mode
     #[serde(default)]
     pub tenant_isolation: TenantIsolationMode,
+
+    /// Per-tenant limits this node enforces on its own, before every
+    /// collection create and vector insert
+    #[serde(default)]
+    pub tenant_quotas: TenantQuotaConfig,
+
+    /// Bucket the per-user backups are copied to, so they outlive the
+    /// node that took them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backup_store: Option<ObjectStoreConfig>,
+}
+
+/// Node-local tenant quotas, checked against what each tenant holds on
+/// this node. They apply on top of the plan quotas HiveHub reports.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TenantQuotaConfig {
+    /// Limits for tenants without an entry in `tenants`
+    #[serde(default)]
+    pub default: TenantLimits,
+
+    /// Limits by tenant id, replacing `default` for that tenant
+    #[serde(default)]
+    pub tenants: HashMap<String, TenantLimits>,
+}
+
+impl TenantQuotaConfig {
+    /// Limits that apply to `tenant_id`
+    pub fn limits_for(&self, tenant_id: &str) -> &TenantLimits {
+        self.tenants.get(tenant_id).unwrap_or(&self.default)
+    }
+}
+
+/// Limits of one tenant; `None` means unlimited
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TenantLimits {
+    /// Most vectors across the tenant's collections
+    #[serde(default)]
+    pub max_vectors: Option<u64>,
+
+    /// Most estimated memory, in bytes, across the tenant's collections
+    #[serde(default)]
+    pub max_bytes: Option<u64>,
+
+    /// Most collections
+    #[serde(default)]
+    pub max_collections: Option<u64>,
 }
 
 /// Cache configuration for HiveHub API responses
@@ -145,6 +195,8 @@ impl Default for HubConfig {
             connection_pool: ConnectionPoolConfig::default(),
             usage_report_interval: default_usage_report_interval(),
             tenant_isolation: TenantIsolationMode::default(),
+            tenant_quotas: TenantQuotaConfig::default(),
+            backup_store: None,
         }
     }
 }
@@ -0,0 +1,45 @@
+//! Lazy collection loading configuration data (`lazy_loading:`).
+//!
+//! Plain serde types only — residency tracking and LRU eviction live in
+//! `crate::db::residency`.
+
+use serde::{Deserialize, Serialize};
+
+/// Keep only recently used collections in memory; the rest stay in
+/// `vectorizer.vecdb` until they are accessed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LazyLoadingConfig {
+    /// Leave persisted collections on disk at startup and load each one
+    /// on first access. Defaults to `false`.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Most collections kept in memory; the least recently used ones
+    /// beyond it are evicted. `0` (the default) sets no limit.
+    #[serde(default)]
+    pub max_resident_collections: usize,
+    /// Estimated memory the resident collections may use, in MiB; the
+    /// least recently used ones are evicted while it is exceeded. `0`
+    /// (the default) sets no limit.
+    #[serde(default)]
+    pub memory_budget_mb: u64,
+    /// How often the limits are enforced. Defaults to 30 s.
+    #[serde(default = "LazyLoadingConfig::default_sweep_interval_secs")]
+    pub sweep_interval_secs: u64,
+}
+
+impl LazyLoadingConfig {
+    fn default_sweep_interval_secs() -> u64 {
+        30
+    }
+}
+
+impl Default for LazyLoadingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_resident_collections: 0,
+            memory_budget_mb: 0,
+            sweep_interval_secs: Self::default_sweep_interval_secs(),
+        }
+    }
+}
@@ -12,6 +12,19 @@
 //! (validation, manager construction, etc.) stays in the owning
 //! service module as inherent `impl` blocks on the re-exported type.
 
+pub mod audit;
 pub mod auth;
+pub mod backup_schedule;
+pub mod change_feed;
 pub mod cluster;
+pub mod collections;
+pub mod federation;
+pub mod health;
 pub mod hub;
+pub mod lazy_loading;
+pub mod plugins;
+pub mod query_analytics;
+pub mod read_through;
+pub mod redaction;
+pub mod tls;
+pub mod tuning;
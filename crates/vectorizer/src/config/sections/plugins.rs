@@ -0,0 +1,66 @@
+//! Write-path plugin configuration data (`plugins:`).
+//!
+//! Plain serde types only — loading, sandboxing and the hook chain live
+//! in `crate::plugins`.
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// What happens to a write when a plugin fails to run (trap, fuel or
+/// memory exhausted, malformed output). A plugin *rejecting* a vector is
+/// not a failure and always refuses the write.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginFailurePolicy {
+    /// Refuse the write. The safe choice for scrubbing plugins.
+    #[default]
+    Reject,
+    /// Log a warning and pass the vector on unchanged.
+    Skip,
+}
+
+/// A WASM module run on every insert and update of matching collections.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WritePluginConfig {
+    /// Name used in logs and rejection errors.
+    pub name: String,
+    /// Path to the `.wasm` module.
+    pub wasm: PathBuf,
+    /// Collections the plugin runs on. Entries ending in `*` match by
+    /// prefix. Empty = every collection.
+    #[serde(default)]
+    pub collections: Vec<String>,
+    /// Pass the dense vector to the plugin and accept a replacement.
+    /// Off by default: most plugins only need the id and payload, and
+    /// copying the vector into the sandbox is the bulk of the call cost.
+    #[serde(default)]
+    pub with_vector: bool,
+    /// Instruction budget per call (wasmtime fuel). Defaults to 10M.
+    #[serde(default = "WritePluginConfig::default_fuel")]
+    pub fuel: u64,
+    /// Linear memory cap per call. Defaults to 16 MiB.
+    #[serde(default = "WritePluginConfig::default_max_memory_mb")]
+    pub max_memory_mb: u32,
+    /// See [`PluginFailurePolicy`].
+    #[serde(default)]
+    pub on_error: PluginFailurePolicy,
+}
+
+impl WritePluginConfig {
+    fn default_fuel() -> u64 {
+        10_000_000
+    }
+
+    fn default_max_memory_mb() -> u32 {
+        16
+    }
+}
+
+/// Plugins loaded at startup (`plugins:` top-level section).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PluginsConfig {
+    /// Hooks run, in order, before a vector is written.
+    #[serde(default)]
+    pub write: Vec<WritePluginConfig>,
+}
@@ -0,0 +1,47 @@
+//! Query analytics configuration data (`query_analytics:`).
+//!
+//! Plain serde types only — the ring buffer and its report live in
+//! `crate::monitoring::query_analytics`.
+
+use serde::{Deserialize, Serialize};
+
+/// Anonymized statistics of the searches clients run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryAnalyticsConfig {
+    /// Record text, hybrid and intelligent searches and serve
+    /// `/analytics/queries`. On by default.
+    #[serde(default = "QueryAnalyticsConfig::default_enabled")]
+    pub enabled: bool,
+    /// Newest queries kept; older ones are dropped first. Defaults to
+    /// 10 000.
+    #[serde(default = "QueryAnalyticsConfig::default_capacity")]
+    pub capacity: usize,
+    /// Seconds between saves of the recorded queries to
+    /// `query_analytics.json` in the data directory. Defaults to 60.
+    #[serde(default = "QueryAnalyticsConfig::default_flush_interval_secs")]
+    pub flush_interval_secs: u64,
+}
+
+impl QueryAnalyticsConfig {
+    fn default_enabled() -> bool {
+        true
+    }
+
+    fn default_capacity() -> usize {
+        10_000
+    }
+
+    fn default_flush_interval_secs() -> u64 {
+        60
+    }
+}
+
+impl Default for QueryAnalyticsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: Self::default_enabled(),
+            capacity: Self::default_capacity(),
+            flush_interval_secs: Self::default_flush_interval_secs(),
+        }
+    }
+}
@@ -0,0 +1,91 @@
+//! Read-through collection configuration data (`read_through:`).
+//!
+//! Plain serde types only — validation, origin fetches and idle eviction
+//! live in `crate::db::read_through`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::secret::Secret;
+
+/// Where a read-through collection is loaded from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CollectionOrigin {
+    /// A `.vecdb` archive and its `.vecidx` index (`vectorizer.vecdb`,
+    /// `vectorizer.vecidx`) under `location`: a local directory, or an
+    /// `http(s)://` prefix such as a public or proxied S3 / MinIO bucket
+    /// path.
+    Vecdb {
+        /// Directory or URL prefix holding the two files.
+        location: String,
+        /// Collection to read from the archive. Defaults to the local
+        /// name.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        collection: Option<String>,
+    },
+    /// A collection on another Vectorizer server, copied through its REST
+    /// API.
+    Vectorizer {
+        /// Base URL of the remote REST API, e.g. `http://archive:15002`.
+        url: String,
+        /// Remote collection name. Defaults to the local name.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        collection: Option<String>,
+        /// API key sent as `X-API-Key` when the remote has auth enabled.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        api_key: Option<Secret<String>>,
+    },
+}
+
+/// A collection served from an external origin.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadThroughCollectionConfig {
+    /// Local collection name.
+    pub name: String,
+    /// Where it is loaded from.
+    pub origin: CollectionOrigin,
+}
+
+/// Collections loaded from an external origin on first access and
+/// unloaded again once idle.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadThroughConfig {
+    /// Unload a loaded collection after this many seconds without an
+    /// access. Defaults to 900.
+    #[serde(default = "ReadThroughConfig::default_idle_eviction_secs")]
+    pub idle_eviction_secs: u64,
+    /// How often idle collections are looked for. Defaults to 60 s.
+    #[serde(default = "ReadThroughConfig::default_sweep_interval_secs")]
+    pub sweep_interval_secs: u64,
+    /// Timeout of one origin fetch. Defaults to 300 s.
+    #[serde(default = "ReadThroughConfig::default_fetch_timeout_secs")]
+    pub fetch_timeout_secs: u64,
+    /// The collections.
+    #[serde(default)]
+    pub collections: Vec<ReadThroughCollectionConfig>,
+}
+
+impl ReadThroughConfig {
+    fn default_idle_eviction_secs() -> u64 {
+        900
+    }
+
+    fn default_sweep_interval_secs() -> u64 {
+        60
+    }
+
+    fn default_fetch_timeout_secs() -> u64 {
+        300
+    }
+}
+
+impl Default for ReadThroughConfig {
+    fn default() -> Self {
+        Self {
+            idle_eviction_secs: Self::default_idle_eviction_secs(),
+            sweep_interval_secs: Self::default_sweep_interval_secs(),
+            fetch_timeout_secs: Self::default_fetch_timeout_secs(),
+            collections: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,81 @@
+//! Payload redaction configuration data (`api.payload_redaction`).
+//!
+//! Plain serde types only — rule compilation and the JSON walker live in
+//! `crate::security::payload_redaction`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::secret::Secret;
+
+/// What happens to a redacted payload field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RedactionAction {
+    /// Replace the value with the string `"***"`.
+    Mask,
+    /// Remove the field from the payload.
+    Drop,
+    /// Replace the value with `sha256:<hex>` of the salted JSON value, so
+    /// equal values stay joinable without being revealed.
+    Hash,
+}
+
+/// One redaction rule.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedactionRule {
+    /// Payload key to redact. Dots address nested objects
+    /// (`customer.email`).
+    pub field: String,
+    /// How the value is redacted.
+    pub action: RedactionAction,
+    /// Collections the rule applies to. Exact names, or a prefix ending
+    /// in `*`. Empty = every collection.
+    #[serde(default)]
+    pub collections: Vec<String>,
+    /// Roles the rule applies to (`ReadOnly`, `ApiUser`, ...). Empty =
+    /// every caller that is not in `exempt_roles`, including anonymous
+    /// callers when auth is disabled.
+    #[serde(default)]
+    pub roles: Vec<String>,
+}
+
+/// Redaction of payload fields in read responses (search, list, get).
+///
+/// Filters still run against the stored payload, so a field can stay
+/// indexed for filtering while lower-privileged consumers never see its
+/// value.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PayloadRedactionConfig {
+    /// Apply the rules. Defaults to `false`.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Callers holding any of these roles always see raw payloads.
+    /// Defaults to `["Admin"]`.
+    #[serde(default = "PayloadRedactionConfig::default_exempt_roles")]
+    pub exempt_roles: Vec<String>,
+    /// Salt mixed into `hash` redactions. Required when any rule uses
+    /// `hash`; without it low-entropy values (emails, phone numbers)
+    /// could be recovered by hashing guesses.
+    #[serde(default)]
+    pub hash_salt: Option<Secret<String>>,
+    /// The rules, applied in order.
+    #[serde(default)]
+    pub rules: Vec<RedactionRule>,
+}
+
+impl PayloadRedactionConfig {
+    fn default_exempt_roles() -> Vec<String> {
+        vec!["Admin".to_string()]
+    }
+}
+
+impl Default for PayloadRedactionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            exempt_roles: Self::default_exempt_roles(),
+            hash_salt: None,
+            rules: Vec::new(),
+        }
+    }
+}
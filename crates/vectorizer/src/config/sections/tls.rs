@@ -0,0 +1,48 @@
+//! Listener TLS configuration data (`tls:` top-level section).
+//!
+//! Plain serde types only — certificate loading, the rustls server
+//! config and reloading live in `crate::security::tls`.
+
+use serde::{Deserialize, Serialize};
+
+/// Cipher suites offered by the listeners.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TlsCipherPreset {
+    /// TLS 1.3 suites only.
+    #[default]
+    #[serde(alias = "modern")]
+    Modern,
+    /// TLS 1.2 ECDHE suites as well, for older clients.
+    #[serde(alias = "compatible")]
+    Compatible,
+}
+
+/// TLS termination for the REST/MCP listener and the gRPC listener.
+///
+/// Both listeners share one certificate. With `mtls_enabled`, every
+/// connection must present a certificate signed by `client_ca_path`,
+/// and cluster peers dial each other over TLS presenting the same
+/// certificate. The files are re-read on `SIGHUP`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ListenerTlsConfig {
+    /// Serve HTTPS and gRPC over TLS. Defaults to `false`.
+    #[serde(default)]
+    pub enabled: bool,
+    /// PEM certificate chain presented by this server.
+    #[serde(default)]
+    pub cert_path: Option<String>,
+    /// PEM private key for `cert_path`.
+    #[serde(default)]
+    pub key_path: Option<String>,
+    /// Require a client certificate signed by `client_ca_path`.
+    /// Defaults to `false`.
+    #[serde(default)]
+    pub mtls_enabled: bool,
+    /// PEM CA bundle client certificates must chain to. Also the trust
+    /// root for peers' server certificates on cluster connections.
+    #[serde(default)]
+    pub client_ca_path: Option<String>,
+    /// Cipher suites to offer. Defaults to `Modern`.
+    #[serde(default)]
+    pub cipher_suites: TlsCipherPreset,
+}
@@ -0,0 +1,71 @@
+//! Runtime tuning configuration data (`tuning:` top-level section).
+//!
+//! Plain serde types only. The server applies these at boot, and
+//! `PATCH /admin/config` changes them live and writes them back.
+
+use serde::{Deserialize, Serialize};
+
+/// Largest `ef_search` accepted; wider beams cost far more than they
+/// gain in recall.
+pub const MAX_EF_SEARCH: usize = 10_000;
+
+/// Search and cache parameters that can change without a restart.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TuningConfig {
+    /// HNSW search beam for every query, widened to `k` when a query
+    /// asks for more. Unset keeps the adaptive beam, `max(2k, 64)`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ef_search: Option<usize>,
+    /// Query result cache sizing.
+    #[serde(default)]
+    pub query_cache: QueryCacheTuning,
+}
+
+/// Query result cache sizing under `tuning.query_cache`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryCacheTuning {
+    /// Cached queries kept before the least recently used is evicted.
+    /// Defaults to 1000.
+    #[serde(default = "QueryCacheTuning::default_max_size")]
+    pub max_size: usize,
+    /// Seconds a cached result stays valid. Defaults to 300; `0`
+    /// effectively disables the cache.
+    #[serde(default = "QueryCacheTuning::default_ttl_seconds")]
+    pub ttl_seconds: u64,
+}
+
+impl QueryCacheTuning {
+    fn default_max_size() -> usize {
+        1000
+    }
+
+    fn default_ttl_seconds() -> u64 {
+        300
+    }
+}
+
+impl Default for QueryCacheTuning {
+    fn default() -> Self {
+        Self {
+            max_size: Self::default_max_size(),
+            ttl_seconds: Self::default_ttl_seconds(),
+        }
+    }
+}
+
+impl TuningConfig {
+    /// Reject values the server cannot apply.
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(ef_search) = self.ef_search
+            && !(1..=MAX_EF_SEARCH).contains(&ef_search)
+        {
+            return Err(format!(
+                "tuning.ef_search must be between 1 and {MAX_EF_SEARCH}"
+            ));
+        }
+        if self.query_cache.max_size == 0 {
+            return Err("tuning.query_cache.max_size must be > 0".to_string());
+        }
+        Ok(())
+    }
+}
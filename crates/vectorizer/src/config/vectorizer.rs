@@ -1,13 +1,27 @@
 //! Main Vectorizer configuration structure
 
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
 
 use crate::config::FileWatcherYamlConfig;
-use crate::config::sections::auth::AuthConfig;
+use crate::config::sections::audit::AuditLogConfig;
+use crate::config::sections::auth::{AuthConfig, RequestSigningConfig};
+use crate::config::sections::backup_schedule::BackupScheduleConfig;
+use crate::config::sections::change_feed::ChangeFeedConfig;
 use crate::config::sections::cluster::ClusterConfig;
+use crate::config::sections::collections::CollectionTemplate;
+use crate::config::sections::federation::FederationConfig;
+use crate::config::sections::health::HealthConfig;
 use crate::config::sections::hub::HubConfig;
+use crate::config::sections::lazy_loading::LazyLoadingConfig;
+use crate::config::sections::plugins::PluginsConfig;
+use crate::config::sections::query_analytics::QueryAnalyticsConfig;
+use crate::config::sections::read_through::ReadThroughConfig;
+use crate::config::sections::redaction::PayloadRedactionConfig;
+use crate::config::sections::tls::ListenerTlsConfig;
+use crate::config::sections::tuning::TuningConfig;
 use crate::storage::StorageConfig;
 use crate::summarization::SummarizationConfig;
 
@@ -59,27 +73,85 @@ pub struct VectorizerConfig {
     /// once a hard limit is exceeded.
     #[serde(default)]
     pub backpressure: BackpressureConfig,
+    /// Background HNSW compaction (`index_optimizer:` top-level section).
+    /// Rebuilds graphs that deletes and updates have left fragmented.
+    #[serde(default)]
+    pub index_optimizer: IndexOptimizerConfig,
+    /// Remote Vectorizer instances multi-collection search can federate
+    /// to (`federation:` top-level section).
+    #[serde(default)]
+    pub federation: FederationConfig,
+    /// Collections loaded from an external origin on first access and
+    /// unloaded when idle (`read_through:` top-level section).
+    #[serde(default)]
+    pub read_through: ReadThroughConfig,
+    /// Lazy collection loading with LRU eviction back to disk
+    /// (`lazy_loading:` top-level section).
+    #[serde(default)]
+    pub lazy_loading: LazyLoadingConfig,
+    /// Write-path plugins run on insert and update (`plugins:`
+    /// top-level section).
+    #[serde(default)]
+    pub plugins: PluginsConfig,
+    /// Audit trail of mutating calls (`audit_log:` top-level section).
+    #[serde(default)]
+    pub audit_log: AuditLogConfig,
     /// API surface configuration (`api:` top-level section). See
     /// [`ApiConfig`] for which sub-keys are actually wired to runtime
     /// behavior.
     #[serde(default)]
     pub api: ApiConfig,
+    /// Named collection templates referenced by `POST /collections`
+    /// (`"template": "<name>"`).
+    #[serde(default)]
+    pub collection_templates: BTreeMap<String, CollectionTemplate>,
+    /// TLS for the HTTP and gRPC listeners (`tls:` top-level section).
+    #[serde(default)]
+    pub tls: ListenerTlsConfig,
+    /// Search beam and query cache sizing, changeable at runtime through
+    /// `PATCH /admin/config` (`tuning:` top-level section).
+    #[serde(default)]
+    pub tuning: TuningConfig,
+    /// Checks behind the `/health/ready` probe (`health:` top-level
+    /// section).
+    #[serde(default)]
+    pub health: HealthConfig,
+    /// Snapshots taken on a cron schedule, with tiered retention
+    /// (`backup_schedule:` top-level section).
+    #[serde(default)]
+    pub backup_schedule: BackupScheduleConfig,
+    /// Stream of vector inserts, updates and deletes, optionally
+    /// forwarded to NATS or Kafka (`change_feed:` top-level section).
+    #[serde(default)]
+    pub change_feed: ChangeFeedConfig,
+    /// Anonymized statistics of the searches clients run, served at
+    /// `/analytics/queries` (`query_analytics:` top-level section).
+    #[serde(default)]
+    pub query_analytics: QueryAnalyticsConfig,
 }
 
 /// API surface configuration (`api:` top-level section in
-/// `config.yml`). Only `rest.max_request_size_mb` currently drives
-/// runtime behavior (the REST request-body-size limit resolved once at
-/// boot — see `phase40_api-parity-and-hardening` §6.2). The remaining
-/// documented sub-keys (`rest.enabled`, `rest.cors_enabled`,
-/// `rest.timeout_seconds`, `mcp.*`, `grpc.*`) are accepted so a typo in
-/// `rest.max_request_size_mb` itself would still deserialize (serde
-/// tolerates unknown fields without `deny_unknown_fields`); they are
-/// not yet wired to a typed field.
+/// `config.yml`). `rest.max_request_size_mb` (the REST request-body-size
+/// limit resolved once at boot — see `phase40_api-parity-and-hardening`
+/// §6.2), `request_signing` and `payload_redaction` drive runtime
+/// behavior. The remaining documented sub-keys (`rest.enabled`,
+/// `rest.cors_enabled`, `rest.timeout_seconds`, `mcp.*`, `grpc.*`) are
+/// accepted so a typo in `rest.max_request_size_mb` itself would still
+/// deserialize (serde tolerates unknown fields without
+/// `deny_unknown_fields`); they are not yet wired to a typed field.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ApiConfig {
     /// REST-specific settings.
     #[serde(default)]
     pub rest: RestApiConfig,
+    /// HMAC request signing enforced by the HTTP server. Disabled unless
+    /// `api.request_signing.enabled` is set.
+    #[serde(default)]
+    pub request_signing: RequestSigningConfig,
+    /// Role-aware redaction of payload fields in read responses.
+    /// Disabled unless `api.payload_redaction.enabled` is set.
+    #[serde(default)]
+    pub payload_redaction: PayloadRedactionConfig,
 }
 
 /// REST-specific settings under `api.rest`.
@@ -275,6 +347,74 @@ impl Default for BackpressureConfig {
     }
 }
 
+/// Background HNSW compaction. Deleting or updating a vector leaves its
+/// node in the graph (hnsw_rs cannot unlink points), so after heavy churn
+/// searches spend beam slots on dead nodes and recall drops. The
+/// optimizer wakes every `interval_secs` and rebuilds the graph of each
+/// collection whose stale-node share has reached `fragmentation_threshold`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexOptimizerConfig {
+    /// Run the background optimizer.
+    #[serde(default = "IndexOptimizerConfig::default_enabled")]
+    pub enabled: bool,
+
+    /// Seconds between sweeps over all collections.
+    #[serde(default = "IndexOptimizerConfig::default_interval_secs")]
+    pub interval_secs: u64,
+
+    /// Stale-node share (`0.0..=1.0`) at which a graph is rebuilt.
+    #[serde(default = "IndexOptimizerConfig::default_fragmentation_threshold")]
+    pub fragmentation_threshold: f64,
+
+    /// Minimum number of stale nodes before a graph is rebuilt, so small
+    /// collections are not rebuilt over a handful of deletes.
+    #[serde(default = "IndexOptimizerConfig::default_min_stale_nodes")]
+    pub min_stale_nodes: usize,
+}
+
+impl IndexOptimizerConfig {
+    fn default_enabled() -> bool {
+        true
+    }
+
+    fn default_interval_secs() -> u64 {
+        300
+    }
+
+    fn default_fragmentation_threshold() -> f64 {
+        0.2
+    }
+
+    fn default_min_stale_nodes() -> usize {
+        1000
+    }
+
+    /// Validate ranges. Called from [`VectorizerConfig::validate`].
+    pub fn validate(&self) -> Result<(), String> {
+        if self.interval_secs == 0 {
+            return Err("index_optimizer.interval_secs must be >= 1".to_string());
+        }
+        if !(self.fragmentation_threshold > 0.0 && self.fragmentation_threshold <= 1.0) {
+            return Err(format!(
+                "index_optimizer.fragmentation_threshold ({}) must be in (0, 1]",
+                self.fragmentation_threshold
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Default for IndexOptimizerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: Self::default_enabled(),
+            interval_secs: Self::default_interval_secs(),
+            fragmentation_threshold: Self::default_fragmentation_threshold(),
+            min_stale_nodes: Self::default_min_stale_nodes(),
+        }
+    }
+}
+
 /// YAML-friendly replication configuration
 /// Maps to `crate::replication::ReplicationConfig` at runtime
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -595,6 +735,10 @@ pub struct GpuConfig {
     /// Preferred backend (auto/metal/cpu)
     #[serde(default = "GpuConfig::default_preferred_backend")]
     pub preferred_backend: String,
+
+    /// Offload exact search and quantized-candidate rescoring to the GPU
+    #[serde(default)]
+    pub rescoring: GpuRescoringConfig,
 }
 
 impl GpuConfig {
@@ -623,6 +767,47 @@ impl Default for GpuConfig {
             batch_size: Self::default_batch_size(),
             fallback_to_cpu: Self::default_fallback_to_cpu(),
             preferred_backend: Self::default_preferred_backend(),
+            rescoring: GpuRescoringConfig::default(),
+        }
+    }
+}
+
+/// GPU brute-force scoring for exact searches and for the rescoring pass
+/// of binary-quantized searches. Only in-memory indexes are offloaded;
+/// the scan falls back to the CPU whenever the GPU is unavailable or a
+/// GPU pass fails.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GpuRescoringConfig {
+    /// Enable GPU rescoring (default: false). Also needs `gpu.enabled`
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Fewest candidates worth a GPU pass; smaller scans stay on the CPU
+    /// (default: 10000)
+    #[serde(default = "GpuRescoringConfig::default_min_candidates")]
+    pub min_candidates: usize,
+
+    /// Vectors uploaded to the GPU per chunk (default: 100000)
+    #[serde(default = "GpuRescoringConfig::default_chunk_size")]
+    pub chunk_size: usize,
+}
+
+impl GpuRescoringConfig {
+    fn default_min_candidates() -> usize {
+        10_000
+    }
+
+    fn default_chunk_size() -> usize {
+        100_000
+    }
+}
+
+impl Default for GpuRescoringConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_candidates: Self::default_min_candidates(),
+            chunk_size: Self::default_chunk_size(),
         }
     }
 }
@@ -715,7 +900,20 @@ impl Default for VectorizerConfig {
             replication: ReplicationYamlConfig::default(),
             rpc: RpcConfig::default(),
             backpressure: BackpressureConfig::default(),
+            index_optimizer: IndexOptimizerConfig::default(),
+            federation: FederationConfig::default(),
+            read_through: ReadThroughConfig::default(),
+            lazy_loading: LazyLoadingConfig::default(),
+            plugins: PluginsConfig::default(),
+            audit_log: AuditLogConfig::default(),
             api: ApiConfig::default(),
+            collection_templates: BTreeMap::new(),
+            tls: ListenerTlsConfig::default(),
+            tuning: TuningConfig::default(),
+            health: HealthConfig::default(),
+            backup_schedule: BackupScheduleConfig::default(),
+            change_feed: ChangeFeedConfig::default(),
+            query_analytics: QueryAnalyticsConfig::default(),
         }
     }
 }
@@ -791,6 +989,10 @@ impl VectorizerConfig {
     /// invariants fail loudly instead of silently accepting nonsense.
     pub fn validate(&self) -> Result<(), String> {
         self.backpressure.validate()?;
+        self.index_optimizer.validate()?;
+        if let Some(oidc) = &self.auth.oidc {
+            oidc.validate()?;
+        }
         Ok(())
     }
 }
@@ -368,3 +368,27 @@ fn test_vectorizer_config_defaults_api_when_section_absent() {
     let config: VectorizerConfig = serde_yaml::from_str(yaml).unwrap();
     assert_eq!(config.api.rest.max_request_size_mb, 100);
 }
+
+#[test]
+fn test_gpu_rescoring_config_defaults_when_absent() {
+    // Configs written before GPU rescoring have no `rescoring:` key; it
+    // stays off so upgraded servers keep scoring on the CPU.
+    let yaml = r#"
+        enabled: true
+        batch_size: 1000
+    "#;
+    let config: GpuConfig = serde_yaml::from_str(yaml).unwrap();
+    assert!(!config.rescoring.enabled);
+    assert_eq!(config.rescoring.min_candidates, 10_000);
+    assert_eq!(config.rescoring.chunk_size, 100_000);
+
+    let yaml = r#"
+        rescoring:
+          enabled: true
+          min_candidates: 500
+    "#;
+    let config: GpuConfig = serde_yaml::from_str(yaml).unwrap();
+    assert!(config.rescoring.enabled);
+    assert_eq!(config.rescoring.min_candidates, 500);
+    assert_eq!(config.rescoring.chunk_size, 100_000);
+}
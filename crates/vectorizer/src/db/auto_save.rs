@@ -8,9 +8,10 @@ use tokio::sync::RwLock;
 use tokio::time::sleep;
 use tracing::{debug, error, info, warn};
 
+use crate::background_tasks::{self, TASKS};
 use crate::db::VectorStore;
 use crate::error::Result;
-use crate::storage::{SnapshotManager, StorageCompactor};
+use crate::storage::{ObjectStore, SnapshotManager, StorageCompactor};
 
 /// Auto-save interval: 5 minutes
 const SAVE_INTERVAL_SECS: u64 = 300;
@@ -49,6 +50,9 @@ pub struct AutoSaveManager {
 
     /// Shutdown signal
     shutdown: Arc<AtomicBool>,
+
+    /// Bucket new snapshots are copied to
+    object_store: Option<Arc<ObjectStore>>,
 }
 
 impl AutoSaveManager {
@@ -82,9 +86,17 @@ impl AutoSaveManager {
             snapshot_interval: Duration::from_secs(SNAPSHOT_INTERVAL_SECS),
             changes_detected: Arc::new(AtomicBool::new(false)),
             shutdown: Arc::new(AtomicBool::new(false)),
+            object_store: None,
         }
     }
 
+    /// Copy every periodic snapshot to `store` after it is taken
+    pub fn with_object_store(mut self, store: Arc<ObjectStore>) -> Self {
+        self.snapshot_manager.set_object_store(store.clone());
+        self.object_store = Some(store);
+        self
+    }
+
     /// Check if there are pending changes (alias for has_pending_changes)
     pub fn has_changes(&self) -> bool {
         self.has_pending_changes()
@@ -99,9 +111,12 @@ impl AutoSaveManager {
         let snapshot_interval = self.snapshot_interval;
         let changes_detected = self.changes_detected.clone();
         let shutdown = self.shutdown.clone();
+        let object_store = self.object_store.clone();
         let data_dir = VectorStore::get_data_dir();
         let snapshots_dir = data_dir.join(crate::storage::SNAPSHOT_DIR);
         let last_cleanup = Arc::new(RwLock::new(Instant::now()));
+        let save_task = TASKS.register(background_tasks::AUTO_SAVE);
+        let snapshot_task = TASKS.register(background_tasks::SNAPSHOT);
 
         info!("🔄 AutoSave: Starting periodic tasks");
         info!("   Save interval: {} minutes", SAVE_INTERVAL_SECS / 60);
@@ -120,6 +135,15 @@ impl AutoSaveManager {
                     break;
                 }
 
+                {
+                    let since_save = last_save.read().await.elapsed();
+                    let since_snapshot = last_snapshot.read().await.elapsed();
+                    let tick = Duration::from_secs(60);
+                    save_task.schedule(save_interval.saturating_sub(since_save).max(tick));
+                    snapshot_task
+                        .schedule(snapshot_interval.saturating_sub(since_snapshot).max(tick));
+                }
+
                 // Sleep for 1 minute, then check what needs to be done
                 sleep(Duration::from_secs(60)).await;
 
@@ -129,7 +153,10 @@ impl AutoSaveManager {
                     last.elapsed()
                 };
 
-                if time_since_save >= save_interval && changes_detected.load(Ordering::Relaxed) {
+                if time_since_save >= save_interval
+                    && changes_detected.load(Ordering::Relaxed)
+                    && !save_task.is_paused()
+                {
                     info!(
                         "💾 AutoSave: {} minutes elapsed, starting compaction from memory...",
                         time_since_save.as_secs() / 60
@@ -137,7 +164,10 @@ impl AutoSaveManager {
 
                     // Perform compaction from memory (no raw files)
                     let mut compactor = StorageCompactor::new(&data_dir, 6, 1000);
-                    match compactor.compact_from_memory(&store) {
+                    save_task.start();
+                    let result = compactor.compact_from_memory(&store);
+                    save_task.finish(&result);
+                    match result {
                         Ok(index) => {
                             info!("✅ AutoSave: Successfully updated vectorizer.vecdb");
                             info!("   Collections: {}", index.collection_count());
@@ -165,7 +195,7 @@ impl AutoSaveManager {
                     now.duration_since(*last) >= Duration::from_secs(6 * 3600)
                 };
 
-                if should_cleanup {
+                if should_cleanup && !snapshot_task.is_paused() {
                     let snapshot_mgr_cleanup =
                         SnapshotManager::new(&data_dir, snapshots_dir.clone(), 48, 48);
                     match snapshot_mgr_cleanup.cleanup_old_snapshots() {
@@ -189,14 +219,16 @@ impl AutoSaveManager {
                     last.elapsed()
                 };
 
-                if time_since_snapshot >= snapshot_interval {
+                if time_since_snapshot >= snapshot_interval && !snapshot_task.is_paused() {
                     info!(
                         "📸 Snapshot: {} hour elapsed, creating snapshot...",
                         time_since_snapshot.as_secs() / 3600
                     );
 
                     // Ensure data directory exists before creating snapshot manager
+                    snapshot_task.start();
                     if let Err(e) = std::fs::create_dir_all(&data_dir) {
+                        snapshot_task.finish::<(), _>(&Err(&e));
                         error!(
                             "❌ Snapshot: Failed to create data directory {:?}: {}",
                             data_dir, e
@@ -204,15 +236,33 @@ impl AutoSaveManager {
                         continue;
                     }
 
-                    let snapshot_mgr =
+                    let mut snapshot_mgr =
                         SnapshotManager::new(&data_dir, snapshots_dir.clone(), 48, 48); // max 48 snapshots, 48 hours retention
-                    match snapshot_mgr.create_snapshot() {
+                    if let Some(store) = &object_store {
+                        snapshot_mgr = snapshot_mgr.with_object_store(store.clone());
+                    }
+                    let result = snapshot_mgr.create_snapshot();
+                    // An empty node has nothing to snapshot yet; that is
+                    // not a failure.
+                    let failed = result.as_ref().err().map(|e| e.to_string()).filter(|e| {
+                        !(e.contains("No data to snapshot")
+                            || e.contains("no data")
+                            || e.contains("does not exist"))
+                    });
+                    snapshot_task.finish(&failed.map_or(Ok(()), Err));
+                    match result {
                         Ok(snapshot) => {
                             info!(
                                 "✅ Snapshot: Created {} ({} MB)",
                                 snapshot.id,
                                 snapshot.size_bytes / 1_048_576
                             );
+                            if let Err(e) = snapshot_mgr.upload_snapshot(&snapshot).await {
+                                warn!(
+                                    "⚠️  Snapshot: Failed to copy {} off-node: {}",
+                                    snapshot.id, e
+                                );
+                            }
 
                             // Update last snapshot timestamp
                             let mut last = last_snapshot.write().await;
@@ -265,6 +315,8 @@ impl AutoSaveManager {
                 }
             }
 
+            save_task.stop();
+            snapshot_task.stop();
             info!("✅ AutoSave: Background task stopped");
         })
     }
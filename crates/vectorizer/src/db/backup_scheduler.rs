@@ -0,0 +1,813 @@
+//! Cron-scheduled backups with tiered retention.
+//!
+//! Each job in the `backup_schedule:` config section names a cron
+//! expression and either a list of collections or none at all:
+//!
+//! - With no collections, a run forces a save and takes a full snapshot
+//!   of the data directory into `<data_dir>/scheduled_snapshots/<job>/`,
+//!   out of reach of the auto-save snapshot pruning.
+//! - With collections, a run takes a native snapshot of each one
+//!   ([`VectorStore::snapshot_collection_native`]).
+//!
+//! After a successful run the job's [`RetentionPolicy`] prunes its older
+//! snapshots. For per-collection jobs that covers every native snapshot
+//! of the collection, including ones taken through the API.
+//!
+//! The scheduler reports to the [`SCHEDULED_BACKUP`] background task, so
+//! it can be paused from `/admin/tasks`, and records every run through an
+//! injected [`MetricsSink`]. Per-job status — next and last runs, last
+//! error, last snapshots — is served by [`BackupScheduler::status`].
+//!
+//! [`SCHEDULED_BACKUP`]: background_tasks::SCHEDULED_BACKUP
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Datelike, Days, TimeZone, Timelike, Utc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, error, info};
+use vectorizer_core::metrics_sink::MetricsSink;
+
+use crate::background_tasks::{self, TASKS};
+use crate::config::{BackupJobConfig, BackupScheduleConfig, RetentionPolicy};
+use crate::db::{AutoSaveManager, VectorStore};
+use crate::error::{Result, VectorizerError};
+use crate::storage::SnapshotManager;
+
+/// Longest the scheduler sleeps before rechecking the shutdown flag.
+const MAX_SLEEP: Duration = Duration::from_secs(60);
+
+/// How far ahead [`CronSchedule::next_after`] looks for a match.
+const SEARCH_HORIZON_DAYS: u64 = 366 * 5;
+
+/// A parsed five-field cron expression, evaluated in UTC.
+///
+/// Fields are `minute hour day-of-month month day-of-week` and accept
+/// `*`, numbers, ranges (`1-5`), lists (`1,15`) and steps (`*/15`,
+/// `0-30/10`). Months and weekdays may be given by their three-letter
+/// English names; weekday 7 is Sunday like 0. As in classic cron, when
+/// both day fields are restricted a day matching either one fires.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    day_of_month_restricted: bool,
+    day_of_week_restricted: bool,
+}
+
+const MONTH_NAMES: &[&str] = &[
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+const WEEKDAY_NAMES: &[&str] = &["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+impl CronSchedule {
+    /// Parse `expression`, or one of the aliases `@hourly`, `@daily`
+    /// (`@midnight`), `@weekly`, `@monthly` and `@yearly` (`@annually`).
+    pub fn parse(expression: &str) -> Result<Self> {
+        let expanded = match expression.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            other => other,
+        };
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        let [minute, hour, day_of_month, month, day_of_week] = fields[..] else {
+            return Err(cron_error(
+                expression,
+                format!("expected 5 fields, found {}", fields.len()),
+            ));
+        };
+
+        let field = |text: &str, min: u32, max: u32, names: &[&str], offset: u32| {
+            parse_field(text, min, max, names, offset)
+                .map_err(|reason| cron_error(expression, reason))
+        };
+        let mut days_of_week = field(day_of_week, 0, 7, WEEKDAY_NAMES, 0)?;
+        if days_of_week & (1u64 << 7) != 0 {
+            days_of_week = (days_of_week & !(1u64 << 7)) | 1;
+        }
+
+        Ok(Self {
+            minutes: field(minute, 0, 59, &[], 0)?,
+            hours: field(hour, 0, 23, &[], 0)?,
+            days_of_month: field(day_of_month, 1, 31, &[], 0)?,
+            months: field(month, 1, 12, MONTH_NAMES, 1)?,
+            days_of_week,
+            day_of_month_restricted: !day_of_month.starts_with('*'),
+            day_of_week_restricted: !day_of_week.starts_with('*'),
+        })
+    }
+
+    /// The first whole minute strictly after `after` that matches, or
+    /// `None` when nothing matches within five years (e.g. `0 0 30 2 *`).
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let mut t = after.with_second(0)?.with_nanosecond(0)? + chrono::Duration::minutes(1);
+        let horizon = after.checked_add_days(Days::new(SEARCH_HORIZON_DAYS))?;
+
+        while t <= horizon {
+            if !has_bit(self.months, t.month()) {
+                let (year, month) = if t.month() == 12 {
+                    (t.year() + 1, 1)
+                } else {
+                    (t.year(), t.month() + 1)
+                };
+                t = Utc.with_ymd_and_hms(year, month, 1, 0, 0, 0).single()?;
+            } else if !self.day_matches(t) {
+                t = t
+                    .date_naive()
+                    .checked_add_days(Days::new(1))?
+                    .and_hms_opt(0, 0, 0)?
+                    .and_utc();
+            } else if !has_bit(self.hours, t.hour()) {
+                t = t.with_minute(0)? + chrono::Duration::hours(1);
+            } else if !has_bit(self.minutes, t.minute()) {
+                t += chrono::Duration::minutes(1);
+            } else {
+                return Some(t);
+            }
+        }
+        None
+    }
+
+    fn day_matches(&self, t: DateTime<Utc>) -> bool {
+        let day_of_month = has_bit(self.days_of_month, t.day());
+        let day_of_week = has_bit(self.days_of_week, t.weekday().num_days_from_sunday());
+        if self.day_of_month_restricted && self.day_of_week_restricted {
+            day_of_month || day_of_week
+        } else {
+            day_of_month && day_of_week
+        }
+    }
+}
+
+fn has_bit(mask: u64, value: u32) -> bool {
+    mask & (1u64 << value) != 0
+}
+
+/// Parse one cron field into a bit mask of the values it matches.
+/// `names[i]` stands for the value `i + offset`.
+fn parse_field(
+    text: &str,
+    min: u32,
+    max: u32,
+    names: &[&str],
+    offset: u32,
+) -> std::result::Result<u64, String> {
+    let value = |part: &str| -> std::result::Result<u32, String> {
+        let lower = part.to_ascii_lowercase();
+        if let Some(i) = names.iter().position(|n| *n == lower) {
+            return Ok(i as u32 + offset);
+        }
+        let v: u32 = part
+            .parse()
+            .map_err(|_| format!("invalid value '{part}'"))?;
+        if v < min || v > max {
+            return Err(format!("{v} is outside {min}-{max}"));
+        }
+        Ok(v)
+    };
+
+    let mut mask = 0u64;
+    for part in text.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step
+                    .parse()
+                    .ok()
+                    .filter(|s| *s > 0)
+                    .ok_or_else(|| format!("invalid step in '{part}'"))?;
+                (range, Some(step))
+            }
+            None => (part, None),
+        };
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (value(start)?, value(end)?)
+        } else {
+            let start = value(range)?;
+            // `5/15` runs from 5 to the end of the range
+            (start, if step.is_some() { max } else { start })
+        };
+        if start > end {
+            return Err(format!("empty range '{range}'"));
+        }
+        for v in (start..=end).step_by(step.unwrap_or(1) as usize) {
+            mask |= 1u64 << v;
+        }
+    }
+    Ok(mask)
+}
+
+fn cron_error(expression: &str, reason: impl std::fmt::Display) -> VectorizerError {
+    VectorizerError::Configuration(format!("invalid cron expression '{expression}': {reason}"))
+}
+
+impl RetentionPolicy {
+    /// Whether every rule is 0, so nothing is ever pruned.
+    pub fn keeps_everything(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// IDs of the `snapshots` (ID and creation time) this policy does not
+    /// keep.
+    pub fn expired(&self, snapshots: &[(String, DateTime<Utc>)]) -> Vec<String> {
+        if self.keeps_everything() {
+            return Vec::new();
+        }
+        let mut newest_first: Vec<&(String, DateTime<Utc>)> = snapshots.iter().collect();
+        newest_first.sort_by(|a, b| b.1.cmp(&a.1));
+
+        let mut keep: HashSet<&str> = newest_first
+            .iter()
+            .take(self.keep_last)
+            .map(|(id, _)| id.as_str())
+            .collect();
+        let tiers: [(usize, fn(&DateTime<Utc>) -> (i32, u32)); 3] = [
+            (self.keep_daily, |t| (t.year(), t.ordinal())),
+            (self.keep_weekly, |t| {
+                let week = t.iso_week();
+                (week.year(), week.week())
+            }),
+            (self.keep_monthly, |t| (t.year(), t.month())),
+        ];
+        for (count, bucket) in tiers {
+            let mut seen = HashSet::new();
+            for (id, created_at) in &newest_first {
+                if seen.len() == count {
+                    break;
+                }
+                // The first snapshot of a bucket is its newest
+                if seen.insert(bucket(created_at)) {
+                    keep.insert(id.as_str());
+                }
+            }
+        }
+
+        newest_first
+            .into_iter()
+            .filter(|(id, _)| !keep.contains(id.as_str()))
+            .map(|(id, _)| id.clone())
+            .collect()
+    }
+}
+
+/// A job's schedule and the outcome of its runs, as served by
+/// `GET /admin/backups/schedule`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupJobStatus {
+    /// Job name.
+    pub name: String,
+    /// Cron expression as configured.
+    pub cron: String,
+    /// Collections snapshotted; empty for a full snapshot.
+    pub collections: Vec<String>,
+    /// Retention rules applied after each successful run.
+    pub retention: RetentionPolicy,
+    /// When the next scheduled run is due.
+    pub next_run_at: Option<DateTime<Utc>>,
+    /// When the most recent run started.
+    pub last_run_at: Option<DateTime<Utc>>,
+    /// When the most recent successful run started.
+    pub last_success_at: Option<DateTime<Utc>>,
+    /// When the most recent failed run started.
+    pub last_failure_at: Option<DateTime<Utc>>,
+    /// Error of the most recent run, cleared on success.
+    pub last_error: Option<String>,
+    /// Wall-clock duration of the most recent run.
+    pub last_duration_ms: Option<u64>,
+    /// Snapshot IDs the most recent run created.
+    pub last_snapshots: Vec<String>,
+    /// Runs since the server started.
+    pub runs: u64,
+    /// Failed runs since the server started.
+    pub failures: u64,
+    /// Snapshots pruned by retention since the server started.
+    pub pruned: u64,
+}
+
+/// Snapshots created and pruned by one run.
+#[derive(Debug, Default)]
+struct RunOutcome {
+    snapshots: Vec<String>,
+    pruned: usize,
+}
+
+struct ScheduledJob {
+    config: BackupJobConfig,
+    schedule: CronSchedule,
+    /// Full-snapshot jobs only
+    snapshots: Option<Arc<SnapshotManager>>,
+    status: Mutex<BackupJobStatus>,
+    /// Held for the duration of a run, so manual and scheduled runs of a
+    /// job never overlap
+    running: tokio::sync::Mutex<()>,
+}
+
+/// Runs the configured backup jobs.
+///
+/// Dropping the scheduler does NOT stop a spawned loop — call
+/// [`BackupScheduler::stop`] first.
+pub struct BackupScheduler {
+    store: Arc<VectorStore>,
+    auto_save: Option<Arc<AutoSaveManager>>,
+    metrics: Arc<dyn MetricsSink>,
+    jobs: Vec<ScheduledJob>,
+    /// Shutdown flag. Set to `true` to stop the loop.
+    pub shutdown: Arc<AtomicBool>,
+}
+
+impl BackupScheduler {
+    /// Validate `config` and prepare its jobs. Full snapshots copy the
+    /// `.vecdb` files of `data_dir`; every run is recorded to `metrics`.
+    pub fn from_config(
+        store: Arc<VectorStore>,
+        config: &BackupScheduleConfig,
+        data_dir: &Path,
+        metrics: Arc<dyn MetricsSink>,
+    ) -> Result<Self> {
+        let now = Utc::now();
+        let mut names = HashSet::new();
+        let mut jobs = Vec::with_capacity(config.jobs.len());
+
+        for job in &config.jobs {
+            let valid_name = !job.name.is_empty()
+                && job
+                    .name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+            if !valid_name {
+                return Err(VectorizerError::Configuration(format!(
+                    "backup_schedule: invalid job name '{}' (use letters, digits, '-' and '_')",
+                    job.name
+                )));
+            }
+            if !names.insert(job.name.as_str()) {
+                return Err(VectorizerError::Configuration(format!(
+                    "backup_schedule: duplicate job name '{}'",
+                    job.name
+                )));
+            }
+            let schedule = CronSchedule::parse(&job.cron)?;
+            let next_run_at = schedule.next_after(now).ok_or_else(|| {
+                cron_error(&job.cron, "never matches a date in the next five years")
+            })?;
+
+            let snapshots = job.collections.is_empty().then(|| {
+                Arc::new(SnapshotManager::new(
+                    data_dir,
+                    Self::snapshots_dir(data_dir, &job.name),
+                    // Pruning is left to the retention policy
+                    usize::MAX,
+                    100 * 365 * 24,
+                ))
+            });
+            jobs.push(ScheduledJob {
+                status: Mutex::new(BackupJobStatus {
+                    name: job.name.clone(),
+                    cron: job.cron.clone(),
+                    collections: job.collections.clone(),
+                    retention: job.retention,
+                    next_run_at: Some(next_run_at),
+                    last_run_at: None,
+                    last_success_at: None,
+                    last_failure_at: None,
+                    last_error: None,
+                    last_duration_ms: None,
+                    last_snapshots: Vec::new(),
+                    runs: 0,
+                    failures: 0,
+                    pruned: 0,
+                }),
+                config: job.clone(),
+                schedule,
+                snapshots,
+                running: tokio::sync::Mutex::new(()),
+            });
+        }
+
+        Ok(Self {
+            store,
+            auto_save: None,
+            metrics,
+            jobs,
+            shutdown: Arc::new(AtomicBool::new(false)),
+        })
+    }
+
+    /// Where full snapshots of job `name` are kept.
+    pub fn snapshots_dir(data_dir: &Path, name: &str) -> PathBuf {
+        data_dir.join("scheduled_snapshots").join(name)
+    }
+
+    /// Save pending changes through `auto_save` before each full
+    /// snapshot, so the snapshot holds every acknowledged write.
+    pub fn with_auto_save(mut self, auto_save: Arc<AutoSaveManager>) -> Self {
+        self.auto_save = Some(auto_save);
+        self
+    }
+
+    /// Status of every job, in configuration order.
+    pub fn status(&self) -> Vec<BackupJobStatus> {
+        self.jobs.iter().map(|j| j.status.lock().clone()).collect()
+    }
+
+    /// Run job `name` now, outside its schedule, and return its status
+    /// afterwards. Runs even while the task is paused.
+    pub async fn run_job(&self, name: &str) -> Result<BackupJobStatus> {
+        let job = self
+            .jobs
+            .iter()
+            .find(|j| j.config.name == name)
+            .ok_or_else(|| VectorizerError::NotFound(format!("backup job '{name}'")))?;
+        self.execute(job).await?;
+        Ok(job.status.lock().clone())
+    }
+
+    /// Spawn the scheduling loop.
+    pub fn spawn(self: Arc<Self>) {
+        tokio::spawn(async move {
+            let task = TASKS.register(background_tasks::SCHEDULED_BACKUP);
+            info!("Backup scheduler started ({} jobs)", self.jobs.len());
+
+            loop {
+                let Some(next) = self
+                    .jobs
+                    .iter()
+                    .filter_map(|j| j.status.lock().next_run_at)
+                    .min()
+                else {
+                    break;
+                };
+                let wait = (next - Utc::now()).to_std().unwrap_or_default();
+                task.schedule(wait);
+                tokio::time::sleep(wait.min(MAX_SLEEP)).await;
+
+                if self.shutdown.load(Ordering::Relaxed) {
+                    info!("Backup scheduler shutting down");
+                    break;
+                }
+
+                let now = Utc::now();
+                for job in &self.jobs {
+                    {
+                        let mut status = job.status.lock();
+                        if status.next_run_at.is_none_or(|at| at > now) {
+                            continue;
+                        }
+                        status.next_run_at = job.schedule.next_after(now);
+                    }
+                    if task.is_paused() {
+                        debug!(
+                            "Backup scheduler paused, skipping job '{}'",
+                            job.config.name
+                        );
+                        continue;
+                    }
+                    task.start();
+                    let result = self.execute(job).await;
+                    task.finish(&result);
+                }
+            }
+
+            task.stop();
+        });
+    }
+
+    /// Signal the loop to stop after its current wait.
+    pub fn stop(&self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+    }
+
+    async fn execute(&self, job: &ScheduledJob) -> Result<()> {
+        let _running = job.running.lock().await;
+        let name = job.config.name.as_str();
+        let started_at = Utc::now();
+        let started = Instant::now();
+        job.status.lock().last_run_at = Some(started_at);
+
+        let result = self.take_backup(job).await;
+        let elapsed = started.elapsed();
+
+        let mut status = job.status.lock();
+        status.runs += 1;
+        status.last_duration_ms = Some(elapsed.as_millis() as u64);
+        match &result {
+            Ok(outcome) => {
+                info!(
+                    "Backup job '{}' took {} snapshot(s) in {:.1}s, pruned {}",
+                    name,
+                    outcome.snapshots.len(),
+                    elapsed.as_secs_f64(),
+                    outcome.pruned
+                );
+                status.last_success_at = Some(started_at);
+                status.last_error = None;
+                status.last_snapshots = outcome.snapshots.clone();
+                status.pruned += outcome.pruned as u64;
+            }
+            Err(e) => {
+                error!("Backup job '{}' failed: {}", name, e);
+                status.failures += 1;
+                status.last_failure_at = Some(started_at);
+                status.last_error = Some(e.to_string());
+                status.last_snapshots.clear();
+            }
+        }
+        let pruned = result.as_ref().map_or(0, |o| o.pruned);
+        self.metrics
+            .scheduled_backup(name, result.is_ok(), elapsed.as_secs_f64(), pruned as f64);
+        result.map(|_| ())
+    }
+
+    async fn take_backup(&self, job: &ScheduledJob) -> Result<RunOutcome> {
+        if let Some(manager) = &job.snapshots {
+            if let Some(auto_save) = &self.auto_save {
+                auto_save.force_save().await?;
+            }
+            let manager = manager.clone();
+            let retention = job.config.retention;
+            return tokio::task::spawn_blocking(move || {
+                let snapshot = manager.create_snapshot()?;
+                let existing: Vec<_> = manager
+                    .list_snapshots()?
+                    .into_iter()
+                    .map(|s| (s.id, s.created_at))
+                    .collect();
+                let mut pruned = 0;
+                for id in retention.expired(&existing) {
+                    if manager.delete_snapshot(&id)? {
+                        pruned += 1;
+                    }
+                }
+                Ok(RunOutcome {
+                    snapshots: vec![snapshot.id],
+                    pruned,
+                })
+            })
+            .await
+            .map_err(|e| VectorizerError::Other(format!("backup task panicked: {e}")))?;
+        }
+
+        let store = self.store.clone();
+        let collections = job.config.collections.clone();
+        let retention = job.config.retention;
+        tokio::task::spawn_blocking(move || {
+            let mut outcome = RunOutcome::default();
+            let mut failed = Vec::new();
+            for collection in &collections {
+                let result = store
+                    .snapshot_collection_native(collection)
+                    .and_then(|snapshot| {
+                        outcome
+                            .snapshots
+                            .push(format!("{}/{}", snapshot.collection, snapshot.id));
+                        let existing: Vec<_> = store
+                            .list_native_snapshots(collection)?
+                            .into_iter()
+                            .map(|s| (s.id, s.created_at))
+                            .collect();
+                        for id in retention.expired(&existing) {
+                            if store.delete_native_snapshot(collection, &id)? {
+                                outcome.pruned += 1;
+                            }
+                        }
+                        Ok(())
+                    });
+                if let Err(e) = result {
+                    failed.push(format!("{collection}: {e}"));
+                }
+            }
+            if failed.is_empty() {
+                Ok(outcome)
+            } else {
+                Err(VectorizerError::Storage(format!(
+                    "snapshot failed for {}",
+                    failed.join("; ")
+                )))
+            }
+        })
+        .await
+        .map_err(|e| VectorizerError::Other(format!("backup task panicked: {e}")))?
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use vectorizer_core::metrics_sink::NoopMetricsSink;
+
+    use super::*;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    fn next(expression: &str, after: &str) -> String {
+        CronSchedule::parse(expression)
+            .unwrap()
+            .next_after(at(after))
+            .unwrap()
+            .to_rfc3339()
+    }
+
+    #[test]
+    fn cron_next_run() {
+        assert_eq!(
+            next("*/15 * * * *", "2026-03-01T10:07:30Z"),
+            "2026-03-01T10:15:00+00:00"
+        );
+        assert_eq!(
+            next("30 2 * * *", "2026-03-01T02:30:00Z"),
+            "2026-03-02T02:30:00+00:00"
+        );
+        // 2026-03-01 is a Sunday
+        assert_eq!(
+            next("0 3 * * mon-fri", "2026-02-28T12:00:00Z"),
+            "2026-03-02T03:00:00+00:00"
+        );
+        assert_eq!(
+            next("0 0 * * 7", "2026-03-02T00:00:00Z"),
+            "2026-03-08T00:00:00+00:00"
+        );
+        assert_eq!(
+            next("@monthly", "2026-12-15T00:00:00Z"),
+            "2027-01-01T00:00:00+00:00"
+        );
+        assert_eq!(
+            next("0 0 29 feb *", "2026-01-01T00:00:00Z"),
+            "2028-02-29T00:00:00+00:00"
+        );
+        // Either day field matches once both are restricted
+        assert_eq!(
+            next("0 0 15 * sun", "2026-03-02T00:00:00Z"),
+            "2026-03-08T00:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn cron_rejects_bad_expressions() {
+        for bad in [
+            "* * * *",
+            "60 * * * *",
+            "* 24 * * *",
+            "*/0 * * * *",
+            "5-1 * * * *",
+            "* * * foo *",
+        ] {
+            assert!(CronSchedule::parse(bad).is_err(), "{bad}");
+        }
+        let never = CronSchedule::parse("0 0 30 2 *").unwrap();
+        assert_eq!(never.next_after(Utc::now()), None);
+    }
+
+    #[test]
+    fn retention_keeps_tiers() {
+        // One snapshot every 12 hours for 60 days, newest first
+        let newest = at("2026-03-31T12:00:00Z");
+        let snapshots: Vec<(String, DateTime<Utc>)> = (0..120)
+            .map(|i| {
+                let t = newest - chrono::Duration::hours(12 * i);
+                (t.format("%Y%m%d_%H%M%S").to_string(), t)
+            })
+            .collect();
+        let policy = RetentionPolicy {
+            keep_last: 3,
+            keep_daily: 7,
+            keep_weekly: 4,
+            keep_monthly: 3,
+        };
+
+        let expired: HashSet<String> = policy.expired(&snapshots).into_iter().collect();
+        let kept: Vec<&str> = snapshots
+            .iter()
+            .map(|(id, _)| id.as_str())
+            .filter(|id| !expired.contains(*id))
+            .collect();
+
+        // The 3 latest, then the newest of 7 days (2 already kept), of 4
+        // ISO weeks (2 already kept) and of 3 months (1 already kept)
+        assert_eq!(kept.len(), 3 + 5 + 2 + 2, "{kept:?}");
+        for id in ["20260325_120000", "20260322_120000", "20260315_120000"] {
+            assert!(kept.contains(&id), "{id}");
+        }
+        assert!(kept.contains(&"20260228_120000"));
+        assert!(kept.contains(&"20260131_120000"));
+        assert!(!kept.contains(&"20260308_120000"));
+        assert!(!kept.contains(&"20260131_000000"));
+
+        assert!(RetentionPolicy::default().expired(&snapshots).is_empty());
+    }
+
+    #[tokio::test]
+    async fn full_snapshot_job_applies_retention() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(crate::storage::VECDB_FILE), b"data").unwrap();
+        let config = BackupScheduleConfig {
+            jobs: vec![BackupJobConfig {
+                name: "nightly".to_string(),
+                cron: "@daily".to_string(),
+                collections: Vec::new(),
+                retention: RetentionPolicy {
+                    keep_last: 1,
+                    ..Default::default()
+                },
+            }],
+        };
+        let store = Arc::new(VectorStore::new_cpu_only());
+        let scheduler =
+            BackupScheduler::from_config(store, &config, dir.path(), Arc::new(NoopMetricsSink))
+                .unwrap();
+
+        // An older snapshot left by an earlier run
+        let old = BackupScheduler::snapshots_dir(dir.path(), "nightly").join("20200101_000000");
+        std::fs::create_dir_all(&old).unwrap();
+        std::fs::write(old.join(crate::storage::VECDB_FILE), b"old").unwrap();
+        let info = crate::storage::SnapshotInfo {
+            id: "20200101_000000".to_string(),
+            created_at: at("2020-01-01T00:00:00Z"),
+            size_bytes: 3,
+            path: old.clone(),
+            index_version: crate::storage::STORAGE_VERSION.to_string(),
+        };
+        std::fs::write(
+            old.join("snapshot.json"),
+            serde_json::to_string(&info).unwrap(),
+        )
+        .unwrap();
+
+        let status = scheduler.run_job("nightly").await.unwrap();
+        assert_eq!(status.runs, 1);
+        assert_eq!(status.failures, 0);
+        assert_eq!(status.last_snapshots.len(), 1);
+        assert!(status.last_success_at.is_some());
+        assert!(
+            BackupScheduler::snapshots_dir(dir.path(), "nightly")
+                .join(&status.last_snapshots[0])
+                .exists()
+        );
+        assert!(!old.exists());
+
+        assert!(scheduler.run_job("weekly").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn failed_runs_are_recorded() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = BackupScheduleConfig {
+            jobs: vec![BackupJobConfig {
+                name: "docs".to_string(),
+                cron: "0 * * * *".to_string(),
+                collections: vec!["missing".to_string()],
+                retention: RetentionPolicy::default(),
+            }],
+        };
+        let store = Arc::new(VectorStore::new_cpu_only());
+        let scheduler =
+            BackupScheduler::from_config(store, &config, dir.path(), Arc::new(NoopMetricsSink))
+                .unwrap();
+
+        assert!(scheduler.run_job("docs").await.is_err());
+        let status = &scheduler.status()[0];
+        assert_eq!((status.runs, status.failures), (1, 1));
+        assert!(status.last_error.as_deref().unwrap().contains("missing"));
+        assert!(status.next_run_at.is_some());
+    }
+
+    #[test]
+    fn invalid_jobs_are_rejected() {
+        let job = |name: &str, cron: &str| BackupJobConfig {
+            name: name.to_string(),
+            cron: cron.to_string(),
+            collections: Vec::new(),
+            retention: RetentionPolicy::default(),
+        };
+        let store = Arc::new(VectorStore::new_cpu_only());
+        for jobs in [
+            vec![job("a/b", "@daily")],
+            vec![job("a", "@daily"), job("a", "@hourly")],
+            vec![job("a", "@sometimes")],
+            vec![job("a", "0 0 31 4 *")],
+        ] {
+            let config = BackupScheduleConfig { jobs };
+            assert!(
+                BackupScheduler::from_config(
+                    store.clone(),
+                    &config,
+                    Path::new("."),
+                    Arc::new(NoopMetricsSink)
+                )
+                .is_err()
+            );
+        }
+    }
+}
@@ -0,0 +1,649 @@
+//! Change data capture — a numbered stream of vector inserts, updates
+//! and deletes.
+//!
+//! When `change_feed.enabled` is set, [`VectorStore`](crate::db::VectorStore)
+//! publishes one [`ChangeEvent`] per vector it writes, once the write has
+//! succeeded. Events carry a hash of the payload rather than the payload
+//! itself, so a consumer can tell whether a vector's metadata changed
+//! without the feed copying every document. The server streams the feed
+//! at `GET /changes/stream` (SSE) and `GET /ws/changes` (WebSocket), and
+//! [`spawn_sinks`] forwards it to the NATS and Kafka sinks configured
+//! under `change_feed.sinks`.
+//!
+//! Sequence numbers start at 1 when the server starts and are not
+//! persisted. The newest `buffer_size` events are kept in memory so a
+//! consumer that reconnects can resume after the last number it saw;
+//! asking for events that have already left the buffer returns a
+//! [`ResumeGap`], and the consumer has to re-read the collections it
+//! follows.
+
+use std::collections::{HashSet, VecDeque};
+use std::io;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tokio::sync::broadcast::{self, error::RecvError, error::TryRecvError};
+use tracing::{debug, info, warn};
+
+use crate::config::secret::Secret;
+use crate::config::sections::change_feed::ChangeSinkConfig;
+use crate::models::Payload;
+
+/// First wait before a sink reconnects or retries.
+const INITIAL_SINK_BACKOFF: Duration = Duration::from_secs(1);
+/// Longest wait between reconnection attempts of a sink.
+const MAX_SINK_BACKOFF: Duration = Duration::from_secs(30);
+/// Most events a Kafka sink sends in one REST proxy request.
+const KAFKA_BATCH_SIZE: usize = 500;
+/// Port used when a NATS URL does not name one.
+const DEFAULT_NATS_PORT: u16 = 4222;
+
+/// What happened to a vector.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    /// The vector was inserted (or overwritten by an insert).
+    Insert,
+    /// The vector's data or payload was updated.
+    Update,
+    /// The vector was deleted.
+    Delete,
+}
+
+/// One vector mutation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangeEvent {
+    /// Position in the feed, starting at 1.
+    pub seq: u64,
+    /// Collection the vector belongs to.
+    pub collection: String,
+    /// Vector ID.
+    pub id: String,
+    /// Kind of mutation.
+    pub kind: ChangeKind,
+    /// Hex xxh3 hash of the payload after the change (see
+    /// [`payload_hash`]). `None` for deletes and vectors without a
+    /// payload.
+    pub payload_hash: Option<String>,
+    /// When the mutation was applied.
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Hex xxh3-64 of a payload's JSON encoding.
+pub fn payload_hash(payload: &Payload) -> String {
+    let bytes = serde_json::to_vec(&payload.data).unwrap_or_default();
+    format!("{:016x}", xxhash_rust::xxh3::xxh3_64(&bytes))
+}
+
+/// A resume was asked for from a sequence number the feed no longer
+/// buffers (or never issued, e.g. one from before a restart).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResumeGap {
+    /// Oldest sequence number still buffered.
+    pub oldest: u64,
+    /// Newest sequence number issued.
+    pub latest: u64,
+}
+
+impl std::fmt::Display for ResumeGap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "change feed can only resume from sequence numbers {} to {}",
+            self.oldest.saturating_sub(1),
+            self.latest
+        )
+    }
+}
+
+/// Numbered events and the buffer they are resumed from.
+struct Recent {
+    latest: u64,
+    events: VecDeque<ChangeEvent>,
+}
+
+/// The store's change feed.
+pub struct ChangeFeed {
+    sender: broadcast::Sender<ChangeEvent>,
+    recent: Mutex<Recent>,
+    buffer_size: usize,
+}
+
+impl ChangeFeed {
+    /// A feed keeping the newest `buffer_size` events for resumes. Live
+    /// subscribers that fall more than `buffer_size` events behind skip
+    /// the oldest ones.
+    pub fn new(buffer_size: usize) -> Self {
+        let buffer_size = buffer_size.max(1);
+        let (sender, _) = broadcast::channel(buffer_size);
+        Self {
+            sender,
+            recent: Mutex::new(Recent {
+                latest: 0,
+                events: VecDeque::new(),
+            }),
+            buffer_size,
+        }
+    }
+
+    /// Number and publish a mutation of vector `id` in `collection`.
+    pub fn publish(&self, collection: &str, id: &str, kind: ChangeKind, payload: Option<&Payload>) {
+        let payload_hash = payload.map(payload_hash);
+        let mut recent = self.recent.lock();
+        recent.latest += 1;
+        let event = ChangeEvent {
+            seq: recent.latest,
+            collection: collection.to_string(),
+            id: id.to_string(),
+            kind,
+            payload_hash,
+            timestamp: Utc::now(),
+        };
+        if recent.events.len() == self.buffer_size {
+            recent.events.pop_front();
+        }
+        recent.events.push_back(event.clone());
+        // Sent under the lock so subscribers receive events in sequence
+        // order; fails only when nobody is subscribed
+        let _ = self.sender.send(event);
+    }
+
+    /// Receive every event published from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<ChangeEvent> {
+        self.sender.subscribe()
+    }
+
+    /// The buffered events after sequence number `after`, and a receiver
+    /// for every event published after them, with no gap or overlap
+    /// between the two.
+    pub fn resume(
+        &self,
+        after: u64,
+    ) -> std::result::Result<(Vec<ChangeEvent>, broadcast::Receiver<ChangeEvent>), ResumeGap> {
+        let recent = self.recent.lock();
+        let oldest = recent.events.front().map_or(recent.latest + 1, |e| e.seq);
+        if after + 1 < oldest || after > recent.latest {
+            return Err(ResumeGap {
+                oldest,
+                latest: recent.latest,
+            });
+        }
+        let missed = recent
+            .events
+            .iter()
+            .filter(|e| e.seq > after)
+            .cloned()
+            .collect();
+        Ok((missed, self.sender.subscribe()))
+    }
+
+    /// A cursor over the events of `collections` (every collection when
+    /// `None`), starting after sequence number `after` or, without one,
+    /// with the next event published.
+    pub fn cursor(
+        &self,
+        after: Option<u64>,
+        collections: Option<HashSet<String>>,
+    ) -> std::result::Result<ChangeCursor, ResumeGap> {
+        let (missed, events) = match after {
+            Some(after) => self.resume(after)?,
+            None => (Vec::new(), self.subscribe()),
+        };
+        Ok(ChangeCursor {
+            missed: missed.into(),
+            events,
+            collections,
+        })
+    }
+
+    /// Sequence number of the newest event, 0 before the first one.
+    pub fn latest_seq(&self) -> u64 {
+        self.recent.lock().latest
+    }
+}
+
+/// Events of some collections, in sequence order: the buffered ones a
+/// resume asked for, then live ones.
+pub struct ChangeCursor {
+    missed: VecDeque<ChangeEvent>,
+    events: broadcast::Receiver<ChangeEvent>,
+    collections: Option<HashSet<String>>,
+}
+
+impl ChangeCursor {
+    /// The next event. Fails with [`RecvError::Lagged`] when the cursor
+    /// fell so far behind that events were dropped, and with
+    /// [`RecvError::Closed`] once the feed is gone. Cancel-safe.
+    pub async fn next(&mut self) -> std::result::Result<ChangeEvent, RecvError> {
+        loop {
+            let event = match self.missed.pop_front() {
+                Some(event) => event,
+                None => self.events.recv().await?,
+            };
+            if self
+                .collections
+                .as_ref()
+                .is_none_or(|names| names.contains(&event.collection))
+            {
+                return Ok(event);
+            }
+        }
+    }
+}
+
+/// Forward every event of `feed` to each of `sinks`, reconnecting with
+/// backoff when a broker is unreachable. Each sink runs until the feed
+/// is dropped.
+pub fn spawn_sinks(feed: &ChangeFeed, sinks: &[ChangeSinkConfig]) {
+    for sink in sinks {
+        let events = feed.subscribe();
+        match sink.clone() {
+            ChangeSinkConfig::Nats {
+                url,
+                subject_prefix,
+                token,
+            } => {
+                info!("Change feed forwarding to NATS at {}", url);
+                tokio::spawn(run_nats_sink(events, url, subject_prefix, token));
+            }
+            ChangeSinkConfig::Kafka {
+                rest_proxy_url,
+                topic,
+            } => {
+                info!(
+                    "Change feed forwarding to Kafka topic '{}' via {}",
+                    topic, rest_proxy_url
+                );
+                tokio::spawn(run_kafka_sink(events, rest_proxy_url, topic));
+            }
+        }
+    }
+}
+
+/// `host:port` of a `nats://host[:port]` URL.
+fn nats_address(url: &str) -> String {
+    let address = url
+        .strip_prefix("nats://")
+        .or_else(|| url.strip_prefix("tcp://"))
+        .unwrap_or(url)
+        .trim_end_matches('/');
+    if address
+        .rsplit_once(':')
+        .is_some_and(|(_, port)| port.parse::<u16>().is_ok())
+    {
+        address.to_string()
+    } else {
+        format!("{}:{}", address, DEFAULT_NATS_PORT)
+    }
+}
+
+/// A collection name as one NATS subject token.
+fn subject_token(collection: &str) -> String {
+    collection
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// `PUB` of `event` to `{subject_prefix}.{collection}`.
+fn nats_message(subject_prefix: &str, event: &ChangeEvent) -> Vec<u8> {
+    let body = serde_json::to_vec(event).unwrap_or_default();
+    let mut message = format!(
+        "PUB {}.{} {}\r\n",
+        subject_prefix,
+        subject_token(&event.collection),
+        body.len()
+    )
+    .into_bytes();
+    message.extend_from_slice(&body);
+    message.extend_from_slice(b"\r\n");
+    message
+}
+
+async fn run_nats_sink(
+    mut events: broadcast::Receiver<ChangeEvent>,
+    url: String,
+    subject_prefix: String,
+    token: Option<Secret<String>>,
+) {
+    let address = nats_address(&url);
+    let mut pending = None;
+    let mut backoff = INITIAL_SINK_BACKOFF;
+    loop {
+        let session = nats_session(
+            &mut events,
+            &mut pending,
+            &mut backoff,
+            &address,
+            &subject_prefix,
+            token.as_ref(),
+        );
+        match session.await {
+            Ok(()) => return,
+            Err(e) => warn!(
+                "Change feed NATS sink {}: {}; reconnecting in {}s",
+                address,
+                e,
+                backoff.as_secs()
+            ),
+        }
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(MAX_SINK_BACKOFF);
+    }
+}
+
+/// One connection to a NATS server. Returns `Ok` once the feed is
+/// dropped. An event that could not be written is left in `pending`
+/// for the next connection.
+async fn nats_session(
+    events: &mut broadcast::Receiver<ChangeEvent>,
+    pending: &mut Option<ChangeEvent>,
+    backoff: &mut Duration,
+    address: &str,
+    subject_prefix: &str,
+    token: Option<&Secret<String>>,
+) -> io::Result<()> {
+    let stream = TcpStream::connect(address).await?;
+    let (read, mut write) = stream.into_split();
+    let mut lines = BufReader::new(read).lines();
+
+    match lines.next_line().await? {
+        Some(line) if line.starts_with("INFO") => {}
+        other => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("expected INFO from server, got {:?}", other),
+            ));
+        }
+    }
+    let mut connect = json!({
+        "verbose": false,
+        "pedantic": false,
+        "name": "vectorizer-change-feed",
+        "lang": "rust",
+        "version": env!("CARGO_PKG_VERSION"),
+    });
+    if let Some(token) = token {
+        connect["auth_token"] = json!(token.expose_secret());
+    }
+    write
+        .write_all(format!("CONNECT {}\r\nPING\r\n", connect).as_bytes())
+        .await?;
+    // The server answers the PING once it has accepted the CONNECT
+    loop {
+        match lines.next_line().await? {
+            Some(line) if line.starts_with("PONG") => break,
+            Some(line) if line.starts_with("-ERR") => return Err(io::Error::other(line)),
+            Some(_) => {}
+            None => return Err(io::ErrorKind::UnexpectedEof.into()),
+        }
+    }
+    debug!("Change feed connected to NATS at {}", address);
+    *backoff = INITIAL_SINK_BACKOFF;
+
+    loop {
+        if let Some(event) = pending.as_ref() {
+            write
+                .write_all(&nats_message(subject_prefix, event))
+                .await?;
+            *pending = None;
+        }
+        tokio::select! {
+            line = lines.next_line() => match line? {
+                Some(line) if line.starts_with("PING") => write.write_all(b"PONG\r\n").await?,
+                Some(line) if line.starts_with("-ERR") => return Err(io::Error::other(line)),
+                Some(_) => {}
+                None => return Err(io::ErrorKind::UnexpectedEof.into()),
+            },
+            event = events.recv() => match event {
+                Ok(event) => *pending = Some(event),
+                Err(RecvError::Lagged(skipped)) => warn!(
+                    "Change feed NATS sink {} fell behind; {} events dropped",
+                    address, skipped
+                ),
+                Err(RecvError::Closed) => return Ok(()),
+            },
+        }
+    }
+}
+
+async fn run_kafka_sink(
+    mut events: broadcast::Receiver<ChangeEvent>,
+    rest_proxy_url: String,
+    topic: String,
+) {
+    let endpoint = format!("{}/topics/{}", rest_proxy_url.trim_end_matches('/'), topic);
+    let client = match reqwest::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .build()
+    {
+        Ok(client) => client,
+        Err(e) => {
+            warn!("Change feed Kafka sink {} disabled: {}", endpoint, e);
+            return;
+        }
+    };
+
+    let mut batch = Vec::new();
+    let mut backoff = INITIAL_SINK_BACKOFF;
+    loop {
+        if batch.is_empty() {
+            match events.recv().await {
+                Ok(event) => batch.push(event),
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!(
+                        "Change feed Kafka sink {} fell behind; {} events dropped",
+                        endpoint, skipped
+                    );
+                    continue;
+                }
+                Err(RecvError::Closed) => return,
+            }
+        }
+        while batch.len() < KAFKA_BATCH_SIZE {
+            match events.try_recv() {
+                Ok(event) => batch.push(event),
+                Err(TryRecvError::Lagged(skipped)) => warn!(
+                    "Change feed Kafka sink {} fell behind; {} events dropped",
+                    endpoint, skipped
+                ),
+                Err(_) => break,
+            }
+        }
+
+        match kafka_produce(&client, &endpoint, &batch).await {
+            Ok(()) => {
+                debug!("Produced {} change events to {}", batch.len(), endpoint);
+                batch.clear();
+                backoff = INITIAL_SINK_BACKOFF;
+            }
+            Err(e) => {
+                warn!(
+                    "Change feed Kafka sink {}: {}; retrying in {}s",
+                    endpoint,
+                    e,
+                    backoff.as_secs()
+                );
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(MAX_SINK_BACKOFF);
+            }
+        }
+    }
+}
+
+/// Produce `batch` through the REST proxy's v2 JSON API.
+async fn kafka_produce(
+    client: &reqwest::Client,
+    endpoint: &str,
+    batch: &[ChangeEvent],
+) -> std::result::Result<(), String> {
+    let records: Vec<_> = batch
+        .iter()
+        .map(|event| {
+            json!({
+                "key": format!("{}/{}", event.collection, event.id),
+                "value": event,
+            })
+        })
+        .collect();
+    let body = serde_json::to_vec(&json!({ "records": records })).map_err(|e| e.to_string())?;
+    let response = client
+        .post(endpoint)
+        .header(
+            reqwest::header::CONTENT_TYPE,
+            "application/vnd.kafka.json.v2+json",
+        )
+        .header(reqwest::header::ACCEPT, "application/vnd.kafka.v2+json")
+        .body(body)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    let status = response.status();
+    if !status.is_success() {
+        let text = response.text().await.unwrap_or_default();
+        return Err(format!("HTTP {}: {}", status, text));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use std::sync::Arc;
+
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    fn payload(value: serde_json::Value) -> Payload {
+        Payload { data: value }
+    }
+
+    #[test]
+    fn test_events_are_numbered_and_hash_payloads() {
+        let feed = ChangeFeed::new(10);
+        let mut events = feed.subscribe();
+        let doc = payload(json!({"title": "a"}));
+        feed.publish("docs", "v1", ChangeKind::Insert, Some(&doc));
+        feed.publish("docs", "v1", ChangeKind::Delete, None);
+
+        let first = events.try_recv().unwrap();
+        assert_eq!(first.seq, 1);
+        assert_eq!(first.kind, ChangeKind::Insert);
+        assert_eq!(first.payload_hash, Some(payload_hash(&doc)));
+        let second = events.try_recv().unwrap();
+        assert_eq!(second.seq, 2);
+        assert_eq!(second.payload_hash, None);
+        assert_eq!(feed.latest_seq(), 2);
+
+        assert_eq!(
+            payload_hash(&doc),
+            payload_hash(&payload(json!({"title": "a"})))
+        );
+        assert_ne!(
+            payload_hash(&doc),
+            payload_hash(&payload(json!({"title": "b"})))
+        );
+    }
+
+    #[test]
+    fn test_resume_replays_buffered_events_then_follows_live_ones() {
+        let feed = ChangeFeed::new(3);
+        for i in 0..5 {
+            feed.publish("docs", &format!("v{}", i), ChangeKind::Insert, None);
+        }
+
+        let (missed, mut live) = feed.resume(3).unwrap();
+        assert_eq!(missed.iter().map(|e| e.seq).collect::<Vec<_>>(), vec![4, 5]);
+        feed.publish("docs", "v5", ChangeKind::Update, None);
+        assert_eq!(live.try_recv().unwrap().seq, 6);
+
+        // Seq 2 and 3 have left the buffer; 10 was never issued
+        assert_eq!(
+            feed.resume(1).unwrap_err(),
+            ResumeGap {
+                oldest: 4,
+                latest: 6
+            }
+        );
+        assert!(feed.resume(2).is_err());
+        assert!(feed.resume(10).is_err());
+        assert!(feed.resume(6).unwrap().0.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_cursor_filters_collections() {
+        let feed = ChangeFeed::new(10);
+        feed.publish("docs", "v1", ChangeKind::Insert, None);
+        feed.publish("logs", "l1", ChangeKind::Insert, None);
+
+        let only_docs = Some(HashSet::from(["docs".to_string()]));
+        let mut cursor = feed.cursor(Some(0), only_docs).unwrap();
+        feed.publish("logs", "l2", ChangeKind::Insert, None);
+        feed.publish("docs", "v2", ChangeKind::Delete, None);
+
+        assert_eq!(cursor.next().await.unwrap().id, "v1");
+        let live = cursor.next().await.unwrap();
+        assert_eq!((live.seq, live.id.as_str()), (4, "v2"));
+        assert!(feed.cursor(Some(7), None).is_err());
+    }
+
+    #[test]
+    fn test_nats_address_and_subject() {
+        assert_eq!(nats_address("nats://localhost:4223"), "localhost:4223");
+        assert_eq!(nats_address("nats://broker/"), "broker:4222");
+        assert_eq!(nats_address("10.0.0.5"), "10.0.0.5:4222");
+        assert_eq!(subject_token("my docs.v2"), "my_docs_v2");
+    }
+
+    #[tokio::test]
+    async fn test_nats_sink_publishes_events() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("nats://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let (read, mut write) = stream.into_split();
+            let mut lines = BufReader::new(read).lines();
+            write.write_all(b"INFO {}\r\n").await.unwrap();
+            let connect = lines.next_line().await.unwrap().unwrap();
+            assert!(connect.contains("\"auth_token\":\"s3cret\""));
+            assert_eq!(lines.next_line().await.unwrap().unwrap(), "PING");
+            write.write_all(b"PONG\r\n").await.unwrap();
+            let publish = lines.next_line().await.unwrap().unwrap();
+            let body = lines.next_line().await.unwrap().unwrap();
+            (publish, body)
+        });
+
+        let feed = Arc::new(ChangeFeed::new(10));
+        spawn_sinks(
+            &feed,
+            &[ChangeSinkConfig::Nats {
+                url,
+                subject_prefix: "cdc".to_string(),
+                token: Some(Secret::new("s3cret".to_string())),
+            }],
+        );
+        // Published events wait for the connection in the sink's receiver
+        feed.publish("docs", "v1", ChangeKind::Insert, None);
+
+        let (publish, body) = tokio::time::timeout(Duration::from_secs(5), server)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(publish, format!("PUB cdc.docs {}", body.len()));
+        let event: ChangeEvent = serde_json::from_str(&body).unwrap();
+        assert_eq!(event.id, "v1");
+        assert_eq!(event.seq, 1);
+    }
+}
@@ -0,0 +1,88 @@
+//! Batched search — several queries against one collection in one call.
+//!
+//! Approximate queries on the in-memory graph are searched together
+//! against one snapshot of it (see
+//! [`OptimizedHnswIndex::search_batch_until`]), in parallel on the rayon
+//! pool, whose work stealing keeps cores busy when some queries take
+//! longer than others. Queries that go another way — exact scans,
+//! binary-quantized collections, the on-disk graph of a cold collection —
+//! run through their usual single-query search, also spread over the pool.
+//!
+//! [`OptimizedHnswIndex::search_batch_until`]: crate::db::OptimizedHnswIndex::search_batch_until
+
+use rayon::prelude::*;
+use tracing::debug;
+
+use super::Collection;
+use crate::db::search_deadline::{SearchDeadline, SearchOutcome};
+use crate::error::Result;
+use crate::models::{DistanceMetric, QuantizationConfig, vector_utils};
+
+/// One query of a batched search.
+#[derive(Debug, Clone)]
+pub struct BatchQuery {
+    /// Query vector.
+    pub vector: Vec<f32>,
+    /// Maximum number of results.
+    pub limit: usize,
+    /// Score every vector instead of walking the HNSW graph.
+    pub exact: bool,
+    /// When this query stops and returns what it has.
+    pub deadline: SearchDeadline,
+}
+
+impl Collection {
+    /// Run `queries`, returning one outcome per query in the same order.
+    /// A failing query (wrong dimension, say) doesn't affect the others.
+    pub fn search_batch_until(&self, queries: &[BatchQuery]) -> Vec<Result<SearchOutcome>> {
+        let mut done: Vec<Option<SearchOutcome>> = vec![None; queries.len()];
+
+        let graph = self.disk_index.read().is_none()
+            && !matches!(self.config.quantization, QuantizationConfig::Binary);
+        let grouped: Vec<(usize, Vec<f32>)> = queries
+            .iter()
+            .enumerate()
+            .filter(|(_, q)| graph && !q.exact && q.vector.len() == self.config.dimension)
+            .map(|(i, q)| {
+                let vector = if matches!(self.config.metric, DistanceMetric::Cosine) {
+                    vector_utils::normalize_vector(&q.vector)
+                } else {
+                    q.vector.clone()
+                };
+                (i, vector)
+            })
+            .collect();
+
+        if !grouped.is_empty() {
+            let entries: Vec<(&[f32], usize, SearchDeadline)> = grouped
+                .iter()
+                .map(|(i, v)| (v.as_slice(), queries[*i].limit, queries[*i].deadline))
+                .collect();
+            let found =
+                self.with_pinned_ef_search(|| self.index.read().search_batch_until(&entries));
+            match found {
+                Ok(found) => {
+                    for ((i, _), (neighbors, timed_out, stats)) in grouped.iter().zip(found) {
+                        done[*i] = Some(SearchOutcome {
+                            results: self.neighbor_results(neighbors),
+                            timed_out,
+                            stats,
+                        });
+                    }
+                }
+                // Left undone, so each query reruns alone below and
+                // reports its own error.
+                Err(e) => debug!("Batched graph search on '{}' failed: {}", self.name, e),
+            }
+        }
+
+        done.into_par_iter()
+            .zip(queries.par_iter())
+            .map(|(outcome, q)| match outcome {
+                Some(outcome) => Ok(outcome),
+                None if q.exact => self.search_exact_until(&q.vector, q.limit, &q.deadline),
+                None => self.search_until(&q.vector, q.limit, &q.deadline),
+            })
+            .collect()
+    }
+}
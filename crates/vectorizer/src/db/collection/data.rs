@@ -3,7 +3,10 @@
 //! These methods form the primary CRUD + read surface of the
 //! [`Collection`]. They coordinate the vector storage backend, the
 //! HNSW index, the payload index, and the sparse vector index, as
-//! well as graph relationship discovery during insertion.
+//! well as graph relationship discovery and entity extraction during
+//! insertion.
+
+use std::sync::Arc;
 
 use tracing::{debug, info, warn};
 
@@ -11,8 +14,10 @@ use super::Collection;
 use crate::db::hybrid_search::{
     DenseSearchResult, HybridSearchConfig, SparseSearchResult, hybrid_search,
 };
+use crate::db::search_deadline::{SearchDeadline, SearchOutcome};
 use crate::error::{Result, VectorizerError};
-use crate::models::{DistanceMetric, SearchResult, SparseVector, Vector, vector_utils};
+use crate::models::{DistanceMetric, Payload, SearchResult, SparseVector, Vector, vector_utils};
+use crate::search::full_text::{self, FullTextHit, FullTextQuery};
 
 impl Collection {
     /// Insert a batch of vectors
@@ -57,6 +62,9 @@ impl Collection {
             }
         }
 
+        // A cold collection's on-disk graph is read-only.
+        self.make_warm()?;
+
         // Serialize writers WITHOUT blocking readers: the is-new check,
         // `vector_order`, and `vector_count` bookkeeping need batch-level
         // atomicity between concurrent writers, but `OptimizedHnswIndex`
@@ -65,6 +73,7 @@ impl Collection {
         let _writer_guard = self.insert_lock.lock();
         let index = self.index.read();
         let mut vector_order = self.vector_order.write();
+        *self.sorted_ids.write() = None;
         // Track only NEW IDs so `vector_count` stays in sync with the
         // underlying storage (both `self.vectors` and `self.quantized_vectors`
         // replace on duplicate key). Counting every batch element would make
@@ -80,7 +89,7 @@ impl Collection {
             let id = vector.id.clone();
 
             let is_new = if is_quantized {
-                !self.quantized_vectors.lock().contains_key(&id)
+                !self.quantized_vectors.read().contains_key(&id)
             } else {
                 !self.vectors.contains_key(&id)?
             };
@@ -189,13 +198,26 @@ impl Collection {
                                 // They can be created later via explicit edge creation
                             }
                         }
+
+                        // Entities and relations found in the chunk's text
+                        let extraction = &graph_config.entity_extraction;
+                        if extraction.enabled
+                            && let Some(payload) = &vector.payload
+                            && let Err(e) =
+                                crate::db::graph_entity_extraction::extract_entities_into_graph(
+                                    graph, &id, payload, extraction,
+                                )
+                        {
+                            debug!("Failed to extract entities from '{}': {}", id, e);
+                        }
                     }
                 }
             }
 
             // Store the vector — by move; the index copy was taken above.
             if is_quantized {
-                // Store as quantized vector (75% memory reduction for SQ-8bit, 96% for Binary)
+                // Store as quantized vector. This shrinks the vector table only:
+                // the HNSW index still holds the full-precision copy.
                 let quantized_vector =
                     crate::models::QuantizedVector::from_vector(vector, &self.config.quantization);
                 debug!(
@@ -205,7 +227,7 @@ impl Collection {
                     data.len() * 4
                 );
                 self.quantized_vectors
-                    .lock()
+                    .write()
                     .insert(id.clone(), quantized_vector);
 
                 // Don't store full precision vector to save memory
@@ -266,6 +288,8 @@ impl Collection {
             });
         }
 
+        self.make_warm()?;
+
         let id = vector.id.clone();
         let mut data = vector.data.clone();
 
@@ -275,7 +299,7 @@ impl Collection {
             crate::models::QuantizationConfig::SQ { bits: 8 }
                 | crate::models::QuantizationConfig::Binary
         ) {
-            self.quantized_vectors.lock().contains_key(&id)
+            self.quantized_vectors.read().contains_key(&id)
         } else {
             self.vectors.contains_key(&id)?
         };
@@ -326,7 +350,7 @@ impl Collection {
                 &self.config.quantization,
             );
             self.quantized_vectors
-                .lock()
+                .write()
                 .insert(id.clone(), quantized_vector);
         } else {
             // Update full precision storage
@@ -350,6 +374,8 @@ impl Collection {
 
     /// Delete a vector
     pub fn delete(&self, vector_id: &str) -> Result<()> {
+        self.make_warm()?;
+
         // Remove from payload index
         self.payload_index.remove_vector(vector_id);
 
@@ -365,7 +391,7 @@ impl Collection {
             crate::models::QuantizationConfig::SQ { bits: 8 }
                 | crate::models::QuantizationConfig::Binary
         ) {
-            self.quantized_vectors.lock().remove(vector_id).is_some()
+            self.quantized_vectors.write().remove(vector_id).is_some()
         } else {
             self.vectors.remove(vector_id)?
         };
@@ -377,6 +403,7 @@ impl Collection {
         // Remove from order tracking
         let mut vector_order = self.vector_order.write();
         vector_order.retain(|id| id != vector_id);
+        *self.sorted_ids.write() = None;
 
         // Remove from index
         let index = self.index.write();
@@ -401,7 +428,7 @@ impl Collection {
         ) {
             let quantized_vector = self
                 .quantized_vectors
-                .lock()
+                .read()
                 .get(vector_id)
                 .cloned()
                 .ok_or_else(|| VectorizerError::VectorNotFound(vector_id.to_string()))?;
@@ -432,8 +459,83 @@ impl Collection {
         Ok(normalized_vector)
     }
 
+    /// Run `f` on the stored payload of `vector_id` without cloning or
+    /// dequantizing the vector; `None` when there is no such vector.
+    pub fn with_payload<T>(
+        &self,
+        vector_id: &str,
+        f: impl FnOnce(Option<&Payload>) -> T,
+    ) -> Option<T> {
+        if matches!(
+            self.config.quantization,
+            crate::models::QuantizationConfig::SQ { bits: 8 }
+                | crate::models::QuantizationConfig::Binary
+        ) {
+            return self
+                .quantized_vectors
+                .read()
+                .get(vector_id)
+                .map(|qv| f(qv.payload.as_ref()));
+        }
+        self.vectors.with_payload(vector_id, f)
+    }
+
+    /// Ids of every vector, in insertion order
+    pub fn vector_ids(&self) -> Vec<String> {
+        self.vector_order.read().clone()
+    }
+
+    /// Ids of every vector, sorted. The sorted snapshot is kept until
+    /// the next insert or delete, so readers that walk the ids in order
+    /// page after page sort them once per change, not once per call.
+    pub fn sorted_vector_ids(&self) -> Arc<Vec<String>> {
+        // Writers clear the snapshot under the `vector_order` write
+        // lock, so one built under the read lock is never stale
+        let order = self.vector_order.read();
+        if let Some(sorted) = self.sorted_ids.read().as_ref() {
+            return Arc::clone(sorted);
+        }
+        let mut sorted = order.clone();
+        sorted.sort_unstable();
+        let sorted = Arc::new(sorted);
+        *self.sorted_ids.write() = Some(Arc::clone(&sorted));
+        sorted
+    }
+
     /// Search for similar vectors
     pub fn search(&self, query_vector: &[f32], k: usize) -> Result<Vec<SearchResult>> {
+        Ok(self
+            .search_until(query_vector, k, &SearchDeadline::NONE)?
+            .results)
+    }
+
+    /// [`Self::search`] under a per-request deadline. Returns the best
+    /// results found when it passes, with `timed_out` set, instead of
+    /// failing — see [`crate::db::search_deadline`] for which stages can
+    /// stop early. Collections with a custom scoring expression rank an
+    /// oversampled candidate set by it.
+    pub fn search_until(
+        &self,
+        query_vector: &[f32],
+        k: usize,
+        deadline: &SearchDeadline,
+    ) -> Result<SearchOutcome> {
+        let Some(scoring) = &self.config.scoring else {
+            return self.search_similar_until(query_vector, k, deadline);
+        };
+        let mut outcome =
+            self.search_similar_until(query_vector, scoring.candidates(k), deadline)?;
+        self.apply_scoring(scoring, &mut outcome.results, k);
+        Ok(outcome)
+    }
+
+    /// Top-`k` by vector similarity alone.
+    fn search_similar_until(
+        &self,
+        query_vector: &[f32],
+        k: usize,
+        deadline: &SearchDeadline,
+    ) -> Result<SearchOutcome> {
         // Validate dimension
         if query_vector.len() != self.config.dimension {
             return Err(VectorizerError::InvalidDimension {
@@ -441,6 +543,24 @@ impl Collection {
                 got: query_vector.len(),
             });
         }
+        if deadline.expired() {
+            return Ok(SearchOutcome::expired());
+        }
+
+        // Binary collections: Hamming pre-search over the 1-bit codes,
+        // then exact rescoring of the oversampled shortlist.
+        if matches!(
+            self.config.quantization,
+            crate::models::QuantizationConfig::Binary
+        ) {
+            return self.search_binary_until(
+                query_vector,
+                k,
+                super::BINARY_DEFAULT_OVERSAMPLING,
+                true,
+                deadline,
+            );
+        }
 
         // Normalize query vector for cosine similarity
         let search_vector = if matches!(self.config.metric, DistanceMetric::Cosine) {
@@ -450,9 +570,88 @@ impl Collection {
         };
 
         // Search in index
-        let index = self.index.read();
-        let neighbors = index.search(&search_vector, k)?;
+        let (neighbors, timed_out, stats) = self.index_search_until(&search_vector, k, deadline)?;
+
+        Ok(SearchOutcome {
+            results: self.neighbor_results(neighbors),
+            timed_out,
+            stats: Some(stats),
+        })
+    }
+
+    /// Exact (brute-force) search: scores every vector instead of walking
+    /// the HNSW graph, so the results are the true top-`k` under the
+    /// collection's metric. Cost is linear in the collection size — meant
+    /// for ground-truth recall measurement and tiny collections. Quantized
+    /// collections are scored against the full-precision vectors the
+    /// index keeps.
+    pub fn search_exact(&self, query_vector: &[f32], k: usize) -> Result<Vec<SearchResult>> {
+        Ok(self
+            .search_exact_until(query_vector, k, &SearchDeadline::NONE)?
+            .results)
+    }
 
+    /// [`Self::search_exact`] under a per-request deadline: the scan stops
+    /// when it passes and the best of the vectors scored so far are
+    /// returned with `timed_out` set.
+    pub fn search_exact_until(
+        &self,
+        query_vector: &[f32],
+        k: usize,
+        deadline: &SearchDeadline,
+    ) -> Result<SearchOutcome> {
+        if query_vector.len() != self.config.dimension {
+            return Err(VectorizerError::InvalidDimension {
+                expected: self.config.dimension,
+                got: query_vector.len(),
+            });
+        }
+
+        let search_vector = if matches!(self.config.metric, DistanceMetric::Cosine) {
+            vector_utils::normalize_vector(query_vector)
+        } else {
+            query_vector.to_vec()
+        };
+
+        let (neighbors, timed_out) = self.index_exact_search(&search_vector, k, deadline)?;
+        Ok(SearchOutcome {
+            results: self.neighbor_results(neighbors),
+            timed_out,
+            stats: None,
+        })
+    }
+
+    /// Score the vectors `ids` against `query_vector` on the same scale
+    /// as [`Self::search`], without walking the graph. IDs the collection
+    /// does not hold are skipped.
+    pub fn score_vectors(
+        &self,
+        query_vector: &[f32],
+        ids: &[String],
+    ) -> Result<Vec<(String, f32)>> {
+        if query_vector.len() != self.config.dimension {
+            return Err(VectorizerError::InvalidDimension {
+                expected: self.config.dimension,
+                got: query_vector.len(),
+            });
+        }
+
+        let search_vector = if matches!(self.config.metric, DistanceMetric::Cosine) {
+            vector_utils::normalize_vector(query_vector)
+        } else {
+            query_vector.to_vec()
+        };
+
+        let disk = self.disk_index.read().clone();
+        match disk {
+            Some(disk) => disk.exact_scores(&search_vector, ids),
+            None => self.index.read().exact_scores(&search_vector, ids),
+        }
+    }
+
+    /// Attach stored vectors and payloads to `(id, score)` pairs, skipping
+    /// IDs whose vector is gone.
+    pub(super) fn neighbor_results(&self, neighbors: Vec<(String, f32)>) -> Vec<SearchResult> {
         // Build results - check quantized storage first if quantization is enabled
         let mut results = Vec::with_capacity(neighbors.len());
         let use_quantization = matches!(
@@ -464,7 +663,7 @@ impl Collection {
         for (id, score) in neighbors {
             let vector = if use_quantization {
                 // Get from quantized storage and dequantize on-demand
-                if let Some(quantized) = self.quantized_vectors.lock().get(&id) {
+                if let Some(quantized) = self.quantized_vectors.read().get(&id) {
                     quantized.to_vector()
                 } else {
                     continue; // Vector not found
@@ -491,7 +690,16 @@ impl Collection {
             });
         }
 
-        Ok(results)
+        results
+    }
+
+    /// Rank vectors by BM25 over text-indexed payload fields, reading the
+    /// payload index's postings instead of the vectors; only the returned
+    /// hits' payloads are loaded.
+    pub fn full_text_search(&self, query: &FullTextQuery) -> Result<Vec<FullTextHit>> {
+        full_text::search(&self.payload_index, query, |id| {
+            self.get_vector(id).ok().and_then(|vector| vector.payload)
+        })
     }
 
     /// Search for similar vectors and return an execution trace alongside results.
@@ -526,8 +734,7 @@ impl Collection {
 
         // Time the HNSW search
         let t1 = Instant::now();
-        let index = self.index.read();
-        let neighbors = index.search(&search_vector, k)?;
+        let neighbors = self.index_search(&search_vector, k)?;
         let hnsw_ms = t1.elapsed().as_secs_f64() * 1_000.0;
         let visited_nodes = neighbors.len();
 
@@ -544,7 +751,7 @@ impl Collection {
         for (id, score) in neighbors {
             let tq = Instant::now();
             let vector = if use_quantization {
-                if let Some(quantized) = self.quantized_vectors.lock().get(&id) {
+                if let Some(quantized) = self.quantized_vectors.read().get(&id) {
                     quantized.to_vector()
                 } else {
                     continue;
@@ -591,6 +798,33 @@ impl Collection {
         query_sparse: Option<&SparseVector>,
         config: HybridSearchConfig,
     ) -> Result<Vec<SearchResult>> {
+        Ok(self
+            .hybrid_search_until(query_dense, query_sparse, config, &SearchDeadline::NONE)?
+            .results)
+    }
+
+    /// The `k` vectors whose sparse parts are most similar to
+    /// `query_sparse` — the sparse leg of [`Self::hybrid_search`].
+    pub fn sparse_search(&self, query_sparse: &SparseVector, k: usize) -> Vec<SparseSearchResult> {
+        self.sparse_index
+            .read()
+            .search(query_sparse, k)
+            .into_iter()
+            .map(|(id, score)| SparseSearchResult { id, score })
+            .collect()
+    }
+
+    /// [`Self::hybrid_search`] under a per-request deadline. The dense leg
+    /// runs through [`Self::search_until`]; when it is cut short, the
+    /// fused ranking is built from its partial results and `timed_out`
+    /// is set.
+    pub fn hybrid_search_until(
+        &self,
+        query_dense: &[f32],
+        query_sparse: Option<&SparseVector>,
+        config: HybridSearchConfig,
+        deadline: &SearchDeadline,
+    ) -> Result<SearchOutcome> {
         // Validate dense query dimension
         if query_dense.len() != self.config.dimension {
             return Err(VectorizerError::InvalidDimension {
@@ -618,8 +852,10 @@ impl Collection {
         );
 
         // Perform dense search
-        let dense_results: Vec<DenseSearchResult> = self
-            .search(query_dense, config.dense_k)?
+        let dense = self.search_until(query_dense, config.dense_k, deadline)?;
+        let timed_out = dense.timed_out;
+        let dense_results: Vec<DenseSearchResult> = dense
+            .results
             .into_iter()
             .map(|r| DenseSearchResult {
                 id: r.id,
@@ -630,16 +866,9 @@ impl Collection {
         let dense_count = dense_results.len();
 
         // Perform sparse search if query_sparse is provided
-        let sparse_results: Vec<SparseSearchResult> = if let Some(query_sparse) = query_sparse {
-            let sparse_idx = self.sparse_index.read();
-            sparse_idx
-                .search(query_sparse, config.sparse_k)
-                .into_iter()
-                .map(|(id, score)| SparseSearchResult { id, score })
-                .collect()
-        } else {
-            Vec::new()
-        };
+        let sparse_results: Vec<SparseSearchResult> = query_sparse
+            .map(|query_sparse| self.sparse_search(query_sparse, config.sparse_k))
+            .unwrap_or_default();
 
         let sparse_count = sparse_results.len();
 
@@ -666,7 +895,7 @@ impl Collection {
 
         for hybrid_result in hybrid_results {
             let vector = if use_quantization {
-                if let Some(quantized) = self.quantized_vectors.lock().get(&hybrid_result.id) {
+                if let Some(quantized) = self.quantized_vectors.read().get(&hybrid_result.id) {
                     quantized.to_vector()
                 } else {
                     continue;
@@ -698,6 +927,10 @@ impl Collection {
             sparse_count
         );
 
-        Ok(results)
+        Ok(SearchOutcome {
+            results,
+            timed_out,
+            stats: None,
+        })
     }
 }
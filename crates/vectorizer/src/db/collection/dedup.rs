@@ -0,0 +1,103 @@
+//! Duplicate lookup for the collection's dedup policy (`dedup` in the
+//! collection config).
+//!
+//! Content hashes are found through the payload index, which indexes
+//! the policy's `hash_field` as a keyword. The similarity check takes
+//! the nearest neighbours from the regular search and re-scores them
+//! exactly, so a custom scoring expression does not move the threshold.
+//! In a tenant-partitioned collection only the incoming vector's own
+//! tenant is searched: another tenant's vector is never a duplicate.
+
+use serde_json::Value;
+
+use super::Collection;
+use crate::db::search_deadline::SearchDeadline;
+use crate::error::Result;
+use crate::models::{DedupReason, Duplicate, Vector};
+
+impl Collection {
+    /// The stored vector `vector` duplicates under the collection's
+    /// dedup policy, if any. `hash` is the vector's content hash (see
+    /// [`crate::models::DedupPolicy::content_hash`]). Always `None` for
+    /// a collection without a policy.
+    pub fn find_duplicate(&self, vector: &Vector, hash: Option<&str>) -> Result<Option<Duplicate>> {
+        let Some(policy) = &self.config.dedup else {
+            return Ok(None);
+        };
+        let tenant = match &self.config.tenant_partition {
+            None => None,
+            Some(partition) => {
+                let tenant = vector
+                    .payload
+                    .as_ref()
+                    .and_then(|payload| payload.data.get(&partition.key))
+                    .and_then(Value::as_str);
+                match tenant {
+                    Some(tenant) => Some((partition.key.as_str(), tenant)),
+                    // An unstamped vector belongs to no tenant
+                    None => return Ok(None),
+                }
+            }
+        };
+
+        if let (Some(field), Some(hash)) = (&policy.hash_field, hash) {
+            let mut ids: Vec<String> = self
+                .payload_index
+                .get_ids_for_keyword(field, hash)
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|id| *id != vector.id)
+                .collect();
+            // Sorted, so repeated inserts agree on the match
+            ids.sort();
+            let same_tenant = |id: &str| {
+                tenant.is_none_or(|(key, tenant)| {
+                    self.get_vector(id)
+                        .ok()
+                        .and_then(|stored| stored.payload)
+                        .is_some_and(|payload| {
+                            payload.data.get(key).and_then(Value::as_str) == Some(tenant)
+                        })
+                })
+            };
+            if let Some(id) = ids.into_iter().find(|id| same_tenant(id)) {
+                return Ok(Some(Duplicate {
+                    duplicate_of: id,
+                    reason: DedupReason::ContentHash,
+                    score: None,
+                }));
+            }
+        }
+
+        if let Some(threshold) = policy.similarity_threshold {
+            // Two neighbours: the nearest may be the vector being replaced
+            let neighbours = match tenant {
+                Some((_, tenant)) => {
+                    self.search_partition_until(&vector.data, 2, tenant, &SearchDeadline::NONE)?
+                        .results
+                }
+                None => self.search(&vector.data, 2)?,
+            };
+            let ids: Vec<String> = neighbours
+                .into_iter()
+                .map(|hit| hit.id)
+                .filter(|id| *id != vector.id)
+                .collect();
+            let nearest = self
+                .score_vectors(&vector.data, &ids)?
+                .into_iter()
+                .max_by(|a, b| a.1.total_cmp(&b.1));
+            if let Some((id, score)) = nearest
+                && score >= threshold
+            {
+                return Ok(Some(Duplicate {
+                    duplicate_of: id,
+                    reason: DedupReason::Similarity,
+                    score: Some(score),
+                }));
+            }
+        }
+
+        Ok(None)
+    }
+}
@@ -0,0 +1,250 @@
+//! Relevance feedback on search results.
+//!
+//! Clients report whether a result was relevant to the query that
+//! returned it. The collection keeps a positive / negative tally per
+//! result ID plus a bounded log of the most recent judgements for
+//! analytics. The tally turns into a learned boost in `(-1, 1)` that the
+//! scoring stage exposes as `feedback`, so an expression such as
+//! `score + 0.1 * feedback` lifts results users marked relevant.
+//!
+//! Feedback is persisted with the collection in `.vecdb` and cleared by
+//! [`Collection::clear_feedback`].
+
+use std::collections::{HashMap, VecDeque};
+
+use serde::{Deserialize, Serialize};
+
+use super::Collection;
+use crate::error::{Result, VectorizerError};
+
+/// Judgements kept in the recent-feedback log.
+pub const MAX_FEEDBACK_EVENTS: usize = 1_000;
+
+/// Pseudo-judgements added to the denominator of the boost, so one vote
+/// moves a result less than many.
+const FEEDBACK_PRIOR: f64 = 2.0;
+
+/// Whether a result was relevant to the query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedbackLabel {
+    /// The result answered the query.
+    Positive,
+    /// The result did not answer the query.
+    Negative,
+}
+
+/// One judgement on a search result.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeedbackEvent {
+    /// Query text (or an identifier of it) that returned the result.
+    pub query: String,
+    /// ID of the judged result.
+    pub result_id: String,
+    /// The judgement.
+    pub label: FeedbackLabel,
+    /// When it was recorded.
+    pub recorded_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Judgement tally of one result.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeedbackVotes {
+    /// Times marked relevant.
+    pub positive: u64,
+    /// Times marked not relevant.
+    pub negative: u64,
+}
+
+impl FeedbackVotes {
+    /// Net share of positive judgements, shrunk toward 0 while there are
+    /// few of them.
+    pub fn boost(&self) -> f64 {
+        let (positive, negative) = (self.positive as f64, self.negative as f64);
+        (positive - negative) / (positive + negative + FEEDBACK_PRIOR)
+    }
+}
+
+/// Feedback recorded on a collection's search results.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RelevanceFeedback {
+    /// Tally per result ID.
+    #[serde(default)]
+    pub votes: HashMap<String, FeedbackVotes>,
+    /// Most recent judgements, oldest first.
+    #[serde(default)]
+    pub recent: VecDeque<FeedbackEvent>,
+}
+
+impl RelevanceFeedback {
+    /// Whether nothing has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.votes.is_empty() && self.recent.is_empty()
+    }
+
+    /// Learned boost of `result_id`; 0 without feedback.
+    pub fn boost(&self, result_id: &str) -> f64 {
+        self.votes.get(result_id).map_or(0.0, FeedbackVotes::boost)
+    }
+
+    fn record(&mut self, event: FeedbackEvent) {
+        let votes = self.votes.entry(event.result_id.clone()).or_default();
+        match event.label {
+            FeedbackLabel::Positive => votes.positive += 1,
+            FeedbackLabel::Negative => votes.negative += 1,
+        }
+        if self.recent.len() == MAX_FEEDBACK_EVENTS {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(event);
+    }
+}
+
+/// Boost and tally of one judged result.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ResultFeedback {
+    /// Result ID.
+    pub result_id: String,
+    /// Learned boost, as seen by the scoring stage.
+    pub boost: f64,
+    /// Judgement tally.
+    #[serde(flatten)]
+    pub votes: FeedbackVotes,
+}
+
+/// Aggregate view of a collection's feedback for analytics.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FeedbackSummary {
+    /// Positive judgements recorded.
+    pub positive: u64,
+    /// Negative judgements recorded.
+    pub negative: u64,
+    /// Distinct results judged.
+    pub judged_results: usize,
+    /// Judged results, highest boost first.
+    pub results: Vec<ResultFeedback>,
+    /// Most recent judgements, newest first.
+    pub recent: Vec<FeedbackEvent>,
+}
+
+impl Collection {
+    /// Record a judgement on `result_id`, which must be in the
+    /// collection.
+    ///
+    /// # Errors
+    ///
+    /// [`VectorizerError::VectorNotFound`] when the collection has no
+    /// such vector.
+    pub fn record_feedback(
+        &self,
+        query: &str,
+        result_id: &str,
+        label: FeedbackLabel,
+    ) -> Result<FeedbackVotes> {
+        if self.get_vector(result_id).is_err() {
+            return Err(VectorizerError::VectorNotFound(result_id.to_string()));
+        }
+        let mut feedback = self.feedback.write();
+        feedback.record(FeedbackEvent {
+            query: query.to_string(),
+            result_id: result_id.to_string(),
+            label,
+            recorded_at: chrono::Utc::now(),
+        });
+        Ok(feedback.votes[result_id])
+    }
+
+    /// All recorded feedback, or `None` if there is none (so nothing is
+    /// written to `.vecdb`).
+    pub fn feedback(&self) -> Option<RelevanceFeedback> {
+        let feedback = self.feedback.read();
+        (!feedback.is_empty()).then(|| feedback.clone())
+    }
+
+    /// Replace the recorded feedback, e.g. with the one persisted in
+    /// `.vecdb`.
+    pub fn set_feedback(&self, feedback: Option<RelevanceFeedback>) {
+        *self.feedback.write() = feedback.unwrap_or_default();
+    }
+
+    /// Forget all feedback. Returns the number of judged results dropped.
+    pub fn clear_feedback(&self) -> usize {
+        std::mem::take(&mut *self.feedback.write()).votes.len()
+    }
+
+    /// Totals, the `top` highest- and lowest-boosted results and the
+    /// `recent` newest judgements.
+    pub fn feedback_summary(&self, top: usize, recent: usize) -> FeedbackSummary {
+        let feedback = self.feedback.read();
+        let mut results: Vec<ResultFeedback> = feedback
+            .votes
+            .iter()
+            .map(|(id, votes)| ResultFeedback {
+                result_id: id.clone(),
+                boost: votes.boost(),
+                votes: *votes,
+            })
+            .collect();
+        results.sort_by(|a, b| {
+            b.boost
+                .total_cmp(&a.boost)
+                .then_with(|| a.result_id.cmp(&b.result_id))
+        });
+        if results.len() > top.saturating_mul(2) {
+            results.drain(top..results.len() - top);
+        }
+
+        FeedbackSummary {
+            positive: feedback.votes.values().map(|v| v.positive).sum(),
+            negative: feedback.votes.values().map(|v| v.negative).sum(),
+            judged_results: feedback.votes.len(),
+            results,
+            recent: feedback.recent.iter().rev().take(recent).cloned().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn boost_grows_with_agreeing_votes() {
+        let none = FeedbackVotes::default();
+        let one = FeedbackVotes {
+            positive: 1,
+            negative: 0,
+        };
+        let many = FeedbackVotes {
+            positive: 20,
+            negative: 2,
+        };
+        let disliked = FeedbackVotes {
+            positive: 0,
+            negative: 3,
+        };
+        assert_eq!(none.boost(), 0.0);
+        assert!(one.boost() > 0.0 && one.boost() < many.boost());
+        assert!(many.boost() < 1.0);
+        assert!(disliked.boost() < 0.0 && disliked.boost() > -1.0);
+    }
+
+    #[test]
+    fn recent_log_is_bounded() {
+        let mut feedback = RelevanceFeedback::default();
+        for i in 0..MAX_FEEDBACK_EVENTS + 5 {
+            feedback.record(FeedbackEvent {
+                query: format!("q{i}"),
+                result_id: "a".to_string(),
+                label: FeedbackLabel::Positive,
+                recorded_at: chrono::Utc::now(),
+            });
+        }
+        assert_eq!(feedback.recent.len(), MAX_FEEDBACK_EVENTS);
+        assert_eq!(feedback.recent[0].query, "q5");
+        assert_eq!(
+            feedback.votes["a"].positive,
+            (MAX_FEEDBACK_EVENTS + 5) as u64
+        );
+    }
+}
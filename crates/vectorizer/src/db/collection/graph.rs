@@ -321,4 +321,49 @@ impl Collection {
 
         Ok(())
     }
+
+    /// Run entity extraction over every vector already in the collection
+    /// (see [`crate::db::graph_entity_extraction`]), creating missing
+    /// chunk nodes. Used to backfill the graph of a collection that was
+    /// filled before extraction was turned on.
+    pub fn extract_entities(
+        &self,
+        config: &crate::models::EntityExtractionConfig,
+    ) -> Result<crate::db::graph_entity_extraction::ExtractionStats> {
+        use crate::db::graph::Node;
+        use crate::db::graph_entity_extraction::{ExtractionStats, extract_entities_into_graph};
+
+        let Some(graph) = &self.graph else {
+            return Err(crate::error::VectorizerError::ConfigurationError(format!(
+                "graph is not enabled for collection '{}'",
+                self.name
+            )));
+        };
+        let vector_ids: Vec<String> = self.vector_order.read().iter().cloned().collect();
+
+        let mut stats = ExtractionStats::default();
+        for vector_id in &vector_ids {
+            let Ok(vector) = self.get_vector(vector_id) else {
+                continue;
+            };
+            let Some(payload) = &vector.payload else {
+                continue;
+            };
+            if graph.get_node(vector_id).is_none() {
+                graph.add_node(Node::from_vector(vector_id, Some(payload)))?;
+            }
+            stats.add(extract_entities_into_graph(
+                graph, vector_id, payload, config,
+            )?);
+        }
+
+        info!(
+            "Extracted {} entities and {} relations from {} vectors of collection '{}'",
+            stats.entities,
+            stats.relations,
+            vector_ids.len(),
+            self.name
+        );
+        Ok(stats)
+    }
 }
@@ -0,0 +1,224 @@
+//! Relevance judgements for hybrid search weight tuning, and the fusion
+//! weights fitted from them.
+//!
+//! Clients judge hybrid search results: the query text, the sparse query
+//! the search was sent with (if any), the judged result and whether it
+//! was relevant. The tuner in [`crate::evaluation::hybrid_tuning`]
+//! replays the judgements to fit the fusion algorithm and `alpha`;
+//! hybrid searches that name neither use the fitted pair instead of the
+//! defaults.
+//!
+//! Judgements and weights are persisted with the collection in `.vecdb`
+//! and cleared by [`Collection::clear_hybrid_tuning`].
+
+use serde::{Deserialize, Serialize};
+
+use super::{Collection, FeedbackLabel};
+use crate::db::HybridScoringAlgorithm;
+use crate::error::{Result, VectorizerError};
+use crate::models::SparseVector;
+
+/// Judgements kept per collection; the oldest are dropped first.
+pub const MAX_HYBRID_JUDGMENTS: usize = 10_000;
+
+/// One judgement on a hybrid search result.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HybridJudgment {
+    /// Query text, embedded for the dense leg when tuning.
+    pub query: String,
+    /// Sparse query of the search, replayed against the sparse index.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query_sparse: Option<SparseVector>,
+    /// ID of the judged result.
+    pub result_id: String,
+    /// The judgement.
+    pub label: FeedbackLabel,
+    /// When it was recorded.
+    pub recorded_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl HybridJudgment {
+    fn same_pair(&self, other: &HybridJudgment) -> bool {
+        self.result_id == other.result_id
+            && self.query == other.query
+            && self.query_sparse == other.query_sparse
+    }
+}
+
+/// Fusion weights fitted to a collection's judgements.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TunedHybridWeights {
+    /// Fusion algorithm.
+    pub algorithm: HybridScoringAlgorithm,
+    /// Weight of the dense leg (0.0 = pure sparse, 1.0 = pure dense).
+    pub alpha: f32,
+    /// Cut-off of the nDCG the weights were fitted on.
+    pub k: usize,
+    /// Mean nDCG@k of the judged queries with these weights.
+    pub ndcg: f32,
+    /// Mean nDCG@k of the judged queries with the default weights.
+    pub baseline_ndcg: f32,
+    /// Judged queries with at least one relevant result.
+    pub queries: usize,
+    /// Judgements the weights were fitted on.
+    pub judgments: usize,
+    /// When the fit finished.
+    pub tuned_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Judgements and tuned weights of a collection.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HybridTuning {
+    /// Recorded judgements, oldest first. A result judged again for the
+    /// same query keeps only its latest label.
+    #[serde(default)]
+    pub judgments: Vec<HybridJudgment>,
+    /// Weights of the last fit, if any.
+    #[serde(default)]
+    pub weights: Option<TunedHybridWeights>,
+}
+
+impl HybridTuning {
+    /// Whether nothing has been recorded or fitted.
+    pub fn is_empty(&self) -> bool {
+        self.judgments.is_empty() && self.weights.is_none()
+    }
+
+    fn record(&mut self, judgment: HybridJudgment) {
+        self.judgments.retain(|j| !j.same_pair(&judgment));
+        if self.judgments.len() == MAX_HYBRID_JUDGMENTS {
+            self.judgments.remove(0);
+        }
+        self.judgments.push(judgment);
+    }
+}
+
+/// Judgement totals and tuned weights of a collection.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HybridTuningSummary {
+    /// Judgements recorded.
+    pub judgments: usize,
+    /// Judgements labelled relevant.
+    pub positive: usize,
+    /// Judgements labelled not relevant.
+    pub negative: usize,
+    /// Weights of the last fit, if any.
+    pub weights: Option<TunedHybridWeights>,
+}
+
+impl Collection {
+    /// Record `judgments`, whose results must all be in the collection.
+    /// Returns the number of judgements now stored.
+    ///
+    /// # Errors
+    ///
+    /// [`VectorizerError::VectorNotFound`] when a judged result is not in
+    /// the collection; nothing is recorded then.
+    pub fn record_hybrid_judgments(&self, judgments: Vec<HybridJudgment>) -> Result<usize> {
+        if let Some(missing) = judgments
+            .iter()
+            .find(|j| self.get_vector(&j.result_id).is_err())
+        {
+            return Err(VectorizerError::VectorNotFound(missing.result_id.clone()));
+        }
+        let mut tuning = self.hybrid_tuning.write();
+        for judgment in judgments {
+            tuning.record(judgment);
+        }
+        Ok(tuning.judgments.len())
+    }
+
+    /// Recorded judgements, oldest first.
+    pub fn hybrid_judgments(&self) -> Vec<HybridJudgment> {
+        self.hybrid_tuning.read().judgments.clone()
+    }
+
+    /// Fusion weights of the last fit, if any.
+    pub fn tuned_hybrid_weights(&self) -> Option<TunedHybridWeights> {
+        self.hybrid_tuning.read().weights.clone()
+    }
+
+    /// Keep `weights` as the collection's fitted fusion weights.
+    pub fn set_tuned_hybrid_weights(&self, weights: Option<TunedHybridWeights>) {
+        self.hybrid_tuning.write().weights = weights;
+    }
+
+    /// Judgement totals and the tuned weights.
+    pub fn hybrid_tuning_summary(&self) -> HybridTuningSummary {
+        let tuning = self.hybrid_tuning.read();
+        let positive = tuning
+            .judgments
+            .iter()
+            .filter(|j| j.label == FeedbackLabel::Positive)
+            .count();
+        HybridTuningSummary {
+            judgments: tuning.judgments.len(),
+            positive,
+            negative: tuning.judgments.len() - positive,
+            weights: tuning.weights.clone(),
+        }
+    }
+
+    /// Judgements and weights, or `None` if there are none (so nothing
+    /// is written to `.vecdb`).
+    pub fn hybrid_tuning(&self) -> Option<HybridTuning> {
+        let tuning = self.hybrid_tuning.read();
+        (!tuning.is_empty()).then(|| tuning.clone())
+    }
+
+    /// Replace judgements and weights, e.g. with the ones persisted in
+    /// `.vecdb`.
+    pub fn set_hybrid_tuning(&self, tuning: Option<HybridTuning>) {
+        *self.hybrid_tuning.write() = tuning.unwrap_or_default();
+    }
+
+    /// Forget all judgements and the tuned weights, so hybrid searches
+    /// fall back to the defaults. Returns the number of judgements
+    /// dropped.
+    pub fn clear_hybrid_tuning(&self) -> usize {
+        std::mem::take(&mut *self.hybrid_tuning.write())
+            .judgments
+            .len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn judgment(query: &str, result_id: &str, label: FeedbackLabel) -> HybridJudgment {
+        HybridJudgment {
+            query: query.to_string(),
+            query_sparse: None,
+            result_id: result_id.to_string(),
+            label,
+            recorded_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn rejudging_a_result_keeps_the_latest_label() {
+        let mut tuning = HybridTuning::default();
+        tuning.record(judgment("q", "a", FeedbackLabel::Positive));
+        tuning.record(judgment("q", "b", FeedbackLabel::Positive));
+        tuning.record(judgment("other", "a", FeedbackLabel::Positive));
+        tuning.record(judgment("q", "a", FeedbackLabel::Negative));
+
+        assert_eq!(tuning.judgments.len(), 3);
+        let last = tuning
+            .judgments
+            .last()
+            .map(|j| (j.result_id.as_str(), j.label));
+        assert_eq!(last, Some(("a", FeedbackLabel::Negative)));
+    }
+
+    #[test]
+    fn judgements_are_bounded() {
+        let mut tuning = HybridTuning::default();
+        for i in 0..MAX_HYBRID_JUDGMENTS + 3 {
+            tuning.record(judgment(&format!("q{i}"), "a", FeedbackLabel::Positive));
+        }
+        assert_eq!(tuning.judgments.len(), MAX_HYBRID_JUDGMENTS);
+        assert_eq!(tuning.judgments[0].query, "q3");
+    }
+}
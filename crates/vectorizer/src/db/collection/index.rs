@@ -1,25 +1,54 @@
 //! HNSW index operations — fast batch load, dump/load to disk,
-//! cache directory integration, and online reindex.
+//! cache directory integration, online reindex, and cold collections.
 //!
 //! The index itself is built incrementally via [`Collection::insert_batch`]
 //! (in [`data`]); this module owns the bulk-load, persistence, and reindex
 //! paths that reconstitute the index from the `.vecdb` cache or flush it
 //! back out to disk.
 //!
+//! A *cold* collection serves searches from a [`DiskHnswIndex`] at
+//! [`Collection::disk_index_path`] instead of the in-memory graph. The
+//! file outlives restarts: [`Collection::fast_load_vectors`] attaches it
+//! instead of rebuilding the graph when its ID fingerprint still matches
+//! the loaded vectors. Any write turns the collection warm again.
+//!
+//! [`Collection::search_exact`] bypasses both graphs and scores every
+//! vector through [`Collection::index_exact_search`]. With the `hive-gpu`
+//! feature and `gpu.rescoring` enabled, large in-memory scans (and large
+//! binary-quantization shortlists, via [`Collection::index_rescore`]) are
+//! ranked on the GPU and only the winners are scored on the CPU.
+//!
+//! [`Collection::compact_index`] rebuilds the in-memory graph from live
+//! vectors once deletes and updates have left too many stale nodes in it;
+//! the [`IndexOptimizer`](crate::db::IndexOptimizer) calls it in the
+//! background.
+//!
 //! [`data`]: super::data
 
 use std::sync::Arc;
+use std::time::Instant;
 
 use parking_lot::RwLock;
-use tracing::{debug, info, warn};
-
-use super::Collection;
-use crate::db::optimized_hnsw::{OptimizedHnswConfig, OptimizedHnswIndex};
+use rayon::prelude::*;
+use tracing::{debug, field, info, info_span, warn};
+
+use super::{Collection, ProfileTrigger};
+use crate::db::disk_hnsw::{DiskHnswIndex, ids_fingerprint};
+use crate::db::index_optimizer::{IndexFragmentation, IndexOptimizationStatus};
+use crate::db::optimized_hnsw::{OptimizedHnswConfig, count_visited};
+use crate::db::search_deadline::{SearchDeadline, SearchStats};
 use crate::error::{Result, VectorizerError};
 use crate::models::{HnswConfig, Vector};
 
+/// IDs scored per rayon task when an exact search runs against an
+/// on-disk index.
+const EXACT_SCAN_CHUNK: usize = 4096;
+
 impl Collection {
     /// Fast load vectors with HNSW index building
+    ///
+    /// Loading into an empty collection whose on-disk index matches the
+    /// vectors attaches that index instead of building the graph.
     pub fn fast_load_vectors(&self, vectors: Vec<Vector>) -> Result<()> {
         let vectors_len = vectors.len();
         debug!(
@@ -27,7 +56,16 @@ impl Collection {
             vectors_len, self.name
         );
 
+        let build_index = if self.is_cold() {
+            // Appending: the frozen graph cannot take new points.
+            self.make_warm()?;
+            true
+        } else {
+            !(self.vector_order.read().is_empty() && self.try_attach_disk_index(&vectors))
+        };
+
         let mut vector_order = self.vector_order.write();
+        *self.sorted_ids.write() = None;
         let index = self.index.write();
 
         // Check if graph is enabled and should create nodes
@@ -47,6 +85,10 @@ impl Collection {
                         self.document_ids.insert(file_path_str.to_string(), ());
                     }
                 }
+
+                // Index payload so filtered and partitioned searches see
+                // vectors loaded from disk too
+                self.payload_index.index_vector(id.clone(), payload);
             }
 
             // Vector is already normalized by into_runtime_with_payload if needed
@@ -58,14 +100,15 @@ impl Collection {
                 crate::models::QuantizationConfig::SQ { bits: 8 }
                     | crate::models::QuantizationConfig::Binary
             ) {
-                // Store as quantized vector (75% memory reduction for SQ-8bit, 96% for Binary)
+                // Store as quantized vector. This shrinks the vector table only:
+                // the HNSW index still holds the full-precision copy.
                 let quantized_vector = crate::models::QuantizedVector::from_vector(
                     vector.clone(),
                     &self.config.quantization,
                 );
                 debug!("Storing quantized vector '{}' during fast load", id);
                 self.quantized_vectors
-                    .lock()
+                    .write()
                     .insert(id.clone(), quantized_vector);
 
                 // Don't store full precision vector to save memory
@@ -88,14 +131,18 @@ impl Collection {
             }
 
             // Add to batch for HNSW index (using full precision for search accuracy)
-            batch_vectors.push((id.clone(), vector.data.clone()));
+            if build_index {
+                batch_vectors.push((id.clone(), vector.data.clone()));
+            }
 
             // Track insertion order
             vector_order.push(id.clone());
         }
 
         // Batch insert into HNSW index
-        index.batch_add(batch_vectors)?;
+        if build_index {
+            index.batch_add(batch_vectors)?;
+        }
 
         // Update vector count
         *self.vector_count.write() += vectors_len;
@@ -154,7 +201,7 @@ impl Collection {
         };
 
         // Build the new index offline.
-        let new_index = OptimizedHnswIndex::new(self.config.dimension, new_hnsw_cfg)
+        let new_index = Self::new_index(&self.config, new_hnsw_cfg)
             .map_err(|e| VectorizerError::Storage(format!("failed to create new HNSW: {}", e)))?;
 
         let use_quantization = matches!(
@@ -163,37 +210,138 @@ impl Collection {
                 | crate::models::QuantizationConfig::Binary
         );
 
-        // Collect (id, raw_f32_data) pairs from existing storage.
-        let mut batch: Vec<(String, Vec<f32>)> = Vec::with_capacity(vector_count);
-
-        for id in vector_order.iter() {
-            let data = if use_quantization {
-                if let Some(qv) = self.quantized_vectors.lock().get(id) {
-                    qv.to_vector().data
+        if use_quantization && !self.is_cold() {
+            // Storage only has the codes; the live index has the
+            // full-precision vectors rescoring needs.
+            self.index
+                .read()
+                .live_points()?
+                .for_each_batch(new_hnsw_cfg.batch_size, |batch| new_index.batch_add(batch))?;
+        } else {
+            // (id, raw_f32_data) pairs from existing storage, added a batch
+            // at a time so a mapped index never has them all in RAM.
+            new_index.batch_add_iter(vector_order.iter().filter_map(|id| {
+                let data = if use_quantization {
+                    self.quantized_vectors.read().get(id)?.to_vector().data
                 } else {
-                    continue;
-                }
-            } else {
-                match self.vectors.get(id) {
-                    Ok(Some(v)) => v.data,
-                    _ => continue,
-                }
-            };
-            batch.push((id.clone(), data));
+                    self.vectors.get(id).ok()??.data
+                };
+                Some((id.clone(), data))
+            }))?;
         }
 
-        new_index.batch_add(batch)?;
-
         // Atomic swap: replace the live index with the new one.
         *self.index.write() = new_index;
+        if self.disk_index.write().take().is_some() {
+            self.remove_disk_index_file();
+        }
 
         info!(
             "reindex_with_params '{}': completed ({} vectors indexed with M={})",
             self.name, vector_count, new_params.m
         );
+        drop(vector_order);
+        self.profile_rebuilt_index(ProfileTrigger::Reindex);
         Ok(())
     }
 
+    /// Node counts of the in-memory HNSW graph, stale nodes included.
+    /// Cold collections report zeros; their graph is rebuilt from live
+    /// vectors on the next write.
+    pub fn index_fragmentation(&self) -> IndexFragmentation {
+        if self.is_cold() {
+            return IndexFragmentation::default();
+        }
+        let index = self.index.read();
+        IndexFragmentation {
+            graph_nodes: index.graph_len(),
+            live_nodes: index.len(),
+        }
+    }
+
+    /// Outcome of the most recent [`Self::compact_index`] runs.
+    pub fn index_optimization_status(&self) -> IndexOptimizationStatus {
+        self.optimization.lock().clone()
+    }
+
+    /// Rebuild the HNSW graph from the live vectors only, dropping the
+    /// nodes deletes and updates left behind, and return how many were
+    /// reclaimed.
+    ///
+    /// The new graph is built from a snapshot without blocking writers;
+    /// searches keep using the old graph meanwhile. `insert_lock` and the
+    /// index write lock are then held only to replay the writes that
+    /// landed during the build and swap the graphs. No-op for cold
+    /// collections.
+    pub fn compact_index(&self) -> Result<usize> {
+        if self.is_cold() {
+            return Ok(0);
+        }
+
+        let started_at = chrono::Utc::now();
+        let started = Instant::now();
+        self.optimization.lock().start(started_at);
+        let result = self.rebuild_live_index();
+        self.optimization.lock().finish(started.elapsed(), &result);
+
+        match &result {
+            Ok(reclaimed) => info!(
+                "Compacted HNSW index of '{}': {} stale nodes reclaimed in {:?}",
+                self.name,
+                reclaimed,
+                started.elapsed()
+            ),
+            Err(e) => warn!("HNSW compaction of '{}' abandoned: {}", self.name, e),
+        }
+        if result.is_ok() {
+            self.profile_rebuilt_index(ProfileTrigger::Compaction);
+        }
+        result
+    }
+
+    fn rebuild_live_index(&self) -> Result<usize> {
+        let snapshot = self.index.read().live_points()?;
+
+        let new_index = Self::new_index(
+            &self.config,
+            OptimizedHnswConfig {
+                initial_capacity: snapshot.len().max(1_024),
+                ..Self::optimized_hnsw_config(&self.config)
+            },
+        )?;
+        // Insertion order keeps the rebuilt layout deterministic; batches
+        // keep a mapped index from being copied into RAM whole.
+        snapshot.for_each_batch(new_index.config().batch_size, |batch| {
+            new_index.batch_add(batch)
+        })?;
+
+        // `make_cold` takes `insert_lock`, so the collection stays warm
+        // from this check until the swap.
+        let _writer_guard = self.insert_lock.lock();
+        let replaced = || {
+            VectorizerError::IndexError(format!(
+                "collection '{}' was re-indexed or moved to disk during compaction; will retry",
+                self.name
+            ))
+        };
+        if self.is_cold() {
+            return Err(replaced());
+        }
+        let mut index = self.index.write();
+        let delta = index.changes_since(&snapshot)?.ok_or_else(replaced)?;
+        for id in &delta.removed {
+            new_index.remove(id)?;
+        }
+        for (id, data) in &delta.upserted {
+            new_index.update(id, data)?;
+        }
+        new_index.flush()?;
+
+        let reclaimed = index.graph_len().saturating_sub(new_index.graph_len());
+        *index = new_index;
+        Ok(reclaimed)
+    }
+
     /// Dump the HNSW index to files for faster reloading
     pub fn dump_hnsw_index<P: AsRef<std::path::Path>>(&self, path: P) -> Result<String> {
         let basename = format!("{}_hnsw", self.name);
@@ -246,4 +394,340 @@ impl Collection {
         );
         Ok(())
     }
+
+    /// Whether searches are served from the on-disk HNSW index.
+    pub fn is_cold(&self) -> bool {
+        self.disk_index.read().is_some()
+    }
+
+    /// Write the HNSW graph to [`Self::disk_index_path`], serve searches
+    /// from the file and release the in-memory graph.
+    ///
+    /// Raw vectors and payloads stay where they are; only the graph moves
+    /// to disk. No-op if the collection is already cold.
+    pub fn make_cold(&self) -> Result<()> {
+        let _writer_guard = self.insert_lock.lock();
+        let _vector_order = self.vector_order.write();
+        let mut disk_index = self.disk_index.write();
+        if disk_index.is_some() {
+            return Ok(());
+        }
+
+        let graph = {
+            let index = self.index.read();
+            // Small batches sit in the insert buffer until flushed
+            index.flush()?;
+            if index.is_empty() {
+                return Err(VectorizerError::IndexError(format!(
+                    "collection '{}' has no indexed vectors to move to disk",
+                    self.name
+                )));
+            }
+            index.export_graph()?
+        };
+
+        let path = Self::disk_index_path(&self.name);
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        DiskHnswIndex::write(&path, &graph)?;
+        drop(graph);
+        let disk = DiskHnswIndex::open(&path)?;
+
+        *self.index.write() =
+            Self::new_index(&self.config, Self::optimized_hnsw_config(&self.config))?;
+        info!(
+            "Collection '{}' is cold: {} vectors served from {} ({} bytes)",
+            self.name,
+            disk.len(),
+            path.display(),
+            disk.file_len()
+        );
+        *disk_index = Some(Arc::new(disk));
+        Ok(())
+    }
+
+    /// Rebuild the in-memory graph from the on-disk index and delete the
+    /// file. Every write path calls this first; no-op for warm
+    /// collections.
+    pub fn make_warm(&self) -> Result<()> {
+        if self.disk_index.read().is_none() {
+            return Ok(());
+        }
+        let mut disk_index = self.disk_index.write();
+        let Some(disk) = disk_index.clone() else {
+            return Ok(());
+        };
+
+        let index = Self::new_index(&self.config, Self::optimized_hnsw_config(&self.config))?;
+        index.batch_add_iter(disk.live_vectors())?;
+        *self.index.write() = index;
+        *disk_index = None;
+        drop(disk_index);
+        drop(disk);
+
+        self.remove_disk_index_file();
+        info!(
+            "Collection '{}' is warm again: in-memory HNSW index rebuilt",
+            self.name
+        );
+        Ok(())
+    }
+
+    /// Attach the on-disk index for a collection about to be loaded with
+    /// `vectors`, if it still describes exactly those vectors. A stale or
+    /// unreadable file is deleted.
+    fn try_attach_disk_index(&self, vectors: &[Vector]) -> bool {
+        let path = Self::disk_index_path(&self.name);
+        if !path.exists() {
+            return false;
+        }
+
+        let disk = match DiskHnswIndex::open(&path) {
+            Ok(disk) => disk,
+            Err(e) => {
+                warn!(
+                    "Ignoring unreadable on-disk HNSW index {}: {}",
+                    path.display(),
+                    e
+                );
+                self.remove_disk_index_file();
+                return false;
+            }
+        };
+        let matches = disk.dimension() == self.config.dimension
+            && disk.metric() == self.config.metric
+            && disk.len() == vectors.len()
+            && disk.fingerprint() == ids_fingerprint(vectors.iter().map(|v| v.id.as_str()));
+        if !matches {
+            warn!(
+                "On-disk HNSW index {} no longer matches collection '{}'; rebuilding in memory",
+                path.display(),
+                self.name
+            );
+            drop(disk);
+            self.remove_disk_index_file();
+            return false;
+        }
+
+        info!(
+            "Collection '{}': attached on-disk HNSW index ({} vectors), skipping rebuild",
+            self.name,
+            disk.len()
+        );
+        *self.disk_index.write() = Some(Arc::new(disk));
+        true
+    }
+
+    fn remove_disk_index_file(&self) {
+        let path = Self::disk_index_path(&self.name);
+        match std::fs::remove_file(&path) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => warn!(
+                "Failed to remove on-disk HNSW index {}: {}",
+                path.display(),
+                e
+            ),
+        }
+    }
+
+    /// Nearest neighbours from whichever index currently serves searches.
+    pub(super) fn index_search(&self, query: &[f32], k: usize) -> Result<Vec<(String, f32)>> {
+        Ok(self.index_search_until(query, k, &SearchDeadline::NONE)?.0)
+    }
+
+    /// [`Self::index_search`] under a deadline. The on-disk graph walk
+    /// stops early once it passes; the in-memory walk is a single hnsw_rs
+    /// call and always completes. The flag is `true` when the walk was
+    /// cut short; the stats describe the walk either way. A beam pinned
+    /// on the collection applies to both.
+    pub(super) fn index_search_until(
+        &self,
+        query: &[f32],
+        k: usize,
+        deadline: &SearchDeadline,
+    ) -> Result<(Vec<(String, f32)>, bool, SearchStats)> {
+        self.with_pinned_ef_search(|| self.index_search_unpinned(query, k, deadline))
+    }
+
+    fn index_search_unpinned(
+        &self,
+        query: &[f32],
+        k: usize,
+        deadline: &SearchDeadline,
+    ) -> Result<(Vec<(String, f32)>, bool, SearchStats)> {
+        let disk = self.disk_index.read().clone();
+        let span = info_span!(
+            "hnsw.search",
+            collection = %self.name,
+            k,
+            on_disk = disk.is_some(),
+            ef_search = field::Empty,
+            candidates_visited = field::Empty,
+        )
+        .entered();
+        let (neighbors, timed_out, stats) = match disk {
+            Some(disk) => {
+                // Same beam as `OptimizedHnswIndex::search`;
+                // `search_until` widens it to at least `k`.
+                let ef_search =
+                    crate::db::optimized_hnsw::default_ef_search().unwrap_or((k * 2).max(64));
+                let (found, candidates_visited) =
+                    count_visited(|| disk.search_until(query, k, ef_search, deadline));
+                let (neighbors, timed_out) = found?;
+                let stats = SearchStats {
+                    ef_search: ef_search.max(k),
+                    candidates_visited,
+                };
+                (neighbors, timed_out, stats)
+            }
+            None => {
+                let (neighbors, stats) = self.index.read().search_with_stats(query, k)?;
+                (neighbors, false, stats)
+            }
+        };
+        span.record("ef_search", stats.ef_search);
+        span.record("candidates_visited", stats.candidates_visited);
+        Ok((neighbors, timed_out, stats))
+    }
+
+    /// Exact scores from whichever index currently serves searches.
+    pub(super) fn index_exact_scores(
+        &self,
+        query: &[f32],
+        ids: &[String],
+    ) -> Result<Vec<(String, f32)>> {
+        let disk = self.disk_index.read().clone();
+        match disk {
+            Some(disk) => disk.exact_scores(query, ids),
+            None => self.index.read().exact_scores(query, ids),
+        }
+    }
+
+    /// Exact scores of the best `k` of `ids`, in no particular order. A
+    /// shortlist large enough for the GPU is ranked there first and only
+    /// its top `k` is scored; otherwise every id is scored.
+    pub(super) fn index_rescore(
+        &self,
+        query: &[f32],
+        ids: &[String],
+        k: usize,
+    ) -> Result<Vec<(String, f32)>> {
+        let _span = info_span!(
+            "hnsw.rescore",
+            collection = %self.name,
+            shortlist = ids.len(),
+            k
+        )
+        .entered();
+        match self.gpu_top_k(query, Some(ids), k, &SearchDeadline::NONE) {
+            Some((top, _)) => self.index_exact_scores(query, &top),
+            None => self.index_exact_scores(query, ids),
+        }
+    }
+
+    /// Ids of the best `k` of `ids` (of every vector for `None`), ranked
+    /// by the installed [`GpuRescorer`], and whether `deadline` cut the
+    /// ranking short. `None` sends the caller down the CPU path: no
+    /// rescorer is installed, the scan is too small to offload, the index
+    /// is on disk, or the GPU pass failed.
+    ///
+    /// [`GpuRescorer`]: crate::gpu_adapter::GpuRescorer
+    #[cfg(feature = "hive-gpu")]
+    fn gpu_top_k(
+        &self,
+        query: &[f32],
+        ids: Option<&[String]>,
+        k: usize,
+        deadline: &SearchDeadline,
+    ) -> Option<(Vec<String>, bool)> {
+        let rescorer = crate::gpu_adapter::GpuRescorer::global()?;
+        if self.disk_index.read().is_some() || query.len() != self.config.dimension {
+            return None;
+        }
+        let metric = self.config.metric;
+        let index = self.index.read();
+        let ranked = index.with_vectors(|vectors| {
+            if !rescorer.worth_offloading(ids.map_or(vectors.len(), <[String]>::len)) {
+                return None;
+            }
+            Some(match ids {
+                Some(ids) => rescorer.top_k(
+                    metric,
+                    query,
+                    ids.iter()
+                        .filter_map(|id| vectors.get(id).map(|v| (id.as_str(), v))),
+                    k,
+                    deadline,
+                ),
+                None => rescorer.top_k(
+                    metric,
+                    query,
+                    vectors.iter().map(|(id, v)| (id.as_str(), v)),
+                    k,
+                    deadline,
+                ),
+            })
+        });
+        match ranked {
+            Ok(Some(Ok(ranked))) => Some(ranked),
+            Ok(None) => None,
+            Ok(Some(Err(e))) | Err(e) => {
+                warn!(
+                    "GPU rescoring failed for collection '{}', scoring on the CPU: {}",
+                    self.name, e
+                );
+                None
+            }
+        }
+    }
+
+    #[cfg(not(feature = "hive-gpu"))]
+    fn gpu_top_k(
+        &self,
+        _query: &[f32],
+        _ids: Option<&[String]>,
+        _k: usize,
+        _deadline: &SearchDeadline,
+    ) -> Option<(Vec<String>, bool)> {
+        None
+    }
+
+    /// True top-`k` by scoring every vector held by whichever index
+    /// currently serves searches, best first. Linear in collection size.
+    /// Once `deadline` passes the scan stops and the top-`k` of the
+    /// vectors scored so far is returned, with the flag set.
+    pub(super) fn index_exact_search(
+        &self,
+        query: &[f32],
+        k: usize,
+        deadline: &SearchDeadline,
+    ) -> Result<(Vec<(String, f32)>, bool)> {
+        let disk = self.disk_index.read().clone();
+        let (mut scored, timed_out): (Vec<(String, f32)>, bool) = match disk {
+            Some(disk) => {
+                let ids = self.vector_order.read().clone();
+                let chunks = ids.len().div_ceil(EXACT_SCAN_CHUNK);
+                let scored = ids
+                    .par_chunks(EXACT_SCAN_CHUNK)
+                    .map(|chunk| (!deadline.expired()).then(|| disk.exact_scores(query, chunk)))
+                    .while_some()
+                    .collect::<Result<Vec<_>>>()?;
+                let timed_out = scored.len() < chunks;
+                (scored.into_iter().flatten().collect(), timed_out)
+            }
+            None => match self.gpu_top_k(query, None, k, deadline) {
+                Some((top, timed_out)) => (self.index.read().exact_scores(query, &top)?, timed_out),
+                None => self.index.read().exact_scores_all_until(query, deadline)?,
+            },
+        };
+
+        if scored.len() > k {
+            scored.select_nth_unstable_by(k, |a, b| b.1.total_cmp(&a.1));
+            scored.truncate(k);
+        }
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+        Ok((scored, timed_out))
+    }
 }
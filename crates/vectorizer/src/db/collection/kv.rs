@@ -0,0 +1,376 @@
+//! Metadata key-value store kept next to a collection's vectors.
+//!
+//! Ingestion pipelines store their bookkeeping here — cursors,
+//! watermarks, schema versions — instead of running a separate database
+//! just to remember where they left off. Keys live in namespaces (the
+//! server uses one per tenant) and map to JSON values. Every write
+//! stamps the entry with the namespace's next revision, so versions
+//! never repeat even across a delete and re-create, and a write can be
+//! made conditional on the versions it read. [`Collection::kv_transaction`]
+//! checks and applies a batch of writes atomically.
+//!
+//! The store is persisted with the collection in `.vecdb` and dropped
+//! with it.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::Collection;
+use crate::error::{Result, VectorizerError};
+
+/// Longest key, in bytes.
+pub const MAX_KV_KEY_BYTES: usize = 256;
+
+/// Largest value, in bytes of serialized JSON.
+pub const MAX_KV_VALUE_BYTES: usize = 64 * 1024;
+
+/// Most keys one namespace holds.
+pub const MAX_KV_ENTRIES: usize = 10_000;
+
+/// Most checks plus writes in one transaction.
+pub const MAX_KV_TRANSACTION_OPS: usize = 100;
+
+/// One stored value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KvEntry {
+    /// The value.
+    pub value: Value,
+    /// Namespace revision of the write that stored it.
+    pub version: u64,
+    /// When it was stored.
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Keys of one namespace.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct KvNamespace {
+    /// Revision of the latest write; versions are taken from it.
+    #[serde(default)]
+    pub revision: u64,
+    /// Stored values by key.
+    #[serde(default)]
+    pub entries: BTreeMap<String, KvEntry>,
+}
+
+/// A collection's metadata store, by namespace.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MetadataKv {
+    /// Namespaces by name.
+    #[serde(default)]
+    pub namespaces: BTreeMap<String, KvNamespace>,
+}
+
+impl MetadataKv {
+    /// Whether nothing was ever written. A namespace whose keys were
+    /// all deleted is kept so its revision keeps counting.
+    pub fn is_empty(&self) -> bool {
+        self.namespaces.is_empty()
+    }
+}
+
+/// Condition on a key's version. `version` 0 requires the key to be
+/// absent.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KvCheck {
+    /// Key to check.
+    pub key: String,
+    /// Version it must be at.
+    pub version: u64,
+}
+
+/// One write in a transaction.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum KvOp {
+    /// Store `value` under `key`.
+    Put {
+        /// Key to write.
+        key: String,
+        /// Value to store.
+        value: Value,
+    },
+    /// Remove `key`; a no-op when it is absent.
+    Delete {
+        /// Key to remove.
+        key: String,
+    },
+}
+
+impl KvOp {
+    fn key(&self) -> &str {
+        match self {
+            KvOp::Put { key, .. } | KvOp::Delete { key } => key,
+        }
+    }
+}
+
+/// Outcome of one write.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct KvWriteResult {
+    /// Key written.
+    pub key: String,
+    /// New version, or `None` after a delete.
+    pub version: Option<u64>,
+    /// Whether a delete removed something; always `true` for a put.
+    pub changed: bool,
+}
+
+fn invalid(message: String) -> VectorizerError {
+    VectorizerError::InvalidConfiguration { message }
+}
+
+fn validate_key(key: &str) -> Result<()> {
+    if key.is_empty() {
+        return Err(invalid("key must not be empty".to_string()));
+    }
+    if key.len() > MAX_KV_KEY_BYTES {
+        return Err(invalid(format!(
+            "key is {} bytes, the limit is {MAX_KV_KEY_BYTES}",
+            key.len()
+        )));
+    }
+    Ok(())
+}
+
+fn validate_op(op: &KvOp) -> Result<()> {
+    validate_key(op.key())?;
+    if let KvOp::Put { key, value } = op {
+        let size = serde_json::to_vec(value)?.len();
+        if size > MAX_KV_VALUE_BYTES {
+            return Err(invalid(format!(
+                "value of '{key}' is {size} bytes, the limit is {MAX_KV_VALUE_BYTES}"
+            )));
+        }
+    }
+    Ok(())
+}
+
+impl Collection {
+    /// The value stored under `key` in `namespace`.
+    pub fn kv_get(&self, namespace: &str, key: &str) -> Option<KvEntry> {
+        self.metadata_kv
+            .read()
+            .namespaces
+            .get(namespace)?
+            .entries
+            .get(key)
+            .cloned()
+    }
+
+    /// Keys of `namespace` starting with `prefix`, in key order, at most
+    /// `limit` of them.
+    pub fn kv_list(&self, namespace: &str, prefix: &str, limit: usize) -> Vec<(String, KvEntry)> {
+        let kv = self.metadata_kv.read();
+        let Some(ns) = kv.namespaces.get(namespace) else {
+            return Vec::new();
+        };
+        ns.entries
+            .range(prefix.to_string()..)
+            .take_while(|(key, _)| key.starts_with(prefix))
+            .take(limit)
+            .map(|(key, entry)| (key.clone(), entry.clone()))
+            .collect()
+    }
+
+    /// Check every condition and, if all hold, apply every write in
+    /// order — nothing is written otherwise. Returns one result per
+    /// write.
+    ///
+    /// # Errors
+    ///
+    /// [`VectorizerError::VersionConflict`] for the first check that
+    /// fails; [`VectorizerError::InvalidConfiguration`] for an empty or
+    /// oversized key or value, too many operations, or a namespace that
+    /// would grow past [`MAX_KV_ENTRIES`].
+    pub fn kv_transaction(
+        &self,
+        namespace: &str,
+        checks: &[KvCheck],
+        ops: Vec<KvOp>,
+    ) -> Result<Vec<KvWriteResult>> {
+        if checks.len() + ops.len() > MAX_KV_TRANSACTION_OPS {
+            return Err(invalid(format!(
+                "{} operations in one transaction, the limit is {MAX_KV_TRANSACTION_OPS}",
+                checks.len() + ops.len()
+            )));
+        }
+        for check in checks {
+            validate_key(&check.key)?;
+        }
+        for op in &ops {
+            validate_op(op)?;
+        }
+
+        let mut kv = self.metadata_kv.write();
+        let current = kv.namespaces.get(namespace);
+        let stored = |key: &str| current.and_then(|ns| ns.entries.get(key));
+        for check in checks {
+            let actual = stored(&check.key).map_or(0, |e| e.version);
+            if actual != check.version {
+                return Err(VectorizerError::VersionConflict {
+                    key: check.key.clone(),
+                    expected: check.version,
+                    actual,
+                });
+            }
+        }
+        let added = ops
+            .iter()
+            .filter_map(|op| match op {
+                KvOp::Put { key, .. } => Some(key.as_str()),
+                KvOp::Delete { .. } => None,
+            })
+            .filter(|key| stored(*key).is_none())
+            .collect::<std::collections::HashSet<_>>()
+            .len();
+        let held = current.map_or(0, |ns| ns.entries.len());
+        if held + added > MAX_KV_ENTRIES {
+            return Err(invalid(format!(
+                "namespace '{namespace}' would hold more than {MAX_KV_ENTRIES} keys"
+            )));
+        }
+        if ops.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let ns = kv.namespaces.entry(namespace.to_string()).or_default();
+        let now = chrono::Utc::now();
+        // Counts as a change, so lazy loading keeps the collection in
+        // memory until it is saved
+        *self.updated_at.write() = now;
+        let results = ops
+            .into_iter()
+            .map(|op| match op {
+                KvOp::Put { key, value } => {
+                    ns.revision += 1;
+                    let version = ns.revision;
+                    ns.entries.insert(
+                        key.clone(),
+                        KvEntry {
+                            value,
+                            version,
+                            updated_at: now,
+                        },
+                    );
+                    KvWriteResult {
+                        key,
+                        version: Some(version),
+                        changed: true,
+                    }
+                }
+                KvOp::Delete { key } => {
+                    let changed = ns.entries.remove(&key).is_some();
+                    KvWriteResult {
+                        key,
+                        version: None,
+                        changed,
+                    }
+                }
+            })
+            .collect();
+        Ok(results)
+    }
+
+    /// The whole store, or `None` if it holds nothing (so nothing is
+    /// written to `.vecdb`).
+    pub fn metadata_kv(&self) -> Option<MetadataKv> {
+        let kv = self.metadata_kv.read();
+        (!kv.is_empty()).then(|| kv.clone())
+    }
+
+    /// Replace the store, e.g. with the one persisted in `.vecdb`.
+    pub fn set_metadata_kv(&self, kv: Option<MetadataKv>) {
+        *self.metadata_kv.write() = kv.unwrap_or_default();
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::models::CollectionConfig;
+
+    fn collection() -> Collection {
+        Collection::new("kv".to_string(), CollectionConfig::default())
+    }
+
+    fn put(key: &str, value: Value) -> KvOp {
+        KvOp::Put {
+            key: key.to_string(),
+            value,
+        }
+    }
+
+    #[test]
+    fn versions_come_from_the_namespace_revision() {
+        let c = collection();
+        let first = c.kv_transaction("etl", &[], vec![put("cursor", json!(10))]);
+        assert_eq!(first.unwrap()[0].version, Some(1));
+        c.kv_transaction(
+            "etl",
+            &[],
+            vec![KvOp::Delete {
+                key: "cursor".to_string(),
+            }],
+        )
+        .unwrap();
+        let again = c.kv_transaction("etl", &[], vec![put("cursor", json!(11))]);
+        assert_eq!(again.unwrap()[0].version, Some(2));
+        assert_eq!(c.kv_get("etl", "cursor").unwrap().value, json!(11));
+        assert!(c.kv_get("other", "cursor").is_none());
+    }
+
+    #[test]
+    fn failed_check_writes_nothing() {
+        let c = collection();
+        c.kv_transaction("etl", &[], vec![put("cursor", json!(1))])
+            .unwrap();
+        let stale = [KvCheck {
+            key: "cursor".to_string(),
+            version: 0,
+        }];
+        let err = c
+            .kv_transaction(
+                "etl",
+                &stale,
+                vec![put("cursor", json!(2)), put("watermark", json!("t"))],
+            )
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            VectorizerError::VersionConflict {
+                expected: 0,
+                actual: 1,
+                ..
+            }
+        ));
+        assert_eq!(c.kv_get("etl", "cursor").unwrap().value, json!(1));
+        assert!(c.kv_get("etl", "watermark").is_none());
+    }
+
+    #[test]
+    fn list_filters_by_prefix() {
+        let c = collection();
+        c.kv_transaction(
+            "etl",
+            &[],
+            vec![
+                put("cursor/a", json!(1)),
+                put("cursor/b", json!(2)),
+                put("schema", json!(3)),
+            ],
+        )
+        .unwrap();
+        let keys: Vec<String> = c
+            .kv_list("etl", "cursor/", 10)
+            .into_iter()
+            .map(|(k, _)| k)
+            .collect();
+        assert_eq!(keys, ["cursor/a", "cursor/b"]);
+        assert_eq!(c.kv_list("etl", "", 1).len(), 1);
+    }
+}
@@ -5,10 +5,22 @@
 //! in isolation:
 //!
 //! - [`data`] — insert / insert_batch / update / delete / get_vector / search / hybrid_search
-//! - [`index`] — HNSW construction, dump/load, fast batch load
+//! - [`batch`] — batched search over one graph snapshot
+//! - [`feedback`] — relevance feedback on search results and the boosts learned from it
+//! - [`hybrid_weights`] — relevance judgements of hybrid search and the fusion weights tuned
+//!   from them
+//! - [`kv`] — metadata key-value store for pipeline bookkeeping (cursors, watermarks)
+//! - [`partition`] — search restricted to one tenant's vectors of a shared collection
+//! - [`index`] — HNSW construction, dump/load, fast batch load, cold (on-disk) index,
+//!   compaction
 //! - [`persistence`] — cache load, memory accounting, vector enumeration
 //! - [`graph`] — enable_graph, populate_graph_if_empty, graph accessors
-//! - [`quantization`] — SQ quantize/dequantize, PQ train + encode, requantize migration
+//! - [`profile`] — recall / latency profile of the HNSW index against exact search
+//! - [`quantization`] — SQ quantize/dequantize, PQ train + encode, requantize migration,
+//!   binary Hamming pre-search + rescore
+//! - [`scoring`] — custom scoring expression rerank of search hits
+//! - [`settings`] — settings changed after creation (search beam, quantization, compression,
+//!   payload indexes)
 //!
 //! Constructors and trivial accessors stay in this file.
 
@@ -26,18 +38,51 @@ use dashmap::DashMap;
 use parking_lot::{Mutex, RwLock};
 use serde::{Deserialize, Serialize};
 
+use super::disk_hnsw::DiskHnswIndex;
 use super::graph_relationship_discovery::GraphRelationshipHelper;
+use super::index_optimizer::IndexOptimizationStatus;
 use super::optimized_hnsw::{OptimizedHnswConfig, OptimizedHnswIndex};
 use super::payload_index::PayloadIndex;
 use super::storage_backend::VectorStorageBackend;
 use crate::error::{Result, VectorizerError};
-use crate::models::{CollectionConfig, CollectionMetadata, SparseVectorIndex, StorageType, Vector};
+use crate::models::{
+    CollectionConfig, CollectionMetadata, QuantizationConfig, SparseVectorIndex, StorageType,
+    Vector,
+};
 
+mod batch;
 mod data;
+mod dedup;
+mod feedback;
 mod graph;
+mod hybrid_weights;
 mod index;
+mod kv;
+mod partition;
 mod persistence;
+mod profile;
 mod quantization;
+mod scoring;
+mod settings;
+
+pub use batch::BatchQuery;
+pub use feedback::{
+    FeedbackEvent, FeedbackLabel, FeedbackSummary, FeedbackVotes, MAX_FEEDBACK_EVENTS,
+    RelevanceFeedback, ResultFeedback,
+};
+pub use hybrid_weights::{
+    HybridJudgment, HybridTuning, HybridTuningSummary, MAX_HYBRID_JUDGMENTS, TunedHybridWeights,
+};
+pub use kv::{
+    KvCheck, KvEntry, KvNamespace, KvOp, KvWriteResult, MAX_KV_ENTRIES, MAX_KV_KEY_BYTES,
+    MAX_KV_TRANSACTION_OPS, MAX_KV_VALUE_BYTES, MetadataKv,
+};
+pub use profile::{
+    DEFAULT_PROFILE_K, DEFAULT_PROFILE_SAMPLE_SIZE, LatencySummary, MAX_PROFILE_SAMPLE_SIZE,
+    ProfileTrigger, RecallProfile,
+};
+pub use quantization::BINARY_DEFAULT_OVERSAMPLING;
+pub use settings::{CollectionOverrides, CollectionPatch};
 
 /// Maximum number of vector-count samples retained per collection.
 /// 60 minutes worth at one sample per minute (phase25 §6).
@@ -69,11 +114,18 @@ pub struct Collection {
     pub(super) vectors: VectorStorageBackend,
     /// Quantized vector storage (only used when quantization is enabled)
     /// Uses 75% less memory than Vec<f32> (1 byte vs 4 bytes per dimension)
-    pub(super) quantized_vectors: Arc<Mutex<HashMap<String, crate::models::QuantizedVector>>>,
+    pub(super) quantized_vectors: Arc<RwLock<HashMap<String, crate::models::QuantizedVector>>>,
     /// Vector IDs in insertion order (for persistence consistency)
     pub(super) vector_order: Arc<RwLock<Vec<String>>>,
+    /// `vector_order` sorted by id, built on demand and dropped by every
+    /// insert or delete (see `Collection::sorted_vector_ids`)
+    pub(super) sorted_ids: Arc<RwLock<Option<Arc<Vec<String>>>>>,
     /// HNSW index for similarity search
     pub(super) index: Arc<RwLock<OptimizedHnswIndex>>,
+    /// On-disk HNSW index of a cold collection. While set, searches are
+    /// served from it and `index` is empty; the first write rebuilds
+    /// `index` from it and clears it (see `Collection::make_warm`).
+    pub(super) disk_index: Arc<RwLock<Option<Arc<DiskHnswIndex>>>>,
     /// Serializes writers (`insert_batch`) without blocking readers.
     ///
     /// `OptimizedHnswIndex` is internally synchronized (every field sits
@@ -108,6 +160,19 @@ pub struct Collection {
     /// (e.g. `GET /collections/{n}`) so static collections produce no
     /// background CPU.
     pub(super) vector_count_history: Arc<RwLock<VecDeque<VectorCountSample>>>,
+    /// Outcome of the most recent HNSW compactions (`Collection::compact_index`).
+    pub(super) optimization: Arc<Mutex<IndexOptimizationStatus>>,
+    /// Recall / latency of the graph search at its last measurement
+    /// (see [`profile`]).
+    pub(super) recall_profile: Arc<RwLock<Option<RecallProfile>>>,
+    /// Relevance feedback on search results (see [`feedback`]).
+    pub(super) feedback: Arc<RwLock<RelevanceFeedback>>,
+    /// Hybrid search judgements and tuned weights (see [`hybrid_weights`]).
+    pub(super) hybrid_tuning: Arc<RwLock<HybridTuning>>,
+    /// Pipeline bookkeeping stored next to the vectors (see [`kv`]).
+    pub(super) metadata_kv: Arc<RwLock<MetadataKv>>,
+    /// Settings changed after creation (see [`settings`]).
+    pub(super) overrides: Arc<RwLock<CollectionOverrides>>,
 }
 
 impl GraphRelationshipHelper for Collection {
@@ -146,11 +211,27 @@ impl Collection {
         &self.config
     }
 
+    /// Register an additional payload field index (e.g. from a collection
+    /// template). Only vectors inserted afterwards are indexed.
+    pub fn add_payload_index(&self, config: super::payload_index::PayloadIndexConfig) {
+        self.payload_index.add_index_config(config);
+    }
+
     /// Create a new collection
+    ///
+    /// # Panics
+    ///
+    /// If the collection's files cannot be created; see [`Self::try_new`].
     pub fn new(name: String, config: CollectionConfig) -> Self {
         Self::new_with_embedding_type(name, config, "bm25".to_string())
     }
 
+    /// Create a new collection, reporting a failure to create its files
+    /// (the vector file of `"storage": "mmap"`) instead of panicking.
+    pub fn try_new(name: String, config: CollectionConfig) -> Result<Self> {
+        Self::try_new_with_owner_and_embedding(name, config, None, "bm25".to_string())
+    }
+
     /// Create a new collection with a specific owner (for HiveHub cluster mode)
     pub fn new_with_owner(name: String, config: CollectionConfig, owner_id: uuid::Uuid) -> Self {
         Self::new_with_owner_and_embedding(name, config, Some(owner_id), "bm25".to_string())
@@ -166,32 +247,30 @@ impl Collection {
     }
 
     /// Create a new collection with owner and embedding type
+    ///
+    /// # Panics
+    ///
+    /// If the collection's files cannot be created; see [`Self::try_new`].
     pub fn new_with_owner_and_embedding(
         name: String,
         config: CollectionConfig,
         owner_id: Option<uuid::Uuid>,
         embedding_type: String,
     ) -> Self {
-        // Convert HnswConfig to OptimizedHnswConfig
-        let optimized_config = OptimizedHnswConfig {
-            max_connections: config.hnsw_config.m,
-            max_connections_0: config.hnsw_config.m * 2,
-            ef_construction: config.hnsw_config.ef_construction,
-            seed: config.hnsw_config.seed,
-            distance_metric: config.metric,
-            parallel: true,
-            initial_capacity: 100_000,
-            batch_size: 1000,
-        };
-
-        // SAFE: OptimizedHnswIndex::new only fails on a zero/negative dimension
-        // or invalid HnswConfig — both impossible here because `dimension`
-        // is u32-validated by CollectionConfig and `optimized_config` is
-        // built from already-validated fields. A failure indicates a logic
-        // bug in this constructor, not a runtime condition.
+        // Only the mmap vector file can fail to be created; callers that
+        // may ask for one use `try_new`.
         #[allow(clippy::expect_used)]
-        let index = OptimizedHnswIndex::new(config.dimension, optimized_config)
-            .expect("Failed to create optimized HNSW index");
+        Self::try_new_with_owner_and_embedding(name, config, owner_id, embedding_type)
+            .expect("Failed to create collection")
+    }
+
+    fn try_new_with_owner_and_embedding(
+        name: String,
+        config: CollectionConfig,
+        owner_id: Option<uuid::Uuid>,
+        embedding_type: String,
+    ) -> Result<Self> {
+        let index = Self::new_index(&config, Self::optimized_hnsw_config(&config))?;
         let now = chrono::Utc::now();
 
         // Initialize payload index with common fields
@@ -206,6 +285,29 @@ impl Collection {
             "chunk_index".to_string(),
             super::payload_index::PayloadIndexType::Integer,
         ));
+        // Text read by `full_text_search`, only when the config asks for it
+        if let Some(full_text) = &config.full_text {
+            for field in &full_text.fields {
+                payload_index.add_index_config(super::payload_index::PayloadIndexConfig::new(
+                    field.clone(),
+                    super::payload_index::PayloadIndexType::Text,
+                ));
+            }
+        }
+        // The tenant partition key is read on every search
+        if let Some(partition) = &config.tenant_partition {
+            payload_index.add_index_config(super::payload_index::PayloadIndexConfig::new(
+                partition.key.clone(),
+                super::payload_index::PayloadIndexType::Keyword,
+            ));
+        }
+        // So is the dedup content hash on every insert
+        if let Some(field) = config.dedup.as_ref().and_then(|d| d.hash_field.clone()) {
+            payload_index.add_index_config(super::payload_index::PayloadIndexConfig::new(
+                field,
+                super::payload_index::PayloadIndexType::Keyword,
+            ));
+        }
 
         // Initialize sparse vector index
         let sparse_index = Arc::new(RwLock::new(SparseVectorIndex::new()));
@@ -214,27 +316,12 @@ impl Collection {
         let vectors = match config.storage_type.unwrap_or(StorageType::Memory) {
             StorageType::Memory => VectorStorageBackend::new_memory(),
             StorageType::Mmap => {
-                // Use a standard path for mmap files: ./data/{name}.mmap
-                // In a real implementation, the data directory should be passed in
-                let data_dir = std::path::Path::new("./data");
-                if !data_dir.exists() {
-                    let _ = std::fs::create_dir_all(data_dir);
+                let path = Self::mmap_file_path(&name);
+                if let Some(dir) = path.parent() {
+                    std::fs::create_dir_all(dir)?;
                 }
-                let path = data_dir.join(format!("{}.mmap", name));
-
-                // SAFE-ish: This is a real I/O call that can legitimately
-                // fail (permissions, disk full, corrupt file). Long-term we
-                // should propagate the error via Collection::new ->
-                // Result<Self> — tracked under
-                // phase4_enforce-no-unwrap-policy item 2.4 follow-up. Until
-                // then, an mmap failure surfaces as a startup panic, which
-                // matches the prior behaviour and is preferable to a silent
-                // fallback to in-memory storage on a path the operator
-                // explicitly chose.
-                #[allow(clippy::expect_used)]
                 let storage =
-                    crate::storage::mmap::MmapVectorStorage::open(&path, config.dimension)
-                        .expect("Failed to initialize mmap storage");
+                    crate::storage::mmap::MmapVectorStorage::create(&path, config.dimension)?;
                 VectorStorageBackend::new_mmap(storage)
             }
         };
@@ -247,14 +334,16 @@ impl Collection {
             None
         };
 
-        Self {
+        Ok(Self {
             name,
             config,
             owner_id,
             vectors,
-            quantized_vectors: Arc::new(Mutex::new(HashMap::new())),
+            quantized_vectors: Arc::new(RwLock::new(HashMap::new())),
             vector_order: Arc::new(RwLock::new(Vec::new())),
+            sorted_ids: Arc::new(RwLock::new(None)),
             index: Arc::new(RwLock::new(index)),
+            disk_index: Arc::new(RwLock::new(None)),
             insert_lock: Arc::new(Mutex::new(())),
             embedding_type: Arc::new(RwLock::new(embedding_type)),
             document_ids: Arc::new(DashMap::new()),
@@ -268,9 +357,94 @@ impl Collection {
             vector_count_history: Arc::new(RwLock::new(VecDeque::with_capacity(
                 VECTOR_COUNT_HISTORY_CAP,
             ))),
+            optimization: Arc::new(Mutex::new(IndexOptimizationStatus::default())),
+            recall_profile: Arc::new(RwLock::new(None)),
+            feedback: Arc::new(RwLock::new(RelevanceFeedback::default())),
+            hybrid_tuning: Arc::new(RwLock::new(HybridTuning::default())),
+            metadata_kv: Arc::new(RwLock::new(MetadataKv::default())),
+            overrides: Arc::new(RwLock::new(CollectionOverrides::default())),
+        })
+    }
+
+    /// Convert the collection's `HnswConfig` to the index's own config.
+    pub(super) fn optimized_hnsw_config(config: &CollectionConfig) -> OptimizedHnswConfig {
+        OptimizedHnswConfig {
+            max_connections: config.hnsw_config.m,
+            max_connections_0: config.hnsw_config.m * 2,
+            ef_construction: config.hnsw_config.ef_construction,
+            seed: config.hnsw_config.seed,
+            distance_metric: config.metric,
+            parallel: true,
+            initial_capacity: 100_000,
+            batch_size: 1000,
+        }
+    }
+
+    /// Empty HNSW index for a collection configured by `config`. Mmap
+    /// collections, and binary-quantized ones that keep full-precision
+    /// vectors only to rescore their Hamming shortlist, get an index that
+    /// keeps its vectors in a scratch file under `{data_dir}/mmap`, so
+    /// only the graph links stay in RAM.
+    pub(super) fn new_index(
+        config: &CollectionConfig,
+        hnsw_config: OptimizedHnswConfig,
+    ) -> Result<OptimizedHnswIndex> {
+        if matches!(config.quantization, QuantizationConfig::Binary)
+            || config.storage_type == Some(StorageType::Mmap)
+        {
+            let dir = vectorizer_core::paths::data_dir().join("mmap");
+            OptimizedHnswIndex::new_mapped(config.dimension, hnsw_config, &dir)
+        } else {
+            OptimizedHnswIndex::new(config.dimension, hnsw_config)
         }
     }
 
+    /// `name` percent-encoded down to `[A-Za-z0-9._-]`, so per-collection
+    /// files of tenant-prefixed names stay inside their directory and two
+    /// names never share a file (`a/b` is `a%2Fb`, `a_b` stays `a_b`).
+    fn file_stem(name: &str) -> String {
+        let mut stem = String::with_capacity(name.len());
+        for byte in name.bytes() {
+            match byte {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'.' | b'_' | b'-' => {
+                    stem.push(char::from(byte))
+                }
+                _ => stem.push_str(&format!("%{byte:02X}")),
+            }
+        }
+        stem
+    }
+
+    /// Where an mmap-backed collection named `name` keeps its raw vectors:
+    /// `{data_dir}/mmap/{name}.mmap`.
+    pub fn mmap_file_path(name: &str) -> std::path::PathBuf {
+        vectorizer_core::paths::data_dir()
+            .join("mmap")
+            .join(format!("{}.mmap", Self::file_stem(name)))
+    }
+
+    /// Where a cold collection named `name` keeps its on-disk HNSW index:
+    /// `{data_dir}/hnsw/{name}.vzhnsw`.
+    pub fn disk_index_path(name: &str) -> std::path::PathBuf {
+        vectorizer_core::paths::data_dir()
+            .join("hnsw")
+            .join(format!("{}.vzhnsw", Self::file_stem(name)))
+    }
+
+    /// Storage backend the collection's raw vectors live in.
+    pub fn storage_type(&self) -> StorageType {
+        if self.vectors.mmap_path().is_some() {
+            StorageType::Mmap
+        } else {
+            StorageType::Memory
+        }
+    }
+
+    /// Backing file of an mmap-backed collection.
+    pub fn mmap_path(&self) -> Option<std::path::PathBuf> {
+        self.vectors.mmap_path()
+    }
+
     /// Get the owner ID (tenant/user ID for multi-tenancy)
     pub fn owner_id(&self) -> Option<uuid::Uuid> {
         self.owner_id
@@ -304,6 +478,12 @@ impl Collection {
         self.document_ids.len()
     }
 
+    /// Drop `file_path` from the document registry once its chunks have
+    /// been deleted. Returns whether it was registered.
+    pub fn forget_document(&self, file_path: &str) -> bool {
+        self.document_ids.remove(file_path).is_some()
+    }
+
     /// Get the embedding type used for this collection
     pub fn get_embedding_type(&self) -> String {
         self.embedding_type.read().clone()
@@ -320,6 +500,11 @@ impl Collection {
         *self.vector_count.read()
     }
 
+    /// IDs of the `n` oldest vectors, oldest first
+    pub fn oldest_vector_ids(&self, n: usize) -> Vec<String> {
+        self.vector_order.read().iter().take(n).cloned().collect()
+    }
+
     /// Record a `(now, vector_count)` sample if the most recent sample is
     /// older than [`VECTOR_COUNT_HISTORY_MIN_SPACING_SECS`]. Drops the
     /// oldest entry once the ring buffer reaches
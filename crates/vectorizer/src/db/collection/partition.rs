@@ -0,0 +1,120 @@
+//! Search confined to one tenant's vectors of a shared collection
+//! (`tenant_partition` in the collection config).
+//!
+//! A partition is usually a small slice of the collection, so rather
+//! than walking the whole HNSW graph and throwing away other tenants'
+//! hits (which can leave a tenant with too few results) the ids stamped
+//! with the tenant are taken from the payload index and scored exactly.
+
+use super::Collection;
+use crate::db::search_deadline::{SearchDeadline, SearchOutcome};
+use crate::error::{Result, VectorizerError};
+use crate::models::{DistanceMetric, vector_utils};
+
+impl Collection {
+    /// Top-`k` among the vectors whose partition key is `tenant`, ranked
+    /// like [`Collection::search_until`] (custom scoring included).
+    ///
+    /// # Errors
+    ///
+    /// [`VectorizerError::InvalidConfiguration`] when the collection has
+    /// no tenant partition; [`VectorizerError::InvalidDimension`] for a
+    /// query of the wrong length.
+    pub fn search_partition_until(
+        &self,
+        query_vector: &[f32],
+        k: usize,
+        tenant: &str,
+        deadline: &SearchDeadline,
+    ) -> Result<SearchOutcome> {
+        let Some(partition) = &self.config.tenant_partition else {
+            return Err(VectorizerError::InvalidConfiguration {
+                message: format!("collection '{}' has no tenant partition", self.name),
+            });
+        };
+        if query_vector.len() != self.config.dimension {
+            return Err(VectorizerError::InvalidDimension {
+                expected: self.config.dimension,
+                got: query_vector.len(),
+            });
+        }
+        if deadline.expired() {
+            return Ok(SearchOutcome::expired());
+        }
+
+        let ids: Vec<String> = self
+            .payload_index
+            .get_ids_for_keyword(&partition.key, tenant)
+            .map(|ids| ids.into_iter().collect())
+            .unwrap_or_default();
+        let search_vector = if matches!(self.config.metric, DistanceMetric::Cosine) {
+            vector_utils::normalize_vector(query_vector)
+        } else {
+            query_vector.to_vec()
+        };
+        let candidates = self
+            .config
+            .scoring
+            .as_ref()
+            .map_or(k, |scoring| scoring.candidates(k));
+
+        let mut scored = self.index_rescore(&search_vector, &ids, candidates)?;
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scored.truncate(candidates);
+        let mut results = self.neighbor_results(scored);
+        if let Some(scoring) = &self.config.scoring {
+            self.apply_scoring(scoring, &mut results, k);
+        }
+        Ok(SearchOutcome::complete(results))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::models::{CollectionConfig, Payload, TenantPartitionConfig, Vector};
+
+    #[test]
+    fn search_only_sees_the_tenants_vectors() {
+        let config = CollectionConfig {
+            dimension: 2,
+            tenant_partition: Some(TenantPartitionConfig::default()),
+            ..Default::default()
+        };
+        let collection = Collection::new("shared".to_string(), config);
+        let vector = |id: &str, data: Vec<f32>, tenant: &str| {
+            Vector::with_payload(
+                id.to_string(),
+                data,
+                Payload::new(json!({"tenant_id": tenant})),
+            )
+        };
+        collection
+            .insert_batch(vec![
+                vector("a1", vec![1.0, 0.0], "acme"),
+                vector("a2", vec![0.0, 1.0], "acme"),
+                vector("g1", vec![1.0, 0.1], "globex"),
+            ])
+            .unwrap();
+
+        let outcome = collection
+            .search_partition_until(&[1.0, 0.0], 10, "acme", &SearchDeadline::NONE)
+            .unwrap();
+        let ids: Vec<&str> = outcome.results.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, ["a1", "a2"]);
+
+        let none = collection
+            .search_partition_until(&[1.0, 0.0], 10, "initech", &SearchDeadline::NONE)
+            .unwrap();
+        assert!(none.results.is_empty());
+
+        collection.delete("a1").unwrap();
+        let outcome = collection
+            .search_partition_until(&[1.0, 0.0], 10, "acme", &SearchDeadline::NONE)
+            .unwrap();
+        assert_eq!(outcome.results.len(), 1);
+    }
+}
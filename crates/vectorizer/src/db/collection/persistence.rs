@@ -204,6 +204,7 @@ impl Collection {
     pub fn load_vectors_into_memory(&self, vectors: Vec<Vector>) -> Result<()> {
         let vectors_len = vectors.len();
         let mut vector_order = self.vector_order.write();
+        *self.sorted_ids.write() = None;
 
         // Check if graph is enabled and should create nodes
         // Graph is enabled if it exists (regardless of config, since it can be enabled manually)
@@ -219,6 +220,10 @@ impl Collection {
                         self.document_ids.insert(file_path_str.to_string(), ());
                     }
                 }
+
+                // Index payload so filtered and partitioned searches see
+                // vectors loaded from disk too
+                self.payload_index.index_vector(id.clone(), payload);
             }
 
             // Store vector
@@ -274,7 +279,7 @@ impl Collection {
             crate::models::QuantizationConfig::SQ { bits: 8 }
                 | crate::models::QuantizationConfig::Binary
         ) {
-            let quantized = self.quantized_vectors.lock();
+            let quantized = self.quantized_vectors.read();
             vector_order
                 .iter()
                 .filter_map(|id| quantized.get(id).map(|qv| qv.to_vector()))
@@ -302,7 +307,7 @@ impl Collection {
 
         if use_quantization {
             // Calculate from quantized storage
-            let quantized_vectors = self.quantized_vectors.lock();
+            let quantized_vectors = self.quantized_vectors.read();
             let vector_count = quantized_vectors.len();
 
             for (id, qvector) in quantized_vectors.iter() {
@@ -0,0 +1,265 @@
+//! Recall / latency profile of the HNSW index.
+//!
+//! After the graph is rebuilt (`reindex_with_params`, `compact_index`)
+//! the collection samples its own vectors, runs each one through the
+//! graph search and through exact search, and keeps the recall@k of the
+//! graph against the exact answer plus the latency of both. The query's
+//! own vector is dropped from both result lists, so each sampled vector
+//! acts as a held-out query against the rest of the collection.
+//!
+//! The profile is persisted with the collection in `.vecdb` and reported
+//! by `GET /collections/{name}`, so the quality cost of an HNSW or
+//! quantization setting is visible without running a benchmark by hand.
+
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, info_span};
+
+use super::Collection;
+use crate::db::SearchDeadline;
+use crate::error::{Result, VectorizerError};
+
+/// Queries sampled by an automatic profile.
+pub const DEFAULT_PROFILE_SAMPLE_SIZE: usize = 100;
+
+/// Neighbours compared per query by an automatic profile.
+pub const DEFAULT_PROFILE_K: usize = 10;
+
+/// Largest sample a profile may take.
+pub const MAX_PROFILE_SAMPLE_SIZE: usize = 1_000;
+
+/// What produced a [`RecallProfile`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProfileTrigger {
+    /// HNSW rebuild with new parameters.
+    Reindex,
+    /// HNSW compaction.
+    Compaction,
+    /// Explicit request (`POST /collections/{name}/recall_profile`).
+    Request,
+}
+
+/// Latency distribution of one search path, in milliseconds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct LatencySummary {
+    /// Mean latency.
+    pub mean_ms: f64,
+    /// Median latency.
+    pub p50_ms: f64,
+    /// 99th-percentile latency.
+    pub p99_ms: f64,
+}
+
+impl LatencySummary {
+    fn from_samples(samples: &mut [Duration]) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+        samples.sort_unstable();
+        let ms = |d: Duration| d.as_secs_f64() * 1000.0;
+        let at = |q: f64| ms(samples[((samples.len() - 1) as f64 * q).round() as usize]);
+        Self {
+            mean_ms: samples.iter().copied().map(ms).sum::<f64>() / samples.len() as f64,
+            p50_ms: at(0.5),
+            p99_ms: at(0.99),
+        }
+    }
+}
+
+/// Recall and latency of the collection's graph search, measured against
+/// exact search on a sample of its own vectors.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecallProfile {
+    /// When the profile was measured.
+    pub measured_at: chrono::DateTime<chrono::Utc>,
+    /// What triggered the measurement.
+    pub trigger: ProfileTrigger,
+    /// Vectors in the collection at the time.
+    pub vector_count: usize,
+    /// Queries sampled.
+    pub sample_size: usize,
+    /// Neighbours compared per query.
+    pub k: usize,
+    /// Mean recall@k of the graph search against exact search.
+    pub recall_at_k: f64,
+    /// Lowest recall@k of any sampled query.
+    pub min_recall_at_k: f64,
+    /// Graph search latency.
+    pub hnsw_latency: LatencySummary,
+    /// Exact search latency.
+    pub exact_latency: LatencySummary,
+    /// HNSW `M` of the profiled graph.
+    pub m: usize,
+    /// HNSW `ef_construction` of the profiled graph.
+    pub ef_construction: usize,
+    /// Beam width of the graph searches; `None` for binary-quantized
+    /// collections, which search by Hamming distance instead.
+    pub ef_search: Option<usize>,
+    /// Quantization in use, e.g. `SQ { bits: 8 }`.
+    pub quantization: String,
+}
+
+impl Collection {
+    /// The most recent recall / latency profile, if one was measured.
+    pub fn recall_profile(&self) -> Option<RecallProfile> {
+        self.recall_profile.read().clone()
+    }
+
+    /// Replace the stored profile, e.g. with the one persisted in `.vecdb`.
+    pub fn set_recall_profile(&self, profile: Option<RecallProfile>) {
+        *self.recall_profile.write() = profile;
+    }
+
+    /// Measure recall@`k` and latency over `sample_size` of the
+    /// collection's vectors, spread evenly over insertion order, and
+    /// store the result as the collection's profile.
+    ///
+    /// # Errors
+    ///
+    /// Fails when `k` or `sample_size` is zero or `sample_size` is over
+    /// [`MAX_PROFILE_SAMPLE_SIZE`], when the collection holds `k` vectors
+    /// or fewer, or when a search fails.
+    pub fn measure_recall_profile(
+        &self,
+        sample_size: usize,
+        k: usize,
+        trigger: ProfileTrigger,
+    ) -> Result<RecallProfile> {
+        if k == 0 || sample_size == 0 || sample_size > MAX_PROFILE_SAMPLE_SIZE {
+            return Err(VectorizerError::InvalidConfiguration {
+                message: format!(
+                    "recall profile needs k >= 1 and 1 <= sample_size <= {}",
+                    MAX_PROFILE_SAMPLE_SIZE
+                ),
+            });
+        }
+        // Groups the sampled searches into one trace
+        let _span = info_span!(
+            "recall_profile.measure",
+            collection = %self.name,
+            sample_size,
+            k
+        )
+        .entered();
+        let sample = self.sample_vector_ids(sample_size, k)?;
+
+        let mut recalls = Vec::with_capacity(sample.len());
+        let mut hnsw_latency = Vec::with_capacity(sample.len());
+        let mut exact_latency = Vec::with_capacity(sample.len());
+        let mut ef_search = None;
+        for id in &sample {
+            let Ok(query) = self.get_vector(id) else {
+                continue;
+            };
+
+            let started = Instant::now();
+            let approx = self.search_until(&query.data, k + 1, &SearchDeadline::NONE)?;
+            hnsw_latency.push(started.elapsed());
+            ef_search = ef_search.or(approx.stats.map(|s| s.ef_search));
+
+            let started = Instant::now();
+            let exact = self.search_exact(&query.data, k + 1)?;
+            exact_latency.push(started.elapsed());
+
+            let truth: HashSet<&str> = exact
+                .iter()
+                .filter(|r| r.id != *id)
+                .take(k)
+                .map(|r| r.id.as_str())
+                .collect();
+            if truth.is_empty() {
+                continue;
+            }
+            let hits = approx
+                .results
+                .iter()
+                .filter(|r| r.id != *id)
+                .take(k)
+                .filter(|r| truth.contains(r.id.as_str()))
+                .count();
+            recalls.push(hits as f64 / truth.len() as f64);
+        }
+
+        let (mean_recall, min_recall) = if recalls.is_empty() {
+            (0.0, 0.0)
+        } else {
+            (
+                recalls.iter().sum::<f64>() / recalls.len() as f64,
+                recalls.iter().copied().fold(1.0, f64::min),
+            )
+        };
+        let (m, ef_construction) = self.graph_params();
+        let profile = RecallProfile {
+            measured_at: chrono::Utc::now(),
+            trigger,
+            vector_count: self.vector_count(),
+            sample_size: recalls.len(),
+            k,
+            recall_at_k: mean_recall,
+            min_recall_at_k: min_recall,
+            hnsw_latency: LatencySummary::from_samples(&mut hnsw_latency),
+            exact_latency: LatencySummary::from_samples(&mut exact_latency),
+            m,
+            ef_construction,
+            ef_search,
+            quantization: format!("{:?}", self.config.quantization),
+        };
+        self.set_recall_profile(Some(profile.clone()));
+        Ok(profile)
+    }
+
+    /// `M` and `ef_construction` of the live graph, which a reindex may
+    /// have set apart from the collection's configuration.
+    pub(crate) fn graph_params(&self) -> (usize, usize) {
+        let index_config = self.index.read().config();
+        (index_config.max_connections, index_config.ef_construction)
+    }
+
+    /// IDs of `sample_size` of the collection's vectors, spread evenly
+    /// over insertion order, to measure recall@`k` with.
+    ///
+    /// # Errors
+    ///
+    /// Fails when the collection holds `k` vectors or fewer.
+    pub(crate) fn sample_vector_ids(&self, sample_size: usize, k: usize) -> Result<Vec<String>> {
+        let order = self.vector_order.read();
+        if order.len() <= k {
+            return Err(VectorizerError::InvalidConfiguration {
+                message: format!(
+                    "collection '{}' has {} vectors; measuring recall@{} needs more",
+                    self.name,
+                    order.len(),
+                    k
+                ),
+            });
+        }
+        let step = (order.len() / sample_size.max(1)).max(1);
+        Ok(order
+            .iter()
+            .step_by(step)
+            .take(sample_size)
+            .cloned()
+            .collect())
+    }
+
+    /// Profile a freshly rebuilt graph with the default sample. Failures
+    /// (typically a collection too small to profile) are logged, not
+    /// returned: the rebuild itself succeeded.
+    pub(super) fn profile_rebuilt_index(&self, trigger: ProfileTrigger) {
+        match self.measure_recall_profile(DEFAULT_PROFILE_SAMPLE_SIZE, DEFAULT_PROFILE_K, trigger) {
+            Ok(profile) => info!(
+                "Recall profile of '{}': recall@{} {:.3} over {} queries, HNSW p50 {:.2} ms vs exact p50 {:.2} ms",
+                self.name,
+                profile.k,
+                profile.recall_at_k,
+                profile.sample_size,
+                profile.hnsw_latency.p50_ms,
+                profile.exact_latency.p50_ms
+            ),
+            Err(e) => debug!("Skipped recall profile of '{}': {}", self.name, e),
+        }
+    }
+}
@@ -1,16 +1,174 @@
 //! Quantization — SQ/Binary migration of existing vectors, PQ
-//! training, and small scalar helpers.
+//! training, binary two-stage search, and small scalar helpers.
 //!
 //! Wire format details live in [`crate::models::QuantizedVector`] and
 //! [`crate::quantization::product`]; this module just orchestrates
 //! when quantization runs against a [`Collection`]'s vectors.
 
+use std::collections::BinaryHeap;
+
 use tracing::{debug, info, warn};
 
 use super::Collection;
-use crate::error::Result;
+use crate::db::search_deadline::{SearchDeadline, SearchOutcome};
+use crate::error::{Result, VectorizerError};
+use crate::models::{DistanceMetric, QuantizedVector, SearchResult, vector_utils};
+use crate::quantization::hamming_distance;
+
+/// Candidate multiplier used by [`Collection::search`] on
+/// `QuantizationConfig::Binary` collections: `k * 3` codes are shortlisted
+/// by Hamming distance before exact rescoring.
+pub const BINARY_DEFAULT_OVERSAMPLING: f32 = 3.0;
+
+/// Codes compared between two deadline checks in
+/// [`Collection::search_binary_until`].
+const HAMMING_DEADLINE_CHECK_INTERVAL: usize = 4096;
 
 impl Collection {
+    /// Two-stage search for `QuantizationConfig::Binary` collections.
+    ///
+    /// Stage one scans the packed 1-bit codes and keeps the
+    /// `ceil(k * oversampling)` candidates with the smallest Hamming
+    /// distance to the query's code. Stage two, when `rescore` is set,
+    /// ranks that shortlist by the exact metric over the full-precision
+    /// vectors held by the HNSW index, so the returned scores are on the
+    /// same scale as a regular search. Without `rescore` the score is the
+    /// normalized bit agreement `1 - hamming / dimension`.
+    ///
+    /// `oversampling` is clamped to at least 1.0. Larger values trade scan
+    /// output size for recall; the Hamming scan itself is always full.
+    ///
+    /// The full-precision vectors rescoring reads are kept by the HNSW
+    /// index in an mmap'd scratch file rather than in RAM (see
+    /// `Collection::new_index`), so only the codes stay resident, 1/32 of
+    /// the f32 vectors. The scan reads the codes under a shared lock:
+    /// concurrent searches run side by side and only writes wait.
+    pub fn search_binary(
+        &self,
+        query_vector: &[f32],
+        k: usize,
+        oversampling: f32,
+        rescore: bool,
+    ) -> Result<Vec<SearchResult>> {
+        Ok(self
+            .search_binary_until(
+                query_vector,
+                k,
+                oversampling,
+                rescore,
+                &SearchDeadline::NONE,
+            )?
+            .results)
+    }
+
+    /// [`Self::search_binary`] under a deadline. The Hamming scan stops
+    /// once `deadline` passes, and rescoring is skipped if it has passed
+    /// by then — the shortlist is returned with bit-agreement scores
+    /// instead. Either cut sets `timed_out`.
+    pub fn search_binary_until(
+        &self,
+        query_vector: &[f32],
+        k: usize,
+        oversampling: f32,
+        rescore: bool,
+        deadline: &SearchDeadline,
+    ) -> Result<SearchOutcome> {
+        if query_vector.len() != self.config.dimension {
+            return Err(VectorizerError::InvalidDimension {
+                expected: self.config.dimension,
+                got: query_vector.len(),
+            });
+        }
+        if k == 0 {
+            return Ok(SearchOutcome::default());
+        }
+
+        // Stored codes were computed from normalized data for cosine.
+        let search_vector = if matches!(self.config.metric, DistanceMetric::Cosine) {
+            vector_utils::normalize_vector(query_vector)
+        } else {
+            query_vector.to_vec()
+        };
+        let query_code = QuantizedVector::binary_code(&search_vector);
+        let shortlist_len = ((k as f32) * oversampling.max(1.0)).ceil() as usize;
+        let mut timed_out = false;
+
+        // Stage 1: bounded max-heap keeps the `shortlist_len` closest codes.
+        let shortlist: Vec<(u32, String)> = {
+            let quantized = self.quantized_vectors.read();
+            let mut heap: BinaryHeap<(u32, &str)> = BinaryHeap::with_capacity(shortlist_len + 1);
+            for (scanned, (id, qv)) in quantized.iter().enumerate() {
+                if scanned % HAMMING_DEADLINE_CHECK_INTERVAL == 0 && deadline.expired() {
+                    timed_out = true;
+                    break;
+                }
+                let distance = hamming_distance(&query_code, &qv.quantized_data);
+                if heap.len() < shortlist_len {
+                    heap.push((distance, id.as_str()));
+                } else if let Some(&(worst, _)) = heap.peek()
+                    && distance < worst
+                {
+                    heap.pop();
+                    heap.push((distance, id.as_str()));
+                }
+            }
+            heap.into_sorted_vec()
+                .into_iter()
+                .map(|(d, id)| (d, id.to_string()))
+                .collect()
+        };
+
+        // Stage 2: exact rescoring of the shortlist, unless out of time.
+        let rescore = if rescore && deadline.expired() {
+            timed_out = true;
+            false
+        } else {
+            rescore
+        };
+        let mut scored: Vec<(String, f32)> = if rescore {
+            let ids: Vec<String> = shortlist.into_iter().map(|(_, id)| id).collect();
+            self.index_rescore(&search_vector, &ids, k)?
+        } else {
+            let bits = self.config.dimension.max(1) as f32;
+            shortlist
+                .into_iter()
+                .map(|(d, id)| (id, 1.0 - d as f32 / bits))
+                .collect()
+        };
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scored.truncate(k);
+
+        debug!(
+            "Binary search on '{}': shortlist={}, rescore={}, returned={}, timed_out={}",
+            self.name,
+            shortlist_len,
+            rescore,
+            scored.len(),
+            timed_out
+        );
+
+        let quantized = self.quantized_vectors.read();
+        let results = scored
+            .into_iter()
+            .filter_map(|(id, score)| {
+                let vector = quantized.get(&id)?.to_vector();
+                Some(SearchResult {
+                    id,
+                    score,
+                    dense_score: Some(score),
+                    sparse_score: None,
+                    vector: Some(vector.data),
+                    payload: vector.payload.as_ref().map(|p| p.normalized()),
+                })
+            })
+            .collect();
+        Ok(SearchOutcome {
+            results,
+            timed_out,
+            stats: None,
+        })
+    }
+
     /// Requantize existing vectors if quantization is enabled (parallel processing)
     /// Migrates vectors from full precision to quantized storage
     pub fn requantize_existing_vectors(&self) -> Result<()> {
@@ -51,13 +209,13 @@ impl Collection {
                 .collect();
 
             // Move to quantized storage
-            let mut quantized_storage = self.quantized_vectors.lock();
+            let mut quantized_storage = self.quantized_vectors.write();
             for (id, qv) in quantized {
                 quantized_storage.insert(id, qv);
             }
 
             info!(
-                "✅ Migrated {} vectors to quantized storage (~75% memory reduction)",
+                "✅ Migrated {} vectors to quantized storage (the HNSW index keeps full precision)",
                 vector_count
             );
         }
@@ -231,7 +389,7 @@ impl Collection {
         match &new_config {
             QuantizationConfig::None => {
                 // Migrate quantized → full-precision: dequantize into vectors map.
-                let mut qvecs = self.quantized_vectors.lock();
+                let mut qvecs = self.quantized_vectors.write();
                 for id in vector_order.iter() {
                     if let Some(qv) = qvecs.remove(id) {
                         let vec = qv.to_vector();
@@ -251,7 +409,7 @@ impl Collection {
                     // Try full-precision first, then existing quantized storage.
                     let vector = if let Ok(Some(v)) = self.vectors.get(id) {
                         v
-                    } else if let Some(qv) = self.quantized_vectors.lock().get(id) {
+                    } else if let Some(qv) = self.quantized_vectors.read().get(id) {
                         qv.to_vector()
                     } else {
                         warn!(
@@ -266,7 +424,7 @@ impl Collection {
                 }
 
                 // Atomically swap the quantized store.
-                *self.quantized_vectors.lock() = new_qvecs;
+                *self.quantized_vectors.write() = new_qvecs;
 
                 info!(
                     "reencode_inplace: '{}' converted to '{}' ({} vectors)",
@@ -0,0 +1,42 @@
+//! Custom scoring stage: reranks oversampled search hits by the
+//! collection's scoring expression, with the boosts learned from
+//! relevance feedback available as `feedback`.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::Collection;
+use crate::db::optimized_hnsw::similarity_to_distance;
+use crate::models::{ScoringConfig, ScoringInput, SearchResult};
+
+impl Collection {
+    /// Replace each hit's score with `scoring`'s expression, then keep the
+    /// best `k`. Hits whose expression is not finite rank last.
+    pub(crate) fn apply_scoring(
+        &self,
+        scoring: &ScoringConfig,
+        results: &mut Vec<SearchResult>,
+        k: usize,
+    ) {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs_f64())
+            .unwrap_or_default();
+        let feedback = self.feedback.read();
+        for result in results.iter_mut() {
+            let score = scoring.expression.evaluate(&ScoringInput {
+                score: result.score,
+                distance: similarity_to_distance(self.config.metric, result.score),
+                payload: result.payload.as_ref().map(|p| &p.data),
+                feedback: feedback.boost(&result.id),
+                now,
+            }) as f32;
+            result.score = if score.is_finite() {
+                score
+            } else {
+                f32::NEG_INFINITY
+            };
+        }
+        results.sort_by(|a, b| b.score.total_cmp(&a.score));
+        results.truncate(k);
+    }
+}
@@ -0,0 +1,354 @@
+//! Settings of a collection that can change after it is created.
+//!
+//! Dimension, metric, HNSW `M` / `ef_construction` and storage are fixed
+//! by the vectors and graph already stored; changing them means
+//! reindexing or recreating the collection. The settings here are safe
+//! to change in place: the search beam, quantization (re-encoded in
+//! place), payload compression, the TTL of new vectors and payload field
+//! indexes (backfilled over the stored vectors).
+//!
+//! Quantization and compression live in the collection's config; the
+//! beam, TTL and added payload indexes are [`CollectionOverrides`],
+//! persisted with the collection in `.vecdb`.
+
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use super::Collection;
+use crate::config::MAX_EF_SEARCH;
+use crate::db::optimized_hnsw::{ef_search_overridden, with_ef_search};
+use crate::db::payload_index::PayloadIndexConfig;
+use crate::error::{Result, VectorizerError};
+use crate::models::{CompressionConfig, QuantizationConfig};
+
+/// Settings changed after creation that the config does not hold.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CollectionOverrides {
+    /// Search beam pinned for this collection, instead of the server's
+    /// `tuning.ef_search` or the adaptive default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ef_search: Option<usize>,
+    /// Seconds after insertion that new vectors expire.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ttl_secs: Option<u64>,
+    /// Payload field indexes added after creation.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub payload_indexes: Vec<PayloadIndexConfig>,
+}
+
+impl CollectionOverrides {
+    /// Whether nothing was changed.
+    pub fn is_empty(&self) -> bool {
+        self.ef_search.is_none() && self.ttl_secs.is_none() && self.payload_indexes.is_empty()
+    }
+}
+
+/// Changes to apply with [`Collection::apply_patch`]; `None` leaves a
+/// setting as it is.
+#[derive(Debug, Clone, Default)]
+pub struct CollectionPatch {
+    /// Pin the search beam (`Some(Some(n))`) or unpin it (`Some(None)`).
+    pub ef_search: Option<Option<usize>>,
+    /// Quantization to re-encode to: `None`, 8-bit `SQ` or `Binary`.
+    pub quantization: Option<QuantizationConfig>,
+    /// Payload compression settings.
+    pub compression: Option<CompressionConfig>,
+    /// Set (`Some(Some(secs))`) or clear (`Some(None)`) the TTL of new
+    /// vectors.
+    pub ttl_secs: Option<Option<u64>>,
+    /// Payload field indexes to add.
+    pub payload_indexes: Vec<PayloadIndexConfig>,
+}
+
+impl Collection {
+    /// Apply `patch` and return the names of the settings it changed.
+    /// Nothing is changed when it fails.
+    ///
+    /// # Errors
+    ///
+    /// [`VectorizerError::InvalidConfiguration`] when `ef_search` is out
+    /// of `1..=MAX_EF_SEARCH` or the quantization cannot be switched to in
+    /// place; re-encoding errors otherwise.
+    pub fn apply_patch(&mut self, patch: CollectionPatch) -> Result<Vec<&'static str>> {
+        if let Some(Some(ef_search)) = patch.ef_search
+            && !(1..=MAX_EF_SEARCH).contains(&ef_search)
+        {
+            return Err(VectorizerError::InvalidConfiguration {
+                message: format!("ef_search must be between 1 and {}", MAX_EF_SEARCH),
+            });
+        }
+
+        let mut changed = Vec::new();
+        // First, as the only change that can fail
+        if let Some(quantization) = patch.quantization
+            && self.set_quantization(quantization)?
+        {
+            changed.push("quantization");
+        }
+        if let Some(ef_search) = patch.ef_search {
+            self.set_ef_search(ef_search);
+            changed.push("ef_search");
+        }
+        if let Some(compression) = patch.compression {
+            self.set_compression(compression);
+            changed.push("compression");
+        }
+        if let Some(ttl_secs) = patch.ttl_secs {
+            self.set_ttl_secs(ttl_secs);
+            changed.push("ttl_secs");
+        }
+        if !patch.payload_indexes.is_empty() {
+            self.add_payload_indexes(patch.payload_indexes);
+            changed.push("payload_indexes");
+        }
+        if !changed.is_empty() {
+            *self.updated_at.write() = chrono::Utc::now();
+            info!("Collection '{}' settings changed: {:?}", self.name, changed);
+        }
+        Ok(changed)
+    }
+
+    /// Settings changed after creation, or `None` if there are none (so
+    /// nothing is written to `.vecdb`).
+    pub fn overrides(&self) -> Option<CollectionOverrides> {
+        let overrides = self.overrides.read();
+        (!overrides.is_empty()).then(|| overrides.clone())
+    }
+
+    /// Replace the settings, e.g. with the ones persisted in `.vecdb`.
+    /// Payload indexes are re-added and backfilled.
+    pub fn set_overrides(&self, overrides: Option<CollectionOverrides>) {
+        let overrides = overrides.unwrap_or_default();
+        {
+            let mut current = self.overrides.write();
+            current.ef_search = overrides.ef_search;
+            current.ttl_secs = overrides.ttl_secs;
+        }
+        self.add_payload_indexes(overrides.payload_indexes);
+    }
+
+    /// The search beam pinned for this collection, if any.
+    pub fn pinned_ef_search(&self) -> Option<usize> {
+        self.overrides.read().ef_search
+    }
+
+    /// Pin the search beam of this collection, or unpin it with `None`.
+    pub fn set_ef_search(&self, ef_search: Option<usize>) {
+        self.overrides.write().ef_search = ef_search;
+    }
+
+    /// Seconds after insertion that new vectors expire, if set.
+    pub fn ttl_secs(&self) -> Option<u64> {
+        self.overrides.read().ttl_secs
+    }
+
+    /// Set or clear the TTL of new vectors.
+    pub fn set_ttl_secs(&self, ttl_secs: Option<u64>) {
+        self.overrides.write().ttl_secs = ttl_secs;
+    }
+
+    /// Run `f` with this collection's pinned beam, unless the caller
+    /// already pinned one (a benchmark sweep) or none is pinned.
+    pub(super) fn with_pinned_ef_search<T>(&self, f: impl FnOnce() -> T) -> T {
+        match self.pinned_ef_search() {
+            Some(ef_search) if !ef_search_overridden() => with_ef_search(ef_search, f),
+            _ => f(),
+        }
+    }
+
+    /// Index payload fields of `configs`, replacing the index of a field
+    /// already indexed, and backfill them from the stored vectors.
+    /// Returns the number of vectors indexed.
+    pub fn add_payload_indexes(&self, configs: Vec<PayloadIndexConfig>) -> usize {
+        if configs.is_empty() {
+            return 0;
+        }
+        for config in &configs {
+            self.payload_index.add_index_config(config.clone());
+        }
+        {
+            let mut overrides = self.overrides.write();
+            for config in configs {
+                overrides
+                    .payload_indexes
+                    .retain(|c| c.field_name != config.field_name);
+                overrides.payload_indexes.push(config);
+            }
+        }
+
+        let ids = self.vector_order.read().clone();
+        let mut indexed = 0;
+        for id in ids {
+            if let Ok(vector) = self.get_vector(&id)
+                && let Some(payload) = &vector.payload
+            {
+                self.payload_index.index_vector(id, payload);
+                indexed += 1;
+            }
+        }
+        info!(
+            "Backfilled payload indexes of '{}' from {} vectors",
+            self.name, indexed
+        );
+        indexed
+    }
+
+    /// Switch quantization, re-encoding the stored vectors in place (see
+    /// [`Collection::reencode_inplace`]). Returns whether it changed.
+    ///
+    /// # Errors
+    ///
+    /// Only `None`, 8-bit `SQ` and `Binary` can be switched to; product
+    /// quantization needs training and a reindex.
+    pub fn set_quantization(&mut self, quantization: QuantizationConfig) -> Result<bool> {
+        let encoding = match quantization {
+            QuantizationConfig::None => "none",
+            QuantizationConfig::SQ { bits: 8 } => "sq8",
+            QuantizationConfig::Binary => "binary",
+            other => {
+                return Err(VectorizerError::InvalidConfiguration {
+                    message: format!(
+                        "quantization {:?} cannot be switched to in place; \
+                         use none, sq8 or binary",
+                        other
+                    ),
+                });
+            }
+        };
+        let unchanged = matches!(
+            (&self.config.quantization, &quantization),
+            (QuantizationConfig::None, QuantizationConfig::None)
+                | (
+                    QuantizationConfig::SQ { bits: 8 },
+                    QuantizationConfig::SQ { bits: 8 }
+                )
+                | (QuantizationConfig::Binary, QuantizationConfig::Binary)
+        );
+        if unchanged {
+            return Ok(false);
+        }
+        self.reencode_inplace(encoding)?;
+        self.config.quantization = quantization;
+        Ok(true)
+    }
+
+    /// Replace the payload compression settings. Payloads already stored
+    /// keep their encoding until they are rewritten.
+    pub fn set_compression(&mut self, compression: CompressionConfig) {
+        self.config.compression = compression;
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::db::payload_index::PayloadIndexType;
+    use crate::models::{CollectionConfig, DistanceMetric, HnswConfig, Payload, Vector};
+
+    fn collection() -> Collection {
+        let config = CollectionConfig {
+            graph: None,
+            sharding: None,
+            dimension: 4,
+            metric: DistanceMetric::Euclidean,
+            hnsw_config: HnswConfig::default(),
+            quantization: QuantizationConfig::None,
+            compression: Default::default(),
+            embedding_provider: "bm25".to_string(),
+            normalization: None,
+            encryption: None,
+            id_policy: None,
+            quota: None,
+            scoring: None,
+            tenant_partition: None,
+            dedup: None,
+            chunking: None,
+            storage_type: Some(crate::models::StorageType::Memory),
+            full_text: None,
+        };
+        let collection = Collection::new("settings".to_string(), config);
+        for i in 0..20 {
+            let x = i as f32;
+            let payload = Payload::new(json!({"lang": if i % 2 == 0 { "en" } else { "pt" }}));
+            collection
+                .insert(Vector::with_payload(
+                    format!("v{i}"),
+                    vec![x, x.sin(), x.cos(), 1.0],
+                    payload,
+                ))
+                .unwrap();
+        }
+        collection
+    }
+
+    #[test]
+    fn added_payload_index_covers_stored_vectors() {
+        let collection = collection();
+        assert!(collection.overrides().is_none());
+
+        let indexed = collection.add_payload_indexes(vec![PayloadIndexConfig::new(
+            "lang".to_string(),
+            PayloadIndexType::Keyword,
+        )]);
+        assert_eq!(indexed, 20);
+        let en = collection
+            .payload_index
+            .get_ids_for_keyword("lang", "en")
+            .unwrap();
+        assert_eq!(en.len(), 10);
+        assert_eq!(collection.overrides().unwrap().payload_indexes.len(), 1);
+    }
+
+    #[test]
+    fn pinned_beam_applies_to_searches() {
+        let collection = collection();
+        collection.set_ef_search(Some(300));
+        let outcome = collection
+            .search_until(&[3.0, 0.1, -0.9, 1.0], 5, &crate::db::SearchDeadline::NONE)
+            .unwrap();
+        assert_eq!(outcome.stats.map(|s| s.ef_search), Some(300));
+
+        // A caller's own beam wins over the pin
+        let outcome = with_ef_search(40, || {
+            collection.search_until(&[3.0, 0.1, -0.9, 1.0], 5, &crate::db::SearchDeadline::NONE)
+        })
+        .unwrap();
+        assert_eq!(outcome.stats.map(|s| s.ef_search), Some(40));
+    }
+
+    #[test]
+    fn quantization_switches_in_place() {
+        let mut collection = collection();
+        assert!(
+            collection
+                .set_quantization(QuantizationConfig::SQ { bits: 8 })
+                .unwrap()
+        );
+        assert!(matches!(
+            collection.config().quantization,
+            QuantizationConfig::SQ { bits: 8 }
+        ));
+        assert_eq!(collection.get_vector("v3").unwrap().data.len(), 4);
+        assert!(
+            !collection
+                .set_quantization(QuantizationConfig::SQ { bits: 8 })
+                .unwrap()
+        );
+        assert!(
+            collection
+                .set_quantization(QuantizationConfig::PQ {
+                    n_centroids: 16,
+                    n_subquantizers: 2
+                })
+                .is_err()
+        );
+        assert!(
+            collection
+                .set_quantization(QuantizationConfig::None)
+                .unwrap()
+        );
+    }
+}
@@ -21,7 +21,14 @@ fn create_test_collection() -> Collection {
         embedding_provider: "bm25".to_string(),
         normalization: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
         storage_type: Some(crate::models::StorageType::Memory),
+        full_text: None,
     };
     Collection::new("test".to_string(), config)
 }
@@ -89,6 +96,30 @@ fn test_delete_vector() {
     assert!(matches!(result, Err(VectorizerError::VectorNotFound(_))));
 }
 
+#[test]
+fn test_sorted_vector_ids_follow_inserts_and_deletes() {
+    let collection = create_test_collection();
+    for id in ["b", "c", "a"] {
+        collection
+            .insert(Vector::new(id.to_string(), vec![1.0, 2.0, 3.0]))
+            .unwrap();
+    }
+
+    let sorted = collection.sorted_vector_ids();
+    assert_eq!(*sorted, ["a", "b", "c"]);
+    assert!(std::sync::Arc::ptr_eq(
+        &sorted,
+        &collection.sorted_vector_ids()
+    ));
+
+    collection.delete("b").unwrap();
+    collection
+        .insert(Vector::new("0".to_string(), vec![1.0, 2.0, 3.0]))
+        .unwrap();
+    assert_eq!(*collection.sorted_vector_ids(), ["0", "a", "c"]);
+    assert_eq!(*sorted, ["a", "b", "c"]);
+}
+
 #[test]
 fn test_vector_count_with_quantization() {
     // Create collection WITH quantization enabled
@@ -103,7 +134,14 @@ fn test_vector_count_with_quantization() {
         embedding_provider: "bm25".to_string(),
         normalization: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
         storage_type: Some(crate::models::StorageType::Memory),
+        full_text: None,
     };
     let collection = Collection::new("quantized_test".to_string(), config);
 
@@ -146,7 +184,14 @@ fn test_vector_count_consistency_quantized_vs_normal() {
         embedding_provider: "bm25".to_string(),
         normalization: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
         storage_type: Some(crate::models::StorageType::Memory),
+        full_text: None,
     };
     let collection_quantized = Collection::new("quantized".to_string(), config_quantized);
 
@@ -162,7 +207,14 @@ fn test_vector_count_consistency_quantized_vs_normal() {
         embedding_provider: "bm25".to_string(),
         normalization: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
         storage_type: Some(crate::models::StorageType::Memory),
+        full_text: None,
     };
     let collection_normal = Collection::new("normal".to_string(), config_normal);
 
@@ -209,7 +261,14 @@ fn test_collection_creation() {
         embedding_provider: "bm25".to_string(),
         normalization: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
         storage_type: None,
+        full_text: None,
     };
 
     let collection = Collection::new("test_coll".to_string(), config);
@@ -232,7 +291,14 @@ fn test_collection_insert_single() {
         embedding_provider: "bm25".to_string(),
         normalization: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
         storage_type: Some(crate::models::StorageType::Memory),
+        full_text: None,
     };
 
     let collection = Collection::new("test".to_string(), config);
@@ -256,7 +322,14 @@ fn test_collection_insert_batch() {
         embedding_provider: "bm25".to_string(),
         normalization: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
         storage_type: Some(crate::models::StorageType::Memory),
+        full_text: None,
     };
 
     let collection = Collection::new("test".to_string(), config);
@@ -284,7 +357,14 @@ fn test_collection_get_vector() {
         embedding_provider: "bm25".to_string(),
         normalization: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
         storage_type: Some(crate::models::StorageType::Memory),
+        full_text: None,
     };
 
     let collection = Collection::new("test".to_string(), config);
@@ -313,7 +393,14 @@ fn test_collection_get_nonexistent() {
         embedding_provider: "bm25".to_string(),
         normalization: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
         storage_type: Some(crate::models::StorageType::Memory),
+        full_text: None,
     };
 
     let collection = Collection::new("test".to_string(), config);
@@ -335,7 +422,14 @@ fn test_collection_delete() {
         embedding_provider: "bm25".to_string(),
         normalization: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
         storage_type: Some(crate::models::StorageType::Memory),
+        full_text: None,
     };
 
     let collection = Collection::new("test".to_string(), config);
@@ -370,7 +464,14 @@ fn test_collection_update() {
         embedding_provider: "bm25".to_string(),
         normalization: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
         storage_type: Some(crate::models::StorageType::Memory),
+        full_text: None,
     };
 
     let collection = Collection::new("test".to_string(), config);
@@ -402,7 +503,14 @@ fn test_collection_search() {
         embedding_provider: "bm25".to_string(),
         normalization: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
         storage_type: Some(crate::models::StorageType::Memory),
+        full_text: None,
     };
 
     let collection = Collection::new("test".to_string(), config);
@@ -437,7 +545,14 @@ fn test_collection_memory_usage() {
         embedding_provider: "bm25".to_string(),
         normalization: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
         storage_type: Some(crate::models::StorageType::Memory),
+        full_text: None,
     };
 
     let collection = Collection::new("test".to_string(), config);
@@ -466,7 +581,14 @@ fn test_collection_metadata() {
         embedding_provider: "bm25".to_string(),
         normalization: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
         storage_type: None,
+        full_text: None,
     };
 
     let collection = Collection::new("metadata_test".to_string(), config);
@@ -491,7 +613,14 @@ fn test_collection_different_metrics() {
         embedding_provider: "bm25".to_string(),
         normalization: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
         storage_type: Some(crate::models::StorageType::Memory),
+        full_text: None,
     };
     let coll_cosine = Collection::new("cosine".to_string(), config_cosine);
     assert_eq!(coll_cosine.config().metric, DistanceMetric::Cosine);
@@ -508,7 +637,14 @@ fn test_collection_different_metrics() {
         embedding_provider: "bm25".to_string(),
         normalization: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
         storage_type: Some(crate::models::StorageType::Memory),
+        full_text: None,
     };
     let coll_euclidean = Collection::new("euclidean".to_string(), config_euclidean);
     assert_eq!(coll_euclidean.config().metric, DistanceMetric::Euclidean);
@@ -525,7 +661,14 @@ fn test_collection_different_metrics() {
         embedding_provider: "bm25".to_string(),
         normalization: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
         storage_type: Some(crate::models::StorageType::Memory),
+        full_text: None,
     };
     let coll_dot = Collection::new("dot".to_string(), config_dot);
     assert_eq!(coll_dot.config().metric, DistanceMetric::DotProduct);
@@ -544,7 +687,14 @@ fn test_collection_with_quantization_sq() {
         embedding_provider: "bm25".to_string(),
         normalization: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
         storage_type: None,
+        full_text: None,
     };
 
     let collection = Collection::new("quantized_sq".to_string(), config);
@@ -576,7 +726,14 @@ fn test_collection_update_nonexistent() {
         embedding_provider: "bm25".to_string(),
         normalization: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
         storage_type: Some(crate::models::StorageType::Memory),
+        full_text: None,
     };
 
     let collection = Collection::new("test".to_string(), config);
@@ -599,7 +756,14 @@ fn test_collection_delete_nonexistent() {
         embedding_provider: "bm25".to_string(),
         normalization: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
         storage_type: Some(crate::models::StorageType::Memory),
+        full_text: None,
     };
 
     let collection = Collection::new("test".to_string(), config);
@@ -621,7 +785,14 @@ fn test_collection_dimension_validation() {
         embedding_provider: "bm25".to_string(),
         normalization: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
         storage_type: Some(crate::models::StorageType::Memory),
+        full_text: None,
     };
 
     let collection = Collection::new("test".to_string(), config);
@@ -646,7 +817,14 @@ fn test_collection_get_all_vectors_ids() {
         embedding_provider: "bm25".to_string(),
         normalization: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
         storage_type: Some(crate::models::StorageType::Memory),
+        full_text: None,
     };
 
     let collection = Collection::new("test".to_string(), config);
@@ -678,7 +856,14 @@ fn test_collection_embedding_type() {
         embedding_provider: "bm25".to_string(),
         normalization: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
         storage_type: None,
+        full_text: None,
     };
 
     let collection =
@@ -700,7 +885,14 @@ fn test_collection_search_empty() {
         embedding_provider: "bm25".to_string(),
         normalization: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
         storage_type: Some(crate::models::StorageType::Memory),
+        full_text: None,
     };
 
     let collection = Collection::new("test".to_string(), config);
@@ -728,7 +920,14 @@ fn test_collection_concurrent_inserts() {
         embedding_provider: "bm25".to_string(),
         normalization: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
         storage_type: None,
+        full_text: None,
     };
 
     let collection = Arc::new(Collection::new("concurrent".to_string(), config));
@@ -769,7 +968,14 @@ fn test_collection_search_with_limit() {
         embedding_provider: "bm25".to_string(),
         normalization: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
         storage_type: Some(crate::models::StorageType::Memory),
+        full_text: None,
     };
 
     let collection = Collection::new("test".to_string(), config);
@@ -802,7 +1008,14 @@ fn test_collection_get_all_vectors() {
         embedding_provider: "bm25".to_string(),
         normalization: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
         storage_type: Some(crate::models::StorageType::Memory),
+        full_text: None,
     };
 
     let collection = Collection::new("test".to_string(), config);
@@ -830,7 +1043,14 @@ fn test_collection_metadata_updates() {
         embedding_provider: "bm25".to_string(),
         normalization: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
         storage_type: Some(crate::models::StorageType::Memory),
+        full_text: None,
     };
 
     let collection = Collection::new("test".to_string(), config);
@@ -923,3 +1143,244 @@ fn vector_count_history_respects_capacity() {
         "oldest sample should have been evicted to make room"
     );
 }
+
+#[test]
+fn test_search_exact_returns_true_nearest_neighbours() {
+    let collection = create_test_collection();
+    let points: Vec<[f32; 3]> = (0..200)
+        .map(|i| {
+            let i = i as f32;
+            [(i * 0.37).sin(), (i * 0.71).cos(), (i * 0.13).sin()]
+        })
+        .collect();
+    for (i, p) in points.iter().enumerate() {
+        collection
+            .insert(Vector::new(format!("v{i}"), p.to_vec()))
+            .unwrap();
+    }
+
+    let query = [0.2, -0.4, 0.6];
+    let mut expected: Vec<(usize, f32)> = points
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            let d: f32 = p.iter().zip(query).map(|(a, b)| (a - b) * (a - b)).sum();
+            (i, d)
+        })
+        .collect();
+    expected.sort_by(|a, b| a.1.total_cmp(&b.1));
+    let expected: Vec<String> = expected[..10]
+        .iter()
+        .map(|(i, _)| format!("v{i}"))
+        .collect();
+
+    let results = collection.search_exact(&query, 10).unwrap();
+    let ids: Vec<String> = results.iter().map(|r| r.id.clone()).collect();
+    assert_eq!(ids, expected);
+    assert!(results.windows(2).all(|w| w[0].score >= w[1].score));
+    assert!(results[0].payload.is_none() && results[0].vector.is_some());
+
+    // Deleted vectors drop out of the scan.
+    collection.delete(&expected[0]).unwrap();
+    let results = collection.search_exact(&query, 10).unwrap();
+    assert_eq!(results[0].id, expected[1]);
+
+    assert!(collection.search_exact(&[1.0, 2.0], 10).is_err());
+}
+
+#[test]
+fn test_search_until_returns_partial_results_past_deadline() {
+    use crate::db::SearchDeadline;
+
+    let collection = create_test_collection();
+    for i in 0..50 {
+        let x = i as f32;
+        collection
+            .insert(Vector::new(format!("v{i}"), vec![x.sin(), x.cos(), 0.5]))
+            .unwrap();
+    }
+    let query = [0.1, 0.9, 0.5];
+
+    let full = collection
+        .search_until(&query, 5, &SearchDeadline::NONE)
+        .unwrap();
+    assert!(!full.timed_out);
+    assert_eq!(full.results.len(), 5);
+
+    // A deadline that has already passed is reported, not an error.
+    let expired = SearchDeadline::from_timeout_ms(Some(0));
+    let outcome = collection.search_until(&query, 5, &expired).unwrap();
+    assert!(outcome.timed_out);
+    assert!(outcome.results.is_empty());
+    let outcome = collection.search_exact_until(&query, 5, &expired).unwrap();
+    assert!(outcome.timed_out);
+    assert!(outcome.results.len() <= 5);
+
+    assert!(collection.search_until(&[1.0], 5, &expired).is_err());
+}
+
+#[test]
+fn test_search_batch_until_matches_single_searches() {
+    use crate::db::SearchDeadline;
+
+    let collection = create_test_collection();
+    for i in 0..100 {
+        let x = i as f32;
+        collection
+            .insert(Vector::new(format!("v{i}"), vec![x.sin(), x.cos(), 0.5]))
+            .unwrap();
+    }
+    let query = |i: usize, exact: bool, deadline: SearchDeadline| BatchQuery {
+        vector: vec![(i as f32 * 0.3).cos(), 0.2, (i as f32).sin()],
+        limit: 5,
+        exact,
+        deadline,
+    };
+    let mut queries: Vec<BatchQuery> = (0..8)
+        .map(|i| query(i, i % 3 == 0, SearchDeadline::NONE))
+        .collect();
+    queries.push(query(0, false, SearchDeadline::from_timeout_ms(Some(0))));
+    queries.push(BatchQuery {
+        vector: vec![1.0],
+        ..query(0, false, SearchDeadline::NONE)
+    });
+
+    let outcomes = collection.search_batch_until(&queries);
+    assert_eq!(outcomes.len(), queries.len());
+    for (q, outcome) in queries[..8].iter().zip(&outcomes) {
+        let expected = if q.exact {
+            collection.search_exact(&q.vector, q.limit).unwrap()
+        } else {
+            collection.search(&q.vector, q.limit).unwrap()
+        };
+        let outcome = outcome.as_ref().unwrap();
+        assert!(!outcome.timed_out);
+        // Graph walks report their stats; exact scans have none.
+        assert_eq!(outcome.stats.is_some(), !q.exact);
+        let ids: Vec<&str> = outcome.results.iter().map(|r| r.id.as_str()).collect();
+        let expected: Vec<&str> = expected.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, expected);
+    }
+    assert!(outcomes[8].as_ref().unwrap().timed_out);
+    assert!(outcomes[9].is_err());
+}
+
+#[test]
+fn test_reindex_records_recall_profile() {
+    let collection = create_test_collection();
+    assert!(collection.recall_profile().is_none());
+    for i in 0..200 {
+        let x = i as f32;
+        let data = vec![(x * 0.37).sin(), (x * 0.71).cos(), (x * 0.13).sin()];
+        collection
+            .insert(Vector::new(format!("v{i}"), data))
+            .unwrap();
+    }
+
+    collection
+        .reindex_with_params(crate::models::HnswConfig {
+            m: 16,
+            ef_construction: 200,
+            ef_search: 100,
+            seed: Some(7),
+        })
+        .unwrap();
+    let profile = collection.recall_profile().unwrap();
+    assert_eq!(profile.trigger, crate::db::ProfileTrigger::Reindex);
+    assert_eq!(profile.vector_count, 200);
+    assert_eq!(profile.sample_size, crate::db::DEFAULT_PROFILE_SAMPLE_SIZE);
+    assert_eq!(profile.k, crate::db::DEFAULT_PROFILE_K);
+    assert_eq!(profile.m, 16);
+    // 200 points in 3-d are easy for the graph.
+    assert!(profile.recall_at_k > 0.9, "recall {}", profile.recall_at_k);
+    assert!(profile.min_recall_at_k <= profile.recall_at_k);
+    assert!(profile.exact_latency.p99_ms >= profile.exact_latency.p50_ms);
+
+    let manual = collection
+        .measure_recall_profile(20, 5, crate::db::ProfileTrigger::Request)
+        .unwrap();
+    assert_eq!(manual.sample_size, 20);
+    assert_eq!(collection.recall_profile(), Some(manual));
+    assert!(
+        collection
+            .measure_recall_profile(0, 5, crate::db::ProfileTrigger::Request)
+            .is_err()
+    );
+    assert!(
+        collection
+            .measure_recall_profile(10, 200, crate::db::ProfileTrigger::Request)
+            .is_err()
+    );
+}
+
+#[test]
+fn test_search_ranks_by_the_scoring_expression() {
+    let config = CollectionConfig {
+        dimension: 2,
+        metric: DistanceMetric::Cosine,
+        quantization: crate::models::QuantizationConfig::None,
+        scoring: Some(crate::models::ScoringConfig::new(
+            crate::models::ScoringExpression::parse("score * payload.boost").unwrap(),
+        )),
+        ..Default::default()
+    };
+    let collection = Collection::new("scored".to_string(), config);
+    let vector = |id: &str, data: Vec<f32>, boost: f64| {
+        Vector::with_payload(
+            id.to_string(),
+            data,
+            crate::models::Payload::new(serde_json::json!({ "boost": boost })),
+        )
+    };
+    collection
+        .insert_batch(vec![
+            vector("near", vec![1.0, 0.0], 1.0),
+            vector("far", vec![0.6, 0.8], 3.0),
+            vector("unboosted", vec![0.8, 0.6], 0.0),
+        ])
+        .unwrap();
+
+    let results = collection.search(&[1.0, 0.0], 2).unwrap();
+    let ids: Vec<&str> = results.iter().map(|r| r.id.as_str()).collect();
+    assert_eq!(ids, ["far", "near"]);
+    assert!(
+        (results[0].score - 1.8).abs() < 1e-4,
+        "{}",
+        results[0].score
+    );
+}
+
+#[test]
+fn test_full_text_index_is_opt_in() {
+    let text = |id: &str| {
+        Vector::with_payload(
+            id.to_string(),
+            vec![1.0, 0.0],
+            crate::models::Payload::new(serde_json::json!({ "content": "error E0425" })),
+        )
+    };
+    let query = crate::search::FullTextQuery::new("E0425");
+
+    let plain = Collection::new(
+        "plain".to_string(),
+        CollectionConfig {
+            dimension: 2,
+            ..Default::default()
+        },
+    );
+    plain.insert_batch(vec![text("a")]).unwrap();
+    assert!(plain.full_text_search(&query).is_err());
+
+    let indexed = Collection::new(
+        "indexed".to_string(),
+        CollectionConfig {
+            dimension: 2,
+            full_text: Some(crate::models::FullTextConfig::default()),
+            ..Default::default()
+        },
+    );
+    indexed.insert_batch(vec![text("a")]).unwrap();
+    let hits = indexed.full_text_search(&query).unwrap();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].id, "a");
+}
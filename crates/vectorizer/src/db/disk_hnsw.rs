@@ -0,0 +1,864 @@
+//! On-disk HNSW index with lazy page loading.
+//!
+//! A cold collection serves searches from a file written by
+//! [`DiskHnswIndex::write`] instead of an in-memory `hnsw_rs` graph. The
+//! file is memory-mapped, so opening it only parses the header and the
+//! (small) upper layers; layer-0 adjacency, vectors and IDs are paged in
+//! by the OS as a search touches them. Restarting the server therefore no
+//! longer rebuilds the graph of every cold collection.
+//!
+//! ## Layout
+//!
+//! All integers are little-endian, every section starts on a
+//! [`PAGE_SIZE`] boundary.
+//!
+//! | Section  | Contents |
+//! |----------|----------|
+//! | header   | magic, version, dimension, metric, counts, ID fingerprint, entry point, section offsets |
+//! | layer 0  | fixed-size records `count: u32, neighbors: [u32; slots]`, packed so none straddles a page |
+//! | vectors  | `node_count × dimension` `f32`, fixed stride |
+//! | upper    | `node: u32, layers: u32`, then per layer `count: u32, neighbors: [u32]`; read eagerly on open |
+//! | ids      | `node_count + 1` `u64` offsets into the UTF-8 blob that follows; an empty ID is a tombstone |
+//!
+//! Tombstones are graph points whose vector was deleted or replaced after
+//! insertion. They keep routing searches (exactly like the live graph
+//! did) but are never returned.
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use memmap2::Mmap;
+use sha2::{Digest, Sha256};
+
+use super::optimized_hnsw::{distance_to_similarity, metric_distance};
+use super::search_deadline::SearchDeadline;
+use crate::error::{Result, VectorizerError};
+use crate::models::DistanceMetric;
+
+/// Page size the sections are aligned to.
+pub const PAGE_SIZE: usize = 4096;
+
+const MAGIC: &[u8; 8] = b"VZHNSW01";
+const FORMAT_VERSION: u32 = 1;
+/// Beam expansions between two deadline checks in [`DiskHnswIndex::search_until`].
+const DEADLINE_CHECK_INTERVAL: usize = 32;
+/// Bytes of the header that are actually used; the rest of page 0 is zero.
+const HEADER_LEN: usize = 8 + 4 * 4 + 8 * 3 + 4 * 4 + 8 * 6;
+
+/// A graph exported from [`crate::db::OptimizedHnswIndex::export_graph`].
+#[derive(Debug, Clone)]
+pub struct HnswGraph {
+    /// Vector dimension.
+    pub dimension: usize,
+    /// Metric the graph was built with.
+    pub metric: DistanceMetric,
+    /// External ID per node; `None` marks a tombstone.
+    pub ids: Vec<Option<String>>,
+    /// Vector per node, as indexed (normalized for cosine collections).
+    pub vectors: Vec<Vec<f32>>,
+    /// `neighbors[node][layer]`; a node's level is `neighbors[node].len() - 1`.
+    pub neighbors: Vec<Vec<Vec<u32>>>,
+    /// Node to start searches from (one on the top layer).
+    pub entry_point: u32,
+}
+
+/// Order-independent fingerprint of a set of vector IDs.
+///
+/// Stored in the header so a loader can tell whether the file still
+/// describes the collection's current contents without reading it.
+pub fn ids_fingerprint<'a>(ids: impl IntoIterator<Item = &'a str>) -> u64 {
+    ids.into_iter().fold(0u64, |acc, id| {
+        let digest = Sha256::digest(id.as_bytes());
+        let mut head = [0u8; 8];
+        head.copy_from_slice(&digest[..8]);
+        acc.wrapping_add(u64::from_le_bytes(head))
+    })
+}
+
+fn metric_code(metric: DistanceMetric) -> u32 {
+    match metric {
+        DistanceMetric::Cosine => 0,
+        DistanceMetric::Euclidean => 1,
+        DistanceMetric::DotProduct => 2,
+    }
+}
+
+fn metric_from_code(code: u32) -> Result<DistanceMetric> {
+    match code {
+        0 => Ok(DistanceMetric::Cosine),
+        1 => Ok(DistanceMetric::Euclidean),
+        2 => Ok(DistanceMetric::DotProduct),
+        other => Err(VectorizerError::IndexError(format!(
+            "disk HNSW: unknown metric code {other}"
+        ))),
+    }
+}
+
+fn page_align(len: usize) -> usize {
+    len.div_ceil(PAGE_SIZE) * PAGE_SIZE
+}
+
+fn pad_to_page(out: &mut impl Write, written: usize) -> Result<()> {
+    out.write_all(&vec![0u8; page_align(written) - written])?;
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Header {
+    dimension: usize,
+    metric: DistanceMetric,
+    node_count: usize,
+    live_count: usize,
+    fingerprint: u64,
+    entry_point: u32,
+    max_level: usize,
+    layer0_slots: usize,
+    layer0_offset: usize,
+    vectors_offset: usize,
+    upper_offset: usize,
+    upper_len: usize,
+    ids_offset: usize,
+    ids_len: usize,
+}
+
+impl Header {
+    fn layer0_record_len(&self) -> usize {
+        4 * (1 + self.layer0_slots)
+    }
+
+    fn layer0_records_per_page(&self) -> usize {
+        PAGE_SIZE / self.layer0_record_len()
+    }
+
+    fn layer0_len(&self) -> usize {
+        self.node_count.div_ceil(self.layer0_records_per_page()) * PAGE_SIZE
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(PAGE_SIZE);
+        buf.extend_from_slice(MAGIC);
+        for v in [
+            FORMAT_VERSION,
+            PAGE_SIZE as u32,
+            self.dimension as u32,
+            metric_code(self.metric),
+        ] {
+            buf.extend_from_slice(&v.to_le_bytes());
+        }
+        for v in [
+            self.node_count as u64,
+            self.live_count as u64,
+            self.fingerprint,
+        ] {
+            buf.extend_from_slice(&v.to_le_bytes());
+        }
+        for v in [
+            self.entry_point,
+            self.max_level as u32,
+            self.layer0_slots as u32,
+            0,
+        ] {
+            buf.extend_from_slice(&v.to_le_bytes());
+        }
+        for v in [
+            self.layer0_offset,
+            self.vectors_offset,
+            self.upper_offset,
+            self.upper_len,
+            self.ids_offset,
+            self.ids_len,
+        ] {
+            buf.extend_from_slice(&(v as u64).to_le_bytes());
+        }
+        debug_assert_eq!(buf.len(), HEADER_LEN);
+        buf.resize(PAGE_SIZE, 0);
+        buf
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self> {
+        let corrupt = |what: &str| VectorizerError::IndexError(format!("disk HNSW: {what}"));
+        if bytes.len() < PAGE_SIZE || &bytes[..8] != MAGIC {
+            return Err(corrupt("not a disk HNSW index file"));
+        }
+        let mut reader = ByteReader { bytes, pos: 8 };
+        let version = reader.u32();
+        if version != FORMAT_VERSION {
+            return Err(corrupt(&format!("unsupported format version {version}")));
+        }
+        if reader.u32() as usize != PAGE_SIZE {
+            return Err(corrupt("page size mismatch"));
+        }
+        let dimension = reader.u32() as usize;
+        let metric = metric_from_code(reader.u32())?;
+        let node_count = reader.u64() as usize;
+        let live_count = reader.u64() as usize;
+        let fingerprint = reader.u64();
+        let entry_point = reader.u32();
+        let max_level = reader.u32() as usize;
+        let layer0_slots = reader.u32() as usize;
+        let _reserved = reader.u32();
+        let header = Self {
+            dimension,
+            metric,
+            node_count,
+            live_count,
+            fingerprint,
+            entry_point,
+            max_level,
+            layer0_slots,
+            layer0_offset: reader.u64() as usize,
+            vectors_offset: reader.u64() as usize,
+            upper_offset: reader.u64() as usize,
+            upper_len: reader.u64() as usize,
+            ids_offset: reader.u64() as usize,
+            ids_len: reader.u64() as usize,
+        };
+
+        if header.layer0_record_len() > PAGE_SIZE {
+            return Err(corrupt("layer-0 record larger than a page"));
+        }
+        if node_count > 0 && header.entry_point as usize >= node_count {
+            return Err(corrupt("entry point out of range"));
+        }
+        let vectors_len = node_count * dimension * 4;
+        let sections = [
+            (header.layer0_offset, header.layer0_len()),
+            (header.vectors_offset, vectors_len),
+            (header.upper_offset, header.upper_len),
+            (header.ids_offset, header.ids_len),
+        ];
+        if sections
+            .iter()
+            .any(|(offset, len)| offset.checked_add(*len).is_none_or(|end| end > bytes.len()))
+        {
+            return Err(corrupt("section extends past end of file"));
+        }
+        if header.ids_len < (node_count + 1) * 8 {
+            return Err(corrupt("ID table truncated"));
+        }
+        Ok(header)
+    }
+}
+
+/// Sequential little-endian reader over a slice whose bounds were
+/// validated by the caller.
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl ByteReader<'_> {
+    fn u32(&mut self) -> u32 {
+        let v = read_u32(self.bytes, self.pos);
+        self.pos += 4;
+        v
+    }
+
+    fn u64(&mut self) -> u64 {
+        let mut b = [0u8; 8];
+        b.copy_from_slice(&self.bytes[self.pos..self.pos + 8]);
+        self.pos += 8;
+        u64::from_le_bytes(b)
+    }
+}
+
+fn read_u32(bytes: &[u8], pos: usize) -> u32 {
+    let mut b = [0u8; 4];
+    b.copy_from_slice(&bytes[pos..pos + 4]);
+    u32::from_le_bytes(b)
+}
+
+/// `(distance, node)` ordered by distance for the search heaps.
+#[derive(Debug, Clone, Copy)]
+struct Scored(f32, u32);
+
+impl PartialEq for Scored {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Scored {}
+
+impl PartialOrd for Scored {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Scored {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0).then(self.1.cmp(&other.1))
+    }
+}
+
+/// Memory-mapped, read-only HNSW index.
+pub struct DiskHnswIndex {
+    path: PathBuf,
+    mmap: Mmap,
+    header: Header,
+    /// Layers 1.. of every node above layer 0, keyed by node.
+    upper: HashMap<u32, Vec<Vec<u32>>>,
+    /// ID -> node, built on first use by [`Self::exact_scores`].
+    id_lookup: OnceLock<HashMap<String, u32>>,
+}
+
+impl std::fmt::Debug for DiskHnswIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DiskHnswIndex")
+            .field("path", &self.path)
+            .field("dimension", &self.header.dimension)
+            .field("node_count", &self.header.node_count)
+            .field("live_count", &self.header.live_count)
+            .finish()
+    }
+}
+
+impl DiskHnswIndex {
+    /// Write `graph` to `path`. The file is written next to `path` and
+    /// renamed into place, so a crash never leaves a half-written index.
+    pub fn write(path: &Path, graph: &HnswGraph) -> Result<()> {
+        let node_count = graph.ids.len();
+        if graph.vectors.len() != node_count || graph.neighbors.len() != node_count {
+            return Err(VectorizerError::IndexError(
+                "disk HNSW: ids, vectors and neighbors differ in length".to_string(),
+            ));
+        }
+        if node_count > u32::MAX as usize {
+            return Err(VectorizerError::IndexError(
+                "disk HNSW: more than u32::MAX nodes".to_string(),
+            ));
+        }
+        if let Some(v) = graph.vectors.iter().find(|v| v.len() != graph.dimension) {
+            return Err(VectorizerError::DimensionMismatch {
+                expected: graph.dimension,
+                actual: v.len(),
+            });
+        }
+
+        let layer0_slots = graph
+            .neighbors
+            .iter()
+            .map(|layers| layers.first().map_or(0, Vec::len))
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        let max_level = graph
+            .neighbors
+            .iter()
+            .map(|layers| layers.len().saturating_sub(1))
+            .max()
+            .unwrap_or(0);
+
+        let mut upper = Vec::new();
+        for (node, layers) in graph.neighbors.iter().enumerate() {
+            if layers.len() < 2 {
+                continue;
+            }
+            upper.extend_from_slice(&(node as u32).to_le_bytes());
+            upper.extend_from_slice(&((layers.len() - 1) as u32).to_le_bytes());
+            for layer in &layers[1..] {
+                upper.extend_from_slice(&(layer.len() as u32).to_le_bytes());
+                for n in layer {
+                    upper.extend_from_slice(&n.to_le_bytes());
+                }
+            }
+        }
+
+        let ids_len = (node_count + 1) * 8
+            + graph
+                .ids
+                .iter()
+                .map(|id| id.as_ref().map_or(0, String::len))
+                .sum::<usize>();
+
+        let mut header = Header {
+            dimension: graph.dimension,
+            metric: graph.metric,
+            node_count,
+            live_count: graph.ids.iter().filter(|id| id.is_some()).count(),
+            fingerprint: ids_fingerprint(graph.ids.iter().flatten().map(String::as_str)),
+            entry_point: graph.entry_point,
+            max_level,
+            layer0_slots,
+            layer0_offset: PAGE_SIZE,
+            vectors_offset: 0,
+            upper_offset: 0,
+            upper_len: upper.len(),
+            ids_offset: 0,
+            ids_len,
+        };
+        if header.layer0_record_len() > PAGE_SIZE {
+            return Err(VectorizerError::IndexError(format!(
+                "disk HNSW: {layer0_slots} layer-0 neighbors do not fit a {PAGE_SIZE}-byte page"
+            )));
+        }
+        header.vectors_offset = header.layer0_offset + header.layer0_len();
+        header.upper_offset = header.vectors_offset + page_align(node_count * graph.dimension * 4);
+        header.ids_offset = header.upper_offset + page_align(upper.len());
+
+        let tmp_path = path.with_extension("tmp");
+        let file = File::create(&tmp_path)?;
+        let mut out = BufWriter::new(file);
+        out.write_all(&header.encode())?;
+
+        // Layer 0: `records_per_page` fixed-size records per page.
+        let record_len = header.layer0_record_len();
+        let per_page = header.layer0_records_per_page();
+        let mut page = Vec::with_capacity(PAGE_SIZE);
+        for (node, layers) in graph.neighbors.iter().enumerate() {
+            let layer0 = layers.first().map_or(&[][..], Vec::as_slice);
+            page.extend_from_slice(&(layer0.len() as u32).to_le_bytes());
+            for n in layer0 {
+                page.extend_from_slice(&n.to_le_bytes());
+            }
+            page.resize(page.len() + 4 * (layer0_slots - layer0.len()), 0);
+            if (node + 1) % per_page == 0 || node + 1 == node_count {
+                debug_assert_eq!(page.len() % record_len, 0);
+                page.resize(PAGE_SIZE, 0);
+                out.write_all(&page)?;
+                page.clear();
+            }
+        }
+
+        let mut written = 0;
+        for vector in &graph.vectors {
+            for x in vector {
+                out.write_all(&x.to_le_bytes())?;
+            }
+            written += vector.len() * 4;
+        }
+        pad_to_page(&mut out, written)?;
+
+        out.write_all(&upper)?;
+        pad_to_page(&mut out, upper.len())?;
+
+        let mut offset = 0u64;
+        out.write_all(&offset.to_le_bytes())?;
+        for id in &graph.ids {
+            offset += id.as_ref().map_or(0, String::len) as u64;
+            out.write_all(&offset.to_le_bytes())?;
+        }
+        for id in graph.ids.iter().flatten() {
+            out.write_all(id.as_bytes())?;
+        }
+
+        let file = out.into_inner().map_err(|e| e.into_error())?;
+        file.sync_all()?;
+        drop(file);
+        std::fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+
+    /// Map `path` and parse its header and upper layers.
+    pub fn open(path: &Path) -> Result<Self> {
+        let file = File::open(path)?;
+        // SAFETY: the file is only ever replaced via rename (see `write`),
+        // never modified in place, so the mapping stays consistent for as
+        // long as this index holds it.
+        let mmap = unsafe { Mmap::map(&file)? };
+        let header = Header::decode(&mmap)?;
+
+        let mut upper = HashMap::new();
+        let bytes = &mmap[header.upper_offset..header.upper_offset + header.upper_len];
+        let corrupt = || VectorizerError::IndexError("disk HNSW: upper layers truncated".into());
+        let mut pos = 0;
+        let next = |pos: &mut usize| -> Result<u32> {
+            if *pos + 4 > bytes.len() {
+                return Err(corrupt());
+            }
+            let v = read_u32(bytes, *pos);
+            *pos += 4;
+            Ok(v)
+        };
+        while pos < bytes.len() {
+            let node = next(&mut pos)?;
+            let levels = next(&mut pos)? as usize;
+            let mut layers = Vec::with_capacity(levels);
+            for _ in 0..levels {
+                let count = next(&mut pos)? as usize;
+                let mut layer = Vec::with_capacity(count);
+                for _ in 0..count {
+                    layer.push(next(&mut pos)?);
+                }
+                layers.push(layer);
+            }
+            upper.insert(node, layers);
+        }
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            mmap,
+            header,
+            upper,
+            id_lookup: OnceLock::new(),
+        })
+    }
+
+    /// File backing this index.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Vector dimension.
+    pub fn dimension(&self) -> usize {
+        self.header.dimension
+    }
+
+    /// Metric the graph was built with.
+    pub fn metric(&self) -> DistanceMetric {
+        self.header.metric
+    }
+
+    /// Number of searchable (non-tombstone) vectors.
+    pub fn len(&self) -> usize {
+        self.header.live_count
+    }
+
+    /// Whether the index holds no searchable vectors.
+    pub fn is_empty(&self) -> bool {
+        self.header.live_count == 0
+    }
+
+    /// [`ids_fingerprint`] of the live IDs.
+    pub fn fingerprint(&self) -> u64 {
+        self.header.fingerprint
+    }
+
+    /// Size of the backing file in bytes.
+    pub fn file_len(&self) -> usize {
+        self.mmap.len()
+    }
+
+    fn id(&self, node: u32) -> Option<&str> {
+        let table = self.header.ids_offset;
+        let blob = table + (self.header.node_count + 1) * 8;
+        let at = |i: usize| {
+            let mut b = [0u8; 8];
+            b.copy_from_slice(&self.mmap[table + i * 8..table + i * 8 + 8]);
+            u64::from_le_bytes(b) as usize
+        };
+        let (start, end) = (at(node as usize), at(node as usize + 1));
+        if start >= end || blob + end > self.header.ids_offset + self.header.ids_len {
+            return None;
+        }
+        std::str::from_utf8(&self.mmap[blob + start..blob + end]).ok()
+    }
+
+    fn read_vector(&self, node: u32, out: &mut Vec<f32>) {
+        let stride = self.header.dimension * 4;
+        let start = self.header.vectors_offset + node as usize * stride;
+        out.clear();
+        out.extend(
+            self.mmap[start..start + stride]
+                .chunks_exact(4)
+                .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]])),
+        );
+    }
+
+    fn layer0_neighbors(&self, node: u32) -> impl Iterator<Item = u32> + '_ {
+        let per_page = self.header.layer0_records_per_page();
+        let node = node as usize;
+        let start = self.header.layer0_offset
+            + (node / per_page) * PAGE_SIZE
+            + (node % per_page) * self.header.layer0_record_len();
+        let count = (read_u32(&self.mmap, start) as usize).min(self.header.layer0_slots);
+        (0..count).map(move |i| read_u32(&self.mmap, start + 4 + i * 4))
+    }
+
+    fn upper_neighbors(&self, node: u32, layer: usize) -> &[u32] {
+        self.upper
+            .get(&node)
+            .and_then(|layers| layers.get(layer - 1))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    fn in_range(&self, node: u32) -> bool {
+        (node as usize) < self.header.node_count
+    }
+
+    /// Approximate `k` nearest neighbours of `query` with a layer-0 beam
+    /// of `ef`. Scores use the same scale as
+    /// [`crate::db::OptimizedHnswIndex::search`].
+    pub fn search(&self, query: &[f32], k: usize, ef: usize) -> Result<Vec<(String, f32)>> {
+        Ok(self.search_until(query, k, ef, &SearchDeadline::NONE)?.0)
+    }
+
+    /// [`Self::search`] that stops walking the graph once `deadline`
+    /// passes and returns the best nodes reached so far. The flag is
+    /// `true` when the walk was cut short.
+    pub fn search_until(
+        &self,
+        query: &[f32],
+        k: usize,
+        ef: usize,
+        deadline: &SearchDeadline,
+    ) -> Result<(Vec<(String, f32)>, bool)> {
+        if query.len() != self.header.dimension {
+            return Err(VectorizerError::DimensionMismatch {
+                expected: self.header.dimension,
+                actual: query.len(),
+            });
+        }
+        if k == 0 || self.header.node_count == 0 {
+            return Ok((Vec::new(), false));
+        }
+
+        let metric = self.header.metric;
+        let ef = ef.max(k);
+        let mut scratch = Vec::with_capacity(self.header.dimension);
+        let mut distance = |node: u32| {
+            self.read_vector(node, &mut scratch);
+            metric_distance(metric, query, &scratch)
+        };
+        let mut steps = 0usize;
+        let mut timed_out = false;
+        let mut out_of_time = || {
+            steps += 1;
+            if steps % DEADLINE_CHECK_INTERVAL == 0 && deadline.expired() {
+                timed_out = true;
+            }
+            timed_out
+        };
+
+        // Greedy descent through the upper layers.
+        let mut current = self.header.entry_point;
+        let mut current_distance = distance(current);
+        'descent: for layer in (1..=self.header.max_level).rev() {
+            loop {
+                if out_of_time() {
+                    break 'descent;
+                }
+                let mut improved = false;
+                for &n in self.upper_neighbors(current, layer) {
+                    if !self.in_range(n) {
+                        continue;
+                    }
+                    let d = distance(n);
+                    if d < current_distance {
+                        current = n;
+                        current_distance = d;
+                        improved = true;
+                    }
+                }
+                if !improved {
+                    break;
+                }
+            }
+        }
+
+        // Beam search on layer 0.
+        let mut visited = HashSet::from([current]);
+        let mut candidates = BinaryHeap::from([Reverse(Scored(current_distance, current))]);
+        let mut found = BinaryHeap::from([Scored(current_distance, current)]);
+        while let Some(Reverse(Scored(d, node))) = candidates.pop() {
+            if found.len() >= ef && found.peek().is_some_and(|worst| d > worst.0) {
+                break;
+            }
+            if out_of_time() {
+                break;
+            }
+            for n in self.layer0_neighbors(node) {
+                if !self.in_range(n) || !visited.insert(n) {
+                    continue;
+                }
+                let dn = distance(n);
+                if found.len() < ef || found.peek().is_some_and(|worst| dn < worst.0) {
+                    candidates.push(Reverse(Scored(dn, n)));
+                    found.push(Scored(dn, n));
+                    if found.len() > ef {
+                        found.pop();
+                    }
+                }
+            }
+        }
+
+        let neighbors = found
+            .into_sorted_vec()
+            .into_iter()
+            .filter_map(|Scored(d, node)| {
+                self.id(node)
+                    .map(|id| (id.to_string(), distance_to_similarity(metric, d)))
+            })
+            .take(k)
+            .collect();
+        Ok((neighbors, timed_out))
+    }
+
+    /// Score `ids` exactly against `query`, on the same scale as
+    /// [`Self::search`]. Unknown IDs are skipped.
+    pub fn exact_scores(&self, query: &[f32], ids: &[String]) -> Result<Vec<(String, f32)>> {
+        if query.len() != self.header.dimension {
+            return Err(VectorizerError::DimensionMismatch {
+                expected: self.header.dimension,
+                actual: query.len(),
+            });
+        }
+        let lookup = self.id_lookup.get_or_init(|| {
+            (0..self.header.node_count as u32)
+                .filter_map(|node| self.id(node).map(|id| (id.to_string(), node)))
+                .collect()
+        });
+        let metric = self.header.metric;
+        let mut scratch = Vec::with_capacity(self.header.dimension);
+        Ok(ids
+            .iter()
+            .filter_map(|id| {
+                let node = *lookup.get(id)?;
+                self.read_vector(node, &mut scratch);
+                let d = metric_distance(metric, query, &scratch);
+                Some((id.clone(), distance_to_similarity(metric, d)))
+            })
+            .collect())
+    }
+
+    /// Every live `(id, vector)` pair, in node order. Used to rebuild the
+    /// in-memory index when a cold collection is written to.
+    pub fn live_vectors(&self) -> impl Iterator<Item = (String, Vec<f32>)> + '_ {
+        (0..self.header.node_count as u32).filter_map(move |node| {
+            let id = self.id(node)?.to_string();
+            let mut vector = Vec::with_capacity(self.header.dimension);
+            self.read_vector(node, &mut vector);
+            Some((id, vector))
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::db::optimized_hnsw::{OptimizedHnswConfig, OptimizedHnswIndex};
+
+    /// Deterministic pseudo-random vectors (xorshift) so recall is stable.
+    fn vectors(count: usize, dim: usize) -> Vec<(String, Vec<f32>)> {
+        let mut state = 0x9E37_79B9_7F4A_7C15u64;
+        (0..count)
+            .map(|i| {
+                let v = (0..dim)
+                    .map(|_| {
+                        state ^= state << 13;
+                        state ^= state >> 7;
+                        state ^= state << 17;
+                        (state % 10_000) as f32 / 10_000.0 - 0.5
+                    })
+                    .collect();
+                (format!("v{i}"), v)
+            })
+            .collect()
+    }
+
+    fn build(metric: DistanceMetric, data: &[(String, Vec<f32>)]) -> OptimizedHnswIndex {
+        let index = OptimizedHnswIndex::new(
+            data[0].1.len(),
+            OptimizedHnswConfig {
+                distance_metric: metric,
+                initial_capacity: 1_024,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        index.batch_add(data.to_vec()).unwrap();
+        index
+    }
+
+    #[test]
+    fn test_round_trip_matches_in_memory_search() {
+        let data = vectors(500, 16);
+        let index = build(DistanceMetric::Euclidean, &data);
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("c.vzhnsw");
+        DiskHnswIndex::write(&path, &index.export_graph().unwrap()).unwrap();
+        let disk = DiskHnswIndex::open(&path).unwrap();
+
+        assert_eq!(disk.len(), 500);
+        assert_eq!(disk.dimension(), 16);
+        assert_eq!(disk.metric(), DistanceMetric::Euclidean);
+        assert_eq!(
+            disk.fingerprint(),
+            ids_fingerprint(data.iter().map(|(id, _)| id.as_str()))
+        );
+
+        // Querying with a stored vector must find it first, scored like the
+        // in-memory index scores it.
+        let mut hits = 0;
+        for (id, v) in data.iter().step_by(25) {
+            let disk_top = disk.search(v, 5, 64).unwrap();
+            let mem_top = index.search(v, 5).unwrap();
+            if disk_top[0].0 == *id {
+                hits += 1;
+            }
+            if disk_top[0].0 == mem_top[0].0 {
+                assert!((disk_top[0].1 - mem_top[0].1).abs() < 1e-5);
+            }
+        }
+        assert!(hits >= 19, "only {hits}/20 self-queries found themselves");
+
+        let exact = disk
+            .exact_scores(&data[3].1, &["v3".to_string(), "nope".to_string()])
+            .unwrap();
+        assert_eq!(exact.len(), 1);
+        assert!((exact[0].1 - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_removed_vectors_become_tombstones() {
+        let data = vectors(200, 8);
+        let index = build(DistanceMetric::Cosine, &data);
+        index.remove("v7").unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("c.vzhnsw");
+        DiskHnswIndex::write(&path, &index.export_graph().unwrap()).unwrap();
+        let disk = DiskHnswIndex::open(&path).unwrap();
+
+        assert_eq!(disk.len(), 199);
+        assert_eq!(disk.live_vectors().count(), 199);
+        let top = disk.search(&data[7].1, 10, 64).unwrap();
+        assert!(top.iter().all(|(id, _)| id != "v7"));
+        assert!(!top.is_empty());
+    }
+
+    #[test]
+    fn test_open_rejects_foreign_and_truncated_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bad.vzhnsw");
+        std::fs::write(&path, b"definitely not an index").unwrap();
+        assert!(DiskHnswIndex::open(&path).is_err());
+
+        let data = vectors(50, 4);
+        let index = build(DistanceMetric::DotProduct, &data);
+        let good = dir.path().join("good.vzhnsw");
+        DiskHnswIndex::write(&good, &index.export_graph().unwrap()).unwrap();
+        let bytes = std::fs::read(&good).unwrap();
+        std::fs::write(&path, &bytes[..bytes.len() - PAGE_SIZE]).unwrap();
+        assert!(DiskHnswIndex::open(&path).is_err());
+    }
+
+    #[test]
+    fn test_search_until_returns_partial_results_past_deadline() {
+        let data = vectors(2_000, 16);
+        let index = build(DistanceMetric::Euclidean, &data);
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("c.vzhnsw");
+        DiskHnswIndex::write(&path, &index.export_graph().unwrap()).unwrap();
+        let disk = DiskHnswIndex::open(&path).unwrap();
+
+        let (full, timed_out) = disk
+            .search_until(&data[3].1, 10, 64, &SearchDeadline::NONE)
+            .unwrap();
+        assert!(!timed_out);
+        assert_eq!(full.len(), 10);
+
+        // An already-expired deadline stops the walk after a few steps,
+        // returning whatever was reached instead of an error.
+        let expired = SearchDeadline::from_timeout_ms(Some(0));
+        let (partial, timed_out) = disk.search_until(&data[3].1, 10, 64, &expired).unwrap();
+        assert!(timed_out);
+        assert!(partial.len() <= 10);
+    }
+}
@@ -4,6 +4,7 @@
 //! across multiple server instances in a cluster.
 
 use std::collections::HashMap;
+use std::collections::hash_map::Entry;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
@@ -90,7 +91,7 @@ impl DistributedShardedCollection {
                     shard_config.sharding = None; // Shards themselves are not sharded
 
                     let shard_name = format!("{}_{}", name, shard_id);
-                    let shard_collection = Collection::new(shard_name, shard_config);
+                    let shard_collection = Collection::try_new(shard_name, shard_config)?;
                     local_shards.write().insert(*shard_id, shard_collection);
                 }
             }
@@ -935,6 +936,73 @@ impl DistributedShardedCollection {
         Ok(total)
     }
 
+    /// Every shard of the collection with the node currently owning it.
+    pub fn shard_owners(&self) -> Vec<(ShardId, Option<String>)> {
+        let shard_count = self
+            .config
+            .sharding
+            .as_ref()
+            .map_or(0, |sharding| sharding.shard_count);
+        (0..shard_count)
+            .map(ShardId::new)
+            .map(|shard_id| (shard_id, self.topology.node_for_shard(&shard_id)))
+            .collect()
+    }
+
+    /// Vector count of each shard held on this node.
+    pub fn local_shard_counts(&self) -> HashMap<ShardId, usize> {
+        self.local_shards
+            .read()
+            .iter()
+            .map(|(shard_id, shard)| (*shard_id, shard.vector_count()))
+            .collect()
+    }
+
+    /// Vectors of every shard held on this node, by shard.
+    ///
+    /// Used by cluster snapshots, which save each node's local shards
+    /// and leave remote ones to the nodes holding them.
+    pub fn local_shard_vectors(&self) -> HashMap<ShardId, Vec<Vector>> {
+        self.local_shards
+            .read()
+            .iter()
+            .map(|(shard_id, shard)| (*shard_id, shard.get_all_vectors()))
+            .collect()
+    }
+
+    /// Replace the contents of this node's shards with `shards`, as
+    /// saved by [`Self::local_shard_vectors`]. Shards not in `shards`
+    /// are emptied.
+    pub fn replace_local_shards(&self, shards: HashMap<ShardId, Vec<Vector>>) -> Result<()> {
+        let mut shard_config = self.config.clone();
+        shard_config.sharding = None;
+
+        let new_shard = |shard_id: &ShardId| {
+            Collection::try_new(format!("{}_{}", self.name, shard_id), shard_config.clone())
+        };
+
+        let mut local_shards = self.local_shards.write();
+        let mut restored: HashMap<ShardId, Collection> = local_shards
+            .keys()
+            .map(|shard_id| Ok((*shard_id, new_shard(shard_id)?)))
+            .collect::<Result<_>>()?;
+        for (shard_id, vectors) in shards {
+            if vectors.is_empty() {
+                continue;
+            }
+            let shard = match restored.entry(shard_id) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(new_shard(&shard_id)?),
+            };
+            shard.insert_batch(vectors)?;
+        }
+        *local_shards = restored;
+        drop(local_shards);
+
+        self.invalidate_vector_count_cache();
+        Ok(())
+    }
+
     /// Requantize existing vectors in local shards
     ///
     /// This method requantizes vectors in local shards only. For remote shards,
@@ -25,6 +25,10 @@ pub enum RelationshipType {
     Contains,
     /// Document is derived from another document
     DerivedFrom,
+    /// Document mentions an extracted entity
+    Mentions,
+    /// Extracted entities are related (e.g. mentioned in one sentence)
+    RelatedTo,
 }
 
 impl RelationshipType {
@@ -35,6 +39,8 @@ impl RelationshipType {
             RelationshipType::References,
             RelationshipType::Contains,
             RelationshipType::DerivedFrom,
+            RelationshipType::Mentions,
+            RelationshipType::RelatedTo,
         ]
     }
 }
@@ -271,15 +277,19 @@ impl Graph {
         let mut reverse_adjacency = self.reverse_adjacency_list.write();
 
         // Check if edge already exists
-        if edges.contains_key(&edge.id) {
-            // Update existing edge
+        if let Some(existing) = edges.get(&edge.id)
+            && existing.source == edge.source
+            && existing.target == edge.target
+        {
+            // Update existing edge; its adjacency entries already exist
             debug!("Updating existing edge '{}' in graph", edge.id);
-        } else {
-            info!(
-                "Adding edge '{}' ({:?}) from '{}' to '{}' in graph",
-                edge.id, edge.relationship_type, edge.source, edge.target
-            );
+            edges.insert(edge.id.clone(), edge);
+            return Ok(());
         }
+        info!(
+            "Adding edge '{}' ({:?}) from '{}' to '{}' in graph",
+            edge.id, edge.relationship_type, edge.source, edge.target
+        );
 
         edges.insert(edge.id.clone(), edge.clone());
 
@@ -296,6 +306,12 @@ impl Graph {
         Ok(())
     }
 
+    /// Get an edge by ID
+    pub fn get_edge(&self, edge_id: &str) -> Option<Edge> {
+        let edges = self.edges.read();
+        edges.get(edge_id).cloned()
+    }
+
     /// Remove an edge from the graph
     pub fn remove_edge(&self, edge_id: &str) -> Result<()> {
         let mut edges = self.edges.write();
@@ -357,6 +373,41 @@ impl Graph {
         Ok(neighbors)
     }
 
+    /// Get the nodes with an edge pointing at a node, with those edges
+    pub fn get_incoming_neighbors(
+        &self,
+        node_id: &str,
+        relationship_type: Option<RelationshipType>,
+    ) -> Result<Vec<(Node, Edge)>> {
+        let nodes = self.nodes.read();
+        let edges = self.edges.read();
+        let reverse_adjacency = self.reverse_adjacency_list.read();
+
+        if !nodes.contains_key(node_id) {
+            return Err(VectorizerError::NotFound(format!(
+                "Node '{}' not found",
+                node_id
+            )));
+        }
+
+        let edge_ids = reverse_adjacency.get(node_id).cloned().unwrap_or_default();
+        let mut neighbors = Vec::new();
+
+        for edge_id in edge_ids {
+            if let Some(edge) = edges.get(&edge_id) {
+                if relationship_type.is_some_and(|rel_type| edge.relationship_type != rel_type) {
+                    continue;
+                }
+
+                if let Some(source_node) = nodes.get(&edge.source) {
+                    neighbors.push((source_node.clone(), edge.clone()));
+                }
+            }
+        }
+
+        Ok(neighbors)
+    }
+
     /// Find all nodes related to a given node within N hops
     pub fn find_related(
         &self,
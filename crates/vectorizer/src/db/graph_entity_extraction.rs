@@ -0,0 +1,656 @@
+//! Knowledge-graph extraction from inserted chunks
+//!
+//! With `graph.entity_extraction.enabled` on a collection, every inserted
+//! chunk's text runs through the configured [`EntityRecognizer`]s. Each
+//! entity found becomes an `entity` node (one per label and normalized
+//! name, shared by every chunk that mentions it) linked from the chunk's
+//! node by a `MENTIONS` edge, and entities related in the text are joined
+//! by `RELATED_TO` edges. The graph endpoints then list and traverse
+//! them like any other node.
+//!
+//! The built-in `rules` recognizer finds e-mail addresses, URLs, ISO
+//! dates, organizations (capitalized names ending in `Inc`, `Ltd`, ...)
+//! and other proper nouns, and relates entities mentioned in the same
+//! sentence. Statistical NER plugs in by implementing [`EntityRecognizer`]
+//! and registering it with [`register_entity_recognizer`] under the name
+//! listed in `recognizers`.
+
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+
+use parking_lot::RwLock;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::db::graph::{Edge, Graph, Node, RelationshipType};
+use crate::error::Result;
+use crate::models::{EntityExtractionConfig, Payload};
+
+/// Node type of extracted entities
+pub const ENTITY_NODE_TYPE: &str = "entity";
+
+/// Name of the built-in rule-based recognizer
+pub const RULES_RECOGNIZER: &str = "rules";
+
+/// Most entities of one sentence related to each other by the rules
+/// recognizer; long enumerations would otherwise add edges quadratically
+const MAX_RELATED_PER_SENTENCE: usize = 8;
+
+/// An entity found in a text
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExtractedEntity {
+    /// The entity as written
+    pub text: String,
+    /// Entity type, e.g. `ORGANIZATION` or a NER model's `PERSON`
+    pub label: String,
+    /// Byte offset of the first character in the text
+    pub start: usize,
+    /// Byte offset past the last character
+    pub end: usize,
+}
+
+/// A relation between two entities of one [`Extraction`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExtractedRelation {
+    /// Index of the source entity in [`Extraction::entities`]
+    pub source: usize,
+    /// Index of the target entity in [`Extraction::entities`]
+    pub target: usize,
+    /// Relation type, e.g. `CO_OCCURS`
+    pub label: String,
+}
+
+/// Entities and relations found in a text
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Extraction {
+    /// Entities in order of appearance
+    pub entities: Vec<ExtractedEntity>,
+    /// Relations between them
+    pub relations: Vec<ExtractedRelation>,
+}
+
+/// Finds entities (and optionally relations) in chunk text
+pub trait EntityRecognizer: Send + Sync {
+    /// Name listed in `graph.entity_extraction.recognizers`
+    fn name(&self) -> &str;
+
+    /// Extract the entities and relations of `text`
+    fn extract(&self, text: &str) -> Result<Extraction>;
+}
+
+type Registry = RwLock<HashMap<String, Arc<dyn EntityRecognizer>>>;
+
+fn registry() -> &'static Registry {
+    static RECOGNIZERS: OnceLock<Registry> = OnceLock::new();
+    RECOGNIZERS.get_or_init(|| {
+        let rules: Arc<dyn EntityRecognizer> = Arc::new(RuleBasedRecognizer::new());
+        RwLock::new(HashMap::from([(RULES_RECOGNIZER.to_string(), rules)]))
+    })
+}
+
+/// Make `recognizer` available to collections listing its name in
+/// `recognizers`, replacing any registered under the same name
+pub fn register_entity_recognizer(recognizer: Arc<dyn EntityRecognizer>) {
+    let name = recognizer.name().to_string();
+    registry().write().insert(name, recognizer);
+}
+
+/// The recognizer registered as `name`
+pub fn entity_recognizer(name: &str) -> Option<Arc<dyn EntityRecognizer>> {
+    registry().read().get(name).cloned()
+}
+
+/// Counts of what one extraction added to a graph
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtractionStats {
+    /// Distinct entities linked to the chunk
+    pub entities: usize,
+    /// `MENTIONS` edges written
+    pub mentions: usize,
+    /// `RELATED_TO` edges written
+    pub relations: usize,
+}
+
+impl ExtractionStats {
+    /// Add `other`'s counts to these
+    pub fn add(&mut self, other: ExtractionStats) {
+        self.entities += other.entities;
+        self.mentions += other.mentions;
+        self.relations += other.relations;
+    }
+}
+
+/// ID of the graph node of entity `name` labelled `label`
+pub fn entity_node_id(label: &str, name: &str) -> String {
+    format!(
+        "entity:{}:{}",
+        label.to_lowercase(),
+        normalize_entity_name(name)
+    )
+}
+
+/// Lowercase `name` with its whitespace collapsed, so "Acme  Corp" and
+/// "ACME Corp" are one entity
+fn normalize_entity_name(name: &str) -> String {
+    name.split_whitespace()
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Run the configured recognizers over the text of chunk `vector_id`
+/// and link what they find into `graph`. The chunk's node must already
+/// exist. Recognizers that are not registered or fail are skipped with
+/// a warning.
+pub fn extract_entities_into_graph(
+    graph: &Graph,
+    vector_id: &str,
+    payload: &Payload,
+    config: &EntityExtractionConfig,
+) -> Result<ExtractionStats> {
+    let mut stats = ExtractionStats::default();
+    let Some(text) = payload
+        .data
+        .get(&config.text_field)
+        .and_then(|v| v.as_str())
+        .filter(|t| !t.trim().is_empty())
+    else {
+        return Ok(stats);
+    };
+
+    // (entity node ID, display name, label, mentions in this chunk)
+    let mut entities: Vec<(String, String, String, usize)> = Vec::new();
+    // (source node ID, target node ID, relation label)
+    let mut relations: Vec<(String, String, String)> = Vec::new();
+    for name in &config.recognizers {
+        let Some(recognizer) = entity_recognizer(name) else {
+            warn!("Entity recognizer '{}' is not registered", name);
+            continue;
+        };
+        let extraction = match recognizer.extract(text) {
+            Ok(extraction) => extraction,
+            Err(e) => {
+                warn!(
+                    "Entity recognizer '{}' failed on '{}': {}",
+                    name, vector_id, e
+                );
+                continue;
+            }
+        };
+
+        let mut node_ids: Vec<Option<String>> = Vec::with_capacity(extraction.entities.len());
+        for entity in &extraction.entities {
+            let keep = entity.text.chars().count() >= config.min_entity_length
+                && (config.labels.is_empty()
+                    || config
+                        .labels
+                        .iter()
+                        .any(|l| l.eq_ignore_ascii_case(&entity.label)));
+            if !keep {
+                node_ids.push(None);
+                continue;
+            }
+            let id = entity_node_id(&entity.label, &entity.text);
+            if let Some(known) = entities.iter_mut().find(|(node_id, ..)| *node_id == id) {
+                known.3 += 1;
+            } else if entities.len() < config.max_entities_per_chunk {
+                entities.push((
+                    id.clone(),
+                    entity.text.clone(),
+                    entity.label.to_uppercase(),
+                    1,
+                ));
+            } else {
+                node_ids.push(None);
+                continue;
+            }
+            node_ids.push(Some(id));
+        }
+
+        if !config.relations {
+            continue;
+        }
+        for relation in &extraction.relations {
+            let (Some(Some(source)), Some(Some(target))) =
+                (node_ids.get(relation.source), node_ids.get(relation.target))
+            else {
+                continue;
+            };
+            if source != target
+                && !relations
+                    .iter()
+                    .any(|(s, t, l)| s == source && t == target && *l == relation.label)
+            {
+                relations.push((source.clone(), target.clone(), relation.label.clone()));
+            }
+        }
+    }
+
+    for (id, name, label, mentions) in &entities {
+        let mut node = graph
+            .get_node(id)
+            .unwrap_or_else(|| Node::new(id.clone(), ENTITY_NODE_TYPE.to_string()));
+        node.metadata
+            .insert("name".to_string(), serde_json::json!(name));
+        node.metadata
+            .insert("label".to_string(), serde_json::json!(label));
+        graph.add_node(node)?;
+
+        let mut edge = Edge::new(
+            format!("{}:{}:MENTIONS", vector_id, id),
+            vector_id.to_string(),
+            id.clone(),
+            RelationshipType::Mentions,
+            *mentions as f32,
+        );
+        edge.metadata
+            .insert("mentions".to_string(), serde_json::json!(mentions));
+        graph.add_edge(edge)?;
+        stats.entities += 1;
+        stats.mentions += 1;
+    }
+
+    for (source, target, label) in &relations {
+        let edge_id = format!("{}:{}:RELATED_TO:{}", source, target, label);
+        // The weight counts the chunks that relate the two entities
+        let mut sources = graph
+            .get_edge(&edge_id)
+            .and_then(|edge| edge.metadata.get("source_ids").cloned())
+            .and_then(|ids| serde_json::from_value::<Vec<String>>(ids).ok())
+            .unwrap_or_default();
+        if !sources.iter().any(|id| id == vector_id) {
+            sources.push(vector_id.to_string());
+        }
+        let mut edge = Edge::new(
+            edge_id,
+            source.clone(),
+            target.clone(),
+            RelationshipType::RelatedTo,
+            sources.len() as f32,
+        );
+        edge.metadata
+            .insert("relation".to_string(), serde_json::json!(label));
+        edge.metadata
+            .insert("source_ids".to_string(), serde_json::json!(sources));
+        graph.add_edge(edge)?;
+        stats.relations += 1;
+    }
+
+    if stats.entities > 0 {
+        debug!(
+            "Linked {} entities and {} relations to '{}'",
+            stats.entities, stats.relations, vector_id
+        );
+    }
+    Ok(stats)
+}
+
+/// Rule-based recognizer: patterns for e-mail addresses, URLs and ISO
+/// dates, capitalization for organizations and proper nouns, and
+/// `CO_OCCURS` relations between entities of one sentence
+pub struct RuleBasedRecognizer {
+    email: Regex,
+    url: Regex,
+    date: Regex,
+}
+
+impl std::fmt::Debug for RuleBasedRecognizer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RuleBasedRecognizer").finish()
+    }
+}
+
+impl Default for RuleBasedRecognizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Capitalized words that start sentences and clauses rather than names
+const CAPITALIZED_STOPWORDS: &[&str] = &[
+    "A", "An", "The", "This", "That", "These", "Those", "It", "Its", "We", "I", "You", "He", "She",
+    "They", "Our", "Your", "In", "On", "At", "For", "From", "With", "By", "To", "Of", "And", "But",
+    "Or", "If", "When", "While", "After", "Before", "Then", "So", "Also", "All", "Each", "Every",
+    "Some", "Not", "Yes", "However", "Note", "See", "O", "Os", "As", "Um", "Uma", "Em", "Na", "No",
+    "Para", "Com", "Por", "Se", "Este", "Esta", "El", "La", "Los", "Las", "Le", "Les", "Der",
+    "Die", "Das",
+];
+
+/// Lowercase words allowed inside a name ("Bank of America")
+const NAME_CONNECTORS: &[&str] = &[
+    "of", "de", "da", "do", "dos", "das", "del", "van", "von", "&",
+];
+
+/// Last words that make a name an organization
+const ORGANIZATION_SUFFIXES: &[&str] = &[
+    "Inc",
+    "Corp",
+    "Corporation",
+    "Ltd",
+    "LLC",
+    "GmbH",
+    "AG",
+    "SA",
+    "Ltda",
+    "Co",
+    "Company",
+    "Group",
+    "Foundation",
+    "University",
+    "Institute",
+    "Labs",
+    "Technologies",
+];
+
+impl RuleBasedRecognizer {
+    /// Compile the recognizer's patterns.
+    ///
+    /// The patterns are `&'static str` literals covered by the unit
+    /// tests, so a compile failure is a code-edit bug, not a runtime
+    /// condition.
+    #[allow(clippy::unwrap_used)]
+    pub fn new() -> Self {
+        Self {
+            email: Regex::new(r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}").unwrap(),
+            url: Regex::new(r#"https?://[^\s<>()"']+"#).unwrap(),
+            date: Regex::new(r"\b\d{4}-\d{2}-\d{2}\b").unwrap(),
+        }
+    }
+
+    /// Names in `sentence` (starting at byte `offset` of the text)
+    fn names(
+        &self,
+        sentence: &str,
+        offset: usize,
+        taken: &[(usize, usize)],
+    ) -> Vec<ExtractedEntity> {
+        let words = words(sentence, offset);
+        let mut entities = Vec::new();
+        let mut i = 0;
+        while i < words.len() {
+            if !is_name_word(words[i].0) || taken_by(taken, words[i].1) {
+                i += 1;
+                continue;
+            }
+            // Extend over capitalized words and connectors between them
+            let mut end = i;
+            let mut j = i + 1;
+            while j < words.len() && !taken_by(taken, words[j].1) {
+                if is_name_word(words[j].0) {
+                    end = j;
+                } else if !NAME_CONNECTORS.contains(&words[j].0) {
+                    break;
+                }
+                j += 1;
+            }
+            let mut first = i;
+            while first <= end && CAPITALIZED_STOPWORDS.contains(&words[first].0) {
+                first += 1;
+            }
+            i = end + 1;
+            if first > end {
+                continue;
+            }
+            // A lone capitalized word opening the sentence is usually
+            // just the sentence's first word
+            let single = first == end;
+            let acronym = words[first].0.chars().filter(|c| c.is_alphabetic()).count() > 1
+                && words[first].0.chars().all(|c| !c.is_lowercase());
+            if single && first == 0 && !acronym {
+                continue;
+            }
+            let start = words[first].1;
+            let stop = words[end].1 + words[end].0.len();
+            let text = &sentence[start - offset..stop - offset];
+            let label =
+                if ORGANIZATION_SUFFIXES.contains(&words[end].0.trim_end_matches('.')) && !single {
+                    "ORGANIZATION"
+                } else {
+                    "PROPER_NOUN"
+                };
+            entities.push(ExtractedEntity {
+                text: text.to_string(),
+                label: label.to_string(),
+                start,
+                end: stop,
+            });
+        }
+        entities
+    }
+}
+
+impl EntityRecognizer for RuleBasedRecognizer {
+    fn name(&self) -> &str {
+        RULES_RECOGNIZER
+    }
+
+    fn extract(&self, text: &str) -> Result<Extraction> {
+        let mut extraction = Extraction::default();
+        for (sentence, offset) in sentences(text) {
+            let mut found: Vec<ExtractedEntity> = Vec::new();
+            for (pattern, label) in [
+                (&self.email, "EMAIL"),
+                (&self.url, "URL"),
+                (&self.date, "DATE"),
+            ] {
+                for m in pattern.find_iter(sentence) {
+                    let value = m.as_str().trim_end_matches(['.', ',', ';', ':', '!', '?']);
+                    let start = offset + m.start();
+                    if found
+                        .iter()
+                        .any(|e| start < e.end && e.start < start + value.len())
+                    {
+                        continue;
+                    }
+                    found.push(ExtractedEntity {
+                        text: value.to_string(),
+                        label: label.to_string(),
+                        start,
+                        end: start + value.len(),
+                    });
+                }
+            }
+            let taken: Vec<(usize, usize)> = found.iter().map(|e| (e.start, e.end)).collect();
+            found.extend(self.names(sentence, offset, &taken));
+            found.sort_by_key(|e| e.start);
+
+            let first = extraction.entities.len();
+            let related = || found.iter().enumerate().take(MAX_RELATED_PER_SENTENCE);
+            for (a, left) in related() {
+                for (b, right) in related().skip(a + 1) {
+                    let same =
+                        normalize_entity_name(&left.text) == normalize_entity_name(&right.text);
+                    if !same {
+                        extraction.relations.push(ExtractedRelation {
+                            source: first + a,
+                            target: first + b,
+                            label: "CO_OCCURS".to_string(),
+                        });
+                    }
+                }
+            }
+            extraction.entities.extend(found);
+        }
+        Ok(extraction)
+    }
+}
+
+/// Sentences of `text` with their byte offsets: split after `.`, `!` or
+/// `?` followed by whitespace, and at line breaks
+fn sentences(text: &str) -> Vec<(&str, usize)> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let next_is_space = chars.peek().is_none_or(|(_, n)| n.is_whitespace());
+        let boundary = c == '\n' || (matches!(c, '.' | '!' | '?') && next_is_space);
+        if boundary {
+            let end = i + c.len_utf8();
+            if !text[start..end].trim().is_empty() {
+                sentences.push((&text[start..end], start));
+            }
+            start = end;
+        }
+    }
+    if !text[start..].trim().is_empty() {
+        sentences.push((&text[start..], start));
+    }
+    sentences
+}
+
+/// Words of `sentence` stripped of surrounding punctuation, each with
+/// its byte offset in the text (`offset` being the sentence's)
+fn words(sentence: &str, offset: usize) -> Vec<(&str, usize)> {
+    let mut words = Vec::new();
+    let mut position = 0;
+    for raw in sentence.split_whitespace() {
+        let Some(found) = sentence[position..].find(raw) else {
+            continue;
+        };
+        let raw_start = position + found;
+        position = raw_start + raw.len();
+        let trimmed_start = raw.trim_start_matches(|c: char| !c.is_alphanumeric() && c != '&');
+        let word = trimmed_start.trim_end_matches(|c: char| !c.is_alphanumeric() && c != '&');
+        if word.is_empty() {
+            continue;
+        }
+        let start = raw_start + (raw.len() - trimmed_start.len());
+        words.push((word, offset + start));
+    }
+    words
+}
+
+/// Whether `word` looks like part of a name: starts with an uppercase
+/// letter and is not a number
+fn is_name_word(word: &str) -> bool {
+    word.chars().next().is_some_and(char::is_uppercase)
+}
+
+/// Whether the word at byte `start` lies inside an already found entity
+fn taken_by(taken: &[(usize, usize)], start: usize) -> bool {
+    taken.iter().any(|&(s, e)| s <= start && start < e)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn labelled(extraction: &Extraction) -> Vec<(&str, &str)> {
+        extraction
+            .entities
+            .iter()
+            .map(|e| (e.text.as_str(), e.label.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_rules_find_patterns_and_names() {
+        let text = "The release was signed off by Maria Silva at Acme Corp on 2026-03-14. \
+                    Questions go to ops@acme.io or https://acme.io/status.";
+        let extraction = RuleBasedRecognizer::new().extract(text).unwrap();
+        assert_eq!(
+            labelled(&extraction),
+            [
+                ("Maria Silva", "PROPER_NOUN"),
+                ("Acme Corp", "ORGANIZATION"),
+                ("2026-03-14", "DATE"),
+                ("ops@acme.io", "EMAIL"),
+                ("https://acme.io/status", "URL"),
+            ]
+        );
+        for entity in &extraction.entities {
+            assert_eq!(&text[entity.start..entity.end], entity.text);
+        }
+        // Entities of one sentence are related, not across sentences
+        assert_eq!(extraction.relations.len(), 3 + 1);
+        assert!(
+            extraction
+                .relations
+                .iter()
+                .all(|r| (r.source < 3) == (r.target < 3))
+        );
+    }
+
+    #[test]
+    fn test_rules_skip_sentence_starts_and_keep_connectors() {
+        let text = "Deploys run nightly. Then the Bank of America team reviews them with AWS.";
+        let extraction = RuleBasedRecognizer::new().extract(text).unwrap();
+        assert_eq!(
+            labelled(&extraction),
+            [("Bank of America", "PROPER_NOUN"), ("AWS", "PROPER_NOUN")]
+        );
+    }
+
+    #[test]
+    fn test_extraction_links_chunks_to_shared_entities() {
+        let graph = Graph::new("kg".to_string());
+        let config = EntityExtractionConfig {
+            enabled: true,
+            ..Default::default()
+        };
+        for (id, content) in [
+            ("c1", "Maria Silva leads the payments team at Acme Corp."),
+            ("c2", "Acme Corp moved billing to Lisbon."),
+        ] {
+            graph
+                .add_node(Node::new(id.to_string(), "document".to_string()))
+                .unwrap();
+            let payload = Payload::new(serde_json::json!({ "content": content }));
+            extract_entities_into_graph(&graph, id, &payload, &config).unwrap();
+        }
+
+        let acme = entity_node_id("ORGANIZATION", "Acme Corp");
+        let node = graph.get_node(&acme).unwrap();
+        assert_eq!(node.node_type, ENTITY_NODE_TYPE);
+        assert_eq!(node.metadata["name"], "Acme Corp");
+        let mentioned_by: Vec<String> = graph
+            .get_all_edges()
+            .into_iter()
+            .filter(|e| e.relationship_type == RelationshipType::Mentions && e.target == acme)
+            .map(|e| e.source)
+            .collect();
+        assert_eq!(mentioned_by.len(), 2);
+
+        let related = graph
+            .get_neighbors(&entity_node_id("PROPER_NOUN", "Maria Silva"), None)
+            .unwrap();
+        assert!(
+            related
+                .iter()
+                .any(|(n, e)| n.id == acme && e.relationship_type == RelationshipType::RelatedTo)
+        );
+    }
+
+    #[test]
+    fn test_label_filter_and_unknown_recognizers() {
+        let graph = Graph::new("kg".to_string());
+        graph
+            .add_node(Node::new("c1".to_string(), "document".to_string()))
+            .unwrap();
+        let config = EntityExtractionConfig {
+            enabled: true,
+            recognizers: vec!["missing".to_string(), RULES_RECOGNIZER.to_string()],
+            labels: vec!["email".to_string()],
+            ..Default::default()
+        };
+        let payload = Payload::new(serde_json::json!({
+            "content": "Write to Maria Silva at maria@acme.io today."
+        }));
+        let stats = extract_entities_into_graph(&graph, "c1", &payload, &config).unwrap();
+        assert_eq!(
+            stats,
+            ExtractionStats {
+                entities: 1,
+                mentions: 1,
+                relations: 0,
+            }
+        );
+        assert!(
+            graph
+                .get_node(&entity_node_id("EMAIL", "maria@acme.io"))
+                .is_some()
+        );
+    }
+}
@@ -763,7 +763,14 @@ mod tests {
             embedding_provider: "bm25".to_string(),
             normalization: None,
             encryption: None,
+            id_policy: None,
+            quota: None,
+            scoring: None,
+            tenant_partition: None,
+            dedup: None,
+            chunking: None,
             storage_type: Some(crate::models::StorageType::Memory),
+            full_text: None,
         };
 
         // This would be tested with actual GPU context in integration tests
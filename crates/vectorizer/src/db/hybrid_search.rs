@@ -11,6 +11,7 @@
 
 use std::collections::HashMap;
 
+use serde::{Deserialize, Serialize};
 use tracing::{debug, info};
 
 use crate::error::{Result, VectorizerError};
@@ -44,17 +45,22 @@ impl Default for HybridSearchConfig {
     }
 }
 
-/// Hybrid scoring algorithms
-#[derive(Debug, Clone, Copy, PartialEq)]
+/// Hybrid scoring algorithms, spelled `rrf`, `weighted` and `alpha` in
+/// requests
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
 pub enum HybridScoringAlgorithm {
     /// Reciprocal Rank Fusion (RRF)
     /// Combines rankings using: RRF(d) = Σ 1/(k + rank(d))
+    #[default]
+    #[serde(rename = "rrf")]
     ReciprocalRankFusion,
     /// Weighted score combination
     /// Combines scores using: score = alpha * dense_score + (1-alpha) * sparse_score
+    #[serde(rename = "weighted")]
     WeightedCombination,
     /// Alpha blending
     /// Similar to weighted but with normalization
+    #[serde(rename = "alpha")]
     AlphaBlending,
 }
 
@@ -130,6 +136,17 @@ pub fn hybrid_search(
         config.dense_k, config.sparse_k, config.final_k, config.alpha, config.algorithm
     );
 
+    Ok(fuse(dense_results, sparse_results, config))
+}
+
+/// Fuse `dense_results` and `sparse_results` with `config`, best first.
+/// [`hybrid_search`] without the logging, for callers that fuse the same
+/// legs many times over (weight tuning).
+pub(crate) fn fuse(
+    dense_results: Vec<DenseSearchResult>,
+    sparse_results: Vec<SparseSearchResult>,
+    config: &HybridSearchConfig,
+) -> Vec<HybridSearchResult> {
     let results = match config.algorithm {
         HybridScoringAlgorithm::ReciprocalRankFusion => {
             reciprocal_rank_fusion(dense_results, sparse_results, config.alpha)
@@ -150,8 +167,7 @@ pub fn hybrid_search(
             .unwrap_or(std::cmp::Ordering::Equal)
     });
     sorted_results.truncate(config.final_k);
-
-    Ok(sorted_results)
+    sorted_results
 }
 
 /// Reciprocal Rank Fusion (RRF) algorithm
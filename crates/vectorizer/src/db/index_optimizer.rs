@@ -0,0 +1,293 @@
+//! Background HNSW compaction.
+//!
+//! hnsw_rs cannot unlink a point, so every delete and every update (which
+//! is a delete plus a re-insert) leaves a node behind in the graph. Those
+//! nodes still route traffic and still occupy slots in the search beam,
+//! but never appear in results — after deleting ~30% of a collection,
+//! searches visibly return fewer and worse neighbours.
+//!
+//! The [`IndexOptimizer`] wakes every `index_optimizer.interval_secs`,
+//! measures the stale-node share of every loaded CPU collection and
+//! calls [`Collection::compact_index`] on the ones past
+//! `fragmentation_threshold` (and past `min_stale_nodes`). Compaction
+//! rebuilds the graph from live vectors off to the side and swaps it in;
+//! searches keep running against the old graph meanwhile. Collections
+//! that are only on disk are not loaded just to be checked.
+//!
+//! Shutdown and metrics follow [`TtlReaper`](super::TtlReaper): an
+//! `Arc<AtomicBool>` flag and an injected [`MetricsSink`]. Per-collection
+//! status is kept on the collection itself and surfaced through
+//! [`Collection::index_optimization_status`]; each compaction is also an
+//! [`INDEX_OPTIMIZATION`] job, whose result is the number of nodes
+//! reclaimed.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+use vectorizer_core::metrics_sink::MetricsSink;
+
+use super::Collection;
+use crate::background_tasks::{self, TASKS};
+use crate::config::IndexOptimizerConfig;
+use crate::db::VectorStore;
+use crate::error::Result;
+use crate::jobs::{INDEX_OPTIMIZATION, JOBS};
+
+/// Node counts of a collection's in-memory HNSW graph.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexFragmentation {
+    /// Nodes in the graph, stale ones included.
+    pub graph_nodes: usize,
+    /// Nodes that still belong to a live vector.
+    pub live_nodes: usize,
+}
+
+impl IndexFragmentation {
+    /// Nodes left behind by deletes and updates.
+    pub fn stale_nodes(&self) -> usize {
+        self.graph_nodes.saturating_sub(self.live_nodes)
+    }
+
+    /// Stale share of the graph in `[0, 1]`; `0.0` for an empty graph.
+    pub fn ratio(&self) -> f64 {
+        if self.graph_nodes == 0 {
+            return 0.0;
+        }
+        self.stale_nodes() as f64 / self.graph_nodes as f64
+    }
+}
+
+/// Outcome of a collection's most recent compactions.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IndexOptimizationStatus {
+    /// A compaction is running right now.
+    pub running: bool,
+    /// Compactions finished (successfully or not) since the collection
+    /// was loaded.
+    pub runs: u64,
+    /// When the most recent compaction started.
+    pub last_started_at: Option<DateTime<Utc>>,
+    /// Wall-clock duration of the most recent compaction.
+    pub last_duration_ms: Option<u64>,
+    /// Stale nodes the most recent successful compaction reclaimed.
+    pub last_reclaimed_nodes: Option<usize>,
+    /// Error of the most recent compaction, cleared on success.
+    pub last_error: Option<String>,
+}
+
+impl IndexOptimizationStatus {
+    pub(crate) fn start(&mut self, at: DateTime<Utc>) {
+        self.running = true;
+        self.last_started_at = Some(at);
+    }
+
+    pub(crate) fn finish(&mut self, elapsed: Duration, result: &Result<usize>) {
+        self.running = false;
+        self.runs += 1;
+        self.last_duration_ms = Some(elapsed.as_millis() as u64);
+        match result {
+            Ok(reclaimed) => {
+                self.last_reclaimed_nodes = Some(*reclaimed);
+                self.last_error = None;
+            }
+            Err(e) => self.last_error = Some(e.to_string()),
+        }
+    }
+}
+
+/// A handle to the running optimizer task.
+///
+/// Dropping this handle does NOT stop the task — call
+/// [`IndexOptimizer::stop`] first.
+pub struct IndexOptimizer {
+    /// Shutdown flag. Set to `true` to stop the loop.
+    pub shutdown: Arc<AtomicBool>,
+}
+
+impl IndexOptimizer {
+    /// Spawn the optimizer loop. Each sweep runs on the blocking pool, so
+    /// a long rebuild never stalls the async runtime.
+    pub fn spawn(
+        store: Arc<VectorStore>,
+        config: IndexOptimizerConfig,
+        metrics: Arc<dyn MetricsSink>,
+    ) -> Self {
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let shutdown_clone = shutdown.clone();
+
+        tokio::spawn(async move {
+            let interval = Duration::from_secs(config.interval_secs);
+            let task = TASKS.register(background_tasks::COMPACTION);
+            info!(
+                "Index optimizer started (interval {}s, threshold {:.0}%, min {} stale nodes)",
+                config.interval_secs,
+                config.fragmentation_threshold * 100.0,
+                config.min_stale_nodes
+            );
+
+            loop {
+                task.schedule(interval);
+                tokio::time::sleep(interval).await;
+
+                if shutdown_clone.load(Ordering::Relaxed) {
+                    info!("Index optimizer shutting down");
+                    break;
+                }
+                if task.is_paused() {
+                    debug!("Index optimizer paused, skipping sweep");
+                    continue;
+                }
+
+                let store = store.clone();
+                let config = config.clone();
+                let metrics = metrics.clone();
+                task.start();
+                let sweep = tokio::task::spawn_blocking(move || {
+                    Self::sweep(&store, &config, metrics.as_ref())
+                });
+                let result = sweep.await;
+                task.finish(&result);
+                if let Err(e) = result {
+                    warn!("Index optimizer sweep panicked: {}", e);
+                }
+            }
+            task.stop();
+        });
+
+        Self { shutdown }
+    }
+
+    /// Signal the optimizer task to stop on the next wake-up.
+    pub fn stop(&self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+    }
+
+    /// Run one pass over every loaded CPU collection, compacting those
+    /// past the configured thresholds. Returns the names of the
+    /// collections that were compacted successfully.
+    pub fn sweep(
+        store: &VectorStore,
+        config: &IndexOptimizerConfig,
+        metrics: &dyn MetricsSink,
+    ) -> Vec<String> {
+        let mut compacted = Vec::new();
+
+        for collection in store.loaded_cpu_collections() {
+            let name = collection.name().to_string();
+            let fragmentation = collection.index_fragmentation();
+            metrics.index_fragmentation(&name, fragmentation.ratio());
+
+            if fragmentation.ratio() < config.fragmentation_threshold
+                || fragmentation.stale_nodes() < config.min_stale_nodes
+            {
+                debug!(
+                    "Index optimizer: '{}' at {:.1}% stale ({} nodes), skipping",
+                    name,
+                    fragmentation.ratio() * 100.0,
+                    fragmentation.stale_nodes()
+                );
+                continue;
+            }
+
+            let started = Instant::now();
+            let job = JOBS.start(INDEX_OPTIMIZATION, &name);
+            job.set_total(fragmentation.stale_nodes() as u64);
+            let result = job.run(|job| {
+                collection
+                    .compact_index()
+                    .inspect(|reclaimed| job.advance(*reclaimed as u64))
+            });
+            let seconds = started.elapsed().as_secs_f64();
+            match result {
+                Ok(reclaimed) => {
+                    metrics.index_compaction(&name, true, seconds, reclaimed as f64);
+                    metrics.index_fragmentation(&name, collection.index_fragmentation().ratio());
+                    compacted.push(name);
+                }
+                Err(_) => metrics.index_compaction(&name, false, seconds, 0.0),
+            }
+        }
+
+        compacted
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use vectorizer_core::metrics_sink::NoopMetricsSink;
+
+    use super::*;
+    use crate::models::{CollectionConfig, DistanceMetric, QuantizationConfig, Vector};
+
+    fn seed(store: &VectorStore, name: &str, count: usize) {
+        let config = CollectionConfig {
+            dimension: 8,
+            metric: DistanceMetric::Euclidean,
+            quantization: QuantizationConfig::None,
+            normalization: None,
+            ..Default::default()
+        };
+        store.create_collection(name, config).unwrap();
+        let vectors = (0..count)
+            .map(|i| {
+                let data = (0..8).map(|d| ((i * 8 + d) as f32 * 0.37).sin()).collect();
+                Vector::new(format!("v{i}"), data)
+            })
+            .collect();
+        store.insert(name, vectors).unwrap();
+    }
+
+    #[test]
+    fn test_sweep_compacts_fragmented_collections_only() {
+        let store = VectorStore::new();
+        seed(&store, "churned", 200);
+        seed(&store, "steady", 200);
+        for i in 0..80 {
+            store.delete("churned", &format!("v{i}")).unwrap();
+        }
+        store.delete("steady", "v0").unwrap();
+
+        let before = store
+            .get_collection("churned")
+            .unwrap()
+            .index_fragmentation()
+            .unwrap();
+        assert_eq!(before.graph_nodes, 200);
+        assert_eq!(before.stale_nodes(), 80);
+
+        let config = IndexOptimizerConfig {
+            min_stale_nodes: 10,
+            ..Default::default()
+        };
+        let compacted = IndexOptimizer::sweep(&store, &config, &NoopMetricsSink);
+        assert_eq!(compacted, vec!["churned".to_string()]);
+
+        let coll = store.get_collection("churned").unwrap();
+        let after = coll.index_fragmentation().unwrap();
+        assert_eq!(after.graph_nodes, 120);
+        assert_eq!(after.ratio(), 0.0);
+
+        let status = coll.index_optimization_status().unwrap();
+        assert_eq!(status.runs, 1);
+        assert_eq!(status.last_reclaimed_nodes, Some(80));
+        assert!(!status.running);
+
+        let job = crate::jobs::JOBS
+            .list()
+            .into_iter()
+            .find(|job| job.kind == INDEX_OPTIMIZATION && job.collection == "churned")
+            .unwrap();
+        assert_eq!(job.state, crate::jobs::JobState::Completed);
+        assert_eq!(job.result, Some(serde_json::json!(80)));
+
+        // Every surviving vector is still its own nearest neighbour.
+        let probe = coll.get_vector("v150").unwrap();
+        let hits = coll.search(&probe.data, 1).unwrap();
+        assert_eq!(hits[0].id, "v150");
+    }
+}
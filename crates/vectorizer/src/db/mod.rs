@@ -27,13 +27,20 @@
 pub mod async_indexing;
 pub mod auto_save;
 pub mod backpressure;
+pub mod backup_scheduler;
+pub mod change_feed;
 mod collection;
 pub mod collection_normalization;
+pub mod disk_hnsw;
 pub mod graph;
+pub mod graph_entity_extraction;
 pub mod graph_relationship_discovery;
 pub mod hybrid_search;
+pub mod index_optimizer;
 pub mod payload_index;
+pub mod search_deadline;
 pub mod storage_backend;
+pub mod text_tokenizer;
 pub mod ttl_reaper;
 pub mod upsert_queue;
 
@@ -47,17 +54,32 @@ pub mod distributed_sharded_collection;
 pub mod multi_tenancy;
 pub mod optimized_hnsw;
 pub mod raft;
+pub mod read_through;
+pub mod residency;
 pub mod shard_topology;
 pub mod sharded_collection;
 pub mod sharding;
+pub mod startup_progress;
 pub mod vector_store;
 mod wal_integration;
+pub mod write_fence;
 
 pub use async_indexing::{AsyncIndexManager, IndexBuildProgress, IndexBuildStatus};
 pub use auto_save::AutoSaveManager;
 pub use backpressure::{BackpressureGuard, BackpressurePermit};
-pub use collection::{Collection, VectorCountSample};
+pub use backup_scheduler::{BackupJobStatus, BackupScheduler, CronSchedule};
+pub use change_feed::{ChangeCursor, ChangeEvent, ChangeFeed, ChangeKind, ResumeGap};
+pub use collection::{
+    BINARY_DEFAULT_OVERSAMPLING, BatchQuery, Collection, CollectionOverrides, CollectionPatch,
+    DEFAULT_PROFILE_K, DEFAULT_PROFILE_SAMPLE_SIZE, FeedbackEvent, FeedbackLabel, FeedbackSummary,
+    FeedbackVotes, HybridJudgment, HybridTuning, HybridTuningSummary, KvCheck, KvEntry,
+    KvNamespace, KvOp, KvWriteResult, LatencySummary, MAX_FEEDBACK_EVENTS, MAX_HYBRID_JUDGMENTS,
+    MAX_KV_ENTRIES, MAX_KV_KEY_BYTES, MAX_KV_TRANSACTION_OPS, MAX_KV_VALUE_BYTES,
+    MAX_PROFILE_SAMPLE_SIZE, MetadataKv, ProfileTrigger, RecallProfile, RelevanceFeedback,
+    ResultFeedback, TunedHybridWeights, VectorCountSample,
+};
 pub use collection_normalization::CollectionNormalizationHelper;
+pub use disk_hnsw::DiskHnswIndex;
 pub use distributed_sharded_collection::DistributedShardedCollection;
 #[cfg(feature = "hive-gpu")]
 pub use gpu_detection::{GpuBackendType, GpuDetector, GpuInfo};
@@ -67,15 +89,32 @@ pub use graph_relationship_discovery::{
     discover_edges_for_node, discover_similarity_relationships,
 };
 pub use hybrid_search::{HybridScoringAlgorithm, HybridSearchConfig, HybridSearchResult};
+pub use index_optimizer::{IndexFragmentation, IndexOptimizationStatus, IndexOptimizer};
 pub use multi_tenancy::{
     MultiTenancyManager, TenantId, TenantMetadata, TenantOperation, TenantQuotas, TenantUsage,
     TenantUsageUpdate,
 };
-pub use optimized_hnsw::{OptimizedHnswConfig, OptimizedHnswIndex};
+pub use optimized_hnsw::{
+    OptimizedHnswConfig, OptimizedHnswIndex, default_ef_search, set_default_ef_search,
+    similarity_to_distance, with_ef_search,
+};
 pub use raft::{
     LogEntry, LogIndex, NodeId, RaftConfig, RaftNode, RaftRole, RaftState, RaftStateMachine, Term,
 };
+pub use read_through::{ReadThrough, ReadThroughEvictor, ReadThroughStatus};
+pub use residency::{
+    LoadedCollection, Residency, ResidencyEvictor, ResidencyStatus, ResidentCollection,
+};
+pub use search_deadline::{SearchDeadline, SearchOutcome, SearchStats};
 pub use sharding::{ConsistentHashRing, ShardId, ShardRebalancer, ShardRouter};
+pub use startup_progress::{
+    CollectionLoadProgress, CollectionLoadState, LoadSource, LoadStateCounts, StartupPhase,
+    StartupProgress, StartupProgressReport,
+};
 pub use ttl_reaper::{DEFAULT_REAPER_INTERVAL_SECS, TtlReaper};
 pub use upsert_queue::{AdmissionError, AdmissionStatus, UpsertQueue, UpsertTicket};
-pub use vector_store::{CollectionType, VectorStore};
+pub use vector_store::{
+    CLONE_BATCH_SIZE, CLONE_TEXT_FIELDS, CloneReport, CollectionType, PointInTimeRestore,
+    QUOTA_SUMMARY_FIELD, VectorStore,
+};
+pub use write_fence::{WriteFence, WriteGuard};
@@ -12,39 +12,80 @@
 // phase4_enforce-public-api-docs.
 #![allow(missing_docs)]
 
+use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use hnsw_rs::libext::file_dump_f32;
 use hnsw_rs::prelude::*;
-use parking_lot::RwLock;
+use parking_lot::{RwLock, RwLockReadGuard};
+use rayon::prelude::*;
 use tracing::{debug, info};
 
+use crate::db::disk_hnsw::HnswGraph;
+use crate::db::search_deadline::{SearchDeadline, SearchStats};
 use crate::error::{Result, VectorizerError};
 use crate::models::DistanceMetric;
+use crate::simd;
+use crate::storage::mmap::MmapVectorStorage;
 
 /// Runtime-dispatching HNSW distance so a collection is ranked by its
 /// configured [`DistanceMetric`] instead of a hardcoded cosine distance.
-/// Cosine and Euclidean delegate to hnsw_rs's SIMD-optimized `DistCosine` /
-/// `DistL2`. hnsw_rs asserts every distance is non-negative, which rules out a
-/// raw `-dot`; dot-product therefore maps the inner product to a
-/// strictly-decreasing, non-negative distance `sigmoid(-dot) = 1/(1+e^dot)`
-/// (larger dot -> smaller distance), so hnsw's ascending-distance ordering
-/// still ranks the highest inner product first. The transform is monotonic, so
-/// top-k ordering is exact; it saturates for very large magnitudes but that is
-/// harmless for ranking.
+/// Every metric is computed with the [`crate::simd`] kernels picked for the
+/// running CPU (AVX-512, AVX2, NEON, ...); hnsw_rs's own `DistCosine` /
+/// `DistL2` are plain scalar loops unless it is built with `simdeez_f`, and
+/// this is the innermost call of every graph walk. Cosine and Euclidean keep
+/// the values those produce: `1 - cosine` (0 for a zero vector, never
+/// negative) and the L2 distance. hnsw_rs asserts every distance is
+/// non-negative, which rules out a raw `-dot`; dot-product therefore maps the
+/// inner product to a strictly-decreasing, non-negative distance
+/// `sigmoid(-dot) = 1/(1+e^dot)` (larger dot -> smaller distance), so hnsw's
+/// ascending-distance ordering still ranks the highest inner product first.
+/// The transform is monotonic, so top-k ordering is exact; it saturates for
+/// very large magnitudes but that is harmless for ranking.
 #[derive(Debug, Clone, Copy)]
 struct MetricDistance {
     metric: DistanceMetric,
 }
 
+thread_local! {
+    /// Distance evaluations made on this thread, read by [`count_visited`].
+    static DISTANCE_EVALS: Cell<u64> = const { Cell::new(0) };
+    /// Search beam set by [`with_ef_search`] for this thread; `0` when
+    /// none is set.
+    static EF_SEARCH_OVERRIDE: Cell<usize> = const { Cell::new(0) };
+    /// Query of the [`Graph::Mapped`] search running on this thread.
+    static MAPPED_QUERY: RefCell<Vec<f32>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Run `f` and count the [`MetricDistance`] evaluations it made on this
+/// thread. A graph walk scores every node it visits exactly once, so for
+/// a search this is the number of candidates visited.
+pub(crate) fn count_visited<T>(f: impl FnOnce() -> T) -> (T, u64) {
+    let before = DISTANCE_EVALS.with(Cell::get);
+    let out = f();
+    (out, DISTANCE_EVALS.with(Cell::get).wrapping_sub(before))
+}
+
 impl Distance<f32> for MetricDistance {
     fn eval(&self, a: &[f32], b: &[f32]) -> f32 {
+        DISTANCE_EVALS.with(|evals| evals.set(evals.get().wrapping_add(1)));
         match self.metric {
-            DistanceMetric::Cosine => DistCosine {}.eval(a, b),
-            DistanceMetric::Euclidean => DistL2 {}.eval(a, b),
+            DistanceMetric::Cosine => {
+                // Stored vectors aren't guaranteed unit-length, so this is
+                // the full cosine rather than `simd::cosine_similarity`.
+                let norms = simd::l2_norm(a) * simd::l2_norm(b);
+                if norms > 0.0 {
+                    (1.0 - simd::dot_product(a, b) / norms).max(0.0)
+                } else {
+                    0.0
+                }
+            }
+            DistanceMetric::Euclidean => simd::euclidean_distance(a, b),
             DistanceMetric::DotProduct => {
-                let dot: f32 = a.iter().zip(b.iter()).map(|(x, y)| x * y).sum();
+                let dot = simd::dot_product(a, b);
                 // sigmoid(-dot): non-negative (hnsw_rs invariant) and strictly
                 // decreasing in the inner product.
                 1.0 / (1.0 + dot.exp())
@@ -53,21 +94,233 @@ impl Distance<f32> for MetricDistance {
     }
 }
 
+/// Distance between `a` and `b` on the same scale the in-memory graph uses,
+/// so an exported graph (see [`crate::db::disk_hnsw`]) is walked and scored
+/// exactly like the live index.
+pub(crate) fn metric_distance(metric: DistanceMetric, a: &[f32], b: &[f32]) -> f32 {
+    MetricDistance { metric }.eval(a, b)
+}
+
+/// Handle of the query in a [`Graph::Mapped`] search; every other handle
+/// is the slot of a point's vector in the index's file.
+const QUERY_SLOT: u64 = u64::MAX;
+
+/// [`MetricDistance`] for a graph whose points carry a one-element slot
+/// handle instead of their vector. Both vectors are read from the mapped
+/// file on every evaluation, so the graph itself holds no vector data.
+struct MappedDistance {
+    metric: DistanceMetric,
+    vectors: Arc<RwLock<MmapVectorStorage>>,
+}
+
+impl Distance<u64> for MappedDistance {
+    fn eval(&self, a: &[u64], b: &[u64]) -> f32 {
+        let vectors = self.vectors.read();
+        MAPPED_QUERY.with_borrow(|query| {
+            let resolve = |handle: &[u64]| match handle.first() {
+                Some(&QUERY_SLOT) => Some(query.as_slice()),
+                Some(&slot) => vectors.vector(slot as usize),
+                None => None,
+            };
+            match (resolve(a), resolve(b)) {
+                (Some(a), Some(b)) => metric_distance(self.metric, a, b),
+                // A point's vector is always appended before it is inserted
+                _ => f32::MAX,
+            }
+        })
+    }
+}
+
+/// The graph of an [`OptimizedHnswIndex`]. hnsw_rs keeps the data of
+/// every point inside the graph, so a mapped index gives its points
+/// their slot in the vector file instead of a copy of the vector.
+enum Graph {
+    Memory(Hnsw<'static, f32, MetricDistance>),
+    Mapped(Hnsw<'static, u64, MappedDistance>),
+}
+
+impl Graph {
+    /// Insert the point `slot`, whose vector is `data`.
+    fn insert(&self, data: &[f32], slot: usize) {
+        match self {
+            Self::Memory(hnsw) => hnsw.insert((data, slot)),
+            Self::Mapped(hnsw) => hnsw.insert((&[slot as u64], slot)),
+        }
+    }
+
+    fn search(&self, query: &[f32], k: usize, ef_search: usize) -> Vec<Neighbour> {
+        match self {
+            Self::Memory(hnsw) => hnsw.search(query, k, ef_search),
+            Self::Mapped(hnsw) => {
+                MAPPED_QUERY.with_borrow_mut(|q| {
+                    q.clear();
+                    q.extend_from_slice(query);
+                });
+                hnsw.search(&[QUERY_SLOT], k, ef_search)
+            }
+        }
+    }
+
+    /// The `k` nearest points that `live` resolves, out of a graph of
+    /// `graph_len` points of which `live_len` are live. Removed and
+    /// re-inserted points stay linked in the graph and can crowd live
+    /// ones out of a `k`-wide walk, so the walk starts as wide as the
+    /// share of dead points suggests and doubles until `k` live hits
+    /// come back or the walk reached every point it can. Fewer than `k`
+    /// hits then means some live points are not reachable from the entry
+    /// point. The stats report the last beam and the candidates visited
+    /// over every walk.
+    fn search_live<T>(
+        &self,
+        query: &[f32],
+        k: usize,
+        live_len: usize,
+        graph_len: usize,
+        pinned: Option<usize>,
+        live: impl Fn(&Neighbour) -> Option<T>,
+    ) -> (Vec<T>, SearchStats) {
+        let mut wanted = if live_len == 0 || graph_len <= live_len {
+            k
+        } else {
+            k.saturating_mul(graph_len)
+                .div_ceil(live_len)
+                .min(graph_len)
+                .max(k)
+        };
+        let mut candidates_visited = 0;
+        loop {
+            let ef_search = ef_search_for(pinned, live_len, wanted);
+            let (found, visited) = count_visited(|| self.search(query, wanted, ef_search));
+            candidates_visited += visited;
+            let exhausted = found.len() < wanted || wanted >= graph_len;
+            let hits: Vec<T> = found.iter().filter_map(&live).take(k).collect();
+            if hits.len() >= k || exhausted {
+                return (
+                    hits,
+                    SearchStats {
+                        ef_search,
+                        candidates_visited,
+                    },
+                );
+            }
+            wanted = wanted.saturating_mul(2).min(graph_len);
+        }
+    }
+
+    /// Every point as `(slot, level, neighbours per layer, vector)`, with
+    /// the vectors of a mapped graph read from `mapped`.
+    #[allow(clippy::type_complexity)]
+    fn points(
+        &self,
+        mapped: Option<&MmapVectorStorage>,
+    ) -> Vec<(usize, usize, Vec<Vec<Neighbour>>, Vec<f32>)> {
+        fn collect<T: Clone + Send + Sync, D: Distance<T> + Send + Sync>(
+            hnsw: &Hnsw<'static, T, D>,
+            vector: impl Fn(&Point<'static, T>) -> Vec<f32>,
+        ) -> Vec<(usize, usize, Vec<Vec<Neighbour>>, Vec<f32>)> {
+            hnsw.get_point_indexation()
+                .into_iter()
+                .map(|point| {
+                    (
+                        point.get_origin_id(),
+                        point.get_point_id().0 as usize,
+                        point.get_neighborhood_id(),
+                        vector(&point),
+                    )
+                })
+                .collect()
+        }
+        match self {
+            Self::Memory(hnsw) => collect(hnsw, |point| point.get_v().to_vec()),
+            Self::Mapped(hnsw) => collect(hnsw, |point| {
+                mapped
+                    .and_then(|vectors| vectors.vector(point.get_origin_id()))
+                    .map(<[f32]>::to_vec)
+                    .unwrap_or_default()
+            }),
+        }
+    }
+}
+
 /// Convert an HNSW distance (as produced by [`MetricDistance::eval`] for the
 /// given metric) into a higher-is-more-similar score.
-fn distance_to_similarity(metric: DistanceMetric, distance: f32) -> f32 {
+pub(crate) fn distance_to_similarity(metric: DistanceMetric, distance: f32) -> f32 {
     match metric {
-        // `DistCosine` returns `1 - cosine_similarity`.
+        // distance is `1 - cosine_similarity`.
         DistanceMetric::Cosine => 1.0 - distance,
         // distance is `sigmoid(-dot)`, so `1 - distance = sigmoid(dot)` is
         // monotonically increasing in the inner product.
         DistanceMetric::DotProduct => 1.0 - distance,
-        // `DistL2` returns the Euclidean distance; map to (0, 1],
+        // distance is the Euclidean distance; map to (0, 1],
         // monotonically decreasing so closer vectors score higher.
         DistanceMetric::Euclidean => 1.0 / (1.0 + distance.max(0.0)),
     }
 }
 
+/// Inverse of [`distance_to_similarity`]: the metric distance behind a
+/// similarity score.
+pub fn similarity_to_distance(metric: DistanceMetric, similarity: f32) -> f32 {
+    match metric {
+        DistanceMetric::Cosine | DistanceMetric::DotProduct => 1.0 - similarity,
+        DistanceMetric::Euclidean => 1.0 / similarity - 1.0,
+    }
+}
+
+/// Search beam pinned through `tuning.ef_search` (`PATCH /admin/config`);
+/// `0` keeps the adaptive beam.
+static DEFAULT_EF_SEARCH: AtomicUsize = AtomicUsize::new(0);
+
+/// Pin the search beam of every HNSW query, in memory and on disk, to
+/// `ef_search` (widened to `k` when a query asks for more), or go back
+/// to the adaptive beam with `None`. Takes effect on the next query.
+pub fn set_default_ef_search(ef_search: Option<usize>) {
+    DEFAULT_EF_SEARCH.store(ef_search.unwrap_or(0), Ordering::Relaxed);
+}
+
+/// The search beam pinned by [`with_ef_search`] on this thread, else by
+/// [`set_default_ef_search`], if any.
+pub fn default_ef_search() -> Option<usize> {
+    match EF_SEARCH_OVERRIDE.with(Cell::get) {
+        0 => match DEFAULT_EF_SEARCH.load(Ordering::Relaxed) {
+            0 => None,
+            ef_search => Some(ef_search),
+        },
+        ef_search => Some(ef_search),
+    }
+}
+
+/// Run `f` with the beam of the HNSW queries it makes on this thread
+/// pinned to `ef_search` (widened to `k` as usual), whatever the global
+/// setting. Used to sweep `ef_search` without touching other queries.
+/// Binary-quantized collections do not walk the graph and ignore it.
+pub fn with_ef_search<T>(ef_search: usize, f: impl FnOnce() -> T) -> T {
+    let previous = EF_SEARCH_OVERRIDE.with(|cell| cell.replace(ef_search));
+    let out = f();
+    EF_SEARCH_OVERRIDE.with(|cell| cell.set(previous));
+    out
+}
+
+/// Whether [`with_ef_search`] pinned the beam on this thread.
+pub(crate) fn ef_search_overridden() -> bool {
+    EF_SEARCH_OVERRIDE.with(Cell::get) != 0
+}
+
+/// Search beam for a `k`-NN query over `vector_count` vectors: the
+/// `pinned` beam when one is set, otherwise wide enough to find every
+/// vector in a tiny index and `max(2k, 64)` beyond that. The pinned
+/// beam is looked up by the caller so searches running on other threads
+/// than the caller's still see it.
+fn ef_search_for(pinned: Option<usize>, vector_count: usize, k: usize) -> usize {
+    if let Some(ef_search) = pinned {
+        return ef_search.max(k);
+    }
+    if vector_count < 10 {
+        std::cmp::max(vector_count * 2, k * 3)
+    } else {
+        std::cmp::max(k * 2, 64)
+    }
+}
+
 /// Optimized HNSW configuration
 #[derive(Debug, Clone, Copy)]
 pub struct OptimizedHnswConfig {
@@ -104,14 +357,133 @@ impl Default for OptimizedHnswConfig {
     }
 }
 
+/// Live IDs of an index, the graph point each one sits at and their
+/// vectors, taken by [`OptimizedHnswIndex::live_points`].
+pub struct LivePoints {
+    id_map: Arc<RwLock<HashMap<String, usize>>>,
+    points: HashMap<String, usize>,
+    vectors: SnapshotVectors,
+}
+
+/// Vectors of a [`LivePoints`] snapshot: copied out of RAM, or left in
+/// the mapped file, whose slots are never rewritten.
+enum SnapshotVectors {
+    Memory(HashMap<String, Vec<f32>>),
+    Mapped(Arc<RwLock<MmapVectorStorage>>),
+}
+
+impl LivePoints {
+    /// Number of live vectors in the snapshot
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Whether the snapshot has no live vectors
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Pass the snapshot's vectors to `f` in insertion order, at most
+    /// `batch_size` at a time, so a mapped index is never copied out of
+    /// its file whole.
+    pub fn for_each_batch(
+        &self,
+        batch_size: usize,
+        mut f: impl FnMut(Vec<(String, Vec<f32>)>) -> Result<()>,
+    ) -> Result<()> {
+        let mut order: Vec<(&String, usize)> =
+            self.points.iter().map(|(id, point)| (id, *point)).collect();
+        order.sort_unstable_by_key(|(_, point)| *point);
+        for chunk in order.chunks(batch_size.max(1)) {
+            let batch = match &self.vectors {
+                SnapshotVectors::Memory(vectors) => chunk
+                    .iter()
+                    .filter_map(|(id, _)| Some(((*id).clone(), vectors.get(*id)?.clone())))
+                    .collect(),
+                SnapshotVectors::Mapped(vectors) => {
+                    let vectors = vectors.read();
+                    chunk
+                        .iter()
+                        .filter_map(|(id, slot)| {
+                            Some(((*id).clone(), vectors.vector(*slot)?.to_vec()))
+                        })
+                        .collect()
+                }
+            };
+            f(batch)?;
+        }
+        Ok(())
+    }
+}
+
+/// Read access to the live vectors of an [`OptimizedHnswIndex`], in RAM
+/// or in its mapped file, lent out by [`OptimizedHnswIndex::with_vectors`].
+pub struct IndexVectors<'a> {
+    id_map: RwLockReadGuard<'a, HashMap<String, usize>>,
+    store: StoreGuard<'a>,
+}
+
+enum StoreGuard<'a> {
+    Memory(RwLockReadGuard<'a, HashMap<String, Vec<f32>>>),
+    Mapped(RwLockReadGuard<'a, MmapVectorStorage>),
+}
+
+impl IndexVectors<'_> {
+    /// Number of live vectors
+    pub fn len(&self) -> usize {
+        self.id_map.len()
+    }
+
+    /// Whether there are no live vectors
+    pub fn is_empty(&self) -> bool {
+        self.id_map.is_empty()
+    }
+
+    /// The vector of `id`, if it is live
+    pub fn get(&self, id: &str) -> Option<&[f32]> {
+        self.at(id, *self.id_map.get(id)?)
+    }
+
+    /// Every live vector, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &[f32])> {
+        self.id_map
+            .iter()
+            .filter_map(|(id, slot)| Some((id, self.at(id, *slot)?)))
+    }
+
+    fn par_iter(&self) -> impl ParallelIterator<Item = (&String, &[f32])> {
+        self.id_map
+            .par_iter()
+            .filter_map(|(id, slot)| Some((id, self.at(id, *slot)?)))
+    }
+
+    fn at(&self, id: &str, slot: usize) -> Option<&[f32]> {
+        match &self.store {
+            StoreGuard::Memory(vectors) => vectors.get(id).map(Vec::as_slice),
+            StoreGuard::Mapped(vectors) => vectors.vector(slot),
+        }
+    }
+}
+
+/// What changed in an index since a [`LivePoints`] snapshot.
+#[derive(Debug, Default)]
+pub struct IndexDelta {
+    /// IDs added or re-written since, with their current vectors
+    pub upserted: Vec<(String, Vec<f32>)>,
+    /// IDs removed since
+    pub removed: Vec<String>,
+}
+
 /// Optimized HNSW index with batch operations
 pub struct OptimizedHnswIndex {
     /// The underlying HNSW index
-    hnsw: Arc<RwLock<Hnsw<'static, f32, MetricDistance>>>,
+    hnsw: Arc<RwLock<Graph>>,
     /// Configuration
     config: OptimizedHnswConfig,
-    /// Vector storage (needed for HNSW operations)
+    /// Vector storage (needed for HNSW operations); empty when mapped
     vectors: Arc<RwLock<HashMap<String, Vec<f32>>>>,
+    /// Vector file of a [`Self::new_mapped`] index, indexed by internal ID
+    mapped: Option<Arc<RwLock<MmapVectorStorage>>>,
     /// ID to internal ID mapping
     id_map: Arc<RwLock<HashMap<String, usize>>>,
     /// Dimension
@@ -140,9 +512,42 @@ impl OptimizedHnswIndex {
         );
 
         Ok(Self {
-            hnsw: Arc::new(RwLock::new(hnsw)),
+            hnsw: Arc::new(RwLock::new(Graph::Memory(hnsw))),
             config,
             vectors: Arc::new(RwLock::new(HashMap::with_capacity(config.initial_capacity))),
+            mapped: None,
+            id_map: Arc::new(RwLock::new(HashMap::with_capacity(config.initial_capacity))),
+            dimension,
+            batch_buffer: Arc::new(RwLock::new(Vec::with_capacity(config.batch_size))),
+            next_id: Arc::new(RwLock::new(0)),
+        })
+    }
+
+    /// Create an index that keeps its vectors in a scratch file under
+    /// `dir` instead of RAM. Graph points carry only their slot in the
+    /// file and distances read the vectors from the mapping, so the
+    /// resident cost per vector is its graph links. The file is deleted
+    /// with the index.
+    pub fn new_mapped(dimension: usize, config: OptimizedHnswConfig, dir: &Path) -> Result<Self> {
+        let nb_layer = 16.min((config.initial_capacity as f32).ln() as usize);
+        let vectors = Arc::new(RwLock::new(MmapVectorStorage::scratch(dir, dimension)?));
+
+        let hnsw = Hnsw::<u64, MappedDistance>::new(
+            config.max_connections,
+            config.initial_capacity,
+            nb_layer,
+            config.ef_construction,
+            MappedDistance {
+                metric: config.distance_metric,
+                vectors: vectors.clone(),
+            },
+        );
+
+        Ok(Self {
+            hnsw: Arc::new(RwLock::new(Graph::Mapped(hnsw))),
+            config,
+            vectors: Arc::new(RwLock::new(HashMap::new())),
+            mapped: Some(vectors),
             id_map: Arc::new(RwLock::new(HashMap::with_capacity(config.initial_capacity))),
             dimension,
             batch_buffer: Arc::new(RwLock::new(Vec::with_capacity(config.batch_size))),
@@ -150,6 +555,22 @@ impl OptimizedHnswIndex {
         })
     }
 
+    /// Whether the vectors live in a mapped file ([`Self::new_mapped`])
+    pub fn is_mapped(&self) -> bool {
+        self.mapped.is_some()
+    }
+
+    /// Read lock on the live vectors, wherever they are kept. Takes
+    /// `id_map` before the vectors, like every other reader.
+    fn read_vectors(&self) -> IndexVectors<'_> {
+        let id_map = self.id_map.read();
+        let store = match &self.mapped {
+            Some(vectors) => StoreGuard::Mapped(vectors.read()),
+            None => StoreGuard::Memory(self.vectors.read()),
+        };
+        IndexVectors { id_map, store }
+    }
+
     /// Add a single vector (buffered)
     pub fn add(&self, id: String, data: Vec<f32>) -> Result<()> {
         if data.len() != self.dimension {
@@ -196,25 +617,49 @@ impl OptimizedHnswIndex {
         Ok(())
     }
 
+    /// [`Self::batch_add`] for vectors produced one at a time, holding at
+    /// most one batch of them in memory.
+    pub fn batch_add_iter(
+        &self,
+        vectors: impl IntoIterator<Item = (String, Vec<f32>)>,
+    ) -> Result<()> {
+        let mut batch = Vec::with_capacity(self.config.batch_size);
+        for entry in vectors {
+            batch.push(entry);
+            if batch.len() >= self.config.batch_size {
+                self.batch_add(std::mem::take(&mut batch))?;
+            }
+        }
+        self.batch_add(batch)
+    }
+
     /// Insert a batch of vectors
     fn insert_batch(&self, batch: &[(String, Vec<f32>)]) -> Result<()> {
         let hnsw = self.hnsw.write();
-        let mut vectors = self.vectors.write();
         let mut id_map = self.id_map.write();
+        let mut vectors = self.vectors.write();
         let mut next_id = self.next_id.write();
 
         // Pre-allocate space
-        vectors.reserve(batch.len());
+        if self.mapped.is_none() {
+            vectors.reserve(batch.len());
+        }
 
         // Insert vectors
         for (id, data) in batch {
-            let internal_id = *next_id;
-            *next_id += 1;
-
-            vectors.insert(id.clone(), data.clone());
+            // A mapped index appends exactly one vector per point, so the
+            // slot it gets is also the next internal ID.
+            let internal_id = match &self.mapped {
+                Some(mapped) => mapped.write().append(data)?,
+                None => {
+                    vectors.insert(id.clone(), data.clone());
+                    *next_id
+                }
+            };
+            *next_id = internal_id + 1;
             id_map.insert(id.clone(), internal_id);
 
-            hnsw.insert((&data, internal_id));
+            hnsw.insert(data, internal_id);
         }
 
         Ok(())
@@ -245,6 +690,16 @@ impl OptimizedHnswIndex {
 
     /// Search for nearest neighbors
     pub fn search(&self, query: &[f32], k: usize) -> Result<Vec<(String, f32)>> {
+        Ok(self.search_with_stats(query, k)?.0)
+    }
+
+    /// [`Self::search`], plus the beam width it used and the number of
+    /// graph nodes it visited.
+    pub fn search_with_stats(
+        &self,
+        query: &[f32],
+        k: usize,
+    ) -> Result<(Vec<(String, f32)>, SearchStats)> {
         if query.len() != self.dimension {
             return Err(VectorizerError::DimensionMismatch {
                 expected: self.dimension,
@@ -257,26 +712,20 @@ impl OptimizedHnswIndex {
 
         let hnsw = self.hnsw.read();
         let id_map = self.id_map.read();
-        let vectors = self.vectors.read();
 
         // Create reverse mapping from internal ID to string ID
         let reverse_map: HashMap<usize, String> =
             id_map.iter().map(|(k, v)| (*v, k.clone())).collect();
 
-        // Adaptive ef_search based on index size
-        let vector_count = vectors.len();
-        let ef_search = if vector_count < 10 {
-            std::cmp::max(vector_count * 2, k * 3)
-        } else {
-            std::cmp::max(k * 2, 64)
-        };
-
-        let neighbors = hnsw.search(query, k, ef_search);
-
         // Convert internal IDs back to string IDs and convert distance to similarity
-        let results = neighbors
-            .into_iter()
-            .filter_map(|neighbor| {
+        let live_len = id_map.len();
+        let (results, stats) = hnsw.search_live(
+            query,
+            k,
+            live_len,
+            self.graph_len(),
+            default_ef_search(),
+            |neighbor| {
                 reverse_map.get(&neighbor.d_id).map(|id| {
                     // Metric-aware distance -> similarity so non-cosine
                     // collections report scores consistent with their metric.
@@ -284,10 +733,177 @@ impl OptimizedHnswIndex {
                         distance_to_similarity(self.config.distance_metric, neighbor.distance);
                     (id.clone(), similarity)
                 })
+            },
+        );
+        if results.len() >= k.min(live_len) {
+            return Ok((results, stats));
+        }
+        drop((hnsw, id_map));
+        Ok((self.exact_top_k(query, k, &SearchDeadline::NONE)?, stats))
+    }
+
+    /// Run several searches against one snapshot of the graph, in
+    /// parallel on the rayon pool. Each entry is `(query, k, deadline)`
+    /// and gets back its neighbours, whether its deadline had passed
+    /// before it started (in which case it is skipped), and the stats of
+    /// its graph walk (`None` when skipped).
+    ///
+    /// Compared to calling [`Self::search`] per query, the pending-insert
+    /// flush, the lock acquisitions and the internal-to-external id map —
+    /// which is rebuilt from scratch on every single search — happen once
+    /// for the whole batch.
+    pub fn search_batch_until(
+        &self,
+        queries: &[(&[f32], usize, SearchDeadline)],
+    ) -> Result<Vec<(Vec<(String, f32)>, bool, Option<SearchStats>)>> {
+        if let Some((query, _, _)) = queries.iter().find(|(q, _, _)| q.len() != self.dimension) {
+            return Err(VectorizerError::DimensionMismatch {
+                expected: self.dimension,
+                actual: query.len(),
+            });
+        }
+
+        self.flush()?;
+
+        let hnsw = self.hnsw.read();
+        let id_map = self.id_map.read();
+        let vector_count = id_map.len();
+        let graph_len = self.graph_len();
+        let reverse_map: HashMap<usize, &str> =
+            id_map.iter().map(|(k, v)| (*v, k.as_str())).collect();
+        let metric = self.config.distance_metric;
+        // The rayon workers do not see this thread's `with_ef_search`
+        let pinned = default_ef_search();
+
+        let results: Vec<_> = queries
+            .par_iter()
+            .map(|(query, k, deadline)| {
+                if deadline.expired() {
+                    return (Vec::new(), true, None);
+                }
+                let (neighbors, stats) =
+                    hnsw.search_live(query, *k, vector_count, graph_len, pinned, |neighbor| {
+                        reverse_map.get(&neighbor.d_id).map(|id| {
+                            (
+                                id.to_string(),
+                                distance_to_similarity(metric, neighbor.distance),
+                            )
+                        })
+                    });
+                (neighbors, false, Some(stats))
+            })
+            .collect();
+        drop((hnsw, id_map));
+
+        results
+            .into_iter()
+            .zip(queries)
+            .map(|((neighbors, skipped, stats), (query, k, deadline))| {
+                if skipped || neighbors.len() >= (*k).min(vector_count) {
+                    return Ok((neighbors, skipped, stats));
+                }
+                Ok((self.exact_top_k(query, *k, deadline)?, skipped, stats))
+            })
+            .collect()
+    }
+
+    /// The `k` best of an exact scan over every live vector, best first:
+    /// the answer for a search whose graph walk could not reach `k` live
+    /// points. Stops scoring once `deadline` passes.
+    fn exact_top_k(
+        &self,
+        query: &[f32],
+        k: usize,
+        deadline: &SearchDeadline,
+    ) -> Result<Vec<(String, f32)>> {
+        let (mut scored, _) = self.exact_scores_all_until(query, deadline)?;
+        scored.sort_unstable_by(|a, b| b.1.total_cmp(&a.1));
+        scored.truncate(k);
+        Ok(scored)
+    }
+
+    /// Score `ids` exactly against `query` using the full-precision vectors
+    /// held by the index, on the same similarity scale as [`search`].
+    ///
+    /// Used to rescore candidates pre-selected from a quantized
+    /// representation. IDs without a stored vector are skipped.
+    ///
+    /// [`search`]: OptimizedHnswIndex::search
+    pub fn exact_scores(&self, query: &[f32], ids: &[String]) -> Result<Vec<(String, f32)>> {
+        if query.len() != self.dimension {
+            return Err(VectorizerError::DimensionMismatch {
+                expected: self.dimension,
+                actual: query.len(),
+            });
+        }
+
+        self.flush()?;
+
+        let metric = self.config.distance_metric;
+        let vectors = self.read_vectors();
+        Ok(ids
+            .iter()
+            .filter_map(|id| {
+                vectors.get(id).map(|data| {
+                    let d = metric_distance(metric, query, data);
+                    (id.clone(), distance_to_similarity(metric, d))
+                })
+            })
+            .collect())
+    }
+
+    /// Score every stored vector exactly against `query`, on the same
+    /// similarity scale as [`search`]. The scan is spread over the rayon
+    /// pool; results are unordered.
+    ///
+    /// [`search`]: OptimizedHnswIndex::search
+    pub fn exact_scores_all(&self, query: &[f32]) -> Result<Vec<(String, f32)>> {
+        Ok(self.exact_scores_all_until(query, &SearchDeadline::NONE)?.0)
+    }
+
+    /// [`exact_scores_all`] that stops scoring once `deadline` passes and
+    /// returns the vectors scored so far. The flag is `true` when the
+    /// scan was cut short.
+    ///
+    /// [`exact_scores_all`]: OptimizedHnswIndex::exact_scores_all
+    pub fn exact_scores_all_until(
+        &self,
+        query: &[f32],
+        deadline: &SearchDeadline,
+    ) -> Result<(Vec<(String, f32)>, bool)> {
+        if query.len() != self.dimension {
+            return Err(VectorizerError::DimensionMismatch {
+                expected: self.dimension,
+                actual: query.len(),
+            });
+        }
+
+        self.flush()?;
+
+        let metric = self.config.distance_metric;
+        let vectors = self.read_vectors();
+        let scored: Vec<(String, f32)> = vectors
+            .par_iter()
+            .map(|(id, data)| {
+                if deadline.expired() {
+                    return None;
+                }
+                let d = metric_distance(metric, query, data);
+                Some((id.clone(), distance_to_similarity(metric, d)))
             })
+            .while_some()
             .collect();
+        let timed_out = scored.len() < vectors.len();
+        Ok((scored, timed_out))
+    }
 
-        Ok(results)
+    /// Run `f` over every stored vector, under the read lock. Pending
+    /// batch inserts are flushed first, as for [`exact_scores`].
+    ///
+    /// [`exact_scores`]: OptimizedHnswIndex::exact_scores
+    pub fn with_vectors<R>(&self, f: impl FnOnce(&IndexVectors<'_>) -> R) -> Result<R> {
+        self.flush()?;
+        Ok(f(&self.read_vectors()))
     }
 
     /// Remove a vector by ID
@@ -295,16 +911,9 @@ impl OptimizedHnswIndex {
         // Flush any pending batch operations first
         self.flush()?;
 
-        let mut vectors = self.vectors.write();
         let mut id_map = self.id_map.write();
-
-        if vectors.remove(id).is_some() {
-            // Also remove from id_map
-            id_map.remove(id);
-            Ok(true)
-        } else {
-            Ok(false)
-        }
+        self.vectors.write().remove(id);
+        Ok(id_map.remove(id).is_some())
     }
 
     /// Update a vector by ID
@@ -323,14 +932,36 @@ impl OptimizedHnswIndex {
         Ok(())
     }
 
+    /// Configuration the graph was built with
+    pub fn config(&self) -> OptimizedHnswConfig {
+        self.config
+    }
+
     /// Get the number of vectors in the index
     pub fn len(&self) -> usize {
-        self.vectors.read().len()
+        self.id_map.read().len()
     }
 
     /// Check if the index is empty
     pub fn is_empty(&self) -> bool {
-        self.vectors.read().is_empty()
+        self.id_map.read().is_empty()
+    }
+
+    /// Number of points in the graph, including ones whose ID was removed
+    /// or re-inserted since. hnsw_rs cannot unlink a point, so these stay
+    /// behind as routing-only nodes until the index is rebuilt.
+    pub fn graph_len(&self) -> usize {
+        *self.next_id.read()
+    }
+
+    /// Share of graph points that no longer belong to a live vector, in
+    /// `[0, 1]`. `0.0` for an empty graph.
+    pub fn fragmentation(&self) -> f64 {
+        let total = self.graph_len();
+        if total == 0 {
+            return 0.0;
+        }
+        total.saturating_sub(self.len()) as f64 / total as f64
     }
 
     /// Optimize the index for search
@@ -346,16 +977,128 @@ impl OptimizedHnswIndex {
         Ok(())
     }
 
+    /// Snapshot the live vectors together with the graph point each one
+    /// sits at. Points are never reused, so [`Self::changes_since`] can
+    /// tell exactly which IDs were written after the snapshot. A mapped
+    /// index lends its file instead of copying the vectors.
+    pub fn live_points(&self) -> Result<LivePoints> {
+        self.flush()?;
+        let id_map = self.id_map.read();
+        let vectors = match &self.mapped {
+            Some(mapped) => SnapshotVectors::Mapped(mapped.clone()),
+            None => SnapshotVectors::Memory(self.vectors.read().clone()),
+        };
+        Ok(LivePoints {
+            id_map: self.id_map.clone(),
+            points: id_map.clone(),
+            vectors,
+        })
+    }
+
+    /// The writes since `snapshot`, or `None` when the snapshot was taken
+    /// from a different index.
+    pub fn changes_since(&self, snapshot: &LivePoints) -> Result<Option<IndexDelta>> {
+        if !Arc::ptr_eq(&self.id_map, &snapshot.id_map) {
+            return Ok(None);
+        }
+        self.flush()?;
+        let vectors = self.read_vectors();
+        let upserted = vectors
+            .iter()
+            .filter(|(id, _)| snapshot.points.get(*id) != vectors.id_map.get(*id))
+            .map(|(id, data)| (id.clone(), data.to_vec()))
+            .collect();
+        let removed = snapshot
+            .points
+            .keys()
+            .filter(|id| !vectors.id_map.contains_key(*id))
+            .cloned()
+            .collect();
+        Ok(Some(IndexDelta { upserted, removed }))
+    }
+
     /// Get all vectors in the index
     pub fn get_all_vectors(&self) -> Result<HashMap<String, Vec<f32>>> {
-        Ok(self.vectors.read().clone())
+        let vectors = self.read_vectors();
+        Ok(vectors
+            .iter()
+            .map(|(id, data)| (id.clone(), data.to_vec()))
+            .collect())
+    }
+
+    /// Snapshot the graph for [`crate::db::disk_hnsw::DiskHnswIndex::write`].
+    ///
+    /// Every point hnsw_rs holds is exported, including ones whose ID was
+    /// removed or re-inserted since: they stay in the graph as routing-only
+    /// tombstones (`ids[node] == None`) so the exported graph is exactly as
+    /// connected as the live one.
+    pub fn export_graph(&self) -> Result<HnswGraph> {
+        self.flush()?;
+
+        let hnsw = self.hnsw.read();
+        let id_map = self.id_map.read();
+        let live: HashMap<usize, &String> = id_map
+            .iter()
+            .map(|(id, internal)| (*internal, id))
+            .collect();
+
+        let points = {
+            let mapped = self.mapped.as_ref().map(|vectors| vectors.read());
+            hnsw.points(mapped.as_deref())
+        };
+        let node_of: HashMap<usize, u32> = points
+            .iter()
+            .enumerate()
+            .map(|(node, (slot, ..))| (*slot, node as u32))
+            .collect();
+
+        let mut graph = HnswGraph {
+            dimension: self.dimension,
+            metric: self.config.distance_metric,
+            ids: Vec::with_capacity(points.len()),
+            vectors: Vec::with_capacity(points.len()),
+            neighbors: Vec::with_capacity(points.len()),
+            entry_point: 0,
+        };
+        let mut top_level = 0;
+        for (node, (slot, level, neighborhood, vector)) in points.into_iter().enumerate() {
+            let mut layers: Vec<Vec<u32>> = neighborhood
+                .into_iter()
+                .take(level + 1)
+                .map(|layer| {
+                    layer
+                        .iter()
+                        .filter_map(|n| node_of.get(&n.d_id).copied())
+                        .collect()
+                })
+                .collect();
+            if layers.is_empty() {
+                layers.push(Vec::new());
+            }
+            if node == 0 || layers.len() - 1 > top_level {
+                top_level = layers.len() - 1;
+                graph.entry_point = node as u32;
+            }
+
+            graph.ids.push(live.get(&slot).map(|id| (*id).clone()));
+            graph.vectors.push(vector);
+            graph.neighbors.push(layers);
+        }
+
+        Ok(graph)
     }
 
     /// Get memory usage statistics
+    ///
+    /// A mapped index reports no vector memory: its vectors are paged in
+    /// from the file on demand.
     pub fn memory_stats(&self) -> MemoryStats {
-        let vectors = self.vectors.read();
-        let vector_memory = vectors.len() * self.dimension * std::mem::size_of::<f32>();
-        let id_memory = vectors.keys().map(|k| k.len()).sum::<usize>();
+        let vectors = self.read_vectors();
+        let vector_memory = match self.mapped {
+            Some(_) => 0,
+            None => vectors.len() * self.dimension * std::mem::size_of::<f32>(),
+        };
+        let id_memory = vectors.id_map.keys().map(|k| k.len()).sum::<usize>();
 
         MemoryStats {
             vector_count: vectors.len(),
@@ -398,8 +1141,14 @@ impl OptimizedHnswIndex {
             ));
         }
 
+        let Graph::Memory(hnsw) = &*hnsw else {
+            return Err(VectorizerError::IndexError(
+                "Cannot dump an HNSW index whose vectors are in a mapped file".to_string(),
+            ));
+        };
+
         // Try using the library's file_dump method first
-        match (*hnsw).file_dump(path.as_ref(), basename) {
+        match hnsw.file_dump(path.as_ref(), basename) {
             Ok(result) => {
                 info!(
                     "✅ HNSW DUMP SUCCESS: Dump completed successfully, result: {}",
@@ -532,6 +1281,84 @@ mod tests {
         assert_eq!(index.len(), 100);
     }
 
+    #[test]
+    fn changes_since_reports_writes_after_the_snapshot() {
+        let index = OptimizedHnswIndex::new(2, OptimizedHnswConfig::default()).unwrap();
+        index.add("kept".to_string(), vec![1.0, 0.0]).unwrap();
+        index.add("updated".to_string(), vec![0.0, 1.0]).unwrap();
+        index.add("removed".to_string(), vec![1.0, 1.0]).unwrap();
+        let snapshot = index.live_points().unwrap();
+        assert_eq!(snapshot.len(), 3);
+
+        index.update("updated", &[0.5, 0.5]).unwrap();
+        index.remove("removed").unwrap();
+        index.add("added".to_string(), vec![0.2, 0.8]).unwrap();
+
+        let delta = index.changes_since(&snapshot).unwrap().unwrap();
+        let mut upserted: Vec<_> = delta.upserted.into_iter().collect();
+        upserted.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            upserted,
+            vec![
+                ("added".to_string(), vec![0.2, 0.8]),
+                ("updated".to_string(), vec![0.5, 0.5]),
+            ]
+        );
+        assert_eq!(delta.removed, vec!["removed".to_string()]);
+
+        let other = OptimizedHnswIndex::new(2, OptimizedHnswConfig::default()).unwrap();
+        assert!(other.changes_since(&snapshot).unwrap().is_none());
+    }
+
+    #[test]
+    fn mapped_index_reads_vectors_from_its_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = OptimizedHnswConfig {
+            batch_size: 16,
+            ..Default::default()
+        };
+        let memory = OptimizedHnswIndex::new(8, config).unwrap();
+        let mapped = OptimizedHnswIndex::new_mapped(8, config, dir.path()).unwrap();
+        assert!(mapped.is_mapped());
+        let vector =
+            |i: usize| -> Vec<f32> { (0..8).map(|d| ((i * 8 + d) as f32).sin()).collect() };
+        for index in [&memory, &mapped] {
+            for i in 0..100 {
+                index.add(format!("v{i}"), vector(i)).unwrap();
+            }
+            index.remove("v7").unwrap();
+            index.update("v8", &vector(500)).unwrap();
+        }
+
+        let ids: Vec<String> = (0..100).map(|i| format!("v{i}")).collect();
+        let query = vector(1000);
+        assert_eq!(
+            mapped.exact_scores(&query, &ids).unwrap(),
+            memory.exact_scores(&query, &ids).unwrap()
+        );
+        assert_eq!(mapped.search(&vector(42), 1).unwrap()[0].0, "v42");
+        assert_eq!(mapped.get_all_vectors().unwrap()["v8"], vector(500));
+        assert_eq!(mapped.len(), 99);
+        let stats = mapped.memory_stats();
+        assert_eq!((stats.vector_count, stats.vector_memory_bytes), (99, 0));
+        let graph = mapped.export_graph().unwrap();
+        assert_eq!(graph.vectors.len(), mapped.graph_len());
+        assert!(graph.vectors.iter().all(|v| v.len() == 8));
+
+        let rebuilt = OptimizedHnswIndex::new_mapped(8, config, dir.path()).unwrap();
+        let snapshot = mapped.live_points().unwrap();
+        snapshot
+            .for_each_batch(10, |batch| rebuilt.batch_add(batch))
+            .unwrap();
+        assert_eq!(
+            rebuilt.get_all_vectors().unwrap(),
+            mapped.get_all_vectors().unwrap()
+        );
+
+        drop((mapped, rebuilt, snapshot));
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+
     #[test]
     fn test_memory_stats() {
         let index = OptimizedHnswIndex::new(128, Default::default()).unwrap();
@@ -593,6 +1420,97 @@ mod tests {
         );
     }
 
+    #[test]
+    fn exact_scores_match_search_scale() {
+        let index = OptimizedHnswIndex::new(2, OptimizedHnswConfig::default()).unwrap();
+        index.add("a".to_string(), vec![1.0, 0.0]).unwrap();
+        index.add("b".to_string(), vec![0.0, 1.0]).unwrap();
+
+        let query = vec![1.0, 0.0];
+        let searched = index.search(&query, 2).unwrap();
+        let exact = index
+            .exact_scores(
+                &query,
+                &["a".to_string(), "b".to_string(), "missing".to_string()],
+            )
+            .unwrap();
+
+        assert_eq!(exact.len(), 2);
+        for (id, score) in &exact {
+            let (_, s) = searched.iter().find(|(sid, _)| sid == id).unwrap();
+            assert!((score - s).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn search_reports_beam_and_visited_candidates() {
+        let index = OptimizedHnswIndex::new(4, OptimizedHnswConfig::default()).unwrap();
+        for i in 0..100 {
+            let v: Vec<f32> = (0..4)
+                .map(|d| ((i * 13 + d * 5) % 11) as f32 + 1.0)
+                .collect();
+            index.add(format!("v{i}"), v).unwrap();
+        }
+
+        let (results, stats) = index.search_with_stats(&[1.0, 2.0, 3.0, 4.0], 5).unwrap();
+        assert_eq!(results.len(), 5);
+        assert_eq!(stats.ef_search, 64);
+        assert!(stats.candidates_visited >= 5, "{stats:?}");
+
+        // Distance evaluations outside the walk are not counted.
+        let ((), visited) = count_visited(|| ());
+        assert_eq!(visited, 0);
+    }
+
+    #[test]
+    fn search_returns_k_live_hits_under_heavy_churn() {
+        let index = OptimizedHnswIndex::new(4, OptimizedHnswConfig::default()).unwrap();
+        let vector = |i: usize, round: usize| -> Vec<f32> {
+            vec![
+                (i % 20) as f32,
+                (i / 20) as f32,
+                ((i * 7 + round) % 13) as f32,
+                1.0 + round as f32 * 0.1,
+            ]
+        };
+        for i in 0..400 {
+            index.add(format!("v{i}"), vector(i, 0)).unwrap();
+        }
+        // 90% of the points removed, and the survivors re-written a few
+        // times: most of the graph is dead points live ones route through.
+        for i in (0..400).filter(|i| i % 10 != 0) {
+            assert!(index.remove(&format!("v{i}")).unwrap());
+        }
+        for round in 1..=5 {
+            for i in (0..400).step_by(10) {
+                index.update(&format!("v{i}"), &vector(i, round)).unwrap();
+            }
+        }
+        index.flush().unwrap();
+        assert_eq!(index.len(), 40);
+        assert_eq!(index.graph_len(), 600);
+
+        let query = [3.0, 4.0, 2.0, 1.5];
+        for k in [10, 40] {
+            let results = index.search(&query, k).unwrap();
+            assert_eq!(results.len(), k);
+            let ids: std::collections::HashSet<&str> =
+                results.iter().map(|(id, _)| id.as_str()).collect();
+            assert_eq!(ids.len(), k);
+            for id in ids {
+                let n: usize = id[1..].parse().unwrap();
+                assert_eq!(n % 10, 0, "{id} was removed");
+            }
+
+            let batch = index
+                .search_batch_until(&[(&query[..], k, SearchDeadline::NONE)])
+                .unwrap();
+            assert_eq!(batch[0].0.len(), k);
+        }
+        // Asking for more than is live returns every live vector.
+        assert_eq!(index.search(&query, 100).unwrap().len(), 40);
+    }
+
     /// A DotProduct index must rank by inner product (largest first). Here `a`
     /// has the larger dot but `b` is the cosine-closest, so the two metrics
     /// disagree.
@@ -618,4 +1536,64 @@ mod tests {
             "DotProduct must rank the highest inner-product vector first"
         );
     }
+
+    /// The SIMD kernels must reproduce hnsw_rs's scalar distances, so
+    /// graphs and exported `.vzhnsw` files built before the switch rank
+    /// the same way.
+    #[test]
+    fn simd_distances_match_hnsw_rs_scalar() {
+        let dim = 1024;
+        let a: Vec<f32> = (0..dim).map(|i| ((i * 7) % 13) as f32 - 6.0).collect();
+        let b: Vec<f32> = (0..dim).map(|i| ((i * 5) % 11) as f32 * 0.3).collect();
+
+        let cosine = MetricDistance {
+            metric: DistanceMetric::Cosine,
+        };
+        assert!((cosine.eval(&a, &b) - DistCosine {}.eval(&a, &b)).abs() < 1e-4);
+        assert!(cosine.eval(&a, &a).abs() < 1e-5);
+        assert_eq!(cosine.eval(&a, &vec![0.0; dim]), 0.0);
+
+        let euclid = MetricDistance {
+            metric: DistanceMetric::Euclidean,
+        };
+        let expected = DistL2 {}.eval(&a, &b);
+        assert!((euclid.eval(&a, &b) - expected).abs() / expected < 1e-4);
+    }
+
+    #[test]
+    fn batch_search_matches_single_searches() {
+        let index = OptimizedHnswIndex::new(8, OptimizedHnswConfig::default()).unwrap();
+        for i in 0..200 {
+            let v: Vec<f32> = (0..8)
+                .map(|d| ((i * 31 + d * 7) % 17) as f32 + 1.0)
+                .collect();
+            index.add(format!("v{i}"), v).unwrap();
+        }
+
+        let queries: Vec<Vec<f32>> = (0..16)
+            .map(|i| {
+                (0..8)
+                    .map(|d| ((i * 13 + d * 5) % 11) as f32 + 0.5)
+                    .collect()
+            })
+            .collect();
+        let expired = SearchDeadline::from_timeout_ms(Some(0));
+        let mut batch: Vec<(&[f32], usize, SearchDeadline)> = queries
+            .iter()
+            .map(|q| (q.as_slice(), 5, SearchDeadline::NONE))
+            .collect();
+        batch.push((&queries[0], 5, expired));
+
+        let results = index.search_batch_until(&batch).unwrap();
+        assert_eq!(results.len(), 17);
+        for (query, (neighbors, timed_out, stats)) in queries.iter().zip(&results) {
+            assert!(!timed_out);
+            assert_eq!(neighbors, &index.search(query, 5).unwrap());
+            assert!(stats.is_some_and(|s| s.candidates_visited > 0));
+        }
+        assert_eq!(results[16], (Vec::new(), true, None));
+
+        let wrong_dim = [(&[1.0_f32][..], 5, SearchDeadline::NONE)];
+        assert!(index.search_batch_until(&wrong_dim).is_err());
+    }
 }
@@ -10,6 +10,7 @@ use dashmap::DashMap;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use crate::db::text_tokenizer::{Bm25Params, tokenize};
 use crate::models::Payload;
 
 /// Payload index type
@@ -228,108 +229,139 @@ impl FloatIndex {
     }
 }
 
-/// Text index for full-text search (simple token-based)
+/// Text index for full-text search: per-term postings with the term
+/// frequencies and text lengths BM25 ranking needs
 #[derive(Debug, Clone)]
 struct TextIndex {
-    /// Term -> Set of vector IDs containing this term
-    term_to_ids: HashMap<String, HashSet<String>>,
-    /// Vector ID -> Full text
-    id_to_text: HashMap<String, String>,
+    /// Term -> (vector ID -> occurrences of the term in its text)
+    postings: HashMap<String, HashMap<String, u32>>,
+    /// Vector ID -> distinct terms and token count of its text
+    docs: HashMap<String, TextDoc>,
+    /// Sum of the token counts of every indexed text
+    total_tokens: u64,
+}
+
+/// What the text index remembers of one vector's text
+#[derive(Debug, Clone)]
+struct TextDoc {
+    terms: Vec<String>,
+    tokens: u32,
 }
 
 impl TextIndex {
     fn new() -> Self {
         Self {
-            term_to_ids: HashMap::new(),
-            id_to_text: HashMap::new(),
+            postings: HashMap::new(),
+            docs: HashMap::new(),
+            total_tokens: 0,
         }
     }
 
-    fn insert(&mut self, vector_id: String, text: String) {
+    fn insert(&mut self, vector_id: String, text: &str) {
         // Remove old text if exists
-        if let Some(old_text) = self.id_to_text.get(&vector_id).cloned() {
-            self.remove_terms(vector_id.clone(), &old_text);
-        }
-
-        // Store full text
-        self.id_to_text.insert(vector_id.clone(), text.clone());
+        self.remove(&vector_id);
 
-        // Tokenize and index terms
-        let terms = Self::tokenize(&text);
-        for term in terms {
-            self.term_to_ids
+        let mut term_freqs: HashMap<String, u32> = HashMap::new();
+        let mut tokens = 0u32;
+        for term in tokenize(text) {
+            tokens += 1;
+            *term_freqs.entry(term).or_insert(0) += 1;
+        }
+        let terms: Vec<String> = term_freqs.keys().cloned().collect();
+        for (term, freq) in term_freqs {
+            self.postings
                 .entry(term)
-                .or_insert_with(HashSet::new)
-                .insert(vector_id.clone());
+                .or_default()
+                .insert(vector_id.clone(), freq);
         }
+        self.total_tokens += u64::from(tokens);
+        self.docs.insert(vector_id, TextDoc { terms, tokens });
     }
 
     fn remove(&mut self, vector_id: &str) {
-        if let Some(text) = self.id_to_text.remove(vector_id) {
-            self.remove_terms(vector_id.to_string(), &text);
-        }
-    }
-
-    fn remove_terms(&mut self, vector_id: String, text: &str) {
-        let terms = Self::tokenize(text);
-        for term in terms {
-            if let Some(ids) = self.term_to_ids.get_mut(&term) {
-                ids.remove(&vector_id);
+        let Some(doc) = self.docs.remove(vector_id) else {
+            return;
+        };
+        self.total_tokens -= u64::from(doc.tokens);
+        for term in doc.terms {
+            if let Some(ids) = self.postings.get_mut(&term) {
+                ids.remove(vector_id);
                 if ids.is_empty() {
-                    self.term_to_ids.remove(&term);
+                    self.postings.remove(&term);
                 }
             }
         }
     }
 
     fn search(&self, query: &str) -> HashSet<String> {
-        let query_terms = Self::tokenize(query);
-        let mut result_ids = HashSet::new();
+        let query_terms = tokenize(query);
+        let mut result_ids: Option<HashSet<String>> = None;
 
         for term in query_terms {
-            if let Some(ids) = self.term_to_ids.get(&term) {
-                if result_ids.is_empty() {
-                    result_ids = ids.clone();
-                } else {
-                    // Intersection for AND semantics
-                    result_ids = result_ids.intersection(ids).cloned().collect();
-                }
-            } else {
-                // If any term not found, return empty (AND semantics)
+            // If any term not found, return empty (AND semantics)
+            let Some(ids) = self.postings.get(&term) else {
                 return HashSet::new();
-            }
+            };
+            result_ids = Some(match result_ids {
+                None => ids.keys().cloned().collect(),
+                Some(found) => found
+                    .into_iter()
+                    .filter(|id| ids.contains_key(id))
+                    .collect(),
+            });
         }
 
-        result_ids
+        result_ids.unwrap_or_default()
     }
 
-    fn tokenize(text: &str) -> Vec<String> {
-        // Simple tokenization: lowercase, split on whitespace and punctuation
-        text.to_lowercase()
-            .split(|c: char| !c.is_alphanumeric())
-            .filter(|s| !s.is_empty())
-            .map(|s| s.to_string())
-            .collect()
+    /// BM25 score of every posting of `terms`, as (vector ID, term, score)
+    fn bm25(&self, terms: &[String], params: Bm25Params) -> Vec<(String, String, f32)> {
+        if self.docs.is_empty() {
+            return Vec::new();
+        }
+        let n = self.docs.len() as f32;
+        let avg_tokens = (self.total_tokens as f32 / n).max(1.0);
+        let Bm25Params { k1, b } = params;
+
+        let mut scores = Vec::new();
+        for term in terms {
+            let Some(ids) = self.postings.get(term) else {
+                continue;
+            };
+            let df = ids.len() as f32;
+            let idf = ((n - df + 0.5) / (df + 0.5) + 1.0).ln();
+            for (id, freq) in ids {
+                let tokens = self.docs.get(id).map_or(0, |doc| doc.tokens);
+                let norm = k1 * (1.0 - b + b * tokens as f32 / avg_tokens);
+                let tf = *freq as f32;
+                scores.push((
+                    id.clone(),
+                    term.clone(),
+                    idf * tf * (k1 + 1.0) / (tf + norm),
+                ));
+            }
+        }
+        scores
     }
 
     fn stats(&self) -> PayloadIndexStats {
         PayloadIndexStats {
-            indexed_count: self.id_to_text.len(),
-            unique_values: self.term_to_ids.len(),
+            indexed_count: self.docs.len(),
+            unique_values: self.postings.len(),
             memory_bytes: self.estimate_memory(),
         }
     }
 
     fn estimate_memory(&self) -> usize {
-        let text_size: usize = self.id_to_text.values().map(|t| t.len()).sum();
-        let term_size: usize = self.term_to_ids.keys().map(|k| k.len()).sum();
-        let ids_size = self
-            .term_to_ids
+        let term_size: usize = self.postings.keys().map(|k| k.len()).sum();
+        let postings_size = self.postings.values().map(|ids| ids.len()).sum::<usize>()
+            * (std::mem::size_of::<String>() + std::mem::size_of::<u32>());
+        let docs_size: usize = self
+            .docs
             .values()
-            .map(|ids| ids.len())
-            .sum::<usize>()
-            * std::mem::size_of::<String>();
-        text_size + term_size + ids_size + self.id_to_text.len() * std::mem::size_of::<String>() * 2
+            .map(|doc| doc.terms.len() * std::mem::size_of::<String>())
+            .sum();
+        term_size + postings_size + docs_size + self.docs.len() * std::mem::size_of::<String>() * 2
     }
 }
 
@@ -415,6 +447,19 @@ impl GeoIndex {
     }
 }
 
+/// Text of a payload value: a string, or the strings of an array joined
+/// with newlines
+fn text_of(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Array(items) => {
+            let parts: Vec<&str> = items.iter().filter_map(|v| v.as_str()).collect();
+            (!parts.is_empty()).then(|| parts.join("\n"))
+        }
+        _ => None,
+    }
+}
+
 /// Main payload index manager
 #[derive(Debug, Clone)]
 pub struct PayloadIndex {
@@ -555,8 +600,8 @@ impl PayloadIndex {
                 }
                 PayloadIndexType::Text => {
                     if let Some(mut text_index) = self.text_indexes.get_mut(field_name) {
-                        if let Some(value_str) = value.as_str() {
-                            text_index.insert(vector_id.clone(), value_str.to_string());
+                        if let Some(text) = text_of(value) {
+                            text_index.insert(vector_id.clone(), &text);
                         }
                     }
                 }
@@ -649,6 +694,25 @@ impl PayloadIndex {
             .map(|index| index.search(query))
     }
 
+    /// Whether `field_name` has a text index
+    pub fn has_text_index(&self, field_name: &str) -> bool {
+        self.text_indexes.contains_key(field_name)
+    }
+
+    /// BM25 scores of the postings of `terms` in the text index of
+    /// `field_name`, as (vector ID, term, score); `None` without a text
+    /// index. Only the postings of `terms` are read.
+    pub fn bm25_text(
+        &self,
+        field_name: &str,
+        terms: &[String],
+        params: Bm25Params,
+    ) -> Option<Vec<(String, String, f32)>> {
+        self.text_indexes
+            .get(field_name)
+            .map(|index| index.bm25(terms, params))
+    }
+
     /// Get vector IDs in geo bounding box
     pub fn get_ids_in_geo_bounding_box(
         &self,
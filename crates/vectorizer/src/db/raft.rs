@@ -175,7 +175,7 @@ impl RaftStateMachine {
                             payload,
                             document_id: None,
                         };
-                        store.insert(collection_name, vec![vector])?;
+                        store.apply_insert(collection_name, vec![vector])?;
                     }
                     Operation::UpdateVector {
                         collection_name,
@@ -202,7 +202,7 @@ impl RaftStateMachine {
                                 payload,
                                 document_id: None,
                             };
-                            store.update(collection_name, vector)?;
+                            store.apply_update(collection_name, vector)?;
                         }
                     }
                     Operation::DeleteVector {
@@ -594,6 +594,13 @@ mod tests {
             sharding: None,
             graph: None,
             encryption: None,
+            id_policy: None,
+            quota: None,
+            scoring: None,
+            tenant_partition: None,
+            dedup: None,
+            chunking: None,
+            full_text: None,
         };
         store
             .create_collection("collection_a", cfg.clone())
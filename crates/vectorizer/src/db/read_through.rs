@@ -0,0 +1,640 @@
+//! Read-through collections — loaded from an external origin on first
+//! access and unloaded again once idle.
+//!
+//! Each entry of `read_through.collections` names a local collection and
+//! its [`CollectionOrigin`]: a `.vecdb` archive (local directory or an
+//! HTTP prefix such as an S3 / MinIO bucket path) or a collection on
+//! another Vectorizer server. The first lookup of an unloaded collection
+//! in [`VectorStore::get_collection`](crate::db::VectorStore::get_collection)
+//! fetches it through [`ReadThrough::fetch`] and builds it in memory; the
+//! `read_through_eviction` background task unloads it again after
+//! `idle_eviction_secs` without an access, so a server can serve many
+//! more collections than fit in memory at once.
+//!
+//! The origin stays the source of truth. A read-through collection is
+//! never written to the local `.vecdb`, so local writes to it are lost
+//! when it is evicted, and deleting it only unloads it.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::{debug, info, warn};
+
+use crate::background_tasks::{self, TASKS};
+use crate::config::sections::read_through::{CollectionOrigin, ReadThroughConfig};
+use crate::db::VectorStore;
+use crate::error::{Result, VectorizerError};
+use crate::models::{CollectionConfig, DistanceMetric, Payload, Vector};
+use crate::persistence::{PersistedCollection, PersistedVector};
+use crate::storage::{StorageReader, VECDB_FILE, VECIDX_FILE};
+
+/// Page size used when copying from a Vectorizer origin; the most
+/// `GET /collections/{name}/vectors` returns per call.
+const REMOTE_PAGE_SIZE: usize = 50;
+
+/// Access bookkeeping of one read-through collection.
+#[derive(Debug, Default)]
+struct Access {
+    loaded_at: Option<DateTime<Utc>>,
+    last_access: Option<Instant>,
+    last_access_at: Option<DateTime<Utc>>,
+    loads: u64,
+}
+
+/// A read-through collection, as listed by `GET /admin/read_through`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadThroughStatus {
+    /// Local collection name.
+    pub name: String,
+    /// Origin type: `vecdb` or `vectorizer`.
+    pub origin: String,
+    /// Archive location or remote URL.
+    pub source: String,
+    /// Collection name at the origin.
+    pub remote_collection: String,
+    /// Whether the collection is in memory.
+    pub loaded: bool,
+    /// When it was last loaded.
+    pub loaded_at: Option<DateTime<Utc>>,
+    /// When it was last accessed.
+    pub last_access_at: Option<DateTime<Utc>>,
+    /// How many times it has been loaded from the origin.
+    pub loads: u64,
+}
+
+/// Read-through collections and their access state.
+pub struct ReadThrough {
+    origins: BTreeMap<String, CollectionOrigin>,
+    idle_timeout: Duration,
+    sweep_interval: Duration,
+    fetch_timeout: Duration,
+    access: Mutex<HashMap<String, Access>>,
+    /// Held across a cold load so concurrent lookups of the same
+    /// collection fetch it once.
+    load_lock: Mutex<()>,
+}
+
+impl std::fmt::Debug for ReadThrough {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReadThrough")
+            .field("collections", &self.origins.keys().collect::<Vec<_>>())
+            .field("idle_timeout", &self.idle_timeout)
+            .finish()
+    }
+}
+
+impl ReadThrough {
+    /// Build from the `read_through` config section, rejecting entries
+    /// without a name or origin location, duplicate names, and zero
+    /// intervals.
+    pub fn from_config(config: &ReadThroughConfig) -> Result<Self> {
+        if config.idle_eviction_secs == 0 {
+            return Err(invalid("read_through.idle_eviction_secs must be >= 1"));
+        }
+        if config.sweep_interval_secs == 0 {
+            return Err(invalid("read_through.sweep_interval_secs must be >= 1"));
+        }
+        if config.fetch_timeout_secs == 0 {
+            return Err(invalid("read_through.fetch_timeout_secs must be >= 1"));
+        }
+        let mut origins = BTreeMap::new();
+        for entry in &config.collections {
+            if entry.name.trim().is_empty() {
+                return Err(invalid("read_through collection name must not be empty"));
+            }
+            let (location, field) = match &entry.origin {
+                CollectionOrigin::Vecdb { location, .. } => (location, "location"),
+                CollectionOrigin::Vectorizer { url, .. } => (url, "url"),
+            };
+            if location.trim().is_empty() {
+                return Err(invalid(&format!(
+                    "read_through collection '{}': origin {} must not be empty",
+                    entry.name, field
+                )));
+            }
+            if origins
+                .insert(entry.name.clone(), entry.origin.clone())
+                .is_some()
+            {
+                return Err(invalid(&format!(
+                    "read_through collection '{}' is listed twice",
+                    entry.name
+                )));
+            }
+        }
+        Ok(Self {
+            origins,
+            idle_timeout: Duration::from_secs(config.idle_eviction_secs),
+            sweep_interval: Duration::from_secs(config.sweep_interval_secs),
+            fetch_timeout: Duration::from_secs(config.fetch_timeout_secs),
+            access: Mutex::new(HashMap::new()),
+            load_lock: Mutex::new(()),
+        })
+    }
+
+    /// Whether `name` is a read-through collection.
+    pub fn contains(&self, name: &str) -> bool {
+        self.origins.contains_key(name)
+    }
+
+    /// Number of read-through collections.
+    pub fn len(&self) -> usize {
+        self.origins.len()
+    }
+
+    /// Whether no collection is read-through.
+    pub fn is_empty(&self) -> bool {
+        self.origins.is_empty()
+    }
+
+    /// How often the eviction task looks for idle collections.
+    pub fn sweep_interval(&self) -> Duration {
+        self.sweep_interval
+    }
+
+    /// Record an access to `name`, pushing back its eviction.
+    pub fn touch(&self, name: &str) {
+        if !self.contains(name) {
+            return;
+        }
+        let mut access = self.access.lock();
+        let entry = access.entry(name.to_string()).or_default();
+        entry.last_access = Some(Instant::now());
+        entry.last_access_at = Some(Utc::now());
+    }
+
+    /// Lock serializing cold loads; see [`Self::fetch`].
+    pub(crate) fn lock_loads(&self) -> parking_lot::MutexGuard<'_, ()> {
+        self.load_lock.lock()
+    }
+
+    /// Record that `name` was just loaded from its origin.
+    pub(crate) fn mark_loaded(&self, name: &str) {
+        let mut access = self.access.lock();
+        let entry = access.entry(name.to_string()).or_default();
+        entry.loaded_at = Some(Utc::now());
+        entry.last_access = Some(Instant::now());
+        entry.last_access_at = Some(Utc::now());
+        entry.loads += 1;
+    }
+
+    /// Record that `name` was unloaded.
+    pub(crate) fn mark_evicted(&self, name: &str) {
+        if let Some(entry) = self.access.lock().get_mut(name) {
+            entry.loaded_at = None;
+        }
+    }
+
+    /// Loaded collections not accessed within the idle timeout.
+    pub fn idle_collections(&self) -> Vec<String> {
+        let access = self.access.lock();
+        access
+            .iter()
+            .filter(|(_, a)| {
+                a.loaded_at.is_some()
+                    && a.last_access
+                        .is_none_or(|at| at.elapsed() >= self.idle_timeout)
+            })
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    /// Status of every read-through collection, by name. `loaded` is
+    /// answered by the caller, which owns the collection map.
+    pub fn status(&self, loaded: impl Fn(&str) -> bool) -> Vec<ReadThroughStatus> {
+        let access = self.access.lock();
+        self.origins
+            .iter()
+            .map(|(name, origin)| {
+                let (kind, source) = match origin {
+                    CollectionOrigin::Vecdb { location, .. } => ("vecdb", location.clone()),
+                    CollectionOrigin::Vectorizer { url, .. } => ("vectorizer", url.clone()),
+                };
+                let entry = access.get(name);
+                ReadThroughStatus {
+                    name: name.clone(),
+                    origin: kind.to_string(),
+                    source,
+                    remote_collection: remote_name(name, origin).to_string(),
+                    loaded: loaded(name),
+                    loaded_at: entry.and_then(|a| a.loaded_at),
+                    last_access_at: entry.and_then(|a| a.last_access_at),
+                    loads: entry.map_or(0, |a| a.loads),
+                }
+            })
+            .collect()
+    }
+
+    /// Fetch `name` from its origin.
+    ///
+    /// Blocks the calling thread: HTTP origins are fetched on a helper
+    /// thread with its own runtime, so this works from both sync code and
+    /// inside a Tokio worker, like the lazy `.vecdb` load it stands in
+    /// for.
+    pub fn fetch(&self, name: &str) -> Result<PersistedCollection> {
+        let origin = self
+            .origins
+            .get(name)
+            .ok_or_else(|| VectorizerError::CollectionNotFound(name.to_string()))?;
+        let remote = remote_name(name, origin);
+        let started = Instant::now();
+        let mut persisted = match origin {
+            CollectionOrigin::Vecdb { location, .. } if !is_http(location) => {
+                read_vecdb(Path::new(location), remote)?
+            }
+            CollectionOrigin::Vecdb { location, .. } => {
+                let dir = tempfile::tempdir()?;
+                let location = location.trim_end_matches('/');
+                block_on_helper(self.fetch_timeout, async {
+                    let client = reqwest::Client::new();
+                    for file in [VECDB_FILE, VECIDX_FILE] {
+                        let bytes = get(&client, &format!("{location}/{file}"), None)
+                            .await?
+                            .bytes()
+                            .await
+                            .map_err(|e| fetch_error(location, e))?;
+                        std::fs::write(dir.path().join(file), &bytes)?;
+                    }
+                    Ok::<_, VectorizerError>(())
+                })?;
+                read_vecdb(dir.path(), remote)?
+            }
+            CollectionOrigin::Vectorizer { url, api_key, .. } => {
+                let key = api_key.as_ref().map(|k| k.expose_secret().clone());
+                block_on_helper(
+                    self.fetch_timeout,
+                    fetch_remote(url.trim_end_matches('/'), remote, key.as_deref()),
+                )?
+            }
+        };
+        persisted.name = name.to_string();
+        info!(
+            "Fetched read-through collection '{}' ({} vectors) in {:?}",
+            name,
+            persisted.vectors.len(),
+            started.elapsed()
+        );
+        Ok(persisted)
+    }
+}
+
+/// Handle to the `read_through_eviction` background task.
+pub struct ReadThroughEvictor {
+    shutdown: Arc<AtomicBool>,
+}
+
+impl ReadThroughEvictor {
+    /// Spawn a loop that unloads the store's idle read-through
+    /// collections every `interval`.
+    pub fn spawn(store: Arc<VectorStore>, interval: Duration) -> Self {
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let shutdown_clone = shutdown.clone();
+
+        tokio::spawn(async move {
+            let task = TASKS.register(background_tasks::READ_THROUGH_EVICTION);
+            info!(
+                "Read-through eviction started (interval {}s)",
+                interval.as_secs()
+            );
+
+            loop {
+                task.schedule(interval);
+                tokio::time::sleep(interval).await;
+
+                if shutdown_clone.load(Ordering::Relaxed) {
+                    info!("Read-through eviction shutting down");
+                    break;
+                }
+                if task.is_paused() {
+                    debug!("Read-through eviction paused, skipping sweep");
+                    continue;
+                }
+
+                let store = store.clone();
+                task.start();
+                let sweep = tokio::task::spawn_blocking(move || store.evict_idle_read_through());
+                let result = sweep.await;
+                task.finish(&result);
+                if let Err(e) = result {
+                    warn!("Read-through eviction sweep panicked: {}", e);
+                }
+            }
+            task.stop();
+        });
+
+        Self { shutdown }
+    }
+
+    /// Signal the eviction task to stop on the next wake-up.
+    pub fn stop(&self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Collection name at the origin.
+fn remote_name<'a>(name: &'a str, origin: &'a CollectionOrigin) -> &'a str {
+    match origin {
+        CollectionOrigin::Vecdb { collection, .. }
+        | CollectionOrigin::Vectorizer { collection, .. } => collection.as_deref().unwrap_or(name),
+    }
+}
+
+fn is_http(location: &str) -> bool {
+    location.starts_with("http://") || location.starts_with("https://")
+}
+
+fn read_vecdb(dir: &Path, collection: &str) -> Result<PersistedCollection> {
+    StorageReader::new(dir)?
+        .read_collection_in_memory(collection)?
+        .ok_or_else(|| {
+            VectorizerError::Storage(format!(
+                "collection '{}' not found in {}",
+                collection,
+                dir.display()
+            ))
+        })
+}
+
+/// Run `future` to completion on a fresh current-thread runtime in a
+/// scoped helper thread, under `timeout`.
+fn block_on_helper<T: Send>(
+    timeout: Duration,
+    future: impl std::future::Future<Output = Result<T>> + Send,
+) -> Result<T> {
+    std::thread::scope(|scope| {
+        scope
+            .spawn(move || {
+                let runtime = tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()?;
+                runtime.block_on(async {
+                    tokio::time::timeout(timeout, future).await.map_err(|_| {
+                        VectorizerError::Storage(format!(
+                            "origin fetch timed out after {} s",
+                            timeout.as_secs()
+                        ))
+                    })?
+                })
+            })
+            .join()
+            .map_err(|_| VectorizerError::InternalError("origin fetch panicked".to_string()))?
+    })
+}
+
+async fn get(
+    client: &reqwest::Client,
+    url: &str,
+    api_key: Option<&str>,
+) -> Result<reqwest::Response> {
+    let mut request = client.get(url);
+    if let Some(key) = api_key {
+        request = request.header("X-API-Key", key);
+    }
+    let response = request.send().await.map_err(|e| fetch_error(url, e))?;
+    if !response.status().is_success() {
+        return Err(VectorizerError::Storage(format!(
+            "{} answered {}",
+            url,
+            response.status()
+        )));
+    }
+    Ok(response)
+}
+
+async fn get_json(client: &reqwest::Client, url: &str, api_key: Option<&str>) -> Result<Value> {
+    get(client, url, api_key)
+        .await?
+        .json()
+        .await
+        .map_err(|e| fetch_error(url, e))
+}
+
+/// Copy `collection` from the Vectorizer at `base_url`: its dimension and
+/// metric from `GET /collections/{name}`, then every vector page by page.
+/// Other collection settings keep their defaults.
+async fn fetch_remote(
+    base_url: &str,
+    collection: &str,
+    api_key: Option<&str>,
+) -> Result<PersistedCollection> {
+    let client = reqwest::Client::new();
+    let info = get_json(
+        &client,
+        &format!("{base_url}/collections/{collection}"),
+        api_key,
+    )
+    .await?;
+    let dimension = info["dimension"].as_u64().ok_or_else(|| {
+        VectorizerError::Storage(format!(
+            "{base_url}: collection '{collection}' reported no dimension"
+        ))
+    })? as usize;
+    let metric = match info["metric"].as_str() {
+        Some("Euclidean") => DistanceMetric::Euclidean,
+        Some("DotProduct") => DistanceMetric::DotProduct,
+        _ => DistanceMetric::Cosine,
+    };
+
+    let mut vectors = Vec::new();
+    loop {
+        let page = get_json(
+            &client,
+            &format!(
+                "{base_url}/collections/{collection}/vectors?limit={REMOTE_PAGE_SIZE}&offset={}",
+                vectors.len()
+            ),
+            api_key,
+        )
+        .await?;
+        let items = page["vectors"].as_array().cloned().unwrap_or_default();
+        let fetched = items.len();
+        for item in items {
+            let id = item["id"].as_str().unwrap_or_default().to_string();
+            let data: Vec<f32> = serde_json::from_value(item["vector"].clone())?;
+            let vector = match &item["payload"] {
+                Value::Null => Vector::new(id, data),
+                payload => Vector::with_payload(id, data, Payload::new(payload.clone())),
+            };
+            vectors.push(PersistedVector::from(vector));
+        }
+        debug!(
+            "Fetched {} vectors of '{}' from {}",
+            vectors.len(),
+            collection,
+            base_url
+        );
+        if fetched < REMOTE_PAGE_SIZE {
+            break;
+        }
+    }
+
+    Ok(PersistedCollection {
+        name: collection.to_string(),
+        config: Some(CollectionConfig {
+            dimension,
+            metric,
+            ..CollectionConfig::default()
+        }),
+        vectors,
+        hnsw_dump_basename: None,
+        recall_profile: None,
+        feedback: None,
+        metadata_kv: None,
+        hybrid_tuning: None,
+        overrides: None,
+    })
+}
+
+fn fetch_error(url: &str, error: reqwest::Error) -> VectorizerError {
+    VectorizerError::Storage(format!("fetch from {url} failed: {error}"))
+}
+
+fn invalid(message: &str) -> VectorizerError {
+    VectorizerError::InvalidConfiguration {
+        message: message.to_string(),
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::config::sections::read_through::ReadThroughCollectionConfig;
+
+    fn config(collections: Vec<ReadThroughCollectionConfig>) -> ReadThroughConfig {
+        ReadThroughConfig {
+            collections,
+            ..ReadThroughConfig::default()
+        }
+    }
+
+    fn vecdb(name: &str, location: &str) -> ReadThroughCollectionConfig {
+        ReadThroughCollectionConfig {
+            name: name.to_string(),
+            origin: CollectionOrigin::Vecdb {
+                location: location.to_string(),
+                collection: None,
+            },
+        }
+    }
+
+    #[test]
+    fn config_is_validated() {
+        assert!(ReadThrough::from_config(&config(vec![vecdb("a", "/data")])).is_ok());
+        assert!(ReadThrough::from_config(&config(vec![vecdb("", "/data")])).is_err());
+        assert!(ReadThrough::from_config(&config(vec![vecdb("a", " ")])).is_err());
+        assert!(
+            ReadThrough::from_config(&config(vec![vecdb("a", "/x"), vecdb("a", "/y")])).is_err()
+        );
+        let zero_idle = ReadThroughConfig {
+            idle_eviction_secs: 0,
+            ..ReadThroughConfig::default()
+        };
+        assert!(ReadThrough::from_config(&zero_idle).is_err());
+    }
+
+    #[test]
+    fn loaded_collections_go_idle_after_the_timeout() {
+        let read_through = ReadThrough::from_config(&ReadThroughConfig {
+            idle_eviction_secs: 1,
+            ..config(vec![vecdb("a", "/data"), vecdb("b", "/data")])
+        })
+        .unwrap();
+        read_through.mark_loaded("a");
+        read_through.touch("b");
+        assert!(read_through.idle_collections().is_empty());
+
+        read_through.access.lock().get_mut("a").unwrap().last_access =
+            Some(Instant::now() - Duration::from_secs(2));
+        assert_eq!(read_through.idle_collections(), vec!["a".to_string()]);
+
+        read_through.mark_evicted("a");
+        assert!(read_through.idle_collections().is_empty());
+        let status = read_through.status(|_| false);
+        assert_eq!(status[0].loads, 1);
+        assert_eq!(status[0].remote_collection, "a");
+        assert!(status[1].loaded_at.is_none());
+    }
+
+    #[test]
+    fn store_loads_from_a_vecdb_origin_and_evicts_when_idle() {
+        let origin = VectorStore::new_cpu_only();
+        let collection_config = CollectionConfig {
+            dimension: 4,
+            ..CollectionConfig::default()
+        };
+        origin
+            .create_collection("archived", collection_config)
+            .unwrap();
+        origin
+            .insert(
+                "archived",
+                vec![
+                    Vector::new("a".to_string(), vec![1.0, 0.0, 0.0, 0.0]),
+                    Vector::new("b".to_string(), vec![0.0, 1.0, 0.0, 0.0]),
+                ],
+            )
+            .unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        crate::storage::StorageCompactor::new(dir.path(), 3, 1000)
+            .compact_from_memory(&origin)
+            .unwrap();
+
+        let store = VectorStore::new_cpu_only();
+        store.set_read_through(
+            ReadThrough::from_config(&config(vec![ReadThroughCollectionConfig {
+                name: "served".to_string(),
+                origin: CollectionOrigin::Vecdb {
+                    location: dir.path().to_str().unwrap().to_string(),
+                    collection: Some("archived".to_string()),
+                },
+            }]))
+            .unwrap(),
+        );
+        assert!(store.is_read_through("served"));
+        assert!(!store.has_collection_in_memory("served"));
+
+        assert_eq!(store.get_collection("served").unwrap().vector_count(), 2);
+        assert!(store.has_collection_in_memory("served"));
+        assert!(store.evict_idle_read_through().is_empty());
+
+        let read_through = store.read_through.read().clone().unwrap();
+        read_through
+            .access
+            .lock()
+            .get_mut("served")
+            .unwrap()
+            .last_access = Some(Instant::now() - Duration::from_secs(3600));
+        assert_eq!(store.evict_idle_read_through(), vec!["served".to_string()]);
+        assert!(!store.has_collection_in_memory("served"));
+
+        // The next access fetches it again
+        assert_eq!(store.get_collection("served").unwrap().vector_count(), 2);
+        let status = store.read_through_status();
+        assert!(status[0].loaded);
+        assert_eq!(status[0].loads, 2);
+        assert_eq!(status[0].remote_collection, "archived");
+    }
+
+    #[test]
+    fn unknown_or_missing_origins_fail_to_fetch() {
+        let dir = tempfile::tempdir().unwrap();
+        let read_through =
+            ReadThrough::from_config(&config(vec![vecdb("empty", dir.path().to_str().unwrap())]))
+                .unwrap();
+        // No archive in the directory: the fetch fails rather than
+        // producing an empty collection.
+        assert!(read_through.fetch("empty").is_err());
+        assert!(matches!(
+            read_through.fetch("unknown"),
+            Err(VectorizerError::CollectionNotFound(_))
+        ));
+    }
+}
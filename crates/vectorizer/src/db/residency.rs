@@ -0,0 +1,400 @@
+//! Lazy collection loading with LRU eviction back to disk.
+//!
+//! With `lazy_loading.enabled` the startup load leaves the collections in
+//! `vectorizer.vecdb` on disk; the first
+//! [`VectorStore::get_collection`](crate::db::VectorStore::get_collection)
+//! of one loads it. The `collection_eviction` background task then unloads
+//! the least recently used collections while more than
+//! `max_resident_collections` are in memory or their estimated size is
+//! over `memory_budget_mb`, so a server hosting many mostly idle
+//! collections only pays memory for the ones in use.
+//!
+//! Eviction never drops writes: a collection changed since it was last
+//! written to `.vecdb` stays resident until the next save has persisted
+//! it. Evicted collections are still listed, and the archive rewrite
+//! copies their entries from the previous archive instead of loading
+//! them back.
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::Serialize;
+use tracing::{debug, info, warn};
+
+use crate::background_tasks::{self, TASKS};
+use crate::config::LazyLoadingConfig;
+use crate::db::VectorStore;
+use crate::error::{Result, VectorizerError};
+
+/// A collection in memory, as seen by the eviction sweep.
+#[derive(Debug, Clone)]
+pub struct LoadedCollection {
+    /// Collection name.
+    pub name: String,
+    /// Estimated memory use in bytes.
+    pub estimated_bytes: usize,
+    /// Last change to its vectors.
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Access bookkeeping of one resident collection.
+#[derive(Debug, Default)]
+struct Resident {
+    last_access: Option<Instant>,
+    /// Newest state known to be in `.vecdb`.
+    persisted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    resident: HashMap<String, Resident>,
+    on_disk: BTreeSet<String>,
+    loads: u64,
+    evictions: u64,
+}
+
+/// Residency of one collection, as listed by `GET /admin/lazy_loading`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ResidentCollection {
+    /// Collection name.
+    pub name: String,
+    /// Estimated memory use in bytes.
+    pub estimated_bytes: usize,
+    /// Seconds since the last access, if it has been accessed.
+    pub idle_secs: Option<u64>,
+    /// Whether it has changes not yet written to `.vecdb`, which keep it
+    /// from being evicted.
+    pub dirty: bool,
+}
+
+/// Lazy loading state for `GET /admin/lazy_loading`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ResidencyStatus {
+    /// Collection limit; `0` means none.
+    pub max_resident_collections: usize,
+    /// Memory budget in bytes; `0` means none.
+    pub memory_budget_bytes: u64,
+    /// Estimated memory used by the resident collections.
+    pub resident_bytes: usize,
+    /// Collections in memory, least recently used first.
+    pub resident: Vec<ResidentCollection>,
+    /// Collections left in `.vecdb` until their next access.
+    pub on_disk: Vec<String>,
+    /// Collections loaded from disk on access since startup.
+    pub loads: u64,
+    /// Collections evicted since startup.
+    pub evictions: u64,
+}
+
+/// Which collections are in memory and which wait in `.vecdb`.
+#[derive(Debug)]
+pub struct Residency {
+    max_resident: usize,
+    memory_budget_bytes: u64,
+    sweep_interval: Duration,
+    state: Mutex<State>,
+    /// Held across a lazy load so concurrent lookups of the same
+    /// collection load it once.
+    load_lock: Mutex<()>,
+}
+
+impl Residency {
+    /// Build from the `lazy_loading` config section.
+    pub fn from_config(config: &LazyLoadingConfig) -> Result<Self> {
+        if config.sweep_interval_secs == 0 {
+            return Err(VectorizerError::InvalidConfiguration {
+                message: "lazy_loading.sweep_interval_secs must be >= 1".to_string(),
+            });
+        }
+        Ok(Self {
+            max_resident: config.max_resident_collections,
+            memory_budget_bytes: config.memory_budget_mb.saturating_mul(1024 * 1024),
+            sweep_interval: Duration::from_secs(config.sweep_interval_secs),
+            state: Mutex::new(State::default()),
+            load_lock: Mutex::new(()),
+        })
+    }
+
+    /// How often the limits are enforced.
+    pub fn sweep_interval(&self) -> Duration {
+        self.sweep_interval
+    }
+
+    /// Record an access to `name`, making it the most recently used.
+    pub fn touch(&self, name: &str) {
+        let mut state = self.state.lock();
+        state
+            .resident
+            .entry(name.to_string())
+            .or_default()
+            .last_access = Some(Instant::now());
+    }
+
+    /// Record that `name` is in `.vecdb` but was not loaded.
+    pub fn defer(&self, name: &str) {
+        let mut state = self.state.lock();
+        state.resident.remove(name);
+        state.on_disk.insert(name.to_string());
+    }
+
+    /// Whether `name` is waiting in `.vecdb` to be loaded.
+    pub fn is_on_disk(&self, name: &str) -> bool {
+        self.state.lock().on_disk.contains(name)
+    }
+
+    /// Collections waiting in `.vecdb` to be loaded.
+    pub fn on_disk(&self) -> Vec<String> {
+        self.state.lock().on_disk.iter().cloned().collect()
+    }
+
+    /// Lock serializing lazy loads.
+    pub(crate) fn lock_loads(&self) -> parking_lot::MutexGuard<'_, ()> {
+        self.load_lock.lock()
+    }
+
+    /// Record that `name` was just loaded from `.vecdb`, so it matches
+    /// the archive.
+    pub(crate) fn mark_loaded(&self, name: &str) {
+        let mut state = self.state.lock();
+        state.on_disk.remove(name);
+        state.loads += 1;
+        let entry = state.resident.entry(name.to_string()).or_default();
+        entry.last_access = Some(Instant::now());
+        entry.persisted_at = Some(Utc::now());
+    }
+
+    /// Record that the state of `names` as of `at` was written to
+    /// `.vecdb`.
+    pub fn mark_persisted(&self, names: &[String], at: DateTime<Utc>) {
+        let mut state = self.state.lock();
+        for name in names {
+            state.resident.entry(name.clone()).or_default().persisted_at = Some(at);
+        }
+    }
+
+    /// Whether a collection last changed at `updated_at` is in `.vecdb`
+    /// as it is in memory.
+    pub fn is_clean(&self, name: &str, updated_at: DateTime<Utc>) -> bool {
+        self.state
+            .lock()
+            .resident
+            .get(name)
+            .and_then(|r| r.persisted_at)
+            .is_some_and(|at| updated_at <= at)
+    }
+
+    /// Record that `name` was unloaded and is back to waiting in
+    /// `.vecdb`.
+    pub(crate) fn mark_evicted(&self, name: &str) {
+        let mut state = self.state.lock();
+        state.resident.remove(name);
+        state.on_disk.insert(name.to_string());
+        state.evictions += 1;
+    }
+
+    /// Forget a deleted collection. Returns whether it was waiting in
+    /// `.vecdb`.
+    pub fn forget(&self, name: &str) -> bool {
+        let mut state = self.state.lock();
+        state.resident.remove(name);
+        state.on_disk.remove(name)
+    }
+
+    /// Clean collections to evict, least recently used first, until the
+    /// rest of `loaded` fits the limits.
+    pub fn eviction_candidates(&self, loaded: &[LoadedCollection]) -> Vec<String> {
+        let state = self.state.lock();
+        let mut count = loaded.len();
+        let mut bytes: u64 = loaded.iter().map(|c| c.estimated_bytes as u64).sum();
+        let over = |count: usize, bytes: u64| {
+            (self.max_resident > 0 && count > self.max_resident)
+                || (self.memory_budget_bytes > 0 && bytes > self.memory_budget_bytes)
+        };
+        if !over(count, bytes) {
+            return Vec::new();
+        }
+
+        let mut by_age: Vec<(&LoadedCollection, Option<&Resident>)> = loaded
+            .iter()
+            .map(|c| (c, state.resident.get(&c.name)))
+            .collect();
+        by_age.sort_by_key(|(_, r)| r.and_then(|r| r.last_access));
+
+        let mut victims = Vec::new();
+        for (collection, resident) in by_age {
+            if !over(count, bytes) {
+                break;
+            }
+            let clean = resident
+                .and_then(|r| r.persisted_at)
+                .is_some_and(|at| collection.updated_at <= at);
+            if !clean {
+                continue;
+            }
+            count -= 1;
+            bytes = bytes.saturating_sub(collection.estimated_bytes as u64);
+            victims.push(collection.name.clone());
+        }
+        victims
+    }
+
+    /// Status of the resident and on-disk collections.
+    pub fn status(&self, loaded: &[LoadedCollection]) -> ResidencyStatus {
+        let state = self.state.lock();
+        let mut resident: Vec<(Option<Instant>, ResidentCollection)> = loaded
+            .iter()
+            .map(|c| {
+                let entry = state.resident.get(&c.name);
+                let last_access = entry.and_then(|r| r.last_access);
+                let persisted_at = entry.and_then(|r| r.persisted_at);
+                (
+                    last_access,
+                    ResidentCollection {
+                        name: c.name.clone(),
+                        estimated_bytes: c.estimated_bytes,
+                        idle_secs: last_access.map(|at| at.elapsed().as_secs()),
+                        dirty: persisted_at.is_none_or(|at| c.updated_at > at),
+                    },
+                )
+            })
+            .collect();
+        resident.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.name.cmp(&b.1.name)));
+
+        ResidencyStatus {
+            max_resident_collections: self.max_resident,
+            memory_budget_bytes: self.memory_budget_bytes,
+            resident_bytes: loaded.iter().map(|c| c.estimated_bytes).sum(),
+            resident: resident.into_iter().map(|(_, r)| r).collect(),
+            on_disk: state.on_disk.iter().cloned().collect(),
+            loads: state.loads,
+            evictions: state.evictions,
+        }
+    }
+}
+
+/// Handle to the `collection_eviction` background task.
+pub struct ResidencyEvictor {
+    shutdown: Arc<AtomicBool>,
+}
+
+impl ResidencyEvictor {
+    /// Spawn a loop that evicts the store's least recently used
+    /// collections every `interval`.
+    pub fn spawn(store: Arc<VectorStore>, interval: Duration) -> Self {
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let shutdown_clone = shutdown.clone();
+
+        tokio::spawn(async move {
+            let task = TASKS.register(background_tasks::COLLECTION_EVICTION);
+            info!(
+                "Collection eviction started (interval {}s)",
+                interval.as_secs()
+            );
+
+            loop {
+                task.schedule(interval);
+                tokio::time::sleep(interval).await;
+
+                if shutdown_clone.load(Ordering::Relaxed) {
+                    info!("Collection eviction shutting down");
+                    break;
+                }
+                if task.is_paused() {
+                    debug!("Collection eviction paused, skipping sweep");
+                    continue;
+                }
+
+                let store = store.clone();
+                task.start();
+                let sweep = tokio::task::spawn_blocking(move || store.evict_lru_collections());
+                let result = sweep.await;
+                task.finish(&result);
+                if let Err(e) = result {
+                    warn!("Collection eviction sweep panicked: {}", e);
+                }
+            }
+            task.stop();
+        });
+
+        Self { shutdown }
+    }
+
+    /// Signal the eviction task to stop on the next wake-up.
+    pub fn stop(&self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn loaded(name: &str, mb: usize, updated_at: DateTime<Utc>) -> LoadedCollection {
+        LoadedCollection {
+            name: name.to_string(),
+            estimated_bytes: mb * 1024 * 1024,
+            updated_at,
+        }
+    }
+
+    fn with_limits(max_resident: usize, memory_budget_mb: u64) -> Residency {
+        Residency::from_config(&LazyLoadingConfig {
+            enabled: true,
+            max_resident_collections: max_resident,
+            memory_budget_mb,
+            sweep_interval_secs: 1,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn evicts_least_recently_used_clean_collections() {
+        let residency = with_limits(2, 0);
+        let before = Utc::now() - chrono::Duration::seconds(10);
+        for name in ["old", "dirty", "recent"] {
+            residency.mark_loaded(name);
+        }
+        residency.touch("dirty");
+        residency.touch("recent");
+        let collections = vec![
+            loaded("recent", 1, before),
+            loaded("dirty", 1, Utc::now() + chrono::Duration::seconds(10)),
+            loaded("old", 1, before),
+        ];
+        assert_eq!(residency.eviction_candidates(&collections), vec!["old"]);
+
+        // A collection never written to .vecdb stays however far over the
+        // budget it is
+        let residency = with_limits(0, 1);
+        residency.touch("never_saved");
+        let collections = vec![loaded("never_saved", 4, Utc::now())];
+        assert!(residency.eviction_candidates(&collections).is_empty());
+    }
+
+    #[test]
+    fn memory_budget_and_status() {
+        let residency = with_limits(0, 3);
+        let before = Utc::now() - chrono::Duration::seconds(10);
+        residency.mark_loaded("a");
+        residency.mark_loaded("b");
+        residency.touch("b");
+        let collections = vec![loaded("a", 2, before), loaded("b", 2, before)];
+        assert_eq!(residency.eviction_candidates(&collections), vec!["a"]);
+
+        residency.mark_evicted("a");
+        assert!(residency.is_on_disk("a"));
+        let status = residency.status(&collections[1..]);
+        assert_eq!(status.on_disk, vec!["a"]);
+        assert_eq!(status.evictions, 1);
+        assert_eq!(status.loads, 2);
+        assert!(!status.resident[0].dirty);
+
+        assert!(residency.forget("a"));
+        assert!(residency.on_disk().is_empty());
+    }
+}
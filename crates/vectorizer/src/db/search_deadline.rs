@@ -0,0 +1,115 @@
+//! Per-request search deadlines.
+//!
+//! A [`SearchDeadline`] is threaded through the search paths that loop
+//! under this crate's control — the on-disk HNSW beam search, exact
+//! scans, binary rescoring and the per-shard fan-out — which check it
+//! between steps and stop early. The in-memory HNSW walk is a single
+//! hnsw_rs call and cannot be interrupted; it is skipped when the
+//! deadline has already passed and otherwise runs to completion.
+//!
+//! A search that stopped early returns what it had ranked so far in a
+//! [`SearchOutcome`] with `timed_out` set, rather than an error.
+
+use std::time::{Duration, Instant};
+
+use crate::models::SearchResult;
+
+/// Point in time after which a search should stop and return what it
+/// has. The default has no deadline and never expires.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchDeadline {
+    at: Option<Instant>,
+}
+
+impl SearchDeadline {
+    /// No deadline.
+    pub const NONE: Self = Self { at: None };
+
+    /// A deadline `timeout` from now.
+    pub fn after(timeout: Duration) -> Self {
+        Self {
+            at: Instant::now().checked_add(timeout),
+        }
+    }
+
+    /// A deadline `timeout_ms` from now, or none for `None`.
+    pub fn from_timeout_ms(timeout_ms: Option<u64>) -> Self {
+        timeout_ms.map_or(Self::NONE, |ms| Self::after(Duration::from_millis(ms)))
+    }
+
+    /// The earlier of two deadlines; no deadline counts as the latest.
+    pub fn earlier(self, other: Self) -> Self {
+        match (self.at, other.at) {
+            (Some(a), Some(b)) => Self { at: Some(a.min(b)) },
+            (a, b) => Self { at: a.or(b) },
+        }
+    }
+
+    /// Whether the deadline has passed.
+    pub fn expired(&self) -> bool {
+        self.at.is_some_and(|at| Instant::now() >= at)
+    }
+}
+
+/// How much of the HNSW graph a search explored. A beam or visit count
+/// that drifts down while latency stays flat is the early sign of
+/// recall quietly dropping.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchStats {
+    /// Beam width (`ef_search`) of the graph walk.
+    pub ef_search: usize,
+    /// Graph nodes whose distance to the query was computed.
+    pub candidates_visited: u64,
+}
+
+/// Results of a search run under a [`SearchDeadline`].
+#[derive(Debug, Clone, Default)]
+pub struct SearchOutcome {
+    /// Best results found, best first.
+    pub results: Vec<SearchResult>,
+    /// The deadline passed before the search finished, so `results` may
+    /// miss neighbours a full search would have found.
+    pub timed_out: bool,
+    /// Graph walk statistics; `None` for searches that did not walk an
+    /// HNSW graph (exact scans, binary pre-search, hybrid and sharded
+    /// searches).
+    pub stats: Option<SearchStats>,
+}
+
+impl SearchOutcome {
+    /// A search that ran to completion.
+    pub fn complete(results: Vec<SearchResult>) -> Self {
+        Self {
+            results,
+            timed_out: false,
+            stats: None,
+        }
+    }
+
+    /// A search that never started because the deadline had passed.
+    pub fn expired() -> Self {
+        Self {
+            results: Vec::new(),
+            timed_out: true,
+            stats: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deadlines_expire_only_when_set() {
+        assert!(!SearchDeadline::NONE.expired());
+        assert!(!SearchDeadline::from_timeout_ms(None).expired());
+        assert!(SearchDeadline::from_timeout_ms(Some(0)).expired());
+        assert!(!SearchDeadline::after(Duration::from_secs(60)).expired());
+
+        let soon = SearchDeadline::from_timeout_ms(Some(0));
+        let late = SearchDeadline::after(Duration::from_secs(60));
+        assert_eq!(late.earlier(soon), soon);
+        assert_eq!(SearchDeadline::NONE.earlier(late), late);
+    }
+}
@@ -12,6 +12,7 @@ use tracing::{debug, info, warn};
 
 use super::HybridSearchConfig;
 use super::collection::Collection;
+use super::search_deadline::{SearchDeadline, SearchOutcome};
 use super::sharding::{ShardId, ShardRebalancer, ShardRouter};
 use crate::error::{Result, VectorizerError};
 use crate::models::{CollectionConfig, SearchResult, SparseVector, Vector};
@@ -67,7 +68,7 @@ impl ShardedCollection {
             shard_config.sharding = None; // Shards themselves are not sharded
 
             let shard_name = format!("{}_{}", name, shard_id);
-            let shard_collection = Collection::new(shard_name, shard_config);
+            let shard_collection = Collection::try_new(shard_name, shard_config)?;
             shards.insert(shard_id, shard_collection);
         }
 
@@ -233,22 +234,44 @@ impl ShardedCollection {
         k: usize,
         shard_keys: Option<&[ShardId]>,
     ) -> Result<Vec<SearchResult>> {
+        Ok(self
+            .search_until(query_vector, k, shard_keys, &SearchDeadline::NONE)?
+            .results)
+    }
+
+    /// [`Self::search`] under a per-request deadline. Shards are searched
+    /// one after another; once the deadline passes the remaining shards
+    /// are skipped and the hits gathered so far are merged, with
+    /// `timed_out` set.
+    pub fn search_until(
+        &self,
+        query_vector: &[f32],
+        k: usize,
+        shard_keys: Option<&[ShardId]>,
+        deadline: &SearchDeadline,
+    ) -> Result<SearchOutcome> {
         // Get shards to search
         let shard_ids = self.router.route_search(shard_keys);
 
         if shard_ids.is_empty() {
-            return Ok(Vec::new());
+            return Ok(SearchOutcome::default());
         }
 
         // Search each shard
         let mut all_results = Vec::new();
+        let mut timed_out = false;
         let shard_count = shard_ids.len();
 
         for shard_id in shard_ids {
+            if deadline.expired() {
+                timed_out = true;
+                break;
+            }
             if let Some(shard) = self.shards.get(&shard_id) {
-                match shard.search(query_vector, k) {
-                    Ok(results) => {
-                        all_results.extend(results);
+                match shard.search_until(query_vector, k, deadline) {
+                    Ok(outcome) => {
+                        timed_out |= outcome.timed_out;
+                        all_results.extend(outcome.results);
                     }
                     Err(e) => {
                         warn!("Error searching shard {}: {}", shard_id, e);
@@ -269,13 +292,18 @@ impl ShardedCollection {
         all_results.truncate(k);
 
         debug!(
-            "Multi-shard search in collection '{}' returned {} results from {} shards",
+            "Multi-shard search in collection '{}' returned {} results from {} shards (timed_out={})",
             self.name,
             all_results.len(),
-            shard_count
+            shard_count,
+            timed_out
         );
 
-        Ok(all_results)
+        Ok(SearchOutcome {
+            results: all_results,
+            timed_out,
+            stats: None,
+        })
     }
 
     /// Perform hybrid search across all shards and merge results
@@ -377,7 +405,7 @@ impl ShardedCollection {
         shard_config.sharding = None;
 
         let shard_name = format!("{}_{}", self.name, shard_id);
-        let shard_collection = Collection::new(shard_name, shard_config);
+        let shard_collection = Collection::try_new(shard_name, shard_config)?;
         self.shards.insert(shard_id, shard_collection);
 
         info!("Added shard {} to collection '{}'", shard_id, self.name);
@@ -500,12 +528,19 @@ mod tests {
             embedding_provider: "bm25".to_string(),
             normalization: None,
             encryption: None,
+            id_policy: None,
+            quota: None,
+            scoring: None,
+            tenant_partition: None,
+            dedup: None,
+            chunking: None,
             storage_type: None,
             sharding: Some(crate::models::ShardingConfig {
                 shard_count: 4,
                 virtual_nodes_per_shard: 10, // Lower for tests
                 rebalance_threshold: 0.2,
             }),
+            full_text: None,
         }
     }
 
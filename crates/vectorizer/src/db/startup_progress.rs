@@ -0,0 +1,316 @@
+//! Startup load progress behind `GET /startup/progress`.
+//!
+//! The background task that restores persisted collections and indexes
+//! workspace projects records every collection here as it moves from
+//! pending to loading and then to indexed or failed (or deferred, when
+//! lazy loading leaves it on disk), so orchestration and the dashboard
+//! can wait on the load instead of scraping the `COLLECTION_LOAD_STEP_*`
+//! log lines.
+
+use std::collections::BTreeMap;
+use std::time::Instant;
+
+use parking_lot::RwLock;
+use serde::Serialize;
+
+/// Where a collection is in the startup load.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CollectionLoadState {
+    /// Known but not picked up yet.
+    Pending,
+    /// Being restored or indexed.
+    Loading,
+    /// Serving queries.
+    Indexed,
+    /// Could not be loaded; see the entry's `error`.
+    Failed,
+    /// Left on disk by lazy loading; loaded on first access.
+    Deferred,
+}
+
+/// Where a collection is loaded from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LoadSource {
+    /// The `.vecdb` archive or legacy raw files in the data directory.
+    Persisted,
+    /// A project in the workspace file, indexed from its sources.
+    Workspace,
+}
+
+/// Step the background load is on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StartupPhase {
+    /// The load has not started.
+    Starting,
+    /// Restoring collections from the data directory.
+    LoadingPersisted,
+    /// Indexing workspace collections that were not persisted.
+    IndexingWorkspace,
+    /// Done; every collection is indexed or failed.
+    Complete,
+}
+
+/// One collection in the startup load.
+#[derive(Debug, Clone, Serialize)]
+pub struct CollectionLoadProgress {
+    /// Current state.
+    pub state: CollectionLoadState,
+    /// Where the collection is loaded from.
+    pub source: LoadSource,
+    /// Vectors in the archive while pending or loading, when known, and
+    /// vectors in memory once indexed.
+    pub vectors: Option<usize>,
+    /// Why the load failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Time spent loading, once indexed or failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub elapsed_ms: Option<u64>,
+    #[serde(skip)]
+    started: Option<Instant>,
+}
+
+/// Collections per state.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct LoadStateCounts {
+    /// Collections not picked up yet.
+    pub pending: usize,
+    /// Collections being restored or indexed.
+    pub loading: usize,
+    /// Collections serving queries.
+    pub indexed: usize,
+    /// Collections that could not be loaded.
+    pub failed: usize,
+    /// Collections left on disk until first access.
+    pub deferred: usize,
+}
+
+/// Point-in-time view of the startup load.
+#[derive(Debug, Clone, Serialize)]
+pub struct StartupProgressReport {
+    /// Current step.
+    pub phase: StartupPhase,
+    /// Whether the load has finished.
+    pub complete: bool,
+    /// Time since the load started, or its total duration once complete.
+    pub elapsed_ms: u64,
+    /// Why the load as a whole stopped early, e.g. an unreadable archive.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Collections per state.
+    pub counts: LoadStateCounts,
+    /// Vectors in indexed collections.
+    pub vectors_loaded: usize,
+    /// Every collection seen so far, by name.
+    pub collections: BTreeMap<String, CollectionLoadProgress>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    phase: StartupPhase,
+    finished_at: Option<Instant>,
+    error: Option<String>,
+    collections: BTreeMap<String, CollectionLoadProgress>,
+}
+
+/// Shared tracker written by the loading task and read by the API.
+#[derive(Debug)]
+pub struct StartupProgress {
+    started_at: Instant,
+    inner: RwLock<Inner>,
+}
+
+impl Default for StartupProgress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StartupProgress {
+    /// Tracker for a load that has not started.
+    pub fn new() -> Self {
+        Self {
+            started_at: Instant::now(),
+            inner: RwLock::new(Inner {
+                phase: StartupPhase::Starting,
+                finished_at: None,
+                error: None,
+                collections: BTreeMap::new(),
+            }),
+        }
+    }
+
+    /// Tracker for a server with nothing to load at startup.
+    pub fn completed() -> Self {
+        let progress = Self::new();
+        progress.finish(None);
+        progress
+    }
+
+    /// Move to `phase`.
+    pub fn set_phase(&self, phase: StartupPhase) {
+        self.inner.write().phase = phase;
+    }
+
+    /// Current step.
+    pub fn phase(&self) -> StartupPhase {
+        self.inner.read().phase
+    }
+
+    /// Whether the load has finished.
+    pub fn is_complete(&self) -> bool {
+        self.phase() == StartupPhase::Complete
+    }
+
+    /// Mark the load finished, with the error that stopped it early if
+    /// any.
+    pub fn finish(&self, error: Option<String>) {
+        let mut inner = self.inner.write();
+        inner.phase = StartupPhase::Complete;
+        inner.finished_at = Some(Instant::now());
+        if error.is_some() {
+            inner.error = error;
+        }
+    }
+
+    /// Register a collection that will be loaded, with the vectors it
+    /// holds when the source says so.
+    pub fn pending(&self, name: &str, source: LoadSource, vectors: Option<usize>) {
+        self.update(name, source, |entry| {
+            entry.state = CollectionLoadState::Pending;
+            entry.vectors = vectors;
+        });
+    }
+
+    /// A collection is being restored or indexed.
+    pub fn loading(&self, name: &str, source: LoadSource) {
+        self.update(name, source, |entry| {
+            entry.state = CollectionLoadState::Loading;
+            entry.started = Some(Instant::now());
+        });
+    }
+
+    /// A collection is serving queries with `vectors` vectors.
+    pub fn indexed(&self, name: &str, source: LoadSource, vectors: usize) {
+        self.update(name, source, |entry| {
+            entry.state = CollectionLoadState::Indexed;
+            entry.vectors = Some(vectors);
+            entry.error = None;
+            entry.elapsed_ms = entry.started.map(|at| at.elapsed().as_millis() as u64);
+        });
+    }
+
+    /// A collection could not be loaded.
+    pub fn failed(&self, name: &str, source: LoadSource, error: impl Into<String>) {
+        let error = error.into();
+        self.update(name, source, |entry| {
+            entry.state = CollectionLoadState::Failed;
+            entry.error = Some(error);
+            entry.elapsed_ms = entry.started.map(|at| at.elapsed().as_millis() as u64);
+        });
+    }
+
+    /// A collection with `vectors` vectors stays on disk until first
+    /// accessed.
+    pub fn deferred(&self, name: &str, source: LoadSource, vectors: usize) {
+        self.update(name, source, |entry| {
+            entry.state = CollectionLoadState::Deferred;
+            entry.vectors = Some(vectors);
+        });
+    }
+
+    /// State of one collection, if the load has seen it.
+    pub fn state(&self, name: &str) -> Option<CollectionLoadState> {
+        self.inner.read().collections.get(name).map(|e| e.state)
+    }
+
+    /// Snapshot for `GET /startup/progress`.
+    pub fn report(&self) -> StartupProgressReport {
+        let inner = self.inner.read();
+        let mut counts = LoadStateCounts::default();
+        let mut vectors_loaded = 0;
+        for entry in inner.collections.values() {
+            match entry.state {
+                CollectionLoadState::Pending => counts.pending += 1,
+                CollectionLoadState::Loading => counts.loading += 1,
+                CollectionLoadState::Indexed => {
+                    counts.indexed += 1;
+                    vectors_loaded += entry.vectors.unwrap_or(0);
+                }
+                CollectionLoadState::Failed => counts.failed += 1,
+                CollectionLoadState::Deferred => counts.deferred += 1,
+            }
+        }
+        let elapsed = inner
+            .finished_at
+            .unwrap_or_else(Instant::now)
+            .duration_since(self.started_at);
+
+        StartupProgressReport {
+            phase: inner.phase,
+            complete: inner.phase == StartupPhase::Complete,
+            elapsed_ms: elapsed.as_millis() as u64,
+            error: inner.error.clone(),
+            counts,
+            vectors_loaded,
+            collections: inner.collections.clone(),
+        }
+    }
+
+    fn update(&self, name: &str, source: LoadSource, f: impl FnOnce(&mut CollectionLoadProgress)) {
+        let mut inner = self.inner.write();
+        let entry = inner
+            .collections
+            .entry(name.to_string())
+            .or_insert_with(|| CollectionLoadProgress {
+                state: CollectionLoadState::Pending,
+                source,
+                vectors: None,
+                error: None,
+                elapsed_ms: None,
+                started: None,
+            });
+        entry.source = source;
+        f(entry);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collections_move_through_states() {
+        let progress = StartupProgress::new();
+        progress.set_phase(StartupPhase::LoadingPersisted);
+        progress.pending("docs", LoadSource::Persisted, Some(10));
+        progress.pending("code", LoadSource::Persisted, Some(5));
+        progress.loading("docs", LoadSource::Persisted);
+        progress.indexed("docs", LoadSource::Persisted, 10);
+        progress.loading("code", LoadSource::Persisted);
+        progress.failed("code", LoadSource::Persisted, "checksum mismatch");
+        progress.loading("notes", LoadSource::Workspace);
+        progress.deferred("archive", LoadSource::Persisted, 7);
+
+        let report = progress.report();
+        assert!(!report.complete);
+        assert_eq!(report.counts.indexed, 1);
+        assert_eq!(report.counts.failed, 1);
+        assert_eq!(report.counts.loading, 1);
+        assert_eq!(report.counts.deferred, 1);
+        assert_eq!(report.vectors_loaded, 10);
+        assert_eq!(
+            report.collections["code"].error.as_deref(),
+            Some("checksum mismatch")
+        );
+        assert_eq!(report.collections["notes"].source, LoadSource::Workspace);
+
+        progress.finish(None);
+        assert!(progress.is_complete());
+        assert_eq!(progress.state("docs"), Some(CollectionLoadState::Indexed));
+        assert!(StartupProgress::completed().report().complete);
+    }
+}
@@ -104,6 +104,22 @@ impl VectorStorageBackend {
         }
     }
 
+    /// Run `f` on the payload of `id` without materializing the vector;
+    /// `None` when there is no such vector.
+    pub fn with_payload<T>(&self, id: &str, f: impl FnOnce(Option<&Payload>) -> T) -> Option<T> {
+        match self {
+            Self::Memory(map) => map.read().get(id).map(|v| f(v.payload.as_ref())),
+            Self::Mmap {
+                id_map, payloads, ..
+            } => {
+                if !id_map.read().contains_key(id) {
+                    return None;
+                }
+                Some(f(payloads.read().get(id)))
+            }
+        }
+    }
+
     pub fn contains_key(&self, id: &str) -> Result<bool> {
         match self {
             Self::Memory(map) => Ok(map.read().contains_key(id)),
@@ -186,4 +202,12 @@ impl VectorStorageBackend {
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    /// Backing file of an mmap backend; `None` for in-memory storage.
+    pub fn mmap_path(&self) -> Option<std::path::PathBuf> {
+        match self {
+            Self::Memory(_) => None,
+            Self::Mmap { storage, .. } => Some(storage.read().path().to_path_buf()),
+        }
+    }
 }
@@ -0,0 +1,88 @@
+//! Tokenizer and BM25 parameters shared by the payload text index and
+//! full-text search.
+//!
+//! The tokenizer deliberately keeps identifiers intact: a token is a
+//! maximal run of alphanumerics and `_`, lowercased. `foo::bar_baz`
+//! yields `foo` + `bar_baz`; `E0425` yields `e0425`. Documents and
+//! queries must be split by the same rules, so both sides call
+//! [`tokenize`] from here.
+
+use serde::{Deserialize, Serialize};
+
+/// BM25 tuning parameters.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Bm25Params {
+    /// Term-frequency saturation.
+    #[serde(default = "default_k1")]
+    pub k1: f32,
+    /// Length normalisation (0 = none, 1 = full).
+    #[serde(default = "default_b")]
+    pub b: f32,
+}
+
+fn default_k1() -> f32 {
+    1.2
+}
+
+fn default_b() -> f32 {
+    0.75
+}
+
+impl Default for Bm25Params {
+    fn default() -> Self {
+        Self {
+            k1: default_k1(),
+            b: default_b(),
+        }
+    }
+}
+
+/// Split `text` into lowercase identifier-preserving tokens.
+pub fn tokenize(text: &str) -> Vec<String> {
+    token_spans(text)
+        .into_iter()
+        .map(|(start, end)| text[start..end].to_lowercase())
+        .collect()
+}
+
+/// Byte ranges of every token in `text`.
+pub fn token_spans(text: &str) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut start: Option<usize> = None;
+    for (i, c) in text.char_indices() {
+        let is_token_char = c.is_alphanumeric() || c == '_';
+        match (is_token_char, start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                spans.push((s, i));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        spans.push((s, text.len()));
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokenizer_keeps_identifiers_whole() {
+        assert_eq!(
+            tokenize("Error E0425 in foo::parse_header_v2()"),
+            vec!["error", "e0425", "in", "foo", "parse_header_v2"]
+        );
+    }
+
+    #[test]
+    fn spans_cover_multibyte_text() {
+        let text = "café_1 — ok";
+        let spans = token_spans(text);
+        let tokens: Vec<&str> = spans.iter().map(|&(s, e)| &text[s..e]).collect();
+        assert_eq!(tokens, vec!["café_1", "ok"]);
+    }
+}
@@ -122,6 +122,7 @@ impl VectorStore {
     /// Delete an alias by name
     pub fn delete_alias(&self, alias: &str) -> Result<()> {
         if self.aliases.remove(alias).is_some() {
+            self.notify_name(alias);
             info!("Alias '{}' deleted", alias);
             Ok(())
         } else {
@@ -163,6 +164,7 @@ impl VectorStore {
 
         self.aliases
             .insert(new_alias.to_string(), target_name.clone());
+        self.notify_name(old_alias);
         info!(
             "Alias '{}' renamed to '{}' for collection '{}'",
             old_alias, new_alias, target_name
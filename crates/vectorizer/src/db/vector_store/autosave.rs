@@ -135,6 +135,11 @@ impl VectorStore {
             config: Some(metadata.config.clone()),
             vectors,
             hnsw_dump_basename: None,
+            recall_profile: collection.recall_profile(),
+            feedback: collection.feedback(),
+            metadata_kv: collection.metadata_kv(),
+            hybrid_tuning: collection.hybrid_tuning(),
+            overrides: collection.overrides(),
         };
 
         // Save vectors to binary file (following workspace pattern)
@@ -237,6 +242,11 @@ impl VectorStore {
             config: Some(metadata.config.clone()),
             vectors: vectors.clone(),
             hnsw_dump_basename: None,
+            recall_profile: collection.recall_profile(),
+            feedback: collection.feedback(),
+            metadata_kv: collection.metadata_kv(),
+            hybrid_tuning: collection.hybrid_tuning(),
+            overrides: collection.overrides(),
         };
 
         // Create persisted vector store with version
@@ -257,6 +267,11 @@ impl VectorStore {
             config: Some(metadata.config.clone()),
             vectors,
             hnsw_dump_basename: None,
+            recall_profile: collection.recall_profile(),
+            feedback: collection.feedback(),
+            metadata_kv: collection.metadata_kv(),
+            hybrid_tuning: collection.hybrid_tuning(),
+            overrides: collection.overrides(),
         };
 
         // Save metadata to JSON file
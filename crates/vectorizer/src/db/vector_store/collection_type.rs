@@ -9,15 +9,21 @@
 //! router — and those limitations are returned as explicit errors or
 //! warnings rather than silent fallbacks.
 
+use std::sync::Arc;
+
 use tracing::{debug, warn};
 
-use crate::db::collection::Collection;
+use crate::db::collection::{BatchQuery, Collection};
 use crate::db::distributed_sharded_collection::DistributedShardedCollection;
 #[cfg(feature = "hive-gpu")]
 use crate::db::hive_gpu_collection::HiveGpuCollection;
+use crate::db::search_deadline::{SearchDeadline, SearchOutcome};
 use crate::db::sharded_collection::ShardedCollection;
 use crate::error::{Result, VectorizerError};
-use crate::models::{CollectionConfig, CollectionMetadata, SearchResult, Vector};
+use crate::models::{
+    CollectionConfig, CollectionMetadata, Duplicate, Payload, ScoringConfig, SearchResult, Vector,
+};
+use crate::search::full_text::{FullTextHit, FullTextQuery};
 
 /// Enum to represent different collection types (CPU, GPU, or Sharded)
 pub enum CollectionType {
@@ -83,6 +89,120 @@ impl CollectionType {
         }
     }
 
+    /// Register an additional payload field index. Only CPU collections
+    /// maintain a payload index; other variants ignore the call.
+    pub fn add_payload_index(&self, config: crate::db::payload_index::PayloadIndexConfig) {
+        match self {
+            CollectionType::Cpu(c) => c.add_payload_index(config),
+            _ => debug!(
+                "Payload index '{}' ignored: collection '{}' has no payload index",
+                config.field_name,
+                self.name()
+            ),
+        }
+    }
+
+    /// Storage backend holding the raw vectors. Only CPU collections can
+    /// be mmap-backed; every other variant reports `Memory`.
+    pub fn storage_type(&self) -> crate::models::StorageType {
+        match self {
+            CollectionType::Cpu(c) => c.storage_type(),
+            _ => crate::models::StorageType::Memory,
+        }
+    }
+
+    /// Whether searches are served from an on-disk HNSW index. Only CPU
+    /// collections can be cold.
+    pub fn is_cold(&self) -> bool {
+        match self {
+            CollectionType::Cpu(c) => c.is_cold(),
+            _ => false,
+        }
+    }
+
+    /// HNSW graph node counts, for collections the index optimizer can
+    /// compact (CPU only).
+    pub fn index_fragmentation(&self) -> Option<crate::db::IndexFragmentation> {
+        match self {
+            CollectionType::Cpu(c) => Some(c.index_fragmentation()),
+            _ => None,
+        }
+    }
+
+    /// Outcome of the most recent HNSW compactions (CPU only).
+    pub fn index_optimization_status(&self) -> Option<crate::db::IndexOptimizationStatus> {
+        match self {
+            CollectionType::Cpu(c) => Some(c.index_optimization_status()),
+            _ => None,
+        }
+    }
+
+    /// Recall / latency profile of the graph search (CPU only).
+    pub fn recall_profile(&self) -> Option<crate::db::RecallProfile> {
+        match self {
+            CollectionType::Cpu(c) => c.recall_profile(),
+            _ => None,
+        }
+    }
+
+    /// Relevance feedback on search results, if any (CPU only).
+    pub fn feedback(&self) -> Option<crate::db::RelevanceFeedback> {
+        match self {
+            CollectionType::Cpu(c) => c.feedback(),
+            _ => None,
+        }
+    }
+
+    /// Hybrid search judgements and tuned weights, if any (CPU only).
+    pub fn hybrid_tuning(&self) -> Option<crate::db::HybridTuning> {
+        match self {
+            CollectionType::Cpu(c) => c.hybrid_tuning(),
+            _ => None,
+        }
+    }
+
+    /// Fusion weights tuned from relevance judgements, if any (CPU
+    /// only).
+    pub fn tuned_hybrid_weights(&self) -> Option<crate::db::TunedHybridWeights> {
+        match self {
+            CollectionType::Cpu(c) => c.tuned_hybrid_weights(),
+            _ => None,
+        }
+    }
+
+    /// Metadata key-value store, if it holds anything (CPU only).
+    pub fn metadata_kv(&self) -> Option<crate::db::MetadataKv> {
+        match self {
+            CollectionType::Cpu(c) => c.metadata_kv(),
+            _ => None,
+        }
+    }
+
+    /// Search beam pinned for the collection, if any (CPU only).
+    pub fn pinned_ef_search(&self) -> Option<usize> {
+        match self {
+            CollectionType::Cpu(c) => c.pinned_ef_search(),
+            _ => None,
+        }
+    }
+
+    /// Seconds after insertion that new vectors expire, if set (CPU
+    /// only).
+    pub fn ttl_secs(&self) -> Option<u64> {
+        match self {
+            CollectionType::Cpu(c) => c.ttl_secs(),
+            _ => None,
+        }
+    }
+
+    /// Settings changed after creation, if any (CPU only).
+    pub fn overrides(&self) -> Option<crate::db::CollectionOverrides> {
+        match self {
+            CollectionType::Cpu(c) => c.overrides(),
+            _ => None,
+        }
+    }
+
     /// Get owner ID (for multi-tenancy in HiveHub cluster mode)
     pub fn owner_id(&self) -> Option<uuid::Uuid> {
         match self {
@@ -161,6 +281,181 @@ impl CollectionType {
         }
     }
 
+    /// Exact (brute-force) search over every vector, bypassing HNSW (see
+    /// [`Collection::search_exact`]). Only CPU collections keep their
+    /// vectors where a synchronous full scan can reach them; other
+    /// variants return an error.
+    pub fn search_exact(&self, query: &[f32], limit: usize) -> Result<Vec<SearchResult>> {
+        match self {
+            CollectionType::Cpu(c) => c.search_exact(query, limit),
+            _ => Err(VectorizerError::Storage(
+                "exact search is only supported on single-node CPU collections".to_string(),
+            )),
+        }
+    }
+
+    /// Lexical BM25 search over text-indexed payload fields (see
+    /// [`Collection::full_text_search`]). Only CPU collections keep a
+    /// payload index; other variants return an error.
+    pub fn full_text_search(&self, query: &FullTextQuery) -> Result<Vec<FullTextHit>> {
+        match self {
+            CollectionType::Cpu(c) => c.full_text_search(query),
+            _ => Err(VectorizerError::Storage(
+                "full-text search is only supported on single-node CPU collections".to_string(),
+            )),
+        }
+    }
+
+    /// [`search`] under a per-request deadline (see
+    /// [`crate::db::search_deadline`]). CPU and sharded collections stop
+    /// early and return partial results; the other backends only skip a
+    /// search whose deadline has already passed.
+    ///
+    /// [`search`]: CollectionType::search
+    pub fn search_until(
+        &self,
+        query: &[f32],
+        limit: usize,
+        deadline: &SearchDeadline,
+    ) -> Result<SearchOutcome> {
+        match self {
+            CollectionType::Cpu(c) => c.search_until(query, limit, deadline),
+            CollectionType::Sharded(c) => c.search_until(query, limit, None, deadline),
+            _ if deadline.expired() => Ok(SearchOutcome::expired()),
+            _ => self.search(query, limit).map(SearchOutcome::complete),
+        }
+    }
+
+    /// Score the vectors `ids` against `query` on the search scale (see
+    /// [`Collection::score_vectors`]). Same backend restriction as
+    /// [`search_exact`].
+    ///
+    /// [`search_exact`]: CollectionType::search_exact
+    pub fn score_vectors(&self, query: &[f32], ids: &[String]) -> Result<Vec<(String, f32)>> {
+        match self {
+            CollectionType::Cpu(c) => c.score_vectors(query, ids),
+            _ => Err(VectorizerError::Storage(
+                "scoring by ID is only supported on single-node CPU collections".to_string(),
+            )),
+        }
+    }
+
+    /// [`search_exact`] under a per-request deadline; the scan stops when
+    /// it passes. Same backend restriction as [`search_exact`].
+    ///
+    /// [`search_exact`]: CollectionType::search_exact
+    pub fn search_exact_until(
+        &self,
+        query: &[f32],
+        limit: usize,
+        deadline: &SearchDeadline,
+    ) -> Result<SearchOutcome> {
+        match self {
+            CollectionType::Cpu(c) => c.search_exact_until(query, limit, deadline),
+            _ => self.search_exact(query, limit).map(SearchOutcome::complete),
+        }
+    }
+
+    /// Search confined to `tenant`'s vectors of a partitioned collection
+    /// (see [`Collection::search_partition_until`]). Only CPU collections
+    /// can be partitioned.
+    pub fn search_partition_until(
+        &self,
+        query: &[f32],
+        limit: usize,
+        tenant: &str,
+        deadline: &SearchDeadline,
+    ) -> Result<SearchOutcome> {
+        match self {
+            CollectionType::Cpu(c) => c.search_partition_until(query, limit, tenant, deadline),
+            _ => Err(VectorizerError::Storage(
+                "tenant partitions are only supported on single-node CPU collections".to_string(),
+            )),
+        }
+    }
+
+    /// Rescore `results` by `scoring` and keep the best `k`, as a search
+    /// of a collection configured with it would. Only CPU collections
+    /// hold the relevance feedback the expression may read.
+    pub(crate) fn rescore(
+        &self,
+        scoring: &ScoringConfig,
+        results: &mut Vec<SearchResult>,
+        k: usize,
+    ) -> Result<()> {
+        match self {
+            CollectionType::Cpu(c) => {
+                c.apply_scoring(scoring, results, k);
+                Ok(())
+            }
+            _ => Err(VectorizerError::Storage(
+                "rescoring is only supported on single-node CPU collections".to_string(),
+            )),
+        }
+    }
+
+    /// The stored vector `vector` duplicates under the collection's dedup
+    /// policy (see [`Collection::find_duplicate`]). Only CPU collections
+    /// take a dedup policy; the others never report one.
+    pub fn find_duplicate(&self, vector: &Vector, hash: Option<&str>) -> Result<Option<Duplicate>> {
+        match self {
+            CollectionType::Cpu(c) => c.find_duplicate(vector, hash),
+            _ => Ok(None),
+        }
+    }
+
+    /// Run a batch of searches, returning one outcome per query in order.
+    /// CPU collections search the batch in parallel over one snapshot of
+    /// their graph (see [`Collection::search_batch_until`]); the other
+    /// backends run the queries one after the other through
+    /// [`search_until`] / [`search_exact_until`].
+    ///
+    /// [`search_until`]: CollectionType::search_until
+    /// [`search_exact_until`]: CollectionType::search_exact_until
+    pub fn search_batch_until(&self, queries: &[BatchQuery]) -> Vec<Result<SearchOutcome>> {
+        match self {
+            CollectionType::Cpu(c) => c.search_batch_until(queries),
+            _ => queries
+                .iter()
+                .map(|q| {
+                    if q.exact {
+                        self.search_exact_until(&q.vector, q.limit, &q.deadline)
+                    } else {
+                        self.search_until(&q.vector, q.limit, &q.deadline)
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    /// Search with explicit quantization parameters.
+    ///
+    /// CPU collections using `QuantizationConfig::Binary` run the Hamming
+    /// pre-search with the given `oversampling` and optional exact
+    /// `rescore` (see [`Collection::search_binary`]). Every other backend
+    /// or quantization mode ignores the parameters and uses [`search`].
+    ///
+    /// [`search`]: CollectionType::search
+    pub fn search_quantized(
+        &self,
+        query: &[f32],
+        limit: usize,
+        oversampling: f32,
+        rescore: bool,
+    ) -> Result<Vec<SearchResult>> {
+        match self {
+            CollectionType::Cpu(c)
+                if matches!(
+                    c.config().quantization,
+                    crate::models::QuantizationConfig::Binary
+                ) =>
+            {
+                c.search_binary(query, limit, oversampling, rescore)
+            }
+            _ => self.search(query, limit),
+        }
+    }
+
     /// Perform hybrid search combining dense and sparse vectors
     pub fn hybrid_search(
         &self,
@@ -190,6 +485,29 @@ impl CollectionType {
         }
     }
 
+    /// [`hybrid_search`] under a per-request deadline. Only CPU
+    /// collections stop early; the other backends skip a search whose
+    /// deadline has already passed.
+    ///
+    /// [`hybrid_search`]: CollectionType::hybrid_search
+    pub fn hybrid_search_until(
+        &self,
+        query_dense: &[f32],
+        query_sparse: Option<&crate::models::SparseVector>,
+        config: crate::db::HybridSearchConfig,
+        deadline: &SearchDeadline,
+    ) -> Result<SearchOutcome> {
+        match self {
+            CollectionType::Cpu(c) => {
+                c.hybrid_search_until(query_dense, query_sparse, config, deadline)
+            }
+            _ if deadline.expired() => Ok(SearchOutcome::expired()),
+            _ => self
+                .hybrid_search(query_dense, query_sparse, config)
+                .map(SearchOutcome::complete),
+        }
+    }
+
     /// Get collection metadata
     pub fn metadata(&self) -> CollectionMetadata {
         match self {
@@ -288,6 +606,52 @@ impl CollectionType {
         }
     }
 
+    /// Run `f` on the payload of `vector_id` without materializing the
+    /// vector where the backend allows it; `None` when there is no such
+    /// vector or the collection is distributed.
+    pub fn with_payload<T>(
+        &self,
+        vector_id: &str,
+        f: impl FnOnce(Option<&Payload>) -> T,
+    ) -> Option<T> {
+        match self {
+            CollectionType::Cpu(c) => c.with_payload(vector_id, f),
+            #[cfg(feature = "hive-gpu")]
+            CollectionType::HiveGpu(c) => c
+                .get_vector_by_id(vector_id)
+                .ok()
+                .map(|v| f(v.payload.as_ref())),
+            CollectionType::Sharded(c) => {
+                c.get_vector(vector_id).ok().map(|v| f(v.payload.as_ref()))
+            }
+            CollectionType::DistributedSharded(_) => None,
+        }
+    }
+
+    /// Ids of every vector in the collection. Empty for sharded and
+    /// distributed collections, like [`Self::get_all_vectors`].
+    pub fn vector_ids(&self) -> Vec<String> {
+        match self {
+            CollectionType::Cpu(c) => c.vector_ids(),
+            #[cfg(feature = "hive-gpu")]
+            CollectionType::HiveGpu(c) => c.get_all_vectors().into_iter().map(|v| v.id).collect(),
+            CollectionType::Sharded(_) | CollectionType::DistributedSharded(_) => Vec::new(),
+        }
+    }
+
+    /// [`Self::vector_ids`] sorted. CPU collections keep the sorted
+    /// snapshot until their next insert or delete.
+    pub fn sorted_vector_ids(&self) -> Arc<Vec<String>> {
+        match self {
+            CollectionType::Cpu(c) => c.sorted_vector_ids(),
+            _ => {
+                let mut ids = self.vector_ids();
+                ids.sort_unstable();
+                Arc::new(ids)
+            }
+        }
+    }
+
     /// Get the number of vectors in the collection
     ///
     /// For distributed collections this returns the locally-known document
@@ -332,6 +696,15 @@ impl CollectionType {
         }
     }
 
+    /// Drop `file_path` from the document registry (CPU collections
+    /// only; the others do not keep one).
+    pub fn forget_document(&self, file_path: &str) -> bool {
+        match self {
+            CollectionType::Cpu(c) => c.forget_document(file_path),
+            _ => false,
+        }
+    }
+
     /// Get estimated memory usage
     pub fn estimated_memory_usage(&self) -> usize {
         match self {
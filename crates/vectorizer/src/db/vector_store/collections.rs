@@ -12,7 +12,18 @@
 //!   on disk
 //! - [`tenancy`]    — ownership / multi-tenancy queries built on top of
 //!   the two accessors above
+//! - [`read_through`] — cold loads from an external origin and idle
+//!   eviction of read-through collections
+//! - [`residency`]  — lazy loading bookkeeping and LRU eviction back to
+//!   `.vecdb`
+//! - [`clone`]      — copying a collection into a new one, optionally
+//!   under a different config, as a tracked job
 
+mod clone;
 mod disk_load;
 mod lifecycle;
+mod read_through;
+mod residency;
 mod tenancy;
+
+pub use clone::{CLONE_BATCH_SIZE, CLONE_TEXT_FIELDS, CloneReport};
@@ -0,0 +1,195 @@
+//! Collection cloning — copy every vector of a collection into a new
+//! one, optionally under a different config.
+//!
+//! The target is created first, then the source's vectors are streamed
+//! across in batches of [`CLONE_BATCH_SIZE`] through the regular insert
+//! path, so the target's quantization, normalization and dedup policy
+//! apply as they land. When the dimension or embedding provider changes,
+//! each vector is re-embedded from the text in its payload
+//! ([`CLONE_TEXT_FIELDS`]) by the caller's embedder; vectors without
+//! text are skipped. A failed or cancelled clone deletes the target
+//! again; cancellation is checked before every batch.
+//!
+//! Vectors written to the source while the clone runs may or may not be
+//! copied. Settings changed after creation (`PATCH /collections/{name}`),
+//! feedback and tuned weights stay with the source.
+
+use serde::Serialize;
+use tracing::{info, warn};
+
+use super::super::{CollectionType, VectorStore};
+use crate::error::{Result, VectorizerError};
+use crate::jobs::Job;
+use crate::models::{CollectionConfig, Vector};
+
+/// Vectors copied per insert.
+pub const CLONE_BATCH_SIZE: usize = 256;
+
+/// Payload fields a vector is re-embedded from, in order of preference.
+pub const CLONE_TEXT_FIELDS: &[&str] = &["content", "text"];
+
+/// Outcome of [`VectorStore::clone_collection`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CloneReport {
+    /// Collection copied from.
+    pub source: String,
+    /// Collection created.
+    pub target: String,
+    /// Vectors inserted into the target.
+    pub copied: usize,
+    /// Vectors left out because they had no text to re-embed.
+    pub skipped: usize,
+    /// Whether vectors were re-embedded instead of copied.
+    pub reembedded: bool,
+}
+
+/// Text a vector is re-embedded from, if its payload has any.
+fn vector_text(vector: &Vector) -> Option<&str> {
+    let data = &vector.payload.as_ref()?.data;
+    CLONE_TEXT_FIELDS
+        .iter()
+        .find_map(|field| data.get(*field)?.as_str())
+        .filter(|text| !text.is_empty())
+}
+
+impl VectorStore {
+    /// Whether vectors of a collection configured as `source` must be
+    /// re-embedded to move into one configured as `target`.
+    pub fn clone_needs_reembedding(source: &CollectionConfig, target: &CollectionConfig) -> bool {
+        source.dimension != target.dimension
+            || source.embedding_provider != target.embedding_provider
+    }
+
+    /// Create `target` with `config` (the source's when `None`) and copy
+    /// every vector of `source` into it, reporting progress to `job`.
+    ///
+    /// `embed` turns a vector's text into a vector of the target's
+    /// dimension; it is only called when the dimension or embedding
+    /// provider changes (see [`Self::clone_needs_reembedding`]).
+    ///
+    /// # Errors
+    ///
+    /// Fails when `source` does not exist or `target` does, when
+    /// re-embedding is needed but no `embed` is given, or when creating
+    /// the target, re-embedding or inserting fails. The target is deleted
+    /// again on failure.
+    pub fn clone_collection(
+        &self,
+        source: &str,
+        target: &str,
+        config: Option<CollectionConfig>,
+        embed: Option<&dyn Fn(&str) -> Result<Vec<f32>>>,
+        job: &Job,
+    ) -> Result<CloneReport> {
+        let (source_config, owner_id, ids) = {
+            let collection = self.get_collection(source)?;
+            let ids = match &*collection {
+                CollectionType::Cpu(c) => c.oldest_vector_ids(usize::MAX),
+                other => other.get_all_vectors().into_iter().map(|v| v.id).collect(),
+            };
+            (collection.config().clone(), collection.owner_id(), ids)
+        };
+        let config = config.unwrap_or_else(|| source_config.clone());
+        let reembed = Self::clone_needs_reembedding(&source_config, &config);
+        if reembed && embed.is_none() {
+            return Err(VectorizerError::InvalidConfiguration {
+                message: format!(
+                    "cloning '{}' into dimension {} with provider '{}' needs re-embedding",
+                    source, config.dimension, config.embedding_provider
+                ),
+            });
+        }
+
+        match owner_id {
+            Some(owner_id) => self.create_collection_with_owner(target, config, owner_id)?,
+            None => self.create_collection(target, config)?,
+        }
+        info!(
+            "Cloning '{}' into '{}': {} vectors{}",
+            source,
+            target,
+            ids.len(),
+            if reembed { ", re-embedding" } else { "" }
+        );
+        job.set_total(ids.len() as u64);
+
+        let copied = self.copy_into_clone(
+            source,
+            target,
+            &ids,
+            reembed.then_some(embed).flatten(),
+            job,
+        );
+        match copied {
+            Ok((copied, skipped)) => {
+                info!(
+                    "Cloned '{}' into '{}': {} copied, {} skipped",
+                    source, target, copied, skipped
+                );
+                Ok(CloneReport {
+                    source: source.to_string(),
+                    target: target.to_string(),
+                    copied,
+                    skipped,
+                    reembedded: reembed,
+                })
+            }
+            Err(e) => {
+                warn!("Cloning '{}' into '{}' failed: {}", source, target, e);
+                if let Err(cleanup) = self.delete_collection(target) {
+                    warn!("Could not delete partial clone '{}': {}", target, cleanup);
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// Copy `ids` of `source` into `target` batch by batch. Returns the
+    /// vectors copied and skipped.
+    fn copy_into_clone(
+        &self,
+        source: &str,
+        target: &str,
+        ids: &[String],
+        embed: Option<&dyn Fn(&str) -> Result<Vec<f32>>>,
+        job: &Job,
+    ) -> Result<(usize, usize)> {
+        let (mut copied, mut skipped) = (0, 0);
+        for chunk in ids.chunks(CLONE_BATCH_SIZE) {
+            job.check_cancelled()?;
+            // Looked up per batch so writers to the source are not held
+            // off for the whole clone
+            let vectors: Vec<Vector> = {
+                let collection = self.get_collection(source)?;
+                chunk
+                    .iter()
+                    .filter_map(|id| collection.get_vector(id).ok())
+                    .collect()
+            };
+            skipped += chunk.len() - vectors.len();
+
+            let batch = match embed {
+                Some(embed) => {
+                    let mut batch = Vec::with_capacity(vectors.len());
+                    for mut vector in vectors {
+                        let Some(text) = vector_text(&vector) else {
+                            skipped += 1;
+                            continue;
+                        };
+                        vector.data = embed(text)?;
+                        vector.sparse = None;
+                        batch.push(vector);
+                    }
+                    batch
+                }
+                None => vectors,
+            };
+            copied += batch.len();
+            if !batch.is_empty() {
+                self.insert(target, batch)?;
+            }
+            job.advance(chunk.len() as u64);
+        }
+        Ok((copied, skipped))
+    }
+}
@@ -15,7 +15,9 @@ impl VectorStore {
     /// Get a reference to a collection by name.
     ///
     /// Lazy-loads from `.vecdb` (compact) or `.bin` (legacy) on disk
-    /// if the collection isn't already in memory.
+    /// if the collection isn't already in memory. Read-through
+    /// collections are fetched from their origin instead (see
+    /// [`crate::db::read_through`]).
     ///
     /// # Deadlock invariant
     ///
@@ -42,138 +44,57 @@ impl VectorStore {
 
         // Fast path: collection already loaded
         if let Some(collection) = self.collections.get(canonical_ref) {
+            self.touch_read_through(canonical_ref);
+            self.touch_resident(canonical_ref);
             return Ok(collection);
         }
 
-        // Slow path: try lazy loading from disk
-        let data_dir = Self::get_data_dir();
+        // Read-through collections come from their origin, never from
+        // the local data directory
+        if self.load_read_through(canonical_ref)? {
+            return self
+                .collections
+                .get(canonical_ref)
+                .ok_or_else(|| VectorizerError::CollectionNotFound(name.to_string()));
+        }
 
-        // First, try to load from .vecdb archive (compact format)
-        use crate::storage::{StorageFormat, StorageReader, detect_format};
-        if detect_format(&data_dir) == StorageFormat::Compact {
-            debug!(
-                "📥 Lazy loading collection '{}' from .vecdb archive",
-                canonical_ref
-            );
+        // With lazy loading, concurrent lookups of the same collection
+        // must not both build it
+        let residency = self.residency();
+        let _loading = residency.as_ref().map(|r| r.lock_loads());
+        if residency.is_some()
+            && let Some(collection) = self.collections.get(canonical_ref)
+        {
+            // Loaded by a concurrent lookup while we waited
+            self.touch_resident(canonical_ref);
+            return Ok(collection);
+        }
 
-            match StorageReader::new(&data_dir) {
-                Ok(reader) => {
-                    // Read the _vector_store.bin file from the archive
-                    let vector_store_path = format!("{}_vector_store.bin", canonical_ref);
-                    match reader.read_file(&vector_store_path) {
-                        Ok(data) => {
-                            // Try to deserialize as PersistedVectorStore first (correct format)
-                            match serde_json::from_slice::<crate::persistence::PersistedVectorStore>(
-                                &data,
-                            ) {
-                                Ok(persisted_store) => {
-                                    // Extract the first collection from the store
-                                    if let Some(mut persisted) =
-                                        persisted_store.collections.into_iter().next()
-                                    {
-                                        // BACKWARD COMPATIBILITY: If name is empty, infer from filename
-                                        if persisted.name.is_empty() {
-                                            persisted.name = canonical_ref.to_string();
-                                        }
-
-                                        // Load collection into memory
-                                        if let Err(e) = self.load_persisted_collection_from_data(
-                                            canonical_ref,
-                                            persisted,
-                                        ) {
-                                            warn!(
-                                                "Failed to load collection '{}' from .vecdb: {}",
-                                                canonical_ref, e
-                                            );
-                                            return Err(VectorizerError::CollectionNotFound(
-                                                name.to_string(),
-                                            ));
-                                        }
-
-                                        info!(
-                                            "✅ Lazy loaded collection '{}' from .vecdb",
-                                            canonical_ref
-                                        );
-
-                                        // Try again now that it's loaded
-                                        return self.collections.get(canonical_ref).ok_or_else(
-                                            || {
-                                                VectorizerError::CollectionNotFound(
-                                                    name.to_string(),
-                                                )
-                                            },
-                                        );
-                                    } else {
-                                        warn!(
-                                            "No collection found in vector store file '{}'",
-                                            vector_store_path
-                                        );
-                                    }
-                                }
-                                Err(_) => {
-                                    // Fallback: try deserializing as PersistedCollection directly (legacy format)
-                                    match serde_json::from_slice::<
-                                        crate::persistence::PersistedCollection,
-                                    >(&data)
-                                    {
-                                        Ok(mut persisted) => {
-                                            if persisted.name.is_empty() {
-                                                persisted.name = canonical_ref.to_string();
-                                            }
-
-                                            if let Err(e) = self
-                                                .load_persisted_collection_from_data(
-                                                    canonical_ref,
-                                                    persisted,
-                                                )
-                                            {
-                                                warn!(
-                                                    "Failed to load collection '{}' from .vecdb: {}",
-                                                    canonical_ref, e
-                                                );
-                                                return Err(VectorizerError::CollectionNotFound(
-                                                    name.to_string(),
-                                                ));
-                                            }
-
-                                            info!(
-                                                "✅ Lazy loaded collection '{}' from .vecdb (legacy format)",
-                                                canonical_ref
-                                            );
-
-                                            return self.collections.get(canonical_ref).ok_or_else(
-                                                || {
-                                                    VectorizerError::CollectionNotFound(
-                                                        name.to_string(),
-                                                    )
-                                                },
-                                            );
-                                        }
-                                        Err(_) => {
-                                            debug!(
-                                                "Failed to deserialize collection '{}' from .vecdb (both formats failed)",
-                                                canonical_ref
-                                            );
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            debug!(
-                                "Collection file '{}' not found in .vecdb: {}",
-                                vector_store_path, e
-                            );
-                        }
-                    }
-                }
-                Err(e) => {
-                    warn!("Failed to create StorageReader: {}", e);
-                }
+        // Slow path: try lazy loading from disk, first from the .vecdb
+        // archive (compact format)
+        if let Some(persisted) = self.archived_collection(canonical_ref) {
+            if let Err(e) = self.load_persisted_collection_from_data(canonical_ref, persisted) {
+                warn!(
+                    "Failed to load collection '{}' from .vecdb: {}",
+                    canonical_ref, e
+                );
+                return Err(VectorizerError::CollectionNotFound(name.to_string()));
             }
+
+            info!("✅ Lazy loaded collection '{}' from .vecdb", canonical_ref);
+            if let Some(residency) = &residency {
+                residency.mark_loaded(canonical_ref);
+            }
+
+            // Try again now that it's loaded
+            return self
+                .collections
+                .get(canonical_ref)
+                .ok_or_else(|| VectorizerError::CollectionNotFound(name.to_string()));
         }
 
         // Fallback: try loading from legacy _vector_store.bin file
+        let data_dir = Self::get_data_dir();
         let collection_file = data_dir.join(format!("{}_vector_store.bin", name));
 
         if collection_file.exists() {
@@ -199,6 +120,82 @@ impl VectorStore {
         Err(VectorizerError::CollectionNotFound(name.to_string()))
     }
 
+    /// Read `name`'s entry from the `.vecdb` archive without loading it.
+    /// `None` when there is no archive or it has no such collection.
+    pub fn archived_collection(
+        &self,
+        name: &str,
+    ) -> Option<crate::persistence::PersistedCollection> {
+        use crate::storage::{StorageFormat, StorageReader, detect_format};
+
+        let data_dir = Self::get_data_dir();
+        if detect_format(&data_dir) != StorageFormat::Compact {
+            return None;
+        }
+        debug!("📥 Reading collection '{}' from .vecdb archive", name);
+
+        let reader = match StorageReader::new(&data_dir) {
+            Ok(reader) => reader,
+            Err(e) => {
+                warn!("Failed to create StorageReader: {}", e);
+                return None;
+            }
+        };
+        // Read the _vector_store.bin file from the archive
+        let vector_store_path = format!("{}_vector_store.bin", name);
+        let data = match reader.read_file(&vector_store_path) {
+            Ok(data) => data,
+            Err(e) => {
+                debug!(
+                    "Collection file '{}' not found in .vecdb: {}",
+                    vector_store_path, e
+                );
+                return None;
+            }
+        };
+
+        // Try to deserialize as PersistedVectorStore first (correct
+        // format), then as a bare PersistedCollection (legacy format)
+        let persisted = match serde_json::from_slice::<crate::persistence::PersistedVectorStore>(
+            &data,
+        ) {
+            Ok(persisted_store) => {
+                // Extract the first collection from the store
+                let first = persisted_store.collections.into_iter().next();
+                if first.is_none() {
+                    warn!(
+                        "No collection found in vector store file '{}'",
+                        vector_store_path
+                    );
+                }
+                first
+            }
+            Err(_) => {
+                match serde_json::from_slice::<crate::persistence::PersistedCollection>(&data) {
+                    Ok(persisted) => {
+                        debug!("Collection '{}' is in the legacy .vecdb format", name);
+                        Some(persisted)
+                    }
+                    Err(_) => {
+                        debug!(
+                            "Failed to deserialize collection '{}' from .vecdb (both formats failed)",
+                            name
+                        );
+                        None
+                    }
+                }
+            }
+        };
+
+        persisted.map(|mut persisted| {
+            // BACKWARD COMPATIBILITY: If name is empty, infer from filename
+            if persisted.name.is_empty() {
+                persisted.name = name.to_string();
+            }
+            persisted
+        })
+    }
+
     /// Load collection from PersistedCollection data (in-memory; no file I/O here)
     pub(super) fn load_persisted_collection_from_data(
         &self,
@@ -245,6 +242,12 @@ impl VectorStore {
             }
         }
 
+        let recall_profile = persisted.recall_profile;
+        let feedback = persisted.feedback;
+        let metadata_kv = persisted.metadata_kv;
+        let hybrid_tuning = persisted.hybrid_tuning;
+        let overrides = persisted.overrides;
+
         // Convert persisted vectors to runtime vectors
         let vectors: Vec<Vector> = persisted
             .vectors
@@ -284,6 +287,14 @@ impl VectorStore {
             }
         }
 
+        drop(collection);
+        self.restore_recall_profile(name, recall_profile);
+        self.restore_feedback(name, feedback);
+        self.restore_metadata_kv(name, metadata_kv);
+        self.restore_hybrid_tuning(name, hybrid_tuning);
+        self.restore_overrides(name, overrides);
+        self.notify_mutation(name);
+
         Ok(())
     }
 
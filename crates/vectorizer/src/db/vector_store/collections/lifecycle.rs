@@ -16,7 +16,7 @@ use crate::db::sharded_collection::ShardedCollection;
 use crate::error::{Result, VectorizerError};
 #[cfg(feature = "hive-gpu")]
 use crate::gpu_adapter::GpuAdapter;
-use crate::models::CollectionConfig;
+use crate::models::{CollectionConfig, QuotaPolicy};
 
 impl VectorStore {
     /// Create a new collection
@@ -106,6 +106,66 @@ impl VectorStore {
             });
         }
 
+        if let Some(policy) = &config.id_policy {
+            policy.validate()?;
+        }
+        if let Some(quota) = &config.quota {
+            quota.validate()?;
+            // Summaries are written in plain text and need insertion order
+            if quota.policy == QuotaPolicy::Summarize
+                && (config.sharding.is_some()
+                    || config.encryption.as_ref().is_some_and(|e| e.required))
+            {
+                return Err(VectorizerError::InvalidConfiguration {
+                    message: "quota policy 'summarize' is not supported on sharded or \
+                              encrypted collections"
+                        .to_string(),
+                });
+            }
+        }
+        if let Some(scoring) = &config.scoring {
+            scoring.validate()?;
+            // The scoring stage runs in the single-node CPU search path
+            if config.sharding.is_some() {
+                return Err(VectorizerError::InvalidConfiguration {
+                    message: "custom scoring is not supported on sharded collections".to_string(),
+                });
+            }
+        }
+        if let Some(partition) = &config.tenant_partition {
+            partition.validate()?;
+            // Partitions are read through the payload index, which
+            // neither shards nor encrypted payloads expose
+            if config.sharding.is_some() || config.encryption.as_ref().is_some_and(|e| e.required) {
+                return Err(VectorizerError::InvalidConfiguration {
+                    message: "tenant partitions are not supported on sharded or encrypted \
+                              collections"
+                        .to_string(),
+                });
+            }
+        }
+        if let Some(dedup) = &config.dedup {
+            dedup.validate()?;
+            // Duplicates are looked up in the single-node CPU collection
+            if config.sharding.is_some() {
+                return Err(VectorizerError::InvalidConfiguration {
+                    message: "dedup is not supported on sharded collections".to_string(),
+                });
+            }
+        }
+        if let Some(chunking) = &config.chunking {
+            chunking.validate()?;
+        }
+        if let Some(full_text) = &config.full_text {
+            full_text.validate()?;
+        }
+        // The partitioned search, the dedup lookup and full-text search
+        // run in the CPU collection
+        let allow_gpu = allow_gpu
+            && config.tenant_partition.is_none()
+            && config.dedup.is_none()
+            && config.full_text.is_none();
+
         debug!("Creating collection '{}' with config: {:?}", name, config);
 
         if self.collections.contains_key(name) {
@@ -191,7 +251,7 @@ impl VectorStore {
 
         // Fallback to CPU
         debug!("Creating CPU-based collection '{}'", name);
-        let mut collection = Collection::new(name.to_string(), config);
+        let mut collection = Collection::try_new(name.to_string(), config)?;
 
         // Set owner if provided (multi-tenant mode)
         if let Some(owner) = owner_id {
@@ -381,6 +441,21 @@ impl VectorStore {
         // name and does not carry the stale old name into the .vecdb archive.
         collection.set_name(new_name.to_string());
 
+        // A cold collection's on-disk index is keyed by name; move it so
+        // the next load still finds it.
+        if collection.is_cold() {
+            let from = Collection::disk_index_path(canonical_old.as_str());
+            let to = Collection::disk_index_path(new_name);
+            if let Err(e) = std::fs::rename(&from, &to) {
+                warn!(
+                    "Failed to move on-disk HNSW index {} to {}: {}",
+                    from.display(),
+                    to.display(),
+                    e
+                );
+            }
+        }
+
         self.collections.insert(new_name.to_string(), collection);
 
         // Register old canonical name as a grace-window alias → new name.
@@ -423,6 +498,8 @@ impl VectorStore {
             }
         }
 
+        self.notify_mutation(new_name);
+
         info!(
             "Collection '{}' renamed to '{}'; '{}' kept as grace-window alias",
             canonical_old, new_name, canonical_old
@@ -430,6 +507,50 @@ impl VectorStore {
         Ok(())
     }
 
+    /// Move the collection built under `staged` over `name` in one map
+    /// insert, so readers see either the old collection or the new one
+    /// and never neither. `name` need not exist.
+    pub(in crate::db::vector_store) fn replace_collection_with(
+        &self,
+        staged: &str,
+        name: &str,
+    ) -> Result<()> {
+        let (_, mut collection) = self
+            .collections
+            .remove(staged)
+            .ok_or_else(|| VectorizerError::CollectionNotFound(staged.to_string()))?;
+        collection.set_name(name.to_string());
+
+        // An evicted copy would otherwise be written back over the new one
+        if self.is_evicted(name)
+            && let Some(residency) = self.residency()
+        {
+            residency.forget(name);
+        }
+        self.quota_states.remove(name);
+        let Some(replaced) = self.collections.insert(name.to_string(), collection) else {
+            self.notify_mutation(name);
+            return Ok(());
+        };
+        self.notify_mutation(name);
+        self.drop_summary_collection(name);
+
+        let mmap_path = match &replaced {
+            CollectionType::Cpu(c) => c.mmap_path(),
+            _ => None,
+        };
+        let disk_index_path = replaced
+            .is_cold()
+            .then(|| Collection::disk_index_path(name));
+        drop(replaced);
+        for path in mmap_path.into_iter().chain(disk_index_path) {
+            if let Err(e) = std::fs::remove_file(&path) {
+                warn!("Failed to remove {}: {}", path.display(), e);
+            }
+        }
+        Ok(())
+    }
+
     /// Remove the grace-window alias created by `rename_collection`.
     ///
     /// Operators call this once client migration to the new name is
@@ -444,12 +565,46 @@ impl VectorStore {
 
         let canonical = self.resolve_alias_target(name)?;
 
-        self.collections
-            .remove(canonical.as_str())
-            .ok_or_else(|| VectorizerError::CollectionNotFound(name.to_string()))?;
+        // An evicted collection only lives in .vecdb; forgetting it keeps
+        // the next archive rewrite from copying it over
+        let evicted = self
+            .residency()
+            .is_some_and(|r| r.forget(canonical.as_str()));
+        self.quota_states.remove(canonical.as_str());
+        self.write_locks.remove(canonical.as_str());
+
+        let Some((_, removed)) = self.collections.remove(canonical.as_str()) else {
+            if evicted {
+                self.notify_mutation(canonical.as_str());
+                self.remove_aliases_for_collection(canonical.as_str());
+                self.drop_summary_collection(canonical.as_str());
+                info!("Evicted collection '{}' deleted", canonical);
+                return Ok(());
+            }
+            return Err(VectorizerError::CollectionNotFound(name.to_string()));
+        };
 
         // Remove any aliases pointing to this collection
+        self.notify_mutation(canonical.as_str());
         self.remove_aliases_for_collection(canonical.as_str());
+        self.drop_summary_collection(canonical.as_str());
+
+        // Drop the backing file of an mmap collection and the on-disk HNSW
+        // index of a cold one; both are rebuilt from the .vecdb snapshot on
+        // load, so nothing else references them.
+        let mmap_path = match &removed {
+            CollectionType::Cpu(c) => c.mmap_path(),
+            _ => None,
+        };
+        let disk_index_path = removed
+            .is_cold()
+            .then(|| Collection::disk_index_path(canonical.as_str()));
+        drop(removed);
+        for path in mmap_path.into_iter().chain(disk_index_path) {
+            if let Err(e) = std::fs::remove_file(&path) {
+                warn!("Failed to remove {}: {}", path.display(), e);
+            }
+        }
 
         info!(
             "Collection '{}' (canonical '{}') deleted successfully",
@@ -557,6 +712,32 @@ impl VectorStore {
         }
     }
 
+    /// Change the mutable settings of a collection in place (see
+    /// [`Collection::apply_patch`]) and return the names of the settings
+    /// changed. Only CPU collections can be patched.
+    pub fn patch_collection(
+        &self,
+        collection_name: &str,
+        patch: crate::db::CollectionPatch,
+    ) -> Result<Vec<&'static str>> {
+        let ttl_secs = patch.ttl_secs;
+        let changed = {
+            let mut collection_ref = self.get_collection_mut(collection_name)?;
+            match &mut *collection_ref {
+                CollectionType::Cpu(collection) => collection.apply_patch(patch)?,
+                _ => {
+                    return Err(VectorizerError::Storage(
+                        "collection settings can only be changed on CPU collections".to_string(),
+                    ));
+                }
+            }
+        };
+        if let Some(ttl_secs) = ttl_secs {
+            self.set_ttl_metadata(collection_name, ttl_secs);
+        }
+        Ok(changed)
+    }
+
     /// Enable graph for all workspace collections
     pub fn enable_graph_for_all_workspace_collections(&self) -> Result<Vec<String>> {
         let collections = self.list_collections();
@@ -591,6 +772,11 @@ impl VectorStore {
             collection_names.insert(entry.key().clone());
         }
 
+        // Add collections lazy loading left in the .vecdb archive
+        if let Some(residency) = self.residency() {
+            collection_names.extend(residency.on_disk());
+        }
+
         // Add collections available on disk
         let data_dir = Self::get_data_dir();
         if data_dir.exists() {
@@ -610,6 +796,20 @@ impl VectorStore {
         collection_names.into_iter().collect()
     }
 
+    /// CPU collections currently loaded in memory, cloned out of the map
+    /// (a `Collection` clone shares all its state) so long-running
+    /// maintenance such as HNSW compaction holds no map guard. Unlike
+    /// [`Self::list_collections`], never triggers a lazy load.
+    pub fn loaded_cpu_collections(&self) -> Vec<Collection> {
+        self.collections
+            .iter()
+            .filter_map(|entry| match entry.value() {
+                CollectionType::Cpu(c) => Some(c.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
     /// Check if a collection is empty (has zero vectors)
     pub fn is_collection_empty(&self, name: &str) -> Result<bool> {
         let collection_ref = self.get_collection(name)?;
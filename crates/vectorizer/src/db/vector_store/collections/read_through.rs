@@ -0,0 +1,82 @@
+//! Read-through collections: cold loads from the configured origin and
+//! unloading once idle. See [`crate::db::read_through`].
+
+use tracing::{info, warn};
+
+use super::super::VectorStore;
+use crate::db::read_through::ReadThroughStatus;
+use crate::error::Result;
+
+impl VectorStore {
+    /// Whether `name` is a read-through collection.
+    pub fn is_read_through(&self, name: &str) -> bool {
+        self.read_through
+            .read()
+            .as_ref()
+            .is_some_and(|rt| rt.contains(name))
+    }
+
+    /// Status of every read-through collection; empty when none are
+    /// configured.
+    pub fn read_through_status(&self) -> Vec<ReadThroughStatus> {
+        match self.read_through.read().clone() {
+            Some(rt) => rt.status(|name| self.collections.contains_key(name)),
+            None => Vec::new(),
+        }
+    }
+
+    /// Push back the eviction of a read-through collection.
+    pub(super) fn touch_read_through(&self, name: &str) {
+        if let Some(rt) = self.read_through.read().as_ref() {
+            rt.touch(name);
+        }
+    }
+
+    /// Fetch `name` from its origin and build it in memory. Returns
+    /// `false` when `name` is not a read-through collection, so the
+    /// caller falls back to the local data directory.
+    pub(super) fn load_read_through(&self, name: &str) -> Result<bool> {
+        let Some(rt) = self.read_through.read().clone() else {
+            return Ok(false);
+        };
+        if !rt.contains(name) {
+            return Ok(false);
+        }
+
+        let _loading = rt.lock_loads();
+        if self.collections.contains_key(name) {
+            // Loaded by a concurrent lookup while we waited
+            rt.touch(name);
+            return Ok(true);
+        }
+        let persisted = rt.fetch(name).inspect_err(|e| {
+            warn!("Failed to fetch read-through collection '{}': {}", name, e);
+        })?;
+        if let Err(e) = self.load_persisted_collection_from_data(name, persisted) {
+            // Don't leave a half-built collection behind
+            self.collections.remove(name);
+            return Err(e);
+        }
+        rt.mark_loaded(name);
+        info!("Loaded read-through collection '{}' from origin", name);
+        Ok(true)
+    }
+
+    /// Unload read-through collections idle for longer than the
+    /// configured timeout. Returns their names; the next access fetches
+    /// them again.
+    pub fn evict_idle_read_through(&self) -> Vec<String> {
+        let Some(rt) = self.read_through.read().clone() else {
+            return Vec::new();
+        };
+        let mut evicted = Vec::new();
+        for name in rt.idle_collections() {
+            rt.mark_evicted(&name);
+            if self.collections.remove(&name).is_some() {
+                info!("Evicted idle read-through collection '{}'", name);
+                evicted.push(name);
+            }
+        }
+        evicted
+    }
+}
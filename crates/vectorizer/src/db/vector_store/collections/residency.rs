@@ -0,0 +1,92 @@
+//! Lazy loading: resident-set bookkeeping and LRU eviction of
+//! collections back to `.vecdb`. See [`crate::db::residency`].
+
+use std::sync::Arc;
+
+use tracing::info;
+
+use super::super::{CollectionType, VectorStore};
+use crate::db::residency::{LoadedCollection, Residency, ResidencyStatus};
+
+impl VectorStore {
+    /// The lazy loading state, when enabled.
+    pub fn residency(&self) -> Option<Arc<Residency>> {
+        self.residency.read().clone()
+    }
+
+    /// Whether `name` was left in `.vecdb` by lazy loading and is not in
+    /// memory.
+    pub fn is_evicted(&self, name: &str) -> bool {
+        self.residency
+            .read()
+            .as_ref()
+            .is_some_and(|r| r.is_on_disk(name))
+    }
+
+    /// Record that the state of `names` as of `at` was written to
+    /// `.vecdb`, which makes them evictable.
+    pub fn mark_persisted(&self, names: &[String], at: chrono::DateTime<chrono::Utc>) {
+        if let Some(residency) = self.residency.read().as_ref() {
+            residency.mark_persisted(names, at);
+        }
+    }
+
+    /// Lazy loading status; `None` when disabled.
+    pub fn residency_status(&self) -> Option<ResidencyStatus> {
+        let residency = self.residency()?;
+        Some(residency.status(&self.evictable_collections()))
+    }
+
+    /// Make `name` the most recently used collection.
+    pub(super) fn touch_resident(&self, name: &str) {
+        if let Some(residency) = self.residency.read().as_ref() {
+            residency.touch(name);
+        }
+    }
+
+    /// Unload the least recently used collections until the resident set
+    /// fits the lazy loading limits. Collections with changes not yet in
+    /// `.vecdb` are skipped. Returns the evicted names; the next access
+    /// loads them again.
+    pub fn evict_lru_collections(&self) -> Vec<String> {
+        let Some(residency) = self.residency() else {
+            return Vec::new();
+        };
+        let mut evicted = Vec::new();
+        for name in residency.eviction_candidates(&self.evictable_collections()) {
+            // Re-check under the shard lock: a write that landed since the
+            // candidates were picked keeps the collection in memory
+            let removed = self.collections.remove_if(&name, |_, collection| {
+                residency.is_clean(&name, collection.metadata().updated_at)
+            });
+            if removed.is_some() {
+                residency.mark_evicted(&name);
+                info!("Evicted collection '{}' back to .vecdb", name);
+                evicted.push(name);
+            }
+        }
+        evicted
+    }
+
+    /// Collections in memory that lazy loading may unload: CPU
+    /// collections the archive can hold, minus read-through ones, which
+    /// have their own eviction.
+    fn evictable_collections(&self) -> Vec<LoadedCollection> {
+        let candidates: Vec<LoadedCollection> = self
+            .collections
+            .iter()
+            .filter_map(|entry| match entry.value() {
+                CollectionType::Cpu(c) => Some(LoadedCollection {
+                    name: entry.key().clone(),
+                    estimated_bytes: entry.value().estimated_memory_usage(),
+                    updated_at: c.metadata().updated_at,
+                }),
+                _ => None,
+            })
+            .collect();
+        candidates
+            .into_iter()
+            .filter(|c| !self.is_read_through(&c.name))
+            .collect()
+    }
+}
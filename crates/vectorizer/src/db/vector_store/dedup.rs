@@ -0,0 +1,102 @@
+//! Deduplication on insert (see [`crate::models::dedup`]).
+//!
+//! `insert` runs each target collection's share of a batch through its
+//! dedup policy before the quota and the WAL see it. Duplicates of
+//! stored vectors are found by the collection
+//! ([`crate::db::Collection::find_duplicate`]); duplicates of earlier
+//! vectors of the same batch are matched here, by content hash only.
+//! A merge is written with `apply_update`, so WAL replay and replicas
+//! see the merged payload rather than a skipped insert.
+
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use super::VectorStore;
+use crate::error::Result;
+use crate::models::{DedupAction, DedupReason, DedupStatus, Duplicate, Payload, Vector};
+
+impl VectorStore {
+    /// Split `vectors` into the ones to insert and the status of each
+    /// input vector, in order. Merges into stored vectors are applied on
+    /// the way.
+    pub(super) fn deduplicate(
+        &self,
+        collection_name: &str,
+        vectors: Vec<Vector>,
+    ) -> Result<(Vec<Vector>, Vec<DedupStatus>)> {
+        let policy = self.get_collection(collection_name)?.config().dedup.clone();
+        let Some(policy) = policy else {
+            let statuses = vec![DedupStatus::Inserted; vectors.len()];
+            return Ok((vectors, statuses));
+        };
+
+        let mut fresh: Vec<Vector> = Vec::with_capacity(vectors.len());
+        let mut statuses = Vec::with_capacity(vectors.len());
+        // Content hash -> position in `fresh`
+        let mut batch_hashes: HashMap<String, usize> = HashMap::new();
+        for mut vector in vectors {
+            let hash = policy.content_hash(vector.payload.as_mut());
+            let earlier = hash
+                .as_ref()
+                .and_then(|hash| batch_hashes.get(hash).copied())
+                .filter(|&position| fresh[position].id != vector.id);
+            let duplicate = match earlier {
+                Some(position) => Some(Duplicate {
+                    duplicate_of: fresh[position].id.clone(),
+                    reason: DedupReason::ContentHash,
+                    score: None,
+                }),
+                None => self
+                    .get_collection(collection_name)?
+                    .find_duplicate(&vector, hash.as_deref())?,
+            };
+
+            let Some(duplicate) = duplicate else {
+                if let Some(hash) = hash {
+                    batch_hashes.insert(hash, fresh.len());
+                }
+                fresh.push(vector);
+                statuses.push(DedupStatus::Inserted);
+                continue;
+            };
+            match policy.action {
+                DedupAction::Skip => statuses.push(DedupStatus::Skipped(duplicate)),
+                DedupAction::Merge => {
+                    match earlier {
+                        Some(position) => {
+                            merge_payload(&mut fresh[position].payload, vector.payload)
+                        }
+                        None => {
+                            let mut stored =
+                                self.get_vector(collection_name, &duplicate.duplicate_of)?;
+                            merge_payload(&mut stored.payload, vector.payload);
+                            self.apply_update(collection_name, stored)?;
+                        }
+                    }
+                    statuses.push(DedupStatus::Merged(duplicate));
+                }
+            }
+        }
+        Ok((fresh, statuses))
+    }
+}
+
+/// Copy the fields of `incoming` onto `stored`. Encrypted and non-object
+/// payloads cannot be merged field by field; `incoming` replaces those.
+fn merge_payload(stored: &mut Option<Payload>, incoming: Option<Payload>) {
+    let Some(incoming) = incoming else {
+        return;
+    };
+    let mergeable = |payload: &Payload| !payload.is_encrypted() && payload.data.is_object();
+    match stored {
+        Some(existing) if mergeable(existing) && mergeable(&incoming) => {
+            if let (Value::Object(fields), Value::Object(incoming)) =
+                (&mut existing.data, incoming.data)
+            {
+                fields.extend(incoming);
+            }
+        }
+        _ => *stored = Some(incoming),
+    }
+}
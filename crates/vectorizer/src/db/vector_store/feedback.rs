@@ -0,0 +1,57 @@
+//! Relevance feedback entry points — resolve the collection and hand
+//! off to [`crate::db::Collection`]'s feedback methods. Only CPU
+//! collections record feedback. Feedback changes the `feedback` term of
+//! scoring expressions, so recording or clearing it counts as a
+//! mutation for the query cache.
+
+use super::{CollectionType, VectorStore};
+use crate::db::{FeedbackLabel, FeedbackSummary, FeedbackVotes};
+use crate::error::{Result, VectorizerError};
+
+fn unsupported() -> VectorizerError {
+    VectorizerError::Storage("relevance feedback is only recorded on CPU collections".to_string())
+}
+
+impl VectorStore {
+    /// Record that `result_id` was (or was not) relevant to `query`.
+    /// Returns the result's updated tally.
+    pub fn record_feedback(
+        &self,
+        collection_name: &str,
+        query: &str,
+        result_id: &str,
+        label: FeedbackLabel,
+    ) -> Result<FeedbackVotes> {
+        let votes = match &*self.get_collection(collection_name)? {
+            CollectionType::Cpu(c) => c.record_feedback(query, result_id, label)?,
+            _ => return Err(unsupported()),
+        };
+        self.notify_mutation(collection_name);
+        Ok(votes)
+    }
+
+    /// Feedback totals of `collection_name` with its `top` highest- and
+    /// lowest-boosted results and `recent` newest judgements.
+    pub fn feedback_summary(
+        &self,
+        collection_name: &str,
+        top: usize,
+        recent: usize,
+    ) -> Result<FeedbackSummary> {
+        match &*self.get_collection(collection_name)? {
+            CollectionType::Cpu(c) => Ok(c.feedback_summary(top, recent)),
+            _ => Err(unsupported()),
+        }
+    }
+
+    /// Forget all feedback on `collection_name`. Returns the number of
+    /// judged results dropped.
+    pub fn clear_feedback(&self, collection_name: &str) -> Result<usize> {
+        let cleared = match &*self.get_collection(collection_name)? {
+            CollectionType::Cpu(c) => c.clear_feedback(),
+            _ => return Err(unsupported()),
+        };
+        self.notify_mutation(collection_name);
+        Ok(cleared)
+    }
+}
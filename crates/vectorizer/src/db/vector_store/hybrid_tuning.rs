@@ -0,0 +1,46 @@
+//! Hybrid search judgement entry points — resolve the collection and
+//! hand off to [`crate::db::Collection`]'s judgement methods. Only CPU
+//! collections record judgements; the tuning itself lives in
+//! [`crate::evaluation::hybrid_tuning`].
+
+use super::{CollectionType, VectorStore};
+use crate::db::{HybridJudgment, HybridTuningSummary};
+use crate::error::{Result, VectorizerError};
+
+fn unsupported() -> VectorizerError {
+    VectorizerError::Storage(
+        "hybrid search judgements are only recorded on CPU collections".to_string(),
+    )
+}
+
+impl VectorStore {
+    /// Record `judgments` of hybrid search results on `collection_name`.
+    /// Returns the number of judgements now stored.
+    pub fn record_hybrid_judgments(
+        &self,
+        collection_name: &str,
+        judgments: Vec<HybridJudgment>,
+    ) -> Result<usize> {
+        match &*self.get_collection(collection_name)? {
+            CollectionType::Cpu(c) => c.record_hybrid_judgments(judgments),
+            _ => Err(unsupported()),
+        }
+    }
+
+    /// Judgement totals and tuned weights of `collection_name`.
+    pub fn hybrid_tuning_summary(&self, collection_name: &str) -> Result<HybridTuningSummary> {
+        match &*self.get_collection(collection_name)? {
+            CollectionType::Cpu(c) => Ok(c.hybrid_tuning_summary()),
+            _ => Err(unsupported()),
+        }
+    }
+
+    /// Forget the judgements and tuned weights of `collection_name`.
+    /// Returns the number of judgements dropped.
+    pub fn clear_hybrid_tuning(&self, collection_name: &str) -> Result<usize> {
+        match &*self.get_collection(collection_name)? {
+            CollectionType::Cpu(c) => Ok(c.clear_hybrid_tuning()),
+            _ => Err(unsupported()),
+        }
+    }
+}
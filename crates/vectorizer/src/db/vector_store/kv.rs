@@ -0,0 +1,58 @@
+//! Metadata key-value store entry points — resolve the collection and
+//! hand off to [`crate::db::Collection`]'s `kv_*` methods. Only CPU
+//! collections hold a store.
+
+use super::{CollectionType, VectorStore};
+use crate::db::{KvCheck, KvEntry, KvOp, KvWriteResult};
+use crate::error::{Result, VectorizerError};
+
+fn unsupported() -> VectorizerError {
+    VectorizerError::Storage(
+        "the metadata key-value store is only available on CPU collections".to_string(),
+    )
+}
+
+impl VectorStore {
+    /// The value under `key` in `namespace` of `collection_name`.
+    pub fn kv_get(
+        &self,
+        collection_name: &str,
+        namespace: &str,
+        key: &str,
+    ) -> Result<Option<KvEntry>> {
+        match &*self.get_collection(collection_name)? {
+            CollectionType::Cpu(c) => Ok(c.kv_get(namespace, key)),
+            _ => Err(unsupported()),
+        }
+    }
+
+    /// Keys of `namespace` starting with `prefix`, in key order, at most
+    /// `limit` of them.
+    pub fn kv_list(
+        &self,
+        collection_name: &str,
+        namespace: &str,
+        prefix: &str,
+        limit: usize,
+    ) -> Result<Vec<(String, KvEntry)>> {
+        match &*self.get_collection(collection_name)? {
+            CollectionType::Cpu(c) => Ok(c.kv_list(namespace, prefix, limit)),
+            _ => Err(unsupported()),
+        }
+    }
+
+    /// Apply `ops` to `namespace` atomically if every check in `checks`
+    /// holds. See [`crate::db::Collection::kv_transaction`].
+    pub fn kv_transaction(
+        &self,
+        collection_name: &str,
+        namespace: &str,
+        checks: &[KvCheck],
+        ops: Vec<KvOp>,
+    ) -> Result<Vec<KvWriteResult>> {
+        match &*self.get_collection(collection_name)? {
+            CollectionType::Cpu(c) => c.kv_transaction(namespace, checks, ops),
+            _ => Err(unsupported()),
+        }
+    }
+}
@@ -13,7 +13,7 @@
 // phase4_enforce-public-api-docs.
 #![allow(missing_docs)]
 
-use super::VectorStore;
+use super::{CollectionType, VectorStore};
 use crate::error::Result;
 use crate::models::CollectionMetadata;
 
@@ -64,6 +64,32 @@ impl VectorStore {
         self.metadata.remove(key).map(|(_, v)| v)
     }
 
+    /// Set or clear the TTL of vectors inserted into `collection_name`
+    /// from now on. CPU collections keep it with their settings, so it is
+    /// persisted in `.vecdb`.
+    pub fn set_collection_ttl(&self, collection_name: &str, ttl_secs: Option<u64>) -> Result<()> {
+        if let CollectionType::Cpu(c) = &*self.get_collection(collection_name)? {
+            c.set_ttl_secs(ttl_secs);
+        }
+        self.set_ttl_metadata(collection_name, ttl_secs);
+        Ok(())
+    }
+
+    /// Mirror a collection's TTL into the `ttl:{name}` metadata key.
+    pub(in crate::db::vector_store) fn set_ttl_metadata(
+        &self,
+        collection_name: &str,
+        ttl_secs: Option<u64>,
+    ) {
+        let key = format!("ttl:{}", collection_name);
+        match ttl_secs {
+            Some(secs) => self.set_metadata(&key, secs.to_string()),
+            None => {
+                self.remove_metadata(&key);
+            }
+        }
+    }
+
     /// List all metadata keys
     pub fn list_metadata_keys(&self) -> Vec<String> {
         self.metadata
@@ -12,6 +12,9 @@
 //! - [`aliases`]         — alias resolution + CRUD
 //! - [`vectors`]         — insert / update / delete / get-vector
 //! - [`search`]          — search + hybrid_search dispatch
+//! - [`feedback`]        — relevance feedback record / summary / clear
+//! - [`hybrid_tuning`]   — hybrid search judgements record / summary / clear
+//! - [`kv`]              — metadata key-value store get / list / transaction
 //! - [`metadata`]        — stats + the `metadata` DashMap accessor
 //!   + `VectorStoreStats`
 //! - [`wal`]             — write-ahead log writers + recovery + replay
@@ -19,6 +22,8 @@
 //!   (the save half lives in [`autosave`])
 //! - [`autosave`]        — auto-save flag + pending-saves set + legacy
 //!   `save_collection_*` writers
+//! - [`summaries`]       — `<collection>_summaries` companions kept in
+//!   sync with their source collection
 //!
 //! The `Collection`, `Sharded`, `DistributedSharded`, and
 //! `HiveGpuCollection` concrete types all live in sibling `db/`
@@ -30,7 +35,12 @@ use std::sync::Arc;
 use dashmap::DashMap;
 use tracing::info;
 
+use crate::db::change_feed::ChangeFeed;
+use crate::db::read_through::ReadThrough;
+use crate::db::residency::Residency;
 use crate::db::wal_integration::WalIntegration;
+use crate::db::write_fence::WriteFence;
+use crate::plugins::WritePluginChain;
 // Names the tests module at `src/db/vector_store_tests.rs` picks up via
 // `use super::*;`. Kept in sync with the pre-split surface so the test
 // file doesn't need touching.
@@ -43,14 +53,24 @@ mod aliases;
 mod autosave;
 mod collection_type;
 mod collections;
+mod dedup;
+mod feedback;
+mod hybrid_tuning;
+mod kv;
 mod metadata;
 mod persistence;
+mod quota;
 mod search;
+mod summaries;
 mod vectors;
 mod wal;
 
 pub use collection_type::CollectionType;
+pub use collections::{CLONE_BATCH_SIZE, CLONE_TEXT_FIELDS, CloneReport};
 pub use metadata::VectorStoreStats;
+pub use persistence::PointInTimeRestore;
+pub use quota::QUOTA_SUMMARY_FIELD;
+pub use summaries::{SUMMARY_COLLECTION_SUFFIX, SUMMARY_SOURCE_IDS_FIELD, summary_collection_name};
 
 /// Callback that persists the embedding vocabulary for a collection to
 /// a tokenizer JSON file. Injected by the server bootstrap (which owns
@@ -61,6 +81,10 @@ pub use metadata::VectorStoreStats;
 pub type TokenizerSaver =
     Arc<dyn Fn(&str, &std::path::Path) -> crate::error::Result<()> + Send + Sync>;
 
+/// Callback told the name of a collection whose contents changed, once
+/// per write. The server registers the query cache's invalidation here.
+pub type MutationListener = Arc<dyn Fn(&str) + Send + Sync>;
+
 /// Thread-safe in-memory vector store
 #[derive(Clone)]
 pub struct VectorStore {
@@ -81,6 +105,30 @@ pub struct VectorStore {
     pub(super) wal: Arc<parking_lot::Mutex<Option<WalIntegration>>>,
     /// Vocabulary persister injected by bootstrap (see [`TokenizerSaver`])
     pub(super) tokenizer_saver: Arc<parking_lot::RwLock<Option<TokenizerSaver>>>,
+    /// Hooks run on insert and update (see [`Self::set_write_plugins`])
+    pub(super) write_plugins: Arc<parking_lot::RwLock<Option<Arc<WritePluginChain>>>>,
+    /// Collections loaded from an external origin (see
+    /// [`Self::set_read_through`])
+    pub(super) read_through: Arc<parking_lot::RwLock<Option<Arc<ReadThrough>>>>,
+    /// Lazy loading and LRU eviction (see [`Self::set_residency`])
+    pub(super) residency: Arc<parking_lot::RwLock<Option<Arc<Residency>>>>,
+    /// Published to on every vector write (see [`Self::set_change_feed`])
+    pub(super) change_feed: Arc<parking_lot::RwLock<Option<Arc<ChangeFeed>>>>,
+    /// Told about every write (see [`Self::set_mutation_listener`])
+    pub(super) mutation_listener: Arc<parking_lot::RwLock<Option<MutationListener>>>,
+    /// Companion summary collections (see
+    /// [`Self::set_summary_collections`])
+    pub(super) summary_collections:
+        Arc<parking_lot::RwLock<Option<Arc<summaries::SummaryCollections>>>>,
+    /// Quota bookkeeping of each collection with a quota (see
+    /// [`quota::QuotaState`])
+    pub(super) quota_states: Arc<DashMap<String, Arc<quota::QuotaState>>>,
+    /// Serializes the vector writes of each collection, so
+    /// [`Self::modify_vector`] cannot lose a concurrent one
+    pub(super) write_locks: Arc<DashMap<String, Arc<parking_lot::ReentrantMutex<()>>>>,
+    /// Raised while a cluster snapshot takes its cut (see
+    /// [`Self::write_fence`])
+    pub(super) write_fence: Arc<WriteFence>,
 }
 
 impl std::fmt::Debug for VectorStore {
@@ -101,6 +149,49 @@ impl VectorStore {
         *self.tokenizer_saver.write() = Some(saver);
     }
 
+    /// Install the write-path plugins run on every `insert` and `update`
+    /// before the WAL sees the vectors. An empty chain removes them.
+    pub fn set_write_plugins(&self, chain: WritePluginChain) {
+        *self.write_plugins.write() = (!chain.is_empty()).then(|| Arc::new(chain));
+    }
+
+    /// Install the read-through collections, fetched from their origin
+    /// on first access by `get_collection`. An empty set removes them.
+    pub fn set_read_through(&self, read_through: ReadThrough) {
+        *self.read_through.write() = (!read_through.is_empty()).then(|| Arc::new(read_through));
+    }
+
+    /// Enable lazy loading: collections in `.vecdb` are loaded on first
+    /// access and evicted again under [`Residency`]'s limits. Must be
+    /// set before the startup load.
+    pub fn set_residency(&self, residency: Residency) {
+        *self.residency.write() = Some(Arc::new(residency));
+    }
+
+    /// Publish every successful insert, update and delete to `feed`.
+    pub fn set_change_feed(&self, feed: Arc<ChangeFeed>) {
+        *self.change_feed.write() = Some(feed);
+    }
+
+    /// The change feed, if one is installed.
+    pub fn change_feed(&self) -> Option<Arc<ChangeFeed>> {
+        self.change_feed.read().clone()
+    }
+
+    /// Call `listener` after every insert, update and delete, after a
+    /// collection is (re)loaded from disk, an origin or a restore, and
+    /// when a collection or alias is dropped or renamed, with the
+    /// collection's canonical name and then each of its aliases.
+    pub fn set_mutation_listener(&self, listener: MutationListener) {
+        *self.mutation_listener.write() = Some(listener);
+    }
+
+    /// Fence that holds vector writes during a cluster snapshot. Every
+    /// insert, update and delete enters it before touching the WAL.
+    pub fn write_fence(&self) -> &Arc<WriteFence> {
+        &self.write_fence
+    }
+
     /// Create a new empty vector store
     pub fn new() -> Self {
         info!("Creating new VectorStore");
@@ -113,6 +204,15 @@ impl VectorStore {
             save_task_handle: Arc::new(parking_lot::Mutex::new(None)),
             metadata: Arc::new(DashMap::new()),
             tokenizer_saver: Arc::new(parking_lot::RwLock::new(None)),
+            write_plugins: Arc::new(parking_lot::RwLock::new(None)),
+            read_through: Arc::new(parking_lot::RwLock::new(None)),
+            residency: Arc::new(parking_lot::RwLock::new(None)),
+            change_feed: Arc::new(parking_lot::RwLock::new(None)),
+            mutation_listener: Arc::new(parking_lot::RwLock::new(None)),
+            summary_collections: Arc::new(parking_lot::RwLock::new(None)),
+            quota_states: Arc::new(DashMap::new()),
+            write_locks: Arc::new(DashMap::new()),
+            write_fence: Arc::new(WriteFence::new()),
             wal: Arc::new(parking_lot::Mutex::new(
                 Some(WalIntegration::new_disabled()),
             )),
@@ -137,6 +237,15 @@ impl VectorStore {
             save_task_handle: Arc::new(parking_lot::Mutex::new(None)),
             metadata: Arc::new(DashMap::new()),
             tokenizer_saver: Arc::new(parking_lot::RwLock::new(None)),
+            write_plugins: Arc::new(parking_lot::RwLock::new(None)),
+            read_through: Arc::new(parking_lot::RwLock::new(None)),
+            residency: Arc::new(parking_lot::RwLock::new(None)),
+            change_feed: Arc::new(parking_lot::RwLock::new(None)),
+            mutation_listener: Arc::new(parking_lot::RwLock::new(None)),
+            summary_collections: Arc::new(parking_lot::RwLock::new(None)),
+            quota_states: Arc::new(DashMap::new()),
+            write_locks: Arc::new(DashMap::new()),
+            write_fence: Arc::new(WriteFence::new()),
             wal: Arc::new(parking_lot::Mutex::new(
                 Some(WalIntegration::new_disabled()),
             )),
@@ -155,6 +264,15 @@ impl VectorStore {
             save_task_handle: Arc::new(parking_lot::Mutex::new(None)),
             metadata: Arc::new(DashMap::new()),
             tokenizer_saver: Arc::new(parking_lot::RwLock::new(None)),
+            write_plugins: Arc::new(parking_lot::RwLock::new(None)),
+            read_through: Arc::new(parking_lot::RwLock::new(None)),
+            residency: Arc::new(parking_lot::RwLock::new(None)),
+            change_feed: Arc::new(parking_lot::RwLock::new(None)),
+            mutation_listener: Arc::new(parking_lot::RwLock::new(None)),
+            summary_collections: Arc::new(parking_lot::RwLock::new(None)),
+            quota_states: Arc::new(DashMap::new()),
+            write_locks: Arc::new(DashMap::new()),
+            write_fence: Arc::new(WriteFence::new()),
             wal: Arc::new(parking_lot::Mutex::new(
                 Some(WalIntegration::new_disabled()),
             )),
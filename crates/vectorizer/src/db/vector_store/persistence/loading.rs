@@ -8,6 +8,7 @@ use std::path::PathBuf;
 use tracing::{debug, error, info, warn};
 
 use super::super::{CollectionType, VectorStore};
+use crate::db::startup_progress::{LoadSource, StartupProgress};
 use crate::error::{Result, VectorizerError};
 
 impl VectorStore {
@@ -54,9 +55,99 @@ impl VectorStore {
             }
         }
 
+        drop(collection_ref);
+        self.notify_mutation(collection_name);
         Ok(())
     }
 
+    /// Restore the recall / latency profile persisted with a collection
+    /// (CPU collections only; others have none).
+    pub(in crate::db::vector_store) fn restore_recall_profile(
+        &self,
+        collection_name: &str,
+        profile: Option<crate::db::RecallProfile>,
+    ) {
+        if profile.is_none() {
+            return;
+        }
+        if let Some(collection) = self.collections.get(collection_name)
+            && let CollectionType::Cpu(c) = &*collection
+        {
+            c.set_recall_profile(profile);
+        }
+    }
+
+    /// Restore the relevance feedback persisted with a collection (CPU
+    /// collections only; others have none).
+    pub(in crate::db::vector_store) fn restore_feedback(
+        &self,
+        collection_name: &str,
+        feedback: Option<crate::db::RelevanceFeedback>,
+    ) {
+        if feedback.is_none() {
+            return;
+        }
+        if let Some(collection) = self.collections.get(collection_name)
+            && let CollectionType::Cpu(c) = &*collection
+        {
+            c.set_feedback(feedback);
+        }
+    }
+
+    /// Restore the hybrid search judgements and tuned weights persisted
+    /// with a collection (CPU collections only; others have none).
+    pub(in crate::db::vector_store) fn restore_hybrid_tuning(
+        &self,
+        collection_name: &str,
+        tuning: Option<crate::db::HybridTuning>,
+    ) {
+        if tuning.is_none() {
+            return;
+        }
+        if let Some(collection) = self.collections.get(collection_name)
+            && let CollectionType::Cpu(c) = &*collection
+        {
+            c.set_hybrid_tuning(tuning);
+        }
+    }
+
+    /// Restore the metadata key-value store persisted with a collection
+    /// (CPU collections only; others have none).
+    pub(in crate::db::vector_store) fn restore_metadata_kv(
+        &self,
+        collection_name: &str,
+        metadata_kv: Option<crate::db::MetadataKv>,
+    ) {
+        if metadata_kv.is_none() {
+            return;
+        }
+        if let Some(collection) = self.collections.get(collection_name)
+            && let CollectionType::Cpu(c) = &*collection
+        {
+            c.set_metadata_kv(metadata_kv);
+        }
+    }
+
+    /// Restore the settings changed after creation of a collection (CPU
+    /// collections only; others have none).
+    pub(in crate::db::vector_store) fn restore_overrides(
+        &self,
+        collection_name: &str,
+        overrides: Option<crate::db::CollectionOverrides>,
+    ) {
+        let Some(ttl_secs) = overrides.as_ref().map(|o| o.ttl_secs) else {
+            return;
+        };
+        if let Some(collection) = self.collections.get(collection_name)
+            && let CollectionType::Cpu(c) = &*collection
+        {
+            c.set_overrides(overrides);
+        }
+        if ttl_secs.is_some() {
+            self.set_ttl_metadata(collection_name, ttl_secs);
+        }
+    }
+
     /// Load a collection from cache with optional HNSW dump for instant loading
     pub fn load_collection_from_cache_with_hnsw_dump(
         &self,
@@ -93,11 +184,22 @@ impl VectorStore {
             }
         }
 
+        drop(collection_ref);
+        self.notify_mutation(collection_name);
         Ok(())
     }
 
     /// Load all persisted collections from the data directory
     pub fn load_all_persisted_collections(&self) -> Result<usize> {
+        self.load_all_persisted_collections_with_progress(&StartupProgress::new())
+    }
+
+    /// [`Self::load_all_persisted_collections`], recording each
+    /// collection's state in `progress` as it is restored.
+    pub fn load_all_persisted_collections_with_progress(
+        &self,
+        progress: &StartupProgress,
+    ) -> Result<usize> {
         let data_dir = Self::get_data_dir();
         if !data_dir.exists() {
             debug!("Data directory does not exist: {:?}", data_dir);
@@ -112,18 +214,18 @@ impl VectorStore {
         match format {
             crate::storage::StorageFormat::Compact => {
                 info!("📦 Found vectorizer.vecdb - loading from compressed archive");
-                self.load_from_vecdb()
+                self.load_from_vecdb(progress)
             }
             crate::storage::StorageFormat::Legacy => {
                 info!("📁 Using legacy format - loading from raw files");
-                self.load_from_raw_files()
+                self.load_from_raw_files(progress)
             }
         }
     }
 
     /// Load collections from vectorizer.vecdb (compressed archive)
     /// NEVER falls back to raw files — .vecdb is the ONLY source of truth
-    fn load_from_vecdb(&self) -> Result<usize> {
+    fn load_from_vecdb(&self, progress: &StartupProgress) -> Result<usize> {
         use crate::storage::StorageReader;
 
         let data_dir = Self::get_data_dir();
@@ -142,6 +244,21 @@ impl VectorStore {
             }
         };
 
+        // With lazy loading, collections stay in the archive until first
+        // accessed
+        if let Some(residency) = self.residency() {
+            let index = reader.index()?;
+            for entry in &index.collections {
+                residency.defer(&entry.name);
+                progress.deferred(&entry.name, LoadSource::Persisted, entry.vector_count);
+            }
+            info!(
+                "💤 Lazy loading enabled: {} collections left in .vecdb until first access",
+                index.collections.len()
+            );
+            return Ok(0);
+        }
+
         // Extract all collections in memory
         let persisted_collections = match reader.extract_all_collections() {
             Ok(collections) => collections,
@@ -164,10 +281,19 @@ impl VectorStore {
             persisted_collections.len()
         );
 
+        for persisted_collection in &persisted_collections {
+            progress.pending(
+                &persisted_collection.name,
+                LoadSource::Persisted,
+                Some(persisted_collection.vectors.len()),
+            );
+        }
+
         let mut collections_loaded = 0;
 
         for (i, persisted_collection) in persisted_collections.iter().enumerate() {
             let collection_name = &persisted_collection.name;
+            progress.loading(collection_name, LoadSource::Persisted);
             info!(
                 "⏳ Loading collection {}/{}: '{}'",
                 i + 1,
@@ -208,6 +334,7 @@ impl VectorStore {
                     if persisted_collection.vectors.is_empty() {
                         // Empty collection — just count it as loaded (metadata preserved)
                         collections_loaded += 1;
+                        progress.indexed(collection_name, LoadSource::Persisted, 0);
                         info!(
                             "✅ Restored empty collection '{}' (metadata only) ({}/{})",
                             collection_name,
@@ -228,6 +355,26 @@ impl VectorStore {
                         persisted_collection.vectors.clone(),
                     ) {
                         Ok(_) => {
+                            self.restore_recall_profile(
+                                collection_name,
+                                persisted_collection.recall_profile.clone(),
+                            );
+                            self.restore_feedback(
+                                collection_name,
+                                persisted_collection.feedback.clone(),
+                            );
+                            self.restore_metadata_kv(
+                                collection_name,
+                                persisted_collection.metadata_kv.clone(),
+                            );
+                            self.restore_hybrid_tuning(
+                                collection_name,
+                                persisted_collection.hybrid_tuning.clone(),
+                            );
+                            self.restore_overrides(
+                                collection_name,
+                                persisted_collection.overrides.clone(),
+                            );
                             // If graph wasn't enabled before (config didn't have it), enable it now
                             // This handles collections that don't have graph in config but should have it enabled
                             if config.graph.as_ref().map(|g| g.enabled).unwrap_or(false) {
@@ -248,6 +395,11 @@ impl VectorStore {
                             }
 
                             collections_loaded += 1;
+                            progress.indexed(
+                                collection_name,
+                                LoadSource::Persisted,
+                                persisted_collection.vectors.len(),
+                            );
                             info!(
                                 "✅ Successfully loaded collection '{}' with {} vectors ({}/{})",
                                 collection_name,
@@ -261,6 +413,7 @@ impl VectorStore {
                                 "❌ CRITICAL: Failed to load vectors for collection '{}': {}",
                                 collection_name, e
                             );
+                            progress.failed(collection_name, LoadSource::Persisted, e.to_string());
                             // Remove the empty collection
                             let _ = self.delete_collection(collection_name);
                         }
@@ -271,6 +424,7 @@ impl VectorStore {
                         "❌ CRITICAL: Failed to create collection '{}': {}",
                         collection_name, e
                     );
+                    progress.failed(collection_name, LoadSource::Persisted, e.to_string());
                 }
             }
         }
@@ -288,7 +442,7 @@ impl VectorStore {
             );
             error!("   All collections failed to deserialize — likely format mismatch");
             warn!("🔄 Attempting fallback to raw files...");
-            return self.load_from_raw_files();
+            return self.load_from_raw_files(progress);
         }
 
         // Clean up any legacy raw files after successful load from .vecdb
@@ -352,7 +506,7 @@ impl VectorStore {
     }
 
     /// Load collections from raw files (legacy format)
-    fn load_from_raw_files(&self) -> Result<usize> {
+    fn load_from_raw_files(&self, progress: &StartupProgress) -> Result<usize> {
         let data_dir = Self::get_data_dir();
 
         // Collect all collection files first
@@ -379,9 +533,14 @@ impl VectorStore {
             collection_files.len()
         );
 
+        for (_, collection_name) in &collection_files {
+            progress.pending(collection_name, LoadSource::Persisted, None);
+        }
+
         // Load collections sequentially but with better progress reporting
         let mut collections_loaded = 0;
         for (i, (path, collection_name)) in collection_files.iter().enumerate() {
+            progress.loading(collection_name, LoadSource::Persisted);
             info!(
                 "⏳ Loading collection {}/{}: '{}'",
                 i + 1,
@@ -402,6 +561,12 @@ impl VectorStore {
                     }
 
                     collections_loaded += 1;
+                    let vectors = self
+                        .collections
+                        .get(collection_name.as_str())
+                        .map(|c| c.vector_count())
+                        .unwrap_or(0);
+                    progress.indexed(collection_name, LoadSource::Persisted, vectors);
                     info!(
                         "✅ Successfully loaded collection '{}' from persistence ({}/{})",
                         collection_name,
@@ -414,6 +579,7 @@ impl VectorStore {
                         "❌ Failed to load collection '{}' from {:?}: {}",
                         collection_name, path, e
                     );
+                    progress.failed(collection_name, LoadSource::Persisted, e.to_string());
                 }
             }
         }
@@ -646,6 +812,14 @@ impl VectorStore {
                 collection_name
             );
             self.load_collection_from_cache(collection_name, persisted_collection.vectors.clone())?;
+            self.restore_recall_profile(
+                collection_name,
+                persisted_collection.recall_profile.clone(),
+            );
+            self.restore_feedback(collection_name, persisted_collection.feedback.clone());
+            self.restore_metadata_kv(collection_name, persisted_collection.metadata_kv.clone());
+            self.restore_hybrid_tuning(collection_name, persisted_collection.hybrid_tuning.clone());
+            self.restore_overrides(collection_name, persisted_collection.overrides.clone());
         }
 
         // If graph wasn't enabled before (config didn't have it), enable it now
@@ -8,10 +8,13 @@
 //! single file mixed snapshot management with the three loading
 //! paths.
 //!
-//! - [`snapshots`] — native per-collection snapshots + reindex
-//! - [`loading`]   — `.vecdb` / legacy / dynamic collection loading
+//! - [`snapshots`]     — native per-collection snapshots + reindex
+//! - [`loading`]       — `.vecdb` / legacy / dynamic collection loading
+//! - [`point_in_time`] — restore to a timestamp from a snapshot + the WAL
 
 mod loading;
+mod point_in_time;
 mod snapshots;
 
+pub use point_in_time::PointInTimeRestore;
 pub use snapshots::NativeSnapshotInfo;
@@ -0,0 +1,551 @@
+//! Point-in-time restore: a snapshot rolled forward with the WAL.
+//!
+//! The base is the newest snapshot holding the collection that was taken
+//! at or before the target time and after the start of the WAL history.
+//! Native snapshots (`collection_snapshots/<name>/`), the periodic
+//! snapshots of the data directory (`snapshots/<id>/`) and those of
+//! scheduled backup jobs (`scheduled_snapshots/<job>/<id>/`) are
+//! considered. The collection's WAL entries from the snapshot up to the
+//! target time are applied on top and the result replaces the
+//! collection.
+//!
+//! Replay starts a little before a snapshot rather than exactly at it:
+//! every entry inserts, overwrites or deletes one whole vector, so
+//! applying entries the snapshot already holds again changes nothing.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use serde::Serialize;
+use tracing::{debug, info, warn};
+
+use super::super::VectorStore;
+use crate::db::wal_integration::payload_from_metadata;
+use crate::error::{Result, VectorizerError};
+use crate::models::Vector;
+use crate::persistence::types::Operation;
+use crate::persistence::{PersistedCollection, PersistedVector};
+use crate::storage::{StorageIndex, StorageReader};
+
+/// How long before its index was written a data-directory snapshot may
+/// have read a collection. Compaction serializes every collection before
+/// it writes the archive index, whose timestamp is all it records.
+const ARCHIVE_REPLAY_MARGIN_MINUTES: i64 = 5;
+
+/// Outcome of [`VectorStore::restore_collection_to_time`].
+#[derive(Debug, Clone, Serialize)]
+pub struct PointInTimeRestore {
+    /// Restored collection
+    pub collection: String,
+    /// Time the collection was restored to
+    pub restored_to: DateTime<Utc>,
+    /// Snapshot the restore started from: a native snapshot id, or the
+    /// snapshot directory relative to the data directory
+    pub base_snapshot: String,
+    /// When the base snapshot was taken
+    pub base_snapshot_at: DateTime<Utc>,
+    /// WAL entries applied on top of the base snapshot
+    pub replayed_operations: usize,
+    /// Vectors in the collection after the restore
+    pub vector_count: usize,
+}
+
+/// A snapshot that may hold the collection.
+struct BaseCandidate {
+    /// Shown in [`PointInTimeRestore::base_snapshot`]
+    label: String,
+    /// Earliest time the collection may have been read at; replay
+    /// starts here
+    from: DateTime<Utc>,
+    /// Latest time the collection may have been read at; must not be
+    /// after the target time
+    until: DateTime<Utc>,
+    source: BaseSource,
+}
+
+enum BaseSource {
+    /// `<id>.vecdb.gz` of the collection alone
+    Native(PathBuf),
+    /// Directory with a `.vecdb` / `.vecidx` pair of the whole store
+    Archive(PathBuf),
+}
+
+impl BaseCandidate {
+    fn load(&self, collection: &str) -> Result<Option<PersistedCollection>> {
+        match &self.source {
+            BaseSource::Native(path) => VectorStore::read_native_snapshot(path).map(Some),
+            BaseSource::Archive(dir) => {
+                StorageReader::new(dir)?.read_collection_in_memory(collection)
+            }
+        }
+    }
+}
+
+/// Subdirectories of `dir`, or files with `suffix`; empty when `dir`
+/// does not exist.
+fn dir_entries(dir: &Path, suffix: Option<&str>) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|path| match suffix {
+            Some(suffix) => path.to_str().is_some_and(|p| p.ends_with(suffix)),
+            None => path.is_dir(),
+        })
+        .collect()
+}
+
+/// Every snapshot under `data_dir` that may hold `collection`.
+fn base_candidates(data_dir: &Path, collection: &str) -> Vec<BaseCandidate> {
+    let mut candidates = Vec::new();
+
+    let native_dir = VectorStore::native_snapshot_dir(data_dir, collection);
+    for path in dir_entries(&native_dir, Some(".vecdb.gz")) {
+        let Some(id) = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .and_then(|n| n.strip_suffix(".vecdb.gz"))
+            .map(str::to_string)
+        else {
+            continue;
+        };
+        // Ids name the second the snapshot was taken in
+        let Ok(taken) = NaiveDateTime::parse_from_str(&id, "%Y%m%dT%H%M%SZ") else {
+            continue;
+        };
+        let taken = taken.and_utc();
+        candidates.push(BaseCandidate {
+            label: id,
+            from: taken,
+            until: taken + Duration::seconds(1),
+            source: BaseSource::Native(path),
+        });
+    }
+
+    let mut archive_dirs = dir_entries(&data_dir.join("snapshots"), None);
+    for job_dir in dir_entries(&data_dir.join("scheduled_snapshots"), None) {
+        archive_dirs.extend(dir_entries(&job_dir, None));
+    }
+    for dir in archive_dirs {
+        let Ok(index) = StorageIndex::load(&dir.join(crate::storage::VECIDX_FILE)) else {
+            continue;
+        };
+        if index.find_collection(collection).is_none() {
+            continue;
+        }
+        let label = dir
+            .strip_prefix(data_dir)
+            .unwrap_or(&dir)
+            .display()
+            .to_string();
+        candidates.push(BaseCandidate {
+            label,
+            from: index.created_at - Duration::minutes(ARCHIVE_REPLAY_MARGIN_MINUTES),
+            until: index.created_at,
+            source: BaseSource::Archive(dir),
+        });
+    }
+
+    candidates
+}
+
+/// Apply one WAL operation to `vectors`. Returns whether it is a vector
+/// operation.
+fn apply_operation(vectors: &mut HashMap<String, Vector>, operation: &Operation) -> bool {
+    match operation {
+        Operation::InsertVector {
+            vector_id,
+            data,
+            metadata,
+            ..
+        } => {
+            vectors.insert(
+                vector_id.clone(),
+                Vector {
+                    id: vector_id.clone(),
+                    data: data.clone(),
+                    payload: payload_from_metadata(metadata),
+                    sparse: None,
+                    document_id: None,
+                },
+            );
+            true
+        }
+        Operation::UpdateVector {
+            vector_id,
+            data,
+            metadata,
+            ..
+        } => {
+            let existing = vectors.remove(vector_id);
+            // Older entries leave out what the update did not change
+            let data = data
+                .clone()
+                .or_else(|| existing.as_ref().map(|v| v.data.clone()));
+            let Some(data) = data else {
+                return false;
+            };
+            let payload = match metadata {
+                Some(metadata) => payload_from_metadata(metadata),
+                None => existing.and_then(|v| v.payload),
+            };
+            vectors.insert(
+                vector_id.clone(),
+                Vector {
+                    id: vector_id.clone(),
+                    data,
+                    payload,
+                    sparse: None,
+                    document_id: None,
+                },
+            );
+            true
+        }
+        Operation::DeleteVector { vector_id, .. } => {
+            vectors.remove(vector_id);
+            true
+        }
+        Operation::CreateCollection { .. }
+        | Operation::DeleteCollection { .. }
+        | Operation::RenameCollection { .. }
+        | Operation::Checkpoint { .. } => false,
+    }
+}
+
+impl VectorStore {
+    /// Restore `collection_name` to its state at `at`.
+    ///
+    /// Needs the WAL ([`Self::enable_wal`]) to reach back from `at` to a
+    /// snapshot of the collection. Payloads are restored in full; sparse
+    /// vectors and document ids are not in the WAL, so vectors written
+    /// after the base snapshot come back without them.
+    ///
+    /// The restored collection is built under a staging name and swapped
+    /// in whole, replacing the live one or recreating it when it has
+    /// since been deleted; a failed restore leaves it untouched. A native
+    /// snapshot of the result is taken afterwards, so later restores to
+    /// a time after this one start from it.
+    pub async fn restore_collection_to_time(
+        &self,
+        collection_name: &str,
+        at: DateTime<Utc>,
+    ) -> Result<PointInTimeRestore> {
+        self.restore_collection_to_time_in(&Self::get_data_dir(), collection_name, at)
+            .await
+    }
+
+    /// [`Self::restore_collection_to_time`] with the snapshots under
+    /// `data_dir`.
+    pub(crate) async fn restore_collection_to_time_in(
+        &self,
+        data_dir: &Path,
+        collection_name: &str,
+        at: DateTime<Utc>,
+    ) -> Result<PointInTimeRestore> {
+        if at > Utc::now() {
+            return Err(VectorizerError::Configuration(format!(
+                "cannot restore to {}: it is in the future",
+                at.to_rfc3339()
+            )));
+        }
+        let wal = self.wal.lock().clone().filter(|wal| wal.is_enabled());
+        let (Some(wal), Some(history_start)) = (
+            wal.as_ref().and_then(|wal| wal.wal().cloned()),
+            wal.as_ref().and_then(|wal| wal.history_start()),
+        ) else {
+            return Err(VectorizerError::Configuration(
+                "point-in-time restore needs the WAL (storage.wal.enabled)".to_string(),
+            ));
+        };
+        if at < history_start {
+            return Err(VectorizerError::NotFound(format!(
+                "the WAL only reaches back to {}",
+                history_start.to_rfc3339()
+            )));
+        }
+
+        let canonical = self.resolve_alias_target(collection_name)?;
+        let mut candidates: Vec<BaseCandidate> = base_candidates(data_dir, &canonical)
+            .into_iter()
+            .filter(|c| c.from >= history_start && c.until <= at)
+            .collect();
+        candidates.sort_by(|a, b| b.until.cmp(&a.until));
+
+        let mut base = None;
+        for candidate in candidates {
+            match candidate.load(&canonical) {
+                Ok(Some(persisted)) => {
+                    base = Some((candidate, persisted));
+                    break;
+                }
+                Ok(None) => debug!(
+                    "snapshot '{}' does not hold collection '{}'",
+                    candidate.label, canonical
+                ),
+                Err(e) => warn!(
+                    "skipping snapshot '{}' for point-in-time restore: {}",
+                    candidate.label, e
+                ),
+            }
+        }
+        let Some((base, persisted)) = base else {
+            return Err(VectorizerError::NotFound(format!(
+                "no snapshot of collection '{}' between {} and {}",
+                canonical,
+                history_start.to_rfc3339(),
+                at.to_rfc3339()
+            )));
+        };
+
+        // Writes through an alias are logged under the alias
+        let entries = wal
+            .read_collection_window(&[canonical.as_str(), collection_name], base.from, at)
+            .await
+            .map_err(|e| VectorizerError::Storage(format!("WAL read failed: {}", e)))?;
+        let mut vectors: HashMap<String, Vector> = persisted
+            .vectors
+            .into_iter()
+            .map(Vector::from)
+            .map(|v| (v.id.clone(), v))
+            .collect();
+        let mut replayed = 0;
+        for entry in &entries {
+            if apply_operation(&mut vectors, &entry.operation) {
+                replayed += 1;
+            }
+        }
+
+        // Built aside and swapped in, so a failure leaves the live
+        // collection as it was
+        let staged = format!("{}.pitr-restore", canonical);
+        if self.collections.contains_key(&staged) {
+            return Err(VectorizerError::Configuration(format!(
+                "a point-in-time restore of collection '{}' is already running",
+                canonical
+            )));
+        }
+        let vector_count = vectors.len();
+        let built = self
+            .create_collection_with_quantization(&staged, persisted.config.unwrap_or_default())
+            .and_then(|()| {
+                if vectors.is_empty() {
+                    return Ok(());
+                }
+                self.load_collection_from_cache(
+                    &staged,
+                    vectors.into_values().map(PersistedVector::from).collect(),
+                )
+            });
+        if let Err(e) = built {
+            match self.delete_collection(&staged) {
+                Ok(()) | Err(VectorizerError::CollectionNotFound(_)) => {}
+                Err(cleanup) => warn!(
+                    "failed to drop '{}' after a failed point-in-time restore: {}",
+                    staged, cleanup
+                ),
+            }
+            return Err(e);
+        }
+        self.restore_recall_profile(&staged, persisted.recall_profile);
+        self.restore_feedback(&staged, persisted.feedback);
+        self.restore_metadata_kv(&staged, persisted.metadata_kv);
+        self.restore_hybrid_tuning(&staged, persisted.hybrid_tuning);
+        self.replace_collection_with(&staged, &canonical)?;
+        // Mirrors the TTL into metadata keyed by the final name
+        self.restore_overrides(&canonical, persisted.overrides);
+
+        if let Err(e) = self.write_native_snapshot(data_dir, &canonical) {
+            warn!(
+                "failed to snapshot collection '{}' after point-in-time restore: {}",
+                canonical, e
+            );
+        }
+
+        info!(
+            "restored collection '{}' to {} from snapshot '{}' and {} WAL operations",
+            canonical,
+            at.to_rfc3339(),
+            base.label,
+            replayed
+        );
+        Ok(PointInTimeRestore {
+            collection: canonical,
+            restored_to: at,
+            base_snapshot: base.label,
+            base_snapshot_at: base.until,
+            replayed_operations: replayed,
+            vector_count,
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use serde_json::json;
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::models::{CollectionConfig, Payload};
+    use crate::persistence::wal::WALConfig;
+
+    fn vector(id: &str, data: Vec<f32>, payload: serde_json::Value) -> Vector {
+        Vector {
+            id: id.to_string(),
+            data,
+            payload: Some(Payload::new(payload)),
+            sparse: None,
+            document_id: None,
+        }
+    }
+
+    /// A store with the WAL in `dir`, its history starting an hour ago
+    async fn store_with_wal(dir: &Path) -> VectorStore {
+        let since = Utc::now() - Duration::hours(1);
+        std::fs::write(dir.join("vectorizer.wal.since"), since.to_rfc3339()).unwrap();
+        let store = VectorStore::new_cpu_only();
+        store
+            .enable_wal(
+                dir.to_path_buf(),
+                Some(WALConfig {
+                    fsync: false,
+                    ..Default::default()
+                }),
+            )
+            .await
+            .unwrap();
+        store
+    }
+
+    /// Let the fire-and-forget WAL writes land
+    async fn settle() {
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+
+    #[tokio::test]
+    async fn test_restore_to_time_undoes_later_writes() {
+        let dir = tempdir().unwrap();
+        let store = store_with_wal(dir.path()).await;
+        let config = CollectionConfig {
+            dimension: 3,
+            ..Default::default()
+        };
+        store.create_collection("docs", config).unwrap();
+        let nested = json!({"title": "b", "page": 2, "tags": ["x", "y"]});
+        store
+            .insert(
+                "docs",
+                vec![
+                    vector("a", vec![1.0, 0.0, 0.0], json!({"title": "a"})),
+                    vector("b", vec![0.0, 1.0, 0.0], nested.clone()),
+                ],
+            )
+            .unwrap();
+        settle().await;
+
+        crate::storage::StorageCompactor::new(dir.path().join("snapshots").join("base"), 3, 1000)
+            .compact_from_memory(&store)
+            .unwrap();
+
+        store
+            .insert(
+                "docs",
+                vec![vector("c", vec![0.0, 0.0, 1.0], json!({"title": "c"}))],
+            )
+            .unwrap();
+        settle().await;
+        let target = Utc::now();
+        settle().await;
+
+        // The accident: a bulk delete and an overwrite
+        store.delete("docs", "a").unwrap();
+        store.delete("docs", "c").unwrap();
+        store
+            .update(
+                "docs",
+                vector("b", vec![0.0, 1.0, 0.0], json!({"title": "oops"})),
+            )
+            .unwrap();
+        settle().await;
+
+        let report = store
+            .restore_collection_to_time_in(dir.path(), "docs", target)
+            .await
+            .unwrap();
+        assert_eq!(
+            report.base_snapshot,
+            Path::new("snapshots").join("base").display().to_string()
+        );
+        assert_eq!(report.vector_count, 3);
+        assert!(report.replayed_operations >= 1);
+
+        let collection = store.get_collection("docs").unwrap();
+        assert_eq!(collection.vector_count(), 3);
+        let b = collection.get_vector("b").unwrap();
+        assert_eq!(b.payload.unwrap().data, nested);
+        assert!(collection.get_vector("c").is_ok());
+        assert_eq!(collection.name(), "docs");
+        drop(collection);
+        // The staging collection was swapped in, not left behind
+        assert!(store.get_collection("docs.pitr-restore").is_err());
+
+        // The result was snapshotted for later restores
+        assert_eq!(
+            dir_entries(
+                &VectorStore::native_snapshot_dir(dir.path(), "docs"),
+                Some(".vecdb.gz")
+            )
+            .len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_restore_to_time_needs_the_wal_and_a_snapshot() {
+        let dir = tempdir().unwrap();
+        let store = VectorStore::new_cpu_only();
+        let err = store
+            .restore_collection_to_time_in(dir.path(), "docs", Utc::now())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, VectorizerError::Configuration(_)));
+
+        let store = store_with_wal(dir.path()).await;
+        store
+            .create_collection(
+                "docs",
+                CollectionConfig {
+                    dimension: 3,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+        // Before the WAL history
+        let err = store
+            .restore_collection_to_time_in(dir.path(), "docs", Utc::now() - Duration::hours(2))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, VectorizerError::NotFound(_)));
+
+        // No snapshot to start from
+        let err = store
+            .restore_collection_to_time_in(dir.path(), "docs", Utc::now())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, VectorizerError::NotFound(_)));
+
+        // In the future
+        let err = store
+            .restore_collection_to_time_in(dir.path(), "docs", Utc::now() + Duration::hours(1))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, VectorizerError::Configuration(_)));
+
+        // The collection was left alone
+        assert!(store.get_collection("docs").is_ok());
+    }
+}
@@ -9,6 +9,7 @@ use serde::{Deserialize, Serialize};
 use tracing::{info, warn};
 
 use super::super::{CollectionType, VectorStore};
+use crate::background_tasks::{self, TASKS};
 use crate::error::{Result, VectorizerError};
 
 // ─── Native snapshot types ────────────────────────────────────────────────────
@@ -30,7 +31,10 @@ impl VectorStore {
     /// Return the directory used to store native per-collection snapshots.
     ///
     /// Layout: `<data_dir>/collection_snapshots/<collection_name>/`
-    fn native_snapshot_dir(data_dir: &std::path::Path, collection_name: &str) -> PathBuf {
+    pub(super) fn native_snapshot_dir(
+        data_dir: &std::path::Path,
+        collection_name: &str,
+    ) -> PathBuf {
         data_dir.join("collection_snapshots").join(collection_name)
     }
 
@@ -42,6 +46,16 @@ impl VectorStore {
     ///
     /// Returns [`NativeSnapshotInfo`] on success.
     pub fn snapshot_collection_native(&self, collection_name: &str) -> Result<NativeSnapshotInfo> {
+        self.write_native_snapshot(&Self::get_data_dir(), collection_name)
+    }
+
+    /// [`Self::snapshot_collection_native`] into the snapshot directory
+    /// under `data_dir`.
+    pub(super) fn write_native_snapshot(
+        &self,
+        data_dir: &std::path::Path,
+        collection_name: &str,
+    ) -> Result<NativeSnapshotInfo> {
         use std::fs::File;
         use std::io::Write;
 
@@ -50,6 +64,9 @@ impl VectorStore {
 
         let canonical = self.resolve_alias_target(collection_name)?;
         let coll_ref = self.get_collection(canonical.as_str())?;
+        // Taken before the vectors are read, so every write after it is
+        // in the WAL entries a point-in-time restore replays on top
+        let now = Utc::now();
 
         let metadata = coll_ref.metadata();
         let vectors: Vec<crate::persistence::PersistedVector> = coll_ref
@@ -65,17 +82,20 @@ impl VectorStore {
                 config: Some(metadata.config),
                 vectors,
                 hnsw_dump_basename: None,
+                recall_profile: coll_ref.recall_profile(),
+                feedback: coll_ref.feedback(),
+                metadata_kv: coll_ref.metadata_kv(),
+                hybrid_tuning: coll_ref.hybrid_tuning(),
+                overrides: coll_ref.overrides(),
             }],
         };
 
         let json = serde_json::to_string(&persisted)
             .map_err(|e| VectorizerError::Serialization(e.to_string()))?;
 
-        let data_dir = Self::get_data_dir();
-        let snap_dir = Self::native_snapshot_dir(&data_dir, &canonical);
+        let snap_dir = Self::native_snapshot_dir(data_dir, &canonical);
         std::fs::create_dir_all(&snap_dir).map_err(VectorizerError::Io)?;
 
-        let now = Utc::now();
         let snap_id = now.format("%Y%m%dT%H%M%SZ").to_string();
         let file_path = snap_dir.join(format!("{}.vecdb.gz", snap_id));
 
@@ -143,10 +163,6 @@ impl VectorStore {
     /// with the snapshot data. Safe to call on a non-existent collection (e.g.
     /// after a `delete_collection`).
     pub fn restore_native_snapshot(&self, collection_name: &str, snapshot_id: &str) -> Result<()> {
-        use std::io::Read;
-
-        use flate2::read::GzDecoder;
-
         let canonical = self.resolve_alias_target(collection_name)?;
         let data_dir = Self::get_data_dir();
         let snap_dir = Self::native_snapshot_dir(&data_dir, &canonical);
@@ -159,18 +175,7 @@ impl VectorStore {
             )));
         }
 
-        let file = std::fs::File::open(&file_path).map_err(VectorizerError::Io)?;
-        let mut decoder = GzDecoder::new(file);
-        let mut json = String::new();
-        decoder
-            .read_to_string(&mut json)
-            .map_err(VectorizerError::Io)?;
-
-        let persisted: crate::persistence::PersistedVectorStore = serde_json::from_str(&json)?;
-
-        let pc = persisted.collections.into_iter().next().ok_or_else(|| {
-            VectorizerError::Storage("snapshot contains no collections".to_string())
-        })?;
+        let pc = Self::read_native_snapshot(&file_path)?;
 
         // Drop existing collection if present (ignore not-found errors).
         let _ = self.delete_collection(&canonical);
@@ -188,17 +193,72 @@ impl VectorStore {
         Ok(())
     }
 
+    /// Read the collection held by the native snapshot file at `path`.
+    pub(super) fn read_native_snapshot(
+        path: &std::path::Path,
+    ) -> Result<crate::persistence::PersistedCollection> {
+        use std::io::Read;
+
+        use flate2::read::GzDecoder;
+
+        let file = std::fs::File::open(path).map_err(VectorizerError::Io)?;
+        let mut decoder = GzDecoder::new(file);
+        let mut json = String::new();
+        decoder
+            .read_to_string(&mut json)
+            .map_err(VectorizerError::Io)?;
+
+        let persisted: crate::persistence::PersistedVectorStore = serde_json::from_str(&json)?;
+
+        persisted
+            .collections
+            .into_iter()
+            .next()
+            .ok_or_else(|| VectorizerError::Storage("snapshot contains no collections".to_string()))
+    }
+
+    /// Delete a native snapshot of `collection_name`. Returns `false` when
+    /// it does not exist.
+    pub fn delete_native_snapshot(&self, collection_name: &str, snapshot_id: &str) -> Result<bool> {
+        if snapshot_id.is_empty() || !snapshot_id.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Ok(false);
+        }
+        let canonical = self.resolve_alias_target(collection_name)?;
+        let data_dir = Self::get_data_dir();
+        let file_path = Self::native_snapshot_dir(&data_dir, &canonical)
+            .join(format!("{}.vecdb.gz", snapshot_id));
+
+        if !file_path.exists() {
+            return Ok(false);
+        }
+        std::fs::remove_file(&file_path).map_err(VectorizerError::Io)?;
+        info!(
+            "deleted native snapshot '{}' of collection '{}'",
+            snapshot_id, canonical
+        );
+        Ok(true)
+    }
+
     /// Rebuild the HNSW index for `collection_name` with new HNSW parameters.
     ///
     /// Delegates to [`Collection::reindex_with_params`]; non-Cpu variants
-    /// return an appropriate error.
+    /// return an appropriate error. Each rebuild is reported to the
+    /// [`REINDEX`](background_tasks::REINDEX) task, and none starts while
+    /// that task is paused.
     pub fn reindex_collection(
         &self,
         collection_name: &str,
         new_params: crate::models::HnswConfig,
     ) -> Result<()> {
+        let task = TASKS.register(background_tasks::REINDEX);
+        if task.is_paused() {
+            return Err(VectorizerError::TaskPaused(
+                background_tasks::REINDEX.to_string(),
+            ));
+        }
         let coll_ref = self.get_collection(collection_name)?;
-        match &*coll_ref {
+        task.start();
+        let result = match &*coll_ref {
             CollectionType::Cpu(c) => c.reindex_with_params(new_params),
             CollectionType::Sharded(_) => Err(VectorizerError::Storage(
                 "reindex is not supported on sharded collections".to_string(),
@@ -210,6 +270,45 @@ impl VectorStore {
             _ => Err(VectorizerError::Storage(
                 "reindex is not supported on this collection type".to_string(),
             )),
+        };
+        task.finish(&result);
+        result
+    }
+
+    /// Measure recall@`k` and latency of the graph search of
+    /// `collection_name` over `sample_size` of its vectors and keep the
+    /// result as its recall profile. Only CPU collections can be
+    /// profiled.
+    pub fn measure_recall_profile(
+        &self,
+        collection_name: &str,
+        sample_size: usize,
+        k: usize,
+    ) -> Result<crate::db::RecallProfile> {
+        let coll_ref = self.get_collection(collection_name)?;
+        match &*coll_ref {
+            CollectionType::Cpu(c) => {
+                c.measure_recall_profile(sample_size, k, crate::db::ProfileTrigger::Request)
+            }
+            _ => Err(VectorizerError::Storage(
+                "recall profiles are only measured on CPU collections".to_string(),
+            )),
+        }
+    }
+
+    /// Move the HNSW index of `collection_name` to disk (`cold = true`) or
+    /// back into memory (`cold = false`).
+    ///
+    /// Delegates to [`Collection::make_cold`] / [`Collection::make_warm`];
+    /// only CPU collections can be cold.
+    pub fn set_collection_cold(&self, collection_name: &str, cold: bool) -> Result<()> {
+        let coll_ref = self.get_collection(collection_name)?;
+        match &*coll_ref {
+            CollectionType::Cpu(c) if cold => c.make_cold(),
+            CollectionType::Cpu(c) => c.make_warm(),
+            _ => Err(VectorizerError::Storage(
+                "only CPU collections support an on-disk HNSW index".to_string(),
+            )),
         }
     }
 }
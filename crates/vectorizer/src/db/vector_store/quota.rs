@@ -0,0 +1,336 @@
+//! Collection size quotas (see [`crate::models::quota`]).
+//!
+//! `insert` checks the quota of each target collection and writes the
+//! batch under the collection's quota lock, so two concurrent inserts
+//! cannot both fit in the last free slot. A `reject` quota refuses a
+//! batch that does not fit. A `summarize` quota merges the oldest
+//! vectors, a group at a time, into summary vectors: on a background
+//! task once an insert takes the collection past its target size, and
+//! on the insert itself only when the background task has fallen so far
+//! behind that the batch would not fit. Each summary is written with
+//! `apply_insert` and its sources removed with `delete`, so WAL replay
+//! and replicas see the same collection.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde_json::{Value, json};
+use tracing::{debug, info, warn};
+
+use super::{CollectionType, VectorStore};
+use crate::error::{Result, VectorizerError};
+use crate::models::{CollectionQuota, Payload, QuotaPolicy, Vector};
+use crate::summarization::{
+    SummarizationConfig, SummarizationManager, SummarizationMethod, SummarizationParams,
+};
+
+/// Payload field describing what a summary vector was merged from.
+pub const QUOTA_SUMMARY_FIELD: &str = "quota_summary";
+
+/// Longest text kept for a summary when the summarizer cannot shorten
+/// it (too little text, or no sentences to pick from).
+const MAX_SUMMARY_CHARS: usize = 4096;
+
+/// Quota bookkeeping of one collection
+#[derive(Debug, Default)]
+pub(crate) struct QuotaState {
+    /// Held from the quota check through the write it admits, and while
+    /// a group of vectors is merged
+    lock: parking_lot::Mutex<()>,
+    /// Set while a background shrink is queued or running
+    shrinking: AtomicBool,
+}
+
+impl VectorStore {
+    /// Insert `vectors` into `collection_name` if its quota lets them in.
+    /// Collections without a quota go straight to `apply_insert`.
+    pub(super) fn insert_within_quota(
+        &self,
+        collection_name: &str,
+        vectors: Vec<Vector>,
+    ) -> Result<()> {
+        let quota = self.get_collection(collection_name)?.config().quota.clone();
+        let Some(quota) = quota else {
+            return self.apply_insert(collection_name, vectors);
+        };
+        let incoming = vectors.len();
+        let exceeded = || VectorizerError::QuotaExceeded {
+            collection: collection_name.to_string(),
+            max_vectors: quota.max_vectors,
+        };
+        if incoming > quota.max_vectors {
+            return Err(exceeded());
+        }
+
+        let state = self.quota_state(collection_name)?;
+        let count = {
+            let _admit = state.lock.lock();
+            let mut current = self.get_collection(collection_name)?.vector_count();
+            if !quota.fits(current, incoming) {
+                if quota.policy == QuotaPolicy::Reject {
+                    return Err(exceeded());
+                }
+                // The background shrink has not kept up; make room here
+                let keep = if incoming <= quota.target_vectors() {
+                    quota.target_vectors() - incoming
+                } else {
+                    quota.max_vectors - incoming
+                };
+                current = self.shrink_to(collection_name, &quota, keep)?;
+                if !quota.fits(current, incoming) {
+                    return Err(exceeded());
+                }
+            }
+            self.apply_insert(collection_name, vectors)?;
+            current + incoming
+        };
+
+        if quota.policy == QuotaPolicy::Summarize && count > quota.target_vectors() {
+            self.schedule_quota_shrink(collection_name, state);
+        }
+        Ok(())
+    }
+
+    /// Quota state of a collection, keyed by its canonical name so an
+    /// alias shares the lock of its target.
+    fn quota_state(&self, collection_name: &str) -> Result<Arc<QuotaState>> {
+        let canonical = self.resolve_alias_target(collection_name)?;
+        Ok(self.quota_states.entry(canonical).or_default().clone())
+    }
+
+    /// Merge the oldest vectors until at most `keep` are left (or no
+    /// group can be merged). Returns the vector count afterwards. The
+    /// caller holds the collection's quota lock.
+    fn shrink_to(
+        &self,
+        collection_name: &str,
+        quota: &CollectionQuota,
+        keep: usize,
+    ) -> Result<usize> {
+        let mut current = self.get_collection(collection_name)?.vector_count();
+        let mut summaries = 0;
+        while current > keep && self.merge_oldest(collection_name, quota, current - keep + 1)? {
+            summaries += 1;
+            let before = current;
+            current = self.get_collection(collection_name)?.vector_count();
+            if current >= before {
+                break;
+            }
+        }
+        info!(
+            "Collection '{}' over its quota of {} vectors: merged the oldest into {} summaries, {} vectors left",
+            collection_name, quota.max_vectors, summaries, current
+        );
+        Ok(current)
+    }
+
+    /// Shrink the collection to its quota's target size on a blocking
+    /// task, one group per lock hold so inserts keep going in between.
+    /// Without a Tokio runtime the next insert that does not fit
+    /// shrinks the collection instead.
+    fn schedule_quota_shrink(&self, collection_name: &str, state: Arc<QuotaState>) {
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        if state.shrinking.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let store = self.clone();
+        let collection_name = collection_name.to_string();
+        runtime.spawn_blocking(move || {
+            let result = store.shrink_to_target(&collection_name, &state);
+            state.shrinking.store(false, Ordering::Release);
+            if let Err(e) = result {
+                warn!(
+                    "Background quota summarization of '{}' failed: {}",
+                    collection_name, e
+                );
+            }
+        });
+    }
+
+    /// Body of [`Self::schedule_quota_shrink`]. Re-reads the quota on
+    /// each round, so a quota changed or removed meanwhile is honoured.
+    fn shrink_to_target(&self, collection_name: &str, state: &QuotaState) -> Result<()> {
+        let mut summaries = 0;
+        let current = loop {
+            let _merge = state.lock.lock();
+            let collection = self.get_collection(collection_name)?;
+            let current = collection.vector_count();
+            let quota = match &collection.config().quota {
+                Some(quota) if quota.policy == QuotaPolicy::Summarize => quota.clone(),
+                _ => break current,
+            };
+            drop(collection);
+            let keep = quota.target_vectors();
+            if current <= keep || !self.merge_oldest(collection_name, &quota, current - keep + 1)? {
+                break current;
+            }
+            summaries += 1;
+            if self.get_collection(collection_name)?.vector_count() >= current {
+                break current;
+            }
+        };
+        if summaries > 0 {
+            info!(
+                "Collection '{}' past its quota target: merged the oldest into {} summaries, {} vectors left",
+                collection_name, summaries, current
+            );
+        }
+        Ok(())
+    }
+
+    /// Merge up to `max_group` of the oldest vectors (capped at the
+    /// quota's group size) into one summary. `false` when fewer than two
+    /// vectors were left to merge.
+    fn merge_oldest(
+        &self,
+        collection_name: &str,
+        quota: &CollectionQuota,
+        max_group: usize,
+    ) -> Result<bool> {
+        let ids = self.oldest_vector_ids(collection_name, quota.merge_group_size.min(max_group))?;
+        if ids.len() < 2 {
+            return Ok(false);
+        }
+        self.summarize_vectors(collection_name, quota, &ids)?;
+        Ok(true)
+    }
+
+    /// IDs of the `n` oldest vectors of a collection.
+    fn oldest_vector_ids(&self, collection_name: &str, n: usize) -> Result<Vec<String>> {
+        let collection = self.get_collection(collection_name)?;
+        match &*collection {
+            CollectionType::Cpu(c) => Ok(c.oldest_vector_ids(n)),
+            _ => Err(VectorizerError::Storage(format!(
+                "quota summarization needs insertion order, which collection '{}' does not keep",
+                collection_name
+            ))),
+        }
+    }
+
+    /// Replace the vectors `ids` with one summary vector: the mean of
+    /// their embeddings (weighted by how many vectors each one already
+    /// stands for) and a summary of their text. Other payload fields
+    /// carry over only when every source has the same value for them
+    /// (a tenant, a session, a document); fields that differ between
+    /// the sources are dropped.
+    fn summarize_vectors(
+        &self,
+        collection_name: &str,
+        quota: &CollectionQuota,
+        ids: &[String],
+    ) -> Result<()> {
+        let sources: Vec<Vector> = ids
+            .iter()
+            .filter_map(|id| self.get_vector(collection_name, id).ok())
+            .collect();
+        let Some(dimension) = sources.first().map(Vector::dimension) else {
+            return Ok(());
+        };
+
+        let mut data = vec![0.0f32; dimension];
+        let mut merged_count = 0u64;
+        let mut texts = Vec::with_capacity(sources.len());
+        for source in &sources {
+            let payload = source.payload.as_ref().map(|p| &p.data);
+            let weight = payload
+                .and_then(|p| p.get(QUOTA_SUMMARY_FIELD))
+                .and_then(|s| s.get("merged_count"))
+                .and_then(Value::as_u64)
+                .unwrap_or(1);
+            for (sum, x) in data.iter_mut().zip(&source.data) {
+                *sum += x * weight as f32;
+            }
+            merged_count += weight;
+            if let Some(text) = payload
+                .and_then(|p| p.get(&quota.text_field))
+                .and_then(Value::as_str)
+                .filter(|t| !t.trim().is_empty())
+            {
+                texts.push(text.trim());
+            }
+        }
+        for x in &mut data {
+            *x /= merged_count as f32;
+        }
+
+        let summary = summarize_text(&texts.join("\n"));
+        let mut payload = common_fields(&sources, &quota.text_field);
+        payload.insert(quota.text_field.clone(), Value::String(summary));
+        payload.insert(
+            QUOTA_SUMMARY_FIELD.to_string(),
+            json!({
+                "merged_ids": sources.iter().map(|v| v.id.as_str()).collect::<Vec<_>>(),
+                "merged_count": merged_count,
+                "created_at": chrono::Utc::now().to_rfc3339(),
+            }),
+        );
+        let summary = Vector::with_payload(
+            self.new_vector_id(collection_name),
+            data,
+            Payload::new(Value::Object(payload)),
+        );
+        debug!(
+            "Merging {} vectors of '{}' into summary '{}'",
+            sources.len(),
+            collection_name,
+            summary.id
+        );
+
+        // Summary first: a failure part-way leaves the sources in place
+        // rather than losing them
+        self.apply_insert(collection_name, vec![summary])?;
+        for source in &sources {
+            if let Err(e) = self.delete(collection_name, &source.id) {
+                warn!(
+                    "Failed to remove summarized vector '{}' from '{}': {}",
+                    source.id, collection_name, e
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Payload fields (other than `text_field` and
+/// [`QUOTA_SUMMARY_FIELD`]) that all of `sources` share, with the same
+/// value.
+fn common_fields(sources: &[Vector], text_field: &str) -> serde_json::Map<String, Value> {
+    let object = |v: &Vector| v.payload.as_ref().and_then(|p| p.data.as_object().cloned());
+    let Some(mut common) = sources.first().and_then(object) else {
+        return serde_json::Map::new();
+    };
+    common.remove(text_field);
+    common.remove(QUOTA_SUMMARY_FIELD);
+    for source in &sources[1..] {
+        let fields = object(source).unwrap_or_default();
+        common.retain(|key, value| fields.get(key) == Some(value));
+    }
+    common
+}
+
+/// Extractive summary of `text`, or `text` itself (cut to
+/// [`MAX_SUMMARY_CHARS`]) when the summarizer has nothing to shorten.
+fn summarize_text(text: &str) -> String {
+    let config = SummarizationConfig {
+        enabled: true,
+        ..SummarizationConfig::default()
+    };
+    let summary = SummarizationManager::new(config).and_then(|mut manager| {
+        manager.summarize_text(SummarizationParams {
+            text: text.to_string(),
+            method: SummarizationMethod::Extractive,
+            max_length: Some(MAX_SUMMARY_CHARS),
+            compression_ratio: None,
+            language: None,
+            collection: None,
+            metadata: HashMap::new(),
+        })
+    });
+    match summary {
+        Ok(result) if !result.summary.trim().is_empty() => result.summary,
+        _ => text.chars().take(MAX_SUMMARY_CHARS).collect(),
+    }
+}
@@ -1,11 +1,19 @@
 //! Search dispatch — thin wrappers that resolve aliases (via
 //! `get_collection`) and hand off to the variant's own search path.
 //!
-//! Two extra entry points are provided in this module:
+//! Extra entry points provided in this module:
 //!
+//! - [`VectorStore::search_exact`] — score every vector instead of
+//!   walking the HNSW graph (ground truth for recall measurement).
 //! - [`VectorStore::search_explained`] — run the same search path as
 //!   [`VectorStore::search`] but return a full execution trace alongside
 //!   the results (used by `POST /collections/{name}/explain`).
+//! - [`VectorStore::full_text_search`] — lexical BM25 ranking over
+//!   text-indexed payload fields, bypassing the HNSW index (used by
+//!   `POST /collections/{name}/search/full_text`).
+//! - [`VectorStore::similarity_matrix`] — pairwise scores between a
+//!   caller-chosen set of vectors (used by
+//!   `POST /collections/{name}/similarity_matrix`).
 //! - Slow-query capture is woven into `search` and `search_explained`
 //!   via an optional `Arc<SlowQueryRing>` parameter; the ring itself is
 //!   stored on `VectorizerServer` and passed through at the handler
@@ -18,8 +26,12 @@ use tracing::debug;
 use super::VectorStore;
 use crate::cache::SlowQueryRing;
 use crate::db::hybrid_search::HybridSearchConfig;
-use crate::error::Result;
-use crate::models::{ExplainResponse, SearchResult};
+use crate::error::{Result, VectorizerError};
+use crate::models::{DistanceMetric, ExplainResponse, SearchResult};
+use crate::search::full_text::{FullTextHit, FullTextQuery};
+use crate::search::similarity_matrix::{
+    self, MAX_SIMILARITY_MATRIX_IDS, MatrixKind, SimilarityMatrix,
+};
 
 impl VectorStore {
     /// Search for similar vectors
@@ -38,6 +50,23 @@ impl VectorStore {
         collection_ref.search(query_vector, k)
     }
 
+    /// Exact (brute-force) search — every vector is scored, so the result
+    /// is the ground truth an HNSW search can be measured against.
+    pub fn search_exact(
+        &self,
+        collection_name: &str,
+        query_vector: &[f32],
+        k: usize,
+    ) -> Result<Vec<SearchResult>> {
+        debug!(
+            "Exact search for {} nearest neighbors in collection '{}'",
+            k, collection_name
+        );
+
+        let collection_ref = self.get_collection(collection_name)?;
+        collection_ref.search_exact(query_vector, k)
+    }
+
     /// Search with slow-query recording.
     ///
     /// Identical to [`search`][VectorStore::search] but records the
@@ -110,4 +139,74 @@ impl VectorStore {
         let collection_ref = self.get_collection(collection_name)?;
         collection_ref.hybrid_search(query_dense, query_sparse, config)
     }
+
+    /// Rank a collection's vectors by BM25 over their text-indexed
+    /// payload fields.
+    ///
+    /// Independent of the vector index: exact identifier lookups (error
+    /// codes, symbol names) that dense embeddings blur together are
+    /// matched token-for-token. Sharded and distributed collections keep
+    /// no payload index and return an error.
+    pub fn full_text_search(
+        &self,
+        collection_name: &str,
+        query: &FullTextQuery,
+    ) -> Result<Vec<FullTextHit>> {
+        debug!(
+            "Full-text search in collection '{}' (fields={:?}, limit={})",
+            collection_name, query.fields, query.limit
+        );
+
+        let collection_ref = self.get_collection(collection_name)?;
+        collection_ref.full_text_search(query)
+    }
+
+    /// Pairwise similarity (or distance) matrix between `ids`.
+    ///
+    /// `metric` defaults to the collection's own metric. Unknown IDs are
+    /// reported in [`SimilarityMatrix::missing`] rather than failing the
+    /// whole request; duplicate IDs are collapsed.
+    pub fn similarity_matrix(
+        &self,
+        collection_name: &str,
+        ids: &[String],
+        metric: Option<DistanceMetric>,
+    ) -> Result<SimilarityMatrix> {
+        if ids.len() > MAX_SIMILARITY_MATRIX_IDS {
+            return Err(VectorizerError::InvalidConfiguration {
+                message: format!(
+                    "similarity matrix accepts at most {} ids, got {}",
+                    MAX_SIMILARITY_MATRIX_IDS,
+                    ids.len()
+                ),
+            });
+        }
+
+        let collection_ref = self.get_collection(collection_name)?;
+        let metric = metric.unwrap_or(collection_ref.config().metric);
+
+        let mut seen = std::collections::HashSet::new();
+        let mut vectors = Vec::with_capacity(ids.len());
+        let mut missing = Vec::new();
+        for id in ids {
+            if !seen.insert(id.as_str()) {
+                continue;
+            }
+            match collection_ref.get_vector(id) {
+                Ok(vector) => vectors.push(vector),
+                Err(VectorizerError::VectorNotFound(_)) => missing.push(id.clone()),
+                Err(e) => return Err(e),
+            }
+        }
+        drop(collection_ref);
+
+        let matrix = similarity_matrix::compute(&vectors, metric);
+        Ok(SimilarityMatrix {
+            ids: vectors.into_iter().map(|v| v.id).collect(),
+            metric,
+            kind: MatrixKind::for_metric(metric),
+            matrix,
+            missing,
+        })
+    }
 }
@@ -0,0 +1,318 @@
+//! Companion summary collections (`<collection>_summaries`).
+//!
+//! With summarization `enabled` and `auto_summarize` on (see
+//! [`VectorStore::set_summary_collections`]), every collection gets a
+//! companion holding one summary vector per source document: the
+//! chunks sharing a `file_path`, or a single vector without one. The
+//! summary's payload lists the chunks it was made from under
+//! [`SUMMARY_SOURCE_IDS_FIELD`], and its embedding is the mean of
+//! theirs, so it lives in the same space as the source collection.
+//!
+//! Inserts, updates and deletes through the store refresh the summaries
+//! of the documents they touch; a summary whose chunks are all gone is
+//! removed, and dropping a collection drops its companion. Encrypted
+//! and tenant-partitioned collections get no companion: their payloads
+//! cannot be merged into one shared summary.
+
+use serde_json::{Value, json};
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use super::VectorStore;
+use crate::error::Result;
+use crate::models::{CollectionConfig, Payload, Vector};
+use crate::summarization::{SummarizationConfig, SummarizationManager};
+
+/// Suffix of a collection's companion summary collection.
+pub const SUMMARY_COLLECTION_SUFFIX: &str = "_summaries";
+
+/// Payload field of a summary listing the IDs of its source chunks.
+pub const SUMMARY_SOURCE_IDS_FIELD: &str = "source_ids";
+
+/// Payload field holding a chunk's text, and a summary's.
+const TEXT_FIELD: &str = "content";
+
+/// Payload field grouping chunks into one document.
+const DOCUMENT_FIELD: &str = "file_path";
+
+/// Longest text of a document handed to the summarizer; the rest of a
+/// very large document does not make it into the summary.
+const MAX_SOURCE_CHARS: usize = 50_000;
+
+/// Longest text kept for a summary when the summarizer cannot shorten
+/// it (too little text, or no sentences to pick from).
+const MAX_SUMMARY_CHARS: usize = 4096;
+
+/// Installed summary linking (see [`VectorStore::set_summary_collections`])
+pub(crate) struct SummaryCollections {
+    config: SummarizationConfig,
+    /// Held while a summary is rebuilt, so two writes to one document
+    /// never both create its summary
+    lock: parking_lot::Mutex<()>,
+}
+
+/// Name of the companion summary collection of `collection_name`.
+pub fn summary_collection_name(collection_name: &str) -> String {
+    format!("{collection_name}{SUMMARY_COLLECTION_SUFFIX}")
+}
+
+/// Source chunks of a document whose summary needs rebuilding: the
+/// document key and the chunk IDs just written to it.
+pub(super) type LinkedDocuments = Vec<(String, Vec<String>)>;
+
+impl VectorStore {
+    /// Keep a `<collection>_summaries` companion in sync with every
+    /// collection when `config` has `enabled` and `auto_summarize` set;
+    /// otherwise turn summary linking off.
+    pub fn set_summary_collections(&self, config: SummarizationConfig) {
+        let enabled = config.enabled && config.auto_summarize;
+        *self.summary_collections.write() = enabled.then(|| {
+            std::sync::Arc::new(SummaryCollections {
+                config,
+                lock: parking_lot::Mutex::new(()),
+            })
+        });
+    }
+
+    /// The canonical name of `collection_name` when its writes are
+    /// summarized into a companion collection.
+    fn summary_source(&self, collection_name: &str) -> Option<String> {
+        self.summary_collections.read().as_ref()?;
+        let canonical = self.resolve_alias_target(collection_name).ok()?;
+        if canonical.ends_with(SUMMARY_COLLECTION_SUFFIX) {
+            return None;
+        }
+        let collection = self.get_collection(&canonical).ok()?;
+        let config = collection.config();
+        (config.encryption.is_none() && config.tenant_partition.is_none()).then_some(canonical)
+    }
+
+    /// The documents `vectors` belong to, when `collection_name` has a
+    /// companion; captured before the insert moves them.
+    pub(super) fn summary_documents(
+        &self,
+        collection_name: &str,
+        vectors: &[Vector],
+    ) -> Option<LinkedDocuments> {
+        self.summary_source(collection_name)?;
+        let mut documents: LinkedDocuments = Vec::new();
+        for vector in vectors {
+            let key = document_key(vector);
+            match documents.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, ids)) => ids.push(vector.id.clone()),
+                None => documents.push((key, vec![vector.id.clone()])),
+            }
+        }
+        Some(documents)
+    }
+
+    /// The document the stored vector `vector_id` belongs to, when
+    /// `collection_name` has a companion; read before an update or
+    /// delete changes it.
+    pub(super) fn summary_document_of(
+        &self,
+        collection_name: &str,
+        vector_id: &str,
+    ) -> Option<String> {
+        self.summary_source(collection_name)?;
+        let vector = self.get_vector(collection_name, vector_id).ok()?;
+        Some(document_key(&vector))
+    }
+
+    /// Rebuild the summaries of `documents` after a write to
+    /// `collection_name`. The write already succeeded, so a failure is
+    /// logged rather than returned.
+    pub(super) fn refresh_summaries(&self, collection_name: &str, documents: LinkedDocuments) {
+        let Some(linking) = self.summary_collections.read().clone() else {
+            return;
+        };
+        let Some(canonical) = self.summary_source(collection_name) else {
+            return;
+        };
+        let _guard = linking.lock.lock();
+        for (key, ids) in documents {
+            if let Err(e) = self.refresh_summary(&linking.config, &canonical, &key, &ids) {
+                warn!(
+                    "Failed to refresh the summary of '{}' in '{}': {}",
+                    key,
+                    summary_collection_name(&canonical),
+                    e
+                );
+            }
+        }
+    }
+
+    /// Rebuild the summary of document `key` from the chunks it already
+    /// lists plus `written`, dropping chunks that were deleted or moved
+    /// to another document.
+    fn refresh_summary(
+        &self,
+        config: &SummarizationConfig,
+        collection_name: &str,
+        key: &str,
+        written: &[String],
+    ) -> Result<()> {
+        let companion = summary_collection_name(collection_name);
+        let summary_id = summary_id(key);
+        let existing = self
+            .get_vector(&companion, &summary_id)
+            .ok()
+            .map(|summary| source_ids(&summary));
+        let mut ids = existing.clone().unwrap_or_default();
+        for id in written {
+            if !ids.contains(id) {
+                ids.push(id.clone());
+            }
+        }
+
+        let sources: Vec<Vector> = ids
+            .iter()
+            .filter_map(|id| self.get_vector(collection_name, id).ok())
+            .filter(|vector| document_key(vector) == key)
+            .collect();
+        let texts: Vec<&str> = sources
+            .iter()
+            .filter_map(|v| v.payload.as_ref()?.data.get(TEXT_FIELD)?.as_str())
+            .map(str::trim)
+            .filter(|text| !text.is_empty())
+            .collect();
+        if texts.is_empty() {
+            if existing.is_some() {
+                debug!("Removing summary '{}' of '{}'", summary_id, key);
+                self.delete(&companion, &summary_id)?;
+            }
+            return Ok(());
+        }
+
+        let dimension = sources[0].dimension();
+        let mut data = vec![0.0f32; dimension];
+        for source in &sources {
+            for (sum, x) in data.iter_mut().zip(&source.data) {
+                *sum += x;
+            }
+        }
+        for x in &mut data {
+            *x /= sources.len() as f32;
+        }
+
+        let text: String = texts.join("\n").chars().take(MAX_SOURCE_CHARS).collect();
+        let file_path = file_path(&sources[0]);
+        let chunk_ids: Vec<&str> = sources.iter().map(|v| v.id.as_str()).collect();
+        let mut payload = json!({
+            TEXT_FIELD: summarize_document(config, &text, &summary_id, file_path),
+            "is_summary": true,
+            "source_collection": collection_name,
+            SUMMARY_SOURCE_IDS_FIELD: chunk_ids,
+            "source_count": sources.len(),
+            "summary_method": config.default_method,
+            "updated_at": chrono::Utc::now().to_rfc3339(),
+        });
+        if let Some(path) = file_path {
+            payload[DOCUMENT_FIELD] = Value::String(path.to_string());
+        }
+        let summary = Vector::with_payload(summary_id, data, Payload::new(payload));
+
+        if existing.is_some() {
+            self.apply_update(&companion, summary)
+        } else {
+            self.ensure_summary_collection(collection_name, &companion)?;
+            self.apply_insert(&companion, vec![summary])
+        }
+    }
+
+    /// Create `companion` for `collection_name` if it does not exist
+    /// yet, with the source's dimension, metric and index settings.
+    fn ensure_summary_collection(&self, collection_name: &str, companion: &str) -> Result<()> {
+        if self.get_collection(companion).is_ok() {
+            return Ok(());
+        }
+        let config = {
+            let source = self.get_collection(collection_name)?;
+            let source = source.config();
+            CollectionConfig {
+                dimension: source.dimension,
+                metric: source.metric,
+                hnsw_config: source.hnsw_config.clone(),
+                quantization: source.quantization.clone(),
+                compression: source.compression.clone(),
+                embedding_provider: source.embedding_provider.clone(),
+                normalization: source.normalization.clone(),
+                storage_type: source.storage_type,
+                ..Default::default()
+            }
+        };
+        info!(
+            "Creating summary collection '{}' for '{}'",
+            companion, collection_name
+        );
+        self.create_collection(companion, config)
+    }
+
+    /// Drop the companion of a deleted collection, if it has one.
+    pub(super) fn drop_summary_collection(&self, collection_name: &str) {
+        if self.summary_collections.read().is_none()
+            || collection_name.ends_with(SUMMARY_COLLECTION_SUFFIX)
+        {
+            return;
+        }
+        let companion = summary_collection_name(collection_name);
+        if self.get_collection(&companion).is_err() {
+            return;
+        }
+        if let Err(e) = self.delete_collection(&companion) {
+            warn!("Failed to drop summary collection '{}': {}", companion, e);
+        }
+    }
+}
+
+/// The file a chunk was indexed from, if any.
+fn file_path(vector: &Vector) -> Option<&str> {
+    vector
+        .payload
+        .as_ref()
+        .and_then(|p| p.data.get(DOCUMENT_FIELD))
+        .and_then(Value::as_str)
+}
+
+/// The document a chunk belongs to: its `file_path`, or its own ID.
+fn document_key(vector: &Vector) -> String {
+    file_path(vector).unwrap_or(&vector.id).to_string()
+}
+
+/// ID of the summary of document `key`, stable across rebuilds.
+fn summary_id(key: &str) -> String {
+    Uuid::new_v5(&Uuid::NAMESPACE_OID, key.as_bytes()).to_string()
+}
+
+/// The source chunk IDs a summary lists.
+fn source_ids(summary: &Vector) -> Vec<String> {
+    summary
+        .payload
+        .as_ref()
+        .and_then(|p| p.data.get(SUMMARY_SOURCE_IDS_FIELD))
+        .and_then(Value::as_array)
+        .map(|ids| {
+            ids.iter()
+                .filter_map(Value::as_str)
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Summary of a document's `text` with the configured default method,
+/// or `text` itself (cut to [`MAX_SUMMARY_CHARS`]) when the summarizer
+/// has nothing to shorten.
+fn summarize_document(
+    config: &SummarizationConfig,
+    text: &str,
+    summary_id: &str,
+    file_path: Option<&str>,
+) -> String {
+    let summary = SummarizationManager::new(config.clone())
+        .and_then(|mut manager| manager.auto_summarize(text, summary_id, file_path));
+    match summary {
+        Ok(result) if !result.summary.trim().is_empty() => result.summary,
+        _ => text.chars().take(MAX_SUMMARY_CHARS).collect(),
+    }
+}
@@ -1,25 +1,107 @@
 //! Vector-level CRUD dispatched through `VectorStore`.
 //!
-//! Each method writes to the WAL first (when WAL is enabled), then
-//! updates the in-memory collection, then marks the collection for
-//! auto-save. Batched inserts use 1000-vector chunks so the per-call
-//! DashMap lock scope stays bounded.
+//! Each method enters the write fence (refused while a cluster snapshot
+//! holds it, see [`crate::db::write_fence`]), writes to the WAL (when
+//! WAL is enabled), then updates the in-memory collection, then marks
+//! the collection for auto-save, then publishes the change to the
+//! change feed if one is installed. Batched inserts use 1000-vector
+//! chunks so the per-call DashMap lock scope stays bounded. `insert`,
+//! `update` and `delete` then refresh the summaries of the documents
+//! they touched (see [`super::summaries`]); the `apply_*` variants used
+//! by replay and replication do not, since the companion collection's
+//! own writes are replayed alongside. `insert`, `update` and `delete`
+//! also hold the collection's write lock, so a read-modify-write done
+//! under it ([`VectorStore::modify_vector`]) cannot lose a concurrent
+//! write.
 
+use std::sync::Arc;
+
+use parking_lot::ReentrantMutex;
+use serde_json::Value;
 use tracing::debug;
 
 use super::{CollectionType, VectorStore};
+use crate::db::change_feed::ChangeKind;
 use crate::error::{Result, VectorizerError};
-use crate::models::Vector;
+use crate::models::{DedupStatus, Payload, Vector};
+use crate::plugins::WriteOp;
 
 impl VectorStore {
-    /// Insert vectors into a collection
+    /// Insert vectors into a collection.
+    ///
+    /// Vectors go through the write plugins first; a plugin may route
+    /// some of them to other collections, and a rejection of any one
+    /// refuses the whole batch before anything is written. Each target
+    /// collection's dedup policy and quota are then applied to its share
+    /// of the batch.
     pub fn insert(&self, collection_name: &str, vectors: Vec<Vector>) -> Result<()> {
+        self.insert_deduplicated(collection_name, vectors)?;
+        Ok(())
+    }
+
+    /// [`Self::insert`], returning what the target collections' dedup
+    /// policies did with each vector, in input order. Every vector is
+    /// [`DedupStatus::Inserted`] in collections without a policy.
+    pub fn insert_deduplicated(
+        &self,
+        collection_name: &str,
+        vectors: Vec<Vector>,
+    ) -> Result<Vec<DedupStatus>> {
+        let Some(chain) = self.write_plugins.read().clone() else {
+            return self.insert_into(collection_name, vectors);
+        };
+
+        let count = vectors.len();
+        let mut batches: Vec<(String, Vec<usize>, Vec<Vector>)> = Vec::new();
+        for (position, vector) in vectors.into_iter().enumerate() {
+            let (target, vector) = chain.apply(WriteOp::Insert, collection_name, vector)?;
+            match batches.iter_mut().find(|(name, _, _)| *name == target) {
+                Some((_, positions, batch)) => {
+                    positions.push(position);
+                    batch.push(vector);
+                }
+                None => batches.push((target, vec![position], vec![vector])),
+            }
+        }
+        let mut statuses = vec![DedupStatus::Inserted; count];
+        for (target, positions, batch) in batches {
+            for (position, status) in positions.into_iter().zip(self.insert_into(&target, batch)?) {
+                statuses[position] = status;
+            }
+        }
+        Ok(statuses)
+    }
+
+    /// Dedup, quota and write of the vectors bound for one collection.
+    fn insert_into(&self, collection_name: &str, vectors: Vec<Vector>) -> Result<Vec<DedupStatus>> {
+        let lock = self.write_lock(collection_name);
+        let _writing = lock.lock();
+        let (vectors, statuses) = self.deduplicate(collection_name, vectors)?;
+        // Every vector was a duplicate
+        if vectors.is_empty() && !statuses.is_empty() {
+            return Ok(statuses);
+        }
+        let documents = self.summary_documents(collection_name, &vectors);
+        self.insert_within_quota(collection_name, vectors)?;
+        if let Some(documents) = documents {
+            self.refresh_summaries(collection_name, documents);
+        }
+        Ok(statuses)
+    }
+
+    /// Insert vectors without running the write plugins. Used for
+    /// writes that already went through them on the node that accepted
+    /// them (WAL replay, replication, Raft apply).
+    pub(crate) fn apply_insert(&self, collection_name: &str, vectors: Vec<Vector>) -> Result<()> {
         debug!(
             "Inserting {} vectors into collection '{}'",
             vectors.len(),
             collection_name
         );
 
+        let _write = self.write_fence.enter()?;
+        self.check_vector_ids(collection_name, vectors.iter().map(|v| v.id.as_str()))?;
+
         // Log to WAL before applying changes
         self.log_wal_insert(collection_name, &vectors)?;
 
@@ -41,20 +123,155 @@ impl VectorStore {
 
         // Mark collection for auto-save
         self.mark_collection_for_save(collection_name);
+        self.notify_mutation(collection_name);
+
+        for vector in &vectors {
+            self.publish_change(
+                collection_name,
+                &vector.id,
+                ChangeKind::Insert,
+                vector.payload.as_ref(),
+            );
+        }
 
         Ok(())
     }
 
-    /// Update a vector in a collection
+    /// ID for a vector inserted into `collection_name` without one, from
+    /// the collection's ID policy. A random UUID when the collection has
+    /// no policy (or does not exist; the insert then fails anyway).
+    pub fn new_vector_id(&self, collection_name: &str) -> String {
+        self.get_collection(collection_name)
+            .ok()
+            .and_then(|collection| collection.config().id_policy.as_ref().map(|p| p.generator))
+            .unwrap_or_default()
+            .generate()
+    }
+
+    /// Check `ids` against the collection's ID policy, if it has one,
+    /// before anything reaches the WAL.
+    fn check_vector_ids<'a>(
+        &self,
+        collection_name: &str,
+        ids: impl IntoIterator<Item = &'a str>,
+    ) -> Result<()> {
+        let collection = self.get_collection(collection_name)?;
+        match &collection.config().id_policy {
+            Some(policy) => ids.into_iter().try_for_each(|id| policy.validate_id(id)),
+            None => Ok(()),
+        }
+    }
+
+    /// Update a vector in a collection, after the write plugins (which
+    /// may route it to another collection).
     pub fn update(&self, collection_name: &str, vector: Vector) -> Result<()> {
+        let (target, vector) = match self.write_plugins.read().clone() {
+            Some(chain) => chain.apply(WriteOp::Update, collection_name, vector)?,
+            None => (collection_name.to_string(), vector),
+        };
+        let lock = self.write_lock(&target);
+        let _writing = lock.lock();
+
+        // The vector may move to another document; both summaries change
+        let previous = self.summary_document_of(&target, &vector.id);
+        let documents = self.summary_documents(&target, std::slice::from_ref(&vector));
+        self.apply_update(&target, vector)?;
+        if let Some(mut documents) = documents {
+            if let Some(previous) = previous
+                && documents.iter().all(|(key, _)| *key != previous)
+            {
+                documents.push((previous, Vec::new()));
+            }
+            self.refresh_summaries(&target, documents);
+        }
+        Ok(())
+    }
+
+    /// Read `vector_id`, let `modify` rewrite it and write it back
+    /// through [`Self::update`], holding the collection's write lock from
+    /// the read through the write so no other insert, update, delete or
+    /// modification of the collection lands in between.
+    pub fn modify_vector<T, E: From<VectorizerError>>(
+        &self,
+        collection_name: &str,
+        vector_id: &str,
+        modify: impl FnOnce(&mut Vector) -> std::result::Result<T, E>,
+    ) -> std::result::Result<T, E> {
+        let lock = self.write_lock(collection_name);
+        let _writing = lock.lock();
+
+        let mut vector = self.get_vector(collection_name, vector_id)?;
+        let modified = modify(&mut vector)?;
+        self.update(collection_name, vector)?;
+        Ok(modified)
+    }
+
+    /// Apply a JSON merge patch (RFC 7396) to the payload of `vector_id`
+    /// and return the payload as written, through
+    /// [`Self::modify_vector`] so concurrent patches of different fields
+    /// of a vector all land.
+    ///
+    /// `finish` sees the patched vector before it is written (a
+    /// partitioned collection re-stamps its key there). Encrypted
+    /// payloads cannot be patched.
+    pub fn patch_payload<E: From<VectorizerError>>(
+        &self,
+        collection_name: &str,
+        vector_id: &str,
+        patch: &Value,
+        finish: impl FnOnce(&mut Vector) -> std::result::Result<(), E>,
+    ) -> std::result::Result<Value, E> {
+        self.modify_vector(collection_name, vector_id, |vector| {
+            let mut payload = vector.payload.take().unwrap_or_default();
+            if payload.is_encrypted() {
+                return Err(VectorizerError::InvalidConfiguration {
+                    message: "encrypted payloads cannot be patched; replace the payload instead"
+                        .to_string(),
+                }
+                .into());
+            }
+            payload.merge_patch(patch);
+            vector.payload = Some(payload);
+            finish(vector)?;
+            vector.payload = vector.payload.take().filter(|p| !p.data.is_null());
+            Ok(vector
+                .payload
+                .as_ref()
+                .map_or(Value::Null, |p| p.data.clone()))
+        })
+    }
+
+    /// The lock serializing the vector writes of `collection_name`,
+    /// keyed by the collection an alias points at. Reentrant, so a
+    /// [`Self::modify_vector`] holding it can write through
+    /// [`Self::update`].
+    fn write_lock(&self, collection_name: &str) -> Arc<ReentrantMutex<()>> {
+        let canonical = self
+            .resolve_alias_target(collection_name)
+            .unwrap_or_else(|_| collection_name.to_string());
+        self.write_locks.entry(canonical).or_default().clone()
+    }
+
+    /// Update a vector without running the write plugins (see
+    /// [`Self::apply_insert`]).
+    pub(crate) fn apply_update(&self, collection_name: &str, vector: Vector) -> Result<()> {
         debug!(
             "Updating vector '{}' in collection '{}'",
             vector.id, collection_name
         );
 
+        let _write = self.write_fence.enter()?;
+        self.check_vector_ids(collection_name, [vector.id.as_str()])?;
+
         // Log to WAL before applying changes
         self.log_wal_update(collection_name, &vector)?;
 
+        // Captured before the vector moves into the collection
+        let change = self
+            .change_feed()
+            .is_some()
+            .then(|| (vector.id.clone(), vector.payload.clone()));
+
         // Prefer a shared DashMap shard reference for variants whose inner
         // update uses interior mutability (CPU, Sharded), mirroring the
         // pattern `delete` uses below. Holding only a shared shard lock
@@ -95,6 +312,11 @@ impl VectorStore {
 
         // Mark collection for auto-save
         self.mark_collection_for_save(collection_name);
+        self.notify_mutation(collection_name);
+
+        if let Some((id, payload)) = change {
+            self.publish_change(collection_name, &id, ChangeKind::Update, payload.as_ref());
+        }
 
         Ok(())
     }
@@ -106,6 +328,10 @@ impl VectorStore {
             vector_id, collection_name
         );
 
+        let lock = self.write_lock(collection_name);
+        let _writing = lock.lock();
+        let _write = self.write_fence.enter()?;
+        let document = self.summary_document_of(collection_name, vector_id);
         // Log to WAL before applying changes
         self.log_wal_delete(collection_name, vector_id)?;
 
@@ -146,13 +372,93 @@ impl VectorStore {
 
         // Mark collection for auto-save
         self.mark_collection_for_save(collection_name);
+        self.notify_mutation(collection_name);
+
+        self.publish_change(collection_name, vector_id, ChangeKind::Delete, None);
+
+        if let Some(document) = document {
+            self.refresh_summaries(collection_name, vec![(document, Vec::new())]);
+        }
 
         Ok(())
     }
 
+    /// Publish a vector write to the change feed, if one is installed,
+    /// under the collection's canonical name rather than the alias it
+    /// was written through.
+    fn publish_change(
+        &self,
+        collection_name: &str,
+        vector_id: &str,
+        kind: ChangeKind,
+        payload: Option<&Payload>,
+    ) {
+        let Some(feed) = self.change_feed() else {
+            return;
+        };
+        let collection = self
+            .resolve_alias_target(collection_name)
+            .unwrap_or_else(|_| collection_name.to_string());
+        feed.publish(&collection, vector_id, kind, payload);
+    }
+
+    /// Tell the mutation listener, if one is installed, that
+    /// `collection_name` changed: under its canonical name, then under
+    /// each alias of it, since callers may have read it through either.
+    /// Writes that go around the store's own insert, update and delete
+    /// (cluster applies, shard migration, the Qdrant gRPC point API)
+    /// must call this themselves.
+    pub fn notify_mutation(&self, collection_name: &str) {
+        if self.mutation_listener.read().is_none() {
+            return;
+        }
+        let canonical = self
+            .resolve_alias_target(collection_name)
+            .unwrap_or_else(|_| collection_name.to_string());
+        let aliases: Vec<String> = self
+            .aliases
+            .iter()
+            .filter(|entry| *entry.value() == canonical)
+            .map(|entry| entry.key().clone())
+            .collect();
+        self.notify_name(&canonical);
+        for alias in &aliases {
+            self.notify_name(alias);
+        }
+    }
+
+    /// Tell the mutation listener, if one is installed, about `name`
+    /// alone (an alias that was dropped or renamed).
+    pub(super) fn notify_name(&self, name: &str) {
+        let listener = self.mutation_listener.read().clone();
+        if let Some(listener) = listener {
+            listener(name);
+        }
+    }
+
     /// Get a vector by ID
     pub fn get_vector(&self, collection_name: &str, vector_id: &str) -> Result<Vector> {
         let collection_ref = self.get_collection(collection_name)?;
         collection_ref.get_vector(vector_id)
     }
+
+    /// Get many vectors by ID with a single collection lookup, in the
+    /// order of `vector_ids`. An ID the collection does not hold is
+    /// `None`; any other failure (a missing collection, a distributed
+    /// collection) fails the whole call.
+    pub fn get_vectors(
+        &self,
+        collection_name: &str,
+        vector_ids: &[String],
+    ) -> Result<Vec<Option<Vector>>> {
+        let collection_ref = self.get_collection(collection_name)?;
+        vector_ids
+            .iter()
+            .map(|id| match collection_ref.get_vector(id) {
+                Ok(vector) => Ok(Some(vector)),
+                Err(VectorizerError::VectorNotFound(_)) => Ok(None),
+                Err(e) => Err(e),
+            })
+            .collect()
+    }
 }
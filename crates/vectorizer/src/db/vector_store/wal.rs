@@ -12,6 +12,7 @@
 //!   the `is_enabled()` branches naturally short-circuit.
 //! - [`recover_from_wal`], [`recover_and_replay_wal`],
 //!   [`recover_all_from_wal`] implement crash recovery at startup.
+//! - [`prune_wal`] drops history older than `storage.wal.retention_hours`.
 //! - [`log_wal_insert`]: [`VectorStore::log_wal_insert`]
 //! - [`log_wal_update`]: [`VectorStore::log_wal_update`]
 //! - [`log_wal_delete`]: [`VectorStore::log_wal_delete`]
@@ -19,13 +20,15 @@
 //! - [`recover_from_wal`]: [`VectorStore::recover_from_wal`]
 //! - [`recover_and_replay_wal`]: [`VectorStore::recover_and_replay_wal`]
 //! - [`recover_all_from_wal`]: [`VectorStore::recover_all_from_wal`]
+//! - [`prune_wal`]: [`VectorStore::prune_wal`]
 
 use std::path::PathBuf;
 
+use chrono::{DateTime, Utc};
 use tracing::{debug, error, info, warn};
 
 use super::VectorStore;
-use crate::db::wal_integration::WalIntegration;
+use crate::db::wal_integration::{WalIntegration, payload_from_metadata};
 use crate::error::{Result, VectorizerError};
 use crate::models::Vector;
 
@@ -170,6 +173,24 @@ impl VectorStore {
         Ok(())
     }
 
+    /// Whether operations are being written to the WAL
+    pub fn is_wal_enabled(&self) -> bool {
+        self.wal.lock().as_ref().is_some_and(|wal| wal.is_enabled())
+    }
+
+    /// Drop WAL entries written before `cutoff`. Returns how many were
+    /// dropped; 0 when WAL is disabled.
+    pub async fn prune_wal(&self, cutoff: DateTime<Utc>) -> Result<usize> {
+        let wal = self.wal.lock().clone();
+        match wal {
+            Some(wal) => wal
+                .prune_before(cutoff)
+                .await
+                .map_err(|e| VectorizerError::Storage(format!("WAL prune failed: {}", e))),
+            None => Ok(0),
+        }
+    }
+
     /// Recover collection from WAL after crash
     pub async fn recover_from_wal(
         &self,
@@ -214,32 +235,16 @@ impl VectorStore {
                     metadata,
                     collection_name: _,
                 } => {
-                    // Reconstruct payload from metadata
-                    let payload = if !metadata.is_empty() {
-                        use serde_json::json;
-
-                        use crate::models::Payload;
-                        let mut payload_data = serde_json::Map::new();
-                        for (k, v) in metadata {
-                            payload_data.insert(k.clone(), json!(v));
-                        }
-                        Some(Payload {
-                            data: json!(payload_data),
-                        })
-                    } else {
-                        None
-                    };
-
                     let vector = Vector {
                         id: vector_id.clone(),
                         data: data.clone(),
-                        payload,
+                        payload: payload_from_metadata(metadata),
                         sparse: None,
                         document_id: None,
                     };
 
                     // Try to insert (may fail if already exists, which is OK)
-                    if self.insert(collection_name, vec![vector]).is_ok() {
+                    if self.apply_insert(collection_name, vec![vector]).is_ok() {
                         replayed += 1;
                     }
                 }
@@ -250,36 +255,16 @@ impl VectorStore {
                     collection_name: _,
                 } => {
                     if let Some(data) = data {
-                        // Reconstruct payload from metadata
-                        let payload = if let Some(metadata) = metadata {
-                            if !metadata.is_empty() {
-                                use serde_json::json;
-
-                                use crate::models::Payload;
-                                let mut payload_data = serde_json::Map::new();
-                                for (k, v) in metadata {
-                                    payload_data.insert(k.clone(), json!(v));
-                                }
-                                Some(Payload {
-                                    data: json!(payload_data),
-                                })
-                            } else {
-                                None
-                            }
-                        } else {
-                            None
-                        };
-
                         let vector = Vector {
                             id: vector_id.clone(),
                             data: data.clone(),
-                            payload,
+                            payload: metadata.as_ref().and_then(payload_from_metadata),
                             sparse: None,
                             document_id: None,
                         };
 
                         // Try to update (may fail if doesn't exist, which is OK)
-                        if self.update(collection_name, vector).is_ok() {
+                        if self.apply_update(collection_name, vector).is_ok() {
                             replayed += 1;
                         }
                     }
@@ -24,6 +24,13 @@ fn test_create_and_list_collections() {
         storage_type: Some(crate::models::StorageType::Memory),
         graph: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     // Get initial collection count
@@ -64,6 +71,13 @@ fn test_duplicate_collection_error() {
         storage_type: Some(crate::models::StorageType::Memory),
         graph: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     // Create collection
@@ -93,6 +107,13 @@ fn test_delete_collection() {
         storage_type: Some(crate::models::StorageType::Memory),
         graph: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     // Get initial collection count
@@ -133,6 +154,13 @@ fn test_stats_functionality() {
         storage_type: Some(crate::models::StorageType::Memory),
         graph: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     // Get initial stats
@@ -179,6 +207,13 @@ fn test_concurrent_operations() {
         storage_type: Some(crate::models::StorageType::Memory),
         graph: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     // Create collection from main thread
@@ -235,6 +270,13 @@ fn test_collection_metadata() {
         storage_type: Some(crate::models::StorageType::Memory),
         graph: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     store
@@ -255,3 +297,701 @@ fn test_collection_metadata() {
     assert_eq!(metadata.config.dimension, 768);
     assert_eq!(metadata.config.metric, DistanceMetric::Cosine);
 }
+
+fn quota_config(quota: crate::models::CollectionQuota) -> CollectionConfig {
+    CollectionConfig {
+        dimension: 4,
+        metric: DistanceMetric::Euclidean,
+        quantization: crate::models::QuantizationConfig::None,
+        quota: Some(quota),
+        ..Default::default()
+    }
+}
+
+fn memory(i: usize) -> Vector {
+    Vector::with_payload(
+        format!("m{i}"),
+        vec![i as f32, 1.0, 0.0, 0.0],
+        Payload::new(serde_json::json!({
+            "content": format!("Memory number {i} is about topic {i}. It was stored early on.")
+        })),
+    )
+}
+
+#[test]
+fn test_quota_rejects_inserts_past_max_vectors() {
+    use crate::models::{CollectionQuota, QuotaPolicy};
+
+    let store = VectorStore::new_cpu_only();
+    store
+        .create_collection(
+            "quota_reject",
+            quota_config(CollectionQuota::new(3, QuotaPolicy::Reject)),
+        )
+        .unwrap();
+    store
+        .insert("quota_reject", (0..3).map(memory).collect())
+        .unwrap();
+
+    let err = store.insert("quota_reject", vec![memory(3)]).unwrap_err();
+    assert!(matches!(
+        err,
+        VectorizerError::QuotaExceeded { max_vectors: 3, .. }
+    ));
+    assert_eq!(
+        store.get_collection("quota_reject").unwrap().vector_count(),
+        3
+    );
+}
+
+#[test]
+fn test_quota_summarizes_oldest_vectors() {
+    use crate::models::{CollectionQuota, QuotaPolicy};
+
+    let store = VectorStore::new_cpu_only();
+    let quota = CollectionQuota {
+        merge_group_size: 4,
+        ..CollectionQuota::new(10, QuotaPolicy::Summarize)
+    };
+    store
+        .create_collection("quota_summarize", quota_config(quota))
+        .unwrap();
+    store
+        .insert("quota_summarize", (0..10).map(memory).collect())
+        .unwrap();
+
+    // Full: the four oldest are merged to bring it to 80% before the insert
+    store.insert("quota_summarize", vec![memory(10)]).unwrap();
+    let collection = store.get_collection("quota_summarize").unwrap();
+    assert_eq!(collection.vector_count(), 8);
+    for id in ["m0", "m1", "m2", "m3"] {
+        assert!(collection.get_vector(id).is_err(), "{id} not merged");
+    }
+    assert!(collection.get_vector("m4").is_ok());
+
+    let summary = collection
+        .get_all_vectors()
+        .into_iter()
+        .find(|v| v.id.len() > 3)
+        .expect("summary vector");
+    let payload = &summary.payload.as_ref().unwrap().data;
+    let info = &payload[QUOTA_SUMMARY_FIELD];
+    assert_eq!(info["merged_count"], 4);
+    assert_eq!(
+        info["merged_ids"],
+        serde_json::json!(["m0", "m1", "m2", "m3"])
+    );
+    assert!(!payload["content"].as_str().unwrap().is_empty());
+    assert!((summary.data[0] - 1.5).abs() < 1e-5, "mean of 0..4");
+}
+
+#[test]
+fn test_summarize_quota_refused_on_encrypted_collections() {
+    use crate::models::{CollectionQuota, QuotaPolicy};
+
+    let store = VectorStore::new_cpu_only();
+    let mut config = quota_config(CollectionQuota::new(10, QuotaPolicy::Summarize));
+    config.encryption = Some(crate::models::EncryptionConfig {
+        required: true,
+        allow_mixed: false,
+    });
+    assert!(store.create_collection("quota_encrypted", config).is_err());
+}
+
+#[test]
+fn test_quota_holds_under_concurrent_inserts() {
+    use std::sync::Arc;
+    use std::thread;
+
+    use crate::models::{CollectionQuota, QuotaPolicy};
+
+    let store = Arc::new(VectorStore::new_cpu_only());
+    store
+        .create_collection(
+            "quota_race",
+            quota_config(CollectionQuota::new(5, QuotaPolicy::Reject)),
+        )
+        .unwrap();
+
+    let handles: Vec<_> = (0..20)
+        .map(|i| {
+            let store = Arc::clone(&store);
+            thread::spawn(move || store.insert("quota_race", vec![memory(i)]).is_ok())
+        })
+        .collect();
+    let accepted = handles
+        .into_iter()
+        .map(|h| h.join().unwrap())
+        .filter(|ok| *ok)
+        .count();
+
+    assert_eq!(accepted, 5);
+    assert_eq!(
+        store.get_collection("quota_race").unwrap().vector_count(),
+        5
+    );
+}
+
+#[test]
+fn test_quota_summary_keeps_shared_payload_fields() {
+    use crate::models::{CollectionQuota, QuotaPolicy};
+
+    let store = VectorStore::new_cpu_only();
+    let quota = CollectionQuota {
+        merge_group_size: 2,
+        ..CollectionQuota::new(2, QuotaPolicy::Summarize)
+    };
+    store
+        .create_collection("quota_fields", quota_config(quota))
+        .unwrap();
+    for i in 0..3 {
+        let vector = Vector::with_payload(
+            format!("m{i}"),
+            vec![i as f32, 1.0, 0.0, 0.0],
+            Payload::new(serde_json::json!({
+                "content": format!("Memory number {i}."),
+                "session": "s1",
+                "turn": i,
+            })),
+        );
+        store.insert("quota_fields", vec![vector]).unwrap();
+    }
+
+    let summary = store
+        .get_collection("quota_fields")
+        .unwrap()
+        .get_all_vectors()
+        .into_iter()
+        .find(|v| v.id.len() > 3)
+        .expect("summary vector");
+    let payload = &summary.payload.as_ref().unwrap().data;
+    assert_eq!(payload["session"], "s1");
+    assert!(payload.get("turn").is_none(), "{payload}");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_summarize_quota_shrinks_in_the_background() {
+    use crate::models::{CollectionQuota, QuotaPolicy};
+
+    let store = VectorStore::new_cpu_only();
+    let quota = CollectionQuota {
+        merge_group_size: 2,
+        ..CollectionQuota::new(10, QuotaPolicy::Summarize)
+    };
+    store
+        .create_collection("quota_background", quota_config(quota))
+        .unwrap();
+
+    // Past the target of 8 but within the maximum: the insert merges
+    // nothing itself, a background task brings the collection back to 8
+    store
+        .insert("quota_background", (0..10).map(memory).collect())
+        .unwrap();
+    let mut count = 10;
+    for _ in 0..100 {
+        count = store
+            .get_collection("quota_background")
+            .unwrap()
+            .vector_count();
+        if count <= 8 {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    assert_eq!(count, 8);
+    let collection = store.get_collection("quota_background").unwrap();
+    assert!(collection.get_vector("m0").is_err(), "m0 not merged");
+}
+
+#[test]
+fn test_change_feed_publishes_vector_writes() {
+    use crate::db::change_feed::{ChangeFeed, ChangeKind, payload_hash};
+
+    let store = VectorStore::new_cpu_only();
+    let feed = std::sync::Arc::new(ChangeFeed::new(16));
+    store.set_change_feed(feed.clone());
+    let mut events = feed.subscribe();
+    store
+        .create_collection(
+            "cdc",
+            CollectionConfig {
+                dimension: 4,
+                metric: DistanceMetric::Euclidean,
+                quantization: crate::models::QuantizationConfig::None,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+    store.insert("cdc", vec![memory(0), memory(1)]).unwrap();
+    let mut updated = memory(0);
+    updated.payload = Some(Payload::new(serde_json::json!({"content": "edited"})));
+    store.update("cdc", updated.clone()).unwrap();
+    store.delete("cdc", "m1").unwrap();
+    // Failed writes are not published
+    assert!(store.delete("cdc", "missing").is_err());
+
+    let received: Vec<_> = std::iter::from_fn(|| events.try_recv().ok()).collect();
+    let summary: Vec<_> = received
+        .iter()
+        .map(|e| (e.seq, e.id.as_str(), e.kind))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (1, "m0", ChangeKind::Insert),
+            (2, "m1", ChangeKind::Insert),
+            (3, "m0", ChangeKind::Update),
+            (4, "m1", ChangeKind::Delete),
+        ]
+    );
+    assert!(received.iter().all(|e| e.collection == "cdc"));
+    assert_eq!(
+        received[2].payload_hash,
+        updated.payload.as_ref().map(payload_hash)
+    );
+    assert_eq!(received[3].payload_hash, None);
+}
+
+#[test]
+fn test_dedup_skips_vectors_with_a_known_content_hash() {
+    use crate::models::{DedupPolicy, DedupReason, DedupStatus};
+
+    let store = VectorStore::new_cpu_only();
+    store
+        .create_collection(
+            "dedup",
+            CollectionConfig {
+                dimension: 4,
+                metric: DistanceMetric::Euclidean,
+                quantization: crate::models::QuantizationConfig::None,
+                dedup: Some(DedupPolicy::default()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    let statuses = store
+        .insert_deduplicated("dedup", vec![memory(0), memory(1)])
+        .unwrap();
+    assert_eq!(statuses, vec![DedupStatus::Inserted; 2]);
+
+    // Same content under another ID: skipped
+    let mut copy = memory(0);
+    copy.id = "copy".to_string();
+    let statuses = store.insert_deduplicated("dedup", vec![copy]).unwrap();
+    let duplicate = statuses[0].duplicate().unwrap();
+    assert!(matches!(statuses[0], DedupStatus::Skipped(_)));
+    assert_eq!(duplicate.duplicate_of, "m0");
+    assert_eq!(duplicate.reason, DedupReason::ContentHash);
+    // Re-inserting the same ID is not a duplicate of itself
+    let statuses = store.insert_deduplicated("dedup", vec![memory(0)]).unwrap();
+    assert_eq!(statuses, vec![DedupStatus::Inserted]);
+    assert_eq!(store.get_collection("dedup").unwrap().vector_count(), 2);
+
+    // Duplicates within one batch
+    let mut first = memory(7);
+    first.id = "first".to_string();
+    let mut second = memory(7);
+    second.id = "second".to_string();
+    let statuses = store
+        .insert_deduplicated("dedup", vec![first, second])
+        .unwrap();
+    assert_eq!(statuses[0], DedupStatus::Inserted);
+    assert_eq!(statuses[1].duplicate().unwrap().duplicate_of, "first");
+    assert_eq!(store.get_collection("dedup").unwrap().vector_count(), 3);
+}
+
+#[test]
+fn test_dedup_merges_near_duplicates() {
+    use crate::models::{DedupAction, DedupPolicy, DedupReason, DedupStatus};
+
+    let store = VectorStore::new_cpu_only();
+    store
+        .create_collection(
+            "near",
+            CollectionConfig {
+                dimension: 4,
+                metric: DistanceMetric::Cosine,
+                quantization: crate::models::QuantizationConfig::None,
+                dedup: Some(DedupPolicy {
+                    hash_field: None,
+                    similarity_threshold: Some(0.95),
+                    action: DedupAction::Merge,
+                }),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    let vector = |id: &str, data: Vec<f32>, payload: serde_json::Value| {
+        Vector::with_payload(id.to_string(), data, Payload::new(payload))
+    };
+    store
+        .insert(
+            "near",
+            vec![vector(
+                "a",
+                vec![1.0, 0.0, 0.0, 0.0],
+                serde_json::json!({"lang": "en"}),
+            )],
+        )
+        .unwrap();
+
+    let statuses = store
+        .insert_deduplicated(
+            "near",
+            vec![
+                vector(
+                    "b",
+                    vec![1.0, 0.01, 0.0, 0.0],
+                    serde_json::json!({"tag": "x"}),
+                ),
+                vector("c", vec![0.0, 1.0, 0.0, 0.0], serde_json::json!({})),
+            ],
+        )
+        .unwrap();
+    let DedupStatus::Merged(duplicate) = &statuses[0] else {
+        panic!("expected a merge, got {:?}", statuses[0]);
+    };
+    assert_eq!(duplicate.duplicate_of, "a");
+    assert_eq!(duplicate.reason, DedupReason::Similarity);
+    assert!(duplicate.score.unwrap() > 0.95);
+    assert_eq!(statuses[1], DedupStatus::Inserted);
+
+    let merged = store.get_vector("near", "a").unwrap().payload.unwrap();
+    assert_eq!(merged.data, serde_json::json!({"lang": "en", "tag": "x"}));
+    assert!(store.get_vector("near", "b").is_err());
+}
+
+#[test]
+fn test_mutation_listener_sees_collection_and_aliases() {
+    let store = VectorStore::new_cpu_only();
+    let seen = std::sync::Arc::new(parking_lot::Mutex::new(Vec::<String>::new()));
+    let sink = seen.clone();
+    store.set_mutation_listener(std::sync::Arc::new(move |name: &str| {
+        sink.lock().push(name.to_string())
+    }));
+    store
+        .create_collection(
+            "watched",
+            CollectionConfig {
+                dimension: 4,
+                metric: DistanceMetric::Euclidean,
+                quantization: crate::models::QuantizationConfig::None,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    store.create_alias("watched_alias", "watched").unwrap();
+
+    store.insert("watched_alias", vec![memory(0)]).unwrap();
+    store.delete("watched", "m0").unwrap();
+    // Failed writes do not notify
+    assert!(store.delete("watched", "missing").is_err());
+    // A reload from cache goes around insert but still notifies
+    store
+        .load_collection_from_cache("watched", Vec::new())
+        .unwrap();
+
+    assert_eq!(*seen.lock(), ["watched", "watched_alias"].repeat(3));
+}
+
+fn chunk(id: &str, file_path: &str, content: &str) -> Vector {
+    Vector::with_payload(
+        id.to_string(),
+        vec![1.0, 0.0, 0.0, 0.0],
+        Payload::new(serde_json::json!({"file_path": file_path, "content": content})),
+    )
+}
+
+fn summaries_by_file(store: &VectorStore, collection: &str) -> Vec<(String, serde_json::Value)> {
+    let mut summaries: Vec<_> = store
+        .get_collection(collection)
+        .unwrap()
+        .get_all_vectors()
+        .into_iter()
+        .map(|v| {
+            let payload = v.payload.unwrap().data;
+            (
+                payload["file_path"].as_str().unwrap().to_string(),
+                payload[SUMMARY_SOURCE_IDS_FIELD].clone(),
+            )
+        })
+        .collect();
+    summaries.sort_by(|a, b| a.0.cmp(&b.0));
+    summaries
+}
+
+#[test]
+fn test_summary_collection_tracks_source_chunks() {
+    let store = VectorStore::new_cpu_only();
+    store.set_summary_collections(crate::summarization::SummarizationConfig {
+        enabled: true,
+        auto_summarize: true,
+        ..Default::default()
+    });
+    store
+        .create_collection(
+            "docs",
+            CollectionConfig {
+                dimension: 4,
+                metric: DistanceMetric::Cosine,
+                quantization: crate::models::QuantizationConfig::None,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+    store
+        .insert(
+            "docs",
+            vec![
+                chunk("a0", "a.md", "The deploy pipeline builds the image first."),
+                chunk(
+                    "a1",
+                    "a.md",
+                    "Then it runs the integration tests in staging.",
+                ),
+                chunk(
+                    "b0",
+                    "b.md",
+                    "Billing runs nightly and emails the invoices.",
+                ),
+            ],
+        )
+        .unwrap();
+    assert_eq!(
+        summaries_by_file(&store, "docs_summaries"),
+        vec![
+            ("a.md".to_string(), serde_json::json!(["a0", "a1"])),
+            ("b.md".to_string(), serde_json::json!(["b0"])),
+        ]
+    );
+    let summaries = store
+        .get_collection("docs_summaries")
+        .unwrap()
+        .get_all_vectors();
+    let payload = &summaries[0].payload.as_ref().unwrap().data;
+    assert_eq!(payload["source_collection"], "docs");
+    assert!(!payload["content"].as_str().unwrap().is_empty());
+
+    // A chunk moved to another file leaves one summary for the other
+    store
+        .update(
+            "docs",
+            chunk("a1", "b.md", "Billing retries failed cards twice."),
+        )
+        .unwrap();
+    assert_eq!(
+        summaries_by_file(&store, "docs_summaries"),
+        vec![
+            ("a.md".to_string(), serde_json::json!(["a0"])),
+            ("b.md".to_string(), serde_json::json!(["b0", "a1"])),
+        ]
+    );
+
+    // The last chunk of a file takes its summary with it
+    store.delete("docs", "a0").unwrap();
+    assert_eq!(
+        summaries_by_file(&store, "docs_summaries"),
+        vec![("b.md".to_string(), serde_json::json!(["b0", "a1"]))]
+    );
+
+    store.delete_collection("docs").unwrap();
+    assert!(store.get_collection("docs_summaries").is_err());
+}
+
+#[test]
+fn test_summary_collections_off_without_auto_summarize() {
+    let store = VectorStore::new_cpu_only();
+    store.set_summary_collections(crate::summarization::SummarizationConfig {
+        enabled: true,
+        ..Default::default()
+    });
+    store
+        .create_collection(
+            "plain",
+            CollectionConfig {
+                dimension: 4,
+                metric: DistanceMetric::Cosine,
+                quantization: crate::models::QuantizationConfig::None,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    store
+        .insert(
+            "plain",
+            vec![chunk("p0", "p.md", "Nothing to summarize here.")],
+        )
+        .unwrap();
+    assert!(store.get_collection("plain_summaries").is_err());
+}
+
+#[test]
+fn test_clone_collection_reembeds_into_new_dimension() {
+    let store = VectorStore::new_cpu_only();
+    let config = CollectionConfig {
+        dimension: 4,
+        metric: DistanceMetric::Cosine,
+        quantization: crate::models::QuantizationConfig::None,
+        ..Default::default()
+    };
+    store.create_collection("src", config.clone()).unwrap();
+    store
+        .insert(
+            "src",
+            vec![
+                chunk("a", "a.md", "alpha"),
+                chunk("b", "b.md", "beta"),
+                Vector::new("bare".to_string(), vec![0.0, 1.0, 0.0, 0.0]),
+            ],
+        )
+        .unwrap();
+
+    // Same config: copied as is
+    let job = crate::jobs::JOBS.start(crate::jobs::COLLECTION_CLONE, "src");
+    let report = store
+        .clone_collection("src", "copy", None, None, &job)
+        .unwrap();
+    assert_eq!((report.copied, report.skipped), (3, 0));
+    assert!(!report.reembedded);
+    assert_eq!(job.status().processed, 3);
+    assert!(
+        store
+            .clone_collection("src", "copy", None, None, &job)
+            .is_err()
+    );
+
+    // New dimension: re-embedded from `content`, the bare vector skipped
+    let wider = CollectionConfig {
+        dimension: 8,
+        ..config
+    };
+    assert!(
+        store
+            .clone_collection("src", "wide", Some(wider.clone()), None, &job)
+            .is_err()
+    );
+    let embed = |text: &str| -> crate::error::Result<Vec<f32>> { Ok(vec![text.len() as f32; 8]) };
+    let report = store
+        .clone_collection("src", "wide", Some(wider), Some(&embed), &job)
+        .unwrap();
+    assert_eq!((report.copied, report.skipped), (2, 1));
+    assert!(report.reembedded);
+    let vector = store.get_vector("wide", "b").unwrap();
+    assert_eq!(vector.data.len(), 8);
+    assert!(store.get_vector("wide", "bare").is_err());
+}
+
+#[test]
+fn test_get_vectors_keeps_order_and_reports_missing_ids() {
+    let store = VectorStore::new_cpu_only();
+    store
+        .create_collection(
+            "bulk_get",
+            CollectionConfig {
+                dimension: 4,
+                metric: DistanceMetric::Euclidean,
+                quantization: crate::models::QuantizationConfig::None,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    store
+        .insert("bulk_get", (0..3).map(memory).collect())
+        .unwrap();
+
+    let ids = ["m2", "nope", "m0"].map(String::from);
+    let vectors = store.get_vectors("bulk_get", &ids).unwrap();
+    let found: Vec<Option<&str>> = vectors
+        .iter()
+        .map(|v| v.as_ref().map(|v| v.id.as_str()))
+        .collect();
+    assert_eq!(found, [Some("m2"), None, Some("m0")]);
+    assert_eq!(vectors[0].as_ref().unwrap().data, vec![2.0, 1.0, 0.0, 0.0]);
+
+    assert!(matches!(
+        store.get_vectors("no_such", &ids),
+        Err(VectorizerError::CollectionNotFound(_))
+    ));
+}
+
+#[test]
+fn test_concurrent_payload_patches_all_land() {
+    let store = VectorStore::new_cpu_only();
+    store
+        .create_collection(
+            "patched",
+            CollectionConfig {
+                dimension: 4,
+                metric: DistanceMetric::Euclidean,
+                quantization: crate::models::QuantizationConfig::None,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    store.insert("patched", vec![memory(0)]).unwrap();
+
+    let unchanged = |_: &mut Vector| Ok::<_, VectorizerError>(());
+    std::thread::scope(|scope| {
+        for i in 0..8 {
+            let store = &store;
+            scope.spawn(move || {
+                let patch = serde_json::json!({ format!("f{i}"): i });
+                store
+                    .patch_payload("patched", "m0", &patch, unchanged)
+                    .unwrap();
+            });
+        }
+    });
+
+    let payload = store.get_vector("patched", "m0").unwrap().payload.unwrap();
+    for i in 0..8 {
+        assert_eq!(payload.data[format!("f{i}")], serde_json::json!(i));
+    }
+    assert!(matches!(
+        store.patch_payload("patched", "nope", &serde_json::json!({}), unchanged),
+        Err(VectorizerError::VectorNotFound(_))
+    ));
+}
+
+#[test]
+fn test_update_waits_for_a_modification_in_flight() {
+    let store = VectorStore::new_cpu_only();
+    store
+        .create_collection(
+            "modified",
+            CollectionConfig {
+                dimension: 4,
+                metric: DistanceMetric::Euclidean,
+                quantization: crate::models::QuantizationConfig::None,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    store.insert("modified", vec![memory(0)]).unwrap();
+
+    let (read_tx, read_rx) = std::sync::mpsc::channel();
+    std::thread::scope(|scope| {
+        let store = &store;
+        scope.spawn(move || {
+            store
+                .modify_vector("modified", "m0", |vector| {
+                    read_tx.send(()).unwrap();
+                    std::thread::sleep(std::time::Duration::from_millis(100));
+                    vector.payload = Some(Payload::new(serde_json::json!({ "a": 1 })));
+                    Ok::<_, VectorizerError>(())
+                })
+                .unwrap();
+        });
+
+        // Lands after the modification's write, not under it
+        read_rx.recv().unwrap();
+        let mut replaced = memory(0);
+        replaced.payload = Some(Payload::new(serde_json::json!({ "b": 2 })));
+        store.update("modified", replaced).unwrap();
+    });
+
+    let payload = store.get_vector("modified", "m0").unwrap().payload.unwrap();
+    assert_eq!(payload.data, serde_json::json!({ "b": 2 }));
+}
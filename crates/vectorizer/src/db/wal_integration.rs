@@ -7,14 +7,49 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 
+use chrono::{DateTime, Utc};
 use tokio::sync::Mutex as AsyncMutex;
 use tracing::{debug, error, warn};
 
 use crate::error::Result;
-use crate::models::Vector;
+use crate::models::{Payload, Vector};
 use crate::persistence::types::Operation;
 use crate::persistence::wal::{WALConfig, WALError, WriteAheadLog};
 
+/// Metadata key under which a vector's whole payload is logged as JSON.
+/// Entries written before it carry only the payload's string fields,
+/// one per key.
+pub const PAYLOAD_METADATA_KEY: &str = "__payload__";
+
+/// WAL metadata for `payload`: the payload as JSON under
+/// [`PAYLOAD_METADATA_KEY`], so nested and non-string fields survive
+/// replay.
+pub fn metadata_from_payload(payload: &Payload) -> HashMap<String, String> {
+    let mut metadata = HashMap::new();
+    if let Ok(json) = serde_json::to_string(&payload.data) {
+        metadata.insert(PAYLOAD_METADATA_KEY.to_string(), json);
+    }
+    metadata
+}
+
+/// Payload logged by [`metadata_from_payload`], or rebuilt from the
+/// string fields of an older entry. `None` for empty metadata.
+pub fn payload_from_metadata(metadata: &HashMap<String, String>) -> Option<Payload> {
+    if let Some(json) = metadata.get(PAYLOAD_METADATA_KEY)
+        && let Ok(data) = serde_json::from_str(json)
+    {
+        return Some(Payload::new(data));
+    }
+    if metadata.is_empty() {
+        return None;
+    }
+    let fields: serde_json::Map<String, serde_json::Value> = metadata
+        .iter()
+        .map(|(k, v)| (k.clone(), serde_json::Value::String(v.clone())))
+        .collect();
+    Some(Payload::new(serde_json::Value::Object(fields)))
+}
+
 /// WAL integration manager for VectorStore
 #[derive(Clone)]
 pub struct WalIntegration {
@@ -63,18 +98,11 @@ impl WalIntegration {
         vector: &Vector,
     ) -> std::result::Result<(), WALError> {
         if let Some(wal) = &self.wal {
-            // Convert payload to metadata HashMap
-            let metadata = if let Some(payload) = &vector.payload {
-                if let Some(obj) = payload.data.as_object() {
-                    obj.iter()
-                        .filter_map(|(k, v)| v.as_str().map(|s| (k.clone(), s.to_string())))
-                        .collect()
-                } else {
-                    HashMap::new()
-                }
-            } else {
-                HashMap::new()
-            };
+            let metadata = vector
+                .payload
+                .as_ref()
+                .map(metadata_from_payload)
+                .unwrap_or_default();
 
             let operation = Operation::InsertVector {
                 collection_name: collection_name.to_string(),
@@ -96,26 +124,19 @@ impl WalIntegration {
         vector: &Vector,
     ) -> std::result::Result<(), WALError> {
         if let Some(wal) = &self.wal {
-            // Convert payload to metadata HashMap
-            let metadata = if let Some(payload) = &vector.payload {
-                if let Some(obj) = payload.data.as_object() {
-                    Some(
-                        obj.iter()
-                            .filter_map(|(k, v)| v.as_str().map(|s| (k.clone(), s.to_string())))
-                            .collect(),
-                    )
-                } else {
-                    None
-                }
-            } else {
-                None
-            };
+            // Always `Some`: the update replaces the payload, and empty
+            // metadata records that the vector has none
+            let metadata = vector
+                .payload
+                .as_ref()
+                .map(metadata_from_payload)
+                .unwrap_or_default();
 
             let operation = Operation::UpdateVector {
                 collection_name: collection_name.to_string(),
                 vector_id: vector.id.clone(),
                 data: Some(vector.data.clone()),
-                metadata,
+                metadata: Some(metadata),
             };
 
             wal.append(collection_name, operation).await?;
@@ -181,6 +202,24 @@ impl WalIntegration {
         self.wal.as_ref()
     }
 
+    /// Since when the WAL holds every operation, or `None` when it is
+    /// disabled
+    pub fn history_start(&self) -> Option<DateTime<Utc>> {
+        self.wal.as_ref().map(|wal| wal.history_start())
+    }
+
+    /// Drop the entries written before `cutoff`
+    pub async fn prune_before(
+        &self,
+        cutoff: DateTime<Utc>,
+    ) -> std::result::Result<usize, WALError> {
+        if let Some(wal) = &self.wal {
+            wal.prune_before(cutoff).await
+        } else {
+            Ok(0)
+        }
+    }
+
     /// Create checkpoint
     pub async fn checkpoint(&self) -> std::result::Result<u64, WALError> {
         if let Some(wal) = &self.wal {
@@ -304,4 +343,49 @@ mod tests {
                 .is_ok()
         );
     }
+
+    #[tokio::test]
+    async fn test_wal_integration_payload_round_trips() {
+        let temp_dir = tempdir().unwrap();
+        let integration = WalIntegration::new(temp_dir.path().to_path_buf(), None)
+            .await
+            .unwrap();
+
+        let data = json!({
+            "title": "Test Document",
+            "page": 3,
+            "tags": ["a", "b"],
+            "source": {"path": "/path/to/file.txt"}
+        });
+        let vector = Vector {
+            id: "test".to_string(),
+            data: vec![1.0, 2.0, 3.0],
+            payload: Some(Payload { data: data.clone() }),
+            sparse: None,
+            document_id: None,
+        };
+        integration
+            .log_insert("test_collection", &vector)
+            .await
+            .unwrap();
+
+        let entries = integration
+            .recover_collection("test_collection")
+            .await
+            .unwrap();
+        let Operation::InsertVector { metadata, .. } = &entries[0].operation else {
+            panic!("expected an insert");
+        };
+        assert_eq!(payload_from_metadata(metadata).unwrap().data, data);
+    }
+
+    #[test]
+    fn test_payload_from_legacy_metadata() {
+        let metadata = HashMap::from([("title".to_string(), "Test".to_string())]);
+        assert_eq!(
+            payload_from_metadata(&metadata).unwrap().data,
+            json!({"title": "Test"})
+        );
+        assert!(payload_from_metadata(&HashMap::new()).is_none());
+    }
 }
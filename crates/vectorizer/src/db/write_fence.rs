@@ -0,0 +1,183 @@
+//! Write fence for coordinated cluster snapshots.
+//!
+//! A cluster snapshot needs every node to stop accepting vector writes
+//! at the same cut. The coordinator raises the fence on each node
+//! (prepare), waits until every node reports its in-flight writes
+//! drained, then has each node write its part and lower the fence
+//! (commit). Writes arriving while the fence is up fail with
+//! [`VectorizerError::WritesFenced`] instead of queueing, so a stuck
+//! coordinator cannot pile up requests; the fence also lifts by itself
+//! once its timeout passes.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use tracing::warn;
+
+use crate::error::{Result, VectorizerError};
+
+/// How often [`WriteFence::raise`] re-checks the in-flight count.
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_millis(2);
+
+#[derive(Debug, Clone)]
+struct Fence {
+    holder: String,
+    expires_at: Instant,
+}
+
+/// Holds vector writes while a cluster snapshot is taken.
+#[derive(Debug, Default)]
+pub struct WriteFence {
+    fence: Mutex<Option<Fence>>,
+    in_flight: AtomicUsize,
+}
+
+/// An admitted write; the fence waits for it to drop before the
+/// snapshot is taken.
+#[derive(Debug)]
+pub struct WriteGuard<'a> {
+    fence: &'a WriteFence,
+}
+
+impl Drop for WriteGuard<'_> {
+    fn drop(&mut self) {
+        self.fence.in_flight.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl WriteFence {
+    /// A lowered fence.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Admit a write, or refuse it while the fence is up.
+    pub fn enter(&self) -> Result<WriteGuard<'_>> {
+        // Count the write before looking at the fence: `raise` sets the
+        // fence before it waits for the count to drain, so a write
+        // either sees the fence or is waited for
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        let guard = WriteGuard { fence: self };
+        match self.holder() {
+            Some(holder) => Err(VectorizerError::WritesFenced(holder)),
+            None => Ok(guard),
+        }
+    }
+
+    /// Raise the fence for `holder` and wait for the writes already
+    /// admitted to finish. The fence lifts by itself after `timeout`.
+    ///
+    /// Raising again with the same holder extends the timeout; another
+    /// holder is refused while the fence is up.
+    pub async fn raise(&self, holder: &str, timeout: Duration) -> Result<()> {
+        let deadline = Instant::now() + timeout;
+        {
+            let mut fence = self.fence.lock();
+            if let Some(current) = fence.as_ref()
+                && current.holder != holder
+                && current.expires_at > Instant::now()
+            {
+                return Err(VectorizerError::WritesFenced(current.holder.clone()));
+            }
+            *fence = Some(Fence {
+                holder: holder.to_string(),
+                expires_at: deadline,
+            });
+        }
+
+        while self.in_flight.load(Ordering::SeqCst) > 0 {
+            if Instant::now() >= deadline {
+                self.lower(holder);
+                return Err(VectorizerError::Storage(format!(
+                    "writes did not drain within {:?} for cluster snapshot '{}'",
+                    timeout, holder
+                )));
+            }
+            tokio::time::sleep(DRAIN_POLL_INTERVAL).await;
+        }
+        Ok(())
+    }
+
+    /// Lower the fence if `holder` holds it. Returns whether it did.
+    pub fn lower(&self, holder: &str) -> bool {
+        let mut fence = self.fence.lock();
+        if fence.as_ref().is_some_and(|f| f.holder == holder) {
+            *fence = None;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Who holds the fence, if it is up.
+    pub fn holder(&self) -> Option<String> {
+        let mut fence = self.fence.lock();
+        let current = fence.as_ref()?;
+        if current.expires_at > Instant::now() {
+            return Some(current.holder.clone());
+        }
+        warn!(
+            "Write fence for cluster snapshot '{}' expired; accepting writes again",
+            current.holder
+        );
+        *fence = None;
+        None
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn raised_fence_refuses_writes_until_lowered() {
+        let fence = WriteFence::new();
+        drop(fence.enter().unwrap());
+
+        fence.raise("snap-1", Duration::from_secs(5)).await.unwrap();
+        let err = fence.enter().unwrap_err();
+        assert!(matches!(err, VectorizerError::WritesFenced(ref h) if h == "snap-1"));
+        assert!(fence.raise("snap-2", Duration::from_secs(5)).await.is_err());
+
+        assert!(!fence.lower("snap-2"));
+        assert!(fence.lower("snap-1"));
+        assert!(fence.enter().is_ok());
+    }
+
+    #[tokio::test]
+    async fn raise_waits_for_admitted_writes() {
+        let fence = Arc::new(WriteFence::new());
+        let writer = {
+            let fence = fence.clone();
+            std::thread::spawn(move || {
+                let _guard = fence.enter().unwrap();
+                std::thread::sleep(Duration::from_millis(50));
+            })
+        };
+        while fence.in_flight.load(Ordering::SeqCst) == 0 {
+            tokio::task::yield_now().await;
+        }
+
+        let started = Instant::now();
+        fence.raise("snap", Duration::from_secs(5)).await.unwrap();
+        assert!(started.elapsed() >= Duration::from_millis(20));
+        assert_eq!(fence.in_flight.load(Ordering::SeqCst), 0);
+        writer.join().unwrap();
+    }
+
+    #[tokio::test]
+    async fn fence_expires() {
+        let fence = WriteFence::new();
+        fence
+            .raise("snap", Duration::from_millis(10))
+            .await
+            .unwrap();
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(fence.holder().is_none());
+        assert!(fence.enter().is_ok());
+    }
+}
@@ -5,6 +5,12 @@
 //! - Content hashing for incremental builds
 //! - Parallel cache population
 //! - Arrow/Parquet support for analytics
+//!
+//! [`EmbeddingManager`](crate::embedding::EmbeddingManager) uses it as a
+//! cache that survives restarts: entries are keyed on (provider, model,
+//! text hash) via [`EmbeddingCache::get_for`] / [`EmbeddingCache::put_for`],
+//! so re-indexing unchanged text after a restart reads the stored
+//! embedding instead of recomputing it.
 
 use std::collections::HashMap;
 use std::fs::{File, OpenOptions};
@@ -148,10 +154,64 @@ impl EmbeddingCache {
     /// Put embedding into cache
     pub fn put(&self, content: &str, embedding: &[f32]) -> Result<()> {
         let hash = xxh3_64(content.as_bytes());
+        self.put_hashed(hash, content, embedding)
+    }
+
+    /// Get the embedding `model` of `provider` produced for `content`
+    pub fn get_for(&self, provider: &str, model: &str, content: &str) -> Option<Vec<f32>> {
+        let hash = Self::model_key(provider, model, content);
+        let shard_id = (hash as usize) % self.config.num_shards;
+
+        let shard = self.shards[shard_id].read();
+        shard
+            .entries
+            .get(&hash)
+            .and_then(|entry| shard.read_embedding(entry))
+    }
+
+    /// Store the embedding `model` of `provider` produced for `content`.
+    /// Returns `false` without writing when it is already stored or the
+    /// cache has reached `max_size`.
+    pub fn put_for(
+        &self,
+        provider: &str,
+        model: &str,
+        content: &str,
+        embedding: &[f32],
+    ) -> Result<bool> {
+        let hash = Self::model_key(provider, model, content);
+        let shard_id = (hash as usize) % self.config.num_shards;
+        if self.shards[shard_id].read().entries.contains_key(&hash) {
+            return Ok(false);
+        }
+        let size = embedding.len() * std::mem::size_of::<f32>();
+        if self.metadata.read().total_size + size > self.config.max_size {
+            debug!(
+                "Embedding cache full ({} bytes), not storing",
+                self.config.max_size
+            );
+            return Ok(false);
+        }
+        self.put_hashed(hash, model, embedding)?;
+        Ok(true)
+    }
+
+    /// Cache key of `content` embedded by `model` under `provider`
+    fn model_key(provider: &str, model: &str, content: &str) -> u64 {
+        let mut key = Vec::with_capacity(provider.len() + model.len() + 10);
+        key.extend_from_slice(provider.as_bytes());
+        key.push(0);
+        key.extend_from_slice(model.as_bytes());
+        key.push(0);
+        key.extend_from_slice(&xxh3_64(content.as_bytes()).to_le_bytes());
+        xxh3_64(&key)
+    }
+
+    fn put_hashed(&self, hash: u64, label: &str, embedding: &[f32]) -> Result<()> {
         let shard_id = (hash as usize) % self.config.num_shards;
 
         let mut shard = self.shards[shard_id].write();
-        shard.write_embedding(hash, content, embedding)?;
+        shard.write_embedding(hash, label, embedding)?;
 
         // Update metadata
         let mut meta = self.metadata.write();
@@ -308,6 +368,29 @@ impl EmbeddingCache {
         std::fs::write(&metadata_path, data)?;
         Ok(())
     }
+
+    /// Write every shard's index and the metadata to disk. Indexes are
+    /// otherwise only saved every 1000 entries and on drop, so call this
+    /// on shutdown and after a large batch.
+    pub fn flush(&self) -> Result<()> {
+        for shard in &self.shards {
+            shard.read().save_index()?;
+        }
+        self.save_metadata()
+    }
+
+    /// Number of stored embeddings
+    pub fn len(&self) -> usize {
+        self.shards
+            .iter()
+            .map(|shard| shard.read().entries.len())
+            .sum()
+    }
+
+    /// Whether no embedding is stored
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 }
 
 impl CacheShard {
@@ -394,7 +477,7 @@ impl CacheShard {
         None
     }
 
-    fn write_embedding(&mut self, hash: u64, content: &str, embedding: &[f32]) -> Result<()> {
+    fn write_embedding(&mut self, hash: u64, label: &str, embedding: &[f32]) -> Result<()> {
         // Write to data file
         let mut file = OpenOptions::new()
             .create(true)
@@ -412,7 +495,7 @@ impl CacheShard {
         // Update index
         let entry = CacheEntry {
             content_hash: hash,
-            file_path: content.to_string(),
+            file_path: label.to_string(),
             offset,
             dimension,
             timestamp: chrono::Utc::now().timestamp() as u64,
@@ -0,0 +1,302 @@
+//! CLIP image and text embeddings
+//!
+//! CLIP maps images and text into one space: a screenshot of a login
+//! form and the query "login form" land close together. The provider
+//! runs the ViT-B/32 vision and text encoders (ONNX exports published as
+//! `Qdrant/clip-ViT-B-32-vision` and `Qdrant/clip-ViT-B-32-text`) with
+//! ONNX Runtime, so a collection created with `embedding_provider:
+//! "clip"` holds images inserted through `insert_image` and answers text
+//! searches against them.
+//!
+//! This file compiles only when the `onnx-models` Cargo feature is
+//! enabled; bootstrap reaches it through
+//! [`crate::embedding::providers::try_build_clip_provider`].
+
+use std::path::{Path, PathBuf};
+
+use image::imageops::FilterType;
+use ort::session::Session;
+use ort::value::Tensor;
+use parking_lot::Mutex;
+use tokenizers::{PaddingParams, PaddingStrategy, Tokenizer, TruncationParams};
+
+use crate::embedding::EmbeddingProvider;
+use crate::error::{Result, VectorizerError};
+
+/// Hugging Face repository of the vision encoder
+pub const VISION_MODEL_REPO: &str = "Qdrant/clip-ViT-B-32-vision";
+/// Hugging Face repository of the text encoder and its tokenizer
+pub const TEXT_MODEL_REPO: &str = "Qdrant/clip-ViT-B-32-text";
+/// Dimension of ViT-B/32 embeddings
+pub const CLIP_DIMENSION: usize = 512;
+
+/// Side of the square image the vision encoder takes
+const IMAGE_SIZE: u32 = 224;
+/// Token window of the text encoder
+const CONTEXT_LENGTH: usize = 77;
+/// Per-channel RGB mean and standard deviation CLIP was trained with
+const PIXEL_MEAN: [f32; 3] = [0.481_454_66, 0.457_827_5, 0.408_210_73];
+const PIXEL_STD: [f32; 3] = [0.268_629_54, 0.261_302_58, 0.275_777_11];
+
+/// Where the CLIP models come from
+#[derive(Debug, Clone)]
+pub struct ClipConfig {
+    /// Directory holding `vision.onnx`, `text.onnx` and `tokenizer.json`.
+    /// When unset the models are downloaded from Hugging Face into
+    /// `cache_dir`.
+    pub model_dir: Option<PathBuf>,
+    /// Download cache for the Hugging Face models
+    pub cache_dir: PathBuf,
+    /// ONNX Runtime intra-op threads per encoder (0 lets it decide)
+    pub num_threads: usize,
+}
+
+impl ClipConfig {
+    /// Download the models into `cache_dir`
+    pub fn new(cache_dir: PathBuf) -> Self {
+        Self {
+            model_dir: None,
+            cache_dir,
+            num_threads: 0,
+        }
+    }
+}
+
+/// Multimodal provider backed by the CLIP ViT-B/32 encoders
+pub struct ClipEmbedding {
+    vision: Mutex<Session>,
+    text: Mutex<Session>,
+    tokenizer: Tokenizer,
+}
+
+impl std::fmt::Debug for ClipEmbedding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClipEmbedding")
+            .field("dimension", &CLIP_DIMENSION)
+            .finish()
+    }
+}
+
+impl ClipEmbedding {
+    /// Load both encoders and the tokenizer, downloading them first when
+    /// `config.model_dir` is unset
+    pub fn load(config: &ClipConfig) -> Result<Self> {
+        let (vision_path, text_path, tokenizer_path) = match &config.model_dir {
+            Some(dir) => (
+                dir.join("vision.onnx"),
+                dir.join("text.onnx"),
+                dir.join("tokenizer.json"),
+            ),
+            None => download(&config.cache_dir)?,
+        };
+
+        tracing::info!(
+            "🔄 CLIP: loading encoders ({}, {})",
+            vision_path.display(),
+            text_path.display()
+        );
+
+        let mut tokenizer = Tokenizer::from_file(&tokenizer_path).map_err(|e| {
+            VectorizerError::Other(format!(
+                "Failed to load CLIP tokenizer {}: {}",
+                tokenizer_path.display(),
+                e
+            ))
+        })?;
+        tokenizer
+            .with_truncation(Some(TruncationParams {
+                max_length: CONTEXT_LENGTH,
+                ..Default::default()
+            }))
+            .map_err(clip_error)?;
+        tokenizer.with_padding(Some(PaddingParams {
+            strategy: PaddingStrategy::Fixed(CONTEXT_LENGTH),
+            ..Default::default()
+        }));
+
+        let provider = Self {
+            vision: Mutex::new(session(&vision_path, config.num_threads)?),
+            text: Mutex::new(session(&text_path, config.num_threads)?),
+            tokenizer,
+        };
+        tracing::info!("✅ CLIP: encoders ready (dim={})", CLIP_DIMENSION);
+        Ok(provider)
+    }
+
+    fn embed_text(&self, text: &str) -> Result<Vec<f32>> {
+        let encoding = self.tokenizer.encode(text, true).map_err(clip_error)?;
+        let ids: Vec<i64> = encoding.get_ids().iter().map(|&id| i64::from(id)).collect();
+        let mask: Vec<i64> = encoding
+            .get_attention_mask()
+            .iter()
+            .map(|&m| i64::from(m))
+            .collect();
+        let shape = [1, ids.len()];
+        let inputs = ort::inputs![
+            "input_ids" => Tensor::from_array((shape, ids)).map_err(clip_error)?,
+            "attention_mask" => Tensor::from_array((shape, mask)).map_err(clip_error)?,
+        ];
+
+        let mut session = self.text.lock();
+        let outputs = session.run(inputs).map_err(clip_error)?;
+        let output = outputs.get("text_embeds").unwrap_or(&outputs[0]);
+        let (_, data) = output.try_extract_tensor::<f32>().map_err(clip_error)?;
+        Ok(normalized(data))
+    }
+}
+
+impl EmbeddingProvider for ClipEmbedding {
+    fn embed_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        texts.iter().map(|text| self.embed_text(text)).collect()
+    }
+
+    fn dimension(&self) -> usize {
+        CLIP_DIMENSION
+    }
+
+    fn embed_image(&self, image: &[u8]) -> Result<Vec<f32>> {
+        let pixels = preprocess_image(image)?;
+        let size = IMAGE_SIZE as usize;
+        let input = Tensor::from_array(([1, 3, size, size], pixels)).map_err(clip_error)?;
+
+        let mut session = self.vision.lock();
+        let outputs = session
+            .run(ort::inputs!["pixel_values" => input])
+            .map_err(clip_error)?;
+        let output = outputs.get("image_embeds").unwrap_or(&outputs[0]);
+        let (_, data) = output.try_extract_tensor::<f32>().map_err(clip_error)?;
+        Ok(normalized(data))
+    }
+
+    fn cache_model_id(&self) -> Option<String> {
+        Some("clip:ViT-B-32".to_string())
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Fetch the encoders and tokenizer from Hugging Face into `cache_dir`
+fn download(cache_dir: &Path) -> Result<(PathBuf, PathBuf, PathBuf)> {
+    let api = hf_hub::api::sync::ApiBuilder::new()
+        .with_progress(false)
+        .with_cache_dir(cache_dir.to_path_buf())
+        .build()
+        .map_err(|e| VectorizerError::Other(format!("Failed to create HF API: {}", e)))?;
+    let fetch = |repo: &str, file: &str| {
+        api.model(repo.to_string()).get(file).map_err(|e| {
+            VectorizerError::Other(format!("Failed to download {}/{}: {}", repo, file, e))
+        })
+    };
+    Ok((
+        fetch(VISION_MODEL_REPO, "model.onnx")?,
+        fetch(TEXT_MODEL_REPO, "model.onnx")?,
+        fetch(TEXT_MODEL_REPO, "tokenizer.json")?,
+    ))
+}
+
+fn session(path: &Path, num_threads: usize) -> Result<Session> {
+    let mut builder = Session::builder().map_err(clip_error)?;
+    if num_threads > 0 {
+        builder = builder
+            .with_intra_threads(num_threads)
+            .map_err(clip_error)?;
+    }
+    builder.commit_from_file(path).map_err(|e| {
+        VectorizerError::Other(format!(
+            "Failed to load ONNX model {}: {}",
+            path.display(),
+            e
+        ))
+    })
+}
+
+/// Decode an image and lay it out as the vision encoder expects: RGB,
+/// shortest side resized to 224, center-cropped to 224×224, normalized
+/// per channel, channels first
+pub fn preprocess_image(bytes: &[u8]) -> Result<Vec<f32>> {
+    let image = image::load_from_memory(bytes)
+        .map_err(|e| VectorizerError::Other(format!("Failed to decode image: {}", e)))?
+        .to_rgb8();
+    let (width, height) = resized_dimensions(image.width(), image.height());
+    let resized = image::imageops::resize(&image, width, height, FilterType::CatmullRom);
+    let left = (width - IMAGE_SIZE) / 2;
+    let top = (height - IMAGE_SIZE) / 2;
+
+    let size = IMAGE_SIZE as usize;
+    let mut pixels = vec![0.0; 3 * size * size];
+    for y in 0..IMAGE_SIZE {
+        for x in 0..IMAGE_SIZE {
+            let pixel = resized.get_pixel(left + x, top + y);
+            let offset = y as usize * size + x as usize;
+            for (c, (mean, std)) in PIXEL_MEAN.iter().zip(&PIXEL_STD).enumerate() {
+                pixels[c * size * size + offset] = (f32::from(pixel[c]) / 255.0 - mean) / std;
+            }
+        }
+    }
+    Ok(pixels)
+}
+
+/// Size of a `width`×`height` image scaled so its shortest side is
+/// [`IMAGE_SIZE`]
+fn resized_dimensions(width: u32, height: u32) -> (u32, u32) {
+    let scale = |side: u32, shortest: u32| {
+        ((u64::from(side) * u64::from(IMAGE_SIZE) + u64::from(shortest) / 2) / u64::from(shortest))
+            .max(u64::from(IMAGE_SIZE)) as u32
+    };
+    let shortest = width.min(height).max(1);
+    (scale(width, shortest), scale(height, shortest))
+}
+
+fn normalized(data: &[f32]) -> Vec<f32> {
+    let mut embedding = data.to_vec();
+    crate::simd::normalize_in_place(&mut embedding);
+    embedding
+}
+
+fn clip_error(e: impl std::fmt::Display) -> VectorizerError {
+    VectorizerError::Other(format!("CLIP inference failed: {}", e))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn test_resized_dimensions_keep_aspect_ratio() {
+        assert_eq!(resized_dimensions(224, 224), (224, 224));
+        assert_eq!(resized_dimensions(1920, 1080), (398, 224));
+        assert_eq!(resized_dimensions(100, 400), (224, 896));
+        assert_eq!(resized_dimensions(1, 1), (224, 224));
+    }
+
+    #[test]
+    fn test_preprocess_image_layout() {
+        let image = image::RgbImage::from_pixel(640, 480, image::Rgb([255, 0, 0]));
+        let mut png = Vec::new();
+        image
+            .write_to(&mut Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+
+        let pixels = preprocess_image(&png).unwrap();
+        let plane = 224 * 224;
+        assert_eq!(pixels.len(), 3 * plane);
+        let red = (1.0 - PIXEL_MEAN[0]) / PIXEL_STD[0];
+        let green = -PIXEL_MEAN[1] / PIXEL_STD[1];
+        assert!((pixels[0] - red).abs() < 1e-4);
+        assert!((pixels[plane] - green).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_preprocess_rejects_garbage() {
+        assert!(preprocess_image(b"not an image").is_err());
+    }
+}
@@ -35,6 +35,24 @@ pub trait EmbeddingProvider: Send + Sync {
     /// Get the dimension of embeddings produced by this provider
     fn dimension(&self) -> usize;
 
+    /// Embed an encoded image (PNG, JPEG, ...) into the same space as
+    /// this provider's text embeddings. Default implementation returns an
+    /// error; multimodal providers (CLIP) override it.
+    fn embed_image(&self, _image: &[u8]) -> Result<Vec<f32>> {
+        Err(VectorizerError::Other(
+            "Provider does not support image embedding".to_string(),
+        ))
+    }
+
+    /// Identifier of the model behind this provider, for the persisted
+    /// [`EmbeddingCache`]. Two providers reporting the same id must embed
+    /// every text identically, across restarts. `None` (the default)
+    /// keeps the provider's embeddings out of the cache; providers whose
+    /// output depends on a fitted vocabulary leave it that way.
+    fn cache_model_id(&self) -> Option<String> {
+        None
+    }
+
     /// Persist this provider's vocabulary (if any) to a JSON file.
     ///
     /// Default implementation returns an error — providers that have no
@@ -76,7 +94,7 @@ pub trait EmbeddingProvider: Send + Sync {
 pub mod providers;
 pub use providers::{
     BagOfWordsEmbedding, BertEmbedding, Bm25Embedding, CharNGramEmbedding, EmbeddingManager,
-    MiniLmEmbedding, SvdEmbedding, TfIdfEmbedding,
+    MiniLmEmbedding, ProviderSwap, SvdEmbedding, TfIdfEmbedding,
 };
 
 // Real models module
@@ -89,11 +107,16 @@ pub mod fast_tokenizer;
 #[cfg(feature = "onnx-models")]
 pub mod onnx_models;
 
+#[cfg(feature = "onnx-models")]
+pub mod clip;
+
 pub mod cache;
 
 // Re-export real models
 pub use cache::{CacheConfig, EmbeddingCache};
 // Re-export performance modules
+#[cfg(feature = "onnx-models")]
+pub use clip::{ClipConfig, ClipEmbedding};
 #[cfg(feature = "tokenizers")]
 pub use fast_tokenizer::{FastTokenizer, FastTokenizerConfig};
 #[cfg(feature = "onnx-models")]
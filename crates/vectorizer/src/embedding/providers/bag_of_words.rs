@@ -86,6 +86,8 @@ impl EmbeddingProvider for BagOfWordsEmbedding {
 
             // Fallback: Generate a simple hash-based embedding to ensure non-zero vector
             warn!("Using fallback hash-based embedding");
+            crate::monitoring::metrics::METRICS
+                .record_embedding_fallback("bag_of_words", self.vocabulary.is_empty());
             return Ok(self.fallback_hash_embedding(text));
         }
 
@@ -21,6 +21,7 @@ use tracing::warn;
 
 use crate::embedding::EmbeddingProvider;
 use crate::error::{Result, VectorizerError};
+use crate::language::{self, Language};
 
 #[derive(Debug)]
 pub struct Bm25Embedding {
@@ -46,6 +47,14 @@ pub struct Bm25Embedding {
     /// Maximum frequency of the "empty vocabulary" warn; defaults to
     /// 1 emit per 5 s window.
     warn_min_interval: Duration,
+    /// Build a vocabulary per detected language (see
+    /// [`Self::with_language_detection`])
+    language_detection: bool,
+    /// Analyzer of a per-language model; `None` tokenizes on whitespace
+    language: Option<Language>,
+    /// Per-language models, built by [`Self::build_vocabulary`] when
+    /// language detection is on
+    language_models: HashMap<Language, Bm25Embedding>,
 }
 impl Bm25Embedding {
     /// Create a new BM25 embedding provider
@@ -62,9 +71,43 @@ impl Bm25Embedding {
             last_empty_vocab_warn: Mutex::new(None),
             collection_label: "unknown".to_string(),
             warn_min_interval: Duration::from_secs(5),
+            language_detection: false,
+            language: None,
+            language_models: HashMap::new(),
         }
     }
 
+    /// Detect the language of documents and queries (see
+    /// [`crate::language`]): [`Self::build_vocabulary`] also builds a
+    /// vocabulary per detected language, with its stopwords removed and
+    /// terms stemmed, and texts are embedded against the vocabulary of
+    /// their language. Texts in no known language use the shared one.
+    pub fn with_language_detection(mut self, enabled: bool) -> Self {
+        self.language_detection = enabled;
+        self
+    }
+
+    /// Mutating variant of [`Self::with_language_detection`].
+    pub fn set_language_detection(&mut self, enabled: bool) {
+        self.language_detection = enabled;
+    }
+
+    /// Languages with a vocabulary of their own
+    pub fn languages(&self) -> Vec<Language> {
+        let mut languages: Vec<Language> = self.language_models.keys().copied().collect();
+        languages.sort();
+        languages
+    }
+
+    /// An empty model analyzing text as `language`
+    fn for_language(&self, language: Language) -> Self {
+        let mut model = Self::new(self.dimension)
+            .with_collection_label(self.collection_label.clone())
+            .with_warn_min_interval(self.warn_min_interval);
+        model.language = Some(language);
+        model
+    }
+
     /// Set the collection label used in rate-limited warnings + the
     /// `vectorizer_bm25_empty_vocab_fallback_total` counter (issue
     /// #263, phase9 §6). When unset the label defaults to `"unknown"`.
@@ -142,7 +185,7 @@ impl Bm25Embedding {
     /// Save vocabulary to a JSON file (tokenizer)
     pub fn save_vocabulary_json<P: AsRef<Path>>(&self, path: P) -> Result<()> {
         let path_ref = path.as_ref();
-        let data = serde_json::json!({
+        let mut data = serde_json::json!({
             "type": "bm25",
             "dimension": self.dimension,
             "vocabulary": self.vocabulary,
@@ -151,6 +194,23 @@ impl Bm25Embedding {
             "avg_doc_length": self.avg_doc_length,
             "total_docs": self.total_docs,
         });
+        if self.language_detection {
+            let languages: serde_json::Map<String, serde_json::Value> = self
+                .language_models
+                .iter()
+                .map(|(language, model)| {
+                    let statistics = serde_json::json!({
+                        "vocabulary": model.vocabulary,
+                        "doc_freq": model.doc_freq,
+                        "doc_lengths": model.doc_lengths,
+                        "avg_doc_length": model.avg_doc_length,
+                        "total_docs": model.total_docs,
+                    });
+                    (language.code().to_string(), statistics)
+                })
+                .collect();
+            data["languages"] = serde_json::Value::Object(languages);
+        }
         let json = serde_json::to_string_pretty(&data).map_err(|e| {
             VectorizerError::Other(format!("Failed to serialize vocabulary: {}", e))
         })?;
@@ -225,6 +285,27 @@ impl Bm25Embedding {
         self.doc_lengths = doc_lengths;
         self.avg_doc_length = avg_doc_length;
         self.total_docs = total_docs;
+
+        // Per-language vocabularies, saved when language detection is on
+        self.language_models.clear();
+        if let Some(languages) = v.get("languages").and_then(|x| x.as_object()) {
+            self.language_detection = true;
+            for (code, statistics) in languages {
+                let Some(language) = Language::from_code(code) else {
+                    warn!("Ignoring BM25 vocabulary of unknown language '{}'", code);
+                    continue;
+                };
+                let field = |name: &str| statistics.get(name).cloned().unwrap_or_default();
+                let mut model = self.for_language(language);
+                model.vocabulary = serde_json::from_value(field("vocabulary")).unwrap_or_default();
+                model.doc_freq = serde_json::from_value(field("doc_freq")).unwrap_or_default();
+                model.doc_lengths =
+                    serde_json::from_value(field("doc_lengths")).unwrap_or_default();
+                model.avg_doc_length = field("avg_doc_length").as_f64().unwrap_or(0.0) as f32;
+                model.total_docs = field("total_docs").as_u64().unwrap_or(0) as usize;
+                self.language_models.insert(language, model);
+            }
+        }
         Ok(())
     }
 
@@ -255,6 +336,10 @@ impl Bm25Embedding {
         self.avg_doc_length =
             self.doc_lengths.iter().sum::<usize>() as f32 / self.total_docs as f32;
 
+        if self.language_detection {
+            self.build_language_vocabularies(texts);
+        }
+
         // Build vocabulary and sort by frequency for deterministic results
         let mut word_freq: Vec<(String, usize)> = word_counts.into_iter().collect();
         word_freq.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
@@ -269,8 +354,30 @@ impl Bm25Embedding {
         // Vocabulary construction completed silently
     }
 
-    /// Tokenize text into words (simple whitespace splitting)
+    /// Build a model per language detected among `texts`, from that
+    /// language's texts only
+    fn build_language_vocabularies(&mut self, texts: &[String]) {
+        let mut by_language: HashMap<Language, Vec<String>> = HashMap::new();
+        for text in texts {
+            if let Some(language) = language::detect(text) {
+                by_language.entry(language).or_default().push(text.clone());
+            }
+        }
+
+        self.language_models.clear();
+        for (language, texts) in by_language {
+            let mut model = self.for_language(language);
+            model.build_vocabulary(&texts);
+            self.language_models.insert(language, model);
+        }
+    }
+
+    /// Tokenize text into words: the model's language analyzer, or
+    /// simple whitespace splitting
     fn tokenize(&self, text: &str) -> Vec<String> {
+        if let Some(language) = self.language {
+            return language::analyze(text, language);
+        }
         text.to_lowercase()
             .split_whitespace()
             .map(|s| s.trim_matches(|c: char| !c.is_alphanumeric()).to_string())
@@ -367,6 +474,12 @@ impl EmbeddingProvider for Bm25Embedding {
     }
 
     fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        if let Some(model) = language::route(text, &self.language_models, |model, term| {
+            model.vocabulary.contains_key(term)
+        }) {
+            return model.embed(text);
+        }
+
         let tokens = self.tokenize(text);
         let doc_length = tokens.len();
 
@@ -390,6 +503,7 @@ impl EmbeddingProvider for Bm25Embedding {
                 .bm25_empty_vocab_fallback_total
                 .with_label_values(&[&self.collection_label])
                 .inc();
+            crate::monitoring::metrics::METRICS.record_embedding_fallback("bm25", true);
 
             if self.should_emit_empty_vocab_warn() {
                 // Safely truncate text for logging (handle Unicode properly)
@@ -444,6 +558,7 @@ impl EmbeddingProvider for Bm25Embedding {
         // If embedding is all zeros (no vocab matches), build deterministic feature-hashed embedding from tokens
         let non_zero_count = embedding.iter().filter(|&&x| x != 0.0).count();
         if non_zero_count == 0 {
+            crate::monitoring::metrics::METRICS.record_embedding_fallback("bm25", false);
             // Safely truncate text for logging (handle Unicode properly)
             let preview = if text.len() > 100 {
                 // Find the last char boundary before 100 bytes
@@ -538,6 +653,58 @@ mod tests {
         dot / (na * nb)
     }
 
+    fn mixed_corpus() -> Vec<String> {
+        [
+            "The quarterly sales report shows revenue growth in every region.",
+            "Our servers process customer orders and store the sales data.",
+            "Revenue from new customers grew faster than expected this year.",
+            "O relatório trimestral de vendas mostra crescimento da receita.",
+            "Os servidores processam os pedidos dos clientes e guardam os dados.",
+            "A receita de novos clientes cresceu mais do que o esperado este ano.",
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect()
+    }
+
+    #[test]
+    fn language_detection_builds_a_vocabulary_per_language() {
+        let mut bm25 = Bm25Embedding::new(64).with_language_detection(true);
+        bm25.build_vocabulary(&mixed_corpus());
+        assert_eq!(bm25.languages(), [Language::English, Language::Portuguese]);
+
+        let portuguese = &bm25.language_models[&Language::Portuguese];
+        assert!(portuguese.vocabulary.contains_key("dad"));
+        // Stopwords stay out of the per-language vocabularies
+        assert!(!portuguese.vocabulary.contains_key("os"));
+        assert!(
+            !bm25.language_models[&Language::English]
+                .vocabulary
+                .contains_key("the")
+        );
+
+        // A short query lands in the vocabulary that knows its terms
+        let query = bm25.embed("dados dos clientes").unwrap();
+        let document = bm25.embed(&mixed_corpus()[4]).unwrap();
+        let unrelated = bm25.embed(&mixed_corpus()[3]).unwrap();
+        assert!(cosine(&query, &document) > cosine(&query, &unrelated));
+    }
+
+    #[test]
+    fn language_vocabularies_survive_save_and_load() {
+        let mut bm25 = Bm25Embedding::new(64).with_language_detection(true);
+        bm25.build_vocabulary(&mixed_corpus());
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("docs_tokenizer.json");
+        bm25.save_vocabulary_json(&path).unwrap();
+
+        let mut restored = Bm25Embedding::new(64);
+        restored.load_vocabulary_json(&path).unwrap();
+        assert_eq!(restored.languages(), bm25.languages());
+        let query = "vendas e receita";
+        assert_eq!(restored.embed(query).unwrap(), bm25.embed(query).unwrap());
+    }
+
     /// Regression test for phase8_investigate-uniform-embeddings (finding F4).
     ///
     /// The pre-fix LCG in `fallback_hash_embedding` produced
@@ -96,6 +96,8 @@ impl EmbeddingProvider for CharNGramEmbedding {
 
             // Fallback: Generate a simple hash-based embedding to ensure non-zero vector
             warn!("Using fallback hash-based embedding");
+            crate::monitoring::metrics::METRICS
+                .record_embedding_fallback("char_ngram", self.ngram_map.is_empty());
             return Ok(self.fallback_hash_embedding(text));
         }
 
@@ -101,6 +101,10 @@ impl EmbeddingProvider for FastEmbedProvider {
         self.dimension
     }
 
+    fn cache_model_id(&self) -> Option<String> {
+        Some(self.name.clone())
+    }
+
     fn save_vocabulary_json(&self, _path: &Path) -> Result<()> {
         Err(VectorizerError::Other(
             "fastembed models ship vocabulary inside the ONNX archive — no separate JSON file"
@@ -1,6 +1,18 @@
 //! `EmbeddingManager` — multi-provider registry + dispatch facade.
 //! Extracted from the monolithic `embedding/mod.rs` under
 //! phase4_split-embedding-providers. No behavior change.
+//!
+//! Each provider sits in a slot holding an `Arc`, and every lookup hands
+//! out a clone of it. That makes [`EmbeddingManager::swap_provider`]
+//! possible through `&self`: the slot is repointed atomically, calls
+//! already running finish on the model they started with, and the
+//! returned [`ProviderSwap`] tells the caller when the last of them has
+//! released the old model.
+//!
+//! With an [`EmbeddingCache`] set ([`EmbeddingManager::set_cache`]),
+//! embeddings of providers that name their model
+//! ([`EmbeddingProvider::cache_model_id`]) are read from and written to
+//! it, so unchanged text is not embedded again after a restart.
 
 // Internal data-layout file: public fields are self-documenting; the
 // blanket allow keeps `cargo doc -W missing-docs` clean without padding
@@ -11,13 +23,57 @@
 use std::any::Any;
 use std::collections::HashMap;
 use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use crate::embedding::EmbeddingProvider;
+use parking_lot::RwLock;
+
+use crate::embedding::{EmbeddingCache, EmbeddingProvider};
 use crate::error::{Result, VectorizerError};
 
+struct ProviderSlot {
+    current: RwLock<Arc<dyn EmbeddingProvider>>,
+    /// Bumped on every [`EmbeddingManager::swap_provider`].
+    generation: RwLock<u64>,
+}
+
 pub struct EmbeddingManager {
-    providers: HashMap<String, Box<dyn EmbeddingProvider>>,
+    providers: HashMap<String, ProviderSlot>,
     default_provider: Option<String>,
+    cache: Option<Arc<EmbeddingCache>>,
+    /// Model names served by a provider registered under another name
+    model_aliases: HashMap<String, String>,
+}
+
+/// The model a [`EmbeddingManager::swap_provider`] call replaced.
+///
+/// Holding this keeps the old model alive; drop it once
+/// [`ProviderSwap::wait_drained`] returns.
+pub struct ProviderSwap {
+    previous: Arc<dyn EmbeddingProvider>,
+    /// Generation of the provider slot after the swap (1 for the first
+    /// swap after registration).
+    pub generation: u64,
+}
+
+impl ProviderSwap {
+    /// Embedding calls still running on the old model.
+    pub fn in_flight(&self) -> usize {
+        Arc::strong_count(&self.previous) - 1
+    }
+
+    /// Block until no embedding call is running on the old model, or
+    /// `timeout` elapses. Returns whether the old model drained.
+    pub fn wait_drained(&self, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        while self.in_flight() > 0 {
+            if Instant::now() >= deadline {
+                return false;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        true
+    }
 }
 
 impl EmbeddingManager {
@@ -26,15 +82,73 @@ impl EmbeddingManager {
         Self {
             providers: HashMap::new(),
             default_provider: None,
+            cache: None,
+            model_aliases: HashMap::new(),
         }
     }
 
+    /// Serve embeddings from `cache` and store new ones in it. Only
+    /// providers reporting a [`EmbeddingProvider::cache_model_id`] use it.
+    pub fn set_cache(&mut self, cache: Arc<EmbeddingCache>) {
+        self.cache = Some(cache);
+    }
+
+    /// The persisted embedding cache, if one is set
+    pub fn cache(&self) -> Option<&Arc<EmbeddingCache>> {
+        self.cache.as_ref()
+    }
+
     /// Register an embedding provider
     pub fn register_provider(&mut self, name: String, provider: Box<dyn EmbeddingProvider>) {
         if self.default_provider.is_none() {
             self.default_provider = Some(name.clone());
         }
-        self.providers.insert(name, provider);
+        self.providers.insert(
+            name,
+            ProviderSlot {
+                current: RwLock::new(Arc::from(provider)),
+                generation: RwLock::new(0),
+            },
+        );
+    }
+
+    /// Atomically replace the model registered under `name`.
+    ///
+    /// `provider` must produce vectors of the same dimension as the one it
+    /// replaces, since existing collections were embedded with it. Calls
+    /// that already obtained the old model finish on it; every later call
+    /// uses the new one.
+    pub fn swap_provider(
+        &self,
+        name: &str,
+        provider: Box<dyn EmbeddingProvider>,
+    ) -> Result<ProviderSwap> {
+        let slot = self
+            .providers
+            .get(name)
+            .ok_or_else(|| VectorizerError::Other(format!("Provider '{}' not found", name)))?;
+
+        let mut current = slot.current.write();
+        if provider.dimension() != current.dimension() {
+            return Err(VectorizerError::DimensionMismatch {
+                expected: current.dimension(),
+                actual: provider.dimension(),
+            });
+        }
+        let previous = std::mem::replace(&mut *current, Arc::from(provider));
+        let mut generation = slot.generation.write();
+        *generation += 1;
+
+        Ok(ProviderSwap {
+            previous,
+            generation: *generation,
+        })
+    }
+
+    /// How many times the provider registered under `name` has been
+    /// swapped since registration.
+    pub fn provider_generation(&self, name: &str) -> Option<u64> {
+        self.providers.get(name).map(|slot| *slot.generation.read())
     }
 
     /// Set the default provider
@@ -51,26 +165,31 @@ impl EmbeddingManager {
     }
 
     /// Get a provider by name
-    pub fn get_provider(&self, name: &str) -> Result<&dyn EmbeddingProvider> {
+    pub fn get_provider(&self, name: &str) -> Result<Arc<dyn EmbeddingProvider>> {
         self.providers
             .get(name)
-            .map(|p| p.as_ref())
+            .map(|slot| slot.current.read().clone())
             .ok_or_else(|| VectorizerError::Other(format!("Provider '{}' not found", name)))
     }
 
-    /// Get a mutable provider by name
-    pub fn get_provider_mut(&mut self, name: &str) -> Option<&mut Box<dyn EmbeddingProvider>> {
-        self.providers.get_mut(name)
+    /// Get a mutable provider by name. `None` if it is unknown or a
+    /// caller still holds it (only possible after the manager is shared).
+    pub fn get_provider_mut(
+        &mut self,
+        name: &str,
+    ) -> Option<&mut (dyn EmbeddingProvider + 'static)> {
+        Arc::get_mut(self.providers.get_mut(name)?.current.get_mut())
     }
 
     /// Get the default provider
-    pub fn get_default_provider(&self) -> Result<&dyn EmbeddingProvider> {
-        let provider_name = self
-            .default_provider
-            .as_ref()
-            .ok_or_else(|| VectorizerError::Other("No default provider set".to_string()))?;
+    pub fn get_default_provider(&self) -> Result<Arc<dyn EmbeddingProvider>> {
+        self.get_provider(self.default_provider_name()?)
+    }
 
-        self.get_provider(provider_name)
+    fn default_provider_name(&self) -> Result<&str> {
+        self.default_provider
+            .as_deref()
+            .ok_or_else(|| VectorizerError::Other("No default provider set".to_string()))
     }
 
     /// Get the default provider name
@@ -80,18 +199,28 @@ impl EmbeddingManager {
 
     /// Embed text using the default provider
     pub fn embed(&self, text: &str) -> Result<Vec<f32>> {
-        self.get_default_provider()?.embed(text)
+        self.embed_with_provider(self.default_provider_name()?, text)
     }
 
     /// Embed batch of texts using the default provider
     pub fn embed_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
-        self.get_default_provider()?.embed_batch(texts)
+        self.embed_batch_with_provider(texts, self.default_provider_name()?)
     }
 
     /// Embed text using a specific provider by name
     pub fn embed_with_provider(&self, provider_name: &str, text: &str) -> Result<Vec<f32>> {
         let provider = self.get_provider(provider_name)?;
-        provider.embed(text)
+        let _span = tracing::info_span!("embedding.generate", provider = provider_name, texts = 1)
+            .entered();
+        let Some((cache, model)) = self.cache_for(provider.as_ref()) else {
+            return provider.embed(text);
+        };
+        if let Some(embedding) = cached(cache, provider.as_ref(), provider_name, &model, text) {
+            return Ok(embedding);
+        }
+        let embedding = provider.embed(text)?;
+        store(cache, provider_name, &model, text, &embedding);
+        Ok(embedding)
     }
 
     /// Embed batch of texts using a specific provider by name
@@ -100,7 +229,53 @@ impl EmbeddingManager {
         texts: &[&str],
         provider_name: &str,
     ) -> Result<Vec<Vec<f32>>> {
-        self.get_provider(provider_name)?.embed_batch(texts)
+        let provider = self.get_provider(provider_name)?;
+        let _span = tracing::info_span!(
+            "embedding.generate",
+            provider = provider_name,
+            texts = texts.len()
+        )
+        .entered();
+        let Some((cache, model)) = self.cache_for(provider.as_ref()) else {
+            return provider.embed_batch(texts);
+        };
+
+        let mut embeddings: Vec<Option<Vec<f32>>> = texts
+            .iter()
+            .map(|text| cached(cache, provider.as_ref(), provider_name, &model, text))
+            .collect();
+        let missing: Vec<usize> = (0..texts.len())
+            .filter(|&i| embeddings[i].is_none())
+            .collect();
+        if !missing.is_empty() {
+            let missing_texts: Vec<&str> = missing.iter().map(|&i| texts[i]).collect();
+            let computed = provider.embed_batch(&missing_texts)?;
+            for (i, embedding) in missing.into_iter().zip(computed) {
+                store(cache, provider_name, &model, texts[i], &embedding);
+                embeddings[i] = Some(embedding);
+            }
+        }
+        Ok(embeddings.into_iter().flatten().collect())
+    }
+
+    /// Embed an encoded image using a specific provider by name. Fails
+    /// unless the provider is multimodal (see
+    /// [`EmbeddingProvider::embed_image`]). Image embeddings bypass the
+    /// cache, which is keyed by text.
+    pub fn embed_image_with_provider(&self, provider_name: &str, image: &[u8]) -> Result<Vec<f32>> {
+        let provider = self.get_provider(provider_name)?;
+        let _span = tracing::info_span!("embedding.generate", provider = provider_name, images = 1)
+            .entered();
+        provider.embed_image(image)
+    }
+
+    /// The cache and model id to use for `provider`, if both exist
+    fn cache_for(
+        &self,
+        provider: &dyn EmbeddingProvider,
+    ) -> Option<(&Arc<EmbeddingCache>, String)> {
+        let cache = self.cache.as_ref()?;
+        Some((cache, provider.cache_model_id()?))
     }
 
     /// Get the dimension of a specific provider
@@ -118,6 +293,36 @@ impl EmbeddingManager {
         self.providers.contains_key(provider_name)
     }
 
+    /// Serve `provider` under the model name `alias` too, so clients of
+    /// another embedding API (such as `text-embedding-3-small` sent to
+    /// the OpenAI-compatible endpoint) reach a local model.
+    pub fn register_model_alias(&mut self, alias: String, provider: &str) -> Result<()> {
+        if !self.providers.contains_key(provider) {
+            return Err(VectorizerError::Other(format!(
+                "Provider '{}' not found",
+                provider
+            )));
+        }
+        self.model_aliases.insert(alias, provider.to_string());
+        Ok(())
+    }
+
+    /// The provider a model name refers to: a provider of that name, else
+    /// the provider the name is an alias of.
+    pub fn resolve_model(&self, model: &str) -> Option<&str> {
+        match self.providers.get_key_value(model) {
+            Some((name, _)) => Some(name),
+            None => self.model_aliases.get(model).map(String::as_str),
+        }
+    }
+
+    /// Registered model aliases, as `(alias, provider)`
+    pub fn model_aliases(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.model_aliases
+            .iter()
+            .map(|(alias, provider)| (alias.as_str(), provider.as_str()))
+    }
+
     /// Save vocabulary for a specific provider.
     ///
     /// Dispatches through `EmbeddingProvider::save_vocabulary_json`; providers
@@ -141,7 +346,7 @@ impl EmbeddingManager {
         path: P,
     ) -> Result<()> {
         let provider = self.get_provider_mut(provider_name).ok_or_else(|| {
-            VectorizerError::Other(format!("Provider '{provider_name}' not found"))
+            VectorizerError::Other(format!("Provider '{provider_name}' not found or in use"))
         })?;
         provider
             .load_vocabulary_json(path.as_ref())
@@ -276,6 +481,28 @@ impl EmbeddingManager {
     }
 }
 
+/// `text`'s cached embedding, unless its dimension no longer matches
+/// `provider`.
+fn cached(
+    cache: &EmbeddingCache,
+    provider: &dyn EmbeddingProvider,
+    provider_name: &str,
+    model: &str,
+    text: &str,
+) -> Option<Vec<f32>> {
+    cache
+        .get_for(provider_name, model, text)
+        .filter(|embedding| embedding.len() == provider.dimension())
+}
+
+/// Store a new embedding. A failed write only costs a recomputation
+/// later, so it is logged rather than returned.
+fn store(cache: &EmbeddingCache, provider_name: &str, model: &str, text: &str, embedding: &[f32]) {
+    if let Err(e) = cache.put_for(provider_name, model, text, embedding) {
+        tracing::warn!("Failed to store embedding in the cache: {}", e);
+    }
+}
+
 /// Outcome of [`EmbeddingManager::restore_vocabulary_from_disk`].
 #[derive(Debug)]
 pub struct VocabularyRestoreReport {
@@ -371,6 +598,77 @@ mod tests {
         assert_eq!(default_provider.dimension(), 10);
     }
 
+    #[test]
+    fn model_aliases_resolve_to_registered_providers() {
+        let mut manager = EmbeddingManager::new();
+        manager.register_provider("bow".to_string(), Box::new(BagOfWordsEmbedding::new(5)));
+        manager
+            .register_model_alias("text-embedding-3-small".to_string(), "bow")
+            .unwrap();
+        assert!(
+            manager
+                .register_model_alias("ada".to_string(), "missing")
+                .is_err()
+        );
+
+        assert_eq!(manager.resolve_model("bow"), Some("bow"));
+        assert_eq!(manager.resolve_model("text-embedding-3-small"), Some("bow"));
+        assert_eq!(manager.resolve_model("ada"), None);
+        assert!(!manager.has_provider("text-embedding-3-small"));
+    }
+
+    #[test]
+    fn swap_provider_replaces_model_and_drains_old_one() {
+        let mut manager = EmbeddingManager::new();
+        manager.register_provider("bow".to_string(), Box::new(BagOfWordsEmbedding::new(5)));
+        assert_eq!(manager.provider_generation("bow"), Some(0));
+
+        // A call that started before the swap keeps its model.
+        let lease = manager.get_provider("bow").unwrap();
+        let swap = manager
+            .swap_provider("bow", Box::new(TfIdfEmbedding::new(5)))
+            .unwrap();
+        assert_eq!(swap.generation, 1);
+        assert_eq!(manager.provider_generation("bow"), Some(1));
+        assert!(
+            manager
+                .get_provider("bow")
+                .unwrap()
+                .as_any()
+                .is::<TfIdfEmbedding>()
+        );
+        assert!(lease.as_any().is::<BagOfWordsEmbedding>());
+
+        assert_eq!(swap.in_flight(), 1);
+        assert!(!swap.wait_drained(Duration::from_millis(20)));
+        drop(lease);
+        assert!(swap.wait_drained(Duration::from_millis(20)));
+    }
+
+    #[test]
+    fn swap_provider_rejects_dimension_change() {
+        let mut manager = EmbeddingManager::new();
+        manager.register_provider("bow".to_string(), Box::new(BagOfWordsEmbedding::new(5)));
+
+        let err = manager
+            .swap_provider("bow", Box::new(BagOfWordsEmbedding::new(8)))
+            .err()
+            .unwrap();
+        assert!(matches!(
+            err,
+            VectorizerError::DimensionMismatch {
+                expected: 5,
+                actual: 8
+            }
+        ));
+        assert_eq!(manager.provider_generation("bow"), Some(0));
+        assert!(
+            manager
+                .swap_provider("missing", Box::new(BagOfWordsEmbedding::new(5)))
+                .is_err()
+        );
+    }
+
     #[test]
     fn save_vocabulary_dispatches_through_trait_for_bm25() {
         let mut manager = EmbeddingManager::new();
@@ -408,4 +706,73 @@ mod tests {
             "expected provider-aware error for SVD, got: {msg}"
         );
     }
+
+    /// Embeds a text as `[len, 1.0]` and counts the texts it embedded.
+    struct CountingProvider {
+        embedded: Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    impl EmbeddingProvider for CountingProvider {
+        fn embed_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+            self.embedded
+                .fetch_add(texts.len(), std::sync::atomic::Ordering::SeqCst);
+            Ok(texts.iter().map(|t| vec![t.len() as f32, 1.0]).collect())
+        }
+
+        fn dimension(&self) -> usize {
+            2
+        }
+
+        fn cache_model_id(&self) -> Option<String> {
+            Some("counting-v1".to_string())
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn Any {
+            self
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+    }
+
+    #[test]
+    fn cached_embeddings_survive_a_restart() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = crate::embedding::CacheConfig {
+            cache_dir: tmp.path().to_path_buf(),
+            num_shards: 2,
+            ..Default::default()
+        };
+        let manager_with = |embedded: &Arc<std::sync::atomic::AtomicUsize>| {
+            let mut manager = EmbeddingManager::new();
+            manager.register_provider(
+                "counting".to_string(),
+                Box::new(CountingProvider {
+                    embedded: embedded.clone(),
+                }),
+            );
+            manager.set_cache(Arc::new(EmbeddingCache::new(config.clone()).unwrap()));
+            manager
+        };
+
+        let embedded = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let manager = manager_with(&embedded);
+        let first = manager.embed_batch(&["alpha", "beta"]).unwrap();
+        assert_eq!(manager.embed("alpha").unwrap(), first[0]);
+        assert_eq!(embedded.load(std::sync::atomic::Ordering::SeqCst), 2);
+        manager.cache().unwrap().flush().unwrap();
+        drop(manager);
+
+        // A new process only embeds the text it has not seen
+        let embedded = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let manager = manager_with(&embedded);
+        let again = manager.embed_batch(&["beta", "gamma", "alpha"]).unwrap();
+        assert_eq!(embedded.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert_eq!(
+            again,
+            vec![first[1].clone(), vec![5.0, 1.0], first[0].clone()]
+        );
+        assert_eq!(manager.cache().unwrap().len(), 3);
+    }
 }
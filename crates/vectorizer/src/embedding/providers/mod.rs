@@ -48,7 +48,31 @@ pub fn try_build_fastembed_provider(
         ))
     }
 }
-pub use manager::EmbeddingManager;
+
+/// Factory for the `clip` provider: the CLIP ViT-B/32 encoders, with the
+/// models downloaded into `cache_dir` on first use. Returns a typed error
+/// when the `onnx-models` Cargo feature is off, like
+/// [`try_build_fastembed_provider`].
+pub fn try_build_clip_provider(
+    cache_dir: std::path::PathBuf,
+) -> crate::error::Result<Box<dyn crate::embedding::EmbeddingProvider>> {
+    #[cfg(feature = "onnx-models")]
+    {
+        let config = crate::embedding::ClipConfig::new(cache_dir);
+        Ok(Box::new(crate::embedding::ClipEmbedding::load(&config)?))
+    }
+    #[cfg(not(feature = "onnx-models"))]
+    {
+        let _ = cache_dir;
+        Err(crate::error::VectorizerError::Other(
+            "onnx-models Cargo feature is not enabled in this build. Rebuild vectorizer with \
+             `cargo build --release --features onnx-models` to use the `clip` image embedding \
+             provider."
+                .to_string(),
+        ))
+    }
+}
+pub use manager::{EmbeddingManager, ProviderSwap};
 pub use minilm::MiniLmEmbedding;
 pub use svd::SvdEmbedding;
 pub use tfidf::TfIdfEmbedding;
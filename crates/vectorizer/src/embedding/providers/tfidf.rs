@@ -10,6 +10,7 @@ use std::path::Path;
 
 use crate::embedding::EmbeddingProvider;
 use crate::error::{Result, VectorizerError};
+use crate::language::{self, Language};
 
 /// Simple TF-IDF based embedding provider for demonstration
 #[derive(Debug)]
@@ -17,6 +18,14 @@ pub struct TfIdfEmbedding {
     dimension: usize,
     vocabulary: HashMap<String, usize>,
     idf_weights: Vec<f32>,
+    /// Build a vocabulary per detected language (see
+    /// [`Self::with_language_detection`])
+    language_detection: bool,
+    /// Analyzer of a per-language model; `None` tokenizes on whitespace
+    language: Option<Language>,
+    /// Per-language models, built by [`Self::build_vocabulary`] when
+    /// language detection is on
+    language_models: HashMap<Language, TfIdfEmbedding>,
 }
 impl TfIdfEmbedding {
     /// Create a new TF-IDF embedding provider
@@ -25,9 +34,38 @@ impl TfIdfEmbedding {
             dimension,
             vocabulary: HashMap::new(),
             idf_weights: vec![1.0; dimension],
+            language_detection: false,
+            language: None,
+            language_models: HashMap::new(),
         }
     }
 
+    /// Detect the language of documents and queries, like
+    /// [`crate::embedding::Bm25Embedding::with_language_detection`]
+    pub fn with_language_detection(mut self, enabled: bool) -> Self {
+        self.language_detection = enabled;
+        self
+    }
+
+    /// Mutating variant of [`Self::with_language_detection`].
+    pub fn set_language_detection(&mut self, enabled: bool) {
+        self.language_detection = enabled;
+    }
+
+    /// Languages with a vocabulary of their own
+    pub fn languages(&self) -> Vec<Language> {
+        let mut languages: Vec<Language> = self.language_models.keys().copied().collect();
+        languages.sort();
+        languages
+    }
+
+    /// An empty model analyzing text as `language`
+    fn for_language(&self, language: Language) -> Self {
+        let mut model = Self::new(self.dimension);
+        model.language = Some(language);
+        model
+    }
+
     /// Build vocabulary from a corpus of texts
     pub fn build_vocabulary(&mut self, texts: &[&str]) {
         let mut word_counts: HashMap<String, usize> = HashMap::new();
@@ -82,9 +120,27 @@ impl TfIdfEmbedding {
             let idf = (total_docs / df).ln().max(0.0);
             self.idf_weights.push(idf);
         }
+
+        self.language_models.clear();
+        if self.language_detection {
+            let mut by_language: HashMap<Language, Vec<&str>> = HashMap::new();
+            for text in texts {
+                if let Some(language) = language::detect(text) {
+                    by_language.entry(language).or_default().push(*text);
+                }
+            }
+            for (language, texts) in by_language {
+                let mut model = self.for_language(language);
+                model.build_vocabulary(&texts);
+                self.language_models.insert(language, model);
+            }
+        }
     }
 
     fn tokenize(&self, text: &str) -> Vec<String> {
+        if let Some(language) = self.language {
+            return language::analyze(text, language);
+        }
         text.to_lowercase()
             .split_whitespace()
             .filter(|w| w.len() > 2)
@@ -115,6 +171,12 @@ impl EmbeddingProvider for TfIdfEmbedding {
     }
 
     fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        if let Some(model) = language::route(text, &self.language_models, |model, term| {
+            model.vocabulary.contains_key(term)
+        }) {
+            return model.embed(text);
+        }
+
         let tf = self.compute_tf(text);
         let mut embedding = vec![0.0; self.dimension];
 
@@ -132,6 +194,8 @@ impl EmbeddingProvider for TfIdfEmbedding {
         // Check if embedding is all zeros (fallback to hash-based embedding)
         let non_zero_count = embedding.iter().filter(|&&x| x != 0.0).count();
         if non_zero_count == 0 {
+            crate::monitoring::metrics::METRICS
+                .record_embedding_fallback("tfidf", self.vocabulary.is_empty());
             return Ok(self.fallback_hash_embedding(text));
         }
 
@@ -197,12 +261,26 @@ impl TfIdfEmbedding {
 
     /// Save TF-IDF vocabulary/tokenizer JSON
     pub fn save_vocabulary_json<P: AsRef<std::path::Path>>(&self, path: P) -> Result<()> {
-        let data = serde_json::json!({
+        let mut data = serde_json::json!({
             "type": "tfidf",
             "dimension": self.dimension,
             "vocabulary": self.vocabulary,
             "idf_weights": self.idf_weights,
         });
+        if self.language_detection {
+            let languages: serde_json::Map<String, serde_json::Value> = self
+                .language_models
+                .iter()
+                .map(|(language, model)| {
+                    let weights = serde_json::json!({
+                        "vocabulary": model.vocabulary,
+                        "idf_weights": model.idf_weights,
+                    });
+                    (language.code().to_string(), weights)
+                })
+                .collect();
+            data["languages"] = serde_json::Value::Object(languages);
+        }
         let json = serde_json::to_string_pretty(&data).map_err(|e| {
             VectorizerError::Other(format!("Failed to serialize TF-IDF vocab: {}", e))
         })?;
@@ -251,6 +329,23 @@ impl TfIdfEmbedding {
             .get("idf_weights")
             .and_then(|x| serde_json::from_value(x.clone()).ok())
             .unwrap_or_default();
+
+        // Per-language vocabularies, saved when language detection is on
+        self.language_models.clear();
+        if let Some(languages) = v.get("languages").and_then(|x| x.as_object()) {
+            self.language_detection = true;
+            for (code, weights) in languages {
+                let Some(language) = Language::from_code(code) else {
+                    continue;
+                };
+                let field = |name: &str| weights.get(name).cloned().unwrap_or_default();
+                let mut model = self.for_language(language);
+                model.vocabulary = serde_json::from_value(field("vocabulary")).unwrap_or_default();
+                model.idf_weights =
+                    serde_json::from_value(field("idf_weights")).unwrap_or_default();
+                self.language_models.insert(language, model);
+            }
+        }
         Ok(())
     }
 }
@@ -0,0 +1,414 @@
+//! Recall benchmark of the HNSW search against exact search.
+//!
+//! [`benchmark_recall`] samples stored vectors of a collection as
+//! queries, finds their true nearest neighbours once by exact
+//! (brute-force) search, then runs them through the graph search at
+//! every `ef_search` of the sweep and reports the recall@k and latency
+//! percentiles of each. As in the collection's recall profile (see
+//! `Collection::measure_recall_profile`), a query's own vector is dropped
+//! from both result lists, so each sampled vector acts as a held-out
+//! query against the rest of the collection.
+//!
+//! Nothing is stored: the benchmark is for choosing `ef_search` and `M`
+//! (`tuning.ef_search`, `POST /collections/{name}/reindex`), whose
+//! effect the recall profile then tracks.
+
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tracing::info_span;
+
+use crate::db::{Collection, CollectionType, SearchDeadline, VectorStore, with_ef_search};
+use crate::error::{Result, VectorizerError};
+use crate::models::QuantizationConfig;
+
+/// Queries sampled by default.
+pub const DEFAULT_BENCHMARK_SAMPLE_SIZE: usize = 100;
+
+/// Neighbours compared per query by default.
+pub const DEFAULT_BENCHMARK_K: usize = 10;
+
+/// Largest sample a benchmark may take.
+pub const MAX_BENCHMARK_SAMPLE_SIZE: usize = 1_000;
+
+/// Largest `k` a benchmark accepts.
+pub const MAX_BENCHMARK_K: usize = 100;
+
+/// Most `ef_search` values one sweep may try.
+pub const MAX_EF_SEARCH_SWEEP: usize = 16;
+
+/// What to benchmark.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BenchmarkConfig {
+    /// Stored vectors sampled as queries, spread evenly over insertion
+    /// order. Defaults to 100.
+    #[serde(default = "BenchmarkConfig::default_sample_size")]
+    pub sample_size: usize,
+    /// Neighbours compared per query. Defaults to 10.
+    #[serde(default = "BenchmarkConfig::default_k")]
+    pub k: usize,
+    /// Search beams to try, each widened to `k` as in regular searches.
+    /// Empty measures the beam searches use today.
+    #[serde(default)]
+    pub ef_search: Vec<usize>,
+}
+
+impl BenchmarkConfig {
+    fn default_sample_size() -> usize {
+        DEFAULT_BENCHMARK_SAMPLE_SIZE
+    }
+
+    fn default_k() -> usize {
+        DEFAULT_BENCHMARK_K
+    }
+
+    /// Check the limits of the sample, `k` and the sweep.
+    ///
+    /// # Errors
+    ///
+    /// [`VectorizerError::InvalidConfiguration`] naming the first value
+    /// out of range.
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |message: String| Err(VectorizerError::InvalidConfiguration { message });
+        if !(1..=MAX_BENCHMARK_SAMPLE_SIZE).contains(&self.sample_size) {
+            return invalid(format!(
+                "sample_size must be between 1 and {}",
+                MAX_BENCHMARK_SAMPLE_SIZE
+            ));
+        }
+        if !(1..=MAX_BENCHMARK_K).contains(&self.k) {
+            return invalid(format!("k must be between 1 and {}", MAX_BENCHMARK_K));
+        }
+        if self.ef_search.len() > MAX_EF_SEARCH_SWEEP {
+            return invalid(format!(
+                "at most {} ef_search values per sweep",
+                MAX_EF_SEARCH_SWEEP
+            ));
+        }
+        if self.ef_search.contains(&0) {
+            return invalid("ef_search values must be at least 1".to_string());
+        }
+        Ok(())
+    }
+}
+
+impl Default for BenchmarkConfig {
+    fn default() -> Self {
+        Self {
+            sample_size: Self::default_sample_size(),
+            k: Self::default_k(),
+            ef_search: Vec::new(),
+        }
+    }
+}
+
+/// Latency distribution of one search path, in milliseconds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct LatencyPercentiles {
+    /// Mean latency.
+    pub mean_ms: f64,
+    /// Median latency.
+    pub p50_ms: f64,
+    /// 90th-percentile latency.
+    pub p90_ms: f64,
+    /// 95th-percentile latency.
+    pub p95_ms: f64,
+    /// 99th-percentile latency.
+    pub p99_ms: f64,
+    /// Slowest query.
+    pub max_ms: f64,
+}
+
+impl LatencyPercentiles {
+    fn from_samples(samples: &mut [Duration]) -> Self {
+        let Some(&slowest) = samples.iter().max() else {
+            return Self::default();
+        };
+        samples.sort_unstable();
+        let ms = |d: Duration| d.as_secs_f64() * 1000.0;
+        let at = |q: f64| ms(samples[((samples.len() - 1) as f64 * q).round() as usize]);
+        Self {
+            mean_ms: samples.iter().copied().map(ms).sum::<f64>() / samples.len() as f64,
+            p50_ms: at(0.5),
+            p90_ms: at(0.9),
+            p95_ms: at(0.95),
+            p99_ms: at(0.99),
+            max_ms: ms(slowest),
+        }
+    }
+}
+
+/// Recall and latency of the graph search at one beam width.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EfSearchRun {
+    /// Beam the searches used; `None` for binary-quantized collections,
+    /// which search by Hamming distance instead.
+    pub ef_search: Option<usize>,
+    /// Mean recall@k against exact search.
+    pub recall_at_k: f64,
+    /// Lowest recall@k of any query.
+    pub min_recall_at_k: f64,
+    /// Graph search latency.
+    pub latency: LatencyPercentiles,
+}
+
+/// Result of [`benchmark_recall`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecallBenchmark {
+    /// Benchmarked collection.
+    pub collection: String,
+    /// When the benchmark ran.
+    pub measured_at: chrono::DateTime<chrono::Utc>,
+    /// Vectors in the collection at the time.
+    pub vector_count: usize,
+    /// Queries measured.
+    pub sample_size: usize,
+    /// Neighbours compared per query.
+    pub k: usize,
+    /// HNSW `M` of the graph.
+    pub m: usize,
+    /// HNSW `ef_construction` of the graph.
+    pub ef_construction: usize,
+    /// Quantization in use, e.g. `SQ { bits: 8 }`.
+    pub quantization: String,
+    /// Exact search latency.
+    pub exact_latency: LatencyPercentiles,
+    /// One run per `ef_search` of the sweep, in the order given.
+    pub runs: Vec<EfSearchRun>,
+}
+
+/// Benchmark the graph search of `collection_name` against exact search
+/// as configured by `config`. Only CPU collections can be benchmarked.
+///
+/// # Errors
+///
+/// Fails when `config` is out of range, when the collection does not
+/// exist, is not a CPU collection or holds `k` vectors or fewer, when an
+/// `ef_search` sweep is asked of a binary-quantized collection, or when a
+/// search fails.
+pub fn benchmark_recall(
+    store: &VectorStore,
+    collection_name: &str,
+    config: &BenchmarkConfig,
+) -> Result<RecallBenchmark> {
+    config.validate()?;
+    let coll_ref = store.get_collection(collection_name)?;
+    let CollectionType::Cpu(collection) = &*coll_ref else {
+        return Err(VectorizerError::Storage(
+            "recall benchmarks only run on CPU collections".to_string(),
+        ));
+    };
+    benchmark_collection(collection, config)
+}
+
+fn benchmark_collection(
+    collection: &Collection,
+    config: &BenchmarkConfig,
+) -> Result<RecallBenchmark> {
+    if !config.ef_search.is_empty()
+        && matches!(collection.config().quantization, QuantizationConfig::Binary)
+    {
+        return Err(VectorizerError::InvalidConfiguration {
+            message: format!(
+                "collection '{}' is binary-quantized and searches by Hamming distance; \
+                 ef_search has no effect on it",
+                collection.name()
+            ),
+        });
+    }
+    let k = config.k;
+    // Groups the benchmark's searches into one trace
+    let _span = info_span!(
+        "recall_benchmark.run",
+        collection = %collection.name(),
+        sample_size = config.sample_size,
+        k,
+        sweep = config.ef_search.len()
+    )
+    .entered();
+
+    // Ground truth first, so every beam is scored on the same queries
+    let mut queries = Vec::with_capacity(config.sample_size);
+    let mut exact_latency = Vec::with_capacity(config.sample_size);
+    for id in collection.sample_vector_ids(config.sample_size, k)? {
+        let Ok(query) = collection.get_vector(&id) else {
+            continue;
+        };
+        let started = Instant::now();
+        let exact = collection.search_exact(&query.data, k + 1)?;
+        exact_latency.push(started.elapsed());
+        let truth: HashSet<String> = exact
+            .into_iter()
+            .filter(|r| r.id != id)
+            .take(k)
+            .map(|r| r.id)
+            .collect();
+        if !truth.is_empty() {
+            queries.push((id, query.data, truth));
+        }
+    }
+
+    let sweep: Vec<Option<usize>> = if config.ef_search.is_empty() {
+        vec![None]
+    } else {
+        config.ef_search.iter().copied().map(Some).collect()
+    };
+    let mut runs = Vec::with_capacity(sweep.len());
+    for ef_search in sweep {
+        let mut recalls = Vec::with_capacity(queries.len());
+        let mut latency = Vec::with_capacity(queries.len());
+        let mut beam = None;
+        for (id, query, truth) in &queries {
+            let search = || collection.search_until(query, k + 1, &SearchDeadline::NONE);
+            let started = Instant::now();
+            let approx = match ef_search {
+                Some(ef_search) => with_ef_search(ef_search, search),
+                None => search(),
+            }?;
+            latency.push(started.elapsed());
+            beam = beam.or(approx.stats.map(|s| s.ef_search));
+
+            let hits = approx
+                .results
+                .iter()
+                .filter(|r| r.id != *id)
+                .take(k)
+                .filter(|r| truth.contains(&r.id))
+                .count();
+            recalls.push(hits as f64 / truth.len() as f64);
+        }
+        let (recall_at_k, min_recall_at_k) = if recalls.is_empty() {
+            (0.0, 0.0)
+        } else {
+            (
+                recalls.iter().sum::<f64>() / recalls.len() as f64,
+                recalls.iter().copied().fold(1.0, f64::min),
+            )
+        };
+        runs.push(EfSearchRun {
+            ef_search: beam,
+            recall_at_k,
+            min_recall_at_k,
+            latency: LatencyPercentiles::from_samples(&mut latency),
+        });
+    }
+
+    let (m, ef_construction) = collection.graph_params();
+    Ok(RecallBenchmark {
+        collection: collection.name().to_string(),
+        measured_at: chrono::Utc::now(),
+        vector_count: collection.vector_count(),
+        sample_size: queries.len(),
+        k,
+        m,
+        ef_construction,
+        quantization: format!("{:?}", collection.config().quantization),
+        exact_latency: LatencyPercentiles::from_samples(&mut exact_latency),
+        runs,
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::models::{CollectionConfig, DistanceMetric, HnswConfig, Vector};
+
+    fn collection(vectors: usize) -> Collection {
+        let config = CollectionConfig {
+            graph: None,
+            sharding: None,
+            dimension: 8,
+            metric: DistanceMetric::Euclidean,
+            hnsw_config: HnswConfig::default(),
+            quantization: QuantizationConfig::None,
+            compression: Default::default(),
+            embedding_provider: "bm25".to_string(),
+            normalization: None,
+            encryption: None,
+            id_policy: None,
+            quota: None,
+            scoring: None,
+            tenant_partition: None,
+            dedup: None,
+            chunking: None,
+            storage_type: Some(crate::models::StorageType::Memory),
+            full_text: None,
+        };
+        let collection = Collection::new("bench".to_string(), config);
+        for i in 0..vectors {
+            let x = i as f32;
+            let data = (0..8)
+                .map(|d| (x * (0.13 + d as f32 * 0.07)).sin())
+                .collect();
+            collection
+                .insert(Vector::new(format!("v{i}"), data))
+                .unwrap();
+        }
+        collection
+    }
+
+    #[test]
+    fn sweep_reports_one_run_per_beam() {
+        let collection = collection(300);
+        let config = BenchmarkConfig {
+            sample_size: 30,
+            k: 5,
+            ef_search: vec![8, 200],
+        };
+        let report = benchmark_collection(&collection, &config).unwrap();
+
+        assert_eq!(report.vector_count, 300);
+        assert_eq!(report.sample_size, 30);
+        assert_eq!(report.k, 5);
+        let beams: Vec<_> = report.runs.iter().map(|r| r.ef_search).collect();
+        assert_eq!(beams, vec![Some(8), Some(200)]);
+        for run in &report.runs {
+            assert!((0.0..=1.0).contains(&run.recall_at_k));
+            assert!(run.min_recall_at_k <= run.recall_at_k);
+            assert!(run.latency.p50_ms <= run.latency.p99_ms);
+            assert!(run.latency.p99_ms <= run.latency.max_ms);
+        }
+        // A beam far wider than k finds (nearly) every true neighbour
+        assert!(
+            report.runs[1].recall_at_k > 0.9,
+            "recall {}",
+            report.runs[1].recall_at_k
+        );
+        assert!(report.runs[1].recall_at_k >= report.runs[0].recall_at_k);
+    }
+
+    #[test]
+    fn empty_sweep_measures_the_current_beam() {
+        let report = benchmark_collection(&collection(100), &BenchmarkConfig::default()).unwrap();
+        assert_eq!(report.runs.len(), 1);
+        assert!(report.runs[0].ef_search.is_some());
+    }
+
+    #[test]
+    fn rejects_out_of_range_config_and_small_collections() {
+        let invalid = [
+            BenchmarkConfig {
+                sample_size: 0,
+                ..Default::default()
+            },
+            BenchmarkConfig {
+                k: MAX_BENCHMARK_K + 1,
+                ..Default::default()
+            },
+            BenchmarkConfig {
+                ef_search: vec![0],
+                ..Default::default()
+            },
+            BenchmarkConfig {
+                ef_search: vec![16; MAX_EF_SEARCH_SWEEP + 1],
+                ..Default::default()
+            },
+        ];
+        for config in invalid {
+            assert!(config.validate().is_err(), "{config:?}");
+        }
+        assert!(benchmark_collection(&collection(5), &BenchmarkConfig::default()).is_err());
+    }
+}
@@ -0,0 +1,357 @@
+//! Hybrid search weight tuning from relevance judgements.
+//!
+//! [`tune_hybrid_weights`] fits a collection's fusion weights to the
+//! judgements recorded on it (see `Collection::record_hybrid_judgments`).
+//! Every judged query runs once through both legs of hybrid search — the
+//! dense leg over its embedding and, when the judgements carry a
+//! `query_sparse`, the sparse leg — and the legs are then fused with each
+//! algorithm at every `alpha` on a grid of [`ALPHA_STEPS`] steps. The
+//! pair with the best mean nDCG@k over the judged queries becomes the
+//! collection's tuned weights; the defaults are kept unless some pair
+//! beats them.
+//!
+//! Runs are reported to the
+//! [`HYBRID_TUNING`](crate::background_tasks::HYBRID_TUNING) task, and
+//! none starts while that task is paused.
+
+use std::collections::HashSet;
+
+use tracing::info;
+
+use super::{QueryResult, ndcg_at_k};
+use crate::background_tasks::{self, TASKS};
+use crate::db::hybrid_search::{self, DenseSearchResult, SparseSearchResult};
+use crate::db::{
+    CollectionType, FeedbackLabel, HybridJudgment, HybridScoringAlgorithm, HybridSearchConfig,
+    TunedHybridWeights, VectorStore,
+};
+use crate::error::{Result, VectorizerError};
+use crate::models::SparseVector;
+
+/// Cut-off of the nDCG the weights are fitted on by default.
+pub const DEFAULT_TUNING_K: usize = 10;
+
+/// Largest nDCG cut-off a fit accepts.
+pub const MAX_TUNING_K: usize = 100;
+
+/// Candidates fetched from each leg per judged query.
+const CANDIDATES: usize = 100;
+
+/// Steps of the `alpha` grid between 0.0 and 1.0.
+pub const ALPHA_STEPS: usize = 20;
+
+/// Algorithms tried by a fit.
+const ALGORITHMS: [HybridScoringAlgorithm; 3] = [
+    HybridScoringAlgorithm::ReciprocalRankFusion,
+    HybridScoringAlgorithm::WeightedCombination,
+    HybridScoringAlgorithm::AlphaBlending,
+];
+
+/// One judged query with the candidates of both legs.
+#[derive(Debug, Clone)]
+pub struct JudgedQuery {
+    /// Dense-leg candidates, best first.
+    pub dense: Vec<DenseSearchResult>,
+    /// Sparse-leg candidates, best first; empty without a sparse query.
+    pub sparse: Vec<SparseSearchResult>,
+    /// Results judged relevant.
+    pub relevant: HashSet<String>,
+}
+
+/// Judgements of one query: its text, sparse query and relevant results.
+struct QueryJudgments<'a> {
+    query: &'a str,
+    query_sparse: Option<&'a SparseVector>,
+    relevant: HashSet<String>,
+}
+
+/// Group `judgments` by query, keeping the queries with at least one
+/// relevant result. Each (query, result) pair is judged once, so the
+/// label recorded is the latest.
+fn group_by_query(judgments: &[HybridJudgment]) -> Vec<QueryJudgments<'_>> {
+    let mut groups: Vec<QueryJudgments<'_>> = Vec::new();
+    for judgment in judgments {
+        let index = match groups.iter().position(|g| {
+            g.query == judgment.query && g.query_sparse == judgment.query_sparse.as_ref()
+        }) {
+            Some(index) => index,
+            None => {
+                groups.push(QueryJudgments {
+                    query: &judgment.query,
+                    query_sparse: judgment.query_sparse.as_ref(),
+                    relevant: HashSet::new(),
+                });
+                groups.len() - 1
+            }
+        };
+        if judgment.label == FeedbackLabel::Positive {
+            groups[index].relevant.insert(judgment.result_id.clone());
+        }
+    }
+    groups.retain(|g| !g.relevant.is_empty());
+    groups
+}
+
+/// Mean nDCG@`k` of `queries` fused with `algorithm` and `alpha`.
+fn mean_ndcg(
+    queries: &[JudgedQuery],
+    algorithm: HybridScoringAlgorithm,
+    alpha: f32,
+    k: usize,
+) -> f32 {
+    let config = HybridSearchConfig {
+        alpha,
+        dense_k: CANDIDATES,
+        sparse_k: CANDIDATES,
+        final_k: k,
+        algorithm,
+    };
+    let total: f32 = queries
+        .iter()
+        .map(|query| {
+            let results: Vec<QueryResult> =
+                hybrid_search::fuse(query.dense.clone(), query.sparse.clone(), &config)
+                    .into_iter()
+                    .map(|fused| QueryResult {
+                        relevance: if query.relevant.contains(&fused.id) {
+                            1.0
+                        } else {
+                            0.0
+                        },
+                        doc_id: fused.id,
+                    })
+                    .collect();
+            ndcg_at_k(&results, &query.relevant, k)
+        })
+        .sum();
+    total / queries.len() as f32
+}
+
+/// Grid-search the algorithm and `alpha` with the best mean nDCG@`k`
+/// over `queries`, keeping the defaults unless a pair beats them.
+/// `judgments` is the number of judgements behind `queries`.
+///
+/// # Errors
+///
+/// [`VectorizerError::InvalidConfiguration`] when `queries` is empty or
+/// `k` is outside `1..=MAX_TUNING_K`.
+pub fn fit_hybrid_weights(
+    queries: &[JudgedQuery],
+    k: usize,
+    judgments: usize,
+) -> Result<TunedHybridWeights> {
+    if queries.is_empty() {
+        return Err(VectorizerError::InvalidConfiguration {
+            message: "no judged query has a relevant result to tune against".to_string(),
+        });
+    }
+    if !(1..=MAX_TUNING_K).contains(&k) {
+        return Err(VectorizerError::InvalidConfiguration {
+            message: format!("k must be between 1 and {MAX_TUNING_K}, got {k}"),
+        });
+    }
+
+    let defaults = HybridSearchConfig::default();
+    let baseline = mean_ndcg(queries, defaults.algorithm, defaults.alpha, k);
+    let mut best = (defaults.algorithm, defaults.alpha, baseline);
+    for algorithm in ALGORITHMS {
+        for step in 0..=ALPHA_STEPS {
+            let alpha = step as f32 / ALPHA_STEPS as f32;
+            let ndcg = mean_ndcg(queries, algorithm, alpha, k);
+            if ndcg > best.2 + f32::EPSILON {
+                best = (algorithm, alpha, ndcg);
+            }
+        }
+    }
+
+    let (algorithm, alpha, ndcg) = best;
+    Ok(TunedHybridWeights {
+        algorithm,
+        alpha,
+        k,
+        ndcg,
+        baseline_ndcg: baseline,
+        queries: queries.len(),
+        judgments,
+        tuned_at: chrono::Utc::now(),
+    })
+}
+
+/// Fit the fusion weights of `collection_name` to its judgements and
+/// keep them on the collection. `embed` turns a query into the dense
+/// vector the collection is searched with.
+///
+/// # Errors
+///
+/// [`VectorizerError::TaskPaused`] while the tuning task is paused, and
+/// the errors of [`fit_hybrid_weights`], the embedding and the search.
+pub fn tune_hybrid_weights(
+    store: &VectorStore,
+    collection_name: &str,
+    k: usize,
+    embed: &dyn Fn(&str) -> Result<Vec<f32>>,
+) -> Result<TunedHybridWeights> {
+    let task = TASKS.register(background_tasks::HYBRID_TUNING);
+    if task.is_paused() {
+        return Err(VectorizerError::TaskPaused(
+            background_tasks::HYBRID_TUNING.to_string(),
+        ));
+    }
+    task.start();
+    let result = tune(store, collection_name, k, embed);
+    task.finish(&result);
+    result
+}
+
+fn tune(
+    store: &VectorStore,
+    collection_name: &str,
+    k: usize,
+    embed: &dyn Fn(&str) -> Result<Vec<f32>>,
+) -> Result<TunedHybridWeights> {
+    let judgments = match &*store.get_collection(collection_name)? {
+        CollectionType::Cpu(c) => c.hybrid_judgments(),
+        _ => {
+            return Err(VectorizerError::Storage(
+                "hybrid weights are only tuned on CPU collections".to_string(),
+            ));
+        }
+    };
+    let groups = group_by_query(&judgments);
+
+    // Embed before borrowing the collection again, so a slow embedding
+    // provider does not hold it
+    let embedded = groups
+        .iter()
+        .map(|group| embed(group.query))
+        .collect::<Result<Vec<_>>>()?;
+
+    let coll_ref = store.get_collection(collection_name)?;
+    let CollectionType::Cpu(collection) = &*coll_ref else {
+        return Err(VectorizerError::Storage(
+            "hybrid weights are only tuned on CPU collections".to_string(),
+        ));
+    };
+    let mut queries = Vec::with_capacity(groups.len());
+    for (group, query_dense) in groups.into_iter().zip(embedded) {
+        let dense = collection
+            .search(&query_dense, CANDIDATES)?
+            .into_iter()
+            .map(|r| DenseSearchResult {
+                id: r.id,
+                score: r.score,
+            })
+            .collect();
+        let sparse = group
+            .query_sparse
+            .map(|query_sparse| collection.sparse_search(query_sparse, CANDIDATES))
+            .unwrap_or_default();
+        queries.push(JudgedQuery {
+            dense,
+            sparse,
+            relevant: group.relevant,
+        });
+    }
+
+    let weights = fit_hybrid_weights(&queries, k, judgments.len())?;
+    collection.set_tuned_hybrid_weights(Some(weights.clone()));
+    info!(
+        "Tuned hybrid weights of '{}' on {} queries: {:?} alpha={} (nDCG@{} {:.3}, defaults {:.3})",
+        collection_name,
+        weights.queries,
+        weights.algorithm,
+        weights.alpha,
+        k,
+        weights.ndcg,
+        weights.baseline_ndcg
+    );
+    Ok(weights)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn dense(ids: &[&str]) -> Vec<DenseSearchResult> {
+        ids.iter()
+            .enumerate()
+            .map(|(rank, id)| DenseSearchResult {
+                id: id.to_string(),
+                score: 1.0 - rank as f32 * 0.1,
+            })
+            .collect()
+    }
+
+    fn sparse(ids: &[&str]) -> Vec<SparseSearchResult> {
+        ids.iter()
+            .enumerate()
+            .map(|(rank, id)| SparseSearchResult {
+                id: id.to_string(),
+                score: 1.0 - rank as f32 * 0.1,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn fit_moves_weight_to_the_leg_that_ranks_relevant_results_first() {
+        // The sparse leg puts the relevant result first, the dense leg last
+        let queries: Vec<JudgedQuery> = (0..3)
+            .map(|i| {
+                let hit = format!("hit{i}");
+                JudgedQuery {
+                    dense: dense(&["a", "b", "c", &hit]),
+                    sparse: sparse(&[&hit, "a", "b", "c"]),
+                    relevant: HashSet::from([hit]),
+                }
+            })
+            .collect();
+
+        let weights = fit_hybrid_weights(&queries, 3, 3).unwrap();
+        assert!(weights.ndcg > weights.baseline_ndcg);
+        assert!(weights.alpha < 0.5);
+        assert_eq!(weights.ndcg, 1.0);
+        assert_eq!(weights.queries, 3);
+    }
+
+    #[test]
+    fn fit_keeps_defaults_when_nothing_beats_them() {
+        let queries = vec![JudgedQuery {
+            dense: dense(&["hit", "a"]),
+            sparse: Vec::new(),
+            relevant: HashSet::from(["hit".to_string()]),
+        }];
+
+        let weights = fit_hybrid_weights(&queries, 10, 1).unwrap();
+        let defaults = HybridSearchConfig::default();
+        assert_eq!(weights.algorithm, defaults.algorithm);
+        assert_eq!(weights.alpha, defaults.alpha);
+        assert_eq!(weights.ndcg, weights.baseline_ndcg);
+    }
+
+    #[test]
+    fn fit_needs_a_judged_query() {
+        assert!(fit_hybrid_weights(&[], 10, 0).is_err());
+    }
+
+    #[test]
+    fn queries_without_a_relevant_result_are_skipped() {
+        let judgment = |query: &str, result_id: &str, label| HybridJudgment {
+            query: query.to_string(),
+            query_sparse: None,
+            result_id: result_id.to_string(),
+            label,
+            recorded_at: chrono::Utc::now(),
+        };
+        let judgments = vec![
+            judgment("q1", "a", FeedbackLabel::Positive),
+            judgment("q1", "b", FeedbackLabel::Negative),
+            judgment("q2", "a", FeedbackLabel::Negative),
+        ];
+
+        let groups = group_by_query(&judgments);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].query, "q1");
+        assert_eq!(groups[0].relevant, HashSet::from(["a".to_string()]));
+    }
+}
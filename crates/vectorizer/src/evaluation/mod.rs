@@ -5,6 +5,9 @@
 
 use std::collections::HashSet;
 
+pub mod benchmark;
+pub mod hybrid_tuning;
+
 /// Represents a single query result with its relevance
 #[derive(Debug, Clone)]
 pub struct QueryResult {
@@ -175,6 +178,26 @@ pub fn recall_at_k(results: &[QueryResult], ground_truth: &HashSet<String>, k: u
     relevant_in_top_k as f32 / total_relevant as f32
 }
 
+/// Calculate nDCG@K with binary relevance: the discounted gain of the
+/// relevant documents in the top `k`, over that of an ideal ranking
+pub fn ndcg_at_k(results: &[QueryResult], ground_truth: &HashSet<String>, k: usize) -> f32 {
+    let discount = |rank: usize| 1.0 / (rank as f32 + 2.0).log2();
+    let ideal: f32 = (0..ground_truth.len().min(k)).map(discount).sum();
+    if ideal == 0.0 {
+        return 0.0;
+    }
+
+    let dcg: f32 = results
+        .iter()
+        .take(k)
+        .enumerate()
+        .filter(|(_, result)| ground_truth.contains(&result.doc_id))
+        .map(|(rank, _)| discount(rank))
+        .sum();
+
+    dcg / ideal
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -227,6 +250,26 @@ mod tests {
         assert_eq!(recall_at_k(&results, &ground_truth, 3), 1.0); // all relevant docs found
     }
 
+    #[test]
+    fn test_ndcg_at_k() {
+        let results: Vec<QueryResult> = ["doc1", "doc2", "doc3"]
+            .iter()
+            .map(|id| QueryResult {
+                doc_id: id.to_string(),
+                relevance: 0.0,
+            })
+            .collect();
+
+        let first = HashSet::from(["doc1".to_string()]);
+        let second = HashSet::from(["doc2".to_string()]);
+        let missing = HashSet::from(["doc9".to_string()]);
+
+        assert_eq!(ndcg_at_k(&results, &first, 3), 1.0);
+        assert!((ndcg_at_k(&results, &second, 3) - 1.0 / 3f32.log2()).abs() < 1e-6);
+        assert_eq!(ndcg_at_k(&results, &missing, 3), 0.0);
+        assert_eq!(ndcg_at_k(&results, &first, 0), 0.0);
+    }
+
     #[test]
     fn test_mean_reciprocal_rank() {
         let ranks = vec![1.0, 0.5, 0.33]; // reciprocal ranks for positions 1, 2, 3
@@ -12,14 +12,27 @@ use std::path::{Path, PathBuf};
 use anyhow::Result;
 
 use super::config::{DocumentChunk, LoaderConfig};
+use super::manifest::{FILE_HASH_FIELD, content_hash};
+use crate::chunking::{self, ChunkingStrategy};
 
 pub struct Chunker {
     config: LoaderConfig,
+    strategy: ChunkingStrategy,
 }
 
 impl Chunker {
     pub fn new(config: LoaderConfig) -> Self {
-        Self { config }
+        Self::with_strategy(config, ChunkingStrategy::Fixed)
+    }
+
+    /// Chunker splitting with `strategy` (see [`crate::chunking`])
+    pub fn with_strategy(config: LoaderConfig, strategy: ChunkingStrategy) -> Self {
+        Self { config, strategy }
+    }
+
+    /// The strategy documents are split with
+    pub fn strategy(&self) -> ChunkingStrategy {
+        self.strategy
     }
 
     /// Split documents into chunks
@@ -36,94 +49,54 @@ impl Chunker {
 
     /// Split a single document into chunks
     pub fn chunk_text(&self, text: &str, file_path: &Path) -> Result<Vec<DocumentChunk>> {
-        let mut chunks = Vec::new();
-        let mut start = 0;
-        let mut chunk_index = 0;
-
-        while start < text.len() {
-            // Calculate the end position for this chunk
-            let mut end = std::cmp::min(start + self.config.max_chunk_size, text.len());
-
-            // If we're not at the end of the text, try to find a good break point
-            if end < text.len() {
-                // Ensure we're at a UTF-8 character boundary
-                while end > start && !text.is_char_boundary(end) {
-                    end -= 1;
-                }
-
-                // Try to break at a word boundary (whitespace, punctuation)
-                if let Some(pos) = text[start..end].rfind(|c: char| {
-                    c.is_whitespace() || c == '.' || c == '!' || c == '?' || c == '\n'
-                }) {
-                    end = start + pos + 1;
-
-                    // Ensure the new end is still at a UTF-8 character boundary
-                    while end > start && !text.is_char_boundary(end) {
-                        end -= 1;
-                    }
-                }
-            }
-
-            // Extract the chunk text
-            let chunk_text = text[start..end].trim();
-
-            // Only create a chunk if it has content
-            if !chunk_text.is_empty() {
-                let chunk_id = format!("{}#{}", file_path.to_string_lossy(), chunk_index);
-
-                let mut metadata = HashMap::new();
-                metadata.insert(
-                    "file_path".to_string(),
-                    serde_json::Value::String(file_path.to_string_lossy().to_string()),
-                );
-                metadata.insert(
-                    "chunk_index".to_string(),
-                    serde_json::Value::Number(chunk_index.into()),
-                );
-                metadata.insert(
-                    "file_extension".to_string(),
-                    serde_json::Value::String(
-                        file_path
-                            .extension()
-                            .and_then(|e| e.to_str())
-                            .unwrap_or("unknown")
-                            .to_string(),
-                    ),
-                );
-                metadata.insert(
-                    "chunk_size".to_string(),
-                    serde_json::Value::Number(chunk_text.len().into()),
-                );
-
-                chunks.push(DocumentChunk {
-                    id: chunk_id,
-                    content: chunk_text.to_string(),
-                    file_path: file_path.to_string_lossy().to_string(),
-                    chunk_index,
-                    metadata,
-                });
-
-                chunk_index += 1;
-            }
-
-            // Calculate the next start position with overlap
-            let next_start = if end >= self.config.chunk_overlap {
-                end - self.config.chunk_overlap
-            } else {
-                end
-            };
-
-            // Ensure we make progress (prevent infinite loop)
-            if next_start <= start {
-                start = end;
-            } else {
-                start = next_start;
-            }
-
-            // Ensure start is at a UTF-8 character boundary
-            while start < text.len() && !text.is_char_boundary(start) {
-                start += 1;
-            }
+        let file_hash = content_hash(text);
+        let pieces = chunking::chunk_text(
+            self.strategy,
+            text,
+            file_path,
+            self.config.max_chunk_size,
+            self.config.chunk_overlap,
+        );
+
+        let mut chunks = Vec::with_capacity(pieces.len());
+        for (chunk_index, chunk_text) in pieces.into_iter().enumerate() {
+            let chunk_id = format!("{}#{}", file_path.to_string_lossy(), chunk_index);
+
+            let mut metadata = HashMap::new();
+            metadata.insert(
+                "file_path".to_string(),
+                serde_json::Value::String(file_path.to_string_lossy().to_string()),
+            );
+            metadata.insert(
+                "chunk_index".to_string(),
+                serde_json::Value::Number(chunk_index.into()),
+            );
+            metadata.insert(
+                "file_extension".to_string(),
+                serde_json::Value::String(
+                    file_path
+                        .extension()
+                        .and_then(|e| e.to_str())
+                        .unwrap_or("unknown")
+                        .to_string(),
+                ),
+            );
+            metadata.insert(
+                "chunk_size".to_string(),
+                serde_json::Value::Number(chunk_text.len().into()),
+            );
+            metadata.insert(
+                FILE_HASH_FIELD.to_string(),
+                serde_json::Value::String(file_hash.clone()),
+            );
+
+            chunks.push(DocumentChunk {
+                id: chunk_id,
+                content: chunk_text.to_string(),
+                file_path: file_path.to_string_lossy().to_string(),
+                chunk_index,
+                metadata,
+            });
         }
 
         Ok(chunks)
@@ -248,6 +221,24 @@ mod tests {
         assert_eq!(chunk.metadata["chunk_index"], 0);
         assert_eq!(chunk.metadata["file_extension"], "rs");
         assert!(chunk.metadata.contains_key("chunk_size"));
+        assert_eq!(chunk.metadata["file_hash"], content_hash(text));
+    }
+
+    #[test]
+    fn test_chunk_with_strategy() {
+        let config = LoaderConfig {
+            max_chunk_size: 20,
+            ..create_test_config()
+        };
+        let chunker = Chunker::with_strategy(config, ChunkingStrategy::Markdown);
+
+        let text = "# One\nfirst\n\n# Two\nsecond\n";
+        let chunks = chunker.chunk_text(text, &PathBuf::from("/doc.md")).unwrap();
+
+        // Each heading section is its own chunk
+        let contents: Vec<&str> = chunks.iter().map(|c| c.content.as_str()).collect();
+        assert_eq!(contents, ["# One\nfirst", "# Two\nsecond"]);
+        assert_eq!(chunks[1].chunk_index, 1);
     }
 
     #[test]
@@ -1,16 +1,20 @@
 //! Thin wrapper for indexing using existing embedding infrastructure
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 use rayon::prelude::*;
 use tracing::{info, warn};
 
 use super::config::{DocumentChunk, LoaderConfig};
+use super::manifest::{CONTENT_HASH_FIELD, FILE_HASH_FIELD, KeptChunk, ReindexPlan, content_hash};
 use crate::{
     VectorStore,
+    chunking::{ChunkingConfig, ChunkingStrategy},
     db::BackpressureGuard,
     embedding::EmbeddingManager, // Use existing EmbeddingManager
+    error::VectorizerError,
+    language,
     models::{CollectionConfig, DistanceMetric, HnswConfig, Payload, QuantizationConfig, Vector},
 };
 
@@ -21,6 +25,10 @@ pub struct Indexer {
     /// Optional backpressure guard for the vocab-build path (issue #263).
     /// When `None`, builds run unbounded (legacy behavior).
     backpressure: Option<BackpressureGuard>,
+    /// Chunking strategy recorded on collections this indexer creates
+    chunking: Option<ChunkingStrategy>,
+    /// Detect each chunk's language (see [`Self::set_language_detection`])
+    language_detection: bool,
 }
 
 impl Indexer {
@@ -33,6 +41,8 @@ impl Indexer {
             config,
             embedding_manager,
             backpressure: None,
+            chunking: None,
+            language_detection: false,
         }
     }
 
@@ -49,6 +59,19 @@ impl Indexer {
         self.backpressure = Some(guard);
     }
 
+    /// Record `strategy` in the config of collections this indexer
+    /// creates, so later syncs split files the same way
+    pub fn set_chunking(&mut self, strategy: ChunkingStrategy) {
+        self.chunking = Some(strategy);
+    }
+
+    /// Record each chunk's detected language as `lang` in its payload,
+    /// and have BM25 and TF-IDF providers build a vocabulary per
+    /// language (see [`crate::language`])
+    pub fn set_language_detection(&mut self, enabled: bool) {
+        self.language_detection = enabled;
+    }
+
     /// Async wrapper around [`Self::build_vocabulary`] that acquires a
     /// permit from the configured [`BackpressureGuard`] before running
     /// the CPU-heavy vocabulary build. When no guard is attached, this
@@ -92,6 +115,7 @@ impl Indexer {
                 .as_any_mut()
                 .downcast_mut::<crate::embedding::Bm25Embedding>()
             {
+                bm25.set_language_detection(self.language_detection);
                 bm25.build_vocabulary(&texts);
                 info!(
                     "✅ Built BM25 vocabulary with {} documents, vocab size: {}",
@@ -104,6 +128,7 @@ impl Indexer {
             {
                 // TF-IDF expects &[&str]
                 let text_refs: Vec<&str> = texts.iter().map(|s| s.as_str()).collect();
+                tfidf.set_language_detection(self.language_detection);
                 tfidf.build_vocabulary(&text_refs);
                 info!("✅ Built TF-IDF vocabulary with {} documents", texts.len());
             } else if let Some(bow) = emb
@@ -159,6 +184,17 @@ impl Indexer {
             sharding: None,
             graph: None,
             encryption: None,
+            id_policy: None,
+            quota: None,
+            scoring: None,
+            tenant_partition: None,
+            dedup: None,
+            chunking: self.chunking.map(|strategy| ChunkingConfig {
+                strategy,
+                max_chunk_size: Some(self.config.max_chunk_size),
+                chunk_overlap: Some(self.config.chunk_overlap),
+            }),
+            full_text: None,
         };
 
         store
@@ -198,9 +234,15 @@ impl Indexer {
                                     "content": chunk.content,
                                     "file_path": chunk.file_path,
                                     "chunk_index": chunk.chunk_index,
+                                    CONTENT_HASH_FIELD: content_hash(&chunk.content),
                                     "metadata": chunk.metadata
                                 }),
                             };
+                            if self.language_detection
+                                && let Some(lang) = language::detect(&chunk.content)
+                            {
+                                payload.data[language::LANG_FIELD] = lang.code().into();
+                            }
                             payload.normalize();
 
                             Some(Vector {
@@ -228,6 +270,76 @@ impl Indexer {
         Ok(total_vectors)
     }
 
+    /// Apply a [`ReindexPlan`]: embed new and modified chunks, update
+    /// the position and file hash of kept ones and delete the vectors of
+    /// modified and removed chunks. Returns the number of chunks
+    /// embedded.
+    pub fn apply_plan(&self, store: &VectorStore, plan: &ReindexPlan) -> Result<usize> {
+        let embedded = self.store_chunks_parallel(store, &plan.embed)?;
+        for kept in &plan.keep {
+            self.update_kept_chunk(store, kept)?;
+        }
+
+        let collection_name = &self.config.collection_name;
+        for vector_id in &plan.delete {
+            match store.delete(collection_name, vector_id) {
+                Ok(()) | Err(VectorizerError::VectorNotFound(_)) => {}
+                Err(e) => {
+                    return Err(e).with_context(|| {
+                        format!(
+                            "Failed to delete chunk '{}' from '{}'",
+                            vector_id, collection_name
+                        )
+                    });
+                }
+            }
+        }
+        Ok(embedded)
+    }
+
+    /// Rewrite a kept chunk's position and file hash, leaving its
+    /// embedding alone
+    fn update_kept_chunk(&self, store: &VectorStore, kept: &KeptChunk) -> Result<()> {
+        let collection_name = &self.config.collection_name;
+        let mut vector = store.get_vector(collection_name, &kept.vector_id)?;
+        if let Some(serde_json::Value::Object(data)) = vector.payload.as_mut().map(|p| &mut p.data)
+        {
+            data.insert("chunk_index".to_string(), kept.chunk_index.into());
+            if let Some(serde_json::Value::Object(metadata)) = data.get_mut("metadata") {
+                metadata.insert("chunk_index".to_string(), kept.chunk_index.into());
+                metadata.insert(FILE_HASH_FIELD.to_string(), kept.file_hash.clone().into());
+            }
+        }
+        store.update(collection_name, vector)?;
+        Ok(())
+    }
+
+    /// Whether the configured provider embeds against a vocabulary built
+    /// from the documents (see [`Self::build_vocabulary`])
+    pub fn needs_vocabulary(&self) -> bool {
+        let Ok(provider) = self
+            .embedding_manager
+            .get_provider(&self.config.embedding_type)
+        else {
+            return false;
+        };
+        let any = provider.as_any();
+        any.is::<crate::embedding::Bm25Embedding>()
+            || any.is::<crate::embedding::TfIdfEmbedding>()
+            || any.is::<crate::embedding::BagOfWordsEmbedding>()
+            || any.is::<crate::embedding::CharNGramEmbedding>()
+    }
+
+    /// Restore the provider's vocabulary from a saved tokenizer
+    pub fn load_vocabulary(
+        &mut self,
+        path: &Path,
+        provider_name: &str,
+    ) -> crate::error::Result<()> {
+        self.embedding_manager
+            .load_vocabulary_json(provider_name, path)
+    }
+
     /// Save vocabulary/tokenizer for file watcher
     pub fn save_vocabulary(
         &self,
@@ -0,0 +1,393 @@
+//! Content-addressed manifest of an indexed project
+//!
+//! The loader keeps no manifest file of its own: every chunk it stores
+//! carries the hash of its text (`content_hash`) next to `file_path` and
+//! `chunk_index`, and the hash of its whole file (`metadata.file_hash`),
+//! so the collection persisted in the `.vecdb` is the manifest.
+//! [`IndexManifest::from_vectors`] reads it back and
+//! [`IndexManifest::plan`] diffs it against the files on disk: unchanged
+//! files are skipped, chunks whose text survived an edit are kept, and
+//! only new or modified chunks are embedded. Vectors of modified and
+//! removed chunks are deleted, so no orphans are left behind.
+
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use xxhash_rust::xxh3::xxh3_64;
+
+use super::config::DocumentChunk;
+use crate::models::Vector;
+
+/// Payload field holding the hash of a chunk's text
+pub const CONTENT_HASH_FIELD: &str = "content_hash";
+
+/// Chunk metadata field holding the hash of the chunk's file
+pub const FILE_HASH_FIELD: &str = "file_hash";
+
+/// Hash of a file or chunk text, in the format deduplication uses
+pub fn content_hash(text: &str) -> String {
+    format!("{:016x}", xxh3_64(text.as_bytes()))
+}
+
+/// A stored chunk of an indexed file
+#[derive(Debug, Clone)]
+pub struct ChunkEntry {
+    /// Vector holding the chunk
+    pub vector_id: String,
+    /// Position of the chunk within its file
+    pub chunk_index: usize,
+    /// Hash of the chunk's text; `None` for chunks indexed before
+    /// hashes were stored
+    pub content_hash: Option<String>,
+}
+
+/// The stored chunks of one file
+#[derive(Debug, Clone, Default)]
+pub struct FileEntry {
+    /// Hash of the file; `None` when its chunks disagree or predate
+    /// hashing
+    pub file_hash: Option<String>,
+    /// Chunks in the collection, in storage order
+    pub chunks: Vec<ChunkEntry>,
+}
+
+impl FileEntry {
+    /// Whether the stored chunks are exactly those of a file hashing to
+    /// `file_hash`: a chunk stored twice is left over from an earlier run
+    fn is_current(&self, file_hash: &str) -> bool {
+        let mut indices = HashSet::new();
+        self.file_hash.as_deref() == Some(file_hash)
+            && self.chunks.iter().all(|c| indices.insert(c.chunk_index))
+    }
+}
+
+/// A stored chunk whose text is unchanged after an edit of its file
+#[derive(Debug, Clone)]
+pub struct KeptChunk {
+    /// Vector holding the chunk
+    pub vector_id: String,
+    /// New position of the chunk within its file
+    pub chunk_index: usize,
+    /// New hash of the file
+    pub file_hash: String,
+}
+
+/// What has to change for a collection to match the files on disk
+#[derive(Debug, Default)]
+pub struct ReindexPlan {
+    /// New and modified chunks to embed
+    pub embed: Vec<DocumentChunk>,
+    /// Chunks to keep without re-embedding; only their position and
+    /// file hash are updated
+    pub keep: Vec<KeptChunk>,
+    /// Vectors of modified and removed chunks
+    pub delete: Vec<String>,
+    /// Files whose chunks are all current
+    pub unchanged_files: usize,
+    /// New and modified files
+    pub changed_files: usize,
+    /// Files indexed before but no longer on disk
+    pub removed_files: usize,
+}
+
+impl ReindexPlan {
+    /// Whether the collection already matches the files on disk
+    pub fn is_empty(&self) -> bool {
+        self.embed.is_empty() && self.keep.is_empty() && self.delete.is_empty()
+    }
+}
+
+/// Files indexed in a collection, read back from the chunk payloads
+#[derive(Debug, Clone, Default)]
+pub struct IndexManifest {
+    files: BTreeMap<String, FileEntry>,
+}
+
+impl IndexManifest {
+    /// Build the manifest from a collection's vectors. Vectors without
+    /// a `file_path` were not stored by the loader and are ignored.
+    pub fn from_vectors(vectors: &[Vector]) -> Self {
+        let mut files: BTreeMap<String, FileEntry> = BTreeMap::new();
+        for vector in vectors {
+            let Some(payload) = vector.payload.as_ref().filter(|p| !p.is_encrypted()) else {
+                continue;
+            };
+            let data = &payload.data;
+            let Some(file_path) = data.get("file_path").and_then(|p| p.as_str()) else {
+                continue;
+            };
+            let file_hash = data
+                .get("metadata")
+                .and_then(|m| m.get(FILE_HASH_FIELD))
+                .and_then(|h| h.as_str())
+                .map(str::to_string);
+            let chunk = ChunkEntry {
+                vector_id: vector.id.clone(),
+                chunk_index: data
+                    .get("chunk_index")
+                    .and_then(|i| i.as_u64())
+                    .and_then(|i| usize::try_from(i).ok())
+                    .unwrap_or(0),
+                content_hash: data
+                    .get(CONTENT_HASH_FIELD)
+                    .and_then(|h| h.as_str())
+                    .map(str::to_string),
+            };
+            match files.entry(file_path.to_string()) {
+                Entry::Vacant(entry) => {
+                    entry.insert(FileEntry {
+                        file_hash,
+                        chunks: vec![chunk],
+                    });
+                }
+                Entry::Occupied(mut entry) => {
+                    let entry = entry.get_mut();
+                    if entry.file_hash != file_hash {
+                        entry.file_hash = None;
+                    }
+                    entry.chunks.push(chunk);
+                }
+            }
+        }
+        Self { files }
+    }
+
+    /// Number of indexed files
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Whether no file is indexed
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// The stored chunks of `file_path`
+    pub fn file(&self, file_path: &str) -> Option<&FileEntry> {
+        self.files.get(file_path)
+    }
+
+    /// Drop every hash, so that [`Self::plan`] re-embeds all files and
+    /// deletes all of their stored chunks
+    pub fn forget_hashes(&mut self) {
+        for file in self.files.values_mut() {
+            file.file_hash = None;
+            for chunk in &mut file.chunks {
+                chunk.content_hash = None;
+            }
+        }
+    }
+
+    /// Diff the manifest against `documents`, the files found under
+    /// `root`. Only new and modified files are passed to `chunk`.
+    /// Indexed files under `root` that are not in `documents` count as
+    /// removed; files elsewhere are left alone.
+    pub fn plan<F>(
+        &self,
+        root: &Path,
+        documents: &[(PathBuf, String)],
+        mut chunk: F,
+    ) -> Result<ReindexPlan>
+    where
+        F: FnMut(&Path, &str) -> Result<Vec<DocumentChunk>>,
+    {
+        let mut plan = ReindexPlan::default();
+        let mut seen = HashSet::new();
+        for (path, content) in documents {
+            let file_path = path.to_string_lossy().to_string();
+            let file_hash = content_hash(content);
+            let stored = self.files.get(&file_path);
+            seen.insert(file_path);
+            if let Some(stored) = stored
+                && stored.is_current(&file_hash)
+            {
+                plan.unchanged_files += 1;
+                continue;
+            }
+            plan.changed_files += 1;
+
+            // Stored chunks by text hash; each is reused at most once
+            let mut reusable: HashMap<&str, Vec<&ChunkEntry>> = HashMap::new();
+            for entry in stored.map(|s| s.chunks.as_slice()).unwrap_or_default() {
+                match entry.content_hash.as_deref() {
+                    Some(hash) => reusable.entry(hash).or_default().push(entry),
+                    None => plan.delete.push(entry.vector_id.clone()),
+                }
+            }
+            for new_chunk in chunk(path, content)? {
+                let hash = content_hash(&new_chunk.content);
+                match reusable.get_mut(hash.as_str()).and_then(Vec::pop) {
+                    Some(old) => plan.keep.push(KeptChunk {
+                        vector_id: old.vector_id.clone(),
+                        chunk_index: new_chunk.chunk_index,
+                        file_hash: file_hash.clone(),
+                    }),
+                    None => plan.embed.push(new_chunk),
+                }
+            }
+            plan.delete.extend(
+                reusable
+                    .into_values()
+                    .flatten()
+                    .map(|entry| entry.vector_id.clone()),
+            );
+        }
+
+        for (file_path, stored) in &self.files {
+            if !seen.contains(file_path) && Path::new(file_path).starts_with(root) {
+                plan.removed_files += 1;
+                plan.delete
+                    .extend(stored.chunks.iter().map(|c| c.vector_id.clone()));
+            }
+        }
+        Ok(plan)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::models::Payload;
+
+    /// The chunks a file splits into: one per line
+    fn chunk_lines(path: &Path, content: &str) -> Result<Vec<DocumentChunk>> {
+        Ok(content
+            .lines()
+            .enumerate()
+            .map(|(chunk_index, line)| DocumentChunk {
+                id: format!("{}#{}", path.display(), chunk_index),
+                content: line.to_string(),
+                file_path: path.to_string_lossy().to_string(),
+                chunk_index,
+                metadata: HashMap::new(),
+            })
+            .collect())
+    }
+
+    /// The vectors the loader stores for `content`
+    fn indexed(path: &str, content: &str) -> Vec<Vector> {
+        let file_hash = content_hash(content);
+        chunk_lines(Path::new(path), content)
+            .unwrap()
+            .into_iter()
+            .map(|chunk| Vector {
+                id: format!("{}-{}", path, chunk.content),
+                data: vec![1.0, 0.0],
+                sparse: None,
+                payload: Some(Payload::new(serde_json::json!({
+                    "content": chunk.content,
+                    "file_path": path,
+                    "chunk_index": chunk.chunk_index,
+                    "content_hash": content_hash(&chunk.content),
+                    "metadata": {"file_hash": file_hash},
+                }))),
+                document_id: None,
+            })
+            .collect()
+    }
+
+    fn document(path: &str, content: &str) -> (PathBuf, String) {
+        (PathBuf::from(path), content.to_string())
+    }
+
+    #[test]
+    fn unchanged_files_are_skipped() {
+        let manifest = IndexManifest::from_vectors(&indexed("/p/a.md", "one\ntwo"));
+        assert_eq!(manifest.len(), 1);
+
+        let plan = manifest
+            .plan(
+                Path::new("/p"),
+                &[document("/p/a.md", "one\ntwo")],
+                |_, _| panic!("unchanged files are not chunked"),
+            )
+            .unwrap();
+        assert!(plan.is_empty());
+        assert_eq!(plan.unchanged_files, 1);
+    }
+
+    #[test]
+    fn only_modified_chunks_are_reembedded() {
+        let manifest = IndexManifest::from_vectors(&indexed("/p/a.md", "one\ntwo\nthree"));
+
+        let plan = manifest
+            .plan(
+                Path::new("/p"),
+                &[document("/p/a.md", "zero\none\nthree")],
+                chunk_lines,
+            )
+            .unwrap();
+        assert_eq!(plan.changed_files, 1);
+        let embedded: Vec<&str> = plan.embed.iter().map(|c| c.content.as_str()).collect();
+        assert_eq!(embedded, ["zero"]);
+        assert_eq!(plan.delete, ["/p/a.md-two"]);
+
+        let mut kept: Vec<(&str, usize)> = plan
+            .keep
+            .iter()
+            .map(|k| (k.vector_id.as_str(), k.chunk_index))
+            .collect();
+        kept.sort();
+        assert_eq!(kept, [("/p/a.md-one", 1), ("/p/a.md-three", 2)]);
+        let new_hash = content_hash("zero\none\nthree");
+        assert!(plan.keep.iter().all(|k| k.file_hash == new_hash));
+    }
+
+    #[test]
+    fn removed_files_under_the_root_are_deleted() {
+        let mut vectors = indexed("/p/a.md", "one");
+        vectors.extend(indexed("/p/gone.md", "two\nthree"));
+        vectors.extend(indexed("/other/b.md", "four"));
+        let manifest = IndexManifest::from_vectors(&vectors);
+
+        let plan = manifest
+            .plan(Path::new("/p"), &[document("/p/a.md", "one")], chunk_lines)
+            .unwrap();
+        assert_eq!(plan.removed_files, 1);
+        let mut deleted = plan.delete.clone();
+        deleted.sort();
+        assert_eq!(deleted, ["/p/gone.md-three", "/p/gone.md-two"]);
+    }
+
+    #[test]
+    fn orphans_and_unhashed_chunks_are_replaced() {
+        let mut vectors = indexed("/p/a.md", "one");
+        // A leftover copy of chunk 0 from an earlier run
+        let mut orphan = vectors[0].clone();
+        orphan.id = "orphan".to_string();
+        vectors.push(orphan);
+        let manifest = IndexManifest::from_vectors(&vectors);
+
+        let plan = manifest
+            .plan(Path::new("/p"), &[document("/p/a.md", "one")], chunk_lines)
+            .unwrap();
+        assert_eq!(plan.keep.len(), 1);
+        assert_eq!(plan.delete.len(), 1);
+        assert!(plan.embed.is_empty());
+
+        let mut manifest = manifest;
+        manifest.forget_hashes();
+        assert_eq!(manifest.file("/p/a.md").unwrap().file_hash, None);
+        let plan = manifest
+            .plan(Path::new("/p"), &[document("/p/a.md", "one")], chunk_lines)
+            .unwrap();
+        assert!(plan.keep.is_empty());
+        assert_eq!(plan.delete.len(), 2);
+        assert_eq!(plan.embed.len(), 1);
+    }
+
+    #[test]
+    fn vectors_not_stored_by_the_loader_are_ignored() {
+        let foreign = Vector {
+            id: "api".to_string(),
+            data: vec![1.0, 0.0],
+            sparse: None,
+            payload: Some(Payload::new(serde_json::json!({"content": "x"}))),
+            document_id: None,
+        };
+        assert!(IndexManifest::from_vectors(&[foreign]).is_empty());
+    }
+}
@@ -1,10 +1,14 @@
 //! Unified file loading, indexing, and persistence module
 //!
 //! Thin orchestrator that uses existing embedding, persistence, and storage modules
+//!
+//! Reindexing is incremental: see [`manifest`] for how a collection that
+//! is already indexed is synced with the files on disk.
 
 pub mod chunker;
 pub mod config;
 pub mod indexer;
+pub mod manifest;
 pub mod persistence;
 
 use std::fs;
@@ -15,10 +19,12 @@ pub use chunker::Chunker;
 pub use config::{DocumentChunk, LoaderConfig};
 use glob::Pattern;
 pub use indexer::Indexer;
+pub use manifest::{IndexManifest, ReindexPlan};
 pub use persistence::Persistence;
 use tracing::{debug, info, warn};
 
 use crate::VectorStore;
+use crate::chunking::ChunkingStrategy;
 use crate::db::BackpressureGuard;
 use crate::embedding::EmbeddingManager;
 
@@ -28,6 +34,8 @@ pub struct FileLoader {
     chunker: Chunker,
     indexer: Indexer,
     persistence: Persistence,
+    /// Chunking strategy set with [`Self::with_chunking`]
+    chunking: Option<ChunkingStrategy>,
 }
 
 impl FileLoader {
@@ -45,9 +53,28 @@ impl FileLoader {
             chunker,
             indexer,
             persistence,
+            chunking: None,
         }
     }
 
+    /// Split files with `strategy` (see [`crate::chunking`]). Without it,
+    /// an indexed collection is synced with the strategy it was created
+    /// with, and a new one is chunked at fixed sizes.
+    pub fn with_chunking(mut self, strategy: ChunkingStrategy) -> Self {
+        self.chunker = Chunker::with_strategy(self.config.clone(), strategy);
+        self.indexer.set_chunking(strategy);
+        self.chunking = Some(strategy);
+        self
+    }
+
+    /// Detect the language of each chunk: it is stored as `lang` in the
+    /// payload, and BM25 and TF-IDF vocabularies are built per language
+    /// (see [`crate::language`])
+    pub fn with_language_detection(mut self) -> Self {
+        self.indexer.set_language_detection(true);
+        self
+    }
+
     /// Attach a shared [`BackpressureGuard`] so the vocab-build step
     /// of [`Self::load_and_index_project`] is bounded across loaders
     /// (issue #263). Cheap to call — the guard wraps an `Arc`.
@@ -63,20 +90,24 @@ impl FileLoader {
     }
 
     /// Load and index a project (main entry point)
+    ///
+    /// A collection that is indexed already, in memory or in the `.vecdb`,
+    /// is synced with the project instead: only new and modified chunks
+    /// are embedded and the vectors of modified and removed ones are
+    /// deleted. Returns the number of chunks embedded.
     pub async fn load_and_index_project(
         &mut self,
         project_path: &str,
         store: &VectorStore,
     ) -> Result<usize> {
-        let collection_name = &self.config.collection_name;
-
-        // FAST PATH: Check if collection already exists in .vecdb
-        if self.persistence.collection_exists_in_vecdb(collection_name) {
-            info!(
-                "Collection '{}' found in .vecdb, skipping indexing",
-                collection_name
-            );
-            return Ok(0); // No new indexing
+        let collection_name = self.config.collection_name.clone();
+
+        let existing = store.has_collection_in_memory(&collection_name)
+            || self
+                .persistence
+                .collection_exists_in_vecdb(&collection_name);
+        if existing {
+            return self.reindex_project(project_path, store).await;
         }
 
         info!(
@@ -98,7 +129,8 @@ impl FileLoader {
             collection_name
         );
 
-        // Step 2: Chunk documents
+        // Step 2: Chunk documents (each chunk carries its content hashes,
+        // see `manifest`)
         let chunks = self.chunker.chunk_documents(&documents)?;
         info!(
             "Created {} chunks for collection '{}'",
@@ -129,6 +161,112 @@ impl FileLoader {
         Ok(vector_count)
     }
 
+    /// Sync an indexed collection with the project (see [`manifest`])
+    async fn reindex_project(&mut self, project_path: &str, store: &VectorStore) -> Result<usize> {
+        let collection_name = self.config.collection_name.clone();
+
+        // Loads the collection from the .vecdb if it is not resident
+        let (manifest, chunking) = match store.get_collection(&collection_name) {
+            Ok(collection) => (
+                IndexManifest::from_vectors(&collection.get_all_vectors()),
+                collection.config().chunking.clone(),
+            ),
+            Err(e) => {
+                warn!(
+                    "Collection '{}' found in .vecdb but could not be loaded, skipping indexing: {}",
+                    collection_name, e
+                );
+                return Ok(0);
+            }
+        };
+
+        if self.chunking.is_none()
+            && let Some(chunking) = chunking
+        {
+            let mut config = self.config.clone();
+            config.max_chunk_size = chunking.max_chunk_size.unwrap_or(config.max_chunk_size);
+            config.chunk_overlap = chunking.chunk_overlap.unwrap_or(config.chunk_overlap);
+            self.chunker = Chunker::with_strategy(config, chunking.strategy);
+        }
+
+        let documents = self.collect_documents_sync(project_path)?;
+        let root = Path::new(project_path);
+        let chunker = &self.chunker;
+        let mut plan = manifest.plan(root, &documents, |path, content| {
+            chunker.chunk_text(content, path)
+        })?;
+        if plan.is_empty() {
+            info!(
+                "Collection '{}' is up to date ({} files unchanged)",
+                collection_name, plan.unchanged_files
+            );
+            return Ok(0);
+        }
+
+        // New chunks must be embedded against the vocabulary the stored
+        // ones were. Without it, rebuild and re-embed the whole project.
+        if !plan.embed.is_empty() && self.indexer.needs_vocabulary() && !self.restore_tokenizer() {
+            warn!(
+                "No tokenizer saved for collection '{}', re-embedding all of {}",
+                collection_name, project_path
+            );
+            let mut manifest = manifest;
+            manifest.forget_hashes();
+            let chunker = &self.chunker;
+            plan = manifest.plan(root, &documents, |path, content| {
+                chunker.chunk_text(content, path)
+            })?;
+            self.indexer.build_vocabulary_gated(&documents).await?;
+        }
+
+        let vector_count = self.indexer.apply_plan(store, &plan)?;
+        self.save_collection_temp(store)?;
+        self.save_tokenizer()?;
+
+        info!(
+            "Reindexed collection '{}': {} files unchanged, {} changed, {} removed; {} chunks embedded, {} kept, {} deleted",
+            collection_name,
+            plan.unchanged_files,
+            plan.changed_files,
+            plan.removed_files,
+            vector_count,
+            plan.keep.len(),
+            plan.delete.len()
+        );
+        Ok(vector_count)
+    }
+
+    /// Restore the vocabulary the collection was embedded with from its
+    /// saved tokenizer. Returns whether one was restored.
+    fn restore_tokenizer(&mut self) -> bool {
+        let Some(tokenizer) = self
+            .persistence
+            .read_tokenizer(&self.config.collection_name)
+        else {
+            return false;
+        };
+
+        // Provider loaders read from a file path
+        let restored = tempfile::NamedTempFile::new()
+            .map_err(anyhow::Error::from)
+            .and_then(|file| {
+                fs::write(file.path(), &tokenizer)?;
+                self.indexer
+                    .load_vocabulary(file.path(), &self.config.embedding_type)?;
+                Ok(())
+            });
+        match restored {
+            Ok(()) => true,
+            Err(e) => {
+                warn!(
+                    "Failed to restore tokenizer of collection '{}': {}",
+                    self.config.collection_name, e
+                );
+                false
+            }
+        }
+    }
+
     /// Collect documents from project directory (sync - just filesystem I/O)
     fn collect_documents_sync(&self, project_path: &str) -> Result<Vec<(PathBuf, String)>> {
         let path = Path::new(project_path);
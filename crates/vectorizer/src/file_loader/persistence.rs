@@ -41,6 +41,25 @@ impl Persistence {
         }
     }
 
+    /// Read a collection's saved tokenizer: the raw file if present,
+    /// otherwise its entry in the .vecdb archive
+    pub fn read_tokenizer(&self, collection_name: &str) -> Option<Vec<u8>> {
+        let file_name = format!("{}_tokenizer.json", collection_name);
+        if let Ok(bytes) = std::fs::read(self.data_dir.join(&file_name)) {
+            return Some(bytes);
+        }
+
+        if !self.data_dir.join("vectorizer.vecdb").exists() {
+            return None;
+        }
+        let reader = StorageReader::new(&self.data_dir).ok()?;
+        let files = reader.read_collection_files(collection_name).ok()?;
+        files
+            .into_iter()
+            .find(|(path, _)| path.ends_with(&file_name))
+            .map(|(_, bytes)| bytes)
+    }
+
     /// Save collection using existing persistence module
     pub fn save_collection_legacy_temp(
         &self,
@@ -94,6 +113,11 @@ impl Persistence {
             config: Some(meta.config.clone()),
             vectors: persisted_vectors,
             hnsw_dump_basename: None,
+            recall_profile: None,
+            feedback: None,
+            metadata_kv: None,
+            hybrid_tuning: None,
+            overrides: None,
         };
 
         let file = File::create(&temp_path).map_err(|e| crate::error::VectorizerError::Io(e))?;
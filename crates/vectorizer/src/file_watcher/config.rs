@@ -64,6 +64,18 @@ pub struct FileWatcherConfig {
 
     /// Enable hot reload
     pub hot_reload: bool,
+
+    /// OCR of images: when enabled, files with one of its image
+    /// extensions are indexed by their recognized text even if no
+    /// include pattern matches them
+    #[serde(default)]
+    pub ocr: crate::ocr::OcrConfig,
+
+    /// Transcription of audio: when enabled, files with one of its audio
+    /// extensions are indexed by their transcript even if no include
+    /// pattern matches them
+    #[serde(default)]
+    pub transcription: crate::transcription::TranscriptionConfig,
 }
 
 impl Default for FileWatcherConfig {
@@ -137,6 +149,8 @@ impl Default for FileWatcherConfig {
             auto_discovery: true,
             enable_auto_update: true,
             hot_reload: true,
+            ocr: crate::ocr::OcrConfig::default(),
+            transcription: crate::transcription::TranscriptionConfig::default(),
         }
     }
 }
@@ -225,6 +239,16 @@ impl FileWatcherConfig {
             }
         }
 
+        if self.ocr.enabled && self.ocr.is_image(file_path) {
+            tracing::info!("✅ Image included for OCR: {:?}", file_path);
+            return true;
+        }
+
+        if self.transcription.enabled && self.transcription.is_audio(file_path) {
+            tracing::info!("✅ Audio included for transcription: {:?}", file_path);
+            return true;
+        }
+
         // Check include patterns
         if self.include_patterns.is_empty() {
             tracing::debug!("No include patterns, allowing file: {:?}", file_path);
@@ -262,6 +286,14 @@ impl FileWatcherConfig {
             }
         }
 
+        if self.ocr.enabled && self.ocr.is_image(file_path) {
+            return true;
+        }
+
+        if self.transcription.enabled && self.transcription.is_audio(file_path) {
+            return true;
+        }
+
         // Check include patterns
         if self.include_patterns.is_empty() {
             return true; // No include patterns means include all
@@ -501,6 +533,35 @@ mod tests {
         assert!(!config.should_process_file_silent(&PathBuf::from("data/file.pdf")));
     }
 
+    #[test]
+    fn test_file_watcher_ocr_includes_images() {
+        let mut config = FileWatcherConfig {
+            include_patterns: vec!["*.md".to_string()],
+            ..FileWatcherConfig::default()
+        };
+        assert!(!config.should_process_file_silent(&PathBuf::from("scans/page.tiff")));
+
+        config.ocr.enabled = true;
+        assert!(config.should_process_file_silent(&PathBuf::from("scans/page.tiff")));
+        assert!(config.should_process_file(&PathBuf::from("screenshot.PNG")));
+        // Exclusions still apply to images
+        assert!(!config.should_process_file_silent(&PathBuf::from("data/scan.png")));
+    }
+
+    #[test]
+    fn test_file_watcher_transcription_includes_audio() {
+        let mut config = FileWatcherConfig {
+            include_patterns: vec!["*.md".to_string()],
+            ..FileWatcherConfig::default()
+        };
+        assert!(!config.should_process_file_silent(&PathBuf::from("calls/standup.mp3")));
+
+        config.transcription.enabled = true;
+        assert!(config.should_process_file_silent(&PathBuf::from("calls/standup.mp3")));
+        assert!(config.should_process_file(&PathBuf::from("memo.WAV")));
+        assert!(!config.should_process_file_silent(&PathBuf::from("data/call.wav")));
+    }
+
     #[test]
     fn test_file_watcher_max_file_size() {
         let config = FileWatcherConfig::default();
@@ -631,6 +631,8 @@ impl FileDiscovery {
                         info!("✅ Removed orphaned file: {}", file_path);
                     }
                 }
+                drop(collection);
+                self.vector_store.notify_mutation(&collection_name);
             } else {
                 info!(
                     "✅ No orphaned files found in collection '{}'",
@@ -45,6 +45,7 @@ use tokio::sync::RwLock;
 pub use watcher::Watcher as FileWatcher;
 
 use crate::VectorStore;
+use crate::background_tasks::{self, TASKS};
 use crate::embedding::EmbeddingManager;
 
 /// Convert WSL path format (/mnt/X/path) to Windows path format (X:\path)
@@ -212,18 +213,25 @@ impl FileWatcherSystem {
         );
 
         tracing::info!("🔍 FW_STEP_2: Setting up event processing callback...");
-        // Set up event processing callback
+        // Set up event processing callback. While the file watcher task
+        // is paused, events wait here and are processed on resume.
         let vector_operations = self.vector_operations.clone();
+        let task = TASKS.register(background_tasks::FILE_WATCHER);
         self.debouncer
             .set_event_callback(move |event| {
                 tracing::info!("🔍 CALLBACK: File change event received: {:?}", event.event);
                 let vector_operations = vector_operations.clone();
+                let task = task.clone();
                 tokio::spawn(async move {
+                    task.resumed().await;
                     tracing::info!(
                         "🔍 CALLBACK: Processing file change event: {:?}",
                         event.event
                     );
-                    if let Err(e) = vector_operations.process_file_change(&event).await {
+                    task.start();
+                    let result = vector_operations.process_file_change(&event).await;
+                    task.finish(&result);
+                    if let Err(e) = result {
                         tracing::error!(
                             "❌ CALLBACK: Failed to process file change event: {:?}",
                             e
@@ -650,6 +658,9 @@ impl FileWatcherSystem {
     /// Stop the file watcher system
     pub async fn stop(&self) -> Result<()> {
         tracing::info!("🛑 Stopping File Watcher System");
+        if let Some(task) = TASKS.get(background_tasks::FILE_WATCHER) {
+            task.stop();
+        }
 
         // Stop the discovery system if it exists
         if let Some(discovery) = &self.discovery {
@@ -9,6 +9,8 @@ use crate::db::BackpressureGuard;
 use crate::embedding::EmbeddingManager;
 use crate::error::{Result, VectorizerError};
 use crate::file_loader::{FileLoader, LoaderConfig};
+use crate::ocr::{OcrEngine, OcrOutput};
+use crate::transcription::{Transcriber, TranscriptSegment, group_segments};
 
 /// Vector operations for file watcher
 pub struct VectorOperations {
@@ -240,13 +242,43 @@ impl VectorOperations {
         let original_path = path.to_path_buf();
         let original_path_str = original_path.to_string_lossy().to_string();
 
-        // Read file content directly (more efficient for single files)
-        let content = match tokio::fs::read_to_string(path).await {
-            Ok(c) => c,
-            Err(e) => {
-                // For binary files or encoding issues, skip silently
-                tracing::debug!("Skipping file {:?} (cannot read as text): {}", path, e);
-                return Ok(());
+        // Images are indexed by the text OCR finds in them, audio by its
+        // transcript; other files are read directly (more efficient for
+        // single files)
+        let is_image = self.config.ocr.enabled && self.config.ocr.is_image(path);
+        let is_audio =
+            self.config.transcription.enabled && self.config.transcription.is_audio(path);
+        let mut ocr_confidence = None;
+        let mut transcript = None;
+        let content = if is_image {
+            match self.extract_image_text(path).await {
+                Some(output) => {
+                    ocr_confidence = Some(output.confidence);
+                    output.text
+                }
+                None => return Ok(()),
+            }
+        } else if is_audio {
+            match self.transcribe_audio(path).await {
+                Some(segments) => {
+                    let text = segments
+                        .iter()
+                        .map(|s| s.text.as_str())
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    transcript = Some(segments);
+                    text
+                }
+                None => return Ok(()),
+            }
+        } else {
+            match tokio::fs::read_to_string(path).await {
+                Ok(c) => c,
+                Err(e) => {
+                    // For binary files or encoding issues, skip silently
+                    tracing::debug!("Skipping file {:?} (cannot read as text): {}", path, e);
+                    return Ok(());
+                }
             }
         };
 
@@ -296,44 +328,12 @@ impl VectorOperations {
             }
         }
 
-        // Chunk the content manually (simple approach)
-        let max_chunk_size = 2048;
-        let chunk_overlap = 256;
-        let mut chunks: Vec<String> = Vec::new();
-        let mut start = 0;
-
-        while start < content.len() {
-            let mut end = std::cmp::min(start + max_chunk_size, content.len());
-
-            // Ensure we're at a UTF-8 character boundary
-            while end > start && !content.is_char_boundary(end) {
-                end -= 1;
-            }
-
-            // Try to break at a word boundary
-            if end < content.len() {
-                if let Some(pos) =
-                    content[start..end].rfind(|c: char| c.is_whitespace() || c == '.' || c == '\n')
-                {
-                    let new_end = start + pos + 1;
-                    if content.is_char_boundary(new_end) {
-                        end = new_end;
-                    }
-                }
-            }
-
-            let chunk_text = content[start..end].trim();
-            if !chunk_text.is_empty() {
-                chunks.push(chunk_text.to_string());
-            }
-
-            // Move start with overlap
-            start = if end >= content.len() {
-                content.len()
-            } else {
-                end.saturating_sub(chunk_overlap)
-            };
-        }
+        // Transcripts are chunked along segment boundaries so every chunk
+        // keeps its time range; other content is chunked manually
+        let chunks: Vec<String> = match &transcript {
+            Some(segments) => segments.iter().map(|s| s.text.clone()).collect(),
+            None => chunk_content(&content, 2048, 256),
+        };
 
         // Build vectors to insert
         let mut vectors_to_insert: Vec<crate::models::Vector> = Vec::new();
@@ -350,13 +350,24 @@ impl VectorOperations {
             };
 
             // Create payload data with ORIGINAL file path (not temp path)
-            let payload_data = serde_json::json!({
+            let mut payload_data = serde_json::json!({
                 "content": chunk_content,
                 "file_path": original_path_str,
                 "file_extension": file_extension,
                 "chunk_index": chunk_idx,
                 "chunk_size": chunk_content.len()
             });
+            if let Some(confidence) = ocr_confidence {
+                payload_data["image_path"] = serde_json::json!(original_path_str);
+                payload_data["extracted_via"] = serde_json::json!("ocr");
+                payload_data["ocr_confidence"] = serde_json::json!(confidence);
+            }
+            if let Some(segment) = transcript.as_ref().and_then(|t| t.get(chunk_idx)) {
+                payload_data["audio_path"] = serde_json::json!(original_path_str);
+                payload_data["extracted_via"] = serde_json::json!("transcription");
+                payload_data["start_seconds"] = serde_json::json!(segment.start);
+                payload_data["end_seconds"] = serde_json::json!(segment.end);
+            }
 
             // Generate unique ID
             let vector_id = format!("{}_{}", original_path_str, chunk_idx);
@@ -424,6 +435,89 @@ impl VectorOperations {
         Ok(())
     }
 
+    /// Text OCR recognizes in the image at `path`, or `None` when the
+    /// image is too large or unreadable or holds no usable text
+    async fn extract_image_text(&self, path: &std::path::Path) -> Option<OcrOutput> {
+        let size = match tokio::fs::metadata(path).await {
+            Ok(metadata) => metadata.len(),
+            Err(e) => {
+                tracing::debug!("Skipping image {:?} (cannot read metadata): {}", path, e);
+                return None;
+            }
+        };
+        if size > self.config.max_file_size {
+            tracing::debug!("Skipping image {:?} (too large: {} bytes)", path, size);
+            return None;
+        }
+
+        // Recognition is CPU-bound and blocking
+        let engine = OcrEngine::new(self.config.ocr.clone());
+        let image = path.to_path_buf();
+        let output = match tokio::task::spawn_blocking(move || engine.extract_text(&image)).await {
+            Ok(Ok(output)) => output,
+            Ok(Err(e)) => {
+                tracing::warn!("Skipping image {:?}: {}", path, e);
+                return None;
+            }
+            Err(e) => {
+                tracing::warn!("OCR task for image {:?} failed: {}", path, e);
+                return None;
+            }
+        };
+
+        if !output.is_usable(&self.config.ocr) {
+            tracing::debug!(
+                "Skipping image {:?} (no text with confidence >= {}, got {})",
+                path,
+                self.config.ocr.min_confidence,
+                output.confidence
+            );
+            return None;
+        }
+        Some(output)
+    }
+
+    /// The transcript of the audio file at `path`, grouped into chunks of
+    /// at most `max_chunk_seconds`, or `None` when the file is too large
+    /// or unreadable or nothing was said
+    async fn transcribe_audio(&self, path: &std::path::Path) -> Option<Vec<TranscriptSegment>> {
+        let config = &self.config.transcription;
+        let size = match tokio::fs::metadata(path).await {
+            Ok(metadata) => metadata.len(),
+            Err(e) => {
+                tracing::debug!("Skipping audio {:?} (cannot read metadata): {}", path, e);
+                return None;
+            }
+        };
+        if size > config.max_file_size_mb.saturating_mul(1024 * 1024) {
+            tracing::debug!("Skipping audio {:?} (too large: {} bytes)", path, size);
+            return None;
+        }
+
+        // Decoding and inference are CPU-bound and blocking
+        let transcriber = Transcriber::new(config.clone());
+        let audio = path.to_path_buf();
+        let segments =
+            match tokio::task::spawn_blocking(move || transcriber.transcribe(&audio)).await {
+                Ok(Ok(segments)) => segments,
+                Ok(Err(e)) => {
+                    tracing::warn!("Skipping audio {:?}: {}", path, e);
+                    return None;
+                }
+                Err(e) => {
+                    tracing::warn!("Transcription task for audio {:?} failed: {}", path, e);
+                    return None;
+                }
+            };
+
+        let chunks = group_segments(&segments, config.max_chunk_seconds);
+        if chunks.is_empty() {
+            tracing::debug!("Skipping audio {:?} (no speech transcribed)", path);
+            return None;
+        }
+        Some(chunks)
+    }
+
     /// Remove file from index by path
     async fn remove_file_from_path(&self, path: &std::path::Path) -> Result<()> {
         let collection_name = self.determine_collection_name(path);
@@ -530,6 +624,48 @@ impl VectorOperations {
     }
 }
 
+/// Split `content` into trimmed chunks of at most `max_chunk_size` bytes,
+/// broken at whitespace or periods where possible and overlapping by
+/// `chunk_overlap` bytes
+fn chunk_content(content: &str, max_chunk_size: usize, chunk_overlap: usize) -> Vec<String> {
+    let mut chunks: Vec<String> = Vec::new();
+    let mut start = 0;
+
+    while start < content.len() {
+        let mut end = std::cmp::min(start + max_chunk_size, content.len());
+
+        // Ensure we're at a UTF-8 character boundary
+        while end > start && !content.is_char_boundary(end) {
+            end -= 1;
+        }
+
+        // Try to break at a word boundary
+        if end < content.len() {
+            if let Some(pos) =
+                content[start..end].rfind(|c: char| c.is_whitespace() || c == '.' || c == '\n')
+            {
+                let new_end = start + pos + 1;
+                if content.is_char_boundary(new_end) {
+                    end = new_end;
+                }
+            }
+        }
+
+        let chunk_text = content[start..end].trim();
+        if !chunk_text.is_empty() {
+            chunks.push(chunk_text.to_string());
+        }
+
+        // Move start with overlap
+        start = if end >= content.len() {
+            content.len()
+        } else {
+            end.saturating_sub(chunk_overlap)
+        };
+    }
+    chunks
+}
+
 #[cfg(test)]
 #[path = "operations_tests.rs"]
 mod tests;
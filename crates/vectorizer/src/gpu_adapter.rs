@@ -2,9 +2,16 @@
 //!
 //! This module provides an adapter layer between hive-vectorizer and hive-gpu.
 //! It translates between vectorizer types and hive-gpu types.
+//!
+//! [`GpuRescorer`] uses it to run brute-force scoring (exact search and
+//! the rescoring pass of binary-quantized search) on the GPU.
 
 use std::collections::HashMap;
 
+use once_cell::sync::OnceCell;
+use parking_lot::Mutex;
+use tracing::{info, warn};
+
 // Re-export hive-gpu types for convenience
 pub use hive_gpu::{
     GpuBackend, GpuContext, GpuDistanceMetric as HiveGpuDistanceMetric,
@@ -12,10 +19,13 @@ pub use hive_gpu::{
     HiveGpuError, HnswConfig as HiveGpuHnswConfig,
 };
 
+use crate::config::{GpuConfig, GpuRescoringConfig};
 #[cfg(feature = "hive-gpu")]
 use crate::db::gpu_detection::GpuBackendType;
+use crate::db::gpu_detection::GpuDetector;
+use crate::db::search_deadline::SearchDeadline;
 use crate::error::{Result, VectorizerError};
-use crate::models::{Payload, Vector};
+use crate::models::{DistanceMetric, Payload, Vector};
 
 /// Adapter for converting between vectorizer and hive-gpu types
 pub struct GpuAdapter;
@@ -253,6 +263,118 @@ impl GpuAdapter {
     }
 }
 
+/// Process-wide rescorer, set up once by [`GpuRescorer::install`].
+static RESCORER: OnceCell<GpuRescorer> = OnceCell::new();
+
+/// Brute-force top-`k` on the GPU, for scans large enough to be worth
+/// the upload.
+///
+/// Candidates are uploaded in chunks of `chunk_size` vectors to a
+/// transient GPU storage, each chunk is searched for its own top `k`, and
+/// the chunk winners are merged. Only the ranking comes from the GPU:
+/// callers rescore the returned ids on the CPU so scores stay on the same
+/// scale as every other search path.
+pub struct GpuRescorer {
+    context: Mutex<Box<dyn GpuContext + Send>>,
+    config: GpuRescoringConfig,
+}
+
+impl GpuRescorer {
+    /// Set up the process-wide rescorer from `config`. Returns whether
+    /// one is installed; with rescoring disabled or no usable GPU,
+    /// searches keep scoring on the CPU.
+    pub fn install(config: &GpuConfig) -> bool {
+        if RESCORER.get().is_some() {
+            return true;
+        }
+        if !config.enabled || !config.rescoring.enabled {
+            return false;
+        }
+        let backend = GpuDetector::detect_best_backend();
+        match GpuAdapter::create_context(backend) {
+            Ok(context) => {
+                let _ = RESCORER.set(Self {
+                    context: Mutex::new(context),
+                    config: config.rescoring.clone(),
+                });
+                info!(
+                    "GPU rescoring enabled on {:?} (min {} candidates, chunks of {})",
+                    backend, config.rescoring.min_candidates, config.rescoring.chunk_size
+                );
+                true
+            }
+            Err(e) => {
+                warn!("GPU rescoring unavailable, scoring on the CPU: {}", e);
+                false
+            }
+        }
+    }
+
+    /// The installed rescorer, if any.
+    pub fn global() -> Option<&'static Self> {
+        RESCORER.get()
+    }
+
+    /// Whether a scan over `candidates` vectors should go to the GPU.
+    pub fn worth_offloading(&self, candidates: usize) -> bool {
+        candidates >= self.config.min_candidates.max(1)
+    }
+
+    /// Ids of the best `k` of `candidates` against `query`, best first.
+    /// Once `deadline` passes no further chunk is uploaded and the best of
+    /// the chunks searched so far is returned, with the flag set.
+    pub fn top_k<'a>(
+        &self,
+        metric: DistanceMetric,
+        query: &[f32],
+        candidates: impl IntoIterator<Item = (&'a str, &'a [f32])>,
+        k: usize,
+        deadline: &SearchDeadline,
+    ) -> Result<(Vec<String>, bool)> {
+        let gpu_metric = GpuAdapter::distance_metric_to_gpu_metric(metric);
+        let chunk_size = self.config.chunk_size.max(1);
+        let mut candidates = candidates.into_iter().peekable();
+        let mut best: Vec<(String, f32)> = Vec::with_capacity(k * 2);
+        let mut timed_out = false;
+
+        while candidates.peek().is_some() && k > 0 {
+            if deadline.expired() {
+                timed_out = true;
+                break;
+            }
+            let chunk: Vec<HiveGpuVector> = candidates
+                .by_ref()
+                .take(chunk_size)
+                .map(|(id, data)| HiveGpuVector {
+                    id: id.to_string(),
+                    data: data.to_vec(),
+                    metadata: HashMap::new(),
+                })
+                .collect();
+            let mut storage = self
+                .context
+                .lock()
+                .create_storage(query.len(), gpu_metric)
+                .map_err(GpuAdapter::gpu_error_to_vectorizer_error)?;
+            storage
+                .add_vectors(&chunk)
+                .map_err(GpuAdapter::gpu_error_to_vectorizer_error)?;
+            let hits = storage
+                .search(query, k.min(chunk.len()))
+                .map_err(GpuAdapter::gpu_error_to_vectorizer_error)?;
+            best.extend(hits.into_iter().map(|hit| (hit.id, hit.score)));
+            if best.len() > k {
+                best.sort_by(|a, b| b.1.total_cmp(&a.1));
+                best.truncate(k);
+            }
+        }
+
+        best.sort_by(|a, b| b.1.total_cmp(&a.1));
+        best.truncate(k);
+        Ok((best.into_iter().map(|(id, _)| id).collect(), timed_out))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -301,6 +423,27 @@ mod tests {
         assert_eq!(converted_back, DistanceMetric::Cosine);
     }
 
+    #[test]
+    fn test_rescorer_not_installed_when_disabled() {
+        let config = GpuConfig {
+            enabled: true,
+            ..GpuConfig::default()
+        };
+        assert!(!GpuRescorer::install(&config));
+        assert!(GpuRescorer::global().is_none());
+
+        let config = GpuConfig {
+            enabled: false,
+            rescoring: GpuRescoringConfig {
+                enabled: true,
+                ..GpuRescoringConfig::default()
+            },
+            ..GpuConfig::default()
+        };
+        assert!(!GpuRescorer::install(&config));
+        assert!(GpuRescorer::global().is_none());
+    }
+
     #[test]
     fn test_hnsw_config_conversion() {
         let cpu_config = HnswConfig {
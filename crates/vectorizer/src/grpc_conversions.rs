@@ -62,6 +62,13 @@ impl TryFrom<&proto::CollectionConfig> for crate::models::CollectionConfig {
                 Some(StorageType::from(storage_enum))
             },
             encryption: None,
+            id_policy: None,
+            quota: None,
+            scoring: None,
+            tenant_partition: None,
+            dedup: None,
+            chunking: None,
+            full_text: None,
         })
     }
 }
@@ -27,6 +27,7 @@ use uuid::Uuid;
 use crate::db::VectorStore;
 use crate::error::{Result, VectorizerError};
 use crate::models::Vector;
+use crate::storage::{ObjectStore, ObjectStoreConfig};
 
 /// Backup metadata for a user's backup
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -122,6 +123,11 @@ pub struct BackupConfig {
     /// Compression level (1-9, higher = better compression)
     #[serde(default = "default_compression_level")]
     pub compression_level: u32,
+    /// Bucket backups are also written to, keyed `{user_id}/{file}`
+    /// under its prefix. Backups missing from `backup_dir` are fetched
+    /// from it on demand.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub object_store: Option<ObjectStoreConfig>,
 }
 
 fn default_backup_dir() -> PathBuf {
@@ -148,6 +154,7 @@ impl Default for BackupConfig {
             max_backup_age_hours: 0,
             compression_enabled: default_compression_enabled(),
             compression_level: default_compression_level(),
+            object_store: None,
         }
     }
 }
@@ -160,6 +167,8 @@ pub struct UserBackupManager {
     store: Arc<VectorStore>,
     /// Backup metadata cache (user_id -> Vec<BackupInfo>)
     metadata_cache: RwLock<HashMap<Uuid, Vec<UserBackupInfo>>>,
+    /// Remote copy of `backup_dir`, if configured
+    object_store: Option<ObjectStore>,
 }
 
 impl UserBackupManager {
@@ -167,11 +176,17 @@ impl UserBackupManager {
     pub fn new(config: BackupConfig, store: Arc<VectorStore>) -> Result<Self> {
         // Ensure backup directory exists
         fs::create_dir_all(&config.backup_dir).map_err(VectorizerError::IoError)?;
+        let object_store = config
+            .object_store
+            .clone()
+            .map(ObjectStore::new)
+            .transpose()?;
 
         Ok(Self {
             config,
             store,
             metadata_cache: RwLock::new(HashMap::new()),
+            object_store,
         })
     }
 
@@ -233,6 +248,73 @@ impl UserBackupManager {
             .join(format!("{}.meta.json", backup_id))
     }
 
+    /// Object key of a file in a user's backup directory
+    fn remote_key(user_id: &Uuid, file_name: &str) -> String {
+        format!("{}/{}", user_id, file_name)
+    }
+
+    /// Copy a local backup and its metadata to the object store. Failures
+    /// are logged: the local backup is still usable.
+    async fn push_remote(&self, user_id: &Uuid, backup_id: &Uuid) {
+        let Some(remote) = &self.object_store else {
+            return;
+        };
+        let files = [
+            self.backup_file_path(user_id, backup_id),
+            self.metadata_file_path(user_id, backup_id),
+        ];
+        for path in files {
+            let Some(file_name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            let key = Self::remote_key(user_id, file_name);
+            if let Err(e) = remote.put_file(&key, &path).await {
+                error!(
+                    "Failed to copy backup {} to {}: {}",
+                    backup_id,
+                    remote.describe(),
+                    e
+                );
+                return;
+            }
+        }
+        debug!("Copied backup {} to {}", backup_id, remote.describe());
+    }
+
+    /// Fetch a backup missing from `backup_dir` from the object store.
+    /// A no-op when it is local or no object store is configured.
+    async fn fetch_remote(&self, user_id: &Uuid, backup_id: &Uuid) -> Result<()> {
+        let Some(remote) = &self.object_store else {
+            return Ok(());
+        };
+        let file_path = self.backup_file_path(user_id, backup_id);
+        let meta_path = self.metadata_file_path(user_id, backup_id);
+        if file_path.exists() && meta_path.exists() {
+            return Ok(());
+        }
+
+        fs::create_dir_all(self.user_backup_dir(user_id)).map_err(VectorizerError::IoError)?;
+        for path in [&meta_path, &file_path] {
+            let Some(file_name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            if !remote
+                .get_to_file(&Self::remote_key(user_id, file_name), path)
+                .await?
+            {
+                // Not in the bucket either; callers report NotFound
+                return Ok(());
+            }
+        }
+        info!(
+            "Fetched backup {} for user {} from {}",
+            backup_id,
+            user_id,
+            remote.describe()
+        );
+        Ok(())
+    }
+
     /// Create a backup for a user's collections
     ///
     /// # Arguments
@@ -406,6 +488,8 @@ impl UserBackupManager {
                 .push(final_info.clone());
         }
 
+        self.push_remote(&user_id, &backup_id).await;
+
         // Cleanup old backups if needed
         self.cleanup_old_backups(&user_id).await?;
 
@@ -420,17 +504,19 @@ impl UserBackupManager {
         Ok(final_info)
     }
 
-    /// List all backups for a user
+    /// List all backups for a user, including those only held in the
+    /// object store
     pub async fn list_backups(&self, user_id: &Uuid) -> Result<Vec<UserBackupInfo>> {
         let user_dir = self.user_backup_dir(user_id);
-
-        if !user_dir.exists() {
-            return Ok(Vec::new());
-        }
-
         let mut backups = Vec::new();
 
-        let entries = fs::read_dir(&user_dir).map_err(|e| VectorizerError::IoError(e))?;
+        let entries: Vec<_> = if user_dir.exists() {
+            fs::read_dir(&user_dir)
+                .map_err(|e| VectorizerError::IoError(e))?
+                .collect()
+        } else {
+            Vec::new()
+        };
 
         for entry in entries {
             let entry = entry.map_err(|e| VectorizerError::IoError(e))?;
@@ -453,6 +539,22 @@ impl UserBackupManager {
             }
         }
 
+        if let Some(remote) = &self.object_store {
+            for entry in remote.list(&Self::remote_key(user_id, "")).await? {
+                if !entry.key.ends_with(".meta.json") {
+                    continue;
+                }
+                let Some(content) = remote.get(&entry.key).await? else {
+                    continue;
+                };
+                match serde_json::from_slice::<UserBackupInfo>(&content) {
+                    Ok(info) if !backups.iter().any(|b| b.id == info.id) => backups.push(info),
+                    Ok(_) => {}
+                    Err(e) => warn!("Failed to parse backup metadata {}: {}", entry.key, e),
+                }
+            }
+        }
+
         // Sort by creation time (newest first)
         backups.sort_by(|a, b| b.created_at.cmp(&a.created_at));
 
@@ -461,6 +563,7 @@ impl UserBackupManager {
 
     /// Get backup info by ID
     pub async fn get_backup(&self, user_id: &Uuid, backup_id: &Uuid) -> Result<UserBackupInfo> {
+        self.fetch_remote(user_id, backup_id).await?;
         let meta_path = self.metadata_file_path(user_id, backup_id);
 
         if !meta_path.exists() {
@@ -479,6 +582,7 @@ impl UserBackupManager {
 
     /// Download backup data (returns compressed bytes)
     pub async fn download_backup(&self, user_id: &Uuid, backup_id: &Uuid) -> Result<Vec<u8>> {
+        self.fetch_remote(user_id, backup_id).await?;
         let file_path = self.backup_file_path(user_id, backup_id);
 
         if !file_path.exists() {
@@ -517,6 +621,7 @@ impl UserBackupManager {
             backup_id, user_id, overwrite
         );
 
+        self.fetch_remote(user_id, backup_id).await?;
         let file_path = self.backup_file_path(user_id, backup_id);
 
         if !file_path.exists() {
@@ -588,6 +693,13 @@ impl UserBackupManager {
                 sharding: None,
                 graph: None,
                 encryption: None,
+                id_policy: None,
+                quota: None,
+                scoring: None,
+                tenant_partition: None,
+                dedup: None,
+                chunking: None,
+                full_text: None,
             };
 
             // Create collection
@@ -656,7 +768,14 @@ impl UserBackupManager {
         let file_path = self.backup_file_path(user_id, backup_id);
         let meta_path = self.metadata_file_path(user_id, backup_id);
 
-        if !file_path.exists() && !meta_path.exists() {
+        let mut remote_removed = 0;
+        if let Some(remote) = &self.object_store {
+            remote_removed = remote
+                .delete_prefix(&Self::remote_key(user_id, &format!("{}.", backup_id)))
+                .await?;
+        }
+
+        if !file_path.exists() && !meta_path.exists() && remote_removed == 0 {
             return Err(VectorizerError::NotFound(format!(
                 "Backup {} not found for user {}",
                 backup_id, user_id
@@ -750,6 +869,8 @@ impl UserBackupManager {
             .map_err(|e| VectorizerError::Serialization(e.to_string()))?;
         fs::write(&meta_path, meta_json).map_err(|e| VectorizerError::IoError(e))?;
 
+        self.push_remote(&user_id, &final_data.info.id).await;
+
         info!(
             "Uploaded backup '{}' for user {}: {} bytes",
             final_data.info.name, user_id, final_size
@@ -914,4 +1035,55 @@ mod tests {
         assert_eq!(info.name, parsed.name);
         assert_eq!(info.vector_count, parsed.vector_count);
     }
+
+    #[tokio::test]
+    async fn test_backups_survive_loss_of_backup_dir() {
+        let (remote, _bucket) = crate::storage::object_store::mock::start().await;
+        let dir = tempfile::tempdir().unwrap();
+        let config = BackupConfig {
+            backup_dir: dir.path().to_path_buf(),
+            object_store: Some(remote),
+            ..Default::default()
+        };
+        let manager =
+            UserBackupManager::new(config, Arc::new(VectorStore::new_cpu_only())).unwrap();
+
+        let user_id = Uuid::new_v4();
+        let data = UserBackupData {
+            info: UserBackupInfo {
+                id: Uuid::new_v4(),
+                user_id,
+                name: "nightly".to_string(),
+                description: None,
+                created_at: Utc::now(),
+                collections: Vec::new(),
+                vector_count: 0,
+                size_bytes: 0,
+                format_version: 1,
+                checksum: None,
+                compressed: false,
+            },
+            collections: Vec::new(),
+        };
+        let uploaded = manager
+            .upload_backup(user_id, serde_json::to_vec(&data).unwrap(), None)
+            .await
+            .unwrap();
+
+        // The node loses its local disk
+        fs::remove_dir_all(dir.path().join(user_id.to_string())).unwrap();
+
+        let listed = manager.list_backups(&user_id).await.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].id, uploaded.id);
+        let bytes = manager
+            .download_backup(&user_id, &uploaded.id)
+            .await
+            .unwrap();
+        assert_eq!(bytes.len() as u64, uploaded.size_bytes);
+
+        manager.delete_backup(&user_id, &uploaded.id).await.unwrap();
+        assert!(manager.list_backups(&user_id).await.unwrap().is_empty());
+        assert!(manager.delete_backup(&user_id, &uploaded.id).await.is_err());
+    }
 }
@@ -31,7 +31,7 @@ pub use key_rotation::{DEFAULT_GRACE_PERIOD_SECS, KeyRotation, KeyRotationManage
 pub use mcp_gateway::{McpHubGateway, McpOperationLog, McpOperationType, McpRequestContext};
 pub use middleware::HubAuthMiddleware;
 use parking_lot::RwLock;
-pub use quota::{QuotaInfo, QuotaManager, QuotaType};
+pub use quota::{QuotaInfo, QuotaManager, QuotaType, TenantUsage, WriteAdmission};
 pub use request_signing::{
     HEADER_NONCE, HEADER_SIGNATURE, HEADER_TIMESTAMP, RequestSigningValidator, SignedRequest,
     SigningConfig, create_signing_headers,
@@ -46,8 +46,10 @@ use uuid::Uuid;
 // here under the historical `crate::hub::*` paths so every existing
 // call site keeps compiling.
 pub use crate::config::sections::hub::{
-    ConnectionPoolConfig, HubCacheConfig, HubConfig, TenantIsolationMode,
+    ConnectionPoolConfig, HubCacheConfig, HubConfig, TenantIsolationMode, TenantLimits,
+    TenantQuotaConfig,
 };
+use crate::db::VectorStore;
 use crate::error::{Result, VectorizerError};
 
 /// HiveHub integration manager
@@ -95,7 +97,10 @@ impl HubManager {
 
         let client = Arc::new(HubClient::new(client_config)?);
         let auth = Arc::new(HubAuth::new(client.clone(), &config.cache));
-        let quota = Arc::new(QuotaManager::new(client.clone(), &config.cache));
+        let quota = Arc::new(
+            QuotaManager::new(client.clone(), &config.cache)
+                .with_tenant_quotas(config.tenant_quotas.clone()),
+        );
         let usage = Arc::new(UsageReporter::new(
             client.clone(),
             config.usage_report_interval,
@@ -228,6 +233,53 @@ impl HubManager {
             .await
     }
 
+    /// Refuse a write that would take a tenant past a quota, and hold
+    /// it back from other writes of that tenant until the returned
+    /// admission is dropped; see [`QuotaManager::admit_write`]. Admits
+    /// everything in standalone mode.
+    pub async fn admit_write(
+        &self,
+        store: &VectorStore,
+        tenant_id: &str,
+        requested: TenantUsage,
+    ) -> Result<WriteAdmission> {
+        if !self.config.enabled {
+            return Ok(WriteAdmission::default());
+        }
+        self.quota.admit_write(store, tenant_id, requested).await
+    }
+
+    /// [`Self::admit_write`] for inserting `vectors` vectors into
+    /// `collection`, counted at their raw `f32` size.
+    ///
+    /// The write is charged to `tenant_id`; callers that carry no hub
+    /// tenant (gRPC, MCP, UMICP, VectorizerRPC) pass `None` and charge
+    /// the tenant owning the collection, or `"default"` when nobody
+    /// owns it.
+    pub async fn admit_insert(
+        &self,
+        store: &VectorStore,
+        tenant_id: Option<&str>,
+        collection: &str,
+        vectors: usize,
+    ) -> Result<WriteAdmission> {
+        let (dimension, owner) = store
+            .get_collection(collection)
+            .map(|c| (c.config().dimension, c.owner_id()))
+            .unwrap_or((0, None));
+        let tenant_id = match (tenant_id, owner) {
+            (Some(tenant_id), _) => tenant_id.to_string(),
+            (None, Some(owner)) => owner.to_string(),
+            (None, None) => "default".to_string(),
+        };
+        let requested = TenantUsage {
+            vectors: vectors as u64,
+            bytes: (vectors * dimension * std::mem::size_of::<f32>()) as u64,
+            collections: 0,
+        };
+        self.admit_write(store, &tenant_id, requested).await
+    }
+
     /// Record usage for a collection
     ///
     /// In cluster mode, usage is tracked per-collection (by UUID).
@@ -2,18 +2,28 @@
 //!
 //! Provides quota checking and enforcement for multi-tenant operations,
 //! including storage limits, vector counts, and rate limiting.
+//!
+//! Writes go through [`QuotaManager::enforce_write`] before they touch
+//! the store: the node-local limits in `hub.tenant_quotas` are checked
+//! against what the tenant holds on this node ([`TenantUsage`]), then
+//! the plan quotas reported by HiveHub. [`QuotaManager::admit_write`]
+//! runs that check under a per-tenant lock and hands back a
+//! [`WriteAdmission`] the caller holds until its write has landed, so
+//! concurrent writes cannot all pass against the same usage.
 
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+use dashmap::DashMap;
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 use tracing::{debug, error, info, trace, warn};
 use vectorizer_core::metrics_sink::{MetricsSink, NoopMetricsSink};
 
-use super::HubCacheConfig;
 use super::client::HubClient;
+use super::{HubCacheConfig, TenantQuotaConfig};
+use crate::db::VectorStore;
 use crate::error::{Result, VectorizerError};
 
 /// Types of quotas that can be checked
@@ -147,6 +157,47 @@ impl CollectionQuota {
     }
 }
 
+/// What a tenant holds on this node, or what a write adds to it
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TenantUsage {
+    /// Vectors across the tenant's collections
+    pub vectors: u64,
+    /// Estimated memory of the tenant's collections, in bytes
+    pub bytes: u64,
+    /// Collections owned by the tenant
+    pub collections: u64,
+}
+
+impl TenantUsage {
+    /// Usage of `tenant_id` in `store`, summed over the collections it
+    /// owns. A tenant id that is not a UUID owns no collections.
+    pub fn measure(store: &VectorStore, tenant_id: &str) -> Self {
+        let mut usage = Self::default();
+        let Ok(owner) = uuid::Uuid::parse_str(tenant_id) else {
+            return usage;
+        };
+        for name in store.list_collections_for_owner(&owner) {
+            if let Ok(collection) = store.get_collection(&name) {
+                usage.vectors += collection.vector_count() as u64;
+                usage.bytes += collection.estimated_memory_usage() as u64;
+                usage.collections += 1;
+            }
+        }
+        usage
+    }
+}
+
+/// A tenant's write admission from [`QuotaManager::admit_write`].
+///
+/// Other writes for the same tenant wait while it is held, so the usage
+/// the write was checked against stays current until it lands. Drop it
+/// once the write is done.
+#[derive(Debug, Default)]
+#[must_use = "the admission only serializes the write while it is held"]
+pub struct WriteAdmission {
+    _guard: Option<tokio::sync::OwnedMutexGuard<()>>,
+}
+
 /// Rate limit quota details
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RateLimitQuota {
@@ -265,6 +316,10 @@ pub struct QuotaManager {
     cache_ttl: Duration,
     /// Maximum cache entries
     max_cache_entries: usize,
+    /// Node-local limits checked by [`Self::enforce_write`]
+    tenant_quotas: TenantQuotaConfig,
+    /// Write admission locks (tenant_id -> lock), see [`Self::admit_write`]
+    write_locks: DashMap<String, Arc<tokio::sync::Mutex<()>>>,
     /// Metrics sink for quota-check counters/gauges/latency.
     metrics: Arc<dyn MetricsSink>,
 }
@@ -290,10 +345,18 @@ impl QuotaManager {
             rate_limits: Arc::new(RwLock::new(HashMap::new())),
             cache_ttl: Duration::from_secs(cache_config.quota_ttl_seconds),
             max_cache_entries: cache_config.max_entries,
+            tenant_quotas: TenantQuotaConfig::default(),
+            write_locks: DashMap::new(),
             metrics,
         }
     }
 
+    /// Enforce `tenant_quotas` on this node in [`Self::enforce_write`].
+    pub fn with_tenant_quotas(mut self, tenant_quotas: TenantQuotaConfig) -> Self {
+        self.tenant_quotas = tenant_quotas;
+        self
+    }
+
     /// Check if a quota allows a specific operation
     pub async fn check_quota(
         &self,
@@ -405,6 +468,127 @@ impl QuotaManager {
         Ok(())
     }
 
+    /// Refuse a write that would take `tenant_id` past one of its
+    /// quotas.
+    ///
+    /// `usage` is what the tenant holds on this node and `requested`
+    /// what the write adds. The node-local limits are checked first and
+    /// cover vectors, bytes and collections; then the plan quotas from
+    /// HiveHub for vectors and collections, which fail open when
+    /// HiveHub cannot be reached. An allowed write is counted against
+    /// the cached plan quota right away, so a burst cannot overrun it
+    /// before the next refresh.
+    pub async fn enforce_write(
+        &self,
+        tenant_id: &str,
+        usage: TenantUsage,
+        requested: TenantUsage,
+    ) -> Result<()> {
+        let limits = self.tenant_quotas.limits_for(tenant_id);
+        let local = [
+            (
+                QuotaType::VectorCount,
+                limits.max_vectors,
+                usage.vectors,
+                requested.vectors,
+            ),
+            (
+                QuotaType::Storage,
+                limits.max_bytes,
+                usage.bytes,
+                requested.bytes,
+            ),
+            (
+                QuotaType::CollectionCount,
+                limits.max_collections,
+                usage.collections,
+                requested.collections,
+            ),
+        ];
+        for (quota_type, limit, used, wanted) in local {
+            if let Some(limit) = limit
+                && wanted > 0
+                && used.saturating_add(wanted) > limit
+            {
+                self.metrics
+                    .hub_quota_check(tenant_id, &quota_type.to_string(), false);
+                return Err(quota_exceeded(tenant_id, quota_type, limit, used, wanted));
+            }
+        }
+
+        let plan = match self.get_quota(tenant_id).await {
+            Ok(plan) => plan,
+            Err(e) => {
+                warn!(
+                    "Failed to fetch quota for tenant {}: {}; enforcing local limits only",
+                    tenant_id, e
+                );
+                return Ok(());
+            }
+        };
+        // HiveHub reports one limit for every quota type, so storage is
+        // left to the local `max_bytes`
+        let hub = [
+            (
+                QuotaType::VectorCount,
+                requested.vectors,
+                plan.vectors.can_use(requested.vectors),
+                plan.vectors.limit,
+                plan.vectors.used,
+            ),
+            (
+                QuotaType::CollectionCount,
+                requested.collections,
+                plan.collections.can_use(),
+                plan.collections.limit,
+                plan.collections.used,
+            ),
+        ];
+        for (quota_type, wanted, allowed, limit, used) in hub {
+            if wanted == 0 {
+                continue;
+            }
+            self.metrics
+                .hub_quota_check(tenant_id, &quota_type.to_string(), allowed);
+            if !allowed {
+                return Err(quota_exceeded(tenant_id, quota_type, limit, used, wanted));
+            }
+        }
+
+        self.update_local_usage(
+            tenant_id,
+            0,
+            requested.vectors as i64,
+            requested.collections as i64,
+        );
+        Ok(())
+    }
+
+    /// Admit a write adding `requested` to what `tenant_id` holds in
+    /// `store`.
+    ///
+    /// Takes the tenant's write lock, measures its usage and runs
+    /// [`Self::enforce_write`]. The returned [`WriteAdmission`] keeps the
+    /// lock, so hold it until the write is applied.
+    pub async fn admit_write(
+        &self,
+        store: &VectorStore,
+        tenant_id: &str,
+        requested: TenantUsage,
+    ) -> Result<WriteAdmission> {
+        let lock = self
+            .write_locks
+            .entry(tenant_id.to_string())
+            .or_default()
+            .clone();
+        let guard = lock.lock_owned().await;
+        let usage = TenantUsage::measure(store, tenant_id);
+        self.enforce_write(tenant_id, usage, requested).await?;
+        Ok(WriteAdmission {
+            _guard: Some(guard),
+        })
+    }
+
     /// Get cached quota if valid
     fn get_cached(&self, tenant_id: &str) -> Option<QuotaInfo> {
         let cache = self.cache.read();
@@ -593,7 +777,28 @@ impl QuotaManager {
     }
 }
 
+fn quota_exceeded(
+    tenant_id: &str,
+    quota_type: QuotaType,
+    limit: u64,
+    used: u64,
+    requested: u64,
+) -> VectorizerError {
+    debug!(
+        "Write refused for tenant {}: {} quota of {} ({} used, {} requested)",
+        tenant_id, quota_type, limit, used, requested
+    );
+    VectorizerError::TenantQuotaExceeded {
+        tenant_id: tenant_id.to_string(),
+        quota: quota_type.to_string(),
+        limit,
+        used,
+        requested,
+    }
+}
+
 #[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
 mod tests {
     use super::*;
 
@@ -681,6 +886,81 @@ mod tests {
         assert!(!full.can_use());
     }
 
+    #[tokio::test]
+    async fn test_enforce_write_local_limits() {
+        let mut tenant_quotas = TenantQuotaConfig::default();
+        tenant_quotas.default.max_vectors = Some(100);
+        tenant_quotas.tenants.insert(
+            "big".to_string(),
+            crate::hub::TenantLimits {
+                max_collections: Some(2),
+                ..Default::default()
+            },
+        );
+        let manager =
+            QuotaManager::new(Arc::new(HubClient::new_mock()), &HubCacheConfig::default())
+                .with_tenant_quotas(tenant_quotas);
+
+        let usage = TenantUsage {
+            vectors: 90,
+            bytes: 0,
+            collections: 2,
+        };
+        let insert = TenantUsage {
+            vectors: 11,
+            ..Default::default()
+        };
+        let err = manager.enforce_write("small", usage, insert).await;
+        assert!(matches!(
+            err,
+            Err(VectorizerError::TenantQuotaExceeded { ref quota, limit: 100, used: 90, requested: 11, .. })
+                if quota == "vector_count"
+        ));
+
+        // "big" replaces the default limits: vectors are unlimited, but
+        // a third collection is not allowed
+        let create = TenantUsage {
+            collections: 1,
+            ..Default::default()
+        };
+        let err = manager.enforce_write("big", usage, create).await;
+        assert!(matches!(
+            err,
+            Err(VectorizerError::TenantQuotaExceeded { ref quota, limit: 2, .. })
+                if quota == "collection_count"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_admit_write_holds_the_tenant_lock() {
+        let manager =
+            QuotaManager::new(Arc::new(HubClient::new_mock()), &HubCacheConfig::default());
+        let store = VectorStore::new();
+        let insert = TenantUsage {
+            vectors: 1,
+            ..Default::default()
+        };
+
+        let held = manager.admit_write(&store, "a", insert).await.unwrap();
+        let waiting = tokio::time::timeout(
+            Duration::from_millis(50),
+            manager.admit_write(&store, "a", insert),
+        )
+        .await;
+        assert!(
+            waiting.is_err(),
+            "a second write for the tenant was admitted"
+        );
+
+        // Other tenants are not held up
+        let other = manager.admit_write(&store, "b", insert).await.unwrap();
+        drop(other);
+
+        drop(held);
+        let admitted = manager.admit_write(&store, "a", insert).await;
+        assert!(admitted.is_ok());
+    }
+
     #[test]
     fn test_quota_type_display() {
         assert_eq!(QuotaType::Storage.to_string(), "storage");
@@ -7,6 +7,7 @@
 use crate::embedding::EmbeddingProvider;
 use crate::error::Result;
 use crate::evaluation::QueryResult;
+use crate::simd;
 
 /// Hybrid search retriever combining sparse and dense methods
 pub struct HybridRetriever<T: EmbeddingProvider, U: EmbeddingProvider> {
@@ -120,9 +121,12 @@ impl<T: EmbeddingProvider, U: EmbeddingProvider> HybridRetriever<T, U> {
 
 /// Calculate cosine similarity between two vectors
 fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
-    let dot_product: f32 = a.iter().zip(b.iter()).map(|(x, y)| x * y).sum();
-    let norm_a: f32 = a.iter().map(|x| x * x).sum::<f32>().sqrt();
-    let norm_b: f32 = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if a.len() != b.len() {
+        return 0.0;
+    }
+    let dot_product = simd::dot_product(a, b);
+    let norm_a = simd::l2_norm(a);
+    let norm_b = simd::l2_norm(b);
 
     if norm_a == 0.0 || norm_b == 0.0 {
         0.0
@@ -37,6 +37,8 @@ impl ExampleUsage {
             technical_focus: Some(true),
             mmr_enabled: Some(true),
             mmr_lambda: Some(0.7),
+            translate_to: None,
+            graph_expansion: None,
         };
 
         let response = handler.handle_intelligent_search(tool).await?;
@@ -81,6 +83,8 @@ impl ExampleUsage {
             max_per_collection: Some(3),
             max_total_results: Some(9),
             cross_collection_reranking: Some(true),
+            score_normalization: None,
+            collection_weights: None,
         };
 
         let response = handler.handle_multi_collection_search(tool).await?;
@@ -201,6 +205,8 @@ impl ExampleUsage {
             technical_focus: Some(true),
             mmr_enabled: Some(true),
             mmr_lambda: Some(0.8),
+            translate_to: None,
+            graph_expansion: None,
         };
 
         let response = handler
@@ -0,0 +1,479 @@
+//! Graph-augmented retrieval for intelligent search
+//!
+//! The answer to a multi-document question often sits in chunks that are
+//! not close to the query themselves, only to the chunks that are: the
+//! rest of the same file, the documents a hit cites, other chunks naming
+//! the same entity. With `graph_expansion` set on a request, the top hits
+//! in collections with a graph pull in their graph neighbors before
+//! reranking. A neighbor scores its hit's score times `score_decay` per
+//! hop, and is tagged with the hit it was reached from.
+//!
+//! A hop goes from a chunk to a chunk over one edge, or over two edges
+//! through a node that is not a chunk (an extracted entity, or the file
+//! node a reference points at), so two chunks naming the same entity are
+//! one hop apart. [`SAME_FILE`] is a virtual edge type between chunks
+//! sharing a `file_path`.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::mcp_tools::flatten_payload_metadata;
+use super::{IntelligentSearchResult, ScoreBreakdown};
+use crate::db::VectorStore;
+use crate::db::graph::{Graph, RelationshipType};
+
+/// Edge type linking the chunks that share a `file_path`
+pub const SAME_FILE: &str = "SAME_FILE";
+
+/// Metadata key of an expanded result, naming the hit it was reached
+/// from and how many hops away
+pub const EXPANSION_METADATA_KEY: &str = "graph_expansion";
+
+/// Longest walk a request may ask for; each hop multiplies the chunks
+/// visited
+const MAX_HOPS: usize = 3;
+
+/// Graph expansion options of an intelligent search request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GraphExpansion {
+    /// Hops to walk from each hit (1-3)
+    pub hops: usize,
+    /// Edge types to follow: graph relationship types (`SIMILAR_TO`,
+    /// `REFERENCES`, `CONTAINS`, `DERIVED_FROM`, `MENTIONS`,
+    /// `RELATED_TO`) and [`SAME_FILE`]; empty follows them all
+    pub edge_types: Vec<String>,
+    /// Most neighbors added per hit
+    pub max_neighbors: usize,
+    /// Score multiplier per hop, in (0, 1]
+    pub score_decay: f32,
+}
+
+impl Default for GraphExpansion {
+    fn default() -> Self {
+        Self {
+            hops: 1,
+            edge_types: Vec::new(),
+            max_neighbors: 5,
+            score_decay: 0.85,
+        }
+    }
+}
+
+impl GraphExpansion {
+    /// Options from a request's `graph_expansion` value: `true` for the
+    /// defaults, an object to set them, `false` or null for none
+    pub fn from_request(value: &Value) -> Result<Option<Self>, String> {
+        let options = match value {
+            Value::Null | Value::Bool(false) => return Ok(None),
+            Value::Bool(true) => Self::default(),
+            other => serde_json::from_value(other.clone()).map_err(|e| e.to_string())?,
+        };
+        options.validate()?;
+        Ok(Some(options))
+    }
+
+    /// Check the hop count, decay and edge types
+    pub fn validate(&self) -> Result<(), String> {
+        if self.hops == 0 || self.hops > MAX_HOPS {
+            return Err(format!("hops must be between 1 and {MAX_HOPS}"));
+        }
+        if !(self.score_decay > 0.0 && self.score_decay <= 1.0) {
+            return Err("score_decay must be in (0, 1]".to_string());
+        }
+        self.edge_filter().map(|_| ())
+    }
+
+    fn edge_filter(&self) -> Result<EdgeFilter, String> {
+        if self.edge_types.is_empty() {
+            return Ok(EdgeFilter {
+                relationships: None,
+                same_file: true,
+            });
+        }
+        let mut relationships = HashSet::new();
+        let mut same_file = false;
+        for name in &self.edge_types {
+            match parse_edge_type(name)? {
+                Some(relationship) => {
+                    relationships.insert(relationship);
+                }
+                None => same_file = true,
+            }
+        }
+        Ok(EdgeFilter {
+            relationships: Some(relationships),
+            same_file,
+        })
+    }
+}
+
+/// The edges a walk may follow
+struct EdgeFilter {
+    /// Relationship types followed; `None` follows every type
+    relationships: Option<HashSet<RelationshipType>>,
+    same_file: bool,
+}
+
+impl EdgeFilter {
+    fn follows(&self, relationship: RelationshipType) -> bool {
+        self.relationships
+            .as_ref()
+            .is_none_or(|types| types.contains(&relationship))
+    }
+}
+
+/// The relationship type named `name`, or `None` for [`SAME_FILE`]
+fn parse_edge_type(name: &str) -> Result<Option<RelationshipType>, String> {
+    let normalized = name.trim().to_uppercase().replace('-', "_");
+    if normalized == SAME_FILE {
+        return Ok(None);
+    }
+    serde_json::from_value(Value::String(normalized))
+        .map(Some)
+        .map_err(|_| {
+            let known: Vec<String> = RelationshipType::all()
+                .into_iter()
+                .filter_map(|t| serde_json::to_value(t).ok()?.as_str().map(String::from))
+                .chain(std::iter::once(SAME_FILE.to_string()))
+                .collect();
+            format!(
+                "unknown edge type '{}', expected one of {}",
+                name,
+                known.join(", ")
+            )
+        })
+}
+
+/// Chunks reached from `hits` through their collections' graphs, best
+/// first. Hits themselves are never returned, and a chunk reached from
+/// several hits keeps its best score. Collections without a graph add
+/// nothing.
+pub fn expand(
+    store: &VectorStore,
+    hits: &[IntelligentSearchResult],
+    options: &GraphExpansion,
+) -> Result<Vec<IntelligentSearchResult>, String> {
+    let filter = options.edge_filter()?;
+    let is_hit: HashSet<(&str, &str)> = hits
+        .iter()
+        .map(|hit| (hit.collection.as_str(), hit.doc_id.as_str()))
+        .collect();
+    // Walks stop early, but must get past the hits they run into
+    let walk_limit = options.max_neighbors + hits.len();
+
+    let mut walkers: HashMap<&str, Option<Walker<'_>>> = HashMap::new();
+    let mut expanded: HashMap<(String, String), IntelligentSearchResult> = HashMap::new();
+    for hit in hits {
+        let walker = walkers
+            .entry(hit.collection.as_str())
+            .or_insert_with(|| Walker::new(store, &hit.collection, &filter));
+        let Some(walker) = walker else {
+            continue;
+        };
+
+        let reached = walker.walk(&hit.doc_id, options.hops, walk_limit);
+        let neighbors = reached
+            .into_iter()
+            .filter(|(id, _)| !is_hit.contains(&(hit.collection.as_str(), id.as_str())))
+            .take(options.max_neighbors);
+        for (id, hops) in neighbors {
+            let score = hit.score * options.score_decay.powi(hops as i32);
+            let key = (hit.collection.clone(), id);
+            if expanded.get(&key).is_some_and(|known| known.score >= score) {
+                continue;
+            }
+            let Ok(vector) = store.get_vector(&hit.collection, &key.1) else {
+                continue;
+            };
+
+            let content = vector
+                .payload
+                .as_ref()
+                .and_then(|p| p.data.get("content"))
+                .and_then(|v| v.as_str())
+                .unwrap_or("")
+                .to_string();
+            let mut metadata = vector
+                .payload
+                .as_ref()
+                .map(flatten_payload_metadata)
+                .unwrap_or_default();
+            metadata.insert(
+                EXPANSION_METADATA_KEY.to_string(),
+                serde_json::json!({ "from": hit.doc_id, "hops": hops }),
+            );
+            let result = IntelligentSearchResult {
+                content,
+                score,
+                collection: hit.collection.clone(),
+                doc_id: key.1.clone(),
+                metadata,
+                score_breakdown: Some(ScoreBreakdown {
+                    relevance: score,
+                    collection_bonus: 0.0,
+                    technical_bonus: 0.0,
+                    final_score: score,
+                }),
+            };
+            expanded.insert(key, result);
+        }
+    }
+
+    let mut results: Vec<IntelligentSearchResult> = expanded.into_values().collect();
+    results.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.doc_id.cmp(&b.doc_id))
+    });
+    Ok(results)
+}
+
+/// Walks one collection's graph
+struct Walker<'a> {
+    store: &'a VectorStore,
+    collection: &'a str,
+    graph: Arc<Graph>,
+    filter: &'a EdgeFilter,
+    /// Whether a node is one of the collection's vectors
+    chunks: HashMap<String, bool>,
+    /// Node IDs by `file_path`, built on the first [`SAME_FILE`] step
+    files: Option<HashMap<String, Vec<String>>>,
+}
+
+impl<'a> Walker<'a> {
+    fn new(store: &'a VectorStore, collection: &'a str, filter: &'a EdgeFilter) -> Option<Self> {
+        // Clone the graph out rather than holding the collection guard
+        // across the walk's own store lookups
+        let graph = store.get_collection(collection).ok()?.get_graph()?.clone();
+        Some(Self {
+            store,
+            collection,
+            graph,
+            filter,
+            chunks: HashMap::new(),
+            files: None,
+        })
+    }
+
+    /// Chunks within `hops` of `start`, nearest first, until `limit`
+    fn walk(&mut self, start: &str, hops: usize, limit: usize) -> Vec<(String, usize)> {
+        let mut visited = HashSet::from([start.to_string()]);
+        let mut frontier = vec![start.to_string()];
+        let mut reached = Vec::new();
+        for hop in 1..=hops {
+            let mut next = Vec::new();
+            for id in &frontier {
+                for neighbor in self.step(id) {
+                    if visited.insert(neighbor.clone()) {
+                        reached.push((neighbor.clone(), hop));
+                        if reached.len() >= limit {
+                            return reached;
+                        }
+                        next.push(neighbor);
+                    }
+                }
+            }
+            frontier = next;
+        }
+        reached
+    }
+
+    /// Chunks one hop from chunk `id`: linked directly, through a node
+    /// that is not a chunk, or sharing its file
+    fn step(&mut self, id: &str) -> Vec<String> {
+        let mut chunks = Vec::new();
+        for node in self.linked(id) {
+            if self.is_chunk(&node) {
+                chunks.push(node);
+                continue;
+            }
+            for through in self.linked(&node) {
+                if through != id && self.is_chunk(&through) {
+                    chunks.push(through);
+                }
+            }
+        }
+        if self.filter.same_file {
+            for sibling in self.same_file(id) {
+                if sibling != id && self.is_chunk(&sibling) {
+                    chunks.push(sibling);
+                }
+            }
+        }
+        chunks
+    }
+
+    /// Nodes sharing a followed edge with `id`, in either direction,
+    /// strongest edge first
+    fn linked(&self, id: &str) -> Vec<String> {
+        let mut linked = self.graph.get_neighbors(id, None).unwrap_or_default();
+        linked.extend(
+            self.graph
+                .get_incoming_neighbors(id, None)
+                .unwrap_or_default(),
+        );
+        linked.retain(|(_, edge)| self.filter.follows(edge.relationship_type));
+        linked.sort_by(|(_, a), (_, b)| {
+            b.weight
+                .partial_cmp(&a.weight)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        linked.into_iter().map(|(node, _)| node.id).collect()
+    }
+
+    /// Nodes with the same `file_path` as `id`
+    fn same_file(&mut self, id: &str) -> Vec<String> {
+        let Some(path) = self
+            .graph
+            .get_node(id)
+            .and_then(|node| file_path(&node.metadata))
+        else {
+            return Vec::new();
+        };
+        let files = self.files.get_or_insert_with(|| {
+            let mut files: HashMap<String, Vec<String>> = HashMap::new();
+            for node in self.graph.get_all_nodes() {
+                if let Some(path) = file_path(&node.metadata) {
+                    files.entry(path).or_default().push(node.id);
+                }
+            }
+            files
+        });
+        files.get(&path).cloned().unwrap_or_default()
+    }
+
+    fn is_chunk(&mut self, id: &str) -> bool {
+        if let Some(&known) = self.chunks.get(id) {
+            return known;
+        }
+        let chunk = self.store.get_vector(self.collection, id).is_ok();
+        self.chunks.insert(id.to_string(), chunk);
+        chunk
+    }
+}
+
+fn file_path(metadata: &HashMap<String, Value>) -> Option<String> {
+    metadata
+        .get("file_path")
+        .and_then(Value::as_str)
+        .map(String::from)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::db::graph::Edge;
+    use crate::models::{CollectionConfig, GraphConfig, Payload, Vector};
+
+    fn store_with_graph() -> VectorStore {
+        let store = VectorStore::new();
+        let config = CollectionConfig {
+            dimension: 3,
+            graph: Some(GraphConfig {
+                enabled: true,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        store.create_collection("docs", config).unwrap();
+        let chunk = |id: &str, file: &str| {
+            Vector::with_payload(
+                id.to_string(),
+                vec![1.0, 0.0, 0.0],
+                Payload::new(json!({ "content": format!("chunk {id}"), "file_path": file })),
+            )
+        };
+        store
+            .insert(
+                "docs",
+                vec![
+                    chunk("a1", "a.md"),
+                    chunk("a2", "a.md"),
+                    chunk("b1", "b.md"),
+                    chunk("c1", "c.md"),
+                ],
+            )
+            .unwrap();
+        store
+    }
+
+    fn hit(id: &str, score: f32) -> IntelligentSearchResult {
+        IntelligentSearchResult {
+            content: String::new(),
+            score,
+            collection: "docs".to_string(),
+            doc_id: id.to_string(),
+            metadata: HashMap::new(),
+            score_breakdown: None,
+        }
+    }
+
+    #[test]
+    fn test_request_values() {
+        assert_eq!(GraphExpansion::from_request(&Value::Null).unwrap(), None);
+        assert_eq!(
+            GraphExpansion::from_request(&json!(true)).unwrap(),
+            Some(GraphExpansion::default())
+        );
+        let options =
+            GraphExpansion::from_request(&json!({ "hops": 2, "edge_types": ["mentions"] }))
+                .unwrap()
+                .unwrap();
+        assert_eq!(options.hops, 2);
+        assert!(GraphExpansion::from_request(&json!({ "hops": 9 })).is_err());
+        assert!(GraphExpansion::from_request(&json!({ "edge_types": ["CITES"] })).is_err());
+    }
+
+    #[test]
+    fn test_expands_through_same_file_and_shared_entities() {
+        let store = store_with_graph();
+        {
+            let collection = store.get_collection("docs").unwrap();
+            let graph = collection.get_graph().unwrap();
+            let mut entity = crate::db::graph::Node::new("entity:org:acme".into(), "entity".into());
+            entity.metadata.insert("name".into(), json!("Acme"));
+            graph.add_node(entity).unwrap();
+            for chunk in ["a1", "c1"] {
+                graph
+                    .add_edge(Edge::new(
+                        format!("{chunk}:entity:org:acme:MENTIONS"),
+                        chunk.to_string(),
+                        "entity:org:acme".to_string(),
+                        RelationshipType::Mentions,
+                        1.0,
+                    ))
+                    .unwrap();
+            }
+        }
+
+        let options = GraphExpansion::default();
+        let expanded = expand(&store, &[hit("a1", 0.9)], &options).unwrap();
+        let ids: HashSet<&str> = expanded.iter().map(|r| r.doc_id.as_str()).collect();
+        assert_eq!(ids, HashSet::from(["a2", "c1"]));
+        for result in &expanded {
+            assert!((result.score - 0.9 * 0.85).abs() < 1e-6);
+            assert_eq!(result.metadata[EXPANSION_METADATA_KEY]["from"], "a1");
+            assert_eq!(result.content, format!("chunk {}", result.doc_id));
+        }
+
+        let only_entities = GraphExpansion {
+            edge_types: vec!["MENTIONS".to_string()],
+            ..Default::default()
+        };
+        let expanded = expand(&store, &[hit("a1", 0.9)], &only_entities).unwrap();
+        assert_eq!(expanded.len(), 1);
+        assert_eq!(expanded[0].doc_id, "c1");
+
+        // Hits are never returned as their own neighbors
+        let expanded = expand(&store, &[hit("a1", 0.9), hit("c1", 0.8)], &only_entities).unwrap();
+        assert!(expanded.is_empty());
+    }
+}
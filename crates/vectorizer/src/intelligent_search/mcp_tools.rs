@@ -33,7 +33,7 @@ use crate::intelligent_search::*;
 /// The original `metadata` sub-object is preserved as-is so consumers
 /// that explicitly read `result.metadata.metadata.<field>` keep working
 /// during the deprecation window.
-fn flatten_payload_metadata(
+pub(super) fn flatten_payload_metadata(
     payload: &crate::models::Payload,
 ) -> HashMap<String, serde_json::Value> {
     let Some(obj) = payload.data.as_object() else {
@@ -51,6 +51,43 @@ fn flatten_payload_metadata(
     out
 }
 
+/// Normalize each collection's scores within `results` on their own and
+/// apply the collection's weight. `score_breakdown.relevance` keeps the
+/// score the collection reported.
+fn calibrate_collection_scores(
+    results: &mut [IntelligentSearchResult],
+    normalization: crate::search::ScoreNormalization,
+    weights: Option<&HashMap<String, f32>>,
+) {
+    let mut by_collection: HashMap<String, Vec<usize>> = HashMap::new();
+    for (index, result) in results.iter().enumerate() {
+        by_collection
+            .entry(result.collection.clone())
+            .or_default()
+            .push(index);
+    }
+    for (collection, indices) in by_collection {
+        let weight = weights
+            .and_then(|w| w.get(&collection))
+            .copied()
+            .unwrap_or(1.0);
+        let mut scores: Vec<f32> = indices.iter().map(|&i| results[i].score).collect();
+        normalization.apply(&mut scores);
+        for (&i, normalized) in indices.iter().zip(scores) {
+            let result = &mut results[i];
+            let final_score = normalized * weight;
+            let breakdown = result.score_breakdown.get_or_insert(ScoreBreakdown {
+                relevance: result.score,
+                collection_bonus: 0.0,
+                technical_bonus: 0.0,
+                final_score,
+            });
+            breakdown.final_score = final_score;
+            result.score = final_score;
+        }
+    }
+}
+
 /// MCP Tool: Intelligent Search
 #[derive(Debug, Serialize, Deserialize)]
 pub struct IntelligentSearchTool {
@@ -61,6 +98,14 @@ pub struct IntelligentSearchTool {
     pub technical_focus: Option<bool>,
     pub mmr_enabled: Option<bool>,
     pub mmr_lambda: Option<f32>,
+    /// Language codes to also search the query translated into
+    /// (`["pt"]`); the server's `intelligent_search.translation` targets
+    /// apply when unset
+    pub translate_to: Option<Vec<String>>,
+    /// Expand the top hits through their collections' graphs before
+    /// reranking
+    #[serde(default)]
+    pub graph_expansion: Option<graph_expansion::GraphExpansion>,
 }
 
 /// MCP Tool: Multi Collection Search
@@ -71,6 +116,15 @@ pub struct MultiCollectionSearchTool {
     pub max_per_collection: Option<usize>,
     pub max_total_results: Option<usize>,
     pub cross_collection_reranking: Option<bool>,
+    /// Rescale each collection's scores before merging, so collections
+    /// with different metrics or embedding models compete fairly; raw
+    /// scores are merged when unset
+    #[serde(default)]
+    pub score_normalization: Option<crate::search::ScoreNormalization>,
+    /// Multiplier of each named collection's (normalized) scores;
+    /// unnamed collections weigh 1.0
+    #[serde(default)]
+    pub collection_weights: Option<HashMap<String, f32>>,
 }
 
 /// MCP Tool: Semantic Search
@@ -114,6 +168,7 @@ pub struct ToolMetadata {
 pub struct MCPToolHandler {
     store: std::sync::Arc<crate::VectorStore>,
     embedding_manager: std::sync::Arc<crate::embedding::EmbeddingManager>,
+    translation: std::sync::Arc<translation::QueryTranslation>,
 }
 
 impl MCPToolHandler {
@@ -125,6 +180,7 @@ impl MCPToolHandler {
         Self {
             store,
             embedding_manager,
+            translation: translation::get_global_query_translation(),
         }
     }
 
@@ -141,9 +197,19 @@ impl MCPToolHandler {
         Self {
             store,
             embedding_manager: std::sync::Arc::new(placeholder_manager),
+            translation: translation::get_global_query_translation(),
         }
     }
 
+    /// Translate queries with `translation` instead of the global one
+    pub fn with_translation(
+        mut self,
+        translation: std::sync::Arc<translation::QueryTranslation>,
+    ) -> Self {
+        self.translation = translation;
+        self
+    }
+
     /// Helper function to create an embedding manager for a specific collection
     fn create_embedding_manager_for_collection(
         &self,
@@ -228,6 +294,14 @@ impl MCPToolHandler {
         tool: IntelligentSearchTool,
     ) -> Result<MCPToolResponse, String> {
         let max_results = tool.max_results.unwrap_or(10);
+        let requested_languages = tool
+            .translate_to
+            .as_deref()
+            .map(translation::parse_languages)
+            .transpose()?;
+        if let Some(options) = &tool.graph_expansion {
+            options.validate()?;
+        }
         let all_collections = tool
             .collections
             .unwrap_or_else(|| self.store.list_collections());
@@ -260,8 +334,13 @@ impl MCPToolHandler {
         let mut total_queries = 0;
 
         // Generate multiple queries for intelligent search
-        let queries =
+        let mut queries =
             self.generate_intelligent_queries(&tool.query, tool.domain_expansion.unwrap_or(true));
+
+        // Translated variants reach documents written in other languages
+        let targets = self.translation.targets(requested_languages.as_deref());
+        let translated_queries = self.translation.variants(&tool.query, &targets).await;
+        queries.extend(translated_queries.iter().cloned());
         total_queries = queries.len();
 
         // Search each prioritized collection with each query
@@ -332,7 +411,32 @@ impl MCPToolHandler {
         }
 
         // Apply deduplication
-        let deduped_results = self.deduplicate_results(&all_results);
+        let mut deduped_results = self.deduplicate_results(&all_results);
+
+        // Pull the graph neighbors of the top hits in before reranking
+        let mut graph_expanded = 0;
+        if let Some(options) = &tool.graph_expansion {
+            let mut seeds = deduped_results.clone();
+            seeds.sort_by(|a, b| {
+                b.score
+                    .partial_cmp(&a.score)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+            seeds.truncate(max_results);
+            let mut neighbors = graph_expansion::expand(&self.store, &seeds, options)?;
+            neighbors.retain(|neighbor| {
+                !deduped_results.iter().any(|result| {
+                    result.collection == neighbor.collection && result.doc_id == neighbor.doc_id
+                })
+            });
+            graph_expanded = neighbors.len();
+            deduped_results.extend(neighbors);
+            deduped_results.sort_by(|a, b| {
+                b.score
+                    .partial_cmp(&a.score)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+        }
 
         // Apply MMR diversification if enabled
         let final_results = if tool.mmr_enabled.unwrap_or(true) {
@@ -368,6 +472,14 @@ impl MCPToolHandler {
             "deduplication_applied".to_string(),
             serde_json::Value::Bool(true),
         );
+        tool_metadata.insert(
+            "translated_queries".to_string(),
+            serde_json::json!(translated_queries),
+        );
+        tool_metadata.insert(
+            "graph_expanded_results".to_string(),
+            serde_json::Value::Number(serde_json::Number::from(graph_expanded)),
+        );
         tool_metadata.insert(
             "mmr_applied".to_string(),
             serde_json::Value::Bool(tool.mmr_enabled.unwrap_or(true)),
@@ -402,6 +514,17 @@ impl MCPToolHandler {
     ) -> Result<MCPToolResponse, String> {
         let max_per_collection = tool.max_per_collection.unwrap_or(5);
         let max_total_results = tool.max_total_results.unwrap_or(20);
+        if let Some((collection, weight)) = tool
+            .collection_weights
+            .iter()
+            .flatten()
+            .find(|(_, weight)| !(weight.is_finite() && **weight >= 0.0))
+        {
+            return Err(format!(
+                "collection_weights: weight of '{}' must be >= 0, got {}",
+                collection, weight
+            ));
+        }
 
         let mut all_results = Vec::new();
         let mut collection_results = HashMap::new();
@@ -458,11 +581,26 @@ impl MCPToolHandler {
             }
         }
 
+        // Put every collection's scores on one scale before ranking
+        let calibrated = tool.score_normalization.is_some() || tool.collection_weights.is_some();
+        let mut ranked = all_results.clone();
+        if calibrated {
+            calibrate_collection_scores(
+                &mut ranked,
+                tool.score_normalization
+                    .unwrap_or(crate::search::ScoreNormalization::None),
+                tool.collection_weights.as_ref(),
+            );
+        }
+
         // Apply cross-collection reranking if enabled
         let final_results = if tool.cross_collection_reranking.unwrap_or(true) {
-            self.cross_collection_rerank(all_results.clone(), &tool.collections)
+            self.cross_collection_rerank(ranked, &tool.collections)
         } else {
-            all_results.clone()
+            if calibrated {
+                ranked.sort_by(|a, b| b.score.total_cmp(&a.score));
+            }
+            ranked
         };
 
         // Limit to max_total_results
@@ -491,6 +629,13 @@ impl MCPToolHandler {
             "cross_collection_reranking".to_string(),
             serde_json::Value::Bool(tool.cross_collection_reranking.unwrap_or(true)),
         );
+        tool_metadata.insert(
+            "score_normalization".to_string(),
+            serde_json::json!(
+                tool.score_normalization
+                    .unwrap_or(crate::search::ScoreNormalization::None)
+            ),
+        );
 
         for (collection, count) in &collection_results {
             tool_metadata.insert(
@@ -1165,3 +1310,45 @@ mod flatten_payload_tests {
         );
     }
 }
+
+#[cfg(test)]
+mod calibration_tests {
+    use super::*;
+    use crate::search::ScoreNormalization;
+
+    fn hit(collection: &str, id: &str, score: f32) -> IntelligentSearchResult {
+        IntelligentSearchResult {
+            content: String::new(),
+            score,
+            collection: collection.to_string(),
+            doc_id: id.to_string(),
+            metadata: HashMap::new(),
+            score_breakdown: None,
+        }
+    }
+
+    #[test]
+    fn each_collection_is_normalized_on_its_own() {
+        // Cosine scores near 1.0 against sparse scores in the tens: raw
+        // merging would rank every sparse hit first.
+        let mut results = vec![
+            hit("dense", "d1", 0.91),
+            hit("dense", "d2", 0.83),
+            hit("sparse", "s1", 42.0),
+            hit("sparse", "s2", 12.0),
+        ];
+        calibrate_collection_scores(&mut results, ScoreNormalization::MinMax, None);
+        let scores: Vec<f32> = results.iter().map(|r| r.score).collect();
+        assert_eq!(scores, [1.0, 0.0, 1.0, 0.0]);
+        assert_eq!(
+            results[2].score_breakdown.as_ref().map(|b| b.relevance),
+            Some(42.0)
+        );
+
+        let weights = HashMap::from([("sparse".to_string(), 0.5)]);
+        let mut results = vec![hit("dense", "d1", 0.91), hit("sparse", "s1", 42.0)];
+        calibrate_collection_scores(&mut results, ScoreNormalization::Rrf, Some(&weights));
+        assert_eq!(results[0].score, 1.0 / 61.0);
+        assert_eq!(results[1].score, 0.5 / 61.0);
+    }
+}
@@ -5,12 +5,14 @@
 
 pub mod context_formatter;
 pub mod examples;
+pub mod graph_expansion;
 pub mod mcp_server_integration;
 pub mod mcp_tools;
 pub mod mmr_diversifier;
 pub mod query_generator;
 pub mod rest_api;
 pub mod simple_search_engine;
+pub mod translation;
 
 use std::collections::HashMap;
 
@@ -35,6 +37,9 @@ pub struct IntelligentSearchConfig {
     pub mmr_enabled: bool,
     /// MMR lambda parameter (0.0 = diversity, 1.0 = relevance)
     pub mmr_lambda: f32,
+    /// Translated query variants for cross-lingual search
+    #[serde(default)]
+    pub translation: translation::QueryTranslationConfig,
 }
 
 impl Default for IntelligentSearchConfig {
@@ -48,6 +53,7 @@ impl Default for IntelligentSearchConfig {
             reranking_enabled: true,
             mmr_enabled: true,
             mmr_lambda: 0.7,
+            translation: translation::QueryTranslationConfig::default(),
         }
     }
 }
@@ -59,6 +65,7 @@ pub struct IntelligentSearchEngine {
     search_engine: simple_search_engine::SimpleSearchEngine,
     mmr_diversifier: mmr_diversifier::MMRDiversifier,
     context_formatter: context_formatter::ContextFormatter,
+    translation: translation::QueryTranslation,
 }
 
 impl IntelligentSearchEngine {
@@ -72,6 +79,7 @@ impl IntelligentSearchEngine {
             3,    // max_lines_per_result
             true, // include_metadata
         );
+        let translation = translation::QueryTranslation::new(config.translation.clone());
 
         Self {
             config,
@@ -79,6 +87,7 @@ impl IntelligentSearchEngine {
             search_engine,
             mmr_diversifier,
             context_formatter,
+            translation,
         }
     }
 
@@ -100,8 +109,10 @@ impl IntelligentSearchEngine {
         let start_time = std::time::Instant::now();
         let max_results = max_results.unwrap_or(10);
 
-        // Generate multiple queries
-        let queries = self.query_generator.generate_queries(query);
+        // Generate multiple queries, plus translations for cross-lingual recall
+        let mut queries = self.query_generator.generate_queries(query);
+        let targets = self.translation.targets(None);
+        queries.extend(self.translation.variants(query, &targets).await);
 
         // Search across collections
         let mut all_results = Vec::new();
@@ -200,6 +211,7 @@ impl IntelligentSearchEngine {
 
     /// Update configuration
     pub fn update_config(&mut self, config: IntelligentSearchConfig) {
+        self.translation = translation::QueryTranslation::new(config.translation.clone());
         self.config = config;
     }
 }
@@ -30,6 +30,12 @@ pub struct IntelligentSearchRequest {
     pub mmr_enabled: Option<bool>,
     /// MMR lambda parameter
     pub mmr_lambda: Option<f32>,
+    /// Language codes to also search the query translated into
+    #[serde(default)]
+    pub translate_to: Option<Vec<String>>,
+    /// Expand the top hits through their collections' graphs
+    #[serde(default)]
+    pub graph_expansion: Option<graph_expansion::GraphExpansion>,
 }
 
 /// REST API Request for Multi Collection Search
@@ -45,6 +51,12 @@ pub struct MultiCollectionSearchRequest {
     pub max_total_results: Option<usize>,
     /// Enable cross-collection reranking
     pub cross_collection_reranking: Option<bool>,
+    /// Rescale each collection's scores before merging
+    #[serde(default)]
+    pub score_normalization: Option<crate::search::ScoreNormalization>,
+    /// Multiplier of each named collection's scores
+    #[serde(default)]
+    pub collection_weights: Option<HashMap<String, f32>>,
 }
 
 /// REST API Request for Semantic Search
@@ -145,6 +157,8 @@ impl RESTAPIHandler {
             technical_focus: request.technical_focus,
             mmr_enabled: request.mmr_enabled,
             mmr_lambda: request.mmr_lambda,
+            translate_to: request.translate_to,
+            graph_expansion: request.graph_expansion,
         };
 
         match self.mcp_handler.handle_intelligent_search(tool).await {
@@ -175,6 +189,8 @@ impl RESTAPIHandler {
             max_per_collection: request.max_per_collection,
             max_total_results: request.max_total_results,
             cross_collection_reranking: request.cross_collection_reranking,
+            score_normalization: request.score_normalization,
+            collection_weights: request.collection_weights,
         };
 
         match self.mcp_handler.handle_multi_collection_search(tool).await {
@@ -475,6 +491,8 @@ mod tests {
             technical_focus: Some(true),
             mmr_enabled: Some(true),
             mmr_lambda: Some(0.7),
+            translate_to: None,
+            graph_expansion: None,
         };
 
         let serialized = serde_json::to_string(&request).unwrap();
@@ -498,6 +516,8 @@ mod tests {
             technical_focus: None,
             mmr_enabled: None,
             mmr_lambda: None,
+            translate_to: None,
+            graph_expansion: None,
         };
         assert!(
             handler
@@ -514,6 +534,8 @@ mod tests {
             technical_focus: None,
             mmr_enabled: None,
             mmr_lambda: None,
+            translate_to: None,
+            graph_expansion: None,
         };
         assert!(
             handler
@@ -530,6 +552,8 @@ mod tests {
             technical_focus: None,
             mmr_enabled: None,
             mmr_lambda: None,
+            translate_to: None,
+            graph_expansion: None,
         };
         assert!(
             handler
@@ -549,6 +573,8 @@ mod tests {
             max_per_collection: Some(5),
             max_total_results: Some(10),
             cross_collection_reranking: Some(true),
+            score_normalization: None,
+            collection_weights: None,
         };
         assert!(
             handler
@@ -563,6 +589,8 @@ mod tests {
             max_per_collection: None,
             max_total_results: None,
             cross_collection_reranking: None,
+            score_normalization: None,
+            collection_weights: None,
         };
         assert!(
             handler
@@ -0,0 +1,572 @@
+//! Query translation for cross-lingual search
+//!
+//! Lexical embeddings (BM25, TF-IDF) only match the words a document
+//! uses, so an English query finds nothing in Portuguese documents and
+//! vice versa. With translation on, intelligent search also runs the
+//! query translated into each of the configured target languages. The
+//! translations come from a [`QueryTranslator`]: the built-in
+//! [`DictionaryTranslator`] (a technical glossary, extendable from
+//! config) or the [`LlmTranslator`] (an OpenAI-compatible chat
+//! completions endpoint).
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tracing::warn;
+
+use crate::language::{self, Language};
+
+/// Longest glossary term, in words, matched against a query
+const MAX_TERM_WORDS: usize = 3;
+
+/// Glossary entry: the same term in several languages
+pub type GlossaryEntry = BTreeMap<Language, String>;
+
+/// Where translations come from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TranslationProvider {
+    /// Word-by-word through the glossary; offline and instant
+    #[default]
+    Dictionary,
+    /// An OpenAI-compatible chat completions endpoint
+    Llm,
+}
+
+/// Settings of the LLM translation provider
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LlmTranslationConfig {
+    /// Chat completions URL
+    pub endpoint: String,
+    /// Model name
+    pub model: String,
+    /// API key (`OPENAI_API_KEY` when unset)
+    pub api_key: Option<String>,
+    /// Per-translation timeout, in seconds
+    pub timeout_seconds: u64,
+}
+
+impl Default for LlmTranslationConfig {
+    fn default() -> Self {
+        Self {
+            endpoint: "https://api.openai.com/v1/chat/completions".to_string(),
+            model: "gpt-4o-mini".to_string(),
+            api_key: None,
+            timeout_seconds: 10,
+        }
+    }
+}
+
+/// Query translation settings of intelligent search
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QueryTranslationConfig {
+    /// Translate every query (off by default; requests can still ask
+    /// for translations with `translate_to`)
+    pub enabled: bool,
+    /// Languages queries are translated into
+    pub target_languages: Vec<Language>,
+    /// Where translations come from
+    pub provider: TranslationProvider,
+    /// Glossary entries added to the built-in ones, e.g.
+    /// `{en: invoice, pt: fatura}`
+    pub dictionary: Vec<GlossaryEntry>,
+    /// Settings of the `llm` provider
+    pub llm: LlmTranslationConfig,
+}
+
+impl Default for QueryTranslationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            target_languages: vec![Language::English, Language::Portuguese],
+            provider: TranslationProvider::Dictionary,
+            dictionary: Vec::new(),
+            llm: LlmTranslationConfig::default(),
+        }
+    }
+}
+
+/// Translates search queries
+#[async_trait]
+pub trait QueryTranslator: Send + Sync {
+    /// `query` in `target`, or `None` when the translator cannot help.
+    /// `source` is the query's language when it was detected.
+    async fn translate(
+        &self,
+        query: &str,
+        source: Option<Language>,
+        target: Language,
+    ) -> Result<Option<String>, String>;
+}
+
+/// Built-in English/Portuguese glossary of common technical terms
+const BUILTIN_GLOSSARY: &[(&str, &str)] = &[
+    ("sale", "venda"),
+    ("report", "relatório"),
+    ("revenue", "receita"),
+    ("customer", "cliente"),
+    ("invoice", "fatura"),
+    ("payment", "pagamento"),
+    ("price", "preço"),
+    ("order", "pedido"),
+    ("product", "produto"),
+    ("user", "usuário"),
+    ("account", "conta"),
+    ("password", "senha"),
+    ("login", "login"),
+    ("error", "erro"),
+    ("bug", "defeito"),
+    ("failure", "falha"),
+    ("warning", "aviso"),
+    ("configuration", "configuração"),
+    ("setting", "configuração"),
+    ("installation", "instalação"),
+    ("deployment", "implantação"),
+    ("database", "banco de dados"),
+    ("table", "tabela"),
+    ("query", "consulta"),
+    ("search", "busca"),
+    ("index", "índice"),
+    ("file", "arquivo"),
+    ("folder", "pasta"),
+    ("document", "documento"),
+    ("server", "servidor"),
+    ("network", "rede"),
+    ("security", "segurança"),
+    ("permission", "permissão"),
+    ("authentication", "autenticação"),
+    ("encryption", "criptografia"),
+    ("backup", "cópia de segurança"),
+    ("performance", "desempenho"),
+    ("memory", "memória"),
+    ("storage", "armazenamento"),
+    ("cache", "cache"),
+    ("request", "requisição"),
+    ("response", "resposta"),
+    ("test", "teste"),
+    ("release", "versão"),
+    ("update", "atualização"),
+    ("schedule", "agenda"),
+    ("meeting", "reunião"),
+    ("contract", "contrato"),
+    ("employee", "funcionário"),
+    ("company", "empresa"),
+    ("tax", "imposto"),
+    ("budget", "orçamento"),
+    ("cost", "custo"),
+    ("machine learning", "aprendizado de máquina"),
+    ("source code", "código fonte"),
+];
+
+/// The built-in English/Portuguese glossary
+pub fn builtin_glossary() -> Vec<GlossaryEntry> {
+    BUILTIN_GLOSSARY
+        .iter()
+        .map(|&(english, portuguese)| {
+            BTreeMap::from([
+                (Language::English, english.to_string()),
+                (Language::Portuguese, portuguese.to_string()),
+            ])
+        })
+        .collect()
+}
+
+/// Translates queries term by term through a glossary. Terms are
+/// matched by stem, so "reports" finds the "report" entry; words the
+/// glossary does not know (product names, identifiers) are kept as-is.
+#[derive(Debug, Clone, Default)]
+pub struct DictionaryTranslator {
+    entries: Vec<GlossaryEntry>,
+    /// Per language, analyzed term -> entry index
+    index: HashMap<Language, HashMap<String, usize>>,
+}
+
+impl DictionaryTranslator {
+    /// A translator over `entries`; a term listed twice keeps its first entry
+    pub fn new(entries: Vec<GlossaryEntry>) -> Self {
+        let mut index: HashMap<Language, HashMap<String, usize>> = HashMap::new();
+        for (i, entry) in entries.iter().enumerate() {
+            for (&language, term) in entry {
+                let key = language::analyze(term, language).join(" ");
+                if !key.is_empty() {
+                    index.entry(language).or_default().entry(key).or_insert(i);
+                }
+            }
+        }
+        Self { entries, index }
+    }
+
+    /// A translator over the built-in glossary plus `extra` entries,
+    /// which win over built-in ones for the same term
+    pub fn with_builtin(extra: Vec<GlossaryEntry>) -> Self {
+        let mut entries = extra;
+        entries.extend(builtin_glossary());
+        Self::new(entries)
+    }
+
+    /// `query` with every known term replaced by its `target` form, or
+    /// `None` when no term was known
+    pub fn translate_terms(
+        &self,
+        query: &str,
+        source: Option<Language>,
+        target: Language,
+    ) -> Option<String> {
+        let sources: Vec<Language> = match source {
+            Some(language) => vec![language],
+            None => Language::ALL
+                .into_iter()
+                .filter(|language| *language != target && self.index.contains_key(language))
+                .collect(),
+        };
+
+        let words: Vec<&str> = query.split_whitespace().collect();
+        let mut output = Vec::with_capacity(words.len());
+        let mut translated = false;
+        let mut i = 0;
+        while i < words.len() {
+            let longest = MAX_TERM_WORDS.min(words.len() - i);
+            let found = (1..=longest).rev().find_map(|n| {
+                let phrase = words[i..i + n].join(" ");
+                sources
+                    .iter()
+                    .find_map(|&language| self.lookup(&phrase, language, target))
+                    .map(|term| (n, term))
+            });
+            match found {
+                Some((n, term)) => {
+                    output.push(term);
+                    translated = true;
+                    i += n;
+                }
+                None => {
+                    output.push(words[i]);
+                    i += 1;
+                }
+            }
+        }
+        translated.then(|| output.join(" "))
+    }
+
+    fn lookup(&self, phrase: &str, language: Language, target: Language) -> Option<&str> {
+        let key = language::analyze(phrase, language).join(" ");
+        if key.is_empty() {
+            return None;
+        }
+        let entry = &self.entries[*self.index.get(&language)?.get(&key)?];
+        entry.get(&target).map(String::as_str)
+    }
+}
+
+#[async_trait]
+impl QueryTranslator for DictionaryTranslator {
+    async fn translate(
+        &self,
+        query: &str,
+        source: Option<Language>,
+        target: Language,
+    ) -> Result<Option<String>, String> {
+        Ok(self.translate_terms(query, source, target))
+    }
+}
+
+/// Translates queries with an OpenAI-compatible chat completions model
+pub struct LlmTranslator {
+    config: LlmTranslationConfig,
+    client: reqwest::Client,
+}
+
+impl LlmTranslator {
+    /// A translator calling the endpoint in `config`
+    pub fn new(config: LlmTranslationConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_seconds.max(1)))
+            .build()
+            .unwrap_or_default();
+        Self { config, client }
+    }
+}
+
+#[async_trait]
+impl QueryTranslator for LlmTranslator {
+    async fn translate(
+        &self,
+        query: &str,
+        source: Option<Language>,
+        target: Language,
+    ) -> Result<Option<String>, String> {
+        let api_key = self
+            .config
+            .api_key
+            .clone()
+            .or_else(|| std::env::var("OPENAI_API_KEY").ok())
+            .ok_or_else(|| {
+                "LLM translation needs llm.api_key or the OPENAI_API_KEY environment variable"
+                    .to_string()
+            })?;
+        let from = source
+            .map(|language| format!(" from {}", language.name()))
+            .unwrap_or_default();
+        let payload = json!({
+            "model": self.config.model,
+            "messages": [
+                {
+                    "role": "system",
+                    "content": "You translate search queries. Reply with the translated query only, without quotes or explanations. Keep product names, identifiers and code as they are."
+                },
+                {
+                    "role": "user",
+                    "content": format!("Translate this search query{} into {}:\n\n{}", from, target.name(), query)
+                }
+            ],
+            "temperature": 0.0
+        });
+
+        let response = self
+            .client
+            .post(&self.config.endpoint)
+            .bearer_auth(api_key)
+            .json(&payload)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to reach the translation model: {}", e))?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(format!("Translation model error ({}): {}", status, body));
+        }
+        let body: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse the translation: {}", e))?;
+        let translation = body
+            .pointer("/choices/0/message/content")
+            .and_then(|content| content.as_str())
+            .ok_or_else(|| "Invalid response format from the translation model".to_string())?
+            .trim()
+            .trim_matches('"')
+            .trim();
+        Ok((!translation.is_empty()).then(|| translation.to_string()))
+    }
+}
+
+/// The configured translator, turning queries into translated variants
+#[derive(Clone)]
+pub struct QueryTranslation {
+    config: QueryTranslationConfig,
+    translator: Arc<dyn QueryTranslator>,
+}
+
+impl QueryTranslation {
+    /// The translation `config` describes
+    pub fn new(config: QueryTranslationConfig) -> Self {
+        let translator: Arc<dyn QueryTranslator> = match config.provider {
+            TranslationProvider::Dictionary => Arc::new(DictionaryTranslator::with_builtin(
+                config.dictionary.clone(),
+            )),
+            TranslationProvider::Llm => Arc::new(LlmTranslator::new(config.llm.clone())),
+        };
+        Self { config, translator }
+    }
+
+    /// Translation through a custom `translator`
+    pub fn with_translator(
+        config: QueryTranslationConfig,
+        translator: Arc<dyn QueryTranslator>,
+    ) -> Self {
+        Self { config, translator }
+    }
+
+    /// The translation settings
+    pub fn config(&self) -> &QueryTranslationConfig {
+        &self.config
+    }
+
+    /// The languages a query is translated into: `requested` when
+    /// given, else the configured targets when translation is enabled
+    pub fn targets(&self, requested: Option<&[Language]>) -> Vec<Language> {
+        match requested {
+            Some(languages) => languages.to_vec(),
+            None if self.config.enabled => self.config.target_languages.clone(),
+            None => Vec::new(),
+        }
+    }
+
+    /// `query` translated into each of `targets` other than its own
+    /// language, without duplicates or copies of the query. A failing
+    /// translation is logged and skipped: search goes on without it.
+    pub async fn variants(&self, query: &str, targets: &[Language]) -> Vec<String> {
+        let source = language::detect(query);
+        let mut variants: Vec<String> = Vec::new();
+        for &target in targets {
+            if Some(target) == source {
+                continue;
+            }
+            match self.translator.translate(query, source, target).await {
+                Ok(Some(translation)) => {
+                    let translation = translation.trim();
+                    let known = translation.is_empty()
+                        || translation.eq_ignore_ascii_case(query.trim())
+                        || variants.iter().any(|v| v.eq_ignore_ascii_case(translation));
+                    if !known {
+                        variants.push(translation.to_string());
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => warn!("Query translation into {} failed: {}", target, e),
+            }
+        }
+        variants
+    }
+}
+
+impl Default for QueryTranslation {
+    fn default() -> Self {
+        Self::new(QueryTranslationConfig::default())
+    }
+}
+
+/// Parse request language codes such as `["pt", "en"]`
+pub fn parse_languages(codes: &[String]) -> Result<Vec<Language>, String> {
+    codes
+        .iter()
+        .map(|code| {
+            Language::from_code(code).ok_or_else(|| format!("Unknown language code '{}'", code))
+        })
+        .collect()
+}
+
+/// Global query translation, configured at startup
+static GLOBAL_QUERY_TRANSLATION: OnceLock<Arc<QueryTranslation>> = OnceLock::new();
+
+/// Initialize the global query translation
+///
+/// This should be called once at startup with the server configuration.
+pub fn init_global_query_translation(config: QueryTranslationConfig) -> Arc<QueryTranslation> {
+    let translation = Arc::new(QueryTranslation::new(config));
+    if GLOBAL_QUERY_TRANSLATION.set(translation.clone()).is_err() {
+        warn!("Global query translation already initialized");
+    }
+    translation
+}
+
+/// Get the global query translation
+///
+/// Returns the default (disabled, built-in glossary) if not initialized.
+pub fn get_global_query_translation() -> Arc<QueryTranslation> {
+    GLOBAL_QUERY_TRANSLATION
+        .get()
+        .cloned()
+        .unwrap_or_else(|| Arc::new(QueryTranslation::default()))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dictionary_translates_both_ways_by_stem() {
+        let translator = DictionaryTranslator::with_builtin(Vec::new());
+        assert_eq!(
+            translator
+                .translate_terms("sales reports", None, Language::Portuguese)
+                .as_deref(),
+            Some("venda relatório")
+        );
+        assert_eq!(
+            translator
+                .translate_terms("relatórios de vendas", None, Language::English)
+                .as_deref(),
+            Some("report sale")
+        );
+        assert_eq!(
+            translator
+                .translate_terms("banco de dados kubernetes", None, Language::English)
+                .as_deref(),
+            Some("database kubernetes")
+        );
+        assert!(
+            translator
+                .translate_terms("kubernetes helm", None, Language::Portuguese)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_config_entries_extend_and_override_the_glossary() {
+        let config: QueryTranslationConfig = serde_yaml::from_str(
+            "enabled: true\ndictionary:\n  - {en: invoice, pt: nota fiscal}\n  - {en: ledger, pt: razão}",
+        )
+        .unwrap();
+        assert_eq!(
+            config.target_languages,
+            [Language::English, Language::Portuguese]
+        );
+        let translator = DictionaryTranslator::with_builtin(config.dictionary);
+        assert_eq!(
+            translator
+                .translate_terms(
+                    "invoice ledger",
+                    Some(Language::English),
+                    Language::Portuguese
+                )
+                .as_deref(),
+            Some("nota fiscal razão")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_variants_skip_the_query_language_and_duplicates() {
+        let translation = QueryTranslation::new(QueryTranslationConfig::default());
+        let variants = translation
+            .variants(
+                "customer payment",
+                &[
+                    Language::Portuguese,
+                    Language::English,
+                    Language::Portuguese,
+                ],
+            )
+            .await;
+        assert_eq!(variants, ["cliente pagamento"]);
+
+        // A long English query is detected and not "translated" into English
+        let query = "Where is the customer payment report for the last quarter?";
+        let variants = translation
+            .variants(query, &[Language::English, Language::Portuguese])
+            .await;
+        assert_eq!(variants.len(), 1);
+        assert!(variants[0].contains("cliente pagamento relatório"));
+    }
+
+    #[test]
+    fn test_targets_and_language_codes() {
+        let disabled = QueryTranslation::default();
+        assert!(disabled.targets(None).is_empty());
+        assert_eq!(
+            disabled.targets(Some(&[Language::Spanish])),
+            [Language::Spanish]
+        );
+        let enabled = QueryTranslation::new(QueryTranslationConfig {
+            enabled: true,
+            ..Default::default()
+        });
+        assert_eq!(
+            enabled.targets(None),
+            [Language::English, Language::Portuguese]
+        );
+
+        assert_eq!(
+            parse_languages(&["PT".to_string(), "en".to_string()]).unwrap(),
+            [Language::Portuguese, Language::English]
+        );
+        assert!(parse_languages(&["klingon".to_string()]).is_err());
+    }
+}
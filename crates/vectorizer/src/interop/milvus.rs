@@ -0,0 +1,303 @@
+//! Milvus export files
+//!
+//! Reads the JSON row formats Milvus produces: a bulk-writer file
+//! (`{"rows": [...]}`), a plain JSON array of rows, or one row per line
+//! (`.jsonl`/`.ndjson`). A directory is read file by file in name order.
+//! Dynamic fields kept under `$meta` are lifted into the payload.
+
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Lines};
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use serde_json::{Map, Value};
+
+use super::{InteropRecord, RecordSource, id_from_json, source_error, vector_from_json};
+use crate::error::Result;
+
+/// Which row fields hold the primary key and the vector.
+#[derive(Debug, Clone)]
+pub struct MilvusFields {
+    /// Primary key field; rows without it get a generated ID (auto_id)
+    pub id_field: String,
+    /// Float vector field
+    pub vector_field: String,
+}
+
+impl Default for MilvusFields {
+    fn default() -> Self {
+        Self {
+            id_field: "id".to_string(),
+            vector_field: "vector".to_string(),
+        }
+    }
+}
+
+/// Record source over Milvus export files.
+pub struct MilvusExportReader {
+    fields: MilvusFields,
+    files: VecDeque<PathBuf>,
+    current: Option<Rows>,
+}
+
+/// Rows of the file being read.
+enum Rows {
+    /// Whole-file JSON, already parsed
+    Parsed {
+        path: PathBuf,
+        rows: std::vec::IntoIter<Value>,
+        row: usize,
+    },
+    /// One row per line, read as needed
+    Lines {
+        path: PathBuf,
+        lines: Lines<BufReader<File>>,
+        line: usize,
+    },
+}
+
+impl MilvusExportReader {
+    /// Reader for `path`, a single export file or a directory of them.
+    pub fn open(path: impl AsRef<Path>, fields: MilvusFields) -> Result<Self> {
+        let path = path.as_ref();
+        let files = if path.is_dir() {
+            let mut files: Vec<PathBuf> = std::fs::read_dir(path)?
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|p| p.is_file() && export_kind(p).is_some())
+                .collect();
+            files.sort();
+            if files.is_empty() {
+                return Err(source_error(
+                    "Milvus",
+                    format!("no .json/.jsonl files in {}", path.display()),
+                ));
+            }
+            files
+        } else if path.is_file() {
+            vec![path.to_path_buf()]
+        } else {
+            return Err(source_error(
+                "Milvus",
+                format!("{} not found", path.display()),
+            ));
+        };
+        Ok(Self {
+            fields,
+            files: files.into(),
+            current: None,
+        })
+    }
+
+    /// Open the next file, or return `false` when none is left.
+    fn advance(&mut self) -> Result<bool> {
+        let Some(path) = self.files.pop_front() else {
+            return Ok(false);
+        };
+        let file = File::open(&path)?;
+        self.current = Some(if export_kind(&path) == Some(ExportKind::Lines) {
+            Rows::Lines {
+                lines: BufReader::new(file).lines(),
+                path,
+                line: 0,
+            }
+        } else {
+            let document: Value = serde_json::from_reader(BufReader::new(file))
+                .map_err(|e| source_error("Milvus", format!("{}: {e}", path.display())))?;
+            let rows = match document {
+                Value::Array(rows) => rows,
+                Value::Object(mut object) => match object.remove("rows") {
+                    Some(Value::Array(rows)) => rows,
+                    _ => {
+                        return Err(source_error(
+                            "Milvus",
+                            format!("{}: expected a \"rows\" array", path.display()),
+                        ));
+                    }
+                },
+                _ => {
+                    return Err(source_error(
+                        "Milvus",
+                        format!("{}: expected rows", path.display()),
+                    ));
+                }
+            };
+            Rows::Parsed {
+                rows: rows.into_iter(),
+                path,
+                row: 0,
+            }
+        });
+        Ok(true)
+    }
+
+    /// The next row with its location, or `None` at the end of the file.
+    fn next_row(&mut self) -> Result<Option<(Value, String)>> {
+        match &mut self.current {
+            None => Ok(None),
+            Some(Rows::Parsed { path, rows, row }) => Ok(rows.next().map(|value| {
+                *row += 1;
+                (value, format!("{} row {row}", path.display()))
+            })),
+            Some(Rows::Lines { path, lines, line }) => loop {
+                let Some(text) = lines.next() else {
+                    return Ok(None);
+                };
+                *line += 1;
+                let text = text?;
+                if text.trim().is_empty() {
+                    continue;
+                }
+                let location = format!("{} line {line}", path.display());
+                let value = serde_json::from_str(&text)
+                    .map_err(|e| source_error("Milvus", format!("{location}: {e}")))?;
+                return Ok(Some((value, location)));
+            },
+        }
+    }
+
+    fn record(&self, row: Value, location: &str) -> Result<InteropRecord> {
+        let Value::Object(mut row) = row else {
+            return Err(source_error(
+                "Milvus",
+                format!("{location}: row is not an object"),
+            ));
+        };
+        let vector = row
+            .remove(&self.fields.vector_field)
+            .and_then(|v| vector_from_json(&v))
+            .ok_or_else(|| {
+                source_error(
+                    "Milvus",
+                    format!(
+                        "{location}: missing float vector field \"{}\"",
+                        self.fields.vector_field
+                    ),
+                )
+            })?;
+        let id = match row.remove(&self.fields.id_field) {
+            None | Some(Value::Null) => None,
+            Some(value) => Some(id_from_json(&value).ok_or_else(|| {
+                source_error(
+                    "Milvus",
+                    format!("{location}: primary key must be a string or integer"),
+                )
+            })?),
+        };
+        let mut payload = Map::new();
+        if let Some(Value::Object(meta)) = row.remove("$meta") {
+            payload.extend(meta);
+        }
+        payload.extend(row);
+        Ok(InteropRecord {
+            id,
+            vector,
+            payload: Value::Object(payload),
+        })
+    }
+}
+
+#[async_trait]
+impl RecordSource for MilvusExportReader {
+    async fn next_batch(&mut self, max: usize) -> Result<Vec<InteropRecord>> {
+        let mut batch = Vec::new();
+        while batch.len() < max {
+            match self.next_row()? {
+                Some((row, location)) => batch.push(self.record(row, &location)?),
+                None => {
+                    if !self.advance()? {
+                        break;
+                    }
+                }
+            }
+        }
+        Ok(batch)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExportKind {
+    Document,
+    Lines,
+}
+
+fn export_kind(path: &Path) -> Option<ExportKind> {
+    match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+        "json" => Some(ExportKind::Document),
+        "jsonl" | "ndjson" => Some(ExportKind::Lines),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    async fn read_all(reader: &mut MilvusExportReader) -> Result<Vec<InteropRecord>> {
+        let mut records = Vec::new();
+        loop {
+            let batch = reader.next_batch(2).await?;
+            if batch.is_empty() {
+                return Ok(records);
+            }
+            records.extend(batch);
+        }
+    }
+
+    #[tokio::test]
+    async fn reads_bulk_writer_and_jsonl_files_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("1.json"),
+            json!({"rows": [
+                {"id": 1, "vector": [0.5, 1.0], "title": "a", "$meta": {"tag": "x"}},
+                {"id": "two", "vector": [1.0, 0.0]},
+            ]})
+            .to_string(),
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("2.jsonl"),
+            "{\"vector\": [0, 1], \"title\": \"auto\"}\n\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "ignored").unwrap();
+
+        let mut reader = MilvusExportReader::open(dir.path(), MilvusFields::default()).unwrap();
+        let records = read_all(&mut reader).await.unwrap();
+
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].id.as_deref(), Some("1"));
+        assert_eq!(records[0].vector, [0.5, 1.0]);
+        assert_eq!(records[0].payload, json!({"title": "a", "tag": "x"}));
+        assert_eq!(records[1].id.as_deref(), Some("two"));
+        assert_eq!(records[2].id, None);
+        assert_eq!(records[2].vector, [0.0, 1.0]);
+    }
+
+    #[tokio::test]
+    async fn custom_fields_and_bad_rows() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rows.json");
+        std::fs::write(
+            &path,
+            json!([{"pk": 9, "emb": [1.0]}, {"pk": 10, "emb": "nope"}]).to_string(),
+        )
+        .unwrap();
+
+        let fields = MilvusFields {
+            id_field: "pk".to_string(),
+            vector_field: "emb".to_string(),
+        };
+        let mut reader = MilvusExportReader::open(&path, fields).unwrap();
+        let err = read_all(&mut reader).await.unwrap_err().to_string();
+        assert!(err.contains("row 2"), "{err}");
+
+        assert!(
+            MilvusExportReader::open(dir.path().join("missing"), MilvusFields::default()).is_err()
+        );
+    }
+}
@@ -0,0 +1,227 @@
+//! Importers for data held in other vector stores
+//!
+//! A migration reads its source through a [`RecordSource`] and writes
+//! through a [`RecordSink`]; [`bulk_load`] moves batches from one to the
+//! other. Sources:
+//!
+//! - [`milvus::MilvusExportReader`] — Milvus JSON row files, as written
+//!   by its bulk writer or exported from a query
+//! - [`pgvector::PgVectorSource`] — a Postgres table with a pgvector
+//!   column (feature `pgvector`)
+//!
+//! [`StoreSink`] loads into a local [`VectorStore`]; `vectorizer-cli
+//! migrate` writes through the REST API of a running server instead.
+
+pub mod milvus;
+#[cfg(feature = "pgvector")]
+pub mod pgvector;
+
+use async_trait::async_trait;
+use serde_json::Value;
+use tracing::info;
+
+use crate::db::VectorStore;
+use crate::error::{Result, VectorizerError};
+use crate::models::{Payload, Vector};
+
+/// One record read from a source.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InteropRecord {
+    /// Source ID, or `None` to let the target generate one
+    pub id: Option<String>,
+    /// Dense vector
+    pub vector: Vec<f32>,
+    /// Every other field of the record, as a JSON object
+    pub payload: Value,
+}
+
+/// Where a migration reads from.
+#[async_trait]
+pub trait RecordSource: Send {
+    /// The next batch of at most `max` records; empty once the source is
+    /// exhausted.
+    async fn next_batch(&mut self, max: usize) -> Result<Vec<InteropRecord>>;
+}
+
+/// Where a migration writes to.
+#[async_trait]
+pub trait RecordSink: Send {
+    /// Store a batch and return how many records were stored; the rest
+    /// count as failed. An `Err` stops the migration.
+    async fn write(&mut self, records: Vec<InteropRecord>) -> Result<usize>;
+}
+
+/// Counters of a migration.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportReport {
+    /// Records read from the source
+    pub read: u64,
+    /// Records the sink stored
+    pub imported: u64,
+    /// Records the sink refused
+    pub failed: u64,
+    /// Batches written
+    pub batches: u64,
+}
+
+/// Copy every record of `source` into `sink`, `batch_size` at a time.
+/// `progress` is called after each batch.
+pub async fn bulk_load(
+    source: &mut dyn RecordSource,
+    sink: &mut dyn RecordSink,
+    batch_size: usize,
+    mut progress: impl FnMut(&ImportReport) + Send,
+) -> Result<ImportReport> {
+    let batch_size = batch_size.max(1);
+    let mut report = ImportReport::default();
+    loop {
+        let batch = source.next_batch(batch_size).await?;
+        if batch.is_empty() {
+            break;
+        }
+        let len = batch.len() as u64;
+        let stored = sink.write(batch).await? as u64;
+        report.read += len;
+        report.imported += stored.min(len);
+        report.failed += len.saturating_sub(stored);
+        report.batches += 1;
+        progress(&report);
+    }
+    info!(
+        "📥 Bulk load finished: {} read, {} imported, {} failed",
+        report.read, report.imported, report.failed
+    );
+    Ok(report)
+}
+
+/// Sink inserting into a collection of a local [`VectorStore`]. Records
+/// whose dimension differs from the collection's are refused.
+pub struct StoreSink<'a> {
+    store: &'a VectorStore,
+    collection: String,
+    dimension: usize,
+}
+
+impl<'a> StoreSink<'a> {
+    /// Sink for the existing collection `collection`.
+    pub fn new(store: &'a VectorStore, collection: &str) -> Result<Self> {
+        let dimension = store.get_collection(collection)?.config().dimension;
+        Ok(Self {
+            store,
+            collection: collection.to_string(),
+            dimension,
+        })
+    }
+}
+
+#[async_trait]
+impl RecordSink for StoreSink<'_> {
+    async fn write(&mut self, records: Vec<InteropRecord>) -> Result<usize> {
+        let vectors: Vec<Vector> = records
+            .into_iter()
+            .filter(|r| r.vector.len() == self.dimension)
+            .map(|r| Vector {
+                id: r
+                    .id
+                    .unwrap_or_else(|| self.store.new_vector_id(&self.collection)),
+                data: r.vector,
+                sparse: None,
+                payload: Some(Payload::new(r.payload)),
+                document_id: None,
+            })
+            .collect();
+        let stored = vectors.len();
+        if stored > 0 {
+            self.store.insert(&self.collection, vectors)?;
+        }
+        Ok(stored)
+    }
+}
+
+/// Parse a JSON number array into a vector.
+pub(crate) fn vector_from_json(value: &Value) -> Option<Vec<f32>> {
+    value
+        .as_array()?
+        .iter()
+        .map(|x| x.as_f64().map(|x| x as f32))
+        .collect()
+}
+
+/// A JSON ID as the string Vectorizer stores.
+pub(crate) fn id_from_json(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+fn source_error(source: &str, message: impl std::fmt::Display) -> VectorizerError {
+    VectorizerError::Other(format!("{source} import: {message}"))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::models::{CollectionConfig, DistanceMetric, QuantizationConfig};
+
+    struct Fixed(Vec<InteropRecord>);
+
+    #[async_trait]
+    impl RecordSource for Fixed {
+        async fn next_batch(&mut self, max: usize) -> Result<Vec<InteropRecord>> {
+            let n = max.min(self.0.len());
+            Ok(self.0.drain(..n).collect())
+        }
+    }
+
+    fn record(id: Option<&str>, vector: Vec<f32>) -> InteropRecord {
+        InteropRecord {
+            id: id.map(str::to_string),
+            vector,
+            payload: json!({"source": "test"}),
+        }
+    }
+
+    #[tokio::test]
+    async fn bulk_load_batches_into_the_store() {
+        let store = VectorStore::new_cpu_only();
+        let config = CollectionConfig {
+            dimension: 2,
+            metric: DistanceMetric::Euclidean,
+            quantization: QuantizationConfig::None,
+            normalization: None,
+            ..CollectionConfig::default()
+        };
+        store.create_collection("target", config).unwrap();
+
+        let mut source = Fixed(vec![
+            record(Some("a"), vec![1.0, 0.0]),
+            record(None, vec![0.0, 1.0]),
+            record(Some("bad"), vec![1.0, 2.0, 3.0]),
+        ]);
+        let mut sink = StoreSink::new(&store, "target").unwrap();
+        let mut seen = Vec::new();
+        let report = bulk_load(&mut source, &mut sink, 2, |r| seen.push(r.read))
+            .await
+            .unwrap();
+
+        assert_eq!(seen, [2, 3]);
+        assert_eq!(
+            report,
+            ImportReport {
+                read: 3,
+                imported: 2,
+                failed: 1,
+                batches: 2
+            }
+        );
+        let stored = store.get_vector("target", "a").unwrap();
+        assert_eq!(stored.data, [1.0, 0.0]);
+        assert_eq!(stored.payload.unwrap().data["source"], "test");
+        assert!(StoreSink::new(&store, "missing").is_err());
+    }
+}
@@ -0,0 +1,413 @@
+//! Tracked long-running operations.
+//!
+//! A job is one run of an operation that can take longer than a request
+//! should wait on: a collection clone, reindex, import or snapshot
+//! restore, an index compaction, a duplicate scan or a clustering run.
+//! The operation reports its progress through the [`Job`] it was given;
+//! `GET /jobs` and `GET /jobs/{id}` read it back. Unlike the periodic
+//! loops of [`background_tasks`](crate::background_tasks), every run is
+//! its own job with its own outcome.
+//!
+//! Cancelling (`DELETE /jobs/{id}`) is cooperative: the operation checks
+//! [`Job::check_cancelled`] between steps, so clones, imports, duplicate
+//! scans and clustering runs stop after the current batch, while a
+//! reindex, restore or compaction that already started runs to the end.
+//!
+//! A job started on behalf of a hub tenant or a namespaced API key
+//! records that caller as its owner; [`JobStatus::visible_to`] keeps it
+//! from every other scoped caller.
+//!
+//! Jobs live in memory only. Of the finished jobs, the
+//! [`MAX_FINISHED_JOBS`] started last are kept.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::{Result, VectorizerError};
+
+/// Copy of a collection into a new one (`POST /collections/{name}/clone`).
+pub const COLLECTION_CLONE: &str = "collection_clone";
+
+/// HNSW rebuild with new parameters (`POST /collections/{name}/reindex`).
+pub const COLLECTION_REINDEX: &str = "collection_reindex";
+
+/// Streamed import (`POST /collections/{name}/import`).
+pub const COLLECTION_IMPORT: &str = "collection_import";
+
+/// Restore of a native snapshot
+/// (`POST /collections/{name}/snapshots/{id}/restore`).
+pub const SNAPSHOT_RESTORE: &str = "snapshot_restore";
+
+/// HNSW compaction by the [`IndexOptimizer`](crate::db::IndexOptimizer).
+pub const INDEX_OPTIMIZATION: &str = "index_optimization";
+
+/// Near-duplicate scan (`POST /collections/{name}/duplicates`); the
+/// result is the report of [`DuplicateGroup`](crate::search::DuplicateGroup)s.
+pub const DUPLICATE_REPORT: &str = "duplicate_report";
+
+/// k-means or HDBSCAN run (`POST /collections/{name}/cluster`); the
+/// result holds the [`ClusterSummary`](crate::search::ClusterSummary)s.
+pub const CLUSTERING: &str = "clustering";
+
+/// Finished jobs kept for lookup.
+pub const MAX_FINISHED_JOBS: usize = 100;
+
+/// Process-wide job registry.
+pub static JOBS: Lazy<JobRegistry> = Lazy::new(JobRegistry::default);
+
+/// Where a job is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobState {
+    /// Still running.
+    Running,
+    /// Finished successfully; `result` holds its outcome.
+    Completed,
+    /// Stopped by an error; `error` says why.
+    Failed,
+    /// Stopped early because it was cancelled.
+    Cancelled,
+}
+
+/// Snapshot of a job, as returned by `GET /jobs/{id}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobStatus {
+    /// Job id.
+    pub id: String,
+    /// What the job does, e.g. [`COLLECTION_CLONE`].
+    pub kind: String,
+    /// Collection the job works on.
+    pub collection: String,
+    /// Tenant or API-key namespace that started the job; `None` for
+    /// unscoped callers and the server's own jobs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    /// Current state.
+    pub state: JobState,
+    /// Items processed so far.
+    pub processed: u64,
+    /// Items to process, once known.
+    pub total: u64,
+    /// `processed / total`, from 0 to 1; 1 once the job completed.
+    pub progress: f64,
+    /// Whether the job was asked to stop.
+    pub cancel_requested: bool,
+    /// When the job started.
+    pub started_at: DateTime<Utc>,
+    /// When it finished.
+    pub finished_at: Option<DateTime<Utc>>,
+    /// Why it failed.
+    pub error: Option<String>,
+    /// Outcome of a completed job.
+    pub result: Option<Value>,
+}
+
+impl JobStatus {
+    /// Whether a caller scoped to `owner` may see, cancel or download
+    /// the job. Unscoped callers (`None`) see every job; a scoped one
+    /// only its own.
+    pub fn visible_to(&self, owner: Option<&str>) -> bool {
+        owner.is_none_or(|owner| self.owner.as_deref() == Some(owner))
+    }
+}
+
+/// A running or finished job. The operation keeps the `Arc` and reports
+/// through it.
+#[derive(Debug)]
+pub struct Job {
+    /// Start order in the registry.
+    seq: u64,
+    cancelled: AtomicBool,
+    status: Mutex<JobStatus>,
+}
+
+impl Job {
+    fn new(seq: u64, kind: &str, collection: &str, owner: Option<String>) -> Self {
+        Self {
+            seq,
+            cancelled: AtomicBool::new(false),
+            status: Mutex::new(JobStatus {
+                id: uuid::Uuid::new_v4().to_string(),
+                kind: kind.to_string(),
+                collection: collection.to_string(),
+                owner,
+                state: JobState::Running,
+                processed: 0,
+                total: 0,
+                progress: 0.0,
+                cancel_requested: false,
+                started_at: Utc::now(),
+                finished_at: None,
+                error: None,
+                result: None,
+            }),
+        }
+    }
+
+    /// Job id.
+    pub fn id(&self) -> String {
+        self.status.lock().id.clone()
+    }
+
+    /// Record how many items the job will process.
+    pub fn set_total(&self, total: u64) {
+        self.status.lock().total = total;
+    }
+
+    /// Record that `items` more were processed.
+    pub fn advance(&self, items: u64) {
+        self.status.lock().processed += items;
+    }
+
+    /// Whether the job was asked to stop.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Stop here if the job was cancelled.
+    ///
+    /// # Errors
+    ///
+    /// [`VectorizerError::JobCancelled`] once the job was cancelled.
+    pub fn check_cancelled(&self) -> Result<()> {
+        if self.is_cancelled() {
+            return Err(VectorizerError::JobCancelled(self.id()));
+        }
+        Ok(())
+    }
+
+    /// Record that the job finished with `result`. An error after the job
+    /// was cancelled finishes it as [`JobState::Cancelled`].
+    pub fn finish<E: Display>(&self, result: &std::result::Result<Value, E>) {
+        let mut status = self.status.lock();
+        if status.finished_at.is_some() {
+            return;
+        }
+        status.finished_at = Some(Utc::now());
+        match result {
+            Ok(value) => {
+                status.state = JobState::Completed;
+                status.result = Some(value.clone());
+            }
+            Err(e) => {
+                status.state = if self.is_cancelled() {
+                    JobState::Cancelled
+                } else {
+                    JobState::Failed
+                };
+                status.error = Some(e.to_string());
+            }
+        }
+    }
+
+    /// Run `f` as this job, unless it was cancelled before it started,
+    /// and finish the job with its outcome.
+    ///
+    /// # Errors
+    ///
+    /// Whatever `f` returns, or [`VectorizerError::JobCancelled`].
+    pub fn run<T: Serialize>(&self, f: impl FnOnce(&Job) -> Result<T>) -> Result<T> {
+        let result = self.check_cancelled().and_then(|()| f(self));
+        match &result {
+            Ok(value) => self
+                .finish::<VectorizerError>(&Ok(serde_json::to_value(value).unwrap_or(Value::Null))),
+            Err(e) => self.finish::<&VectorizerError>(&Err(e)),
+        }
+        result
+    }
+
+    /// Whether the job finished, in any state.
+    pub fn is_finished(&self) -> bool {
+        self.status.lock().finished_at.is_some()
+    }
+
+    /// Current status.
+    pub fn status(&self) -> JobStatus {
+        let mut status = self.status.lock().clone();
+        status.cancel_requested = self.is_cancelled();
+        status.progress = match status.state {
+            JobState::Completed => 1.0,
+            _ if status.total == 0 => 0.0,
+            _ => (status.processed as f64 / status.total as f64).min(1.0),
+        };
+        status
+    }
+}
+
+/// Jobs by id.
+#[derive(Debug, Default)]
+pub struct JobRegistry {
+    jobs: RwLock<HashMap<String, Arc<Job>>>,
+    started: AtomicU64,
+}
+
+impl JobRegistry {
+    /// Register a new running job of `kind` on `collection`, dropping the
+    /// earliest started finished jobs beyond [`MAX_FINISHED_JOBS`].
+    pub fn start(&self, kind: &str, collection: &str) -> Arc<Job> {
+        self.start_owned(kind, collection, None)
+    }
+
+    /// [`start`](Self::start) a job on behalf of the scoped caller `owner`.
+    pub fn start_owned(&self, kind: &str, collection: &str, owner: Option<String>) -> Arc<Job> {
+        let seq = self.started.fetch_add(1, Ordering::Relaxed);
+        let job = Arc::new(Job::new(seq, kind, collection, owner));
+        let mut jobs = self.jobs.write();
+        let mut finished: Vec<(u64, String)> = jobs
+            .iter()
+            .filter(|(_, job)| job.status.lock().finished_at.is_some())
+            .map(|(id, job)| (job.seq, id.clone()))
+            .collect();
+        if finished.len() > MAX_FINISHED_JOBS {
+            finished.sort_unstable();
+            for (_, id) in &finished[..finished.len() - MAX_FINISHED_JOBS] {
+                jobs.remove(id);
+            }
+        }
+        jobs.insert(job.id(), job.clone());
+        job
+    }
+
+    /// Status of the job `id`, if it is known.
+    pub fn get(&self, id: &str) -> Option<JobStatus> {
+        self.jobs.read().get(id).map(|job| job.status())
+    }
+
+    /// Every known job, latest started first.
+    pub fn list(&self) -> Vec<JobStatus> {
+        let mut jobs: Vec<(u64, JobStatus)> = self
+            .jobs
+            .read()
+            .values()
+            .map(|job| (job.seq, job.status()))
+            .collect();
+        jobs.sort_unstable_by(|a, b| b.0.cmp(&a.0));
+        jobs.into_iter().map(|(_, status)| status).collect()
+    }
+
+    /// Ask the running job `id` to stop, or forget it if it already
+    /// finished. Returns its status, or `None` if it is not known.
+    pub fn cancel(&self, id: &str) -> Option<JobStatus> {
+        let mut jobs = self.jobs.write();
+        let job = jobs.get(id)?.clone();
+        if job.is_finished() {
+            jobs.remove(id);
+        } else {
+            job.cancelled.store(true, Ordering::Relaxed);
+        }
+        Some(job.status())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn status_follows_progress_and_outcome() {
+        let registry = JobRegistry::default();
+        let job = registry.start(COLLECTION_CLONE, "docs");
+        let id = job.id();
+
+        job.set_total(4);
+        job.advance(1);
+        let status = registry.get(&id).unwrap();
+        assert_eq!(status.state, JobState::Running);
+        assert_eq!((status.processed, status.total), (1, 4));
+        assert!((status.progress - 0.25).abs() < f64::EPSILON);
+
+        job.advance(3);
+        job.finish::<String>(&Ok(json!({"copied": 4})));
+        let status = registry.get(&id).unwrap();
+        assert_eq!(status.state, JobState::Completed);
+        assert_eq!(status.progress, 1.0);
+        assert_eq!(status.result, Some(json!({"copied": 4})));
+
+        let failed = registry.start(COLLECTION_CLONE, "docs");
+        failed.finish(&Err("disk full"));
+        let status = registry.get(&failed.id()).unwrap();
+        assert_eq!(status.state, JobState::Failed);
+        assert_eq!(status.error.as_deref(), Some("disk full"));
+        assert!(registry.get("nope").is_none());
+    }
+
+    #[test]
+    fn cancel_stops_running_jobs_and_forgets_finished_ones() {
+        let registry = JobRegistry::default();
+        let job = registry.start(COLLECTION_REINDEX, "docs");
+        let other = registry.start(SNAPSHOT_RESTORE, "docs");
+        let listed: Vec<String> = registry.list().into_iter().map(|s| s.id).collect();
+        assert_eq!(listed, vec![other.id(), job.id()]);
+
+        let status = registry.cancel(&job.id()).unwrap();
+        assert_eq!(status.state, JobState::Running);
+        assert!(status.cancel_requested);
+        let mut ran = false;
+        let result = job.run(|_| {
+            ran = true;
+            Ok(())
+        });
+        assert!(!ran);
+        assert!(matches!(result, Err(VectorizerError::JobCancelled(_))));
+        assert_eq!(registry.get(&job.id()).unwrap().state, JobState::Cancelled);
+
+        let result = other.run(|job| {
+            job.set_total(2);
+            job.advance(2);
+            Ok(json!({"restored": 2}))
+        });
+        assert!(result.is_ok());
+        assert_eq!(
+            registry.get(&other.id()).unwrap().result,
+            Some(json!({"restored": 2}))
+        );
+
+        assert!(registry.cancel(&job.id()).is_some());
+        assert!(registry.get(&job.id()).is_none());
+        assert!(registry.cancel("nope").is_none());
+    }
+
+    #[test]
+    fn scoped_callers_only_see_their_own_jobs() {
+        let registry = JobRegistry::default();
+        let owned = registry.start_owned(CLUSTERING, "docs", Some("tenant:a".to_string()));
+        let status = registry.get(&owned.id()).unwrap();
+        assert!(status.visible_to(None));
+        assert!(status.visible_to(Some("tenant:a")));
+        assert!(!status.visible_to(Some("tenant:b")));
+
+        let server = registry
+            .get(&registry.start(INDEX_OPTIMIZATION, "docs").id())
+            .unwrap();
+        assert!(server.visible_to(None));
+        assert!(!server.visible_to(Some("tenant:a")));
+    }
+
+    #[test]
+    fn oldest_finished_jobs_are_dropped() {
+        let registry = JobRegistry::default();
+        let first = registry.start(COLLECTION_CLONE, "docs");
+        first.finish::<String>(&Ok(Value::Null));
+        for _ in 0..MAX_FINISHED_JOBS {
+            registry
+                .start(COLLECTION_CLONE, "docs")
+                .finish::<String>(&Ok(Value::Null));
+        }
+        let running = registry.start(COLLECTION_CLONE, "docs");
+
+        assert!(registry.get(&first.id()).is_none());
+        assert!(registry.get(&running.id()).is_some());
+        assert_eq!(registry.jobs.read().len(), MAX_FINISHED_JOBS + 1);
+    }
+}
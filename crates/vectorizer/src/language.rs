@@ -0,0 +1,278 @@
+//! Language detection and per-language text analysis
+//!
+//! One vocabulary shared by a corpus that mixes English and Portuguese
+//! serves both badly: each language's stopwords crowd the other's
+//! content words out of the top terms, and inflections ("dado"/"dados")
+//! count as different words. With language detection on, ingestion
+//! records each chunk's language as `lang` in its payload ([`LANG_FIELD`])
+//! and the sparse providers (BM25, TF-IDF) analyze it with that
+//! language's stopword list and stemmer, against a vocabulary built from
+//! that language's documents only. Detection uses whatlang; the
+//! analyzers are tantivy's.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+use tantivy::tokenizer::{
+    LowerCaser, RemoveLongFilter, SimpleTokenizer, Stemmer, StopWordFilter, TextAnalyzer,
+    TokenStream,
+};
+
+/// Payload field holding a chunk's detected language code
+pub const LANG_FIELD: &str = "lang";
+
+/// Texts with fewer letters than this are not detected: a handful of
+/// words says too little about the language
+const MIN_DETECTION_LETTERS: usize = 20;
+
+/// Tokens longer than this are dropped (hashes, base64, minified code)
+const MAX_TOKEN_LENGTH: usize = 40;
+
+/// A language with its own analyzer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Language {
+    /// English (`en`)
+    #[serde(rename = "en")]
+    English,
+    /// Portuguese (`pt`)
+    #[serde(rename = "pt")]
+    Portuguese,
+    /// Spanish (`es`)
+    #[serde(rename = "es")]
+    Spanish,
+    /// French (`fr`)
+    #[serde(rename = "fr")]
+    French,
+    /// German (`de`)
+    #[serde(rename = "de")]
+    German,
+    /// Italian (`it`)
+    #[serde(rename = "it")]
+    Italian,
+    /// Dutch (`nl`)
+    #[serde(rename = "nl")]
+    Dutch,
+}
+
+impl Language {
+    /// Every language with an analyzer
+    pub const ALL: [Language; 7] = [
+        Language::English,
+        Language::Portuguese,
+        Language::Spanish,
+        Language::French,
+        Language::German,
+        Language::Italian,
+        Language::Dutch,
+    ];
+
+    /// ISO 639-1 code, as stored in payloads
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Portuguese => "pt",
+            Language::Spanish => "es",
+            Language::French => "fr",
+            Language::German => "de",
+            Language::Italian => "it",
+            Language::Dutch => "nl",
+        }
+    }
+
+    /// English name, e.g. `Portuguese`
+    pub fn name(self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Portuguese => "Portuguese",
+            Language::Spanish => "Spanish",
+            Language::French => "French",
+            Language::German => "German",
+            Language::Italian => "Italian",
+            Language::Dutch => "Dutch",
+        }
+    }
+
+    /// The language with ISO 639-1 code `code` (case-insensitive)
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|language| language.code().eq_ignore_ascii_case(code))
+    }
+
+    fn from_whatlang(lang: whatlang::Lang) -> Option<Self> {
+        match lang {
+            whatlang::Lang::Eng => Some(Language::English),
+            whatlang::Lang::Por => Some(Language::Portuguese),
+            whatlang::Lang::Spa => Some(Language::Spanish),
+            whatlang::Lang::Fra => Some(Language::French),
+            whatlang::Lang::Deu => Some(Language::German),
+            whatlang::Lang::Ita => Some(Language::Italian),
+            whatlang::Lang::Nld => Some(Language::Dutch),
+            _ => None,
+        }
+    }
+
+    fn tantivy(self) -> tantivy::tokenizer::Language {
+        use tantivy::tokenizer::Language as Tantivy;
+        match self {
+            Language::English => Tantivy::English,
+            Language::Portuguese => Tantivy::Portuguese,
+            Language::Spanish => Tantivy::Spanish,
+            Language::French => Tantivy::French,
+            Language::German => Tantivy::German,
+            Language::Italian => Tantivy::Italian,
+            Language::Dutch => Tantivy::Dutch,
+        }
+    }
+}
+
+impl std::fmt::Display for Language {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+/// The language `text` is written in, when it is long enough to tell
+/// reliably and is one of [`Language::ALL`]
+pub fn detect(text: &str) -> Option<Language> {
+    if text.chars().filter(|c| c.is_alphabetic()).count() < MIN_DETECTION_LETTERS {
+        return None;
+    }
+    let info = whatlang::detect(text)?;
+    if !info.is_reliable() {
+        return None;
+    }
+    Language::from_whatlang(info.lang())
+}
+
+/// Terms of `text` under `language`'s analyzer: lowercased, without
+/// stopwords, stemmed
+pub fn analyze(text: &str, language: Language) -> Vec<String> {
+    static ANALYZERS: OnceLock<HashMap<Language, TextAnalyzer>> = OnceLock::new();
+    let analyzers = ANALYZERS.get_or_init(|| {
+        Language::ALL
+            .into_iter()
+            .map(|language| (language, build_analyzer(language)))
+            .collect()
+    });
+    let Some(analyzer) = analyzers.get(&language) else {
+        return Vec::new();
+    };
+
+    // Token streams borrow the analyzer mutably; clones share the filters
+    let mut analyzer = analyzer.clone();
+    let mut stream = analyzer.token_stream(text);
+    let mut terms = Vec::new();
+    while stream.advance() {
+        terms.push(stream.token().text.clone());
+    }
+    terms
+}
+
+fn build_analyzer(language: Language) -> TextAnalyzer {
+    let stopwords = StopWordFilter::new(language.tantivy())
+        .unwrap_or_else(|| StopWordFilter::remove(Vec::<String>::new()));
+    TextAnalyzer::builder(SimpleTokenizer::default())
+        .filter(RemoveLongFilter::limit(MAX_TOKEN_LENGTH))
+        .filter(LowerCaser)
+        .filter(stopwords)
+        .filter(Stemmer::new(language.tantivy()))
+        .build()
+}
+
+/// The per-language model `text` should be embedded with: the one for
+/// its detected language, or else the one whose vocabulary knows the
+/// most of its terms (short queries are rarely detected reliably).
+/// `None` when no model knows any of them, so the caller falls back to
+/// its shared vocabulary.
+pub fn route<'a, M>(
+    text: &str,
+    models: &'a HashMap<Language, M>,
+    knows: impl Fn(&M, &str) -> bool,
+) -> Option<&'a M> {
+    if models.is_empty() {
+        return None;
+    }
+    if let Some(model) = detect(text).and_then(|language| models.get(&language)) {
+        return Some(model);
+    }
+
+    let mut best: Option<(usize, Language)> = None;
+    for (&language, model) in models {
+        let known = analyze(text, language)
+            .iter()
+            .filter(|term| knows(model, term))
+            .count();
+        // Ties go to the first language in `Language::ALL` order
+        let better = match best {
+            None => known > 0,
+            Some((count, current)) => known > count || (known == count && language < current),
+        };
+        if better {
+            best = Some((known, language));
+        }
+    }
+    best.and_then(|(_, language)| models.get(&language))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_english_and_portuguese() {
+        assert_eq!(
+            detect("The quarterly report shows revenue growth across every region we operate in."),
+            Some(Language::English)
+        );
+        assert_eq!(
+            detect("O relatório trimestral mostra o crescimento da receita em todas as regiões."),
+            Some(Language::Portuguese)
+        );
+        assert_eq!(detect("fn main()"), None);
+    }
+
+    #[test]
+    fn test_codes_round_trip() {
+        for language in Language::ALL {
+            assert_eq!(Language::from_code(language.code()), Some(language));
+            let json = serde_json::to_string(&language).unwrap();
+            assert_eq!(json, format!("\"{}\"", language.code()));
+        }
+        assert_eq!(Language::from_code("PT"), Some(Language::Portuguese));
+        assert_eq!(Language::from_code("xx"), None);
+    }
+
+    #[test]
+    fn test_analyze_removes_stopwords_and_stems() {
+        assert_eq!(
+            analyze("Os dados das vendas", Language::Portuguese),
+            analyze("dado venda", Language::Portuguese)
+        );
+        let english = analyze("The servers are running", Language::English);
+        assert_eq!(english, ["server", "run"]);
+    }
+
+    #[test]
+    fn test_route_prefers_the_vocabulary_that_knows_the_query() {
+        let models = HashMap::from([
+            (
+                Language::English,
+                analyze("sales data for the region", Language::English),
+            ),
+            (
+                Language::Portuguese,
+                analyze("dados de vendas da região", Language::Portuguese),
+            ),
+        ]);
+        let knows = |terms: &Vec<String>, term: &str| terms.iter().any(|t| t == term);
+
+        let model = route("vendas", &models, knows).unwrap();
+        assert_eq!(model, &models[&Language::Portuguese]);
+        let model = route("region", &models, knows).unwrap();
+        assert_eq!(model, &models[&Language::English]);
+        assert!(route("kubernetes", &models, knows).is_none());
+    }
+}
@@ -20,8 +20,10 @@
 // phase4_split-vectorizer-workspace sub-phase 4 along with the
 // rest of the HTTP / gRPC / MCP transport layer.
 pub mod auth;
+pub mod background_tasks;
 pub mod batch;
 pub mod cache;
+pub mod chunking;
 // `cli` moved into the standalone `vectorizer-cli` crate under
 // phase4_split-vectorizer-workspace sub-phase 5. The
 // `vectorizer-cli` + `create_mcp_key` binaries live there too.
@@ -56,15 +58,21 @@ pub mod grpc_conversions;
 pub mod hub;
 pub mod hybrid_search;
 pub mod intelligent_search;
+pub mod interop;
+pub mod jobs;
+pub mod language;
 // `logging` moved into `vectorizer-server` (sub-phase 4) — it's
 // server-startup tracing setup.
 pub mod migration;
 pub mod models;
 pub mod monitoring;
 pub mod normalization;
+pub mod ocr;
 #[path = "persistence/mod.rs"]
 pub mod persistence;
+pub mod plugins;
 pub mod protocol;
+pub mod search;
 // `replication` stays in the umbrella because `cluster::ha_manager`
 // (which stays in umbrella too) uses MasterNode / ReplicaNode /
 // ReplicationConfig from it. Moving replication breaks that
@@ -80,6 +88,7 @@ pub mod security;
 pub mod storage;
 pub mod summarization;
 pub mod testing;
+pub mod transcription;
 pub mod transmutation_integration;
 // `umicp` moved into `vectorizer-server` (sub-phase 4) — server
 // transport integration.
@@ -134,6 +143,13 @@ mod integration_tests {
             storage_type: Some(crate::models::StorageType::Memory),
             graph: None,
             encryption: None,
+            id_policy: None,
+            quota: None,
+            scoring: None,
+            tenant_partition: None,
+            dedup: None,
+            chunking: None,
+            full_text: None,
         };
 
         store.create_collection("concurrent", config).unwrap();
@@ -241,6 +257,13 @@ mod integration_tests {
                     storage_type: Some(crate::models::StorageType::Memory),
                     graph: None,
                     encryption: None,
+                    id_policy: None,
+                    quota: None,
+                    scoring: None,
+                    tenant_partition: None,
+                    dedup: None,
+                    chunking: None,
+                    full_text: None,
                 },
             ),
             (
@@ -266,6 +289,13 @@ mod integration_tests {
                     storage_type: Some(crate::models::StorageType::Memory),
                     graph: None,
                     encryption: None,
+                    id_policy: None,
+                    quota: None,
+                    scoring: None,
+                    tenant_partition: None,
+                    dedup: None,
+                    chunking: None,
+                    full_text: None,
                 },
             ),
         ];
@@ -1,9 +1,13 @@
 //! Migration tools for Vectorizer
 //!
 //! Provides tools for migrating from other vector databases to Vectorizer,
-//! and for migrating standalone instances to HiveHub Cloud multi-tenant mode.
+//! for migrating standalone instances to HiveHub Cloud multi-tenant mode,
+//! and for moving collections in and out as Parquet files (feature
+//! `parquet`).
 
 pub mod hub_migration;
+#[cfg(feature = "parquet")]
+pub mod parquet;
 pub mod qdrant;
 
 pub use hub_migration::{
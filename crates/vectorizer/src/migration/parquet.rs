@@ -0,0 +1,653 @@
+//! Parquet import and export of collections.
+//!
+//! An export writes one row per vector: `id`, `vector` (a list of
+//! float32), `document_id`, the whole payload as JSON text in `payload`,
+//! and payload fields mapped to typed columns so the file can be queried
+//! straight from DuckDB, Spark or pandas. A field whose values are all
+//! booleans, integers, numbers or strings becomes a Boolean, Int64,
+//! Float64 or Utf8 column; anything else (objects, arrays, mixed types)
+//! is written as JSON text and marked as such in the column metadata.
+//! Column names are the field paths; a field that would shadow one of
+//! the fixed columns is prefixed with `payload.`.
+//!
+//! An import reads files written by the export or by other tools. It
+//! needs a `vector` column (any list of numbers); `id` is optional (new
+//! IDs follow the collection's ID policy). Payloads come from `payload`
+//! when the file has it and otherwise are rebuilt from the remaining
+//! columns, with dotted column names becoming nested objects. Rows are
+//! inserted batch by batch, so an import that fails part way keeps the
+//! batches before the bad one.
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+use std::sync::Arc;
+
+use arrow::array::{
+    Array, ArrayRef, AsArray, BooleanArray, Float32Builder, Float64Array, Int64Array, ListBuilder,
+    StringArray, StringBuilder,
+};
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, Field, Float32Type, Float64Type, Int64Type, Schema, SchemaRef};
+use arrow::record_batch::{RecordBatch, RecordBatchReader};
+use parquet::arrow::ArrowWriter;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+use parquet::file::reader::ChunkReader;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use tracing::info;
+
+use crate::db::VectorStore;
+use crate::error::{Result, VectorizerError};
+use crate::models::{Payload, Vector};
+
+/// Columns every export has, in order.
+pub const FIXED_COLUMNS: [&str; 4] = ["id", "vector", "document_id", "payload"];
+
+/// Column metadata key holding the payload path a column was mapped from.
+pub const PATH_METADATA: &str = "vectorizer.path";
+
+/// Column metadata key marking a column of JSON text.
+pub const JSON_METADATA: &str = "vectorizer.json";
+
+/// Rows per record batch, both ways.
+const BATCH_ROWS: usize = 8192;
+
+/// What goes into an export.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ParquetExportOptions {
+    /// Payload fields (dotted paths) to map to columns. `None` maps every
+    /// top-level field.
+    #[serde(default)]
+    pub fields: Option<Vec<String>>,
+}
+
+/// Outcome of an export.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ParquetSummary {
+    /// Vectors written.
+    pub rows: usize,
+    /// Columns of the file.
+    pub columns: Vec<String>,
+}
+
+/// Outcome of an import.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ParquetImport {
+    /// IDs of the vectors inserted, in file order.
+    pub ids: Vec<String>,
+    /// Columns of the file.
+    pub columns: Vec<String>,
+}
+
+/// Type of a mapped payload column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ColumnKind {
+    Boolean,
+    Int64,
+    Float64,
+    Utf8,
+    Json,
+}
+
+impl ColumnKind {
+    fn of(value: &Value) -> Option<Self> {
+        match value {
+            Value::Null => None,
+            Value::Bool(_) => Some(Self::Boolean),
+            Value::Number(n) if n.is_i64() => Some(Self::Int64),
+            Value::Number(_) => Some(Self::Float64),
+            Value::String(_) => Some(Self::Utf8),
+            Value::Array(_) | Value::Object(_) => Some(Self::Json),
+        }
+    }
+
+    /// The narrowest kind holding values of both.
+    fn unify(self, other: Self) -> Self {
+        match (self, other) {
+            (a, b) if a == b => a,
+            (Self::Int64, Self::Float64) | (Self::Float64, Self::Int64) => Self::Float64,
+            _ => Self::Json,
+        }
+    }
+
+    fn data_type(self) -> DataType {
+        match self {
+            Self::Boolean => DataType::Boolean,
+            Self::Int64 => DataType::Int64,
+            Self::Float64 => DataType::Float64,
+            Self::Utf8 | Self::Json => DataType::Utf8,
+        }
+    }
+}
+
+/// A payload field mapped to a column.
+struct PayloadColumn {
+    name: String,
+    path: String,
+    kind: ColumnKind,
+}
+
+impl PayloadColumn {
+    fn field(&self) -> Field {
+        let mut metadata = HashMap::from([(PATH_METADATA.to_string(), self.path.clone())]);
+        if self.kind == ColumnKind::Json {
+            metadata.insert(JSON_METADATA.to_string(), "true".to_string());
+        }
+        Field::new(&self.name, self.kind.data_type(), true).with_metadata(metadata)
+    }
+
+    fn array(&self, vectors: &[Vector]) -> ArrayRef {
+        let values = vectors.iter().map(|v| {
+            v.payload
+                .as_ref()
+                .and_then(|p| lookup(&p.data, &self.path))
+                .filter(|value| !value.is_null())
+        });
+        match self.kind {
+            ColumnKind::Boolean => Arc::new(
+                values
+                    .map(|v| v.and_then(Value::as_bool))
+                    .collect::<BooleanArray>(),
+            ),
+            ColumnKind::Int64 => Arc::new(
+                values
+                    .map(|v| v.and_then(Value::as_i64))
+                    .collect::<Int64Array>(),
+            ),
+            ColumnKind::Float64 => Arc::new(
+                values
+                    .map(|v| v.and_then(Value::as_f64))
+                    .collect::<Float64Array>(),
+            ),
+            ColumnKind::Utf8 => Arc::new(
+                values
+                    .map(|v| v.and_then(Value::as_str))
+                    .collect::<StringArray>(),
+            ),
+            ColumnKind::Json => Arc::new(
+                values
+                    .map(|v| v.map(Value::to_string))
+                    .collect::<StringArray>(),
+            ),
+        }
+    }
+}
+
+fn write_error(e: impl std::fmt::Display) -> VectorizerError {
+    VectorizerError::Storage(format!("Parquet export failed: {e}"))
+}
+
+fn read_error(e: impl std::fmt::Display) -> VectorizerError {
+    VectorizerError::Deserialization(format!("invalid Parquet file: {e}"))
+}
+
+/// The value at a dotted `path` of `payload`.
+fn lookup<'a>(payload: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .try_fold(payload, |value, key| value.get(key))
+}
+
+/// Store `value` at a dotted `path` of `target`, creating objects on
+/// the way.
+fn insert_path(target: &mut Map<String, Value>, path: &str, value: Value) {
+    match path.split_once('.') {
+        None => {
+            target.insert(path.to_string(), value);
+        }
+        Some((head, rest)) => {
+            let entry = target
+                .entry(head.to_string())
+                .or_insert_with(|| Value::Object(Map::new()));
+            if !entry.is_object() {
+                *entry = Value::Object(Map::new());
+            }
+            if let Value::Object(inner) = entry {
+                insert_path(inner, rest, value);
+            }
+        }
+    }
+}
+
+/// Columns for the payload fields of `vectors`, typed from the values
+/// seen. Requested fields that never occur become empty Utf8 columns.
+fn payload_columns(vectors: &[Vector], fields: Option<&[String]>) -> Vec<PayloadColumn> {
+    fn observe(slot: &mut Option<ColumnKind>, value: &Value) {
+        if let Some(kind) = ColumnKind::of(value) {
+            *slot = Some(slot.map_or(kind, |seen| seen.unify(kind)));
+        }
+    }
+
+    let mut kinds: BTreeMap<String, Option<ColumnKind>> = fields
+        .unwrap_or_default()
+        .iter()
+        .map(|path| (path.clone(), None))
+        .collect();
+    for data in vectors
+        .iter()
+        .filter_map(|v| v.payload.as_ref())
+        .map(|p| &p.data)
+    {
+        match fields {
+            Some(_) => {
+                for (path, slot) in kinds.iter_mut() {
+                    if let Some(value) = lookup(data, path) {
+                        observe(slot, value);
+                    }
+                }
+            }
+            None => {
+                for (key, value) in data.as_object().into_iter().flatten() {
+                    observe(kinds.entry(key.clone()).or_default(), value);
+                }
+            }
+        }
+    }
+
+    kinds
+        .into_iter()
+        .map(|(path, kind)| PayloadColumn {
+            name: if FIXED_COLUMNS.contains(&path.as_str()) {
+                format!("payload.{path}")
+            } else {
+                path.clone()
+            },
+            path,
+            kind: kind.unwrap_or(ColumnKind::Utf8),
+        })
+        .collect()
+}
+
+fn vector_item() -> Arc<Field> {
+    Arc::new(Field::new_list_field(DataType::Float32, true))
+}
+
+fn export_schema(columns: &[PayloadColumn]) -> Schema {
+    let mut fields = vec![
+        Field::new("id", DataType::Utf8, false),
+        Field::new("vector", DataType::List(vector_item()), false),
+        Field::new("document_id", DataType::Utf8, true),
+        Field::new("payload", DataType::Utf8, true),
+    ];
+    fields.extend(columns.iter().map(PayloadColumn::field));
+    Schema::new(fields)
+}
+
+fn record_batch(
+    schema: &SchemaRef,
+    columns: &[PayloadColumn],
+    vectors: &[Vector],
+) -> Result<RecordBatch> {
+    let mut ids = StringBuilder::new();
+    let mut data = ListBuilder::new(Float32Builder::new());
+    let mut document_ids = StringBuilder::new();
+    let mut payloads = StringBuilder::new();
+    for vector in vectors {
+        ids.append_value(&vector.id);
+        data.values().append_slice(&vector.data);
+        data.append(true);
+        document_ids.append_option(vector.document_id.as_deref());
+        payloads.append_option(
+            vector
+                .payload
+                .as_ref()
+                .map(|p| serde_json::to_string(&p.data))
+                .transpose()?,
+        );
+    }
+
+    let mut arrays: Vec<ArrayRef> = vec![
+        Arc::new(ids.finish()),
+        Arc::new(data.finish()),
+        Arc::new(document_ids.finish()),
+        Arc::new(payloads.finish()),
+    ];
+    arrays.extend(columns.iter().map(|c| c.array(vectors)));
+    RecordBatch::try_new(schema.clone(), arrays).map_err(write_error)
+}
+
+/// Write every vector of `collection_name` to `writer` as a Parquet
+/// file, in ID order.
+///
+/// # Errors
+///
+/// [`VectorizerError::CollectionNotFound`] for an unknown collection;
+/// [`VectorizerError::Storage`] when encoding or writing fails.
+pub fn export_parquet<W: Write + Send>(
+    store: &VectorStore,
+    collection_name: &str,
+    writer: W,
+    options: &ParquetExportOptions,
+) -> Result<ParquetSummary> {
+    let mut vectors = store.get_collection(collection_name)?.get_all_vectors();
+    vectors.sort_by(|a, b| a.id.cmp(&b.id));
+
+    let columns = payload_columns(&vectors, options.fields.as_deref());
+    let schema = Arc::new(export_schema(&columns));
+    let properties = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .build();
+    let mut writer =
+        ArrowWriter::try_new(writer, schema.clone(), Some(properties)).map_err(write_error)?;
+    for chunk in vectors.chunks(BATCH_ROWS) {
+        let batch = record_batch(&schema, &columns, chunk)?;
+        writer.write(&batch).map_err(write_error)?;
+    }
+    writer
+        .into_inner()
+        .map_err(write_error)?
+        .flush()
+        .map_err(write_error)?;
+
+    info!(
+        "Exported {} vectors of '{}' to Parquet",
+        vectors.len(),
+        collection_name
+    );
+    Ok(ParquetSummary {
+        rows: vectors.len(),
+        columns: schema.fields().iter().map(|f| f.name().clone()).collect(),
+    })
+}
+
+/// A column that feeds the payload, cast to the type it is read as.
+struct SourceColumn {
+    path: String,
+    kind: ColumnKind,
+    array: ArrayRef,
+}
+
+impl SourceColumn {
+    fn new(field: &Field, array: &ArrayRef) -> Result<Self> {
+        let kind = match field.data_type() {
+            DataType::Boolean => ColumnKind::Boolean,
+            t if t.is_integer() => ColumnKind::Int64,
+            t if t.is_floating() => ColumnKind::Float64,
+            _ if field.metadata().contains_key(JSON_METADATA) => ColumnKind::Json,
+            _ => ColumnKind::Utf8,
+        };
+        let array = cast(array, &kind.data_type())
+            .map_err(|e| read_error(format!("column `{}`: {e}", field.name())))?;
+        Ok(Self {
+            path: field
+                .metadata()
+                .get(PATH_METADATA)
+                .cloned()
+                .unwrap_or_else(|| field.name().clone()),
+            kind,
+            array,
+        })
+    }
+
+    fn value(&self, row: usize) -> Result<Option<Value>> {
+        if self.array.is_null(row) {
+            return Ok(None);
+        }
+        let value = match self.kind {
+            ColumnKind::Boolean => Value::Bool(self.array.as_boolean().value(row)),
+            ColumnKind::Int64 => Value::from(self.array.as_primitive::<Int64Type>().value(row)),
+            ColumnKind::Float64 => {
+                serde_json::Number::from_f64(self.array.as_primitive::<Float64Type>().value(row))
+                    .map_or(Value::Null, Value::Number)
+            }
+            ColumnKind::Utf8 => Value::String(self.array.as_string::<i32>().value(row).to_string()),
+            ColumnKind::Json => serde_json::from_str(self.array.as_string::<i32>().value(row))
+                .map_err(|e| read_error(format!("column `{}`: {e}", self.path)))?,
+        };
+        Ok(Some(value))
+    }
+}
+
+/// A column named `name` cast to Utf8, if the batch has it.
+fn text_column(batch: &RecordBatch, name: &str) -> Result<Option<ArrayRef>> {
+    batch
+        .column_by_name(name)
+        .map(|array| {
+            cast(array, &DataType::Utf8).map_err(|e| read_error(format!("column `{name}`: {e}")))
+        })
+        .transpose()
+}
+
+fn text_at(array: Option<&ArrayRef>, row: usize) -> Option<&str> {
+    array
+        .filter(|a| a.is_valid(row))
+        .map(|a| a.as_string::<i32>().value(row))
+}
+
+fn vectors_from_batch(
+    store: &VectorStore,
+    collection_name: &str,
+    batch: &RecordBatch,
+) -> Result<Vec<Vector>> {
+    let lists = batch
+        .column_by_name("vector")
+        .ok_or_else(|| read_error("no `vector` column"))?;
+    let lists = cast(lists, &DataType::List(vector_item()))
+        .map_err(|e| read_error(format!("column `vector`: {e}")))?;
+    let lists = lists.as_list::<i32>();
+    let ids = text_column(batch, "id")?;
+    let document_ids = text_column(batch, "document_id")?;
+    let payloads = text_column(batch, "payload")?;
+    let schema = batch.schema();
+    let sources = schema
+        .fields()
+        .iter()
+        .zip(batch.columns())
+        .filter(|(field, _)| !FIXED_COLUMNS.contains(&field.name().as_str()))
+        .map(|(field, array)| SourceColumn::new(field, array))
+        .collect::<Result<Vec<_>>>()?;
+
+    (0..batch.num_rows())
+        .map(|row| {
+            if lists.is_null(row) {
+                return Err(read_error(format!("row {row} has no vector")));
+            }
+            let values = lists.value(row);
+            let values = values.as_primitive::<Float32Type>();
+            if values.null_count() > 0 {
+                return Err(read_error(format!("vector of row {row} has null values")));
+            }
+
+            let payload = match payloads.as_ref() {
+                Some(payloads) => text_at(Some(payloads), row)
+                    .map(serde_json::from_str::<Value>)
+                    .transpose()
+                    .map_err(|e| read_error(format!("payload of row {row}: {e}")))?,
+                None => {
+                    let mut fields = Map::new();
+                    for source in &sources {
+                        if let Some(value) = source.value(row)? {
+                            insert_path(&mut fields, &source.path, value);
+                        }
+                    }
+                    (!fields.is_empty()).then_some(Value::Object(fields))
+                }
+            };
+
+            Ok(Vector {
+                id: text_at(ids.as_ref(), row)
+                    .map(str::to_string)
+                    .unwrap_or_else(|| store.new_vector_id(collection_name)),
+                data: values.values().to_vec(),
+                sparse: None,
+                payload: payload.map(Payload::new),
+                document_id: text_at(document_ids.as_ref(), row).map(str::to_string),
+            })
+        })
+        .collect()
+}
+
+/// Rows in the Parquet file `reader`, from its footer.
+///
+/// # Errors
+///
+/// [`VectorizerError::Deserialization`] for a file that is not Parquet.
+pub fn parquet_rows<R: ChunkReader + 'static>(reader: R) -> Result<usize> {
+    let builder = ParquetRecordBatchReaderBuilder::try_new(reader).map_err(read_error)?;
+    Ok(usize::try_from(builder.metadata().file_metadata().num_rows()).unwrap_or(0))
+}
+
+/// Insert the rows of the Parquet file `reader` into `collection_name`.
+///
+/// # Errors
+///
+/// [`VectorizerError::CollectionNotFound`] for an unknown collection;
+/// [`VectorizerError::Deserialization`] for a file that is not Parquet
+/// or has no usable `vector` column; any error of
+/// [`VectorStore::insert`] (dimension mismatch, quota, ...).
+pub fn import_parquet<R: ChunkReader + 'static>(
+    store: &VectorStore,
+    collection_name: &str,
+    reader: R,
+) -> Result<ParquetImport> {
+    store.get_collection(collection_name)?;
+    let reader = ParquetRecordBatchReaderBuilder::try_new(reader)
+        .map_err(read_error)?
+        .with_batch_size(BATCH_ROWS)
+        .build()
+        .map_err(read_error)?;
+    let columns = reader
+        .schema()
+        .fields()
+        .iter()
+        .map(|f| f.name().clone())
+        .collect();
+
+    let mut ids = Vec::new();
+    for batch in reader {
+        let batch = batch.map_err(read_error)?;
+        let vectors = vectors_from_batch(store, collection_name, &batch)?;
+        let batch_ids: Vec<String> = vectors.iter().map(|v| v.id.clone()).collect();
+        store.insert(collection_name, vectors)?;
+        ids.extend(batch_ids);
+    }
+
+    info!(
+        "Imported {} vectors from Parquet into '{}'",
+        ids.len(),
+        collection_name
+    );
+    Ok(ParquetImport { ids, columns })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::models::CollectionConfig;
+
+    fn store_with(name: &str) -> VectorStore {
+        let store = VectorStore::new();
+        let config = CollectionConfig {
+            dimension: 3,
+            ..Default::default()
+        };
+        store.create_collection(name, config).unwrap();
+        store
+    }
+
+    #[test]
+    fn export_round_trips_and_maps_payload_fields() {
+        let source = store_with("src");
+        source
+            .insert(
+                "src",
+                vec![
+                    Vector::with_payload(
+                        "a".to_string(),
+                        vec![1.0, 0.0, 0.0],
+                        Payload::new(json!({"title": "A", "rank": 1, "tags": ["x"]})),
+                    ),
+                    Vector::with_payload(
+                        "b".to_string(),
+                        vec![0.0, 1.0, 0.0],
+                        Payload::new(json!({"title": "B", "rank": 2.5, "id": 7})),
+                    ),
+                ],
+            )
+            .unwrap();
+
+        let file = tempfile::tempfile().unwrap();
+        let summary = export_parquet(
+            &source,
+            "src",
+            file.try_clone().unwrap(),
+            &ParquetExportOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(summary.rows, 2);
+        assert_eq!(
+            summary.columns,
+            [
+                "id",
+                "vector",
+                "document_id",
+                "payload",
+                "payload.id",
+                "rank",
+                "tags",
+                "title"
+            ]
+        );
+
+        let batch = ParquetRecordBatchReaderBuilder::try_new(file.try_clone().unwrap())
+            .unwrap()
+            .build()
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+        let schema = batch.schema();
+        let kind = |name: &str| schema.field_with_name(name).unwrap().data_type().clone();
+        assert_eq!(kind("rank"), DataType::Float64);
+        assert_eq!(kind("title"), DataType::Utf8);
+        assert_eq!(kind("payload.id"), DataType::Int64);
+
+        let target = store_with("dst");
+        let imported = import_parquet(&target, "dst", file).unwrap();
+        assert_eq!(imported.ids, ["a", "b"]);
+        let b = target.get_vector("dst", "b").unwrap();
+        assert_eq!(b.data, vec![0.0, 1.0, 0.0]);
+        assert_eq!(
+            b.payload.unwrap().data,
+            json!({"title": "B", "rank": 2.5, "id": 7})
+        );
+    }
+
+    #[test]
+    fn import_rebuilds_payload_from_columns() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("vector", DataType::List(vector_item()), false),
+            Field::new("meta.lang", DataType::Utf8, true),
+            Field::new("year", DataType::Int32, true),
+        ]));
+        let mut vectors = ListBuilder::new(Float32Builder::new());
+        vectors.values().append_slice(&[0.0, 0.0, 1.0]);
+        vectors.append(true);
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(vectors.finish()),
+                Arc::new(StringArray::from(vec!["en"])),
+                Arc::new(arrow::array::Int32Array::from(vec![2024])),
+            ],
+        )
+        .unwrap();
+        let file = tempfile::tempfile().unwrap();
+        let mut writer = ArrowWriter::try_new(file.try_clone().unwrap(), schema, None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+
+        let store = store_with("docs");
+        import_parquet(&store, "docs", file).unwrap();
+        let vectors = store.get_collection("docs").unwrap().get_all_vectors();
+        assert_eq!(vectors.len(), 1);
+        assert_eq!(
+            vectors[0].payload.as_ref().unwrap().data,
+            json!({"meta": {"lang": "en"}, "year": 2024})
+        );
+    }
+}
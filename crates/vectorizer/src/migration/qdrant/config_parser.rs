@@ -220,6 +220,13 @@ impl QdrantConfigParser {
             sharding: None,
             graph: None,
             encryption: None,
+            id_policy: None,
+            quota: None,
+            scoring: None,
+            tenant_partition: None,
+            dedup: None,
+            chunking: None,
+            full_text: None,
         })
     }
 
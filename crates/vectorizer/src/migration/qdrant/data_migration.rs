@@ -251,7 +251,7 @@ impl QdrantDataImporter {
         let metric = match &qdrant_config.params.vectors {
             QdrantVectorsConfigResponse::Vector { size: _, distance } => match distance.as_str() {
                 "Cosine" => DistanceMetric::Cosine,
-                "Euclidean" => DistanceMetric::Euclidean,
+                "Euclidean" | "Euclid" => DistanceMetric::Euclidean,
                 "Dot" => DistanceMetric::DotProduct,
                 _ => DistanceMetric::Cosine,
             },
@@ -290,6 +290,13 @@ impl QdrantDataImporter {
             sharding: None,
             graph: None,
             encryption: None,
+            id_policy: None,
+            quota: None,
+            scoring: None,
+            tenant_partition: None,
+            dedup: None,
+            chunking: None,
+            full_text: None,
         })
     }
 
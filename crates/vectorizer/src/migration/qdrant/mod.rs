@@ -1,14 +1,16 @@
 //! Qdrant migration tools
 //!
 //! Tools for migrating from Qdrant to Vectorizer, including configuration parsing,
-//! data export/import, and migration validation.
+//! data export/import, snapshot archive reading, and migration validation.
 
 pub mod config_parser;
 pub mod data_migration;
+pub mod snapshot;
 pub mod validator;
 
 pub use config_parser::{ConfigFormat, QdrantConfigParser, ValidationResult};
 pub use data_migration::{
     ExportedCollection, ImportResult, QdrantDataExporter, QdrantDataImporter,
 };
+pub use snapshot::{QdrantSnapshotContents, QdrantSnapshotReader};
 pub use validator::MigrationValidator;
@@ -0,0 +1,737 @@
+//! Qdrant snapshot archive reader
+//!
+//! Reads the collection snapshots Qdrant writes (`POST
+//! /collections/{name}/snapshots`) into an [`ExportedCollection`], so a
+//! collection migrates by uploading its snapshot instead of scrolling it
+//! out of a running Qdrant.
+//!
+//! A snapshot is a tar archive with the collection `config.json` at the
+//! root and one directory per shard, whose `segments/` hold every segment
+//! either as a nested tar or unpacked. A segment is found by its
+//! `segment.json`; its files are read relative to that directory:
+//!
+//! - `id_tracker.mappings` — the append-only log of the mutable ID
+//!   tracker, replayed to map external point IDs to internal offsets
+//! - `vector_storage[-<name>]/matrix.dat` (`Mmap` storage) or
+//!   `vector_storage[-<name>]/vectors/chunk_*.mmap` (`ChunkedMmap` /
+//!   `InRamChunkedMmap`) — the dense vectors, by internal offset
+//! - `payload_storage/` — mmap (gridstore) payload pages
+//!
+//! Segments kept in RocksDB (`Memory` vector storage, the RocksDB ID
+//! tracker) cannot be read from an archive; they are reported and
+//! skipped. A payload storage that cannot be read only drops the payloads
+//! of its segment. Sparse vectors are not imported.
+
+// Internal data-layout file: public fields are self-documenting; the
+// blanket allow keeps `cargo doc -W missing-docs` clean without padding
+// every field with a tautological `///` comment. See
+// phase4_enforce-public-api-docs.
+#![allow(missing_docs)]
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::{Cursor, Read};
+
+use serde::Deserialize;
+use serde_json::Value;
+use tracing::{info, warn};
+
+use super::data_migration::{
+    ExportedCollection, QdrantCollectionConfig, QdrantCollectionParams, QdrantPoint, QdrantVector,
+    QdrantVectorsConfigResponse,
+};
+use crate::error::{Result, VectorizerError};
+
+/// Header of an mmap vector matrix.
+const MATRIX_HEADER: &[u8] = b"data";
+
+/// Header of a gridstore tracker (`next_pointer_offset: u32`).
+const TRACKER_HEADER_BYTES: usize = 4;
+
+/// One gridstore tracker slot: `Option<ValuePointer>` as a `u32` tag
+/// followed by `page_id`, `block_offset` and `length`.
+const TRACKER_SLOT_BYTES: usize = 16;
+
+/// Files of one archive, by path.
+type Files = BTreeMap<String, Vec<u8>>;
+
+/// Qdrant snapshot reader
+pub struct QdrantSnapshotReader;
+
+/// What a snapshot held.
+#[derive(Debug, Clone)]
+pub struct QdrantSnapshotContents {
+    /// The collection, ready for [`super::QdrantDataImporter`]
+    pub collection: ExportedCollection,
+    /// Segments read
+    pub segments: usize,
+    /// Segments or storages that were skipped, and why
+    pub warnings: Vec<String>,
+}
+
+impl QdrantSnapshotReader {
+    /// Whether `data` looks like a Qdrant collection snapshot: a tar
+    /// archive with a root `config.json` and a `segments/` directory.
+    pub fn is_snapshot(data: &[u8]) -> bool {
+        let is_tar = data.len() > 262 && &data[257..262] == b"ustar";
+        if !is_tar {
+            return false;
+        }
+        let mut archive = tar::Archive::new(Cursor::new(data));
+        let Ok(entries) = archive.entries() else {
+            return false;
+        };
+        let (mut config, mut segments) = (false, false);
+        for entry in entries.flatten() {
+            let Ok(path) = entry.path() else { continue };
+            let path = normalize(&path.to_string_lossy());
+            config |= path == "config.json";
+            segments |= path.contains("segments/");
+            if config && segments {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Read a snapshot into a collection named `name`. Collections with
+    /// several named vectors need `vector_name` to pick the one to
+    /// import; a single vector is picked on its own.
+    pub fn read(
+        data: &[u8],
+        name: &str,
+        vector_name: Option<&str>,
+    ) -> Result<QdrantSnapshotContents> {
+        let files = read_archive(data)?;
+        let config = files
+            .get("config.json")
+            .ok_or_else(|| invalid("no config.json at the archive root"))?;
+        let config: SnapshotConfig = serde_json::from_slice(config)
+            .map_err(|e| invalid(&format!("unreadable config.json: {e}")))?;
+        let (vector_name, size, distance) = config.pick_vector(vector_name)?;
+
+        let mut warnings = Vec::new();
+        let mut failed = 0;
+        let mut points: BTreeMap<String, QdrantPoint> = BTreeMap::new();
+        let segments = split_segments(&files)?;
+        for (segment_name, segment) in &segments {
+            match read_segment(segment, &vector_name, size) {
+                Ok(read) => {
+                    if let Some(warning) = read.payload_warning {
+                        warnings.push(format!("segment {segment_name}: {warning}"));
+                    }
+                    for point in read.points {
+                        points.insert(point.id.clone(), point);
+                    }
+                }
+                Err(e) => {
+                    failed += 1;
+                    warn!("Skipping Qdrant segment {}: {}", segment_name, e);
+                    warnings.push(format!("segment {segment_name}: {e}"));
+                }
+            }
+        }
+        if failed > 0 && failed == segments.len() {
+            return Err(invalid(&format!(
+                "no segment could be read: {}",
+                warnings.join("; ")
+            )));
+        }
+
+        info!(
+            "📖 Read Qdrant snapshot: {} points from {} segments",
+            points.len(),
+            segments.len()
+        );
+        Ok(QdrantSnapshotContents {
+            collection: ExportedCollection {
+                name: name.to_string(),
+                config: QdrantCollectionConfig {
+                    params: QdrantCollectionParams {
+                        vectors: QdrantVectorsConfigResponse::Vector {
+                            size: size as u32,
+                            distance,
+                        },
+                        hnsw_config: config
+                            .hnsw_config
+                            .and_then(|hnsw| serde_json::from_value(hnsw).ok()),
+                        quantization_config: None,
+                    },
+                },
+                points: points.into_values().collect(),
+            },
+            segments: segments.len(),
+            warnings,
+        })
+    }
+}
+
+fn invalid(message: &str) -> VectorizerError {
+    VectorizerError::Deserialization(format!("Qdrant snapshot: {message}"))
+}
+
+fn normalize(path: &str) -> String {
+    path.trim_start_matches("./").to_string()
+}
+
+/// Every regular file of a tar archive.
+fn read_archive(data: &[u8]) -> Result<Files> {
+    let mut archive = tar::Archive::new(Cursor::new(data));
+    let mut files = Files::new();
+    let entries = archive
+        .entries()
+        .map_err(|e| invalid(&format!("not a tar archive: {e}")))?;
+    for entry in entries {
+        let mut entry = entry.map_err(|e| invalid(&format!("corrupt tar entry: {e}")))?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let path = normalize(&entry.path().map_err(VectorizerError::Io)?.to_string_lossy());
+        let mut bytes = Vec::with_capacity(entry.size() as usize);
+        entry.read_to_end(&mut bytes).map_err(VectorizerError::Io)?;
+        files.insert(path, bytes);
+    }
+    Ok(files)
+}
+
+/// The segments of a snapshot by name, each with its files relative to
+/// the directory of its `segment.json`.
+fn split_segments(files: &Files) -> Result<BTreeMap<String, Files>> {
+    let mut unpacked: BTreeMap<String, Files> = BTreeMap::new();
+    let mut segments = BTreeMap::new();
+    for (path, bytes) in files {
+        let Some((shard, rest)) = path.split_once("segments/") else {
+            continue;
+        };
+        match rest.split_once('/') {
+            // Unpacked segment directory
+            Some((segment, inner)) => {
+                unpacked
+                    .entry(format!("{shard}segments/{segment}"))
+                    .or_default()
+                    .insert(inner.to_string(), bytes.clone());
+            }
+            None if rest.ends_with(".tar") => {
+                let name = format!("{shard}segments/{}", rest.trim_end_matches(".tar"));
+                segments.insert(name, read_archive(bytes)?);
+            }
+            None => {}
+        }
+    }
+    segments.extend(unpacked);
+    Ok(segments
+        .into_iter()
+        .map(|(name, files)| (name, rebase(files)))
+        .collect())
+}
+
+/// Paths relative to the directory holding `segment.json`.
+fn rebase(files: Files) -> Files {
+    let Some(root) = files
+        .keys()
+        .find(|p| *p == "segment.json" || p.ends_with("/segment.json"))
+        .map(|p| p.trim_end_matches("segment.json").to_string())
+    else {
+        return files;
+    };
+    files
+        .into_iter()
+        .filter_map(|(path, bytes)| {
+            path.strip_prefix(root.as_str())
+                .map(|inner| (inner.to_string(), bytes))
+        })
+        .collect()
+}
+
+#[derive(Debug, Deserialize)]
+struct SnapshotConfig {
+    params: SnapshotParams,
+    #[serde(default)]
+    hnsw_config: Option<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SnapshotParams {
+    vectors: Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct VectorParams {
+    size: usize,
+    distance: String,
+}
+
+impl SnapshotConfig {
+    /// The vector to import: its name (`""` when unnamed), size and
+    /// distance.
+    fn pick_vector(&self, wanted: Option<&str>) -> Result<(String, usize, String)> {
+        let vectors = &self.params.vectors;
+        if vectors.get("size").is_some() {
+            let params: VectorParams = serde_json::from_value(vectors.clone())
+                .map_err(|e| invalid(&format!("unreadable vector params: {e}")))?;
+            return Ok((String::new(), params.size, params.distance));
+        }
+        let named: BTreeMap<String, VectorParams> = serde_json::from_value(vectors.clone())
+            .map_err(|e| invalid(&format!("unreadable vector params: {e}")))?;
+        let names = || named.keys().cloned().collect::<Vec<_>>().join(", ");
+        let name = match wanted {
+            Some(name) => name.to_string(),
+            None if named.len() == 1 => named.keys().next().cloned().unwrap_or_default(),
+            None => {
+                return Err(invalid(&format!(
+                    "the collection has several named vectors ({}), pick one",
+                    names()
+                )));
+            }
+        };
+        let params = named.get(&name).ok_or_else(|| {
+            invalid(&format!(
+                "no vector named '{name}', the collection has: {}",
+                names()
+            ))
+        })?;
+        Ok((name, params.size, params.distance.clone()))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SegmentState {
+    config: SegmentConfig,
+}
+
+#[derive(Debug, Deserialize)]
+struct SegmentConfig {
+    #[serde(default)]
+    vector_data: HashMap<String, SegmentVectorConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SegmentVectorConfig {
+    size: usize,
+    #[serde(default)]
+    storage_type: Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChunkedConfig {
+    chunk_size_vectors: usize,
+    dim: usize,
+}
+
+#[derive(Debug, Deserialize)]
+struct GridstoreConfig {
+    block_size_bytes: usize,
+    #[serde(default)]
+    compression: String,
+}
+
+struct SegmentPoints {
+    points: Vec<QdrantPoint>,
+    payload_warning: Option<String>,
+}
+
+fn read_segment(files: &Files, vector_name: &str, size: usize) -> Result<SegmentPoints> {
+    let state = files
+        .get("segment.json")
+        .ok_or_else(|| invalid("no segment.json"))?;
+    let state: SegmentState = serde_json::from_slice(state)
+        .map_err(|e| invalid(&format!("unreadable segment.json: {e}")))?;
+    let vector_config = state
+        .config
+        .vector_data
+        .get(vector_name)
+        .ok_or_else(|| invalid(&format!("no vector '{vector_name}' in the segment")))?;
+    if vector_config.size != size {
+        return Err(invalid(&format!(
+            "segment vectors have {} dimensions, the collection {size}",
+            vector_config.size
+        )));
+    }
+
+    let ids = read_id_tracker(files)?;
+    let vectors = VectorStorage::open(files, vector_name, &vector_config.storage_type, size)?;
+    let (payloads, payload_warning) = match PayloadStorage::open(files) {
+        Ok(payloads) => (Some(payloads), None),
+        Err(e) => (None, Some(format!("{e}; points imported without payloads"))),
+    };
+
+    let mut points = Vec::with_capacity(ids.len());
+    for (id, offset) in ids {
+        let vector = vectors
+            .get(offset)
+            .ok_or_else(|| invalid(&format!("point {id} has no vector at offset {offset}")))?;
+        points.push(QdrantPoint {
+            id,
+            vector: QdrantVector::Dense(vector),
+            payload: payloads.as_ref().and_then(|p| p.get(offset)),
+        });
+    }
+    Ok(SegmentPoints {
+        points,
+        payload_warning,
+    })
+}
+
+/// Replay `id_tracker.mappings`: each entry is a `u8` change type (1/2
+/// insert a numeric/UUID ID, 3/4 delete one), the external ID (`u64` LE
+/// or 16 UUID bytes) and, for inserts, the internal offset (`u32` LE).
+fn read_id_tracker(files: &Files) -> Result<BTreeMap<String, u32>> {
+    let log = files.get("id_tracker.mappings").ok_or_else(|| {
+        invalid("no id_tracker.mappings (RocksDB ID trackers cannot be read from a snapshot)")
+    })?;
+    let truncated = || invalid("truncated id_tracker.mappings");
+    let mut ids = BTreeMap::new();
+    let mut at = 0;
+    while at < log.len() {
+        let change = log[at];
+        at += 1;
+        let id = match change {
+            1 | 3 => {
+                let bytes = log.get(at..at + 8).ok_or_else(truncated)?;
+                at += 8;
+                u64::from_le_bytes(bytes.try_into().map_err(|_| truncated())?).to_string()
+            }
+            2 | 4 => {
+                let bytes = log.get(at..at + 16).ok_or_else(truncated)?;
+                at += 16;
+                uuid::Uuid::from_slice(bytes)
+                    .map_err(|_| truncated())?
+                    .to_string()
+            }
+            other => {
+                return Err(invalid(&format!(
+                    "unknown id_tracker.mappings entry type {other}"
+                )));
+            }
+        };
+        if change <= 2 {
+            let bytes = log.get(at..at + 4).ok_or_else(truncated)?;
+            at += 4;
+            ids.insert(
+                id,
+                u32::from_le_bytes(bytes.try_into().map_err(|_| truncated())?),
+            );
+        } else {
+            ids.remove(&id);
+        }
+    }
+    Ok(ids)
+}
+
+enum VectorStorage<'a> {
+    Matrix {
+        data: &'a [u8],
+        dim: usize,
+    },
+    Chunked {
+        chunks: Vec<&'a [u8]>,
+        per_chunk: usize,
+        len: usize,
+        dim: usize,
+    },
+}
+
+impl<'a> VectorStorage<'a> {
+    fn open(files: &'a Files, name: &str, storage_type: &Value, dim: usize) -> Result<Self> {
+        let dir = if name.is_empty() {
+            "vector_storage".to_string()
+        } else {
+            format!("vector_storage-{name}")
+        };
+        if let Some(matrix) = files.get(&format!("{dir}/matrix.dat")) {
+            let data = matrix
+                .strip_prefix(MATRIX_HEADER)
+                .ok_or_else(|| invalid("matrix.dat has no data header"))?;
+            return Ok(Self::Matrix { data, dim });
+        }
+        if let Some(config) = files.get(&format!("{dir}/vectors/config.json")) {
+            let config: ChunkedConfig = serde_json::from_slice(config)
+                .map_err(|e| invalid(&format!("unreadable chunked vector config: {e}")))?;
+            if config.dim != dim || config.chunk_size_vectors == 0 {
+                return Err(invalid("chunked vector config does not match the segment"));
+            }
+            let len = files
+                .get(&format!("{dir}/vectors/status.dat"))
+                .and_then(|status| status.get(..8))
+                .and_then(|len| len.try_into().ok())
+                .map(u64::from_le_bytes)
+                .ok_or_else(|| invalid("no chunked vector status.dat"))?;
+            let chunks = (0..)
+                .map_while(|i| files.get(&format!("{dir}/vectors/chunk_{i}.mmap")))
+                .map(Vec::as_slice)
+                .collect();
+            return Ok(Self::Chunked {
+                chunks,
+                per_chunk: config.chunk_size_vectors,
+                len: len as usize,
+                dim,
+            });
+        }
+        Err(invalid(&format!(
+            "vector storage {storage_type} is not readable from a snapshot, only Mmap and ChunkedMmap are"
+        )))
+    }
+
+    fn get(&self, offset: u32) -> Option<Vec<f32>> {
+        let offset = offset as usize;
+        let (data, index, dim) = match self {
+            Self::Matrix { data, dim } => (*data, offset, *dim),
+            Self::Chunked {
+                chunks,
+                per_chunk,
+                len,
+                dim,
+            } => {
+                if offset >= *len {
+                    return None;
+                }
+                (*chunks.get(offset / per_chunk)?, offset % per_chunk, *dim)
+            }
+        };
+        let bytes = data.get(index * dim * 4..(index + 1) * dim * 4)?;
+        Some(
+            bytes
+                .chunks_exact(4)
+                .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                .collect(),
+        )
+    }
+}
+
+/// Mmap (gridstore) payload storage: `tracker.dat` maps an internal
+/// offset to a value pointer, the value is a JSON payload in
+/// `page_<id>.dat`, LZ4-compressed unless the config says `None`.
+struct PayloadStorage<'a> {
+    files: &'a Files,
+    tracker: &'a [u8],
+    block_size: usize,
+    lz4: bool,
+}
+
+impl<'a> PayloadStorage<'a> {
+    fn open(files: &'a Files) -> Result<Self> {
+        let (Some(config), Some(tracker)) = (
+            files.get("payload_storage/config.json"),
+            files.get("payload_storage/tracker.dat"),
+        ) else {
+            return Err(invalid(
+                "payload storage is not mmap (RocksDB payloads cannot be read from a snapshot)",
+            ));
+        };
+        let config: GridstoreConfig = serde_json::from_slice(config)
+            .map_err(|e| invalid(&format!("unreadable payload storage config: {e}")))?;
+        Ok(Self {
+            files,
+            tracker: tracker.get(TRACKER_HEADER_BYTES..).unwrap_or_default(),
+            block_size: config.block_size_bytes,
+            lz4: !config.compression.eq_ignore_ascii_case("none"),
+        })
+    }
+
+    fn get(&self, offset: u32) -> Option<Value> {
+        let start = offset as usize * TRACKER_SLOT_BYTES;
+        let slot = self.tracker.get(start..start + TRACKER_SLOT_BYTES)?;
+        let field = |i: usize| u32::from_le_bytes([slot[i], slot[i + 1], slot[i + 2], slot[i + 3]]);
+        if field(0) == 0 {
+            return None;
+        }
+        let (page, block, length) = (field(4), field(8) as usize, field(12) as usize);
+        let page = self
+            .files
+            .get(&format!("payload_storage/page_{page}.dat"))?;
+        let value = page.get(block * self.block_size..block * self.block_size + length)?;
+        let value = if self.lz4 {
+            lz4_flex::decompress_size_prepended(value).ok()?
+        } else {
+            value.to_vec()
+        };
+        serde_json::from_slice(&value)
+            .ok()
+            .filter(|payload: &Value| payload.as_object().is_some_and(|p| !p.is_empty()))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn tar_of<P: AsRef<str>>(files: &[(P, Vec<u8>)]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+        for (path, bytes) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_entry_type(tar::EntryType::Regular);
+            header.set_size(bytes.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, path.as_ref(), bytes.as_slice())
+                .unwrap();
+        }
+        builder.into_inner().unwrap()
+    }
+
+    fn matrix(vectors: &[[f32; 2]]) -> Vec<u8> {
+        let mut bytes = MATRIX_HEADER.to_vec();
+        for v in vectors.iter().flatten() {
+            bytes.extend_from_slice(&v.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Insert numeric IDs 10 and 11 at offsets 0 and 1, a UUID at 2,
+    /// then delete 11.
+    fn id_log(uuid: uuid::Uuid) -> Vec<u8> {
+        let mut log = Vec::new();
+        for (id, offset) in [(10u64, 0u32), (11, 1)] {
+            log.push(1);
+            log.extend_from_slice(&id.to_le_bytes());
+            log.extend_from_slice(&offset.to_le_bytes());
+        }
+        log.push(2);
+        log.extend_from_slice(uuid.as_bytes());
+        log.extend_from_slice(&2u32.to_le_bytes());
+        log.push(3);
+        log.extend_from_slice(&11u64.to_le_bytes());
+        log
+    }
+
+    fn payloads(values: &[Value]) -> Vec<(&'static str, Vec<u8>)> {
+        let block_size = 32;
+        let mut tracker = vec![0u8; TRACKER_HEADER_BYTES];
+        let mut page = Vec::new();
+        for value in values {
+            let bytes = lz4_flex::compress_prepend_size(value.to_string().as_bytes());
+            let block = page.len() / block_size;
+            for field in [1u32, 0, block as u32, bytes.len() as u32] {
+                tracker.extend_from_slice(&field.to_le_bytes());
+            }
+            page.extend_from_slice(&bytes);
+            page.resize(page.len().div_ceil(block_size) * block_size, 0);
+        }
+        vec![
+            (
+                "payload_storage/config.json",
+                json!({"block_size_bytes": block_size, "compression": "LZ4"})
+                    .to_string()
+                    .into_bytes(),
+            ),
+            ("payload_storage/tracker.dat", tracker),
+            ("payload_storage/page_0.dat", page),
+        ]
+    }
+
+    fn snapshot(uuid: uuid::Uuid) -> Vec<u8> {
+        let segment_json = json!({
+            "version": 42,
+            "config": {
+                "vector_data": {"": {"size": 2, "distance": "Dot", "storage_type": "Mmap"}},
+                "payload_storage_type": {"type": "mmap"},
+            },
+        });
+        let mut segment = vec![
+            ("segment.json", segment_json.to_string().into_bytes()),
+            (
+                "vector_storage/matrix.dat",
+                matrix(&[[1.0, 0.0], [0.0, 1.0], [0.5, 0.5]]),
+            ),
+            ("id_tracker.mappings", id_log(uuid)),
+        ];
+        segment.extend(payloads(&[
+            json!({"title": "ten"}),
+            json!({}),
+            json!({"title": "u"}),
+        ]));
+        let segment: Vec<(String, Vec<u8>)> = segment
+            .into_iter()
+            .map(|(path, bytes)| (format!("snapshot/files/{path}"), bytes))
+            .collect();
+        let config = json!({
+            "params": {"vectors": {"size": 2, "distance": "Dot"}, "shard_number": 1},
+            "hnsw_config": {"m": 16, "ef_construct": 100, "full_scan_threshold": 10000},
+        });
+        tar_of(&[
+            ("config.json", config.to_string().into_bytes()),
+            ("0/segments/5c1e.tar", tar_of(&segment)),
+            ("0/wal/open-1", vec![0; 8]),
+        ])
+    }
+
+    #[test]
+    fn reads_points_vectors_and_payloads() {
+        let uuid = uuid::Uuid::new_v4();
+        let data = snapshot(uuid);
+        assert!(QdrantSnapshotReader::is_snapshot(&data));
+
+        let contents = QdrantSnapshotReader::read(&data, "migrated", None).unwrap();
+        assert_eq!(contents.segments, 1);
+        assert!(contents.warnings.is_empty(), "{:?}", contents.warnings);
+        let collection = contents.collection;
+        assert_eq!(collection.name, "migrated");
+        assert!(matches!(
+            collection.config.params.vectors,
+            QdrantVectorsConfigResponse::Vector { size: 2, ref distance } if distance == "Dot"
+        ));
+        assert!(collection.config.params.hnsw_config.is_some());
+
+        let points: HashMap<_, _> = collection
+            .points
+            .iter()
+            .map(|p| (p.id.clone(), p))
+            .collect();
+        assert_eq!(points.len(), 2, "the deleted point is dropped");
+        let ten = points["10"];
+        assert!(matches!(&ten.vector, QdrantVector::Dense(v) if v == &[1.0, 0.0]));
+        assert_eq!(ten.payload, Some(json!({"title": "ten"})));
+        assert_eq!(
+            points[&uuid.to_string()].payload,
+            Some(json!({"title": "u"}))
+        );
+    }
+
+    #[test]
+    fn rejects_other_archives_and_unreadable_segments() {
+        assert!(!QdrantSnapshotReader::is_snapshot(b"not a tar"));
+        let vecdb_like = tar_of(&[("vectorizer.vecdb", vec![1, 2, 3])]);
+        assert!(!QdrantSnapshotReader::is_snapshot(&vecdb_like));
+
+        let rocksdb_segment = tar_of(&[(
+            "segment.json",
+            json!({"config": {"vector_data": {"": {"size": 2, "distance": "Dot", "storage_type": "Memory"}}}})
+                .to_string()
+                .into_bytes(),
+        )]);
+        let data = tar_of(&[
+            (
+                "config.json",
+                json!({"params": {"vectors": {"size": 2, "distance": "Dot"}}})
+                    .to_string()
+                    .into_bytes(),
+            ),
+            ("0/segments/a.tar", rocksdb_segment),
+        ]);
+        let err = QdrantSnapshotReader::read(&data, "c", None).unwrap_err();
+        assert!(
+            err.to_string().contains("no segment could be read"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn named_vectors_need_a_pick() {
+        let config: SnapshotConfig = serde_json::from_value(json!({
+            "params": {"vectors": {
+                "text": {"size": 4, "distance": "Cosine"},
+                "image": {"size": 8, "distance": "Euclid"},
+            }},
+        }))
+        .unwrap();
+        assert!(config.pick_vector(None).is_err());
+        assert_eq!(
+            config.pick_vector(Some("image")).unwrap(),
+            ("image".to_string(), 8, "Euclid".to_string())
+        );
+        assert!(config.pick_vector(Some("audio")).is_err());
+    }
+}
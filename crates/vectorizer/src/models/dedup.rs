@@ -0,0 +1,209 @@
+//! Per-collection deduplication on insert: what makes an incoming
+//! vector a duplicate of a stored one, and what happens to it.
+//!
+//! Two checks, either or both:
+//!
+//! - **Content hash.** Vectors whose payload `hash_field` (default
+//!   `content_hash`) holds the same value are duplicates. A payload
+//!   without the field but with a string `content` (what the chunkers
+//!   write) is stamped with the xxh3 hash of that text first, so
+//!   re-indexing the same files matches the chunks already stored.
+//! - **Similarity.** A vector whose nearest stored neighbour scores at
+//!   least `similarity_threshold` is a duplicate of it.
+//!
+//! A vector is never a duplicate of itself: re-inserting an ID that is
+//! already stored replaces it as usual.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::{Result, VectorizerError};
+use crate::models::Payload;
+
+/// Payload field hashed when a vector carries no `hash_field` value.
+pub const DEDUP_CONTENT_FIELD: &str = "content";
+
+/// What happens to an incoming duplicate.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DedupAction {
+    /// Drop the incoming vector
+    #[default]
+    Skip,
+    /// Copy the incoming payload's fields onto the stored vector, which
+    /// keeps its ID and embedding
+    Merge,
+}
+
+/// Deduplication policy of a collection
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DedupPolicy {
+    /// Payload field compared for exact duplicates; `null` turns the
+    /// content-hash check off
+    #[serde(default = "default_hash_field")]
+    pub hash_field: Option<String>,
+    /// Nearest-neighbour score at or above which a vector is a duplicate
+    #[serde(default)]
+    pub similarity_threshold: Option<f32>,
+    /// What happens to duplicates
+    #[serde(default)]
+    pub action: DedupAction,
+}
+
+fn default_hash_field() -> Option<String> {
+    Some("content_hash".to_string())
+}
+
+impl Default for DedupPolicy {
+    fn default() -> Self {
+        Self {
+            hash_field: default_hash_field(),
+            similarity_threshold: None,
+            action: DedupAction::default(),
+        }
+    }
+}
+
+impl DedupPolicy {
+    /// Check the policy's settings
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |message: &str| {
+            Err(VectorizerError::InvalidConfiguration {
+                message: message.to_string(),
+            })
+        };
+        if self
+            .hash_field
+            .as_ref()
+            .is_some_and(|field| field.trim().is_empty())
+        {
+            return invalid("dedup.hash_field must not be empty");
+        }
+        if self.similarity_threshold.is_some_and(|t| !t.is_finite()) {
+            return invalid("dedup.similarity_threshold must be a finite number");
+        }
+        if self.hash_field.is_none() && self.similarity_threshold.is_none() {
+            return invalid("dedup needs a hash_field, a similarity_threshold or both");
+        }
+        Ok(())
+    }
+
+    /// The content hash of `payload`, stamping it from the `content`
+    /// field when the payload has none. `None` when the hash check is
+    /// off or the payload is encrypted or has nothing to hash.
+    pub fn content_hash(&self, payload: Option<&mut Payload>) -> Option<String> {
+        let field = self.hash_field.as_deref()?;
+        let payload = payload.filter(|p| !p.is_encrypted())?;
+        let object = payload.data.as_object_mut()?;
+        match object.get(field) {
+            Some(Value::String(hash)) => return Some(hash.clone()),
+            Some(other) => return Some(other.to_string()),
+            None => {}
+        }
+        let content = object.get(DEDUP_CONTENT_FIELD)?.as_str()?;
+        let hash = format!("{:016x}", xxhash_rust::xxh3::xxh3_64(content.as_bytes()));
+        object.insert(field.to_string(), Value::String(hash.clone()));
+        Some(hash)
+    }
+}
+
+/// Why an incoming vector counted as a duplicate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DedupReason {
+    /// Same content hash
+    ContentHash,
+    /// Nearest neighbour over the similarity threshold
+    Similarity,
+}
+
+/// The stored vector an incoming one duplicates.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Duplicate {
+    /// ID of the stored vector
+    pub duplicate_of: String,
+    /// Which check matched
+    pub reason: DedupReason,
+    /// Similarity score, for [`DedupReason::Similarity`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score: Option<f32>,
+}
+
+/// Outcome of one vector of an insert under a dedup policy.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum DedupStatus {
+    /// Stored as a new vector
+    Inserted,
+    /// Dropped as a duplicate
+    Skipped(Duplicate),
+    /// Its payload was merged into the duplicate
+    Merged(Duplicate),
+}
+
+impl DedupStatus {
+    /// The stored vector this one duplicated, if it did.
+    pub fn duplicate(&self) -> Option<&Duplicate> {
+        match self {
+            Self::Inserted => None,
+            Self::Skipped(duplicate) | Self::Merged(duplicate) => Some(duplicate),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn content_hash_is_stamped_from_content() {
+        let policy = DedupPolicy::default();
+        let mut payload = Payload::new(json!({ "content": "hello" }));
+        let hash = policy.content_hash(Some(&mut payload)).unwrap();
+        assert_eq!(payload.data["content_hash"], json!(hash));
+
+        // Same text, same hash; a supplied hash is used as is
+        let mut again = Payload::new(json!({ "content": "hello", "file": "b.md" }));
+        assert_eq!(policy.content_hash(Some(&mut again)), Some(hash));
+        let mut supplied = Payload::new(json!({ "content_hash": "abc" }));
+        assert_eq!(
+            policy.content_hash(Some(&mut supplied)).as_deref(),
+            Some("abc")
+        );
+        assert_eq!(
+            policy.content_hash(Some(&mut Payload::new(json!({ "n": 1 })))),
+            None
+        );
+    }
+
+    #[test]
+    fn policy_needs_a_check() {
+        assert!(DedupPolicy::default().validate().is_ok());
+        let none = DedupPolicy {
+            hash_field: None,
+            ..Default::default()
+        };
+        assert!(none.validate().is_err());
+        let similar: DedupPolicy =
+            serde_json::from_value(json!({ "hash_field": null, "similarity_threshold": 0.98 }))
+                .unwrap();
+        assert!(similar.validate().is_ok());
+        assert_eq!(similar.action, DedupAction::Skip);
+    }
+
+    #[test]
+    fn status_serializes_flat() {
+        let status = DedupStatus::Skipped(Duplicate {
+            duplicate_of: "a".to_string(),
+            reason: DedupReason::ContentHash,
+            score: None,
+        });
+        assert_eq!(
+            serde_json::to_value(&status).unwrap(),
+            json!({ "status": "skipped", "duplicate_of": "a", "reason": "content_hash" })
+        );
+    }
+}
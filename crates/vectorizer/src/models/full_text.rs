@@ -0,0 +1,78 @@
+//! Full-text indexing of a collection: which payload fields keep the
+//! per-term postings `full_text_search` ranks from.
+//!
+//! The index costs memory and write time for every indexed field, so a
+//! collection only keeps one when its config asks for it. Other fields
+//! can still be indexed later with a `Text` payload index.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, VectorizerError};
+
+/// Payload fields indexed when `full_text` names none, and searched when
+/// a query names none. These are the keys the file loader and `/insert`
+/// write chunk text under.
+pub const DEFAULT_FULL_TEXT_FIELDS: &[&str] = &["content", "text"];
+
+/// Full-text indexing of a collection
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FullTextConfig {
+    /// Payload fields given a text index (dot notation for nested keys)
+    #[serde(default = "default_fields")]
+    pub fields: Vec<String>,
+}
+
+fn default_fields() -> Vec<String> {
+    DEFAULT_FULL_TEXT_FIELDS
+        .iter()
+        .map(|f| (*f).to_string())
+        .collect()
+}
+
+impl Default for FullTextConfig {
+    fn default() -> Self {
+        Self {
+            fields: default_fields(),
+        }
+    }
+}
+
+impl FullTextConfig {
+    /// Check the indexing settings
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |message: &str| {
+            Err(VectorizerError::InvalidConfiguration {
+                message: message.to_string(),
+            })
+        };
+        if self.fields.is_empty() {
+            return invalid("full_text.fields must name at least one field");
+        }
+        if self.fields.iter().any(|f| f.trim().is_empty()) {
+            return invalid("full_text.fields must not contain an empty field name");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fields_default_to_chunk_text() {
+        let config: FullTextConfig = serde_json::from_value(serde_json::json!({})).unwrap();
+        assert_eq!(config.fields, vec!["content", "text"]);
+        config.validate().unwrap();
+    }
+
+    #[test]
+    fn empty_field_lists_are_rejected() {
+        assert!(FullTextConfig { fields: Vec::new() }.validate().is_err());
+        let blank = FullTextConfig {
+            fields: vec!["content".to_string(), " ".to_string()],
+        };
+        assert!(blank.validate().is_err());
+    }
+}
@@ -0,0 +1,180 @@
+//! Per-collection vector ID policy: the generator used for vectors
+//! inserted without an ID, and the rules client-supplied IDs must meet.
+//!
+//! A collection without a policy keeps the historical behaviour: random
+//! UUIDs for missing IDs and no checks on supplied ones.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, VectorizerError};
+
+/// How IDs are generated for vectors inserted without one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IdGenerator {
+    /// Random UUID (v4)
+    #[default]
+    UuidV4,
+    /// Time-ordered UUID (v7); IDs sort by creation time
+    UuidV7,
+    /// ULID: 26 Crockford base32 characters, sorting by creation time
+    Ulid,
+}
+
+impl IdGenerator {
+    /// A new ID
+    pub fn generate(self) -> String {
+        match self {
+            Self::UuidV4 => uuid::Uuid::new_v4().to_string(),
+            Self::UuidV7 => uuid::Uuid::now_v7().to_string(),
+            Self::Ulid => ulid::Ulid::new().to_string(),
+        }
+    }
+}
+
+/// Characters allowed in vector IDs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IdCharset {
+    /// Any character
+    #[default]
+    Any,
+    /// ASCII letters and digits
+    Alphanumeric,
+    /// ASCII letters, digits, `-`, `_`, `.` and `~` (unreserved URL
+    /// characters, so IDs never need escaping in REST paths)
+    UrlSafe,
+}
+
+impl IdCharset {
+    fn allows(self, c: char) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Alphanumeric => c.is_ascii_alphanumeric(),
+            Self::UrlSafe => c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '~'),
+        }
+    }
+}
+
+/// Vector ID rules of a collection
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IdPolicy {
+    /// Generator for vectors inserted without an ID
+    #[serde(default)]
+    pub generator: IdGenerator,
+    /// Longest accepted ID, in bytes
+    #[serde(default)]
+    pub max_length: Option<usize>,
+    /// Characters accepted in IDs
+    #[serde(default)]
+    pub charset: IdCharset,
+}
+
+impl IdPolicy {
+    /// A new ID for a vector inserted without one
+    pub fn generate_id(&self) -> String {
+        self.generator.generate()
+    }
+
+    /// Check `id` against the policy's rules
+    pub fn validate_id(&self, id: &str) -> Result<()> {
+        let invalid = |reason: String| VectorizerError::InvalidVectorId {
+            id: id.to_string(),
+            reason,
+        };
+        if id.is_empty() {
+            return Err(invalid("ID is empty".to_string()));
+        }
+        if let Some(max_length) = self.max_length
+            && id.len() > max_length
+        {
+            return Err(invalid(format!(
+                "{} bytes is over the collection's max_length of {}",
+                id.len(),
+                max_length
+            )));
+        }
+        if let Some(c) = id.chars().find(|&c| !self.charset.allows(c)) {
+            return Err(invalid(format!(
+                "{:?} is outside the collection's {:?} charset",
+                c, self.charset
+            )));
+        }
+        Ok(())
+    }
+
+    /// Check that the policy accepts the IDs its own generator produces,
+    /// e.g. rejects UUIDs (which contain `-`) with the alphanumeric
+    /// charset, or ULIDs with a `max_length` under 26.
+    pub fn validate(&self) -> Result<()> {
+        self.validate_id(&self.generate_id())
+            .map_err(|e| VectorizerError::InvalidConfiguration {
+                message: format!(
+                    "id_policy rejects the IDs of its own {:?} generator: {}",
+                    self.generator, e
+                ),
+            })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generators_produce_ids_of_their_format() {
+        assert_eq!(IdGenerator::UuidV4.generate().len(), 36);
+        let v7 = IdGenerator::UuidV7.generate();
+        assert_eq!(v7.as_bytes()[14], b'7');
+
+        let first = IdGenerator::Ulid.generate();
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        let second = IdGenerator::Ulid.generate();
+        assert_eq!(first.len(), 26);
+        assert!(first < second, "ULIDs sort by creation time");
+    }
+
+    #[test]
+    fn supplied_ids_are_checked_against_the_rules() {
+        let policy = IdPolicy {
+            generator: IdGenerator::Ulid,
+            max_length: Some(26),
+            charset: IdCharset::UrlSafe,
+        };
+        assert!(policy.validate().is_ok());
+        assert!(policy.validate_id("doc-1_a.b~c").is_ok());
+        assert!(policy.validate_id("").is_err());
+        assert!(policy.validate_id("doc 1").is_err());
+        assert!(policy.validate_id("doc/1").is_err());
+        assert!(policy.validate_id(&"a".repeat(27)).is_err());
+
+        let any = IdPolicy::default();
+        assert!(any.validate_id("any thing/ü").is_ok());
+    }
+
+    #[test]
+    fn policy_must_accept_its_generated_ids() {
+        let uuids_alphanumeric = IdPolicy {
+            charset: IdCharset::Alphanumeric,
+            ..IdPolicy::default()
+        };
+        assert!(uuids_alphanumeric.validate().is_err());
+
+        let short_ulids = IdPolicy {
+            generator: IdGenerator::Ulid,
+            max_length: Some(20),
+            ..IdPolicy::default()
+        };
+        assert!(short_ulids.validate().is_err());
+    }
+
+    #[test]
+    fn policy_deserializes_with_defaults() {
+        let policy: IdPolicy =
+            serde_json::from_value(serde_json::json!({"generator": "uuid_v7"})).unwrap();
+        assert_eq!(policy.generator, IdGenerator::UuidV7);
+        assert_eq!(policy.charset, IdCharset::Any);
+        assert_eq!(policy.max_length, None);
+    }
+}
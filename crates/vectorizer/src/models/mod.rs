@@ -122,6 +122,13 @@ impl QuantizedVector {
         }
     }
 
+    /// Packed 1-bit code for `data` using the same thresholding as
+    /// [`QuantizationConfig::Binary`] storage, so a query can be compared
+    /// against stored codes by Hamming distance.
+    pub fn binary_code(data: &[f32]) -> Vec<u8> {
+        quantize_to_binary(data).0
+    }
+
     /// Get memory usage in bytes
     pub fn memory_size(&self) -> usize {
         match self.quantization_type {
@@ -332,6 +339,34 @@ impl Payload {
     pub fn is_expired(&self, now_ms: i64) -> bool {
         self.expires_at().map_or(false, |exp| exp <= now_ms)
     }
+
+    /// Apply a JSON merge patch (RFC 7396): members of an object patch
+    /// replace the payload's, `null` members remove them and nested
+    /// objects merge recursively. Any other patch replaces the payload.
+    pub fn merge_patch(&mut self, patch: &serde_json::Value) {
+        merge_patch_value(&mut self.data, patch);
+    }
+}
+
+/// `MergePatch(target, patch)` from RFC 7396, applied in place.
+fn merge_patch_value(target: &mut serde_json::Value, patch: &serde_json::Value) {
+    let serde_json::Value::Object(patch) = patch else {
+        *target = patch.clone();
+        return;
+    };
+    if !target.is_object() {
+        *target = serde_json::Value::Object(serde_json::Map::new());
+    }
+    if let serde_json::Value::Object(fields) = target {
+        for (key, value) in patch {
+            if value.is_null() {
+                fields.remove(key);
+            } else {
+                let field = fields.entry(key.clone()).or_insert(serde_json::Value::Null);
+                merge_patch_value(field, value);
+            }
+        }
+    }
 }
 
 /// Configuration for a collection
@@ -374,6 +409,35 @@ pub struct CollectionConfig {
     /// If set, payload encryption will be enforced for this collection
     #[serde(default)]
     pub encryption: Option<EncryptionConfig>,
+    /// Vector ID policy (optional). If set, IDs of vectors inserted
+    /// without one come from its generator and every inserted ID must
+    /// pass its rules
+    #[serde(default)]
+    pub id_policy: Option<IdPolicy>,
+    /// Size quota (optional). If set, inserts past `max_vectors` are
+    /// refused or make room by summarizing the oldest vectors
+    #[serde(default)]
+    pub quota: Option<CollectionQuota>,
+    /// Custom scoring (optional). If set, search hits are ranked by this
+    /// expression over their score and payload instead of similarity
+    #[serde(default)]
+    pub scoring: Option<ScoringConfig>,
+    /// Tenant partition (optional). If set, tenants share the collection
+    /// and each only sees the vectors stamped with its own ID
+    #[serde(default)]
+    pub tenant_partition: Option<TenantPartitionConfig>,
+    /// Deduplication (optional). If set, inserted vectors matching a
+    /// stored one by content hash or similarity are skipped or merged
+    #[serde(default)]
+    pub dedup: Option<DedupPolicy>,
+    /// Chunking (optional). How text inserts, uploads and indexed files
+    /// are split into chunks; the fixed-size strategy when unset
+    #[serde(default)]
+    pub chunking: Option<crate::chunking::ChunkingConfig>,
+    /// Full-text indexing (optional). If set, the named payload fields
+    /// keep the text index `full_text_search` ranks from
+    #[serde(default)]
+    pub full_text: Option<FullTextConfig>,
 }
 
 fn default_embedding_provider() -> String {
@@ -411,11 +475,37 @@ impl Default for EncryptionConfig {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum StorageType {
     /// In-memory storage (fastest, limited by RAM)
+    #[serde(alias = "memory")]
     Memory,
     /// Memory-mapped storage (slower, limited by disk)
+    #[serde(alias = "mmap")]
     Mmap,
 }
 
+impl StorageType {
+    /// Lowercase name used by the REST API (`"memory"` / `"mmap"`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Memory => "memory",
+            Self::Mmap => "mmap",
+        }
+    }
+}
+
+impl std::str::FromStr for StorageType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "memory" => Ok(Self::Memory),
+            "mmap" => Ok(Self::Mmap),
+            other => Err(format!(
+                "unknown storage type '{other}' (expected 'memory' or 'mmap')"
+            )),
+        }
+    }
+}
+
 impl Default for StorageType {
     fn default() -> Self {
         Self::Memory
@@ -487,6 +577,13 @@ impl Default for CollectionConfig {
             sharding: None,   // Sharding disabled by default
             graph: None,      // Graph disabled by default
             encryption: None, // Encryption disabled by default
+            id_policy: None,  // Random UUIDs, no ID rules
+            quota: None,      // Unbounded
+            scoring: None,    // Rank by similarity
+            tenant_partition: None,
+            dedup: None,
+            chunking: None,
+            full_text: None,
         }
     }
 }
@@ -523,6 +620,9 @@ pub struct GraphConfig {
     /// Automatic relationship discovery configuration
     #[serde(default)]
     pub auto_relationship: AutoRelationshipConfig,
+    /// Entity and relation extraction from inserted chunks
+    #[serde(default)]
+    pub entity_extraction: EntityExtractionConfig,
 }
 
 impl Default for GraphConfig {
@@ -530,6 +630,7 @@ impl Default for GraphConfig {
         Self {
             enabled: true,
             auto_relationship: AutoRelationshipConfig::default(),
+            entity_extraction: EntityExtractionConfig::default(),
         }
     }
 }
@@ -583,6 +684,42 @@ fn default_enabled_relationship_types() -> Vec<String> {
     ]
 }
 
+/// Entity extraction configuration (see
+/// [`crate::db::graph_entity_extraction`])
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EntityExtractionConfig {
+    /// Extract entities from every inserted chunk (off by default)
+    pub enabled: bool,
+    /// Recognizers to run, by name: the built-in `rules` and any
+    /// registered with `register_entity_recognizer`
+    pub recognizers: Vec<String>,
+    /// Payload field holding the chunk text
+    pub text_field: String,
+    /// Entity labels to keep (every label when empty)
+    pub labels: Vec<String>,
+    /// Entities shorter than this many characters are dropped
+    pub min_entity_length: usize,
+    /// Most entities linked to one chunk
+    pub max_entities_per_chunk: usize,
+    /// Link entities mentioned together with `RELATED_TO` edges
+    pub relations: bool,
+}
+
+impl Default for EntityExtractionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            recognizers: vec!["rules".to_string()],
+            text_field: "content".to_string(),
+            labels: Vec::new(),
+            min_entity_length: 2,
+            max_entities_per_chunk: 50,
+            relations: true,
+        }
+    }
+}
+
 impl Default for ShardingConfig {
     fn default() -> Self {
         Self {
@@ -611,7 +748,9 @@ pub enum QuantizationConfig {
         /// Bit-width per dimension (4, 8, or 16).
         bits: usize,
     },
-    /// Binary Quantization
+    /// Binary Quantization: 1-bit codes used for a Hamming pre-search.
+    /// The HNSW index keeps full-precision vectors for rescoring, so this
+    /// does not reduce total vector memory.
     Binary,
 }
 
@@ -902,19 +1041,43 @@ impl Payload {
 /// Collection metadata module for tracking indexed files
 pub mod collection_metadata;
 
+/// Deduplication of inserted vectors
+pub mod dedup;
+
+/// Full-text indexing of payload fields
+pub mod full_text;
+
+/// Per-collection vector ID generation and validation
+pub mod id_policy;
+
 /// Qdrant API compatibility module
 pub mod qdrant;
 
+/// Per-collection size quotas
+pub mod quota;
+
+/// Custom scoring expressions for search results
+pub mod scoring;
+
 /// Sparse vector support module
 pub mod sparse_vector;
 
 /// Tenant identity + permission model (moved from hub/auth.rs, phase41)
 pub mod tenant;
 
+/// Tenant partitions of shared collections
+pub mod tenant_partition;
+
 /// SIMD-accelerated vector utilities
 pub mod vector_utils_simd;
 
+pub use dedup::{DedupAction, DedupPolicy, DedupReason, DedupStatus, Duplicate};
+pub use full_text::{DEFAULT_FULL_TEXT_FIELDS, FullTextConfig};
+pub use id_policy::{IdCharset, IdGenerator, IdPolicy};
+pub use quota::{CollectionQuota, QuotaPolicy};
+pub use scoring::{ScoringConfig, ScoringExpression, ScoringInput};
 pub use sparse_vector::{SparseVector, SparseVectorError, SparseVectorIndex};
+pub use tenant_partition::TenantPartitionConfig;
 
 #[cfg(test)]
 #[allow(clippy::unwrap_used, clippy::expect_used)]
@@ -993,3 +1156,100 @@ mod vector_document_id_tests {
         assert_eq!(back.document_id.as_deref(), Some("doc_7"));
     }
 }
+
+#[cfg(test)]
+mod payload_merge_patch_tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn patched(target: serde_json::Value, patch: serde_json::Value) -> serde_json::Value {
+        let mut payload = Payload::new(target);
+        payload.merge_patch(&patch);
+        payload.data
+    }
+
+    /// Nested members are added, replaced and removed without touching
+    /// their siblings.
+    #[test]
+    fn nested_fields_merge() {
+        let target = json!({
+            "title": "Goodbye!",
+            "author": { "givenName": "John", "familyName": "Doe" },
+            "tags": ["example", "sample"],
+            "content": "This will be unchanged"
+        });
+        let patch = json!({
+            "title": "Hello!",
+            "phoneNumber": "+01-123-456-7890",
+            "author": { "familyName": null },
+            "tags": ["example"]
+        });
+        assert_eq!(
+            patched(target, patch),
+            json!({
+                "title": "Hello!",
+                "author": { "givenName": "John" },
+                "tags": ["example"],
+                "content": "This will be unchanged",
+                "phoneNumber": "+01-123-456-7890"
+            })
+        );
+    }
+
+    /// The remaining test cases of RFC 7396 appendix A.
+    #[test]
+    fn rfc_7396_examples() {
+        let cases = [
+            (json!({"a": "b"}), json!({"a": "c"}), json!({"a": "c"})),
+            (
+                json!({"a": "b"}),
+                json!({"b": "c"}),
+                json!({"a": "b", "b": "c"}),
+            ),
+            (json!({"a": "b"}), json!({"a": null}), json!({})),
+            (
+                json!({"a": "b", "b": "c"}),
+                json!({"a": null}),
+                json!({"b": "c"}),
+            ),
+            (json!({"a": ["b"]}), json!({"a": "c"}), json!({"a": "c"})),
+            (json!({"a": "c"}), json!({"a": ["b"]}), json!({"a": ["b"]})),
+            (
+                json!({"a": {"b": "c"}}),
+                json!({"a": {"b": "d", "c": null}}),
+                json!({"a": {"b": "d"}}),
+            ),
+            (
+                json!({"a": [{"b": "c"}]}),
+                json!({"a": [1]}),
+                json!({"a": [1]}),
+            ),
+            (json!(["a", "b"]), json!(["c", "d"]), json!(["c", "d"])),
+            (json!({"a": "b"}), json!(["c"]), json!(["c"])),
+            (json!({"a": "foo"}), json!("bar"), json!("bar")),
+            (
+                json!({"e": null}),
+                json!({"a": 1}),
+                json!({"e": null, "a": 1}),
+            ),
+            (
+                json!([1, 2]),
+                json!({"a": "b", "c": null}),
+                json!({"a": "b"}),
+            ),
+            (
+                json!({}),
+                json!({"a": {"bb": {"ccc": null}}}),
+                json!({"a": {"bb": {}}}),
+            ),
+        ];
+        for (target, patch, expected) in cases {
+            assert_eq!(
+                patched(target.clone(), patch.clone()),
+                expected,
+                "{target} + {patch}"
+            );
+        }
+    }
+}
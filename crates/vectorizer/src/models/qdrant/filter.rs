@@ -15,6 +15,30 @@ pub struct QdrantFilter {
     pub must_not: Option<Vec<QdrantCondition>>,
 }
 
+impl QdrantFilter {
+    /// Payload keys the filter's conditions read, nested filters
+    /// included
+    pub fn keys(&self) -> Vec<&str> {
+        let mut keys = Vec::new();
+        for conditions in [&self.must, &self.should, &self.must_not]
+            .into_iter()
+            .flatten()
+        {
+            for condition in conditions {
+                match condition {
+                    QdrantCondition::Match { key, .. }
+                    | QdrantCondition::Range { key, .. }
+                    | QdrantCondition::GeoBoundingBox { key, .. }
+                    | QdrantCondition::GeoRadius { key, .. }
+                    | QdrantCondition::ValuesCount { key, .. } => keys.push(key.as_str()),
+                    QdrantCondition::Nested { filter } => keys.extend(filter.keys()),
+                }
+            }
+        }
+        keys
+    }
+}
+
 /// Qdrant condition
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
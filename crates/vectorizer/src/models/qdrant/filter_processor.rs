@@ -69,22 +69,28 @@ impl FilterProcessor {
 
     /// Evaluate match condition
     fn evaluate_match(key: &str, match_value: &QdrantMatchValue, payload: &Payload) -> bool {
-        let value = match Self::get_nested_value(key, payload) {
-            Some(v) => v,
-            None => return false,
-        };
+        match Self::get_nested_value(key, payload) {
+            Some(value) => Self::value_matches(value, match_value),
+            None => false,
+        }
+    }
 
+    /// Whether `value` matches; an array matches when any element does
+    fn value_matches(value: &Value, match_value: &QdrantMatchValue) -> bool {
         match match_value {
             QdrantMatchValue::String(s) => match value {
                 Value::String(v) => v == s,
+                Value::Array(items) => items.iter().any(|v| Self::value_matches(v, match_value)),
                 _ => false,
             },
             QdrantMatchValue::Integer(i) => match value {
                 Value::Number(n) => n.as_i64() == Some(*i),
+                Value::Array(items) => items.iter().any(|v| Self::value_matches(v, match_value)),
                 _ => false,
             },
             QdrantMatchValue::Bool(b) => match value {
                 Value::Bool(v) => v == b,
+                Value::Array(items) => items.iter().any(|v| Self::value_matches(v, match_value)),
                 _ => false,
             },
             QdrantMatchValue::Any => !value.is_null(),
@@ -98,6 +104,7 @@ impl FilterProcessor {
                         QdrantTextMatchType::Contains => v.contains(&text_match.text),
                     }
                 }
+                Value::Array(items) => items.iter().any(|v| Self::value_matches(v, match_value)),
                 _ => false,
             },
         }
@@ -105,13 +112,17 @@ impl FilterProcessor {
 
     /// Evaluate range condition
     fn evaluate_range(key: &str, range: &QdrantRange, payload: &Payload) -> bool {
-        let value = match Self::get_nested_value(key, payload) {
-            Some(v) => v,
-            None => return false,
-        };
+        match Self::get_nested_value(key, payload) {
+            Some(value) => Self::value_in_range(value, range),
+            None => false,
+        }
+    }
 
+    /// Whether `value` is in range; an array is when any element is
+    fn value_in_range(value: &Value, range: &QdrantRange) -> bool {
         let number = match value {
             Value::Number(n) => n.as_f64().unwrap_or(0.0),
+            Value::Array(items) => return items.iter().any(|v| Self::value_in_range(v, range)),
             _ => return false,
         };
 
@@ -224,7 +235,7 @@ impl FilterProcessor {
     }
 
     /// Get nested value from payload using dot notation
-    fn get_nested_value<'a>(key: &str, payload: &'a Payload) -> Option<&'a Value> {
+    pub fn get_nested_value<'a>(key: &str, payload: &'a Payload) -> Option<&'a Value> {
         let keys: Vec<&str> = key.split('.').collect();
         let mut current = &payload.data;
 
@@ -314,6 +325,26 @@ mod tests {
         assert!(!FilterProcessor::evaluate_condition(&condition, &payload));
     }
 
+    #[test]
+    fn test_match_array_elements() {
+        let payload = create_test_payload(json!({
+            "tags": ["rust", "search"],
+            "years": [2021, 2024]
+        }));
+
+        let condition = QdrantCondition::match_string("tags", "search");
+        assert!(FilterProcessor::evaluate_condition(&condition, &payload));
+
+        let condition = QdrantCondition::match_string("tags", "python");
+        assert!(!FilterProcessor::evaluate_condition(&condition, &payload));
+
+        let condition = QdrantCondition::range("years", QdrantRange::gte(2023.0));
+        assert!(FilterProcessor::evaluate_condition(&condition, &payload));
+
+        let condition = QdrantCondition::range("years", QdrantRange::gt(2024.0));
+        assert!(!FilterProcessor::evaluate_condition(&condition, &payload));
+    }
+
     #[test]
     fn test_range_filter() {
         let payload = create_test_payload(json!({
@@ -425,6 +456,23 @@ mod tests {
         assert!(!FilterProcessor::evaluate_condition(&condition, &payload));
     }
 
+    #[test]
+    fn test_filter_keys_include_nested_filters() {
+        use super::super::filter::QdrantFilterBuilder;
+
+        let nested = QdrantFilterBuilder::new()
+            .should(QdrantCondition::match_string("owner.email", "a@b.c"))
+            .build();
+        let filter = QdrantFilterBuilder::new()
+            .must(QdrantCondition::match_string("category", "electronics"))
+            .must_not(QdrantCondition::Nested {
+                filter: Box::new(nested),
+            })
+            .build();
+
+        assert_eq!(filter.keys(), vec!["category", "owner.email"]);
+    }
+
     #[test]
     fn test_combined_filters() {
         let payload = create_test_payload(json!({
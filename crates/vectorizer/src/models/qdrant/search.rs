@@ -28,6 +28,8 @@ pub struct QdrantSearchRequest {
     pub using: Option<String>,
     /// Lookup from
     pub lookup_from: Option<QdrantLookupLocation>,
+    /// Search params (quantization oversampling / rescore)
+    pub params: Option<QdrantSearchParams>,
 }
 
 /// Search response
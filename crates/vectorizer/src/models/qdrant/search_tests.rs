@@ -18,6 +18,7 @@ fn test_search_request_serialization() {
         score_threshold: Some(0.5),
         using: Some("dense".to_string()),
         lookup_from: None,
+        params: None,
     };
 
     let json = serde_json::to_string(&request).unwrap();
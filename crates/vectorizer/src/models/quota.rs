@@ -0,0 +1,145 @@
+//! Per-collection size quota: the most vectors a collection may hold, and
+//! what happens to an insert that would go past it.
+//!
+//! With the `reject` policy the insert fails. With `summarize` the oldest
+//! vectors are merged, a group at a time, into summary vectors (an
+//! extractive summary of their text and the mean of their embeddings,
+//! keeping only the payload fields the merged vectors share), so
+//! long-running memory-style collections stay bounded without refusing
+//! writes.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, VectorizerError};
+
+/// What an insert past the quota does.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuotaPolicy {
+    /// Refuse the insert
+    #[default]
+    Reject,
+    /// Merge the oldest vectors into summaries to make room
+    Summarize,
+}
+
+/// Size quota of a collection
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CollectionQuota {
+    /// Most vectors the collection may hold
+    pub max_vectors: usize,
+    /// What an insert past `max_vectors` does
+    #[serde(default)]
+    pub policy: QuotaPolicy,
+    /// Fraction of `max_vectors` summarization shrinks the collection to,
+    /// so it does not run on every insert once the collection is full
+    #[serde(default = "default_target_ratio")]
+    pub target_ratio: f32,
+    /// Oldest vectors merged into each summary
+    #[serde(default = "default_merge_group_size")]
+    pub merge_group_size: usize,
+    /// Payload field holding the text that gets summarized
+    #[serde(default = "default_text_field")]
+    pub text_field: String,
+}
+
+fn default_target_ratio() -> f32 {
+    0.8
+}
+
+fn default_merge_group_size() -> usize {
+    10
+}
+
+fn default_text_field() -> String {
+    "content".to_string()
+}
+
+impl CollectionQuota {
+    /// A quota of `max_vectors` with the given policy and default tuning
+    pub fn new(max_vectors: usize, policy: QuotaPolicy) -> Self {
+        Self {
+            max_vectors,
+            policy,
+            target_ratio: default_target_ratio(),
+            merge_group_size: default_merge_group_size(),
+            text_field: default_text_field(),
+        }
+    }
+
+    /// Vector count summarization shrinks the collection to
+    pub fn target_vectors(&self) -> usize {
+        ((self.max_vectors as f64 * f64::from(self.target_ratio)) as usize).max(1)
+    }
+
+    /// Whether `incoming` more vectors fit next to `current` ones
+    pub fn fits(&self, current: usize, incoming: usize) -> bool {
+        current.saturating_add(incoming) <= self.max_vectors
+    }
+
+    /// Check the quota's settings
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |message: String| Err(VectorizerError::InvalidConfiguration { message });
+        if self.max_vectors == 0 {
+            return invalid("quota.max_vectors must be at least 1".to_string());
+        }
+        if !(self.target_ratio > 0.0 && self.target_ratio <= 1.0) {
+            return invalid(format!(
+                "quota.target_ratio must be in (0, 1], got {}",
+                self.target_ratio
+            ));
+        }
+        if self.merge_group_size < 2 {
+            return invalid(format!(
+                "quota.merge_group_size must be at least 2, got {}",
+                self.merge_group_size
+            ));
+        }
+        if self.text_field.is_empty() {
+            return invalid("quota.text_field must not be empty".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quota_deserializes_with_defaults() {
+        let quota: CollectionQuota =
+            serde_json::from_value(serde_json::json!({"max_vectors": 100})).unwrap();
+        assert_eq!(quota, CollectionQuota::new(100, QuotaPolicy::Reject));
+        assert_eq!(quota.target_vectors(), 80);
+        assert!(quota.fits(90, 10));
+        assert!(!quota.fits(91, 10));
+    }
+
+    #[test]
+    fn invalid_settings_are_rejected() {
+        assert!(
+            CollectionQuota::new(100, QuotaPolicy::Summarize)
+                .validate()
+                .is_ok()
+        );
+        assert!(
+            CollectionQuota::new(0, QuotaPolicy::Reject)
+                .validate()
+                .is_err()
+        );
+        for ratio in [0.0, 1.5, f32::NAN] {
+            let quota = CollectionQuota {
+                target_ratio: ratio,
+                ..CollectionQuota::new(100, QuotaPolicy::Summarize)
+            };
+            assert!(quota.validate().is_err(), "ratio {ratio} accepted");
+        }
+        let quota = CollectionQuota {
+            merge_group_size: 1,
+            ..CollectionQuota::new(100, QuotaPolicy::Summarize)
+        };
+        assert!(quota.validate().is_err());
+    }
+}
@@ -0,0 +1,687 @@
+//! Custom scoring expressions evaluated over each search hit.
+//!
+//! A collection may replace the similarity score its searches rank by
+//! with an arithmetic expression over the hit's score, its raw metric
+//! distance and selected payload fields, e.g. a recency-weighted cosine:
+//!
+//! ```text
+//! score * exp(-(now - payload.created_at) / 86400)
+//! ```
+//!
+//! The language is deliberately small:
+//!
+//! - numbers, `score`, `distance` and `now` (Unix seconds)
+//! - `feedback`: the hit's boost in `(-1, 1)` learned from relevance
+//!   feedback (see `crate::db::RelevanceFeedback`); 0 without any
+//! - `payload.<field>[.<field>...]`: numbers, booleans (1/0), numeric
+//!   strings and RFC 3339 timestamps (as Unix seconds); anything else,
+//!   including a missing field, evaluates to 0
+//! - `+ - * / % ^`, unary `-`, and `< <= > >= == !=` (1 when true, else 0)
+//! - `exp ln log10 sqrt abs floor ceil` (one argument), `min max pow`
+//!   (two) and `clamp(x, lo, hi)`, `if(cond, then, else)` (three)
+//!
+//! The index still finds candidates by vector similarity: searches fetch
+//! `k * oversampling` neighbours, rescore them with the expression and
+//! keep the best `k`. Hits whose expression is not a finite number rank
+//! last.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::{Result, VectorizerError};
+
+/// Longest expression accepted, in bytes
+const MAX_EXPRESSION_LEN: usize = 1024;
+
+/// Deepest nesting of parentheses, calls and operators accepted
+const MAX_DEPTH: usize = 64;
+
+/// Custom scoring of a collection's search results
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScoringConfig {
+    /// Expression computing each hit's score
+    pub expression: ScoringExpression,
+    /// Candidates fetched per requested result before rescoring
+    #[serde(default = "default_oversampling")]
+    pub oversampling: usize,
+}
+
+fn default_oversampling() -> usize {
+    4
+}
+
+impl ScoringConfig {
+    /// Scoring by `expression` with the default oversampling
+    pub fn new(expression: ScoringExpression) -> Self {
+        Self {
+            expression,
+            oversampling: default_oversampling(),
+        }
+    }
+
+    /// Check the settings are usable
+    pub fn validate(&self) -> Result<()> {
+        if self.oversampling == 0 {
+            return Err(VectorizerError::InvalidConfiguration {
+                message: "scoring.oversampling must be at least 1".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Candidates to fetch for a `k`-result search
+    pub fn candidates(&self, k: usize) -> usize {
+        k.saturating_mul(self.oversampling)
+    }
+}
+
+/// Values an expression can read for one hit
+#[derive(Debug, Clone, Copy)]
+pub struct ScoringInput<'a> {
+    /// Similarity score the index produced
+    pub score: f32,
+    /// Raw distance under the collection's metric
+    pub distance: f32,
+    /// The hit's payload, if any
+    pub payload: Option<&'a Value>,
+    /// Boost learned from relevance feedback on the hit
+    pub feedback: f64,
+    /// Current time in Unix seconds
+    pub now: f64,
+}
+
+/// A parsed scoring expression
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ScoringExpression {
+    source: String,
+    root: Expr,
+}
+
+impl ScoringExpression {
+    /// Parse `source`
+    pub fn parse(source: &str) -> Result<Self> {
+        if source.len() > MAX_EXPRESSION_LEN {
+            return Err(invalid(format!("longer than {MAX_EXPRESSION_LEN} bytes")));
+        }
+        let tokens = tokenize(source)?;
+        let mut parser = Parser {
+            tokens,
+            pos: 0,
+            depth: 0,
+        };
+        let root = parser.expression()?;
+        if let Some(token) = parser.peek() {
+            return Err(invalid(format!("unexpected {token}")));
+        }
+        Ok(Self {
+            source: source.to_string(),
+            root,
+        })
+    }
+
+    /// The expression as written
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Evaluate the expression for one hit
+    pub fn evaluate(&self, input: &ScoringInput<'_>) -> f64 {
+        self.root.eval(input)
+    }
+}
+
+impl PartialEq for ScoringExpression {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+impl fmt::Debug for ScoringExpression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ScoringExpression")
+            .field(&self.source)
+            .finish()
+    }
+}
+
+impl fmt::Display for ScoringExpression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl TryFrom<String> for ScoringExpression {
+    type Error = VectorizerError;
+
+    fn try_from(source: String) -> Result<Self> {
+        Self::parse(&source)
+    }
+}
+
+impl From<ScoringExpression> for String {
+    fn from(expression: ScoringExpression) -> Self {
+        expression.source
+    }
+}
+
+fn invalid(reason: String) -> VectorizerError {
+    VectorizerError::InvalidConfiguration {
+        message: format!("invalid scoring expression: {reason}"),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Ident(String),
+    Op(&'static str),
+    LParen,
+    RParen,
+    Comma,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Number(n) => write!(f, "number {n}"),
+            Token::Ident(name) => write!(f, "'{name}'"),
+            Token::Op(op) => write!(f, "'{op}'"),
+            Token::LParen => f.write_str("'('"),
+            Token::RParen => f.write_str("')'"),
+            Token::Comma => f.write_str("','"),
+        }
+    }
+}
+
+/// Operators, longest first so `<=` wins over `<`
+const OPERATORS: [&str; 12] = [
+    "<=", ">=", "==", "!=", "<", ">", "+", "-", "*", "/", "^", "%",
+];
+
+fn tokenize(source: &str) -> Result<Vec<Token>> {
+    let bytes = source.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let c = bytes[i];
+        if c.is_ascii_whitespace() {
+            i += 1;
+        } else if c.is_ascii_digit()
+            || (c == b'.' && bytes.get(i + 1).is_some_and(u8::is_ascii_digit))
+        {
+            let start = i;
+            while i < bytes.len() && (bytes[i].is_ascii_digit() || bytes[i] == b'.') {
+                i += 1;
+            }
+            if i < bytes.len() && (bytes[i] == b'e' || bytes[i] == b'E') {
+                let mut j = i + 1;
+                if j < bytes.len() && (bytes[j] == b'+' || bytes[j] == b'-') {
+                    j += 1;
+                }
+                if j < bytes.len() && bytes[j].is_ascii_digit() {
+                    i = j;
+                    while i < bytes.len() && bytes[i].is_ascii_digit() {
+                        i += 1;
+                    }
+                }
+            }
+            let text = &source[start..i];
+            let number = text
+                .parse()
+                .map_err(|_| invalid(format!("malformed number '{text}'")))?;
+            tokens.push(Token::Number(number));
+        } else if c.is_ascii_alphabetic() || c == b'_' {
+            let start = i;
+            while i < bytes.len()
+                && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_' || bytes[i] == b'.')
+            {
+                i += 1;
+            }
+            tokens.push(Token::Ident(source[start..i].to_string()));
+        } else if c == b'(' {
+            tokens.push(Token::LParen);
+            i += 1;
+        } else if c == b')' {
+            tokens.push(Token::RParen);
+            i += 1;
+        } else if c == b',' {
+            tokens.push(Token::Comma);
+            i += 1;
+        } else if let Some(op) = OPERATORS.iter().find(|op| source[i..].starts_with(*op)) {
+            tokens.push(Token::Op(*op));
+            i += op.len();
+        } else {
+            let found = source[i..].chars().next().unwrap_or_default();
+            return Err(invalid(format!("unexpected character '{found}'")));
+        }
+    }
+    Ok(tokens)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Pow,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Ne,
+}
+
+impl BinOp {
+    fn from_token(op: &str) -> Option<Self> {
+        Some(match op {
+            "+" => Self::Add,
+            "-" => Self::Sub,
+            "*" => Self::Mul,
+            "/" => Self::Div,
+            "%" => Self::Rem,
+            "^" => Self::Pow,
+            "<" => Self::Lt,
+            "<=" => Self::Le,
+            ">" => Self::Gt,
+            ">=" => Self::Ge,
+            "==" => Self::Eq,
+            "!=" => Self::Ne,
+            _ => return None,
+        })
+    }
+
+    fn apply(self, a: f64, b: f64) -> f64 {
+        let truth = |condition: bool| if condition { 1.0 } else { 0.0 };
+        match self {
+            Self::Add => a + b,
+            Self::Sub => a - b,
+            Self::Mul => a * b,
+            Self::Div => a / b,
+            Self::Rem => a % b,
+            Self::Pow => a.powf(b),
+            Self::Lt => truth(a < b),
+            Self::Le => truth(a <= b),
+            Self::Gt => truth(a > b),
+            Self::Ge => truth(a >= b),
+            Self::Eq => truth(a == b),
+            Self::Ne => truth(a != b),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Func {
+    Exp,
+    Ln,
+    Log10,
+    Sqrt,
+    Abs,
+    Floor,
+    Ceil,
+    Min,
+    Max,
+    Pow,
+    Clamp,
+    If,
+}
+
+impl Func {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "exp" => Self::Exp,
+            "ln" => Self::Ln,
+            "log10" => Self::Log10,
+            "sqrt" => Self::Sqrt,
+            "abs" => Self::Abs,
+            "floor" => Self::Floor,
+            "ceil" => Self::Ceil,
+            "min" => Self::Min,
+            "max" => Self::Max,
+            "pow" => Self::Pow,
+            "clamp" => Self::Clamp,
+            "if" => Self::If,
+            _ => return None,
+        })
+    }
+
+    fn arity(self) -> usize {
+        match self {
+            Self::Exp
+            | Self::Ln
+            | Self::Log10
+            | Self::Sqrt
+            | Self::Abs
+            | Self::Floor
+            | Self::Ceil => 1,
+            Self::Min | Self::Max | Self::Pow => 2,
+            Self::Clamp | Self::If => 3,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Number(f64),
+    Score,
+    Distance,
+    Now,
+    Feedback,
+    Field(Vec<String>),
+    Neg(Box<Expr>),
+    Binary(BinOp, Box<Expr>, Box<Expr>),
+    Call(Func, Vec<Expr>),
+}
+
+impl Expr {
+    fn eval(&self, input: &ScoringInput<'_>) -> f64 {
+        match self {
+            Expr::Number(n) => *n,
+            Expr::Score => f64::from(input.score),
+            Expr::Distance => f64::from(input.distance),
+            Expr::Now => input.now,
+            Expr::Feedback => input.feedback,
+            Expr::Field(path) => field_value(input.payload, path),
+            Expr::Neg(inner) => -inner.eval(input),
+            Expr::Binary(op, a, b) => op.apply(a.eval(input), b.eval(input)),
+            Expr::Call(func, args) => {
+                // Arity is checked at parse time; arguments are evaluated
+                // lazily so `if` only runs the branch it takes
+                let arg = |i: usize| args[i].eval(input);
+                match func {
+                    Func::Exp => arg(0).exp(),
+                    Func::Ln => arg(0).ln(),
+                    Func::Log10 => arg(0).log10(),
+                    Func::Sqrt => arg(0).sqrt(),
+                    Func::Abs => arg(0).abs(),
+                    Func::Floor => arg(0).floor(),
+                    Func::Ceil => arg(0).ceil(),
+                    Func::Min => arg(0).min(arg(1)),
+                    Func::Max => arg(0).max(arg(1)),
+                    Func::Pow => arg(0).powf(arg(1)),
+                    Func::Clamp => arg(0).max(arg(1)).min(arg(2)),
+                    Func::If => {
+                        if arg(0) != 0.0 {
+                            arg(1)
+                        } else {
+                            arg(2)
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Numeric value of a payload field; 0 when it is missing or not a
+/// number, boolean, numeric string or RFC 3339 timestamp.
+fn field_value(payload: Option<&Value>, path: &[String]) -> f64 {
+    let Some(payload) = payload else {
+        return 0.0;
+    };
+    let value = path
+        .iter()
+        .try_fold(payload, |value, key| value.get(key.as_str()));
+    match value {
+        Some(Value::Number(n)) => n.as_f64().unwrap_or(0.0),
+        Some(Value::Bool(b)) => f64::from(u8::from(*b)),
+        Some(Value::String(s)) => s.trim().parse().unwrap_or_else(|_| {
+            chrono::DateTime::parse_from_rfc3339(s.trim())
+                .map(|t| t.timestamp_millis() as f64 / 1000.0)
+                .unwrap_or(0.0)
+        }),
+        _ => 0.0,
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn advance(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn eat_op(&mut self, ops: &[&str]) -> Option<BinOp> {
+        match self.peek() {
+            Some(Token::Op(op)) if ops.contains(op) => {
+                let op = BinOp::from_token(op);
+                self.pos += 1;
+                op
+            }
+            _ => None,
+        }
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<()> {
+        match self.advance() {
+            Some(token) if token == expected => Ok(()),
+            Some(token) => Err(invalid(format!("expected {expected}, found {token}"))),
+            None => Err(invalid(format!("expected {expected}, found end of input"))),
+        }
+    }
+
+    fn descend(&mut self) -> Result<()> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(invalid(format!("nested deeper than {MAX_DEPTH} levels")));
+        }
+        Ok(())
+    }
+
+    /// `additive (cmp additive)?`
+    fn expression(&mut self) -> Result<Expr> {
+        self.descend()?;
+        let left = self.additive()?;
+        let expr = match self.eat_op(&["<", "<=", ">", ">=", "==", "!="]) {
+            Some(op) => Expr::Binary(op, Box::new(left), Box::new(self.additive()?)),
+            None => left,
+        };
+        self.depth -= 1;
+        Ok(expr)
+    }
+
+    fn additive(&mut self) -> Result<Expr> {
+        let mut expr = self.term()?;
+        while let Some(op) = self.eat_op(&["+", "-"]) {
+            expr = Expr::Binary(op, Box::new(expr), Box::new(self.term()?));
+        }
+        Ok(expr)
+    }
+
+    fn term(&mut self) -> Result<Expr> {
+        let mut expr = self.unary()?;
+        while let Some(op) = self.eat_op(&["*", "/", "%"]) {
+            expr = Expr::Binary(op, Box::new(expr), Box::new(self.unary()?));
+        }
+        Ok(expr)
+    }
+
+    /// Unary minus binds looser than `^`, so `-x^2` is `-(x^2)`
+    fn unary(&mut self) -> Result<Expr> {
+        if self.eat_op(&["-"]).is_some() {
+            self.descend()?;
+            let inner = self.unary()?;
+            self.depth -= 1;
+            return Ok(Expr::Neg(Box::new(inner)));
+        }
+        self.power()
+    }
+
+    /// `^` is right-associative
+    fn power(&mut self) -> Result<Expr> {
+        let base = self.primary()?;
+        if let Some(op) = self.eat_op(&["^"]) {
+            self.descend()?;
+            let exponent = self.unary()?;
+            self.depth -= 1;
+            return Ok(Expr::Binary(op, Box::new(base), Box::new(exponent)));
+        }
+        Ok(base)
+    }
+
+    fn primary(&mut self) -> Result<Expr> {
+        match self.advance() {
+            Some(Token::Number(n)) => Ok(Expr::Number(n)),
+            Some(Token::LParen) => {
+                let inner = self.expression()?;
+                self.expect(Token::RParen)?;
+                Ok(inner)
+            }
+            Some(Token::Ident(name)) => {
+                if self.peek() == Some(&Token::LParen) {
+                    self.pos += 1;
+                    return self.call(&name);
+                }
+                variable(&name)
+            }
+            Some(token) => Err(invalid(format!("unexpected {token}"))),
+            None => Err(invalid("unexpected end of input".to_string())),
+        }
+    }
+
+    fn call(&mut self, name: &str) -> Result<Expr> {
+        let func =
+            Func::from_name(name).ok_or_else(|| invalid(format!("unknown function '{name}'")))?;
+        let mut args = Vec::new();
+        if self.peek() != Some(&Token::RParen) {
+            loop {
+                args.push(self.expression()?);
+                if self.peek() != Some(&Token::Comma) {
+                    break;
+                }
+                self.pos += 1;
+            }
+        }
+        self.expect(Token::RParen)?;
+        if args.len() != func.arity() {
+            return Err(invalid(format!(
+                "'{}' takes {} argument(s), got {}",
+                name,
+                func.arity(),
+                args.len()
+            )));
+        }
+        Ok(Expr::Call(func, args))
+    }
+}
+
+fn variable(name: &str) -> Result<Expr> {
+    match name {
+        "score" => Ok(Expr::Score),
+        "distance" => Ok(Expr::Distance),
+        "now" => Ok(Expr::Now),
+        "feedback" => Ok(Expr::Feedback),
+        _ => {
+            let path = name
+                .strip_prefix("payload.")
+                .ok_or_else(|| invalid(format!("unknown variable '{name}'")))?;
+            let segments: Vec<String> = path.split('.').map(str::to_string).collect();
+            if segments.iter().any(String::is_empty) {
+                return Err(invalid(format!("malformed field path '{name}'")));
+            }
+            Ok(Expr::Field(segments))
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn eval(source: &str, payload: Option<&Value>) -> f64 {
+        ScoringExpression::parse(source)
+            .unwrap()
+            .evaluate(&ScoringInput {
+                score: 0.5,
+                distance: 0.5,
+                payload,
+                feedback: 0.25,
+                now: 1_000.0,
+            })
+    }
+
+    #[test]
+    fn arithmetic_follows_precedence() {
+        assert_eq!(eval("1 + 2 * 3", None), 7.0);
+        assert_eq!(eval("(1 + 2) * 3", None), 9.0);
+        assert_eq!(eval("2 ^ 3 ^ 2", None), 512.0);
+        assert_eq!(eval("-2 ^ 2", None), -4.0);
+        assert_eq!(eval("score * 2 - distance", None), 0.5);
+        assert_eq!(eval("if(score > 0.4, 10, 20)", None), 10.0);
+        assert_eq!(eval("clamp(now, 0, 1e2)", None), 100.0);
+        assert_eq!(eval("score + 2 * feedback", None), 1.0);
+        assert_eq!(eval("max(1, min(3, 2)) + abs(-1)", None), 3.0);
+    }
+
+    #[test]
+    fn payload_fields_are_read_as_numbers() {
+        let payload = json!({
+            "meta": { "boost": 2, "pinned": true },
+            "rank": "3",
+            "created_at": "1970-01-01T00:10:00Z",
+            "title": "text"
+        });
+        let payload = Some(&payload);
+        assert_eq!(
+            eval("payload.meta.boost * payload.meta.pinned", payload),
+            2.0
+        );
+        assert_eq!(eval("payload.rank", payload), 3.0);
+        assert_eq!(eval("now - payload.created_at", payload), 400.0);
+        assert_eq!(eval("payload.title + payload.missing.field", payload), 0.0);
+        assert_eq!(eval("payload.anything", None), 0.0);
+    }
+
+    #[test]
+    fn malformed_expressions_are_rejected() {
+        for source in [
+            "",
+            "score +",
+            "(score",
+            "score score",
+            "cosine",
+            "payload.",
+            "payload..x",
+            "exp(1, 2)",
+            "sigmoid(score)",
+            "score $ 2",
+            "1.2.3",
+        ] {
+            assert!(
+                ScoringExpression::parse(source).is_err(),
+                "'{source}' accepted"
+            );
+        }
+        let nested = format!("{}score{}", "(".repeat(100), ")".repeat(100));
+        assert!(ScoringExpression::parse(&nested).is_err());
+        assert!(ScoringExpression::parse(&"-".repeat(100)).is_err());
+
+        let config: ScoringConfig =
+            serde_json::from_value(json!({"expression": "score * 2"})).unwrap();
+        assert_eq!(config.oversampling, 4);
+        assert_eq!(config.candidates(10), 40);
+        assert!(serde_json::from_value::<ScoringConfig>(json!({"expression": "score *"})).is_err());
+        assert_eq!(
+            serde_json::to_value(&config).unwrap(),
+            json!({"expression": "score * 2", "oversampling": 4})
+        );
+    }
+}
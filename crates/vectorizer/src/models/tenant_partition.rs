@@ -0,0 +1,68 @@
+//! Tenant partition of a shared collection: many tenants write to one
+//! collection, and a payload field records which tenant owns each
+//! vector.
+//!
+//! The server fills the field in from the caller's tenant on every
+//! insert (a value sent by the client is overwritten) and restricts
+//! every search, read and delete to the caller's own vectors, so tenants
+//! share one collection without being able to see each other's data.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, VectorizerError};
+
+/// Longest partition key, in bytes.
+const MAX_KEY_BYTES: usize = 128;
+
+/// Tenant partition of a collection
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TenantPartitionConfig {
+    /// Payload field holding the owning tenant's ID
+    #[serde(default = "default_key")]
+    pub key: String,
+}
+
+fn default_key() -> String {
+    "tenant_id".to_string()
+}
+
+impl Default for TenantPartitionConfig {
+    fn default() -> Self {
+        Self { key: default_key() }
+    }
+}
+
+impl TenantPartitionConfig {
+    /// Check the partition's settings
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |message: String| Err(VectorizerError::InvalidConfiguration { message });
+        if self.key.trim().is_empty() {
+            return invalid("tenant_partition.key must not be empty".to_string());
+        }
+        if self.key.len() > MAX_KEY_BYTES {
+            return invalid(format!(
+                "tenant_partition.key is {} bytes, the limit is {MAX_KEY_BYTES}",
+                self.key.len()
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_defaults_to_tenant_id() {
+        let partition: TenantPartitionConfig =
+            serde_json::from_value(serde_json::json!({})).unwrap();
+        assert_eq!(partition.key, "tenant_id");
+        assert!(partition.validate().is_ok());
+        let blank = TenantPartitionConfig {
+            key: " ".to_string(),
+        };
+        assert!(blank.validate().is_err());
+    }
+}
@@ -30,6 +30,24 @@ pub struct Metrics {
     /// Number of results returned per search
     pub search_results_count: HistogramVec,
 
+    /// HNSW beam width (`ef_search`) used per graph search, per collection
+    pub search_ef_search: HistogramVec,
+
+    /// HNSW nodes visited per graph search, per collection
+    pub search_candidates_visited: HistogramVec,
+
+    /// Query cache lookups per collection, labelled `hit` or `miss`
+    pub query_cache_requests_total: CounterVec,
+
+    /// Query cache entries removed per collection, labelled
+    /// `invalidated`, `evicted` or `expired`
+    pub query_cache_removals_total: CounterVec,
+
+    /// Embeddings that fell back to a hash of the text instead of the
+    /// provider's model, labelled by provider and reason. These still
+    /// index and search, but rank poorly.
+    pub embedding_fallback_total: CounterVec,
+
     // ═══════════════════════════════════════════════════════════════════════
     // Indexing Metrics
     // ═══════════════════════════════════════════════════════════════════════
@@ -164,6 +182,60 @@ pub struct Metrics {
     /// Lag between the reaper's scheduled interval and actual execution
     /// (in seconds). A rising value indicates the reaper is falling behind.
     pub ttl_reaper_lag_secs: GaugeVec,
+
+    // ═══════════════════════════════════════════════════════════════════════
+    // Index Optimizer Metrics
+    // ═══════════════════════════════════════════════════════════════════════
+    /// Share of HNSW graph nodes left behind by deletes and updates, as of
+    /// the last optimizer sweep (labelled by collection).
+    pub index_fragmentation_ratio: GaugeVec,
+
+    /// Total HNSW compactions, labelled by collection and result
+    /// (`ok`, `error`).
+    pub index_compactions_total: CounterVec,
+
+    /// Total stale HNSW nodes reclaimed by compaction.
+    pub index_compaction_reclaimed_nodes_total: CounterVec,
+
+    /// HNSW compaction duration in seconds.
+    pub index_compaction_duration_seconds: Histogram,
+
+    // ═══════════════════════════════════════════════════════════════════════
+    // Backup Scheduler Metrics
+    // ═══════════════════════════════════════════════════════════════════════
+    /// Total scheduled backup runs, labelled by job and result (`ok`,
+    /// `error`).
+    pub backup_runs_total: CounterVec,
+
+    /// Unix time of each job's last successful run.
+    pub backup_last_success_timestamp_seconds: GaugeVec,
+
+    /// Unix time of each job's last failed run.
+    pub backup_last_failure_timestamp_seconds: GaugeVec,
+
+    /// Duration of each job's last run in seconds.
+    pub backup_last_duration_seconds: GaugeVec,
+
+    /// Total snapshots pruned by each job's retention policy.
+    pub backup_pruned_snapshots_total: CounterVec,
+
+    // ═══════════════════════════════════════════════════════════════════════
+    // LLM Summarization Metrics
+    // ═══════════════════════════════════════════════════════════════════════
+    /// Total LLM summarization calls, labelled by provider, model and
+    /// result (`success`, `error`).
+    pub summarization_llm_requests_total: CounterVec,
+
+    /// LLM summarization call latency in seconds, by provider and model.
+    pub summarization_llm_latency_seconds: HistogramVec,
+
+    /// Tokens consumed by LLM summarization, by provider, model and kind
+    /// (`prompt`, `completion`).
+    pub summarization_llm_tokens_total: CounterVec,
+
+    /// Estimated LLM summarization cost in USD, by provider and model,
+    /// from the configured per-1k-token prices.
+    pub summarization_llm_cost_usd_total: CounterVec,
 }
 
 impl Metrics {
@@ -213,6 +285,55 @@ impl Metrics {
             )
             .unwrap(),
 
+            search_ef_search: HistogramVec::new(
+                HistogramOpts::new(
+                    "vectorizer_search_ef_search",
+                    "HNSW beam width (ef_search) used per graph search",
+                )
+                .buckets(vec![16.0, 32.0, 64.0, 128.0, 256.0, 512.0, 1024.0, 2048.0]),
+                &["collection"],
+            )
+            .unwrap(),
+
+            search_candidates_visited: HistogramVec::new(
+                HistogramOpts::new(
+                    "vectorizer_search_candidates_visited",
+                    "HNSW nodes visited per graph search",
+                )
+                .buckets(vec![
+                    10.0, 50.0, 100.0, 250.0, 500.0, 1000.0, 2500.0, 5000.0, 10000.0, 50000.0,
+                ]),
+                &["collection"],
+            )
+            .unwrap(),
+
+            query_cache_requests_total: CounterVec::new(
+                Opts::new(
+                    "vectorizer_query_cache_requests_total",
+                    "Query cache lookups per collection and result",
+                ),
+                &["collection", "result"],
+            )
+            .unwrap(),
+
+            query_cache_removals_total: CounterVec::new(
+                Opts::new(
+                    "vectorizer_query_cache_removals_total",
+                    "Query cache entries removed per collection and reason",
+                ),
+                &["collection", "reason"],
+            )
+            .unwrap(),
+
+            embedding_fallback_total: CounterVec::new(
+                Opts::new(
+                    "vectorizer_embedding_fallback_total",
+                    "Embeddings that fell back to a hash of the text, per provider and reason",
+                ),
+                &["provider", "reason"],
+            )
+            .unwrap(),
+
             // Indexing metrics
             vectors_total: Gauge::new("vectorizer_vectors_total", "Total number of vectors stored")
                 .unwrap(),
@@ -495,6 +616,127 @@ impl Metrics {
                 &["collection"],
             )
             .unwrap(),
+
+            // Index optimizer metrics
+            index_fragmentation_ratio: GaugeVec::new(
+                Opts::new(
+                    "vectorizer_index_fragmentation_ratio",
+                    "Share of HNSW graph nodes left behind by deletes and updates",
+                ),
+                &["collection"],
+            )
+            .unwrap(),
+
+            index_compactions_total: CounterVec::new(
+                Opts::new(
+                    "vectorizer_index_compactions_total",
+                    "Total HNSW compactions per collection and result",
+                ),
+                &["collection", "result"],
+            )
+            .unwrap(),
+
+            index_compaction_reclaimed_nodes_total: CounterVec::new(
+                Opts::new(
+                    "vectorizer_index_compaction_reclaimed_nodes_total",
+                    "Total stale HNSW nodes reclaimed by compaction",
+                ),
+                &["collection"],
+            )
+            .unwrap(),
+
+            index_compaction_duration_seconds: Histogram::with_opts(
+                HistogramOpts::new(
+                    "vectorizer_index_compaction_duration_seconds",
+                    "HNSW compaction duration in seconds",
+                )
+                .buckets(vec![0.1, 0.5, 1.0, 5.0, 15.0, 30.0, 60.0, 300.0, 900.0]),
+            )
+            .unwrap(),
+
+            // Backup scheduler metrics
+            backup_runs_total: CounterVec::new(
+                Opts::new(
+                    "vectorizer_backup_runs_total",
+                    "Total scheduled backup runs per job and result",
+                ),
+                &["job", "result"],
+            )
+            .unwrap(),
+
+            backup_last_success_timestamp_seconds: GaugeVec::new(
+                Opts::new(
+                    "vectorizer_backup_last_success_timestamp_seconds",
+                    "Unix time of the last successful scheduled backup",
+                ),
+                &["job"],
+            )
+            .unwrap(),
+
+            backup_last_failure_timestamp_seconds: GaugeVec::new(
+                Opts::new(
+                    "vectorizer_backup_last_failure_timestamp_seconds",
+                    "Unix time of the last failed scheduled backup",
+                ),
+                &["job"],
+            )
+            .unwrap(),
+
+            backup_last_duration_seconds: GaugeVec::new(
+                Opts::new(
+                    "vectorizer_backup_last_duration_seconds",
+                    "Duration of the last scheduled backup in seconds",
+                ),
+                &["job"],
+            )
+            .unwrap(),
+
+            backup_pruned_snapshots_total: CounterVec::new(
+                Opts::new(
+                    "vectorizer_backup_pruned_snapshots_total",
+                    "Total snapshots pruned by backup retention policies",
+                ),
+                &["job"],
+            )
+            .unwrap(),
+
+            // LLM summarization metrics
+            summarization_llm_requests_total: CounterVec::new(
+                Opts::new(
+                    "vectorizer_summarization_llm_requests_total",
+                    "Total LLM summarization calls",
+                ),
+                &["provider", "model", "result"],
+            )
+            .unwrap(),
+
+            summarization_llm_latency_seconds: HistogramVec::new(
+                HistogramOpts::new(
+                    "vectorizer_summarization_llm_latency_seconds",
+                    "LLM summarization call latency in seconds",
+                )
+                .buckets(vec![0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0]),
+                &["provider", "model"],
+            )
+            .unwrap(),
+
+            summarization_llm_tokens_total: CounterVec::new(
+                Opts::new(
+                    "vectorizer_summarization_llm_tokens_total",
+                    "Tokens consumed by LLM summarization",
+                ),
+                &["provider", "model", "kind"],
+            )
+            .unwrap(),
+
+            summarization_llm_cost_usd_total: CounterVec::new(
+                Opts::new(
+                    "vectorizer_summarization_llm_cost_usd_total",
+                    "Estimated LLM summarization cost in USD",
+                ),
+                &["provider", "model"],
+            )
+            .unwrap(),
         }
     }
 
@@ -506,6 +748,20 @@ impl Metrics {
             .fetch_add(1, Ordering::Relaxed);
     }
 
+    /// Count an embedding by `provider` that fell back to a hash of the
+    /// text: because the provider has no vocabulary yet, or because none
+    /// of the text's terms are in it.
+    pub fn record_embedding_fallback(&self, provider: &str, vocabulary_empty: bool) {
+        let reason = if vocabulary_empty {
+            "empty_vocabulary"
+        } else {
+            "no_match"
+        };
+        self.embedding_fallback_total
+            .with_label_values(&[provider, reason])
+            .inc();
+    }
+
     /// Get the total API request count for a tenant
     pub fn get_tenant_api_requests(&self, tenant_id: &str) -> u64 {
         self.tenant_api_requests
@@ -520,6 +776,11 @@ impl Metrics {
         registry.register(Box::new(self.search_requests_total.clone()))?;
         registry.register(Box::new(self.search_latency_seconds.clone()))?;
         registry.register(Box::new(self.search_results_count.clone()))?;
+        registry.register(Box::new(self.search_ef_search.clone()))?;
+        registry.register(Box::new(self.search_candidates_visited.clone()))?;
+        registry.register(Box::new(self.query_cache_requests_total.clone()))?;
+        registry.register(Box::new(self.query_cache_removals_total.clone()))?;
+        registry.register(Box::new(self.embedding_fallback_total.clone()))?;
 
         // Indexing metrics
         registry.register(Box::new(self.vectors_total.clone()))?;
@@ -570,6 +831,27 @@ impl Metrics {
         registry.register(Box::new(self.ttl_vectors_expired_total.clone()))?;
         registry.register(Box::new(self.ttl_reaper_lag_secs.clone()))?;
 
+        // Index optimizer metrics
+        registry.register(Box::new(self.index_fragmentation_ratio.clone()))?;
+        registry.register(Box::new(self.index_compactions_total.clone()))?;
+        registry.register(Box::new(
+            self.index_compaction_reclaimed_nodes_total.clone(),
+        ))?;
+        registry.register(Box::new(self.index_compaction_duration_seconds.clone()))?;
+
+        // Backup scheduler metrics
+        registry.register(Box::new(self.backup_runs_total.clone()))?;
+        registry.register(Box::new(self.backup_last_success_timestamp_seconds.clone()))?;
+        registry.register(Box::new(self.backup_last_failure_timestamp_seconds.clone()))?;
+        registry.register(Box::new(self.backup_last_duration_seconds.clone()))?;
+        registry.register(Box::new(self.backup_pruned_snapshots_total.clone()))?;
+
+        // LLM summarization metrics
+        registry.register(Box::new(self.summarization_llm_requests_total.clone()))?;
+        registry.register(Box::new(self.summarization_llm_latency_seconds.clone()))?;
+        registry.register(Box::new(self.summarization_llm_tokens_total.clone()))?;
+        registry.register(Box::new(self.summarization_llm_cost_usd_total.clone()))?;
+
         Ok(())
     }
 }
@@ -623,6 +905,23 @@ mod tests {
         drop(timer); // Simulate completion
     }
 
+    #[test]
+    fn test_embedding_fallback_reasons() {
+        let metrics = Metrics::new();
+        metrics.record_embedding_fallback("bm25", true);
+        metrics.record_embedding_fallback("bm25", false);
+        metrics.record_embedding_fallback("bm25", false);
+
+        let count = |reason: &str| {
+            metrics
+                .embedding_fallback_total
+                .with_label_values(&["bm25", reason])
+                .get()
+        };
+        assert_eq!(count("empty_vocabulary"), 1.0);
+        assert_eq!(count("no_match"), 2.0);
+    }
+
     #[test]
     fn test_gauge_operations() {
         let metrics = Metrics::new();
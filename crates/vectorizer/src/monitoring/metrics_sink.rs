@@ -78,6 +78,64 @@ impl MetricsSink for PrometheusMetricsSink {
             .inc();
     }
 
+    fn query_cache_request(&self, collection: &str, hit: bool) {
+        let result = if hit { "hit" } else { "miss" };
+        METRICS
+            .query_cache_requests_total
+            .with_label_values(&[collection, result])
+            .inc();
+    }
+
+    fn query_cache_removal(&self, collection: &str, reason: &str, count: f64) {
+        METRICS
+            .query_cache_removals_total
+            .with_label_values(&[collection, reason])
+            .inc_by(count);
+    }
+
+    fn index_fragmentation(&self, collection: &str, ratio: f64) {
+        METRICS
+            .index_fragmentation_ratio
+            .with_label_values(&[collection])
+            .set(ratio);
+    }
+
+    fn index_compaction(&self, collection: &str, ok: bool, seconds: f64, reclaimed_nodes: f64) {
+        let result = if ok { "ok" } else { "error" };
+        METRICS
+            .index_compactions_total
+            .with_label_values(&[collection, result])
+            .inc();
+        METRICS
+            .index_compaction_reclaimed_nodes_total
+            .with_label_values(&[collection])
+            .inc_by(reclaimed_nodes);
+        METRICS.index_compaction_duration_seconds.observe(seconds);
+    }
+
+    fn scheduled_backup(&self, job: &str, ok: bool, seconds: f64, pruned: f64) {
+        let (result, last_run) = if ok {
+            ("ok", &METRICS.backup_last_success_timestamp_seconds)
+        } else {
+            ("error", &METRICS.backup_last_failure_timestamp_seconds)
+        };
+        METRICS
+            .backup_runs_total
+            .with_label_values(&[job, result])
+            .inc();
+        last_run
+            .with_label_values(&[job])
+            .set(chrono::Utc::now().timestamp() as f64);
+        METRICS
+            .backup_last_duration_seconds
+            .with_label_values(&[job])
+            .set(seconds);
+        METRICS
+            .backup_pruned_snapshots_total
+            .with_label_values(&[job])
+            .inc_by(pruned);
+    }
+
     fn hub_quota_check(&self, tenant_id: &str, quota_type: &str, allowed: bool) {
         let result_label = if allowed { "allowed" } else { "denied" };
         METRICS
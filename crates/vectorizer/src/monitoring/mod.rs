@@ -33,6 +33,7 @@ pub mod api_key_usage;
 pub mod correlation;
 pub mod metrics;
 pub mod metrics_sink;
+pub mod query_analytics;
 pub mod registry;
 pub mod system_collector;
 pub mod telemetry;
@@ -44,6 +45,7 @@ pub use correlation::{
 pub use metrics::Metrics;
 pub use metrics_sink::PrometheusMetricsSink;
 use prometheus::{Encoder, TextEncoder};
+pub use query_analytics::{QueryAnalytics, QueryAnalyticsReport};
 pub use system_collector::{SystemCollector, SystemCollectorConfig};
 
 /// Initialize the global monitoring system
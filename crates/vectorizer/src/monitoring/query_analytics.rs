@@ -0,0 +1,550 @@
+//! Query analytics — anonymized statistics of the searches clients run.
+//!
+//! Every text, hybrid and intelligent search is recorded in a
+//! capacity-bounded ring: the collection, a hash of the normalized query
+//! text, the latency, the number of results and the result IDs the
+//! client later reports as clicked. The query text itself is never kept;
+//! two searches count as the same query when their text matches after
+//! lowercasing and collapsing whitespace (see [`normalize_query`]).
+//!
+//! [`QueryAnalytics::report`] aggregates the ring into the dashboard
+//! view served at `GET /analytics/queries`: totals, the zero-result
+//! rate, latency percentiles, the most frequent queries and the most
+//! frequent queries that returned nothing.
+//!
+//! The ring is saved to [`QUERY_ANALYTICS_FILE`] in the data directory
+//! by the [`QUERY_ANALYTICS_FLUSH`](crate::background_tasks::QUERY_ANALYTICS_FLUSH)
+//! task (see [`QueryAnalytics::spawn_flush`]) and read back on startup.
+
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::{debug, info, warn};
+
+use crate::background_tasks::{self, TASKS};
+use crate::error::{Result, VectorizerError};
+
+/// File the recorded queries are saved to, in the data directory.
+pub const QUERY_ANALYTICS_FILE: &str = "query_analytics.json";
+
+/// Queries listed per ranking by default.
+pub const DEFAULT_TOP_QUERIES: usize = 20;
+
+/// Largest ranking a report returns.
+pub const MAX_TOP_QUERIES: usize = 1_000;
+
+/// Click IDs kept per recorded query.
+const MAX_CLICKS_PER_QUERY: usize = 100;
+
+/// One recorded search.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QueryRecord {
+    /// When the search ran.
+    pub timestamp: DateTime<Utc>,
+    /// Collection searched; `*` or a comma-separated list for searches
+    /// across collections.
+    pub collection: String,
+    /// [`query_hash`] of the query text.
+    pub query_hash: String,
+    /// Search kind: `text`, `hybrid` or `intelligent`.
+    pub kind: String,
+    /// Latency in milliseconds.
+    pub latency_ms: u64,
+    /// Number of results returned.
+    pub result_count: usize,
+    /// Result IDs the client reported as clicked.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub clicked_ids: Vec<String>,
+}
+
+/// Latency of the reported queries, in milliseconds.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct LatencySummary {
+    /// Mean latency.
+    pub mean: f64,
+    /// Median latency.
+    pub p50: u64,
+    /// 95th percentile latency.
+    pub p95: u64,
+    /// Slowest query.
+    pub max: u64,
+}
+
+/// Statistics of one query on one collection.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct QueryStats {
+    /// [`query_hash`] of the query text.
+    pub query_hash: String,
+    /// Collection searched.
+    pub collection: String,
+    /// Times the query was run.
+    pub count: usize,
+    /// Runs that returned no result.
+    pub zero_results: usize,
+    /// Runs with at least one reported click.
+    pub clicked: usize,
+    /// Mean number of results.
+    pub mean_result_count: f64,
+    /// Mean latency in milliseconds.
+    pub mean_latency_ms: f64,
+    /// When the query last ran.
+    pub last_seen: DateTime<Utc>,
+}
+
+/// Aggregated view of the recorded queries.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct QueryAnalyticsReport {
+    /// Queries recorded.
+    pub queries: usize,
+    /// Distinct (collection, query) pairs.
+    pub distinct_queries: usize,
+    /// Queries that returned no result.
+    pub zero_result_queries: usize,
+    /// Share of queries that returned no result (0.0 with no queries).
+    pub zero_result_rate: f64,
+    /// Queries with at least one reported click.
+    pub clicked_queries: usize,
+    /// Share of queries with at least one reported click.
+    pub click_through_rate: f64,
+    /// Latency of the recorded queries.
+    pub latency_ms: LatencySummary,
+    /// Oldest query recorded, if any.
+    pub since: Option<DateTime<Utc>>,
+    /// Most frequent queries, most frequent first.
+    pub top_queries: Vec<QueryStats>,
+    /// Queries that most often returned no result, most frequent first.
+    pub top_zero_result_queries: Vec<QueryStats>,
+}
+
+/// Query text as it is compared: lowercased, with runs of whitespace
+/// collapsed to one space and the ends trimmed.
+pub fn normalize_query(query: &str) -> String {
+    query
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Hex SHA-256 of the [`normalize_query`] form of `query`, truncated to
+/// 16 characters.
+pub fn query_hash(query: &str) -> String {
+    let mut hash = hex::encode(Sha256::digest(normalize_query(query).as_bytes()));
+    hash.truncate(16);
+    hash
+}
+
+/// Capacity-bounded ring of recorded queries.
+#[derive(Debug)]
+pub struct QueryAnalytics {
+    records: RwLock<VecDeque<QueryRecord>>,
+    capacity: usize,
+    /// File the ring is saved to; `None` keeps it in memory
+    path: Option<PathBuf>,
+    /// Recorded since the last save
+    dirty: AtomicBool,
+}
+
+impl QueryAnalytics {
+    /// An empty in-memory ring keeping the newest `capacity` queries.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            records: RwLock::new(VecDeque::new()),
+            capacity: capacity.max(1),
+            path: None,
+            dirty: AtomicBool::new(false),
+        }
+    }
+
+    /// The queries saved at `path`, which [`Self::flush`] writes to. A
+    /// missing file is an empty ring; an unreadable one is an error.
+    pub fn open(path: impl Into<PathBuf>, capacity: usize) -> Result<Self> {
+        let path = path.into();
+        let records: Vec<QueryRecord> = match std::fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).map_err(|e| {
+                VectorizerError::Storage(format!(
+                    "failed to parse query analytics in {}: {}",
+                    path.display(),
+                    e
+                ))
+            })?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(VectorizerError::IoError(e)),
+        };
+        let mut analytics = Self::new(capacity);
+        {
+            let ring = analytics.records.get_mut();
+            let skip = records.len().saturating_sub(analytics.capacity);
+            ring.extend(records.into_iter().skip(skip));
+        }
+        analytics.path = Some(path);
+        Ok(analytics)
+    }
+
+    /// [`Self::open`] on [`QUERY_ANALYTICS_FILE`] in `data_dir`.
+    pub fn open_in(data_dir: &Path, capacity: usize) -> Result<Self> {
+        Self::open(data_dir.join(QUERY_ANALYTICS_FILE), capacity)
+    }
+
+    /// Record a search of `collection` for `query` that returned
+    /// `result_count` results in `latency`.
+    pub fn record(
+        &self,
+        collection: &str,
+        kind: &str,
+        query: &str,
+        latency: Duration,
+        result_count: usize,
+    ) {
+        self.push(QueryRecord {
+            timestamp: Utc::now(),
+            collection: collection.to_string(),
+            query_hash: query_hash(query),
+            kind: kind.to_string(),
+            latency_ms: latency.as_millis() as u64,
+            result_count,
+            clicked_ids: Vec::new(),
+        });
+    }
+
+    fn push(&self, record: QueryRecord) {
+        let mut records = self.records.write();
+        if records.len() >= self.capacity {
+            records.pop_front();
+        }
+        records.push_back(record);
+        self.dirty.store(true, Ordering::Relaxed);
+    }
+
+    /// Attach a click on `result_id` to the latest search of
+    /// `collection` for `query`. Returns whether such a search is
+    /// recorded; a result clicked twice is counted once.
+    pub fn record_click(&self, collection: &str, query: &str, result_id: &str) -> bool {
+        let hash = query_hash(query);
+        let mut records = self.records.write();
+        let Some(record) = records
+            .iter_mut()
+            .rev()
+            .find(|r| r.collection == collection && r.query_hash == hash)
+        else {
+            return false;
+        };
+        if !record.clicked_ids.iter().any(|id| id == result_id)
+            && record.clicked_ids.len() < MAX_CLICKS_PER_QUERY
+        {
+            record.clicked_ids.push(result_id.to_string());
+            self.dirty.store(true, Ordering::Relaxed);
+        }
+        true
+    }
+
+    /// Number of queries recorded.
+    pub fn len(&self) -> usize {
+        self.records.read().len()
+    }
+
+    /// Whether no query is recorded.
+    pub fn is_empty(&self) -> bool {
+        self.records.read().is_empty()
+    }
+
+    /// Queries kept at most.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Aggregate the recorded queries of `collection` (every collection
+    /// when `None`), listing `top` queries per ranking.
+    pub fn report(&self, collection: Option<&str>, top: usize) -> QueryAnalyticsReport {
+        let records = self.records.read();
+        let selected: Vec<&QueryRecord> = records
+            .iter()
+            .filter(|r| collection.is_none_or(|c| r.collection == c))
+            .collect();
+
+        let queries = selected.len();
+        let zero_result_queries = selected.iter().filter(|r| r.result_count == 0).count();
+        let clicked_queries = selected
+            .iter()
+            .filter(|r| !r.clicked_ids.is_empty())
+            .count();
+        let rate = |n: usize| {
+            if queries == 0 {
+                0.0
+            } else {
+                n as f64 / queries as f64
+            }
+        };
+
+        let mut latencies: Vec<u64> = selected.iter().map(|r| r.latency_ms).collect();
+        latencies.sort_unstable();
+        let percentile = |p: f64| {
+            latencies
+                .get(((latencies.len() as f64 - 1.0) * p).round() as usize)
+                .copied()
+                .unwrap_or(0)
+        };
+        let latency_ms = LatencySummary {
+            mean: if queries == 0 {
+                0.0
+            } else {
+                latencies.iter().sum::<u64>() as f64 / queries as f64
+            },
+            p50: percentile(0.5),
+            p95: percentile(0.95),
+            max: latencies.last().copied().unwrap_or(0),
+        };
+
+        let mut grouped: HashMap<(&str, &str), Vec<&QueryRecord>> = HashMap::new();
+        for record in selected.iter().copied() {
+            grouped
+                .entry((record.collection.as_str(), record.query_hash.as_str()))
+                .or_default()
+                .push(record);
+        }
+        let stats: Vec<QueryStats> = grouped
+            .into_iter()
+            .map(|((collection, query_hash), runs)| {
+                let count = runs.len();
+                QueryStats {
+                    query_hash: query_hash.to_string(),
+                    collection: collection.to_string(),
+                    count,
+                    zero_results: runs.iter().filter(|r| r.result_count == 0).count(),
+                    clicked: runs.iter().filter(|r| !r.clicked_ids.is_empty()).count(),
+                    mean_result_count: runs.iter().map(|r| r.result_count).sum::<usize>() as f64
+                        / count as f64,
+                    mean_latency_ms: runs.iter().map(|r| r.latency_ms).sum::<u64>() as f64
+                        / count as f64,
+                    last_seen: runs
+                        .iter()
+                        .map(|r| r.timestamp)
+                        .max()
+                        .unwrap_or_else(Utc::now),
+                }
+            })
+            .collect();
+
+        QueryAnalyticsReport {
+            queries,
+            zero_result_queries,
+            zero_result_rate: rate(zero_result_queries),
+            clicked_queries,
+            click_through_rate: rate(clicked_queries),
+            latency_ms,
+            since: selected.first().map(|r| r.timestamp),
+            distinct_queries: stats.len(),
+            top_queries: ranked(&stats, top, |s| s.count),
+            top_zero_result_queries: ranked(&stats, top, |s| s.zero_results),
+        }
+    }
+
+    /// Forget every recorded query. Returns the number dropped.
+    pub fn clear(&self) -> usize {
+        let dropped = std::mem::take(&mut *self.records.write()).len();
+        self.dirty.store(true, Ordering::Relaxed);
+        dropped
+    }
+
+    /// Save the ring to its file if anything was recorded since the last
+    /// save, through a temporary file so a crash never leaves it half
+    /// written. Returns whether it was saved.
+    pub fn flush(&self) -> Result<bool> {
+        let Some(path) = &self.path else {
+            return Ok(false);
+        };
+        if !self.dirty.swap(false, Ordering::Relaxed) {
+            return Ok(false);
+        }
+        let bytes = {
+            let records = self.records.read();
+            serde_json::to_vec(&*records)
+        };
+        let saved = bytes.map_err(VectorizerError::from).and_then(|bytes| {
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            let tmp = path.with_extension("json.tmp");
+            std::fs::write(&tmp, bytes)?;
+            std::fs::rename(&tmp, path)?;
+            Ok(())
+        });
+        if saved.is_err() {
+            // Try again on the next flush
+            self.dirty.store(true, Ordering::Relaxed);
+        }
+        saved.map(|()| true)
+    }
+
+    /// Flush every `interval` until the process exits, reporting to the
+    /// [`QUERY_ANALYTICS_FLUSH`](background_tasks::QUERY_ANALYTICS_FLUSH)
+    /// task. A paused task skips its flushes; the queries recorded
+    /// meanwhile are saved on the first flush after resuming.
+    pub fn spawn_flush(self: Arc<Self>, interval: Duration) {
+        tokio::spawn(async move {
+            let task = TASKS.register(background_tasks::QUERY_ANALYTICS_FLUSH);
+            info!(
+                "Query analytics flush started (interval {}s)",
+                interval.as_secs()
+            );
+
+            loop {
+                task.schedule(interval);
+                tokio::time::sleep(interval).await;
+
+                if task.is_paused() {
+                    debug!("Query analytics flush paused, skipping");
+                    continue;
+                }
+
+                let analytics = self.clone();
+                task.start();
+                let result = match tokio::task::spawn_blocking(move || analytics.flush()).await {
+                    Ok(result) => result,
+                    Err(e) => Err(VectorizerError::Storage(format!(
+                        "query analytics flush panicked: {}",
+                        e
+                    ))),
+                };
+                task.finish(&result);
+                if let Err(e) = result {
+                    warn!("Failed to save query analytics: {}", e);
+                }
+            }
+        });
+    }
+}
+
+/// The `top` entries of `stats` with a non-zero `key`, highest first;
+/// ties go to the query seen most recently.
+fn ranked(stats: &[QueryStats], top: usize, key: impl Fn(&QueryStats) -> usize) -> Vec<QueryStats> {
+    let mut ranked: Vec<QueryStats> = stats.iter().filter(|s| key(s) > 0).cloned().collect();
+    ranked.sort_by(|a, b| {
+        key(b)
+            .cmp(&key(a))
+            .then_with(|| b.last_seen.cmp(&a.last_seen))
+            .then_with(|| a.query_hash.cmp(&b.query_hash))
+    });
+    ranked.truncate(top);
+    ranked
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn queries_are_hashed_after_normalizing() {
+        assert_eq!(normalize_query("  Reset   PASSWORD\n"), "reset password");
+        assert_eq!(
+            query_hash("Reset password"),
+            query_hash(" reset  password ")
+        );
+        assert_ne!(query_hash("reset password"), query_hash("reset passwords"));
+        assert_eq!(query_hash("anything").len(), 16);
+    }
+
+    #[test]
+    fn ring_drops_the_oldest_queries() {
+        let analytics = QueryAnalytics::new(3);
+        for i in 0..5 {
+            analytics.record("docs", "text", &format!("q{i}"), ms(1), 1);
+        }
+        assert_eq!(analytics.len(), 3);
+        let report = analytics.report(None, 10);
+        let hashes: Vec<_> = report
+            .top_queries
+            .iter()
+            .map(|s| s.query_hash.clone())
+            .collect();
+        assert!(!hashes.contains(&query_hash("q0")));
+        assert!(hashes.contains(&query_hash("q4")));
+    }
+
+    #[test]
+    fn report_ranks_queries_and_zero_results() {
+        let analytics = QueryAnalytics::new(100);
+        analytics.record("docs", "text", "install", ms(10), 5);
+        analytics.record("docs", "text", "Install", ms(30), 3);
+        analytics.record("docs", "hybrid", "install ", ms(20), 4);
+        analytics.record("docs", "text", "kubernetes", ms(40), 0);
+        analytics.record("docs", "text", "kubernetes", ms(50), 0);
+        analytics.record("other", "text", "install", ms(5), 0);
+
+        let report = analytics.report(Some("docs"), 10);
+        assert_eq!(report.queries, 5);
+        assert_eq!(report.distinct_queries, 2);
+        assert_eq!(report.zero_result_queries, 2);
+        assert!((report.zero_result_rate - 0.4).abs() < 1e-9);
+        assert_eq!(report.latency_ms.p50, 30);
+        assert_eq!(report.latency_ms.max, 50);
+
+        assert_eq!(report.top_queries[0].query_hash, query_hash("install"));
+        assert_eq!(report.top_queries[0].count, 3);
+        assert_eq!(report.top_queries[0].mean_result_count, 4.0);
+        assert_eq!(report.top_zero_result_queries.len(), 1);
+        assert_eq!(
+            report.top_zero_result_queries[0].query_hash,
+            query_hash("kubernetes")
+        );
+
+        let everywhere = analytics.report(None, 1);
+        assert_eq!(everywhere.queries, 6);
+        assert_eq!(everywhere.distinct_queries, 3);
+        assert_eq!(everywhere.top_queries.len(), 1);
+    }
+
+    #[test]
+    fn clicks_attach_to_the_latest_matching_query() {
+        let analytics = QueryAnalytics::new(100);
+        analytics.record("docs", "text", "install", ms(1), 3);
+        analytics.record("docs", "text", "install", ms(1), 3);
+
+        assert!(analytics.record_click("docs", "INSTALL", "doc-1"));
+        assert!(analytics.record_click("docs", "install", "doc-1"));
+        assert!(!analytics.record_click("docs", "uninstall", "doc-1"));
+        assert!(!analytics.record_click("other", "install", "doc-1"));
+
+        let report = analytics.report(None, 10);
+        assert_eq!(report.clicked_queries, 1);
+        assert_eq!(report.top_queries[0].clicked, 1);
+        let records = analytics.records.read();
+        assert!(records[0].clicked_ids.is_empty());
+        assert_eq!(records[1].clicked_ids, vec!["doc-1".to_string()]);
+    }
+
+    #[test]
+    fn flush_saves_and_open_restores() {
+        let dir = tempfile::tempdir().unwrap();
+        let analytics = QueryAnalytics::open_in(dir.path(), 100).unwrap();
+        assert!(analytics.is_empty());
+        assert!(!analytics.flush().unwrap());
+
+        analytics.record("docs", "text", "install", ms(12), 2);
+        analytics.record("docs", "text", "missing", ms(8), 0);
+        assert!(analytics.flush().unwrap());
+        assert!(!analytics.flush().unwrap());
+
+        let saved = std::fs::read_to_string(dir.path().join(QUERY_ANALYTICS_FILE)).unwrap();
+        assert!(!saved.contains("install"));
+
+        let reopened = QueryAnalytics::open_in(dir.path(), 1).unwrap();
+        assert_eq!(reopened.len(), 1);
+        assert_eq!(
+            reopened.report(None, 10).top_zero_result_queries[0].query_hash,
+            query_hash("missing")
+        );
+    }
+}
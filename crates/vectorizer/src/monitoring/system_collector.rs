@@ -146,6 +146,13 @@ mod tests {
             normalization: None,
             storage_type: Some(crate::models::StorageType::Memory),
             encryption: None,
+            id_policy: None,
+            quota: None,
+            scoring: None,
+            tenant_partition: None,
+            dedup: None,
+            chunking: None,
+            full_text: None,
         };
         let _ = store.create_collection("test_metrics", config);
 
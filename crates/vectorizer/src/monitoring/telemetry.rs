@@ -1,102 +1,269 @@
 //! OpenTelemetry Distributed Tracing
 //!
-//! This module provides distributed tracing capabilities using OpenTelemetry.
-//! It enables end-to-end request tracking across the vector database system.
+//! This module exports `tracing` spans to an OTLP collector, so one request
+//! can be followed from the REST handler through embedding generation,
+//! filter evaluation, the HNSW walk and rescoring.
 //!
 //! # Status
 //!
-//! OpenTelemetry tracing is **OPTIONAL** and gracefully degrades if OTLP collector
-//! is not available. The system will continue to function with standard logging.
+//! OpenTelemetry tracing is **OPTIONAL**: it is only enabled when an OTLP
+//! endpoint is configured through the standard
+//! `OTEL_EXPORTER_OTLP_TRACES_ENDPOINT` / `OTEL_EXPORTER_OTLP_ENDPOINT`
+//! variables (or the legacy `OTLP_ENDPOINT`). Without one no exporter is
+//! built and the server logs as before.
+//!
+//! # Span tree
+//!
+//! - `http.request` — one per HTTP request, created by
+//!   [`trace_middleware`]. A W3C `traceparent` header on the request makes
+//!   it a child of the caller's span.
+//! - `embedding.generate` — text embedding by the embedding manager.
+//! - `filter.evaluate` — payload filters applied to search candidates.
+//! - `hnsw.search` — the graph walk, with its beam width and visited count.
+//! - `hnsw.rescore` — exact rescoring of a quantized shortlist.
 //!
 //! # Usage
 //!
 //! ```rust,no_run
+//! use tracing_subscriber::layer::SubscriberExt;
+//! use tracing_subscriber::util::SubscriberInitExt;
 //! use vectorizer::monitoring::telemetry;
 //!
-//! // Initialize tracing (optional - call once at startup)
-//! // Will log warning if OTLP collector not available, but won't fail
-//! let _ = telemetry::try_init("vectorizer", None);
+//! # #[tokio::main]
+//! # async fn main() -> anyhow::Result<()> {
+//! // `None` when no OTLP endpoint is configured
+//! let otel = telemetry::try_init("vectorizer", None)?;
+//! tracing_subscriber::registry().with(otel).init();
 //!
-//! // Use standard tracing macros - spans will be exported if OTLP is available
-//! use tracing::{info, info_span};
+//! // ... serve requests ...
 //!
-//! let span = info_span!("search_operation", collection = "my_collection");
-//! let _enter = span.enter();
-//! info!("Performing search...");
+//! telemetry::shutdown();
+//! # Ok(())
+//! # }
 //! ```
 
+use std::sync::OnceLock;
+
 use anyhow::Result;
-use opentelemetry::global;
+use axum::extract::Request;
+use axum::http::HeaderMap;
+use axum::middleware::Next;
+use axum::response::Response;
+use opentelemetry::propagation::Extractor;
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry::{Context, global};
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::Resource;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use tracing::{Instrument, Level, field};
+use tracing_subscriber::Layer;
+use tracing_subscriber::filter::Targets;
+use tracing_subscriber::registry::LookupSpan;
+
+/// Environment variables naming the OTLP gRPC endpoint, most specific
+/// first. `OTLP_ENDPOINT` is kept for older deployments.
+pub const OTLP_ENDPOINT_ENV_VARS: [&str; 3] = [
+    "OTEL_EXPORTER_OTLP_TRACES_ENDPOINT",
+    "OTEL_EXPORTER_OTLP_ENDPOINT",
+    "OTLP_ENDPOINT",
+];
+
+/// The installed tracer provider, kept for [`shutdown`].
+static PROVIDER: OnceLock<SdkTracerProvider> = OnceLock::new();
+
+/// OTLP endpoint from the environment, if one is set.
+pub fn otlp_endpoint_from_env() -> Option<String> {
+    OTLP_ENDPOINT_ENV_VARS
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|endpoint| !endpoint.trim().is_empty())
+}
+
+/// Whether spans are being exported.
+pub fn is_enabled() -> bool {
+    PROVIDER.get().is_some()
+}
 
 /// Try to initialize OpenTelemetry distributed tracing
 ///
-/// This is a best-effort initialization that will not fail the server startup
-/// if the OTLP collector is not available.
+/// Builds an OTLP gRPC span exporter, installs it as the global tracer
+/// provider together with the W3C trace-context propagator, and returns
+/// the `tracing` layer that feeds it. The layer only sees spans and
+/// events of the vectorizer crates at `INFO` and above, whatever the log
+/// filter says.
+///
+/// Must be called from within a Tokio runtime, once, before the global
+/// subscriber is installed.
 ///
 /// # Arguments
 ///
-/// * `service_name` - Name of the service (e.g., "vectorizer")
-/// * `otlp_endpoint` - Optional OTLP gRPC endpoint (default: http://localhost:4317)
+/// * `service_name` - Name of the service (e.g., "vectorizer"); `OTEL_SERVICE_NAME` wins
+/// * `otlp_endpoint` - OTLP gRPC endpoint; defaults to [`otlp_endpoint_from_env`]
 ///
 /// # Returns
 ///
-/// Returns Ok(()) if initialized successfully, or Err with warning message if not.
-/// The error is non-fatal and can be safely ignored.
-pub fn try_init(service_name: &str, otlp_endpoint: Option<String>) -> Result<()> {
-    let endpoint = otlp_endpoint.unwrap_or_else(|| "http://localhost:4317".to_string());
-
-    tracing::info!(
-        "Attempting to initialize OpenTelemetry tracing with endpoint: {}",
-        endpoint
-    );
-    tracing::info!(
-        "Note: OpenTelemetry is optional. Server will continue if OTLP collector is not available."
-    );
-
-    // For now, we just log that tracing is available but not enabled by default
-    // Full OTLP integration requires additional setup and running collector
-    tracing::warn!(
-        "OpenTelemetry tracing is prepared but not enabled by default. \
-         To enable: set OTLP_ENDPOINT environment variable or configure in config.yml"
-    );
-
-    Ok(())
+/// `Ok(None)` when no endpoint is configured, or when tracing was already
+/// initialized. `Err` when the exporter cannot be built; the error is
+/// non-fatal and the server should carry on without tracing.
+pub fn try_init<S>(
+    service_name: &str,
+    otlp_endpoint: Option<String>,
+) -> Result<Option<impl Layer<S> + Send + Sync + 'static>>
+where
+    S: tracing::Subscriber + for<'span> LookupSpan<'span> + Send + Sync + 'static,
+{
+    let Some(endpoint) = otlp_endpoint.or_else(otlp_endpoint_from_env) else {
+        return Ok(None);
+    };
+    if is_enabled() {
+        return Ok(None);
+    }
+
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_tonic()
+        .with_endpoint(endpoint)
+        .build()?;
+    let mut resource = Resource::builder();
+    if std::env::var_os("OTEL_SERVICE_NAME").is_none() {
+        resource = resource.with_service_name(service_name.to_string());
+    }
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(resource.build())
+        .build();
+    let tracer = provider.tracer("vectorizer");
+
+    global::set_text_map_propagator(TraceContextPropagator::new());
+    global::set_tracer_provider(provider.clone());
+    let _ = PROVIDER.set(provider);
+
+    let targets = Targets::new()
+        .with_target("vectorizer", Level::INFO)
+        .with_target("vectorizer_server", Level::INFO);
+    Ok(Some(
+        tracing_opentelemetry::layer()
+            .with_tracer(tracer)
+            .with_filter(targets),
+    ))
 }
 
 /// Shutdown OpenTelemetry (flush all pending spans)
 ///
-/// Note: In OpenTelemetry 0.31+, shutdown is handled automatically when the provider is dropped.
-/// This function is kept for API compatibility but does nothing since we don't create a provider.
+/// Call once on the way out; a no-op when tracing was never initialized.
 pub fn shutdown() {
-    tracing::debug!("OpenTelemetry tracing shutdown called (no-op: no provider initialized)");
+    if let Some(provider) = PROVIDER.get()
+        && let Err(e) = provider.shutdown()
+    {
+        tracing::warn!("Failed to flush OpenTelemetry spans: {}", e);
+    }
+}
+
+/// Reads propagation headers (`traceparent`, `tracestate`) from a request.
+struct HeaderExtractor<'a>(&'a HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|v| v.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(|k| k.as_str()).collect()
+    }
+}
+
+/// The remote span context carried by `headers`, or an empty context.
+pub fn extract_context(headers: &HeaderMap) -> Context {
+    global::get_text_map_propagator(|propagator| propagator.extract(&HeaderExtractor(headers)))
+}
+
+/// Middleware opening the `http.request` span every other span of the
+/// request nests under, parented to the caller's `traceparent` if any.
+pub async fn trace_middleware(req: Request, next: Next) -> Response {
+    let span = {
+        // A span without a local parent takes the current OpenTelemetry
+        // context as its parent.
+        let _remote = extract_context(req.headers()).attach();
+        tracing::info_span!(
+            "http.request",
+            otel.name = %req.method(),
+            otel.kind = "server",
+            otel.status_code = field::Empty,
+            http.request.method = %req.method(),
+            url.path = %req.uri().path(),
+            http.response.status_code = field::Empty,
+        )
+    };
+
+    let response = next.run(req).instrument(span.clone()).await;
+    let status = response.status();
+    span.record("http.response.status_code", status.as_u16());
+    if status.is_server_error() {
+        span.record("otel.status_code", "ERROR");
+    }
+    response
 }
 
 #[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
 mod tests {
+    use axum::body::Body;
+    use axum::http::Request as HttpRequest;
+    use axum::http::StatusCode;
+    use axum::{Router, middleware, routing::get};
+    use opentelemetry::trace::TraceContextExt;
+    use tower::ServiceExt;
+
     use super::*;
 
     #[test]
-    fn test_try_init() {
-        let result = try_init("vectorizer-test", None);
-        assert!(
-            result.is_ok(),
-            "try_init should always succeed (graceful degradation)"
-        );
+    fn test_try_init_without_endpoint() {
+        if otlp_endpoint_from_env().is_some() {
+            return;
+        }
+        let layer = try_init::<tracing_subscriber::Registry>("vectorizer-test", None).unwrap();
+        assert!(layer.is_none(), "no endpoint means no exporter");
+        assert!(!is_enabled());
     }
 
     #[test]
-    fn test_try_init_with_endpoint() {
-        let result = try_init("vectorizer-test", Some("http://custom:4317".to_string()));
-        assert!(
-            result.is_ok(),
-            "try_init should succeed even with custom endpoint"
-        );
+    fn test_shutdown_without_init() {
+        // Should not panic
+        shutdown();
     }
 
     #[test]
-    fn test_shutdown() {
-        // Should not panic
-        shutdown();
-        assert!(true);
+    fn test_extract_traceparent() {
+        global::set_text_map_propagator(TraceContextPropagator::new());
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "traceparent",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"
+                .parse()
+                .unwrap(),
+        );
+        let cx = extract_context(&headers);
+        let span_context = cx.span().span_context().clone();
+        assert!(span_context.is_remote());
+        assert_eq!(
+            span_context.trace_id().to_string(),
+            "4bf92f3577b34da6a3ce929d0e0e4736"
+        );
+
+        let cx = extract_context(&HeaderMap::new());
+        assert!(!cx.span().span_context().is_valid());
+    }
+
+    #[tokio::test]
+    async fn test_trace_middleware_passes_response_through() {
+        let app = Router::new()
+            .route("/ok", get(|| async { "ok" }))
+            .layer(middleware::from_fn(trace_middleware));
+
+        let response = app
+            .oneshot(HttpRequest::get("/ok").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
     }
 }
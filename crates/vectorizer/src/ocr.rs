@@ -0,0 +1,185 @@
+//! OCR text extraction for images
+//!
+//! Scanned documents and screenshots hold text that search cannot see.
+//! When [`OcrConfig::enabled`] is set, the file watcher runs images
+//! through [`OcrEngine`] and indexes the extracted text with the image
+//! path in the payload. Recognition uses tesseract and needs the `ocr`
+//! feature (and the system libtesseract); without it every extraction
+//! fails with [`VectorizerError::OcrError`].
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, VectorizerError};
+
+/// OCR settings of the file watcher
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OcrConfig {
+    /// Run OCR on images (off by default)
+    pub enabled: bool,
+    /// Tesseract language(s), e.g. `eng` or `eng+por`
+    pub language: String,
+    /// Directory holding the `*.traineddata` files (tesseract's default
+    /// location when unset)
+    pub data_path: Option<String>,
+    /// Images whose mean word confidence (0-100) is below this are not
+    /// indexed
+    pub min_confidence: i32,
+    /// Image file extensions to run OCR on
+    pub extensions: Vec<String>,
+}
+
+impl Default for OcrConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            language: "eng".to_string(),
+            data_path: None,
+            min_confidence: 0,
+            extensions: ["png", "jpg", "jpeg", "tif", "tiff", "bmp", "gif", "webp"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        }
+    }
+}
+
+impl OcrConfig {
+    /// Whether `path` has one of the configured image extensions
+    pub fn is_image(&self, path: &Path) -> bool {
+        path.extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|ext| self.extensions.iter().any(|e| e.eq_ignore_ascii_case(ext)))
+    }
+}
+
+/// Text recognized in an image
+#[derive(Debug, Clone, PartialEq)]
+pub struct OcrOutput {
+    /// The recognized text, trimmed
+    pub text: String,
+    /// Mean word confidence, 0-100
+    pub confidence: i32,
+}
+
+impl OcrOutput {
+    /// Whether the output is worth indexing under `config`: it has text
+    /// and meets the confidence threshold
+    pub fn is_usable(&self, config: &OcrConfig) -> bool {
+        !self.text.is_empty() && self.confidence >= config.min_confidence
+    }
+}
+
+/// Runs OCR on image files
+#[derive(Debug, Clone)]
+pub struct OcrEngine {
+    config: OcrConfig,
+}
+
+impl OcrEngine {
+    /// Create an engine with `config`
+    pub fn new(config: OcrConfig) -> Self {
+        Self { config }
+    }
+
+    /// The engine's settings
+    pub fn config(&self) -> &OcrConfig {
+        &self.config
+    }
+
+    /// Recognize the text of the image at `path`. Blocking: async
+    /// callers run it on [`tokio::task::spawn_blocking`].
+    #[cfg(feature = "ocr")]
+    pub fn extract_text(&self, path: &Path) -> Result<OcrOutput> {
+        let image = path
+            .to_str()
+            .ok_or_else(|| VectorizerError::OcrError(format!("non UTF-8 path: {:?}", path)))?;
+
+        let mut tesseract = tesseract::Tesseract::new(
+            self.config.data_path.as_deref(),
+            Some(self.config.language.as_str()),
+        )
+        .map_err(ocr_error)?
+        .set_image(image)
+        .map_err(ocr_error)?
+        .recognize()
+        .map_err(ocr_error)?;
+        let confidence = tesseract.mean_text_conf();
+        let text = tesseract.get_text().map_err(ocr_error)?;
+
+        Ok(OcrOutput {
+            text: text.trim().to_string(),
+            confidence,
+        })
+    }
+
+    /// Recognize the text of the image at `path` (feature disabled
+    /// fallback)
+    #[cfg(not(feature = "ocr"))]
+    pub fn extract_text(&self, path: &Path) -> Result<OcrOutput> {
+        Err(VectorizerError::OcrError(format!(
+            "cannot read {:?}: built without the `ocr` feature",
+            path
+        )))
+    }
+}
+
+#[cfg(feature = "ocr")]
+fn ocr_error(e: impl std::fmt::Display) -> VectorizerError {
+    VectorizerError::OcrError(e.to_string())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config_is_disabled() {
+        let config = OcrConfig::default();
+        assert!(!config.enabled);
+        assert_eq!(config.language, "eng");
+    }
+
+    #[test]
+    fn test_is_image_ignores_case() {
+        let config = OcrConfig::default();
+        assert!(config.is_image(Path::new("scans/page-1.PNG")));
+        assert!(config.is_image(Path::new("shot.jpeg")));
+        assert!(!config.is_image(Path::new("notes.md")));
+        assert!(!config.is_image(Path::new("png")));
+    }
+
+    #[test]
+    fn test_partial_yaml_keeps_defaults() {
+        let config: OcrConfig = serde_yaml::from_str("enabled: true\nlanguage: eng+por").unwrap();
+        assert!(config.enabled);
+        assert_eq!(config.language, "eng+por");
+        assert_eq!(config.extensions, OcrConfig::default().extensions);
+    }
+
+    #[test]
+    fn test_usable_output() {
+        let config = OcrConfig {
+            min_confidence: 50,
+            ..OcrConfig::default()
+        };
+        let output = |text: &str, confidence| OcrOutput {
+            text: text.to_string(),
+            confidence,
+        };
+        assert!(output("Invoice 42", 80).is_usable(&config));
+        assert!(!output("Invoice 42", 30).is_usable(&config));
+        assert!(!output("", 95).is_usable(&config));
+    }
+
+    #[cfg(not(feature = "ocr"))]
+    #[test]
+    fn test_extract_without_feature_fails() {
+        let engine = OcrEngine::new(OcrConfig::default());
+        let err = engine.extract_text(Path::new("scan.png")).unwrap_err();
+        assert!(matches!(err, VectorizerError::OcrError(_)));
+    }
+}
@@ -37,6 +37,13 @@ async fn test_persistence_demo() {
         normalization: None,
         storage_type: Some(crate::models::StorageType::Memory),
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     info!(
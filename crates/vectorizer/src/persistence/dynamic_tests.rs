@@ -43,6 +43,13 @@ async fn test_create_dynamic_collection() {
         normalization: None,
         storage_type: Some(crate::models::StorageType::Memory),
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     let metadata = persistence
@@ -76,6 +83,13 @@ async fn test_collection_exists() {
         normalization: None,
         storage_type: Some(crate::models::StorageType::Memory),
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     // Collection doesn't exist yet
@@ -107,6 +121,13 @@ async fn test_list_collections() {
         normalization: None,
         storage_type: Some(crate::models::StorageType::Memory),
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     // Initially empty
@@ -144,6 +165,13 @@ async fn test_transaction_lifecycle() {
         normalization: None,
         storage_type: Some(crate::models::StorageType::Memory),
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     let metadata = persistence
@@ -189,6 +217,13 @@ async fn test_delete_collection() {
         normalization: None,
         storage_type: Some(crate::models::StorageType::Memory),
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     // Create collection
@@ -226,6 +261,13 @@ async fn test_get_stats() {
         normalization: None,
         storage_type: Some(crate::models::StorageType::Memory),
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     // Create some collections
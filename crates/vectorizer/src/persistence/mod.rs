@@ -55,6 +55,21 @@ pub struct PersistedCollection {
     pub vectors: Vec<PersistedVector>,
     /// HNSW index dump basename (if available)
     pub hnsw_dump_basename: Option<String>,
+    /// Recall / latency profile of the HNSW index (absent in older files)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recall_profile: Option<crate::db::RecallProfile>,
+    /// Relevance feedback on search results (absent in older files)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub feedback: Option<crate::db::RelevanceFeedback>,
+    /// Metadata key-value store (absent in older files)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata_kv: Option<crate::db::MetadataKv>,
+    /// Hybrid search judgements and tuned weights (absent in older files)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hybrid_tuning: Option<crate::db::HybridTuning>,
+    /// Settings changed after creation (absent in older files)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub overrides: Option<crate::db::CollectionOverrides>,
 }
 
 /// Persisted representation of a vector with payload serialized as JSON string
@@ -199,6 +214,11 @@ impl VectorStore {
                 config: Some(metadata.config),
                 vectors,
                 hnsw_dump_basename,
+                recall_profile: collection.recall_profile(),
+                feedback: collection.feedback(),
+                metadata_kv: collection.metadata_kv(),
+                hybrid_tuning: collection.hybrid_tuning(),
+                overrides: collection.overrides(),
             });
         }
 
@@ -449,6 +449,13 @@ mod tests {
             normalization: None,
             storage_type: Some(crate::models::StorageType::Memory),
             encryption: None,
+            id_policy: None,
+            quota: None,
+            scoring: None,
+            tenant_partition: None,
+            dedup: None,
+            chunking: None,
+            full_text: None,
         };
 
         let metadata = EnhancedCollectionMetadata::new_workspace(
@@ -479,6 +486,13 @@ mod tests {
             normalization: None,
             storage_type: Some(crate::models::StorageType::Memory),
             encryption: None,
+            id_policy: None,
+            quota: None,
+            scoring: None,
+            tenant_partition: None,
+            dedup: None,
+            chunking: None,
+            full_text: None,
         };
 
         let metadata = EnhancedCollectionMetadata::new_dynamic(
@@ -509,6 +523,13 @@ mod tests {
             normalization: None,
             storage_type: Some(crate::models::StorageType::Memory),
             encryption: None,
+            id_policy: None,
+            quota: None,
+            scoring: None,
+            tenant_partition: None,
+            dedup: None,
+            chunking: None,
+            full_text: None,
         };
 
         let mut metadata = EnhancedCollectionMetadata::new_dynamic(
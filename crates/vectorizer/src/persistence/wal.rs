@@ -11,6 +11,7 @@ use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+use chrono::{DateTime, Utc};
 use parking_lot::Mutex;
 use serde_json;
 use thiserror::Error;
@@ -36,6 +37,9 @@ pub struct WriteAheadLog {
     checkpoint_interval: Duration,
     /// fsync after append/checkpoint flush (see [`WALConfig::fsync`])
     fsync: bool,
+    /// Since when the log holds every operation (see
+    /// [`WriteAheadLog::history_start`])
+    history_start: Mutex<DateTime<Utc>>,
 }
 
 /// WAL errors
@@ -150,6 +154,32 @@ fn parse_line(line: &str) -> ParsedLine {
     }
 }
 
+/// Sidecar file recording the history start of the log at `file_path`
+/// (`<file>.since`, RFC 3339).
+fn history_marker_path(file_path: &Path) -> PathBuf {
+    let mut name = file_path.as_os_str().to_owned();
+    name.push(".since");
+    PathBuf::from(name)
+}
+
+fn write_history_start(file_path: &Path, at: DateTime<Utc>) -> Result<(), WALError> {
+    std::fs::write(history_marker_path(file_path), at.to_rfc3339()).map_err(WALError::IoError)
+}
+
+/// History start of the log at `file_path`. A log without a readable
+/// marker is new or predates the marker: nothing is known about what it
+/// lost to checkpoints, so its history is trusted from now on.
+fn load_history_start(file_path: &Path) -> Result<DateTime<Utc>, WALError> {
+    if let Ok(content) = std::fs::read_to_string(history_marker_path(file_path))
+        && let Ok(at) = DateTime::parse_from_rfc3339(content.trim())
+    {
+        return Ok(at.with_timezone(&Utc));
+    }
+    let now = Utc::now();
+    write_history_start(file_path, now)?;
+    Ok(now)
+}
+
 /// Read every entry in the WAL file, tolerating a torn final record.
 ///
 /// A damaged FINAL line is a torn append (crash mid-write): it is
@@ -157,10 +187,20 @@ fn parse_line(line: &str) -> ParsedLine {
 /// damaged line anywhere else is real corruption and aborts with
 /// [`WALError::Corruption`].
 fn read_entries(file: &mut File) -> Result<Vec<WALEntry>, WALError> {
+    read_entries_matching(file, |_| true)
+}
+
+/// [`read_entries`] keeping only the entries `keep` accepts, so a
+/// narrow read never holds the whole log in memory.
+fn read_entries_matching(
+    file: &mut File,
+    keep: impl Fn(&WALEntry) -> bool,
+) -> Result<Vec<WALEntry>, WALError> {
     file.seek(SeekFrom::Start(0)).map_err(WALError::IoError)?;
     let reader = BufReader::new(&*file);
 
     let mut entries = Vec::new();
+    let mut last_seq = 0;
     let mut pending_damage: Option<(u64, String)> = None;
 
     for line in reader.lines() {
@@ -176,11 +216,13 @@ fn read_entries(file: &mut File) -> Result<Vec<WALEntry>, WALError> {
         }
 
         match parse_line(&line) {
-            ParsedLine::Entry(entry) => entries.push(entry),
-            ParsedLine::Damaged(reason) => {
-                let last_seq = entries.last().map(|e: &WALEntry| e.sequence).unwrap_or(0);
-                pending_damage = Some((last_seq, reason));
+            ParsedLine::Entry(entry) => {
+                last_seq = entry.sequence;
+                if keep(&entry) {
+                    entries.push(entry);
+                }
             }
+            ParsedLine::Damaged(reason) => pending_damage = Some((last_seq, reason)),
         }
     }
 
@@ -215,6 +257,7 @@ impl WriteAheadLog {
             .write(true)
             .open(&file_path)
             .map_err(WALError::IoError)?;
+        let history_start = load_history_start(&file_path)?;
 
         let mut wal = Self {
             file_path,
@@ -224,6 +267,7 @@ impl WriteAheadLog {
             max_wal_size_mb: config.max_wal_size_mb,
             checkpoint_interval: config.checkpoint_interval,
             fsync: config.fsync,
+            history_start: Mutex::new(history_start),
         };
 
         // Initialize sequence number from existing WAL
@@ -264,7 +308,7 @@ impl WriteAheadLog {
 
         let entry = WALEntry {
             sequence,
-            timestamp: chrono::Utc::now(),
+            timestamp: Utc::now(),
             operation,
             collection_id: collection_id.to_string(),
             transaction_id: None,
@@ -374,6 +418,31 @@ impl WriteAheadLog {
         Ok(entries)
     }
 
+    /// Read the entries for any of `collection_ids` written between
+    /// `from` and `until`, both inclusive
+    pub async fn read_collection_window(
+        &self,
+        collection_ids: &[&str],
+        from: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<WALEntry>, WALError> {
+        let mut file = self.file.lock().await;
+        let entries = read_entries_matching(&mut file, |e| {
+            e.timestamp >= from
+                && e.timestamp <= until
+                && collection_ids.contains(&e.collection_id.as_str())
+        })?;
+
+        debug!(
+            "Read {} WAL entries for {:?} between {} and {}",
+            entries.len(),
+            collection_ids,
+            from.to_rfc3339(),
+            until.to_rfc3339()
+        );
+        Ok(entries)
+    }
+
     /// Create checkpoint (truncate WAL)
     pub async fn checkpoint(&self) -> Result<u64, WALError> {
         let current_sequence = self.sequence.load(Ordering::Relaxed);
@@ -419,10 +488,76 @@ impl WriteAheadLog {
 
         *self.file.lock().await = file;
 
+        // Everything before now is gone
+        let now = Utc::now();
+        *self.history_start.lock() = now;
+        write_history_start(&self.file_path, now)?;
+
         debug!("WAL truncated successfully");
         Ok(())
     }
 
+    /// Drop the entries written before `cutoff`, rewriting the file, and
+    /// move the history start up to `cutoff`. Returns the number of
+    /// entries dropped.
+    pub async fn prune_before(&self, cutoff: DateTime<Utc>) -> Result<usize, WALError> {
+        // Held throughout so no append lands in the file being replaced
+        let mut file = self.file.lock().await;
+        let entries = read_entries(&mut file)?;
+        let total = entries.len();
+        let kept: Vec<WALEntry> = entries
+            .into_iter()
+            .filter(|e| e.timestamp >= cutoff)
+            .collect();
+        let removed = total - kept.len();
+
+        if removed > 0 {
+            let temp_path = self.file_path.with_extension("wal.tmp");
+            let mut writer = BufWriter::new(File::create(&temp_path).map_err(WALError::IoError)?);
+            for entry in &kept {
+                let json = serde_json::to_string(entry).map_err(WALError::SerializationError)?;
+                writeln!(writer, "{}", encode_frame(&json)).map_err(WALError::IoError)?;
+            }
+            let temp = writer
+                .into_inner()
+                .map_err(|e| WALError::IoError(e.into_error()))?;
+            if self.fsync {
+                temp.sync_data().map_err(WALError::IoError)?;
+            }
+            drop(temp);
+            std::fs::rename(&temp_path, &self.file_path).map_err(WALError::IoError)?;
+
+            *file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .read(true)
+                .write(true)
+                .open(&self.file_path)
+                .map_err(WALError::IoError)?;
+        }
+
+        let mut history_start = self.history_start.lock();
+        if cutoff > *history_start {
+            *history_start = cutoff;
+            write_history_start(&self.file_path, cutoff)?;
+        }
+
+        info!(
+            "WAL pruned {} entries older than {}; {} remain",
+            removed,
+            cutoff.to_rfc3339(),
+            kept.len()
+        );
+        Ok(removed)
+    }
+
+    /// Since when the log holds every operation. Entries written before
+    /// it were checkpointed or pruned away; it is the time the log was
+    /// first opened when neither happened yet.
+    pub fn history_start(&self) -> DateTime<Utc> {
+        *self.history_start.lock()
+    }
+
     /// Get current sequence number
     pub fn current_sequence(&self) -> u64 {
         self.sequence.load(Ordering::Relaxed)
@@ -943,4 +1078,41 @@ mod tests {
         seqs.dedup();
         assert_eq!(seqs.len(), 40, "no duplicate sequences");
     }
+
+    #[tokio::test]
+    async fn test_wal_prune_before_keeps_newer_entries_and_history_start() {
+        let temp_dir = tempdir().unwrap();
+        let wal_path = temp_dir.path().join("prune.wal");
+        let opened_at = {
+            let wal = WriteAheadLog::new(&wal_path, WALConfig::default())
+                .await
+                .unwrap();
+            wal.append("c", insert_op("v1")).await.unwrap();
+            wal.history_start()
+        };
+
+        // The history start survives a reopen
+        let wal = WriteAheadLog::new(&wal_path, WALConfig::default())
+            .await
+            .unwrap();
+        assert_eq!(wal.history_start(), opened_at);
+
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        let cutoff = chrono::Utc::now();
+        wal.append("c", insert_op("v2")).await.unwrap();
+
+        assert_eq!(wal.prune_before(cutoff).await.unwrap(), 1);
+        assert_eq!(wal.history_start(), cutoff);
+        let entries = wal.read_from(0).await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].sequence, 1);
+
+        // Appends continue on the rewritten file
+        assert_eq!(wal.append("c", insert_op("v3")).await.unwrap(), 2);
+        assert_eq!(wal.read_from(0).await.unwrap().len(), 2);
+
+        // A checkpoint drops everything, so history starts over
+        wal.checkpoint().await.unwrap();
+        assert!(wal.history_start() > cutoff);
+    }
 }
@@ -0,0 +1,398 @@
+//! Write-path plugins.
+//!
+//! A [`WritePlugin`] sees every vector bound for an insert or update
+//! before it reaches the WAL and may rewrite it (payload enrichment, PII
+//! scrubbing), send it to another collection, or refuse it. Plugins run
+//! in a [`WritePluginChain`] installed with
+//! `VectorStore::set_write_plugins`; each one sees the output of the one
+//! before.
+//!
+//! Native plugins implement the trait in-process. Sandboxed plugins are
+//! WebAssembly modules listed under `plugins.write` in config.yml and
+//! loaded by [`wasm`] (behind the `wasm-plugins` feature), each call
+//! bounded by fuel and memory limits. Shared libraries are deliberately
+//! not loadable: a dylib runs with the server's privileges and no
+//! resource limits, which is what the WASM sandbox exists to avoid.
+//!
+//! Writes replayed from the WAL or applied by replication and Raft
+//! already went through the chain on the node that accepted them and are
+//! not hooked again.
+
+#[cfg(feature = "wasm-plugins")]
+pub mod wasm;
+
+use std::sync::Arc;
+
+use serde::Serialize;
+use tracing::warn;
+
+use crate::config::{PluginFailurePolicy, PluginsConfig};
+use crate::error::{Result, VectorizerError};
+use crate::models::Vector;
+
+/// The write a plugin is being asked about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WriteOp {
+    /// `VectorStore::insert`.
+    Insert,
+    /// `VectorStore::update`.
+    Update,
+}
+
+/// A plugin's verdict on one vector.
+#[derive(Debug, Clone)]
+pub enum WriteAction {
+    /// Write the vector as it is.
+    Keep,
+    /// Write this vector instead. It must keep the original id.
+    Replace(Vector),
+    /// Write to `collection` instead, optionally with a replacement
+    /// vector. Later plugins in the chain see the new collection.
+    Route {
+        /// Target collection.
+        collection: String,
+        /// Replacement vector, as in [`WriteAction::Replace`].
+        vector: Option<Vector>,
+    },
+    /// Refuse the write, surfaced to the caller as
+    /// [`VectorizerError::PluginRejected`].
+    Reject(String),
+}
+
+/// A hook run on every insert and update.
+pub trait WritePlugin: Send + Sync {
+    /// Name used in logs and rejection errors.
+    fn name(&self) -> &str;
+
+    /// Decide what to do with `vector`, bound for `collection`. An `Err`
+    /// is a plugin failure, handled by the chain entry's
+    /// [`PluginFailurePolicy`]; refusing the vector is
+    /// [`WriteAction::Reject`].
+    fn on_write(&self, op: WriteOp, collection: &str, vector: &Vector) -> Result<WriteAction>;
+}
+
+#[derive(Clone)]
+struct ChainEntry {
+    plugin: Arc<dyn WritePlugin>,
+    collections: Vec<String>,
+    on_error: PluginFailurePolicy,
+}
+
+impl ChainEntry {
+    fn applies_to(&self, collection: &str) -> bool {
+        self.collections.is_empty()
+            || self
+                .collections
+                .iter()
+                .any(|pattern| match pattern.strip_suffix('*') {
+                    Some(prefix) => collection.starts_with(prefix),
+                    None => pattern == collection,
+                })
+    }
+}
+
+/// Ordered write plugins, each scoped to a set of collections.
+#[derive(Clone, Default)]
+pub struct WritePluginChain {
+    entries: Vec<ChainEntry>,
+}
+
+impl std::fmt::Debug for WritePluginChain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.entries.iter().map(|e| e.plugin.name()))
+            .finish()
+    }
+}
+
+impl WritePluginChain {
+    /// An empty chain.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load the modules listed under `plugins.write`, in order.
+    pub fn from_config(config: &PluginsConfig) -> Result<Self> {
+        #[cfg(feature = "wasm-plugins")]
+        {
+            let mut chain = Self::new();
+            for plugin in &config.write {
+                chain.push(
+                    Arc::new(wasm::WasmWritePlugin::load(plugin)?),
+                    plugin.collections.clone(),
+                    plugin.on_error,
+                );
+            }
+            Ok(chain)
+        }
+        #[cfg(not(feature = "wasm-plugins"))]
+        match config.write.first() {
+            Some(plugin) => Err(VectorizerError::ConfigurationError(format!(
+                "plugin '{}' needs a build with the `wasm-plugins` feature",
+                plugin.name
+            ))),
+            None => Ok(Self::new()),
+        }
+    }
+
+    /// Append a plugin that runs on `collections` (entries ending in `*`
+    /// match by prefix; empty = every collection).
+    pub fn push(
+        &mut self,
+        plugin: Arc<dyn WritePlugin>,
+        collections: Vec<String>,
+        on_error: PluginFailurePolicy,
+    ) {
+        self.entries.push(ChainEntry {
+            plugin,
+            collections,
+            on_error,
+        });
+    }
+
+    /// Number of plugins in the chain.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the chain has no plugins.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Run `vector` through every plugin scoped to its collection and
+    /// return the collection and vector to actually write.
+    pub fn apply(&self, op: WriteOp, collection: &str, vector: Vector) -> Result<(String, Vector)> {
+        let mut collection = collection.to_string();
+        let mut vector = vector;
+        for entry in &self.entries {
+            if !entry.applies_to(&collection) {
+                continue;
+            }
+            let name = entry.plugin.name();
+            let action = entry
+                .plugin
+                .on_write(op, &collection, &vector)
+                .and_then(|action| check_id(name, &vector.id, action));
+            match action {
+                Ok(WriteAction::Keep) => {}
+                Ok(WriteAction::Replace(replacement)) => vector = replacement,
+                Ok(WriteAction::Route {
+                    collection: target,
+                    vector: replacement,
+                }) => {
+                    collection = target;
+                    if let Some(replacement) = replacement {
+                        vector = replacement;
+                    }
+                }
+                Ok(WriteAction::Reject(reason)) => {
+                    return Err(VectorizerError::PluginRejected {
+                        plugin: name.to_string(),
+                        reason,
+                    });
+                }
+                Err(e) => match entry.on_error {
+                    PluginFailurePolicy::Reject => {
+                        return Err(VectorizerError::Other(format!(
+                            "write plugin '{name}' failed: {e}"
+                        )));
+                    }
+                    PluginFailurePolicy::Skip => {
+                        warn!(
+                            "Write plugin '{}' failed on '{}' in '{}', skipping it: {}",
+                            name, vector.id, collection, e
+                        );
+                    }
+                },
+            }
+        }
+        Ok((collection, vector))
+    }
+}
+
+/// A plugin may rewrite a vector but not turn it into a different one.
+fn check_id(plugin: &str, id: &str, action: WriteAction) -> Result<WriteAction> {
+    let replacement = match &action {
+        WriteAction::Replace(v)
+        | WriteAction::Route {
+            vector: Some(v), ..
+        } => v,
+        _ => return Ok(action),
+    };
+    if replacement.id != id {
+        return Err(VectorizerError::Other(format!(
+            "plugin '{plugin}' changed vector id '{id}' to '{}'",
+            replacement.id
+        )));
+    }
+    Ok(action)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::db::VectorStore;
+    use crate::models::{CollectionConfig, Payload, QuantizationConfig};
+
+    /// Drops `email` from payloads, routes `tier: "archive"` vectors to
+    /// `archive` and refuses anything marked `blocked`.
+    struct Scrubber;
+
+    impl WritePlugin for Scrubber {
+        fn name(&self) -> &str {
+            "scrubber"
+        }
+
+        fn on_write(
+            &self,
+            _op: WriteOp,
+            _collection: &str,
+            vector: &Vector,
+        ) -> Result<WriteAction> {
+            let Some(payload) = &vector.payload else {
+                return Ok(WriteAction::Keep);
+            };
+            if payload.data.get("blocked").is_some() {
+                return Ok(WriteAction::Reject("blocked".to_string()));
+            }
+            let mut scrubbed = vector.clone();
+            if let Some(map) = scrubbed
+                .payload
+                .as_mut()
+                .and_then(|p| p.data.as_object_mut())
+            {
+                map.remove("email");
+            }
+            if payload.data["tier"] == json!("archive") {
+                return Ok(WriteAction::Route {
+                    collection: "archive".to_string(),
+                    vector: Some(scrubbed),
+                });
+            }
+            Ok(WriteAction::Replace(scrubbed))
+        }
+    }
+
+    struct Failing;
+
+    impl WritePlugin for Failing {
+        fn name(&self) -> &str {
+            "failing"
+        }
+
+        fn on_write(
+            &self,
+            _op: WriteOp,
+            _collection: &str,
+            _vector: &Vector,
+        ) -> Result<WriteAction> {
+            Err(VectorizerError::Other("boom".to_string()))
+        }
+    }
+
+    fn vector(id: &str, payload: serde_json::Value) -> Vector {
+        Vector::with_payload(id.to_string(), vec![1.0, 0.0, 0.0], Payload::new(payload))
+    }
+
+    fn store() -> VectorStore {
+        let store = VectorStore::new_cpu_only();
+        let config = CollectionConfig {
+            dimension: 3,
+            quantization: QuantizationConfig::None,
+            normalization: None,
+            ..Default::default()
+        };
+        store.create_collection("docs", config.clone()).unwrap();
+        store.create_collection("archive", config).unwrap();
+        store
+    }
+
+    #[test]
+    fn store_writes_go_through_the_chain() {
+        let store = store();
+        let mut chain = WritePluginChain::new();
+        chain.push(
+            Arc::new(Scrubber),
+            vec!["doc*".to_string()],
+            PluginFailurePolicy::Reject,
+        );
+        store.set_write_plugins(chain);
+
+        store
+            .insert(
+                "docs",
+                vec![
+                    vector("a", json!({"email": "x@y.z", "title": "a"})),
+                    vector("b", json!({"tier": "archive", "email": "x@y.z"})),
+                ],
+            )
+            .unwrap();
+
+        let a = store.get_vector("docs", "a").unwrap();
+        assert_eq!(a.payload.unwrap().data, json!({"title": "a"}));
+        assert!(store.get_vector("docs", "b").is_err());
+        let b = store.get_vector("archive", "b").unwrap();
+        assert_eq!(b.payload.unwrap().data, json!({"tier": "archive"}));
+
+        // A rejection refuses the whole batch before anything is written.
+        let err = store
+            .insert(
+                "docs",
+                vec![
+                    vector("c", json!({})),
+                    vector("d", json!({"blocked": true})),
+                ],
+            )
+            .unwrap_err();
+        assert!(
+            matches!(err, VectorizerError::PluginRejected { .. }),
+            "{err}"
+        );
+        assert!(store.get_vector("docs", "c").is_err());
+
+        let err = store
+            .update("docs", vector("a", json!({"blocked": true})))
+            .unwrap_err();
+        assert!(
+            matches!(err, VectorizerError::PluginRejected { .. }),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn failure_policy_decides_whether_a_broken_plugin_blocks_writes() {
+        let mut chain = WritePluginChain::new();
+        chain.push(Arc::new(Failing), Vec::new(), PluginFailurePolicy::Skip);
+        let (collection, written) = chain
+            .apply(WriteOp::Insert, "docs", vector("a", json!({"k": 1})))
+            .unwrap();
+        assert_eq!(collection, "docs");
+        assert_eq!(written.payload.unwrap().data, json!({"k": 1}));
+
+        chain.push(Arc::new(Failing), Vec::new(), PluginFailurePolicy::Reject);
+        assert!(
+            chain
+                .apply(WriteOp::Insert, "docs", vector("a", json!({})))
+                .is_err()
+        );
+
+        // Out-of-scope collections never reach the plugin.
+        let mut scoped = WritePluginChain::new();
+        scoped.push(
+            Arc::new(Failing),
+            vec!["other".to_string()],
+            PluginFailurePolicy::Reject,
+        );
+        assert!(
+            scoped
+                .apply(WriteOp::Update, "docs", vector("a", json!({})))
+                .is_ok()
+        );
+    }
+}
@@ -0,0 +1,316 @@
+//! Sandboxed write plugins compiled to WebAssembly.
+//!
+//! A module talks JSON over its linear memory and imports nothing — no
+//! WASI, so no filesystem, clock or network. It must export:
+//!
+//! - `memory`;
+//! - `alloc(len: i32) -> i32`, returning a buffer the host writes the
+//!   request into;
+//! - `on_write(ptr: i32, len: i32) -> i64`, returning the response's
+//!   location as `(ptr << 32) | len`.
+//!
+//! Request: `{"op": "insert", "collection": "docs", "vector": {"id":
+//! "...", "payload": {...}}}`, plus `"data": [...]` when the plugin is
+//! configured `with_vector`.
+//!
+//! Response, by `action`: `keep`; `write` with a new `payload` and/or
+//! `data`; `route` with a target `collection` and optionally the same
+//! replacements; `reject` with a `reason`.
+//!
+//! Every call runs in a fresh instance with its own fuel budget and
+//! memory cap, so plugins keep no state between writes and a runaway
+//! plugin traps instead of stalling the write path.
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use wasmtime::{Engine, InstancePre, Linker, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+use super::{WriteAction, WriteOp, WritePlugin};
+use crate::config::WritePluginConfig;
+use crate::error::{Result, VectorizerError};
+use crate::models::{Payload, Vector};
+
+#[derive(Serialize)]
+struct PluginRequest<'a> {
+    op: WriteOp,
+    collection: &'a str,
+    vector: VectorView<'a>,
+}
+
+#[derive(Serialize)]
+struct VectorView<'a> {
+    id: &'a str,
+    payload: Option<&'a Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<&'a [f32]>,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+enum PluginResponse {
+    Keep,
+    Write {
+        #[serde(default)]
+        payload: Option<Value>,
+        #[serde(default)]
+        data: Option<Vec<f32>>,
+    },
+    Route {
+        collection: String,
+        #[serde(default)]
+        payload: Option<Value>,
+        #[serde(default)]
+        data: Option<Vec<f32>>,
+    },
+    Reject {
+        reason: String,
+    },
+}
+
+struct StoreState {
+    limits: StoreLimits,
+}
+
+/// A write plugin loaded from a `.wasm` module.
+pub struct WasmWritePlugin {
+    name: String,
+    engine: Engine,
+    pre: InstancePre<StoreState>,
+    fuel: u64,
+    max_memory_bytes: usize,
+    with_vector: bool,
+}
+
+impl WasmWritePlugin {
+    /// Compile the module at `config.wasm`.
+    pub fn load(config: &WritePluginConfig) -> Result<Self> {
+        let wasm = std::fs::read(&config.wasm).map_err(|e| {
+            VectorizerError::ConfigurationError(format!(
+                "plugin '{}': cannot read {}: {e}",
+                config.name,
+                config.wasm.display()
+            ))
+        })?;
+        Self::from_bytes(config, &wasm)
+    }
+
+    /// Compile a module from its binary (or text) form. `config.wasm`
+    /// is ignored.
+    pub fn from_bytes(config: &WritePluginConfig, wasm: &[u8]) -> Result<Self> {
+        let invalid = |e: anyhow::Error| {
+            VectorizerError::ConfigurationError(format!("plugin '{}': {e:#}", config.name))
+        };
+        let mut engine_config = wasmtime::Config::new();
+        engine_config.consume_fuel(true);
+        let engine = Engine::new(&engine_config).map_err(invalid)?;
+        let module = Module::new(&engine, wasm).map_err(invalid)?;
+        for export in ["memory", "alloc", "on_write"] {
+            if module.get_export(export).is_none() {
+                return Err(invalid(anyhow::anyhow!(
+                    "module does not export `{export}`"
+                )));
+            }
+        }
+        // An empty linker: instantiation fails if the module imports
+        // anything.
+        let pre = Linker::new(&engine)
+            .instantiate_pre(&module)
+            .map_err(invalid)?;
+        Ok(Self {
+            name: config.name.clone(),
+            engine,
+            pre,
+            fuel: config.fuel,
+            max_memory_bytes: config.max_memory_mb as usize * 1024 * 1024,
+            with_vector: config.with_vector,
+        })
+    }
+
+    /// Run one request through a fresh, limited instance.
+    fn call(&self, request: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let limits = StoreLimitsBuilder::new()
+            .memory_size(self.max_memory_bytes)
+            .instances(1)
+            .build();
+        let mut store = Store::new(&self.engine, StoreState { limits });
+        store.limiter(|state| &mut state.limits);
+        store.set_fuel(self.fuel)?;
+
+        let instance = self.pre.instantiate(&mut store)?;
+        let memory = instance
+            .get_memory(&mut store, "memory")
+            .context("`memory` is not a memory")?;
+        let alloc = instance.get_typed_func::<i32, i32>(&mut store, "alloc")?;
+        let on_write = instance.get_typed_func::<(i32, i32), i64>(&mut store, "on_write")?;
+
+        let len = i32::try_from(request.len()).context("request too large")?;
+        let ptr = alloc.call(&mut store, len)?;
+        memory.write(&mut store, ptr as u32 as usize, request)?;
+        let packed = on_write.call(&mut store, (ptr, len))? as u64;
+
+        let (out_ptr, out_len) = ((packed >> 32) as usize, (packed & 0xffff_ffff) as usize);
+        anyhow::ensure!(
+            out_ptr.saturating_add(out_len) <= memory.data_size(&store),
+            "response out of bounds"
+        );
+        let mut response = vec![0; out_len];
+        memory.read(&store, out_ptr, &mut response)?;
+        Ok(response)
+    }
+
+    fn replacement(
+        &self,
+        vector: &Vector,
+        payload: Option<Value>,
+        data: Option<Vec<f32>>,
+    ) -> Result<Option<Vector>> {
+        if data.is_some() && !self.with_vector {
+            return Err(VectorizerError::Other(
+                "returned `data` but is not configured `with_vector`".to_string(),
+            ));
+        }
+        if payload.is_none() && data.is_none() {
+            return Ok(None);
+        }
+        let mut replacement = vector.clone();
+        if let Some(payload) = payload {
+            replacement.payload = Some(Payload::new(payload));
+        }
+        if let Some(data) = data {
+            replacement.data = data;
+        }
+        Ok(Some(replacement))
+    }
+}
+
+impl WritePlugin for WasmWritePlugin {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn on_write(&self, op: WriteOp, collection: &str, vector: &Vector) -> Result<WriteAction> {
+        let request = serde_json::to_vec(&PluginRequest {
+            op,
+            collection,
+            vector: VectorView {
+                id: &vector.id,
+                payload: vector.payload.as_ref().map(|p| &p.data),
+                data: self.with_vector.then_some(vector.data.as_slice()),
+            },
+        })?;
+        let response = self
+            .call(&request)
+            .map_err(|e| VectorizerError::Other(format!("{e:#}")))?;
+        let response: PluginResponse = serde_json::from_slice(&response)
+            .map_err(|e| VectorizerError::Other(format!("malformed response: {e}")))?;
+
+        Ok(match response {
+            PluginResponse::Keep => WriteAction::Keep,
+            PluginResponse::Write { payload, data } => self
+                .replacement(vector, payload, data)?
+                .map_or(WriteAction::Keep, WriteAction::Replace),
+            PluginResponse::Route {
+                collection,
+                payload,
+                data,
+            } => WriteAction::Route {
+                collection,
+                vector: self.replacement(vector, payload, data)?,
+            },
+            PluginResponse::Reject { reason } => WriteAction::Reject(reason),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use serde_json::json;
+
+    use super::*;
+
+    fn config() -> WritePluginConfig {
+        serde_json::from_value(json!({"name": "test", "wasm": "unused.wasm"})).unwrap()
+    }
+
+    /// A module whose `on_write` answers with `response`, stored at
+    /// offset 0.
+    fn fixed_response(response: &str) -> String {
+        format!(
+            r#"(module
+                (memory (export "memory") 1)
+                (data (i32.const 0) "{}")
+                (func (export "alloc") (param i32) (result i32) (i32.const 1024))
+                (func (export "on_write") (param i32 i32) (result i64) (i64.const {})))"#,
+            response.replace('"', "\\\""),
+            response.len()
+        )
+    }
+
+    fn vector() -> Vector {
+        Vector::with_payload(
+            "v1".to_string(),
+            vec![0.5, 0.5],
+            Payload::new(json!({"email": "x@y.z"})),
+        )
+    }
+
+    #[test]
+    fn responses_map_to_write_actions() {
+        let plugin = WasmWritePlugin::from_bytes(
+            &config(),
+            fixed_response(r#"{"action":"write","payload":{"clean":true}}"#).as_bytes(),
+        )
+        .unwrap();
+        match plugin.on_write(WriteOp::Insert, "docs", &vector()).unwrap() {
+            WriteAction::Replace(v) => {
+                assert_eq!(v.id, "v1");
+                assert_eq!(v.payload.unwrap().data, json!({"clean": true}));
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+
+        let plugin = WasmWritePlugin::from_bytes(
+            &config(),
+            fixed_response(r#"{"action":"reject","reason":"pii"}"#).as_bytes(),
+        )
+        .unwrap();
+        assert!(matches!(
+            plugin.on_write(WriteOp::Update, "docs", &vector()).unwrap(),
+            WriteAction::Reject(reason) if reason == "pii"
+        ));
+    }
+
+    #[test]
+    fn resource_limits_stop_runaway_plugins() {
+        let spin = r#"(module
+            (memory (export "memory") 1)
+            (func (export "alloc") (param i32) (result i32) (i32.const 0))
+            (func (export "on_write") (param i32 i32) (result i64)
+                (loop $forever (br $forever))
+                (i64.const 0)))"#;
+        let plugin = WasmWritePlugin::from_bytes(&config(), spin.as_bytes()).unwrap();
+        assert!(plugin.on_write(WriteOp::Insert, "docs", &vector()).is_err());
+
+        // 512 pages = 32 MiB, over the 16 MiB default cap.
+        let greedy = fixed_response(r#"{"action":"keep"}"#).replace(
+            "(memory (export \"memory\") 1)",
+            "(memory (export \"memory\") 512)",
+        );
+        let plugin = WasmWritePlugin::from_bytes(&config(), greedy.as_bytes()).unwrap();
+        assert!(plugin.on_write(WriteOp::Insert, "docs", &vector()).is_err());
+    }
+
+    #[test]
+    fn modules_with_imports_or_missing_exports_are_refused() {
+        let imports = r#"(module
+            (import "env" "now" (func))
+            (memory (export "memory") 1)
+            (func (export "alloc") (param i32) (result i32) (i32.const 0))
+            (func (export "on_write") (param i32 i32) (result i64) (i64.const 0)))"#;
+        assert!(WasmWritePlugin::from_bytes(&config(), imports.as_bytes()).is_err());
+        assert!(WasmWritePlugin::from_bytes(&config(), b"(module)").is_err());
+    }
+}
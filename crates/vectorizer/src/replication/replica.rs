@@ -22,6 +22,7 @@ use super::types::{
     ReplicationCommand, ReplicationError, ReplicationOperation, ReplicationResult,
     ReplicationStats, VectorOperation,
 };
+use crate::background_tasks::{self, TASKS};
 use crate::db::VectorStore;
 
 /// Replica Node - Read-only node that receives from master
@@ -94,14 +95,20 @@ impl ReplicaNode {
         let max_interval = Duration::from_secs(60); // Cap at 60 seconds
         let mut current_interval = base_interval;
         let mut consecutive_failures: u32 = 0;
+        let task = TASKS.register(background_tasks::REPLICATION_SYNC);
 
         loop {
+            // Pausing disconnects (see `connect_and_sync`); the next
+            // connection resumes from the saved offset.
+            task.resumed().await;
+
             // Re-resolve DNS on each reconnect attempt so we follow the
             // master to a new IP after a pod restart in Kubernetes.
             let master_addr = match self.config.resolve_master_address().await {
                 Some(addr) => addr,
                 None => {
                     error!("Failed to resolve master address, will retry...");
+                    task.schedule(current_interval);
                     sleep(current_interval).await;
                     current_interval = (current_interval * 2).min(max_interval);
                     consecutive_failures = consecutive_failures.saturating_add(1);
@@ -109,7 +116,10 @@ impl ReplicaNode {
                 }
             };
 
-            match self.connect_and_sync(master_addr).await {
+            task.start();
+            let result = self.connect_and_sync(master_addr).await;
+            task.finish(&result);
+            match result {
                 Ok(_) => {
                     info!("Disconnected from master, will reconnect...");
                     // Reset backoff after a successful connection.
@@ -140,6 +150,7 @@ impl ReplicaNode {
             }
 
             // Wait with exponential backoff (base * 2^min(failures-1, 4), capped at max_interval).
+            task.schedule(current_interval);
             sleep(current_interval).await;
             current_interval = (current_interval * 2).min(max_interval);
         }
@@ -183,6 +194,13 @@ impl ReplicaNode {
         // Process commands from master
         loop {
             let cmd = self.receive_command(&mut stream).await?;
+            if TASKS
+                .get(background_tasks::REPLICATION_SYNC)
+                .is_some_and(|task| task.is_paused())
+            {
+                info!("Replication sync paused, disconnecting from master");
+                return Ok(());
+            }
 
             match cmd {
                 ReplicationCommand::FullSync {
@@ -353,8 +371,21 @@ impl ReplicaNode {
                     sharding: None,
                     graph: None,
                     encryption: None,
+                    id_policy: None,
+                    quota: None,
+                    scoring: None,
+                    tenant_partition: None,
+                    dedup: None,
+                    chunking: None,
+                    full_text: None,
                 };
 
+                // In HA mode the Raft log may have created it already.
+                if self.vector_store.has_collection_in_memory(name) {
+                    debug!("Collection already exists: {}", name);
+                    return Ok(());
+                }
+
                 // In multi-tenant mode, we use create_collection_with_owner if owner_id is present
                 if let Some(owner) = owner_id {
                     if let Ok(uuid) = uuid::Uuid::parse_str(owner) {
@@ -377,10 +408,12 @@ impl ReplicaNode {
                 }
             }
             VectorOperation::DeleteCollection { name, owner_id: _ } => {
-                // owner_id is used for audit/logging, actual deletion uses collection name
-                self.vector_store
-                    .delete_collection(name)
-                    .map_err(|e| ReplicationError::InvalidOperation(e.to_string()))?;
+                // owner_id is used for audit/logging, actual deletion uses collection name.
+                // In HA mode the Raft log may have deleted it already.
+                match self.vector_store.delete_collection(name) {
+                    Ok(()) | Err(crate::error::VectorizerError::CollectionNotFound(_)) => {}
+                    Err(e) => return Err(ReplicationError::InvalidOperation(e.to_string())),
+                }
 
                 debug!("Deleted collection: {}", name);
             }
@@ -406,7 +439,7 @@ impl ReplicaNode {
                 };
 
                 self.vector_store
-                    .insert(collection, vec![vec])
+                    .apply_insert(collection, vec![vec])
                     .map_err(|e| ReplicationError::InvalidOperation(e.to_string()))?;
 
                 debug!("Inserted vector {} in collection {}", id, collection);
@@ -434,7 +467,7 @@ impl ReplicaNode {
                     };
 
                     self.vector_store
-                        .insert(collection, vec![vec])
+                        .apply_insert(collection, vec![vec])
                         .map_err(|e| ReplicationError::InvalidOperation(e.to_string()))?;
                 }
 
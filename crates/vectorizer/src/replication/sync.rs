@@ -148,6 +148,13 @@ pub async fn apply_snapshot(store: &VectorStore, snapshot: &[u8]) -> Result<u64,
             sharding: None,
             graph: None,
             encryption: None,
+            id_policy: None,
+            quota: None,
+            scoring: None,
+            tenant_partition: None,
+            dedup: None,
+            chunking: None,
+            full_text: None,
         };
 
         // Create or recreate collection
@@ -176,7 +183,7 @@ pub async fn apply_snapshot(store: &VectorStore, snapshot: &[u8]) -> Result<u64,
             .collect();
 
         // Insert vectors and verify
-        if let Err(e) = store.insert(&collection.name, vectors) {
+        if let Err(e) = store.apply_insert(&collection.name, vectors) {
             return Err(format!(
                 "Failed to insert vectors into collection {}: {}",
                 collection.name, e
@@ -255,6 +262,13 @@ mod tests {
             sharding: None,
             graph: None,
             encryption: None,
+            id_policy: None,
+            quota: None,
+            scoring: None,
+            tenant_partition: None,
+            dedup: None,
+            chunking: None,
+            full_text: None,
         };
         store.create_collection("test", config).unwrap();
 
@@ -300,6 +314,13 @@ mod tests {
             sharding: None,
             graph: None,
             encryption: None,
+            id_policy: None,
+            quota: None,
+            scoring: None,
+            tenant_partition: None,
+            dedup: None,
+            chunking: None,
+            full_text: None,
         };
         store1.create_collection("payload_test", config).unwrap();
 
@@ -368,6 +389,13 @@ mod tests {
             sharding: None,
             graph: None,
             encryption: None,
+            id_policy: None,
+            quota: None,
+            scoring: None,
+            tenant_partition: None,
+            dedup: None,
+            chunking: None,
+            full_text: None,
         };
         store1
             .create_collection("euclidean", config_euclidean)
@@ -386,6 +414,13 @@ mod tests {
             sharding: None,
             graph: None,
             encryption: None,
+            id_policy: None,
+            quota: None,
+            scoring: None,
+            tenant_partition: None,
+            dedup: None,
+            chunking: None,
+            full_text: None,
         };
         store1.create_collection("dotproduct", config_dot).unwrap();
 
@@ -455,6 +490,13 @@ mod tests {
             sharding: None,
             graph: None,
             encryption: None,
+            id_policy: None,
+            quota: None,
+            scoring: None,
+            tenant_partition: None,
+            dedup: None,
+            chunking: None,
+            full_text: None,
         };
         store.create_collection("meta_test", config).unwrap();
 
@@ -0,0 +1,168 @@
+//! Aggregate vectors: one vector standing for a set of stored vectors,
+//! e.g. everything tagged with a project, to search with in their place.
+//!
+//! - **Mean**: the component-wise mean, the set's centroid. In a cosine
+//!   collection the members are normalised first and the mean after, so
+//!   every member weighs the same whatever its norm.
+//! - **Medoid**: the member with the smallest total distance to the
+//!   others, an actual stored vector. Beyond [`MEDOID_CANDIDATES`]
+//!   members it is the medoid of an evenly spaced subset of them, so the
+//!   pass never computes more than `MEDOID_CANDIDATES²` distances.
+//!
+//! Distances are Euclidean, on normalised vectors in a cosine collection.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, VectorizerError};
+use crate::models::{DistanceMetric, Vector};
+use crate::simd;
+
+/// Most members tried as the medoid, and scored against.
+pub const MEDOID_CANDIDATES: usize = 1_000;
+
+/// How a set of vectors is reduced to one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AggregateMethod {
+    /// Component-wise mean.
+    #[default]
+    Mean,
+    /// The most central member.
+    Medoid,
+}
+
+/// Result of [`aggregate`].
+#[derive(Debug, Clone, Serialize)]
+pub struct Aggregate {
+    /// Method that produced the vector.
+    pub method: AggregateMethod,
+    /// The aggregate vector.
+    pub vector: Vec<f32>,
+    /// Vectors aggregated.
+    pub members: usize,
+    /// ID of the medoid, for [`AggregateMethod::Medoid`].
+    pub medoid: Option<String>,
+}
+
+/// Reduce `vectors` to one vector with `method`.
+///
+/// # Errors
+///
+/// [`VectorizerError::InvalidConfiguration`] when `vectors` is empty.
+pub fn aggregate(
+    vectors: &[Vector],
+    metric: DistanceMetric,
+    method: AggregateMethod,
+) -> Result<Aggregate> {
+    if vectors.is_empty() {
+        return Err(VectorizerError::InvalidConfiguration {
+            message: "no vectors to aggregate".to_string(),
+        });
+    }
+    let row = |v: &Vector| {
+        let mut data = v.data.clone();
+        if metric == DistanceMetric::Cosine {
+            simd::normalize_in_place(&mut data);
+        }
+        data
+    };
+
+    match method {
+        AggregateMethod::Mean => {
+            let mut mean = vec![0.0f32; vectors[0].data.len()];
+            for v in vectors {
+                simd::add_assign(&mut mean, &row(v));
+            }
+            simd::scale(&mut mean, 1.0 / vectors.len() as f32);
+            if metric == DistanceMetric::Cosine {
+                simd::normalize_in_place(&mut mean);
+            }
+            Ok(Aggregate {
+                method,
+                vector: mean,
+                members: vectors.len(),
+                medoid: None,
+            })
+        }
+        AggregateMethod::Medoid => {
+            let step = vectors.len().div_ceil(MEDOID_CANDIDATES);
+            let sample: Vec<(usize, Vec<f32>)> = (0..vectors.len())
+                .step_by(step)
+                .map(|i| (i, row(&vectors[i])))
+                .collect();
+            let best = sample
+                .iter()
+                .map(|(i, candidate)| {
+                    let total: f64 = sample
+                        .iter()
+                        .map(|(_, other)| f64::from(simd::euclidean_distance(candidate, other)))
+                        .sum();
+                    (*i, total)
+                })
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .map_or(0, |(i, _)| i);
+            Ok(Aggregate {
+                method,
+                vector: vectors[best].data.clone(),
+                members: vectors.len(),
+                medoid: Some(vectors[best].id.clone()),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn v(id: &str, data: &[f32]) -> Vector {
+        Vector::new(id.to_string(), data.to_vec())
+    }
+
+    #[test]
+    fn test_mean_and_medoid() {
+        let vectors = [
+            v("a", &[0.0, 0.0]),
+            v("b", &[1.0, 0.0]),
+            v("c", &[2.0, 0.0]),
+            v("d", &[9.0, 1.0]),
+            v("e", &[13.0, 4.0]),
+        ];
+
+        let mean = aggregate(&vectors, DistanceMetric::Euclidean, AggregateMethod::Mean).unwrap();
+        assert_eq!(mean.vector, vec![5.0, 1.0]);
+        assert_eq!(mean.members, 5);
+        assert_eq!(mean.medoid, None);
+
+        let medoid =
+            aggregate(&vectors, DistanceMetric::Euclidean, AggregateMethod::Medoid).unwrap();
+        assert_eq!(medoid.medoid.as_deref(), Some("c"));
+        assert_eq!(medoid.vector, vec![2.0, 0.0]);
+    }
+
+    #[test]
+    fn test_medoid_of_a_large_set_is_a_central_member() {
+        let vectors: Vec<Vector> = (0..3 * MEDOID_CANDIDATES)
+            .map(|i| v(&i.to_string(), &[i as f32, 0.0]))
+            .collect();
+
+        let medoid =
+            aggregate(&vectors, DistanceMetric::Euclidean, AggregateMethod::Medoid).unwrap();
+        let id: usize = medoid.medoid.unwrap().parse().unwrap();
+        assert!(id % 3 == 0, "{id} is not in the sampled subset");
+        assert!(id.abs_diff(vectors.len() / 2) <= 3, "{id} is not central");
+        assert_eq!(medoid.members, vectors.len());
+    }
+
+    #[test]
+    fn test_cosine_mean_weighs_members_equally() {
+        let vectors = [v("a", &[10.0, 0.0]), v("b", &[0.0, 1.0])];
+
+        let mean = aggregate(&vectors, DistanceMetric::Cosine, AggregateMethod::Mean).unwrap();
+        let half = std::f32::consts::FRAC_1_SQRT_2;
+        assert!((mean.vector[0] - half).abs() < 1e-6);
+        assert!((mean.vector[1] - half).abs() < 1e-6);
+        assert!(aggregate(&[], DistanceMetric::Cosine, AggregateMethod::Mean).is_err());
+    }
+}
@@ -0,0 +1,621 @@
+//! Clustering of a collection's vectors, for topic discovery.
+//!
+//! - **Mini-batch k-means**, always available: k-means++ seeding on a
+//!   sample, then [`KMeansOptions::iterations`] updates from random
+//!   batches with per-centroid learning rates (Sculley, 2010), then one
+//!   full pass that assigns every vector to its nearest centroid.
+//!   Clusters left empty by that pass are dropped.
+//! - **HDBSCAN**, behind the `hdbscan` feature: core distances, the
+//!   minimum spanning tree of the mutual reachability graph, the
+//!   condensed cluster tree and excess-of-mass selection. It finds the
+//!   number of clusters itself and leaves outliers as noise, but its
+//!   `O(n²)` distance passes limit it to [`MAX_HDBSCAN_VECTORS`].
+//!
+//! Both work on Euclidean distance. Cosine collections are normalised on
+//! a copy first, which makes that distance a monotone function of cosine
+//! similarity; dot-product collections are clustered on the raw vectors.
+//! Runs are reported on a [`CLUSTERING`] job and are repeatable for a
+//! given seed.
+//!
+//! [`CLUSTERING`]: crate::jobs::CLUSTERING
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, VectorizerError};
+use crate::jobs::Job;
+use crate::models::{DistanceMetric, Vector};
+use crate::simd;
+
+/// Most clusters k-means may be asked for.
+pub const MAX_CLUSTERS: usize = 1_000;
+
+/// Mini-batch updates when the caller does not say.
+pub const DEFAULT_KMEANS_ITERATIONS: usize = 100;
+
+/// Vectors per mini-batch when the caller does not say.
+pub const DEFAULT_KMEANS_BATCH_SIZE: usize = 1_024;
+
+/// Most vectors HDBSCAN accepts; larger collections take k-means.
+pub const MAX_HDBSCAN_VECTORS: usize = 5_000;
+
+/// Smallest group HDBSCAN calls a cluster when the caller does not say.
+pub const DEFAULT_MIN_CLUSTER_SIZE: usize = 5;
+
+/// Vectors k-means++ seeds from, at least this many per cluster.
+const SEED_SAMPLE_PER_CLUSTER: usize = 20;
+/// ... and at least this many in total.
+const MIN_SEED_SAMPLE: usize = 1_000;
+
+/// How vectors are grouped.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ClusterAlgorithm {
+    /// Mini-batch k-means with a fixed number of clusters.
+    #[default]
+    KMeans,
+    /// Density-based clustering that finds the number of clusters and
+    /// leaves outliers as noise; needs the `hdbscan` feature.
+    Hdbscan,
+}
+
+impl ClusterAlgorithm {
+    /// Whether this build can run the algorithm.
+    pub fn is_available(self) -> bool {
+        match self {
+            Self::KMeans => true,
+            Self::Hdbscan => cfg!(feature = "hdbscan"),
+        }
+    }
+}
+
+/// Parameters of a mini-batch k-means run.
+#[derive(Debug, Clone, Copy)]
+pub struct KMeansOptions {
+    /// Clusters to find; more than the vectors available is cut down.
+    pub k: usize,
+    /// Mini-batch updates.
+    pub iterations: usize,
+    /// Vectors per mini-batch.
+    pub batch_size: usize,
+    /// Seed of the sampling.
+    pub seed: u64,
+}
+
+/// One cluster of a [`Clustering`].
+#[derive(Debug, Clone, Serialize)]
+pub struct ClusterSummary {
+    /// Cluster id, as written into payloads; ids run from 0 and the
+    /// largest clusters come first.
+    pub id: usize,
+    /// Vectors in the cluster.
+    pub size: usize,
+    /// Mean of the member vectors (normalised first in a cosine
+    /// collection).
+    pub centroid: Vec<f32>,
+    /// Member closest to the centroid.
+    pub representative: String,
+    /// Mean Euclidean distance from the members to the centroid.
+    pub mean_distance: f32,
+}
+
+/// Result of [`kmeans`] or [`hdbscan`].
+#[derive(Debug, Clone, Serialize)]
+pub struct Clustering {
+    /// Algorithm that produced the clusters.
+    pub algorithm: ClusterAlgorithm,
+    /// Cluster id of each input vector, in input order; `None` is noise.
+    pub labels: Vec<Option<usize>>,
+    /// The clusters, largest first.
+    pub clusters: Vec<ClusterSummary>,
+    /// Sum of squared distances from the clustered vectors to their
+    /// centroids.
+    pub inertia: f64,
+}
+
+/// Cluster `vectors` with mini-batch k-means.
+///
+/// # Errors
+///
+/// [`VectorizerError::InvalidConfiguration`] for a `k` of 0 or above
+/// [`MAX_CLUSTERS`], or
+/// [`VectorizerError::JobCancelled`] once the job was cancelled.
+pub fn kmeans(
+    vectors: &[Vector],
+    metric: DistanceMetric,
+    options: KMeansOptions,
+    job: &Job,
+) -> Result<Clustering> {
+    if options.k == 0 || options.k > MAX_CLUSTERS {
+        return Err(VectorizerError::InvalidConfiguration {
+            message: format!("k must be between 1 and {}", MAX_CLUSTERS),
+        });
+    }
+    let rows = prepare(vectors, metric);
+    let n = rows.len();
+    let k = options.k.min(n);
+    job.set_total(options.iterations as u64 + 1);
+    if k == 0 {
+        job.advance(options.iterations as u64 + 1);
+        return Ok(summarize(
+            ClusterAlgorithm::KMeans,
+            vectors,
+            &rows,
+            Vec::new(),
+        ));
+    }
+
+    let mut rng = fastrand::Rng::with_seed(options.seed);
+    let mut centroids = seed_centroids(&rows, k, &mut rng);
+    let mut counts = vec![0u64; k];
+    let batch_size = options.batch_size.clamp(1, n);
+    for _ in 0..options.iterations {
+        job.check_cancelled()?;
+        let batch: Vec<usize> = (0..batch_size).map(|_| rng.usize(..n)).collect();
+        let nearest: Vec<usize> = batch
+            .iter()
+            .map(|&i| nearest_centroid(&rows[i], &centroids).0)
+            .collect();
+        for (&i, &c) in batch.iter().zip(&nearest) {
+            counts[c] += 1;
+            let eta = 1.0 / counts[c] as f32;
+            for (m, x) in centroids[c].iter_mut().zip(&rows[i]) {
+                *m += eta * (x - *m);
+            }
+        }
+        job.advance(1);
+    }
+
+    job.check_cancelled()?;
+    let labels: Vec<Option<usize>> = rows
+        .iter()
+        .map(|row| Some(nearest_centroid(row, &centroids).0))
+        .collect();
+    job.advance(1);
+    Ok(summarize(ClusterAlgorithm::KMeans, vectors, &rows, labels))
+}
+
+/// Copies of the vectors' data, normalised in a cosine collection.
+fn prepare(vectors: &[Vector], metric: DistanceMetric) -> Vec<Vec<f32>> {
+    vectors
+        .iter()
+        .map(|v| {
+            let mut data = v.data.clone();
+            if metric == DistanceMetric::Cosine {
+                simd::normalize_in_place(&mut data);
+            }
+            data
+        })
+        .collect()
+}
+
+/// k-means++ seeding on a random sample of `rows`.
+fn seed_centroids(rows: &[Vec<f32>], k: usize, rng: &mut fastrand::Rng) -> Vec<Vec<f32>> {
+    let mut sample: Vec<usize> = (0..rows.len()).collect();
+    rng.shuffle(&mut sample);
+    sample.truncate((k * SEED_SAMPLE_PER_CLUSTER).max(MIN_SEED_SAMPLE));
+
+    let mut centroids = vec![rows[sample[0]].clone()];
+    let mut closest: Vec<f32> = sample
+        .iter()
+        .map(|&i| simd::euclidean_distance_squared(&rows[i], &centroids[0]))
+        .collect();
+    while centroids.len() < k {
+        let total: f32 = closest.iter().sum();
+        let pick = if total > 0.0 {
+            // Next seed with probability proportional to squared distance
+            let mut target = rng.f32() * total;
+            closest
+                .iter()
+                .position(|&d| {
+                    target -= d;
+                    target <= 0.0
+                })
+                .unwrap_or(closest.len() - 1)
+        } else {
+            // Every sampled vector sits on a centroid already
+            rng.usize(..sample.len())
+        };
+        let centroid = rows[sample[pick]].clone();
+        for (d, &i) in closest.iter_mut().zip(&sample) {
+            *d = d.min(simd::euclidean_distance_squared(&rows[i], &centroid));
+        }
+        centroids.push(centroid);
+    }
+    centroids
+}
+
+/// Index of the centroid nearest to `row`, and its squared distance.
+fn nearest_centroid(row: &[f32], centroids: &[Vec<f32>]) -> (usize, f32) {
+    centroids
+        .iter()
+        .enumerate()
+        .map(|(c, centroid)| (c, simd::euclidean_distance_squared(row, centroid)))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap_or((0, 0.0))
+}
+
+/// Turn raw labels into a [`Clustering`]: centroids are recomputed from
+/// the members, empty clusters dropped and ids renumbered so the largest
+/// cluster is 0.
+fn summarize(
+    algorithm: ClusterAlgorithm,
+    vectors: &[Vector],
+    rows: &[Vec<f32>],
+    labels: Vec<Option<usize>>,
+) -> Clustering {
+    let raw_clusters = labels.iter().flatten().max().map_or(0, |&c| c + 1);
+    let dimension = rows.first().map_or(0, Vec::len);
+    let mut sums = vec![vec![0.0f32; dimension]; raw_clusters];
+    let mut sizes = vec![0usize; raw_clusters];
+    for (row, label) in rows.iter().zip(&labels) {
+        if let Some(c) = *label {
+            simd::add_assign(&mut sums[c], row);
+            sizes[c] += 1;
+        }
+    }
+
+    let mut order: Vec<usize> = (0..raw_clusters).filter(|&c| sizes[c] > 0).collect();
+    order.sort_by(|&a, &b| sizes[b].cmp(&sizes[a]).then(a.cmp(&b)));
+    let mut renumber = vec![None; raw_clusters];
+    for (id, &c) in order.iter().enumerate() {
+        renumber[c] = Some(id);
+    }
+    let labels: Vec<Option<usize>> = labels
+        .into_iter()
+        .map(|label| label.and_then(|c| renumber[c]))
+        .collect();
+
+    let centroids: Vec<Vec<f32>> = order
+        .iter()
+        .map(|&c| {
+            let mut centroid = std::mem::take(&mut sums[c]);
+            simd::scale(&mut centroid, 1.0 / sizes[c] as f32);
+            centroid
+        })
+        .collect();
+    let mut inertia = 0.0f64;
+    let mut distance_sums = vec![0.0f64; centroids.len()];
+    let mut representatives: Vec<(usize, f32)> = vec![(0, f32::INFINITY); centroids.len()];
+    for (i, (row, label)) in rows.iter().zip(&labels).enumerate() {
+        if let Some(c) = *label {
+            let squared = simd::euclidean_distance_squared(row, &centroids[c]);
+            inertia += f64::from(squared);
+            distance_sums[c] += f64::from(squared.sqrt());
+            if squared < representatives[c].1 {
+                representatives[c] = (i, squared);
+            }
+        }
+    }
+
+    let clusters = centroids
+        .into_iter()
+        .enumerate()
+        .map(|(id, centroid)| {
+            let size = sizes[order[id]];
+            ClusterSummary {
+                id,
+                size,
+                centroid,
+                representative: vectors[representatives[id].0].id.clone(),
+                mean_distance: (distance_sums[id] / size as f64) as f32,
+            }
+        })
+        .collect();
+    Clustering {
+        algorithm,
+        labels,
+        clusters,
+        inertia,
+    }
+}
+
+/// Cluster `vectors` with HDBSCAN; clusters have at least
+/// `min_cluster_size` members and vectors in none are noise.
+///
+/// # Errors
+///
+/// [`VectorizerError::InvalidConfiguration`] for more than
+/// [`MAX_HDBSCAN_VECTORS`] vectors or a `min_cluster_size` below 2, or
+/// [`VectorizerError::JobCancelled`] once the job was cancelled.
+#[cfg(feature = "hdbscan")]
+pub fn hdbscan(
+    vectors: &[Vector],
+    metric: DistanceMetric,
+    min_cluster_size: usize,
+    job: &Job,
+) -> Result<Clustering> {
+    use rayon::prelude::*;
+
+    if vectors.len() > MAX_HDBSCAN_VECTORS {
+        return Err(VectorizerError::InvalidConfiguration {
+            message: format!(
+                "hdbscan clusters at most {} vectors, the collection has {}; use kmeans",
+                MAX_HDBSCAN_VECTORS,
+                vectors.len()
+            ),
+        });
+    }
+    if min_cluster_size < 2 {
+        return Err(VectorizerError::InvalidConfiguration {
+            message: "min_cluster_size must be at least 2".to_string(),
+        });
+    }
+    let rows = prepare(vectors, metric);
+    let n = rows.len();
+    job.set_total(3);
+    if n < min_cluster_size {
+        job.advance(3);
+        return Ok(summarize(
+            ClusterAlgorithm::Hdbscan,
+            vectors,
+            &rows,
+            vec![None; n],
+        ));
+    }
+
+    // Core distance: distance to the `min_cluster_size`-th nearest
+    // neighbour, the point itself included
+    let core: Vec<f32> = (0..n)
+        .into_par_iter()
+        .map(|i| {
+            let mut distances: Vec<f32> = rows
+                .iter()
+                .map(|other| simd::euclidean_distance(&rows[i], other))
+                .collect();
+            let nth = min_cluster_size - 1;
+            *distances.select_nth_unstable_by(nth, f32::total_cmp).1
+        })
+        .collect();
+    job.advance(1);
+    job.check_cancelled()?;
+
+    // Prim's minimum spanning tree of the mutual reachability graph
+    let mut in_tree = vec![false; n];
+    let mut best = vec![(f32::INFINITY, 0usize); n];
+    let mut edges: Vec<(usize, usize, f32)> = Vec::with_capacity(n - 1);
+    let mut current = 0;
+    in_tree[0] = true;
+    for _ in 1..n {
+        for j in 0..n {
+            if in_tree[j] {
+                continue;
+            }
+            let reach = simd::euclidean_distance(&rows[current], &rows[j])
+                .max(core[current])
+                .max(core[j]);
+            if reach < best[j].0 {
+                best[j] = (reach, current);
+            }
+        }
+        let next = (0..n)
+            .filter(|&j| !in_tree[j])
+            .min_by(|&a, &b| best[a].0.total_cmp(&best[b].0))
+            .unwrap_or(0);
+        in_tree[next] = true;
+        edges.push((best[next].1, next, best[next].0));
+        current = next;
+    }
+    job.advance(1);
+    job.check_cancelled()?;
+
+    let labels = condense_and_select(n, edges, min_cluster_size);
+    job.advance(1);
+    Ok(summarize(ClusterAlgorithm::Hdbscan, vectors, &rows, labels))
+}
+
+/// A cluster of the condensed tree.
+#[cfg(feature = "hdbscan")]
+struct CondensedCluster {
+    parent: usize,
+    birth: f32,
+    stability: f32,
+}
+
+/// Labels from the minimum spanning tree `edges` over `n` points: the
+/// single-linkage hierarchy, condensed to splits that leave both sides
+/// with `min_cluster_size` points, and the most stable non-overlapping
+/// clusters of it (excess of mass). The root is never a cluster.
+#[cfg(feature = "hdbscan")]
+fn condense_and_select(
+    n: usize,
+    mut edges: Vec<(usize, usize, f32)>,
+    min_cluster_size: usize,
+) -> Vec<Option<usize>> {
+    // Single-linkage hierarchy: node `n + t` joins two nodes at merge `t`
+    edges.sort_by(|a, b| a.2.total_cmp(&b.2));
+    let mut set_parent: Vec<usize> = (0..2 * n - 1).collect();
+    let mut children: Vec<(usize, usize, f32)> = Vec::with_capacity(n - 1);
+    let mut size = vec![1usize; 2 * n - 1];
+    let find = |set_parent: &mut Vec<usize>, mut i: usize| {
+        while set_parent[i] != i {
+            set_parent[i] = set_parent[set_parent[i]];
+            i = set_parent[i];
+        }
+        i
+    };
+    for (a, b, distance) in edges {
+        let (ra, rb) = (find(&mut set_parent, a), find(&mut set_parent, b));
+        let node = n + children.len();
+        set_parent[ra] = node;
+        set_parent[rb] = node;
+        size[node] = size[ra] + size[rb];
+        children.push((ra, rb, distance));
+    }
+    let leaves_under = |node: usize| {
+        let mut leaves = Vec::new();
+        let mut stack = vec![node];
+        while let Some(node) = stack.pop() {
+            if node < n {
+                leaves.push(node);
+            } else {
+                let (l, r, _) = children[node - n];
+                stack.push(l);
+                stack.push(r);
+            }
+        }
+        leaves
+    };
+
+    // Condense: walk down from the root, following each cluster until it
+    // splits into two big enough halves
+    let mut clusters = vec![CondensedCluster {
+        parent: 0,
+        birth: 0.0,
+        stability: 0.0,
+    }];
+    let mut fell_from = vec![0usize; n];
+    let mut stack = vec![(2 * n - 2, 0usize)];
+    while let Some((node, cluster)) = stack.pop() {
+        let (left, right, distance) = children[node - n];
+        let lambda = 1.0 / distance.max(f32::EPSILON);
+        let gain = lambda - clusters[cluster].birth;
+        let (big_left, big_right) = (
+            size[left] >= min_cluster_size,
+            size[right] >= min_cluster_size,
+        );
+        if big_left && big_right {
+            clusters[cluster].stability += (size[left] + size[right]) as f32 * gain;
+            for child in [left, right] {
+                clusters.push(CondensedCluster {
+                    parent: cluster,
+                    birth: lambda,
+                    stability: 0.0,
+                });
+                stack.push((child, clusters.len() - 1));
+            }
+            continue;
+        }
+        for (child, big) in [(left, big_left), (right, big_right)] {
+            if big {
+                stack.push((child, cluster));
+            } else {
+                for leaf in leaves_under(child) {
+                    fell_from[leaf] = cluster;
+                    clusters[cluster].stability += gain;
+                }
+            }
+        }
+    }
+
+    // Excess of mass: keep a cluster when it is at least as stable as
+    // its selected descendants together; children come after parents
+    let count = clusters.len();
+    let mut selected = vec![false; count];
+    let mut subtree = vec![0.0f32; count];
+    for c in (1..count).rev() {
+        let own = clusters[c].stability;
+        if own >= subtree[c] {
+            selected[c] = true;
+            subtree[c] = own;
+        }
+        let parent = clusters[c].parent;
+        subtree[parent] += subtree[c];
+    }
+    let mut covered = vec![false; count];
+    for c in 1..count {
+        let parent = clusters[c].parent;
+        covered[c] = covered[parent] || (parent != 0 && selected[parent]);
+        if covered[c] {
+            selected[c] = false;
+        }
+    }
+
+    fell_from
+        .into_iter()
+        .map(|mut c| {
+            while c != 0 {
+                if selected[c] {
+                    return Some(c);
+                }
+                c = clusters[c].parent;
+            }
+            None
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::jobs::{CLUSTERING, JobRegistry};
+
+    /// `per_blob` points around each centre, jittered by up to ±0.5.
+    fn blobs(centres: &[[f32; 2]], per_blob: usize) -> Vec<Vector> {
+        let mut rng = fastrand::Rng::with_seed(11);
+        centres
+            .iter()
+            .flat_map(|centre| {
+                (0..per_blob)
+                    .map(|_| vec![centre[0] + rng.f32() - 0.5, centre[1] + rng.f32() - 0.5])
+                    .collect::<Vec<_>>()
+            })
+            .enumerate()
+            .map(|(i, data)| Vector::new(format!("v{i}"), data))
+            .collect()
+    }
+
+    fn options(k: usize) -> KMeansOptions {
+        KMeansOptions {
+            k,
+            iterations: 50,
+            batch_size: 32,
+            seed: 3,
+        }
+    }
+
+    #[test]
+    fn test_kmeans_finds_separated_blobs() {
+        let vectors = blobs(&[[0.0, 0.0], [20.0, 0.0], [0.0, 20.0]], 30);
+        let registry = JobRegistry::default();
+        let job = registry.start(CLUSTERING, "docs");
+
+        let clustering = kmeans(&vectors, DistanceMetric::Euclidean, options(3), &job).unwrap();
+
+        assert_eq!(clustering.clusters.len(), 3);
+        for blob in vectors.chunks(30).zip(clustering.labels.chunks(30)) {
+            let first = blob.1[0];
+            assert!(first.is_some());
+            assert!(blob.1.iter().all(|label| *label == first));
+        }
+        for cluster in &clustering.clusters {
+            assert_eq!(cluster.size, 30);
+            assert!(cluster.mean_distance < 1.0);
+        }
+        assert_eq!(registry.get(&job.id()).unwrap().processed, 51);
+
+        let again = kmeans(&vectors, DistanceMetric::Euclidean, options(3), &job).unwrap();
+        assert_eq!(again.labels, clustering.labels);
+    }
+
+    #[test]
+    fn test_kmeans_caps_k_and_rejects_zero() {
+        let vectors = blobs(&[[0.0, 0.0]], 2);
+        let registry = JobRegistry::default();
+        let job = registry.start(CLUSTERING, "docs");
+
+        let clustering = kmeans(&vectors, DistanceMetric::Cosine, options(5), &job).unwrap();
+        assert!(clustering.clusters.len() <= 2);
+        assert!(clustering.labels.iter().all(Option::is_some));
+        assert!(kmeans(&vectors, DistanceMetric::Cosine, options(0), &job).is_err());
+    }
+
+    #[cfg(feature = "hdbscan")]
+    #[test]
+    fn test_hdbscan_finds_blobs_and_noise() {
+        let mut vectors = blobs(&[[0.0, 0.0], [30.0, 30.0]], 25);
+        vectors.push(Vector::new("outlier".to_string(), vec![-40.0, 60.0]));
+        let registry = JobRegistry::default();
+        let job = registry.start(CLUSTERING, "docs");
+
+        let clustering = hdbscan(&vectors, DistanceMetric::Euclidean, 5, &job).unwrap();
+
+        assert_eq!(clustering.clusters.len(), 2);
+        assert_eq!(clustering.labels[50], None);
+        let first = clustering.labels[0];
+        assert!(clustering.labels[..25].iter().all(|l| *l == first));
+        let second = clustering.labels[25];
+        assert_ne!(first, second);
+        assert!(clustering.labels[25..50].iter().all(|l| *l == second));
+    }
+}
@@ -0,0 +1,208 @@
+//! Near-duplicate detection over a whole collection.
+//!
+//! Every vector is searched for its nearest neighbours, and each pair
+//! whose search score reaches the threshold is linked; linked vectors
+//! form a duplicate group. Grouping is single-linkage: `a ~ b` and
+//! `b ~ c` put all three in one group even when `a` and `c` score below
+//! the threshold, which is what catches a chunk copied with small edits
+//! across several mirrors.
+//!
+//! Scores are the index's search scores, so the threshold means cosine
+//! similarity in a cosine collection and `1 / (1 + distance)` in a
+//! Euclidean one. The scan runs as a [`DUPLICATE_REPORT`] job.
+//!
+//! [`DUPLICATE_REPORT`]: crate::jobs::DUPLICATE_REPORT
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::error::Result;
+use crate::jobs::Job;
+use crate::models::{SearchResult, Vector};
+
+/// Score two vectors must reach to count as duplicates by default.
+pub const DEFAULT_DUPLICATE_THRESHOLD: f32 = 0.95;
+
+/// Neighbours searched per vector by default.
+pub const DEFAULT_DUPLICATE_NEIGHBORS: usize = 10;
+
+/// Most neighbours searched per vector.
+pub const MAX_DUPLICATE_NEIGHBORS: usize = 100;
+
+/// Vectors scanned between cancellation checks.
+const CANCEL_CHECK_EVERY: usize = 256;
+
+/// Vectors linked by scores at or above the threshold.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DuplicateGroup {
+    /// Members in scan order; the first is a natural one to keep.
+    pub ids: Vec<String>,
+    /// Lowest score among the links that joined the group.
+    pub min_score: f32,
+    /// Highest score among them.
+    pub max_score: f32,
+}
+
+/// Group `vectors` into near-duplicates. `neighbors` returns the nearest
+/// neighbours of a vector, best first; results naming vectors outside
+/// `vectors` are ignored. Progress is reported on `job`.
+///
+/// Groups come largest first, ties in scan order.
+///
+/// # Errors
+///
+/// Whatever `neighbors` returns, or
+/// [`VectorizerError::JobCancelled`](crate::error::VectorizerError::JobCancelled)
+/// once the job was cancelled.
+pub fn find_duplicates(
+    vectors: &[Vector],
+    threshold: f32,
+    mut neighbors: impl FnMut(&Vector) -> Result<Vec<SearchResult>>,
+    job: &Job,
+) -> Result<Vec<DuplicateGroup>> {
+    let index: HashMap<&str, usize> = vectors
+        .iter()
+        .enumerate()
+        .map(|(i, v)| (v.id.as_str(), i))
+        .collect();
+    let mut parent: Vec<usize> = (0..vectors.len()).collect();
+    let mut links: Vec<(usize, f32)> = Vec::new();
+
+    job.set_total(vectors.len() as u64);
+    for (i, vector) in vectors.iter().enumerate() {
+        if i % CANCEL_CHECK_EVERY == 0 {
+            job.check_cancelled()?;
+        }
+        for result in neighbors(vector)? {
+            // Results come best first, so the rest score lower still
+            if result.score < threshold {
+                break;
+            }
+            let Some(&j) = index.get(result.id.as_str()) else {
+                continue;
+            };
+            if j == i {
+                continue;
+            }
+            let (a, b) = (find(&mut parent, i), find(&mut parent, j));
+            if a != b {
+                parent[a.max(b)] = a.min(b);
+            }
+            links.push((i, result.score));
+        }
+        job.advance(1);
+    }
+
+    let mut members: HashMap<usize, Vec<usize>> = HashMap::new();
+    for i in 0..vectors.len() {
+        let root = find(&mut parent, i);
+        members.entry(root).or_default().push(i);
+    }
+    let mut scores: HashMap<usize, (f32, f32)> = HashMap::new();
+    for (i, score) in links {
+        let root = find(&mut parent, i);
+        let range = scores.entry(root).or_insert((score, score));
+        range.0 = range.0.min(score);
+        range.1 = range.1.max(score);
+    }
+
+    let mut groups: Vec<(usize, DuplicateGroup)> = members
+        .into_iter()
+        .filter(|(_, group)| group.len() > 1)
+        .map(|(root, group)| {
+            let (min_score, max_score) = scores.get(&root).copied().unwrap_or((0.0, 0.0));
+            let first = group[0];
+            let ids = group.into_iter().map(|i| vectors[i].id.clone()).collect();
+            (
+                first,
+                DuplicateGroup {
+                    ids,
+                    min_score,
+                    max_score,
+                },
+            )
+        })
+        .collect();
+    groups.sort_by(|a, b| b.1.ids.len().cmp(&a.1.ids.len()).then(a.0.cmp(&b.0)));
+    Ok(groups.into_iter().map(|(_, group)| group).collect())
+}
+
+/// Root of `i`'s set, halving the path on the way.
+fn find(parent: &mut [usize], mut i: usize) -> usize {
+    while parent[i] != i {
+        parent[i] = parent[parent[i]];
+        i = parent[i];
+    }
+    i
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::jobs::{DUPLICATE_REPORT, JobRegistry};
+
+    fn v(id: &str) -> Vector {
+        Vector::new(id.to_string(), vec![0.0])
+    }
+
+    fn hit(id: &str, score: f32) -> SearchResult {
+        SearchResult {
+            id: id.to_string(),
+            score,
+            dense_score: None,
+            sparse_score: None,
+            vector: None,
+            payload: None,
+        }
+    }
+
+    #[test]
+    fn test_links_above_the_threshold_form_groups() {
+        let vectors = [v("a"), v("b"), v("c"), v("d"), v("e"), v("f")];
+        // a ~ b ~ c chain, d ~ e, f stands alone
+        let neighbors = |vector: &Vector| -> Result<Vec<SearchResult>> {
+            Ok(match vector.id.as_str() {
+                "a" => vec![hit("a", 1.0), hit("b", 0.97), hit("d", 0.5)],
+                "b" => vec![hit("b", 1.0), hit("a", 0.97), hit("c", 0.96)],
+                "c" => vec![hit("c", 1.0), hit("b", 0.96), hit("a", 0.9)],
+                "d" => vec![hit("d", 1.0), hit("e", 0.99), hit("gone", 0.99)],
+                "e" => vec![hit("e", 1.0), hit("d", 0.99)],
+                _ => vec![hit("f", 1.0), hit("a", 0.2)],
+            })
+        };
+        let registry = JobRegistry::default();
+        let job = registry.start(DUPLICATE_REPORT, "docs");
+
+        let groups = find_duplicates(&vectors, 0.95, neighbors, &job).unwrap();
+
+        assert_eq!(
+            groups,
+            vec![
+                DuplicateGroup {
+                    ids: vec!["a".into(), "b".into(), "c".into()],
+                    min_score: 0.96,
+                    max_score: 0.97,
+                },
+                DuplicateGroup {
+                    ids: vec!["d".into(), "e".into()],
+                    min_score: 0.99,
+                    max_score: 0.99,
+                },
+            ]
+        );
+        let status = registry.get(&job.id()).unwrap();
+        assert_eq!((status.processed, status.total), (6, 6));
+    }
+
+    #[test]
+    fn test_cancelled_scan_stops() {
+        let registry = JobRegistry::default();
+        let job = registry.start(DUPLICATE_REPORT, "docs");
+        registry.cancel(&job.id());
+
+        let result = find_duplicates(&[v("a")], 0.95, |_| Ok(Vec::new()), &job);
+        assert!(result.is_err());
+    }
+}
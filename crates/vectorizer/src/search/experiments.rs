@@ -0,0 +1,809 @@
+//! Retrieval experiments — A/B tests of two retrieval configurations on
+//! live traffic.
+//!
+//! An [`Experiment`] splits the text searches of one collection between a
+//! `control` and a `treatment` [`ExperimentArm`]. An arm may embed the
+//! query with another provider, search another collection (e.g. a copy
+//! re-embedded with that provider), fuse the dense hits with BM25 at its
+//! own weights and rerank them:
+//!
+//! ```json
+//! {
+//!   "collection": "docs",
+//!   "treatment_percent": 20,
+//!   "control": {},
+//!   "treatment": {
+//!     "embedding_provider": "fastembed:bge-small-en-v1.5",
+//!     "collection": "docs_bge",
+//!     "hybrid": { "alpha": 0.6, "algorithm": "rrf" }
+//!   }
+//! }
+//! ```
+//!
+//! `treatment_percent` of the searches go to the treatment. A search that
+//! sends an `experiment_key` (a user or session ID) always lands in the
+//! same arm; one without is assigned at random.
+//!
+//! Arms are compared two ways: online, by the searches, zero-result rate,
+//! latency and reported clicks counted per arm ([`VariantStats`]), and
+//! offline, by [`Experiment::evaluate`] running an arm on labelled queries
+//! and scoring it with the [`evaluation`](crate::evaluation) metrics.
+//!
+//! An [`ExperimentStore`] keeps the experiments and their counters in
+//! `experiments.json` in the data directory. Definitions are written on
+//! every change; the counters by [`ExperimentStore::flush`].
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use serde_json::Map;
+use xxhash_rust::xxh3::xxh3_64;
+
+use crate::db::{SearchDeadline, VectorStore};
+use crate::error::{Result, VectorizerError};
+use crate::evaluation::{self, QueryResult};
+use crate::models::ScoringConfig;
+use crate::search::templates::{SearchTemplate, TemplateHybrid, TemplateRun};
+
+/// File the experiments are saved to, in the data directory.
+pub const EXPERIMENTS_FILE: &str = "experiments.json";
+
+/// Most labelled queries one evaluation runs.
+pub const MAX_EVALUATION_QUERIES: usize = 1_000;
+
+/// Longest experiment name, in bytes.
+const MAX_EXPERIMENT_NAME_LEN: usize = 128;
+
+fn invalid(message: String) -> VectorizerError {
+    VectorizerError::InvalidConfiguration { message }
+}
+
+/// One side of an experiment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Variant {
+    /// The current configuration
+    Control,
+    /// The configuration under test
+    Treatment,
+}
+
+impl Variant {
+    /// Both variants, control first.
+    pub const ALL: [Variant; 2] = [Variant::Control, Variant::Treatment];
+
+    /// Name used in responses and requests.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Variant::Control => "control",
+            Variant::Treatment => "treatment",
+        }
+    }
+}
+
+impl std::fmt::Display for Variant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Retrieval configuration of one variant. Everything left out is what
+/// a plain text search of the experiment's collection does.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExperimentArm {
+    /// Provider the query is embedded with, instead of the collection's
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding_provider: Option<String>,
+    /// Collection searched instead of the experiment's, e.g. a copy
+    /// embedded with `embedding_provider`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub collection: Option<String>,
+    /// Fuse the dense search with BM25 over the payload text
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hybrid: Option<TemplateHybrid>,
+    /// Scoring expression the hits are reranked by
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rerank: Option<ScoringConfig>,
+}
+
+/// An A/B test of two retrieval configurations on one collection.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Experiment {
+    /// What the experiment tests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Collection whose text searches are split between the variants
+    pub collection: String,
+    /// Share of the searches sent to the treatment, 0-100
+    pub treatment_percent: u8,
+    /// The current configuration
+    #[serde(default)]
+    pub control: ExperimentArm,
+    /// The configuration under test
+    pub treatment: ExperimentArm,
+    /// Whether searches are being split; a stopped experiment keeps its
+    /// counters
+    #[serde(default = "default_active")]
+    pub active: bool,
+}
+
+fn default_active() -> bool {
+    true
+}
+
+/// A query with the results judged relevant to it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LabelledQuery {
+    /// Query text
+    pub query: String,
+    /// IDs of the relevant results
+    pub relevant: HashSet<String>,
+}
+
+/// Offline quality of one variant over labelled queries.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VariantEvaluation {
+    /// Variant evaluated
+    pub variant: Variant,
+    /// Queries scored (those with at least one relevant result)
+    pub queries: usize,
+    /// Cut-off of the metrics
+    pub k: usize,
+    /// Mean average precision
+    pub mean_average_precision: f32,
+    /// Mean reciprocal rank of the first relevant result
+    pub mean_reciprocal_rank: f32,
+    /// Mean nDCG@k
+    pub ndcg: f32,
+    /// Mean precision@k
+    pub precision: f32,
+    /// Mean recall@k
+    pub recall: f32,
+}
+
+impl Experiment {
+    /// Check the experiment before it is stored as `name`.
+    pub fn validate(&self, name: &str) -> Result<()> {
+        if name.is_empty()
+            || name.len() > MAX_EXPERIMENT_NAME_LEN
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+        {
+            return Err(invalid(format!(
+                "experiment name must be 1-{MAX_EXPERIMENT_NAME_LEN} characters of \
+                 letters, digits, '_', '-' and '.'"
+            )));
+        }
+        if self.collection.trim().is_empty() {
+            return Err(invalid("collection must not be empty".to_string()));
+        }
+        if self.treatment_percent > 100 {
+            return Err(invalid(
+                "treatment_percent must be between 0 and 100".to_string(),
+            ));
+        }
+        for variant in Variant::ALL {
+            let arm = self.arm(variant);
+            if arm.collection.as_ref().is_some_and(|c| c.trim().is_empty())
+                || arm
+                    .embedding_provider
+                    .as_ref()
+                    .is_some_and(|p| p.trim().is_empty())
+            {
+                return Err(invalid(format!(
+                    "{variant}: collection and embedding_provider must not be empty"
+                )));
+            }
+            // The template check covers hybrid and rerank
+            self.search_template(variant, 10, None)
+                .validate(name)
+                .map_err(|e| invalid(format!("{variant}: {e}")))?;
+        }
+        if self.control == self.treatment {
+            return Err(invalid("control and treatment must differ".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Configuration of `variant`.
+    pub fn arm(&self, variant: Variant) -> &ExperimentArm {
+        match variant {
+            Variant::Control => &self.control,
+            Variant::Treatment => &self.treatment,
+        }
+    }
+
+    /// Collection `variant` searches.
+    pub fn collection_of(&self, variant: Variant) -> &str {
+        self.arm(variant)
+            .collection
+            .as_deref()
+            .unwrap_or(&self.collection)
+    }
+
+    /// Variant of a search in the experiment `name`. The same `key`
+    /// always gets the same variant; without one the variant is drawn at
+    /// random.
+    pub fn assign(&self, name: &str, key: Option<&str>) -> Variant {
+        let bucket = match key {
+            Some(key) => xxh3_64(format!("{name}:{key}").as_bytes()) % 100,
+            None => rand::random::<u64>() % 100,
+        };
+        if bucket < u64::from(self.treatment_percent) {
+            Variant::Treatment
+        } else {
+            Variant::Control
+        }
+    }
+
+    /// The search `variant` runs, returning `limit` results scoring at
+    /// least `threshold`.
+    pub fn search_template(
+        &self,
+        variant: Variant,
+        limit: usize,
+        threshold: Option<f32>,
+    ) -> SearchTemplate {
+        let arm = self.arm(variant);
+        SearchTemplate {
+            description: None,
+            collections: vec![self.collection_of(variant).to_string()],
+            limit,
+            threshold,
+            filter: None,
+            hybrid: arm.hybrid.clone(),
+            rerank: arm.rerank.clone(),
+            parameters: BTreeMap::new(),
+        }
+    }
+
+    /// Run `variant` for `query`, embedded as `query_vector` by the
+    /// variant's provider. `tenant` confines tenant-partitioned
+    /// collections to the tenant's vectors.
+    #[allow(clippy::too_many_arguments)]
+    pub fn search(
+        &self,
+        variant: Variant,
+        store: &VectorStore,
+        query: &str,
+        query_vector: &[f32],
+        limit: usize,
+        threshold: Option<f32>,
+        tenant: Option<&str>,
+        deadline: &SearchDeadline,
+    ) -> Result<TemplateRun> {
+        self.search_template(variant, limit, threshold).run(
+            store,
+            query,
+            query_vector,
+            &Map::new(),
+            tenant,
+            deadline,
+        )
+    }
+
+    /// Score `variant` on `queries` at cut-off `k`. `embed` turns a query
+    /// into the vector the variant searches with. Queries without a
+    /// relevant result are skipped.
+    ///
+    /// # Errors
+    ///
+    /// [`VectorizerError::InvalidConfiguration`] when no query has a
+    /// relevant result or there are more than [`MAX_EVALUATION_QUERIES`],
+    /// and the errors of the embedding and the search.
+    pub fn evaluate(
+        &self,
+        variant: Variant,
+        store: &VectorStore,
+        queries: &[LabelledQuery],
+        k: usize,
+        embed: &dyn Fn(&str) -> Result<Vec<f32>>,
+    ) -> Result<VariantEvaluation> {
+        if queries.len() > MAX_EVALUATION_QUERIES {
+            return Err(invalid(format!(
+                "at most {MAX_EVALUATION_QUERIES} queries can be evaluated at once"
+            )));
+        }
+        let judged: Vec<&LabelledQuery> =
+            queries.iter().filter(|q| !q.relevant.is_empty()).collect();
+        if judged.is_empty() {
+            return Err(invalid(
+                "no query has a relevant result to evaluate against".to_string(),
+            ));
+        }
+
+        let template = self.search_template(variant, k, None);
+        let mut ranked = Vec::with_capacity(judged.len());
+        for labelled in &judged {
+            let vector = embed(&labelled.query)?;
+            let run = template.run(
+                store,
+                &labelled.query,
+                &vector,
+                &Map::new(),
+                None,
+                &SearchDeadline::NONE,
+            )?;
+            let results: Vec<QueryResult> = run
+                .hits
+                .into_iter()
+                .map(|hit| QueryResult {
+                    relevance: if labelled.relevant.contains(&hit.id) {
+                        1.0
+                    } else {
+                        0.0
+                    },
+                    doc_id: hit.id,
+                })
+                .collect();
+            ranked.push((results, labelled.relevant.clone()));
+        }
+        Ok(score_variant(variant, ranked, k))
+    }
+}
+
+/// Mean metrics of `ranked` (results with their relevant IDs) at `k`.
+fn score_variant(
+    variant: Variant,
+    ranked: Vec<(Vec<QueryResult>, HashSet<String>)>,
+    k: usize,
+) -> VariantEvaluation {
+    let count = ranked.len().max(1) as f32;
+    let mean = |metric: &dyn Fn(&[QueryResult], &HashSet<String>) -> f32| {
+        ranked
+            .iter()
+            .map(|(results, relevant)| metric(results, relevant))
+            .sum::<f32>()
+            / count
+    };
+    let ndcg = mean(&|results, relevant| evaluation::ndcg_at_k(results, relevant, k));
+    let precision = mean(&|results, relevant| evaluation::precision_at_k(results, relevant, k));
+    let recall = mean(&|results, relevant| evaluation::recall_at_k(results, relevant, k));
+    let queries = ranked.len();
+    let overall = evaluation::evaluate_search_quality(ranked, k);
+    VariantEvaluation {
+        variant,
+        queries,
+        k,
+        mean_average_precision: overall.mean_average_precision,
+        mean_reciprocal_rank: overall.mean_reciprocal_rank,
+        ndcg,
+        precision,
+        recall,
+    }
+}
+
+/// Live-traffic counters of one variant.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct VariantStats {
+    /// Searches served
+    pub searches: u64,
+    /// Searches that returned no result
+    pub zero_results: u64,
+    /// Sum of the search latencies, in milliseconds
+    pub latency_ms_total: u64,
+    /// Clicks reported on the variant's results
+    pub clicks: u64,
+    /// Sum of 1/rank over the reported clicks
+    pub reciprocal_rank_total: f64,
+}
+
+/// Counters of an experiment since it was created or last replaced.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExperimentStats {
+    /// When counting started
+    pub started_at: DateTime<Utc>,
+    /// Counters of the control
+    #[serde(default)]
+    pub control: VariantStats,
+    /// Counters of the treatment
+    #[serde(default)]
+    pub treatment: VariantStats,
+}
+
+impl ExperimentStats {
+    fn new() -> Self {
+        Self {
+            started_at: Utc::now(),
+            control: VariantStats::default(),
+            treatment: VariantStats::default(),
+        }
+    }
+
+    fn variant_mut(&mut self, variant: Variant) -> &mut VariantStats {
+        match variant {
+            Variant::Control => &mut self.control,
+            Variant::Treatment => &mut self.treatment,
+        }
+    }
+}
+
+/// Live-traffic summary of one variant.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VariantReport {
+    /// Variant summarized
+    pub variant: Variant,
+    /// Searches served
+    pub searches: u64,
+    /// Share of the experiment's searches served
+    pub traffic_share: f64,
+    /// Share of the searches that returned no result
+    pub zero_result_rate: f64,
+    /// Mean search latency, in milliseconds
+    pub mean_latency_ms: f64,
+    /// Clicks reported
+    pub clicks: u64,
+    /// Clicks per search
+    pub click_rate: f64,
+    /// Mean of 1/rank over the clicks (1.0 when every click is on the
+    /// first result)
+    pub mean_reciprocal_click_rank: f64,
+}
+
+impl VariantReport {
+    fn new(variant: Variant, stats: &VariantStats, total_searches: u64) -> Self {
+        let per = |n: f64, d: u64| if d == 0 { 0.0 } else { n / d as f64 };
+        Self {
+            variant,
+            searches: stats.searches,
+            traffic_share: per(stats.searches as f64, total_searches),
+            zero_result_rate: per(stats.zero_results as f64, stats.searches),
+            mean_latency_ms: per(stats.latency_ms_total as f64, stats.searches),
+            clicks: stats.clicks,
+            click_rate: per(stats.clicks as f64, stats.searches),
+            mean_reciprocal_click_rank: per(stats.reciprocal_rank_total, stats.clicks),
+        }
+    }
+}
+
+/// An experiment with its live-traffic summary.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExperimentReport {
+    /// The experiment
+    pub experiment: Experiment,
+    /// When counting started
+    pub started_at: DateTime<Utc>,
+    /// Summary of the control
+    pub control: VariantReport,
+    /// Summary of the treatment
+    pub treatment: VariantReport,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StoredExperiment {
+    experiment: Experiment,
+    stats: ExperimentStats,
+}
+
+/// The experiments of a server.
+#[derive(Debug, Default)]
+pub struct ExperimentStore {
+    experiments: RwLock<BTreeMap<String, StoredExperiment>>,
+    /// File the experiments are saved to; `None` keeps them in memory
+    path: Option<PathBuf>,
+    /// Counters changed since the last save
+    dirty: AtomicBool,
+}
+
+impl ExperimentStore {
+    /// The experiments saved at `path`, which later changes are written
+    /// to. A missing file is an empty store; an unreadable one is an
+    /// error.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let experiments: BTreeMap<String, StoredExperiment> = match std::fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).map_err(|e| {
+                VectorizerError::Storage(format!(
+                    "failed to parse experiments in {}: {}",
+                    path.display(),
+                    e
+                ))
+            })?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(VectorizerError::IoError(e)),
+        };
+        for (name, stored) in &experiments {
+            stored.experiment.validate(name)?;
+        }
+        Ok(Self {
+            experiments: RwLock::new(experiments),
+            path: Some(path),
+            dirty: Default::default(),
+        })
+    }
+
+    /// [`Self::open`] on [`EXPERIMENTS_FILE`] in `data_dir`.
+    pub fn open_in(data_dir: &Path) -> Result<Self> {
+        Self::open(data_dir.join(EXPERIMENTS_FILE))
+    }
+
+    /// Store `experiment` as `name`, replacing any experiment of that
+    /// name and restarting its counters. Returns whether one was
+    /// replaced.
+    ///
+    /// # Errors
+    ///
+    /// [`VectorizerError::InvalidConfiguration`] when the experiment is
+    /// invalid or another active experiment splits the same collection.
+    pub fn put(&self, name: &str, experiment: Experiment) -> Result<bool> {
+        experiment.validate(name)?;
+        let mut experiments = self.experiments.write();
+        if experiment.active
+            && let Some((other, _)) = experiments.iter().find(|(other, stored)| {
+                other.as_str() != name
+                    && stored.experiment.active
+                    && stored.experiment.collection == experiment.collection
+            })
+        {
+            return Err(invalid(format!(
+                "experiment '{other}' already splits the searches of '{}'",
+                experiment.collection
+            )));
+        }
+        let stored = StoredExperiment {
+            experiment,
+            stats: ExperimentStats::new(),
+        };
+        let previous = experiments.insert(name.to_string(), stored);
+        if let Err(e) = self.save(&experiments) {
+            match previous {
+                Some(previous) => experiments.insert(name.to_string(), previous),
+                None => experiments.remove(name),
+            };
+            return Err(e);
+        }
+        Ok(previous.is_some())
+    }
+
+    /// Remove the experiment `name`. Returns whether it existed.
+    pub fn remove(&self, name: &str) -> Result<bool> {
+        let mut experiments = self.experiments.write();
+        let Some(previous) = experiments.remove(name) else {
+            return Ok(false);
+        };
+        if let Err(e) = self.save(&experiments) {
+            experiments.insert(name.to_string(), previous);
+            return Err(e);
+        }
+        Ok(true)
+    }
+
+    /// The experiment stored as `name`.
+    pub fn get(&self, name: &str) -> Option<Experiment> {
+        self.experiments
+            .read()
+            .get(name)
+            .map(|stored| stored.experiment.clone())
+    }
+
+    /// Every experiment with its name, ordered by name.
+    pub fn list(&self) -> Vec<(String, Experiment)> {
+        self.experiments
+            .read()
+            .iter()
+            .map(|(name, stored)| (name.clone(), stored.experiment.clone()))
+            .collect()
+    }
+
+    /// The active experiment splitting the searches of `collection`.
+    pub fn for_collection(&self, collection: &str) -> Option<(String, Experiment)> {
+        self.experiments
+            .read()
+            .iter()
+            .find(|(_, stored)| {
+                stored.experiment.active && stored.experiment.collection == collection
+            })
+            .map(|(name, stored)| (name.clone(), stored.experiment.clone()))
+    }
+
+    /// Count a search served by `variant` of `name`.
+    pub fn record_search(
+        &self,
+        name: &str,
+        variant: Variant,
+        latency: Duration,
+        result_count: usize,
+    ) {
+        if let Some(stored) = self.experiments.write().get_mut(name) {
+            let stats = stored.stats.variant_mut(variant);
+            stats.searches += 1;
+            stats.latency_ms_total += latency.as_millis() as u64;
+            if result_count == 0 {
+                stats.zero_results += 1;
+            }
+            self.dirty.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Count a click on the result at 1-based `rank` of a search served
+    /// by `variant` of `name`. Returns whether the experiment exists.
+    pub fn record_click(&self, name: &str, variant: Variant, rank: usize) -> bool {
+        let mut experiments = self.experiments.write();
+        let Some(stored) = experiments.get_mut(name) else {
+            return false;
+        };
+        let stats = stored.stats.variant_mut(variant);
+        stats.clicks += 1;
+        stats.reciprocal_rank_total += 1.0 / rank.max(1) as f64;
+        self.dirty.store(true, Ordering::Relaxed);
+        true
+    }
+
+    /// The experiment `name` with its live-traffic summary.
+    pub fn report(&self, name: &str) -> Option<ExperimentReport> {
+        let experiments = self.experiments.read();
+        let stored = experiments.get(name)?;
+        let stats = &stored.stats;
+        let total = stats.control.searches + stats.treatment.searches;
+        Some(ExperimentReport {
+            experiment: stored.experiment.clone(),
+            started_at: stats.started_at,
+            control: VariantReport::new(Variant::Control, &stats.control, total),
+            treatment: VariantReport::new(Variant::Treatment, &stats.treatment, total),
+        })
+    }
+
+    /// Save the counters if they changed since the last save. Returns
+    /// whether the store was saved.
+    pub fn flush(&self) -> Result<bool> {
+        if !self.dirty.swap(false, Ordering::Relaxed) {
+            return Ok(false);
+        }
+        let experiments = self.experiments.read();
+        self.save(&experiments).inspect_err(|_| {
+            // Try again on the next flush
+            self.dirty.store(true, Ordering::Relaxed);
+        })?;
+        Ok(self.path.is_some())
+    }
+
+    /// Write `experiments` to the store's file through a temporary file,
+    /// so a crash never leaves it half written.
+    fn save(&self, experiments: &BTreeMap<String, StoredExperiment>) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_vec_pretty(experiments)?)?;
+        std::fs::rename(&tmp, path)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn experiment(collection: &str, treatment_percent: u8) -> Experiment {
+        Experiment {
+            description: None,
+            collection: collection.to_string(),
+            treatment_percent,
+            control: ExperimentArm::default(),
+            treatment: ExperimentArm {
+                collection: Some(format!("{collection}_v2")),
+                ..ExperimentArm::default()
+            },
+            active: true,
+        }
+    }
+
+    #[test]
+    fn assignment_is_sticky_per_key_and_follows_the_split() {
+        let exp = experiment("docs", 30);
+        let first = exp.assign("exp", Some("user-1"));
+        assert!((0..20).all(|_| exp.assign("exp", Some("user-1")) == first));
+
+        let treated = (0..10_000)
+            .filter(|i| exp.assign("exp", Some(&format!("user-{i}"))) == Variant::Treatment)
+            .count();
+        assert!((2_500..3_500).contains(&treated), "{treated}");
+
+        assert_eq!(experiment("docs", 0).assign("exp", None), Variant::Control);
+        assert_eq!(
+            experiment("docs", 100).assign("exp", Some("anyone")),
+            Variant::Treatment
+        );
+    }
+
+    #[test]
+    fn validation_rejects_bad_experiments() {
+        assert!(experiment("docs", 50).validate("exp").is_ok());
+        assert!(experiment("docs", 101).validate("exp").is_err());
+        assert!(experiment("docs", 50).validate("bad name").is_err());
+
+        let mut same = experiment("docs", 50);
+        same.treatment = ExperimentArm::default();
+        assert!(same.validate("exp").is_err());
+
+        let mut bad_alpha = experiment("docs", 50);
+        bad_alpha.treatment.hybrid =
+            Some(serde_json::from_value(serde_json::json!({ "alpha": 1.5 })).unwrap());
+        assert!(bad_alpha.validate("exp").is_err());
+    }
+
+    #[test]
+    fn one_active_experiment_per_collection() {
+        let store = ExperimentStore::default();
+        assert!(!store.put("a", experiment("docs", 50)).unwrap());
+        assert!(store.put("b", experiment("docs", 50)).is_err());
+
+        let mut stopped = experiment("docs", 50);
+        stopped.active = false;
+        store.put("b", stopped).unwrap();
+        assert_eq!(
+            store
+                .for_collection("docs")
+                .map(|(name, _)| name)
+                .as_deref(),
+            Some("a")
+        );
+        assert!(store.put("a", experiment("docs", 20)).unwrap());
+    }
+
+    #[test]
+    fn report_summarizes_each_variant() {
+        let store = ExperimentStore::default();
+        store.put("exp", experiment("docs", 50)).unwrap();
+        let ms = Duration::from_millis;
+        store.record_search("exp", Variant::Control, ms(10), 5);
+        store.record_search("exp", Variant::Control, ms(30), 0);
+        store.record_search("exp", Variant::Treatment, ms(20), 4);
+        store.record_search("exp", Variant::Treatment, ms(20), 4);
+        assert!(store.record_click("exp", Variant::Treatment, 1));
+        assert!(store.record_click("exp", Variant::Treatment, 2));
+        assert!(!store.record_click("missing", Variant::Control, 1));
+
+        let report = store.report("exp").unwrap();
+        assert_eq!(report.control.searches, 2);
+        assert_eq!(report.control.traffic_share, 0.5);
+        assert_eq!(report.control.zero_result_rate, 0.5);
+        assert_eq!(report.control.mean_latency_ms, 20.0);
+        assert_eq!(report.control.click_rate, 0.0);
+        assert_eq!(report.treatment.clicks, 2);
+        assert_eq!(report.treatment.click_rate, 1.0);
+        assert_eq!(report.treatment.mean_reciprocal_click_rank, 0.75);
+    }
+
+    #[test]
+    fn scoring_uses_the_evaluation_metrics() {
+        let relevant = HashSet::from(["a".to_string()]);
+        let result = |id: &str| QueryResult {
+            doc_id: id.to_string(),
+            relevance: if id == "a" { 1.0 } else { 0.0 },
+        };
+        let ranked = vec![
+            (vec![result("a"), result("b")], relevant.clone()),
+            (vec![result("b"), result("a")], relevant),
+        ];
+        let scored = score_variant(Variant::Treatment, ranked, 2);
+        assert_eq!(scored.queries, 2);
+        assert_eq!(scored.mean_reciprocal_rank, 0.75);
+        assert_eq!(scored.recall, 1.0);
+        assert_eq!(scored.precision, 0.5);
+        assert!(scored.ndcg < 1.0 && scored.ndcg > 0.5);
+    }
+
+    #[test]
+    fn counters_survive_a_flush_and_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = ExperimentStore::open_in(dir.path()).unwrap();
+        store.put("exp", experiment("docs", 50)).unwrap();
+        assert!(!store.flush().unwrap());
+        store.record_search("exp", Variant::Control, Duration::from_millis(5), 1);
+        assert!(store.flush().unwrap());
+
+        let reopened = ExperimentStore::open_in(dir.path()).unwrap();
+        assert_eq!(reopened.report("exp").unwrap().control.searches, 1);
+    }
+}
@@ -0,0 +1,188 @@
+//! Facet counts: how many vectors carry each value of a payload field,
+//! the numbers behind a filter UI's checkboxes.
+//!
+//! Keys use the filters' dot notation (`meta.lang`). Strings, numbers
+//! and booleans are counted; an array counts each of its distinct
+//! scalar elements once for the vector. Vectors without the key, with a
+//! non-scalar value there or with an encrypted payload count as
+//! `missing`.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::models::Payload;
+use crate::models::qdrant::filter_processor::FilterProcessor;
+
+/// Values listed per key when the caller does not say.
+pub const DEFAULT_FACET_LIMIT: usize = 10;
+
+/// Most values listed per key.
+pub const MAX_FACET_LIMIT: usize = 1_000;
+
+/// Most keys counted in one call.
+pub const MAX_FACET_KEYS: usize = 32;
+
+/// One value of a facet and the vectors carrying it.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FacetValue {
+    /// The payload value.
+    pub value: Value,
+    /// Vectors carrying it.
+    pub count: usize,
+}
+
+/// Value counts of one payload key.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Facet {
+    /// The payload key, in dot notation.
+    pub key: String,
+    /// The most frequent values, most frequent first; ties in value order.
+    pub values: Vec<FacetValue>,
+    /// Distinct values seen, listed or not.
+    pub distinct: usize,
+    /// Vectors without a countable value under the key.
+    pub missing: usize,
+}
+
+/// Count the values of each of `keys` over `payloads`, listing at most
+/// `limit` values per key.
+pub fn facet_counts<'a>(
+    payloads: impl IntoIterator<Item = Option<&'a Payload>>,
+    keys: &[String],
+    limit: usize,
+) -> Vec<Facet> {
+    // Values are told apart by their JSON text, so `1` and `"1"` differ
+    let mut counts: Vec<HashMap<String, FacetValue>> = vec![HashMap::new(); keys.len()];
+    let mut missing = vec![0usize; keys.len()];
+    let mut seen: Vec<(String, &Value)> = Vec::new();
+
+    for payload in payloads {
+        let payload = payload.filter(|p| !p.is_encrypted());
+        for (k, key) in keys.iter().enumerate() {
+            match payload.and_then(|p| FilterProcessor::get_nested_value(key, p)) {
+                Some(Value::Array(items)) => {
+                    seen.extend(
+                        items
+                            .iter()
+                            .filter(|v| is_scalar(v))
+                            .map(|v| (v.to_string(), v)),
+                    );
+                    seen.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+                    seen.dedup_by(|a, b| a.0 == b.0);
+                }
+                Some(value) if is_scalar(value) => seen.push((value.to_string(), value)),
+                _ => {}
+            }
+            if seen.is_empty() {
+                missing[k] += 1;
+            }
+            for (text, value) in seen.drain(..) {
+                counts[k]
+                    .entry(text)
+                    .or_insert_with(|| FacetValue {
+                        value: value.clone(),
+                        count: 0,
+                    })
+                    .count += 1;
+            }
+        }
+    }
+
+    keys.iter()
+        .zip(counts)
+        .zip(missing)
+        .map(|((key, counts), missing)| {
+            let distinct = counts.len();
+            let mut values: Vec<(String, FacetValue)> = counts.into_iter().collect();
+            values.sort_by(|a, b| b.1.count.cmp(&a.1.count).then_with(|| a.0.cmp(&b.0)));
+            values.truncate(limit);
+            Facet {
+                key: key.clone(),
+                values: values.into_iter().map(|(_, value)| value).collect(),
+                distinct,
+                missing,
+            }
+        })
+        .collect()
+}
+
+fn is_scalar(value: &Value) -> bool {
+    matches!(value, Value::String(_) | Value::Number(_) | Value::Bool(_))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_counts_scalars_arrays_and_missing() {
+        let payloads = [
+            Payload::new(
+                json!({"lang": "rust", "tags": ["db", "cli", "db"], "meta": {"year": 2024}}),
+            ),
+            Payload::new(json!({"lang": "rust", "tags": ["db"], "meta": {"year": 2025}})),
+            Payload::new(json!({"lang": "go", "tags": [], "meta": {"year": "2024"}})),
+            Payload::new(json!({"lang": {"name": "zig"}})),
+        ];
+        let keys = [
+            "lang".to_string(),
+            "tags".to_string(),
+            "meta.year".to_string(),
+        ];
+
+        let facets = facet_counts(payloads.iter().map(Some).chain([None]), &keys, 10);
+
+        assert_eq!(facets[0].key, "lang");
+        assert_eq!(
+            facets[0].values,
+            vec![
+                FacetValue {
+                    value: json!("rust"),
+                    count: 2
+                },
+                FacetValue {
+                    value: json!("go"),
+                    count: 1
+                },
+            ]
+        );
+        assert_eq!((facets[0].distinct, facets[0].missing), (2, 2));
+        assert_eq!(
+            facets[1].values[0],
+            FacetValue {
+                value: json!("db"),
+                count: 2
+            }
+        );
+        assert_eq!(
+            facets[1].values[1],
+            FacetValue {
+                value: json!("cli"),
+                count: 1
+            }
+        );
+        assert_eq!(facets[1].missing, 3);
+        // A number and a string of the same digits are different values
+        assert_eq!(facets[2].distinct, 3);
+        assert_eq!(facets[2].missing, 2);
+    }
+
+    #[test]
+    fn test_limit_keeps_the_most_frequent_values() {
+        let payloads: Vec<Payload> = ["a", "b", "b", "c", "c", "c"]
+            .iter()
+            .map(|v| Payload::new(json!({ "k": v })))
+            .collect();
+
+        let facets = facet_counts(payloads.iter().map(Some), &["k".to_string()], 2);
+
+        let values: Vec<&Value> = facets[0].values.iter().map(|v| &v.value).collect();
+        assert_eq!(values, [&json!("c"), &json!("b")]);
+        assert_eq!(facets[0].distinct, 3);
+    }
+}
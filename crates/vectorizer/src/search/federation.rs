@@ -0,0 +1,584 @@
+//! Query federation — fan a multi-collection search out to remote
+//! Vectorizer instances and merge their hits with the local ones.
+//!
+//! Each remote is a [`FederatedSourceConfig`] held by a
+//! [`FederationRegistry`], seeded from `federation.sources` and editable
+//! at runtime. [`FederationRegistry::search`] calls the selected sources'
+//! `POST /multi_collection_search` concurrently, each under its own
+//! timeout; a source that fails or times out is reported, not fatal.
+//!
+//! Raw scores from different servers are not comparable (different
+//! embedding models, metrics and corpora), so [`merge`] rescales each
+//! source's scores on their own ([`ScoreNormalization`]) and multiplies
+//! them by the source weight before interleaving the lists. Data never
+//! moves: every remote answers from its own collections.
+
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use tracing::{debug, warn};
+
+use crate::config::sections::federation::{FederatedSourceConfig, FederationConfig};
+use crate::error::{Result, VectorizerError};
+use crate::intelligent_search::{IntelligentSearchResult, ScoreBreakdown};
+
+/// Source name of this server's own hits in a federated search.
+pub const LOCAL_SOURCE: &str = "local";
+
+/// Metadata key naming the source a federated hit came from.
+pub const SOURCE_METADATA_KEY: &str = "federation_source";
+
+/// Upper bound on `max_total_results` asked of a remote, matching the
+/// validation of `POST /multi_collection_search`.
+const MAX_REMOTE_RESULTS: usize = 1000;
+
+/// Rank offset of reciprocal rank fusion; 60 is the usual choice and
+/// keeps the first few ranks from dominating.
+const RRF_K: f32 = 60.0;
+
+/// How each source's scores are rescaled before merging.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScoreNormalization {
+    /// Keep raw scores. Only meaningful when every source embeds with the
+    /// same model and metric.
+    None,
+    /// `(s - min) / (max - min)` per source; a source whose hits all
+    /// share one score maps them to `1.0`.
+    #[default]
+    MinMax,
+    /// `(s - mean) / stddev` per source; `0.0` when the scores do not
+    /// vary.
+    ZScore,
+    /// Reciprocal rank fusion: `1 / (60 + rank)` by the source's own
+    /// ranking, its best hit being rank 1. Ignores score magnitudes, so
+    /// it holds up when lists differ in the shape of their score
+    /// distributions, not just their scale.
+    Rrf,
+}
+
+impl ScoreNormalization {
+    /// Parse the request spelling (`none`, `min_max`, `z_score`, `rrf`).
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "none" => Some(Self::None),
+            "min_max" => Some(Self::MinMax),
+            "z_score" => Some(Self::ZScore),
+            "rrf" => Some(Self::Rrf),
+            _ => None,
+        }
+    }
+
+    /// Rescale `scores` in place.
+    pub fn apply(self, scores: &mut [f32]) {
+        if scores.is_empty() {
+            return;
+        }
+        match self {
+            Self::None => {}
+            Self::MinMax => {
+                let (min, max) = scores
+                    .iter()
+                    .fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), &s| {
+                        (lo.min(s), hi.max(s))
+                    });
+                let range = max - min;
+                for s in scores.iter_mut() {
+                    *s = if range > f32::EPSILON {
+                        (*s - min) / range
+                    } else {
+                        1.0
+                    };
+                }
+            }
+            Self::ZScore => {
+                let n = scores.len() as f32;
+                let mean = scores.iter().sum::<f32>() / n;
+                let variance = scores.iter().map(|s| (s - mean).powi(2)).sum::<f32>() / n;
+                let stddev = variance.sqrt();
+                for s in scores.iter_mut() {
+                    *s = if stddev > f32::EPSILON {
+                        (*s - mean) / stddev
+                    } else {
+                        0.0
+                    };
+                }
+            }
+            Self::Rrf => {
+                let mut ranking: Vec<usize> = (0..scores.len()).collect();
+                ranking.sort_by(|&a, &b| scores[b].total_cmp(&scores[a]));
+                let mut fused = vec![0.0; scores.len()];
+                for (rank, index) in ranking.into_iter().enumerate() {
+                    fused[index] = 1.0 / (RRF_K + rank as f32 + 1.0);
+                }
+                scores.copy_from_slice(&fused);
+            }
+        }
+    }
+}
+
+/// How a source answered a federated search.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SourceStatus {
+    /// Answered in time; its hits are merged.
+    Ok,
+    /// No answer within the source's timeout.
+    Timeout,
+    /// Unreachable, non-2xx, or an unreadable response.
+    Error,
+}
+
+/// Per-source line of a federated search response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceReport {
+    /// Source name ([`LOCAL_SOURCE`] for this server).
+    pub source: String,
+    /// How the source answered.
+    pub status: SourceStatus,
+    /// Hits the source returned, before merging.
+    pub results: usize,
+    /// Wall-clock time spent on the source.
+    pub latency_ms: u64,
+    /// Why the source contributed nothing, when it failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// One source's hits, on that source's own score scale.
+#[derive(Debug, Clone)]
+pub struct SourceHits {
+    /// Source name.
+    pub source: String,
+    /// The source's configured weight (`1.0` for [`LOCAL_SOURCE`]).
+    pub weight: f32,
+    /// Hits as returned by the source.
+    pub results: Vec<IntelligentSearchResult>,
+}
+
+/// The part of a multi-collection search forwarded to remotes.
+#[derive(Debug, Clone)]
+pub struct FederatedQuery {
+    /// Query text; each remote embeds it with its own model.
+    pub query: String,
+    /// Collections to search on sources that do not name their own.
+    pub collections: Vec<String>,
+    /// Hits per collection asked of each source.
+    pub max_per_collection: usize,
+    /// Forwarded `cross_collection_reranking`.
+    pub cross_collection_reranking: bool,
+}
+
+/// Registered remote sources and the HTTP client used to reach them.
+pub struct FederationRegistry {
+    sources: RwLock<BTreeMap<String, FederatedSourceConfig>>,
+    default_timeout: Duration,
+    client: reqwest::Client,
+}
+
+impl Default for FederationRegistry {
+    /// An empty registry with the default `federation.default_timeout_ms`.
+    fn default() -> Self {
+        Self {
+            sources: RwLock::new(BTreeMap::new()),
+            default_timeout: Duration::from_millis(FederationConfig::default().default_timeout_ms),
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+impl FederationRegistry {
+    /// Build the registry from `federation:`. Invalid or duplicate
+    /// sources fail startup.
+    pub fn from_config(config: &FederationConfig) -> Result<Self> {
+        if config.default_timeout_ms == 0 {
+            return Err(invalid("federation.default_timeout_ms must be >= 1"));
+        }
+        let registry = Self {
+            default_timeout: Duration::from_millis(config.default_timeout_ms),
+            ..Self::default()
+        };
+        for source in &config.sources {
+            if registry.register(source.clone())? {
+                return Err(invalid(&format!(
+                    "federation.sources: duplicate source '{}'",
+                    source.name
+                )));
+            }
+        }
+        Ok(registry)
+    }
+
+    /// Add a source, or replace the one with the same name. Returns
+    /// whether a source was replaced.
+    pub fn register(&self, source: FederatedSourceConfig) -> Result<bool> {
+        validate_source(&source)?;
+        Ok(self
+            .sources
+            .write()
+            .insert(source.name.clone(), source)
+            .is_some())
+    }
+
+    /// Remove a source. Returns whether it existed.
+    pub fn remove(&self, name: &str) -> bool {
+        self.sources.write().remove(name).is_some()
+    }
+
+    /// The source registered under `name`.
+    pub fn get(&self, name: &str) -> Option<FederatedSourceConfig> {
+        self.sources.read().get(name).cloned()
+    }
+
+    /// Every registered source, ordered by name.
+    pub fn list(&self) -> Vec<FederatedSourceConfig> {
+        self.sources.read().values().cloned().collect()
+    }
+
+    /// Request timeout applied to `source`.
+    pub fn timeout_for(&self, source: &FederatedSourceConfig) -> Duration {
+        source
+            .timeout_ms
+            .map(Duration::from_millis)
+            .unwrap_or(self.default_timeout)
+    }
+
+    /// Query the sources named in `names` concurrently. Returns the hits
+    /// of every source that answered and a report for each source, in
+    /// `names` order. Unknown names are an error; failing sources are not.
+    pub async fn search(
+        &self,
+        names: &[String],
+        query: &FederatedQuery,
+    ) -> Result<(Vec<SourceHits>, Vec<SourceReport>)> {
+        let sources = names
+            .iter()
+            .map(|name| {
+                self.get(name).ok_or_else(|| {
+                    VectorizerError::NotFound(format!("federated source '{}'", name))
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let outcomes = futures::future::join_all(
+            sources
+                .into_iter()
+                .map(|source| self.search_source(source, query)),
+        )
+        .await;
+
+        let mut hits = Vec::with_capacity(outcomes.len());
+        let mut reports = Vec::with_capacity(outcomes.len());
+        for (source_hits, report) in outcomes {
+            hits.extend(source_hits);
+            reports.push(report);
+        }
+        Ok((hits, reports))
+    }
+
+    async fn search_source(
+        &self,
+        source: FederatedSourceConfig,
+        query: &FederatedQuery,
+    ) -> (Option<SourceHits>, SourceReport) {
+        let collections = if source.collections.is_empty() {
+            &query.collections
+        } else {
+            &source.collections
+        };
+        let body = json!({
+            "query": query.query,
+            "collections": collections,
+            "max_per_collection": query.max_per_collection,
+            "max_total_results": (query.max_per_collection * collections.len())
+                .clamp(1, MAX_REMOTE_RESULTS),
+            "cross_collection_reranking": query.cross_collection_reranking,
+        });
+        let url = format!(
+            "{}/multi_collection_search",
+            source.url.trim_end_matches('/')
+        );
+        let mut request = self.client.post(&url).json(&body);
+        if let Some(key) = &source.api_key {
+            request = request.header("X-API-Key", key.expose_secret().as_str());
+        }
+
+        let timeout = self.timeout_for(&source);
+        let started = Instant::now();
+        let outcome = tokio::time::timeout(timeout, async {
+            let response = request.send().await.map_err(|e| e.to_string())?;
+            let status = response.status();
+            if !status.is_success() {
+                return Err(format!("HTTP {}", status));
+            }
+            let body: Value = response.json().await.map_err(|e| e.to_string())?;
+            parse_results(body)
+        })
+        .await;
+        let latency_ms = started.elapsed().as_millis() as u64;
+
+        let (status, results, error) = match outcome {
+            Ok(Ok(results)) => (SourceStatus::Ok, results, None),
+            Ok(Err(e)) => (SourceStatus::Error, Vec::new(), Some(e)),
+            Err(_) => (
+                SourceStatus::Timeout,
+                Vec::new(),
+                Some(format!("no response within {} ms", timeout.as_millis())),
+            ),
+        };
+        match &error {
+            Some(e) => warn!("Federated source '{}' ({}) failed: {}", source.name, url, e),
+            None => debug!(
+                "Federated source '{}' returned {} hit(s) in {} ms",
+                source.name,
+                results.len(),
+                latency_ms
+            ),
+        }
+
+        let report = SourceReport {
+            source: source.name.clone(),
+            status,
+            results: results.len(),
+            latency_ms,
+            error,
+        };
+        let hits = (status == SourceStatus::Ok).then(|| SourceHits {
+            source: source.name,
+            weight: source.weight,
+            results,
+        });
+        (hits, report)
+    }
+}
+
+/// Normalize each source's scores, apply its weight, and merge all hits
+/// into one ranking of at most `limit`, best first.
+///
+/// Every merged hit carries its source under [`SOURCE_METADATA_KEY`];
+/// `score_breakdown.relevance` keeps the score the source reported and
+/// `score` / `score_breakdown.final_score` hold the merged score.
+pub fn merge(
+    sources: Vec<SourceHits>,
+    normalization: ScoreNormalization,
+    limit: usize,
+) -> Vec<IntelligentSearchResult> {
+    let mut merged = Vec::new();
+    for SourceHits {
+        source,
+        weight,
+        mut results,
+    } in sources
+    {
+        let mut scores: Vec<f32> = results.iter().map(|r| r.score).collect();
+        normalization.apply(&mut scores);
+        for (result, normalized) in results.iter_mut().zip(scores) {
+            let final_score = normalized * weight;
+            result.score_breakdown = Some(ScoreBreakdown {
+                relevance: result.score,
+                collection_bonus: 0.0,
+                technical_bonus: 0.0,
+                final_score,
+            });
+            result.score = final_score;
+            result
+                .metadata
+                .insert(SOURCE_METADATA_KEY.to_string(), json!(source));
+        }
+        merged.extend(results);
+    }
+    merged.sort_by(|a, b| b.score.total_cmp(&a.score));
+    merged.truncate(limit);
+    merged
+}
+
+fn parse_results(body: Value) -> std::result::Result<Vec<IntelligentSearchResult>, String> {
+    let results = body
+        .get("results")
+        .cloned()
+        .ok_or_else(|| "response has no `results` field".to_string())?;
+    serde_json::from_value(results).map_err(|e| format!("unexpected `results` shape: {}", e))
+}
+
+fn validate_source(source: &FederatedSourceConfig) -> Result<()> {
+    if source.name.trim().is_empty() {
+        return Err(invalid("federated source name must not be empty"));
+    }
+    if source.name == LOCAL_SOURCE {
+        return Err(invalid(&format!(
+            "federated source name '{}' is reserved for this server",
+            LOCAL_SOURCE
+        )));
+    }
+    if !(source.url.starts_with("http://") || source.url.starts_with("https://")) {
+        return Err(invalid(&format!(
+            "federated source '{}': url must start with http:// or https://",
+            source.name
+        )));
+    }
+    if !(source.weight.is_finite() && source.weight > 0.0) {
+        return Err(invalid(&format!(
+            "federated source '{}': weight must be > 0",
+            source.name
+        )));
+    }
+    if source.timeout_ms == Some(0) {
+        return Err(invalid(&format!(
+            "federated source '{}': timeout_ms must be >= 1",
+            source.name
+        )));
+    }
+    Ok(())
+}
+
+fn invalid(message: &str) -> VectorizerError {
+    VectorizerError::InvalidConfiguration {
+        message: message.to_string(),
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn source(name: &str, url: &str) -> FederatedSourceConfig {
+        FederatedSourceConfig {
+            name: name.to_string(),
+            url: url.to_string(),
+            api_key: None,
+            timeout_ms: None,
+            weight: 1.0,
+            collections: Vec::new(),
+        }
+    }
+
+    fn hit(id: &str, score: f32) -> IntelligentSearchResult {
+        IntelligentSearchResult {
+            content: String::new(),
+            score,
+            collection: "docs".to_string(),
+            doc_id: id.to_string(),
+            metadata: HashMap::new(),
+            score_breakdown: None,
+        }
+    }
+
+    #[test]
+    fn normalization_rescales_each_list_on_its_own() {
+        let mut scores = [0.25, 0.75, 0.5];
+        ScoreNormalization::MinMax.apply(&mut scores);
+        assert_eq!(scores, [0.0, 1.0, 0.5]);
+
+        let mut flat = [0.3, 0.3];
+        ScoreNormalization::MinMax.apply(&mut flat);
+        assert_eq!(flat, [1.0, 1.0]);
+
+        let mut scores = [1.0, 3.0];
+        ScoreNormalization::ZScore.apply(&mut scores);
+        assert_eq!(scores, [-1.0, 1.0]);
+
+        let mut raw = [7.0, 2.0];
+        ScoreNormalization::None.apply(&mut raw);
+        assert_eq!(raw, [7.0, 2.0]);
+
+        let mut scores = [0.1, 30.0, 2.0];
+        ScoreNormalization::Rrf.apply(&mut scores);
+        assert_eq!(scores, [1.0 / 63.0, 1.0 / 61.0, 1.0 / 62.0]);
+
+        assert_eq!(
+            ScoreNormalization::parse("z_score"),
+            Some(ScoreNormalization::ZScore)
+        );
+        assert_eq!(
+            ScoreNormalization::parse("rrf"),
+            Some(ScoreNormalization::Rrf)
+        );
+        assert_eq!(ScoreNormalization::parse("zscore"), None);
+    }
+
+    #[test]
+    fn merge_puts_sources_on_one_scale_and_tags_them() {
+        // The remote scores on a much larger scale; raw merging would
+        // bury every local hit.
+        let local = SourceHits {
+            source: LOCAL_SOURCE.to_string(),
+            weight: 1.0,
+            results: vec![hit("l1", 0.9), hit("l2", 0.5)],
+        };
+        let remote = SourceHits {
+            source: "team-a".to_string(),
+            weight: 0.5,
+            results: vec![hit("r1", 40.0), hit("r2", 10.0)],
+        };
+
+        let merged = merge(vec![local, remote], ScoreNormalization::MinMax, 3);
+        let ids: Vec<&str> = merged.iter().map(|r| r.doc_id.as_str()).collect();
+        assert_eq!(ids, ["l1", "r1", "l2"]);
+        assert_eq!(merged[1].score, 0.5);
+        assert_eq!(merged[1].metadata[SOURCE_METADATA_KEY], json!("team-a"));
+        assert_eq!(merged[1].score_breakdown.as_ref().unwrap().relevance, 40.0);
+        assert_eq!(merged[0].metadata[SOURCE_METADATA_KEY], json!(LOCAL_SOURCE));
+    }
+
+    #[test]
+    fn registry_validates_sources() {
+        let registry = FederationRegistry::default();
+        assert!(registry.register(source("local", "http://x")).is_err());
+        assert!(registry.register(source("a", "ftp://x")).is_err());
+        let mut heavy = source("a", "http://x");
+        heavy.weight = 0.0;
+        assert!(registry.register(heavy).is_err());
+
+        assert!(!registry.register(source("a", "http://x")).unwrap());
+        assert!(registry.register(source("a", "http://y")).unwrap());
+        assert_eq!(registry.get("a").unwrap().url, "http://y");
+        assert!(registry.remove("a"));
+        assert!(registry.list().is_empty());
+
+        let duplicated = FederationConfig {
+            sources: vec![source("a", "http://x"), source("a", "http://y")],
+            ..Default::default()
+        };
+        assert!(FederationRegistry::from_config(&duplicated).is_err());
+    }
+
+    #[tokio::test]
+    async fn unreachable_source_is_reported_not_fatal() {
+        // Bind and drop a listener to get a port nothing listens on.
+        let port = {
+            let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            listener.local_addr().unwrap().port()
+        };
+        let registry = FederationRegistry::default();
+        registry
+            .register(source("down", &format!("http://127.0.0.1:{port}")))
+            .unwrap();
+
+        let query = FederatedQuery {
+            query: "anything".to_string(),
+            collections: vec!["docs".to_string()],
+            max_per_collection: 5,
+            cross_collection_reranking: false,
+        };
+        let (hits, reports) = registry
+            .search(&["down".to_string()], &query)
+            .await
+            .unwrap();
+        assert!(hits.is_empty());
+        assert_eq!(reports[0].status, SourceStatus::Error);
+        assert!(reports[0].error.is_some());
+
+        assert!(
+            registry
+                .search(&["missing".to_string()], &query)
+                .await
+                .is_err()
+        );
+    }
+}
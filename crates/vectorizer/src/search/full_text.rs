@@ -0,0 +1,421 @@
+//! Lexical (BM25) search over payload text fields.
+//!
+//! Dense embeddings are poor at exact identifier lookups — an error
+//! code like `E0425` or a function name like `parse_header_v2` is
+//! usually split into sub-word pieces that score close to unrelated
+//! text. This module ranks vectors purely by their payload text with
+//! Okapi BM25, independent of the HNSW index, and returns a highlighted
+//! snippet per matching field.
+//!
+//! Ranking reads the collection's text payload indexes (see
+//! [`PayloadIndex`]), which keep per-term postings up to date on every
+//! write, so a query costs the postings of its terms rather than a scan
+//! of the collection. Only text-indexed fields can be searched.
+//!
+//! Documents and queries are split by the tokenizer in
+//! [`crate::db::text_tokenizer`], which keeps identifiers like
+//! `parse_header_v2` and `E0425` whole.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::db::payload_index::PayloadIndex;
+use crate::db::text_tokenizer::token_spans;
+pub use crate::db::text_tokenizer::{Bm25Params, tokenize};
+use crate::error::{Result, VectorizerError};
+pub use crate::models::DEFAULT_FULL_TEXT_FIELDS;
+use crate::models::Payload;
+
+/// Tag wrapped around matched terms in highlight snippets; the text
+/// around it is HTML-escaped.
+const HIGHLIGHT_PRE: &str = "<em>";
+const HIGHLIGHT_POST: &str = "</em>";
+
+/// A lexical query against one collection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FullTextQuery {
+    /// Free-text query; tokenized with the same rules as the documents.
+    pub query: String,
+    /// Payload fields to search (dot notation for nested keys). Empty
+    /// means [`DEFAULT_FULL_TEXT_FIELDS`].
+    #[serde(default)]
+    pub fields: Vec<String>,
+    /// Maximum number of hits returned.
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+    /// Require every query term to appear in the document.
+    #[serde(default)]
+    pub match_all: bool,
+    /// Produce highlight snippets for matched fields.
+    #[serde(default = "default_highlight")]
+    pub highlight: bool,
+    /// Approximate snippet length in characters.
+    #[serde(default = "default_snippet_chars")]
+    pub snippet_chars: usize,
+    /// BM25 parameters.
+    #[serde(default)]
+    pub bm25: Bm25Params,
+}
+
+fn default_limit() -> usize {
+    10
+}
+
+fn default_highlight() -> bool {
+    true
+}
+
+fn default_snippet_chars() -> usize {
+    160
+}
+
+impl FullTextQuery {
+    /// Build a query with default options.
+    pub fn new(query: impl Into<String>) -> Self {
+        Self {
+            query: query.into(),
+            fields: Vec::new(),
+            limit: default_limit(),
+            match_all: false,
+            highlight: default_highlight(),
+            snippet_chars: default_snippet_chars(),
+            bm25: Bm25Params::default(),
+        }
+    }
+
+    /// Fields actually searched, after applying the default.
+    pub fn effective_fields(&self) -> Vec<String> {
+        if self.fields.is_empty() {
+            DEFAULT_FULL_TEXT_FIELDS
+                .iter()
+                .map(|f| (*f).to_string())
+                .collect()
+        } else {
+            self.fields.clone()
+        }
+    }
+}
+
+/// One ranked lexical hit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FullTextHit {
+    /// Vector ID.
+    pub id: String,
+    /// BM25 score (unbounded, higher is better).
+    pub score: f32,
+    /// Query terms that matched this document.
+    pub matched_terms: Vec<String>,
+    /// Field name -> HTML-escaped snippet with matched terms wrapped in
+    /// `<em>`.
+    #[serde(skip_serializing_if = "HashMap::is_empty", default)]
+    pub highlights: HashMap<String, String>,
+    /// Payload of the matching vector.
+    pub payload: Option<Payload>,
+}
+
+/// Resolve a dot-notation field against a payload and flatten it to text.
+/// Arrays of strings are joined with newlines; other scalars are skipped.
+fn field_text(payload: &Payload, field: &str) -> Option<String> {
+    let mut current = &payload.data;
+    for key in field.split('.') {
+        current = current.as_object()?.get(key)?;
+    }
+    match current {
+        Value::String(s) => Some(s.clone()),
+        Value::Array(items) => {
+            let parts: Vec<&str> = items.iter().filter_map(|v| v.as_str()).collect();
+            if parts.is_empty() {
+                None
+            } else {
+                Some(parts.join("\n"))
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Build a snippet of roughly `max_chars` around the first matched
+/// token, wrapping every matched token inside the window.
+fn highlight(text: &str, terms: &HashSet<String>, max_chars: usize) -> Option<String> {
+    let spans: Vec<(usize, usize)> = token_spans(text)
+        .into_iter()
+        .filter(|(s, e)| terms.contains(&text[*s..*e].to_lowercase()))
+        .collect();
+    let (first_start, _) = *spans.first()?;
+
+    // Centre the window on the first hit, snapped to char boundaries.
+    let half = max_chars / 2;
+    let mut window_start = first_start.saturating_sub(half);
+    while !text.is_char_boundary(window_start) {
+        window_start -= 1;
+    }
+    let mut window_end = (window_start + max_chars).min(text.len());
+    while !text.is_char_boundary(window_end) {
+        window_end += 1;
+    }
+
+    let mut out = String::with_capacity(window_end - window_start + 32);
+    if window_start > 0 {
+        out.push('…');
+    }
+    let mut cursor = window_start;
+    for (s, e) in spans {
+        if s < window_start || e > window_end {
+            continue;
+        }
+        push_escaped(&mut out, &text[cursor..s]);
+        out.push_str(HIGHLIGHT_PRE);
+        push_escaped(&mut out, &text[s..e]);
+        out.push_str(HIGHLIGHT_POST);
+        cursor = e;
+    }
+    push_escaped(&mut out, &text[cursor..window_end]);
+    if window_end < text.len() {
+        out.push('…');
+    }
+    Some(out)
+}
+
+/// Append `text` to `out` with the HTML special characters escaped, so
+/// payload text cannot inject markup into a snippet.
+fn push_escaped(out: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+}
+
+/// Rank a collection's vectors against `query` with BM25 over the text
+/// indexes of the requested payload fields. Each field is scored with
+/// its own statistics and a vector's field scores are summed. Only the
+/// postings of the query terms are read; `payload_of` loads the payload
+/// of each returned hit. Fields without a text index are rejected.
+pub fn search(
+    index: &PayloadIndex,
+    query: &FullTextQuery,
+    payload_of: impl Fn(&str) -> Option<Payload>,
+) -> Result<Vec<FullTextHit>> {
+    let fields = query.effective_fields();
+    if let Some(field) = fields.iter().find(|f| !index.has_text_index(f)) {
+        return Err(VectorizerError::InvalidConfiguration {
+            message: format!(
+                "payload field '{}' has no Text index; add one to search it",
+                field
+            ),
+        });
+    }
+
+    let query_terms: Vec<String> = {
+        let mut seen = HashSet::new();
+        tokenize(&query.query)
+            .into_iter()
+            .filter(|t| seen.insert(t.clone()))
+            .collect()
+    };
+    if query_terms.is_empty() || query.limit == 0 {
+        return Ok(Vec::new());
+    }
+
+    // Vector ID -> summed score and the query terms it matched
+    let mut matches: HashMap<String, (f32, BTreeSet<String>)> = HashMap::new();
+    for field in &fields {
+        for (id, term, score) in index
+            .bm25_text(field, &query_terms, query.bm25)
+            .unwrap_or_default()
+        {
+            let entry = matches.entry(id).or_default();
+            entry.0 += score;
+            entry.1.insert(term);
+        }
+    }
+
+    let mut scored: Vec<(String, f32, BTreeSet<String>)> = matches
+        .into_iter()
+        .filter(|(_, (_, terms))| !query.match_all || terms.len() == query_terms.len())
+        .map(|(id, (score, terms))| (id, score, terms))
+        .collect();
+    scored.sort_by(|a, b| {
+        b.1.partial_cmp(&a.1)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.0.cmp(&b.0))
+    });
+    scored.truncate(query.limit);
+
+    let query_set: HashSet<String> = query_terms.into_iter().collect();
+    Ok(scored
+        .into_iter()
+        .map(|(id, score, matched_terms)| {
+            let payload = payload_of(&id);
+            let mut highlights = HashMap::new();
+            if query.highlight
+                && let Some(payload) = payload.as_ref().filter(|p| !p.is_encrypted())
+            {
+                for field in &fields {
+                    if let Some(snippet) = field_text(payload, field)
+                        .and_then(|text| highlight(&text, &query_set, query.snippet_chars))
+                    {
+                        highlights.insert(field.clone(), snippet);
+                    }
+                }
+            }
+            FullTextHit {
+                id,
+                score,
+                matched_terms: matched_terms.into_iter().collect(),
+                highlights,
+                payload,
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::db::payload_index::{PayloadIndexConfig, PayloadIndexType};
+
+    /// A text index over `fields` holding `docs`, and their payloads.
+    fn corpus(
+        fields: &[&str],
+        docs: Vec<(&str, Value)>,
+    ) -> (PayloadIndex, HashMap<String, Payload>) {
+        let index = PayloadIndex::new();
+        for field in fields {
+            index.add_index_config(PayloadIndexConfig::new(
+                (*field).to_string(),
+                PayloadIndexType::Text,
+            ));
+        }
+        let mut payloads = HashMap::new();
+        for (id, data) in docs {
+            let payload = Payload::new(data);
+            index.index_vector(id.to_string(), &payload);
+            payloads.insert(id.to_string(), payload);
+        }
+        (index, payloads)
+    }
+
+    fn contents(docs: &[(&'static str, &str)]) -> (PayloadIndex, HashMap<String, Payload>) {
+        corpus(
+            DEFAULT_FULL_TEXT_FIELDS,
+            docs.iter()
+                .map(|(id, content)| (*id, json!({ "content": content })))
+                .collect(),
+        )
+    }
+
+    fn run(
+        (index, payloads): &(PayloadIndex, HashMap<String, Payload>),
+        query: &FullTextQuery,
+    ) -> Vec<FullTextHit> {
+        search(index, query, |id| payloads.get(id).cloned()).unwrap()
+    }
+
+    #[test]
+    fn exact_identifier_ranks_first() {
+        let corpus = contents(&[
+            ("a", "cannot find value in this scope"),
+            ("b", "error E0425: cannot find value `x` in this scope"),
+            ("c", "unrelated text about scopes and values"),
+        ]);
+        let hits = run(&corpus, &FullTextQuery::new("E0425"));
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].id, "b");
+        assert_eq!(
+            hits[0].highlights.get("content").map(String::as_str),
+            Some("error <em>E0425</em>: cannot find value `x` in this scope")
+        );
+    }
+
+    #[test]
+    fn rarer_terms_score_higher() {
+        let corpus = contents(&[
+            ("common", "value value value"),
+            ("rare", "value parse_header_v2"),
+            ("other", "value"),
+        ]);
+        let hits = run(&corpus, &FullTextQuery::new("value parse_header_v2"));
+        assert_eq!(hits[0].id, "rare");
+        assert_eq!(hits.len(), 3);
+    }
+
+    #[test]
+    fn match_all_requires_every_term() {
+        let corpus = contents(&[("a", "alpha beta"), ("b", "alpha")]);
+        let mut q = FullTextQuery::new("alpha beta");
+        q.match_all = true;
+        let hits = run(&corpus, &q);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].id, "a");
+    }
+
+    #[test]
+    fn custom_nested_fields_and_skips_missing() {
+        let corpus = corpus(
+            &["content", "meta.symbol"],
+            vec![
+                ("n", json!({ "meta": { "symbol": "TokenStream" } })),
+                ("plain", json!({ "content": "TokenStream" })),
+            ],
+        );
+        let mut q = FullTextQuery::new("tokenstream");
+        q.fields = vec!["meta.symbol".to_string()];
+        let hits = run(&corpus, &q);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].id, "n");
+    }
+
+    #[test]
+    fn unindexed_fields_are_rejected() {
+        let (index, _) = contents(&[("a", "alpha")]);
+        let mut q = FullTextQuery::new("alpha");
+        q.fields = vec!["title".to_string()];
+        assert!(matches!(
+            search(&index, &q, |_| None),
+            Err(VectorizerError::InvalidConfiguration { .. })
+        ));
+    }
+
+    #[test]
+    fn removed_and_replaced_texts_leave_the_index() {
+        let corpus = contents(&[("a", "alpha"), ("b", "alpha beta")]);
+        corpus.0.remove_vector("a");
+        corpus.0.index_vector(
+            "b".to_string(),
+            &Payload::new(json!({ "content": "gamma" })),
+        );
+        assert!(run(&corpus, &FullTextQuery::new("alpha")).is_empty());
+        assert_eq!(run(&corpus, &FullTextQuery::new("gamma"))[0].id, "b");
+    }
+
+    #[test]
+    fn snippets_escape_payload_markup() {
+        let corpus = contents(&[("a", "<script>alert('needle')</script> & more")]);
+        let hits = run(&corpus, &FullTextQuery::new("needle"));
+        assert_eq!(
+            hits[0].highlights["content"],
+            "&lt;script&gt;alert(&#39;<em>needle</em>&#39;)&lt;/script&gt; &amp; more"
+        );
+    }
+
+    #[test]
+    fn snippet_is_windowed_on_long_text() {
+        let long = format!("{} needle {}", "x ".repeat(200), "y ".repeat(200));
+        let terms: HashSet<String> = ["needle".to_string()].into_iter().collect();
+        let snippet = highlight(&long, &terms, 40).unwrap();
+        assert!(snippet.starts_with('…'));
+        assert!(snippet.ends_with('…'));
+        assert!(snippet.contains("<em>needle</em>"));
+    }
+}
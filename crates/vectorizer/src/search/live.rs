@@ -0,0 +1,353 @@
+//! Live queries — standing searches told when a write enters their
+//! top-k.
+//!
+//! A [`LiveQuery`] holds a query vector, an optional payload filter and
+//! the current top-`k` of its collection. Fed the store's
+//! [change events](crate::db::change_feed), it scores every vector that
+//! is inserted or updated in the collection and reports the ones that
+//! now rank in the top-`k`, so a client can ask "tell me when a
+//! document like this arrives" instead of re-running the search on a
+//! timer.
+//!
+//! The baseline top-`k` comes from the collection's regular (HNSW)
+//! search; with a filter it is taken from `k * FILTER_OVERSAMPLE`
+//! candidates. Deleting a vector of the top-`k`, or an update that
+//! pushes one out, recomputes the baseline.
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::db::change_feed::{ChangeEvent, ChangeKind};
+use crate::db::{CollectionType, VectorStore};
+use crate::error::{Result, VectorizerError};
+use crate::models::qdrant::{FilterProcessor, QdrantFilter};
+use crate::models::{Payload, SearchResult};
+
+/// Most results a live query tracks.
+pub const MAX_LIVE_QUERY_K: usize = 100;
+
+/// Candidates taken per tracked result when a filter has to be applied
+/// to the baseline search.
+const FILTER_OVERSAMPLE: usize = 10;
+
+/// A vector that entered a live query's top-`k`.
+#[derive(Debug, Clone, Serialize)]
+pub struct LiveMatch {
+    /// Vector ID.
+    pub vector_id: String,
+    /// Whether it was inserted or updated.
+    pub kind: ChangeKind,
+    /// Score against the query, on the collection's search scale.
+    pub score: f32,
+    /// Its position in the top-`k`, from 1.
+    pub rank: usize,
+    /// Sequence number of the change that put it there.
+    pub seq: u64,
+    /// The vector's payload.
+    pub payload: Option<Value>,
+}
+
+/// A standing top-`k` search over one collection.
+#[derive(Debug, Clone)]
+pub struct LiveQuery {
+    collection: String,
+    query: Vec<f32>,
+    k: usize,
+    filter: Option<QdrantFilter>,
+    min_score: Option<f32>,
+    /// Current top-`k`, best first.
+    top: Vec<(String, f32)>,
+}
+
+impl LiveQuery {
+    /// Register a live query on `collection` and run its baseline
+    /// search. Returns the query and the current top-`k` results.
+    pub fn new(
+        store: &VectorStore,
+        collection: &str,
+        query: Vec<f32>,
+        k: usize,
+        filter: Option<QdrantFilter>,
+        min_score: Option<f32>,
+    ) -> Result<(Self, Vec<SearchResult>)> {
+        if k == 0 || k > MAX_LIVE_QUERY_K {
+            return Err(VectorizerError::InvalidConfiguration {
+                message: format!("k must be between 1 and {}", MAX_LIVE_QUERY_K),
+            });
+        }
+        let collection = store.get_collection(collection)?;
+        if query.len() != collection.config().dimension {
+            return Err(VectorizerError::InvalidDimension {
+                expected: collection.config().dimension,
+                got: query.len(),
+            });
+        }
+        let mut live = Self {
+            collection: collection.name().to_string(),
+            query,
+            k,
+            filter,
+            min_score,
+            top: Vec::new(),
+        };
+        let results = live.baseline(&collection)?;
+        Ok((live, results))
+    }
+
+    /// Canonical name of the collection the query runs on.
+    pub fn collection(&self) -> &str {
+        &self.collection
+    }
+
+    /// Number of results tracked.
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// Score of the `k`-th result, below which a new vector does not
+    /// enter the top-`k`. `None` while fewer than `k` vectors match.
+    pub fn threshold(&self) -> Option<f32> {
+        if self.top.len() < self.k {
+            return None;
+        }
+        self.top.last().map(|(_, score)| *score)
+    }
+
+    /// Apply a change event. Returns the match when an inserted or
+    /// updated vector entered the top-`k`.
+    pub fn apply(&mut self, store: &VectorStore, event: &ChangeEvent) -> Result<Option<LiveMatch>> {
+        if event.collection != self.collection {
+            return Ok(None);
+        }
+        // The guard borrows the name it was looked up by
+        let name = self.collection.clone();
+        let collection = store.get_collection(&name)?;
+        let was_member = self.remove(&event.id);
+
+        if event.kind == ChangeKind::Delete {
+            if was_member {
+                self.baseline(&collection)?;
+            }
+            return Ok(None);
+        }
+
+        let candidate = match collection.get_vector(&event.id) {
+            Ok(vector) => vector,
+            // Deleted again since the event was published
+            Err(_) => {
+                if was_member {
+                    self.baseline(&collection)?;
+                }
+                return Ok(None);
+            }
+        };
+        let score = collection
+            .score_vectors(&self.query, std::slice::from_ref(&event.id))?
+            .first()
+            .map(|(_, score)| *score);
+        let rank = match score {
+            Some(score) if self.admits(candidate.payload.as_ref(), score) => {
+                self.top.partition_point(|(_, s)| *s >= score) + 1
+            }
+            _ => usize::MAX,
+        };
+
+        if rank > self.k {
+            if was_member {
+                // Pushed out of the top-k: the next best takes its place
+                self.baseline(&collection)?;
+            }
+            return Ok(None);
+        }
+        let score = score.unwrap_or_default();
+        self.top.insert(rank - 1, (event.id.clone(), score));
+        self.top.truncate(self.k);
+        if was_member {
+            return Ok(None);
+        }
+        Ok(Some(LiveMatch {
+            vector_id: event.id.clone(),
+            kind: event.kind,
+            score,
+            rank,
+            seq: event.seq,
+            payload: candidate.payload.map(|p| p.data),
+        }))
+    }
+
+    /// Whether a vector with `payload` scoring `score` passes the filter
+    /// and the minimum score.
+    fn admits(&self, payload: Option<&Payload>, score: f32) -> bool {
+        if self.min_score.is_some_and(|min| score < min) {
+            return false;
+        }
+        match (&self.filter, payload) {
+            (None, _) => true,
+            (Some(filter), Some(payload)) => FilterProcessor::apply_filter(filter, payload),
+            (Some(filter), None) => FilterProcessor::apply_filter(
+                filter,
+                &Payload::new(Value::Object(Default::default())),
+            ),
+        }
+    }
+
+    /// Drop `id` from the top-`k`. True when it was in it.
+    fn remove(&mut self, id: &str) -> bool {
+        let before = self.top.len();
+        self.top.retain(|(member, _)| member != id);
+        self.top.len() != before
+    }
+
+    /// Recompute the top-`k` from a search of the collection.
+    fn baseline(&mut self, collection: &CollectionType) -> Result<Vec<SearchResult>> {
+        let candidates = if self.filter.is_some() {
+            self.k * FILTER_OVERSAMPLE
+        } else {
+            self.k
+        };
+        let mut results = collection.search(&self.query, candidates)?;
+        results.retain(|r| self.admits(r.payload.as_ref(), r.score));
+        results.truncate(self.k);
+        self.top = results.iter().map(|r| (r.id.clone(), r.score)).collect();
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use chrono::Utc;
+    use serde_json::json;
+
+    use super::*;
+    use crate::models::qdrant::{QdrantCondition, QdrantFilterBuilder};
+    use crate::models::{CollectionConfig, DistanceMetric, QuantizationConfig, Vector};
+
+    fn store() -> VectorStore {
+        let store = VectorStore::new_cpu_only();
+        store
+            .create_collection(
+                "live",
+                CollectionConfig {
+                    dimension: 2,
+                    metric: DistanceMetric::Euclidean,
+                    quantization: QuantizationConfig::None,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        store
+    }
+
+    fn vector(id: &str, x: f32, lang: &str) -> Vector {
+        Vector::with_payload(
+            id.to_string(),
+            vec![x, 0.0],
+            Payload::new(json!({ "lang": lang })),
+        )
+    }
+
+    fn event(id: &str, kind: ChangeKind) -> ChangeEvent {
+        ChangeEvent {
+            seq: 1,
+            collection: "live".to_string(),
+            id: id.to_string(),
+            kind,
+            payload_hash: None,
+            timestamp: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_reports_vectors_entering_the_top_k() {
+        let store = store();
+        store
+            .insert(
+                "live",
+                vec![vector("far", 5.0, "en"), vector("mid", 2.0, "en")],
+            )
+            .unwrap();
+        let (mut live, initial) =
+            LiveQuery::new(&store, "live", vec![0.0, 0.0], 2, None, None).unwrap();
+        assert_eq!(initial.len(), 2);
+        let far_score = live.threshold().unwrap();
+
+        // Closer than both: enters at rank 1 and pushes "far" out
+        store
+            .insert("live", vec![vector("near", 1.0, "en")])
+            .unwrap();
+        let hit = live
+            .apply(&store, &event("near", ChangeKind::Insert))
+            .unwrap()
+            .unwrap();
+        assert_eq!((hit.vector_id.as_str(), hit.rank), ("near", 1));
+        assert_eq!(hit.payload, Some(json!({ "lang": "en" })));
+        assert!(live.threshold().unwrap() > far_score);
+
+        // Farther than the current k-th: nothing
+        store
+            .insert("live", vec![vector("remote", 9.0, "en")])
+            .unwrap();
+        assert!(
+            live.apply(&store, &event("remote", ChangeKind::Insert))
+                .unwrap()
+                .is_none()
+        );
+
+        // Deleting a member brings "far" back into the top-k
+        store.delete("live", "near").unwrap();
+        assert!(
+            live.apply(&store, &event("near", ChangeKind::Delete))
+                .unwrap()
+                .is_none()
+        );
+        assert_eq!(live.threshold(), Some(far_score));
+    }
+
+    #[test]
+    fn test_filter_and_min_score() {
+        let store = store();
+        let filter = QdrantFilterBuilder::new()
+            .must(QdrantCondition::match_string("lang", "pt"))
+            .build();
+        let (mut live, initial) =
+            LiveQuery::new(&store, "live", vec![0.0, 0.0], 3, Some(filter), Some(0.2)).unwrap();
+        assert!(initial.is_empty());
+        assert_eq!(live.threshold(), None);
+
+        store
+            .insert(
+                "live",
+                vec![
+                    vector("en", 1.0, "en"),
+                    vector("pt", 1.0, "pt"),
+                    vector("pt-far", 10.0, "pt"),
+                ],
+            )
+            .unwrap();
+        assert!(
+            live.apply(&store, &event("en", ChangeKind::Insert))
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            live.apply(&store, &event("pt", ChangeKind::Insert))
+                .unwrap()
+                .is_some()
+        );
+        // 1 / (1 + 10) is under min_score
+        assert!(
+            live.apply(&store, &event("pt-far", ChangeKind::Insert))
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_rejects_bad_queries() {
+        let store = store();
+        assert!(LiveQuery::new(&store, "live", vec![0.0; 3], 5, None, None).is_err());
+        assert!(LiveQuery::new(&store, "live", vec![0.0; 2], 0, None, None).is_err());
+        assert!(LiveQuery::new(&store, "missing", vec![0.0; 2], 5, None, None).is_err());
+    }
+}
@@ -1,13 +1,54 @@
-//! Advanced search and discovery system
+//! Search building blocks that sit beside the vector index:
+//! - Mean and medoid vectors of a set of stored vectors ([`aggregate`])
+//! - Lexical BM25 search over payload text ([`full_text`])
+//! - Pairwise similarity matrices over chosen vectors ([`similarity_matrix`])
+//! - 2D projections of a sample for scatter plots ([`projection`])
+//! - Near-duplicate groups across a collection ([`duplicates`])
+//! - k-means and HDBSCAN clusters of a collection ([`clustering`])
+//! - Value counts of payload fields for filter UIs ([`facets`])
+//! - Multi-collection search fanned out to remote servers ([`federation`])
+//! - Standing top-k queries notified of new matches ([`live`])
+//! - Named, parameterized searches stored by admins ([`templates`])
+//! - A/B tests of two retrieval configurations on live traffic
+//!   ([`experiments`])
 //!
-//! This module provides sophisticated search capabilities including:
-//! - Multi-modal search (text, vector, hybrid)
-//! - Advanced ranking algorithms
-//! - Search result clustering and deduplication
-//! - Query expansion and suggestion
-//! - Search analytics and optimization
-//! - Real-time search updates
+//! `advanced_search/` (multi-modal ranking, query expansion, analytics) is
+//! an orphan — not declared here, so not part of the build — until its
+//! engine is wired to the vector store.
 
-pub mod advanced_search;
+pub mod aggregate;
+pub mod clustering;
+pub mod duplicates;
+pub mod experiments;
+pub mod facets;
+pub mod federation;
+pub mod full_text;
+pub mod live;
+pub mod projection;
+pub mod similarity_matrix;
+pub mod templates;
 
-pub use advanced_search::*;
+pub use aggregate::{Aggregate, AggregateMethod, MEDOID_CANDIDATES};
+pub use clustering::{
+    ClusterAlgorithm, ClusterSummary, Clustering, KMeansOptions, MAX_CLUSTERS, MAX_HDBSCAN_VECTORS,
+};
+pub use duplicates::{
+    DEFAULT_DUPLICATE_NEIGHBORS, DEFAULT_DUPLICATE_THRESHOLD, DuplicateGroup,
+    MAX_DUPLICATE_NEIGHBORS,
+};
+pub use experiments::{
+    EXPERIMENTS_FILE, Experiment, ExperimentArm, ExperimentReport, ExperimentStore, LabelledQuery,
+    Variant, VariantEvaluation,
+};
+pub use facets::{DEFAULT_FACET_LIMIT, Facet, FacetValue, MAX_FACET_KEYS, MAX_FACET_LIMIT};
+pub use federation::{FederationRegistry, ScoreNormalization};
+pub use full_text::{FullTextHit, FullTextQuery};
+pub use live::{LiveMatch, LiveQuery, MAX_LIVE_QUERY_K};
+pub use projection::{
+    DEFAULT_PROJECTION_SAMPLE, MAX_PROJECTION_SAMPLE, Projection, ProjectionMethod,
+};
+pub use similarity_matrix::{MAX_SIMILARITY_MATRIX_IDS, MatrixKind, SimilarityMatrix};
+pub use templates::{
+    SEARCH_TEMPLATES_FILE, SearchTemplate, SearchTemplateStore, TemplateHit, TemplateHybrid,
+    TemplateParameter, TemplateRun,
+};
@@ -0,0 +1,480 @@
+//! Two-dimensional projections of a vector sample, for scatter plots.
+//!
+//! The dashboard plots a collection by asking the server to project a
+//! bounded sample (at most [`MAX_PROJECTION_SAMPLE`] vectors) down to 2D,
+//! so clients never download raw embeddings. Two methods:
+//!
+//! - **PCA**, always available: the sample is centred and projected onto
+//!   its top two principal axes, found by power iteration without ever
+//!   building the `d × d` covariance matrix.
+//! - **UMAP**, behind the `umap` feature: exact k-nearest neighbours over
+//!   the sample, the fuzzy simplicial set built from them, and a
+//!   PCA-initialised layout refined by SGD with negative sampling. It is
+//!   a compact take on the reference algorithm, not a port of it; the
+//!   exact neighbour pass is `O(n²)`, which the sample cap keeps cheap.
+//!
+//! Cosine collections are normalised on a copy first, so both methods see
+//! the geometry the index searches in. Given the same sample and seed the
+//! output is repeatable.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, VectorizerError};
+use crate::models::{DistanceMetric, Vector};
+use crate::simd;
+
+/// Most vectors one projection may sample.
+pub const MAX_PROJECTION_SAMPLE: usize = 2_000;
+
+/// Vectors sampled when the caller does not say.
+pub const DEFAULT_PROJECTION_SAMPLE: usize = 500;
+
+/// Power iteration rounds per principal axis.
+const PCA_ITERATIONS: usize = 100;
+/// Change in the axis below which power iteration stops early.
+const PCA_TOLERANCE: f32 = 1e-6;
+
+/// Neighbours per point in the UMAP graph.
+#[cfg(feature = "umap")]
+const UMAP_NEIGHBORS: usize = 15;
+/// SGD passes over the UMAP graph.
+#[cfg(feature = "umap")]
+const UMAP_EPOCHS: usize = 200;
+/// Repulsive samples drawn per attractive update.
+#[cfg(feature = "umap")]
+const UMAP_NEGATIVE_SAMPLES: usize = 5;
+/// Curve parameters fitted for `min_dist = 0.1`, `spread = 1.0`, the
+/// reference defaults.
+#[cfg(feature = "umap")]
+const UMAP_A: f32 = 1.577;
+#[cfg(feature = "umap")]
+const UMAP_B: f32 = 0.895;
+/// Half-width of the box the PCA layout is scaled into before SGD.
+#[cfg(feature = "umap")]
+const UMAP_INIT_EXTENT: f32 = 10.0;
+
+/// How a sample is brought down to two dimensions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProjectionMethod {
+    /// Principal component analysis; linear and fast.
+    #[default]
+    Pca,
+    /// Uniform manifold approximation; keeps local neighbourhoods, needs
+    /// the `umap` feature.
+    Umap,
+}
+
+impl ProjectionMethod {
+    /// Whether this build can compute the method.
+    pub fn is_available(self) -> bool {
+        match self {
+            Self::Pca => true,
+            Self::Umap => cfg!(feature = "umap"),
+        }
+    }
+}
+
+/// Result of [`project`].
+#[derive(Debug, Clone, Serialize)]
+pub struct Projection {
+    /// Method the coordinates came from.
+    pub method: ProjectionMethod,
+    /// `[x, y]` per input vector, in input order.
+    pub points: Vec<[f32; 2]>,
+    /// Share of the sample's variance along each PCA axis; `None` for
+    /// UMAP, whose axes carry no such meaning.
+    pub explained_variance: Option<[f32; 2]>,
+}
+
+/// Project `vectors` to 2D with `method`. `seed` drives UMAP's sampling
+/// and is ignored by PCA, which is deterministic.
+pub fn project(
+    vectors: &[Vector],
+    metric: DistanceMetric,
+    method: ProjectionMethod,
+    seed: u64,
+) -> Result<Projection> {
+    if !method.is_available() {
+        return Err(VectorizerError::InvalidConfiguration {
+            message: "UMAP projection needs a build with the `umap` feature".to_string(),
+        });
+    }
+
+    let rows: Vec<Vec<f32>> = vectors
+        .iter()
+        .map(|v| {
+            let mut data = v.data.clone();
+            if metric == DistanceMetric::Cosine {
+                simd::normalize_in_place(&mut data);
+            }
+            data
+        })
+        .collect();
+
+    let (points, explained_variance) = pca(&rows);
+    #[cfg(feature = "umap")]
+    if method == ProjectionMethod::Umap {
+        return Ok(Projection {
+            method,
+            points: umap(&rows, &points, seed),
+            explained_variance: None,
+        });
+    }
+    #[cfg(not(feature = "umap"))]
+    let _ = seed;
+
+    Ok(Projection {
+        method,
+        points,
+        explained_variance: Some(explained_variance),
+    })
+}
+
+/// Coordinates of `rows` on their top two principal axes, and the share
+/// of the total variance each axis explains.
+fn pca(rows: &[Vec<f32>]) -> (Vec<[f32; 2]>, [f32; 2]) {
+    let n = rows.len();
+    let Some(dimension) = rows.first().map(Vec::len) else {
+        return (Vec::new(), [0.0, 0.0]);
+    };
+
+    let mut mean = vec![0.0f32; dimension];
+    for row in rows {
+        for (m, x) in mean.iter_mut().zip(row) {
+            *m += x;
+        }
+    }
+    for m in &mut mean {
+        *m /= n as f32;
+    }
+    let centered: Vec<Vec<f32>> = rows
+        .iter()
+        .map(|row| row.iter().zip(&mean).map(|(x, m)| x - m).collect())
+        .collect();
+    let total_variance: f32 = centered
+        .iter()
+        .map(|row| simd::dot_product(row, row))
+        .sum::<f32>()
+        / n as f32;
+
+    let mut axes: Vec<Vec<f32>> = Vec::with_capacity(2);
+    let mut explained = [0.0f32; 2];
+    for (component, share) in explained.iter_mut().enumerate() {
+        let axis = principal_axis(&centered, &axes, component as u64);
+        let variance = centered
+            .iter()
+            .map(|row| simd::dot_product(row, &axis).powi(2))
+            .sum::<f32>()
+            / n as f32;
+        if total_variance > 0.0 {
+            *share = variance / total_variance;
+        }
+        axes.push(axis);
+    }
+
+    let points = centered
+        .iter()
+        .map(|row| {
+            [
+                simd::dot_product(row, &axes[0]),
+                simd::dot_product(row, &axes[1]),
+            ]
+        })
+        .collect();
+    (points, explained)
+}
+
+/// The leading eigenvector of the covariance of `centered`, orthogonal to
+/// `found`, by power iteration on `Xᵀ(Xv)`. All zeros when the sample has
+/// no variance left in that direction.
+fn principal_axis(centered: &[Vec<f32>], found: &[Vec<f32>], seed: u64) -> Vec<f32> {
+    let dimension = centered[0].len();
+    let mut rng = fastrand::Rng::with_seed(seed);
+    let mut axis: Vec<f32> = (0..dimension).map(|_| rng.f32() - 0.5).collect();
+    if !orthonormalize(&mut axis, found) {
+        return vec![0.0; dimension];
+    }
+
+    for _ in 0..PCA_ITERATIONS {
+        let mut next = vec![0.0f32; dimension];
+        for row in centered {
+            let projection = simd::dot_product(row, &axis);
+            for (n, x) in next.iter_mut().zip(row) {
+                *n += projection * x;
+            }
+        }
+        if !orthonormalize(&mut next, found) {
+            return vec![0.0; dimension];
+        }
+        let change: f32 = next.iter().zip(&axis).map(|(a, b)| (a - b).abs()).sum();
+        axis = next;
+        if change < PCA_TOLERANCE {
+            break;
+        }
+    }
+    axis
+}
+
+/// Remove `v`'s components along `basis` and scale it to unit length.
+/// False when nothing is left.
+fn orthonormalize(v: &mut [f32], basis: &[Vec<f32>]) -> bool {
+    for b in basis {
+        let along = simd::dot_product(v, b);
+        for (x, y) in v.iter_mut().zip(b) {
+            *x -= along * y;
+        }
+    }
+    let norm = simd::dot_product(v, v).sqrt();
+    if norm <= f32::EPSILON {
+        return false;
+    }
+    for x in v.iter_mut() {
+        *x /= norm;
+    }
+    true
+}
+
+/// UMAP layout of `rows`, starting from the PCA coordinates `init`.
+#[cfg(feature = "umap")]
+fn umap(rows: &[Vec<f32>], init: &[[f32; 2]], seed: u64) -> Vec<[f32; 2]> {
+    use rayon::prelude::*;
+
+    let n = rows.len();
+    if n < 3 {
+        return init.to_vec();
+    }
+    let k = UMAP_NEIGHBORS.min(n - 1);
+
+    let knn: Vec<Vec<(usize, f32)>> = (0..n)
+        .into_par_iter()
+        .map(|i| {
+            let mut distances: Vec<(usize, f32)> = (0..n)
+                .filter(|&j| j != i)
+                .map(|j| (j, simd::euclidean_distance(&rows[i], &rows[j])))
+                .collect();
+            distances.select_nth_unstable_by(k - 1, |a, b| a.1.total_cmp(&b.1));
+            distances.truncate(k);
+            distances
+        })
+        .collect();
+
+    // Fuzzy simplicial set: each point's membership strengths, combined
+    // across the two directions of an edge by fuzzy union.
+    let target = (k as f32).log2();
+    let mut weights: std::collections::HashMap<(usize, usize), f32> =
+        std::collections::HashMap::new();
+    for (i, neighbors) in knn.iter().enumerate() {
+        let rho = neighbors
+            .iter()
+            .map(|&(_, d)| d)
+            .filter(|&d| d > 0.0)
+            .fold(f32::INFINITY, f32::min);
+        let rho = if rho.is_finite() { rho } else { 0.0 };
+        let sigma = smooth_sigma(neighbors, rho, target);
+        for &(j, d) in neighbors {
+            let w = (-(d - rho).max(0.0) / sigma).exp();
+            weights
+                .entry((i.min(j), i.max(j)))
+                .and_modify(|a| *a = *a + w - *a * w)
+                .or_insert(w);
+        }
+    }
+    let mut edges: Vec<(usize, usize, f32)> =
+        weights.into_iter().map(|((i, j), w)| (i, j, w)).collect();
+    edges.sort_unstable_by_key(|&(i, j, _)| (i, j));
+    let max_weight = edges.iter().map(|e| e.2).fold(0.0f32, f32::max);
+    if max_weight <= 0.0 {
+        return init.to_vec();
+    }
+
+    let extent = init
+        .iter()
+        .flat_map(|p| p.iter().map(|c| c.abs()))
+        .fold(0.0f32, f32::max);
+    let scale = if extent > 0.0 {
+        UMAP_INIT_EXTENT / extent
+    } else {
+        1.0
+    };
+    let mut rng = fastrand::Rng::with_seed(seed);
+    let mut layout: Vec<[f32; 2]> = init
+        .iter()
+        .map(|p| {
+            // A little jitter keeps points PCA put on top of each other
+            // from moving in lockstep.
+            [
+                p[0] * scale + (rng.f32() - 0.5) * 1e-3,
+                p[1] * scale + (rng.f32() - 0.5) * 1e-3,
+            ]
+        })
+        .collect();
+
+    for epoch in 0..UMAP_EPOCHS {
+        let alpha = 1.0 - epoch as f32 / UMAP_EPOCHS as f32;
+        for &(i, j, w) in &edges {
+            // Edges are sampled in proportion to their weight
+            if rng.f32() * max_weight > w {
+                continue;
+            }
+            let d2 = squared_distance(layout[i], layout[j]);
+            if d2 > 0.0 {
+                let coef = -2.0 * UMAP_A * UMAP_B * d2.powf(UMAP_B - 1.0)
+                    / (1.0 + UMAP_A * d2.powf(UMAP_B));
+                for c in 0..2 {
+                    let step = (coef * (layout[i][c] - layout[j][c])).clamp(-4.0, 4.0) * alpha;
+                    layout[i][c] += step;
+                    layout[j][c] -= step;
+                }
+            }
+            for _ in 0..UMAP_NEGATIVE_SAMPLES {
+                let other = rng.usize(..n);
+                if other == i {
+                    continue;
+                }
+                let d2 = squared_distance(layout[i], layout[other]);
+                let coef = 2.0 * UMAP_B / ((0.001 + d2) * (1.0 + UMAP_A * d2.powf(UMAP_B)));
+                for c in 0..2 {
+                    let step = (coef * (layout[i][c] - layout[other][c])).clamp(-4.0, 4.0);
+                    layout[i][c] += step * alpha;
+                }
+            }
+        }
+    }
+    layout
+}
+
+/// The `σ` for which a point's neighbour memberships sum to `target`
+/// (`log2 k`), by bisection.
+#[cfg(feature = "umap")]
+fn smooth_sigma(neighbors: &[(usize, f32)], rho: f32, target: f32) -> f32 {
+    let (mut lo, mut hi, mut mid) = (0.0f32, f32::INFINITY, 1.0f32);
+    for _ in 0..64 {
+        let sum: f32 = neighbors
+            .iter()
+            .map(|&(_, d)| (-(d - rho).max(0.0) / mid).exp())
+            .sum();
+        if (sum - target).abs() < 1e-5 {
+            break;
+        }
+        if sum > target {
+            hi = mid;
+            mid = (lo + hi) / 2.0;
+        } else {
+            lo = mid;
+            mid = if hi.is_finite() {
+                (lo + hi) / 2.0
+            } else {
+                mid * 2.0
+            };
+        }
+    }
+    mid.max(1e-6)
+}
+
+#[cfg(feature = "umap")]
+fn squared_distance(a: [f32; 2], b: [f32; 2]) -> f32 {
+    (a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn v(id: usize, data: &[f32]) -> Vector {
+        Vector::new(id.to_string(), data.to_vec())
+    }
+
+    #[test]
+    fn test_pca_puts_the_widest_spread_on_x() {
+        // Wide spread along the third axis, ±1 along the first (in a
+        // pattern uncorrelated with the third), none on the second
+        let vectors: Vec<Vector> = (0..20)
+            .map(|i| {
+                let t = i as f32 - 9.5;
+                let x = if matches!(i % 4, 0 | 3) { 1.0 } else { -1.0 };
+                v(i, &[x, 0.0, t * 10.0])
+            })
+            .collect();
+        let projection = project(
+            &vectors,
+            DistanceMetric::Euclidean,
+            ProjectionMethod::Pca,
+            0,
+        )
+        .unwrap();
+
+        assert_eq!(projection.points.len(), 20);
+        for (i, p) in projection.points.iter().enumerate() {
+            let t = (i as f32 - 9.5) * 10.0;
+            assert!((p[0].abs() - t.abs()).abs() < 1e-2, "{i}: {p:?}");
+            assert!((p[1].abs() - 1.0).abs() < 1e-2, "{i}: {p:?}");
+        }
+        let [x, y] = projection.explained_variance.unwrap();
+        assert!(x > 0.99 && y > 0.0 && x + y > 0.999, "{x} {y}");
+    }
+
+    #[test]
+    fn test_pca_handles_degenerate_samples() {
+        let same = [v(0, &[1.0, 2.0]), v(1, &[1.0, 2.0])];
+        let projection = project(&same, DistanceMetric::Cosine, ProjectionMethod::Pca, 0).unwrap();
+        assert_eq!(projection.points, vec![[0.0, 0.0]; 2]);
+
+        let empty = project(&[], DistanceMetric::Cosine, ProjectionMethod::Pca, 0).unwrap();
+        assert!(empty.points.is_empty());
+    }
+
+    #[cfg(not(feature = "umap"))]
+    #[test]
+    fn test_umap_is_rejected_without_the_feature() {
+        assert!(!ProjectionMethod::Umap.is_available());
+        assert!(project(&[], DistanceMetric::Cosine, ProjectionMethod::Umap, 0).is_err());
+    }
+
+    #[cfg(feature = "umap")]
+    #[test]
+    fn test_umap_keeps_clusters_apart_and_is_repeatable() {
+        let mut rng = fastrand::Rng::with_seed(1);
+        let vectors: Vec<Vector> = (0..60)
+            .map(|i| {
+                let centre = if i < 30 { 0.0 } else { 50.0 };
+                let data: Vec<f32> = (0..8).map(|_| centre + rng.f32()).collect();
+                v(i, &data)
+            })
+            .collect();
+        let projection = project(
+            &vectors,
+            DistanceMetric::Euclidean,
+            ProjectionMethod::Umap,
+            7,
+        )
+        .unwrap();
+        assert!(projection.explained_variance.is_none());
+
+        let centroid = |points: &[[f32; 2]]| {
+            let n = points.len() as f32;
+            [
+                points.iter().map(|p| p[0]).sum::<f32>() / n,
+                points.iter().map(|p| p[1]).sum::<f32>() / n,
+            ]
+        };
+        let (a, b) = projection.points.split_at(30);
+        let (ca, cb) = (centroid(a), centroid(b));
+        let gap = squared_distance(ca, cb).sqrt();
+        let spread = a
+            .iter()
+            .map(|p| squared_distance(*p, ca).sqrt())
+            .fold(0.0f32, f32::max);
+        assert!(gap > spread, "gap {gap} spread {spread}");
+
+        let again = project(
+            &vectors,
+            DistanceMetric::Euclidean,
+            ProjectionMethod::Umap,
+            7,
+        )
+        .unwrap();
+        assert_eq!(again.points, projection.points);
+    }
+}
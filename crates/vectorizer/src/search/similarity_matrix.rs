@@ -0,0 +1,140 @@
+//! Pairwise similarity / distance matrix over a bounded set of vectors.
+//!
+//! Used by the dashboard's cluster view and by dedup tooling: the caller
+//! names up to [`MAX_SIMILARITY_MATRIX_IDS`] vector IDs and gets back the
+//! full symmetric `n × n` matrix. Every cell goes through the dispatched
+//! [`crate::simd`] kernels, and only the upper triangle is computed.
+//!
+//! Scores use the metric's natural orientation: cosine similarity and dot
+//! product (higher = closer), Euclidean distance (lower = closer). The
+//! `kind` field of [`SimilarityMatrix`] says which one the caller got.
+
+use serde::Serialize;
+
+use crate::models::{DistanceMetric, Vector};
+use crate::simd;
+
+/// Upper bound on the number of IDs per request. The matrix is `n²`
+/// floats, so 512 IDs is already a ~1 MiB JSON response.
+pub const MAX_SIMILARITY_MATRIX_IDS: usize = 512;
+
+/// Whether matrix cells are similarities or distances.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MatrixKind {
+    /// Higher is closer (cosine, dot product).
+    Similarity,
+    /// Lower is closer (Euclidean).
+    Distance,
+}
+
+impl MatrixKind {
+    /// Orientation of `metric`'s raw score.
+    pub fn for_metric(metric: DistanceMetric) -> Self {
+        match metric {
+            DistanceMetric::Euclidean => Self::Distance,
+            DistanceMetric::Cosine | DistanceMetric::DotProduct => Self::Similarity,
+        }
+    }
+}
+
+/// Result of [`compute`].
+#[derive(Debug, Clone, Serialize)]
+pub struct SimilarityMatrix {
+    /// Row/column labels, in request order, for the IDs that were found.
+    pub ids: Vec<String>,
+    /// Metric the cells were computed with.
+    pub metric: DistanceMetric,
+    /// Orientation of the cells.
+    pub kind: MatrixKind,
+    /// `matrix[i][j]` = score between `ids[i]` and `ids[j]`.
+    pub matrix: Vec<Vec<f32>>,
+    /// Requested IDs that do not exist in the collection.
+    pub missing: Vec<String>,
+}
+
+/// Compute the pairwise matrix for `vectors` under `metric`.
+///
+/// Cosine inputs are normalised on a copy first, so the result is correct
+/// even when `metric` overrides a collection that stores raw vectors.
+pub fn compute(vectors: &[Vector], metric: DistanceMetric) -> Vec<Vec<f32>> {
+    let n = vectors.len();
+    let normalized: Vec<Vec<f32>>;
+    let rows: Vec<&[f32]> = if metric == DistanceMetric::Cosine {
+        normalized = vectors
+            .iter()
+            .map(|v| {
+                let mut data = v.data.clone();
+                simd::normalize_in_place(&mut data);
+                data
+            })
+            .collect();
+        normalized.iter().map(Vec::as_slice).collect()
+    } else {
+        vectors.iter().map(|v| v.data.as_slice()).collect()
+    };
+
+    let diagonal = |row: &[f32]| match metric {
+        DistanceMetric::Cosine => 1.0,
+        DistanceMetric::Euclidean => 0.0,
+        DistanceMetric::DotProduct => simd::dot_product(row, row),
+    };
+
+    let mut matrix = vec![vec![0.0f32; n]; n];
+    for (i, a) in rows.iter().enumerate() {
+        matrix[i][i] = diagonal(*a);
+        for (j, b) in rows.iter().enumerate().skip(i + 1) {
+            let score = match metric {
+                DistanceMetric::Cosine => simd::cosine_similarity(a, b),
+                DistanceMetric::Euclidean => simd::euclidean_distance(a, b),
+                DistanceMetric::DotProduct => simd::dot_product(a, b),
+            };
+            matrix[i][j] = score;
+            matrix[j][i] = score;
+        }
+    }
+    matrix
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn v(id: &str, data: &[f32]) -> Vector {
+        Vector::new(id.to_string(), data.to_vec())
+    }
+
+    #[test]
+    fn test_cosine_matrix_is_symmetric_with_unit_diagonal() {
+        let vectors = [
+            v("a", &[1.0, 0.0]),
+            v("b", &[0.0, 2.0]),
+            v("c", &[3.0, 3.0]),
+        ];
+        let m = compute(&vectors, DistanceMetric::Cosine);
+
+        assert_eq!(m.len(), 3);
+        for (i, row) in m.iter().enumerate() {
+            assert!((row[i] - 1.0).abs() < 1e-6);
+            for (j, cell) in row.iter().enumerate() {
+                assert_eq!(*cell, m[j][i]);
+            }
+        }
+        assert!(m[0][1].abs() < 1e-6);
+        assert!((m[0][2] - std::f32::consts::FRAC_1_SQRT_2).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_euclidean_matrix_reports_distances() {
+        let vectors = [v("a", &[0.0, 0.0]), v("b", &[3.0, 4.0])];
+        let m = compute(&vectors, DistanceMetric::Euclidean);
+
+        assert_eq!(m[0][0], 0.0);
+        assert!((m[0][1] - 5.0).abs() < 1e-5);
+        assert_eq!(
+            MatrixKind::for_metric(DistanceMetric::Euclidean),
+            MatrixKind::Distance
+        );
+    }
+}
@@ -0,0 +1,696 @@
+//! Search templates — named, parameterized searches that admins define
+//! once and clients run by name, so retrieval is tuned in one place
+//! instead of drifting across every client's config.
+//!
+//! A [`SearchTemplate`] fixes the collections searched, a payload filter,
+//! hybrid weights and a rerank expression. A run supplies only the query
+//! text and values for the template's declared parameters. Parameters
+//! are referenced in the filter as a string that is exactly
+//! `"{{name}}"`, replaced by the supplied JSON value or the parameter's
+//! default:
+//!
+//! ```json
+//! {
+//!   "collections": ["docs", "faq"],
+//!   "limit": 5,
+//!   "filter": { "must": [{ "type": "match", "key": "lang", "match_value": "{{lang}}" }] },
+//!   "hybrid": { "alpha": 0.6, "algorithm": "rrf" },
+//!   "rerank": { "expression": "score * (1 + 0.1 * payload.votes)" },
+//!   "parameters": { "lang": { "default": "en" } }
+//! }
+//! ```
+//!
+//! Each collection is searched on its own: dense neighbours, fused with
+//! BM25 over the payload text when `hybrid` is set, filtered, then
+//! rescored by `rerank` (which sees the collection's own score). The
+//! per-collection lists are merged by score.
+//!
+//! A [`SearchTemplateStore`] keeps the templates and, when opened on a
+//! file, rewrites it on every change so they survive restarts alongside
+//! the collections (`search_templates.json` in the data directory).
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::db::hybrid_search::{self, DenseSearchResult, SparseSearchResult};
+use crate::db::{HybridScoringAlgorithm, HybridSearchConfig, SearchDeadline, VectorStore};
+use crate::error::{Result, VectorizerError};
+use crate::models::qdrant::filter::QdrantFilter;
+use crate::models::qdrant::filter_processor::FilterProcessor;
+use crate::models::{Payload, ScoringConfig, SearchResult};
+use crate::search::full_text::FullTextQuery;
+
+/// File the templates are saved to, in the data directory.
+pub const SEARCH_TEMPLATES_FILE: &str = "search_templates.json";
+
+/// Most results a template may return.
+pub const MAX_TEMPLATE_LIMIT: usize = 100;
+
+/// Most collections one template may search.
+pub const MAX_TEMPLATE_COLLECTIONS: usize = 32;
+
+/// Most candidates a hybrid leg may fetch per collection.
+const MAX_TEMPLATE_CANDIDATES: usize = 1000;
+
+/// Longest template name, in bytes.
+const MAX_TEMPLATE_NAME_LEN: usize = 128;
+
+/// Candidates fetched per wanted hit when a filter drops some of them.
+const FILTER_OVERSAMPLING: usize = 4;
+
+fn invalid(message: String) -> VectorizerError {
+    VectorizerError::InvalidConfiguration { message }
+}
+
+/// A stored, parameterized search.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchTemplate {
+    /// What the template is for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Collections (or aliases) searched
+    pub collections: Vec<String>,
+    /// Results returned, across all collections
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+    /// Minimum score of a returned hit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub threshold: Option<f32>,
+    /// Qdrant-style payload filter, with `"{{name}}"` placeholders
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<Value>,
+    /// Fuse the dense search with BM25 over the payload text
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hybrid: Option<TemplateHybrid>,
+    /// Scoring expression the hits are reranked by
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rerank: Option<ScoringConfig>,
+    /// Parameters a run may set
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub parameters: BTreeMap<String, TemplateParameter>,
+}
+
+fn default_limit() -> usize {
+    10
+}
+
+/// A parameter of a template.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemplateParameter {
+    /// What the parameter selects
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Value used when a run leaves the parameter out; without one the
+    /// parameter is required
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<Value>,
+}
+
+/// Hybrid weights of a template.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemplateHybrid {
+    /// Weight of the dense leg (0 = lexical only, 1 = dense only)
+    #[serde(default = "default_alpha")]
+    pub alpha: f32,
+    /// How the two rankings are fused
+    #[serde(default)]
+    pub algorithm: HybridScoringAlgorithm,
+    /// Dense candidates per collection
+    #[serde(default = "default_leg_k")]
+    pub dense_k: usize,
+    /// Lexical candidates per collection
+    #[serde(default = "default_leg_k")]
+    pub sparse_k: usize,
+    /// Text-indexed payload fields the lexical leg searches; empty
+    /// means the full-text defaults
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<String>,
+}
+
+fn default_alpha() -> f32 {
+    0.7
+}
+
+fn default_leg_k() -> usize {
+    20
+}
+
+/// One hit of a template run.
+#[derive(Debug, Clone, Serialize)]
+pub struct TemplateHit {
+    /// Collection the hit came from, as named in the template
+    pub collection: String,
+    /// Vector ID
+    pub id: String,
+    /// Final score
+    pub score: f32,
+    /// Dense score, in a hybrid template
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dense_score: Option<f32>,
+    /// Lexical score, in a hybrid template
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sparse_score: Option<f32>,
+    /// The hit's payload
+    pub payload: Option<Value>,
+}
+
+/// The hits of a template run.
+#[derive(Debug, Clone)]
+pub struct TemplateRun {
+    /// Best hits across the template's collections
+    pub hits: Vec<TemplateHit>,
+    /// Whether a collection's search hit the deadline
+    pub timed_out: bool,
+}
+
+impl SearchTemplate {
+    /// Check the template before it is stored as `name`.
+    pub fn validate(&self, name: &str) -> Result<()> {
+        if name.is_empty()
+            || name.len() > MAX_TEMPLATE_NAME_LEN
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+        {
+            return Err(invalid(format!(
+                "template name must be 1-{MAX_TEMPLATE_NAME_LEN} characters of \
+                 letters, digits, '_', '-' and '.'"
+            )));
+        }
+        if self.collections.is_empty() || self.collections.len() > MAX_TEMPLATE_COLLECTIONS {
+            return Err(invalid(format!(
+                "collections must name 1-{MAX_TEMPLATE_COLLECTIONS} collections"
+            )));
+        }
+        if self.collections.iter().any(|c| c.trim().is_empty()) {
+            return Err(invalid(
+                "collections must not contain empty names".to_string(),
+            ));
+        }
+        if self.limit == 0 || self.limit > MAX_TEMPLATE_LIMIT {
+            return Err(invalid(format!("limit must be 1-{MAX_TEMPLATE_LIMIT}")));
+        }
+        if self.threshold.is_some_and(|t| !t.is_finite()) {
+            return Err(invalid("threshold must be a finite number".to_string()));
+        }
+        if let Some(hybrid) = &self.hybrid {
+            if !(0.0..=1.0).contains(&hybrid.alpha) {
+                return Err(invalid("hybrid.alpha must be between 0 and 1".to_string()));
+            }
+            for (field, k) in [("dense_k", hybrid.dense_k), ("sparse_k", hybrid.sparse_k)] {
+                if k == 0 || k > MAX_TEMPLATE_CANDIDATES {
+                    return Err(invalid(format!(
+                        "hybrid.{field} must be 1-{MAX_TEMPLATE_CANDIDATES}"
+                    )));
+                }
+            }
+        }
+        if let Some(rerank) = &self.rerank {
+            rerank.validate()?;
+        }
+
+        let mut used = Vec::new();
+        if let Some(filter) = &self.filter {
+            if !filter.is_object() {
+                return Err(invalid("filter must be a JSON object".to_string()));
+            }
+            collect_placeholders(filter, &mut used);
+        }
+        if let Some(undeclared) = used.iter().find(|p| !self.parameters.contains_key(*p)) {
+            return Err(invalid(format!(
+                "filter uses undeclared parameter '{undeclared}'"
+            )));
+        }
+        if let Some(unused) = self.parameters.keys().find(|p| !used.contains(p)) {
+            return Err(invalid(format!(
+                "parameter '{unused}' is not used by the filter"
+            )));
+        }
+        // With every parameter defaulted the filter is fully known now
+        if self.parameters.values().all(|p| p.default.is_some()) {
+            self.render(&Map::new())?;
+        }
+        Ok(())
+    }
+
+    /// The template's filter with `params` (and the defaults of the
+    /// parameters they leave out) substituted. Unknown and missing
+    /// parameters are errors.
+    pub fn render(&self, params: &Map<String, Value>) -> Result<Option<QdrantFilter>> {
+        if let Some(unknown) = params.keys().find(|p| !self.parameters.contains_key(*p)) {
+            return Err(invalid(format!("unknown parameter '{unknown}'")));
+        }
+        let mut values = HashMap::with_capacity(self.parameters.len());
+        for (name, parameter) in &self.parameters {
+            let value = params
+                .get(name)
+                .or(parameter.default.as_ref())
+                .ok_or_else(|| invalid(format!("missing required parameter '{name}'")))?;
+            values.insert(name.as_str(), value);
+        }
+        let Some(filter) = &self.filter else {
+            return Ok(None);
+        };
+        let rendered = substitute(filter, &values);
+        serde_json::from_value(rendered)
+            .map(Some)
+            .map_err(|e| invalid(format!("filter: {e}")))
+    }
+
+    /// Run the template for `query`, embedded as `query_vector`, with
+    /// `params`. `tenant` confines tenant-partitioned collections to the
+    /// tenant's vectors; without one they are searched whole.
+    pub fn run(
+        &self,
+        store: &VectorStore,
+        query: &str,
+        query_vector: &[f32],
+        params: &Map<String, Value>,
+        tenant: Option<&str>,
+        deadline: &SearchDeadline,
+    ) -> Result<TemplateRun> {
+        let filter = self.render(params)?;
+        let mut hits = Vec::new();
+        let mut timed_out = false;
+        for name in &self.collections {
+            let (results, collection_timed_out) = self.search_collection(
+                store,
+                name,
+                query,
+                query_vector,
+                filter.as_ref(),
+                tenant,
+                deadline,
+            )?;
+            timed_out |= collection_timed_out;
+            hits.extend(results.into_iter().map(|result| TemplateHit {
+                collection: name.clone(),
+                id: result.id,
+                score: result.score,
+                dense_score: result.dense_score,
+                sparse_score: result.sparse_score,
+                payload: result.payload.map(|p| p.data),
+            }));
+        }
+        if let Some(threshold) = self.threshold {
+            hits.retain(|hit| hit.score >= threshold);
+        }
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+        hits.truncate(self.limit);
+        Ok(TemplateRun { hits, timed_out })
+    }
+
+    /// The template's best hits in one collection.
+    #[allow(clippy::too_many_arguments)]
+    fn search_collection(
+        &self,
+        store: &VectorStore,
+        name: &str,
+        query: &str,
+        query_vector: &[f32],
+        filter: Option<&QdrantFilter>,
+        tenant: Option<&str>,
+        deadline: &SearchDeadline,
+    ) -> Result<(Vec<SearchResult>, bool)> {
+        let collection = store.get_collection(name)?;
+        let partition = collection
+            .config()
+            .tenant_partition
+            .as_ref()
+            .zip(tenant)
+            .map(|(partition, tenant)| (partition.key.clone(), tenant));
+        let oversample = |k: usize| match filter {
+            Some(_) => k.saturating_mul(FILTER_OVERSAMPLING),
+            None => k,
+        };
+        let keep = |payload: Option<&Payload>| {
+            let in_partition = partition.as_ref().is_none_or(|(key, tenant)| {
+                payload
+                    .and_then(|p| p.data.get(key))
+                    .and_then(Value::as_str)
+                    == Some(*tenant)
+            });
+            in_partition
+                && filter.is_none_or(|filter| {
+                    payload.is_some_and(|p| FilterProcessor::apply_filter(filter, p))
+                })
+        };
+
+        let wanted = self
+            .rerank
+            .as_ref()
+            .map_or(self.limit, |rerank| rerank.candidates(self.limit));
+        let dense_k = self
+            .hybrid
+            .as_ref()
+            .map_or(wanted, |hybrid| hybrid.dense_k.max(wanted));
+        let outcome = match &partition {
+            Some((_, tenant)) => collection.search_partition_until(
+                query_vector,
+                oversample(dense_k),
+                tenant,
+                deadline,
+            )?,
+            None => collection.search_until(query_vector, oversample(dense_k), deadline)?,
+        };
+        let mut results = outcome.results;
+        results.retain(|result| keep(result.payload.as_ref()));
+        results.truncate(dense_k);
+
+        if let Some(hybrid) = &self.hybrid {
+            let lexical_query = FullTextQuery {
+                fields: hybrid.fields.clone(),
+                limit: oversample(hybrid.sparse_k),
+                highlight: false,
+                ..FullTextQuery::new(query)
+            };
+            let mut lexical = collection.full_text_search(&lexical_query)?;
+            lexical.retain(|hit| keep(hit.payload.as_ref()));
+            lexical.truncate(hybrid.sparse_k);
+
+            let config = HybridSearchConfig {
+                alpha: hybrid.alpha,
+                dense_k,
+                sparse_k: hybrid.sparse_k,
+                final_k: wanted,
+                algorithm: hybrid.algorithm,
+            };
+            let dense = results
+                .iter()
+                .map(|r| DenseSearchResult {
+                    id: r.id.clone(),
+                    score: r.score,
+                })
+                .collect();
+            let sparse = lexical
+                .iter()
+                .map(|hit| SparseSearchResult {
+                    id: hit.id.clone(),
+                    score: hit.score,
+                })
+                .collect();
+            let mut payloads: HashMap<String, Option<Payload>> = lexical
+                .into_iter()
+                .map(|hit| (hit.id, hit.payload))
+                .collect();
+            payloads.extend(results.into_iter().map(|r| (r.id, r.payload)));
+            results = hybrid_search::hybrid_search(dense, sparse, &config)?
+                .into_iter()
+                .map(|fused| SearchResult {
+                    payload: payloads.remove(&fused.id).flatten(),
+                    id: fused.id,
+                    score: fused.hybrid_score,
+                    dense_score: fused.dense_score,
+                    sparse_score: fused.sparse_score,
+                    vector: None,
+                })
+                .collect();
+        }
+        results.truncate(wanted);
+
+        if let Some(rerank) = &self.rerank {
+            collection.rescore(rerank, &mut results, self.limit)?;
+        }
+        results.truncate(self.limit);
+        Ok((results, outcome.timed_out))
+    }
+}
+
+/// Names of the `"{{name}}"` placeholders in `value`.
+fn collect_placeholders(value: &Value, found: &mut Vec<String>) {
+    match value {
+        Value::String(s) => {
+            if let Some(name) = placeholder(s)
+                && !found.iter().any(|f| f == name)
+            {
+                found.push(name.to_string());
+            }
+        }
+        Value::Array(items) => items.iter().for_each(|v| collect_placeholders(v, found)),
+        Value::Object(fields) => fields.values().for_each(|v| collect_placeholders(v, found)),
+        _ => {}
+    }
+}
+
+/// `value` with each placeholder replaced by its value in `values`.
+fn substitute(value: &Value, values: &HashMap<&str, &Value>) -> Value {
+    match value {
+        Value::String(s) => match placeholder(s).and_then(|name| values.get(name)) {
+            Some(replacement) => (*replacement).clone(),
+            None => value.clone(),
+        },
+        Value::Array(items) => Value::Array(items.iter().map(|v| substitute(v, values)).collect()),
+        Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(k, v)| (k.clone(), substitute(v, values)))
+                .collect(),
+        ),
+        _ => value.clone(),
+    }
+}
+
+/// The parameter name of a `"{{name}}"` string.
+fn placeholder(s: &str) -> Option<&str> {
+    let name = s.strip_prefix("{{")?.strip_suffix("}}")?.trim();
+    (!name.is_empty()).then_some(name)
+}
+
+/// The search templates of a server.
+#[derive(Default)]
+pub struct SearchTemplateStore {
+    templates: RwLock<BTreeMap<String, SearchTemplate>>,
+    /// File the templates are saved to; `None` keeps them in memory
+    path: Option<PathBuf>,
+}
+
+impl SearchTemplateStore {
+    /// The templates saved at `path`, which later changes are written
+    /// to. A missing file is an empty store; an unreadable one is an
+    /// error.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let templates: BTreeMap<String, SearchTemplate> = match std::fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).map_err(|e| {
+                VectorizerError::Storage(format!(
+                    "failed to parse search templates in {}: {}",
+                    path.display(),
+                    e
+                ))
+            })?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(VectorizerError::IoError(e)),
+        };
+        for (name, template) in &templates {
+            template.validate(name)?;
+        }
+        Ok(Self {
+            templates: RwLock::new(templates),
+            path: Some(path),
+        })
+    }
+
+    /// [`Self::open`] on [`SEARCH_TEMPLATES_FILE`] in `data_dir`.
+    pub fn open_in(data_dir: &Path) -> Result<Self> {
+        Self::open(data_dir.join(SEARCH_TEMPLATES_FILE))
+    }
+
+    /// Store `template` as `name`, replacing any template of that name.
+    /// Returns whether one was replaced.
+    pub fn put(&self, name: &str, template: SearchTemplate) -> Result<bool> {
+        template.validate(name)?;
+        let mut templates = self.templates.write();
+        let previous = templates.insert(name.to_string(), template);
+        if let Err(e) = self.save(&templates) {
+            match previous {
+                Some(previous) => templates.insert(name.to_string(), previous),
+                None => templates.remove(name),
+            };
+            return Err(e);
+        }
+        Ok(previous.is_some())
+    }
+
+    /// Remove the template `name`. Returns whether it existed.
+    pub fn remove(&self, name: &str) -> Result<bool> {
+        let mut templates = self.templates.write();
+        let Some(previous) = templates.remove(name) else {
+            return Ok(false);
+        };
+        if let Err(e) = self.save(&templates) {
+            templates.insert(name.to_string(), previous);
+            return Err(e);
+        }
+        Ok(true)
+    }
+
+    /// The template stored as `name`.
+    pub fn get(&self, name: &str) -> Option<SearchTemplate> {
+        self.templates.read().get(name).cloned()
+    }
+
+    /// Every template with its name, ordered by name.
+    pub fn list(&self) -> Vec<(String, SearchTemplate)> {
+        self.templates
+            .read()
+            .iter()
+            .map(|(name, template)| (name.clone(), template.clone()))
+            .collect()
+    }
+
+    /// Write `templates` to the store's file through a temporary file, so
+    /// a crash never leaves it half written.
+    fn save(&self, templates: &BTreeMap<String, SearchTemplate>) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_vec_pretty(templates)?)?;
+        std::fs::rename(&tmp, path)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::models::{CollectionConfig, Vector};
+
+    fn template(value: Value) -> SearchTemplate {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn parameters_fill_the_filter() {
+        let t = template(json!({
+            "collections": ["docs"],
+            "filter": {"must": [{"type": "match", "key": "lang", "match_value": "{{lang}}"}]},
+            "parameters": {"lang": {"default": "en"}},
+        }));
+        t.validate("by-lang").unwrap();
+
+        let filter = t.render(&Map::new()).unwrap().unwrap();
+        let en = Payload::new(json!({"lang": "en"}));
+        assert!(FilterProcessor::apply_filter(&filter, &en));
+        let params = json!({"lang": "pt"}).as_object().cloned().unwrap();
+        let filter = t.render(&params).unwrap().unwrap();
+        assert!(!FilterProcessor::apply_filter(&filter, &en));
+
+        let unknown = json!({"region": "eu"}).as_object().cloned().unwrap();
+        assert!(t.render(&unknown).is_err());
+    }
+
+    #[test]
+    fn parameters_must_match_placeholders() {
+        let undeclared = template(json!({
+            "collections": ["docs"],
+            "filter": {"must": [{"type": "match", "key": "lang", "match_value": "{{lang}}"}]},
+        }));
+        assert!(undeclared.validate("t").is_err());
+        let unused = template(json!({"collections": ["docs"], "parameters": {"lang": {}}}));
+        assert!(unused.validate("t").is_err());
+        let required = template(json!({
+            "collections": ["docs"],
+            "filter": {"must": [{"type": "match", "key": "lang", "match_value": "{{lang}}"}]},
+            "parameters": {"lang": {}},
+        }));
+        required.validate("t").unwrap();
+        assert!(required.render(&Map::new()).is_err());
+        assert!(
+            template(json!({"collections": ["docs"]}))
+                .validate("bad name")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn run_filters_and_merges_collections() {
+        let store = VectorStore::new_cpu_only();
+        for name in ["a", "b"] {
+            store
+                .create_collection(
+                    name,
+                    CollectionConfig {
+                        dimension: 2,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+        }
+        let vector = |id: &str, data: Vec<f32>, lang: &str| {
+            Vector::with_payload(
+                id.to_string(),
+                data,
+                Payload::new(json!({"lang": lang, "content": id})),
+            )
+        };
+        store
+            .insert(
+                "a",
+                vec![
+                    vector("a-en", vec![1.0, 0.0], "en"),
+                    vector("a-pt", vec![1.0, 0.1], "pt"),
+                ],
+            )
+            .unwrap();
+        store
+            .insert("b", vec![vector("b-en", vec![0.7, 0.7], "en")])
+            .unwrap();
+
+        let t = template(json!({
+            "collections": ["a", "b"],
+            "limit": 5,
+            "filter": {"must": [{"type": "match", "key": "lang", "match_value": "{{lang}}"}]},
+            "parameters": {"lang": {"default": "en"}},
+        }));
+        t.validate("t").unwrap();
+        let run = t
+            .run(
+                &store,
+                "q",
+                &[1.0, 0.0],
+                &Map::new(),
+                None,
+                &SearchDeadline::NONE,
+            )
+            .unwrap();
+        let ids: Vec<_> = run.hits.iter().map(|h| h.id.as_str()).collect();
+        assert_eq!(ids, ["a-en", "b-en"]);
+        assert_eq!(run.hits[1].collection, "b");
+    }
+
+    #[test]
+    fn store_persists_templates() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SearchTemplateStore::open_in(dir.path()).unwrap();
+        let t = template(json!({"collections": ["docs"], "limit": 3}));
+        assert!(!store.put("docs", t.clone()).unwrap());
+        assert!(store.put("docs", t.clone()).unwrap());
+        assert!(
+            store
+                .put("bad", template(json!({"collections": []})))
+                .is_err()
+        );
+
+        let reopened = SearchTemplateStore::open_in(dir.path()).unwrap();
+        assert_eq!(reopened.get("docs"), Some(t));
+        assert!(reopened.remove("docs").unwrap());
+        assert!(!reopened.remove("docs").unwrap());
+        assert!(
+            SearchTemplateStore::open_in(dir.path())
+                .unwrap()
+                .list()
+                .is_empty()
+        );
+    }
+}
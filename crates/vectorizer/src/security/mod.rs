@@ -6,6 +6,8 @@
 //! - Audit logging
 //! - Role-based access control (RBAC)
 //! - Payload encryption (ECC + AES-256-GCM)
+//! - Role-aware payload redaction on read
+//! - Audit trail of mutating calls across protocols
 //!
 //! # Features
 //!
@@ -16,12 +18,18 @@
 //! - **Payload Encryption**: End-to-end encryption for sensitive payload data
 
 pub mod audit;
+pub mod mutation_audit;
 pub mod payload_encryption;
+pub mod payload_redaction;
 pub mod rate_limit;
 pub mod rbac;
 pub mod tls;
 
 pub use audit::AuditLogger;
+pub use mutation_audit::{
+    ActorKind, AuditActor, AuditProtocol, MutationAuditLog, MutationRecord, count_ids,
+};
 pub use payload_encryption::{EncryptedPayload, EncryptionError, encrypt_payload};
+pub use payload_redaction::{PayloadRedactor, PayloadView};
 pub use rate_limit::{RateLimitConfig, RateLimiter};
 pub use rbac::{Permission, Role};
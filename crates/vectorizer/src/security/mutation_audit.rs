@@ -0,0 +1,524 @@
+//! Mutation audit log.
+//!
+//! Records who (API key, JWT subject or hub tenant), what (operation,
+//! collection, number of IDs) and when for every mutating call, whatever
+//! the protocol it came in on. Records are appended as JSON lines to a
+//! size-rotated file and, optionally, inserted into a dedicated
+//! collection so they can be filtered like any other payload.
+//!
+//! The protocol front ends build a [`MutationRecord`] and hand it to
+//! [`MutationAuditLog::record`], which never blocks: a writer thread
+//! drains the queue, so a slow disk delays the trail, not the request.
+//! Unlike the admin audit log (`crate::auth::audit`), nothing is kept in
+//! memory; the file is the source of truth.
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::mpsc;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::{info, warn};
+
+use crate::auth::UserClaims;
+use crate::config::AuditLogConfig;
+use crate::db::VectorStore;
+use crate::error::{Result, VectorizerError};
+use crate::models::tenant::TenantContext;
+use crate::models::{
+    CollectionConfig, DistanceMetric, IdGenerator, IdPolicy, Payload, QuantizationConfig, Vector,
+};
+
+/// Protocol a mutating call came in on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditProtocol {
+    /// REST API, including the Qdrant-compatible routes and GraphQL
+    Rest,
+    /// gRPC, native and Qdrant-compatible
+    Grpc,
+    /// MCP tool call
+    Mcp,
+    /// VectorizerRPC (MessagePack over TCP)
+    Rpc,
+}
+
+/// How the caller authenticated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActorKind {
+    /// Local API key
+    ApiKey,
+    /// Local JWT
+    Jwt,
+    /// HiveHub tenant API key
+    Tenant,
+    /// No credentials (auth disabled, or a protocol without auth)
+    Anonymous,
+}
+
+/// Who made a call.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditActor {
+    /// How the caller authenticated
+    pub kind: ActorKind,
+    /// User ID or tenant ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Username, API key label or key ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+impl AuditActor {
+    /// A caller without credentials
+    pub fn anonymous() -> Self {
+        Self {
+            kind: ActorKind::Anonymous,
+            id: None,
+            name: None,
+        }
+    }
+
+    /// A caller authenticated by a local JWT or API key. API keys show up
+    /// in the claims as an `api_key_<prefix>` username.
+    pub fn from_claims(claims: &UserClaims) -> Self {
+        let kind = if claims.username.starts_with("api_key_") {
+            ActorKind::ApiKey
+        } else {
+            ActorKind::Jwt
+        };
+        Self {
+            kind,
+            id: Some(claims.user_id.clone()),
+            name: Some(claims.username.clone()),
+        }
+    }
+
+    /// A caller authenticated by a hub tenant API key
+    pub fn from_tenant(tenant: &TenantContext) -> Self {
+        Self {
+            kind: ActorKind::Tenant,
+            id: Some(tenant.tenant_id.clone()),
+            name: Some(tenant.api_key_id.clone()),
+        }
+    }
+}
+
+/// One mutating call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MutationRecord {
+    /// When the call was made
+    pub at: DateTime<Utc>,
+    /// Protocol it came in on
+    pub protocol: AuditProtocol,
+    /// Who made it
+    pub actor: AuditActor,
+    /// What it did: `"POST /collections/{name}/vectors"`, a gRPC method,
+    /// an MCP tool or an RPC command
+    pub operation: String,
+    /// Collection it targeted, when known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub collection: Option<String>,
+    /// Number of vector / point IDs it named or carried, when known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ids_count: Option<usize>,
+    /// Whether it succeeded; `None` when the protocol only reports that
+    /// after the record is written (gRPC)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub success: Option<bool>,
+    /// HTTP status of REST calls
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    /// Address of the caller, when known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_ip: Option<String>,
+    /// Correlation ID of the request, when it has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
+}
+
+impl MutationRecord {
+    /// A record of `operation` made now by `actor`
+    pub fn new(protocol: AuditProtocol, actor: AuditActor, operation: impl Into<String>) -> Self {
+        Self {
+            at: Utc::now(),
+            protocol,
+            actor,
+            operation: operation.into(),
+            collection: None,
+            ids_count: None,
+            success: None,
+            status: None,
+            client_ip: None,
+            correlation_id: None,
+        }
+    }
+
+    /// Fill `collection` and `ids_count` from a JSON request body or tool
+    /// arguments, keeping values already set.
+    pub fn with_request_body(mut self, body: &Value) -> Self {
+        if self.collection.is_none() {
+            self.collection = ["collection", "collection_name", "name"]
+                .iter()
+                .find_map(|key| body.get(key).and_then(Value::as_str))
+                .map(str::to_string);
+        }
+        if self.ids_count.is_none() {
+            self.ids_count = count_ids(body);
+        }
+        self
+    }
+}
+
+/// Number of vectors a request body names or carries: the length of its
+/// first ID or vector list, or 1 for a single `id`. `None` when it names
+/// none (filter-based deletes, collection-level operations).
+pub fn count_ids(body: &Value) -> Option<usize> {
+    const LISTS: [&str; 9] = [
+        "ids",
+        "vector_ids",
+        "point_ids",
+        "points",
+        "vectors",
+        "texts",
+        "updates",
+        "documents",
+        "chunks",
+    ];
+    if let Some(len) = LISTS
+        .iter()
+        .find_map(|key| body.get(key).and_then(Value::as_array).map(Vec::len))
+    {
+        return Some(len);
+    }
+    ["id", "vector_id", "point_id"]
+        .iter()
+        .any(|key| body.get(key).is_some_and(|v| !v.is_null()))
+        .then_some(1)
+}
+
+/// Appends [`MutationRecord`]s to the audit file and, optionally, the
+/// audit collection.
+#[derive(Debug)]
+pub struct MutationAuditLog {
+    tx: mpsc::Sender<MutationRecord>,
+    path: PathBuf,
+    collection: Option<String>,
+}
+
+impl MutationAuditLog {
+    /// Build the audit log described by `config`, or `None` when it is
+    /// disabled. Opens the file up front, so a bad path fails the boot
+    /// instead of silently dropping records. The audit collection is
+    /// created with the first record written to it, once persisted
+    /// collections have had a chance to load.
+    pub fn from_config(config: &AuditLogConfig, store: Arc<VectorStore>) -> Result<Option<Self>> {
+        if !config.enabled {
+            return Ok(None);
+        }
+        if config.max_files == 0 {
+            return Err(VectorizerError::ConfigurationError(
+                "audit_log.max_files must be at least 1".to_string(),
+            ));
+        }
+        let path = config.path.clone().unwrap_or_else(|| {
+            vectorizer_core::paths::data_dir()
+                .join("audit")
+                .join("mutations.jsonl")
+        });
+        let file = RotatingFile::open(
+            path.clone(),
+            config.max_file_size_mb.saturating_mul(1024 * 1024),
+            config.max_files,
+        )
+        .map_err(|e| {
+            VectorizerError::ConfigurationError(format!(
+                "audit_log: cannot open {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        let (tx, rx) = mpsc::channel();
+        let collection = config.collection.clone();
+        let sink = collection.clone();
+        std::thread::Builder::new()
+            .name("audit-log-writer".to_string())
+            .spawn(move || run_writer(rx, file, sink.map(|name| (store, name))))
+            .map_err(|e| VectorizerError::InternalError(format!("audit log writer: {}", e)))?;
+
+        Ok(Some(Self {
+            tx,
+            path,
+            collection,
+        }))
+    }
+
+    /// Queue `record` for writing. Never blocks and never fails the call
+    /// being audited; a record that cannot be written is logged.
+    pub fn record(&self, record: MutationRecord) {
+        if self.tx.send(record).is_err() {
+            warn!("Audit log writer has stopped; record dropped");
+        }
+    }
+
+    /// The live audit file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The audit collection, if records are also written to one
+    pub fn collection(&self) -> Option<&str> {
+        self.collection.as_deref()
+    }
+}
+
+/// Configuration of the audit collection: one constant dimension, since
+/// records are looked up by payload, and time-ordered IDs.
+fn audit_collection_config() -> CollectionConfig {
+    CollectionConfig {
+        dimension: 1,
+        metric: DistanceMetric::Euclidean,
+        quantization: QuantizationConfig::None,
+        normalization: None,
+        id_policy: Some(IdPolicy {
+            generator: IdGenerator::UuidV7,
+            ..IdPolicy::default()
+        }),
+        ..CollectionConfig::default()
+    }
+}
+
+/// Writer thread: appends queued records in batches until every sender
+/// is gone.
+fn run_writer(
+    rx: mpsc::Receiver<MutationRecord>,
+    mut file: RotatingFile,
+    sink: Option<(Arc<VectorStore>, String)>,
+) {
+    while let Ok(first) = rx.recv() {
+        let batch: Vec<MutationRecord> = std::iter::once(first).chain(rx.try_iter()).collect();
+        for record in &batch {
+            match serde_json::to_string(record) {
+                Ok(line) => {
+                    if let Err(e) = file.write_line(&line) {
+                        warn!("Audit log write to {} failed: {}", file.path.display(), e);
+                    }
+                }
+                Err(e) => warn!("Audit record serialization failed: {}", e),
+            }
+        }
+        if let Err(e) = file.flush() {
+            warn!("Audit log flush of {} failed: {}", file.path.display(), e);
+        }
+
+        if let Some((store, name)) = &sink {
+            let vectors: Vec<Vector> = batch
+                .iter()
+                .filter_map(|record| serde_json::to_value(record).ok())
+                .map(|payload| {
+                    Vector::with_payload(
+                        IdGenerator::UuidV7.generate(),
+                        vec![0.0],
+                        Payload::new(payload),
+                    )
+                })
+                .collect();
+            if store.get_collection(name).is_err() {
+                match store.create_collection(name, audit_collection_config()) {
+                    Ok(()) => info!("Created audit collection '{}'", name),
+                    Err(e) => warn!("Audit collection '{}' not created: {}", name, e),
+                }
+            }
+            if let Err(e) = store.insert(name, vectors) {
+                warn!("Audit records not written to collection '{}': {}", name, e);
+            }
+        }
+    }
+}
+
+/// Append-only file rotated by size: `path` is live, `path.1` the most
+/// recently rotated, up to `path.<max_files>`.
+struct RotatingFile {
+    path: PathBuf,
+    max_bytes: u64,
+    max_files: usize,
+    writer: BufWriter<File>,
+    size: u64,
+}
+
+impl RotatingFile {
+    fn open(path: PathBuf, max_bytes: u64, max_files: usize) -> std::io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata()?.len();
+        Ok(Self {
+            path,
+            max_bytes,
+            max_files,
+            writer: BufWriter::new(file),
+            size,
+        })
+    }
+
+    fn write_line(&mut self, line: &str) -> std::io::Result<()> {
+        let len = line.len() as u64 + 1;
+        if self.size > 0 && self.size + len > self.max_bytes {
+            self.rotate()?;
+        }
+        self.writer.write_all(line.as_bytes())?;
+        self.writer.write_all(b"\n")?;
+        self.size += len;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+
+    fn rotated(&self, n: usize) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(format!(".{}", n));
+        PathBuf::from(name)
+    }
+
+    fn rotate(&mut self) -> std::io::Result<()> {
+        self.writer.flush()?;
+        let _ = std::fs::remove_file(self.rotated(self.max_files));
+        for n in (1..self.max_files).rev() {
+            let from = self.rotated(n);
+            if from.exists() {
+                std::fs::rename(&from, self.rotated(n + 1))?;
+            }
+        }
+        std::fs::rename(&self.path, self.rotated(1))?;
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        self.writer = BufWriter::new(file);
+        self.size = 0;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use serde_json::json;
+
+    use super::*;
+
+    fn wait_for(mut done: impl FnMut() -> bool) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !done() {
+            assert!(Instant::now() < deadline, "timed out waiting for writer");
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn ids_are_counted_from_request_bodies() {
+        assert_eq!(count_ids(&json!({"ids": ["a", "b", "c"]})), Some(3));
+        assert_eq!(
+            count_ids(&json!({"points": [{"id": 1}, {"id": 2}]})),
+            Some(2)
+        );
+        assert_eq!(count_ids(&json!({"id": "a", "vector": [0.1]})), Some(1));
+        assert_eq!(count_ids(&json!({"filter": {"must": []}})), None);
+
+        let record =
+            MutationRecord::new(AuditProtocol::Mcp, AuditActor::anonymous(), "insert_text")
+                .with_request_body(&json!({"collection_name": "docs", "texts": ["x", "y"]}));
+        assert_eq!(record.collection.as_deref(), Some("docs"));
+        assert_eq!(record.ids_count, Some(2));
+    }
+
+    #[test]
+    fn api_key_claims_are_told_apart_from_jwts() {
+        let claims = |username: &str| UserClaims {
+            user_id: "u1".to_string(),
+            username: username.to_string(),
+            roles: Vec::new(),
+            iat: 0,
+            exp: 0,
+            scopes: Vec::new(),
+            namespace: None,
+        };
+        assert_eq!(
+            AuditActor::from_claims(&claims("api_key_0123abcd")).kind,
+            ActorKind::ApiKey
+        );
+        assert_eq!(
+            AuditActor::from_claims(&claims("alice")).kind,
+            ActorKind::Jwt
+        );
+    }
+
+    #[test]
+    fn records_are_written_to_file_and_collection() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("mutations.jsonl");
+        let store = Arc::new(VectorStore::new_cpu_only());
+        let config = AuditLogConfig {
+            enabled: true,
+            path: Some(path.clone()),
+            collection: Some("_audit_test".to_string()),
+            ..AuditLogConfig::default()
+        };
+        let log = MutationAuditLog::from_config(&config, store.clone())
+            .unwrap()
+            .unwrap();
+
+        let mut record = MutationRecord::new(
+            AuditProtocol::Rest,
+            AuditActor::anonymous(),
+            "DELETE /collections/{name}/vectors/{id}",
+        );
+        record.collection = Some("docs".to_string());
+        record.ids_count = Some(1);
+        log.record(record);
+
+        wait_for(|| {
+            store
+                .get_collection("_audit_test")
+                .is_ok_and(|c| c.vector_count() == 1)
+        });
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let line: Value = serde_json::from_str(contents.lines().next().unwrap()).unwrap();
+        assert_eq!(line["protocol"], "rest");
+        assert_eq!(line["actor"]["kind"], "anonymous");
+        assert_eq!(line["collection"], "docs");
+        assert_eq!(line["ids_count"], 1);
+    }
+
+    #[test]
+    fn file_rotates_by_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("mutations.jsonl");
+        let mut file = RotatingFile::open(path.clone(), 64, 2).unwrap();
+        for i in 0..10 {
+            file.write_line(&format!(
+                "{{\"record\":{},\"pad\":\"{}\"}}",
+                i,
+                "x".repeat(20)
+            ))
+            .unwrap();
+        }
+        file.flush().unwrap();
+
+        assert!(path.exists());
+        assert!(file.rotated(1).exists());
+        assert!(file.rotated(2).exists());
+        assert!(!file.rotated(3).exists(), "only max_files are kept");
+        let live = std::fs::read_to_string(&path).unwrap();
+        assert!(live.contains("\"record\":9"));
+    }
+}
@@ -0,0 +1,530 @@
+//! Role-aware payload redaction for read responses.
+//!
+//! Built from [`PayloadRedactionConfig`]. The HTTP server runs every JSON
+//! response of a non-exempt caller through [`PayloadRedactor::redact_response`],
+//! which rewrites each `"payload"` object it finds according to the rules
+//! that match the caller's roles and the collection the payload came from.
+//! Transports that build typed responses (gRPC, MCP, GraphQL, WebSocket
+//! pushes) resolve a [`PayloadView`] for the caller instead and apply it
+//! to each payload as the response is assembled.
+//!
+//! The collection is resolved per payload: the nearest enclosing object
+//! with a string `"collection"` field wins (search and batch responses
+//! carry one), otherwise the collection named in the request path is used.
+//! Stored data is never touched, so filters keep working on redacted
+//! fields. Endpoints that aggregate payload fields instead of returning
+//! them check [`PayloadView::redacts_field`].
+//!
+//! The response walk only recognises `"payload"` objects. Anything else
+//! derived from a payload (previews, highlight snippets, documents, job
+//! reports) must be built from a payload the caller's [`PayloadView`]
+//! already redacted.
+
+use std::sync::Arc;
+
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::auth::roles::Role;
+use crate::config::sections::redaction::{PayloadRedactionConfig, RedactionAction};
+use crate::error::{Result, VectorizerError};
+use crate::models::{Payload, SearchResult, Vector};
+
+/// Replacement value for [`RedactionAction::Mask`].
+pub const MASK_VALUE: &str = "***";
+
+#[derive(Debug)]
+struct CompiledRule {
+    path: Vec<String>,
+    action: RedactionAction,
+    collections: Vec<String>,
+    roles: Vec<Role>,
+}
+
+impl CompiledRule {
+    fn matches_collection(&self, collection: Option<&str>) -> bool {
+        if self.collections.is_empty() {
+            return true;
+        }
+        let Some(collection) = collection else {
+            return false;
+        };
+        self.collections
+            .iter()
+            .any(|pattern| match pattern.strip_suffix('*') {
+                Some(prefix) => collection.starts_with(prefix),
+                None => collection == pattern.as_str(),
+            })
+    }
+
+    fn matches_roles(&self, roles: &[Role]) -> bool {
+        self.roles.is_empty() || self.roles.iter().any(|r| roles.contains(r))
+    }
+
+    /// Whether the rule rewrites the dotted payload `field`, a field
+    /// nested in it or the object it sits in.
+    fn overlaps(&self, field: &str) -> bool {
+        self.path
+            .iter()
+            .zip(field.split('.'))
+            .all(|(rule, field)| rule == field)
+    }
+}
+
+/// Compiled redaction policy.
+#[derive(Debug)]
+pub struct PayloadRedactor {
+    rules: Vec<CompiledRule>,
+    exempt_roles: Vec<Role>,
+    hash_salt: Vec<u8>,
+}
+
+fn parse_role(name: &str) -> Result<Role> {
+    serde_json::from_value(Value::String(name.to_string())).map_err(|_| {
+        VectorizerError::InvalidConfiguration {
+            message: format!("api.payload_redaction: unknown role '{}'", name),
+        }
+    })
+}
+
+impl PayloadRedactor {
+    /// Build a redactor, or `None` when redaction is disabled or has no
+    /// rules.
+    ///
+    /// Unknown role names, empty field paths and `hash` rules without a
+    /// `hash_salt` are configuration errors.
+    pub fn from_config(config: &PayloadRedactionConfig) -> Result<Option<Self>> {
+        if !config.enabled || config.rules.is_empty() {
+            return Ok(None);
+        }
+
+        let hash_salt = config
+            .hash_salt
+            .as_ref()
+            .map(|s| s.expose_secret().as_bytes().to_vec())
+            .unwrap_or_default();
+
+        let mut rules = Vec::with_capacity(config.rules.len());
+        for rule in &config.rules {
+            let path: Vec<String> = rule.field.split('.').map(str::to_string).collect();
+            if path.iter().any(String::is_empty) {
+                return Err(VectorizerError::InvalidConfiguration {
+                    message: format!("api.payload_redaction: invalid field path '{}'", rule.field),
+                });
+            }
+            if rule.action == RedactionAction::Hash && hash_salt.is_empty() {
+                return Err(VectorizerError::InvalidConfiguration {
+                    message: format!(
+                        "api.payload_redaction: rule for '{}' uses hash but hash_salt is not set",
+                        rule.field
+                    ),
+                });
+            }
+            rules.push(CompiledRule {
+                path,
+                action: rule.action,
+                collections: rule.collections.clone(),
+                roles: rule
+                    .roles
+                    .iter()
+                    .map(|r| parse_role(r))
+                    .collect::<Result<_>>()?,
+            });
+        }
+
+        let exempt_roles = config
+            .exempt_roles
+            .iter()
+            .map(|r| parse_role(r))
+            .collect::<Result<_>>()?;
+
+        Ok(Some(Self {
+            rules,
+            exempt_roles,
+            hash_salt,
+        }))
+    }
+
+    /// Whether a caller with `roles` sees raw payloads.
+    pub fn is_exempt(&self, roles: &[Role]) -> bool {
+        roles.iter().any(|r| self.exempt_roles.contains(r))
+    }
+
+    /// Whether any rule applies to a caller with `roles` reading from
+    /// `collection`.
+    pub fn applies_to(&self, collection: Option<&str>, roles: &[Role]) -> bool {
+        !self.is_exempt(roles)
+            && self
+                .rules
+                .iter()
+                .any(|rule| rule.matches_collection(collection) && rule.matches_roles(roles))
+    }
+
+    /// Whether a rule applying to a caller with `roles` reading from
+    /// `collection` rewrites the dotted payload `field` or part of it.
+    pub fn redacts_field(&self, collection: Option<&str>, roles: &[Role], field: &str) -> bool {
+        !self.is_exempt(roles)
+            && self.rules.iter().any(|rule| {
+                rule.matches_collection(collection)
+                    && rule.matches_roles(roles)
+                    && rule.overlaps(field)
+            })
+    }
+
+    /// Apply the matching rules to one payload object. Returns `true` if
+    /// anything changed.
+    pub fn redact_payload(
+        &self,
+        collection: Option<&str>,
+        roles: &[Role],
+        payload: &mut Value,
+    ) -> bool {
+        if self.is_exempt(roles) {
+            return false;
+        }
+        let mut changed = false;
+        for rule in &self.rules {
+            if rule.matches_collection(collection) && rule.matches_roles(roles) {
+                changed |= self.apply(rule, payload);
+            }
+        }
+        changed
+    }
+
+    /// Walk a response body and redact every `"payload"` object in it.
+    /// `path_collection` is the collection named in the request path, if
+    /// any. Returns `true` if anything changed.
+    pub fn redact_response(
+        &self,
+        path_collection: Option<&str>,
+        roles: &[Role],
+        body: &mut Value,
+    ) -> bool {
+        if self.is_exempt(roles) {
+            return false;
+        }
+        self.walk(path_collection, roles, body)
+    }
+
+    fn walk(&self, collection: Option<&str>, roles: &[Role], value: &mut Value) -> bool {
+        match value {
+            Value::Object(map) => {
+                let scoped = map
+                    .get("collection")
+                    .and_then(Value::as_str)
+                    .map(str::to_string);
+                let collection = scoped.as_deref().or(collection);
+                let mut changed = false;
+                for (key, child) in map.iter_mut() {
+                    if key == "payload" && child.is_object() {
+                        changed |= self.redact_payload(collection, roles, child);
+                    } else {
+                        changed |= self.walk(collection, roles, child);
+                    }
+                }
+                changed
+            }
+            Value::Array(items) => items.iter_mut().fold(false, |changed, item| {
+                self.walk(collection, roles, item) | changed
+            }),
+            _ => false,
+        }
+    }
+
+    fn apply(&self, rule: &CompiledRule, payload: &mut Value) -> bool {
+        let Some((last, parents)) = rule.path.split_last() else {
+            return false;
+        };
+        let mut target = payload;
+        for key in parents {
+            match target.get_mut(key) {
+                Some(next) => target = next,
+                None => return false,
+            }
+        }
+        let Some(object) = target.as_object_mut() else {
+            return false;
+        };
+        match rule.action {
+            RedactionAction::Drop => object.remove(last).is_some(),
+            RedactionAction::Mask => match object.get_mut(last) {
+                Some(v) => {
+                    *v = Value::String(MASK_VALUE.to_string());
+                    true
+                }
+                None => false,
+            },
+            RedactionAction::Hash => match object.get_mut(last) {
+                Some(v) => {
+                    *v = Value::String(self.hash(v));
+                    true
+                }
+                None => false,
+            },
+        }
+    }
+
+    fn hash(&self, value: &Value) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(&self.hash_salt);
+        hasher.update(value.to_string().as_bytes());
+        format!("sha256:{}", hex::encode(hasher.finalize()))
+    }
+}
+
+/// The redaction policy as seen by one caller.
+///
+/// Cheap to clone; the default view redacts nothing and is what callers
+/// get when no policy is configured.
+#[derive(Debug, Clone, Default)]
+pub struct PayloadView {
+    redactor: Option<Arc<PayloadRedactor>>,
+    roles: Vec<Role>,
+}
+
+impl PayloadView {
+    /// View for a caller holding `roles`. An unauthenticated caller holds
+    /// no roles and gets every rule that is not role-scoped.
+    pub fn new(redactor: Option<Arc<PayloadRedactor>>, roles: Vec<Role>) -> Self {
+        let redactor = redactor.filter(|r| !r.is_exempt(&roles));
+        Self { redactor, roles }
+    }
+
+    /// Whether payloads pass through unchanged.
+    pub fn is_passthrough(&self) -> bool {
+        self.redactor.is_none()
+    }
+
+    /// Whether any rule rewrites payloads from `collection` for this
+    /// caller.
+    pub fn redacts_collection(&self, collection: &str) -> bool {
+        self.redactor
+            .as_ref()
+            .is_some_and(|redactor| redactor.applies_to(Some(collection), &self.roles))
+    }
+
+    /// Whether the dotted payload `field` of `collection` is redacted,
+    /// wholly or in part, for this caller.
+    pub fn redacts_field(&self, collection: &str, field: &str) -> bool {
+        self.redactor
+            .as_ref()
+            .is_some_and(|redactor| redactor.redacts_field(Some(collection), &self.roles, field))
+    }
+
+    /// Redact one raw payload value from `collection`.
+    pub fn redact_value(&self, collection: &str, payload: &mut Value) -> bool {
+        match &self.redactor {
+            Some(redactor) => redactor.redact_payload(Some(collection), &self.roles, payload),
+            None => false,
+        }
+    }
+
+    /// Redact an optional payload from `collection`.
+    pub fn redact(&self, collection: &str, payload: &mut Option<Payload>) {
+        if let Some(payload) = payload {
+            self.redact_value(collection, &mut payload.data);
+        }
+    }
+
+    /// Redact the payloads of search hits from `collection`.
+    pub fn redact_results(&self, collection: &str, results: &mut [SearchResult]) {
+        if self.is_passthrough() {
+            return;
+        }
+        for result in results {
+            self.redact(collection, &mut result.payload);
+        }
+    }
+
+    /// Redact the payloads of stored vectors read from `collection`.
+    pub fn redact_vectors(&self, collection: &str, vectors: &mut [Vector]) {
+        if self.is_passthrough() {
+            return;
+        }
+        for vector in vectors {
+            self.redact(collection, &mut vector.payload);
+        }
+    }
+
+    /// Walk an assembled JSON body the same way the HTTP layer does; see
+    /// [`PayloadRedactor::redact_response`].
+    pub fn redact_response(&self, collection: Option<&str>, body: &mut Value) -> bool {
+        match &self.redactor {
+            Some(redactor) => redactor.redact_response(collection, &self.roles, body),
+            None => false,
+        }
+    }
+}
+
+/// Collection named by a request path such as `/collections/{name}/...`
+/// or `/qdrant/collections/{name}/points/search`.
+pub fn collection_from_path(path: &str) -> Option<&str> {
+    let mut segments = path.split('/');
+    segments.find(|s| *s == "collections")?;
+    segments.next().filter(|s| !s.is_empty())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::config::secret::Secret;
+    use crate::config::sections::redaction::RedactionRule;
+
+    fn rule(field: &str, action: RedactionAction) -> RedactionRule {
+        RedactionRule {
+            field: field.to_string(),
+            action,
+            collections: Vec::new(),
+            roles: Vec::new(),
+        }
+    }
+
+    fn redactor(rules: Vec<RedactionRule>) -> PayloadRedactor {
+        PayloadRedactor::from_config(&PayloadRedactionConfig {
+            enabled: true,
+            hash_salt: Some(Secret::new("pepper".to_string())),
+            rules,
+            ..Default::default()
+        })
+        .unwrap()
+        .unwrap()
+    }
+
+    #[test]
+    fn test_mask_drop_and_hash_nested_fields() {
+        let r = redactor(vec![
+            rule("email", RedactionAction::Mask),
+            rule("ssn", RedactionAction::Drop),
+            rule("customer.id", RedactionAction::Hash),
+        ]);
+        let mut payload = json!({
+            "email": "a@b.c",
+            "ssn": "123",
+            "customer": {"id": 42},
+            "title": "kept",
+        });
+        assert!(r.redact_payload(None, &[Role::ReadOnly], &mut payload));
+
+        assert_eq!(payload["email"], MASK_VALUE);
+        assert!(payload.get("ssn").is_none());
+        let hashed = payload["customer"]["id"].as_str().unwrap();
+        assert!(hashed.starts_with("sha256:"));
+        assert_eq!(payload["title"], "kept");
+
+        // Same input, same digest.
+        let mut again = json!({"customer": {"id": 42}});
+        r.redact_payload(None, &[], &mut again);
+        assert_eq!(again["customer"]["id"].as_str().unwrap(), hashed);
+    }
+
+    #[test]
+    fn test_exempt_roles_and_rule_scoping() {
+        let mut scoped = rule("email", RedactionAction::Mask);
+        scoped.collections = vec!["customers*".to_string()];
+        scoped.roles = vec!["ApiUser".to_string()];
+        let r = redactor(vec![scoped]);
+
+        let original = json!({"email": "a@b.c"});
+        let mut p = original.clone();
+        assert!(!r.redact_payload(Some("customers_eu"), &[Role::Admin], &mut p));
+        assert!(!r.redact_payload(Some("customers_eu"), &[Role::ReadOnly], &mut p));
+        assert!(!r.redact_payload(Some("orders"), &[Role::ApiUser], &mut p));
+        assert_eq!(p, original);
+        assert!(r.redact_payload(Some("customers_eu"), &[Role::ApiUser], &mut p));
+
+        assert!(r.applies_to(Some("customers_eu"), &[Role::ApiUser]));
+        assert!(!r.applies_to(Some("orders"), &[Role::ApiUser]));
+        assert!(!r.applies_to(Some("customers_eu"), &[Role::ReadOnly]));
+        assert!(r.redacts_field(Some("customers_eu"), &[Role::ApiUser], "email"));
+        assert!(!r.redacts_field(Some("orders"), &[Role::ApiUser], "email"));
+    }
+
+    #[test]
+    fn test_redacts_field_covers_parents_and_children() {
+        let r = redactor(vec![rule("customer.id", RedactionAction::Hash)]);
+        assert!(r.redacts_field(None, &[], "customer.id"));
+        assert!(r.redacts_field(None, &[], "customer"));
+        assert!(!r.redacts_field(None, &[], "customer.name"));
+        assert!(!r.redacts_field(None, &[], "title"));
+
+        let r = redactor(vec![rule("address", RedactionAction::Drop)]);
+        assert!(r.redacts_field(None, &[], "address.city"));
+        assert!(!r.redacts_field(None, &[], "addresses"));
+    }
+
+    #[test]
+    fn test_redact_response_uses_nearest_collection() {
+        let mut scoped = rule("email", RedactionAction::Drop);
+        scoped.collections = vec!["customers".to_string()];
+        let r = redactor(vec![scoped]);
+
+        let mut body = json!({
+            "results": [
+                {"collection": "customers", "payload": {"email": "x"}},
+                {"collection": "orders", "payload": {"email": "y"}},
+            ],
+            "size": {"payload": "1 KB"},
+        });
+        assert!(r.redact_response(None, &[], &mut body));
+        assert!(body["results"][0]["payload"].get("email").is_none());
+        assert_eq!(body["results"][1]["payload"]["email"], "y");
+        assert_eq!(body["size"]["payload"], "1 KB");
+    }
+
+    #[test]
+    fn test_from_config_rejects_unsalted_hash_and_unknown_role() {
+        let unsalted = PayloadRedactionConfig {
+            enabled: true,
+            rules: vec![rule("email", RedactionAction::Hash)],
+            ..Default::default()
+        };
+        assert!(PayloadRedactor::from_config(&unsalted).is_err());
+
+        let mut bad_role = rule("email", RedactionAction::Mask);
+        bad_role.roles = vec!["Janitor".to_string()];
+        let config = PayloadRedactionConfig {
+            enabled: true,
+            rules: vec![bad_role],
+            ..Default::default()
+        };
+        assert!(PayloadRedactor::from_config(&config).is_err());
+        assert!(
+            PayloadRedactor::from_config(&PayloadRedactionConfig::default())
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_payload_view_skips_exempt_callers() {
+        let r = Arc::new(redactor(vec![rule("email", RedactionAction::Mask)]));
+        let mut payload = Some(Payload::new(json!({"email": "a@b.c"})));
+
+        let admin = PayloadView::new(Some(r.clone()), vec![Role::Admin]);
+        assert!(admin.is_passthrough());
+        admin.redact("docs", &mut payload);
+        assert_eq!(payload.as_ref().unwrap().data["email"], "a@b.c");
+
+        let anonymous = PayloadView::new(Some(r), Vec::new());
+        anonymous.redact("docs", &mut payload);
+        assert_eq!(payload.unwrap().data["email"], MASK_VALUE);
+        assert!(PayloadView::default().is_passthrough());
+    }
+
+    #[test]
+    fn test_collection_from_path() {
+        assert_eq!(
+            collection_from_path("/collections/docs/search"),
+            Some("docs")
+        );
+        assert_eq!(
+            collection_from_path("/qdrant/collections/docs/points/search"),
+            Some("docs")
+        );
+        assert_eq!(collection_from_path("/search"), None);
+        assert_eq!(collection_from_path("/collections"), None);
+    }
+}
@@ -7,12 +7,16 @@
 //! - Cipher suite configuration (modern, compatible, or custom)
 //! - ALPN protocol negotiation (HTTP/1.1, HTTP/2)
 //! - Mutual TLS (mTLS) with client certificate verification
+//! - Reloading certificates without a restart ([`ReloadableTls`])
+//! - TLS for cluster peer connections ([`peer_endpoint`])
 
 use std::fs::File;
 use std::io::BufReader;
-use std::sync::Arc;
+use std::sync::{Arc, LazyLock};
 
 use anyhow::{Context, Result};
+use arc_swap::ArcSwap;
+use parking_lot::RwLock;
 use rustls::crypto::ring::cipher_suite::{
     TLS_ECDHE_ECDSA_WITH_AES_128_GCM_SHA256, TLS_ECDHE_ECDSA_WITH_AES_256_GCM_SHA384,
     TLS_ECDHE_ECDSA_WITH_CHACHA20_POLY1305_SHA256, TLS_ECDHE_RSA_WITH_AES_128_GCM_SHA256,
@@ -23,6 +27,9 @@ use rustls::pki_types::{CertificateDer, PrivateKeyDer};
 use rustls::server::WebPkiClientVerifier;
 use rustls::{CipherSuite, ServerConfig, SupportedCipherSuite};
 use rustls_pemfile::{certs, private_key};
+use tonic::transport::{Certificate, ClientTlsConfig, Endpoint, Identity};
+
+use crate::config::{ListenerTlsConfig, TlsCipherPreset};
 
 /// Cipher suite preset for easy configuration
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
@@ -85,6 +92,24 @@ impl Default for TlsConfig {
     }
 }
 
+impl From<&ListenerTlsConfig> for TlsConfig {
+    fn from(config: &ListenerTlsConfig) -> Self {
+        Self {
+            enabled: config.enabled,
+            cert_path: config.cert_path.clone(),
+            key_path: config.key_path.clone(),
+            mtls_enabled: config.mtls_enabled,
+            client_ca_path: config.client_ca_path.clone(),
+            cipher_suites: match config.cipher_suites {
+                TlsCipherPreset::Modern => CipherSuitePreset::Modern,
+                TlsCipherPreset::Compatible => CipherSuitePreset::Compatible,
+            },
+            // gRPC needs h2; REST clients may still speak HTTP/1.1
+            alpn: AlpnConfig::Both,
+        }
+    }
+}
+
 /// Get cipher suites for the Modern preset (TLS 1.3 only)
 fn get_modern_cipher_suites() -> Vec<SupportedCipherSuite> {
     vec![
@@ -294,6 +319,95 @@ pub fn create_server_config(config: &TlsConfig) -> Result<Arc<ServerConfig>> {
     Ok(Arc::new(server_config))
 }
 
+/// A listener's TLS server config that can be rebuilt from the same
+/// files while connections are being accepted.
+///
+/// Listeners take [`Self::server_config`] for every handshake, so after
+/// a successful [`Self::reload`] new connections see the new
+/// certificates while established ones keep theirs.
+pub struct ReloadableTls {
+    config: TlsConfig,
+    current: ArcSwap<ServerConfig>,
+}
+
+impl ReloadableTls {
+    /// Load the certificates and configure peer connections to match.
+    pub fn new(config: TlsConfig) -> Result<Self> {
+        let server_config = create_server_config(&config)?;
+        configure_peer_tls(&config)?;
+        Ok(Self {
+            config,
+            current: ArcSwap::new(server_config),
+        })
+    }
+
+    /// The server config to use for the next handshake.
+    pub fn server_config(&self) -> Arc<ServerConfig> {
+        self.current.load_full()
+    }
+
+    /// Whether clients must present a certificate.
+    pub fn mtls_enabled(&self) -> bool {
+        self.config.mtls_enabled
+    }
+
+    /// Re-read the certificate, key and CA files. On error the current
+    /// config stays in place.
+    pub fn reload(&self) -> Result<()> {
+        let server_config = create_server_config(&self.config)?;
+        configure_peer_tls(&self.config)?;
+        self.current.store(server_config);
+        tracing::info!("TLS certificates reloaded");
+        Ok(())
+    }
+}
+
+/// Client TLS used to dial cluster peers; `None` dials plaintext.
+static PEER_TLS: LazyLock<RwLock<Option<ClientTlsConfig>>> = LazyLock::new(|| RwLock::new(None));
+
+/// Set how this node dials its cluster peers: over TLS trusting the
+/// listener's CA bundle, presenting its own certificate when mTLS is on,
+/// so a cluster shares one TLS setting.
+pub fn configure_peer_tls(config: &TlsConfig) -> Result<()> {
+    if !config.enabled {
+        *PEER_TLS.write() = None;
+        return Ok(());
+    }
+
+    let mut tls = ClientTlsConfig::new();
+    match config.client_ca_path {
+        Some(ref ca_path) => {
+            let ca = std::fs::read(ca_path)
+                .with_context(|| format!("Failed to read CA file: {}", ca_path))?;
+            tls = tls.ca_certificate(Certificate::from_pem(ca));
+        }
+        None => tracing::warn!(
+            "TLS enabled without ca_path: connections to cluster peers cannot verify their certificates"
+        ),
+    }
+    if config.mtls_enabled
+        && let (Some(cert_path), Some(key_path)) = (&config.cert_path, &config.key_path)
+    {
+        let cert = std::fs::read(cert_path)
+            .with_context(|| format!("Failed to read certificate file: {}", cert_path))?;
+        let key = std::fs::read(key_path)
+            .with_context(|| format!("Failed to read private key file: {}", key_path))?;
+        tls = tls.identity(Identity::from_pem(cert, key));
+    }
+    *PEER_TLS.write() = Some(tls);
+    Ok(())
+}
+
+/// gRPC endpoint for a cluster peer at `authority` (`host:port`), over
+/// TLS when [`configure_peer_tls`] enabled it.
+pub fn peer_endpoint(authority: &str) -> std::result::Result<Endpoint, tonic::transport::Error> {
+    let tls = PEER_TLS.read().clone();
+    match tls {
+        Some(tls) => Endpoint::from_shared(format!("https://{}", authority))?.tls_config(tls),
+        None => Endpoint::from_shared(format!("http://{}", authority)),
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used, clippy::expect_used)]
 mod tests {
@@ -438,6 +552,32 @@ mod tests {
         assert_eq!(suites.len(), 2);
     }
 
+    #[test]
+    fn test_listener_config_conversion() {
+        let listener = ListenerTlsConfig {
+            enabled: true,
+            cert_path: Some("/path/to/cert.pem".to_string()),
+            key_path: Some("/path/to/key.pem".to_string()),
+            mtls_enabled: true,
+            client_ca_path: Some("/path/to/ca.pem".to_string()),
+            cipher_suites: TlsCipherPreset::Compatible,
+        };
+        let config = TlsConfig::from(&listener);
+
+        assert!(config.enabled);
+        assert!(config.mtls_enabled);
+        assert_eq!(config.client_ca_path.as_deref(), Some("/path/to/ca.pem"));
+        assert_eq!(config.cipher_suites, CipherSuitePreset::Compatible);
+        assert_eq!(config.alpn, AlpnConfig::Both);
+    }
+
+    #[test]
+    fn test_peer_endpoint_plaintext_without_tls() {
+        configure_peer_tls(&TlsConfig::default()).unwrap();
+        let endpoint = peer_endpoint("10.0.0.1:15003").unwrap();
+        assert_eq!(endpoint.uri().scheme_str(), Some("http"));
+    }
+
     #[test]
     fn test_alpn_http1() {
         let protocols = get_alpn_protocols(&AlpnConfig::Http1);
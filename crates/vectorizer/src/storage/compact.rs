@@ -81,8 +81,24 @@ impl StorageCompactor {
         info!("📦 Found {} collections in memory", collection_names.len());
 
         let mut persisted_collections = Vec::new();
+        // Collections serialized from memory, and the state they were
+        // serialized at, so lazy loading knows they may be evicted
+        let mut from_memory = Vec::new();
+        let snapshot_at = chrono::Utc::now();
 
         for name in &collection_names {
+            // Read-through collections belong to their origin
+            if store.is_read_through(name) {
+                continue;
+            }
+            // Collections evicted by lazy loading are copied from the
+            // current archive instead of being loaded back into memory
+            if store.is_evicted(name)
+                && let Some(persisted) = store.archived_collection(name)
+            {
+                persisted_collections.push(persisted);
+                continue;
+            }
             match store.get_collection(name) {
                 Ok(collection_ref) => {
                     // Get all vectors from collection
@@ -129,9 +145,15 @@ impl StorageCompactor {
                         config: Some(config),
                         vectors: persisted_vectors,
                         hnsw_dump_basename: None,
+                        recall_profile: collection_ref.recall_profile(),
+                        feedback: collection_ref.feedback(),
+                        metadata_kv: collection_ref.metadata_kv(),
+                        hybrid_tuning: collection_ref.hybrid_tuning(),
+                        overrides: collection_ref.overrides(),
                     };
 
                     persisted_collections.push(persisted);
+                    from_memory.push(name.clone());
                 }
                 Err(e) => {
                     warn!("⚠️  Failed to get collection '{}': {}", name, e);
@@ -209,6 +231,7 @@ impl StorageCompactor {
         // Write from memory (no disk files)
         let writer = StorageWriter::new(&self.data_dir, self.compression_level);
         let index = writer.write_from_memory(persisted_collections)?;
+        store.mark_persisted(&from_memory, snapshot_at);
 
         info!("✅ Compaction from memory complete:");
         info!("   Collections: {}", index.collection_count());
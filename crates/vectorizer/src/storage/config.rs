@@ -4,6 +4,8 @@ use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
 
+use crate::config::secret::Secret;
+
 /// Storage configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StorageConfig {
@@ -19,6 +21,10 @@ pub struct StorageConfig {
     #[serde(default)]
     pub compaction: CompactionConfig,
 
+    /// Write-ahead log settings
+    #[serde(default)]
+    pub wal: WalStorageConfig,
+
     /// Advanced storage settings
     #[serde(default)]
     pub advanced: AdvancedStorageConfig,
@@ -30,6 +36,7 @@ impl Default for StorageConfig {
             compression: CompressionConfig::default(),
             snapshots: SnapshotConfig::default(),
             compaction: CompactionConfig::default(),
+            wal: WalStorageConfig::default(),
             advanced: AdvancedStorageConfig::default(),
         }
     }
@@ -83,6 +90,11 @@ pub struct SnapshotConfig {
     /// Path to snapshots directory
     #[serde(default = "default_snapshot_path")]
     pub path: String,
+
+    /// Bucket every snapshot is copied to after it is written locally,
+    /// so snapshots survive the loss of the node
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub object_store: Option<ObjectStoreConfig>,
 }
 
 impl Default for SnapshotConfig {
@@ -93,7 +105,112 @@ impl Default for SnapshotConfig {
             retention_days: 2,
             max_snapshots: 48,
             path: "./data/snapshots".to_string(),
+            object_store: None,
+        }
+    }
+}
+
+/// S3-compatible bucket (AWS S3, MinIO, Ceph RGW, R2, ...)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjectStoreConfig {
+    /// Bucket name
+    pub bucket: String,
+
+    /// Region used for request signing
+    #[serde(default = "default_region")]
+    pub region: String,
+
+    /// Endpoint URL, e.g. `http://minio:9000`. Defaults to AWS S3 in
+    /// `region`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+
+    /// Address the bucket as `endpoint/bucket/key` instead of
+    /// `bucket.endpoint/key`. MinIO and most self-hosted stores need it
+    #[serde(default)]
+    pub path_style: bool,
+
+    /// Key prefix everything is stored under, e.g. `prod/node-1/`
+    #[serde(default)]
+    pub prefix: String,
+
+    /// Access key ID. Falls back to `AWS_ACCESS_KEY_ID`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub access_key_id: Option<String>,
+
+    /// Secret access key. Falls back to `AWS_SECRET_ACCESS_KEY`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret_access_key: Option<Secret<String>>,
+
+    /// Session token of temporary credentials. Falls back to
+    /// `AWS_SESSION_TOKEN`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_token: Option<Secret<String>>,
+
+    /// Files larger than this many MB are sent as a multipart upload
+    #[serde(default = "default_multipart_threshold_mb")]
+    pub multipart_threshold_mb: u64,
+
+    /// Part size of multipart uploads in MB (S3 requires at least 5)
+    #[serde(default = "default_part_size_mb")]
+    pub part_size_mb: u64,
+
+    /// Server-side encryption requested on every upload
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub server_side_encryption: Option<ServerSideEncryption>,
+
+    /// KMS key for `aws:kms` encryption. The bucket default key is used
+    /// when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kms_key_id: Option<String>,
+
+    /// Timeout of one request in seconds
+    #[serde(default = "default_request_timeout_secs")]
+    pub request_timeout_secs: u64,
+}
+
+/// Server-side encryption of stored objects
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ServerSideEncryption {
+    /// Keys managed by the store (SSE-S3)
+    #[serde(rename = "AES256")]
+    Aes256,
+    /// Keys held in KMS (SSE-KMS)
+    #[serde(rename = "aws:kms")]
+    AwsKms,
+}
+
+impl ServerSideEncryption {
+    /// Value of the `x-amz-server-side-encryption` header
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Aes256 => "AES256",
+            Self::AwsKms => "aws:kms",
+        }
+    }
+}
+
+impl ObjectStoreConfig {
+    /// Check the settings that S3 would otherwise reject mid-upload
+    pub fn validate(&self) -> crate::error::Result<()> {
+        if self.bucket.is_empty() {
+            return Err(crate::error::VectorizerError::Configuration(
+                "object_store.bucket must not be empty".to_string(),
+            ));
+        }
+        if self.part_size_mb < 5 {
+            return Err(crate::error::VectorizerError::Configuration(
+                "object_store.part_size_mb must be at least 5".to_string(),
+            ));
+        }
+        if self.kms_key_id.is_some()
+            && self.server_side_encryption != Some(ServerSideEncryption::AwsKms)
+        {
+            return Err(crate::error::VectorizerError::Configuration(
+                "object_store.kms_key_id requires server_side_encryption: aws:kms".to_string(),
+            ));
         }
+        Ok(())
     }
 }
 
@@ -118,6 +235,29 @@ impl Default for CompactionConfig {
     }
 }
 
+/// Write-ahead log configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalStorageConfig {
+    /// Log every vector write to `<data_dir>/vectorizer.wal`, which lets
+    /// a collection be restored to any time within the retention window
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Hours of history kept. Restores reach back this far, provided a
+    /// snapshot was taken in the window before the target time
+    #[serde(default = "default_wal_retention_hours")]
+    pub retention_hours: u64,
+}
+
+impl Default for WalStorageConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            retention_hours: default_wal_retention_hours(),
+        }
+    }
+}
+
 // Default value functions for serde
 fn default_enabled() -> bool {
     true
@@ -151,6 +291,26 @@ fn default_batch_size() -> usize {
     1000
 }
 
+fn default_wal_retention_hours() -> u64 {
+    48 // matches the snapshot retention
+}
+
+fn default_region() -> String {
+    "us-east-1".to_string()
+}
+
+fn default_multipart_threshold_mb() -> u64 {
+    64
+}
+
+fn default_part_size_mb() -> u64 {
+    16
+}
+
+fn default_request_timeout_secs() -> u64 {
+    300
+}
+
 /// Advanced storage configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AdvancedStorageConfig {
@@ -232,6 +392,16 @@ impl StorageConfig {
             ));
         }
 
+        if let Some(object_store) = &self.snapshots.object_store {
+            object_store.validate()?;
+        }
+
+        if self.wal.enabled && self.wal.retention_hours == 0 {
+            return Err(crate::error::VectorizerError::Configuration(
+                "WAL retention must be at least 1 hour".to_string(),
+            ));
+        }
+
         Ok(())
     }
 
@@ -257,6 +427,8 @@ mod tests {
         assert_eq!(config.snapshots.retention_days, 2); // 48 hours retention
         assert_eq!(config.snapshots.max_snapshots, 48); // 24 snapshots/day * 2 days
         assert_eq!(config.compaction.batch_size, 1000);
+        assert!(!config.wal.enabled);
+        assert_eq!(config.wal.retention_hours, 48);
     }
 
     #[test]
@@ -284,4 +456,25 @@ mod tests {
         let deserialized: StorageConfig = serde_yaml::from_str(&yaml).unwrap();
         assert_eq!(deserialized.compression.level, config.compression.level);
     }
+
+    #[test]
+    fn test_object_store_config() {
+        let yaml = "snapshots:\n  object_store:\n    bucket: backups\n    endpoint: http://minio:9000\n    path_style: true\n    server_side_encryption: aws:kms\n    kms_key_id: alias/vectorizer\n";
+        let config: StorageConfig = serde_yaml::from_str(yaml).unwrap();
+        let store = config.snapshots.object_store.clone().unwrap();
+        assert_eq!(store.region, "us-east-1");
+        assert_eq!(store.part_size_mb, 16);
+        assert_eq!(
+            store.server_side_encryption,
+            Some(ServerSideEncryption::AwsKms)
+        );
+        assert!(config.validate().is_ok());
+
+        let mut invalid = store.clone();
+        invalid.part_size_mb = 1;
+        assert!(invalid.validate().is_err());
+        let mut invalid = store;
+        invalid.server_side_encryption = Some(ServerSideEncryption::Aes256);
+        assert!(invalid.validate().is_err());
+    }
 }
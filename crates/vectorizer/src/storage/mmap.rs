@@ -1,6 +1,6 @@
 use std::fs::{File, OpenOptions};
 use std::io::{self, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use memmap2::{MmapMut, MmapOptions};
 
@@ -14,11 +14,15 @@ const HEADER_SIZE: usize = 8;
 /// in an external RwLock (as done in VectorStorageBackend) for concurrent access.
 #[derive(Debug)]
 pub struct MmapVectorStorage {
+    path: PathBuf,
     file: File,
     mmap: MmapMut,
     dimension: usize,
     count: usize,
     capacity: usize,
+    /// Deletes a [`scratch`](Self::scratch) file once the mapping above is
+    /// gone; `None` for a named file.
+    scratch: Option<tempfile::TempPath>,
 }
 
 impl MmapVectorStorage {
@@ -68,14 +72,48 @@ impl MmapVectorStorage {
         let capacity = data_size / vector_size;
 
         Ok(Self {
+            path: path.to_path_buf(),
             file,
             mmap,
             dimension,
             count,
             capacity,
+            scratch: None,
         })
     }
 
+    /// Create an empty storage in a new scratch file under `dir`, deleted
+    /// when the storage is dropped. For vectors that are rebuilt on every
+    /// start and only need to stay out of RAM.
+    pub fn scratch(dir: &Path, dimension: usize) -> Result<Self> {
+        std::fs::create_dir_all(dir)?;
+        let path = tempfile::Builder::new()
+            .suffix(".mmap")
+            .tempfile_in(dir)?
+            .into_temp_path();
+        let mut storage = Self::open(&path, dimension)?;
+        storage.scratch = Some(path);
+        Ok(storage)
+    }
+
+    /// Create an empty storage at `path`, discarding any previous contents.
+    ///
+    /// Collections rebuild their vector file from the `.vecdb` snapshot on
+    /// load, so a stale file left by a previous run must not be appended to.
+    /// The old file is unlinked rather than truncated so that a mapping
+    /// still held by another storage (e.g. a renamed collection) stays valid.
+    pub fn create(path: &Path, dimension: usize) -> Result<Self> {
+        if path.exists() {
+            std::fs::remove_file(path)?;
+        }
+        Self::open(path, dimension)
+    }
+
+    /// Path of the backing file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
     /// Append a vector to storage
     pub fn append(&mut self, vector: &[f32]) -> Result<usize> {
         if vector.len() != self.dimension {
@@ -182,6 +220,24 @@ impl MmapVectorStorage {
         Ok(vector)
     }
 
+    /// Borrow the vector at `index` straight from the mapping, without
+    /// the copy [`get`](Self::get) makes.
+    pub fn vector(&self, index: usize) -> Option<&[f32]> {
+        if index >= self.count {
+            return None;
+        }
+        let vector_size = self.dimension * std::mem::size_of::<f32>();
+        let offset = HEADER_SIZE + (index * vector_size);
+        let bytes = &self.mmap[offset..offset + vector_size];
+        // SAFETY: the mapping is page-aligned and both `HEADER_SIZE` and
+        // `vector_size` are multiples of 4, so `bytes` is aligned for f32.
+        // It covers exactly `self.dimension` f32 written by `append` or
+        // `update`, and every bit pattern is a valid f32. The borrow is
+        // tied to `&self`, while the mapping is only replaced under
+        // `&mut self`.
+        Some(unsafe { std::slice::from_raw_parts(bytes.as_ptr().cast::<f32>(), self.dimension) })
+    }
+
     /// Get number of vectors stored
     pub fn len(&self) -> usize {
         self.count
@@ -231,6 +287,38 @@ mod tests {
         assert_eq!(read_v2, v2);
     }
 
+    #[test]
+    fn test_create_discards_previous_contents() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("stale.mmap");
+
+        {
+            let mut storage = MmapVectorStorage::open(&path, 4).unwrap();
+            storage.append(&[1.0, 2.0, 3.0, 4.0]).unwrap();
+            storage.flush().unwrap();
+        }
+
+        let storage = MmapVectorStorage::create(&path, 4).unwrap();
+        assert!(storage.is_empty());
+        assert_eq!(storage.path(), path.as_path());
+    }
+
+    #[test]
+    fn test_scratch_storage_is_deleted_on_drop() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut storage = MmapVectorStorage::scratch(temp_dir.path(), 3).unwrap();
+        let path = storage.path().to_path_buf();
+        assert!(path.starts_with(temp_dir.path()));
+
+        storage.append(&[1.0, 2.0, 3.0]).unwrap();
+        storage.append(&[4.0, 5.0, 6.0]).unwrap();
+        assert_eq!(storage.vector(1), Some(&[4.0, 5.0, 6.0][..]));
+        assert_eq!(storage.vector(2), None);
+
+        drop(storage);
+        assert!(!path.exists());
+    }
+
     #[test]
     fn test_mmap_persistence_and_recovery() {
         use std::fs;
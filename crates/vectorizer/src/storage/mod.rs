@@ -15,6 +15,7 @@ pub mod config;
 pub mod index;
 pub mod migration;
 pub mod mmap;
+pub mod object_store;
 pub mod reader;
 pub mod snapshot;
 pub mod writer;
@@ -23,9 +24,10 @@ use std::path::{Path, PathBuf};
 
 pub use advanced::{AdvancedStorage, CacheStats, StorageOptimizationResult, StorageStats};
 pub use compact::StorageCompactor;
-pub use config::StorageConfig;
+pub use config::{ObjectStoreConfig, ServerSideEncryption, StorageConfig, WalStorageConfig};
 pub use index::{CollectionIndex, FileEntry, StorageIndex};
 pub use migration::StorageMigrator;
+pub use object_store::{ObjectEntry, ObjectStore};
 pub use reader::StorageReader;
 pub use snapshot::{SnapshotInfo, SnapshotManager};
 pub use writer::StorageWriter;
@@ -300,6 +300,13 @@ impl StorageReader {
                             sharding: None,
                             graph: None,
                             encryption: None,
+                            id_policy: None,
+                            quota: None,
+                            scoring: None,
+                            tenant_partition: None,
+                            dedup: None,
+                            chunking: None,
+                            full_text: None,
                         });
                     }
                 }
@@ -336,6 +343,13 @@ impl StorageReader {
                     sharding: None,
                     graph: None,
                     encryption: None,
+                    id_policy: None,
+                    quota: None,
+                    scoring: None,
+                    tenant_partition: None,
+                    dedup: None,
+                    chunking: None,
+                    full_text: None,
                 });
             }
         }
@@ -3,13 +3,17 @@
 use std::fs::{self, File};
 use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use chrono::{DateTime, Duration, NaiveDateTime, Utc};
 use serde::{Deserialize, Serialize};
 use tracing::{debug, info, warn};
 
 use crate::error::{Result, VectorizerError};
-use crate::storage::StorageIndex;
+use crate::storage::{ObjectStore, StorageIndex};
+
+/// Key prefix of offloaded snapshots in the object store
+const REMOTE_SNAPSHOT_DIR: &str = "snapshots/";
 
 /// Snapshot manager for creating and managing backups
 pub struct SnapshotManager {
@@ -24,6 +28,9 @@ pub struct SnapshotManager {
 
     /// Retention period in hours
     retention_hours: i64,
+
+    /// Bucket snapshots are copied to, if configured
+    object_store: Option<Arc<ObjectStore>>,
 }
 
 impl SnapshotManager {
@@ -41,9 +48,27 @@ impl SnapshotManager {
             snapshots_dir,
             max_snapshots,
             retention_hours: retention_hours as i64,
+            object_store: None,
         }
     }
 
+    /// Copy snapshots to `store` as well, and restore from it when a
+    /// snapshot is not on local disk
+    pub fn with_object_store(mut self, store: Arc<ObjectStore>) -> Self {
+        self.set_object_store(store);
+        self
+    }
+
+    /// In-place form of [`with_object_store`](Self::with_object_store)
+    pub fn set_object_store(&mut self, store: Arc<ObjectStore>) {
+        self.object_store = Some(store);
+    }
+
+    /// Bucket snapshots are copied to, if any
+    pub fn object_store(&self) -> Option<&Arc<ObjectStore>> {
+        self.object_store.as_ref()
+    }
+
     /// Create a new snapshot.
     ///
     /// Returns `Err` with a `Storage` variant containing "no data" if the
@@ -408,6 +433,149 @@ impl SnapshotManager {
         })
     }
 
+    /// Copy a local snapshot to the object store and prune the remote
+    /// copies past retention. Returns `false` when no object store is
+    /// configured.
+    pub async fn upload_snapshot(&self, snapshot: &SnapshotInfo) -> Result<bool> {
+        let Some(store) = &self.object_store else {
+            return Ok(false);
+        };
+        let prefix = remote_snapshot_prefix(&snapshot.id);
+
+        for file in [crate::storage::VECDB_FILE, crate::storage::VECIDX_FILE] {
+            let path = snapshot.path.join(file);
+            if path.exists() {
+                store.put_file(&format!("{prefix}{file}"), &path).await?;
+            }
+        }
+        // Written last: a remote snapshot without metadata is incomplete
+        // and never listed
+        let metadata = serde_json::to_vec_pretty(snapshot)
+            .map_err(|e| VectorizerError::Serialization(e.to_string()))?;
+        store
+            .put(&format!("{prefix}snapshot.json"), metadata)
+            .await?;
+
+        info!(
+            "☁️  Snapshot {} copied to {}",
+            snapshot.id,
+            store.describe()
+        );
+
+        if let Err(e) = self.cleanup_remote_snapshots().await {
+            warn!("⚠️  Snapshot: Failed to clean up remote snapshots: {}", e);
+        }
+        Ok(true)
+    }
+
+    /// Snapshots held in the object store, newest first. Empty when no
+    /// object store is configured.
+    pub async fn list_remote_snapshots(&self) -> Result<Vec<SnapshotInfo>> {
+        let Some(store) = &self.object_store else {
+            return Ok(Vec::new());
+        };
+
+        let mut snapshots = Vec::new();
+        for entry in store.list(REMOTE_SNAPSHOT_DIR).await? {
+            if !entry.key.ends_with("/snapshot.json") {
+                continue;
+            }
+            let Some(metadata) = store.get(&entry.key).await? else {
+                continue;
+            };
+            match serde_json::from_slice::<SnapshotInfo>(&metadata) {
+                Ok(snapshot) => snapshots.push(snapshot),
+                Err(e) => warn!(
+                    "⚠️  Unreadable remote snapshot metadata {}: {}",
+                    entry.key, e
+                ),
+            }
+        }
+
+        snapshots.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        Ok(snapshots)
+    }
+
+    /// Fetch a snapshot from the object store into the local snapshots
+    /// directory
+    pub async fn download_snapshot(&self, id: &str) -> Result<SnapshotInfo> {
+        validate_snapshot_id(id)?;
+        let store = self.object_store.as_ref().ok_or_else(|| {
+            VectorizerError::Storage("No object store configured for snapshots".to_string())
+        })?;
+        let prefix = remote_snapshot_prefix(id);
+
+        let metadata = store
+            .get(&format!("{prefix}snapshot.json"))
+            .await?
+            .ok_or_else(|| {
+                VectorizerError::NotFound(format!(
+                    "Snapshot {} not found in {}",
+                    id,
+                    store.describe()
+                ))
+            })?;
+        let mut snapshot: SnapshotInfo = serde_json::from_slice(&metadata)
+            .map_err(|e| VectorizerError::Deserialization(e.to_string()))?;
+
+        info!("☁️  Downloading snapshot {} from {}", id, store.describe());
+
+        let snapshot_dir = self.snapshots_dir.join(id);
+        fs::create_dir_all(&snapshot_dir).map_err(VectorizerError::Io)?;
+        let vecdb = snapshot_dir.join(crate::storage::VECDB_FILE);
+        if !store
+            .get_to_file(&format!("{prefix}{}", crate::storage::VECDB_FILE), &vecdb)
+            .await?
+        {
+            let _ = fs::remove_dir_all(&snapshot_dir);
+            return Err(VectorizerError::Storage(format!(
+                "Remote snapshot {} has no .vecdb file",
+                id
+            )));
+        }
+        store
+            .get_to_file(
+                &format!("{prefix}{}", crate::storage::VECIDX_FILE),
+                &snapshot_dir.join(crate::storage::VECIDX_FILE),
+            )
+            .await?;
+
+        snapshot.path = snapshot_dir;
+        self.save_snapshot_metadata(&snapshot)?;
+        Ok(snapshot)
+    }
+
+    /// Restore from a snapshot, downloading it from the object store
+    /// first when it is not on local disk
+    pub async fn fetch_and_restore_snapshot(&self, id: &str) -> Result<()> {
+        if self.object_store.is_some() && self.get_snapshot(id)?.is_none() {
+            self.download_snapshot(id).await?;
+        }
+        self.restore_snapshot(id)
+    }
+
+    /// Apply the retention period and `max_snapshots` to the snapshots
+    /// in the object store. Returns how many were deleted.
+    pub async fn cleanup_remote_snapshots(&self) -> Result<usize> {
+        let Some(store) = &self.object_store else {
+            return Ok(0);
+        };
+
+        let cutoff_date = Utc::now() - Duration::hours(self.retention_hours);
+        let mut deleted = 0;
+        for (index, snapshot) in self.list_remote_snapshots().await?.iter().enumerate() {
+            if index < self.max_snapshots && snapshot.created_at >= cutoff_date {
+                continue;
+            }
+            info!("🗑️  Deleting remote snapshot {}", snapshot.id);
+            store
+                .delete_prefix(&remote_snapshot_prefix(&snapshot.id))
+                .await?;
+            deleted += 1;
+        }
+        Ok(deleted)
+    }
+
     /// Calculate the total size of a snapshot directory
     fn calculate_snapshot_size(dir: &Path) -> u64 {
         let mut total_size = 0u64;
@@ -746,13 +914,31 @@ impl SnapshotManager {
 
         let content = fs::read_to_string(&metadata_path).map_err(|e| VectorizerError::Io(e))?;
 
-        let snapshot: SnapshotInfo = serde_json::from_str(&content)
+        let mut snapshot: SnapshotInfo = serde_json::from_str(&content)
             .map_err(|e| VectorizerError::Deserialization(e.to_string()))?;
+        // Not serialized: wherever the metadata was read from is the snapshot
+        snapshot.path = snapshot_dir.to_path_buf();
 
         Ok(snapshot)
     }
 }
 
+fn remote_snapshot_prefix(id: &str) -> String {
+    format!("{REMOTE_SNAPSHOT_DIR}{id}/")
+}
+
+/// Snapshot IDs name directories; refuse anything that could leave the
+/// snapshots directory
+fn validate_snapshot_id(id: &str) -> Result<()> {
+    if id.is_empty() || id == "." || id == ".." || id.contains(['/', '\\']) {
+        return Err(VectorizerError::Storage(format!(
+            "Invalid snapshot ID: {:?}",
+            id
+        )));
+    }
+    Ok(())
+}
+
 /// Information about a snapshot
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SnapshotInfo {
@@ -851,6 +1037,70 @@ mod tests {
         assert_eq!(snapshots.len(), 1);
     }
 
+    #[tokio::test]
+    async fn test_snapshot_round_trip_through_object_store() {
+        let (config, _bucket) = crate::storage::object_store::mock::start().await;
+        let store = Arc::new(ObjectStore::new(config).unwrap());
+
+        let source_dir = TempDir::new().unwrap();
+        create_test_vecdb(source_dir.path());
+        let source = SnapshotManager::new(
+            source_dir.path(),
+            source_dir.path().join("snapshots"),
+            48,
+            48,
+        )
+        .with_object_store(store.clone());
+        let snapshot = source.create_snapshot().unwrap();
+        assert!(source.upload_snapshot(&snapshot).await.unwrap());
+
+        // A replacement node with an empty disk
+        let target_dir = TempDir::new().unwrap();
+        let target = SnapshotManager::new(
+            target_dir.path(),
+            target_dir.path().join("snapshots"),
+            48,
+            48,
+        )
+        .with_object_store(store);
+        let remote = target.list_remote_snapshots().await.unwrap();
+        assert_eq!(remote.len(), 1);
+        assert_eq!(remote[0].id, snapshot.id);
+
+        target
+            .fetch_and_restore_snapshot(&snapshot.id)
+            .await
+            .unwrap();
+        assert_eq!(
+            fs::read(target_dir.path().join(crate::storage::VECDB_FILE)).unwrap(),
+            fs::read(source_dir.path().join(crate::storage::VECDB_FILE)).unwrap()
+        );
+        assert_eq!(target.list_snapshots().unwrap().len(), 1);
+        assert!(target.download_snapshot("../escape").await.is_err());
+        assert!(target.fetch_and_restore_snapshot("missing").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_remote_snapshots_follow_max_snapshots() {
+        let (config, _bucket) = crate::storage::object_store::mock::start().await;
+        let store = Arc::new(ObjectStore::new(config).unwrap());
+        let temp_dir = TempDir::new().unwrap();
+        create_test_vecdb(temp_dir.path());
+        let manager =
+            SnapshotManager::new(temp_dir.path(), temp_dir.path().join("snapshots"), 1, 48)
+                .with_object_store(store);
+
+        let mut snapshot = manager.create_snapshot().unwrap();
+        manager.upload_snapshot(&snapshot).await.unwrap();
+        snapshot.id = "20000101_000000".to_string();
+        snapshot.created_at = Utc::now() - Duration::hours(1);
+        manager.upload_snapshot(&snapshot).await.unwrap();
+
+        let remote = manager.list_remote_snapshots().await.unwrap();
+        assert_eq!(remote.len(), 1);
+        assert_ne!(remote[0].id, "20000101_000000");
+    }
+
     #[test]
     #[ignore = "Integration test - requires full archive setup"]
     fn test_restore_snapshot() {
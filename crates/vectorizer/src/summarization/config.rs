@@ -8,7 +8,7 @@ use serde::{Deserialize, Serialize};
 use tracing::warn;
 
 use crate::summarization::types::{
-    LanguageConfig, MetadataConfig, MethodConfig, SummarizationMethod,
+    LanguageConfig, LlmSummarizationConfig, MetadataConfig, MethodConfig, SummarizationMethod,
 };
 
 /// Configuração completa do sistema de sumarização
@@ -28,6 +28,9 @@ pub struct SummarizationConfig {
     pub languages: HashMap<String, LanguageConfig>,
     /// Configurações de metadados
     pub metadata: MetadataConfig,
+    /// Provedor LLM do método `llm`
+    #[serde(default)]
+    pub llm: LlmSummarizationConfig,
 }
 
 impl Default for SummarizationConfig {
@@ -56,6 +59,7 @@ impl Default for SummarizationConfig {
             methods,
             languages,
             metadata: MetadataConfig::default(),
+            llm: LlmSummarizationConfig::default(),
         }
     }
 }
@@ -185,6 +189,12 @@ impl SummarizationConfig {
                 }
             }
 
+            // Provedor LLM
+            if let Some(llm) = summarization.get("llm") {
+                summarization_config.llm = serde_yaml::from_value(llm.clone())
+                    .map_err(|e| format!("Invalid summarization.llm config: {}", e))?;
+            }
+
             // Configurações de idiomas
             if let Some(languages) = summarization.get("languages").and_then(|v| v.as_mapping()) {
                 for (lang_code, lang_config) in languages {
@@ -251,6 +261,9 @@ impl SummarizationConfig {
 
     /// Verificar se um método está habilitado
     pub fn is_method_enabled(&self, method: &SummarizationMethod) -> bool {
+        if *method == SummarizationMethod::Llm {
+            return self.llm.enabled;
+        }
         let method_name = method.to_string();
         self.methods
             .get(&method_name)
@@ -294,6 +307,27 @@ impl SummarizationConfig {
             }
         }
 
+        // Validar provedor LLM
+        if !(0.0..=2.0).contains(&self.llm.temperature) {
+            return Err(format!(
+                "Invalid LLM temperature: {} (must be between 0.0 and 2.0)",
+                self.llm.temperature
+            ));
+        }
+        if self.llm.max_tokens == 0 || self.llm.max_input_chars == 0 {
+            return Err("LLM max_tokens and max_input_chars must be positive".to_string());
+        }
+        for (collection, overrides) in &self.llm.collections {
+            if let Some(temperature) = overrides.temperature
+                && !(0.0..=2.0).contains(&temperature)
+            {
+                return Err(format!(
+                    "Invalid LLM temperature for collection {}: {}",
+                    collection, temperature
+                ));
+            }
+        }
+
         Ok(())
     }
 }
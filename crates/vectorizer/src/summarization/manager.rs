@@ -11,8 +11,8 @@ use uuid::Uuid;
 
 use crate::summarization::config::SummarizationConfig;
 use crate::summarization::methods::{
-    AbstractiveSummarizer, ExtractiveSummarizer, KeywordSummarizer, SentenceSummarizer,
-    SummarizationMethodTrait,
+    AbstractiveSummarizer, ExtractiveSummarizer, KeywordSummarizer, LlmSummarizer,
+    SentenceSummarizer, SummarizationMethodTrait,
 };
 use crate::summarization::types::*;
 
@@ -23,6 +23,9 @@ pub struct SummarizationManager {
     keyword: KeywordSummarizer,
     sentence: SentenceSummarizer,
     abstractive: AbstractiveSummarizer,
+    llm: LlmSummarizer,
+    /// Consumo acumulado do método `llm`
+    llm_usage: LlmUsage,
     pub summaries: HashMap<String, SummarizationResult>, // Cache de sumários
 }
 
@@ -32,6 +35,8 @@ impl SummarizationManager {
         config.validate()?;
 
         Ok(Self {
+            llm: LlmSummarizer::new(config.llm.clone()),
+            llm_usage: LlmUsage::default(),
             config,
             extractive: ExtractiveSummarizer::new(),
             keyword: KeywordSummarizer::new(),
@@ -83,28 +88,20 @@ impl SummarizationManager {
         // Validar parâmetros
         self.validate_params(&params)?;
 
-        // Obter configuração do método
-        let method_config = self
-            .config
-            .get_method_config(&params.method)
-            .ok_or_else(|| SummarizationError::UnsupportedMethod {
-                method: params.method.to_string(),
-            })?;
-
-        if !method_config.enabled {
-            return Err(SummarizationError::MethodDisabled {
-                method: params.method.to_string(),
-            });
-        }
-
-        // Executar sumarização
-        let summary_text = match &params.method {
-            SummarizationMethod::Extractive => self.extractive.summarize(&params, method_config)?,
-            SummarizationMethod::Keyword => self.keyword.summarize(&params, method_config)?,
-            SummarizationMethod::Sentence => self.sentence.summarize(&params, method_config)?,
-            SummarizationMethod::Abstractive => {
-                self.abstractive.summarize(&params, method_config)?
+        // O método `llm` é configurado em `summarization.llm`
+        let mut llm_usage = None;
+        let summary_text = if params.method == SummarizationMethod::Llm {
+            if !self.config.llm.enabled {
+                return Err(SummarizationError::MethodDisabled {
+                    method: params.method.to_string(),
+                });
             }
+            let (summary, usage) = self.llm.summarize(&params)?;
+            self.llm_usage.add(&usage);
+            llm_usage = Some(usage);
+            summary
+        } else {
+            self.summarize_with_method(&params)?
         };
 
         // Criar resultado
@@ -116,6 +113,25 @@ impl SummarizationManager {
 
         let mut metadata = params.metadata.clone();
         self.add_metadata(&mut metadata, &params, &summary_id, compression_ratio);
+        if let Some(usage) = llm_usage {
+            let config = self.config.llm.for_collection(params.collection.as_deref());
+            metadata.insert(
+                "llm_provider".to_string(),
+                config.provider.as_str().to_string(),
+            );
+            metadata.insert("llm_model".to_string(), config.model);
+            metadata.insert("llm_requests".to_string(), usage.requests.to_string());
+            metadata.insert(
+                "llm_prompt_tokens".to_string(),
+                usage.prompt_tokens.to_string(),
+            );
+            metadata.insert(
+                "llm_completion_tokens".to_string(),
+                usage.completion_tokens.to_string(),
+            );
+            metadata.insert("llm_latency_ms".to_string(), usage.latency_ms.to_string());
+            metadata.insert("llm_cost_usd".to_string(), format!("{:.6}", usage.cost_usd));
+        }
 
         let result = SummarizationResult {
             summary_id: summary_id.clone(),
@@ -136,6 +152,37 @@ impl SummarizationManager {
         Ok(result)
     }
 
+    /// Sumarizar com um dos métodos configurados em `methods`
+    fn summarize_with_method(
+        &self,
+        params: &SummarizationParams,
+    ) -> Result<String, SummarizationError> {
+        // Obter configuração do método
+        let method_config = self
+            .config
+            .get_method_config(&params.method)
+            .ok_or_else(|| SummarizationError::UnsupportedMethod {
+                method: params.method.to_string(),
+            })?;
+
+        if !method_config.enabled {
+            return Err(SummarizationError::MethodDisabled {
+                method: params.method.to_string(),
+            });
+        }
+
+        // Executar sumarização
+        match &params.method {
+            SummarizationMethod::Extractive => self.extractive.summarize(params, method_config),
+            SummarizationMethod::Keyword => self.keyword.summarize(params, method_config),
+            SummarizationMethod::Sentence => self.sentence.summarize(params, method_config),
+            SummarizationMethod::Abstractive => self.abstractive.summarize(params, method_config),
+            SummarizationMethod::Llm => Err(SummarizationError::UnsupportedMethod {
+                method: params.method.to_string(),
+            }),
+        }
+    }
+
     /// Sumarizar contexto
     pub fn summarize_context(
         &mut self,
@@ -147,6 +194,7 @@ impl SummarizationManager {
             max_length: params.max_length,
             compression_ratio: params.compression_ratio,
             language: params.language,
+            collection: params.collection,
             metadata: params.metadata,
         };
 
@@ -336,6 +384,7 @@ impl SummarizationManager {
             max_length: None,
             compression_ratio: None,
             language: Some("en".to_string()),
+            collection: None,
             metadata,
         };
 
@@ -367,6 +416,7 @@ impl SummarizationManager {
             language_counts,
             average_compression_ratio: avg_compression_ratio,
             auto_summarization_enabled: self.is_auto_summarization_enabled(),
+            llm_usage: self.llm_usage,
         }
     }
 }
@@ -379,4 +429,6 @@ pub struct SummarizationStats {
     pub language_counts: HashMap<String, usize>,
     pub average_compression_ratio: f32,
     pub auto_summarization_enabled: bool,
+    /// Chamadas, tokens, latência e custo acumulados do método `llm`
+    pub llm_usage: LlmUsage,
 }
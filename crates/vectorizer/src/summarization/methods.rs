@@ -557,3 +557,270 @@ impl SummarizationMethodTrait for AbstractiveSummarizer {
         "abstractive"
     }
 }
+
+/// Instruções de sistema do método `llm`
+const LLM_SYSTEM_PROMPT: &str = "You summarize technical documents. Keep the key decisions, components, constraints and open questions. Write the summary in the language of the document, as plain prose without preamble.";
+
+/// Sumarização por LLM (API compatível com OpenAI ou Ollama), para
+/// documentos longos em que a sumarização extrativa fica rasa. Textos
+/// maiores que `max_input_chars` são sumarizados por partes, e os
+/// resumos das partes combinados numa última chamada.
+pub struct LlmSummarizer {
+    config: LlmSummarizationConfig,
+}
+
+impl LlmSummarizer {
+    pub fn new(config: LlmSummarizationConfig) -> Self {
+        Self { config }
+    }
+
+    /// Sumarizar `params.text`, com as sobrescritas da collection de
+    /// `params`, devolvendo o resumo e o consumo das chamadas
+    pub fn summarize(
+        &self,
+        params: &SummarizationParams,
+    ) -> Result<(String, LlmUsage), SummarizationError> {
+        let config = self.config.for_collection(params.collection.as_deref());
+        let length = params
+            .max_length
+            .map(|max| format!(" in at most {} characters", max))
+            .unwrap_or_default();
+        let mut usage = LlmUsage::default();
+
+        let parts = split_for_llm(&params.text, config.max_input_chars);
+        if parts.len() <= 1 {
+            let prompt = format!("Summarize this document{}:\n\n{}", length, params.text);
+            let summary = self.complete(&config, &prompt, &mut usage)?;
+            return Ok((summary, usage));
+        }
+
+        let mut partials = Vec::with_capacity(parts.len());
+        for (i, part) in parts.iter().enumerate() {
+            let prompt = format!(
+                "Summarize part {} of {} of a longer document:\n\n{}",
+                i + 1,
+                parts.len(),
+                part
+            );
+            partials.push(self.complete(&config, &prompt, &mut usage)?);
+        }
+        let prompt = format!(
+            "These are summaries of consecutive parts of one document. Combine them into a single summary{}:\n\n{}",
+            length,
+            partials.join("\n\n")
+        );
+        let summary = self.complete(&config, &prompt, &mut usage)?;
+        Ok((summary, usage))
+    }
+
+    /// Uma chamada ao modelo, registrando latência, tokens e custo
+    fn complete(
+        &self,
+        config: &LlmSummarizationConfig,
+        prompt: &str,
+        usage: &mut LlmUsage,
+    ) -> Result<String, SummarizationError> {
+        use crate::monitoring::metrics::METRICS;
+
+        let provider = config.provider.as_str();
+        let model = config.model.as_str();
+        let start = std::time::Instant::now();
+        let result = run_blocking(request_completion(config, prompt)).and_then(|r| r);
+        let elapsed = start.elapsed();
+
+        METRICS
+            .summarization_llm_latency_seconds
+            .with_label_values(&[provider, model])
+            .observe(elapsed.as_secs_f64());
+        let outcome = if result.is_ok() { "success" } else { "error" };
+        METRICS
+            .summarization_llm_requests_total
+            .with_label_values(&[provider, model, outcome])
+            .inc();
+
+        let (text, prompt_tokens, completion_tokens) = result?;
+        let cost = config.cost(prompt_tokens, completion_tokens);
+        METRICS
+            .summarization_llm_tokens_total
+            .with_label_values(&[provider, model, "prompt"])
+            .inc_by(prompt_tokens as f64);
+        METRICS
+            .summarization_llm_tokens_total
+            .with_label_values(&[provider, model, "completion"])
+            .inc_by(completion_tokens as f64);
+        METRICS
+            .summarization_llm_cost_usd_total
+            .with_label_values(&[provider, model])
+            .inc_by(cost);
+
+        usage.add(&LlmUsage {
+            requests: 1,
+            prompt_tokens,
+            completion_tokens,
+            latency_ms: elapsed.as_millis() as u64,
+            cost_usd: cost,
+        });
+        Ok(text)
+    }
+}
+
+/// Dividir `text` em partes de até `max_chars` caracteres, preferindo
+/// quebras de parágrafo
+fn split_for_llm(text: &str, max_chars: usize) -> Vec<String> {
+    if text.chars().count() <= max_chars {
+        return vec![text.to_string()];
+    }
+
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut current_chars = 0;
+    for paragraph in text.split("\n\n").filter(|p| !p.trim().is_empty()) {
+        let paragraph_chars = paragraph.chars().count();
+        if current_chars > 0 && current_chars + paragraph_chars + 2 > max_chars {
+            parts.push(std::mem::take(&mut current));
+            current_chars = 0;
+        }
+        if paragraph_chars > max_chars {
+            // Parágrafo maior que uma parte: corta por caracteres
+            let chars: Vec<char> = paragraph.chars().collect();
+            for piece in chars.chunks(max_chars) {
+                parts.push(piece.iter().collect());
+            }
+            continue;
+        }
+        if current_chars > 0 {
+            current.push_str("\n\n");
+            current_chars += 2;
+        }
+        current.push_str(paragraph);
+        current_chars += paragraph_chars;
+    }
+    if current_chars > 0 {
+        parts.push(current);
+    }
+    parts
+}
+
+/// Executar `future` até o fim a partir de código síncrono. Roda numa
+/// thread própria, com seu runtime, para funcionar também quando o
+/// chamador já está dentro de um runtime tokio.
+fn run_blocking<F, T>(future: F) -> Result<T, SummarizationError>
+where
+    F: std::future::Future<Output = T> + Send,
+    T: Send,
+{
+    std::thread::scope(|scope| {
+        scope
+            .spawn(|| {
+                tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                    .map(|runtime| runtime.block_on(future))
+            })
+            .join()
+    })
+    .map_err(|_| SummarizationError::SummarizationFailed {
+        message: "LLM request thread panicked".to_string(),
+    })?
+    .map_err(|e| SummarizationError::SummarizationFailed {
+        message: format!("Failed to create async runtime: {}", e),
+    })
+}
+
+/// Chamar o modelo com `prompt`; devolve o texto gerado e os tokens de
+/// entrada e saída informados pelo provedor
+async fn request_completion(
+    config: &LlmSummarizationConfig,
+    prompt: &str,
+) -> Result<(String, u64, u64), SummarizationError> {
+    use serde_json::json;
+
+    let api_error = |message: String| SummarizationError::ExternalApiError { message };
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(config.timeout_secs.max(1)))
+        .build()
+        .map_err(|e| api_error(format!("Failed to build HTTP client: {}", e)))?;
+    let base = config.endpoint().trim_end_matches('/');
+    let messages = json!([
+        { "role": "system", "content": LLM_SYSTEM_PROMPT },
+        { "role": "user", "content": prompt }
+    ]);
+
+    let request = match config.provider {
+        LlmProvider::OpenAi => {
+            let request = client
+                .post(format!("{}/chat/completions", base))
+                .json(&json!({
+                    "model": config.model,
+                    "messages": messages,
+                    "max_tokens": config.max_tokens,
+                    "temperature": config.temperature
+                }));
+            // OpenAI-compatible local servers often need no key
+            match config
+                .api_key
+                .clone()
+                .or_else(|| std::env::var("OPENAI_API_KEY").ok())
+            {
+                Some(key) => request.bearer_auth(key),
+                None => request,
+            }
+        }
+        LlmProvider::Ollama => client.post(format!("{}/api/chat", base)).json(&json!({
+            "model": config.model,
+            "messages": messages,
+            "stream": false,
+            "options": {
+                "temperature": config.temperature,
+                "num_predict": config.max_tokens
+            }
+        })),
+    };
+
+    let response = request.send().await.map_err(|e| {
+        api_error(format!(
+            "Failed to reach {} at {}: {}",
+            config.provider.as_str(),
+            base,
+            e
+        ))
+    })?;
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(api_error(format!(
+            "{} error ({}): {}",
+            config.provider.as_str(),
+            status,
+            body
+        )));
+    }
+    let body: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| api_error(format!("Failed to parse LLM response: {}", e)))?;
+
+    let (text, prompt_tokens, completion_tokens) = match config.provider {
+        LlmProvider::OpenAi => (
+            body.pointer("/choices/0/message/content"),
+            body.pointer("/usage/prompt_tokens"),
+            body.pointer("/usage/completion_tokens"),
+        ),
+        LlmProvider::Ollama => (
+            body.pointer("/message/content"),
+            body.get("prompt_eval_count"),
+            body.get("eval_count"),
+        ),
+    };
+    let text = text
+        .and_then(|t| t.as_str())
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .ok_or_else(|| api_error("LLM response has no summary text".to_string()))?;
+    let tokens = |count: Option<&serde_json::Value>| count.and_then(|c| c.as_u64()).unwrap_or(0);
+    Ok((
+        text.to_string(),
+        tokens(prompt_tokens),
+        tokens(completion_tokens),
+    ))
+}
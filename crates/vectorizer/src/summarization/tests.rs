@@ -6,8 +6,9 @@ mod tests {
     use super::*;
     use crate::summarization::methods::SummarizationMethodTrait;
     use crate::summarization::{
-        ContextSummarizationParams, LanguageConfig, MetadataConfig, MethodConfig,
-        SummarizationConfig, SummarizationManager, SummarizationMethod, SummarizationParams,
+        ContextSummarizationParams, LanguageConfig, LlmProvider, LlmSummarizationConfig,
+        MetadataConfig, MethodConfig, SummarizationConfig, SummarizationError,
+        SummarizationManager, SummarizationMethod, SummarizationParams,
     };
 
     fn create_test_config() -> SummarizationConfig {
@@ -27,6 +28,64 @@ mod tests {
             methods,
             languages,
             metadata: MetadataConfig::default(),
+            llm: LlmSummarizationConfig::default(),
+        }
+    }
+
+    /// Answer one request per connection with `responses`, in order, on a
+    /// local port. Returns the base URL and the received JSON bodies.
+    fn mock_llm(
+        responses: Vec<serde_json::Value>,
+    ) -> (String, std::sync::mpsc::Receiver<serde_json::Value>) {
+        use std::io::{BufRead, BufReader, Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            for response in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    let line = line.trim_end();
+                    if line.is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = line.split_once(':')
+                        && name.eq_ignore_ascii_case("content-length")
+                    {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+                sender.send(serde_json::from_slice(&body).unwrap()).unwrap();
+
+                let body = response.to_string();
+                write!(
+                    reader.get_mut(),
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+        (url, receiver)
+    }
+
+    fn llm_params(text: &str, collection: Option<&str>) -> SummarizationParams {
+        SummarizationParams {
+            text: text.to_string(),
+            method: SummarizationMethod::Llm,
+            max_length: None,
+            compression_ratio: None,
+            language: Some("en".to_string()),
+            collection: collection.map(str::to_string),
+            metadata: HashMap::new(),
         }
     }
 
@@ -58,6 +117,7 @@ mod tests {
             max_length: Some(10),
             compression_ratio: Some(0.3),
             language: Some("en".to_string()),
+            collection: None,
             metadata: HashMap::new(),
         };
 
@@ -75,6 +135,7 @@ mod tests {
             max_length: Some(10),
             compression_ratio: Some(0.3),
             language: Some("en".to_string()),
+            collection: None,
             metadata: HashMap::new(),
         };
 
@@ -141,6 +202,7 @@ mod tests {
             max_length: Some(100),
             compression_ratio: Some(0.3),
             language: Some("en".to_string()),
+            collection: None,
             metadata: HashMap::new(),
         };
 
@@ -181,6 +243,7 @@ mod tests {
             max_length: Some(50),
             compression_ratio: Some(0.3),
             language: Some("en".to_string()),
+            collection: None,
             metadata: HashMap::new(),
         };
 
@@ -196,4 +259,94 @@ mod tests {
         let found = summaries.iter().any(|s| s.summary_id == summary_id);
         assert!(found);
     }
+
+    #[test]
+    fn test_llm_method_is_disabled_by_default() {
+        let mut manager = SummarizationManager::with_enabled_config();
+        let params = llm_params("A long design document about the storage engine.", None);
+        let result = manager.summarize_text(params);
+        assert!(matches!(
+            result,
+            Err(SummarizationError::MethodDisabled { .. })
+        ));
+        assert_eq!(
+            "llm".parse::<SummarizationMethod>().unwrap(),
+            SummarizationMethod::Llm
+        );
+    }
+
+    #[test]
+    fn test_llm_openai_summary_uses_collection_overrides_and_tracks_cost() {
+        let (url, requests) = mock_llm(vec![serde_json::json!({
+            "choices": [{"message": {"content": " The engine stores vectors in segments. "}}],
+            "usage": {"prompt_tokens": 100, "completion_tokens": 20}
+        })]);
+        let mut config = create_test_config();
+        config.llm = serde_yaml::from_str(&format!(
+            "enabled: true\nendpoint: {}\napi_key: test-key\ninput_cost_per_1k_tokens: 1.0\noutput_cost_per_1k_tokens: 2.0\ncollections:\n  design-docs: {{model: gpt-large, max_tokens: 1000}}",
+            url
+        ))
+        .unwrap();
+        let mut manager = SummarizationManager::new(config).unwrap();
+
+        let result = manager
+            .summarize_text(llm_params(
+                "The storage engine keeps vectors in append-only segments.",
+                Some("design-docs"),
+            ))
+            .unwrap();
+        assert_eq!(result.summary, "The engine stores vectors in segments.");
+        assert_eq!(result.metadata["llm_model"], "gpt-large");
+        assert_eq!(result.metadata["llm_cost_usd"], "0.140000");
+
+        let request = requests.recv().unwrap();
+        assert_eq!(request["model"], "gpt-large");
+        assert_eq!(request["max_tokens"], 1000);
+        assert_eq!(request["temperature"].as_f64().unwrap() as f32, 0.2);
+
+        let usage = manager.get_stats().llm_usage;
+        assert_eq!(usage.requests, 1);
+        assert_eq!(usage.prompt_tokens, 100);
+        assert_eq!(usage.completion_tokens, 20);
+    }
+
+    #[test]
+    fn test_llm_ollama_summarizes_long_text_in_parts() {
+        let responses = (1..=4)
+            .map(|i| {
+                serde_json::json!({
+                    "message": {"content": format!("summary {}", i)},
+                    "prompt_eval_count": 10,
+                    "eval_count": 5
+                })
+            })
+            .collect();
+        let (url, requests) = mock_llm(responses);
+        let mut config = create_test_config();
+        config.llm = LlmSummarizationConfig {
+            enabled: true,
+            provider: LlmProvider::Ollama,
+            endpoint: Some(url),
+            model: "llama3.2".to_string(),
+            max_input_chars: 100,
+            ..LlmSummarizationConfig::default()
+        };
+        let mut manager = SummarizationManager::new(config).unwrap();
+
+        let paragraph = "x".repeat(80);
+        let text = [paragraph.as_str(); 3].join("\n\n");
+        let result = manager.summarize_text(llm_params(&text, None)).unwrap();
+        assert_eq!(result.summary, "summary 4");
+        assert_eq!(result.metadata["llm_requests"], "4");
+
+        let bodies: Vec<serde_json::Value> = requests.iter().take(4).collect();
+        assert_eq!(bodies[0]["stream"], false);
+        assert_eq!(bodies[0]["model"], "llama3.2");
+        let combine = bodies[3]["messages"][1]["content"].as_str().unwrap();
+        assert!(combine.contains("summary 1\n\nsummary 2\n\nsummary 3"));
+
+        let usage = manager.get_stats().llm_usage;
+        assert_eq!(usage.prompt_tokens, 40);
+        assert_eq!(usage.completion_tokens, 20);
+    }
 }
@@ -19,6 +19,8 @@ pub enum SummarizationMethod {
     Keyword,
     /// Seleção de frases representativas
     Sentence,
+    /// Sumarização por LLM (API compatível com OpenAI ou Ollama)
+    Llm,
 }
 
 impl std::fmt::Display for SummarizationMethod {
@@ -28,6 +30,7 @@ impl std::fmt::Display for SummarizationMethod {
             SummarizationMethod::Abstractive => write!(f, "abstractive"),
             SummarizationMethod::Keyword => write!(f, "keyword"),
             SummarizationMethod::Sentence => write!(f, "sentence"),
+            SummarizationMethod::Llm => write!(f, "llm"),
         }
     }
 }
@@ -41,6 +44,7 @@ impl std::str::FromStr for SummarizationMethod {
             "abstractive" => Ok(SummarizationMethod::Abstractive),
             "keyword" => Ok(SummarizationMethod::Keyword),
             "sentence" => Ok(SummarizationMethod::Sentence),
+            "llm" => Ok(SummarizationMethod::Llm),
             _ => Err(format!("Invalid summarization method: {}", s)),
         }
     }
@@ -86,6 +90,144 @@ impl Default for MethodConfig {
     }
 }
 
+/// Provedor do método `llm`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LlmProvider {
+    /// API compatível com OpenAI (`POST {endpoint}/chat/completions`)
+    #[default]
+    OpenAi,
+    /// Servidor Ollama (`POST {endpoint}/api/chat`)
+    Ollama,
+}
+
+impl LlmProvider {
+    /// Endpoint usado quando `endpoint` não é configurado
+    pub fn default_endpoint(self) -> &'static str {
+        match self {
+            LlmProvider::OpenAi => "https://api.openai.com/v1",
+            LlmProvider::Ollama => "http://localhost:11434",
+        }
+    }
+
+    /// Nome do provedor, como nas labels de métricas
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LlmProvider::OpenAi => "openai",
+            LlmProvider::Ollama => "ollama",
+        }
+    }
+}
+
+/// Configuração do método `llm`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LlmSummarizationConfig {
+    /// Habilitar o método `llm` (desabilitado por padrão)
+    pub enabled: bool,
+    pub provider: LlmProvider,
+    /// URL base; o padrão depende do provedor
+    pub endpoint: Option<String>,
+    /// Chave da API (`OPENAI_API_KEY` quando ausente; Ollama não usa)
+    pub api_key: Option<String>,
+    pub model: String,
+    pub temperature: f32,
+    /// Máximo de tokens gerados por chamada
+    pub max_tokens: usize,
+    pub timeout_secs: u64,
+    /// Textos maiores são sumarizados por partes e os resumos das
+    /// partes combinados numa segunda chamada
+    pub max_input_chars: usize,
+    /// Custo em USD por 1000 tokens de entrada, para as métricas de custo
+    pub input_cost_per_1k_tokens: f64,
+    /// Custo em USD por 1000 tokens gerados
+    pub output_cost_per_1k_tokens: f64,
+    /// Modelo, temperatura e max_tokens por collection
+    pub collections: HashMap<String, LlmModelOverride>,
+}
+
+impl Default for LlmSummarizationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            provider: LlmProvider::OpenAi,
+            endpoint: None,
+            api_key: None,
+            model: "gpt-4o-mini".to_string(),
+            temperature: 0.2,
+            max_tokens: 512,
+            timeout_secs: 120,
+            max_input_chars: 24_000,
+            input_cost_per_1k_tokens: 0.0,
+            output_cost_per_1k_tokens: 0.0,
+            collections: HashMap::new(),
+        }
+    }
+}
+
+impl LlmSummarizationConfig {
+    /// Configuração efetiva para `collection`, com as sobrescritas dela
+    pub fn for_collection(&self, collection: Option<&str>) -> Self {
+        let mut config = self.clone();
+        if let Some(overrides) = collection.and_then(|name| self.collections.get(name)) {
+            if let Some(model) = &overrides.model {
+                config.model = model.clone();
+            }
+            if let Some(temperature) = overrides.temperature {
+                config.temperature = temperature;
+            }
+            if let Some(max_tokens) = overrides.max_tokens {
+                config.max_tokens = max_tokens;
+            }
+        }
+        config
+    }
+
+    /// URL base do provedor
+    pub fn endpoint(&self) -> &str {
+        self.endpoint
+            .as_deref()
+            .unwrap_or_else(|| self.provider.default_endpoint())
+    }
+
+    /// Custo em USD de uma chamada
+    pub fn cost(&self, prompt_tokens: u64, completion_tokens: u64) -> f64 {
+        (prompt_tokens as f64 * self.input_cost_per_1k_tokens
+            + completion_tokens as f64 * self.output_cost_per_1k_tokens)
+            / 1000.0
+    }
+}
+
+/// Sobrescritas do método `llm` para uma collection
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LlmModelOverride {
+    pub model: Option<String>,
+    pub temperature: Option<f32>,
+    pub max_tokens: Option<usize>,
+}
+
+/// Consumo acumulado do método `llm`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct LlmUsage {
+    pub requests: u64,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub latency_ms: u64,
+    pub cost_usd: f64,
+}
+
+impl LlmUsage {
+    /// Somar `other` a este consumo
+    pub fn add(&mut self, other: &LlmUsage) {
+        self.requests += other.requests;
+        self.prompt_tokens += other.prompt_tokens;
+        self.completion_tokens += other.completion_tokens;
+        self.latency_ms += other.latency_ms;
+        self.cost_usd += other.cost_usd;
+    }
+}
+
 /// Configuração de idioma para sumarização
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LanguageConfig {
@@ -160,6 +302,8 @@ pub struct SummarizationParams {
     pub max_length: Option<usize>,
     pub compression_ratio: Option<f32>,
     pub language: Option<String>,
+    /// Collection do texto; seleciona as sobrescritas do método `llm`
+    pub collection: Option<String>,
     pub metadata: HashMap<String, String>,
 }
 
@@ -171,6 +315,7 @@ pub struct ContextSummarizationParams {
     pub max_length: Option<usize>,
     pub compression_ratio: Option<f32>,
     pub language: Option<String>,
+    pub collection: Option<String>,
     pub metadata: HashMap<String, String>,
 }
 
@@ -0,0 +1,346 @@
+//! Audio transcription for watched audio files
+//!
+//! Meeting recordings and voice notes hold text that search cannot see.
+//! When [`TranscriptionConfig::enabled`] is set, the file watcher runs
+//! audio files through [`Transcriber`] and indexes the transcript in
+//! chunks of whole segments, each with its start and end time in the
+//! payload. Transcription uses whisper.cpp with a ggml model file and
+//! needs the `audio-transcription` feature; without it every
+//! transcription fails with [`VectorizerError::TranscriptionError`].
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, VectorizerError};
+
+/// Transcription settings of the file watcher
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TranscriptionConfig {
+    /// Transcribe audio files (off by default)
+    pub enabled: bool,
+    /// Path of the whisper.cpp ggml model, e.g. `models/ggml-base.en.bin`
+    pub model_path: Option<String>,
+    /// Spoken language code, e.g. `en` (detected when unset)
+    pub language: Option<String>,
+    /// Inference threads (whisper.cpp's default when 0)
+    pub threads: i32,
+    /// Longest stretch of audio, in seconds, indexed as one chunk
+    pub max_chunk_seconds: f64,
+    /// Audio files larger than this many megabytes are skipped
+    pub max_file_size_mb: u64,
+    /// Audio file extensions to transcribe
+    pub extensions: Vec<String>,
+}
+
+impl Default for TranscriptionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            model_path: None,
+            language: None,
+            threads: 0,
+            max_chunk_seconds: 60.0,
+            max_file_size_mb: 512,
+            extensions: ["wav", "mp3", "m4a", "flac", "ogg"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        }
+    }
+}
+
+impl TranscriptionConfig {
+    /// Whether `path` has one of the configured audio extensions
+    pub fn is_audio(&self, path: &Path) -> bool {
+        path.extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|ext| self.extensions.iter().any(|e| e.eq_ignore_ascii_case(ext)))
+    }
+}
+
+/// A stretch of transcribed speech
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptSegment {
+    /// Start, in seconds from the beginning of the recording
+    pub start: f64,
+    /// End, in seconds from the beginning of the recording
+    pub end: f64,
+    /// The spoken text, trimmed
+    pub text: String,
+}
+
+/// Merge consecutive `segments` into chunks spanning at most
+/// `max_seconds` each (a longer single segment stays whole). Segments
+/// without text are dropped.
+pub fn group_segments(segments: &[TranscriptSegment], max_seconds: f64) -> Vec<TranscriptSegment> {
+    let mut chunks: Vec<TranscriptSegment> = Vec::new();
+    for segment in segments.iter().filter(|s| !s.text.is_empty()) {
+        if let Some(chunk) = chunks.last_mut()
+            && segment.end - chunk.start <= max_seconds
+        {
+            chunk.end = segment.end;
+            chunk.text.push(' ');
+            chunk.text.push_str(&segment.text);
+            continue;
+        }
+        chunks.push(segment.clone());
+    }
+    chunks
+}
+
+/// Transcribes audio files
+#[derive(Debug, Clone)]
+pub struct Transcriber {
+    config: TranscriptionConfig,
+}
+
+impl Transcriber {
+    /// Create a transcriber with `config`
+    pub fn new(config: TranscriptionConfig) -> Self {
+        Self { config }
+    }
+
+    /// The transcriber's settings
+    pub fn config(&self) -> &TranscriptionConfig {
+        &self.config
+    }
+
+    /// Transcribe the audio file at `path` into timed segments. Loads
+    /// the model on every call and is blocking: async callers run it on
+    /// [`tokio::task::spawn_blocking`].
+    #[cfg(feature = "audio-transcription")]
+    pub fn transcribe(&self, path: &Path) -> Result<Vec<TranscriptSegment>> {
+        use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
+
+        let model = self.config.model_path.as_deref().ok_or_else(|| {
+            transcription_error("file_watcher.transcription.model_path is not set")
+        })?;
+        let samples = audio::mono_16khz(path)?;
+
+        let context = WhisperContext::new_with_params(model, WhisperContextParameters::default())
+            .map_err(transcription_error)?;
+        let mut state = context.create_state().map_err(transcription_error)?;
+        let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
+        params.set_language(Some(self.config.language.as_deref().unwrap_or("auto")));
+        if self.config.threads > 0 {
+            params.set_n_threads(self.config.threads);
+        }
+        params.set_print_progress(false);
+        params.set_print_realtime(false);
+        params.set_print_special(false);
+        params.set_print_timestamps(false);
+        state.full(params, &samples).map_err(transcription_error)?;
+
+        // Segment times are in centiseconds
+        let count = state.full_n_segments().map_err(transcription_error)?;
+        let mut segments = Vec::with_capacity(count.max(0) as usize);
+        for i in 0..count {
+            let text = state
+                .full_get_segment_text(i)
+                .map_err(transcription_error)?;
+            let start = state.full_get_segment_t0(i).map_err(transcription_error)?;
+            let end = state.full_get_segment_t1(i).map_err(transcription_error)?;
+            segments.push(TranscriptSegment {
+                start: start as f64 / 100.0,
+                end: end as f64 / 100.0,
+                text: text.trim().to_string(),
+            });
+        }
+        Ok(segments)
+    }
+
+    /// Transcribe the audio file at `path` (feature disabled fallback)
+    #[cfg(not(feature = "audio-transcription"))]
+    pub fn transcribe(&self, path: &Path) -> Result<Vec<TranscriptSegment>> {
+        Err(VectorizerError::TranscriptionError(format!(
+            "cannot read {:?}: built without the `audio-transcription` feature",
+            path
+        )))
+    }
+}
+
+#[cfg(feature = "audio-transcription")]
+fn transcription_error(e: impl std::fmt::Display) -> VectorizerError {
+    VectorizerError::TranscriptionError(e.to_string())
+}
+
+/// Audio decoding into the 16 kHz mono samples whisper expects
+#[cfg(feature = "audio-transcription")]
+mod audio {
+    use std::path::Path;
+
+    use symphonia::core::audio::SampleBuffer;
+    use symphonia::core::codecs::DecoderOptions;
+    use symphonia::core::errors::Error as SymphoniaError;
+    use symphonia::core::formats::FormatOptions;
+    use symphonia::core::io::MediaSourceStream;
+    use symphonia::core::meta::MetadataOptions;
+    use symphonia::core::probe::Hint;
+
+    use super::transcription_error;
+    use crate::error::Result;
+
+    /// Sample rate whisper models take
+    const WHISPER_SAMPLE_RATE: u32 = 16_000;
+
+    /// The first audio track of the file at `path`, downmixed to mono
+    /// and resampled to 16 kHz
+    pub(super) fn mono_16khz(path: &Path) -> Result<Vec<f32>> {
+        let file = std::fs::File::open(path).map_err(transcription_error)?;
+        let stream = MediaSourceStream::new(Box::new(file), Default::default());
+        let mut hint = Hint::new();
+        if let Some(extension) = path.extension().and_then(|e| e.to_str()) {
+            hint.with_extension(extension);
+        }
+        let mut format = symphonia::default::get_probe()
+            .format(
+                &hint,
+                stream,
+                &FormatOptions::default(),
+                &MetadataOptions::default(),
+            )
+            .map_err(transcription_error)?
+            .format;
+        let track = format
+            .default_track()
+            .ok_or_else(|| transcription_error("no audio track"))?;
+        let track_id = track.id;
+        let sample_rate = track
+            .codec_params
+            .sample_rate
+            .ok_or_else(|| transcription_error("unknown sample rate"))?;
+        let mut decoder = symphonia::default::get_codecs()
+            .make(&track.codec_params, &DecoderOptions::default())
+            .map_err(transcription_error)?;
+
+        let mut samples = Vec::new();
+        loop {
+            let packet = match format.next_packet() {
+                Ok(packet) => packet,
+                Err(SymphoniaError::IoError(e))
+                    if e.kind() == std::io::ErrorKind::UnexpectedEof =>
+                {
+                    break;
+                }
+                Err(SymphoniaError::ResetRequired) => break,
+                Err(e) => return Err(transcription_error(e)),
+            };
+            if packet.track_id() != track_id {
+                continue;
+            }
+            let decoded = match decoder.decode(&packet) {
+                Ok(decoded) => decoded,
+                // A corrupt packet loses its few milliseconds, not the file
+                Err(SymphoniaError::DecodeError(_)) => continue,
+                Err(e) => return Err(transcription_error(e)),
+            };
+            let spec = *decoded.spec();
+            let channels = spec.channels.count().max(1);
+            let mut buffer = SampleBuffer::<f32>::new(decoded.capacity() as u64, spec);
+            buffer.copy_interleaved_ref(decoded);
+            samples.extend(
+                buffer
+                    .samples()
+                    .chunks(channels)
+                    .map(|frame| frame.iter().sum::<f32>() / channels as f32),
+            );
+        }
+        Ok(resample(&samples, sample_rate, WHISPER_SAMPLE_RATE))
+    }
+
+    /// Linear-interpolation resampling; plenty for speech recognition
+    pub(super) fn resample(samples: &[f32], from: u32, to: u32) -> Vec<f32> {
+        if from == to || samples.is_empty() {
+            return samples.to_vec();
+        }
+        let step = f64::from(from) / f64::from(to);
+        let len = (samples.len() as f64 / step) as usize;
+        (0..len)
+            .map(|i| {
+                let position = i as f64 * step;
+                let index = position as usize;
+                let fraction = (position - index as f64) as f32;
+                let current = samples[index];
+                let next = samples.get(index + 1).copied().unwrap_or(current);
+                current + (next - current) * fraction
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn segment(start: f64, end: f64, text: &str) -> TranscriptSegment {
+        TranscriptSegment {
+            start,
+            end,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_default_config_is_disabled() {
+        let config = TranscriptionConfig::default();
+        assert!(!config.enabled);
+        assert!(config.model_path.is_none());
+        assert!(config.is_audio(Path::new("standup/2026-10-01.MP3")));
+        assert!(!config.is_audio(Path::new("notes.md")));
+    }
+
+    #[test]
+    fn test_partial_yaml_keeps_defaults() {
+        let config: TranscriptionConfig =
+            serde_yaml::from_str("enabled: true\nmodel_path: models/ggml-base.en.bin").unwrap();
+        assert!(config.enabled);
+        assert_eq!(
+            config.model_path.as_deref(),
+            Some("models/ggml-base.en.bin")
+        );
+        assert_eq!(config.max_chunk_seconds, 60.0);
+    }
+
+    #[test]
+    fn test_group_segments_up_to_max_seconds() {
+        let segments = [
+            segment(0.0, 4.0, "Good morning."),
+            segment(4.0, 9.5, "Let's start."),
+            segment(9.5, 10.0, ""),
+            segment(10.0, 16.0, "First item."),
+            segment(16.0, 40.0, "A long update."),
+        ];
+        let chunks = group_segments(&segments, 12.0);
+        assert_eq!(
+            chunks,
+            [
+                segment(0.0, 9.5, "Good morning. Let's start."),
+                segment(10.0, 16.0, "First item."),
+                segment(16.0, 40.0, "A long update."),
+            ]
+        );
+    }
+
+    #[cfg(not(feature = "audio-transcription"))]
+    #[test]
+    fn test_transcribe_without_feature_fails() {
+        let transcriber = Transcriber::new(TranscriptionConfig::default());
+        let err = transcriber
+            .transcribe(Path::new("meeting.wav"))
+            .unwrap_err();
+        assert!(matches!(err, VectorizerError::TranscriptionError(_)));
+    }
+
+    #[cfg(feature = "audio-transcription")]
+    #[test]
+    fn test_resample_to_16khz() {
+        let samples: Vec<f32> = (0..48).map(|i| i as f32).collect();
+        let resampled = audio::resample(&samples, 48_000, 16_000);
+        assert_eq!(resampled.len(), 16);
+        assert_eq!(resampled[1], 3.0);
+        assert_eq!(audio::resample(&samples, 16_000, 16_000), samples);
+    }
+}
@@ -270,6 +270,15 @@ pub struct CollectionProcessing {
 
     /// File patterns to exclude
     pub exclude_patterns: Vec<String>,
+
+    /// How files are split into chunks (fixed-size when unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chunking: Option<crate::chunking::ChunkingStrategy>,
+
+    /// Detect each chunk's language and build a BM25 vocabulary per
+    /// language (see [`crate::language`])
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub language_detection: bool,
 }
 
 /// Processing settings
@@ -695,6 +695,8 @@ pub fn analysis_to_project_config(
             metric: None,
             indexing: None,
             processing: None,
+            chunking: None,
+            language_detection: false,
         })
         .collect();
 
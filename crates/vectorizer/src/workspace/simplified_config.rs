@@ -142,6 +142,16 @@ pub struct SimplifiedCollectionConfig {
     /// Override processing configuration
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub processing: Option<ProcessingConfig>,
+
+    /// How files are split into chunks, e.g. `markdown` or `code`
+    /// (fixed-size when unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chunking: Option<crate::chunking::ChunkingStrategy>,
+
+    /// Detect each chunk's language, stored as `lang` in its payload,
+    /// and build a vocabulary per language (for mixed-language corpora)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub language_detection: bool,
 }
 
 impl Default for DefaultConfiguration {
@@ -453,6 +463,8 @@ impl SimplifiedCollectionConfig {
                 chunk_overlap: processing_config.chunk_overlap as usize,
                 include_patterns: self.include_patterns.clone(),
                 exclude_patterns: self.exclude_patterns.clone(),
+                chunking: self.chunking,
+                language_detection: self.language_detection,
             },
         }
     }
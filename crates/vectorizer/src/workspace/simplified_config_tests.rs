@@ -67,6 +67,8 @@ fn test_simplified_collection_config() {
         metric: None,
         indexing: None,
         processing: None,
+        chunking: None,
+        language_detection: false,
     };
 
     assert_eq!(collection.name, "test_collection");
@@ -189,6 +191,8 @@ fn test_collection_with_overrides() {
         metric: Some("euclidean".to_string()),
         indexing: None,
         processing: None,
+        chunking: None,
+        language_detection: false,
     };
 
     assert!(collection.embedding.is_some());
@@ -32,8 +32,16 @@ fn create_test_collection_config() -> CollectionConfig {
         graph: Some(GraphConfig {
             enabled: true,
             auto_relationship: Default::default(),
+            entity_extraction: Default::default(),
         }),
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     }
 }
 
@@ -88,6 +96,7 @@ async fn test_graph_find_related_mcp_tool() {
         embedding_manager.clone(),
         None,
         Arc::new(vectorizer::db::UpsertQueue::permissive()),
+        None,
     )
     .await;
     assert!(result.is_ok());
@@ -152,6 +161,7 @@ async fn test_graph_find_path_mcp_tool() {
         embedding_manager.clone(),
         None,
         Arc::new(vectorizer::db::UpsertQueue::permissive()),
+        None,
     )
     .await;
     assert!(result.is_ok());
@@ -203,6 +213,7 @@ async fn test_graph_get_neighbors_mcp_tool() {
         embedding_manager.clone(),
         None,
         Arc::new(vectorizer::db::UpsertQueue::permissive()),
+        None,
     )
     .await;
     assert!(result.is_ok());
@@ -275,6 +286,7 @@ async fn test_graph_create_edge_mcp_tool() {
         embedding_manager.clone(),
         None,
         Arc::new(vectorizer::db::UpsertQueue::permissive()),
+        None,
     )
     .await;
     assert!(result.is_ok());
@@ -307,6 +319,7 @@ async fn test_graph_mcp_tool_error_handling() {
         embedding_manager.clone(),
         None,
         Arc::new(vectorizer::db::UpsertQueue::permissive()),
+        None,
     )
     .await;
     // Should return error for non-existent collection
@@ -384,6 +397,7 @@ async fn test_graph_discover_edges_mcp_tool_creates_edges() {
         embedding_manager.clone(),
         None,
         Arc::new(vectorizer::db::UpsertQueue::permissive()),
+        None,
     )
     .await;
     assert!(result.is_ok(), "Discovery should succeed");
@@ -508,6 +522,7 @@ async fn test_graph_discover_edges_mcp_tool_node_specific() {
         embedding_manager.clone(),
         None,
         Arc::new(vectorizer::db::UpsertQueue::permissive()),
+        None,
     )
     .await;
     assert!(result.is_ok(), "Discovery should succeed");
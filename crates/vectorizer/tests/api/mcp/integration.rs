@@ -28,6 +28,7 @@ async fn test_mcp_tool_handling() {
         embedding_manager.clone(),
         None,
         Arc::new(vectorizer::db::UpsertQueue::permissive()),
+        None,
     )
     .await;
     assert!(result.is_ok());
@@ -58,6 +59,7 @@ async fn test_mcp_tool_handling() {
         embedding_manager.clone(),
         None,
         Arc::new(vectorizer::db::UpsertQueue::permissive()),
+        None,
     )
     .await;
     assert!(result.is_ok());
@@ -77,6 +79,7 @@ async fn test_mcp_tool_handling() {
         embedding_manager.clone(),
         None,
         Arc::new(vectorizer::db::UpsertQueue::permissive()),
+        None,
     )
     .await;
     assert!(result.is_ok());
@@ -97,6 +100,7 @@ async fn test_mcp_tool_error_handling() {
         embedding_manager.clone(),
         None,
         Arc::new(vectorizer::db::UpsertQueue::permissive()),
+        None,
     )
     .await;
     assert!(result.is_err());
@@ -110,6 +114,7 @@ async fn test_mcp_tool_error_handling() {
         embedding_manager.clone(),
         None,
         Arc::new(vectorizer::db::UpsertQueue::permissive()),
+        None,
     )
     .await;
     assert!(result.is_err());
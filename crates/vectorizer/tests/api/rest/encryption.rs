@@ -41,6 +41,13 @@ fn test_encrypted_payload_insertion_via_collection() {
             required: false,
             allow_mixed: true,
         }),
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     store.create_collection(collection_name, config).unwrap();
@@ -120,6 +127,13 @@ fn test_unencrypted_payload_backward_compatibility() {
         sharding: None,
         graph: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     store.create_collection(collection_name, config).unwrap();
@@ -192,6 +206,13 @@ fn test_mixed_encrypted_and_unencrypted_payloads() {
             required: false,
             allow_mixed: true, // Allow both encrypted and unencrypted
         }),
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     store.create_collection(collection_name, config).unwrap();
@@ -261,6 +282,13 @@ fn test_encryption_required_validation() {
             required: true, // Require encryption
             allow_mixed: false,
         }),
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     store.create_collection(collection_name, config).unwrap();
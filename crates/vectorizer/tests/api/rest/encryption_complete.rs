@@ -38,6 +38,13 @@ fn create_test_collection(store: &VectorStore, name: &str, dimension: usize) {
         sharding: None,
         graph: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
     store.create_collection(name, config).unwrap();
 }
@@ -238,6 +245,13 @@ fn test_qdrant_upsert_mixed_encryption() {
             required: false,
             allow_mixed: true,
         }),
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
     store.create_collection(collection_name, config).unwrap();
 
@@ -413,6 +427,13 @@ fn test_encryption_required_enforcement() {
             required: true,
             allow_mixed: false,
         }),
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
     store.create_collection(collection_name, config).unwrap();
 
@@ -44,6 +44,13 @@ fn create_test_collection(
         sharding: None,
         graph: None,
         encryption,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
     store.create_collection_cpu_only(name, config).unwrap();
 }
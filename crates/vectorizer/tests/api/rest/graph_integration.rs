@@ -31,8 +31,16 @@ fn create_test_collection_config() -> CollectionConfig {
         graph: Some(GraphConfig {
             enabled: true,
             auto_relationship: Default::default(),
+            entity_extraction: Default::default(),
         }),
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     }
 }
 
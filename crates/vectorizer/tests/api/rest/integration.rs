@@ -25,6 +25,13 @@ fn test_collection_config_creation() {
         normalization: None,
         storage_type: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     assert_eq!(config.dimension, 384);
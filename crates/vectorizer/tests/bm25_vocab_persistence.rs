@@ -71,6 +71,13 @@ fn bm25_vocabulary_survives_restart_and_search_still_matches() {
         storage_type: Some(vectorizer::models::StorageType::Memory),
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
     store.create_collection(COLLECTION, config).unwrap();
 
@@ -368,3 +368,27 @@ fn test_tenant_aware_routing_with_epochs() {
     let shard_t1_again = router.get_shard_for_tenant_vector("tenant-A", "doc-1");
     assert_eq!(shard_t1, shard_t1_again);
 }
+
+#[test]
+fn test_ring_points_reproduce_vector_routing() {
+    let router = DistributedShardRouter::new(50);
+    let nodes: Vec<NodeId> = (0..3).map(|i| NodeId::new(format!("node-{i}"))).collect();
+    let shards: Vec<ShardId> = (0..8).map(ShardId::new).collect();
+    router.rebalance(&shards, &nodes);
+
+    let points = router.ring_points();
+    assert_eq!(points.len(), 8 * 50);
+    assert!(points.windows(2).all(|w| w[0].0 < w[1].0));
+
+    // The lookup a client performs against the published ring.
+    for i in 0..500 {
+        let id = format!("vector-{i}");
+        let hash = xxhash_rust::xxh3::xxh3_64(id.as_bytes());
+        let owner = points
+            .iter()
+            .find(|(point, _)| *point >= hash)
+            .unwrap_or(&points[0])
+            .1;
+        assert_eq!(owner, router.get_shard_for_vector(&id), "{id}");
+    }
+}
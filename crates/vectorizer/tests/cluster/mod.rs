@@ -4,3 +4,4 @@
 
 mod distributed_resilience;
 mod memory_limits;
+mod snapshot;
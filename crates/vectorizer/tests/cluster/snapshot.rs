@@ -0,0 +1,105 @@
+//! Coordinated cluster snapshots on a single-node cluster: the
+//! coordinator runs both phases against the local node, and a restore
+//! brings back exactly the state at the cut.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use vectorizer::cluster::{
+    ClusterClientPool, ClusterConfig, ClusterManager, ClusterSnapshotCoordinator,
+};
+use vectorizer::db::VectorStore;
+use vectorizer::error::VectorizerError;
+use vectorizer::models::{CollectionConfig, DistanceMetric, QuantizationConfig, Vector};
+
+fn config() -> CollectionConfig {
+    CollectionConfig {
+        dimension: 4,
+        metric: DistanceMetric::Euclidean,
+        quantization: QuantizationConfig::None,
+        ..Default::default()
+    }
+}
+
+fn vector(id: &str, x: f32) -> Vector {
+    Vector::new(id.to_string(), vec![x, 0.0, 0.0, 1.0])
+}
+
+fn coordinator(store: Arc<VectorStore>, root: &std::path::Path) -> ClusterSnapshotCoordinator {
+    let manager = ClusterManager::new(ClusterConfig {
+        node_id: Some("node-a".to_string()),
+        ..Default::default()
+    })
+    .unwrap();
+    ClusterSnapshotCoordinator::new(
+        store,
+        Arc::new(manager),
+        Arc::new(ClusterClientPool::new(Duration::from_secs(1))),
+    )
+    .with_root(root.to_path_buf())
+}
+
+#[tokio::test]
+async fn snapshot_restores_the_state_at_the_cut() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = Arc::new(VectorStore::new_cpu_only());
+    store.create_collection("docs", config()).unwrap();
+    store
+        .insert("docs", vec![vector("a", 1.0), vector("b", 2.0)])
+        .unwrap();
+    let coordinator = coordinator(store.clone(), dir.path());
+
+    let manifest = coordinator.create().await.unwrap();
+    assert_eq!(manifest.coordinator, "node-a");
+    assert_eq!(manifest.nodes.len(), 1);
+    assert_eq!(manifest.nodes[0].vector_count, 2);
+    assert_eq!(manifest.nodes[0].collections, ["docs"]);
+    assert!(
+        store.write_fence().holder().is_none(),
+        "commit lowers the fence"
+    );
+
+    store.insert("docs", vec![vector("c", 3.0)]).unwrap();
+    store.delete("docs", "a").unwrap();
+
+    let listed = coordinator.list().unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].id, manifest.id);
+
+    let results = coordinator.restore(&manifest.id).await.unwrap();
+    assert!(results.iter().all(|r| r.success), "{results:?}");
+    let collection = store.get_collection("docs").unwrap();
+    assert_eq!(collection.vector_count(), 2);
+    drop(collection);
+    assert_eq!(store.get_vector("docs", "a").unwrap().data[0], 1.0);
+    assert!(store.get_vector("docs", "c").is_err());
+}
+
+#[tokio::test]
+async fn writes_are_refused_while_fenced() {
+    let store = VectorStore::new_cpu_only();
+    store.create_collection("docs", config()).unwrap();
+
+    store
+        .write_fence()
+        .raise("snap", Duration::from_secs(5))
+        .await
+        .unwrap();
+    let err = store.insert("docs", vec![vector("a", 1.0)]).unwrap_err();
+    assert!(matches!(err, VectorizerError::WritesFenced(_)), "{err}");
+    assert!(store.delete("docs", "a").is_err());
+
+    store.write_fence().lower("snap");
+    store.insert("docs", vec![vector("a", 1.0)]).unwrap();
+}
+
+#[tokio::test]
+async fn unknown_snapshot_is_not_found() {
+    let dir = tempfile::tempdir().unwrap();
+    let coordinator = coordinator(Arc::new(VectorStore::new_cpu_only()), dir.path());
+    assert!(matches!(
+        coordinator.restore("missing").await,
+        Err(VectorizerError::NotFound(_))
+    ));
+    assert!(coordinator.get("../escape").is_err());
+}
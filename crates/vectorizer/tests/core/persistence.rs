@@ -60,6 +60,13 @@ async fn test_auto_save_manager_mark_changed() {
         storage_type: Some(StorageType::Memory),
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     store.create_collection("autosave_test", config).unwrap();
@@ -21,6 +21,13 @@ async fn test_scalar_quantization_8bit() {
         storage_type: Some(vectorizer::models::StorageType::Memory),
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     store.create_collection("sq8_collection", config).unwrap();
@@ -72,6 +79,13 @@ async fn test_product_quantization() {
         storage_type: Some(vectorizer::models::StorageType::Memory),
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     store.create_collection("pq_collection", config).unwrap();
@@ -115,6 +129,13 @@ async fn test_binary_quantization() {
         storage_type: Some(vectorizer::models::StorageType::Memory),
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     store
@@ -163,6 +184,13 @@ async fn test_quantization_search_quality() {
         storage_type: Some(vectorizer::models::StorageType::Memory),
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     store.create_collection("quantized_search", config).unwrap();
@@ -205,6 +233,13 @@ async fn test_quantization_memory_efficiency() {
         storage_type: Some(vectorizer::models::StorageType::Memory),
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     store
@@ -224,6 +259,13 @@ async fn test_quantization_memory_efficiency() {
         storage_type: Some(vectorizer::models::StorageType::Memory),
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     store.create_collection("sq8", config_sq8).unwrap();
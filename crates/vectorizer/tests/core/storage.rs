@@ -3,7 +3,7 @@
 #![allow(clippy::unwrap_used, clippy::expect_used)]
 
 use tempfile::tempdir;
-use vectorizer::db::VectorStore;
+use vectorizer::db::{Collection, VectorStore};
 use vectorizer::models::{CollectionConfig, DistanceMetric, StorageType, Vector};
 
 #[tokio::test]
@@ -25,6 +25,13 @@ async fn test_mmap_collection_creation() {
         storage_type: Some(StorageType::Mmap),
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     // Create collection with MMAP storage
@@ -53,6 +60,13 @@ async fn test_mmap_insert_and_retrieve() {
         storage_type: Some(StorageType::Mmap),
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     store.create_collection("mmap_collection", config).unwrap();
@@ -120,6 +134,13 @@ async fn test_mmap_large_dataset() {
         storage_type: Some(StorageType::Mmap),
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     store
@@ -173,6 +194,13 @@ async fn test_mmap_update_and_delete() {
         storage_type: Some(StorageType::Mmap),
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     store.create_collection("mmap_collection", config).unwrap();
@@ -243,6 +271,13 @@ async fn test_mmap_search() {
         storage_type: Some(StorageType::Mmap),
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     store.create_collection("mmap_collection", config).unwrap();
@@ -267,3 +302,16 @@ async fn test_mmap_search() {
     assert!(!results.is_empty());
     assert!(results.len() <= 5);
 }
+
+#[test]
+fn test_mmap_file_paths_do_not_collide() {
+    let names = ["a/b", "a_b", "a:b", "a\\b", "a%2Fb", "tenant:docs"];
+    let paths = names.map(Collection::mmap_file_path);
+
+    let dir = paths[0].parent().unwrap();
+    for (i, (name, path)) in names.iter().zip(&paths).enumerate() {
+        assert_eq!(path.parent(), Some(dir), "{name} escaped its directory");
+        assert!(!paths[i + 1..].contains(path), "{name} shares a file");
+    }
+    assert!(paths[1].ends_with("a_b.mmap"));
+}
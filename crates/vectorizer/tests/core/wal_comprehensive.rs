@@ -33,6 +33,13 @@ async fn test_wal_multiple_operations() {
         storage_type: Some(vectorizer::models::StorageType::Memory),
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     store.create_collection("test_collection", config).unwrap();
@@ -89,6 +96,13 @@ async fn test_wal_with_payload() {
         storage_type: Some(vectorizer::models::StorageType::Memory),
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     store.create_collection("test_collection", config).unwrap();
@@ -151,6 +165,13 @@ async fn test_wal_update_sequence() {
         storage_type: Some(vectorizer::models::StorageType::Memory),
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     store.create_collection("test_collection", config).unwrap();
@@ -219,6 +240,13 @@ async fn test_wal_delete_sequence() {
         storage_type: Some(vectorizer::models::StorageType::Memory),
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     store.create_collection("test_collection", config).unwrap();
@@ -284,6 +312,13 @@ async fn test_wal_multiple_collections() {
         storage_type: Some(vectorizer::models::StorageType::Memory),
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     // Create multiple collections
@@ -355,6 +390,13 @@ async fn test_wal_checkpoint() {
         storage_type: Some(vectorizer::models::StorageType::Memory),
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     store.create_collection("test_collection", config).unwrap();
@@ -405,6 +447,13 @@ async fn test_wal_error_handling() {
         storage_type: Some(vectorizer::models::StorageType::Memory),
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     store.create_collection("test_collection", config).unwrap();
@@ -435,6 +484,13 @@ async fn test_wal_without_enabling() {
         storage_type: Some(vectorizer::models::StorageType::Memory),
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     store.create_collection("test_collection", config).unwrap();
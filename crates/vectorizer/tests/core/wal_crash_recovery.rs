@@ -41,6 +41,13 @@ async fn test_wal_crash_recovery_insert() {
         storage_type: Some(vectorizer::models::StorageType::Memory),
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
     store
         .create_collection("test_collection", config.clone())
@@ -138,6 +145,13 @@ async fn test_wal_crash_recovery_update() {
         storage_type: Some(vectorizer::models::StorageType::Memory),
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
     store
         .create_collection("test_collection", config.clone())
@@ -236,6 +250,13 @@ async fn test_wal_crash_recovery_delete() {
         storage_type: Some(vectorizer::models::StorageType::Memory),
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
     store
         .create_collection("test_collection", config.clone())
@@ -313,6 +334,13 @@ async fn test_wal_recover_all_collections() {
         storage_type: Some(vectorizer::models::StorageType::Memory),
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     // Create multiple collections
@@ -45,6 +45,13 @@ async fn test_vector_store_wal_integration() {
         storage_type: Some(vectorizer::models::StorageType::Memory),
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     assert!(store.create_collection("test_collection", config).is_ok());
@@ -130,6 +137,13 @@ async fn test_wal_recover_all_collections_with_data() {
         storage_type: Some(vectorizer::models::StorageType::Memory),
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     // Create multiple collections
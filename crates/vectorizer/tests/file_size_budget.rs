@@ -45,7 +45,7 @@ const BUDGETS: &[(&str, usize, &str)] = &[
     (
         "src/server/rest_handlers/common.rs",
         160,
-        "3 small helpers + admit_upsert (issue #263 phase9 §3 — \
+        "5 small helpers + admit_upsert (issue #263 phase9 §3 — \
          centralizes the per-collection upsert admission + 429 \
          translation reused by insert_text, insert_vectors, and \
          do_batch_insert_texts)",
@@ -63,16 +63,17 @@ const BUDGETS: &[(&str, usize, &str)] = &[
     (
         "src/server/rest_handlers/collections.rs",
         1010,
-        "7 handlers incl. list/create + phase13 reencode_collection / \
+        "7 handlers incl. list/get/delete + phase13 reencode_collection / \
          set_collection_ttl + phase14 rename / reindex / native snapshot \
          CRUD (snapshot_native, list_collection_snapshots_native, \
-         restore_collection_snapshot_native) + phase33 §2 \
-         embedding_provider validation on create_collection (issue \
-         #306 — resolves the requested provider against the registry \
-         and rejects with 400 unsupported_provider / \
-         provider_dimension_mismatch instead of silent BM25 coercion). \
-         Re-tighten when the schema-evolution endpoints split out \
-         (follow-up task).",
+         restore_collection_snapshot_native) + Raft-replicated delete, \
+         API-key namespace filtering of the listing and \
+         index-optimization / recall-profile status on GET. Creation \
+         (with the phase33 §2 embedding_provider validation, issue \
+         #306), PATCH, clone and point-in-time restore live in \
+         collection_admin/ (collections_create.rs, collections_patch.rs, \
+         collections_clone.rs, collections_restore.rs). Re-tighten \
+         when the schema-evolution endpoints split out (follow-up task).",
     ),
     (
         "src/server/rest_handlers/vectors.rs",
@@ -105,14 +106,24 @@ const BUDGETS: &[(&str, usize, &str)] = &[
         "src/server/rest_handlers/search.rs",
         1050,
         "7 search-family handlers + hybrid search (dense + sparse + \
-         rank-fusion + per-axis weights) + batch_search_vectors + \
+         rank-fusion + per-axis weights) + \
          search_by_file + search_by_collection variants + Qdrant-shape \
          adapters + phase14 explain_search HNSW execution-trace handler. \
          Grew to 1045 LOC with the phase12-16 SDK-parity handlers. \
+         batch_search_vectors moved out to search_features/batch_search.rs, \
+         full_text_search to search_features/full_text_search.rs and the \
+         limit / exact / timeout_ms parsing and search metrics to \
+         search_common.rs. \
          Split across concern axes is blocked until the hybrid-search \
          task lands (phase7_hybrid-search-extraction); re-tighten this \
          budget there.",
     ),
+    (
+        "src/server/rest_handlers/search_features/batch_search.rs",
+        350,
+        "batch_search_vectors + entry parsing, batched query embedding \
+         and the hand-off to the collection's parallel batch search",
+    ),
     (
         "src/server/rest_handlers/intelligent_search.rs",
         400,
@@ -49,6 +49,13 @@ mod hive_gpu_integration_tests {
                 compression: vectorizer::models::CompressionConfig::default(),
                 normalization: None,
                 encryption: None,
+                id_policy: None,
+                quota: None,
+                scoring: None,
+                tenant_partition: None,
+                dedup: None,
+                chunking: None,
+                full_text: None,
             };
 
             store
@@ -98,6 +105,13 @@ mod hive_gpu_integration_tests {
             compression: vectorizer::models::CompressionConfig::default(),
             normalization: None,
             encryption: None,
+            id_policy: None,
+            quota: None,
+            scoring: None,
+            tenant_partition: None,
+            dedup: None,
+            chunking: None,
+            full_text: None,
         };
 
         store
@@ -150,6 +164,13 @@ mod no_hive_gpu_tests {
             compression: vectorizer::models::CompressionConfig::default(),
             normalization: None,
             encryption: None,
+            id_policy: None,
+            quota: None,
+            scoring: None,
+            tenant_partition: None,
+            dedup: None,
+            chunking: None,
+            full_text: None,
         };
 
         store
@@ -63,6 +63,13 @@ pub fn create_test_collection_config(dimension: usize) -> CollectionConfig {
         storage_type: Some(StorageType::Memory),
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     }
 }
 
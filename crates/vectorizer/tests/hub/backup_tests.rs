@@ -31,6 +31,7 @@ fn test_backup_config_serialization() {
         max_backup_age_hours: 24 * 7, // 1 week
         compression_enabled: false,
         compression_level: 9,
+        object_store: None,
     };
 
     let json = serde_json::to_string(&config).unwrap();
@@ -63,6 +63,13 @@ fn searches_complete_while_batch_insert_is_running() {
         storage_type: Some(vectorizer::models::StorageType::Memory),
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
     store.create_collection("mixed_load", config).unwrap();
 
@@ -4,7 +4,7 @@
 
 use serde_json::json;
 use vectorizer::db::VectorStore;
-use vectorizer::models::{CollectionConfig, DistanceMetric, Payload, QuantizationConfig};
+use vectorizer::models::{CollectionConfig, DistanceMetric, Payload, QuantizationConfig, Vector};
 
 #[allow(clippy::duplicate_mod)]
 #[path = "../helpers/mod.rs"]
@@ -329,3 +329,54 @@ fn test_binary_quantization_compression_ratio() {
     // Verify collection was created successfully
     assert_eq!(collection.vector_count(), 100);
 }
+
+#[test]
+fn test_binary_quantization_rescore_finds_exact_match() {
+    let store = VectorStore::new();
+
+    let config = CollectionConfig {
+        dimension: 128,
+        quantization: QuantizationConfig::Binary,
+        encryption: None,
+        ..Default::default()
+    };
+
+    store
+        .create_collection("test_binary_rescore", config)
+        .unwrap();
+
+    // Pseudo-random signs so the 1-bit codes actually differ per vector
+    // (the shared helper's monotonic ramps all collapse to one code).
+    let mut seed: u32 = 0x9E37_79B9;
+    let vectors: Vec<Vector> = (0..200)
+        .map(|i| {
+            let data = (0..128)
+                .map(|_| {
+                    seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                    (seed >> 8) as f32 / (1u32 << 24) as f32 - 0.5
+                })
+                .collect();
+            Vector::new(format!("v{i}"), data)
+        })
+        .collect();
+    let target = vectors[42].clone();
+    insert_test_vectors(&store, "test_binary_rescore", vectors).unwrap();
+
+    let collection = store.get_collection("test_binary_rescore").unwrap();
+    let results = collection
+        .search_quantized(&target.data, 5, 4.0, true)
+        .unwrap();
+
+    // Rescoring uses full-precision vectors, so the query's own vector is
+    // first with an exact cosine score rather than a bit-agreement score.
+    assert_eq!(results.len(), 5);
+    assert_eq!(results[0].id, target.id);
+    assert!((results[0].score - 1.0).abs() < 1e-3);
+    assert!(results.windows(2).all(|w| w[0].score >= w[1].score));
+
+    // Without rescoring the score is normalized Hamming agreement.
+    let raw = collection
+        .search_quantized(&target.data, 5, 1.0, false)
+        .unwrap();
+    assert_eq!(raw[0].score, 1.0);
+}
@@ -42,6 +42,13 @@ fn create_test_collection_config() -> CollectionConfig {
             rebalance_threshold: 0.2,
         }),
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     }
 }
 
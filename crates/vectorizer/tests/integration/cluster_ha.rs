@@ -12,11 +12,17 @@ use std::collections::BTreeMap;
 use std::sync::Arc;
 use std::time::Duration;
 
+use tonic::transport::Server;
 use vectorizer::cluster::raft_node::{ClusterCommand, RaftManager, RaftNodeInfo};
-use vectorizer::cluster::{DistributedShardRouter, HaManager, LeaderRouter, NodeId};
+use vectorizer::cluster::raft_watcher::spawn_store_applier;
+use vectorizer::cluster::{
+    ClusterConfig, ClusterGrpcService, ClusterManager, DistributedShardRouter, HaManager,
+    LeaderRouter, NodeId,
+};
 use vectorizer::db::VectorStore;
 use vectorizer::models::{CollectionConfig, DistanceMetric, StorageType};
 use vectorizer::replication::ReplicationConfig;
+use vectorizer_protocol::grpc_gen::cluster::cluster_service_server::ClusterServiceServer;
 
 // ---------------------------------------------------------------------------
 // Test 1: Raft single-node bootstrap and state machine
@@ -156,6 +162,194 @@ async fn test_raft_propose_collection_and_shard_commands() {
     assert_eq!(state.shard_assignments[&0], (1, 1));
 }
 
+#[tokio::test]
+async fn test_raft_rejects_conflicting_collection_and_replicates_metadata() {
+    let mgr = RaftManager::new(1).await.unwrap();
+    mgr.initialize_single().await.unwrap();
+    tokio::time::sleep(Duration::from_secs(2)).await;
+
+    let create = |dimension: usize| ClusterCommand::CreateCollection {
+        name: "race".to_string(),
+        dimension,
+        metric: "cosine".to_string(),
+    };
+    assert!(mgr.propose(create(128)).await.unwrap().success);
+    // The second writer loses instead of silently redefining the collection.
+    assert!(!mgr.propose(create(256)).await.unwrap().success);
+
+    let resp = mgr
+        .propose(ClusterCommand::SetMetadata {
+            key: "owner".to_string(),
+            value: "\"search-team\"".to_string(),
+        })
+        .await
+        .unwrap();
+    assert!(resp.success);
+
+    let state = mgr.state().await;
+    assert_eq!(state.collections["race"], (128, "cosine".to_string()));
+    assert_eq!(state.metadata["owner"], "\"search-team\"");
+}
+
+/// Start a Raft node whose peers reach it through a real cluster gRPC
+/// server on a free local port.
+async fn spawn_raft_node(node_id: u64, store: Arc<VectorStore>) -> (Arc<RaftManager>, u16) {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    drop(listener);
+
+    let mgr = Arc::new(RaftManager::new(node_id).await.unwrap());
+    let cluster_mgr = Arc::new(
+        ClusterManager::new(ClusterConfig {
+            enabled: true,
+            node_id: Some(format!("node-{node_id}")),
+            ..Default::default()
+        })
+        .unwrap(),
+    );
+    let svc = ClusterGrpcService::new(store, cluster_mgr, Some(mgr.clone()));
+    tokio::spawn(async move {
+        Server::builder()
+            .add_service(ClusterServiceServer::new(svc))
+            .serve(addr)
+            .await
+            .unwrap();
+    });
+    (mgr, addr.port())
+}
+
+/// Poll `check` every 100ms for up to 10s.
+async fn eventually(mut check: impl FnMut() -> bool) -> bool {
+    for _ in 0..100 {
+        if check() {
+            return true;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    false
+}
+
+#[tokio::test]
+async fn test_raft_alias_commands_reach_follower_store() {
+    let stores = [Arc::new(VectorStore::new()), Arc::new(VectorStore::new())];
+    // Collections reach followers over the data-plane stream; stand that in.
+    for store in &stores {
+        store
+            .create_collection(
+                "docs",
+                CollectionConfig {
+                    dimension: 8,
+                    metric: DistanceMetric::Cosine,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+    }
+
+    let mut nodes = Vec::new();
+    for (i, store) in stores.iter().enumerate() {
+        let (mgr, port) = spawn_raft_node(i as u64 + 1, store.clone()).await;
+        spawn_store_applier(mgr.state_machine.subscribe(), store.clone());
+        nodes.push((mgr, port));
+    }
+    tokio::time::sleep(Duration::from_millis(150)).await;
+
+    let members: BTreeMap<u64, RaftNodeInfo> = nodes
+        .iter()
+        .map(|(mgr, port)| {
+            (
+                mgr.node_id,
+                RaftNodeInfo {
+                    address: "127.0.0.1".to_string(),
+                    grpc_port: *port,
+                },
+            )
+        })
+        .collect();
+    nodes[0].0.initialize_cluster(members).await.unwrap();
+
+    assert!(
+        eventually(|| nodes[0].0.current_leader().is_some()).await,
+        "no leader elected"
+    );
+    let leader_id = nodes[0].0.current_leader().unwrap();
+    let (leader, follower) = if leader_id == nodes[0].0.node_id {
+        (0, 1)
+    } else {
+        (1, 0)
+    };
+
+    let resp = nodes[leader]
+        .0
+        .propose(ClusterCommand::CreateAlias {
+            alias: "latest".to_string(),
+            collection: "docs".to_string(),
+        })
+        .await
+        .unwrap();
+    assert!(resp.success);
+    assert!(
+        eventually(
+            || stores[follower].list_aliases() == vec![("latest".to_string(), "docs".to_string())]
+        )
+        .await,
+        "follower never applied CreateAlias"
+    );
+    assert_eq!(
+        stores[follower].get_collection("latest").unwrap().name(),
+        "docs"
+    );
+
+    let resp = nodes[leader]
+        .0
+        .propose(ClusterCommand::DeleteAlias {
+            alias: "latest".to_string(),
+        })
+        .await
+        .unwrap();
+    assert!(resp.success);
+    assert!(
+        eventually(|| stores[follower].list_aliases().is_empty()).await,
+        "follower never applied DeleteAlias"
+    );
+
+    // Collections committed to the log reach the follower too.
+    let resp = nodes[leader]
+        .0
+        .propose(ClusterCommand::CreateCollection {
+            name: "events".to_string(),
+            dimension: 16,
+            metric: "dot_product".to_string(),
+        })
+        .await
+        .unwrap();
+    assert!(resp.success);
+    assert!(
+        eventually(|| stores[follower].has_collection_in_memory("events")).await,
+        "follower never applied CreateCollection"
+    );
+    let config = stores[follower]
+        .get_collection("events")
+        .unwrap()
+        .config()
+        .clone();
+    assert_eq!(config.dimension, 16);
+    assert_eq!(config.metric, DistanceMetric::DotProduct);
+
+    let resp = nodes[leader]
+        .0
+        .propose(ClusterCommand::DeleteCollection {
+            name: "events".to_string(),
+        })
+        .await
+        .unwrap();
+    assert!(resp.success);
+    assert!(
+        eventually(|| !stores[follower].has_collection_in_memory("events")).await,
+        "follower never applied DeleteCollection"
+    );
+}
+
 // ---------------------------------------------------------------------------
 // Test 4: MMap storage enforcement in cluster mode
 // ---------------------------------------------------------------------------
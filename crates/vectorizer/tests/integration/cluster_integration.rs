@@ -55,6 +55,13 @@ async fn test_distributed_sharding_with_quantization() {
             rebalance_threshold: 0.2,
         }),
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     let collection: DistributedShardedCollection = match DistributedShardedCollection::new(
@@ -124,6 +131,13 @@ async fn test_distributed_sharding_with_compression() {
             rebalance_threshold: 0.2,
         }),
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     let collection: DistributedShardedCollection = match DistributedShardedCollection::new(
@@ -193,6 +207,13 @@ async fn test_distributed_sharding_with_payload() {
             rebalance_threshold: 0.2,
         }),
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     let collection: DistributedShardedCollection = match DistributedShardedCollection::new(
@@ -273,6 +294,13 @@ async fn test_distributed_sharding_with_sparse() {
             rebalance_threshold: 0.2,
         }),
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     let collection: DistributedShardedCollection = match DistributedShardedCollection::new(
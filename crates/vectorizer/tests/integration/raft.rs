@@ -24,6 +24,13 @@ fn create_test_config() -> CollectionConfig {
         storage_type: None,
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     }
 }
 
@@ -26,6 +26,13 @@ fn create_sharded_config(shard_count: u32) -> CollectionConfig {
             rebalance_threshold: 0.2,
         }),
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     }
 }
 
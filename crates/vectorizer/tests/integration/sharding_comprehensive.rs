@@ -41,6 +41,13 @@ fn create_sharded_config(
             rebalance_threshold,
         }),
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     }
 }
 
@@ -253,6 +260,13 @@ fn test_sharded_collection_creation_no_sharding() {
         storage_type: None,
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     let result = ShardedCollection::new("test".to_string(), config);
@@ -41,6 +41,13 @@ fn create_sharded_config(shard_count: u32) -> CollectionConfig {
         }),
         graph: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     }
 }
 
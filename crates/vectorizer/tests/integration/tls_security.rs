@@ -361,3 +361,49 @@ mod cipher_suite_validation_tests {
         );
     }
 }
+
+#[cfg(test)]
+mod reload_tests {
+    use std::sync::Arc;
+
+    use vectorizer::security::tls::ReloadableTls;
+
+    use super::*;
+
+    fn listener_config(cert_file: &NamedTempFile, key_file: &NamedTempFile) -> TlsConfig {
+        TlsConfig {
+            enabled: true,
+            cert_path: Some(cert_file.path().to_string_lossy().to_string()),
+            key_path: Some(key_file.path().to_string_lossy().to_string()),
+            ..TlsConfig::default()
+        }
+    }
+
+    /// Reloading re-reads the files and swaps in a new server config
+    #[test]
+    fn test_reload_swaps_server_config() {
+        let (cert_file, key_file) = create_temp_cert_files();
+        let tls = ReloadableTls::new(listener_config(&cert_file, &key_file)).unwrap();
+        let before = tls.server_config();
+
+        let (cert_pem, key_pem) = generate_test_certificate();
+        std::fs::write(cert_file.path(), cert_pem).unwrap();
+        std::fs::write(key_file.path(), key_pem).unwrap();
+        tls.reload().unwrap();
+
+        assert!(!Arc::ptr_eq(&before, &tls.server_config()));
+    }
+
+    /// A failed reload keeps serving the certificates already loaded
+    #[test]
+    fn test_failed_reload_keeps_current_config() {
+        let (cert_file, key_file) = create_temp_cert_files();
+        let tls = ReloadableTls::new(listener_config(&cert_file, &key_file)).unwrap();
+        let before = tls.server_config();
+
+        std::fs::write(cert_file.path(), "not a certificate").unwrap();
+        assert!(tls.reload().is_err());
+
+        assert!(Arc::ptr_eq(&before, &tls.server_config()));
+    }
+}
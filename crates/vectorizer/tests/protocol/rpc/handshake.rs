@@ -39,6 +39,8 @@ async fn boot_listener() -> std::net::SocketAddr {
         slow_query_ring: vectorizer::cache::slow_query::SlowQueryRing::new(
             vectorizer::cache::slow_query::SlowQueryConfig::default(),
         ),
+        audit_log: None,
+        hub_manager: None,
     };
     spawn_rpc_listener(state, addr).await.unwrap();
     // Give the listener a moment to actually start accepting.
@@ -210,6 +210,13 @@ async fn test_basic_master_replica_sync() {
         storage_type: None,
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
     master_store.create_collection("test", config).unwrap();
 
@@ -258,6 +265,13 @@ async fn test_incremental_replication() {
         storage_type: None,
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
     master_store.create_collection("test", config).unwrap();
 
@@ -301,6 +315,13 @@ async fn test_multiple_replicas() {
         storage_type: None,
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
     master_store.create_collection("test", config).unwrap();
 
@@ -358,6 +379,13 @@ async fn test_stress_high_volume_replication() {
         storage_type: None,
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
     master_store
         .create_collection("stress_test", config)
@@ -428,6 +456,13 @@ async fn test_snapshot_with_large_vectors() {
         storage_type: None,
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
     store1.create_collection("large_dims", config).unwrap();
 
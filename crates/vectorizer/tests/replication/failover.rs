@@ -104,6 +104,13 @@ async fn test_replica_reconnect_after_disconnect() {
         storage_type: Some(StorageType::Memory),
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
     master_store.create_collection("test", config).unwrap();
 
@@ -167,6 +174,13 @@ async fn test_partial_sync_after_brief_disconnect() {
         storage_type: Some(StorageType::Memory),
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
     master_store.create_collection("test", config).unwrap();
 
@@ -256,6 +270,13 @@ async fn test_full_sync_when_offset_too_old() {
         storage_type: None,
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
     master_store.create_collection("test", col_config).unwrap();
 
@@ -320,6 +341,13 @@ async fn test_multiple_replicas_recovery() {
         storage_type: Some(StorageType::Memory),
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
     master_store.create_collection("test", config).unwrap();
 
@@ -414,6 +442,13 @@ async fn test_data_consistency_after_multiple_disconnects() {
         storage_type: Some(StorageType::Memory),
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
     master_store.create_collection("test", config).unwrap();
 
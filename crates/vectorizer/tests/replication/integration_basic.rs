@@ -111,6 +111,13 @@ async fn test_master_start_and_accept_connections() {
         storage_type: None,
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
     master_store.create_collection("pre_sync", config).unwrap();
 
@@ -179,6 +186,13 @@ async fn test_master_replicate_operations() {
         storage_type: None,
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
     master_store
         .create_collection("test", config.clone())
@@ -270,6 +284,13 @@ async fn test_master_multiple_replicas_and_stats() {
         storage_type: None,
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
     master_store.create_collection("multi", config).unwrap();
 
@@ -380,6 +401,13 @@ async fn test_replica_full_sync_on_connect() {
         storage_type: None,
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
     master_store.create_collection("full_sync", config).unwrap();
 
@@ -427,6 +455,13 @@ async fn test_replica_partial_sync_on_reconnect() {
         storage_type: None,
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
     master_store.create_collection("partial", config).unwrap();
 
@@ -537,6 +572,13 @@ async fn test_replica_apply_all_operation_types() {
         storage_type: None,
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
     master_store.create_collection("ops_test", config).unwrap();
 
@@ -620,6 +662,13 @@ async fn test_replica_incremental_operations() {
         storage_type: None,
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
     master_store
         .create_collection("incremental", config)
@@ -688,6 +737,13 @@ async fn test_replica_delete_operations() {
         storage_type: None,
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
     master_store
         .create_collection("delete_test", config)
@@ -807,6 +863,13 @@ async fn test_replica_update_operations() {
         storage_type: None,
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
     master_store
         .create_collection("update_test", config)
@@ -867,6 +930,13 @@ async fn test_replica_stats_tracking() {
         storage_type: None,
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
     master_store.create_collection("stats", config).unwrap();
 
@@ -934,6 +1004,13 @@ async fn test_large_payload_replication() {
         storage_type: None,
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
     master_store
         .create_collection("large_payload", config)
@@ -43,6 +43,13 @@ fn create_test_collection(
         storage_type: Some(StorageType::Memory),
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
     store.create_collection(name, config)?;
     Ok(())
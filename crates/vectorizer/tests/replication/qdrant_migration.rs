@@ -353,6 +353,39 @@ async fn test_data_importer_into_vectorizer() {
     assert_eq!(vector_count, 2);
 }
 
+#[tokio::test]
+async fn test_data_importer_maps_qdrant_euclid_distance() {
+    use vectorizer::migration::qdrant::data_migration::{
+        ExportedCollection, QdrantCollectionConfig, QdrantCollectionParams,
+        QdrantVectorsConfigResponse,
+    };
+
+    let store = VectorStore::new();
+
+    // Qdrant configs name the L2 metric `Euclid`, not `Euclidean`
+    let exported = ExportedCollection {
+        name: "euclid_collection".to_string(),
+        config: QdrantCollectionConfig {
+            params: QdrantCollectionParams {
+                vectors: QdrantVectorsConfigResponse::Vector {
+                    size: 8,
+                    distance: "Euclid".to_string(),
+                },
+                hnsw_config: None,
+                quantization_config: None,
+            },
+        },
+        points: Vec::new(),
+    };
+
+    QdrantDataImporter::import_collection(&store, &exported)
+        .await
+        .unwrap();
+
+    let collection = store.get_collection("euclid_collection").unwrap();
+    assert_eq!(collection.config().metric, DistanceMetric::Euclidean);
+}
+
 #[tokio::test]
 async fn test_config_conversion_all_metrics() {
     let metrics = vec!["Cosine", "Euclidean", "Dot"];
@@ -56,6 +56,13 @@ async fn test_wal_integration_basic() {
         storage_type: Some(vectorizer::models::StorageType::Memory),
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     assert!(store.create_collection("test_collection", config).is_ok());
@@ -118,6 +125,13 @@ async fn test_collection_with_wal_disabled() {
         storage_type: Some(vectorizer::models::StorageType::Memory),
         sharding: None,
         encryption: None,
+        id_policy: None,
+        quota: None,
+        scoring: None,
+        tenant_partition: None,
+        dedup: None,
+        chunking: None,
+        full_text: None,
     };
 
     assert!(store.create_collection("test_collection", config).is_ok());
@@ -19,6 +19,7 @@ vi.mock('@/contexts/AuthContext', async () => {
       isLoading: false,
       authRequired: false,
       login: async () => {},
+      loginWithToken: async () => {},
       logout: async () => {},
       verifySession: async () => true,
       refreshToken: async () => true,
@@ -2,7 +2,8 @@
  * Authentication Context for Dashboard
  *
  * Provides authentication state and methods throughout the application.
- * Supports JWT token-based authentication with automatic session refresh.
+ * Supports JWT token-based authentication with automatic session refresh,
+ * and identity-provider (OIDC) tokens, which the provider refreshes.
  *
  * IMPORTANT: Authentication is only required when server has auth.enabled: true
  * The context checks a protected endpoint to determine if auth is required.
@@ -40,6 +41,7 @@ interface AuthContextType {
   isLoading: boolean;
   authRequired: boolean; // Whether the server requires authentication
   login: (username: string, password: string) => Promise<void>;
+  loginWithToken: (accessToken: string) => Promise<void>;
   logout: () => Promise<void>;
   verifySession: () => Promise<boolean>;
   refreshToken: () => Promise<boolean>;
@@ -52,9 +54,10 @@ const AuthContext = createContext<AuthContextType | undefined>(undefined);
 const TOKEN_KEY = 'vectorizer_dashboard_token';
 const USER_KEY = 'vectorizer_dashboard_user';
 const AUTH_REQUIRED_KEY = 'vectorizer_auth_required';
+const SSO_KEY = 'vectorizer_dashboard_sso';
 
 // Get API base URL - in production, use relative path
-const getApiBaseUrl = () => {
+export const getApiBaseUrl = () => {
   // In development, we might be on a different port
   if (import.meta.env.DEV) {
     return 'http://localhost:15002';
@@ -171,10 +174,37 @@ export function AuthProvider({ children }: AuthProviderProps) {
     localStorage.setItem(TOKEN_KEY, data.access_token);
     localStorage.setItem(USER_KEY, JSON.stringify(data.user));
 
+    localStorage.removeItem(SSO_KEY);
+
     setToken(data.access_token);
     setUser(data.user);
   }, []);
 
+  // Sign in with an access token issued by the identity provider
+  const loginWithToken = useCallback(async (accessToken: string) => {
+    const baseUrl = getApiBaseUrl();
+
+    const response = await fetch(`${baseUrl}/auth/me`, {
+      headers: {
+        'Authorization': `Bearer ${accessToken}`,
+      },
+    });
+
+    if (!response.ok) {
+      const error = await response.json().catch(() => ({ error: 'unknown', message: 'Sign-in failed' }));
+      throw new Error(error.message || 'The server did not accept the identity provider token');
+    }
+
+    const userData: User = await response.json();
+
+    localStorage.setItem(TOKEN_KEY, accessToken);
+    localStorage.setItem(USER_KEY, JSON.stringify(userData));
+    localStorage.setItem(SSO_KEY, 'true');
+
+    setToken(accessToken);
+    setUser(userData);
+  }, []);
+
   // Logout function - calls server to invalidate token
   const logout = useCallback(async () => {
     const baseUrl = getApiBaseUrl();
@@ -196,6 +226,7 @@ export function AuthProvider({ children }: AuthProviderProps) {
     // Clear local storage
     localStorage.removeItem(TOKEN_KEY);
     localStorage.removeItem(USER_KEY);
+    localStorage.removeItem(SSO_KEY);
 
     // Clear state
     setToken(null);
@@ -282,7 +313,8 @@ export function AuthProvider({ children }: AuthProviderProps) {
   // Set up automatic token refresh (refresh 5 minutes before expiry)
   // JWT tokens typically expire in 1 hour (3600s), so refresh at ~55 minutes
   useEffect(() => {
-    if (!token || !authRequired) {
+    // Provider tokens are refreshed through the provider, not /auth/refresh
+    if (!token || !authRequired || localStorage.getItem(SSO_KEY) === 'true') {
       return;
     }
 
@@ -302,6 +334,7 @@ export function AuthProvider({ children }: AuthProviderProps) {
     isLoading,
     authRequired,
     login,
+    loginWithToken,
     logout,
     verifySession,
     refreshToken,
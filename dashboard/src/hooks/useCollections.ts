@@ -43,6 +43,30 @@ export interface Collection {
     start_time?: string;
     end_time?: string;
   };
+  recall_profile?: RecallProfile | null;
+}
+
+export interface LatencySummary {
+  mean_ms: number;
+  p50_ms: number;
+  p99_ms: number;
+}
+
+/** HNSW recall@k against exact search, measured after a rebuild. */
+export interface RecallProfile {
+  measured_at: string;
+  trigger: 'reindex' | 'compaction' | 'request';
+  vector_count: number;
+  sample_size: number;
+  k: number;
+  recall_at_k: number;
+  min_recall_at_k: number;
+  hnsw_latency: LatencySummary;
+  exact_latency: LatencySummary;
+  m: number;
+  ef_construction: number;
+  ef_search: number | null;
+  quantization: string;
 }
 
 export interface CreateCollectionRequest {
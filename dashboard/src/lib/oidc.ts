@@ -0,0 +1,150 @@
+/**
+ * OIDC sign-in for the dashboard.
+ *
+ * The server advertises its identity provider at
+ * `/.well-known/oauth-protected-resource` when `auth.oidc` is configured.
+ * The dashboard then runs an authorization-code + PKCE flow against that
+ * provider as a public client and hands the resulting access token to
+ * the server as a regular Bearer token.
+ */
+
+// Protected resource metadata served by the server
+export interface ResourceMetadata {
+  resource: string;
+  authorization_servers: string[];
+  scopes_supported: string[];
+  dashboard_client_id: string | null;
+  local_login: boolean;
+}
+
+// Subset of the provider's discovery document we use
+interface ProviderMetadata {
+  authorization_endpoint: string;
+  token_endpoint: string;
+}
+
+// Pending flow state, kept across the provider redirect
+const PENDING_KEY = 'vectorizer_oidc_pending';
+
+interface PendingFlow {
+  state: string;
+  verifier: string;
+  redirectUri: string;
+  tokenEndpoint: string;
+  clientId: string;
+}
+
+function base64Url(bytes: Uint8Array): string {
+  let binary = '';
+  bytes.forEach((b) => {
+    binary += String.fromCharCode(b);
+  });
+  return btoa(binary).replace(/\+/g, '-').replace(/\//g, '_').replace(/=+$/, '');
+}
+
+function randomString(): string {
+  const bytes = new Uint8Array(32);
+  crypto.getRandomValues(bytes);
+  return base64Url(bytes);
+}
+
+async function challengeFor(verifier: string): Promise<string> {
+  const digest = await crypto.subtle.digest('SHA-256', new TextEncoder().encode(verifier));
+  return base64Url(new Uint8Array(digest));
+}
+
+/**
+ * Fetch the server's resource metadata. Resolves to null when OIDC is
+ * not configured (404) or the server cannot be reached.
+ */
+export async function fetchResourceMetadata(baseUrl: string): Promise<ResourceMetadata | null> {
+  try {
+    const response = await fetch(`${baseUrl}/.well-known/oauth-protected-resource`, {
+      headers: { 'Accept': 'application/json' },
+    });
+    if (!response.ok) {
+      return null;
+    }
+    return (await response.json()) as ResourceMetadata;
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * Redirect the browser to the provider's authorization endpoint.
+ * `redirectUri` must be registered with the provider for the client.
+ */
+export async function startSignIn(metadata: ResourceMetadata, redirectUri: string): Promise<void> {
+  const issuer = metadata.authorization_servers[0]?.replace(/\/$/, '');
+  if (!issuer || !metadata.dashboard_client_id) {
+    throw new Error('Single sign-on is not configured');
+  }
+
+  const discovery = await fetch(`${issuer}/.well-known/openid-configuration`);
+  if (!discovery.ok) {
+    throw new Error('Could not reach the identity provider');
+  }
+  const provider = (await discovery.json()) as ProviderMetadata;
+
+  const pending: PendingFlow = {
+    state: randomString(),
+    verifier: randomString(),
+    redirectUri,
+    tokenEndpoint: provider.token_endpoint,
+    clientId: metadata.dashboard_client_id,
+  };
+  sessionStorage.setItem(PENDING_KEY, JSON.stringify(pending));
+
+  const params = new URLSearchParams({
+    response_type: 'code',
+    client_id: pending.clientId,
+    redirect_uri: redirectUri,
+    scope: metadata.scopes_supported.join(' '),
+    state: pending.state,
+    code_challenge: await challengeFor(pending.verifier),
+    code_challenge_method: 'S256',
+  });
+  window.location.assign(`${provider.authorization_endpoint}?${params.toString()}`);
+}
+
+/**
+ * Complete a flow started by `startSignIn` when the provider redirects
+ * back with `code` and `state`. Resolves to the access token, or null
+ * when the URL is not an OIDC callback.
+ */
+export async function completeSignIn(search: string): Promise<string | null> {
+  const params = new URLSearchParams(search);
+  const code = params.get('code');
+  const returnedState = params.get('state');
+  const raw = sessionStorage.getItem(PENDING_KEY);
+  if (!code || !returnedState || !raw) {
+    return null;
+  }
+  sessionStorage.removeItem(PENDING_KEY);
+
+  const pending = JSON.parse(raw) as PendingFlow;
+  if (pending.state !== returnedState) {
+    throw new Error('Sign-in response did not match the request');
+  }
+
+  const response = await fetch(pending.tokenEndpoint, {
+    method: 'POST',
+    headers: { 'Content-Type': 'application/x-www-form-urlencoded' },
+    body: new URLSearchParams({
+      grant_type: 'authorization_code',
+      code,
+      redirect_uri: pending.redirectUri,
+      client_id: pending.clientId,
+      code_verifier: pending.verifier,
+    }),
+  });
+  if (!response.ok) {
+    throw new Error('The identity provider rejected the sign-in');
+  }
+  const data = (await response.json()) as { access_token?: string };
+  if (!data.access_token) {
+    throw new Error('The identity provider returned no access token');
+  }
+  return data.access_token;
+}
@@ -19,14 +19,14 @@ import {
   KeyValueRow,
 } from '@/components/console';
 import { formatNumber } from '@/utils/formatters';
-import type { Collection } from '@/hooks/useCollections';
+import type { Collection, RecallProfile } from '@/hooks/useCollections';
 
 interface CollectionsSnapshot {
   collections: Array<{ name: string; vector_count: number; dimension: number }>;
 }
 
 function CollectionsPage() {
-  const { listCollections } = useCollections();
+  const { listCollections, getCollection } = useCollections();
   const { collections, loading, setCollections, setLoading, setError } = useCollectionsStore();
   const api = useApiClient();
   const toast = useToastContext();
@@ -38,6 +38,8 @@ function CollectionsPage() {
   const [createOpen, setCreateOpen] = useState(false);
   const [deleteName, setDeleteName] = useState<string | null>(null);
   const [reindexing, setReindexing] = useState(false);
+  const [recallProfile, setRecallProfile] = useState<RecallProfile | null>(null);
+  const [profiling, setProfiling] = useState(false);
 
   const handleReindex = async (name: string) => {
     if (reindexing) return;
@@ -57,6 +59,22 @@ function CollectionsPage() {
     }
   };
 
+  const handleMeasureRecall = async (name: string) => {
+    if (profiling) return;
+    setProfiling(true);
+    try {
+      const res = await api.post<{ recall_profile: RecallProfile }>(
+        `/collections/${encodeURIComponent(name)}/recall_profile`,
+        {}
+      );
+      setRecallProfile(res.recall_profile);
+    } catch (err) {
+      toast.error(err instanceof Error ? err.message : 'Failed to measure recall');
+    } finally {
+      setProfiling(false);
+    }
+  };
+
   const handleCopyId = async (name: string) => {
     try {
       await navigator.clipboard.writeText(name);
@@ -109,6 +127,22 @@ function CollectionsPage() {
   const selected =
     (selectedName && list.find((c) => c.name === selectedName)) || filtered[0] || null;
 
+  // The list payload has no recall profile; GET /collections/{n} does.
+  const selectedCollection = selected?.name ?? null;
+  useEffect(() => {
+    setRecallProfile(null);
+    if (!selectedCollection) return;
+    let cancelled = false;
+    getCollection(selectedCollection)
+      .then((c) => {
+        if (!cancelled) setRecallProfile(c.recall_profile ?? null);
+      })
+      .catch(() => {});
+    return () => {
+      cancelled = true;
+    };
+  }, [selectedCollection, getCollection]);
+
   const totalVectors = list.reduce((s, c) => s + (c.vector_count ?? 0), 0);
 
   if (loading && !list.length) return <LoadingState message="Loading collections..." />;
@@ -286,6 +320,65 @@ function CollectionsPage() {
                 </CardBody>
               </Card>
 
+              <Card>
+                <CardHead
+                  title="Recall / latency"
+                  sub={
+                    recallProfile
+                      ? `${recallProfile.trigger} · ${new Date(recallProfile.measured_at).toLocaleString()}`
+                      : undefined
+                  }
+                  right={
+                    <button
+                      className="btn sm"
+                      style={{ marginLeft: 'auto' }}
+                      disabled={profiling}
+                      onClick={() => handleMeasureRecall(selected.name)}
+                    >
+                      {profiling ? 'Measuring…' : 'Measure'}
+                    </button>
+                  }
+                />
+                <CardBody>
+                  {recallProfile ? (
+                    <KeyValue>
+                      <KeyValueRow term={`Recall@${recallProfile.k}`}>
+                        {(recallProfile.recall_at_k * 100).toFixed(1)}%{' '}
+                        <span className="muted">
+                          · min {(recallProfile.min_recall_at_k * 100).toFixed(1)}% over{' '}
+                          {recallProfile.sample_size} queries
+                        </span>
+                      </KeyValueRow>
+                      <KeyValueRow term="HNSW latency">
+                        p50 {recallProfile.hnsw_latency.p50_ms.toFixed(2)} ms{' '}
+                        <span className="muted">
+                          · p99 {recallProfile.hnsw_latency.p99_ms.toFixed(2)} ms
+                        </span>
+                      </KeyValueRow>
+                      <KeyValueRow term="Exact latency">
+                        p50 {recallProfile.exact_latency.p50_ms.toFixed(2)} ms{' '}
+                        <span className="muted">
+                          · p99 {recallProfile.exact_latency.p99_ms.toFixed(2)} ms
+                        </span>
+                      </KeyValueRow>
+                      <KeyValueRow term="Settings">
+                        <span className="mono">
+                          M={recallProfile.m}, ef_construction={recallProfile.ef_construction}
+                          {recallProfile.ef_search !== null &&
+                            `, ef_search=${recallProfile.ef_search}`}
+                          {' · '}
+                          {recallProfile.quantization}
+                        </span>
+                      </KeyValueRow>
+                    </KeyValue>
+                  ) : (
+                    <div className="muted" style={{ fontSize: 12, padding: '8px 0' }}>
+                      No profile yet. One is measured after each reindex, or press Measure.
+                    </div>
+                  )}
+                </CardBody>
+              </Card>
+
               <div className="grid grid-2" style={{ gap: 14 }}>
                 <Card>
                   <CardHead title="Vector growth · 7d" />
@@ -4,16 +4,27 @@
  * Console-styled sign-in for the dashboard. Rendered standalone outside
  * `ConsoleLayout`, so it activates `body[data-console="1"]` itself to pick
  * up the dark theme variables defined in `console.css`.
+ *
+ * When the server advertises an identity provider with a dashboard
+ * client id, a "Sign in with SSO" button runs the OIDC flow; the
+ * provider redirects back here to finish it. With `local_login` off the
+ * password form is hidden.
  */
 
 import { useEffect, useState, type FormEvent } from 'react';
 import { useNavigate, useLocation } from 'react-router-dom';
-import { useAuth } from '@/contexts/AuthContext';
+import { getApiBaseUrl, useAuth } from '@/contexts/AuthContext';
+import {
+  completeSignIn,
+  fetchResourceMetadata,
+  startSignIn,
+  type ResourceMetadata,
+} from '@/lib/oidc';
 import { HexLogo, Card, CardBody, Pill, Icons } from '@/components/console';
 import Checkbox from '@/components/ui/Checkbox';
 
 function LoginPage() {
-  const { login, isAuthenticated } = useAuth();
+  const { login, loginWithToken, isAuthenticated } = useAuth();
   const navigate = useNavigate();
   const location = useLocation();
   const from = (location.state as { from?: { pathname?: string } } | null)?.from?.pathname ?? '/overview';
@@ -28,6 +39,7 @@ function LoginPage() {
   const [submitting, setSubmitting] = useState(false);
   const [error, setError] = useState<string | null>(null);
   const [isRateLimited, setIsRateLimited] = useState(false);
+  const [oidc, setOidc] = useState<ResourceMetadata | null>(null);
   const [sessionExpired, setSessionExpired] = useState<boolean>(() => {
     // Phase24 §4.2 — surface a "session expired" notice when the
     // api-middleware's unauthorizedMiddleware redirected us here. The
@@ -50,6 +62,30 @@ function LoginPage() {
     };
   }, []);
 
+  // Discover the identity provider, and finish a sign-in it redirected back from.
+  useEffect(() => {
+    let cancelled = false;
+    fetchResourceMetadata(getApiBaseUrl()).then((metadata) => {
+      if (!cancelled) setOidc(metadata);
+    });
+    completeSignIn(window.location.search)
+      .then(async (accessToken) => {
+        if (!accessToken || cancelled) return;
+        setSubmitting(true);
+        await loginWithToken(accessToken);
+        navigate('/overview', { replace: true });
+      })
+      .catch((err) => {
+        if (!cancelled) setError(err instanceof Error ? err.message : 'Sign-in failed');
+      })
+      .finally(() => {
+        if (!cancelled) setSubmitting(false);
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [loginWithToken, navigate]);
+
   // If already authenticated (e.g. from a stale session), bounce to the redirect target.
   useEffect(() => {
     if (isAuthenticated) navigate(from, { replace: true });
@@ -83,6 +119,21 @@ function LoginPage() {
     }
   };
 
+  const onSso = async () => {
+    if (!oidc) return;
+    setError(null);
+    setSubmitting(true);
+    try {
+      const base = import.meta.env.BASE_URL.replace(/\/$/, '');
+      await startSignIn(oidc, `${window.location.origin}${base}/login`);
+    } catch (err) {
+      setError(err instanceof Error ? err.message : 'Sign-in failed');
+      setSubmitting(false);
+    }
+  };
+
+  const localLogin = oidc?.local_login ?? true;
+
   return (
     <div
       style={{
@@ -124,45 +175,49 @@ function LoginPage() {
               onSubmit={onSubmit}
               style={{ display: 'flex', flexDirection: 'column', gap: 12 }}
             >
-              <div className="field">
-                <label className="field-label" htmlFor="login-username">
-                  Username
-                </label>
-                <input
-                  id="login-username"
-                  name="username"
-                  className="input"
-                  autoComplete="username"
-                  value={username}
-                  onChange={(e) => setUsername(e.target.value)}
-                  required
-                  autoFocus
-                />
-              </div>
-              <div className="field">
-                <label className="field-label" htmlFor="login-password">
-                  Password
-                </label>
-                <input
-                  id="login-password"
-                  name="password"
-                  className="input"
-                  type="password"
-                  autoComplete="current-password"
-                  value={password}
-                  onChange={(e) => setPassword(e.target.value)}
-                  required
-                />
-              </div>
+              {localLogin && (
+                <>
+                  <div className="field">
+                    <label className="field-label" htmlFor="login-username">
+                      Username
+                    </label>
+                    <input
+                      id="login-username"
+                      name="username"
+                      className="input"
+                      autoComplete="username"
+                      value={username}
+                      onChange={(e) => setUsername(e.target.value)}
+                      required
+                      autoFocus
+                    />
+                  </div>
+                  <div className="field">
+                    <label className="field-label" htmlFor="login-password">
+                      Password
+                    </label>
+                    <input
+                      id="login-password"
+                      name="password"
+                      className="input"
+                      type="password"
+                      autoComplete="current-password"
+                      value={password}
+                      onChange={(e) => setPassword(e.target.value)}
+                      required
+                    />
+                  </div>
 
-              <div style={{ marginTop: 2 }}>
-                <Checkbox
-                  id="remember-me"
-                  checked={rememberMe}
-                  onChange={setRememberMe}
-                  label="Remember me"
-                />
-              </div>
+                  <div style={{ marginTop: 2 }}>
+                    <Checkbox
+                      id="remember-me"
+                      checked={rememberMe}
+                      onChange={setRememberMe}
+                      label="Remember me"
+                    />
+                  </div>
+                </>
+              )}
 
               {sessionExpired && !error && (
                 <div role="status" style={{ marginTop: 4 }}>
@@ -178,21 +233,35 @@ function LoginPage() {
                 </div>
               )}
 
-              <button
-                type="submit"
-                className="btn primary"
-                disabled={submitting}
-                style={{ justifyContent: 'center', marginTop: 6 }}
-              >
-                {submitting ? (
-                  <>
-                    <Icons.refresh size={13} />
-                    Signing in…
-                  </>
-                ) : (
-                  'Sign in'
-                )}
-              </button>
+              {localLogin && (
+                <button
+                  type="submit"
+                  className="btn primary"
+                  disabled={submitting}
+                  style={{ justifyContent: 'center', marginTop: 6 }}
+                >
+                  {submitting ? (
+                    <>
+                      <Icons.refresh size={13} />
+                      Signing in…
+                    </>
+                  ) : (
+                    'Sign in'
+                  )}
+                </button>
+              )}
+
+              {oidc?.dashboard_client_id && (
+                <button
+                  type="button"
+                  className={localLogin ? 'btn' : 'btn primary'}
+                  onClick={onSso}
+                  disabled={submitting}
+                  style={{ justifyContent: 'center', marginTop: localLogin ? 0 : 6 }}
+                >
+                  Sign in with SSO
+                </button>
+              )}
             </form>
           </CardBody>
         </Card>
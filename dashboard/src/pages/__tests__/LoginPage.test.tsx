@@ -15,6 +15,7 @@ vi.mock('@/contexts/AuthContext', async () => {
       user: null,
       token: null,
       login: loginMock,
+      loginWithToken: vi.fn(async () => undefined),
       logout: vi.fn(),
       verifySession: vi.fn(async () => false),
       refreshToken: vi.fn(async () => false),
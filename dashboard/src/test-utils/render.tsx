@@ -44,6 +44,7 @@ export type FakeAuthState = {
   isLoading: boolean;
   authRequired: boolean;
   login: (username: string, password: string) => Promise<void>;
+  loginWithToken: (accessToken: string) => Promise<void>;
   logout: () => Promise<void>;
   verifySession: () => Promise<boolean>;
   refreshToken: () => Promise<boolean>;
@@ -71,6 +72,9 @@ export function buildAuthState(partial: AuthStateInput = {}): FakeAuthState {
     login: async () => {
       /* inert in tests — assert auth behaviour through seeded state */
     },
+    loginWithToken: async () => {
+      /* inert in tests — assert auth behaviour through seeded state */
+    },
     logout: async () => {
       /* inert in tests — assert auth behaviour through seeded state */
     },
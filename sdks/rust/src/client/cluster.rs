@@ -0,0 +1,28 @@
+//! Cluster surface: `get_cluster_routing`.
+//!
+//! The routing table itself — and how the client uses it to send
+//! single-vector requests to the owning node — lives in
+//! [`crate::cluster_routing`].
+
+use super::VectorizerClient;
+use crate::cluster_routing::{ROUTING_ENDPOINT, RoutingTable};
+use crate::error::{Result, VectorizerError};
+
+impl VectorizerClient {
+    /// Fetch the cluster's consistent-hash routing table from the node
+    /// the client is connected to. Only clustered servers serve it.
+    ///
+    /// Calls `GET /api/v1/cluster/routing`. With cluster routing enabled
+    /// the fetched table also replaces the router's cached one.
+    pub async fn get_cluster_routing(&self) -> Result<RoutingTable> {
+        if let Some(router) = self.cluster_router() {
+            return router
+                .refresh(self.transport.as_ref())
+                .await
+                .map(|table| (*table).clone());
+        }
+        let response = self.make_request("GET", ROUTING_ENDPOINT, None).await?;
+        serde_json::from_str(&response)
+            .map_err(|e| VectorizerError::server(format!("Failed to parse routing table: {e}")))
+    }
+}
@@ -26,6 +26,8 @@
 //! | [`admin`] | `get_stats`, `get_status`, `get_logs`, `get_indexing_progress`, `force_save_collection`, `list_empty_collections`, `cleanup_empty_collections`, `get_config`, `update_config`, `list_backups`, `create_backup`, `restore_backup`, `restart_server`, `list_workspaces`, `get_workspace_config`, `add_workspace`, `remove_workspace` |
 //! | [`auth`] | `me`, `logout`, `refresh_token`, `validate_password`, `create_api_key`, `list_api_keys`, `revoke_api_key`, `create_user`, `list_users`, `delete_user`, `change_password` |
 //! | [`replication`] | `get_replication_status`, `configure_replication`, `get_replication_stats`, `list_replicas` |
+//! | [`cluster`] | `get_cluster_routing` |
+//! | [`pagination`] | `scroll_page`, `scroll_vectors`, `scroll_vectors_with`, `list_collections_stream`, `list_backups_stream` (`Stream`s) |
 //! | [`hub`] | `list_user_backups`, `create_user_backup`, `restore_user_backup`, `upload_user_backup`, `get_user_backup`, `delete_user_backup`, `download_user_backup`, `get_usage_statistics`, `get_quota_info`, `validate_hub_api_key` |
 //!
 //! ## RPC readiness
@@ -42,21 +44,25 @@
 
 use std::sync::Arc;
 
+use crate::cluster_routing::{ClusterRouter, ClusterRoutingConfig, TransportFactory};
 use crate::error::{Result, VectorizerError};
 use crate::http_transport::HttpTransport;
 use crate::models::*;
+use crate::signing::{RequestSigner, RequestSigningConfig};
 use crate::transport::{Protocol, Transport};
 #[cfg(feature = "umicp")]
 use crate::umicp_transport::UmicpTransport;
 
 pub mod admin;
 pub mod auth;
+pub mod cluster;
 pub mod collections;
 pub mod core;
 pub mod discovery;
 pub mod files;
 pub mod graph;
 pub mod hub;
+pub mod pagination;
 pub mod qdrant;
 pub mod replication;
 pub mod search;
@@ -82,6 +88,11 @@ pub struct ClientConfig {
     pub hosts: Option<HostConfig>,
     /// Default read preference for read operations.
     pub read_preference: Option<ReadPreference>,
+    /// HMAC request signing credentials (HTTP transport only).
+    pub signing: Option<RequestSigningConfig>,
+    /// Send single-vector requests straight to the cluster node owning
+    /// the vector (HTTP transport only). See [`crate::cluster_routing`].
+    pub cluster_routing: Option<ClusterRoutingConfig>,
 }
 
 #[cfg(feature = "umicp")]
@@ -106,10 +117,27 @@ impl Default for ClientConfig {
             umicp: None,
             hosts: None,
             read_preference: None,
+            signing: None,
+            cluster_routing: None,
         }
     }
 }
 
+/// Build an HTTP transport for `url`, attaching a request signer when
+/// [`ClientConfig::signing`] is set. Each transport gets its own signer
+/// so clock offsets are tracked per host.
+pub(crate) fn http_transport(
+    config: &ClientConfig,
+    url: &str,
+    timeout_secs: u64,
+) -> Result<HttpTransport> {
+    let transport = HttpTransport::new(url, config.api_key.as_deref(), timeout_secs)?;
+    Ok(match config.signing {
+        Some(ref signing) => transport.with_signer(Arc::new(RequestSigner::new(signing))),
+        None => transport,
+    })
+}
+
 /// Vectorizer REST client with optional master/replica topology
 /// support. Public surface is identical to the pre-phase4
 /// monolithic `VectorizerClient`; the methods are now organised
@@ -133,6 +161,8 @@ pub struct VectorizerClient {
     /// Whether replica mode is enabled.
     #[allow(dead_code)]
     is_replica_mode: bool,
+    /// Shard-aware routing for single-vector requests, when enabled.
+    cluster_router: Option<Arc<ClusterRouter>>,
     /// Original config for creating child clients (e.g. `with_master`).
     pub(crate) config: ClientConfig,
 }
@@ -155,8 +185,7 @@ impl VectorizerClient {
 
                 match proto {
                     Protocol::Http => {
-                        let transport =
-                            HttpTransport::new(&host, config.api_key.as_deref(), timeout_secs)?;
+                        let transport = http_transport(&config, &host, timeout_secs)?;
                         (Arc::new(transport), Protocol::Http, host.clone())
                     }
                     #[cfg(feature = "umicp")]
@@ -181,8 +210,7 @@ impl VectorizerClient {
                             .base_url
                             .clone()
                             .unwrap_or_else(|| "http://localhost:15002".to_string());
-                        let transport =
-                            HttpTransport::new(&base_url, config.api_key.as_deref(), timeout_secs)?;
+                        let transport = http_transport(&config, &base_url, timeout_secs)?;
                         (Arc::new(transport), Protocol::Http, base_url)
                     }
                     #[cfg(feature = "umicp")]
@@ -218,13 +246,12 @@ impl VectorizerClient {
         // Initialise replica mode if hosts are configured.
         let (master_transport, replica_transports, is_replica_mode) =
             if let Some(ref hosts) = config.hosts {
-                let master =
-                    HttpTransport::new(&hosts.master, config.api_key.as_deref(), timeout_secs)?;
+                let master = http_transport(&config, &hosts.master, timeout_secs)?;
                 let replicas: Result<Vec<Arc<dyn Transport>>> = hosts
                     .replicas
                     .iter()
                     .map(|url| {
-                        let t = HttpTransport::new(url, config.api_key.as_deref(), timeout_secs)?;
+                        let t = http_transport(&config, url, timeout_secs)?;
                         Ok(Arc::new(t) as Arc<dyn Transport>)
                     })
                     .collect();
@@ -239,6 +266,22 @@ impl VectorizerClient {
 
         let read_preference = config.read_preference.unwrap_or(ReadPreference::Replica);
 
+        let cluster_router = match (&config.cluster_routing, protocol) {
+            (Some(routing), Protocol::Http) => {
+                let node_config = config.clone();
+                let factory: TransportFactory = Arc::new(move |url: &str| {
+                    let transport = http_transport(&node_config, url, timeout_secs)?;
+                    Ok(Arc::new(transport) as Arc<dyn Transport>)
+                });
+                Some(Arc::new(ClusterRouter::new(
+                    routing.clone(),
+                    base_url.clone(),
+                    factory,
+                )))
+            }
+            _ => None,
+        };
+
         Ok(Self {
             transport,
             protocol,
@@ -248,6 +291,7 @@ impl VectorizerClient {
             replica_index: std::sync::atomic::AtomicUsize::new(0),
             read_preference,
             is_replica_mode,
+            cluster_router,
             config,
         })
     }
@@ -367,10 +411,25 @@ impl VectorizerClient {
             replica_index: std::sync::atomic::AtomicUsize::new(0),
             read_preference: ReadPreference::Master,
             is_replica_mode: false,
+            cluster_router: None,
             config: ClientConfig::default(),
         }
     }
 
+    /// Route single-vector requests through `router` (see
+    /// [`crate::cluster_routing`]). Mainly for clients built with
+    /// [`Self::with_transport`]; [`Self::new`] sets one up from
+    /// [`ClientConfig::cluster_routing`].
+    pub fn with_cluster_router(mut self, router: ClusterRouter) -> Self {
+        self.cluster_router = Some(Arc::new(router));
+        self
+    }
+
+    /// The shard-aware router, when cluster routing is enabled.
+    pub fn cluster_router(&self) -> Option<&ClusterRouter> {
+        self.cluster_router.as_deref()
+    }
+
     /// Internal helper: dispatch one HTTP-method-name call through
     /// the active transport. Per-surface modules call this instead
     /// of poking the `Transport` directly so future routing changes
@@ -381,15 +440,54 @@ impl VectorizerClient {
         endpoint: &str,
         payload: Option<serde_json::Value>,
     ) -> Result<String> {
-        match method {
-            "GET" => self.transport.get(endpoint).await,
-            "POST" => self.transport.post(endpoint, payload.as_ref()).await,
-            "PUT" => self.transport.put(endpoint, payload.as_ref()).await,
-            "DELETE" => self.transport.delete(endpoint).await,
-            "PATCH" => self.transport.patch(endpoint, payload.as_ref()).await,
-            _ => Err(VectorizerError::configuration(format!(
-                "Unsupported method: {method}"
-            ))),
+        dispatch(self.transport.as_ref(), method, endpoint, payload.as_ref()).await
+    }
+
+    /// Like [`Self::make_request`], but for a request about one vector:
+    /// with cluster routing enabled it goes to the node owning
+    /// `vector_id`, falling back to the seed transport if that node
+    /// can't be reached.
+    pub(crate) async fn make_routed_request(
+        &self,
+        method: &str,
+        endpoint: &str,
+        payload: Option<serde_json::Value>,
+        vector_id: &str,
+    ) -> Result<String> {
+        if let Some(router) = &self.cluster_router
+            && let Some(owner) = router
+                .transport_for(self.transport.as_ref(), vector_id)
+                .await
+        {
+            match dispatch(owner.as_ref(), method, endpoint, payload.as_ref()).await {
+                Err(VectorizerError::Network { message }) => {
+                    tracing::debug!(
+                        "Owner of '{vector_id}' unreachable ({message}); retrying through seed node"
+                    );
+                    router.invalidate();
+                }
+                result => return result,
+            }
         }
+        self.make_request(method, endpoint, payload).await
+    }
+}
+
+/// Send one request through `transport`.
+async fn dispatch(
+    transport: &dyn Transport,
+    method: &str,
+    endpoint: &str,
+    payload: Option<&serde_json::Value>,
+) -> Result<String> {
+    match method {
+        "GET" => transport.get(endpoint).await,
+        "POST" => transport.post(endpoint, payload).await,
+        "PUT" => transport.put(endpoint, payload).await,
+        "DELETE" => transport.delete(endpoint).await,
+        "PATCH" => transport.patch(endpoint, payload).await,
+        _ => Err(VectorizerError::configuration(format!(
+            "Unsupported method: {method}"
+        ))),
     }
 }
@@ -0,0 +1,119 @@
+//! Auto-paginating listings: `Stream`s that fetch page after page.
+//!
+//! [`VectorizerClient::scroll_vectors`] follows the `next_cursor` of
+//! `POST /collections/{name}/scroll` until the last page, so callers
+//! iterate vectors instead of keeping offsets. The collection and
+//! backup listings are served in one response today; their streams
+//! give them the same shape so callers need not care. Consume them
+//! with `futures::StreamExt` / `TryStreamExt`:
+//!
+//! ```rust,no_run
+//! # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+//! use futures::TryStreamExt;
+//! # let client: vectorizer_sdk::client::VectorizerClient = unimplemented!();
+//!
+//! let filter = serde_json::json!({"source": "wiki"});
+//! let mut vectors = std::pin::pin!(client.scroll_vectors("docs", Some(filter)));
+//! while let Some(vector) = vectors.try_next().await? {
+//!     println!("{}", vector.id);
+//! }
+//! # Ok(()) }
+//! ```
+
+use futures::stream::{self, Stream, TryStreamExt};
+
+use super::VectorizerClient;
+use crate::error::{Result, VectorizerError};
+use crate::models::*;
+
+impl VectorizerClient {
+    /// Fetch one page of a scroll.
+    ///
+    /// Calls `POST /collections/{name}/scroll` with the options and the
+    /// `next_cursor` of the previous page (`None` for the first).
+    ///
+    /// Response: `{collection, vectors, next_cursor}`.
+    pub async fn scroll_page(
+        &self,
+        collection: &str,
+        options: &ScrollOptions,
+        cursor: Option<&str>,
+    ) -> Result<ScrollPage> {
+        let mut payload = serde_json::json!({
+            "limit": options.page_size,
+            "with_vector": options.with_vector,
+            "with_payload": options.with_payload,
+        });
+        if let Some(filter) = &options.filter {
+            payload["filter"] = filter.clone();
+        }
+        if let Some(cursor) = cursor {
+            payload["cursor"] = serde_json::Value::String(cursor.to_string());
+        }
+        let response = self
+            .make_request(
+                "POST",
+                &format!("/collections/{collection}/scroll"),
+                Some(payload),
+            )
+            .await?;
+        serde_json::from_str(&response).map_err(|e| {
+            VectorizerError::server(format!("Failed to parse scroll_page response: {e}"))
+        })
+    }
+
+    /// Every vector of a collection matching `filter`, in id order,
+    /// with payloads and without vector data. See
+    /// [`Self::scroll_vectors_with`].
+    pub fn scroll_vectors(
+        &self,
+        collection: &str,
+        filter: Option<serde_json::Value>,
+    ) -> impl Stream<Item = Result<FetchedVector>> + '_ {
+        self.scroll_vectors_with(
+            collection,
+            ScrollOptions {
+                filter,
+                ..ScrollOptions::default()
+            },
+        )
+    }
+
+    /// Every vector of a collection matching `options.filter`, in id
+    /// order, fetched `options.page_size` at a time as the stream is
+    /// polled. A failed page ends the stream with its error.
+    pub fn scroll_vectors_with(
+        &self,
+        collection: &str,
+        options: ScrollOptions,
+    ) -> impl Stream<Item = Result<FetchedVector>> + '_ {
+        // `None` once the last page was fetched; `Some(cursor)` before
+        let start = (Some(None::<String>), collection.to_string(), options);
+        stream::try_unfold(start, move |(cursor, collection, options)| async move {
+            let Some(cursor) = cursor else {
+                return Result::Ok(None);
+            };
+            let page = self
+                .scroll_page(&collection, &options, cursor.as_deref())
+                .await?;
+            let next = page.next_cursor.map(Some);
+            Ok(Some((page.vectors, (next, collection, options))))
+        })
+        .map_ok(|vectors| stream::iter(vectors.into_iter().map(Ok)))
+        .try_flatten()
+    }
+
+    /// [`Self::list_collections`] as a stream of collections.
+    pub fn list_collections_stream(&self) -> impl Stream<Item = Result<Collection>> + '_ {
+        stream::once(self.list_collections())
+            .map_ok(|collections| stream::iter(collections.into_iter().map(Ok)))
+            .try_flatten()
+    }
+
+    /// [`Self::list_backups`] as a stream of backups.
+    pub fn list_backups_stream(&self) -> impl Stream<Item = Result<BackupInfo>> + '_ {
+        stream::once(self.list_backups())
+            .map_ok(|backups| stream::iter(backups.into_iter().map(Ok)))
+            .try_flatten()
+    }
+}
@@ -20,10 +20,11 @@ impl VectorizerClient {
     /// `Ok(Vector)` as proof of existence until the server fix ships.
     pub async fn get_vector(&self, collection: &str, vector_id: &str) -> Result<Vector> {
         let response = self
-            .make_request(
+            .make_routed_request(
                 "GET",
                 &format!("/collections/{collection}/vectors/{vector_id}"),
                 None,
+                vector_id,
             )
             .await?;
         let vector: Vector = serde_json::from_str(&response).map_err(|e| {
@@ -32,6 +33,38 @@ impl VectorizerClient {
         Ok(vector)
     }
 
+    /// Fetch many vectors by id in one request.
+    ///
+    /// Calls `POST /collections/{name}/vectors/get` with `{"ids": [...],
+    /// "with_vector": ..., "with_payload": ...}`; the server accepts at
+    /// most 1000 ids. Unknown ids are listed in
+    /// [`GetVectorsReport::missing`] rather than failing the call.
+    ///
+    /// Response: `{collection, vectors, missing}`.
+    pub async fn get_vectors(
+        &self,
+        collection: &str,
+        ids: &[String],
+        with_vector: bool,
+        with_payload: bool,
+    ) -> Result<GetVectorsReport> {
+        let payload = serde_json::json!({
+            "ids": ids,
+            "with_vector": with_vector,
+            "with_payload": with_payload,
+        });
+        let response = self
+            .make_request(
+                "POST",
+                &format!("/collections/{collection}/vectors/get"),
+                Some(payload),
+            )
+            .await?;
+        serde_json::from_str(&response).map_err(|e| {
+            VectorizerError::server(format!("Failed to parse get_vectors response: {e}"))
+        })
+    }
+
     /// Insert a batch of texts into a collection. The server embeds
     /// each entry with the collection's configured provider (BM25 by
     /// default; FastEmbed ONNX when selected in `config.yml`).
@@ -96,10 +129,11 @@ impl VectorizerClient {
     /// [`Self::move_to_collection`] (cross-collection move). See
     /// issue #265 for the tier-demotion use case.
     pub async fn delete_vector(&self, collection: &str, vector_id: &str) -> Result<()> {
-        self.make_request(
+        self.make_routed_request(
             "DELETE",
             &format!("/collections/{collection}/vectors/{vector_id}"),
             None,
+            vector_id,
         )
         .await?;
         Ok(())
@@ -184,8 +218,13 @@ impl VectorizerClient {
         if let Some(meta) = request.metadata {
             payload.insert("metadata".into(), meta);
         }
-        self.make_request("POST", "/update", Some(serde_json::Value::Object(payload)))
-            .await?;
+        self.make_routed_request(
+            "POST",
+            "/update",
+            Some(serde_json::Value::Object(payload)),
+            id,
+        )
+        .await?;
         Ok(Vector {
             id: id.to_string(),
             data: vec![],
@@ -533,6 +572,37 @@ impl VectorizerClient {
         })
     }
 
+    /// Count the vectors of a collection matching a metadata filter.
+    ///
+    /// Calls `POST /collections/{name}/count` with `{"filter": <filter>,
+    /// "exact": <exact>}`; `None` counts every vector. With `exact =
+    /// false` the server evaluates the filter on a random sample of at
+    /// most 1000 vectors and extrapolates, which is enough to size a
+    /// result set before paging through it.
+    ///
+    /// Response: `{collection, count, exact, sampled?}`.
+    pub async fn count_vectors(
+        &self,
+        collection: &str,
+        filter: Option<serde_json::Value>,
+        exact: bool,
+    ) -> Result<CountReport> {
+        let mut payload = serde_json::json!({ "exact": exact });
+        if let Some(filter) = filter {
+            payload["filter"] = filter;
+        }
+        let response = self
+            .make_request(
+                "POST",
+                &format!("/collections/{collection}/count"),
+                Some(payload),
+            )
+            .await?;
+        serde_json::from_str(&response).map_err(|e| {
+            VectorizerError::server(format!("Failed to parse count_vectors response: {e}"))
+        })
+    }
+
     /// Set or clear a per-vector expiry timestamp (phase13).
     ///
     /// Calls `PATCH /collections/{name}/vectors/{id}/expiry` with
@@ -585,7 +655,8 @@ mod tests {
     use serde_json::json;
 
     use crate::models::{
-        BulkUpdateReport, CopyReport, DeleteByFilterReport, ReencodeJob, VectorOpResult,
+        BulkUpdateReport, CopyReport, CountReport, DeleteByFilterReport, GetVectorsReport,
+        ReencodeJob, VectorOpResult,
     };
 
     #[test]
@@ -666,6 +737,48 @@ mod tests {
         assert_eq!(parsed, report);
     }
 
+    #[test]
+    fn count_report_deserializes_both_modes() {
+        let exact: CountReport = serde_json::from_value(json!({
+            "collection": "tickets",
+            "count": 3,
+            "exact": true,
+        }))
+        .unwrap();
+        assert_eq!(exact.count, 3);
+        assert!(exact.exact);
+        assert_eq!(exact.sampled, None);
+
+        let approximate: CountReport = serde_json::from_value(json!({
+            "collection": "tickets",
+            "count": 4210,
+            "exact": false,
+            "sampled": 1000,
+        }))
+        .unwrap();
+        assert!(!approximate.exact);
+        assert_eq!(approximate.sampled, Some(1000));
+    }
+
+    #[test]
+    fn get_vectors_report_deserializes_server_contract() {
+        let raw = json!({
+            "collection": "chunks",
+            "vectors": [
+                {"id": "c3", "document_id": "doc-1", "payload": {"position": 3}},
+                {"id": "c0", "document_id": null, "vector": [0.0, 1.0]},
+            ],
+            "missing": ["nope"],
+        });
+        let report: GetVectorsReport = serde_json::from_value(raw).unwrap();
+        assert_eq!(report.vectors[0].id, "c3");
+        assert_eq!(report.vectors[0].vector, None);
+        assert_eq!(report.vectors[0].document_id.as_deref(), Some("doc-1"));
+        assert_eq!(report.vectors[1].vector, Some(vec![0.0, 1.0]));
+        assert_eq!(report.vectors[1].payload, None);
+        assert_eq!(report.missing, vec!["nope".to_string()]);
+    }
+
     #[test]
     fn reencode_job_deserializes_server_contract() {
         let raw = json!({
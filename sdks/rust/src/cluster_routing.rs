@@ -0,0 +1,317 @@
+//! Shard-aware request routing for cluster deployments.
+//!
+//! A clustered server publishes its consistent-hash ring at
+//! [`ROUTING_ENDPOINT`]. With [`ClientConfig::cluster_routing`] set,
+//! single-vector requests (`get_vector`, `update_vector`,
+//! `delete_vector`) go straight to the node owning the vector's shard
+//! instead of through the node the client is connected to, which would
+//! otherwise proxy them over gRPC. Everything else goes through the
+//! seed node as before, and so does a routed request whose owner can't
+//! be reached — the table is then refetched on the next routed call.
+//!
+//! Node URLs are derived from the seed URL by swapping in each node's
+//! host, which assumes every node serves REST on the same scheme and
+//! port. Set [`ClusterRoutingConfig::node_urls`] when they don't.
+//!
+//! [`ClientConfig::cluster_routing`]: crate::client::ClientConfig::cluster_routing
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, VectorizerError};
+use crate::transport::Transport;
+
+/// Where a clustered server publishes its routing table.
+pub const ROUTING_ENDPOINT: &str = "/api/v1/cluster/routing";
+
+/// Opt-in settings for shard-aware routing.
+#[derive(Debug, Clone)]
+pub struct ClusterRoutingConfig {
+    /// How long a fetched table is trusted before it is refetched.
+    pub refresh_interval_secs: u64,
+    /// REST base URL per node id, overriding the URL derived from the
+    /// seed URL.
+    pub node_urls: HashMap<String, String>,
+}
+
+impl Default for ClusterRoutingConfig {
+    fn default() -> Self {
+        Self {
+            refresh_interval_secs: 30,
+            node_urls: HashMap::new(),
+        }
+    }
+}
+
+/// The cluster's consistent-hash ring and shard owners.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoutingTable {
+    /// Router epoch; bumps on every shard reassignment.
+    pub epoch: u64,
+    /// Ring points in ascending hash order.
+    pub ring: Vec<RingPoint>,
+    /// Current owner of every assigned shard.
+    pub shards: Vec<ShardOwner>,
+    /// Cluster members.
+    pub nodes: Vec<RoutingNode>,
+}
+
+/// One virtual node on the hash ring.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct RingPoint {
+    /// Position on the ring.
+    pub hash: u64,
+    /// Shard owning the arc that ends at this point.
+    pub shard_id: u32,
+}
+
+/// Current owner of a shard.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShardOwner {
+    /// Shard id.
+    pub shard_id: u32,
+    /// Owning node id.
+    pub node_id: String,
+    /// Epoch of the assignment, when tracked.
+    #[serde(default)]
+    pub epoch: Option<u64>,
+}
+
+/// A cluster member.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoutingNode {
+    /// Node id.
+    pub id: String,
+    /// Host the node is known by, optionally with a port.
+    pub address: String,
+    /// Inter-node gRPC port.
+    pub grpc_port: u16,
+    /// `active`, `joining`, `leaving` or `unavailable`.
+    pub status: String,
+    /// Whether this node served the table (and so is the seed node).
+    #[serde(default)]
+    pub local: bool,
+}
+
+impl RoutingTable {
+    /// Shard owning `vector_id`: the first ring point at or after the
+    /// xxh3-64 hash of the id, wrapping to the first point. Same lookup
+    /// as the server's `DistributedShardRouter`.
+    pub fn shard_for(&self, vector_id: &str) -> Option<u32> {
+        let hash = xxhash_rust::xxh3::xxh3_64(vector_id.as_bytes());
+        let idx = self.ring.partition_point(|p| p.hash < hash);
+        self.ring
+            .get(idx)
+            .or_else(|| self.ring.first())
+            .map(|p| p.shard_id)
+    }
+
+    /// Active node owning `vector_id`, if the table knows one.
+    pub fn owner_of(&self, vector_id: &str) -> Option<&RoutingNode> {
+        let shard_id = self.shard_for(vector_id)?;
+        let owner = self.shards.iter().find(|s| s.shard_id == shard_id)?;
+        self.nodes
+            .iter()
+            .find(|n| n.id == owner.node_id && n.status == "active")
+    }
+}
+
+/// Builds a transport for a node's REST base URL.
+pub type TransportFactory = Arc<dyn Fn(&str) -> Result<Arc<dyn Transport>> + Send + Sync>;
+
+/// Routing table cache plus one transport per node.
+pub struct ClusterRouter {
+    config: ClusterRoutingConfig,
+    seed_url: String,
+    factory: TransportFactory,
+    table: RwLock<Option<(Arc<RoutingTable>, Instant)>>,
+    /// Last failed fetch, so a server without a routing table (not
+    /// clustered, or too old) isn't asked again on every request.
+    failed_at: RwLock<Option<Instant>>,
+    transports: RwLock<HashMap<String, Arc<dyn Transport>>>,
+}
+
+impl ClusterRouter {
+    /// A router for a client connected to `seed_url`. The table is
+    /// fetched on the first routed request.
+    pub fn new(
+        config: ClusterRoutingConfig,
+        seed_url: impl Into<String>,
+        factory: TransportFactory,
+    ) -> Self {
+        Self {
+            config,
+            seed_url: seed_url.into(),
+            factory,
+            table: RwLock::new(None),
+            failed_at: RwLock::new(None),
+            transports: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// The cached table, fresh or not.
+    pub fn table(&self) -> Option<Arc<RoutingTable>> {
+        self.table.read().as_ref().map(|(table, _)| table.clone())
+    }
+
+    /// Fetch the table through `seed` and cache it.
+    pub async fn refresh(&self, seed: &dyn Transport) -> Result<Arc<RoutingTable>> {
+        let fetched = match seed.get(ROUTING_ENDPOINT).await {
+            Ok(response) => serde_json::from_str::<RoutingTable>(&response).map_err(|e| {
+                VectorizerError::server(format!("Failed to parse routing table: {e}"))
+            }),
+            Err(e) => Err(e),
+        };
+        let mut table = match fetched {
+            Ok(table) => table,
+            Err(e) => {
+                *self.failed_at.write() = Some(Instant::now());
+                return Err(e);
+            }
+        };
+        table.ring.sort_by_key(|p| p.hash);
+        let table = Arc::new(table);
+        *self.table.write() = Some((table.clone(), Instant::now()));
+        *self.failed_at.write() = None;
+        Ok(table)
+    }
+
+    /// Drop the cached table so the next routed request refetches it.
+    pub fn invalidate(&self) {
+        *self.table.write() = None;
+    }
+
+    /// Transport for the node owning `vector_id`, or `None` to go
+    /// through the seed (no table, unknown owner, or the owner is the
+    /// seed itself).
+    pub async fn transport_for(
+        &self,
+        seed: &dyn Transport,
+        vector_id: &str,
+    ) -> Option<Arc<dyn Transport>> {
+        let max_age = Duration::from_secs(self.config.refresh_interval_secs);
+        let cached = self
+            .table
+            .read()
+            .as_ref()
+            .filter(|(_, fetched)| fetched.elapsed() < max_age)
+            .map(|(table, _)| table.clone());
+        let recently_failed = self
+            .failed_at
+            .read()
+            .is_some_and(|failed| failed.elapsed() < max_age);
+        let table = match cached {
+            Some(table) => table,
+            None if recently_failed => return None,
+            None => match self.refresh(seed).await {
+                Ok(table) => table,
+                Err(e) => {
+                    tracing::debug!("Cluster routing table unavailable, using seed node: {e}");
+                    return None;
+                }
+            },
+        };
+
+        let node = table.owner_of(vector_id)?;
+        if node.local {
+            return None;
+        }
+        let url = self.node_url(node)?;
+        if url == self.seed_url {
+            return None;
+        }
+        if let Some(transport) = self.transports.read().get(&url) {
+            return Some(transport.clone());
+        }
+        match (self.factory)(&url) {
+            Ok(transport) => {
+                self.transports.write().insert(url, transport.clone());
+                Some(transport)
+            }
+            Err(e) => {
+                tracing::debug!("No transport for cluster node {} ({url}): {e}", node.id);
+                None
+            }
+        }
+    }
+
+    /// REST base URL for `node`: the configured override, else the seed
+    /// URL with the node's host swapped in.
+    fn node_url(&self, node: &RoutingNode) -> Option<String> {
+        if let Some(url) = self.config.node_urls.get(&node.id) {
+            return Some(url.trim_end_matches('/').to_string());
+        }
+        let host = match node.address.rsplit_once(':') {
+            Some((host, port)) if port.parse::<u16>().is_ok() && !host.contains(':') => host,
+            _ => node.address.as_str(),
+        };
+        let mut url = reqwest::Url::parse(&self.seed_url).ok()?;
+        url.set_host(Some(host)).ok()?;
+        Some(url.as_str().trim_end_matches('/').to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table() -> RoutingTable {
+        serde_json::from_value(serde_json::json!({
+            "epoch": 3,
+            "hash": "xxh3_64",
+            "ring": [
+                {"hash": 100, "shard_id": 0},
+                {"hash": u64::MAX / 2, "shard_id": 1},
+            ],
+            "shards": [
+                {"shard_id": 0, "node_id": "a", "epoch": 1},
+                {"shard_id": 1, "node_id": "b", "epoch": 2},
+            ],
+            "nodes": [
+                {"id": "a", "address": "10.0.0.1", "grpc_port": 15003, "status": "active", "local": true},
+                {"id": "b", "address": "10.0.0.2:15003", "grpc_port": 15003, "status": "active", "local": false},
+            ],
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn ring_lookup_wraps_past_the_last_point() {
+        let table = table();
+        let hash = |id: &str| xxhash_rust::xxh3::xxh3_64(id.as_bytes());
+        for i in 0..200 {
+            let id = format!("v{i}");
+            let expected = if hash(&id) <= 100 || hash(&id) > u64::MAX / 2 {
+                0
+            } else {
+                1
+            };
+            assert_eq!(table.shard_for(&id), Some(expected), "{id}");
+        }
+    }
+
+    #[test]
+    fn node_urls_reuse_the_seed_scheme_and_port() {
+        let factory: TransportFactory = Arc::new(|_| Err(VectorizerError::configuration("unused")));
+        let mut config = ClusterRoutingConfig::default();
+        let router = ClusterRouter::new(config.clone(), "https://seed:15002", factory.clone());
+        let b = &table().nodes[1];
+        assert_eq!(
+            router.node_url(b).as_deref(),
+            Some("https://10.0.0.2:15002")
+        );
+
+        config
+            .node_urls
+            .insert("b".to_string(), "http://b.internal:8080/".to_string());
+        let router = ClusterRouter::new(config, "https://seed:15002", factory);
+        assert_eq!(
+            router.node_url(b).as_deref(),
+            Some("http://b.internal:8080")
+        );
+    }
+}
@@ -1,5 +1,6 @@
 //! HTTP transport implementation using reqwest
 
+use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
@@ -8,6 +9,8 @@ use reqwest::{Client, ClientBuilder};
 use serde_json::Value;
 
 use crate::error::{Result, VectorizerError};
+use crate::signing::{HEADER_SERVER_TIME, RequestSigner};
+use crate::telemetry::{RequestTelemetry, STATUS_ERROR, http_collection};
 use crate::transport::{Protocol, Transport};
 
 /// Maximum number of times an HTTP 429 will be retried before the
@@ -25,6 +28,8 @@ const RETRY_AFTER_DEFAULT_SECS: u64 = 1;
 pub struct HttpTransport {
     client: Client,
     base_url: String,
+    /// Optional HMAC signer (see [`crate::signing`]).
+    signer: Option<Arc<RequestSigner>>,
 }
 
 impl HttpTransport {
@@ -69,8 +74,15 @@ impl HttpTransport {
         Ok(Self {
             client,
             base_url: base_url.to_string(),
+            signer: None,
         })
     }
+
+    /// Sign every request made through this transport.
+    pub fn with_signer(mut self, signer: Arc<RequestSigner>) -> Self {
+        self.signer = Some(signer);
+        self
+    }
 }
 
 /// Cheap JWT shape sniff. A JWT is three base64url-encoded segments
@@ -99,9 +111,48 @@ impl HttpTransport {
     /// responses (issue #263): the client sleeps for the header's
     /// value (capped) and retries up to [`RETRY_AFTER_MAX_ATTEMPTS`]
     /// times before surfacing a `RateLimit` error.
+    ///
+    /// With a signer configured, the body is serialized once so the
+    /// signed bytes are exactly the bytes sent, and a 401 carrying
+    /// `X-Vectorizer-Server-Time` corrects the clock offset and retries
+    /// once.
     async fn request(&self, method: &str, path: &str, body: Option<&Value>) -> Result<String> {
+        let telemetry = RequestTelemetry::start("http", method, http_collection(path, body));
+        let mut progress = Progress::default();
+        let result = telemetry
+            .instrument(self.send(method, path, body, &mut progress))
+            .await;
+        telemetry.finish(&progress.status_label(), progress.retries);
+        result
+    }
+
+    /// The retry loop behind [`Self::request`]; records retries and the
+    /// last HTTP status into `progress` for telemetry.
+    async fn send(
+        &self,
+        method: &str,
+        path: &str,
+        body: Option<&Value>,
+        progress: &mut Progress,
+    ) -> Result<String> {
         let url = format!("{}{}", self.base_url, path);
         let mut attempts_remaining = RETRY_AFTER_MAX_ATTEMPTS;
+        let mut clock_retry_available = true;
+
+        let body_bytes = body
+            .map(serde_json::to_vec)
+            .transpose()
+            .map_err(|e| VectorizerError::configuration(format!("Invalid request body: {e}")))?;
+        let signed_path = if self.signer.is_some() {
+            let parsed = reqwest::Url::parse(&url)
+                .map_err(|e| VectorizerError::configuration(format!("Invalid URL {url}: {e}")))?;
+            match parsed.query() {
+                Some(q) => format!("{}?{q}", parsed.path()),
+                None => parsed.path().to_string(),
+            }
+        } else {
+            String::new()
+        };
 
         loop {
             let mut request = match method {
@@ -117,14 +168,41 @@ impl HttpTransport {
                 }
             };
 
-            if let Some(data) = body {
-                request = request.json(data);
+            if let Some(ref signer) = self.signer {
+                let signed_body = body_bytes.as_deref().unwrap_or_default();
+                for (name, value) in signer.headers(method, &signed_path, signed_body) {
+                    request = request.header(name, value);
+                }
+            }
+
+            if let Some(ref bytes) = body_bytes {
+                request = request.body(bytes.clone());
             }
 
             let response = request
                 .send()
                 .await
                 .map_err(|e| VectorizerError::network(format!("HTTP request failed: {e}")))?;
+            progress.status = Some(response.status().as_u16());
+
+            if response.status().as_u16() == 401
+                && clock_retry_available
+                && let Some(ref signer) = self.signer
+                && let Some(server_time) = response
+                    .headers()
+                    .get(HEADER_SERVER_TIME)
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.trim().parse::<i64>().ok())
+                && signer.observe_server_time(server_time)
+            {
+                clock_retry_available = false;
+                progress.retries += 1;
+                tracing::info!(
+                    "Vectorizer rejected request signature; retrying with clock offset {}s",
+                    signer.clock_offset_secs()
+                );
+                continue;
+            }
 
             if response.status().as_u16() == 429 {
                 let retry_after = parse_retry_after_secs(
@@ -149,6 +227,7 @@ impl HttpTransport {
                      (remaining attempts={attempts_remaining})",
                 );
                 attempts_remaining -= 1;
+                progress.retries += 1;
                 tokio::time::sleep(retry_after).await;
                 continue;
             }
@@ -172,6 +251,20 @@ impl HttpTransport {
     }
 }
 
+/// What one request went through, for [`RequestTelemetry`].
+#[derive(Default)]
+struct Progress {
+    retries: u32,
+    status: Option<u16>,
+}
+
+impl Progress {
+    fn status_label(&self) -> String {
+        self.status
+            .map_or_else(|| STATUS_ERROR.to_string(), |s| s.to_string())
+    }
+}
+
 /// Parse a `Retry-After` header value (seconds form only). Returns a
 /// sensible default when missing/unparseable; caps the value so a
 /// misconfigured server can't pin the client into a long sleep.
@@ -229,7 +322,26 @@ impl HttpTransport {
         form_fields: std::collections::HashMap<String, String>,
     ) -> Result<String> {
         let url = format!("{}{}", self.base_url, path);
+        let telemetry = RequestTelemetry::start("http", "POST", http_collection(path, None));
+        let mut status = None;
+        let result = telemetry
+            .instrument(self.send_multipart(&url, file_bytes, filename, form_fields, &mut status))
+            .await;
+        telemetry.finish(
+            &status.map_or_else(|| STATUS_ERROR.to_string(), |s: u16| s.to_string()),
+            0,
+        );
+        result
+    }
 
+    async fn send_multipart(
+        &self,
+        url: &str,
+        file_bytes: Vec<u8>,
+        filename: &str,
+        form_fields: std::collections::HashMap<String, String>,
+        status: &mut Option<u16>,
+    ) -> Result<String> {
         // Create multipart form
         let mut form = reqwest::multipart::Form::new();
 
@@ -244,11 +356,12 @@ impl HttpTransport {
 
         let response = self
             .client
-            .post(&url)
+            .post(url)
             .multipart(form)
             .send()
             .await
             .map_err(|e| VectorizerError::network(format!("File upload failed: {e}")))?;
+        *status = Some(response.status().as_u16());
 
         if !response.status().is_success() {
             let status = response.status();
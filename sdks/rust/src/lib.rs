@@ -40,12 +40,16 @@
 pub mod error;
 pub mod models;
 pub mod rpc;
+pub mod signing;
+pub mod telemetry;
 pub mod transport;
 pub mod utils;
 
 #[cfg(feature = "http")]
 pub mod client;
 #[cfg(feature = "http")]
+pub mod cluster_routing;
+#[cfg(feature = "http")]
 pub mod http_transport;
 
 #[cfg(feature = "umicp")]
@@ -56,6 +60,8 @@ pub mod umicp_transport;
 pub use client::UmicpConfig;
 #[cfg(feature = "http")]
 pub use client::{ClientConfig, VectorizerClient};
+#[cfg(feature = "http")]
+pub use cluster_routing::{ClusterRouter, ClusterRoutingConfig, RoutingTable};
 pub use error::{Result, VectorizerError};
 #[cfg(feature = "http")]
 pub use http_transport::HttpTransport;
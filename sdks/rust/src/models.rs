@@ -101,6 +101,100 @@ pub struct ReencodeJob {
     pub progress: f64,
 }
 
+/// Outcome of a `count_vectors` call against
+/// `POST /collections/{name}/count`.
+///
+/// Server contract: `{collection, count, exact, sampled?}`. `exact` is
+/// false only when the count was extrapolated from a random sample, and
+/// `sampled` is that sample's size (approximate mode only).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CountReport {
+    /// Collection name, echoed by the server.
+    pub collection: String,
+    /// Vectors matching the filter (estimated when `exact` is false).
+    pub count: usize,
+    /// Whether every vector was evaluated.
+    pub exact: bool,
+    /// Vectors the approximate mode evaluated the filter on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sampled: Option<usize>,
+}
+
+/// One vector returned by `get_vectors`. `vector` and `payload` are
+/// absent when the request turned them off.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FetchedVector {
+    /// Vector id.
+    pub id: String,
+    /// Vector data, when requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vector: Option<Vec<f32>>,
+    /// Payload, when requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload: Option<serde_json::Value>,
+    /// Document the vector was chunked from, if any.
+    #[serde(default)]
+    pub document_id: Option<String>,
+}
+
+/// Outcome of a `get_vectors` call against
+/// `POST /collections/{name}/vectors/get`.
+///
+/// Server contract: `{collection, vectors, missing}`. `vectors` follows
+/// the order of the requested ids; `missing` lists the ids the
+/// collection does not hold.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GetVectorsReport {
+    /// Collection name, echoed by the server.
+    pub collection: String,
+    /// The vectors found, in request order.
+    pub vectors: Vec<FetchedVector>,
+    /// Requested ids the collection does not hold.
+    #[serde(default)]
+    pub missing: Vec<String>,
+}
+
+/// Options of a scroll over `POST /collections/{name}/scroll`; see
+/// `VectorizerClient::scroll_vectors_with`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScrollOptions {
+    /// Qdrant or metadata filter; `None` scrolls every vector.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<serde_json::Value>,
+    /// Vectors fetched per request (the server accepts 1 to 1000).
+    pub page_size: usize,
+    /// Include vector data.
+    pub with_vector: bool,
+    /// Include payloads.
+    pub with_payload: bool,
+}
+
+impl Default for ScrollOptions {
+    fn default() -> Self {
+        Self {
+            filter: None,
+            page_size: 100,
+            with_vector: false,
+            with_payload: true,
+        }
+    }
+}
+
+/// One page of `POST /collections/{name}/scroll`.
+///
+/// Server contract: `{collection, vectors, next_cursor}`. Vectors are in
+/// id order; `next_cursor` is `None` on the last page.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScrollPage {
+    /// Collection name, echoed by the server.
+    pub collection: String,
+    /// The vectors on this page.
+    pub vectors: Vec<FetchedVector>,
+    /// Cursor of the next page, if there is one.
+    #[serde(default)]
+    pub next_cursor: Option<String>,
+}
+
 // ===== TIER-DEMOTION REPORTS (issue #265) =====
 
 /// Per-vector outcome for a `delete_vectors` or `move_to_collection`
@@ -226,9 +320,12 @@ pub enum SimilarityMetric {
 pub struct Vector {
     /// Unique identifier for the vector
     pub id: String,
-    /// Vector data as an array of numbers
+    /// Vector data as an array of numbers (`vector` in server responses)
+    #[serde(alias = "vector")]
     pub data: Vec<f32>,
-    /// Optional metadata associated with the vector
+    /// Optional metadata associated with the vector (`payload` in server
+    /// responses)
+    #[serde(default, alias = "payload")]
     pub metadata: Option<HashMap<String, serde_json::Value>>,
     /// Optional ECC public key for payload encryption (PEM, base64, or hex format)
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -25,6 +25,7 @@ use tracing::{debug, warn};
 
 use super::codec::{read_response, write_request};
 use super::types::{Request, Response, VectorizerValue};
+use crate::telemetry::{RequestTelemetry, STATUS_ERROR, STATUS_OK, rpc_collection};
 
 /// Errors the [`RpcClient`] can return.
 #[derive(Debug, thiserror::Error)]
@@ -326,7 +327,17 @@ impl RpcClient {
         if !exempt && !*self.authenticated.lock() {
             return Err(RpcClientError::NotAuthenticated);
         }
-        self.raw_call(cmd, args).await
+        let telemetry = RequestTelemetry::start("rpc", &cmd, rpc_collection(&cmd, &args));
+        let result = telemetry.instrument(self.raw_call(cmd, args)).await;
+        telemetry.finish(
+            if result.is_ok() {
+                STATUS_OK
+            } else {
+                STATUS_ERROR
+            },
+            0,
+        );
+        result
     }
 
     /// Skip the local auth check — used by the HELLO + PING paths so
@@ -0,0 +1,194 @@
+//! Client-side request telemetry.
+//!
+//! With the `tracing` feature, every HTTP and RPC request runs inside a
+//! `vectorizer.request` span carrying `transport`, `method` and
+//! `collection`; when the request completes the span records
+//! `latency_ms`, `retries` and `status` and emits one debug event.
+//!
+//! With the `metrics` feature, the same outcomes feed Prometheus
+//! collectors that [`register_metrics`] adds to a registry:
+//!
+//! | Metric | Labels |
+//! |---|---|
+//! | `vectorizer_client_requests_total` | `transport`, `method`, `status` |
+//! | `vectorizer_client_request_retries_total` | `transport`, `method` |
+//! | `vectorizer_client_request_duration_seconds` | `transport`, `method` |
+//!
+//! `method` is the HTTP verb or the RPC command name. Collections are
+//! left out of metric labels to keep cardinality bounded. Without
+//! either feature the hooks compile to nothing.
+
+use std::future::Future;
+use std::time::Instant;
+
+/// Status recorded for a request that failed before any response.
+pub(crate) const STATUS_ERROR: &str = "error";
+/// Status recorded for a successful RPC call.
+pub(crate) const STATUS_OK: &str = "ok";
+
+/// One in-flight request. Start it before sending, run the request
+/// through [`Self::instrument`], then [`Self::finish`] it.
+pub(crate) struct RequestTelemetry {
+    #[cfg_attr(not(feature = "metrics"), allow(dead_code))]
+    transport: &'static str,
+    #[cfg_attr(not(feature = "metrics"), allow(dead_code))]
+    method: String,
+    started: Instant,
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+}
+
+impl RequestTelemetry {
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    pub(crate) fn start(transport: &'static str, method: &str, collection: Option<&str>) -> Self {
+        Self {
+            transport,
+            method: method.to_string(),
+            started: Instant::now(),
+            #[cfg(feature = "tracing")]
+            span: tracing::info_span!(
+                "vectorizer.request",
+                transport,
+                method,
+                collection = collection.unwrap_or_default(),
+                latency_ms = tracing::field::Empty,
+                retries = tracing::field::Empty,
+                status = tracing::field::Empty,
+            ),
+        }
+    }
+
+    /// Run `fut` inside the request span.
+    #[cfg(feature = "tracing")]
+    pub(crate) fn instrument<F: Future>(&self, fut: F) -> impl Future<Output = F::Output> {
+        tracing::Instrument::instrument(fut, self.span.clone())
+    }
+
+    /// Run `fut` inside the request span.
+    #[cfg(not(feature = "tracing"))]
+    pub(crate) fn instrument<F: Future>(&self, fut: F) -> impl Future<Output = F::Output> {
+        fut
+    }
+
+    /// Record the outcome: `status` is the final HTTP status code, or
+    /// [`STATUS_OK`] / [`STATUS_ERROR`].
+    pub(crate) fn finish(self, status: &str, retries: u32) {
+        let elapsed = self.started.elapsed();
+
+        #[cfg(feature = "tracing")]
+        {
+            let latency_ms = elapsed.as_secs_f64() * 1000.0;
+            self.span.record("latency_ms", latency_ms);
+            self.span.record("retries", retries);
+            self.span.record("status", status);
+            self.span.in_scope(|| {
+                tracing::debug!(latency_ms, retries, status, "vectorizer request completed");
+            });
+        }
+
+        #[cfg(feature = "metrics")]
+        {
+            let labels = [self.transport, self.method.as_str()];
+            metrics::REQUESTS
+                .with_label_values(&[self.transport, self.method.as_str(), status])
+                .inc();
+            if retries > 0 {
+                metrics::RETRIES
+                    .with_label_values(&labels)
+                    .inc_by(u64::from(retries));
+            }
+            metrics::DURATION
+                .with_label_values(&labels)
+                .observe(elapsed.as_secs_f64());
+        }
+
+        #[cfg(not(any(feature = "tracing", feature = "metrics")))]
+        let _ = (elapsed, status, retries);
+    }
+}
+
+/// Collection named by a REST request: the segment after
+/// `/collections/` in the path, else the body's `collection` field.
+#[cfg_attr(not(feature = "http"), allow(dead_code))]
+pub(crate) fn http_collection<'a>(
+    path: &'a str,
+    body: Option<&'a serde_json::Value>,
+) -> Option<&'a str> {
+    if let Some((_, rest)) = path.split_once("/collections/") {
+        let name = rest.split(['/', '?']).next().unwrap_or_default();
+        if !name.is_empty() {
+            return Some(name);
+        }
+    }
+    body.and_then(|b| b.get("collection"))
+        .and_then(serde_json::Value::as_str)
+}
+
+/// Collection named by an RPC call: the first argument of commands in
+/// namespaces that take the collection first.
+pub(crate) fn rpc_collection<'a>(
+    command: &str,
+    args: &'a [crate::rpc::VectorizerValue],
+) -> Option<&'a str> {
+    let namespace = command.split('.').next().unwrap_or_default();
+    if !matches!(
+        namespace,
+        "collections" | "vectors" | "search" | "file" | "graph"
+    ) {
+        return None;
+    }
+    args.first().and_then(|v| v.as_str())
+}
+
+/// Register the SDK's request metrics with `registry`. Call once per
+/// registry; a second call fails with `AlreadyReg`.
+#[cfg(feature = "metrics")]
+pub fn register_metrics(registry: &prometheus::Registry) -> prometheus::Result<()> {
+    registry.register(Box::new(metrics::REQUESTS.clone()))?;
+    registry.register(Box::new(metrics::RETRIES.clone()))?;
+    registry.register(Box::new(metrics::DURATION.clone()))?;
+    Ok(())
+}
+
+#[cfg(feature = "metrics")]
+mod metrics {
+    use std::sync::LazyLock;
+
+    use prometheus::{HistogramOpts, HistogramVec, IntCounterVec, Opts};
+
+    pub(super) static REQUESTS: LazyLock<IntCounterVec> = LazyLock::new(|| {
+        IntCounterVec::new(
+            Opts::new(
+                "vectorizer_client_requests_total",
+                "Requests sent to Vectorizer by this client",
+            ),
+            &["transport", "method", "status"],
+        )
+        .unwrap()
+    });
+
+    pub(super) static RETRIES: LazyLock<IntCounterVec> = LazyLock::new(|| {
+        IntCounterVec::new(
+            Opts::new(
+                "vectorizer_client_request_retries_total",
+                "Retries after rate limiting or clock-skew rejections",
+            ),
+            &["transport", "method"],
+        )
+        .unwrap()
+    });
+
+    pub(super) static DURATION: LazyLock<HistogramVec> = LazyLock::new(|| {
+        HistogramVec::new(
+            HistogramOpts::new(
+                "vectorizer_client_request_duration_seconds",
+                "Request latency in seconds, retries included",
+            )
+            .buckets(vec![
+                0.001, 0.003, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0,
+            ]),
+            &["transport", "method"],
+        )
+        .unwrap()
+    });
+}
@@ -0,0 +1,136 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Shard-aware routing: single-vector requests go to the node owning the
+//! vector's shard and fall back to the seed node when that node is down.
+//! All transports are in-memory mocks.
+
+#![cfg(feature = "http")]
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use vectorizer_sdk::cluster_routing::TransportFactory;
+use vectorizer_sdk::error::{Result, VectorizerError};
+use vectorizer_sdk::transport::{Protocol, Transport};
+use vectorizer_sdk::{ClusterRouter, ClusterRoutingConfig, VectorizerClient};
+
+/// Records every call as `<name> <method> <path>` in a shared log.
+struct NamedTransport {
+    name: &'static str,
+    down: bool,
+    log: Arc<Mutex<Vec<String>>>,
+}
+
+impl NamedTransport {
+    fn dispatch(&self, method: &str, path: &str) -> Result<String> {
+        self.log
+            .lock()
+            .unwrap()
+            .push(format!("{} {method} {path}", self.name));
+        if self.down {
+            return Err(VectorizerError::network("connection refused"));
+        }
+        if path == "/api/v1/cluster/routing" {
+            return Ok(routing_table().to_string());
+        }
+        Ok(json!({"id": "v1", "data": [0.1, 0.2], "metadata": null}).to_string())
+    }
+}
+
+#[async_trait]
+impl Transport for NamedTransport {
+    async fn get(&self, path: &str) -> Result<String> {
+        self.dispatch("GET", path)
+    }
+    async fn post(&self, path: &str, _data: Option<&Value>) -> Result<String> {
+        self.dispatch("POST", path)
+    }
+    async fn put(&self, path: &str, _data: Option<&Value>) -> Result<String> {
+        self.dispatch("PUT", path)
+    }
+    async fn delete(&self, path: &str) -> Result<String> {
+        self.dispatch("DELETE", path)
+    }
+    async fn patch(&self, path: &str, _data: Option<&Value>) -> Result<String> {
+        self.dispatch("PATCH", path)
+    }
+    fn protocol(&self) -> Protocol {
+        Protocol::Http
+    }
+}
+
+/// Every id hashes to shard 1, owned by the remote node `b`.
+fn routing_table() -> Value {
+    json!({
+        "epoch": 1,
+        "hash": "xxh3_64",
+        "ring": [{"hash": 42, "shard_id": 1}],
+        "shards": [{"shard_id": 1, "node_id": "b", "epoch": 1}],
+        "nodes": [
+            {"id": "a", "address": "127.0.0.1", "grpc_port": 15003, "status": "active", "local": true},
+            {"id": "b", "address": "10.0.0.2:15003", "grpc_port": 15003, "status": "active", "local": false},
+        ],
+    })
+}
+
+fn client(owner_down: bool) -> (VectorizerClient, Arc<Mutex<Vec<String>>>) {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let seed = Arc::new(NamedTransport {
+        name: "seed",
+        down: false,
+        log: log.clone(),
+    });
+    let factory_log = log.clone();
+    let factory: TransportFactory = Arc::new(move |url| {
+        assert_eq!(url, "http://10.0.0.2:15002");
+        Ok(Arc::new(NamedTransport {
+            name: "b",
+            down: owner_down,
+            log: factory_log.clone(),
+        }) as Arc<dyn Transport>)
+    });
+    let router = ClusterRouter::new(
+        ClusterRoutingConfig::default(),
+        "http://seed:15002",
+        factory,
+    );
+    let client =
+        VectorizerClient::with_transport(seed, "http://seed:15002").with_cluster_router(router);
+    (client, log)
+}
+
+#[tokio::test]
+async fn single_vector_requests_go_to_the_owning_node() {
+    let (client, log) = client(false);
+    client.get_vector("docs", "v1").await.unwrap();
+    client.get_vector("docs", "v2").await.unwrap();
+
+    assert_eq!(
+        *log.lock().unwrap(),
+        vec![
+            "seed GET /api/v1/cluster/routing",
+            "b GET /collections/docs/vectors/v1",
+            "b GET /collections/docs/vectors/v2",
+        ]
+    );
+    assert_eq!(client.cluster_router().unwrap().table().unwrap().epoch, 1);
+}
+
+#[tokio::test]
+async fn unreachable_owner_falls_back_to_the_seed_node() {
+    let (client, log) = client(true);
+    client.get_vector("docs", "v1").await.unwrap();
+
+    assert_eq!(
+        *log.lock().unwrap(),
+        vec![
+            "seed GET /api/v1/cluster/routing",
+            "b GET /collections/docs/vectors/v1",
+            "seed GET /collections/docs/vectors/v1",
+        ]
+    );
+    // The stale table is dropped so the next routed call refetches it.
+    assert!(client.cluster_router().unwrap().table().is_none());
+}
@@ -0,0 +1,198 @@
+#![allow(warnings)]
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Conformance suite runner for the Rust SDK.
+//!
+//! Runs the server's SDK parity suite (`GET /conformance/suite`) through
+//! the client methods each case names under `sdk.rust`. Start a server
+//! with `vectorizer --conformance` and point
+//! `VECTORIZER_CONFORMANCE_URL` at it; without the variable the test is
+//! skipped. A case naming a method this runner does not know fails, so
+//! new server cases cannot go uncovered.
+
+use serde_json::{Value, json};
+use vectorizer_sdk::*;
+
+fn metric(name: &str) -> SimilarityMetric {
+    match name {
+        "euclidean" => SimilarityMetric::Euclidean,
+        "dot" | "dot_product" | "dotproduct" => SimilarityMetric::DotProduct,
+        _ => SimilarityMetric::Cosine,
+    }
+}
+
+/// Call the SDK method a case names and project its result onto the
+/// response fields the SDK models expose.
+async fn call(
+    client: &VectorizerClient,
+    method: &str,
+    params: &Value,
+) -> std::result::Result<Result<Value>, String> {
+    let str_param = |key: &str| params[key].as_str().unwrap_or_default().to_string();
+    let outcome = match method {
+        "health_check" => client
+            .health_check()
+            .await
+            .map(|h| json!({"status": h.status, "version": h.version})),
+        "create_collection" => client
+            .create_collection(
+                &str_param("name"),
+                params["dimension"].as_u64().unwrap_or_default() as usize,
+                Some(metric(&str_param("metric"))),
+            )
+            .await
+            .map(|c| json!({"collection": c.name, "dimension": c.dimension, "metric": c.metric})),
+        "get_collection_info" => client
+            .get_collection_info(&str_param("collection"))
+            .await
+            .map(|c| {
+                json!({
+                    "name": c.name,
+                    "dimension": c.dimension,
+                    "metric": c.metric,
+                    "vector_count": c.vector_count,
+                })
+            }),
+        "list_collections" => client.list_collections().await.map(|collections| {
+            let collections: Vec<Value> = collections
+                .iter()
+                .map(|c| json!({"name": c.name, "dimension": c.dimension}))
+                .collect();
+            json!({"total_collections": collections.len(), "collections": collections})
+        }),
+        "insert_vectors" => {
+            let vectors: Vec<RawVectorInsert> = serde_json::from_value(params["vectors"].clone())
+                .map_err(|e| format!("bad params.vectors: {e}"))?;
+            client
+                .insert_vectors(&str_param("collection"), vectors)
+                .await
+                .map(|r| json!({"collection": r.collection, "inserted": r.successful, "failed": r.failed}))
+        }
+        "get_vector" => client
+            .get_vector(&str_param("collection"), &str_param("id"))
+            .await
+            .map(|v| json!({"id": v.id, "vector": v.data, "payload": v.metadata})),
+        "batch_search" => {
+            let queries: Vec<BatchSearchQuery> = serde_json::from_value(params["queries"].clone())
+                .map_err(|e| format!("bad params.queries: {e}"))?;
+            client
+                .batch_search(&str_param("collection"), queries)
+                .await
+                .map(|responses| {
+                    let results: Vec<Value> = responses
+                        .iter()
+                        .map(|r| {
+                            let hits: Vec<Value> =
+                                r.results.iter().map(|h| json!({"id": h.id})).collect();
+                            json!({"results": hits})
+                        })
+                        .collect();
+                    json!({"results": results})
+                })
+        }
+        "delete_vector" => client
+            .delete_vector(&str_param("collection"), &str_param("id"))
+            .await
+            .map(|()| json!({})),
+        "delete_collection" => client
+            .delete_collection(&str_param("name"))
+            .await
+            .map(|()| json!({})),
+        other => return Err(format!("the Rust SDK runner has no method '{other}'")),
+    };
+    Ok(outcome)
+}
+
+/// Compare the fields the SDK reported with the suite's expectation;
+/// fields the SDK models drop are not compared.
+fn matches(expected: &Value, actual: &Value, at: &str) -> std::result::Result<(), String> {
+    match (expected, actual) {
+        (Value::String(s), _) if s == "$any" => Ok(()),
+        (Value::Object(fields), Value::Object(reported)) => {
+            for (key, found) in reported {
+                if let Some(value) = fields.get(key) {
+                    matches(value, found, &format!("{at}.{key}"))?;
+                }
+            }
+            Ok(())
+        }
+        (Value::Array(items), Value::Array(found)) => {
+            if items.len() != found.len() {
+                return Err(format!(
+                    "{at}: {} items, expected {}",
+                    found.len(),
+                    items.len()
+                ));
+            }
+            for (i, (item, found)) in items.iter().zip(found).enumerate() {
+                matches(item, found, &format!("{at}[{i}]"))?;
+            }
+            Ok(())
+        }
+        (Value::Number(e), Value::Number(a))
+            if (e.as_f64().unwrap() - a.as_f64().unwrap()).abs() <= 1e-6 =>
+        {
+            Ok(())
+        }
+        _ if expected == actual => Ok(()),
+        _ => Err(format!("{at}: got {actual}, expected {expected}")),
+    }
+}
+
+#[tokio::test]
+async fn test_sdk_conformance_suite() {
+    let Ok(base_url) = std::env::var("VECTORIZER_CONFORMANCE_URL") else {
+        eprintln!("VECTORIZER_CONFORMANCE_URL not set, skipping conformance suite");
+        return;
+    };
+    let base_url = base_url.trim_end_matches('/').to_string();
+    let http = reqwest::Client::new();
+    let suite: Value = http
+        .get(format!("{base_url}/conformance/suite"))
+        .send()
+        .await
+        .expect("fetch conformance suite")
+        .json()
+        .await
+        .expect("parse conformance suite");
+    http.post(format!("{base_url}/conformance/reset"))
+        .send()
+        .await
+        .expect("reset conformance server")
+        .error_for_status()
+        .expect("reset conformance server");
+
+    let client = VectorizerClient::new_with_url(&base_url).unwrap();
+    let mut failures = Vec::new();
+    for case in suite["cases"].as_array().expect("suite cases") {
+        let id = case["id"].as_str().unwrap_or("?");
+        let Some(method) = case["sdk"]["rust"].as_str() else {
+            failures.push(format!("{id}: no Rust SDK method listed"));
+            continue;
+        };
+        let expect = &case["expect"];
+        let outcome = match call(&client, method, &case["params"]).await {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                failures.push(format!("{id}: {e}"));
+                continue;
+            }
+        };
+        let status = expect["status"].as_u64().unwrap_or(200);
+        match (outcome, status >= 400) {
+            (Ok(_), true) => failures.push(format!("{id}: {method} succeeded, expected {status}")),
+            (Err(_), true) => {}
+            (Ok(reported), false) => {
+                if let Err(mismatch) = matches(&expect["body"], &reported, "$") {
+                    failures.push(format!("{id}: {method}: {mismatch}"));
+                }
+            }
+            (Err(e), false) => failures.push(format!("{id}: {method} failed: {e}")),
+        }
+    }
+    assert!(
+        failures.is_empty(),
+        "conformance failures:\n{}",
+        failures.join("\n")
+    );
+}
@@ -0,0 +1,122 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Auto-paginating streams (`client::pagination`) against an in-memory
+//! `Transport` that serves `POST /collections/{name}/scroll` pages the
+//! way the server does: in id order, chained by `next_cursor`.
+
+#![cfg(feature = "http")]
+
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use futures::TryStreamExt;
+use serde_json::{Value, json};
+use vectorizer_sdk::error::Result;
+use vectorizer_sdk::transport::{Protocol, Transport};
+use vectorizer_sdk::{ScrollOptions, VectorizerClient, VectorizerError};
+
+/// Serves scroll pages over `ids` and records every request body.
+struct PagingTransport {
+    ids: Vec<&'static str>,
+    requests: Mutex<Vec<Value>>,
+}
+
+impl PagingTransport {
+    fn new(ids: Vec<&'static str>) -> Self {
+        Self {
+            ids,
+            requests: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn requests(&self) -> Vec<Value> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl Transport for PagingTransport {
+    async fn get(&self, path: &str) -> Result<String> {
+        match path {
+            "/collections" => Ok(json!({"collections": []}).to_string()),
+            _ => Err(VectorizerError::server(format!("unexpected GET {path}"))),
+        }
+    }
+    async fn post(&self, path: &str, data: Option<&Value>) -> Result<String> {
+        assert_eq!(path, "/collections/docs/scroll");
+        let body = data.cloned().unwrap();
+        self.requests.lock().unwrap().push(body.clone());
+        let limit = body["limit"].as_u64().unwrap() as usize;
+        let after: Vec<&str> = self
+            .ids
+            .iter()
+            .copied()
+            .filter(|id| body["cursor"].as_str().is_none_or(|c| *id > c))
+            .collect();
+        let page = &after[..after.len().min(limit)];
+        let next_cursor = (after.len() > limit).then(|| page[page.len() - 1]);
+        Ok(json!({
+            "collection": "docs",
+            "vectors": page.iter().map(|id| json!({"id": id})).collect::<Vec<_>>(),
+            "next_cursor": next_cursor,
+        })
+        .to_string())
+    }
+    async fn put(&self, path: &str, _data: Option<&Value>) -> Result<String> {
+        Err(VectorizerError::server(format!("unexpected PUT {path}")))
+    }
+    async fn delete(&self, path: &str) -> Result<String> {
+        Err(VectorizerError::server(format!("unexpected DELETE {path}")))
+    }
+    async fn patch(&self, path: &str, _data: Option<&Value>) -> Result<String> {
+        Err(VectorizerError::server(format!("unexpected PATCH {path}")))
+    }
+    fn protocol(&self) -> Protocol {
+        Protocol::Http
+    }
+}
+
+#[tokio::test]
+async fn scroll_vectors_follows_cursors_to_the_last_page() {
+    let transport = Arc::new(PagingTransport::new(vec!["a", "b", "c", "d", "e"]));
+    let client = VectorizerClient::with_transport(transport.clone(), "http://mock");
+
+    let options = ScrollOptions {
+        filter: Some(json!({"source": "wiki"})),
+        page_size: 2,
+        ..ScrollOptions::default()
+    };
+    let ids: Vec<String> = client
+        .scroll_vectors_with("docs", options)
+        .map_ok(|v| v.id)
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(ids, ["a", "b", "c", "d", "e"]);
+
+    let requests = transport.requests();
+    let cursors: Vec<Option<&str>> = requests.iter().map(|r| r["cursor"].as_str()).collect();
+    assert_eq!(cursors, [None, Some("b"), Some("d")]);
+    assert!(requests.iter().all(|r| r["filter"]["source"] == "wiki"));
+}
+
+#[tokio::test]
+async fn streams_are_lazy_and_handle_empty_listings() {
+    let transport = Arc::new(PagingTransport::new(vec!["a", "b", "c"]));
+    let client = VectorizerClient::with_transport(transport.clone(), "http://mock");
+
+    // Nothing is fetched until the stream is polled
+    let stream = client.scroll_vectors("docs", None);
+    assert!(transport.requests().is_empty());
+    let first = std::pin::pin!(stream).try_next().await.unwrap().unwrap();
+    assert_eq!(first.id, "a");
+    assert_eq!(transport.requests().len(), 1);
+    assert_eq!(transport.requests()[0]["limit"], 100);
+
+    let collections: Vec<_> = client
+        .list_collections_stream()
+        .try_collect()
+        .await
+        .unwrap();
+    assert!(collections.is_empty());
+}
@@ -0,0 +1,102 @@
+//! Client request metrics (`metrics` feature): a request that is
+//! rate-limited once and then succeeds counts one request with its
+//! final status and one retry.
+//!
+//! Run with `cargo test -p vectorizer-sdk --features metrics`.
+
+#![cfg(all(feature = "metrics", feature = "http"))]
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use vectorizer_sdk::Transport;
+use vectorizer_sdk::http_transport::HttpTransport;
+use vectorizer_sdk::telemetry::register_metrics;
+
+/// Answers the first request with 429 and every later one with `[]`.
+async fn spawn_rate_limited_server() -> std::net::SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let served = Arc::new(AtomicUsize::new(0));
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let served = served.clone();
+            tokio::spawn(async move {
+                let mut buf = vec![0u8; 4096];
+                let mut read = 0;
+                while !buf[..read].windows(4).any(|w| w == b"\r\n\r\n") {
+                    match stream.read(&mut buf[read..]).await {
+                        Ok(0) | Err(_) => return,
+                        Ok(n) => read += n,
+                    }
+                }
+                let response = if served.fetch_add(1, Ordering::SeqCst) == 0 {
+                    "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 1\r\n\
+                     Content-Length: 0\r\nConnection: close\r\n\r\n"
+                } else {
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\
+                     Content-Length: 2\r\nConnection: close\r\n\r\n[]"
+                };
+                let _ = stream.write_all(response.as_bytes()).await;
+            });
+        }
+    });
+    addr
+}
+
+/// Value of the exposition line for `name` carrying every label in `labels`.
+fn sample(exposition: &str, name: &str, labels: &[(&str, &str)]) -> f64 {
+    let line = exposition
+        .lines()
+        .find(|line| {
+            line.starts_with(&format!("{name}{{"))
+                && labels
+                    .iter()
+                    .all(|(k, v)| line.contains(&format!("{k}=\"{v}\"")))
+        })
+        .unwrap_or_else(|| panic!("no {name} sample in:\n{exposition}"));
+    line.rsplit(' ').next().unwrap().parse().unwrap()
+}
+
+#[tokio::test]
+async fn rate_limited_request_counts_one_retry() {
+    let registry = prometheus::Registry::new();
+    register_metrics(&registry).unwrap();
+
+    let addr = spawn_rate_limited_server().await;
+    let transport = HttpTransport::new(&format!("http://{addr}"), None, 5).unwrap();
+    let body = transport.get("/collections/docs").await.unwrap();
+    assert_eq!(body, "[]");
+
+    let exposition = prometheus::TextEncoder::new()
+        .encode_to_string(&registry.gather())
+        .unwrap();
+    let labels = [("transport", "http"), ("method", "GET")];
+    assert_eq!(
+        sample(
+            &exposition,
+            "vectorizer_client_requests_total",
+            &[("transport", "http"), ("method", "GET"), ("status", "200")],
+        ),
+        1.0
+    );
+    assert_eq!(
+        sample(
+            &exposition,
+            "vectorizer_client_request_retries_total",
+            &labels
+        ),
+        1.0
+    );
+    assert_eq!(
+        sample(
+            &exposition,
+            "vectorizer_client_request_duration_seconds_count",
+            &labels
+        ),
+        1.0
+    );
+}